@@ -64,7 +64,7 @@ fn bench_read_only_accounts_cache(c: &mut Criterion) {
         ));
 
         for (pubkey, account) in accounts.iter() {
-            cache.store(*pubkey, slot, account.clone());
+            cache.store(*pubkey, slot, account.clone(), slot);
         }
 
         // Spawn the reader threads in the background. They are reading the
@@ -84,7 +84,7 @@ fn bench_read_only_accounts_cache(c: &mut Criterion) {
                             let mut rng = SmallRng::seed_from_u64(i as u64);
                             while !stop_threads.load(Ordering::Relaxed) {
                                 let pubkey = pubkeys.choose(&mut rng).unwrap();
-                                black_box(cache.load(*pubkey, slot));
+                                black_box(cache.load(*pubkey, slot, slot));
                             }
                         }
                     })
@@ -108,7 +108,7 @@ fn bench_read_only_accounts_cache(c: &mut Criterion) {
                             let mut rng = SmallRng::seed_from_u64(100_u64.saturating_add(i as u64));
                             while !stop_threads.load(Ordering::Relaxed) {
                                 let (pubkey, account) = accounts.choose(&mut rng).unwrap();
-                                cache.store(*pubkey, slot, account.clone());
+                                cache.store(*pubkey, slot, account.clone(), slot);
                             }
                         }
                     })
@@ -123,7 +123,7 @@ fn bench_read_only_accounts_cache(c: &mut Criterion) {
                 for (pubkey, account) in accounts.iter().cycle().take(iters as usize) {
                     // Measure only stores.
                     let start = Instant::now();
-                    cache.store(*pubkey, slot, account.clone());
+                    cache.store(*pubkey, slot, account.clone(), slot);
                     total_time = total_time.saturating_add(start.elapsed());
                 }
                 total_time
@@ -133,7 +133,7 @@ fn bench_read_only_accounts_cache(c: &mut Criterion) {
             b.iter_custom(|iters| {
                 let start = Instant::now();
                 for (pubkey, _) in accounts.iter().cycle().take(iters as usize) {
-                    black_box(cache.load(*pubkey, slot));
+                    black_box(cache.load(*pubkey, slot, slot));
                 }
 
                 start.elapsed()
@@ -186,7 +186,7 @@ fn bench_read_only_accounts_cache_eviction(
         // Fill up the cache.
         let slot = 0;
         for (pubkey, account) in accounts.iter() {
-            cache.store(*pubkey, slot, account.clone());
+            cache.store(*pubkey, slot, account.clone(), slot);
         }
 
         // Spawn the reader threads in the background. They are reading the
@@ -206,7 +206,7 @@ fn bench_read_only_accounts_cache_eviction(
                             let mut rng = SmallRng::seed_from_u64(i as u64);
                             while !stop_threads.load(Ordering::Relaxed) {
                                 let pubkey = pubkeys.choose(&mut rng).unwrap();
-                                black_box(cache.load(*pubkey, slot));
+                                black_box(cache.load(*pubkey, slot, slot));
                             }
                         }
                     })
@@ -233,7 +233,7 @@ fn bench_read_only_accounts_cache_eviction(
                             let mut rng = SmallRng::seed_from_u64(100_u64.saturating_add(i as u64));
                             while !stop_threads.load(Ordering::Relaxed) {
                                 let (pubkey, account) = accounts.choose(&mut rng).unwrap();
-                                cache.store(*pubkey, slot, account.clone());
+                                cache.store(*pubkey, slot, account.clone(), slot);
                             }
                         }
                     })
@@ -253,7 +253,7 @@ fn bench_read_only_accounts_cache_eviction(
                     let pubkey = pubkeys.choose(&mut rng).unwrap().to_owned();
 
                     let start = Instant::now();
-                    black_box(cache.load(pubkey, slot));
+                    black_box(cache.load(pubkey, slot, slot));
                     total_time = total_time.saturating_add(start.elapsed());
                 }
 
@@ -266,7 +266,7 @@ fn bench_read_only_accounts_cache_eviction(
 
                 let start = Instant::now();
                 for (pubkey, account) in accounts {
-                    cache.store(pubkey, slot, account);
+                    cache.store(pubkey, slot, account, slot);
                 }
 
                 start.elapsed()
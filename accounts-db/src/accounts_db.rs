@@ -43,9 +43,9 @@ use {
         accounts_hash::{
             AccountHash, AccountLtHash, AccountsDeltaHash, AccountsHash, AccountsHashKind,
             AccountsHasher, AccountsLtHash, CalcAccountsHashConfig, CalculateHashIntermediate,
-            HashStats, IncrementalAccountsHash, SerdeAccountsDeltaHash, SerdeAccountsHash,
-            SerdeIncrementalAccountsHash, ZeroLamportAccounts, ZERO_LAMPORT_ACCOUNT_HASH,
-            ZERO_LAMPORT_ACCOUNT_LT_HASH,
+            HashStats, IncrementalAccountsHash, MerkleProofLevel, SerdeAccountsDeltaHash,
+            SerdeAccountsHash, SerdeIncrementalAccountsHash, ZeroLamportAccounts, MERKLE_FANOUT,
+            ZERO_LAMPORT_ACCOUNT_HASH, ZERO_LAMPORT_ACCOUNT_LT_HASH,
         },
         accounts_index::{
             in_mem_accounts_index::StartupStats, AccountSecondaryIndexes, AccountsIndex,
@@ -69,7 +69,7 @@ use {
         partitioned_rewards::{
             PartitionedEpochRewardsConfig, DEFAULT_PARTITIONED_EPOCH_REWARDS_CONFIG,
         },
-        read_only_accounts_cache::ReadOnlyAccountsCache,
+        read_only_accounts_cache::{ReadOnlyAccountsCache, ReadOnlyCacheForkStats},
         sorted_storages::SortedStorages,
         storable_accounts::{StorableAccounts, StorableAccountsBySlot},
         u64_align, utils,
@@ -110,7 +110,7 @@ use {
         path::{Path, PathBuf},
         sync::{
             atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering},
-            Arc, Condvar, Mutex, RwLock,
+            Arc, Condvar, Mutex, OnceLock, RwLock,
         },
         thread::{sleep, Builder},
         time::{Duration, Instant},
@@ -516,6 +516,7 @@ pub const ACCOUNTS_DB_CONFIG_FOR_TESTING: AccountsDbConfig = AccountsDbConfig {
     scan_filter_for_shrinking: ScanFilter::OnlyAbnormalWithVerify,
     enable_experimental_accumulator_hash: false,
     verify_experimental_accumulator_hash: false,
+    experimental_accumulator_hash_dual_mode_epochs: 0,
     snapshots_use_experimental_accumulator_hash: false,
     num_clean_threads: None,
     num_foreground_threads: None,
@@ -544,6 +545,7 @@ pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig
     scan_filter_for_shrinking: ScanFilter::OnlyAbnormalWithVerify,
     enable_experimental_accumulator_hash: false,
     verify_experimental_accumulator_hash: false,
+    experimental_accumulator_hash_dual_mode_epochs: 0,
     snapshots_use_experimental_accumulator_hash: false,
     num_clean_threads: None,
     num_foreground_threads: None,
@@ -676,6 +678,9 @@ pub struct AccountsDbConfig {
     pub scan_filter_for_shrinking: ScanFilter,
     pub enable_experimental_accumulator_hash: bool,
     pub verify_experimental_accumulator_hash: bool,
+    /// Number of epochs, starting at the first epoch accounts hash after startup, in which the
+    /// accounts lattice hash is also recalculated from the index and compared
+    pub experimental_accumulator_hash_dual_mode_epochs: u64,
     pub snapshots_use_experimental_accumulator_hash: bool,
     /// Number of threads for background cleaning operations (`thread_pool_clean')
     pub num_clean_threads: Option<NonZeroUsize>,
@@ -1631,6 +1636,15 @@ pub struct AccountsDb {
     /// (For R&D only)
     pub verify_experimental_accumulator_hash: bool,
 
+    /// Number of epochs in which the experimental accounts lattice hash is recalculated and
+    /// compared against the one maintained incrementally.
+    /// (Used while rolling out the accounts lattice hash; 0 disables the comparison)
+    experimental_accumulator_hash_dual_mode_epochs: u64,
+
+    /// The epoch after the last one in which the accounts lattice hash is compared, set the first
+    /// time the comparison is asked about
+    experimental_accumulator_hash_dual_mode_end_epoch: OnceLock<Epoch>,
+
     /// Flag to indicate if the experimental accounts lattice hash is used for snapshots.
     /// (For R&D only; a feature-gate also exists to turn this on.)
     pub snapshots_use_experimental_accumulator_hash: AtomicBool,
@@ -2056,6 +2070,9 @@ impl AccountsDb {
                 .into(),
             verify_experimental_accumulator_hash: accounts_db_config
                 .verify_experimental_accumulator_hash,
+            experimental_accumulator_hash_dual_mode_epochs: accounts_db_config
+                .experimental_accumulator_hash_dual_mode_epochs,
+            experimental_accumulator_hash_dual_mode_end_epoch: OnceLock::new(),
             snapshots_use_experimental_accumulator_hash: accounts_db_config
                 .snapshots_use_experimental_accumulator_hash
                 .into(),
@@ -2142,6 +2159,19 @@ impl AccountsDb {
             .load(Ordering::Acquire)
     }
 
+    /// Returns if the accounts lattice hash should be recalculated and compared in `epoch`
+    ///
+    /// This is the case for the configured number of epochs, counted from the first epoch this is
+    /// asked about.
+    pub fn is_experimental_accumulator_hash_dual_mode_epoch(&self, epoch: Epoch) -> bool {
+        let num_epochs = self.experimental_accumulator_hash_dual_mode_epochs;
+        num_epochs > 0
+            && epoch
+                < *self
+                    .experimental_accumulator_hash_dual_mode_end_epoch
+                    .get_or_init(|| epoch.saturating_add(num_epochs))
+    }
+
     /// Sets if the experimental accounts lattice hash is enabled
     pub fn set_is_experimental_accumulator_hash_enabled(&self, is_enabled: bool) {
         self.is_experimental_accumulator_hash_enabled
@@ -4898,6 +4928,7 @@ impl AccountsDb {
             IndexKey::ProgramId(key) => key,
             IndexKey::SplTokenMint(key) => key,
             IndexKey::SplTokenOwner(key) => key,
+            IndexKey::StakeVoter(key) => key,
         };
         if !self.account_indexes.include_key(key) {
             // the requested key was not indexed in the secondary index, so do a normal scan
@@ -5017,7 +5048,9 @@ impl AccountsDb {
             .ok_or(MatchAccountOwnerError::UnableToLoad)?;
 
         if !storage_location.is_cached() {
-            let result = self.read_only_accounts_cache.load(*account, slot);
+            let result = self
+                .read_only_accounts_cache
+                .load(*account, slot, ancestors.max_slot());
             if let Some(account) = result {
                 return if account.is_zero_lamport() {
                     Err(MatchAccountOwnerError::NoMatch)
@@ -5404,7 +5437,9 @@ impl AccountsDb {
 
         let in_write_cache = storage_location.is_cached();
         if !in_write_cache {
-            let result = self.read_only_accounts_cache.load(*pubkey, slot);
+            let result = self
+                .read_only_accounts_cache
+                .load(*pubkey, slot, ancestors.max_slot());
             if let Some(account) = result {
                 if account.is_zero_lamport() {
                     return None;
@@ -5443,8 +5478,12 @@ impl AccountsDb {
             However, by the assumption for contradiction above ,  'A' has already been updated in 'S' which means '(S, A)'
             must exist in the write cache, which is a contradiction.
             */
-            self.read_only_accounts_cache
-                .store(*pubkey, slot, account.clone());
+            self.read_only_accounts_cache.store(
+                *pubkey,
+                slot,
+                account.clone(),
+                ancestors.max_slot(),
+            );
         }
         Some((account, slot))
     }
@@ -5470,7 +5509,9 @@ impl AccountsDb {
         let in_write_cache = storage_location.is_cached();
         if !load_into_read_cache_only {
             if !in_write_cache {
-                let result = self.read_only_accounts_cache.load(*pubkey, slot);
+                let result =
+                    self.read_only_accounts_cache
+                        .load(*pubkey, slot, ancestors.max_slot());
                 if let Some(account) = result {
                     if matches!(load_zero_lamports, LoadZeroLamports::None)
                         && account.is_zero_lamport()
@@ -5521,8 +5562,12 @@ impl AccountsDb {
             However, by the assumption for contradiction above ,  'A' has already been updated in 'S' which means '(S, A)'
             must exist in the write cache, which is a contradiction.
             */
-            self.read_only_accounts_cache
-                .store(*pubkey, slot, account.clone());
+            self.read_only_accounts_cache.store(
+                *pubkey,
+                slot,
+                account.clone(),
+                ancestors.max_slot(),
+            );
         }
         Some((account, slot))
     }
@@ -7613,6 +7658,31 @@ impl AccountsDb {
         accounts_delta_hash
     }
 
+    /// Returns the hash of `pubkey` as written in `slot`, and the proof that it is included in
+    /// the accounts delta hash of `slot`, or `None` if the account was not written in `slot`.
+    ///
+    /// `skipped_rewrites` must be the skipped rewrites the delta hash was calculated with.
+    pub fn accounts_delta_hash_proof(
+        &self,
+        slot: Slot,
+        pubkey: &Pubkey,
+        mut skipped_rewrites: HashMap<Pubkey, AccountHash>,
+    ) -> Option<(AccountHash, Vec<MerkleProofLevel>)> {
+        let (mut hashes, _scan_us, _accumulate) = self.get_pubkey_hash_for_slot(slot);
+        hashes.iter().for_each(|(k, _h)| {
+            skipped_rewrites.remove(k);
+        });
+        hashes.extend(skipped_rewrites);
+
+        // Same order as `AccountsHasher::accumulate_account_hashes()`
+        hashes.par_sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        let index = hashes.binary_search_by(|(k, _h)| k.cmp(pubkey)).ok()?;
+        let account_hash = hashes[index].1;
+        let hashes = hashes.into_iter().map(|(_k, h)| h.0).collect();
+        let proof = AccountsHasher::compute_merkle_proof(hashes, index, MERKLE_FANOUT);
+        Some((account_hash, proof))
+    }
+
     /// Set the accounts delta hash for `slot` in the `accounts_delta_hashes` map
     ///
     /// returns the previous accounts delta hash for `slot`
@@ -8124,8 +8194,65 @@ impl AccountsDb {
         self.report_store_timings();
     }
 
+    /// Returns the low and high watermarks of the size of the read-only accounts cache
+    pub fn read_only_accounts_cache_max_data_size(&self) -> (usize, usize) {
+        self.read_only_accounts_cache.max_data_size()
+    }
+
+    /// Changes the watermarks of the size of the read-only accounts cache while it is running.
+    /// The cache is shrunk to the low watermark once it exceeds the high watermark
+    pub fn set_read_only_accounts_cache_max_data_size(
+        &self,
+        max_data_size_lo: usize,
+        max_data_size_hi: usize,
+    ) -> Result<(), String> {
+        if max_data_size_lo > max_data_size_hi {
+            return Err(format!(
+                "low watermark {max_data_size_lo} exceeds high watermark {max_data_size_hi}"
+            ));
+        }
+        self.read_only_accounts_cache
+            .set_max_data_size(max_data_size_lo, max_data_size_hi);
+        info!(
+            "read-only accounts cache watermarks set to {max_data_size_lo} and \
+             {max_data_size_hi} bytes"
+        );
+        Ok(())
+    }
+
+    /// Removes every entry from the read-only accounts cache, returning the number of entries
+    /// removed. Loads fall back to the storages until the cache is populated again
+    pub fn flush_read_only_accounts_cache(&self) -> usize {
+        let num_flushed = self.read_only_accounts_cache.flush();
+        info!("flushed {num_flushed} entries from the read-only accounts cache");
+        num_flushed
+    }
+
+    /// Returns the number of entries and data size of the read-only accounts cache
+    pub fn read_only_accounts_cache_usage(&self) -> (usize, usize) {
+        (
+            self.read_only_accounts_cache.cache_len(),
+            self.read_only_accounts_cache.data_size(),
+        )
+    }
+
+    /// Returns the read-only accounts cache accounting of the recent forks, by ascending slot
+    pub fn read_only_accounts_cache_fork_stats(&self) -> Vec<(Slot, ReadOnlyCacheForkStats)> {
+        self.read_only_accounts_cache.fork_stats()
+    }
+
     fn report_store_timings(&self) {
         if self.stats.last_store_report.should_update(1000) {
+            for (fork, stats) in self.read_only_accounts_cache.retire_fork_stats() {
+                datapoint_info!(
+                    "accounts_db_read_only_cache_fork",
+                    ("fork", fork, i64),
+                    ("hits", stats.hits, i64),
+                    ("misses", stats.misses, i64),
+                    ("stores", stats.stores, i64),
+                    ("evicts", stats.evicts, i64),
+                );
+            }
             let read_cache_stats = self.read_only_accounts_cache.get_and_reset_stats();
             datapoint_info!(
                 "accounts_db_store_timings",
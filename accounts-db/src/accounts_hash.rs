@@ -462,6 +462,15 @@ impl CumulativeOffsets {
     }
 }
 
+/// The nodes hashed together with a node at one level of a merkle tree
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MerkleProofLevel {
+    /// The position of the node among the nodes hashed together
+    pub index: usize,
+    /// The other nodes hashed together with the node, in order
+    pub siblings: Vec<Hash>,
+}
+
 #[derive(Debug)]
 pub struct AccountsHasher<'a> {
     pub zero_lamport_accounts: ZeroLamportAccounts,
@@ -774,6 +783,52 @@ impl AccountsHasher<'_> {
         Self::compute_merkle_root_loop(hashes, MERKLE_FANOUT, |i| &i.1 .0)
     }
 
+    /// Returns the proof that the hash at `index` is included in the root computed by
+    /// `compute_merkle_root_recurse(hashes, fanout)`, from the lowest level to the root
+    pub fn compute_merkle_proof(
+        mut hashes: Vec<Hash>,
+        mut index: usize,
+        fanout: usize,
+    ) -> Vec<MerkleProofLevel> {
+        assert!(index < hashes.len());
+        let mut proof = vec![];
+        loop {
+            let start = index / fanout * fanout;
+            let end = std::cmp::min(start + fanout, hashes.len());
+            let mut siblings = hashes[start..end].to_vec();
+            siblings.remove(index - start);
+            proof.push(MerkleProofLevel {
+                index: index - start,
+                siblings,
+            });
+            if hashes.len() <= fanout {
+                return proof;
+            }
+            hashes = hashes
+                .chunks(fanout)
+                .map(|chunk| {
+                    let mut hasher = Hasher::default();
+                    chunk.iter().for_each(|hash| hasher.hash(hash.as_ref()));
+                    hasher.result()
+                })
+                .collect();
+            index /= fanout;
+        }
+    }
+
+    /// Returns the merkle root that `proof` proves `leaf` to be included in, or `None` if the
+    /// proof is malformed
+    pub fn merkle_root_from_proof(leaf: Hash, proof: &[MerkleProofLevel]) -> Option<Hash> {
+        proof.iter().try_fold(leaf, |node, level| {
+            let (before, after) = level.siblings.split_at_checked(level.index)?;
+            let mut hasher = Hasher::default();
+            before.iter().for_each(|hash| hasher.hash(hash.as_ref()));
+            hasher.hash(node.as_ref());
+            after.iter().for_each(|hash| hasher.hash(hash.as_ref()));
+            Some(hasher.result())
+        })
+    }
+
     pub fn compare_two_hash_entries(
         a: &CalculateHashIntermediate,
         b: &CalculateHashIntermediate,
@@ -2343,6 +2398,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compute_merkle_proof() {
+        for fanout in [2, 3, MERKLE_FANOUT] {
+            for count in [
+                1,
+                2,
+                fanout - 1,
+                fanout,
+                fanout + 1,
+                fanout * fanout + 1,
+                100,
+            ] {
+                let hashes: Vec<_> = (0..count).map(|_| Hash::new_unique()).collect();
+                let root = AccountsHasher::compute_merkle_root_recurse(hashes.clone(), fanout);
+                for index in 0..count {
+                    let proof = AccountsHasher::compute_merkle_proof(hashes.clone(), index, fanout);
+                    assert_eq!(
+                        AccountsHasher::merkle_root_from_proof(hashes[index], &proof),
+                        Some(root),
+                    );
+                    assert_ne!(
+                        AccountsHasher::merkle_root_from_proof(Hash::new_unique(), &proof),
+                        Some(root),
+                    );
+                }
+            }
+        }
+
+        let malformed = [MerkleProofLevel {
+            index: 2,
+            siblings: vec![Hash::new_unique()],
+        }];
+        assert_eq!(
+            AccountsHasher::merkle_root_from_proof(Hash::new_unique(), &malformed),
+            None
+        );
+    }
+
     #[test]
     fn test_accountsdb_compute_merkle_root() {
         solana_logger::setup();
@@ -18,10 +18,11 @@ use {
         ThreadPool,
     },
     solana_measure::measure::Measure,
-    solana_pubkey::Pubkey,
+    solana_pubkey::{Pubkey, PUBKEY_BYTES},
     solana_sdk::{
         account::ReadableAccount,
         clock::{BankId, Slot},
+        stake,
     },
     std::{
         collections::{btree_map::BTreeMap, HashSet},
@@ -42,6 +43,11 @@ use {
 };
 
 pub const ITER_BATCH_SIZE: usize = 1000;
+/// Offset of the voter of the delegation in the data of a delegated stake account: the
+/// `StakeStateV2` tag and the stake `Meta` precede it
+pub const STAKE_ACCOUNT_VOTER_OFFSET: usize = 124;
+/// `StakeStateV2` tag of the delegated stake accounts
+const STAKE_STATE_V2_STAKE_TAG: [u8; 4] = 2u32.to_le_bytes();
 pub const BINS_DEFAULT: usize = 8192;
 pub const BINS_FOR_TESTING: usize = 2; // we want > 1, but each bin is a few disk files with a disk based index, so fewer is better
 pub const BINS_FOR_BENCHMARKS: usize = 8192;
@@ -190,6 +196,7 @@ pub enum IndexKey {
     ProgramId(Pubkey),
     SplTokenMint(Pubkey),
     SplTokenOwner(Pubkey),
+    StakeVoter(Pubkey),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -197,6 +204,8 @@ pub enum AccountIndex {
     ProgramId,
     SplTokenMint,
     SplTokenOwner,
+    /// Delegated stake accounts by the vote account they are delegated to
+    StakeVoter,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -214,6 +223,9 @@ pub enum IndexLimitMb {
     Unlimited,
     /// in-mem-only was specified, no disk index
     InMemOnly,
+    /// use disk index and flush the in-memory index more aggressively while its
+    /// estimated size exceeds this many MB
+    Limit(usize),
 }
 
 #[derive(Debug, Default, Clone)]
@@ -666,6 +678,7 @@ pub struct AccountsIndex<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> {
     program_id_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
     spl_token_mint_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
     spl_token_owner_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
+    stake_voter_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
     pub roots_tracker: RwLock<RootsTracker>,
     ongoing_scan_roots: RwLock<BTreeMap<Slot, u64>>,
     // Each scan has some latest slot `S` that is the tip of the fork the scan
@@ -725,6 +738,9 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             spl_token_owner_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
                 "spl_token_owner_index_stats",
             ),
+            stake_voter_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
+                "stake_voter_index_stats",
+            ),
             roots_tracker: RwLock::<RootsTracker>::default(),
             ongoing_scan_roots: RwLock::<BTreeMap<Slot, u64>>::default(),
             removed_bank_ids: Mutex::<HashSet<BankId>>::default(),
@@ -978,6 +994,16 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                     config,
                 );
             }
+            ScanTypes::Indexed(IndexKey::StakeVoter(voter_key)) => {
+                self.do_scan_secondary_index(
+                    ancestors,
+                    func,
+                    &self.stake_voter_index,
+                    &voter_key,
+                    Some(max_root),
+                    config,
+                );
+            }
         }
 
         {
@@ -1591,6 +1617,9 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                 .index
                 .get(index_key)
                 .map(|x| x.len()),
+            AccountIndex::StakeVoter => {
+                self.stake_voter_index.index.get(index_key).map(|x| x.len())
+            }
         }
     }
 
@@ -1608,6 +1637,10 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             info!("secondary index: {:?}", AccountIndex::SplTokenOwner);
             self.spl_token_owner_index.log_contents();
         }
+        if !self.stake_voter_index.index.is_empty() {
+            info!("secondary index: {:?}", AccountIndex::StakeVoter);
+            self.stake_voter_index.log_contents();
+        }
     }
 
     pub(crate) fn update_secondary_indexes(
@@ -1656,6 +1689,29 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             account_data,
             account_indexes,
         );
+
+        if account_indexes.contains(&AccountIndex::StakeVoter) {
+            if let Some(voter_key) = Self::unpack_stake_voter(account_owner, account_data) {
+                if account_indexes.include_key(voter_key) {
+                    self.stake_voter_index.insert(voter_key, pubkey);
+                }
+            }
+        }
+    }
+
+    /// Returns the vote account a stake account is delegated to, if it is delegated
+    fn unpack_stake_voter<'a>(
+        account_owner: &Pubkey,
+        account_data: &'a [u8],
+    ) -> Option<&'a Pubkey> {
+        if *account_owner != stake::program::id()
+            || account_data.get(..STAKE_STATE_V2_STAKE_TAG.len())? != STAKE_STATE_V2_STAKE_TAG
+        {
+            return None;
+        }
+        let voter_key = account_data
+            .get(STAKE_ACCOUNT_VOTER_OFFSET..STAKE_ACCOUNT_VOTER_OFFSET + PUBKEY_BYTES)?;
+        bytemuck::try_from_bytes(voter_key).ok()
     }
 
     pub(crate) fn get_bin(&self, pubkey: &Pubkey) -> AccountMaps<T, U> {
@@ -1896,6 +1952,10 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         if account_indexes.contains(&AccountIndex::SplTokenMint) {
             self.spl_token_mint_index.remove_by_inner_key(inner_key);
         }
+
+        if account_indexes.contains(&AccountIndex::StakeVoter) {
+            self.stake_voter_index.remove_by_inner_key(inner_key);
+        }
     }
 
     fn purge_older_root_entries(
@@ -3724,6 +3784,64 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_stake_voter_secondary_index() {
+        let index = AccountsIndex::<bool, bool>::default_for_tests();
+        let secondary_indexes = AccountSecondaryIndexes {
+            indexes: HashSet::from([AccountIndex::StakeVoter]),
+            keys: None,
+        };
+        let stake_key = Pubkey::new_unique();
+        let voter_key = Pubkey::new_unique();
+        let mut account_data = vec![0; 200];
+        account_data[..4].copy_from_slice(&STAKE_STATE_V2_STAKE_TAG);
+        account_data[STAKE_ACCOUNT_VOTER_OFFSET..STAKE_ACCOUNT_VOTER_OFFSET + PUBKEY_BYTES]
+            .copy_from_slice(voter_key.as_ref());
+
+        // Not a stake account
+        index.update_secondary_indexes(
+            &stake_key,
+            &AccountSharedData::create(1, account_data.clone(), Pubkey::default(), false, 0),
+            &secondary_indexes,
+        );
+        // Not delegated
+        let mut initialized_data = account_data.clone();
+        initialized_data[..4].copy_from_slice(&1u32.to_le_bytes());
+        index.update_secondary_indexes(
+            &stake_key,
+            &AccountSharedData::create(1, initialized_data, stake::program::id(), false, 0),
+            &secondary_indexes,
+        );
+        // Truncated
+        index.update_secondary_indexes(
+            &stake_key,
+            &AccountSharedData::create(
+                1,
+                account_data[..STAKE_ACCOUNT_VOTER_OFFSET].to_vec(),
+                stake::program::id(),
+                false,
+                0,
+            ),
+            &secondary_indexes,
+        );
+        assert!(index.stake_voter_index.index.is_empty());
+
+        index.update_secondary_indexes(
+            &stake_key,
+            &AccountSharedData::create(1, account_data, stake::program::id(), false, 0),
+            &secondary_indexes,
+        );
+        check_secondary_index_mapping_correct(&index.stake_voter_index, &[voter_key], &stake_key);
+        assert_eq!(
+            index.get_index_key_size(&AccountIndex::StakeVoter, &voter_key),
+            Some(1)
+        );
+
+        index.purge_secondary_indexes_by_inner_key(&stake_key, &secondary_indexes);
+        assert!(index.stake_voter_index.index.is_empty());
+        assert!(index.stake_voter_index.reverse_index.is_empty());
+    }
+
     fn run_test_secondary_indexes_same_slot_and_forks<
         SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
     >(
@@ -1215,6 +1215,9 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> InMemAccountsIndex<T,
             if old_value == 0 {
                 self.remaining_ages_to_skip_flushing
                     .store(self.num_ages_to_distribute_flushes, Ordering::Release);
+            } else if self.storage.is_over_mem_budget() {
+                // over the memory budget, so flush this bucket now instead of waiting for its turn
+                Self::update_stat(&self.stats().flushes_for_mem_budget, 1);
             } else {
                 // skipping iteration of the buckets at the current age, but mark the bucket as having aged
                 assert_eq!(current_age, self.storage.current_age());
@@ -60,6 +60,10 @@ pub struct BucketMapHolder<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>>
     /// how many ages should elapse from the last time an item is used where the item will remain in the cache
     pub ages_to_stay_in_cache: Age,
 
+    /// target size of the in-mem index, in bytes. While the estimated size exceeds this,
+    /// buckets are flushed every age instead of waiting for their turn.
+    pub mem_budget_bytes: Option<usize>,
+
     /// startup is a special time for flush to focus on moving everything to disk as fast and efficiently as possible
     /// with less thread count limitations. LRU and access patterns are not important. Freeing memory
     /// and writing to disk in parallel are.
@@ -117,6 +121,18 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> BucketMapHolder<T, U>
         self.age_timer.should_update(self.age_interval_ms())
     }
 
+    /// estimated number of bytes used by the in-mem index
+    pub fn estimate_mem_bytes(&self) -> usize {
+        self.stats.count_in_mem.load(Ordering::Relaxed)
+            * InMemAccountsIndex::<T, U>::approx_size_of_one_entry()
+    }
+
+    /// true if the in-mem index is estimated to be larger than its memory budget
+    pub fn is_over_mem_budget(&self) -> bool {
+        self.mem_budget_bytes
+            .is_some_and(|mem_budget_bytes| self.estimate_mem_bytes() > mem_budget_bytes)
+    }
+
     /// used by bg processes to determine # active threads and how aggressively to flush
     pub fn get_startup(&self) -> bool {
         self.startup.load(Ordering::Relaxed)
@@ -211,18 +227,23 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> BucketMapHolder<T, U>
             config.drives.clone()
         });
 
-        let disk = match config
+        let index_limit_mb = config
             .as_ref()
             .map(|config| config.index_limit_mb)
-            .unwrap_or_default()
-        {
+            .unwrap_or_default();
+        let disk = match index_limit_mb {
             IndexLimitMb::InMemOnly => None,
-            IndexLimitMb::Unlimited => Some(BucketMap::new(bucket_config)),
+            IndexLimitMb::Unlimited | IndexLimitMb::Limit(_) => Some(BucketMap::new(bucket_config)),
+        };
+        let mem_budget_bytes = match index_limit_mb {
+            IndexLimitMb::Limit(mb) => Some(mb.saturating_mul(1024 * 1024)),
+            IndexLimitMb::Unlimited | IndexLimitMb::InMemOnly => None,
         };
 
         Self {
             disk,
             ages_to_stay_in_cache,
+            mem_budget_bytes,
             count_buckets_flushed: AtomicUsize::default(),
             // age = 0
             age: AtomicAge::default(),
@@ -488,6 +509,35 @@ pub mod tests {
         assert!(test.is_disk_index_enabled());
     }
 
+    #[test]
+    fn test_mem_budget() {
+        let bins = 2;
+        let config = AccountsIndexConfig {
+            index_limit_mb: IndexLimitMb::Unlimited,
+            ..AccountsIndexConfig::default()
+        };
+        let test = BucketMapHolder::<u64, u64>::new(bins, &Some(config), 1);
+        assert!(test.mem_budget_bytes.is_none());
+        test.stats.add_mem_count(0, 1_000_000);
+        assert!(!test.is_over_mem_budget());
+
+        let config = AccountsIndexConfig {
+            index_limit_mb: IndexLimitMb::Limit(1),
+            ..AccountsIndexConfig::default()
+        };
+        let test = BucketMapHolder::<u64, u64>::new(bins, &Some(config), 1);
+        assert!(test.is_disk_index_enabled());
+        assert_eq!(test.mem_budget_bytes, Some(1024 * 1024));
+        let entry_size = InMemAccountsIndex::<u64, u64>::approx_size_of_one_entry();
+        let entries_in_budget = 1024 * 1024 / entry_size;
+        test.stats.add_mem_count(0, entries_in_budget);
+        assert!(!test.is_over_mem_budget());
+        test.stats.add_mem_count(1, 1);
+        assert!(test.is_over_mem_budget());
+        test.stats.sub_mem_count(1, 1);
+        assert!(!test.is_over_mem_budget());
+    }
+
     #[test]
     fn test_age_time() {
         solana_logger::setup();
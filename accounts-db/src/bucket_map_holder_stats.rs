@@ -1,6 +1,6 @@
 use {
     crate::{
-        accounts_index::{in_mem_accounts_index::InMemAccountsIndex, DiskIndexValue, IndexValue},
+        accounts_index::{DiskIndexValue, IndexValue},
         bucket_map_holder::{Age, AtomicAge, BucketMapHolder},
     },
     solana_sdk::timing::AtomicInterval,
@@ -62,6 +62,7 @@ pub struct BucketMapHolderStats {
     last_time: AtomicInterval,
     bins: u64,
     pub flush_should_evict_us: AtomicU64,
+    pub flushes_for_mem_budget: AtomicU64,
 }
 
 impl BucketMapHolderStats {
@@ -154,6 +155,11 @@ impl BucketMapHolderStats {
         }
     }
 
+    /// percentage of lookups which were served from the in-mem index
+    fn calc_hit_percent(hits: u64, misses: u64) -> f32 {
+        Self::calc_percent(hits, hits.saturating_add(misses))
+    }
+
     fn calc_percent(ms: u64, elapsed_ms: u64) -> f32 {
         if elapsed_ms == 0 {
             0.0
@@ -225,6 +231,10 @@ impl BucketMapHolderStats {
         // sum of elapsed time in each thread
         let mut thread_time_elapsed_ms = elapsed_ms * storage.threads as u64;
         if disk.is_some() {
+            let gets_from_mem = self.gets_from_mem.swap(0, Ordering::Relaxed);
+            let gets_missing = self.gets_missing.swap(0, Ordering::Relaxed);
+            let entries_from_mem = self.entries_from_mem.swap(0, Ordering::Relaxed);
+            let entries_missing = self.entries_missing.swap(0, Ordering::Relaxed);
             if was_startup {
                 // these stats only apply at startup
                 datapoint_info!(
@@ -260,10 +270,16 @@ impl BucketMapHolderStats {
                 } else {
                     "accounts_index"
                 },
+                ("estimate_mem_bytes", storage.estimate_mem_bytes(), i64),
+                (
+                    "mem_budget_bytes",
+                    storage.mem_budget_bytes.unwrap_or_default(),
+                    i64
+                ),
+                ("over_mem_budget", storage.is_over_mem_budget(), bool),
                 (
-                    "estimate_mem_bytes",
-                    self.count_in_mem.load(Ordering::Relaxed)
-                        * InMemAccountsIndex::<T, U>::approx_size_of_one_entry(),
+                    "flushes_for_mem_budget",
+                    self.flushes_for_mem_budget.swap(0, Ordering::Relaxed),
                     i64
                 ),
                 (
@@ -316,30 +332,28 @@ impl BucketMapHolderStats {
                 ("max_in_bin_disk", disk_stats.1, i64),
                 ("count_from_bins_disk", disk_stats.2, i64),
                 ("median_from_bins_disk", disk_stats.3, i64),
+                ("gets_from_mem", gets_from_mem, i64),
                 (
-                    "gets_from_mem",
-                    self.gets_from_mem.swap(0, Ordering::Relaxed),
-                    i64
+                    "get_hit_percent",
+                    Self::calc_hit_percent(gets_from_mem, gets_missing),
+                    f64
                 ),
                 (
                     "get_mem_us",
                     self.get_mem_us.swap(0, Ordering::Relaxed),
                     i64
                 ),
-                (
-                    "gets_missing",
-                    self.gets_missing.swap(0, Ordering::Relaxed),
-                    i64
-                ),
+                ("gets_missing", gets_missing, i64),
                 (
                     "get_missing_us",
                     self.get_missing_us.swap(0, Ordering::Relaxed),
                     i64
                 ),
+                ("entries_from_mem", entries_from_mem, i64),
                 (
-                    "entries_from_mem",
-                    self.entries_from_mem.swap(0, Ordering::Relaxed),
-                    i64
+                    "entry_hit_percent",
+                    Self::calc_hit_percent(entries_from_mem, entries_missing),
+                    f64
                 ),
                 (
                     "entry_mem_us",
@@ -366,11 +380,7 @@ impl BucketMapHolderStats {
                     self.load_disk_missing_us.swap(0, Ordering::Relaxed),
                     i64
                 ),
-                (
-                    "entries_missing",
-                    self.entries_missing.swap(0, Ordering::Relaxed),
-                    i64
-                ),
+                ("entries_missing", entries_missing, i64),
                 (
                     "entry_missing_us",
                     self.entry_missing_us.swap(0, Ordering::Relaxed),
@@ -48,6 +48,8 @@ mod verify_accounts_hash_in_background;
 pub mod waitable_condvar;
 
 // the accounts-hash-cache-tool needs access to these types
+// the admin rpc service reports the read-only accounts cache accounting
+pub use read_only_accounts_cache::ReadOnlyCacheForkStats;
 pub use {
     accounts_hash::CalculateHashIntermediate as CacheHashDataFileEntry,
     cache_hash_data::{
@@ -33,11 +33,14 @@ const CACHE_ENTRY_SIZE: usize =
 
 type ReadOnlyCacheKey = Pubkey;
 
+/// Number of forks whose accounting is kept, the lowest forks being retired first
+const MAX_TRACKED_FORKS: usize = 256;
+
 #[derive(Debug)]
 #[cfg_attr(feature = "dev-context-only-utils", qualifiers(pub))]
 #[cfg_attr(
     feature = "dev-context-only-utils",
-    field_qualifiers(account(pub), slot(pub), last_update_time(pub))
+    field_qualifiers(account(pub), slot(pub), fork(pub), last_update_time(pub))
 )]
 struct ReadOnlyAccountCacheEntry {
     account: AccountSharedData,
@@ -46,6 +49,8 @@ struct ReadOnlyAccountCacheEntry {
     /// make sure that both pubkey and slot matches in the cache. Otherwise, we
     /// may return the wrong account.
     slot: Slot,
+    /// Slot of the bank which stored the entry, its evictions being accounted to that fork
+    fork: Slot,
     /// Timestamp when the entry was updated, in ns
     last_update_time: AtomicU64,
 }
@@ -62,6 +67,38 @@ pub struct ReadOnlyCacheStats {
     pub evictor_wakeup_count_productive: u64,
 }
 
+/// Accounting of the cache for the loads and stores of the banks of one fork
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadOnlyCacheForkStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub stores: u64,
+    /// Evictions of the entries stored by the fork
+    pub evicts: u64,
+}
+
+#[derive(Default, Debug)]
+struct AtomicReadOnlyCacheForkStats {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    stores: AtomicU64,
+    evicts: AtomicU64,
+}
+
+impl AtomicReadOnlyCacheForkStats {
+    fn load(&self) -> ReadOnlyCacheForkStats {
+        ReadOnlyCacheForkStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            stores: self.stores.load(Ordering::Relaxed),
+            evicts: self.evicts.load(Ordering::Relaxed),
+        }
+    }
+}
+
+type ForkStatsMap = DashMap<Slot, AtomicReadOnlyCacheForkStats, AHashRandomState>;
+
 #[derive(Default, Debug)]
 struct AtomicReadOnlyCacheStats {
     hits: AtomicU64,
@@ -78,12 +115,15 @@ struct AtomicReadOnlyCacheStats {
 #[derive(Debug)]
 pub(crate) struct ReadOnlyAccountsCache {
     cache: Arc<DashMap<ReadOnlyCacheKey, ReadOnlyAccountCacheEntry, AHashRandomState>>,
-    _max_data_size_lo: usize,
-    _max_data_size_hi: usize,
+    /// The evictor evicts entries down to the low watermark once the cache exceeds the high
+    /// watermark. Both can be changed while the cache is running
+    max_data_size_lo: Arc<AtomicUsize>,
+    max_data_size_hi: Arc<AtomicUsize>,
     data_size: Arc<AtomicUsize>,
 
     // Performance statistics
     stats: Arc<AtomicReadOnlyCacheStats>,
+    fork_stats: Arc<ForkStatsMap>,
     highest_slot_stored: AtomicU64,
 
     /// Timer for generating timestamps for entries.
@@ -107,27 +147,32 @@ impl ReadOnlyAccountsCache {
         assert!(max_data_size_lo <= max_data_size_hi);
         assert!(evict_sample_size > 0);
         let cache = Arc::new(DashMap::with_hasher(AHashRandomState::default()));
+        let max_data_size_lo = Arc::new(AtomicUsize::new(max_data_size_lo));
+        let max_data_size_hi = Arc::new(AtomicUsize::new(max_data_size_hi));
         let data_size = Arc::new(AtomicUsize::default());
         let stats = Arc::new(AtomicReadOnlyCacheStats::default());
+        let fork_stats = Arc::new(DashMap::with_hasher(AHashRandomState::default()));
         let timer = Instant::now();
         let evictor_exit_flag = Arc::new(AtomicBool::new(false));
         let evictor_thread_handle = Self::spawn_evictor(
             evictor_exit_flag.clone(),
-            max_data_size_lo,
-            max_data_size_hi,
+            max_data_size_lo.clone(),
+            max_data_size_hi.clone(),
             data_size.clone(),
             evict_sample_size,
             cache.clone(),
             stats.clone(),
+            fork_stats.clone(),
         );
 
         Self {
             highest_slot_stored: AtomicU64::default(),
-            _max_data_size_lo: max_data_size_lo,
-            _max_data_size_hi: max_data_size_hi,
+            max_data_size_lo,
+            max_data_size_hi,
             cache,
             data_size,
             stats,
+            fork_stats,
             timer,
             evictor_thread_handle: ManuallyDrop::new(evictor_thread_handle),
             evictor_exit_flag,
@@ -143,8 +188,9 @@ impl ReadOnlyAccountsCache {
         }
     }
 
+    /// Loads the account stored at `slot`, for a bank of the fork at `fork`
     #[cfg_attr(feature = "dev-context-only-utils", qualifiers(pub))]
-    pub(crate) fn load(&self, pubkey: Pubkey, slot: Slot) -> Option<AccountSharedData> {
+    pub(crate) fn load(&self, pubkey: Pubkey, slot: Slot, fork: Slot) -> Option<AccountSharedData> {
         let (account, load_us) = measure_us!({
             let mut found = None;
             if let Some(entry) = self.cache.get(&pubkey) {
@@ -155,12 +201,14 @@ impl ReadOnlyAccountsCache {
                     let account = entry.account.clone();
                     drop(entry);
                     self.stats.hits.fetch_add(1, Ordering::Relaxed);
+                    Self::record_fork_stat(&self.fork_stats, fork, |stats| &stats.hits);
                     found = Some(account);
                 }
             }
 
             if found.is_none() {
                 self.stats.misses.fetch_add(1, Ordering::Relaxed);
+                Self::record_fork_stat(&self.fork_stats, fork, |stats| &stats.misses);
             }
             found
         });
@@ -172,9 +220,10 @@ impl ReadOnlyAccountsCache {
         CACHE_ENTRY_SIZE + account.data().len()
     }
 
+    /// Stores the account stored at `slot`, for a bank of the fork at `fork`
     #[cfg_attr(feature = "dev-context-only-utils", qualifiers(pub))]
-    pub(crate) fn store(&self, pubkey: Pubkey, slot: Slot, account: AccountSharedData) {
-        self.store_with_timestamp(pubkey, slot, account, self.timestamp())
+    pub(crate) fn store(&self, pubkey: Pubkey, slot: Slot, account: AccountSharedData, fork: Slot) {
+        self.store_with_timestamp(pubkey, slot, account, fork, self.timestamp())
     }
 
     #[cfg_attr(feature = "dev-context-only-utils", qualifiers(pub))]
@@ -183,6 +232,7 @@ impl ReadOnlyAccountsCache {
         pubkey: Pubkey,
         slot: Slot,
         account: AccountSharedData,
+        fork: Slot,
         timestamp: u64,
     ) {
         let measure_store = Measure::start("");
@@ -191,7 +241,9 @@ impl ReadOnlyAccountsCache {
         self.data_size.fetch_add(account_size, Ordering::Relaxed);
         match self.cache.entry(pubkey) {
             Entry::Vacant(entry) => {
-                entry.insert(ReadOnlyAccountCacheEntry::new(account, slot, timestamp));
+                entry.insert(ReadOnlyAccountCacheEntry::new(
+                    account, slot, fork, timestamp,
+                ));
             }
             Entry::Occupied(mut entry) => {
                 let entry = entry.get_mut();
@@ -199,9 +251,11 @@ impl ReadOnlyAccountsCache {
                 self.data_size.fetch_sub(account_size, Ordering::Relaxed);
                 entry.account = account;
                 entry.slot = slot;
+                entry.fork = fork;
                 entry.last_update_time.store(timestamp, Ordering::Relaxed);
             }
         };
+        Self::record_fork_stat(&self.fork_stats, fork, |stats| &stats.stores);
         let store_us = measure_store.end_as_us();
         self.stats.store_us.fetch_add(store_us, Ordering::Relaxed);
     }
@@ -245,6 +299,75 @@ impl ReadOnlyAccountsCache {
         self.data_size.load(Ordering::Relaxed)
     }
 
+    /// Returns the low and high watermarks of the size of the cache
+    pub(crate) fn max_data_size(&self) -> (usize, usize) {
+        (
+            self.max_data_size_lo.load(Ordering::Relaxed),
+            self.max_data_size_hi.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Changes the low and high watermarks of the size of the cache. The evictor shrinks the
+    /// cache to the new low watermark on its next wakeup if it exceeds the new high watermark
+    pub(crate) fn set_max_data_size(&self, max_data_size_lo: usize, max_data_size_hi: usize) {
+        assert!(max_data_size_lo <= max_data_size_hi);
+        self.max_data_size_lo
+            .store(max_data_size_lo, Ordering::Relaxed);
+        self.max_data_size_hi
+            .store(max_data_size_hi, Ordering::Relaxed);
+    }
+
+    /// Removes every entry from the cache, returning the number of entries removed
+    pub(crate) fn flush(&self) -> usize {
+        let mut num_flushed = 0;
+        self.cache.retain(|_, entry| {
+            self.data_size
+                .fetch_sub(Self::account_size(&entry.account), Ordering::Relaxed);
+            num_flushed += 1;
+            false
+        });
+        num_flushed
+    }
+
+    fn record_fork_stat(
+        fork_stats: &ForkStatsMap,
+        fork: Slot,
+        stat: impl Fn(&AtomicReadOnlyCacheForkStats) -> &AtomicU64,
+    ) {
+        // Only take the write lock of the shard the first time the fork is seen
+        if let Some(stats) = fork_stats.get(&fork) {
+            stat(stats.value()).fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        stat(fork_stats.entry(fork).or_default().value()).fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the accounting of the tracked forks, by ascending slot
+    pub(crate) fn fork_stats(&self) -> Vec<(Slot, ReadOnlyCacheForkStats)> {
+        let mut fork_stats: Vec<_> = self
+            .fork_stats
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().load()))
+            .collect();
+        fork_stats.sort_unstable_by_key(|(fork, _)| *fork);
+        fork_stats
+    }
+
+    /// Stops tracking the lowest forks beyond `MAX_TRACKED_FORKS`, returning their final
+    /// accounting
+    pub(crate) fn retire_fork_stats(&self) -> Vec<(Slot, ReadOnlyCacheForkStats)> {
+        let mut forks: Vec<Slot> = self.fork_stats.iter().map(|entry| *entry.key()).collect();
+        if forks.len() <= MAX_TRACKED_FORKS {
+            return vec![];
+        }
+        forks.sort_unstable();
+        forks[..forks.len() - MAX_TRACKED_FORKS]
+            .iter()
+            .filter_map(|fork| self.fork_stats.remove(fork))
+            .map(|(fork, stats)| (fork, stats.load()))
+            .collect()
+    }
+
     pub(crate) fn get_and_reset_stats(&self) -> ReadOnlyCacheStats {
         let hits = self.stats.hits.swap(0, Ordering::Relaxed);
         let misses = self.stats.misses.swap(0, Ordering::Relaxed);
@@ -274,14 +397,16 @@ impl ReadOnlyAccountsCache {
     }
 
     /// Spawns the background thread to handle evictions
+    #[allow(clippy::too_many_arguments)]
     fn spawn_evictor(
         exit: Arc<AtomicBool>,
-        max_data_size_lo: usize,
-        max_data_size_hi: usize,
+        max_data_size_lo: Arc<AtomicUsize>,
+        max_data_size_hi: Arc<AtomicUsize>,
         data_size: Arc<AtomicUsize>,
         evict_sample_size: usize,
         cache: Arc<DashMap<ReadOnlyCacheKey, ReadOnlyAccountCacheEntry, AHashRandomState>>,
         stats: Arc<AtomicReadOnlyCacheStats>,
+        fork_stats: Arc<ForkStatsMap>,
     ) -> thread::JoinHandle<()> {
         thread::Builder::new()
             .name("solAcctReadCache".to_string())
@@ -300,7 +425,8 @@ impl ReadOnlyAccountsCache {
                         .evictor_wakeup_count_all
                         .fetch_add(1, Ordering::Relaxed);
 
-                    if data_size.load(Ordering::Relaxed) <= max_data_size_hi {
+                    if data_size.load(Ordering::Relaxed) <= max_data_size_hi.load(Ordering::Relaxed)
+                    {
                         continue;
                     }
                     stats
@@ -309,18 +435,20 @@ impl ReadOnlyAccountsCache {
 
                     #[cfg(not(feature = "dev-context-only-utils"))]
                     let (num_evicts, evict_us) = measure_us!(Self::evict(
-                        max_data_size_lo,
+                        max_data_size_lo.load(Ordering::Relaxed),
                         &data_size,
                         evict_sample_size,
                         &cache,
+                        &fork_stats,
                         &mut rng,
                     ));
                     #[cfg(feature = "dev-context-only-utils")]
                     let (num_evicts, evict_us) = measure_us!(Self::evict(
-                        max_data_size_lo,
+                        max_data_size_lo.load(Ordering::Relaxed),
                         &data_size,
                         evict_sample_size,
                         &cache,
+                        &fork_stats,
                         &mut rng,
                         |_, _| {}
                     ));
@@ -343,6 +471,7 @@ impl ReadOnlyAccountsCache {
         data_size: &AtomicUsize,
         evict_sample_size: usize,
         cache: &DashMap<ReadOnlyCacheKey, ReadOnlyAccountCacheEntry, AHashRandomState>,
+        fork_stats: &ForkStatsMap,
         rng: &mut R,
         #[cfg(feature = "dev-context-only-utils")] mut callback: impl FnMut(
             &Pubkey,
@@ -382,12 +511,11 @@ impl ReadOnlyAccountsCache {
             }
 
             let key = key_to_evict.expect("eviction sample should not be empty");
-            #[cfg(not(feature = "dev-context-only-utils"))]
-            Self::do_remove(&key, cache, data_size);
-            #[cfg(feature = "dev-context-only-utils")]
-            {
-                let entry = Self::do_remove(&key, cache, data_size);
-                callback(&key, entry.unwrap());
+            let entry = Self::do_remove(&key, cache, data_size);
+            if let Some(entry) = entry {
+                Self::record_fork_stat(fork_stats, entry.fork, |stats| &stats.evicts);
+                #[cfg(feature = "dev-context-only-utils")]
+                callback(&key, entry);
             }
             num_evicts = num_evicts.saturating_add(1);
         }
@@ -414,13 +542,13 @@ impl ReadOnlyAccountsCache {
         R: Rng,
         C: FnMut(&Pubkey, ReadOnlyAccountCacheEntry),
     {
-        #[allow(clippy::used_underscore_binding)]
-        let target_data_size = self._max_data_size_lo;
+        let target_data_size = self.max_data_size_lo.load(Ordering::Relaxed);
         Self::evict(
             target_data_size,
             &self.data_size,
             evict_sample_size,
             &self.cache,
+            &self.fork_stats,
             rng,
             callback,
         )
@@ -439,10 +567,11 @@ impl Drop for ReadOnlyAccountsCache {
 }
 
 impl ReadOnlyAccountCacheEntry {
-    fn new(account: AccountSharedData, slot: Slot, timestamp: u64) -> Self {
+    fn new(account: AccountSharedData, slot: Slot, fork: Slot, timestamp: u64) -> Self {
         Self {
             account,
             slot,
+            fork,
             last_update_time: AtomicU64::new(timestamp),
         }
     }
@@ -507,7 +636,7 @@ mod tests {
                 let element = cache.cache.iter().choose(&mut rng).unwrap();
                 let (pubkey, entry) = element.pair();
                 let slot = entry.slot;
-                let account = cache.load(*pubkey, slot).unwrap();
+                let account = cache.load(*pubkey, slot, slot).unwrap();
                 let (other, other_slot, index) = hash_map.get_mut(pubkey).unwrap();
                 assert_eq!(account, *other);
                 assert_eq!(slot, *other_slot);
@@ -525,7 +654,7 @@ mod tests {
                 let slot = *slots.choose(&mut rng).unwrap();
                 let pubkey = *pubkeys.choose(&mut rng).unwrap();
                 hash_map.insert(pubkey, (account.clone(), slot, ix));
-                cache.store(pubkey, slot, account);
+                cache.store(pubkey, slot, account, slot);
                 cache.evict_in_foreground(evict_sample_size, &mut rng, |_, _| {});
             }
         }
@@ -558,7 +687,7 @@ mod tests {
         for i in 0..MAX_ENTRIES {
             let pubkey = Pubkey::new_unique();
             let account = AccountSharedData::new(i as u64, ACCOUNT_DATA_SIZE, &Pubkey::default());
-            cache.store(pubkey, i as Slot, account);
+            cache.store(pubkey, i as Slot, account, i as Slot);
         }
         // we haven't exceeded the max cache size yet, so no evictions should've happened
         assert_eq!(cache.cache_len(), MAX_ENTRIES);
@@ -569,7 +698,7 @@ mod tests {
         let slot = MAX_ENTRIES as Slot;
         let pubkey = Pubkey::new_unique();
         let account = AccountSharedData::new(42, ACCOUNT_DATA_SIZE, &Pubkey::default());
-        cache.store(pubkey, slot, account.clone());
+        cache.store(pubkey, slot, account.clone(), slot);
 
         // wait for the evictor to run...
         let timer = Instant::now();
@@ -585,4 +714,83 @@ mod tests {
         assert_eq!(cache.cache_len(), MAX_ENTRIES);
         assert_eq!(cache.data_size(), MAX_CACHE_SIZE);
     }
+
+    #[test]
+    fn test_fork_stats() {
+        let cache = ReadOnlyAccountsCache::new(usize::MAX, usize::MAX, 8);
+        let pubkey = Pubkey::new_unique();
+        let account = AccountSharedData::new(1, 0, &Pubkey::default());
+        let (slot, fork_a, fork_b) = (1, 10, 11);
+        cache.store(pubkey, slot, account, fork_a);
+        assert!(cache.load(pubkey, slot, fork_a).is_some());
+        assert!(cache.load(pubkey, slot, fork_b).is_some());
+        assert!(cache.load(pubkey, slot + 1, fork_b).is_none());
+        assert_eq!(
+            cache.fork_stats(),
+            vec![
+                (
+                    fork_a,
+                    ReadOnlyCacheForkStats {
+                        hits: 1,
+                        stores: 1,
+                        ..ReadOnlyCacheForkStats::default()
+                    }
+                ),
+                (
+                    fork_b,
+                    ReadOnlyCacheForkStats {
+                        hits: 1,
+                        misses: 1,
+                        ..ReadOnlyCacheForkStats::default()
+                    }
+                ),
+            ]
+        );
+
+        // Evictions are accounted to the fork which stored the entry
+        cache.set_max_data_size(0, 0);
+        cache.evict_in_foreground(1, &mut thread_rng(), |_, _| {});
+        assert_eq!(cache.fork_stats()[0].1.evicts, 1);
+
+        // The lowest forks are retired first
+        for fork in 0..MAX_TRACKED_FORKS as Slot {
+            cache.load(pubkey, slot, fork_b + 1 + fork);
+        }
+        let retired = cache.retire_fork_stats();
+        assert_eq!(
+            retired.iter().map(|(fork, _)| *fork).collect::<Vec<_>>(),
+            vec![fork_a, fork_b]
+        );
+        assert_eq!(cache.fork_stats().len(), MAX_TRACKED_FORKS);
+        assert!(cache.retire_fork_stats().is_empty());
+    }
+
+    #[test]
+    fn test_set_max_data_size_and_flush() {
+        const ACCOUNT_DATA_SIZE: usize = 200;
+        const ENTRY_SIZE: usize = CACHE_ENTRY_SIZE + ACCOUNT_DATA_SIZE;
+        let cache = ReadOnlyAccountsCache::new(usize::MAX, usize::MAX, 1);
+        for slot in 0..4 {
+            let account = AccountSharedData::new(1, ACCOUNT_DATA_SIZE, &Pubkey::default());
+            cache.store(Pubkey::new_unique(), slot, account, slot);
+        }
+        assert_eq!(cache.data_size(), 4 * ENTRY_SIZE);
+
+        // The evictor shrinks the cache to the new low watermark
+        cache.set_max_data_size(ENTRY_SIZE, 2 * ENTRY_SIZE);
+        assert_eq!(cache.max_data_size(), (ENTRY_SIZE, 2 * ENTRY_SIZE));
+        let timer = Instant::now();
+        while cache.data_size() > ENTRY_SIZE {
+            assert!(
+                timer.elapsed() < Duration::from_secs(5),
+                "timed out waiting for the evictor to run",
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(cache.cache_len(), 1);
+
+        assert_eq!(cache.flush(), 1);
+        assert_eq!(cache.cache_len(), 0);
+        assert_eq!(cache.data_size(), 0);
+    }
 }
@@ -41,7 +41,7 @@ fn test_read_only_accounts_cache_eviction(num_accounts: (usize, usize), evict_sa
             owner: pubkey,
         });
         let slot = 0;
-        cache.store(pubkey, slot, account.clone());
+        cache.store(pubkey, slot, account.clone(), slot);
         if i >= num_accounts_hi / 2 {
             // Store some of the most recently used accounts so we can
             // check that we don't evict from this set.
@@ -67,6 +67,7 @@ fn test_read_only_accounts_cache_eviction(num_accounts: (usize, usize), evict_sa
                 pubkey,
                 entry.slot,
                 entry.account,
+                entry.fork,
                 entry.last_update_time.load(Ordering::Relaxed),
             );
         }
@@ -10,7 +10,9 @@ use {
     solana_client::connection_cache::ConnectionCache,
     solana_core::{
         banking_stage::{update_bank_forks_and_poh_recorder_for_new_tpu_bank, BankingStage},
-        banking_trace::{BankingTracer, Channels, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT},
+        banking_trace::{
+            BankingCapture, BankingTracer, Channels, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT,
+        },
         validator::{BlockProductionMethod, TransactionStructure},
     },
     solana_gossip::cluster_info::{ClusterInfo, Node},
@@ -496,6 +498,7 @@ fn main() {
         bank_forks.clone(),
         &prioritization_fee_cache,
         false,
+        BankingCapture::default(),
     );
 
     // This is so that the signal_receiver does not go out of scope after the closure.
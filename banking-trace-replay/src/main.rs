@@ -0,0 +1,482 @@
+#![allow(clippy::arithmetic_side_effects)]
+//! Re-runs the banking stage's central scheduler offline from a banking trace captured with
+//! `--capture-banking-trace`.
+//!
+//! The trace events (`events*` files) provide the packets exactly as they were received, while
+//! the capture events (`captures*` files) provide the leader slot boundaries, the filter decision
+//! made for every received transaction along with the fee inputs of its priority, and the points
+//! at which the scheduler ran. Replaying them feeds the buffered transactions of each leader slot
+//! through the real scheduler, which is usually enough to answer why a given transaction did or
+//! did not land.
+
+use {
+    clap::{crate_description, crate_name, value_t, values_t, App, Arg},
+    log::*,
+    serde::de::DeserializeOwned,
+    solana_core::{
+        banking_stage::{
+            scheduler_replay::{replay_scheduler, ReplayedSlot},
+            BankingStage,
+        },
+        banking_trace::{
+            BankingTracer, CapturedDecision, CapturedEvent, ChannelLabel, TimedCapturedEvent,
+            TimedTracedEvent, TracedEvent,
+        },
+        validator::BlockProductionMethod,
+    },
+    solana_sdk::{
+        clock::Slot, hash::Hash, signature::Signature, transaction::VersionedTransaction,
+    },
+    std::{
+        collections::{BTreeMap, HashMap, HashSet},
+        fs::File,
+        io::{BufRead, BufReader, ErrorKind},
+        path::{Path, PathBuf},
+        process::exit,
+        str::FromStr,
+        time::SystemTime,
+    },
+};
+
+/// Collects the rotated files of one stream, oldest first.
+fn rotated_file_paths(trace_dir: &Path, file_name: impl Fn(usize) -> String) -> Vec<PathBuf> {
+    let mut paths = (0..)
+        .map(|index| trace_dir.join(file_name(index)))
+        .take_while(|path| path.exists())
+        .collect::<Vec<_>>();
+    paths.reverse();
+    paths
+}
+
+fn read_event_files<T: DeserializeOwned>(paths: &[PathBuf], mut callback: impl FnMut(T)) {
+    for path in paths {
+        let mut reader = match File::open(path) {
+            Ok(file) => BufReader::new(file),
+            Err(err) => {
+                eprintln!("Failed to open {path:?}: {err}");
+                exit(1);
+            }
+        };
+        let mut count = 0;
+        loop {
+            match reader.fill_buf() {
+                Ok(buf) if buf.is_empty() => break,
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("Failed to read {path:?}: {err}");
+                    exit(1);
+                }
+            }
+            match bincode::deserialize_from(&mut reader) {
+                Ok(event) => {
+                    count += 1;
+                    callback(event);
+                }
+                Err(err) => {
+                    // The tail of the newest file is routinely truncated by an unclean shutdown
+                    if matches!(&*err, bincode::ErrorKind::Io(io_err) if io_err.kind() == ErrorKind::UnexpectedEof)
+                    {
+                        warn!("Truncated event in {path:?}, ignoring the rest of the file");
+                        break;
+                    }
+                    eprintln!("Failed to deserialize an event from {path:?}: {err}");
+                    exit(1);
+                }
+            }
+        }
+        info!("Read {count} events from {path:?}");
+    }
+}
+
+struct Arrival {
+    time: SystemTime,
+    signature: Signature,
+    discarded: bool,
+}
+
+/// Fee inputs captured when a transaction was buffered
+struct Buffered {
+    reward: u64,
+    cost: u64,
+    priority: u64,
+}
+
+/// Where the replayed scheduler sent a transaction
+struct Scheduled {
+    slot: Slot,
+    batch_index: usize,
+    num_batches: usize,
+    worker: usize,
+    rank: usize,
+    num_scheduled: usize,
+}
+
+fn decision_name(decision: &CapturedDecision) -> &'static str {
+    match decision {
+        CapturedDecision::DroppedOnSanitization => "dropped on sanitization",
+        CapturedDecision::DroppedOnLockValidation => "dropped on lock validation",
+        CapturedDecision::DroppedOnComputeBudget => "dropped on compute budget",
+        CapturedDecision::DroppedOnTransactionChecks => "dropped on age/status checks",
+        CapturedDecision::DroppedOnFeePayerCheck => "dropped on fee payer check",
+        CapturedDecision::Buffered { .. } => "buffered",
+        CapturedDecision::DroppedOnComputeUnitPrice => "dropped on minimum compute unit price",
+    }
+}
+
+fn find_scheduled(slots: &[ReplayedSlot], target: &Hash) -> Option<Scheduled> {
+    slots.iter().find_map(|replayed_slot| {
+        let mut rank = 0;
+        let mut found = None;
+        for (batch_index, batch) in replayed_slot.batches.iter().enumerate() {
+            for message_hash in &batch.message_hashes {
+                if message_hash == target && found.is_none() {
+                    found = Some((batch_index, batch.worker, rank));
+                }
+                rank += 1;
+            }
+        }
+        found.map(|(batch_index, worker, rank)| Scheduled {
+            slot: replayed_slot.slot,
+            batch_index,
+            num_batches: replayed_slot.batches.len(),
+            worker,
+            rank,
+            num_scheduled: rank_count(replayed_slot),
+        })
+    })
+}
+
+fn rank_count(replayed_slot: &ReplayedSlot) -> usize {
+    replayed_slot
+        .batches
+        .iter()
+        .map(|batch| batch.message_hashes.len())
+        .sum()
+}
+
+fn main() {
+    solana_logger::setup_with_default_filter();
+
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(solana_version::version!())
+        .arg(
+            Arg::with_name("trace_dir")
+                .long("trace-dir")
+                .value_name("DIR")
+                .takes_value(true)
+                .required(true)
+                .help("Banking trace directory, usually <LEDGER>/banking_trace"),
+        )
+        .arg(
+            Arg::with_name("slot")
+                .long("slot")
+                .value_name("SLOT")
+                .takes_value(true)
+                .multiple(true)
+                .help("Only show these leader slots [default: all captured slots]"),
+        )
+        .arg(
+            Arg::with_name("signature")
+                .long("signature")
+                .value_name("SIGNATURE")
+                .takes_value(true)
+                .conflicts_with("message_hash")
+                .help("Show the captured history of the transaction with this signature"),
+        )
+        .arg(
+            Arg::with_name("message_hash")
+                .long("message-hash")
+                .value_name("HASH")
+                .takes_value(true)
+                .help("Show the captured history of the transaction with this message hash"),
+        )
+        .arg(
+            Arg::with_name("top")
+                .long("top")
+                .value_name("NUM")
+                .takes_value(true)
+                .default_value("10")
+                .help("Number of first scheduled transactions to list per slot"),
+        )
+        .arg(
+            Arg::with_name("block_production_method")
+                .long("block-production-method")
+                .value_name("METHOD")
+                .takes_value(true)
+                .possible_values(BlockProductionMethod::cli_names())
+                .help(BlockProductionMethod::cli_message()),
+        )
+        .arg(
+            Arg::with_name("num_threads")
+                .long("num-threads")
+                .value_name("NUM")
+                .takes_value(true)
+                .help(
+                    "Number of banking threads the traced validator ran, votes included. Like \
+                     the validator, values below the banking stage minimum are raised to it \
+                     [default: SOLANA_BANKING_THREADS or its default]",
+                ),
+        )
+        .get_matches();
+
+    let trace_dir = PathBuf::from(matches.value_of("trace_dir").unwrap());
+    let slots = values_t!(matches, "slot", Slot).ok();
+    let top = value_t!(matches, "top", usize).unwrap_or_else(|err| err.exit());
+    let block_production_method =
+        value_t!(matches, "block_production_method", BlockProductionMethod).unwrap_or_default();
+    let num_threads = if matches.is_present("num_threads") {
+        value_t!(matches, "num_threads", u32).unwrap_or_else(|err| err.exit())
+    } else {
+        BankingStage::num_threads()
+    };
+
+    let capture_paths = rotated_file_paths(&trace_dir, BankingTracer::capture_file_name);
+    if capture_paths.is_empty() {
+        eprintln!(
+            "No captured events in {trace_dir:?}; was the validator run with \
+             --capture-banking-trace?"
+        );
+        exit(1);
+    }
+    let event_paths = rotated_file_paths(&trace_dir, BankingTracer::event_file_name);
+
+    let mut captured_events = vec![];
+    read_event_files(&capture_paths, |TimedCapturedEvent(_, event)| {
+        captured_events.push(event)
+    });
+    let mut buffered = HashMap::<Hash, Buffered>::new();
+    let mut decisions = BTreeMap::<Slot, Vec<(Hash, CapturedDecision)>>::new();
+    let mut leader_slots = BTreeMap::<Slot, (Slot, u64)>::new();
+    for event in &captured_events {
+        match event {
+            CapturedEvent::BankStart {
+                slot,
+                parent_slot,
+                lamports_per_signature,
+            } => {
+                leader_slots.insert(*slot, (*parent_slot, *lamports_per_signature));
+            }
+            CapturedEvent::Decision {
+                slot,
+                message_hash,
+                decision,
+            } => {
+                if let CapturedDecision::Buffered {
+                    reward,
+                    cost,
+                    priority,
+                    ..
+                } = *decision
+                {
+                    buffered.insert(
+                        *message_hash,
+                        Buffered {
+                            reward,
+                            cost,
+                            priority,
+                        },
+                    );
+                }
+                decisions
+                    .entry(*slot)
+                    .or_default()
+                    .push((*message_hash, *decision));
+            }
+            CapturedEvent::BankEnd { .. }
+            | CapturedEvent::LoadedAddresses { .. }
+            | CapturedEvent::Received { .. }
+            | CapturedEvent::Cleared => {}
+        }
+    }
+
+    // Only the packets of buffered transactions are kept, traces are much larger than captures
+    let mut arrivals = HashMap::<Hash, Arrival>::new();
+    let mut packets = vec![];
+    let mut traced = HashSet::<Hash>::new();
+    read_event_files(&event_paths, |TimedTracedEvent(time, event)| {
+        let TracedEvent::PacketBatch(ChannelLabel::NonVote, batches) = event else {
+            return;
+        };
+        for packet in batches.iter().flat_map(|batch| batch.iter()) {
+            let Ok(transaction) = packet.deserialize_slice::<VersionedTransaction, _>(..) else {
+                continue;
+            };
+            let Some(signature) = transaction.signatures.first().copied() else {
+                continue;
+            };
+            let message_hash = transaction.message.hash();
+            if !packet.meta().discard()
+                && buffered.contains_key(&message_hash)
+                && traced.insert(message_hash)
+            {
+                packets.push(packet.clone());
+            }
+            arrivals.entry(message_hash).or_insert(Arrival {
+                time,
+                signature,
+                discarded: packet.meta().discard(),
+            });
+        }
+    });
+
+    let schedule = replay_scheduler(
+        &block_production_method,
+        num_threads,
+        packets,
+        captured_events,
+    );
+    if schedule.num_missing_transactions > 0 {
+        warn!(
+            "{} buffered transactions could not be replayed, their packets weren't traced",
+            schedule.num_missing_transactions
+        );
+    }
+
+    let target = if let Some(signature) = matches.value_of("signature") {
+        let signature = Signature::from_str(signature).unwrap_or_else(|err| {
+            eprintln!("Invalid signature {signature}: {err}");
+            exit(1);
+        });
+        let message_hash = arrivals
+            .iter()
+            .find(|(_, arrival)| arrival.signature == signature)
+            .map(|(message_hash, _)| *message_hash);
+        match message_hash {
+            Some(message_hash) => Some(message_hash),
+            None => {
+                println!("Transaction {signature} was never received by the banking stage");
+                return;
+            }
+        }
+    } else {
+        matches.value_of("message_hash").map(|message_hash| {
+            Hash::from_str(message_hash).unwrap_or_else(|err| {
+                eprintln!("Invalid message hash {message_hash}: {err}");
+                exit(1);
+            })
+        })
+    };
+
+    if let Some(target) = target {
+        match arrivals.get(&target) {
+            Some(arrival) => println!(
+                "Transaction {} (message hash {target}) received at {:?}{}",
+                arrival.signature,
+                arrival.time,
+                if arrival.discarded {
+                    ", discarded by sigverify or dedup"
+                } else {
+                    ""
+                },
+            ),
+            None => println!("Message hash {target} not found among traced packets"),
+        }
+        for (slot, decisions) in &decisions {
+            for (message_hash, decision) in decisions {
+                if *message_hash != target {
+                    continue;
+                }
+                match decision {
+                    CapturedDecision::Buffered {
+                        reward,
+                        cost,
+                        priority,
+                        evicted,
+                    } => println!(
+                        "  slot {slot}: buffered with priority {priority} (reward {reward}, cost \
+                         {cost}){}",
+                        if *evicted {
+                            ", evicting lower priority transactions"
+                        } else {
+                            ""
+                        },
+                    ),
+                    _ => println!("  slot {slot}: {}", decision_name(decision)),
+                }
+            }
+        }
+        match find_scheduled(&schedule.slots, &target) {
+            Some(scheduled) => println!(
+                "  replayed scheduler sent it in leader slot {} to worker {}, in batch {} of {}, \
+                 as transaction {} of {}",
+                scheduled.slot,
+                scheduled.worker,
+                scheduled.batch_index + 1,
+                scheduled.num_batches,
+                scheduled.rank + 1,
+                scheduled.num_scheduled,
+            ),
+            None if buffered.contains_key(&target) => {
+                println!("  replayed scheduler never sent it to a worker")
+            }
+            None => {}
+        }
+        return;
+    }
+
+    for replayed_slot in &schedule.slots {
+        let slot = replayed_slot.slot;
+        if let Some(slots) = &slots {
+            if !slots.contains(&slot) {
+                continue;
+            }
+        }
+        let (parent_slot, lamports_per_signature) =
+            leader_slots.get(&slot).copied().unwrap_or_default();
+        let scheduled = replayed_slot
+            .batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .message_hashes
+                    .iter()
+                    .map(move |message_hash| (batch.worker, message_hash))
+            })
+            .collect::<Vec<_>>();
+        let (total_reward, total_cost) = scheduled
+            .iter()
+            .filter_map(|(_, message_hash)| buffered.get(*message_hash))
+            .fold((0, 0), |(reward, cost), buffered| {
+                (reward + buffered.reward, cost + buffered.cost)
+            });
+        let mut drops = BTreeMap::<&str, usize>::new();
+        let mut num_buffered = 0;
+        for (_, decision) in decisions.get(&slot).into_iter().flatten() {
+            if matches!(decision, CapturedDecision::Buffered { .. }) {
+                num_buffered += 1;
+            } else {
+                *drops.entry(decision_name(decision)).or_default() += 1;
+            }
+        }
+        println!(
+            "Slot {slot} (parent {parent_slot}, lamports_per_signature {lamports_per_signature}): \
+             {num_buffered} buffered, {} dropped, {} scheduled in {} batches, total reward {} \
+             lamports, total cost {} CUs",
+            drops.values().sum::<usize>(),
+            scheduled.len(),
+            replayed_slot.batches.len(),
+            total_reward,
+            total_cost,
+        );
+        for (name, count) in drops {
+            println!("  {name}: {count}");
+        }
+        for (rank, (worker, message_hash)) in scheduled.iter().take(top).enumerate() {
+            let signature = arrivals
+                .get(*message_hash)
+                .map(|arrival| arrival.signature.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let Buffered {
+                reward,
+                cost,
+                priority,
+            } = &buffered[*message_hash];
+            println!(
+                "  {:>4}. worker {worker:>2} priority {priority:>12} reward {reward:>10} cost \
+                 {cost:>8} {signature}",
+                rank + 1,
+            );
+        }
+    }
+}
@@ -7,7 +7,9 @@
 
 pub use {
     crate::error::BanksClientError,
-    solana_banks_interface::{BanksClient as TarpcClient, TransactionStatus},
+    solana_banks_interface::{
+        BanksClient as TarpcClient, TransactionFeeDetails, TransactionStatus,
+    },
 };
 use {
     borsh::BorshDeserialize,
@@ -506,6 +508,44 @@ impl BanksClient {
             .await
             .map_err(Into::into)
     }
+
+    /// Return the fee the message would be charged, split into its components.
+    pub async fn get_fee_details_for_message(
+        &self,
+        message: Message,
+    ) -> Result<Option<TransactionFeeDetails>, BanksClientError> {
+        self.get_fee_details_for_message_with_commitment_and_context(
+            context::current(),
+            message,
+            CommitmentLevel::default(),
+        )
+        .await
+    }
+
+    pub async fn get_fee_details_for_message_with_commitment(
+        &self,
+        message: Message,
+        commitment: CommitmentLevel,
+    ) -> Result<Option<TransactionFeeDetails>, BanksClientError> {
+        self.get_fee_details_for_message_with_commitment_and_context(
+            context::current(),
+            message,
+            commitment,
+        )
+        .await
+    }
+
+    pub async fn get_fee_details_for_message_with_commitment_and_context(
+        &self,
+        ctx: Context,
+        message: Message,
+        commitment: CommitmentLevel,
+    ) -> Result<Option<TransactionFeeDetails>, BanksClientError> {
+        self.inner
+            .get_fee_details_for_message_with_commitment_and_context(ctx, message, commitment)
+            .await
+            .map_err(Into::into)
+    }
 }
 
 pub async fn start_client<C>(transport: C) -> Result<BanksClient, BanksClientError>
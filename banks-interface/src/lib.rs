@@ -40,12 +40,28 @@ pub struct TransactionSimulationDetails {
     pub inner_instructions: Option<Vec<InnerInstructions>>,
 }
 
+/// The fee charged for a transaction, split into its components.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionFeeDetails {
+    /// The fee derived from the compute units and compute unit price of the transaction
+    pub transaction_fee: u64,
+    pub prioritization_fee: u64,
+}
+
+impl TransactionFeeDetails {
+    pub fn total_fee(&self) -> u64 {
+        self.transaction_fee.saturating_add(self.prioritization_fee)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionMetadata {
     pub log_messages: Vec<String>,
     pub compute_units_consumed: u64,
     pub return_data: Option<TransactionReturnData>,
+    pub fee_details: TransactionFeeDetails,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -94,6 +110,10 @@ pub trait Banks {
         message: Message,
         commitment: CommitmentLevel,
     ) -> Option<u64>;
+    async fn get_fee_details_for_message_with_commitment_and_context(
+        message: Message,
+        commitment: CommitmentLevel,
+    ) -> Option<TransactionFeeDetails>;
 }
 
 #[cfg(test)]
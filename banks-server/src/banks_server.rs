@@ -5,8 +5,8 @@ use {
     futures::{future, prelude::stream::StreamExt},
     solana_banks_interface::{
         Banks, BanksRequest, BanksResponse, BanksTransactionResultWithMetadata,
-        BanksTransactionResultWithSimulation, TransactionConfirmationStatus, TransactionMetadata,
-        TransactionSimulationDetails, TransactionStatus,
+        BanksTransactionResultWithSimulation, TransactionConfirmationStatus, TransactionFeeDetails,
+        TransactionMetadata, TransactionSimulationDetails, TransactionStatus,
     },
     solana_client::connection_cache::ConnectionCache,
     solana_runtime::{
@@ -368,6 +368,10 @@ impl Banks for BanksServer {
                     compute_units_consumed: details.executed_units,
                     log_messages: details.log_messages.unwrap_or_default(),
                     return_data: details.return_data,
+                    fee_details: TransactionFeeDetails {
+                        transaction_fee: details.fee_details.transaction_fee(),
+                        prioritization_fee: details.fee_details.prioritization_fee(),
+                    },
                 }),
             },
         }
@@ -411,6 +415,23 @@ impl Banks for BanksServer {
                 .ok()?;
         bank.get_fee_for_message(&sanitized_message)
     }
+
+    async fn get_fee_details_for_message_with_commitment_and_context(
+        self,
+        _: Context,
+        message: Message,
+        commitment: CommitmentLevel,
+    ) -> Option<TransactionFeeDetails> {
+        let bank = self.bank(commitment);
+        let sanitized_message =
+            SanitizedMessage::try_from_legacy_message(message, bank.get_reserved_account_keys())
+                .ok()?;
+        let fee_details = bank.get_fee_details_for_message(&sanitized_message);
+        Some(TransactionFeeDetails {
+            transaction_fee: fee_details.transaction_fee(),
+            prioritization_fee: fee_details.prioritization_fee(),
+        })
+    }
 }
 
 pub async fn start_local_server(
@@ -198,6 +198,7 @@ fn main() -> Result<()> {
         if let Some(quic_params) = &quic_params {
             let quic_server_params = QuicServerParams {
                 max_connections_per_ipaddr_per_min: 1024,
+                max_connections_per_ipaddr: 1024,
                 max_connections_per_peer: 1024,
                 ..Default::default()
             };
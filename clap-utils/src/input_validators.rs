@@ -206,13 +206,30 @@ where
     }
 }
 
+pub const X1_MAINNET_RPC_URL: &str = "https://rpc.mainnet.x1.xyz";
+pub const X1_TESTNET_RPC_URL: &str = "https://rpc.testnet.x1.xyz";
+
+/// Built-in aliases of the X1 clusters and the URL of their RPC service
+pub const X1_CLUSTER_ALIASES: &[(&str, &str)] = &[
+    ("x1-mainnet", X1_MAINNET_RPC_URL),
+    ("x1-testnet", X1_TESTNET_RPC_URL),
+];
+
+/// Returns the RPC URL of an X1 cluster alias
+pub fn x1_cluster_alias_url(alias: &str) -> Option<&'static str> {
+    X1_CLUSTER_ALIASES
+        .iter()
+        .find(|(name, _)| *name == alias)
+        .map(|(_, url)| *url)
+}
+
 pub fn normalize_to_url_if_moniker<T: AsRef<str>>(url_or_moniker: T) -> String {
     match url_or_moniker.as_ref() {
         "m" | "mainnet-beta" => "https://api.mainnet-beta.solana.com",
-        "t" | "testnet" => "https://rpc.testnet.x1.xyz",
-        "d" | "devnet" => "https://rpc.testnet.x1.xyz",
+        "t" | "testnet" => X1_TESTNET_RPC_URL,
+        "d" | "devnet" => X1_TESTNET_RPC_URL,
         "l" | "localhost" => "http://localhost:8899",
-        url => url,
+        url => x1_cluster_alias_url(url).unwrap_or(url),
     }
     .to_string()
 }
@@ -436,4 +453,24 @@ mod tests {
         assert!(is_derivation("a/b").is_err());
         assert!(is_derivation("0/4294967296").is_err());
     }
+
+    #[test]
+    fn test_normalize_to_url_if_moniker() {
+        assert_eq!(
+            normalize_to_url_if_moniker("x1-mainnet"),
+            "https://rpc.mainnet.x1.xyz"
+        );
+        assert_eq!(
+            normalize_to_url_if_moniker("x1-testnet"),
+            "https://rpc.testnet.x1.xyz"
+        );
+        assert_eq!(normalize_to_url_if_moniker("l"), "http://localhost:8899");
+        assert_eq!(
+            normalize_to_url_if_moniker("http://127.0.0.1:8899"),
+            "http://127.0.0.1:8899"
+        );
+        assert_eq!(x1_cluster_alias_url("mainnet-beta"), None);
+        assert_eq!(x1_cluster_alias_url("x1-devnet"), None);
+        assert_eq!(is_url_or_moniker("x1-testnet"), Ok(()));
+    }
 }
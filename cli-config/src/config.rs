@@ -64,6 +64,13 @@ pub struct Config {
     /// `solana_commitment_config::CommitmentLevel::Confirmed`.
     #[serde(default)]
     pub commitment: String,
+    /// The genesis hash of each cluster alias, such as `x1-mainnet`, pinned the first time the
+    /// CLI connected to it.
+    ///
+    /// The CLI warns when a node reached through an alias reports a different genesis hash,
+    /// which means the alias no longer points to the same cluster.
+    #[serde(default)]
+    pub pinned_genesis_hashes: HashMap<String, String>,
 }
 
 impl Default for Config {
@@ -93,6 +100,7 @@ impl Default for Config {
             keypair_path,
             address_labels,
             commitment,
+            pinned_genesis_hashes: HashMap::new(),
         }
     }
 }
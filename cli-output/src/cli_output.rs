@@ -26,7 +26,7 @@ use {
     solana_epoch_info::EpochInfo,
     solana_hash::Hash,
     solana_native_token::lamports_to_sol,
-    solana_program::stake::state::{Authorized, Lockup},
+    solana_program::stake::state::{warmup_cooldown_rate, Authorized, Lockup},
     solana_pubkey::Pubkey,
     solana_rpc_client_api::response::{
         RpcAccountBalance, RpcContactInfo, RpcInflationGovernor, RpcInflationRate, RpcKeyedAccount,
@@ -1543,20 +1543,64 @@ pub struct CliStakeHistory {
     pub entries: Vec<CliStakeHistoryEntry>,
     #[serde(skip_serializing)]
     pub use_lamports_unit: bool,
+    #[serde(skip_serializing)]
+    pub show_graph: bool,
 }
 
 impl QuietDisplay for CliStakeHistory {}
 impl VerboseDisplay for CliStakeHistory {}
 
+/// Width of the bars drawn by `solana stake-history --graph`
+const STAKE_HISTORY_GRAPH_WIDTH: u64 = 50;
+
+impl CliStakeHistory {
+    fn write_graph(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}",
+            style("  █ effective  ▒ deactivating (part of effective)  ░ activating").dim()
+        )?;
+        let max_stake = self
+            .entries
+            .iter()
+            .map(|entry| entry.effective_stake.saturating_add(entry.activating_stake))
+            .max()
+            .unwrap_or_default()
+            .max(1);
+        let width = |stake: u64| {
+            (u128::from(stake) * u128::from(STAKE_HISTORY_GRAPH_WIDTH) / u128::from(max_stake))
+                as usize
+        };
+        for entry in &self.entries {
+            let deactivating = width(entry.deactivating_stake.min(entry.effective_stake));
+            let effective = width(entry.effective_stake).saturating_sub(deactivating);
+            let activating = width(entry.activating_stake);
+            writeln!(
+                f,
+                "  {:>5}  {:>6.2}%  {}{}{}",
+                entry.epoch,
+                entry.warmup_cooldown_rate * 100.0,
+                "█".repeat(effective),
+                "▒".repeat(deactivating),
+                "░".repeat(activating),
+            )?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for CliStakeHistory {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f)?;
+        if self.show_graph {
+            return self.write_graph(f);
+        }
         writeln!(
             f,
             "{}",
             style(format!(
-                "  {:<5}  {:>20}  {:>20}  {:>20}",
-                "Epoch", "Effective Stake", "Activating Stake", "Deactivating Stake",
+                "  {:<5}  {:>20}  {:>20}  {:>20}  {:>11}",
+                "Epoch", "Effective Stake", "Activating Stake", "Deactivating Stake", "Rate",
             ))
             .bold()
         )?;
@@ -1568,11 +1612,12 @@ impl fmt::Display for CliStakeHistory {
         for entry in &self.entries {
             writeln!(
                 f,
-                "  {:>5}  {:>20}  {:>20}  {:>20} {}",
+                "  {:>5}  {:>20}  {:>20}  {:>20}  {:>10.2}% {}",
                 entry.epoch,
                 build_balance_message_with_config(entry.effective_stake, &config),
                 build_balance_message_with_config(entry.activating_stake, &config),
                 build_balance_message_with_config(entry.deactivating_stake, &config),
+                entry.warmup_cooldown_rate * 100.0,
                 if self.use_lamports_unit {
                     "lamports"
                 } else {
@@ -1580,17 +1625,29 @@ impl fmt::Display for CliStakeHistory {
                 }
             )?;
         }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Rate: the warmup/cooldown rate of the epoch, the largest fraction of the previous \
+             epoch's effective stake that may activate or deactivate in it"
+        )?;
         Ok(())
     }
 }
 
-impl From<&(Epoch, StakeHistoryEntry)> for CliStakeHistoryEntry {
-    fn from((epoch, entry): &(Epoch, StakeHistoryEntry)) -> Self {
+impl CliStakeHistoryEntry {
+    /// `new_rate_activation_epoch` is the epoch the reduced warmup and cooldown rate took effect
+    pub fn new(
+        epoch: Epoch,
+        entry: &StakeHistoryEntry,
+        new_rate_activation_epoch: Option<Epoch>,
+    ) -> Self {
         Self {
-            epoch: *epoch,
+            epoch,
             effective_stake: entry.effective,
             activating_stake: entry.activating,
             deactivating_stake: entry.deactivating,
+            warmup_cooldown_rate: warmup_cooldown_rate(epoch, new_rate_activation_epoch),
         }
     }
 }
@@ -1602,6 +1659,9 @@ pub struct CliStakeHistoryEntry {
     pub effective_stake: u64,
     pub activating_stake: u64,
     pub deactivating_stake: u64,
+    /// Largest fraction of the effective stake of the previous epoch that may activate or
+    /// deactivate in this epoch
+    pub warmup_cooldown_rate: f64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -1800,6 +1860,217 @@ pub struct CliEpochVotingHistory {
     pub max_credits_per_slot: u8,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliVoteCreditsAudit {
+    pub vote_account: String,
+    pub epoch: Epoch,
+    pub first_slot: Slot,
+    pub last_slot: Slot,
+    pub timely_vote_credits: bool,
+    pub vote_transactions: usize,
+    /// Slots of the epoch voted on by the vote transactions
+    pub voted_slots: usize,
+    /// Voted slots that were rooted by the cluster
+    pub rooted_voted_slots: u64,
+    /// Credits re-derived from the landing latency of the votes on the rooted slots
+    pub expected_credits: u64,
+    /// Credits earned in the epoch according to the vote account
+    pub on_chain_credits: u64,
+    pub discrepancies: Vec<String>,
+}
+
+impl QuietDisplay for CliVoteCreditsAudit {}
+impl VerboseDisplay for CliVoteCreditsAudit {}
+
+impl fmt::Display for CliVoteCreditsAudit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln_name_value(f, "Vote Account:", &self.vote_account)?;
+        writeln_name_value(f, "Epoch:", &self.epoch.to_string())?;
+        writeln_name_value(
+            f,
+            "Audited Slots:",
+            &format!("{}..={}", self.first_slot, self.last_slot),
+        )?;
+        writeln_name_value(
+            f,
+            "Timely Vote Credits:",
+            if self.timely_vote_credits {
+                "active"
+            } else {
+                "inactive"
+            },
+        )?;
+        writeln_name_value(f, "Vote Transactions:", &self.vote_transactions.to_string())?;
+        writeln_name_value(
+            f,
+            "Voted Slots:",
+            &format!("{} ({} rooted)", self.voted_slots, self.rooted_voted_slots),
+        )?;
+        writeln_name_value(f, "Expected Credits:", &self.expected_credits.to_string())?;
+        writeln_name_value(f, "On-chain Credits:", &self.on_chain_credits.to_string())?;
+        writeln!(f)?;
+        if self.discrepancies.is_empty() {
+            writeln!(f, "No discrepancies found")?;
+        } else {
+            writeln!(f, "{}", style("Discrepancies:").bold())?;
+            for discrepancy in &self.discrepancies {
+                writeln!(f, "  {discrepancy}")?;
+            }
+        }
+        writeln!(
+            f,
+            "Note: credits are earned when a voted slot is rooted, so slots voted near the epoch \
+             boundaries may be credited in the adjacent epoch"
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliValidatorStatus {
+    pub identity: String,
+    pub vote_account: String,
+    pub version: Option<String>,
+    pub delinquent: bool,
+    pub commission: u8,
+    pub activated_stake: u64,
+    pub epoch: Epoch,
+    pub current_slot: Slot,
+    pub last_vote: Slot,
+    pub root_slot: Slot,
+    /// Votes of the tower of the vote account
+    pub recent_votes: usize,
+    /// Recent votes that landed within the grace period of timely vote credits
+    pub timely_recent_votes: usize,
+    pub average_vote_latency: f64,
+    pub epoch_credits: u64,
+    /// Credits achievable over the slots of the epoch elapsed so far
+    pub max_epoch_credits_so_far: u64,
+    pub max_epoch_credits: u64,
+    pub leader_slots: usize,
+    pub skipped_leader_slots: usize,
+    pub identity_balance: u64,
+    /// Lamports spent per day by the identity over its recent transactions
+    pub balance_drain_per_day: Option<u64>,
+    #[serde(skip_serializing)]
+    pub use_lamports_unit: bool,
+}
+
+impl QuietDisplay for CliValidatorStatus {}
+impl VerboseDisplay for CliValidatorStatus {}
+
+impl fmt::Display for CliValidatorStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let percent = |value: u64, total: u64| {
+            if total > 0 {
+                format!("{:.2}%", value as f64 / total as f64 * 100.)
+            } else {
+                "-".to_string()
+            }
+        };
+        writeln_name_value(f, "Identity:", &self.identity)?;
+        writeln_name_value(f, "Vote Account:", &self.vote_account)?;
+        writeln_name_value(f, "Version:", self.version.as_deref().unwrap_or("unknown"))?;
+        writeln_name_value(
+            f,
+            "Status:",
+            &if self.delinquent {
+                style("delinquent").red().to_string()
+            } else {
+                "voting".to_string()
+            },
+        )?;
+        writeln_name_value(f, "Commission:", &format!("{}%", self.commission))?;
+        writeln_name_value(
+            f,
+            "Active Stake:",
+            &build_balance_message(self.activated_stake, self.use_lamports_unit, true),
+        )?;
+
+        writeln!(f, "\n{}", style("Voting:").bold())?;
+        writeln_name_value(
+            f,
+            "Last Vote:",
+            &format!(
+                "{} ({} slots behind)",
+                self.last_vote,
+                self.current_slot.saturating_sub(self.last_vote)
+            ),
+        )?;
+        writeln_name_value(
+            f,
+            "Root Slot:",
+            &format!(
+                "{} ({} slots behind)",
+                self.root_slot,
+                self.current_slot.saturating_sub(self.root_slot)
+            ),
+        )?;
+        writeln_name_value(
+            f,
+            "Vote Success Rate:",
+            &format!(
+                "{} of the last {} votes landed in time, {:.2} slots average latency",
+                percent(self.timely_recent_votes as u64, self.recent_votes as u64),
+                self.recent_votes,
+                self.average_vote_latency,
+            ),
+        )?;
+        writeln_name_value(
+            f,
+            &format!("Epoch {} Credits:", self.epoch),
+            &format!(
+                "{} ({} of the {} achievable so far, {} for the epoch)",
+                self.epoch_credits,
+                percent(self.epoch_credits, self.max_epoch_credits_so_far),
+                self.max_epoch_credits_so_far,
+                self.max_epoch_credits,
+            ),
+        )?;
+
+        writeln!(f, "\n{}", style("Block Production:").bold())?;
+        writeln_name_value(
+            f,
+            "Skipped Leader Slots:",
+            &format!(
+                "{} of {} this epoch ({})",
+                self.skipped_leader_slots,
+                self.leader_slots,
+                percent(self.skipped_leader_slots as u64, self.leader_slots as u64),
+            ),
+        )?;
+
+        writeln!(f, "\n{}", style("Identity Balance:").bold())?;
+        writeln_name_value(
+            f,
+            "Balance:",
+            &build_balance_message(self.identity_balance, self.use_lamports_unit, true),
+        )?;
+        match self.balance_drain_per_day {
+            Some(drain) => {
+                writeln_name_value(
+                    f,
+                    "Drain Rate:",
+                    &format!(
+                        "{} per day",
+                        build_balance_message(drain, self.use_lamports_unit, true)
+                    ),
+                )?;
+                if drain > 0 {
+                    writeln_name_value(
+                        f,
+                        "Runway:",
+                        &format!("{:.1} days", self.identity_balance as f64 / drain as f64),
+                    )?;
+                }
+            }
+            None => writeln_name_value(f, "Drain Rate:", "unknown")?,
+        }
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliLandedVote {
@@ -1941,6 +2212,107 @@ impl fmt::Display for CliInflation {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliStakeRewardsEstimate {
+    pub vote_account: String,
+    pub amount: u64,
+    pub epoch: Epoch,
+    pub validator_inflation_rate: f64,
+    pub total_supply: u64,
+    pub total_active_stake: u64,
+    pub commission: u8,
+    /// Number of completed epochs the vote credits are averaged over
+    pub epochs_sampled: usize,
+    pub validator_credits_per_epoch: f64,
+    /// Average vote credits per epoch of the cluster, weighted by stake
+    pub cluster_credits_per_epoch: f64,
+    pub max_credits_per_epoch: u64,
+    pub slots_in_epoch: u64,
+    pub average_slot_time_ms: u64,
+    pub epochs_per_year: f64,
+    pub apr: f64,
+    pub apy: f64,
+    pub rewards_per_epoch: u64,
+    pub rewards_per_year: u64,
+    #[serde(skip_serializing)]
+    pub use_lamports_unit: bool,
+}
+
+impl QuietDisplay for CliStakeRewardsEstimate {}
+impl VerboseDisplay for CliStakeRewardsEstimate {}
+
+impl fmt::Display for CliStakeRewardsEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let balance = |lamports| build_balance_message(lamports, self.use_lamports_unit, true);
+        let percent_of = |value: f64, total: f64| {
+            if total > 0. {
+                value / total * 100.
+            } else {
+                0.
+            }
+        };
+        writeln!(
+            f,
+            "{}",
+            style(format!("Stake Rewards Estimate for {}:", self.vote_account)).bold()
+        )?;
+        writeln!(f, "Amount:            {}", balance(self.amount))?;
+        writeln!(f, "Projected APR:     {:>5.2}%", self.apr * 100.)?;
+        writeln!(f, "Projected APY:     {:>5.2}%", self.apy * 100.)?;
+        writeln!(f, "Rewards per epoch: {}", balance(self.rewards_per_epoch))?;
+        writeln!(f, "Rewards per year:  {}", balance(self.rewards_per_year))?;
+
+        writeln!(f, "\n{}", style("Assumptions:").bold())?;
+        writeln!(
+            f,
+            "* The staking inflation rate of epoch {} stays at {:.2}% of the total supply of {}",
+            self.epoch,
+            self.validator_inflation_rate * 100.,
+            balance(self.total_supply),
+        )?;
+        writeln!(
+            f,
+            "* The active stake of the cluster stays at {} ({:.2}% of the supply)",
+            balance(self.total_active_stake),
+            percent_of(self.total_active_stake as f64, self.total_supply as f64),
+        )?;
+        writeln!(
+            f,
+            "* The validator keeps a commission of {}%",
+            self.commission
+        )?;
+        writeln!(
+            f,
+            "* The validator keeps earning {:.0} vote credits per epoch, its average over the last \
+             {} epochs: {:.2}% of the stake-weighted cluster average of {:.0} and {:.2}% of the \
+             maximum of {}",
+            self.validator_credits_per_epoch,
+            self.epochs_sampled,
+            percent_of(
+                self.validator_credits_per_epoch,
+                self.cluster_credits_per_epoch
+            ),
+            self.cluster_credits_per_epoch,
+            percent_of(
+                self.validator_credits_per_epoch,
+                self.max_credits_per_epoch as f64
+            ),
+            self.max_credits_per_epoch,
+        )?;
+        writeln!(
+            f,
+            "* Epochs of {} slots keep lasting {}ms per slot, {:.1} epochs per year",
+            self.slots_in_epoch, self.average_slot_time_ms, self.epochs_per_year,
+        )?;
+        writeln!(
+            f,
+            "* Rewards are compounded every epoch and the stake is active from the first one"
+        )?;
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct CliSignOnlyData {
@@ -2004,6 +2376,35 @@ impl fmt::Display for CliSignature {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTransferBatch {
+    pub recipients: usize,
+    pub lamports: u64,
+    /// Signatures of the transactions paying the recipients, in the order of the batch file
+    pub signatures: Vec<String>,
+}
+
+impl QuietDisplay for CliTransferBatch {}
+impl VerboseDisplay for CliTransferBatch {}
+
+impl fmt::Display for CliTransferBatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln_name_value(f, "Recipients:", &self.recipients.to_string())?;
+        writeln_name_value(
+            f,
+            "Total Amount:",
+            &build_balance_message(self.lamports, false, true),
+        )?;
+        writeln!(f, "{}", style("Signatures:").bold())?;
+        for signature in &self.signatures {
+            writeln!(f, "  {signature}")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliAccountBalances {
@@ -3536,4 +3937,49 @@ mod tests {
         assert_eq!(s, "Account Balance: 0.00001 SOL\nValidator Identity: 11111111111111111111111111111111\nVote Authority: None\nWithdraw Authority: \nCredits: 0\nCommission: 0%\nRoot Slot: ~\nRecent Timestamp: 1970-01-01T00:00:00Z from slot 0\nEpoch Rewards:\nEpoch,Reward Slot,Time,Amount,New Balance,Percent Change,APR,Commission\n1,100,1970-01-01 00:00:00 UTC,0.00000001,0.0000001,11%,10.00%,1%\n2,200,1970-01-12 13:46:40 UTC,0.000000012,0.0000001,11%,13.00%,1%\n");
         println!("{s}");
     }
+
+    #[test]
+    fn test_format_stake_history_graph() {
+        let new_rate_activation_epoch = Some(2);
+        let entries = vec![
+            CliStakeHistoryEntry::new(
+                2,
+                &StakeHistoryEntry {
+                    effective: 100,
+                    activating: 100,
+                    deactivating: 20,
+                },
+                new_rate_activation_epoch,
+            ),
+            CliStakeHistoryEntry::new(
+                1,
+                &StakeHistoryEntry {
+                    effective: 50,
+                    activating: 0,
+                    deactivating: 0,
+                },
+                new_rate_activation_epoch,
+            ),
+        ];
+        assert_eq!(entries[0].warmup_cooldown_rate, 0.09);
+        assert_eq!(entries[1].warmup_cooldown_rate, 0.25);
+
+        let stake_history = CliStakeHistory {
+            entries,
+            use_lamports_unit: true,
+            show_graph: true,
+        };
+        let s = format!("{stake_history}");
+        let lines: Vec<_> = s.lines().skip(2).collect();
+        assert_eq!(
+            lines[0],
+            format!(
+                "      2    9.00%  {}{}{}",
+                "█".repeat(20),
+                "▒".repeat(5),
+                "░".repeat(25)
+            )
+        );
+        assert_eq!(lines[1], format!("      1   25.00%  {}", "█".repeat(12)));
+    }
 }
@@ -817,6 +817,7 @@ mod test {
                 data: vec![1, 2, 3],
             }),
             compute_units_consumed: Some(1234u64),
+            fee_breakdown: None,
         };
 
         let output = {
@@ -896,6 +897,7 @@ Rewards:
                 data: vec![1, 2, 3],
             }),
             compute_units_consumed: Some(2345u64),
+            fee_breakdown: None,
         };
 
         let output = {
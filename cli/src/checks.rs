@@ -1,11 +1,13 @@
 use {
     crate::cli::CliError,
     solana_commitment_config::CommitmentConfig,
+    solana_hash::Hash,
     solana_message::Message,
     solana_native_token::lamports_to_sol,
     solana_pubkey::Pubkey,
     solana_rpc_client::rpc_client::RpcClient,
     solana_rpc_client_api::client_error::{Error as ClientError, Result as ClientResult},
+    solana_signer::Signer,
 };
 
 pub fn check_account_for_fee(
@@ -127,6 +129,58 @@ pub fn get_fee_for_messages(
         .sum())
 }
 
+/// Describes what a hardware wallet asks to confirm when signing `message` with
+/// `recent_blockhash`: the fee, which on X1 is derived from the compute units of the message
+/// and is only known when `rpc_client` is given, and the message hash the device displays when
+/// blind signing. Returns `None` if no interactive signer signs the message.
+pub fn interactive_signing_summary(
+    rpc_client: Option<&RpcClient>,
+    signers: &[&dyn Signer],
+    message: &Message,
+    recent_blockhash: &Hash,
+) -> Result<Option<String>, CliError> {
+    let signer_keys = message.signer_keys();
+    let interactive_signers: Vec<_> = signers
+        .iter()
+        .filter(|signer| signer.is_interactive() && signer_keys.contains(&&signer.pubkey()))
+        .map(|signer| signer.pubkey().to_string())
+        .collect();
+    if interactive_signers.is_empty() {
+        return Ok(None);
+    }
+    let mut message = message.clone();
+    message.recent_blockhash = *recent_blockhash;
+    let mut summary = format!(
+        "Confirm the transaction on the hardware wallet of {}\n",
+        interactive_signers.join(", ")
+    );
+    if let Some(rpc_client) = rpc_client {
+        let fee = get_fee_for_messages(rpc_client, &[&message])?;
+        summary.push_str(&format!("  Fee: {} SOL\n", lamports_to_sol(fee)));
+    }
+    summary.push_str(&format!(
+        "  Message hash: {}\n",
+        solana_sha256_hasher::hash(&message.serialize())
+    ));
+    Ok(Some(summary))
+}
+
+/// Prints the [`interactive_signing_summary`], so that a fee and a message hash shown by a
+/// hardware wallet can be checked against it before approving.
+pub fn notify_interactive_signers(
+    rpc_client: Option<&RpcClient>,
+    signers: &[&dyn Signer],
+    message: &Message,
+    recent_blockhash: &Hash,
+) -> Result<(), CliError> {
+    if let Some(summary) =
+        interactive_signing_summary(rpc_client, signers, message, recent_blockhash)?
+    {
+        eprint!("{summary}");
+    }
+    Ok(())
+}
+
 pub fn check_account_for_balance(
     rpc_client: &RpcClient,
     account_pubkey: &Pubkey,
@@ -174,10 +228,13 @@ mod tests {
     use {
         super::*,
         serde_json::json,
+        solana_keypair::Keypair,
         solana_rpc_client_api::{
             request::RpcRequest,
             response::{Response, RpcResponseContext},
         },
+        solana_signature::Signature,
+        solana_signer::SignerError,
         solana_system_interface::instruction as system_instruction,
         std::collections::HashMap,
     };
@@ -323,6 +380,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_interactive_signing_summary() {
+        struct InteractiveSigner(Keypair);
+
+        impl Signer for InteractiveSigner {
+            fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+                self.0.try_pubkey()
+            }
+
+            fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+                self.0.try_sign_message(message)
+            }
+
+            fn is_interactive(&self) -> bool {
+                true
+            }
+        }
+
+        let check_fee_response = json!(Response {
+            context: RpcResponseContext {
+                slot: 1,
+                api_version: None
+            },
+            value: json!(15_000),
+        });
+        let mut mocks = HashMap::new();
+        mocks.insert(RpcRequest::GetFeeForMessage, check_fee_response);
+        let rpc_client = RpcClient::new_mock_with_mocks("".to_string(), mocks);
+
+        let payer = Keypair::new();
+        let hardware_wallet = InteractiveSigner(Keypair::new());
+        let ix = system_instruction::transfer(&hardware_wallet.pubkey(), &Pubkey::new_unique(), 1);
+        let message = Message::new(&[ix], Some(&payer.pubkey()));
+        let recent_blockhash = Hash::new_unique();
+
+        assert_eq!(
+            interactive_signing_summary(Some(&rpc_client), &[&payer], &message, &recent_blockhash)
+                .unwrap(),
+            None
+        );
+
+        let mut signed_message = message.clone();
+        signed_message.recent_blockhash = recent_blockhash;
+        let message_hash = solana_sha256_hasher::hash(&signed_message.serialize());
+        assert_eq!(
+            interactive_signing_summary(
+                Some(&rpc_client),
+                &[&payer, &hardware_wallet],
+                &message,
+                &recent_blockhash,
+            )
+            .unwrap()
+            .unwrap(),
+            format!(
+                "Confirm the transaction on the hardware wallet of {}\n  Fee: 0.000015 SOL\n  \
+                 Message hash: {message_hash}\n",
+                hardware_wallet.pubkey()
+            )
+        );
+        assert_eq!(
+            interactive_signing_summary(None, &[&hardware_wallet], &message, &recent_blockhash)
+                .unwrap()
+                .unwrap(),
+            format!(
+                "Confirm the transaction on the hardware wallet of {}\n  Message hash: \
+                 {message_hash}\n",
+                hardware_wallet.pubkey()
+            )
+        );
+    }
+
     #[test]
     fn test_check_unique_pubkeys() {
         let pubkey0 = solana_pubkey::new_rand();
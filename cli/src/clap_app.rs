@@ -1,8 +1,8 @@
 use {
     crate::{
         address_lookup_table::AddressLookupTableSubCommands, cli::*, cluster_query::*, feature::*,
-        inflation::*, nonce::*, program::*, program_v4::ProgramV4SubCommands, stake::*,
-        validator_info::*, vote::*, wallet::*,
+        governance::*, inflation::*, nonce::*, program::*, program_v4::ProgramV4SubCommands,
+        stake::*, validator_info::*, vote::*, wallet::*,
     },
     clap::{App, AppSettings, Arg, ArgGroup, SubCommand},
     solana_clap_utils::{self, hidden_unless_forced, input_validators::*, keypair::*},
@@ -45,7 +45,8 @@ pub fn get_clap_app<'ab, 'v>(name: &str, about: &'ab str, version: &'v str) -> A
                 .validator(is_url_or_moniker)
                 .help(
                     "URL for Solana's JSON RPC or moniker (or their first letter): \
-                    [mainnet-beta, testnet, devnet, localhost]",
+                    [mainnet-beta, testnet, devnet, localhost], or X1 cluster alias: \
+                    [x1-mainnet, x1-testnet]",
                 ),
         )
         .arg(
@@ -157,6 +158,7 @@ pub fn get_clap_app<'ab, 'v>(name: &str, about: &'ab str, version: &'v str) -> A
         )
         .cluster_query_subcommands()
         .feature_subcommands()
+        .governance_subcommands()
         .inflation_subcommands()
         .nonce_subcommands()
         .program_subcommands()
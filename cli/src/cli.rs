@@ -1,7 +1,8 @@
 use {
     crate::{
-        address_lookup_table::*, clap_app::*, cluster_query::*, feature::*, inflation::*, nonce::*,
-        program::*, program_v4::*, spend_utils::*, stake::*, validator_info::*, vote::*, wallet::*,
+        address_lookup_table::*, clap_app::*, cluster_query::*, feature::*, governance::*,
+        inflation::*, nonce::*, program::*, program_v4::*, spend_utils::*, stake::*,
+        validator_info::*, vote::*, wallet::*,
     },
     clap::{crate_description, crate_name, value_t_or_exit, ArgMatches, Shell},
     log::*,
@@ -64,6 +65,7 @@ pub enum CliCommand {
     ClusterDate,
     ClusterVersion,
     Feature(FeatureCliCommand),
+    Governance(GovernanceCliCommand),
     Inflation(InflationCliCommand),
     FindProgramDerivedAddress {
         seeds: Vec<Vec<u8>>,
@@ -263,6 +265,7 @@ pub enum CliCommand {
     ShowStakeHistory {
         use_lamports_unit: bool,
         limit_results: usize,
+        show_graph: bool,
     },
     ShowStakeAccount {
         pubkey: Pubkey,
@@ -347,6 +350,14 @@ pub enum CliCommand {
         with_rewards: Option<usize>,
         starting_epoch: Option<u64>,
     },
+    AuditVoteAccountCredits {
+        pubkey: Pubkey,
+        epoch: Option<Epoch>,
+    },
+    ShowValidatorStatus {
+        identity_pubkey: Pubkey,
+        use_lamports_unit: bool,
+    },
     WithdrawFromVoteAccount {
         vote_account_pubkey: Pubkey,
         destination_account_pubkey: Pubkey,
@@ -450,9 +461,21 @@ pub enum CliCommand {
         derived_address_program_id: Option<Pubkey>,
         compute_unit_price: Option<u64>,
     },
+    TransferBatch {
+        batch_file: String,
+        from: SignerIndex,
+        fee_payer: SignerIndex,
+        no_wait: bool,
+        compute_unit_price: Option<u64>,
+    },
     StakeMinimumDelegation {
         use_lamports_unit: bool,
     },
+    StakeRewardsEstimate {
+        amount: u64,
+        vote_account_pubkey: Pubkey,
+        use_lamports_unit: bool,
+    },
     // Address lookup table commands
     AddressLookupTable(AddressLookupTableCliCommand),
     SignOffchainMessage {
@@ -645,6 +668,9 @@ pub fn parse_command(
             Ok(CliCommandInfo::without_signers(CliCommand::GetGenesisHash))
         }
         ("gossip", Some(_matches)) => Ok(CliCommandInfo::without_signers(CliCommand::ShowGossip)),
+        ("governance", Some(matches)) => {
+            parse_governance_subcommand(matches, default_signer, wallet_manager)
+        }
         ("inflation", Some(matches)) => {
             parse_inflation_subcommand(matches, default_signer, wallet_manager)
         }
@@ -751,6 +777,9 @@ pub fn parse_command(
         ("stake-account", Some(matches)) => parse_show_stake_account(matches, wallet_manager),
         ("stake-history", Some(matches)) => parse_show_stake_history(matches),
         ("stake-minimum-delegation", Some(matches)) => parse_stake_minimum_delegation(matches),
+        ("stake-rewards-estimate", Some(matches)) => {
+            parse_stake_rewards_estimate(matches, wallet_manager)
+        }
         // Validator Info Commands
         ("validator-info", Some(matches)) => match matches.subcommand() {
             ("publish", Some(matches)) => {
@@ -798,6 +827,7 @@ pub fn parse_command(
             CHECKED,
         ),
         ("vote-account", Some(matches)) => parse_vote_get_account_command(matches, wallet_manager),
+        ("validator-status", Some(matches)) => parse_validator_status(matches, wallet_manager),
         ("withdraw-from-vote-account", Some(matches)) => {
             parse_withdraw_from_vote_account(matches, default_signer, wallet_manager)
         }
@@ -927,6 +957,9 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             process_largest_accounts(&rpc_client, config, filter.clone())
         }
         CliCommand::GetTransactionCount => process_get_transaction_count(&rpc_client, config),
+        CliCommand::Governance(governance_subcommand) => {
+            process_governance_subcommand(&rpc_client, config, governance_subcommand)
+        }
         CliCommand::Inflation(inflation_subcommand) => {
             process_inflation_subcommand(&rpc_client, config, inflation_subcommand)
         }
@@ -1340,7 +1373,14 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
         CliCommand::ShowStakeHistory {
             use_lamports_unit,
             limit_results,
-        } => process_show_stake_history(&rpc_client, config, *use_lamports_unit, *limit_results),
+            show_graph,
+        } => process_show_stake_history(
+            &rpc_client,
+            config,
+            *use_lamports_unit,
+            *limit_results,
+            *show_graph,
+        ),
         CliCommand::StakeAuthorize {
             stake_account_pubkey,
             ref new_authorizations,
@@ -1435,6 +1475,17 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
         CliCommand::StakeMinimumDelegation { use_lamports_unit } => {
             process_stake_minimum_delegation(&rpc_client, config, *use_lamports_unit)
         }
+        CliCommand::StakeRewardsEstimate {
+            amount,
+            vote_account_pubkey,
+            use_lamports_unit,
+        } => process_stake_rewards_estimate(
+            &rpc_client,
+            config,
+            *amount,
+            vote_account_pubkey,
+            *use_lamports_unit,
+        ),
 
         // Validator Info Commands
 
@@ -1508,6 +1559,16 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             *with_rewards,
             *starting_epoch,
         ),
+        CliCommand::AuditVoteAccountCredits {
+            pubkey: vote_account_pubkey,
+            epoch,
+        } => process_audit_vote_account_credits(&rpc_client, config, vote_account_pubkey, *epoch),
+        CliCommand::ShowValidatorStatus {
+            identity_pubkey,
+            use_lamports_unit,
+        } => {
+            process_show_validator_status(&rpc_client, config, identity_pubkey, *use_lamports_unit)
+        }
         CliCommand::WithdrawFromVoteAccount {
             vote_account_pubkey,
             withdraw_authority,
@@ -1703,6 +1764,21 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             derived_address_program_id.as_ref(),
             *compute_unit_price,
         ),
+        CliCommand::TransferBatch {
+            batch_file,
+            from,
+            fee_payer,
+            no_wait,
+            compute_unit_price,
+        } => process_transfer_batch(
+            &rpc_client,
+            config,
+            batch_file,
+            *from,
+            *fee_payer,
+            *no_wait,
+            *compute_unit_price,
+        ),
         // Address Lookup Table Commands
         CliCommand::AddressLookupTable(subcommand) => {
             process_address_lookup_table_subcommand(rpc_client, config, subcommand)
@@ -2720,6 +2796,48 @@ mod tests {
                 signers: vec![Box::new(read_keypair_file(&default_keypair_file).unwrap()),],
             }
         );
+
+        // Test Transfer Subcommand, batch file
+        let test_transfer = test_commands.clone().get_matches_from(vec![
+            "test",
+            "transfer",
+            "--batch-file",
+            "payouts.csv",
+            "--no-wait",
+        ]);
+        assert_eq!(
+            parse_command(&test_transfer, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::TransferBatch {
+                    batch_file: "payouts.csv".to_string(),
+                    from: 0,
+                    fee_payer: 0,
+                    no_wait: true,
+                    compute_unit_price: None,
+                },
+                signers: vec![Box::new(read_keypair_file(&default_keypair_file).unwrap())],
+            }
+        );
+        assert!(test_commands
+            .clone()
+            .get_matches_from_safe(vec![
+                "test",
+                "transfer",
+                &to_string,
+                "42",
+                "--batch-file",
+                "payouts.csv",
+            ])
+            .is_err());
+
+        let batch = format!("# recipient,amount\n{to_string},1.5\n\n {from_string} , 0.25\n");
+        assert_eq!(
+            parse_transfer_batch_file(&batch).unwrap(),
+            vec![(to_pubkey, 1_500_000_000), (from_pubkey, 250_000_000)]
+        );
+        assert!(parse_transfer_batch_file(&to_string).is_err());
+        assert!(parse_transfer_batch_file(&format!("{to_string},-1")).is_err());
+        assert!(parse_transfer_batch_file("not-a-pubkey,1").is_err());
     }
 
     #[test]
@@ -1681,6 +1681,7 @@ pub fn process_logs(config: &CliConfig, filter: &RpcTransactionLogsFilter) -> Pr
         filter.clone(),
         RpcTransactionLogsConfig {
             commitment: Some(config.commitment),
+            ..RpcTransactionLogsConfig::default()
         },
     )?;
 
@@ -0,0 +1,255 @@
+use {
+    crate::cli::{CliCommand, CliCommandInfo, CliConfig, CliError, ProcessResult},
+    clap::{App, AppSettings, ArgMatches, SubCommand},
+    console::style,
+    serde::{Deserialize, Serialize},
+    solana_clap_utils::keypair::*,
+    solana_cli_output::{QuietDisplay, VerboseDisplay},
+    solana_clock::Epoch,
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_rpc_client::rpc_client::RpcClient,
+    std::{fmt, rc::Rc},
+    tachyon_governance::{active_parameters, pending_bundle, ParameterBundle},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GovernanceCliCommand {
+    ShowPending,
+}
+
+pub trait GovernanceSubCommands {
+    fn governance_subcommands(self) -> Self;
+}
+
+impl GovernanceSubCommands for App<'_, '_> {
+    fn governance_subcommands(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("governance")
+                .about("Governance staged cluster parameter commands")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(SubCommand::with_name("show-pending").about(
+                    "Show the staged parameter bundle and the parameters currently in effect",
+                )),
+        )
+    }
+}
+
+pub fn parse_governance_subcommand(
+    matches: &ArgMatches<'_>,
+    _default_signer: &DefaultSigner,
+    _wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<CliCommandInfo, CliError> {
+    let command = match matches.subcommand() {
+        ("show-pending", Some(_matches)) => GovernanceCliCommand::ShowPending,
+        _ => unreachable!(),
+    };
+    Ok(CliCommandInfo::without_signers(CliCommand::Governance(
+        command,
+    )))
+}
+
+pub fn process_governance_subcommand(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    governance_subcommand: &GovernanceCliCommand,
+) -> ProcessResult {
+    match governance_subcommand {
+        GovernanceCliCommand::ShowPending => process_show_pending(rpc_client, config),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliParameterBundle {
+    pub activation_epoch: Epoch,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_multiplier: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_cost_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_cost_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vote_cost_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inflation_taper: Option<f64>,
+}
+
+impl From<&ParameterBundle> for CliParameterBundle {
+    fn from(bundle: &ParameterBundle) -> Self {
+        Self {
+            activation_epoch: bundle.activation_epoch,
+            fee_multiplier: bundle.fee_multiplier,
+            account_cost_limit: bundle
+                .block_cost_limits
+                .map(|limits| limits.account_cost_limit),
+            block_cost_limit: bundle
+                .block_cost_limits
+                .map(|limits| limits.block_cost_limit),
+            vote_cost_limit: bundle
+                .block_cost_limits
+                .map(|limits| limits.vote_cost_limit),
+            inflation_taper: bundle.inflation_taper,
+        }
+    }
+}
+
+impl fmt::Display for CliParameterBundle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(fee_multiplier) = self.fee_multiplier {
+            writeln!(
+                f,
+                "  Base fee multiplier:  {fee_multiplier} lamports per CU"
+            )?;
+        }
+        if let Some(block_cost_limit) = self.block_cost_limit {
+            writeln!(f, "  Block cost limit:     {block_cost_limit} CUs")?;
+        }
+        if let Some(account_cost_limit) = self.account_cost_limit {
+            writeln!(f, "  Account cost limit:   {account_cost_limit} CUs")?;
+        }
+        if let Some(vote_cost_limit) = self.vote_cost_limit {
+            writeln!(f, "  Vote cost limit:      {vote_cost_limit} CUs")?;
+        }
+        if let Some(inflation_taper) = self.inflation_taper {
+            writeln!(f, "  Inflation taper:      {:.2}%", inflation_taper * 100.)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CliPendingBundleStatus {
+    /// No bundle is staged
+    None,
+    /// The staged bundle was rejected by validation and will not take effect
+    Invalid(String),
+    /// The staged bundle takes effect at the start of its activation epoch
+    Scheduled,
+    /// The staged bundle took effect at its activation epoch
+    Activated,
+    /// The activation epoch of the staged bundle passed without it taking effect
+    Expired,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliGovernancePending {
+    pub current_epoch: Epoch,
+    pub status: CliPendingBundleStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending: Option<CliParameterBundle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<CliParameterBundle>,
+}
+
+impl QuietDisplay for CliGovernancePending {}
+impl VerboseDisplay for CliGovernancePending {}
+
+impl fmt::Display for CliGovernancePending {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Current epoch: {}", self.current_epoch)?;
+        writeln!(f)?;
+        write!(f, "{} ", style("Staged parameter bundle:").bold())?;
+        match (&self.status, &self.pending) {
+            (CliPendingBundleStatus::Invalid(err), _) => writeln!(f, "invalid, {err}")?,
+            (CliPendingBundleStatus::Scheduled, Some(pending)) => writeln!(
+                f,
+                "takes effect at epoch {} (in {} epochs)",
+                pending.activation_epoch,
+                pending.activation_epoch.saturating_sub(self.current_epoch),
+            )?,
+            (CliPendingBundleStatus::Activated, Some(pending)) => {
+                writeln!(f, "took effect at epoch {}", pending.activation_epoch)?
+            }
+            (CliPendingBundleStatus::Expired, Some(pending)) => writeln!(
+                f,
+                "expired, did not take effect at epoch {}",
+                pending.activation_epoch
+            )?,
+            _ => writeln!(f, "none")?,
+        }
+        if let Some(pending) = &self.pending {
+            write!(f, "{pending}")?;
+        }
+        writeln!(f)?;
+        write!(f, "{} ", style("Governance parameters in effect:").bold())?;
+        match &self.active {
+            Some(active) => {
+                writeln!(f, "since epoch {}", active.activation_epoch)?;
+                write!(f, "{active}")?;
+            }
+            None => writeln!(f, "none, cluster defaults apply")?,
+        }
+        Ok(())
+    }
+}
+
+fn process_show_pending(rpc_client: &RpcClient, config: &CliConfig) -> ProcessResult {
+    let current_epoch = rpc_client
+        .get_epoch_info_with_commitment(config.commitment)?
+        .epoch;
+    let active = rpc_client
+        .get_account_with_commitment(&active_parameters::id(), config.commitment)?
+        .value
+        .map(|account| {
+            bincode::deserialize::<ParameterBundle>(&account.data)
+                .map_err(|err| format!("Invalid active parameters account: {err}"))
+        })
+        .transpose()?;
+    let pending = rpc_client
+        .get_account_with_commitment(&pending_bundle::id(), config.commitment)?
+        .value
+        .map(|account| ParameterBundle::from_account(&account));
+
+    let (status, pending) = match pending {
+        None => (CliPendingBundleStatus::None, None),
+        Some(Err(err)) => (CliPendingBundleStatus::Invalid(err.to_string()), None),
+        Some(Ok(bundle)) => {
+            let status = if bundle.activation_epoch > current_epoch {
+                CliPendingBundleStatus::Scheduled
+            } else if active
+                .as_ref()
+                .is_some_and(|active| active.activation_epoch == bundle.activation_epoch)
+            {
+                CliPendingBundleStatus::Activated
+            } else {
+                CliPendingBundleStatus::Expired
+            };
+            (status, Some(CliParameterBundle::from(&bundle)))
+        }
+    };
+
+    Ok(config
+        .output_format
+        .formatted_string(&CliGovernancePending {
+            current_epoch,
+            status,
+            pending,
+            active: active.as_ref().map(CliParameterBundle::from),
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{clap_app::get_clap_app, cli::parse_command},
+    };
+
+    #[test]
+    fn test_parse_governance_subcommand() {
+        let test_commands = get_clap_app("test", "desc", "version");
+        let default_signer = DefaultSigner::new("", "");
+        let matches =
+            test_commands
+                .clone()
+                .get_matches_from(vec!["test", "governance", "show-pending"]);
+        assert_eq!(
+            parse_command(&matches, &default_signer, &mut None).unwrap(),
+            CliCommandInfo::without_signers(CliCommand::Governance(
+                GovernanceCliCommand::ShowPending
+            ))
+        );
+    }
+}
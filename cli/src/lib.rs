@@ -29,6 +29,7 @@ pub mod cli;
 pub mod cluster_query;
 pub mod compute_budget;
 pub mod feature;
+pub mod governance;
 pub mod inflation;
 pub mod memo;
 pub mod nonce;
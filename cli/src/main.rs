@@ -2,7 +2,7 @@ use {
     clap::{crate_description, crate_name, value_t_or_exit, ArgMatches},
     console::style,
     solana_clap_utils::{
-        input_validators::normalize_to_url_if_moniker,
+        input_validators::{normalize_to_url_if_moniker, x1_cluster_alias_url},
         keypair::{CliSigners, DefaultSigner},
         DisplayError,
     },
@@ -16,6 +16,7 @@ use {
         OutputFormat,
     },
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_rpc_client::rpc_client::RpcClient,
     solana_rpc_client_api::config::RpcSendTransactionConfig,
     solana_tpu_client::tpu_client::DEFAULT_TPU_ENABLE_UDP,
     std::{collections::HashMap, error, path::PathBuf, rc::Rc, time::Duration},
@@ -80,7 +81,15 @@ fn parse_settings(matches: &ArgMatches<'_>) -> Result<bool, Box<dyn error::Error
                 }
                 ("set", Some(subcommand_matches)) => {
                     if let Some(url) = subcommand_matches.value_of("json_rpc_url") {
-                        config.json_rpc_url = normalize_to_url_if_moniker(url);
+                        // Cluster aliases are kept as is, so that the genesis hash pinned for
+                        // them is checked by later commands
+                        config.json_rpc_url = match x1_cluster_alias_url(url) {
+                            Some(alias_url) => {
+                                check_pinned_genesis_hash(&mut config, url, alias_url);
+                                url.to_string()
+                            }
+                            None => normalize_to_url_if_moniker(url),
+                        };
                         // Revert to a computed `websocket_url` value when `json_rpc_url` is
                         // changed
                         config.websocket_url = "".to_string();
@@ -141,15 +150,54 @@ fn parse_settings(matches: &ArgMatches<'_>) -> Result<bool, Box<dyn error::Error
     Ok(parse_args)
 }
 
+/// Checks that the node behind a cluster alias reports the genesis hash pinned for the alias,
+/// warning otherwise to prevent sending to another network by accident. The genesis hash is
+/// pinned if the alias has none yet.
+fn check_pinned_genesis_hash(config: &mut Config, alias: &str, json_rpc_url: &str) {
+    let genesis_hash = match RpcClient::new(json_rpc_url.to_string()).get_genesis_hash() {
+        Ok(genesis_hash) => genesis_hash.to_string(),
+        Err(err) => {
+            eprintln!(
+                "{} unable to fetch the genesis hash of {alias}: {err}",
+                style("Warning:").bold().yellow()
+            );
+            return;
+        }
+    };
+    match config.pinned_genesis_hashes.get(alias) {
+        Some(pinned_genesis_hash) if *pinned_genesis_hash == genesis_hash => (),
+        Some(pinned_genesis_hash) => eprintln!(
+            "{} {json_rpc_url} reports genesis hash {genesis_hash}, but {alias} is pinned to \
+             {pinned_genesis_hash}. It may not be the {alias} cluster.",
+            style("Warning:").bold().yellow()
+        ),
+        None => {
+            println!("Pinned genesis hash of {alias}: {genesis_hash}");
+            config
+                .pinned_genesis_hashes
+                .insert(alias.to_string(), genesis_hash);
+        }
+    }
+}
+
 pub fn parse_args<'a>(
     matches: &ArgMatches<'_>,
     wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
 ) -> Result<(CliConfig<'a>, CliSigners), Box<dyn error::Error>> {
-    let config = if let Some(config_file) = matches.value_of("config_file") {
+    let mut config = if let Some(config_file) = matches.value_of("config_file") {
         Config::load(config_file).unwrap_or_default()
     } else {
         Config::default()
     };
+    let url_or_moniker = matches
+        .value_of("json_rpc_url")
+        .unwrap_or(&config.json_rpc_url)
+        .to_string();
+    if let Some(alias_url) = x1_cluster_alias_url(&url_or_moniker) {
+        if config.pinned_genesis_hashes.contains_key(&url_or_moniker) {
+            check_pinned_genesis_hash(&mut config, &url_or_moniker, alias_url);
+        }
+    }
     let (_, json_rpc_url) = ConfigInput::compute_json_rpc_url_setting(
         matches.value_of("json_rpc_url").unwrap_or(""),
         &config.json_rpc_url,
@@ -1,6 +1,8 @@
 use {
     crate::{
-        checks::{check_account_for_fee_with_commitment, check_unique_pubkeys},
+        checks::{
+            check_account_for_fee_with_commitment, check_unique_pubkeys, notify_interactive_signers,
+        },
         cli::{
             log_instruction_custom_error, CliCommand, CliCommandInfo, CliConfig, CliError,
             ProcessResult,
@@ -29,10 +31,10 @@ use {
     },
     solana_cli_output::{
         self, display::BuildBalanceMessageConfig, return_signers_with_config, CliBalance,
-        CliEpochReward, CliStakeHistory, CliStakeHistoryEntry, CliStakeState, CliStakeType,
-        OutputFormat, ReturnSignersConfig,
+        CliEpochReward, CliStakeHistory, CliStakeHistoryEntry, CliStakeRewardsEstimate,
+        CliStakeState, CliStakeType, OutputFormat, ReturnSignersConfig,
     },
-    solana_clock::{Clock, Epoch, UnixTimestamp, SECONDS_PER_DAY},
+    solana_clock::{Clock, Epoch, UnixTimestamp, DEFAULT_MS_PER_SLOT, SECONDS_PER_DAY},
     solana_commitment_config::CommitmentConfig,
     solana_epoch_schedule::EpochSchedule,
     solana_message::Message,
@@ -59,6 +61,7 @@ use {
     solana_system_interface::{error::SystemError, instruction as system_instruction},
     solana_sysvar::stake_history::StakeHistory,
     solana_transaction::Transaction,
+    solana_vote_program::vote_state::VOTE_CREDITS_MAXIMUM_PER_SLOT,
     std::{ops::Deref, rc::Rc},
 };
 
@@ -781,6 +784,15 @@ impl StakeSubCommands for App<'_, '_> {
                             "Display NUM recent epochs worth of stake history in text mode. 0 for \
                              all",
                         ),
+                )
+                .arg(
+                    Arg::with_name("graph")
+                        .long("graph")
+                        .takes_value(false)
+                        .help(
+                            "Display the stake history as a graph of the effective, activating \
+                             and deactivating stake of each epoch",
+                        ),
                 ),
         )
         .subcommand(
@@ -793,6 +805,35 @@ impl StakeSubCommands for App<'_, '_> {
                         .help("Display minimum delegation in lamports instead of SOL"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("stake-rewards-estimate")
+                .about(
+                    "Estimate the rewards of a stake delegated to a validator from the current \
+                     inflation, its commission and its recent vote credits",
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .value_name("AMOUNT")
+                        .takes_value(true)
+                        .validator(is_amount)
+                        .required(true)
+                        .help("The amount of the stake, in SOL"),
+                )
+                .arg(pubkey!(
+                    Arg::with_name("vote_account_pubkey")
+                        .long("validator")
+                        .value_name("VOTE_ACCOUNT_ADDRESS")
+                        .required(true),
+                    "Vote account of the validator the stake is delegated to."
+                ))
+                .arg(
+                    Arg::with_name("lamports")
+                        .long("lamports")
+                        .takes_value(false)
+                        .help("Display rewards in lamports instead of SOL"),
+                ),
+        )
     }
 }
 
@@ -1349,10 +1390,12 @@ pub fn parse_show_stake_account(
 pub fn parse_show_stake_history(matches: &ArgMatches<'_>) -> Result<CliCommandInfo, CliError> {
     let use_lamports_unit = matches.is_present("lamports");
     let limit_results = value_of(matches, "limit").unwrap();
+    let show_graph = matches.is_present("graph");
     Ok(CliCommandInfo::without_signers(
         CliCommand::ShowStakeHistory {
             use_lamports_unit,
             limit_results,
+            show_graph,
         },
     ))
 }
@@ -1366,6 +1409,23 @@ pub fn parse_stake_minimum_delegation(
     ))
 }
 
+pub fn parse_stake_rewards_estimate(
+    matches: &ArgMatches<'_>,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<CliCommandInfo, CliError> {
+    let amount = lamports_of_sol(matches, "amount").unwrap();
+    let vote_account_pubkey =
+        pubkey_of_signer(matches, "vote_account_pubkey", wallet_manager)?.unwrap();
+    let use_lamports_unit = matches.is_present("lamports");
+    Ok(CliCommandInfo::without_signers(
+        CliCommand::StakeRewardsEstimate {
+            amount,
+            vote_account_pubkey,
+            use_lamports_unit,
+        },
+    ))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn process_create_stake_account(
     rpc_client: &RpcClient,
@@ -1912,6 +1972,13 @@ pub fn process_withdraw_stake(
 
     let mut tx = Transaction::new_unsigned(message);
 
+    notify_interactive_signers(
+        (!sign_only).then_some(rpc_client),
+        &config.signers,
+        &tx.message,
+        &recent_blockhash,
+    )?;
+
     if sign_only {
         tx.try_partial_sign(&config.signers, recent_blockhash)?;
         return_signers_with_config(
@@ -2686,12 +2753,17 @@ pub fn process_show_stake_history(
     config: &CliConfig,
     use_lamports_unit: bool,
     limit_results: usize,
+    show_graph: bool,
 ) -> ProcessResult {
     let stake_history_account = rpc_client.get_account(&stake_history::id())?;
     let stake_history =
         from_account::<StakeHistory, _>(&stake_history_account).ok_or_else(|| {
             CliError::RpcRequestError("Failed to deserialize stake history".to_string())
         })?;
+    let new_rate_activation_epoch = get_feature_activation_epoch(
+        rpc_client,
+        &agave_feature_set::reduce_stake_warmup_cooldown::id(),
+    )?;
 
     let limit_results = match config.output_format {
         OutputFormat::Json | OutputFormat::JsonCompact => usize::MAX,
@@ -2704,12 +2776,17 @@ pub fn process_show_stake_history(
         }
     };
     let mut entries: Vec<CliStakeHistoryEntry> = vec![];
-    for entry in stake_history.deref().iter().take(limit_results) {
-        entries.push(entry.into());
+    for (epoch, entry) in stake_history.deref().iter().take(limit_results) {
+        entries.push(CliStakeHistoryEntry::new(
+            *epoch,
+            entry,
+            new_rate_activation_epoch,
+        ));
     }
     let stake_history_output = CliStakeHistory {
         entries,
         use_lamports_unit,
+        show_graph,
     };
     Ok(config.output_format.formatted_string(&stake_history_output))
 }
@@ -2819,6 +2896,13 @@ pub fn process_delegate_stake(
     simulate_and_update_compute_unit_limit(&compute_unit_limit, rpc_client, &mut message)?;
     let mut tx = Transaction::new_unsigned(message);
 
+    notify_interactive_signers(
+        (!sign_only).then_some(rpc_client),
+        &config.signers,
+        &tx.message,
+        &recent_blockhash,
+    )?;
+
     if sign_only {
         tx.try_partial_sign(&config.signers, recent_blockhash)?;
         return_signers_with_config(
@@ -2875,6 +2959,176 @@ pub fn process_stake_minimum_delegation(
         .formatted_string(&stake_minimum_delegation_output))
 }
 
+/// Average number of vote credits earned per epoch over the completed epochs of
+/// `epoch_credits`, and the number of these epochs
+fn average_credits_per_epoch(
+    epoch_credits: &[(Epoch, u64, u64)],
+    current_epoch: Epoch,
+) -> Option<(f64, usize)> {
+    let (credits, epochs) = epoch_credits
+        .iter()
+        .filter(|(epoch, _, _)| *epoch < current_epoch)
+        .fold(
+            (0u64, 0usize),
+            |(credits, epochs), (_, epoch_credits, prev_credits)| {
+                (
+                    credits.saturating_add(epoch_credits.saturating_sub(*prev_credits)),
+                    epochs + 1,
+                )
+            },
+        );
+    (epochs > 0).then_some((credits as f64 / epochs as f64, epochs))
+}
+
+/// Annual rates of return of a stake delegated to a validator
+#[derive(Debug, PartialEq)]
+struct StakeRewardsRates {
+    /// Share of the stake earned per epoch
+    epoch_rate: f64,
+    epochs_per_year: f64,
+    apr: f64,
+    apy: f64,
+}
+
+/// Projects the rewards of a stake the way the runtime computes them: each epoch issues the
+/// staking inflation for the duration of the epoch at the target slot time, which is shared
+/// between the delegations in proportion to stake times vote credits, less the commission of
+/// the validators
+#[allow(clippy::too_many_arguments)]
+fn estimate_stake_rewards_rates(
+    validator_inflation_rate: f64,
+    total_supply: u64,
+    total_active_stake: u64,
+    commission: u8,
+    validator_credits_per_epoch: f64,
+    cluster_credits_per_epoch: f64,
+    slots_in_epoch: u64,
+    average_slot_time_ms: u64,
+) -> StakeRewardsRates {
+    let seconds_per_year = 365.25 * SECONDS_PER_DAY as f64;
+    let target_epochs_per_year =
+        seconds_per_year * 1_000. / (slots_in_epoch as f64 * DEFAULT_MS_PER_SLOT as f64);
+    let epochs_per_year =
+        seconds_per_year * 1_000. / (slots_in_epoch as f64 * average_slot_time_ms.max(1) as f64);
+    let performance = if cluster_credits_per_epoch > 0. {
+        validator_credits_per_epoch / cluster_credits_per_epoch
+    } else {
+        0.
+    };
+    let epoch_rate = validator_inflation_rate / target_epochs_per_year * total_supply as f64
+        / total_active_stake.max(1) as f64
+        * performance
+        * (1. - f64::from(commission.min(100)) / 100.);
+    StakeRewardsRates {
+        epoch_rate,
+        epochs_per_year,
+        apr: epoch_rate * epochs_per_year,
+        apy: (1. + epoch_rate).powf(epochs_per_year) - 1.,
+    }
+}
+
+pub fn process_stake_rewards_estimate(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    amount: u64,
+    vote_account_pubkey: &Pubkey,
+    use_lamports_unit: bool,
+) -> ProcessResult {
+    let epoch_info = rpc_client.get_epoch_info_with_commitment(config.commitment)?;
+    let inflation_rate = rpc_client.get_inflation_rate()?;
+    let total_supply = rpc_client
+        .supply_with_commitment(config.commitment)?
+        .value
+        .total;
+    let RpcVoteAccountStatus {
+        current,
+        delinquent,
+    } = rpc_client.get_vote_accounts_with_commitment(config.commitment)?;
+
+    let vote_accounts = || current.iter().chain(delinquent.iter());
+    let vote_account = vote_accounts()
+        .find(|vote_account| vote_account.vote_pubkey == vote_account_pubkey.to_string())
+        .ok_or_else(|| {
+            CliError::RpcRequestError(format!("Vote account not found: {vote_account_pubkey}"))
+        })?;
+    let (validator_credits_per_epoch, epochs_sampled) =
+        average_credits_per_epoch(&vote_account.epoch_credits, epoch_info.epoch).ok_or_else(
+            || {
+                CliError::RpcRequestError(format!(
+                    "Vote account {vote_account_pubkey} has not voted for a whole epoch yet"
+                ))
+            },
+        )?;
+
+    let total_active_stake = vote_accounts()
+        .map(|vote_account| vote_account.activated_stake)
+        .sum::<u64>();
+    let cluster_credits_per_epoch = vote_accounts()
+        .filter_map(|vote_account| {
+            let (credits, _) =
+                average_credits_per_epoch(&vote_account.epoch_credits, epoch_info.epoch)?;
+            Some(credits * vote_account.activated_stake as f64)
+        })
+        .sum::<f64>()
+        / total_active_stake.max(1) as f64;
+
+    let tvc_activation_slot =
+        rpc_client.get_feature_activation_slot(&agave_feature_set::timely_vote_credits::id())?;
+    let max_credits_per_slot = if tvc_activation_slot.is_some() {
+        VOTE_CREDITS_MAXIMUM_PER_SLOT
+    } else {
+        1
+    };
+    let average_slot_time_ms = rpc_client
+        .get_recent_performance_samples(Some(60))
+        .ok()
+        .and_then(|samples| {
+            let (slots, secs) = samples.iter().fold((0u64, 0u64), |(slots, secs), sample| {
+                (
+                    slots.saturating_add(sample.num_slots),
+                    secs.saturating_add(sample.sample_period_secs.into()),
+                )
+            });
+            secs.saturating_mul(1000).checked_div(slots)
+        })
+        .unwrap_or(DEFAULT_MS_PER_SLOT);
+
+    let rates = estimate_stake_rewards_rates(
+        inflation_rate.validator,
+        total_supply,
+        total_active_stake,
+        vote_account.commission,
+        validator_credits_per_epoch,
+        cluster_credits_per_epoch,
+        epoch_info.slots_in_epoch,
+        average_slot_time_ms,
+    );
+    let estimate = CliStakeRewardsEstimate {
+        vote_account: vote_account_pubkey.to_string(),
+        amount,
+        epoch: epoch_info.epoch,
+        validator_inflation_rate: inflation_rate.validator,
+        total_supply,
+        total_active_stake,
+        commission: vote_account.commission,
+        epochs_sampled,
+        validator_credits_per_epoch,
+        cluster_credits_per_epoch,
+        max_credits_per_epoch: epoch_info
+            .slots_in_epoch
+            .saturating_mul(u64::from(max_credits_per_slot)),
+        slots_in_epoch: epoch_info.slots_in_epoch,
+        average_slot_time_ms,
+        epochs_per_year: rates.epochs_per_year,
+        apr: rates.apr,
+        apy: rates.apy,
+        rewards_per_epoch: (amount as f64 * rates.epoch_rate) as u64,
+        rewards_per_year: (amount as f64 * rates.apy) as u64,
+        use_lamports_unit,
+    };
+    Ok(config.output_format.formatted_string(&estimate))
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -4655,6 +4909,26 @@ mod tests {
             }
         );
 
+        // Test ShowStakeHistory Subcommand w/ graph
+        let test_show_stake_history = test_commands.clone().get_matches_from(vec![
+            "test",
+            "stake-history",
+            "--limit",
+            "0",
+            "--graph",
+        ]);
+        assert_eq!(
+            parse_command(&test_show_stake_history, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::ShowStakeHistory {
+                    use_lamports_unit: false,
+                    limit_results: 0,
+                    show_graph: true,
+                },
+                signers: vec![],
+            }
+        );
+
         // Test DeactivateStake Subcommand
         let test_deactivate_stake = test_commands.clone().get_matches_from(vec![
             "test",
@@ -5067,5 +5341,99 @@ mod tests {
                 signers: vec![Box::new(read_keypair_file(&default_keypair_file).unwrap()),],
             }
         );
+
+        // Test StakeRewardsEstimate Subcommand
+        let vote_account_pubkey = solana_pubkey::new_rand();
+        let test_stake_rewards_estimate = test_commands.clone().get_matches_from(vec![
+            "test",
+            "stake-rewards-estimate",
+            "--amount",
+            "1.5",
+            "--validator",
+            &vote_account_pubkey.to_string(),
+        ]);
+        assert_eq!(
+            parse_command(&test_stake_rewards_estimate, &default_signer, &mut None).unwrap(),
+            CliCommandInfo::without_signers(CliCommand::StakeRewardsEstimate {
+                amount: 1_500_000_000,
+                vote_account_pubkey,
+                use_lamports_unit: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_average_credits_per_epoch() {
+        let epoch_credits = [(8, 1_000, 0), (9, 3_000, 1_000), (10, 3_500, 3_000)];
+        assert_eq!(
+            average_credits_per_epoch(&epoch_credits, 10),
+            Some((1_500., 2))
+        );
+        assert_eq!(
+            average_credits_per_epoch(&epoch_credits, 11),
+            Some((3_500. / 3., 3))
+        );
+        assert_eq!(average_credits_per_epoch(&epoch_credits[2..], 10), None);
+        assert_eq!(average_credits_per_epoch(&[], 10), None);
+    }
+
+    #[test]
+    fn test_estimate_stake_rewards_rates() {
+        let slots_in_epoch = 432_000;
+        // Half of the supply staked doubles the rate of return of the staking inflation
+        let rates = estimate_stake_rewards_rates(
+            0.05,
+            1_000_000,
+            500_000,
+            0,
+            1_000.,
+            1_000.,
+            slots_in_epoch,
+            DEFAULT_MS_PER_SLOT,
+        );
+        assert!((rates.apr - 0.1).abs() < 1e-9);
+        assert!(rates.apy > rates.apr);
+        assert!(
+            ((1. + rates.epoch_rate).powf(rates.epochs_per_year) - 1. - rates.apy).abs() < 1e-9
+        );
+
+        // The commission and a lower than average performance cut the rewards
+        let rates = estimate_stake_rewards_rates(
+            0.05,
+            1_000_000,
+            500_000,
+            10,
+            800.,
+            1_000.,
+            slots_in_epoch,
+            DEFAULT_MS_PER_SLOT,
+        );
+        assert!((rates.apr - 0.1 * 0.9 * 0.8).abs() < 1e-9);
+
+        // Slots slower than the target issue the same inflation per epoch over fewer epochs
+        let rates = estimate_stake_rewards_rates(
+            0.05,
+            1_000_000,
+            500_000,
+            0,
+            1_000.,
+            1_000.,
+            slots_in_epoch,
+            2 * DEFAULT_MS_PER_SLOT,
+        );
+        assert!((rates.apr - 0.05).abs() < 1e-9);
+
+        // Nothing is earned without vote credits
+        let rates = estimate_stake_rewards_rates(
+            0.05,
+            1_000_000,
+            500_000,
+            0,
+            0.,
+            0.,
+            slots_in_epoch,
+            DEFAULT_MS_PER_SLOT,
+        );
+        assert_eq!(rates.apr, 0.);
     }
 }
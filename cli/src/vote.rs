@@ -1,6 +1,8 @@
 use {
     crate::{
-        checks::{check_account_for_fee_with_commitment, check_unique_pubkeys},
+        checks::{
+            check_account_for_fee_with_commitment, check_unique_pubkeys, notify_interactive_signers,
+        },
         cli::{
             log_instruction_custom_error, CliCommand, CliCommandInfo, CliConfig, CliError,
             ProcessResult,
@@ -26,27 +28,38 @@ use {
         offline::*,
     },
     solana_cli_output::{
-        return_signers_with_config, CliEpochVotingHistory, CliLandedVote, CliVoteAccount,
-        ReturnSignersConfig,
+        return_signers_with_config, CliEpochVotingHistory, CliLandedVote, CliValidatorStatus,
+        CliVoteAccount, CliVoteCreditsAudit, ReturnSignersConfig,
     },
+    solana_clock::{Epoch, Slot, UnixTimestamp, SECONDS_PER_DAY},
     solana_commitment_config::CommitmentConfig,
     solana_message::Message,
     solana_native_token::lamports_to_sol,
     solana_pubkey::Pubkey,
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
-    solana_rpc_client::rpc_client::RpcClient,
-    solana_rpc_client_api::config::RpcGetVoteAccountsConfig,
+    solana_rpc_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    solana_rpc_client_api::{
+        config::{RpcBlockProductionConfig, RpcGetVoteAccountsConfig, RpcTransactionConfig},
+        request::MAX_GET_CONFIRMED_BLOCKS_RANGE,
+        response::RpcVoteAccountStatus,
+    },
     solana_rpc_client_nonce_utils::blockhash_query::BlockhashQuery,
+    solana_signature::Signature,
     solana_system_interface::error::SystemError,
-    solana_transaction::Transaction,
+    solana_transaction::{versioned::VersionedTransaction, Transaction},
+    solana_transaction_status::UiTransactionEncoding,
     solana_vote_program::{
         vote_error::VoteError,
-        vote_instruction::{self, withdraw, CreateVoteAccountConfig},
+        vote_instruction::{self, withdraw, CreateVoteAccountConfig, VoteInstruction},
         vote_state::{
-            VoteAuthorize, VoteInit, VoteState, VoteStateVersions, VOTE_CREDITS_MAXIMUM_PER_SLOT,
+            VoteAuthorize, VoteInit, VoteState, VoteStateVersions, MAX_LOCKOUT_HISTORY,
+            VOTE_CREDITS_GRACE_SLOTS, VOTE_CREDITS_MAXIMUM_PER_SLOT,
         },
     },
-    std::rc::Rc,
+    std::{
+        collections::{HashMap, HashSet},
+        rc::Rc,
+    },
 };
 
 pub trait VoteSubCommands {
@@ -374,6 +387,45 @@ impl VoteSubCommands for App<'_, '_> {
                             "Display rewards for NUM recent epochs, max 10 \
                             [default: latest epoch only]",
                         ),
+                )
+                .arg(
+                    Arg::with_name("audit_credits")
+                        .long("audit-credits")
+                        .takes_value(false)
+                        .conflicts_with("with_rewards")
+                        .help(
+                            "Re-derive the credits of an epoch from the votes in the ledger and \
+                             compare them to the credits of the vote account",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("audit_epoch")
+                        .long("audit-epoch")
+                        .takes_value(true)
+                        .value_name("EPOCH")
+                        .validator(is_parsable::<Epoch>)
+                        .requires("audit_credits")
+                        .help("Epoch to audit [default: the previous epoch]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("validator-status")
+                .about(
+                    "Show the voting, credits, block production and balance of a validator in \
+                     a single view",
+                )
+                .arg(pubkey!(
+                    Arg::with_name("identity_pubkey")
+                        .index(1)
+                        .value_name("VALIDATOR_IDENTITY")
+                        .required(true),
+                    "Identity of the validator."
+                ))
+                .arg(
+                    Arg::with_name("lamports")
+                        .long("lamports")
+                        .takes_value(false)
+                        .help("Display balances in lamports instead of SOL"),
                 ),
         )
         .subcommand(
@@ -688,6 +740,14 @@ pub fn parse_vote_get_account_command(
         None
     };
     let starting_epoch = value_of(matches, "starting_epoch");
+    if matches.is_present("audit_credits") {
+        return Ok(CliCommandInfo::without_signers(
+            CliCommand::AuditVoteAccountCredits {
+                pubkey: vote_account_pubkey,
+                epoch: value_of(matches, "audit_epoch"),
+            },
+        ));
+    }
     Ok(CliCommandInfo::without_signers(
         CliCommand::ShowVoteAccount {
             pubkey: vote_account_pubkey,
@@ -699,6 +759,20 @@ pub fn parse_vote_get_account_command(
     ))
 }
 
+pub fn parse_validator_status(
+    matches: &ArgMatches<'_>,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<CliCommandInfo, CliError> {
+    let identity_pubkey = pubkey_of_signer(matches, "identity_pubkey", wallet_manager)?.unwrap();
+    let use_lamports_unit = matches.is_present("lamports");
+    Ok(CliCommandInfo::without_signers(
+        CliCommand::ShowValidatorStatus {
+            identity_pubkey,
+            use_lamports_unit,
+        },
+    ))
+}
+
 pub fn parse_withdraw_from_vote_account(
     matches: &ArgMatches<'_>,
     default_signer: &DefaultSigner,
@@ -1054,6 +1128,13 @@ pub fn process_vote_authorize(
     simulate_and_update_compute_unit_limit(&compute_unit_limit, rpc_client, &mut message)?;
     let mut tx = Transaction::new_unsigned(message);
 
+    notify_interactive_signers(
+        (!sign_only).then_some(rpc_client),
+        &config.signers,
+        &tx.message,
+        &recent_blockhash,
+    )?;
+
     if sign_only {
         tx.try_partial_sign(&config.signers, recent_blockhash)?;
         return_signers_with_config(
@@ -1362,6 +1443,396 @@ pub fn process_show_vote_account(
     Ok(config.output_format.formatted_string(&vote_account_data))
 }
 
+/// Credits earned for a rooted slot whose vote landed `latency` slots after the slot
+fn credits_for_vote_latency(latency: Slot, is_tvc_active: bool) -> u64 {
+    if !is_tvc_active {
+        return 1;
+    }
+    // The vote program records latencies as a u8
+    let latency = latency.min(u64::from(u8::MAX));
+    let max_credits = u64::from(VOTE_CREDITS_MAXIMUM_PER_SLOT);
+    match latency.checked_sub(u64::from(VOTE_CREDITS_GRACE_SLOTS)) {
+        None | Some(0) => max_credits,
+        Some(diff) => max_credits.saturating_sub(diff).max(1),
+    }
+}
+
+/// Returns the slots voted on by the instructions of `transaction` voting for `vote_account`
+fn voted_slots(transaction: &VersionedTransaction, vote_account: &Pubkey) -> Vec<Slot> {
+    let account_keys = transaction.message.static_account_keys();
+    transaction
+        .message
+        .instructions()
+        .iter()
+        .filter(|instruction| {
+            account_keys.get(usize::from(instruction.program_id_index))
+                == Some(&solana_vote_program::id())
+                && instruction
+                    .accounts
+                    .first()
+                    .and_then(|index| account_keys.get(usize::from(*index)))
+                    == Some(vote_account)
+        })
+        .filter_map(|instruction| bincode::deserialize::<VoteInstruction>(&instruction.data).ok())
+        .flat_map(|instruction| match instruction {
+            VoteInstruction::Vote(vote) | VoteInstruction::VoteSwitch(vote, _) => vote.slots,
+            VoteInstruction::UpdateVoteState(update)
+            | VoteInstruction::UpdateVoteStateSwitch(update, _)
+            | VoteInstruction::CompactUpdateVoteState(update)
+            | VoteInstruction::CompactUpdateVoteStateSwitch(update, _) => update
+                .lockouts
+                .iter()
+                .map(|lockout| lockout.slot())
+                .collect(),
+            VoteInstruction::TowerSync(tower_sync)
+            | VoteInstruction::TowerSyncSwitch(tower_sync, _) => tower_sync
+                .lockouts
+                .iter()
+                .map(|lockout| lockout.slot())
+                .collect(),
+            _ => vec![],
+        })
+        .collect()
+}
+
+/// Explains a difference between the credits re-derived from the ledger and the on-chain ones
+fn find_credit_discrepancies(
+    expected_credits: u64,
+    rooted_voted_slots: u64,
+    on_chain_credits: u64,
+    is_tvc_active: bool,
+) -> Vec<String> {
+    // Slots voted near each epoch boundary may be credited in the adjacent epoch
+    let boundary_slots = 2 * MAX_LOCKOUT_HISTORY as u64;
+    let max_credits_per_slot = if is_tvc_active {
+        u64::from(VOTE_CREDITS_MAXIMUM_PER_SLOT)
+    } else {
+        1
+    };
+    if on_chain_credits.abs_diff(expected_credits) <= boundary_slots * max_credits_per_slot {
+        return vec![];
+    }
+
+    let mut discrepancies = vec![format!(
+        "On-chain credits differ by {} from the {expected_credits} credits re-derived from the \
+         ledger",
+        on_chain_credits.abs_diff(expected_credits),
+    )];
+    if is_tvc_active && on_chain_credits.abs_diff(rooted_voted_slots) <= boundary_slots {
+        discrepancies.push(
+            "On-chain credits match one credit per rooted slot, as if timely vote credits were \
+             not active"
+                .to_string(),
+        );
+    } else if !is_tvc_active && on_chain_credits > rooted_voted_slots + boundary_slots {
+        discrepancies.push(
+            "On-chain credits exceed one credit per rooted slot although timely vote credits are \
+             not active"
+                .to_string(),
+        );
+    } else if on_chain_credits > expected_credits {
+        discrepancies.push(
+            "On-chain credits exceed the re-derived ones; the RPC node may lack part of the \
+             transaction history of the epoch"
+                .to_string(),
+        );
+    } else {
+        discrepancies.push(
+            "On-chain credits fall short of the re-derived ones; the vote program may have \
+             credited votes incorrectly"
+                .to_string(),
+        );
+    }
+    discrepancies
+}
+
+/// Number of signatures fetched per request while auditing the credits of a vote account
+const AUDIT_CREDITS_SIGNATURES_LIMIT: usize = 1_000;
+
+pub fn process_audit_vote_account_credits(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    vote_account_address: &Pubkey,
+    epoch: Option<Epoch>,
+) -> ProcessResult {
+    let (_, vote_state) = get_vote_account(rpc_client, vote_account_address, config.commitment)?;
+
+    let epoch_schedule = rpc_client.get_epoch_schedule()?;
+    let epoch_info = rpc_client.get_epoch_info_with_commitment(CommitmentConfig::finalized())?;
+    let epoch = epoch.unwrap_or_else(|| epoch_info.epoch.saturating_sub(1));
+    if epoch > epoch_info.epoch {
+        return Err(CliError::BadParameter(format!("Epoch {epoch} has not started yet")).into());
+    }
+    let first_slot = epoch_schedule.get_first_slot_in_epoch(epoch);
+    let last_slot = epoch_schedule
+        .get_last_slot_in_epoch(epoch)
+        .min(epoch_info.absolute_slot);
+    let tvc_activation_slot =
+        rpc_client.get_feature_activation_slot(&agave_feature_set::timely_vote_credits::id())?;
+    let is_tvc_active = tvc_activation_slot
+        .map(|slot| epoch_schedule.get_epoch(slot) <= epoch)
+        .unwrap_or_default();
+
+    let mut rooted_slots = HashSet::new();
+    let mut start_slot = first_slot;
+    while start_slot <= last_slot {
+        let end_slot = last_slot.min(start_slot.saturating_add(MAX_GET_CONFIRMED_BLOCKS_RANGE - 1));
+        rooted_slots.extend(rpc_client.get_blocks(start_slot, Some(end_slot))?);
+        start_slot = end_slot.saturating_add(1);
+    }
+
+    // Successful vote transactions landed in the epoch, newest first
+    let mut vote_signatures = vec![];
+    let mut before = None;
+    'fetch: loop {
+        let results = rpc_client.get_signatures_for_address_with_config(
+            vote_account_address,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: None,
+                limit: Some(AUDIT_CREDITS_SIGNATURES_LIMIT),
+                commitment: Some(CommitmentConfig::finalized()),
+            },
+        )?;
+        let num_results = results.len();
+        for result in results {
+            if result.slot < first_slot {
+                break 'fetch;
+            }
+            let signature = result.signature.parse::<Signature>()?;
+            before = Some(signature);
+            if result.slot <= last_slot && result.err.is_none() {
+                vote_signatures.push(signature);
+            }
+        }
+        if num_results < AUDIT_CREDITS_SIGNATURES_LIMIT {
+            break;
+        }
+    }
+
+    // The latency of a voted slot is set by the first vote landing on it
+    let mut voted_slot_latencies = HashMap::new();
+    for signature in vote_signatures.iter().rev() {
+        let transaction = rpc_client.get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::finalized()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?;
+        let landed_slot = transaction.slot;
+        let Some(decoded_transaction) = transaction.transaction.transaction.decode() else {
+            continue;
+        };
+        for voted_slot in voted_slots(&decoded_transaction, vote_account_address) {
+            if (first_slot..=last_slot).contains(&voted_slot) {
+                voted_slot_latencies
+                    .entry(voted_slot)
+                    .or_insert_with(|| landed_slot.saturating_sub(voted_slot));
+            }
+        }
+    }
+
+    let mut rooted_voted_slots = 0;
+    let mut expected_credits = 0;
+    for (voted_slot, latency) in &voted_slot_latencies {
+        if rooted_slots.contains(voted_slot) {
+            rooted_voted_slots += 1;
+            expected_credits += credits_for_vote_latency(*latency, is_tvc_active);
+        }
+    }
+    let on_chain_credits = vote_state
+        .epoch_credits()
+        .iter()
+        .find(|(credits_epoch, _, _)| *credits_epoch == epoch)
+        .map(|(_, credits, prev_credits)| credits.saturating_sub(*prev_credits))
+        .unwrap_or_default();
+
+    let audit = CliVoteCreditsAudit {
+        vote_account: vote_account_address.to_string(),
+        epoch,
+        first_slot,
+        last_slot,
+        timely_vote_credits: is_tvc_active,
+        vote_transactions: vote_signatures.len(),
+        voted_slots: voted_slot_latencies.len(),
+        rooted_voted_slots,
+        expected_credits,
+        on_chain_credits,
+        discrepancies: find_credit_discrepancies(
+            expected_credits,
+            rooted_voted_slots,
+            on_chain_credits,
+            is_tvc_active,
+        ),
+    };
+    Ok(config.output_format.formatted_string(&audit))
+}
+
+/// Number of recent transactions of the identity the balance drain rate is derived from
+const VALIDATOR_STATUS_SIGNATURES_LIMIT: usize = 1_000;
+
+/// Lamports spent per day by an account that paid `fee` for each of `num_transactions`
+/// transactions landed between `oldest_block_time` and `newest_block_time`
+fn balance_drain_per_day(
+    num_transactions: usize,
+    fee: u64,
+    oldest_block_time: UnixTimestamp,
+    newest_block_time: UnixTimestamp,
+) -> Option<u64> {
+    let secs = u64::try_from(newest_block_time.checked_sub(oldest_block_time)?).ok()?;
+    // The first transaction landed at the start of the period
+    let spent = u128::from(fee) * num_transactions.saturating_sub(1) as u128;
+    let drain = spent * u128::from(SECONDS_PER_DAY) / u128::from(secs).max(1);
+    (secs > 0).then(|| u64::try_from(drain).unwrap_or(u64::MAX))
+}
+
+pub fn process_show_validator_status(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    identity_pubkey: &Pubkey,
+    use_lamports_unit: bool,
+) -> ProcessResult {
+    let epoch_info = rpc_client.get_epoch_info_with_commitment(config.commitment)?;
+    let RpcVoteAccountStatus {
+        current,
+        delinquent,
+    } = rpc_client.get_vote_accounts_with_config(RpcGetVoteAccountsConfig {
+        keep_unstaked_delinquents: Some(true),
+        commitment: Some(config.commitment),
+        ..RpcGetVoteAccountsConfig::default()
+    })?;
+    let identity = identity_pubkey.to_string();
+    let is_delinquent = !current
+        .iter()
+        .any(|vote_account| vote_account.node_pubkey == identity);
+    let vote_account = current
+        .into_iter()
+        .chain(delinquent)
+        .filter(|vote_account| vote_account.node_pubkey == identity)
+        .max_by_key(|vote_account| vote_account.activated_stake)
+        .ok_or_else(|| {
+            CliError::RpcRequestError(format!(
+                "No vote account found for validator identity {identity}"
+            ))
+        })?;
+    let vote_account_pubkey = vote_account.vote_pubkey.parse::<Pubkey>()?;
+    let (_, vote_state) = get_vote_account(rpc_client, &vote_account_pubkey, config.commitment)?;
+
+    let tvc_activation_slot =
+        rpc_client.get_feature_activation_slot(&agave_feature_set::timely_vote_credits::id())?;
+    let max_credits_per_slot = if tvc_activation_slot.is_some() {
+        VOTE_CREDITS_MAXIMUM_PER_SLOT
+    } else {
+        1
+    };
+    let epoch_credits = vote_state
+        .epoch_credits()
+        .last()
+        .filter(|(epoch, _, _)| *epoch == epoch_info.epoch)
+        .map(|(_, credits, prev_credits)| credits.saturating_sub(*prev_credits))
+        .unwrap_or_default();
+    let timely_recent_votes = vote_state
+        .votes
+        .iter()
+        .filter(|vote| vote.latency <= VOTE_CREDITS_GRACE_SLOTS)
+        .count();
+    let average_vote_latency = vote_state
+        .votes
+        .iter()
+        .map(|vote| f64::from(vote.latency))
+        .sum::<f64>()
+        / vote_state.votes.len().max(1) as f64;
+
+    let (leader_slots, blocks_produced) = rpc_client
+        .get_block_production_with_config(RpcBlockProductionConfig {
+            identity: Some(identity.clone()),
+            range: None,
+            commitment: Some(config.commitment),
+        })?
+        .value
+        .by_identity
+        .remove(&identity)
+        .unwrap_or_default();
+    let version = rpc_client
+        .get_cluster_nodes()?
+        .into_iter()
+        .find(|node| node.pubkey == identity)
+        .and_then(|node| node.version);
+
+    let identity_balance = rpc_client
+        .get_balance_with_commitment(identity_pubkey, config.commitment)?
+        .value;
+    let signatures = rpc_client.get_signatures_for_address_with_config(
+        identity_pubkey,
+        GetConfirmedSignaturesForAddress2Config {
+            limit: Some(VALIDATOR_STATUS_SIGNATURES_LIMIT),
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..GetConfirmedSignaturesForAddress2Config::default()
+        },
+    )?;
+    // The identity pays the fees of its votes, which dominate its transactions, so the fee of
+    // the newest one is representative
+    let balance_drain_per_day = match (signatures.first(), signatures.last()) {
+        (Some(newest), Some(oldest)) => {
+            let fee = rpc_client
+                .get_transaction_with_config(
+                    &newest.signature.parse::<Signature>()?,
+                    RpcTransactionConfig {
+                        encoding: Some(UiTransactionEncoding::Base64),
+                        commitment: Some(CommitmentConfig::confirmed()),
+                        max_supported_transaction_version: Some(0),
+                    },
+                )?
+                .transaction
+                .meta
+                .map(|meta| meta.fee)
+                .unwrap_or_default();
+            newest.block_time.zip(oldest.block_time).and_then(
+                |(newest_block_time, oldest_block_time)| {
+                    balance_drain_per_day(
+                        signatures.len(),
+                        fee,
+                        oldest_block_time,
+                        newest_block_time,
+                    )
+                },
+            )
+        }
+        _ => None,
+    };
+
+    let status = CliValidatorStatus {
+        identity,
+        vote_account: vote_account.vote_pubkey,
+        version,
+        delinquent: is_delinquent,
+        commission: vote_account.commission,
+        activated_stake: vote_account.activated_stake,
+        epoch: epoch_info.epoch,
+        current_slot: epoch_info.absolute_slot,
+        last_vote: vote_account.last_vote,
+        root_slot: vote_account.root_slot,
+        recent_votes: vote_state.votes.len(),
+        timely_recent_votes,
+        average_vote_latency,
+        epoch_credits,
+        max_epoch_credits_so_far: epoch_info
+            .slot_index
+            .saturating_mul(u64::from(max_credits_per_slot)),
+        max_epoch_credits: epoch_info
+            .slots_in_epoch
+            .saturating_mul(u64::from(max_credits_per_slot)),
+        leader_slots,
+        skipped_leader_slots: leader_slots.saturating_sub(blocks_produced),
+        identity_balance,
+        balance_drain_per_day,
+        use_lamports_unit,
+    };
+    Ok(config.output_format.formatted_string(&status))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn process_withdraw_from_vote_account(
     rpc_client: &RpcClient,
@@ -1556,6 +2027,7 @@ mod tests {
         solana_presigner::Presigner,
         solana_rpc_client_nonce_utils::blockhash_query,
         solana_signer::Signer,
+        solana_vote_program::vote_state::TowerSync,
         tempfile::NamedTempFile,
     };
 
@@ -2393,5 +2865,102 @@ mod tests {
                 ],
             }
         );
+
+        // Test AuditVoteAccountCredits
+        let test_audit_credits = test_commands.clone().get_matches_from(vec![
+            "test",
+            "vote-account",
+            &pubkey_string,
+            "--audit-credits",
+            "--audit-epoch",
+            "7",
+        ]);
+        assert_eq!(
+            parse_command(&test_audit_credits, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::AuditVoteAccountCredits {
+                    pubkey,
+                    epoch: Some(7),
+                },
+                signers: vec![],
+            }
+        );
+
+        // Test ShowValidatorStatus
+        let test_validator_status = test_commands.clone().get_matches_from(vec![
+            "test",
+            "validator-status",
+            &pubkey_string,
+            "--lamports",
+        ]);
+        assert_eq!(
+            parse_command(&test_validator_status, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::ShowValidatorStatus {
+                    identity_pubkey: pubkey,
+                    use_lamports_unit: true,
+                },
+                signers: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_credits_for_vote_latency() {
+        assert_eq!(credits_for_vote_latency(1, true), 16);
+        assert_eq!(credits_for_vote_latency(2, true), 16);
+        assert_eq!(credits_for_vote_latency(3, true), 15);
+        assert_eq!(credits_for_vote_latency(17, true), 1);
+        assert_eq!(credits_for_vote_latency(1_000, true), 1);
+        assert_eq!(credits_for_vote_latency(1, false), 1);
+        assert_eq!(credits_for_vote_latency(1_000, false), 1);
+    }
+
+    #[test]
+    fn test_voted_slots() {
+        let vote_account = Pubkey::new_unique();
+        let authorized_voter = Keypair::new();
+        let tower_sync = TowerSync::from(vec![(3, 3), (4, 2), (6, 1)]);
+        let other_vote = vote_instruction::tower_sync(
+            &Pubkey::new_unique(),
+            &authorized_voter.pubkey(),
+            TowerSync::from(vec![(5, 1)]),
+        );
+        let transaction = VersionedTransaction::from(Transaction::new_with_payer(
+            &[
+                vote_instruction::tower_sync(&vote_account, &authorized_voter.pubkey(), tower_sync),
+                other_vote,
+            ],
+            Some(&authorized_voter.pubkey()),
+        ));
+        assert_eq!(voted_slots(&transaction, &vote_account), vec![3, 4, 6]);
+    }
+
+    #[test]
+    fn test_find_credit_discrepancies() {
+        // within the epoch boundary tolerance
+        assert!(find_credit_discrepancies(16_000, 1_000, 15_500, true).is_empty());
+        // timely vote credits not applied
+        let discrepancies = find_credit_discrepancies(16_000, 1_000, 1_000, true);
+        assert_eq!(discrepancies.len(), 2);
+        assert!(discrepancies[1].contains("timely vote credits"));
+        // credits awarded beyond one per slot without timely vote credits
+        let discrepancies = find_credit_discrepancies(1_000, 1_000, 4_000, false);
+        assert!(discrepancies[1].contains("although timely vote credits are not active"));
+        // too few credits
+        let discrepancies = find_credit_discrepancies(16_000, 1_000, 8_000, true);
+        assert!(discrepancies[1].contains("fall short"));
+    }
+
+    #[test]
+    fn test_balance_drain_per_day() {
+        // 2 votes per second for an hour at 5000 lamports each
+        assert_eq!(
+            balance_drain_per_day(7_201, 5_000, 0, 3_600),
+            Some(2 * 5_000 * SECONDS_PER_DAY)
+        );
+        assert_eq!(balance_drain_per_day(1, 5_000, 100, 100), None);
+        assert_eq!(balance_drain_per_day(10, 5_000, 200, 100), None);
+        assert_eq!(balance_drain_per_day(1, 5_000, 0, 100), Some(0));
     }
 }
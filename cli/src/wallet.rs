@@ -1,10 +1,17 @@
 use {
     crate::{
+        checks::{
+            check_account_for_multiple_fees_with_commitment,
+            check_account_for_spend_and_fee_with_commitment,
+            check_account_for_spend_multiple_fees_with_commitment,
+        },
         cli::{
             log_instruction_custom_error, request_and_confirm_airdrop, CliCommand, CliCommandInfo,
             CliConfig, CliError, ProcessResult,
         },
-        compute_budget::{ComputeUnitConfig, WithComputeUnitConfig},
+        compute_budget::{
+            simulate_and_update_compute_unit_limit, ComputeUnitConfig, WithComputeUnitConfig,
+        },
         memo::WithMemo,
         nonce::check_nonce_account,
         spend_utils::{resolve_spend_tx_and_check_account_balances, SpendAmount},
@@ -25,11 +32,12 @@ use {
     solana_cli_output::{
         display::{build_balance_message, BuildBalanceMessageConfig},
         return_signers_with_config, CliAccount, CliBalance, CliFindProgramDerivedAddress,
-        CliSignatureVerificationStatus, CliTransaction, CliTransactionConfirmation, OutputFormat,
-        ReturnSignersConfig,
+        CliSignatureVerificationStatus, CliTransaction, CliTransactionConfirmation,
+        CliTransferBatch, OutputFormat, ReturnSignersConfig,
     },
     solana_commitment_config::CommitmentConfig,
     solana_message::Message,
+    solana_native_token::sol_to_lamports,
     solana_offchain_message::OffchainMessage,
     solana_pubkey::Pubkey,
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
@@ -45,8 +53,13 @@ use {
         TransactionBinaryEncoding, UiTransactionEncoding,
     },
     std::{fmt::Write as FmtWrite, fs::File, io::Write, rc::Rc, str::FromStr},
+    tachyon_system_interface::transfer_many::transfer_many,
 };
 
+/// Recipients paid by each transaction of `transfer --batch-file`, which keeps the transaction
+/// within the packet size
+const MAX_RECIPIENTS_PER_TRANSFER_MANY: usize = 20;
+
 pub trait WalletSubCommands {
     fn wallet_subcommands(self) -> Self;
 }
@@ -261,7 +274,7 @@ impl WalletSubCommands for App<'_, '_> {
                     Arg::with_name("to")
                         .index(1)
                         .value_name("RECIPIENT_ADDRESS")
-                        .required(true),
+                        .required_unless("batch_file"),
                     "Account of recipient."
                 ))
                 .arg(
@@ -270,9 +283,29 @@ impl WalletSubCommands for App<'_, '_> {
                         .value_name("AMOUNT")
                         .takes_value(true)
                         .validator(is_amount_or_all)
-                        .required(true)
+                        .required_unless("batch_file")
                         .help("The amount to send, in SOL; accepts keyword ALL"),
                 )
+                .arg(
+                    Arg::with_name("batch_file")
+                        .long("batch-file")
+                        .value_name("FILEPATH")
+                        .takes_value(true)
+                        .conflicts_with_all(&[
+                            "to",
+                            "amount",
+                            "derived_address_seed",
+                            SIGN_ONLY_ARG.name,
+                            BLOCKHASH_ARG.name,
+                            NONCE_ARG.name,
+                            MEMO_ARG.name,
+                        ])
+                        .help(
+                            "Pay every recipient listed in FILEPATH, one `RECIPIENT_ADDRESS,AMOUNT` \
+                             line per recipient with the amount in SOL, using TransferMany \
+                             instructions of up to 20 recipients each",
+                        ),
+                )
                 .arg(pubkey!(
                     Arg::with_name("from")
                         .long("from")
@@ -545,6 +578,9 @@ pub fn parse_transfer(
     default_signer: &DefaultSigner,
     wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
 ) -> Result<CliCommandInfo, CliError> {
+    if let Some(batch_file) = matches.value_of("batch_file") {
+        return parse_transfer_batch(matches, batch_file, default_signer, wallet_manager);
+    }
     let amount = SpendAmount::new_from_matches(matches, "amount");
     let to = pubkey_of_signer(matches, "to", wallet_manager)?.unwrap();
     let sign_only = matches.is_present(SIGN_ONLY_ARG.name);
@@ -596,6 +632,31 @@ pub fn parse_transfer(
     })
 }
 
+fn parse_transfer_batch(
+    matches: &ArgMatches<'_>,
+    batch_file: &str,
+    default_signer: &DefaultSigner,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<CliCommandInfo, CliError> {
+    let no_wait = matches.is_present("no_wait");
+    let (fee_payer, fee_payer_pubkey) = signer_of(matches, FEE_PAYER_ARG.name, wallet_manager)?;
+    let (from, from_pubkey) = signer_of(matches, "from", wallet_manager)?;
+    let signer_info =
+        default_signer.generate_unique_signers(vec![fee_payer, from], matches, wallet_manager)?;
+    let compute_unit_price = value_of(matches, COMPUTE_UNIT_PRICE_ARG.name);
+
+    Ok(CliCommandInfo {
+        command: CliCommand::TransferBatch {
+            batch_file: batch_file.to_string(),
+            from: signer_info.index_of(from_pubkey).unwrap(),
+            fee_payer: signer_info.index_of(fee_payer_pubkey).unwrap(),
+            no_wait,
+            compute_unit_price,
+        },
+        signers: signer_info.signers,
+    })
+}
+
 pub fn parse_sign_offchain_message(
     matches: &ArgMatches<'_>,
     default_signer: &DefaultSigner,
@@ -995,6 +1056,130 @@ pub fn process_transfer(
     }
 }
 
+/// Parses the `RECIPIENT_ADDRESS,AMOUNT` lines of a batch file, skipping blank lines and `#`
+/// comments, into the recipients and lamports to pay them
+pub(crate) fn parse_transfer_batch_file(contents: &str) -> Result<Vec<(Pubkey, u64)>, CliError> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            let invalid = |reason: &str| {
+                CliError::BadParameter(format!("line {line_number} of the batch file: {reason}"))
+            };
+            let (recipient, amount) = line
+                .split_once(',')
+                .ok_or_else(|| invalid("expected `RECIPIENT_ADDRESS,AMOUNT`"))?;
+            let recipient = Pubkey::from_str(recipient.trim())
+                .map_err(|_| invalid("invalid recipient address"))?;
+            let amount = amount
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|amount| amount.is_finite() && *amount > 0.0)
+                .ok_or_else(|| invalid("invalid amount"))?;
+            Ok((recipient, sol_to_lamports(amount)))
+        })
+        .collect()
+}
+
+pub fn process_transfer_batch(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    batch_file: &str,
+    from: SignerIndex,
+    fee_payer: SignerIndex,
+    no_wait: bool,
+    compute_unit_price: Option<u64>,
+) -> ProcessResult {
+    let contents = std::fs::read_to_string(batch_file)
+        .map_err(|err| format!("Unable to read {batch_file}: {err}"))?;
+    let transfers = parse_transfer_batch_file(&contents)?;
+    if transfers.is_empty() {
+        return Err(format!("No recipients in {batch_file}").into());
+    }
+    let lamports = transfers
+        .iter()
+        .try_fold(0u64, |total, (_, lamports)| total.checked_add(*lamports))
+        .ok_or("Total amount of the batch overflows")?;
+
+    let from = config.signers[from];
+    let fee_payer = config.signers[fee_payer];
+    let compute_unit_config = ComputeUnitConfig {
+        compute_unit_price,
+        compute_unit_limit: ComputeUnitLimit::Simulated,
+    };
+    let mut messages = vec![];
+    for chunk in transfers.chunks(MAX_RECIPIENTS_PER_TRANSFER_MANY) {
+        let ixs = vec![transfer_many(&from.pubkey(), chunk)]
+            .with_compute_unit_config(&compute_unit_config);
+        let mut message = Message::new(&ixs, Some(&fee_payer.pubkey()));
+        simulate_and_update_compute_unit_limit(
+            &compute_unit_config.compute_unit_limit,
+            rpc_client,
+            &mut message,
+        )?;
+        messages.push(message);
+    }
+
+    let message_refs: Vec<_> = messages.iter().collect();
+    if from.pubkey() == fee_payer.pubkey() {
+        check_account_for_spend_multiple_fees_with_commitment(
+            rpc_client,
+            &from.pubkey(),
+            lamports,
+            &message_refs,
+            config.commitment,
+        )?;
+    } else {
+        check_account_for_multiple_fees_with_commitment(
+            rpc_client,
+            &fee_payer.pubkey(),
+            &message_refs,
+            config.commitment,
+        )?;
+        check_account_for_spend_and_fee_with_commitment(
+            rpc_client,
+            &from.pubkey(),
+            lamports,
+            0,
+            config.commitment,
+        )?;
+    }
+
+    let recent_blockhash = rpc_client.get_latest_blockhash()?;
+    let mut signatures = Vec::with_capacity(messages.len());
+    for message in messages {
+        let mut tx = Transaction::new_unsigned(message);
+        tx.try_sign(&config.signers, recent_blockhash)?;
+        let result = if no_wait {
+            rpc_client.send_transaction_with_config(&tx, config.send_transaction_config)
+        } else {
+            rpc_client.send_and_confirm_transaction_with_spinner_and_config(
+                &tx,
+                config.commitment,
+                config.send_transaction_config,
+            )
+        };
+        let signature = result.map_err(|err| {
+            format!(
+                "Transaction {} of {} failed after {} succeeded: {err}",
+                signatures.len() + 1,
+                transfers.len().div_ceil(MAX_RECIPIENTS_PER_TRANSFER_MANY),
+                signatures.len(),
+            )
+        })?;
+        signatures.push(signature.to_string());
+    }
+
+    Ok(config.output_format.formatted_string(&CliTransferBatch {
+        recipients: transfers.len(),
+        lamports,
+        signatures,
+    }))
+}
+
 pub fn process_sign_offchain_message(
     config: &CliConfig,
     message: &OffchainMessage,
@@ -66,6 +66,7 @@ fn test_send_and_confirm_transactions_in_parallel_without_tpu_client() {
                 encoding: None,
                 max_retries: None,
                 min_context_slot: None,
+                compute_unit_price_escalation_rotations: None,
             },
         },
     );
@@ -131,6 +132,7 @@ fn test_send_and_confirm_transactions_in_parallel_with_tpu_client() {
                 encoding: None,
                 max_retries: None,
                 min_context_slot: None,
+                compute_unit_price_escalation_rotations: None,
             },
         },
     );
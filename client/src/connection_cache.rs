@@ -1,4 +1,4 @@
-pub use solana_connection_cache::connection_cache::Protocol;
+pub use solana_connection_cache::connection_cache::{ConnectionInfo, Protocol};
 use {
     quinn::Endpoint,
     solana_connection_cache::{
@@ -139,6 +139,20 @@ impl ConnectionCache {
             }
         }
     }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Quic(cache) => cache.name(),
+            Self::Udp(cache) => cache.name(),
+        }
+    }
+
+    pub fn connection_infos(&self) -> Vec<(SocketAddr, Vec<ConnectionInfo>)> {
+        match self {
+            Self::Quic(cache) => cache.connection_infos(),
+            Self::Udp(cache) => cache.connection_infos(),
+        }
+    }
 }
 
 macro_rules! dispatch {
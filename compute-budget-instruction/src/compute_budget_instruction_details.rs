@@ -2,6 +2,7 @@ use {
     crate::{
         builtin_programs_filter::{BuiltinProgramsFilter, ProgramKind},
         compute_budget_program_id_filter::ComputeBudgetProgramIdFilter,
+        fee_payer_split::FeePayerSplit,
     },
     agave_feature_set::{self as feature_set, FeatureSet},
     solana_borsh::v1::try_from_slice_unchecked,
@@ -43,6 +44,7 @@ pub struct ComputeBudgetInstructionDetails {
     requested_compute_unit_price: Option<(u8, u64)>,
     requested_heap_size: Option<(u8, u32)>,
     requested_loaded_accounts_data_size_limit: Option<(u8, u32)>,
+    requested_fee_payer_split: Option<(u8, FeePayerSplit)>,
     num_non_compute_budget_instructions: Saturating<u16>,
     // Additional builtin program counters
     num_non_migratable_builtin_instructions: Saturating<u16>,
@@ -50,6 +52,15 @@ pub struct ComputeBudgetInstructionDetails {
     migrating_builtin_feature_counters: MigrationBuiltinFeatureCounter,
 }
 
+/// The largest heap frame a transaction can request with `feature_set`
+pub fn max_heap_frame_bytes(feature_set: &FeatureSet) -> u32 {
+    if feature_set.is_active(&feature_set::raise_max_heap_frame_bytes::id()) {
+        X1_MAX_HEAP_FRAME_BYTES
+    } else {
+        MAX_HEAP_FRAME_BYTES
+    }
+}
+
 impl ComputeBudgetInstructionDetails {
     pub fn try_from<'a>(
         instructions: impl Iterator<Item = (&'a Pubkey, SVMInstruction<'a>)> + Clone,
@@ -102,10 +113,20 @@ impl ComputeBudgetInstructionDetails {
         &self,
         feature_set: &FeatureSet,
     ) -> Result<ComputeBudgetLimits> {
+        // The fee payer split is applied at fee collection, but rejected before its feature
+        if let Some((index, _)) = self.requested_fee_payer_split {
+            if !feature_set.is_active(&feature_set::enable_fee_payer_split::id()) {
+                return Err(TransactionError::InstructionError(
+                    index,
+                    InstructionError::InvalidInstructionData,
+                ));
+            }
+        }
+
         // Sanitize requested heap size
         let updated_heap_bytes =
             if let Some((index, requested_heap_size)) = self.requested_heap_size {
-                if Self::sanitize_requested_heap_size(requested_heap_size) {
+                if Self::sanitize_requested_heap_size(requested_heap_size, feature_set) {
                     requested_heap_size
                 } else {
                     return Err(TransactionError::InstructionError(
@@ -116,7 +137,7 @@ impl ComputeBudgetInstructionDetails {
             } else {
                 MIN_HEAP_FRAME_BYTES
             }
-            .min(MAX_HEAP_FRAME_BYTES);
+            .min(max_heap_frame_bytes(feature_set));
 
         // Calculate compute unit limit
         let compute_unit_limit = self
@@ -157,6 +178,15 @@ impl ComputeBudgetInstructionDetails {
             TransactionError::InstructionError(index, InstructionError::InvalidInstructionData);
         let duplicate_instruction_error = TransactionError::DuplicateInstruction(index);
 
+        if let Some(fee_payer_split) = FeePayerSplit::try_from_instruction(instruction) {
+            let fee_payer_split = fee_payer_split.map_err(|_| invalid_instruction_data_error)?;
+            if self.requested_fee_payer_split.is_some() {
+                return Err(duplicate_instruction_error);
+            }
+            self.requested_fee_payer_split = Some((index, fee_payer_split));
+            return Ok(());
+        }
+
         match try_from_slice_unchecked(instruction.data) {
             Ok(ComputeBudgetInstruction::RequestHeapFrame(bytes)) => {
                 if self.requested_heap_size.is_some() {
@@ -189,8 +219,9 @@ impl ComputeBudgetInstructionDetails {
     }
 
     #[inline]
-    fn sanitize_requested_heap_size(bytes: u32) -> bool {
-        (MIN_HEAP_FRAME_BYTES..=MAX_HEAP_FRAME_BYTES).contains(&bytes) && bytes % 1024 == 0
+    fn sanitize_requested_heap_size(bytes: u32, feature_set: &FeatureSet) -> bool {
+        (MIN_HEAP_FRAME_BYTES..=max_heap_frame_bytes(feature_set)).contains(&bytes)
+            && bytes % 1024 == 0
     }
 
     fn calculate_default_compute_unit_limit(&self, feature_set: &FeatureSet) -> u32 {
@@ -228,6 +259,7 @@ impl ComputeBudgetInstructionDetails {
 mod test {
     use {
         super::*,
+        crate::fee_payer_split::set_fee_payer_split,
         solana_builtins_default_costs::get_migration_feature_position,
         solana_instruction::Instruction,
         solana_keypair::Keypair,
@@ -364,6 +396,60 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_try_from_fee_payer_split() {
+        let co_payer = Pubkey::new_unique();
+        let tx = build_sanitized_transaction(&[
+            Instruction::new_with_bincode(Pubkey::new_unique(), &(), vec![]),
+            set_fee_payer_split(&co_payer, 50),
+        ]);
+        let details =
+            ComputeBudgetInstructionDetails::try_from(SVMMessage::program_instructions_iter(&tx))
+                .unwrap();
+        assert_eq!(
+            details.requested_fee_payer_split,
+            Some((
+                1,
+                FeePayerSplit {
+                    co_payer_index: 1,
+                    co_payer_percent: 50,
+                }
+            ))
+        );
+
+        // Rejected until the feature is active
+        let mut feature_set = FeatureSet::default();
+        assert_eq!(
+            details.sanitize_and_convert_to_compute_budget_limits(&feature_set),
+            Err(TransactionError::InstructionError(
+                1,
+                InstructionError::InvalidInstructionData
+            ))
+        );
+        feature_set.activate(&feature_set::enable_fee_payer_split::id(), 0);
+        assert!(details
+            .sanitize_and_convert_to_compute_budget_limits(&feature_set)
+            .is_ok());
+
+        let tx = build_sanitized_transaction(&[
+            set_fee_payer_split(&co_payer, 50),
+            set_fee_payer_split(&co_payer, 20),
+        ]);
+        assert_eq!(
+            ComputeBudgetInstructionDetails::try_from(SVMMessage::program_instructions_iter(&tx)),
+            Err(TransactionError::DuplicateInstruction(1))
+        );
+
+        let tx = build_sanitized_transaction(&[set_fee_payer_split(&co_payer, 0)]);
+        assert_eq!(
+            ComputeBudgetInstructionDetails::try_from(SVMMessage::program_instructions_iter(&tx)),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidInstructionData
+            ))
+        );
+    }
+
     fn prep_feature_minimial_cus_for_builtin_instructions(
         is_active: bool,
         instruction_details: &ComputeBudgetInstructionDetails,
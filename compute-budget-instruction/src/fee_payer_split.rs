@@ -0,0 +1,172 @@
+//! X1 extension of the compute budget program splitting the fee of a transaction between its
+//! fee payer and a co-payer, so that relayers can pay part of the fees of their users.
+//!
+//! The instruction data is a discriminant no `ComputeBudgetInstruction` uses, followed by the
+//! share of the fee the co-payer pays, in percent. The co-payer is the only account of the
+//! instruction and must be a writable signer other than the fee payer. The instruction is
+//! invalid while the `enable_fee_payer_split` feature is inactive.
+
+use {
+    crate::compute_budget_program_id_filter::ComputeBudgetProgramIdFilter,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_svm_transaction::instruction::SVMInstruction,
+};
+
+pub const SET_FEE_PAYER_SPLIT_DISCRIMINANT: u8 = 0xf0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeePayerSplit {
+    /// Index of the co-payer in the account keys of the transaction
+    pub co_payer_index: u8,
+    /// Share of the fee paid by the co-payer, from 1 to 100 percent
+    pub co_payer_percent: u8,
+}
+
+impl FeePayerSplit {
+    /// Parses a compute budget instruction, returning `None` if it is not a fee payer split and
+    /// `Some(Err(()))` if it is an invalid one
+    pub(crate) fn try_from_instruction(instruction: &SVMInstruction) -> Option<Result<Self, ()>> {
+        let (&SET_FEE_PAYER_SPLIT_DISCRIMINANT, data) = instruction.data.split_first()? else {
+            return None;
+        };
+        Some(match (data, instruction.accounts) {
+            (&[co_payer_percent], &[co_payer_index]) if (1..=100).contains(&co_payer_percent) => {
+                Ok(Self {
+                    co_payer_index,
+                    co_payer_percent,
+                })
+            }
+            _ => Err(()),
+        })
+    }
+
+    /// Splits `fee` into the shares of the fee payer and of the co-payer, rounding the share of
+    /// the co-payer down
+    pub fn split(&self, fee: u64) -> (u64, u64) {
+        let co_payer_fee = (u128::from(fee) * u128::from(self.co_payer_percent) / 100) as u64;
+        (fee - co_payer_fee, co_payer_fee)
+    }
+}
+
+/// Returns the fee payer split requested by the instructions of a transaction whose compute
+/// budget instructions have been processed successfully
+pub fn get_fee_payer_split<'a>(
+    instructions: impl Iterator<Item = (&'a Pubkey, SVMInstruction<'a>)>,
+) -> Option<FeePayerSplit> {
+    let mut filter = ComputeBudgetProgramIdFilter::new();
+    instructions
+        .filter(|(program_id, instruction)| {
+            filter.is_compute_budget_program(instruction.program_id_index as usize, program_id)
+        })
+        .find_map(|(_, instruction)| FeePayerSplit::try_from_instruction(&instruction)?.ok())
+}
+
+/// Creates the instruction making `co_payer` pay `co_payer_percent` percent of the fee of the
+/// transaction
+pub fn set_fee_payer_split(co_payer: &Pubkey, co_payer_percent: u8) -> Instruction {
+    Instruction::new_with_bytes(
+        solana_sdk_ids::compute_budget::id(),
+        &[SET_FEE_PAYER_SPLIT_DISCRIMINANT, co_payer_percent],
+        vec![AccountMeta::new(*co_payer, true)],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_message::Message,
+        solana_svm_transaction::svm_message::SVMMessage,
+        solana_system_interface::instruction::transfer,
+        solana_transaction::{sanitized::SanitizedTransaction, Transaction},
+    };
+
+    #[test]
+    fn test_try_from_instruction() {
+        let instruction = |data: &'static [u8], accounts: &'static [u8]| SVMInstruction {
+            program_id_index: 0,
+            accounts,
+            data,
+        };
+        assert_eq!(
+            FeePayerSplit::try_from_instruction(&instruction(
+                &[SET_FEE_PAYER_SPLIT_DISCRIMINANT, 40],
+                &[2]
+            )),
+            Some(Ok(FeePayerSplit {
+                co_payer_index: 2,
+                co_payer_percent: 40,
+            }))
+        );
+        let invalid_instructions: [(&'static [u8], &'static [u8]); 6] = [
+            (&[SET_FEE_PAYER_SPLIT_DISCRIMINANT, 0], &[2]),
+            (&[SET_FEE_PAYER_SPLIT_DISCRIMINANT, 101], &[2]),
+            (&[SET_FEE_PAYER_SPLIT_DISCRIMINANT, 40, 0], &[2]),
+            (&[SET_FEE_PAYER_SPLIT_DISCRIMINANT], &[2]),
+            (&[SET_FEE_PAYER_SPLIT_DISCRIMINANT, 40], &[]),
+            (&[SET_FEE_PAYER_SPLIT_DISCRIMINANT, 40], &[2, 3]),
+        ];
+        for (data, accounts) in invalid_instructions {
+            assert_eq!(
+                FeePayerSplit::try_from_instruction(&instruction(data, accounts)),
+                Some(Err(()))
+            );
+        }
+        assert_eq!(
+            FeePayerSplit::try_from_instruction(&instruction(&[2, 0, 0, 0, 0], &[])),
+            None
+        );
+        assert_eq!(
+            FeePayerSplit::try_from_instruction(&instruction(&[], &[])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_split() {
+        let split = FeePayerSplit {
+            co_payer_index: 1,
+            co_payer_percent: 30,
+        };
+        assert_eq!(split.split(10_000), (7_000, 3_000));
+        assert_eq!(split.split(5), (4, 1));
+        assert_eq!(split.split(u64::MAX).1, u64::MAX / 100 * 30 + 4);
+        let split = FeePayerSplit {
+            co_payer_index: 1,
+            co_payer_percent: 100,
+        };
+        assert_eq!(split.split(5_000), (0, 5_000));
+    }
+
+    #[test]
+    fn test_get_fee_payer_split() {
+        let payer = Pubkey::new_unique();
+        let co_payer = Pubkey::new_unique();
+        let transaction = SanitizedTransaction::from_transaction_for_tests(
+            Transaction::new_unsigned(Message::new(
+                &[
+                    transfer(&payer, &Pubkey::new_unique(), 1),
+                    set_fee_payer_split(&co_payer, 25),
+                ],
+                Some(&payer),
+            )),
+        );
+        let split =
+            get_fee_payer_split(SVMMessage::program_instructions_iter(&transaction)).unwrap();
+        assert_eq!(split.co_payer_percent, 25);
+        assert_eq!(
+            transaction.account_keys()[usize::from(split.co_payer_index)],
+            co_payer
+        );
+
+        let transaction =
+            SanitizedTransaction::from_transaction_for_tests(Transaction::new_unsigned(
+                Message::new(&[transfer(&payer, &Pubkey::new_unique(), 1)], Some(&payer)),
+            ));
+        assert_eq!(
+            get_fee_payer_split(SVMMessage::program_instructions_iter(&transaction)),
+            None
+        );
+    }
+}
@@ -297,6 +297,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_process_raised_heap_frame_instruction() {
+        let mut feature_set = FeatureSet::all_enabled();
+        assert_eq!(max_heap_frame_bytes(&feature_set), X1_MAX_HEAP_FRAME_BYTES);
+        test!(
+            &[
+                Instruction::new_with_bincode(Pubkey::new_unique(), &0_u8, vec![]),
+                ComputeBudgetInstruction::request_heap_frame(X1_MAX_HEAP_FRAME_BYTES),
+            ],
+            Ok(ComputeBudgetLimits {
+                compute_unit_limit: DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT
+                    + MAX_BUILTIN_ALLOCATION_COMPUTE_UNIT_LIMIT,
+                updated_heap_bytes: X1_MAX_HEAP_FRAME_BYTES,
+                ..ComputeBudgetLimits::default()
+            }),
+            &feature_set
+        );
+        test!(
+            &[
+                Instruction::new_with_bincode(Pubkey::new_unique(), &0_u8, vec![]),
+                ComputeBudgetInstruction::request_heap_frame(X1_MAX_HEAP_FRAME_BYTES + 1024),
+            ],
+            Err(TransactionError::InstructionError(
+                1,
+                InstructionError::InvalidInstructionData,
+            )),
+            &feature_set
+        );
+
+        // Without the feature, heaps above MAX_HEAP_FRAME_BYTES are rejected
+        feature_set.deactivate(&agave_feature_set::raise_max_heap_frame_bytes::id());
+        assert_eq!(max_heap_frame_bytes(&feature_set), MAX_HEAP_FRAME_BYTES);
+        test!(
+            &[
+                Instruction::new_with_bincode(Pubkey::new_unique(), &0_u8, vec![]),
+                ComputeBudgetInstruction::request_heap_frame(MAX_HEAP_FRAME_BYTES + 1024),
+            ],
+            Err(TransactionError::InstructionError(
+                1,
+                InstructionError::InvalidInstructionData,
+            )),
+            &feature_set
+        );
+    }
+
     #[test]
     fn test_process_loaded_accounts_data_size_limit_instruction() {
         test!(
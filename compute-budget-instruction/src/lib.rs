@@ -3,4 +3,5 @@
 mod builtin_programs_filter;
 pub mod compute_budget_instruction_details;
 mod compute_budget_program_id_filter;
+pub mod fee_payer_split;
 pub mod instructions_processor;
@@ -14,6 +14,9 @@ impl ::solana_frozen_abi::abi_example::AbiExample for ComputeBudget {
 /// a transaction.
 pub const MAX_INSTRUCTION_STACK_DEPTH: usize = 5;
 
+/// Max instruction stack depth once the `raise_max_instruction_stack_depth` feature is active
+pub const X1_MAX_INSTRUCTION_STACK_DEPTH: usize = 8;
+
 /// Max call depth. This is the maximum nesting of SBF to SBF call that can happen within a program.
 pub const MAX_CALL_DEPTH: usize = 64;
 
@@ -129,6 +132,21 @@ pub struct ComputeBudget {
     pub alt_bn128_g2_compress: u64,
     /// Number of compute units consumed to call alt_bn128_g2_decompress.
     pub alt_bn128_g2_decompress: u64,
+    /// Number of compute units consumed to aggregate BLS12-381 G1 points.
+    /// The total cost is calculated as `base_cost + num_points * incremental_cost`.
+    pub bls12_381_g1_aggregate_base_cost: u64,
+    pub bls12_381_g1_aggregate_incremental_cost: u64,
+    /// Number of compute units consumed to aggregate BLS12-381 G2 points.
+    /// The total cost is calculated as `base_cost + num_points * incremental_cost`.
+    pub bls12_381_g2_aggregate_base_cost: u64,
+    pub bls12_381_g2_aggregate_incremental_cost: u64,
+    /// Number of compute units consumed to hash a message to a BLS12-381 G2 point, in addition
+    /// to `sha256_byte_cost` per byte of the message
+    pub bls12_381_hash_to_g2_cost: u64,
+    /// Total cost will be bls12_381_pairing_one_pair_cost_first
+    /// + bls12_381_pairing_one_pair_cost_other * (num_elems - 1)
+    pub bls12_381_pairing_one_pair_cost_first: u64,
+    pub bls12_381_pairing_one_pair_cost_other: u64,
 }
 
 impl Default for ComputeBudget {
@@ -196,6 +214,16 @@ impl ComputeBudget {
             alt_bn128_g1_decompress: 398,
             alt_bn128_g2_compress: 86,
             alt_bn128_g2_decompress: 13610,
+            // BLS12-381 costs derived from `cargo bench -p tachyon-bls12-381` at 1 CU per 33 ns,
+            // the median of three runs. The fixed part of an aggregation is below the noise of
+            // the measurement, so both aggregate base costs are the syscall base cost.
+            bls12_381_g1_aggregate_base_cost: 100,
+            bls12_381_g1_aggregate_incremental_cost: 5_011,
+            bls12_381_g2_aggregate_base_cost: 100,
+            bls12_381_g2_aggregate_incremental_cost: 10_849,
+            bls12_381_hash_to_g2_cost: 25_989,
+            bls12_381_pairing_one_pair_cost_first: 82_845,
+            bls12_381_pairing_one_pair_cost_other: 41_320,
         }
     }
 
@@ -12,6 +12,9 @@ pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
 pub const MAX_BUILTIN_ALLOCATION_COMPUTE_UNIT_LIMIT: u32 = 3_000;
 pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
 pub const MAX_HEAP_FRAME_BYTES: u32 = 256 * 1024;
+/// Max heap frame a transaction can request once the `raise_max_heap_frame_bytes` feature is
+/// active. The heap above `MAX_HEAP_FRAME_BYTES` is paid for with a per-byte fee.
+pub const X1_MAX_HEAP_FRAME_BYTES: u32 = 1024 * 1024;
 pub const MIN_HEAP_FRAME_BYTES: u32 = HEAP_LENGTH as u32;
 
 type MicroLamports = u128;
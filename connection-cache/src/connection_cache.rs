@@ -139,6 +139,24 @@ where
             .unwrap()
     }
 
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Snapshot the state of every pooled connection, keyed by remote address
+    pub fn connection_infos(&self) -> Vec<(SocketAddr, Vec<ConnectionInfo>)> {
+        let map = self.map.read().unwrap();
+        map.iter()
+            .map(|(addr, pool)| {
+                let infos = (0..pool.num_connections())
+                    .filter_map(|idx| pool.get(idx).ok())
+                    .map(|connection| connection.connection_info())
+                    .collect();
+                (*addr, infos)
+            })
+            .collect()
+    }
+
     pub fn update_key(&self, key: &Keypair) -> Result<(), Box<dyn std::error::Error>> {
         let mut map = self.map.write().unwrap();
         map.clear();
@@ -467,10 +485,24 @@ pub trait ConnectionPool: Send + Sync + 'static {
     ) -> Arc<Self::BaseClientConnection>;
 }
 
+/// Point-in-time view of a single pooled connection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    pub connected: bool,
+    pub open_streams: u64,
+    pub sent_batches: u64,
+    pub failed_batches: u64,
+}
+
 pub trait BaseClientConnection {
     type BlockingClientConnection: BlockingClientConnection;
     type NonblockingClientConnection: NonblockingClientConnection;
 
+    /// Report the connection state. Connectionless protocols keep the default.
+    fn connection_info(&self) -> ConnectionInfo {
+        ConnectionInfo::default()
+    }
+
     fn new_blocking_connection(
         &self,
         addr: SocketAddr,
@@ -773,4 +805,19 @@ mod tests {
             port
         );
     }
+
+    #[test]
+    fn test_connection_infos() {
+        let connection_manager = MockConnectionManager::default();
+        let connection_cache =
+            ConnectionCache::new("connection_cache_test", connection_manager, 1).unwrap();
+        assert!(connection_cache.connection_infos().is_empty());
+
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8000);
+        connection_cache.get_connection(&addr);
+        assert_eq!(
+            connection_cache.connection_infos(),
+            vec![(addr, vec![ConnectionInfo::default()])]
+        );
+    }
 }
@@ -28,7 +28,7 @@ use {
             unprocessed_transaction_storage::{ThreadType, UnprocessedTransactionStorage},
             BankingStage, BankingStageStats,
         },
-        banking_trace::BankingTracer,
+        banking_trace::{BankingCapture, BankingTracer},
     },
     solana_entry::entry::{next_hash, Entry},
     solana_gossip::cluster_info::{ClusterInfo, Node},
@@ -319,6 +319,7 @@ fn bench_banking(
         bank_forks,
         &Arc::new(PrioritizationFeeCache::new(0u64)),
         false,
+        BankingCapture::default(),
     );
 
     let chunk_len = verified.len() / CHUNKS;
@@ -0,0 +1,242 @@
+//! Fuzzes the deserialization of packets received by banking stage, from arbitrary bytes and
+//! from mutations of valid transactions, checking that it never panics and that the packets
+//! it accepts are consistent with their bytes.
+
+#![no_main]
+
+use {
+    arbitrary::Arbitrary,
+    libfuzzer_sys::fuzz_target,
+    solana_core::banking_stage::immutable_deserialized_packet::{
+        packet_message, ImmutableDeserializedPacket,
+    },
+    solana_perf::packet::{Meta, Packet, PACKET_DATA_SIZE},
+    solana_sdk::{
+        compute_budget::{self, ComputeBudgetInstruction},
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::{v0, Message, VersionedMessage},
+        pubkey::Pubkey,
+        signature::Signature,
+        system_program,
+        transaction::VersionedTransaction,
+    },
+};
+
+#[derive(Arbitrary, Debug)]
+enum Program {
+    System,
+    Vote,
+    ComputeBudget,
+    Other([u8; 32]),
+}
+
+#[derive(Arbitrary, Debug)]
+enum FuzzInstruction {
+    SetComputeUnitLimit(u32),
+    SetComputeUnitPrice(u64),
+    RequestHeapFrame(u32),
+    SetLoadedAccountsDataSizeLimit(u32),
+    Raw {
+        program: Program,
+        accounts: Vec<([u8; 32], bool, bool)>,
+        data: Vec<u8>,
+    },
+}
+
+impl FuzzInstruction {
+    fn into_instruction(self) -> Instruction {
+        match self {
+            Self::SetComputeUnitLimit(units) => {
+                ComputeBudgetInstruction::set_compute_unit_limit(units)
+            }
+            Self::SetComputeUnitPrice(price) => {
+                ComputeBudgetInstruction::set_compute_unit_price(price)
+            }
+            Self::RequestHeapFrame(bytes) => ComputeBudgetInstruction::request_heap_frame(bytes),
+            Self::SetLoadedAccountsDataSizeLimit(bytes) => {
+                ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(bytes)
+            }
+            Self::Raw {
+                program,
+                accounts,
+                data,
+            } => {
+                let program_id = match program {
+                    Program::System => system_program::id(),
+                    Program::Vote => solana_sdk_ids::vote::id(),
+                    Program::ComputeBudget => compute_budget::id(),
+                    Program::Other(bytes) => Pubkey::new_from_array(bytes),
+                };
+                let accounts = accounts
+                    .into_iter()
+                    .map(|(pubkey, is_signer, is_writable)| AccountMeta {
+                        pubkey: Pubkey::new_from_array(pubkey),
+                        is_signer,
+                        is_writable,
+                    })
+                    .collect();
+                Instruction::new_with_bytes(program_id, &data, accounts)
+            }
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+enum Mutation {
+    FlipByte { index: u16, mask: u8 },
+    SetByte { index: u16, value: u8 },
+    Insert { index: u16, bytes: Vec<u8> },
+    Remove { index: u16, len: u8 },
+    Truncate { len: u16 },
+}
+
+impl Mutation {
+    fn apply(self, bytes: &mut Vec<u8>) {
+        if bytes.is_empty() {
+            return;
+        }
+        let index = |index: u16| usize::from(index) % bytes.len();
+        match self {
+            Self::FlipByte { index: i, mask } => {
+                let i = index(i);
+                bytes[i] ^= mask;
+            }
+            Self::SetByte { index: i, value } => {
+                let i = index(i);
+                bytes[i] = value;
+            }
+            Self::Insert {
+                index: i,
+                bytes: inserted,
+            } => {
+                let i = index(i);
+                bytes.splice(i..i, inserted);
+            }
+            Self::Remove { index: i, len } => {
+                let start = index(i);
+                let end = start.saturating_add(usize::from(len)).min(bytes.len());
+                bytes.drain(start..end);
+            }
+            Self::Truncate { len } => bytes.truncate(usize::from(len)),
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+enum FuzzInput {
+    /// Arbitrary packet bytes
+    Raw { data: Vec<u8>, is_simple_vote: bool },
+    /// A valid transaction, then mutated
+    Transaction {
+        payer: [u8; 32],
+        recent_blockhash: [u8; 32],
+        num_signatures: u8,
+        instructions: Vec<FuzzInstruction>,
+        v0: bool,
+        is_simple_vote: bool,
+        mutations: Vec<Mutation>,
+    },
+}
+
+fn transaction_bytes(
+    payer: [u8; 32],
+    recent_blockhash: [u8; 32],
+    num_signatures: u8,
+    instructions: Vec<FuzzInstruction>,
+    v0: bool,
+) -> Option<Vec<u8>> {
+    let payer = Pubkey::new_from_array(payer);
+    let recent_blockhash = Hash::new_from_array(recent_blockhash);
+    let instructions: Vec<_> = instructions
+        .into_iter()
+        .map(FuzzInstruction::into_instruction)
+        .collect();
+    let message = if v0 {
+        VersionedMessage::V0(
+            v0::Message::try_compile(&payer, &instructions, &[], recent_blockhash).ok()?,
+        )
+    } else {
+        VersionedMessage::Legacy(Message::new_with_blockhash(
+            &instructions,
+            Some(&payer),
+            &recent_blockhash,
+        ))
+    };
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default(); usize::from(num_signatures)],
+        message,
+    };
+    bincode::serialize(&transaction).ok()
+}
+
+fn new_packet(bytes: &[u8], is_simple_vote: bool) -> Packet {
+    let size = bytes.len().min(PACKET_DATA_SIZE);
+    let mut buffer = [0u8; PACKET_DATA_SIZE];
+    buffer[..size].copy_from_slice(&bytes[..size]);
+    let mut meta = Meta {
+        size,
+        ..Meta::default()
+    };
+    meta.set_simple_vote(is_simple_vote);
+    Packet::new(buffer, meta)
+}
+
+fn check_packet(packet: Packet) {
+    let data = packet.data(..).unwrap().to_vec();
+
+    // The message is a suffix of the packet data
+    if let Ok(message) = packet_message(&packet) {
+        assert!(data.ends_with(message));
+    }
+
+    let Ok(deserialized) = ImmutableDeserializedPacket::new(packet) else {
+        return;
+    };
+    let message = packet_message(deserialized.original_packet()).unwrap();
+    assert_eq!(
+        *deserialized.message_hash(),
+        VersionedMessage::hash_raw_message(message)
+    );
+    let transaction = deserialized.transaction();
+    assert_eq!(
+        *deserialized.signature(),
+        transaction
+            .get_signatures()
+            .first()
+            .copied()
+            .unwrap_or_default()
+    );
+    assert!(message.starts_with(&transaction.get_message().message.serialize()));
+    if deserialized.is_simple_vote() {
+        assert_eq!(deserialized.compute_unit_price(), 0);
+    }
+}
+
+fuzz_target!(|input: FuzzInput| {
+    match input {
+        FuzzInput::Raw {
+            data,
+            is_simple_vote,
+        } => check_packet(new_packet(&data, is_simple_vote)),
+        FuzzInput::Transaction {
+            payer,
+            recent_blockhash,
+            num_signatures,
+            instructions,
+            v0,
+            is_simple_vote,
+            mutations,
+        } => {
+            let Some(mut bytes) =
+                transaction_bytes(payer, recent_blockhash, num_signatures, instructions, v0)
+            else {
+                return;
+            };
+            for mutation in mutations {
+                mutation.apply(&mut bytes);
+            }
+            check_packet(new_packet(&bytes, is_simple_vote));
+        }
+    }
+});
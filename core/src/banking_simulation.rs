@@ -5,8 +5,8 @@ use {
             update_bank_forks_and_poh_recorder_for_new_tpu_bank, BankingStage, LikeClusterInfo,
         },
         banking_trace::{
-            BankingTracer, ChannelLabel, Channels, TimedTracedEvent, TracedEvent, TracedSender,
-            TracerThread, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT, BASENAME,
+            BankingCapture, BankingTracer, ChannelLabel, Channels, TimedTracedEvent, TracedEvent,
+            TracedSender, TracerThread, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT,
         },
         validator::{BlockProductionMethod, TransactionStructure},
     },
@@ -837,6 +837,7 @@ impl BankingSimulator {
             bank_forks.clone(),
             prioritization_fee_cache,
             false,
+            BankingCapture::default(),
         );
 
         let (&_slot, &raw_base_event_time) = freeze_time_by_slot
@@ -934,10 +935,6 @@ impl BankingSimulator {
     }
 
     pub fn event_file_name(index: usize) -> String {
-        if index == 0 {
-            BASENAME.to_string()
-        } else {
-            format!("{BASENAME}.{index}")
-        }
+        BankingTracer::event_file_name(index)
     }
 }
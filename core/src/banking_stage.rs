@@ -18,13 +18,15 @@ use {
     },
     crate::{
         banking_stage::{
-            consume_worker::ConsumeWorker,
+            consume_worker::{ConsumeWorker, ConsumeWorkerMetrics},
             packet_deserializer::PacketDeserializer,
             transaction_scheduler::{
-                prio_graph_scheduler::PrioGraphScheduler,
-                scheduler_controller::SchedulerController, scheduler_error::SchedulerError,
+                scheduler::{TransactionScheduler, TransactionSchedulerFactory},
+                scheduler_controller::SchedulerController,
+                scheduler_error::SchedulerError,
             },
         },
+        banking_trace::BankingCapture,
         validator::{BlockProductionMethod, TransactionStructure},
     },
     agave_banking_stage_ingress_types::BankingPacketReceiver,
@@ -41,6 +43,7 @@ use {
         vote_sender_types::ReplayVoteSender,
     },
     solana_sdk::{pubkey::Pubkey, timing::AtomicInterval},
+    solana_send_transaction_service::transaction_ingestion_log::TransactionIngestionLog,
     std::{
         cmp, env,
         ops::Deref,
@@ -52,7 +55,7 @@ use {
         time::{Duration, Instant},
     },
     transaction_scheduler::{
-        greedy_scheduler::{GreedyScheduler, GreedySchedulerConfig},
+        greedy_scheduler::GreedySchedulerConfig,
         prio_graph_scheduler::PrioGraphSchedulerConfig,
         receive_and_buffer::{
             ReceiveAndBuffer, SanitizedTransactionReceiveAndBuffer, TransactionViewReceiveAndBuffer,
@@ -63,6 +66,7 @@ use {
 
 // Below modules are pub to allow use by banking_stage bench
 pub mod committer;
+pub mod conflict_graph;
 pub mod consumer;
 pub mod forwarder;
 pub mod leader_slot_metrics;
@@ -70,10 +74,14 @@ pub mod qos_service;
 pub mod unprocessed_packet_batches;
 pub mod unprocessed_transaction_storage;
 
+// Public to allow transaction schedulers outside of this crate
+pub mod scheduler_messages;
+pub mod scheduler_replay;
+pub mod transaction_scheduler;
+
 mod consume_worker;
 mod decision_maker;
 mod forward_packet_batches_by_accounts;
-mod immutable_deserialized_packet;
 mod latest_unprocessed_votes;
 mod leader_slot_timing_metrics;
 mod multi_iterator_scanner;
@@ -81,8 +89,6 @@ mod packet_deserializer;
 mod packet_filter;
 mod packet_receiver;
 mod read_write_account_set;
-mod scheduler_messages;
-mod transaction_scheduler;
 
 // proc_macro_hygiene needs to be stabilzied to use qualifier_attr...
 // error[E0658]: non-inline modules in proc macro input are unstable
@@ -90,6 +96,11 @@ mod transaction_scheduler;
 pub(crate) mod unified_scheduler;
 #[cfg(feature = "dev-context-only-utils")]
 pub mod unified_scheduler;
+// Public to allow use by the packet deserialization fuzz target
+#[cfg(not(feature = "dev-context-only-utils"))]
+mod immutable_deserialized_packet;
+#[cfg(feature = "dev-context-only-utils")]
+pub mod immutable_deserialized_packet;
 
 // Fixed thread size seems to be fastest on GCP setup
 pub const NUM_THREADS: u32 = 6;
@@ -117,6 +128,7 @@ pub struct BankingStageStats {
     consumed_buffered_packets_count: AtomicUsize,
     forwarded_transaction_count: AtomicUsize,
     forwarded_vote_count: AtomicUsize,
+    max_receive_queue_depth: AtomicUsize,
     batch_packet_indexes_len: Histogram,
 
     // Timing
@@ -163,6 +175,7 @@ impl BankingStageStats {
             + self.transaction_processing_elapsed.load(Ordering::Relaxed)
             + self.forwarded_transaction_count.load(Ordering::Relaxed) as u64
             + self.forwarded_vote_count.load(Ordering::Relaxed) as u64
+            + self.max_receive_queue_depth.load(Ordering::Relaxed) as u64
             + self.batch_packet_indexes_len.entries()
     }
 
@@ -236,6 +249,11 @@ impl BankingStageStats {
                     self.forwarded_vote_count.swap(0, Ordering::Relaxed),
                     i64
                 ),
+                (
+                    "max_receive_queue_depth",
+                    self.max_receive_queue_depth.swap(0, Ordering::Relaxed),
+                    i64
+                ),
                 (
                     "consume_buffered_packets_elapsed",
                     self.consume_buffered_packets_elapsed
@@ -369,6 +387,7 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         enable_forwarding: bool,
+        banking_capture: BankingCapture,
     ) -> Self {
         Self::new_num_threads(
             block_production_method,
@@ -386,6 +405,7 @@ impl BankingStage {
             bank_forks,
             prioritization_fee_cache,
             enable_forwarding,
+            banking_capture,
         )
     }
 
@@ -406,39 +426,54 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         enable_forwarding: bool,
+        banking_capture: BankingCapture,
     ) -> Self {
         match block_production_method {
-            BlockProductionMethod::CentralScheduler
-            | BlockProductionMethod::CentralSchedulerGreedy => {
-                let use_greedy_scheduler = matches!(
-                    block_production_method,
-                    BlockProductionMethod::CentralSchedulerGreedy
-                );
-                Self::new_central_scheduler(
-                    transaction_struct,
-                    use_greedy_scheduler,
-                    cluster_info,
-                    poh_recorder,
-                    non_vote_receiver,
-                    tpu_vote_receiver,
-                    gossip_vote_receiver,
-                    num_threads,
-                    transaction_status_sender,
-                    replay_vote_sender,
-                    log_messages_bytes_limit,
-                    connection_cache,
-                    bank_forks,
-                    prioritization_fee_cache,
-                    enable_forwarding,
-                )
-            }
+            BlockProductionMethod::CentralScheduler => Self::new_central_scheduler(
+                transaction_struct,
+                PrioGraphSchedulerConfig::default(),
+                cluster_info,
+                poh_recorder,
+                non_vote_receiver,
+                tpu_vote_receiver,
+                gossip_vote_receiver,
+                num_threads,
+                transaction_status_sender,
+                replay_vote_sender,
+                log_messages_bytes_limit,
+                connection_cache,
+                bank_forks,
+                prioritization_fee_cache,
+                enable_forwarding,
+                banking_capture,
+            ),
+            BlockProductionMethod::CentralSchedulerGreedy => Self::new_central_scheduler(
+                transaction_struct,
+                GreedySchedulerConfig::default(),
+                cluster_info,
+                poh_recorder,
+                non_vote_receiver,
+                tpu_vote_receiver,
+                gossip_vote_receiver,
+                num_threads,
+                transaction_status_sender,
+                replay_vote_sender,
+                log_messages_bytes_limit,
+                connection_cache,
+                bank_forks,
+                prioritization_fee_cache,
+                enable_forwarding,
+                banking_capture,
+            ),
         }
     }
 
+    /// Spawns a banking stage whose central scheduler thread runs the scheduler built by
+    /// `scheduler_factory`, which need not be one of the `BlockProductionMethod`s
     #[allow(clippy::too_many_arguments)]
     pub fn new_central_scheduler(
         transaction_struct: TransactionStructure,
-        use_greedy_scheduler: bool,
+        scheduler_factory: impl TransactionSchedulerFactory,
         cluster_info: &impl LikeClusterInfo,
         poh_recorder: &Arc<RwLock<PohRecorder>>,
         non_vote_receiver: BankingPacketReceiver,
@@ -452,12 +487,16 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         enable_forwarding: bool,
+        banking_capture: BankingCapture,
     ) -> Self {
         assert!(num_threads >= MIN_TOTAL_THREADS);
         // Single thread to generate entries from many banks.
         // This thread talks to poh_service and broadcasts the entries once they have been recorded.
         // Once an entry has been recorded, its blockhash is registered with the bank.
         let data_budget = Arc::new(DataBudget::default());
+        // Votes are forwarded against their own budget so that forwarding bursts of user
+        // transactions can't hold back vote forwarding.
+        let vote_data_budget = Arc::new(DataBudget::default());
         // Keeps track of extraneous vote transactions for the vote threads
         let latest_unprocessed_votes = {
             let bank = bank_forks.read().unwrap().working_bank();
@@ -492,7 +531,7 @@ impl BankingStage {
                     bank_forks.clone(),
                     cluster_info.clone(),
                     connection_cache.clone(),
-                    data_budget.clone(),
+                    vote_data_budget.clone(),
                 ),
                 UnprocessedTransactionStorage::new_vote_storage(
                     latest_unprocessed_votes.clone(),
@@ -511,17 +550,19 @@ impl BankingStage {
                 transaction_struct
             };
 
+        let ingestion_log = banking_capture.ingestion_log().cloned();
         match transaction_struct {
             TransactionStructure::Sdk => {
                 let receive_and_buffer = SanitizedTransactionReceiveAndBuffer::new(
                     PacketDeserializer::new(non_vote_receiver),
                     bank_forks.clone(),
                     enable_forwarding,
+                    banking_capture,
                 );
                 Self::spawn_scheduler_and_workers(
                     &mut bank_thread_hdls,
                     receive_and_buffer,
-                    use_greedy_scheduler,
+                    scheduler_factory,
                     decision_maker,
                     committer,
                     cluster_info,
@@ -532,17 +573,19 @@ impl BankingStage {
                     bank_forks,
                     enable_forwarding,
                     data_budget,
+                    ingestion_log,
                 );
             }
             TransactionStructure::View => {
-                let receive_and_buffer = TransactionViewReceiveAndBuffer {
-                    receiver: non_vote_receiver,
-                    bank_forks: bank_forks.clone(),
-                };
+                let receive_and_buffer = TransactionViewReceiveAndBuffer::new(
+                    non_vote_receiver,
+                    bank_forks.clone(),
+                    banking_capture,
+                );
                 Self::spawn_scheduler_and_workers(
                     &mut bank_thread_hdls,
                     receive_and_buffer,
-                    use_greedy_scheduler,
+                    scheduler_factory,
                     decision_maker,
                     committer,
                     cluster_info,
@@ -553,6 +596,7 @@ impl BankingStage {
                     bank_forks,
                     enable_forwarding,
                     data_budget,
+                    ingestion_log,
                 );
             }
         }
@@ -564,7 +608,7 @@ impl BankingStage {
     fn spawn_scheduler_and_workers<R: ReceiveAndBuffer + Send + Sync + 'static>(
         bank_thread_hdls: &mut Vec<JoinHandle<()>>,
         receive_and_buffer: R,
-        use_greedy_scheduler: bool,
+        scheduler_factory: impl TransactionSchedulerFactory,
         decision_maker: DecisionMaker,
         committer: Committer,
         cluster_info: &impl LikeClusterInfo,
@@ -575,6 +619,7 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         enable_forwarding: bool,
         data_budget: Arc<DataBudget>,
+        ingestion_log: Option<Arc<TransactionIngestionLog>>,
     ) {
         // Create channels for communication between scheduler and workers
         let num_workers = (num_threads).saturating_sub(NUM_VOTE_PROCESSING_THREADS);
@@ -597,6 +642,7 @@ impl BankingStage {
                 ),
                 finished_work_sender.clone(),
                 poh_recorder.read().unwrap().new_leader_bank_notifier(),
+                ingestion_log.clone(),
             );
 
             worker_metrics.push(consume_worker.metrics_handle());
@@ -621,65 +667,54 @@ impl BankingStage {
         });
 
         // Spawn the central scheduler thread
-        if use_greedy_scheduler {
-            bank_thread_hdls.push(
-                Builder::new()
-                    .name("solBnkTxSched".to_string())
-                    .spawn(move || {
-                        let scheduler = GreedyScheduler::new(
-                            work_senders,
-                            finished_work_receiver,
-                            GreedySchedulerConfig::default(),
-                        );
-                        let scheduler_controller = SchedulerController::new(
-                            decision_maker.clone(),
-                            receive_and_buffer,
-                            bank_forks,
-                            scheduler,
-                            worker_metrics,
-                            forwarder,
-                        );
-
-                        match scheduler_controller.run() {
-                            Ok(_) => {}
-                            Err(SchedulerError::DisconnectedRecvChannel(_)) => {}
-                            Err(SchedulerError::DisconnectedSendChannel(_)) => {
-                                warn!("Unexpected worker disconnect from scheduler")
-                            }
-                        }
-                    })
-                    .unwrap(),
-            );
-        } else {
-            bank_thread_hdls.push(
-                Builder::new()
-                    .name("solBnkTxSched".to_string())
-                    .spawn(move || {
-                        let scheduler = PrioGraphScheduler::new(
-                            work_senders,
-                            finished_work_receiver,
-                            PrioGraphSchedulerConfig::default(),
-                        );
-                        let scheduler_controller = SchedulerController::new(
-                            decision_maker.clone(),
-                            receive_and_buffer,
-                            bank_forks,
-                            scheduler,
-                            worker_metrics,
-                            forwarder,
-                        );
-
-                        match scheduler_controller.run() {
-                            Ok(_) => {}
-                            Err(SchedulerError::DisconnectedRecvChannel(_)) => {}
-                            Err(SchedulerError::DisconnectedSendChannel(_)) => {
-                                warn!("Unexpected worker disconnect from scheduler")
-                            }
+        Self::spawn_scheduler_thread(
+            bank_thread_hdls,
+            move || scheduler_factory.new_scheduler(work_senders, finished_work_receiver),
+            decision_maker,
+            receive_and_buffer,
+            bank_forks,
+            worker_metrics,
+            forwarder,
+        );
+    }
+
+    /// Spawns the central scheduler thread, running the scheduler built by `new_scheduler`
+    fn spawn_scheduler_thread<C, R, S>(
+        bank_thread_hdls: &mut Vec<JoinHandle<()>>,
+        new_scheduler: impl FnOnce() -> S + Send + 'static,
+        decision_maker: DecisionMaker,
+        receive_and_buffer: R,
+        bank_forks: Arc<RwLock<BankForks>>,
+        worker_metrics: Vec<Arc<ConsumeWorkerMetrics>>,
+        forwarder: Option<Forwarder<C>>,
+    ) where
+        C: LikeClusterInfo,
+        R: ReceiveAndBuffer + Send + Sync + 'static,
+        S: TransactionScheduler<R::Transaction>,
+    {
+        bank_thread_hdls.push(
+            Builder::new()
+                .name("solBnkTxSched".to_string())
+                .spawn(move || {
+                    let scheduler_controller = SchedulerController::new(
+                        decision_maker,
+                        receive_and_buffer,
+                        bank_forks,
+                        new_scheduler(),
+                        worker_metrics,
+                        forwarder,
+                    );
+
+                    match scheduler_controller.run() {
+                        Ok(_) => {}
+                        Err(SchedulerError::DisconnectedRecvChannel(_)) => {}
+                        Err(SchedulerError::DisconnectedSendChannel(_)) => {
+                            warn!("Unexpected worker disconnect from scheduler")
                         }
-                    })
-                    .unwrap(),
-            );
-        }
+                    }
+                })
+                .unwrap(),
+        );
     }
 
     fn spawn_thread_local_multi_iterator_thread<T: LikeClusterInfo>(
@@ -877,7 +912,10 @@ mod tests {
             },
             poh_service::PohService,
         },
-        solana_runtime::{bank::Bank, genesis_utils::bootstrap_validator_stake_lamports},
+        solana_runtime::{
+            bank::Bank,
+            genesis_utils::{bootstrap_validator_stake_lamports, deactivate_features},
+        },
         solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
         solana_sdk::{
             hash::Hash,
@@ -955,6 +993,7 @@ mod tests {
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
                 false,
+                BankingCapture::default(),
             );
             drop(non_vote_sender);
             drop(tpu_vote_sender);
@@ -1017,6 +1056,7 @@ mod tests {
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
                 false,
+                BankingCapture::default(),
             );
             trace!("sending bank");
             drop(non_vote_sender);
@@ -1099,6 +1139,7 @@ mod tests {
                 bank_forks.clone(), // keep a local-copy of bank-forks so worker threads do not lose weak access to bank-forks
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
                 false,
+                BankingCapture::default(),
             );
 
             // fund another account so we can send 2 good transactions in a single batch.
@@ -1269,6 +1310,7 @@ mod tests {
                     bank_forks,
                     &Arc::new(PrioritizationFeeCache::new(0u64)),
                     false,
+                    BankingCapture::default(),
                 );
 
                 // wait for banking_stage to eat the packets
@@ -1390,6 +1432,11 @@ mod tests {
 
         // For these tests there's only 1 slot, don't want to run out of ticks
         config_info.genesis_config.ticks_per_slot *= 1024;
+        // Prioritized transfers in these tests bid below the minimum compute unit price
+        deactivate_features(
+            &mut config_info.genesis_config,
+            &vec![agave_feature_set::enforce_min_compute_unit_price::id()],
+        );
         config_info
     }
 
@@ -1461,6 +1508,7 @@ mod tests {
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
                 false,
+                BankingCapture::default(),
             );
 
             let keypairs = (0..100).map(|_| Keypair::new()).collect_vec();
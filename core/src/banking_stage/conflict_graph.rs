@@ -0,0 +1,437 @@
+//! Samples of the account conflict graph of the transactions scheduled by the banking stage.
+//!
+//! Once armed, the process-wide [`ConflictGraphSampler`] records the account locks of the
+//! transactions the scheduler tries to schedule during one leader slot. The [`ConflictGraph`]
+//! built from such a sample links every transaction to the earlier transactions it conflicts
+//! with, and summarizes how parallelizable the slot was, how much read locks were shared, and
+//! which accounts serialized it, so that program developers can reduce their write-lock
+//! contention. The graph is written as DOT and JSON files through the admin RPC service.
+
+use {
+    ahash::{AHashMap, AHashSet},
+    solana_sdk::{clock::Slot, pubkey::Pubkey, signature::Signature},
+    solana_svm_transaction::svm_transaction::SVMTransaction,
+    std::{
+        fs,
+        io::{self, BufWriter, Write},
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            LazyLock, Mutex,
+        },
+    },
+};
+
+pub const DEFAULT_MAX_SAMPLED_TRANSACTIONS: usize = 10_000;
+/// Number of accounts reported by [`ConflictGraphSummary::hot_accounts`]
+const MAX_HOT_ACCOUNTS: usize = 20;
+
+/// The account locks of a sampled transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SampledTransaction {
+    pub signature: Signature,
+    pub write_locks: Vec<Pubkey>,
+    pub read_locks: Vec<Pubkey>,
+}
+
+impl SampledTransaction {
+    pub fn new(transaction: &impl SVMTransaction) -> Self {
+        let mut write_locks = vec![];
+        let mut read_locks = vec![];
+        for (index, key) in transaction.account_keys().iter().enumerate() {
+            if transaction.is_writable(index) {
+                write_locks.push(*key);
+            } else {
+                read_locks.push(*key);
+            }
+        }
+        Self {
+            signature: *transaction.signature(),
+            write_locks,
+            read_locks,
+        }
+    }
+}
+
+#[derive(Default)]
+struct SamplerState {
+    /// The first leader slot at or after this slot is sampled
+    min_slot: Slot,
+    max_transactions: usize,
+    sampling_slot: Option<Slot>,
+    signatures: AHashSet<Signature>,
+    transactions: Vec<SampledTransaction>,
+    completed: Option<(Slot, Vec<SampledTransaction>)>,
+}
+
+/// Records the transactions scheduled during a leader slot, when armed
+#[derive(Default)]
+pub struct ConflictGraphSampler {
+    armed: AtomicBool,
+    state: Mutex<SamplerState>,
+}
+
+impl ConflictGraphSampler {
+    /// Samples up to `max_transactions` transactions of the first leader slot at or after
+    /// `min_slot`, discarding any previous sample
+    pub fn arm(&self, min_slot: Slot, max_transactions: usize) {
+        *self.state.lock().unwrap() = SamplerState {
+            min_slot,
+            max_transactions,
+            ..SamplerState::default()
+        };
+        self.armed.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether a sample was requested and its slot isn't over yet
+    pub fn is_sampling(&self) -> bool {
+        self.armed.load(Ordering::Relaxed)
+    }
+
+    /// Records `transaction`, about to be scheduled in `slot`
+    pub fn record(&self, slot: Slot, transaction: &impl SVMTransaction) {
+        if !self.armed.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        if state.sampling_slot.is_none() && slot >= state.min_slot {
+            state.sampling_slot = Some(slot);
+        }
+        if state.sampling_slot != Some(slot)
+            || state.transactions.len() >= state.max_transactions
+            // Transactions retried after conflicts are scheduled again
+            || !state.signatures.insert(*transaction.signature())
+        {
+            return;
+        }
+        state
+            .transactions
+            .push(SampledTransaction::new(transaction));
+    }
+
+    /// Completes the sample once the scheduler is not producing its slot anymore
+    pub fn end_slot(&self, leader_slot: Option<Slot>) {
+        if !self.armed.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        let Some(sampling_slot) = state.sampling_slot else {
+            return;
+        };
+        if leader_slot != Some(sampling_slot) {
+            let transactions = std::mem::take(&mut state.transactions);
+            state.completed = Some((sampling_slot, transactions));
+            self.armed.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the conflict graph of the completed sample, if any
+    pub fn take_sample(&self) -> Option<ConflictGraph> {
+        let (slot, transactions) = self.state.lock().unwrap().completed.take()?;
+        Some(ConflictGraph::new(slot, transactions))
+    }
+}
+
+static CONFLICT_GRAPH_SAMPLER: LazyLock<ConflictGraphSampler> =
+    LazyLock::new(ConflictGraphSampler::default);
+
+/// The conflict graph sampler of the process
+pub fn conflict_graph_sampler() -> &'static ConflictGraphSampler {
+    &CONFLICT_GRAPH_SAMPLER
+}
+
+/// Locks taken on an account by the sampled transactions
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountLockStats {
+    pub pubkey: String,
+    pub write_locks: usize,
+    pub read_locks: usize,
+    /// Conflicts between transactions caused by this account
+    pub conflicts: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictGraphSummary {
+    pub slot: Slot,
+    pub transactions: usize,
+    /// Pairs of transactions which can't be executed in parallel, not counting the conflicts
+    /// implied by others
+    pub conflicts: usize,
+    /// Transactions of the longest chain of conflicting transactions
+    pub depth: usize,
+    /// Transactions which could be executed in parallel on average, i.e. `transactions / depth`
+    pub average_parallel_width: f64,
+    pub accounts: usize,
+    /// Accounts read-locked by several transactions and never write-locked
+    pub shared_read_accounts: usize,
+    /// Read locks taken on the shared read accounts, none of which caused a conflict
+    pub shared_read_locks: usize,
+    /// Accounts which caused the most conflicts, by descending conflicts
+    pub hot_accounts: Vec<AccountLockStats>,
+}
+
+#[derive(Default)]
+struct AccountState {
+    last_writer: Option<usize>,
+    readers_since_write: Vec<usize>,
+    write_locks: usize,
+    read_locks: usize,
+    conflicts: usize,
+}
+
+/// The transactions of a sample, each linked to the earlier transactions it conflicts with
+pub struct ConflictGraph {
+    slot: Slot,
+    transactions: Vec<SampledTransaction>,
+    /// `(earlier, later)` indexes of conflicting transactions
+    edges: Vec<(usize, usize)>,
+    /// Position of every transaction in the longest chain of conflicts ending with it, from 1
+    levels: Vec<usize>,
+    accounts: AHashMap<Pubkey, AccountState>,
+}
+
+impl ConflictGraph {
+    /// Builds the graph of `transactions`, in scheduling order. Like the scheduler, only the
+    /// conflicts with the last writer of every account and the readers since are kept: the
+    /// others are implied by them.
+    pub fn new(slot: Slot, transactions: Vec<SampledTransaction>) -> Self {
+        let mut accounts = AHashMap::<Pubkey, AccountState>::new();
+        let mut edges = vec![];
+        let mut levels = Vec::with_capacity(transactions.len());
+        let mut predecessors = AHashSet::new();
+        for (index, transaction) in transactions.iter().enumerate() {
+            predecessors.clear();
+            let mut add_conflict = |account: &mut AccountState, earlier: usize| {
+                account.conflicts += 1;
+                predecessors.insert(earlier);
+            };
+            for key in &transaction.write_locks {
+                let account = accounts.entry(*key).or_default();
+                if let Some(last_writer) = account.last_writer {
+                    add_conflict(account, last_writer);
+                }
+                for reader in std::mem::take(&mut account.readers_since_write) {
+                    add_conflict(account, reader);
+                }
+                account.last_writer = Some(index);
+                account.write_locks += 1;
+            }
+            for key in &transaction.read_locks {
+                let account = accounts.entry(*key).or_default();
+                if let Some(last_writer) = account.last_writer {
+                    add_conflict(account, last_writer);
+                }
+                account.readers_since_write.push(index);
+                account.read_locks += 1;
+            }
+            let level = predecessors
+                .iter()
+                .map(|earlier| levels[*earlier])
+                .max()
+                .unwrap_or_default()
+                + 1;
+            levels.push(level);
+            let mut sorted_predecessors: Vec<_> = predecessors.iter().copied().collect();
+            sorted_predecessors.sort_unstable();
+            edges.extend(
+                sorted_predecessors
+                    .into_iter()
+                    .map(|earlier| (earlier, index)),
+            );
+        }
+        Self {
+            slot,
+            transactions,
+            edges,
+            levels,
+            accounts,
+        }
+    }
+
+    pub fn slot(&self) -> Slot {
+        self.slot
+    }
+
+    pub fn summary(&self) -> ConflictGraphSummary {
+        let depth = self.levels.iter().copied().max().unwrap_or_default();
+        let (shared_read_accounts, shared_read_locks) = self
+            .accounts
+            .values()
+            .filter(|account| account.write_locks == 0 && account.read_locks > 1)
+            .fold((0, 0), |(accounts, locks), account| {
+                (accounts + 1, locks + account.read_locks)
+            });
+        let mut hot_accounts: Vec<_> = self
+            .accounts
+            .iter()
+            .filter(|(_, account)| account.conflicts > 0)
+            .map(|(pubkey, account)| AccountLockStats {
+                pubkey: pubkey.to_string(),
+                write_locks: account.write_locks,
+                read_locks: account.read_locks,
+                conflicts: account.conflicts,
+            })
+            .collect();
+        hot_accounts.sort_by(|a, b| {
+            b.conflicts
+                .cmp(&a.conflicts)
+                .then_with(|| a.pubkey.cmp(&b.pubkey))
+        });
+        hot_accounts.truncate(MAX_HOT_ACCOUNTS);
+        ConflictGraphSummary {
+            slot: self.slot,
+            transactions: self.transactions.len(),
+            conflicts: self.edges.len(),
+            depth,
+            average_parallel_width: if depth == 0 {
+                0.
+            } else {
+                self.transactions.len() as f64 / depth as f64
+            },
+            accounts: self.accounts.len(),
+            shared_read_accounts,
+            shared_read_locks,
+            hot_accounts,
+        }
+    }
+
+    /// Writes the graph in the DOT language, transactions being labeled by their signature and
+    /// ranked by their level
+    pub fn write_dot(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        writeln!(writer, "digraph \"conflicts of slot {}\" {{", self.slot)?;
+        writeln!(writer, "  rankdir=LR;")?;
+        writeln!(writer, "  node [shape=box, fontname=monospace];")?;
+        for (index, transaction) in self.transactions.iter().enumerate() {
+            let signature = transaction.signature.to_string();
+            writeln!(
+                writer,
+                "  {index} [label=\"{}\\nlevel {}\"];",
+                &signature[..signature.len().min(8)],
+                self.levels[index],
+            )?;
+        }
+        for (earlier, later) in &self.edges {
+            writeln!(writer, "  {earlier} -> {later};")?;
+        }
+        writeln!(writer, "}}")?;
+        writer.flush()
+    }
+
+    /// Writes the summary, transactions and conflicts of the graph as JSON
+    pub fn write_json(&self, path: &Path) -> io::Result<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct JsonTransaction {
+            signature: String,
+            level: usize,
+            write_locks: Vec<String>,
+            read_locks: Vec<String>,
+        }
+        #[derive(Serialize)]
+        struct JsonConflictGraph {
+            summary: ConflictGraphSummary,
+            transactions: Vec<JsonTransaction>,
+            conflicts: Vec<(usize, usize)>,
+        }
+
+        let graph = JsonConflictGraph {
+            summary: self.summary(),
+            transactions: self
+                .transactions
+                .iter()
+                .zip(&self.levels)
+                .map(|(transaction, level)| JsonTransaction {
+                    signature: transaction.signature.to_string(),
+                    level: *level,
+                    write_locks: transaction
+                        .write_locks
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                    read_locks: transaction
+                        .read_locks
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                })
+                .collect(),
+            conflicts: self.edges.clone(),
+        };
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        serde_json::to_writer(&mut writer, &graph)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::TempDir};
+
+    fn sampled_transaction(write_locks: &[Pubkey], read_locks: &[Pubkey]) -> SampledTransaction {
+        SampledTransaction {
+            signature: Signature::new_unique(),
+            write_locks: write_locks.to_vec(),
+            read_locks: read_locks.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_conflict_graph() {
+        let [hot, oracle, a, b, c] = std::array::from_fn(|_| Pubkey::new_unique());
+        let graph = ConflictGraph::new(
+            42,
+            vec![
+                // Transactions writing to `hot` are chained, those only reading `oracle` are not
+                sampled_transaction(&[hot], &[oracle]),
+                sampled_transaction(&[hot, a], &[oracle]),
+                sampled_transaction(&[b], &[oracle]),
+                sampled_transaction(&[hot], &[]),
+                sampled_transaction(&[c], &[a]),
+            ],
+        );
+        assert_eq!(graph.edges, vec![(0, 1), (1, 3), (1, 4)]);
+        assert_eq!(graph.levels, vec![1, 2, 1, 3, 3]);
+
+        let summary = graph.summary();
+        assert_eq!(summary.slot, 42);
+        assert_eq!(summary.transactions, 5);
+        assert_eq!(summary.conflicts, 3);
+        assert_eq!(summary.depth, 3);
+        assert_eq!(summary.average_parallel_width, 5. / 3.);
+        assert_eq!(summary.accounts, 5);
+        assert_eq!(summary.shared_read_accounts, 1);
+        assert_eq!(summary.shared_read_locks, 3);
+        assert_eq!(
+            summary.hot_accounts[0],
+            AccountLockStats {
+                pubkey: hot.to_string(),
+                write_locks: 3,
+                read_locks: 0,
+                conflicts: 2,
+            }
+        );
+        assert_eq!(summary.hot_accounts.len(), 2);
+
+        let dir = TempDir::new().unwrap();
+        graph.write_dot(&dir.path().join("graph.dot")).unwrap();
+        let dot = fs::read_to_string(dir.path().join("graph.dot")).unwrap();
+        assert!(dot.starts_with("digraph"));
+        assert!(dot.contains("1 -> 4;"));
+        graph.write_json(&dir.path().join("graph.json")).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_slice(&fs::read(dir.path().join("graph.json")).unwrap()).unwrap();
+        assert_eq!(json["summary"]["depth"], 3);
+        assert_eq!(json["transactions"].as_array().unwrap().len(), 5);
+        assert_eq!(json["conflicts"][2], serde_json::json!([1, 4]));
+    }
+
+    #[test]
+    fn test_conflict_graph_empty() {
+        let summary = ConflictGraph::new(0, vec![]).summary();
+        assert_eq!(summary.depth, 0);
+        assert_eq!(summary.average_parallel_width, 0.);
+    }
+}
@@ -1,5 +1,6 @@
 use {
     super::{
+        committer::CommitTransactionDetails,
         consumer::{Consumer, ExecuteAndCommitTransactionsOutput, ProcessTransactionBatchOutput},
         leader_slot_timing_metrics::LeaderExecuteAndCommitTimings,
         scheduler_messages::{ConsumeWork, FinishedConsumeWork},
@@ -9,7 +10,10 @@ use {
     solana_poh::leader_bank_notifier::LeaderBankNotifier,
     solana_runtime::bank::Bank,
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
-    solana_sdk::timing::AtomicInterval,
+    solana_sdk::{clock::Slot, timing::AtomicInterval},
+    solana_send_transaction_service::transaction_ingestion_log::{
+        IngestionEvent, IngestionStage, TransactionIngestionLog,
+    },
     solana_svm::transaction_error_metrics::TransactionErrorMetrics,
     std::{
         sync::{
@@ -36,6 +40,8 @@ pub(crate) struct ConsumeWorker<Tx> {
 
     leader_bank_notifier: Arc<LeaderBankNotifier>,
     metrics: Arc<ConsumeWorkerMetrics>,
+    /// Where scheduled and executed transactions are recorded, if enabled
+    ingestion_log: Option<Arc<TransactionIngestionLog>>,
 }
 
 impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
@@ -45,6 +51,7 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
         consumer: Consumer,
         consumed_sender: Sender<FinishedConsumeWork<Tx>>,
         leader_bank_notifier: Arc<LeaderBankNotifier>,
+        ingestion_log: Option<Arc<TransactionIngestionLog>>,
     ) -> Self {
         Self {
             consume_receiver,
@@ -52,6 +59,7 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
             consumed_sender,
             leader_bank_notifier,
             metrics: Arc::new(ConsumeWorkerMetrics::new(id)),
+            ingestion_log,
         }
     }
 
@@ -120,6 +128,14 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
 
         self.metrics.update_for_consume(&output);
         self.metrics.has_data.store(true, Ordering::Relaxed);
+        if let Some(ingestion_log) = &self.ingestion_log {
+            record_ingestion_events(
+                ingestion_log,
+                bank.slot(),
+                &work.transactions,
+                &output.execute_and_commit_transactions_output,
+            );
+        }
 
         self.consumed_sender.send(FinishedConsumeWork {
             work,
@@ -171,6 +187,38 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
     }
 }
 
+/// Records that `transactions` were scheduled for `slot`, followed by whether they were
+/// executed. Retryable transactions are recorded again once they are rescheduled.
+fn record_ingestion_events<Tx: TransactionWithMeta>(
+    ingestion_log: &TransactionIngestionLog,
+    slot: Slot,
+    transactions: &[Tx],
+    ExecuteAndCommitTransactionsOutput {
+        retryable_transaction_indexes,
+        commit_transactions_result,
+        ..
+    }: &ExecuteAndCommitTransactionsOutput,
+) {
+    let commit_details = commit_transactions_result.as_deref().unwrap_or_default();
+    ingestion_log.record_batch(transactions.iter().enumerate().flat_map(|(index, tx)| {
+        let signature = *tx.signature();
+        let outcome = match commit_details.get(index) {
+            _ if retryable_transaction_indexes.contains(&index) => None,
+            Some(CommitTransactionDetails::Committed { .. }) => {
+                Some(IngestionEvent::new(IngestionStage::Executed))
+            }
+            Some(CommitTransactionDetails::NotCommitted) => Some(
+                IngestionEvent::new(IngestionStage::Filtered)
+                    .with_reason("failed to execute, not committed"),
+            ),
+            None => None,
+        };
+        std::iter::once(IngestionEvent::new(IngestionStage::Scheduled))
+            .chain(outcome)
+            .map(move |event| (signature, event.with_slot(slot)))
+    }));
+}
+
 /// Helper function to create an non-blocking iterator over work in the receiver,
 /// starting with the given work item.
 fn try_drain_iter<T>(work: T, receiver: &Receiver<T>) -> impl Iterator<Item = T> + '_ {
@@ -792,6 +840,7 @@ mod tests {
 
         consume_sender: Sender<ConsumeWork<RuntimeTransaction<SanitizedTransaction>>>,
         consumed_receiver: Receiver<FinishedConsumeWork<RuntimeTransaction<SanitizedTransaction>>>,
+        ingestion_log: Arc<TransactionIngestionLog>,
     }
 
     fn setup_test_frame() -> (
@@ -839,12 +888,14 @@ mod tests {
 
         let (consume_sender, consume_receiver) = unbounded();
         let (consumed_sender, consumed_receiver) = unbounded();
+        let ingestion_log = Arc::new(TransactionIngestionLog::new(16));
         let worker = ConsumeWorker::new(
             0,
             consume_receiver,
             consumer,
             consumed_sender,
             poh_recorder.read().unwrap().new_leader_bank_notifier(),
+            Some(ingestion_log.clone()),
         );
 
         (
@@ -860,6 +911,7 @@ mod tests {
                 _replay_vote_receiver: replay_vote_receiver,
                 consume_sender,
                 consumed_receiver,
+                ingestion_log,
             },
             worker,
         )
@@ -919,6 +971,7 @@ mod tests {
             poh_recorder,
             consume_sender,
             consumed_receiver,
+            ingestion_log,
             ..
         } = &test_frame;
         let worker_thread = std::thread::spawn(move || worker.run());
@@ -935,6 +988,7 @@ mod tests {
             1,
             genesis_config.hash(),
         )]);
+        let signature = *transactions[0].signature();
         let bid = TransactionBatchId::new(0);
         let id = 0;
         let max_age = MaxAge {
@@ -953,6 +1007,18 @@ mod tests {
         assert_eq!(consumed.work.ids, vec![id]);
         assert_eq!(consumed.work.max_ages, vec![max_age]);
         assert_eq!(consumed.retryable_indexes, Vec::<usize>::new());
+        let stages: Vec<_> = ingestion_log
+            .get_events(&signature)
+            .into_iter()
+            .map(|event| (event.stage, event.slot))
+            .collect();
+        assert_eq!(
+            stages,
+            vec![
+                (IngestionStage::Scheduled, Some(bank.slot())),
+                (IngestionStage::Executed, Some(bank.slot())),
+            ]
+        );
 
         drop(test_frame);
         let _ = worker_thread.join().unwrap();
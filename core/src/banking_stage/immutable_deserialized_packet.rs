@@ -54,6 +54,7 @@ lazy_static::lazy_static! {
 pub struct ImmutableDeserializedPacket {
     original_packet: Packet,
     transaction: SanitizedVersionedTransaction,
+    signature: Signature,
     message_hash: Hash,
     is_simple_vote: bool,
     compute_unit_price: u64,
@@ -63,6 +64,11 @@ pub struct ImmutableDeserializedPacket {
 impl ImmutableDeserializedPacket {
     pub fn new(packet: Packet) -> Result<Self, DeserializedPacketError> {
         let versioned_transaction: VersionedTransaction = packet.deserialize_slice(..)?;
+        let signature = versioned_transaction
+            .signatures
+            .first()
+            .copied()
+            .unwrap_or_default();
         let sanitized_transaction = SanitizedVersionedTransaction::try_from(versioned_transaction)?;
         let message_bytes = packet_message(&packet)?;
         let message_hash = Message::hash_raw_message(message_bytes);
@@ -90,6 +96,7 @@ impl ImmutableDeserializedPacket {
         Ok(Self {
             original_packet: packet,
             transaction: sanitized_transaction,
+            signature,
             message_hash,
             is_simple_vote,
             compute_unit_price,
@@ -105,6 +112,11 @@ impl ImmutableDeserializedPacket {
         &self.transaction
     }
 
+    /// The first signature of the transaction, which identifies it
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
     pub fn message_hash(&self) -> &Hash {
         &self.message_hash
     }
@@ -192,7 +204,7 @@ impl Ord for ImmutableDeserializedPacket {
 }
 
 /// Read the transaction message from packet data
-fn packet_message(packet: &Packet) -> Result<&[u8], DeserializedPacketError> {
+pub fn packet_message(packet: &Packet) -> Result<&[u8], DeserializedPacketError> {
     let (sig_len, sig_size) = packet
         .data(..)
         .and_then(|bytes| decode_shortu16_len(bytes).ok())
@@ -9,6 +9,7 @@ use {
     crossbeam_channel::RecvTimeoutError,
     solana_perf::packet::PacketBatch,
     solana_sdk::saturating_add_assign,
+    solana_streamer::packet_drop_stats::{packet_drop_stats, PacketDropReason, PacketDropStats},
     std::time::{Duration, Instant},
 };
 
@@ -69,6 +70,25 @@ impl PacketReceiverStats {
             }
         }
     }
+
+    /// Adds the packets dropped to `drop_stats`
+    fn record_drops(&self, drop_stats: &PacketDropStats) {
+        drop_stats.record(PacketDropReason::Sigverify, self.failed_sigverify_count);
+        drop_stats.record(
+            PacketDropReason::Sanitization,
+            self.failed_sanitization_count
+                .saturating_add(self.failed_prioritization_count)
+                .saturating_add(self.invalid_vote_count),
+        );
+        drop_stats.record(
+            PacketDropReason::ExcessivePrecompiles,
+            self.excessive_precompile_count,
+        );
+        drop_stats.record(
+            PacketDropReason::InsufficientComputeUnitLimit,
+            self.insufficient_compute_limit_count,
+        );
+    }
 }
 
 impl PacketDeserializer {
@@ -78,6 +98,11 @@ impl PacketDeserializer {
         }
     }
 
+    /// Number of packet batches waiting to be received.
+    pub fn queue_depth(&self) -> usize {
+        self.packet_batch_receiver.len()
+    }
+
     /// Handles receiving packet batches from sigverify and returns a vector of deserialized packets
     pub fn receive_packets(
         &self,
@@ -129,6 +154,7 @@ impl PacketDeserializer {
                 ));
             }
         }
+        packet_stats.record_drops(packet_drop_stats());
 
         ReceivePacketResults {
             deserialized_packets,
@@ -217,6 +243,7 @@ impl PacketDeserializer {
 mod tests {
     use {
         super::*,
+        crossbeam_channel::unbounded,
         solana_perf::packet::to_packet_batches,
         solana_sdk::{
             hash::Hash, pubkey::Pubkey, signature::Keypair, system_transaction,
@@ -270,4 +297,48 @@ mod tests {
         assert_eq!(results.packet_stats.passed_sigverify_count, 1);
         assert_eq!(results.packet_stats.failed_sigverify_count, 1);
     }
+
+    #[test]
+    fn test_record_drops() {
+        let packet_stats = PacketReceiverStats {
+            passed_sigverify_count: 10,
+            failed_sigverify_count: 1,
+            failed_sanitization_count: 2,
+            failed_prioritization_count: 3,
+            invalid_vote_count: 4,
+            excessive_precompile_count: 5,
+            insufficient_compute_limit_count: 6,
+        };
+        let drop_stats = PacketDropStats::new();
+        packet_stats.record_drops(&drop_stats);
+        assert_eq!(drop_stats.count(PacketDropReason::Sigverify), 1);
+        assert_eq!(drop_stats.count(PacketDropReason::Sanitization), 9);
+        assert_eq!(drop_stats.count(PacketDropReason::ExcessivePrecompiles), 5);
+        assert_eq!(
+            drop_stats.count(PacketDropReason::InsufficientComputeUnitLimit),
+            6
+        );
+        assert_eq!(drop_stats.count(PacketDropReason::FeeFloor), 0);
+    }
+
+    #[test]
+    fn test_queue_depth() {
+        let (sender, receiver) = unbounded();
+        let packet_deserializer = PacketDeserializer::new(receiver);
+        assert_eq!(packet_deserializer.queue_depth(), 0);
+
+        let transactions = vec![random_transfer(), random_transfer()];
+        for packet_batch in to_packet_batches(&transactions, 1) {
+            sender
+                .send(BankingPacketBatch::new(vec![packet_batch]))
+                .unwrap();
+        }
+        assert_eq!(packet_deserializer.queue_depth(), 2);
+
+        let results = packet_deserializer
+            .receive_packets(Duration::ZERO, usize::MAX, Ok)
+            .unwrap();
+        assert_eq!(results.deserialized_packets.len(), 2);
+        assert_eq!(packet_deserializer.queue_depth(), 0);
+    }
 }
@@ -33,6 +33,9 @@ impl PacketReceiver {
         banking_stage_stats: &mut BankingStageStats,
         slot_metrics_tracker: &mut LeaderSlotMetricsTracker,
     ) -> Result<(), RecvTimeoutError> {
+        banking_stage_stats
+            .max_receive_queue_depth
+            .fetch_max(self.packet_deserializer.queue_depth(), Ordering::Relaxed);
         let (result, recv_time_us) = measure_us!({
             let recv_timeout = Self::get_receive_timeout(unprocessed_transaction_storage);
             let mut recv_and_buffer_measure = Measure::start("recv_and_buffer");
@@ -4,7 +4,7 @@ use {
 };
 
 /// A unique identifier for a transaction batch.
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TransactionBatchId(u64);
 
 impl TransactionBatchId {
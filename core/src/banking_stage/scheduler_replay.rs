@@ -0,0 +1,450 @@
+//! Deterministic offline replay of the central scheduler from a banking trace captured with
+//! `--capture-banking-trace`.
+//!
+//! The captured events provide the leader slot boundaries, the transactions the scheduler
+//! buffered along with the priority and cost it computed for them, the end of every receive
+//! and the points at which the scheduler cleared its container. The traced packets provide the
+//! transactions themselves. The replay buffers the same transactions in a container of the
+//! same capacity and runs the real scheduler over it at the points the scheduler thread did:
+//! when a leader slot starts, and after each receive during it.
+//!
+//! Consume workers are simulated by completing every batch before the scheduler runs again,
+//! which makes the replay deterministic. Bank checks aren't re-run either, since the captured
+//! decisions already record their outcome at buffering time. Transactions that expired while
+//! buffered are therefore still scheduled.
+
+use {
+    super::{
+        immutable_deserialized_packet::ImmutableDeserializedPacket,
+        scheduler_messages::{ConsumeWork, FinishedConsumeWork, MaxAge},
+        transaction_scheduler::{
+            greedy_scheduler::GreedySchedulerConfig,
+            prio_graph_scheduler::PrioGraphSchedulerConfig,
+            scheduler::{TransactionScheduler, TransactionSchedulerFactory},
+            transaction_state::SanitizedTransactionTTL,
+            transaction_state_container::{StateContainer, TransactionStateContainer},
+        },
+        MIN_TOTAL_THREADS, NUM_VOTE_PROCESSING_THREADS, TOTAL_BUFFERED_PACKETS,
+    },
+    crate::{
+        banking_trace::{CapturedDecision, CapturedEvent},
+        validator::BlockProductionMethod,
+    },
+    crossbeam_channel::{unbounded, Receiver, Sender},
+    solana_perf::packet::Packet,
+    solana_runtime_transaction::{
+        runtime_transaction::RuntimeTransaction, transaction_meta::StaticMeta,
+    },
+    solana_sdk::{
+        clock::Slot,
+        hash::Hash,
+        message::{v0::LoadedAddresses, SimpleAddressLoader},
+        pubkey::Pubkey,
+        reserved_account_keys::ReservedAccountKeys,
+        transaction::{MessageHash, SanitizedTransaction, SanitizedVersionedTransaction},
+    },
+    std::{
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    },
+};
+
+type Transaction = RuntimeTransaction<SanitizedTransaction>;
+
+/// A batch of transactions the replayed scheduler sent to a consume worker.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReplayedBatch {
+    /// Index of the consume worker the batch was sent to
+    pub worker: usize,
+    pub message_hashes: Vec<Hash>,
+}
+
+/// The batches the replayed scheduler sent during a leader slot, in the order it sent them.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReplayedSlot {
+    pub slot: Slot,
+    pub batches: Vec<ReplayedBatch>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReplayedSchedule {
+    /// Replayed leader slots, in capture order
+    pub slots: Vec<ReplayedSlot>,
+    /// Buffered transactions that couldn't be replayed, because their packet wasn't traced or
+    /// their addresses loaded from lookup tables weren't captured
+    pub num_missing_transactions: usize,
+}
+
+/// Replays the captured `events` with the scheduler of `block_production_method` and as many
+/// consume workers as a banking stage with `num_threads` threads has. Like the banking stage, at
+/// least `MIN_TOTAL_THREADS` threads are assumed.
+///
+/// `packets` must include the traced packets of the transactions buffered by `events`; other
+/// packets are ignored.
+pub fn replay_scheduler(
+    block_production_method: &BlockProductionMethod,
+    num_threads: u32,
+    packets: impl IntoIterator<Item = Packet>,
+    events: impl IntoIterator<Item = CapturedEvent>,
+) -> ReplayedSchedule {
+    let num_workers = num_threads
+        .max(MIN_TOTAL_THREADS)
+        .saturating_sub(NUM_VOTE_PROCESSING_THREADS) as usize;
+    match block_production_method {
+        BlockProductionMethod::CentralScheduler => {
+            SchedulerReplay::new(PrioGraphSchedulerConfig::default(), num_workers)
+                .replay(packets, events)
+        }
+        BlockProductionMethod::CentralSchedulerGreedy => {
+            SchedulerReplay::new(GreedySchedulerConfig::default(), num_workers)
+                .replay(packets, events)
+        }
+    }
+}
+
+struct SchedulerReplay<S: TransactionScheduler<Transaction>> {
+    scheduler: S,
+    container: TransactionStateContainer<Transaction>,
+    consume_work_receivers: Vec<Receiver<ConsumeWork<Transaction>>>,
+    finished_consume_work_sender: Sender<FinishedConsumeWork<Transaction>>,
+    reserved_account_keys: HashSet<Pubkey>,
+    /// Traced packets, by message hash
+    packets: HashMap<Hash, Arc<ImmutableDeserializedPacket>>,
+    /// Captured addresses loaded from lookup tables, by message hash
+    loaded_addresses: HashMap<Hash, LoadedAddresses>,
+    leader_slot: Option<Slot>,
+    schedule: ReplayedSchedule,
+}
+
+impl<S: TransactionScheduler<Transaction>> SchedulerReplay<S> {
+    fn new(
+        scheduler_factory: impl TransactionSchedulerFactory<Scheduler<Transaction> = S>,
+        num_workers: usize,
+    ) -> Self {
+        let (consume_work_senders, consume_work_receivers) =
+            (0..num_workers).map(|_| unbounded()).unzip();
+        let (finished_consume_work_sender, finished_consume_work_receiver) = unbounded();
+        Self {
+            scheduler: scheduler_factory
+                .new_scheduler(consume_work_senders, finished_consume_work_receiver),
+            container: TransactionStateContainer::with_capacity(TOTAL_BUFFERED_PACKETS),
+            consume_work_receivers,
+            finished_consume_work_sender,
+            // Captures don't record the features that were active, assume the newest set
+            reserved_account_keys: ReservedAccountKeys::new_all_activated().active,
+            packets: HashMap::new(),
+            loaded_addresses: HashMap::new(),
+            leader_slot: None,
+            schedule: ReplayedSchedule::default(),
+        }
+    }
+
+    fn replay(
+        mut self,
+        packets: impl IntoIterator<Item = Packet>,
+        events: impl IntoIterator<Item = CapturedEvent>,
+    ) -> ReplayedSchedule {
+        for packet in packets {
+            if let Ok(packet) = ImmutableDeserializedPacket::new(packet) {
+                self.packets
+                    .entry(*packet.message_hash())
+                    .or_insert_with(|| Arc::new(packet));
+            }
+        }
+        for event in events {
+            self.replay_event(event);
+        }
+        self.schedule
+    }
+
+    fn replay_event(&mut self, event: CapturedEvent) {
+        match event {
+            CapturedEvent::BankStart { slot, .. } => {
+                self.leader_slot = Some(slot);
+                self.schedule.slots.push(ReplayedSlot {
+                    slot,
+                    batches: vec![],
+                });
+                self.schedule_until_idle();
+            }
+            CapturedEvent::BankEnd { .. } => self.leader_slot = None,
+            CapturedEvent::Decision {
+                message_hash,
+                decision: CapturedDecision::Buffered { priority, cost, .. },
+                ..
+            } => self.buffer(&message_hash, priority, cost),
+            CapturedEvent::Decision { .. } => {}
+            CapturedEvent::LoadedAddresses {
+                message_hash,
+                writable,
+                readonly,
+            } => {
+                self.loaded_addresses
+                    .insert(message_hash, LoadedAddresses { writable, readonly });
+            }
+            CapturedEvent::Received { slot } => {
+                if self.leader_slot == Some(slot) {
+                    self.schedule_until_idle();
+                }
+            }
+            CapturedEvent::Cleared => {
+                while let Some(id) = self.container.pop() {
+                    self.container.remove_by_id(id.id);
+                }
+            }
+        }
+    }
+
+    fn buffer(&mut self, message_hash: &Hash, priority: u64, cost: u64) {
+        let Some(transaction) = self.sanitize(message_hash) else {
+            self.schedule.num_missing_transactions += 1;
+            return;
+        };
+        let packet = Arc::clone(&self.packets[message_hash]);
+        self.container.insert_new_transaction(
+            SanitizedTransactionTTL {
+                transaction,
+                max_age: MaxAge::MAX,
+            },
+            packet,
+            priority,
+            cost,
+        );
+    }
+
+    fn sanitize(&self, message_hash: &Hash) -> Option<Transaction> {
+        let packet = self.packets.get(message_hash)?;
+        let transaction = packet.transaction();
+        let loaded_addresses = if transaction
+            .get_message()
+            .message
+            .address_table_lookups()
+            .is_some_and(|lookups| !lookups.is_empty())
+        {
+            self.loaded_addresses.get(message_hash)?.clone()
+        } else {
+            LoadedAddresses::default()
+        };
+        RuntimeTransaction::<SanitizedVersionedTransaction>::try_from(
+            transaction.clone(),
+            MessageHash::Precomputed(*message_hash),
+            Some(packet.is_simple_vote()),
+        )
+        .and_then(|transaction| {
+            RuntimeTransaction::<SanitizedTransaction>::try_from(
+                transaction,
+                SimpleAddressLoader::Enabled(loaded_addresses),
+                &self.reserved_account_keys,
+            )
+        })
+        .ok()
+    }
+
+    /// Runs the scheduler until it schedules nothing more, completing each batch it sends
+    /// before running it again.
+    fn schedule_until_idle(&mut self) {
+        loop {
+            let summary = self
+                .scheduler
+                .schedule(&mut self.container, |_, _| {}, |_| true)
+                .expect("simulated workers are connected");
+            self.complete_batches();
+            self.scheduler
+                .receive_completed(&mut self.container)
+                .expect("simulated workers are connected");
+            if summary.num_scheduled == 0 {
+                break;
+            }
+        }
+    }
+
+    /// Records the batches sent to the workers, in the order the scheduler sent them, and
+    /// reports them all as processed.
+    fn complete_batches(&mut self) {
+        let mut works = self
+            .consume_work_receivers
+            .iter()
+            .enumerate()
+            .flat_map(|(worker, receiver)| receiver.try_iter().map(move |work| (worker, work)))
+            .collect::<Vec<_>>();
+        works.sort_by_key(|(_, work)| work.batch_id);
+
+        let slot = self
+            .schedule
+            .slots
+            .last_mut()
+            .expect("scheduling only happens during leader slots");
+        for (worker, work) in works {
+            slot.batches.push(ReplayedBatch {
+                worker,
+                message_hashes: work
+                    .transactions
+                    .iter()
+                    .map(|transaction| *transaction.message_hash())
+                    .collect(),
+            });
+            self.finished_consume_work_sender
+                .send(FinishedConsumeWork {
+                    work,
+                    retryable_indexes: vec![],
+                })
+                .expect("scheduler holds the receiver");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{signature::Keypair, system_transaction},
+    };
+
+    fn buffered(message_hash: Hash, priority: u64) -> CapturedEvent {
+        CapturedEvent::Decision {
+            slot: 1,
+            message_hash,
+            decision: CapturedDecision::Buffered {
+                reward: priority,
+                cost: 1,
+                priority,
+                evicted: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_replay_scheduler() {
+        let payer = Keypair::new();
+        let transactions = (0..3)
+            .map(|lamports| {
+                system_transaction::transfer(
+                    &payer,
+                    &Pubkey::new_unique(),
+                    lamports,
+                    Hash::default(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let message_hashes = transactions
+            .iter()
+            .map(|transaction| transaction.message.hash())
+            .collect::<Vec<_>>();
+        let packets = transactions
+            .iter()
+            .map(|transaction| Packet::from_data(None, transaction).unwrap())
+            .collect::<Vec<_>>();
+
+        for block_production_method in [
+            BlockProductionMethod::CentralScheduler,
+            BlockProductionMethod::CentralSchedulerGreedy,
+        ] {
+            let schedule = replay_scheduler(
+                &block_production_method,
+                3,
+                packets.clone(),
+                [
+                    // held before the leader slot, scheduled as it starts
+                    buffered(message_hashes[0], 1),
+                    CapturedEvent::BankStart {
+                        slot: 1,
+                        parent_slot: 0,
+                        lamports_per_signature: 5000,
+                    },
+                    // received in one go, scheduled highest priority first
+                    buffered(message_hashes[1], 2),
+                    buffered(message_hashes[2], 3),
+                    buffered(Hash::new_unique(), 4),
+                    CapturedEvent::Received { slot: 1 },
+                    CapturedEvent::BankEnd { slot: 1 },
+                ],
+            );
+            assert_eq!(schedule.num_missing_transactions, 1);
+            assert_eq!(schedule.slots.len(), 1);
+            assert_eq!(schedule.slots[0].slot, 1);
+            // All transfers share the payer, so are serialized on the single worker
+            let scheduled = schedule.slots[0]
+                .batches
+                .iter()
+                .inspect(|batch| assert_eq!(batch.worker, 0))
+                .flat_map(|batch| batch.message_hashes.iter().copied())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                scheduled,
+                vec![message_hashes[0], message_hashes[2], message_hashes[1]]
+            );
+        }
+    }
+
+    #[test]
+    fn test_replay_scheduler_min_threads() {
+        let transaction = system_transaction::transfer(
+            &Keypair::new(),
+            &Pubkey::new_unique(),
+            1,
+            Hash::default(),
+        );
+        let message_hash = transaction.message.hash();
+        // Fewer threads than the banking stage runs with still replay with one consume worker
+        for num_threads in 0..=MIN_TOTAL_THREADS {
+            let schedule = replay_scheduler(
+                &BlockProductionMethod::CentralScheduler,
+                num_threads,
+                [Packet::from_data(None, &transaction).unwrap()],
+                [
+                    CapturedEvent::BankStart {
+                        slot: 1,
+                        parent_slot: 0,
+                        lamports_per_signature: 5000,
+                    },
+                    buffered(message_hash, 1),
+                    CapturedEvent::Received { slot: 1 },
+                    CapturedEvent::BankEnd { slot: 1 },
+                ],
+            );
+            assert_eq!(
+                schedule.slots,
+                vec![ReplayedSlot {
+                    slot: 1,
+                    batches: vec![ReplayedBatch {
+                        worker: 0,
+                        message_hashes: vec![message_hash],
+                    }],
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn test_replay_scheduler_cleared() {
+        let transaction = system_transaction::transfer(
+            &Keypair::new(),
+            &Pubkey::new_unique(),
+            1,
+            Hash::default(),
+        );
+        let message_hash = transaction.message.hash();
+        let schedule = replay_scheduler(
+            &BlockProductionMethod::CentralSchedulerGreedy,
+            3,
+            [Packet::from_data(None, &transaction).unwrap()],
+            [
+                buffered(message_hash, 1),
+                CapturedEvent::Cleared,
+                CapturedEvent::BankStart {
+                    slot: 1,
+                    parent_slot: 0,
+                    lamports_per_signature: 5000,
+                },
+                CapturedEvent::BankEnd { slot: 1 },
+            ],
+        );
+        assert_eq!(
+            schedule.slots,
+            vec![ReplayedSlot {
+                slot: 1,
+                batches: vec![],
+            }]
+        );
+    }
+}
@@ -4,7 +4,7 @@ use {
         prio_graph_scheduler::{
             Batches, PrioGraphScheduler, TransactionSchedulingError, TransactionSchedulingInfo,
         },
-        scheduler::{Scheduler, SchedulingSummary},
+        scheduler::{SchedulingSummary, TransactionScheduler, TransactionSchedulerFactory},
         scheduler_error::SchedulerError,
         thread_aware_account_locks::{ThreadAwareAccountLocks, ThreadId, ThreadSet},
         transaction_priority_id::TransactionPriorityId,
@@ -77,7 +77,19 @@ impl<Tx: TransactionWithMeta> GreedyScheduler<Tx> {
     }
 }
 
-impl<Tx: TransactionWithMeta> Scheduler<Tx> for GreedyScheduler<Tx> {
+impl TransactionSchedulerFactory for GreedySchedulerConfig {
+    type Scheduler<Tx: TransactionWithMeta> = GreedyScheduler<Tx>;
+
+    fn new_scheduler<Tx: TransactionWithMeta>(
+        self,
+        consume_work_senders: Vec<Sender<ConsumeWork<Tx>>>,
+        finished_consume_work_receiver: Receiver<FinishedConsumeWork<Tx>>,
+    ) -> GreedyScheduler<Tx> {
+        GreedyScheduler::new(consume_work_senders, finished_consume_work_receiver, self)
+    }
+}
+
+impl<Tx: TransactionWithMeta> TransactionScheduler<Tx> for GreedyScheduler<Tx> {
     fn schedule<S: StateContainer<Tx>>(
         &mut self,
         container: &mut S,
@@ -3,11 +3,11 @@ pub(crate) mod greedy_scheduler;
 mod in_flight_tracker;
 pub(crate) mod prio_graph_scheduler;
 pub(crate) mod receive_and_buffer;
-pub(crate) mod scheduler;
+pub mod scheduler;
 pub(crate) mod scheduler_controller;
-pub(crate) mod scheduler_error;
+pub mod scheduler_error;
 mod scheduler_metrics;
 mod thread_aware_account_locks;
-mod transaction_priority_id;
-mod transaction_state;
-pub(crate) mod transaction_state_container;
+pub mod transaction_priority_id;
+pub mod transaction_state;
+pub mod transaction_state_container;
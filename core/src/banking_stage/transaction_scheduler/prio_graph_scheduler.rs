@@ -1,7 +1,7 @@
 use {
     super::{
         in_flight_tracker::InFlightTracker,
-        scheduler::Scheduler,
+        scheduler::{TransactionScheduler, TransactionSchedulerFactory},
         scheduler_error::SchedulerError,
         thread_aware_account_locks::{ThreadAwareAccountLocks, ThreadId, ThreadSet},
         transaction_state::SanitizedTransactionTTL,
@@ -87,7 +87,19 @@ impl<Tx: TransactionWithMeta> PrioGraphScheduler<Tx> {
     }
 }
 
-impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
+impl TransactionSchedulerFactory for PrioGraphSchedulerConfig {
+    type Scheduler<Tx: TransactionWithMeta> = PrioGraphScheduler<Tx>;
+
+    fn new_scheduler<Tx: TransactionWithMeta>(
+        self,
+        consume_work_senders: Vec<Sender<ConsumeWork<Tx>>>,
+        finished_consume_work_receiver: Receiver<FinishedConsumeWork<Tx>>,
+    ) -> PrioGraphScheduler<Tx> {
+        PrioGraphScheduler::new(consume_work_senders, finished_consume_work_receiver, self)
+    }
+}
+
+impl<Tx: TransactionWithMeta> TransactionScheduler<Tx> for PrioGraphScheduler<Tx> {
     /// Schedule transactions from the given `StateContainer` to be
     /// consumed by the worker threads. Returns summary of scheduling, or an
     /// error.
@@ -8,15 +8,19 @@ use {
             EXTRA_CAPACITY,
         },
     },
-    crate::banking_stage::{
-        consumer::Consumer, decision_maker::BufferedPacketsDecision,
-        immutable_deserialized_packet::ImmutableDeserializedPacket,
-        packet_deserializer::PacketDeserializer, packet_filter::MAX_ALLOWED_PRECOMPILE_SIGNATURES,
-        scheduler_messages::MaxAge,
-        transaction_scheduler::transaction_state::SanitizedTransactionTTL,
-        TransactionStateContainer,
+    crate::{
+        banking_stage::{
+            consumer::Consumer, decision_maker::BufferedPacketsDecision,
+            immutable_deserialized_packet::ImmutableDeserializedPacket,
+            packet_deserializer::PacketDeserializer,
+            packet_filter::MAX_ALLOWED_PRECOMPILE_SIGNATURES, scheduler_messages::MaxAge,
+            transaction_scheduler::transaction_state::SanitizedTransactionTTL,
+            TransactionStateContainer,
+        },
+        banking_trace::{BankingCapture, CapturedDecision, CapturedEvent},
     },
     agave_banking_stage_ingress_types::{BankingPacketBatch, BankingPacketReceiver},
+    agave_feature_set::enforce_min_compute_unit_price,
     agave_transaction_view::{
         resolved_transaction_view::ResolvedTransactionView,
         transaction_version::TransactionVersion, transaction_view::SanitizedTransactionView,
@@ -36,11 +40,14 @@ use {
         address_lookup_table::state::estimate_last_valid_slot,
         clock::{Epoch, Slot, MAX_PROCESSING_AGE},
         fee::FeeBudgetLimits,
+        message::{SanitizedMessage, VersionedMessage},
         saturating_add_assign,
         transaction::{MessageHash, SanitizedTransaction},
     },
+    solana_send_transaction_service::transaction_ingestion_log::{IngestionEvent, IngestionStage},
+    solana_streamer::packet_drop_stats::{packet_drop_stats, PacketDropReason},
     solana_svm::transaction_error_metrics::TransactionErrorMetrics,
-    solana_svm_transaction::svm_message::SVMMessage,
+    solana_svm_transaction::{svm_message::SVMMessage, svm_transaction::SVMTransaction},
     std::{
         sync::{Arc, RwLock},
         time::Instant,
@@ -68,6 +75,10 @@ pub(crate) struct SanitizedTransactionReceiveAndBuffer {
     bank_forks: Arc<RwLock<BankForks>>,
 
     forwarding_enabled: bool,
+
+    /// Records buffering decisions when banking trace capture is enabled.
+    capture: BankingCapture,
+    captured_progress: CapturedProgress,
 }
 
 impl ReceiveAndBuffer for SanitizedTransactionReceiveAndBuffer {
@@ -96,6 +107,14 @@ impl ReceiveAndBuffer for SanitizedTransactionReceiveAndBuffer {
             BufferedPacketsDecision::Forward => (MAX_PACKET_RECEIVE_TIME, self.forwarding_enabled),
             BufferedPacketsDecision::ForwardAndHold => (MAX_PACKET_RECEIVE_TIME, true),
         };
+        self.captured_progress
+            .record_decision(&self.capture, decision);
+
+        let queue_depth = self.packet_receiver.queue_depth();
+        count_metrics.update(|count_metrics| {
+            count_metrics.max_receive_queue_depth =
+                count_metrics.max_receive_queue_depth.max(queue_depth);
+        });
 
         let (received_packet_results, receive_time_us) = measure_us!(self
             .packet_receiver
@@ -126,6 +145,8 @@ impl ReceiveAndBuffer for SanitizedTransactionReceiveAndBuffer {
                     timing_metrics.update(|timing_metrics| {
                         saturating_add_assign!(timing_metrics.buffer_time_us, buffer_time_us);
                     });
+                    self.captured_progress
+                        .record_received(&self.capture, num_received_packets);
                 } else {
                     count_metrics.update(|count_metrics| {
                         saturating_add_assign!(
@@ -149,11 +170,14 @@ impl SanitizedTransactionReceiveAndBuffer {
         packet_receiver: PacketDeserializer,
         bank_forks: Arc<RwLock<BankForks>>,
         forwarding_enabled: bool,
+        capture: BankingCapture,
     ) -> Self {
         Self {
             packet_receiver,
             bank_forks,
             forwarding_enabled,
+            capture,
+            captured_progress: CapturedProgress::default(),
         }
     }
 
@@ -177,6 +201,23 @@ impl SanitizedTransactionReceiveAndBuffer {
         let sanitized_epoch = root_bank.epoch();
         let transaction_account_lock_limit = working_bank.get_transaction_account_lock_limit();
         let vote_only = working_bank.vote_only_bank();
+        let enforce_min_compute_unit_price = working_bank
+            .feature_set
+            .is_active(&enforce_min_compute_unit_price::id());
+        let fee_features = working_bank.fee_features();
+        let working_slot = working_bank.slot();
+        let capture_decision = |packet: &ImmutableDeserializedPacket,
+                                decision: CapturedDecision| {
+            if let Some(reason) = decision.packet_drop_reason() {
+                packet_drop_stats().record(reason, 1);
+            }
+            self.capture.record_decision(
+                working_slot,
+                packet.signature(),
+                packet.message_hash(),
+                decision,
+            )
+        };
 
         const CHUNK_SIZE: usize = 128;
         let lock_results: [_; CHUNK_SIZE] = core::array::from_fn(|_| Ok(()));
@@ -192,29 +233,51 @@ impl SanitizedTransactionReceiveAndBuffer {
             chunk
                 .iter()
                 .filter_map(|packet| {
-                    packet
+                    let sanitized = packet
                         .build_sanitized_transaction(
                             vote_only,
                             root_bank.as_ref(),
                             root_bank.get_reserved_account_keys(),
                         )
-                        .map(|(tx, deactivation_slot)| (packet.clone(), tx, deactivation_slot))
+                        .map(|(tx, deactivation_slot)| (packet.clone(), tx, deactivation_slot));
+                    if sanitized.is_none() {
+                        capture_decision(packet, CapturedDecision::DroppedOnSanitization);
+                    }
+                    sanitized
                 })
                 .inspect(|_| saturating_add_assign!(post_sanitization_count, 1))
-                .filter(|(_packet, tx, _deactivation_slot)| {
-                    validate_account_locks(
+                .filter(|(packet, tx, _deactivation_slot)| {
+                    let is_valid = validate_account_locks(
                         tx.message().account_keys(),
                         transaction_account_lock_limit,
                     )
-                    .is_ok()
+                    .is_ok();
+                    if !is_valid {
+                        capture_decision(packet, CapturedDecision::DroppedOnLockValidation);
+                    }
+                    is_valid
                 })
                 .filter_map(|(packet, tx, deactivation_slot)| {
-                    tx.compute_budget_instruction_details()
+                    match tx
+                        .compute_budget_instruction_details()
                         .sanitize_and_convert_to_compute_budget_limits(&working_bank.feature_set)
-                        .map(|compute_budget| {
-                            (packet, tx, deactivation_slot, compute_budget.into())
-                        })
-                        .ok()
+                    {
+                        Ok(compute_budget) => {
+                            Some((packet, tx, deactivation_slot, compute_budget.into()))
+                        }
+                        Err(_) => {
+                            capture_decision(packet, CapturedDecision::DroppedOnComputeBudget);
+                            None
+                        }
+                    }
+                })
+                .filter(|(packet, tx, _deactivation_slot, _fee_budget_limits)| {
+                    let is_valid = !enforce_min_compute_unit_price
+                        || solana_fee::compute_unit_price_below_minimum(tx, fee_features).is_none();
+                    if !is_valid {
+                        capture_decision(packet, CapturedDecision::DroppedOnComputeUnitPrice);
+                    }
+                    is_valid
                 })
                 .for_each(|(packet, tx, deactivation_slot, fee_budget_limits)| {
                     arc_packets.push(packet);
@@ -245,24 +308,67 @@ impl SanitizedTransactionReceiveAndBuffer {
                     .zip(max_ages.drain(..))
                     .zip(fee_budget_limits_vec.drain(..))
                     .zip(check_results)
-                    .filter(|(_, check_result)| check_result.is_ok())
-                    .filter(|((((_, tx), _), _), _)| {
-                        Consumer::check_fee_payer_unlocked(&working_bank, tx, &mut error_counts)
-                            .is_ok()
+                    .filter(|((((packet, _), _), _), check_result)| {
+                        let is_ok = check_result.is_ok();
+                        if !is_ok {
+                            capture_decision(packet, CapturedDecision::DroppedOnTransactionChecks);
+                        }
+                        is_ok
+                    })
+                    .filter(|((((packet, tx), _), _), _)| {
+                        let is_ok = Consumer::check_fee_payer_unlocked(
+                            &working_bank,
+                            tx,
+                            &mut error_counts,
+                        )
+                        .is_ok();
+                        if !is_ok {
+                            capture_decision(packet, CapturedDecision::DroppedOnFeePayerCheck);
+                        }
+                        is_ok
                     })
             {
                 saturating_add_assign!(post_transaction_check_count, 1);
 
-                let (priority, cost) =
+                let (priority, cost, reward) =
                     calculate_priority_and_cost(&transaction, &fee_budget_limits, &working_bank);
+                let reward = self.capture.is_enabled().then_some(reward);
+                if reward.is_some() {
+                    if let SanitizedMessage::V0(message) = transaction.message() {
+                        self.capture.record_loaded_addresses(
+                            packet.message_hash(),
+                            &message.loaded_addresses,
+                        );
+                    }
+                }
+                let buffered_packet = Arc::clone(&packet);
                 let transaction_ttl = SanitizedTransactionTTL {
                     transaction,
                     max_age,
                 };
 
-                if container.insert_new_transaction(transaction_ttl, packet, priority, cost) {
+                let evicted =
+                    container.insert_new_transaction(transaction_ttl, packet, priority, cost);
+                if evicted {
                     saturating_add_assign!(num_dropped_on_capacity, 1);
                 }
+                match reward {
+                    Some(reward) => capture_decision(
+                        &buffered_packet,
+                        CapturedDecision::Buffered {
+                            reward,
+                            cost,
+                            priority,
+                            evicted,
+                        },
+                    ),
+                    None => self
+                        .capture
+                        .record_ingestion(buffered_packet.signature(), || {
+                            IngestionEvent::new(IngestionStage::ReceivedViaTpu)
+                                .with_slot(working_slot)
+                        }),
+                }
                 saturating_add_assign!(num_buffered, 1);
             }
 
@@ -273,6 +379,8 @@ impl SanitizedTransactionReceiveAndBuffer {
             let num_dropped_on_transaction_checks =
                 post_lock_validation_count.saturating_sub(post_transaction_check_count);
 
+            packet_drop_stats().record(PacketDropReason::Capacity, num_dropped_on_capacity as u64);
+
             count_metrics.update(|count_metrics| {
                 saturating_add_assign!(
                     count_metrics.num_dropped_on_capacity,
@@ -296,9 +404,67 @@ impl SanitizedTransactionReceiveAndBuffer {
     }
 }
 
+/// What has been captured about the progress of the scheduler thread, so that captured
+/// decisions can be replayed against the bank and the receive they were made in.
+#[derive(Default)]
+struct CapturedProgress {
+    /// The leader slot for which a `CapturedEvent::BankStart` was last recorded.
+    leader_slot: Option<Slot>,
+    /// Whether transactions were received since `CapturedEvent::Cleared` was last recorded.
+    received_since_cleared: bool,
+}
+
+impl CapturedProgress {
+    /// Records the start and end of each leader slot, and the scheduler clearing its container
+    /// because of a `Forward` decision, before receiving with `decision`.
+    fn record_decision(&mut self, capture: &BankingCapture, decision: &BufferedPacketsDecision) {
+        if !capture.is_enabled() {
+            return;
+        }
+        if matches!(decision, BufferedPacketsDecision::Forward) && self.received_since_cleared {
+            capture.record(|| CapturedEvent::Cleared);
+            self.received_since_cleared = false;
+        }
+        let bank = decision
+            .bank_start()
+            .map(|bank_start| &bank_start.working_bank);
+        let slot = bank.map(|bank| bank.slot());
+        if slot == self.leader_slot {
+            return;
+        }
+        if let Some(leader_slot) = self.leader_slot {
+            capture.record(|| CapturedEvent::BankEnd { slot: leader_slot });
+        }
+        if let Some(bank) = bank {
+            capture.record(|| CapturedEvent::BankStart {
+                slot: bank.slot(),
+                parent_slot: bank.parent_slot(),
+                lamports_per_signature: bank.get_lamports_per_signature(),
+            });
+        }
+        self.leader_slot = slot;
+    }
+
+    /// Records the end of a receive that buffered `num_received` transactions.
+    fn record_received(&mut self, capture: &BankingCapture, num_received: usize) {
+        if !capture.is_enabled() || num_received == 0 {
+            return;
+        }
+        self.received_since_cleared = true;
+        if let Some(slot) = self.leader_slot {
+            capture.record(|| CapturedEvent::Received { slot });
+        }
+    }
+}
+
 pub(crate) struct TransactionViewReceiveAndBuffer {
-    pub receiver: BankingPacketReceiver,
-    pub bank_forks: Arc<RwLock<BankForks>>,
+    receiver: BankingPacketReceiver,
+    bank_forks: Arc<RwLock<BankForks>>,
+
+    /// Records buffering decisions when banking trace capture is enabled, and in the
+    /// transaction ingestion log, if one is attached.
+    capture: BankingCapture,
+    captured_progress: CapturedProgress,
 }
 
 impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
@@ -318,7 +484,56 @@ impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
             let working_bank = bank_forks.working_bank();
             (root_bank, working_bank)
         };
+        self.captured_progress
+            .record_decision(&self.capture, decision);
+
+        let queue_depth = self.receiver.len();
+        count_metrics.update(|count_metrics| {
+            count_metrics.max_receive_queue_depth =
+                count_metrics.max_receive_queue_depth.max(queue_depth);
+        });
+
+        let result = self.receive_packet_batch_messages(
+            container,
+            timing_metrics,
+            count_metrics,
+            decision,
+            &root_bank,
+            &working_bank,
+        );
+        if let Ok(num_received) = result {
+            self.captured_progress
+                .record_received(&self.capture, num_received);
+        }
+        result
+    }
+}
 
+impl TransactionViewReceiveAndBuffer {
+    pub fn new(
+        receiver: BankingPacketReceiver,
+        bank_forks: Arc<RwLock<BankForks>>,
+        capture: BankingCapture,
+    ) -> Self {
+        Self {
+            receiver,
+            bank_forks,
+            capture,
+            captured_progress: CapturedProgress::default(),
+        }
+    }
+
+    /// Return Err if the receiver is disconnected AND no packets were
+    /// received. Otherwise return Ok(num_received).
+    fn receive_packet_batch_messages(
+        &mut self,
+        container: &mut TransactionViewStateContainer,
+        timing_metrics: &mut SchedulerTimingMetrics,
+        count_metrics: &mut SchedulerCountMetrics,
+        decision: &BufferedPacketsDecision,
+        root_bank: &Bank,
+        working_bank: &Bank,
+    ) -> Result<usize, ()> {
         // Receive packet batches.
         const TIMEOUT: Duration = Duration::from_millis(10);
         let start = Instant::now();
@@ -346,8 +561,8 @@ impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
                         timing_metrics,
                         count_metrics,
                         decision,
-                        &root_bank,
-                        &working_bank,
+                        root_bank,
+                        working_bank,
                         packet_batch_message,
                     );
                 }
@@ -367,8 +582,8 @@ impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
                         timing_metrics,
                         count_metrics,
                         decision,
-                        &root_bank,
-                        &working_bank,
+                        root_bank,
+                        working_bank,
                         packet_batch_message,
                     );
                 }
@@ -381,9 +596,7 @@ impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
 
         Ok(num_received)
     }
-}
 
-impl TransactionViewReceiveAndBuffer {
     /// Return number of received packets.
     fn handle_packet_batch_message(
         &mut self,
@@ -405,6 +618,23 @@ impl TransactionViewReceiveAndBuffer {
         let alt_resolved_slot = root_bank.slot();
         let sanitized_epoch = root_bank.epoch();
         let transaction_account_lock_limit = working_bank.get_transaction_account_lock_limit();
+        let working_slot = working_bank.slot();
+        let capture = &self.capture;
+        let is_recording = capture.is_enabled() || capture.ingestion_log().is_some();
+        let record_decision = |container: &TransactionViewStateContainer,
+                               transaction_id,
+                               decision: CapturedDecision| {
+            let transaction = &container
+                .get_transaction_ttl(transaction_id)
+                .expect("transaction must exist")
+                .transaction;
+            capture.record_decision(
+                working_slot,
+                transaction.signature(),
+                transaction.message_hash(),
+                decision,
+            );
+        };
 
         let mut num_received = 0usize;
         let mut num_buffered = 0usize;
@@ -414,12 +644,14 @@ impl TransactionViewReceiveAndBuffer {
 
         // Create temporary batches of transactions to be age-checked.
         let mut transaction_priority_ids = ArrayVec::<_, EXTRA_CAPACITY>::new();
+        let mut rewards = ArrayVec::<_, EXTRA_CAPACITY>::new();
         let lock_results: [_; EXTRA_CAPACITY] = core::array::from_fn(|_| Ok(()));
         let mut error_counters = TransactionErrorMetrics::default();
 
         let mut check_and_push_to_queue =
             |container: &mut TransactionViewStateContainer,
-             transaction_priority_ids: &mut ArrayVec<TransactionPriorityId, 64>| {
+             transaction_priority_ids: &mut ArrayVec<TransactionPriorityId, 64>,
+             rewards: &mut ArrayVec<u64, 64>| {
                 // Temporary scope so that transaction references are immediately
                 // dropped and transactions not passing
                 let mut check_results = {
@@ -445,6 +677,12 @@ impl TransactionViewReceiveAndBuffer {
                 {
                     if result.is_err() {
                         num_dropped_on_status_age_checks += 1;
+                        packet_drop_stats().record(PacketDropReason::TransactionChecks, 1);
+                        record_decision(
+                            container,
+                            priority_id.id,
+                            CapturedDecision::DroppedOnTransactionChecks,
+                        );
                         container.remove_by_id(priority_id.id);
                         continue;
                     }
@@ -459,29 +697,89 @@ impl TransactionViewReceiveAndBuffer {
                     ) {
                         *result = Err(err);
                         num_dropped_on_status_age_checks += 1;
+                        packet_drop_stats().record(PacketDropReason::FeePayer, 1);
+                        record_decision(
+                            container,
+                            priority_id.id,
+                            CapturedDecision::DroppedOnFeePayerCheck,
+                        );
                         container.remove_by_id(priority_id.id);
                         continue;
                     }
                 }
+
+                // Read what is recorded about buffered transactions before pushing them, since
+                // pushing may evict them from the container.
+                let mut buffered = ArrayVec::<_, EXTRA_CAPACITY>::new();
+                if is_recording {
+                    for ((result, priority_id), reward) in check_results
+                        .iter()
+                        .zip(transaction_priority_ids.iter())
+                        .zip(rewards.iter())
+                    {
+                        if result.is_err() {
+                            continue;
+                        }
+                        let state = container
+                            .get_mut_transaction_state(priority_id.id)
+                            .expect("transaction must exist");
+                        let cost = state.cost();
+                        let transaction = &state.transaction_ttl().transaction;
+                        if let Some(loaded_addresses) = transaction.loaded_addresses() {
+                            capture.record_loaded_addresses(
+                                transaction.message_hash(),
+                                loaded_addresses,
+                            );
+                        }
+                        buffered.push((
+                            *transaction.signature(),
+                            *transaction.message_hash(),
+                            *reward,
+                            cost,
+                            priority_id.priority,
+                        ));
+                    }
+                }
+                rewards.clear();
+
                 // Push non-errored transaction into queue.
-                num_dropped_on_capacity += container.push_ids_into_queue(
+                let num_evicted = container.push_ids_into_queue(
                     check_results
                         .into_iter()
                         .zip(transaction_priority_ids.drain(..))
                         .filter(|(r, _)| r.is_ok())
                         .map(|(_, id)| id),
                 );
+                packet_drop_stats().record(PacketDropReason::Capacity, num_evicted as u64);
+                num_dropped_on_capacity += num_evicted;
+
+                for (signature, message_hash, reward, cost, priority) in buffered {
+                    capture.record_decision(
+                        working_slot,
+                        &signature,
+                        &message_hash,
+                        CapturedDecision::Buffered {
+                            reward,
+                            cost,
+                            priority,
+                            evicted: num_evicted > 0,
+                        },
+                    );
+                }
             };
 
         for packet_batch in packet_batch_message.iter() {
             for packet in packet_batch.iter() {
                 let Some(packet_data) = packet.data(..) else {
+                    packet_drop_stats().record(PacketDropReason::Sigverify, 1);
                     continue;
                 };
 
                 num_received += 1;
 
                 // Reserve free-space to copy packet into, run sanitization checks, and insert.
+                let mut reward = 0;
+                let mut drop_reason = None;
                 if let Some(transaction_id) =
                     container.try_insert_map_only_with_data(packet_data, |bytes| {
                         match Self::try_handle_packet(
@@ -492,12 +790,15 @@ impl TransactionViewReceiveAndBuffer {
                             sanitized_epoch,
                             transaction_account_lock_limit,
                         ) {
-                            Ok(state) => {
+                            Ok((state, transaction_reward)) => {
                                 num_buffered += 1;
+                                reward = transaction_reward;
                                 Ok(state)
                             }
-                            Err(()) => {
+                            Err(reason) => {
                                 num_dropped_on_receive += 1;
+                                packet_drop_stats().record(reason, 1);
+                                drop_reason = Some(reason);
                                 Err(())
                             }
                         }
@@ -509,17 +810,24 @@ impl TransactionViewReceiveAndBuffer {
                         .priority();
                     transaction_priority_ids
                         .push(TransactionPriorityId::new(priority, transaction_id));
+                    rewards.push(reward);
 
                     // If at capacity, run checks and remove invalid transactions.
                     if transaction_priority_ids.len() == EXTRA_CAPACITY {
-                        check_and_push_to_queue(container, &mut transaction_priority_ids);
+                        check_and_push_to_queue(
+                            container,
+                            &mut transaction_priority_ids,
+                            &mut rewards,
+                        );
                     }
+                } else if let Some(reason) = drop_reason.filter(|_| is_recording) {
+                    Self::record_dropped_packet(capture, working_slot, packet_data, reason);
                 }
             }
         }
 
         // Any remaining packets undergo status/age checks
-        check_and_push_to_queue(container, &mut transaction_priority_ids);
+        check_and_push_to_queue(container, &mut transaction_priority_ids, &mut rewards);
 
         let buffer_time_us = start.elapsed().as_micros() as u64;
         timing_metrics.update(|timing_metrics| {
@@ -542,6 +850,27 @@ impl TransactionViewReceiveAndBuffer {
         num_received
     }
 
+    /// Records dropping the packet with `packet_data` for `reason` before it was buffered, if
+    /// the packet can be parsed far enough to identify its transaction.
+    fn record_dropped_packet(
+        capture: &BankingCapture,
+        slot: Slot,
+        packet_data: &[u8],
+        reason: PacketDropReason,
+    ) {
+        let Some(decision) = CapturedDecision::from_packet_drop_reason(reason) else {
+            return;
+        };
+        let Ok(view) = SanitizedTransactionView::try_new_sanitized(packet_data) else {
+            return;
+        };
+        let Some(signature) = view.signatures().first() else {
+            return;
+        };
+        let message_hash = VersionedMessage::hash_raw_message(view.message_data());
+        capture.record_decision(slot, signature, &message_hash, decision);
+    }
+
     fn try_handle_packet(
         bytes: SharedBytes,
         root_bank: &Bank,
@@ -549,10 +878,10 @@ impl TransactionViewReceiveAndBuffer {
         alt_resolved_slot: Slot,
         sanitized_epoch: Epoch,
         transaction_account_lock_limit: usize,
-    ) -> Result<TransactionViewState, ()> {
+    ) -> Result<(TransactionViewState, u64), PacketDropReason> {
         // Parsing and basic sanitization checks
         let Ok(view) = SanitizedTransactionView::try_new_sanitized(bytes) else {
-            return Err(());
+            return Err(PacketDropReason::Sanitization);
         };
 
         let Ok(view) = RuntimeTransaction::<SanitizedTransactionView<_>>::try_from(
@@ -560,12 +889,12 @@ impl TransactionViewReceiveAndBuffer {
             MessageHash::Compute,
             None,
         ) else {
-            return Err(());
+            return Err(PacketDropReason::Sanitization);
         };
 
         // Discard non-vote packets if in vote-only mode.
         if root_bank.vote_only_bank() && !view.is_simple_vote_transaction() {
-            return Err(());
+            return Err(PacketDropReason::Sanitization);
         }
 
         // Check excessive pre-compiles.
@@ -574,20 +903,26 @@ impl TransactionViewReceiveAndBuffer {
             + signature_details.num_secp256k1_instruction_signatures()
             + signature_details.num_secp256r1_instruction_signatures();
         if num_precompiles > MAX_ALLOWED_PRECOMPILE_SIGNATURES {
-            return Err(());
+            return Err(PacketDropReason::ExcessivePrecompiles);
+        }
+
+        // v1 messages are parsed but not supported by the runtime yet, discard them before
+        // loading their addresses.
+        if matches!(view.version(), TransactionVersion::V1) {
+            return Err(PacketDropReason::Sanitization);
         }
 
         // Load addresses for transaction.
         let load_addresses_result = match view.version() {
             TransactionVersion::Legacy => Ok((None, u64::MAX)),
-            TransactionVersion::V0 => root_bank
+            TransactionVersion::V0 | TransactionVersion::V1 => root_bank
                 .load_addresses_from_ref(view.address_table_lookup_iter())
                 .map(|(loaded_addresses, deactivation_slot)| {
                     (Some(loaded_addresses), deactivation_slot)
                 }),
         };
         let Ok((loaded_addresses, deactivation_slot)) = load_addresses_result else {
-            return Err(());
+            return Err(PacketDropReason::Sanitization);
         };
 
         let Ok(view) = RuntimeTransaction::<ResolvedTransactionView<_>>::try_from(
@@ -595,32 +930,46 @@ impl TransactionViewReceiveAndBuffer {
             loaded_addresses,
             root_bank.get_reserved_account_keys(),
         ) else {
-            return Err(());
+            return Err(PacketDropReason::Sanitization);
         };
 
         if validate_account_locks(view.account_keys(), transaction_account_lock_limit).is_err() {
-            return Err(());
+            return Err(PacketDropReason::AccountLocks);
         }
 
         let Ok(compute_budget_limits) = view
             .compute_budget_instruction_details()
             .sanitize_and_convert_to_compute_budget_limits(&working_bank.feature_set)
         else {
-            return Err(());
+            return Err(PacketDropReason::InvalidComputeBudget);
         };
 
+        // Check the minimum compute unit price.
+        if working_bank
+            .feature_set
+            .is_active(&enforce_min_compute_unit_price::id())
+            && solana_fee::compute_unit_price_below_minimum(&view, working_bank.fee_features())
+                .is_some()
+        {
+            return Err(PacketDropReason::FeeFloor);
+        }
+
         let max_age = calculate_max_age(sanitized_epoch, deactivation_slot, alt_resolved_slot);
         let fee_budget_limits = FeeBudgetLimits::from(compute_budget_limits);
-        let (priority, cost) = calculate_priority_and_cost(&view, &fee_budget_limits, working_bank);
+        let (priority, cost, reward) =
+            calculate_priority_and_cost(&view, &fee_budget_limits, working_bank);
 
-        Ok(TransactionState::new(
-            SanitizedTransactionTTL {
-                transaction: view,
-                max_age,
-            },
-            None,
-            priority,
-            cost,
+        Ok((
+            TransactionState::new(
+                SanitizedTransactionTTL {
+                    transaction: view,
+                    max_age,
+                },
+                None,
+                priority,
+                cost,
+            ),
+            reward,
         ))
     }
 }
@@ -636,6 +985,8 @@ impl TransactionViewReceiveAndBuffer {
 /// where P is the priority, R is the reward,
 /// and C is the cost towards block-limits.
 ///
+/// The reward is returned along with the priority and cost.
+///
 /// Current minimum costs are on the order of several hundred,
 /// so the denominator is effectively C, and the +1 is simply
 /// to avoid any division by zero due to a bug - these costs
@@ -647,7 +998,7 @@ fn calculate_priority_and_cost(
     transaction: &impl TransactionWithMeta,
     fee_budget_limits: &FeeBudgetLimits,
     bank: &Bank,
-) -> (u64, u64) {
+) -> (u64, u64, u64) {
     let cost = CostModel::calculate_cost(transaction, &bank.feature_set).sum();
     let reward = bank.calculate_reward_for_transaction(transaction, fee_budget_limits);
 
@@ -662,6 +1013,7 @@ fn calculate_priority_and_cost(
             .saturating_mul(MULTIPLIER)
             .saturating_div(cost.saturating_add(1)),
         cost,
+        reward,
     )
 }
 
@@ -696,21 +1048,37 @@ fn calculate_max_age(
 mod tests {
     use {
         super::*,
-        crate::banking_stage::tests::create_slow_genesis_config,
+        crate::{
+            banking_stage::tests::create_slow_genesis_config,
+            banking_trace::{for_test, TimedCapturedEvent},
+        },
         crossbeam_channel::{unbounded, Receiver},
         solana_ledger::genesis_utils::GenesisConfigInfo,
         solana_perf::packet::{to_packet_batches, Packet, PacketBatch},
+        solana_poh::poh_recorder::BankStart,
         solana_pubkey::Pubkey,
+        solana_runtime::genesis_utils::{activate_feature, deactivate_features},
         solana_sdk::{
+            account::AccountSharedData,
+            address_lookup_table::{
+                self,
+                state::{AddressLookupTable, LookupTableMeta},
+            },
+            compute_budget::ComputeBudgetInstruction,
             hash::Hash,
-            message::{v0, AddressLookupTableAccount, VersionedMessage},
+            message::{
+                v0, AddressLookupTableAccount, MessageAddressTableLookup, MessageHeader,
+                VersionedMessage,
+            },
             packet::{Meta, PACKET_DATA_SIZE},
             signature::Keypair,
             signer::Signer,
             system_instruction,
             system_transaction::transfer,
-            transaction::VersionedTransaction,
+            transaction::{Transaction, VersionedTransaction},
         },
+        solana_send_transaction_service::transaction_ingestion_log::TransactionIngestionLog,
+        std::borrow::Cow,
         test_case::test_case,
     };
 
@@ -734,11 +1102,12 @@ mod tests {
         SanitizedTransactionReceiveAndBuffer,
         TransactionStateContainer<RuntimeTransaction<SanitizedTransaction>>,
     ) {
-        let receive_and_buffer = SanitizedTransactionReceiveAndBuffer {
-            packet_receiver: PacketDeserializer::new(receiver),
+        let receive_and_buffer = SanitizedTransactionReceiveAndBuffer::new(
+            PacketDeserializer::new(receiver),
             bank_forks,
-            forwarding_enabled: false,
-        };
+            false,
+            BankingCapture::default(),
+        );
         let container = TransactionStateContainer::with_capacity(TEST_CONTAINER_CAPACITY);
         (receive_and_buffer, container)
     }
@@ -750,10 +1119,40 @@ mod tests {
         TransactionViewReceiveAndBuffer,
         TransactionViewStateContainer,
     ) {
-        let receive_and_buffer = TransactionViewReceiveAndBuffer {
-            receiver,
+        let receive_and_buffer =
+            TransactionViewReceiveAndBuffer::new(receiver, bank_forks, BankingCapture::default());
+        let container = TransactionViewStateContainer::with_capacity(TEST_CONTAINER_CAPACITY);
+        (receive_and_buffer, container)
+    }
+
+    fn setup_sanitized_transaction_receive_and_buffer_with_capture(
+        receiver: Receiver<BankingPacketBatch>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        capture: BankingCapture,
+    ) -> (
+        SanitizedTransactionReceiveAndBuffer,
+        TransactionStateContainer<RuntimeTransaction<SanitizedTransaction>>,
+    ) {
+        let receive_and_buffer = SanitizedTransactionReceiveAndBuffer::new(
+            PacketDeserializer::new(receiver),
             bank_forks,
-        };
+            false,
+            capture,
+        );
+        let container = TransactionStateContainer::with_capacity(TEST_CONTAINER_CAPACITY);
+        (receive_and_buffer, container)
+    }
+
+    fn setup_transaction_view_receive_and_buffer_with_capture(
+        receiver: Receiver<BankingPacketBatch>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        capture: BankingCapture,
+    ) -> (
+        TransactionViewReceiveAndBuffer,
+        TransactionViewStateContainer,
+    ) {
+        let receive_and_buffer =
+            TransactionViewReceiveAndBuffer::new(receiver, bank_forks, capture);
         let container = TransactionViewStateContainer::with_capacity(TEST_CONTAINER_CAPACITY);
         (receive_and_buffer, container)
     }
@@ -1106,6 +1505,336 @@ mod tests {
         verify_container(&mut container, 1);
     }
 
+    #[test_case(setup_sanitized_transaction_receive_and_buffer; "testcase-sdk")]
+    #[test_case(setup_transaction_view_receive_and_buffer; "testcase-view")]
+    fn test_receive_and_buffer_min_compute_unit_price<R: ReceiveAndBuffer>(
+        setup_receive_and_buffer: impl FnOnce(
+            Receiver<BankingPacketBatch>,
+            Arc<RwLock<BankForks>>,
+        ) -> (R, R::Container),
+    ) {
+        let (sender, receiver) = unbounded();
+        let GenesisConfigInfo {
+            mut genesis_config,
+            mint_keypair,
+            ..
+        } = create_slow_genesis_config(u64::MAX);
+        activate_feature(&mut genesis_config, enforce_min_compute_unit_price::id());
+        let (_bank, bank_forks) = Bank::new_no_wallclock_throttle_for_tests(&genesis_config);
+        let (mut receive_and_buffer, mut container) =
+            setup_receive_and_buffer(receiver, bank_forks.clone());
+        let mut timing_metrics = SchedulerTimingMetrics::default();
+        let mut count_metrics = SchedulerCountMetrics::default();
+
+        let recent_blockhash = bank_forks.read().unwrap().root_bank().last_blockhash();
+        let prioritized_transfer = |compute_unit_price| {
+            Transaction::new_signed_with_payer(
+                &[
+                    system_instruction::transfer(&mint_keypair.pubkey(), &Pubkey::new_unique(), 1),
+                    ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+                ],
+                Some(&mint_keypair.pubkey()),
+                &[&mint_keypair],
+                recent_blockhash,
+            )
+        };
+        // Only the transfer bidding at least the minimum price is buffered
+        let transactions = [
+            prioritized_transfer(1_000),
+            prioritized_transfer(solana_fee::MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS),
+        ];
+        let packet_batches = Arc::new(to_packet_batches(&transactions, 2));
+        sender.send(packet_batches).unwrap();
+
+        let num_received = receive_and_buffer
+            .receive_and_buffer_packets(
+                &mut container,
+                &mut timing_metrics,
+                &mut count_metrics,
+                &BufferedPacketsDecision::Hold,
+            )
+            .unwrap();
+
+        assert_eq!(num_received, 2);
+        verify_container(&mut container, 1);
+    }
+
+    #[test_case(setup_sanitized_transaction_receive_and_buffer; "testcase-sdk")]
+    #[test_case(setup_transaction_view_receive_and_buffer; "testcase-view")]
+    fn test_receive_and_buffer_raised_account_lock_limit<R: ReceiveAndBuffer>(
+        setup_receive_and_buffer: impl Fn(
+            Receiver<BankingPacketBatch>,
+            Arc<RwLock<BankForks>>,
+        ) -> (R, R::Container),
+    ) {
+        for raise_tx_account_lock_limit in [false, true] {
+            let (sender, receiver) = unbounded();
+            let GenesisConfigInfo {
+                mut genesis_config,
+                mint_keypair,
+                ..
+            } = create_slow_genesis_config(u64::MAX);
+            if raise_tx_account_lock_limit {
+                activate_feature(
+                    &mut genesis_config,
+                    agave_feature_set::raise_tx_account_lock_limit::id(),
+                );
+            } else {
+                deactivate_features(
+                    &mut genesis_config,
+                    &vec![agave_feature_set::raise_tx_account_lock_limit::id()],
+                );
+            }
+            let (bank, bank_forks) = Bank::new_no_wallclock_throttle_for_tests(&genesis_config);
+            let (mut receive_and_buffer, mut container) =
+                setup_receive_and_buffer(receiver, bank_forks.clone());
+            let mut timing_metrics = SchedulerTimingMetrics::default();
+            let mut count_metrics = SchedulerCountMetrics::default();
+
+            // 1 static key and 199 looked up keys: over the default limit, under the raised one
+            let table_key = Pubkey::new_unique();
+            let table_addresses: Vec<_> = (0..199).map(|_| Pubkey::new_unique()).collect();
+            let table_state = AddressLookupTable {
+                meta: LookupTableMeta {
+                    // all addresses are usable in the slot the table was extended in
+                    last_extended_slot_start_index: table_addresses.len() as u8,
+                    ..LookupTableMeta::default()
+                },
+                addresses: Cow::Owned(table_addresses),
+            };
+            bank.store_account(
+                &table_key,
+                &AccountSharedData::create(
+                    1,
+                    table_state.serialize_for_tests().unwrap(),
+                    address_lookup_table::program::id(),
+                    false,
+                    0,
+                ),
+            );
+            let message = v0::Message {
+                header: MessageHeader {
+                    num_required_signatures: 1,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 0,
+                },
+                account_keys: vec![mint_keypair.pubkey()],
+                recent_blockhash: bank.last_blockhash(),
+                instructions: vec![],
+                address_table_lookups: vec![MessageAddressTableLookup {
+                    account_key: table_key,
+                    writable_indexes: vec![],
+                    readonly_indexes: (0..199).collect(),
+                }],
+            };
+            let transaction =
+                VersionedTransaction::try_new(VersionedMessage::V0(message), &[&mint_keypair])
+                    .unwrap();
+            let packet_batches = Arc::new(to_packet_batches(&[transaction], 1));
+            sender.send(packet_batches).unwrap();
+
+            let num_received = receive_and_buffer
+                .receive_and_buffer_packets(
+                    &mut container,
+                    &mut timing_metrics,
+                    &mut count_metrics,
+                    &BufferedPacketsDecision::Hold,
+                )
+                .unwrap();
+
+            assert_eq!(num_received, 1);
+            verify_container(&mut container, usize::from(raise_tx_account_lock_limit));
+        }
+    }
+
+    #[test_case(setup_sanitized_transaction_receive_and_buffer_with_capture; "testcase-sdk")]
+    #[test_case(setup_transaction_view_receive_and_buffer_with_capture; "testcase-view")]
+    fn test_receive_and_buffer_capture<R: ReceiveAndBuffer>(
+        setup_receive_and_buffer: impl FnOnce(
+            Receiver<BankingPacketBatch>,
+            Arc<RwLock<BankForks>>,
+            BankingCapture,
+        ) -> (R, R::Container),
+    ) {
+        let (sender, receiver) = unbounded();
+        let (bank_forks, mint_keypair) = test_bank_forks();
+        let (capture, capture_receiver) = for_test::capture_channel();
+        let (mut receive_and_buffer, mut container) =
+            setup_receive_and_buffer(receiver, bank_forks.clone(), capture);
+        let mut timing_metrics = SchedulerTimingMetrics::default();
+        let mut count_metrics = SchedulerCountMetrics::default();
+
+        let bank = bank_forks.read().unwrap().working_bank();
+        let valid_transaction = transfer(
+            &mint_keypair,
+            &Pubkey::new_unique(),
+            1,
+            bank.last_blockhash(),
+        );
+        let invalid_blockhash_transaction =
+            transfer(&mint_keypair, &Pubkey::new_unique(), 1, Hash::new_unique());
+        let packet_batches = Arc::new(to_packet_batches(
+            &[
+                valid_transaction.clone(),
+                invalid_blockhash_transaction.clone(),
+            ],
+            2,
+        ));
+        sender.send(packet_batches.clone()).unwrap();
+
+        let decision = BufferedPacketsDecision::Consume(BankStart {
+            working_bank: bank.clone(),
+            bank_creation_time: Arc::new(Instant::now()),
+        });
+        receive_and_buffer
+            .receive_and_buffer_packets(
+                &mut container,
+                &mut timing_metrics,
+                &mut count_metrics,
+                &decision,
+            )
+            .unwrap();
+        receive_and_buffer
+            .receive_and_buffer_packets(
+                &mut container,
+                &mut timing_metrics,
+                &mut count_metrics,
+                &BufferedPacketsDecision::Hold,
+            )
+            .unwrap();
+
+        let events = capture_receiver
+            .try_iter()
+            .map(|TimedCapturedEvent(_, event)| event)
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 5);
+        assert_eq!(
+            events[0],
+            CapturedEvent::BankStart {
+                slot: bank.slot(),
+                parent_slot: bank.parent_slot(),
+                lamports_per_signature: bank.get_lamports_per_signature(),
+            }
+        );
+        // The view receiver checks the age of transactions in batches, after inserting them
+        let (buffered, dropped) = if matches!(
+            events[1],
+            CapturedEvent::Decision {
+                decision: CapturedDecision::Buffered { .. },
+                ..
+            }
+        ) {
+            (&events[1], &events[2])
+        } else {
+            (&events[2], &events[1])
+        };
+        assert_matches!(
+            buffered,
+            CapturedEvent::Decision {
+                slot,
+                message_hash,
+                decision: CapturedDecision::Buffered { evicted: false, .. },
+            } if *slot == bank.slot() && *message_hash == valid_transaction.message.hash()
+        );
+        assert_matches!(
+            dropped,
+            CapturedEvent::Decision {
+                slot,
+                message_hash,
+                decision: CapturedDecision::DroppedOnTransactionChecks,
+            } if *slot == bank.slot() && *message_hash == invalid_blockhash_transaction.message.hash()
+        );
+        assert_eq!(events[3], CapturedEvent::Received { slot: bank.slot() });
+        assert_eq!(events[4], CapturedEvent::BankEnd { slot: bank.slot() });
+        verify_container(&mut container, 1);
+
+        // Receiving after the leader slot ended records when the container gets cleared
+        sender.send(packet_batches).unwrap();
+        for decision in [
+            BufferedPacketsDecision::ForwardAndHold,
+            BufferedPacketsDecision::Forward,
+        ] {
+            receive_and_buffer
+                .receive_and_buffer_packets(
+                    &mut container,
+                    &mut timing_metrics,
+                    &mut count_metrics,
+                    &decision,
+                )
+                .unwrap();
+        }
+        let events = capture_receiver
+            .try_iter()
+            .map(|TimedCapturedEvent(_, event)| event)
+            .collect::<Vec<_>>();
+        assert_matches!(events.last(), Some(CapturedEvent::Cleared));
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, CapturedEvent::Received { .. })));
+    }
+
+    #[test_case(setup_sanitized_transaction_receive_and_buffer_with_capture; "testcase-sdk")]
+    #[test_case(setup_transaction_view_receive_and_buffer_with_capture; "testcase-view")]
+    fn test_receive_and_buffer_ingestion_log<R: ReceiveAndBuffer>(
+        setup_receive_and_buffer: impl FnOnce(
+            Receiver<BankingPacketBatch>,
+            Arc<RwLock<BankForks>>,
+            BankingCapture,
+        ) -> (R, R::Container),
+    ) {
+        let (sender, receiver) = unbounded();
+        let (bank_forks, mint_keypair) = test_bank_forks();
+        let ingestion_log = Arc::new(TransactionIngestionLog::new(16));
+        let (mut receive_and_buffer, mut container) = setup_receive_and_buffer(
+            receiver,
+            bank_forks.clone(),
+            BankingCapture::default().with_ingestion_log(Some(ingestion_log.clone())),
+        );
+        let mut timing_metrics = SchedulerTimingMetrics::default();
+        let mut count_metrics = SchedulerCountMetrics::default();
+
+        let bank = bank_forks.read().unwrap().working_bank();
+        let valid_transaction = transfer(
+            &mint_keypair,
+            &Pubkey::new_unique(),
+            1,
+            bank.last_blockhash(),
+        );
+        let invalid_blockhash_transaction =
+            transfer(&mint_keypair, &Pubkey::new_unique(), 1, Hash::new_unique());
+        let packet_batches = Arc::new(to_packet_batches(
+            &[
+                valid_transaction.clone(),
+                invalid_blockhash_transaction.clone(),
+            ],
+            2,
+        ));
+        sender.send(packet_batches).unwrap();
+
+        receive_and_buffer
+            .receive_and_buffer_packets(
+                &mut container,
+                &mut timing_metrics,
+                &mut count_metrics,
+                &BufferedPacketsDecision::Hold,
+            )
+            .unwrap();
+        verify_container(&mut container, 1);
+
+        let events = ingestion_log.get_events(&valid_transaction.signatures[0]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].stage, IngestionStage::ReceivedViaTpu);
+        assert_eq!(events[0].slot, Some(bank.slot()));
+
+        let events = ingestion_log.get_events(&invalid_blockhash_transaction.signatures[0]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].stage, IngestionStage::Filtered);
+        assert_eq!(
+            events[0].reason.as_deref(),
+            Some("already processed or blockhash expired")
+        );
+    }
+
     #[test_case(setup_sanitized_transaction_receive_and_buffer; "testcase-sdk")]
     #[test_case(setup_transaction_view_receive_and_buffer; "testcase-view")]
     fn test_receive_and_buffer_overfull<R: ReceiveAndBuffer>(
@@ -1,12 +1,31 @@
 use {
     super::{scheduler_error::SchedulerError, transaction_state_container::StateContainer},
+    crate::banking_stage::scheduler_messages::{ConsumeWork, FinishedConsumeWork},
+    crossbeam_channel::{Receiver, Sender},
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
 };
 
-pub(crate) trait Scheduler<Tx: TransactionWithMeta> {
+/// A scheduling strategy run by the central scheduler thread of the banking stage.
+///
+/// The [`SchedulerController`] buffers received transactions in a [`StateContainer`] ordered by
+/// priority, and calls `schedule` whenever the node is leader, or about to be. Implementations
+/// decide which of the buffered transactions are sent, and in which batches, to the consume
+/// workers, and must release the account locks of a batch once `receive_completed` returns it.
+///
+/// The built-in strategies are selected with `--block-production-method`. Others are run by
+/// passing their [`TransactionSchedulerFactory`] to [`BankingStage::new_central_scheduler`].
+///
+/// [`SchedulerController`]: super::scheduler_controller::SchedulerController
+/// [`BankingStage::new_central_scheduler`]: crate::banking_stage::BankingStage::new_central_scheduler
+pub trait TransactionScheduler<Tx: TransactionWithMeta> {
     /// Schedule transactions from `container`.
     /// pre-graph and pre-lock filters may be passed to be applied
     /// before specific actions internally.
+    ///
+    /// `pre_graph_filter` is called with chunks of transactions popped from `container`, and
+    /// clears the entry of `results` of each transaction that must be dropped, e.g. because it
+    /// is too old or its fee payer cannot pay. `pre_lock_filter` returns false for a transaction
+    /// that must not be scheduled now, before its accounts are locked.
     fn schedule<S: StateContainer<Tx>>(
         &mut self,
         container: &mut S,
@@ -22,9 +41,24 @@ pub(crate) trait Scheduler<Tx: TransactionWithMeta> {
     ) -> Result<(usize, usize), SchedulerError>;
 }
 
+/// Builds the [`TransactionScheduler`] of the central scheduler thread.
+///
+/// The scheduler sends its batches to the consume workers through `consume_work_senders`, one
+/// sender per worker, and receives them back through `finished_consume_work_receiver` once
+/// processed.
+pub trait TransactionSchedulerFactory: Send + 'static {
+    type Scheduler<Tx: TransactionWithMeta>: TransactionScheduler<Tx>;
+
+    fn new_scheduler<Tx: TransactionWithMeta>(
+        self,
+        consume_work_senders: Vec<Sender<ConsumeWork<Tx>>>,
+        finished_consume_work_receiver: Receiver<FinishedConsumeWork<Tx>>,
+    ) -> Self::Scheduler<Tx>;
+}
+
 /// Metrics from scheduling transactions.
 #[derive(Default, Debug, PartialEq, Eq)]
-pub(crate) struct SchedulingSummary {
+pub struct SchedulingSummary {
     /// Number of transactions scheduled.
     pub num_scheduled: usize,
     /// Number of transactions that were not scheduled due to conflicts.
@@ -4,13 +4,14 @@
 use {
     super::{
         receive_and_buffer::ReceiveAndBuffer,
-        scheduler::Scheduler,
+        scheduler::TransactionScheduler,
         scheduler_error::SchedulerError,
         scheduler_metrics::{
             SchedulerCountMetrics, SchedulerLeaderDetectionMetrics, SchedulerTimingMetrics,
         },
     },
     crate::banking_stage::{
+        conflict_graph::conflict_graph_sampler,
         consume_worker::ConsumeWorkerMetrics,
         consumer::Consumer,
         decision_maker::{BufferedPacketsDecision, DecisionMaker},
@@ -37,7 +38,7 @@ pub(crate) struct SchedulerController<C, R, S>
 where
     C: LikeClusterInfo,
     R: ReceiveAndBuffer,
-    S: Scheduler<R::Transaction>,
+    S: TransactionScheduler<R::Transaction>,
 {
     /// Decision maker for determining what should be done with transactions.
     decision_maker: DecisionMaker,
@@ -66,7 +67,7 @@ impl<C, R, S> SchedulerController<C, R, S>
 where
     C: LikeClusterInfo,
     R: ReceiveAndBuffer,
-    S: Scheduler<R::Transaction>,
+    S: TransactionScheduler<R::Transaction>,
 {
     pub fn new(
         decision_maker: DecisionMaker,
@@ -114,6 +115,7 @@ where
                 .maybe_report_and_reset_slot(new_leader_slot);
             self.timing_metrics
                 .maybe_report_and_reset_slot(new_leader_slot);
+            conflict_graph_sampler().end_slot(new_leader_slot);
 
             self.receive_completed()?;
             self.process_transactions(&decision)?;
@@ -147,6 +149,7 @@ where
         let forwarding_enabled = self.forwarder.is_some();
         match decision {
             BufferedPacketsDecision::Consume(bank_start) => {
+                let slot = bank_start.working_bank.slot();
                 let (scheduling_summary, schedule_time_us) = measure_us!(self.scheduler.schedule(
                     &mut self.container,
                     |txs, results| {
@@ -157,7 +160,11 @@ where
                             MAX_PROCESSING_AGE,
                         )
                     },
-                    |_| true // no pre-lock filter for now
+                    |tx| {
+                        // no pre-lock filter for now, only sample the conflict graph if requested
+                        conflict_graph_sampler().record(slot, tx);
+                        true
+                    }
                 )?);
 
                 self.count_metrics.update(|count_metrics| {
@@ -458,16 +465,19 @@ where
 mod tests {
     use {
         super::*,
-        crate::banking_stage::{
-            consumer::TARGET_NUM_TRANSACTIONS_PER_BATCH,
-            packet_deserializer::PacketDeserializer,
-            scheduler_messages::{ConsumeWork, FinishedConsumeWork, TransactionBatchId},
-            tests::create_slow_genesis_config,
-            transaction_scheduler::{
-                prio_graph_scheduler::{PrioGraphScheduler, PrioGraphSchedulerConfig},
-                receive_and_buffer::SanitizedTransactionReceiveAndBuffer,
+        crate::{
+            banking_stage::{
+                consumer::TARGET_NUM_TRANSACTIONS_PER_BATCH,
+                packet_deserializer::PacketDeserializer,
+                scheduler_messages::{ConsumeWork, FinishedConsumeWork, TransactionBatchId},
+                tests::create_slow_genesis_config,
+                transaction_scheduler::{
+                    prio_graph_scheduler::{PrioGraphScheduler, PrioGraphSchedulerConfig},
+                    receive_and_buffer::SanitizedTransactionReceiveAndBuffer,
+                },
+                TransactionViewReceiveAndBuffer,
             },
-            TransactionViewReceiveAndBuffer,
+            banking_trace::BankingCapture,
         },
         agave_banking_stage_ingress_types::{BankingPacketBatch, BankingPacketReceiver},
         crossbeam_channel::{unbounded, Receiver, Sender},
@@ -518,6 +528,7 @@ mod tests {
             PacketDeserializer::new(receiver),
             bank_forks,
             false,
+            BankingCapture::default(),
         )
     }
 
@@ -525,10 +536,7 @@ mod tests {
         receiver: BankingPacketReceiver,
         bank_forks: Arc<RwLock<BankForks>>,
     ) -> TransactionViewReceiveAndBuffer {
-        TransactionViewReceiveAndBuffer {
-            receiver,
-            bank_forks,
-        }
+        TransactionViewReceiveAndBuffer::new(receiver, bank_forks, BankingCapture::default())
     }
 
     #[allow(clippy::type_complexity)]
@@ -641,7 +649,7 @@ mod tests {
         scheduler_controller: &mut SchedulerController<
             Arc<ClusterInfo>,
             R,
-            impl Scheduler<R::Transaction>,
+            impl TransactionScheduler<R::Transaction>,
         >,
     ) {
         let decision = scheduler_controller
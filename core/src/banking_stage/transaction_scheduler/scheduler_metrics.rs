@@ -81,6 +81,8 @@ pub struct SchedulerCountMetricsInner {
     pub min_prioritization_fees: u64,
     /// Max prioritization fees in the transaction container
     pub max_prioritization_fees: u64,
+    /// Max number of packet batches waiting in the non-vote receive channel.
+    pub max_receive_queue_depth: usize,
 }
 
 impl IntervalSchedulerCountMetrics {
@@ -149,7 +151,8 @@ impl SchedulerCountMetricsInner {
             ),
             ("num_dropped_on_capacity", self.num_dropped_on_capacity, i64),
             ("min_priority", self.get_min_priority(), i64),
-            ("max_priority", self.get_max_priority(), i64)
+            ("max_priority", self.get_max_priority(), i64),
+            ("max_receive_queue_depth", self.max_receive_queue_depth, i64)
         );
         if let Some(slot) = slot {
             datapoint.add_field_i64("slot", slot as i64);
@@ -173,6 +176,7 @@ impl SchedulerCountMetricsInner {
             || self.num_dropped_on_clear != 0
             || self.num_dropped_on_age_and_status != 0
             || self.num_dropped_on_capacity != 0
+            || self.max_receive_queue_depth != 0
     }
 
     fn reset(&mut self) {
@@ -193,6 +197,7 @@ impl SchedulerCountMetricsInner {
         self.num_dropped_on_capacity = 0;
         self.min_prioritization_fees = u64::MAX;
         self.max_prioritization_fees = 0;
+        self.max_receive_queue_depth = 0;
     }
 
     pub fn update_priority_stats(&mut self, min_max_fees: MinMaxResult<u64>) {
@@ -7,9 +7,9 @@ use {
 };
 
 /// Simple wrapper type to tie a sanitized transaction to max age slot.
-pub(crate) struct SanitizedTransactionTTL<Tx> {
-    pub(crate) transaction: Tx,
-    pub(crate) max_age: MaxAge,
+pub struct SanitizedTransactionTTL<Tx> {
+    pub transaction: Tx,
+    pub max_age: MaxAge,
 }
 
 /// TransactionState is used to track the state of a transaction in the transaction scheduler
@@ -32,7 +32,7 @@ pub(crate) struct SanitizedTransactionTTL<Tx> {
 ///   to the appropriate thread for processing. This is done to avoid cloning the
 ///  `SanitizedTransaction`.
 #[allow(clippy::large_enum_variant)]
-pub(crate) enum TransactionState<Tx> {
+pub enum TransactionState<Tx> {
     /// The transaction is available for scheduling.
     Unprocessed {
         transaction_ttl: SanitizedTransactionTTL<Tx>,
@@ -76,7 +76,7 @@ impl<Tx> TransactionState<Tx> {
     /// Return the priority of the transaction.
     /// This is *not* the same as the `compute_unit_price` of the transaction.
     /// The priority is used to order transactions for processing.
-    pub(crate) fn priority(&self) -> u64 {
+    pub fn priority(&self) -> u64 {
         match self {
             Self::Unprocessed { priority, .. } => *priority,
             Self::Pending { priority, .. } => *priority,
@@ -85,7 +85,7 @@ impl<Tx> TransactionState<Tx> {
     }
 
     /// Return the cost of the transaction.
-    pub(crate) fn cost(&self) -> u64 {
+    pub fn cost(&self) -> u64 {
         match self {
             Self::Unprocessed { cost, .. } => *cost,
             Self::Pending { cost, .. } => *cost,
@@ -134,7 +134,7 @@ impl<Tx> TransactionState<Tx> {
     /// # Panics
     /// This method will panic if the transaction is already in the `Pending` state,
     ///   as this is an invalid state transition.
-    pub(crate) fn transition_to_pending(&mut self) -> SanitizedTransactionTTL<Tx> {
+    pub fn transition_to_pending(&mut self) -> SanitizedTransactionTTL<Tx> {
         match self.take() {
             TransactionState::Unprocessed {
                 transaction_ttl,
@@ -164,10 +164,7 @@ impl<Tx> TransactionState<Tx> {
     /// # Panics
     /// This method will panic if the transaction is already in the `Unprocessed`
     ///   state, as this is an invalid state transition.
-    pub(crate) fn transition_to_unprocessed(
-        &mut self,
-        transaction_ttl: SanitizedTransactionTTL<Tx>,
-    ) {
+    pub fn transition_to_unprocessed(&mut self, transaction_ttl: SanitizedTransactionTTL<Tx>) {
         match self.take() {
             TransactionState::Unprocessed { .. } => panic!("already unprocessed"),
             TransactionState::Pending {
@@ -192,7 +189,7 @@ impl<Tx> TransactionState<Tx> {
     ///
     /// # Panics
     /// This method will panic if the transaction is in the `Pending` state.
-    pub(crate) fn transaction_ttl(&self) -> &SanitizedTransactionTTL<Tx> {
+    pub fn transaction_ttl(&self) -> &SanitizedTransactionTTL<Tx> {
         match self {
             Self::Unprocessed {
                 transaction_ttl, ..
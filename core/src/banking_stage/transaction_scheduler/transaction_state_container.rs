@@ -49,7 +49,7 @@ pub(crate) struct TransactionStateContainer<Tx: TransactionWithMeta> {
     id_to_transaction_state: Slab<TransactionState<Tx>>,
 }
 
-pub(crate) trait StateContainer<Tx: TransactionWithMeta> {
+pub trait StateContainer<Tx: TransactionWithMeta> {
     /// Create a new `TransactionStateContainer` with the given capacity.
     fn with_capacity(capacity: usize) -> Self;
 
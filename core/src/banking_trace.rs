@@ -4,7 +4,14 @@ use {
     chrono::{DateTime, Local},
     crossbeam_channel::{unbounded, Receiver, SendError, Sender, TryRecvError},
     rolling_file::{RollingCondition, RollingConditionBasic, RollingFileAppender},
-    solana_sdk::{hash::Hash, slot_history::Slot},
+    solana_sdk::{
+        hash::Hash, message::v0::LoadedAddresses, pubkey::Pubkey, signature::Signature,
+        slot_history::Slot,
+    },
+    solana_send_transaction_service::transaction_ingestion_log::{
+        IngestionEvent, IngestionStage, TransactionIngestionLog,
+    },
+    solana_streamer::packet_drop_stats::PacketDropReason,
     std::{
         fs::{create_dir_all, remove_dir_all},
         io::{self, Write},
@@ -40,6 +47,7 @@ pub enum TraceError {
 }
 
 pub(crate) const BASENAME: &str = "events";
+pub const CAPTURE_BASENAME: &str = "captures";
 const TRACE_FILE_ROTATE_COUNT: u64 = 14; // target 2 weeks retention under normal load
 const TRACE_FILE_WRITE_INTERVAL_MS: u64 = 100;
 const BUF_WRITER_CAPACITY: usize = 10 * 1024 * 1024;
@@ -57,6 +65,24 @@ struct ActiveTracer {
 #[derive(Debug)]
 pub struct BankingTracer {
     active_tracer: Option<ActiveTracer>,
+    active_capture: Option<ActiveCapture>,
+}
+
+#[derive(Clone, Debug)]
+struct ActiveCapture {
+    capture_sender: Sender<TimedCapturedEvent>,
+    exit: Arc<AtomicBool>,
+}
+
+/// Handle used by the banking stage to record the decisions it made about traced packets.
+///
+/// Captured events are written next to the banking trace events (see [`CAPTURE_BASENAME`]) so
+/// that scheduling can be re-run offline and compared against what the leader actually did.
+/// The same decisions are recorded in the transaction ingestion log, if one is attached.
+#[derive(Clone, Debug, Default)]
+pub struct BankingCapture {
+    active_capture: Option<ActiveCapture>,
+    ingestion_log: Option<Arc<TransactionIngestionLog>>,
 }
 
 #[cfg_attr(
@@ -74,6 +100,108 @@ pub enum TracedEvent {
     BlockAndBankHash(Slot, Hash, Hash),
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TimedCapturedEvent(pub std::time::SystemTime, pub CapturedEvent);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum CapturedEvent {
+    /// The scheduler started buffering for the leader bank of `slot`
+    BankStart {
+        slot: Slot,
+        parent_slot: Slot,
+        lamports_per_signature: u64,
+    },
+    /// The scheduler stopped buffering for the leader bank of `slot`
+    BankEnd { slot: Slot },
+    /// The outcome of buffering a single received transaction while `slot` was the working bank
+    Decision {
+        slot: Slot,
+        message_hash: Hash,
+        decision: CapturedDecision,
+    },
+    /// The addresses a transaction loaded from address lookup tables, recorded right before it
+    /// is buffered so that it can be sanitized again offline
+    LoadedAddresses {
+        message_hash: Hash,
+        writable: Vec<Pubkey>,
+        readonly: Vec<Pubkey>,
+    },
+    /// A receive during the leader bank of `slot` got transactions. The scheduler runs again
+    /// before the next receive.
+    Received { slot: Slot },
+    /// The scheduler dropped all of its buffered transactions, as it does when the upcoming
+    /// leader is another node
+    Cleared,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapturedDecision {
+    DroppedOnSanitization,
+    DroppedOnLockValidation,
+    DroppedOnComputeBudget,
+    DroppedOnTransactionChecks,
+    DroppedOnFeePayerCheck,
+    Buffered {
+        /// Fee paid to the leader, as computed by `Bank::calculate_reward_for_transaction()`
+        reward: u64,
+        cost: u64,
+        priority: u64,
+        /// Whether buffering this transaction evicted another one from the full container. The
+        /// transaction view structure buffers transactions in batches, and sets this for every
+        /// transaction of a batch that evicted any.
+        evicted: bool,
+    },
+    /// Bid below the minimum compute unit price while `enforce_min_compute_unit_price` is active
+    DroppedOnComputeUnitPrice,
+}
+
+impl CapturedDecision {
+    /// Why the transaction was dropped, `None` if it was buffered.
+    pub fn drop_reason(&self) -> Option<&'static str> {
+        match self {
+            Self::DroppedOnSanitization => Some("sanitization failed"),
+            Self::DroppedOnLockValidation => Some("account lock validation failed"),
+            Self::DroppedOnComputeBudget => Some("invalid compute budget instructions"),
+            Self::DroppedOnTransactionChecks => Some("already processed or blockhash expired"),
+            Self::DroppedOnFeePayerCheck => Some("fee payer check failed"),
+            Self::DroppedOnComputeUnitPrice => Some("compute unit price below minimum"),
+            Self::Buffered { .. } => None,
+        }
+    }
+
+    /// The decision a transaction dropped for `reason` is captured as, `None` if drops for
+    /// `reason` happen before buffering is attempted.
+    pub fn from_packet_drop_reason(reason: PacketDropReason) -> Option<Self> {
+        match reason {
+            PacketDropReason::Sanitization | PacketDropReason::ExcessivePrecompiles => {
+                Some(Self::DroppedOnSanitization)
+            }
+            PacketDropReason::AccountLocks => Some(Self::DroppedOnLockValidation),
+            PacketDropReason::InvalidComputeBudget => Some(Self::DroppedOnComputeBudget),
+            PacketDropReason::TransactionChecks => Some(Self::DroppedOnTransactionChecks),
+            PacketDropReason::FeePayer => Some(Self::DroppedOnFeePayerCheck),
+            PacketDropReason::FeeFloor => Some(Self::DroppedOnComputeUnitPrice),
+            PacketDropReason::RateLimit
+            | PacketDropReason::Sigverify
+            | PacketDropReason::InsufficientComputeUnitLimit
+            | PacketDropReason::Capacity => None,
+        }
+    }
+
+    /// The reason the transaction was dropped is counted under, `None` if it was buffered.
+    pub fn packet_drop_reason(&self) -> Option<PacketDropReason> {
+        match self {
+            Self::DroppedOnSanitization => Some(PacketDropReason::Sanitization),
+            Self::DroppedOnLockValidation => Some(PacketDropReason::AccountLocks),
+            Self::DroppedOnComputeBudget => Some(PacketDropReason::InvalidComputeBudget),
+            Self::DroppedOnTransactionChecks => Some(PacketDropReason::TransactionChecks),
+            Self::DroppedOnFeePayerCheck => Some(PacketDropReason::FeePayer),
+            Self::DroppedOnComputeUnitPrice => Some(PacketDropReason::FeeFloor),
+            Self::Buffered { .. } => None,
+        }
+    }
+}
+
 #[cfg_attr(feature = "frozen-abi", derive(AbiExample, AbiEnumVisitor))]
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
 pub enum ChannelLabel {
@@ -206,13 +334,105 @@ impl Channels {
     }
 }
 
+impl BankingCapture {
+    pub fn with_ingestion_log(self, ingestion_log: Option<Arc<TransactionIngestionLog>>) -> Self {
+        Self {
+            ingestion_log,
+            ..self
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.active_capture.is_some()
+    }
+
+    pub fn ingestion_log(&self) -> Option<&Arc<TransactionIngestionLog>> {
+        self.ingestion_log.as_ref()
+    }
+
+    /// Records `decision` about the transaction `signature`, made while `slot` was the working
+    /// bank.
+    pub fn record_decision(
+        &self,
+        slot: Slot,
+        signature: &Signature,
+        message_hash: &Hash,
+        decision: CapturedDecision,
+    ) {
+        self.record(|| CapturedEvent::Decision {
+            slot,
+            message_hash: *message_hash,
+            decision,
+        });
+        self.record_ingestion(signature, || {
+            let event = match decision.drop_reason() {
+                Some(reason) => IngestionEvent::new(IngestionStage::Filtered).with_reason(reason),
+                None => IngestionEvent::new(IngestionStage::ReceivedViaTpu),
+            };
+            event.with_slot(slot)
+        });
+    }
+
+    /// Records the addresses the transaction with `message_hash` loaded from address lookup
+    /// tables, if any.
+    pub fn record_loaded_addresses(&self, message_hash: &Hash, loaded_addresses: &LoadedAddresses) {
+        if loaded_addresses.is_empty() {
+            return;
+        }
+        self.record(|| CapturedEvent::LoadedAddresses {
+            message_hash: *message_hash,
+            writable: loaded_addresses.writable.clone(),
+            readonly: loaded_addresses.readonly.clone(),
+        });
+    }
+
+    pub fn record_ingestion(
+        &self,
+        signature: &Signature,
+        on_record: impl FnOnce() -> IngestionEvent,
+    ) {
+        if let Some(ingestion_log) = &self.ingestion_log {
+            ingestion_log.record(*signature, on_record());
+        }
+    }
+
+    pub fn record(&self, on_capture: impl FnOnce() -> CapturedEvent) {
+        if let Some(ActiveCapture {
+            capture_sender,
+            exit,
+        }) = &self.active_capture
+        {
+            if !exit.load(Ordering::Relaxed) {
+                capture_sender
+                    .send(TimedCapturedEvent(SystemTime::now(), on_capture()))
+                    .expect("active tracer thread unless exited");
+            }
+        }
+    }
+}
+
 impl BankingTracer {
     pub fn new(
         maybe_config: Option<(&PathBuf, Arc<AtomicBool>, DirByteLimit)>,
+    ) -> Result<(Arc<Self>, TracerThread), TraceError> {
+        Self::new_with_capture(maybe_config, false)
+    }
+
+    /// Like [`Self::new`], additionally capturing the banking stage's buffering decisions and
+    /// leader bank boundaries when `capture` is set. The directory byte limit is then split
+    /// evenly between the trace events and the captured events.
+    pub fn new_with_capture(
+        maybe_config: Option<(&PathBuf, Arc<AtomicBool>, DirByteLimit)>,
+        capture: bool,
     ) -> Result<(Arc<Self>, TracerThread), TraceError> {
         match maybe_config {
             None => Ok((Self::new_disabled(), None)),
             Some((path, exit, dir_byte_limit)) => {
+                let dir_byte_limit = if capture {
+                    dir_byte_limit / 2
+                } else {
+                    dir_byte_limit
+                };
                 let rotate_threshold_size = dir_byte_limit / TRACE_FILE_ROTATE_COUNT;
                 if rotate_threshold_size == 0 {
                     return Err(TraceError::TooSmallDirByteLimit(
@@ -225,12 +445,35 @@ impl BankingTracer {
 
                 let file_appender = Self::create_file_appender(path, rotate_threshold_size)?;
 
-                let tracer_thread =
-                    Self::spawn_background_thread(trace_receiver, file_appender, exit.clone())?;
+                let (active_capture, capture_writer) = if capture {
+                    let (capture_sender, capture_receiver) = unbounded();
+                    let capture_appender = Self::create_file_appender_with_basename(
+                        path,
+                        CAPTURE_BASENAME,
+                        rotate_threshold_size,
+                    )?;
+                    (
+                        Some(ActiveCapture {
+                            capture_sender,
+                            exit: exit.clone(),
+                        }),
+                        Some((capture_receiver, capture_appender)),
+                    )
+                } else {
+                    (None, None)
+                };
+
+                let tracer_thread = Self::spawn_background_thread(
+                    trace_receiver,
+                    file_appender,
+                    capture_writer,
+                    exit.clone(),
+                )?;
 
                 Ok((
                     Arc::new(Self {
                         active_tracer: Some(ActiveTracer { trace_sender, exit }),
+                        active_capture,
                     }),
                     Some(tracer_thread),
                 ))
@@ -241,6 +484,7 @@ impl BankingTracer {
     pub fn new_disabled() -> Arc<Self> {
         Arc::new(Self {
             active_tracer: None,
+            active_capture: None,
         })
     }
 
@@ -248,6 +492,33 @@ impl BankingTracer {
         self.active_tracer.is_some()
     }
 
+    pub fn is_capture_enabled(&self) -> bool {
+        self.active_capture.is_some()
+    }
+
+    pub fn capture(&self) -> BankingCapture {
+        BankingCapture {
+            active_capture: self.active_capture.clone(),
+            ingestion_log: None,
+        }
+    }
+
+    pub fn event_file_name(index: usize) -> String {
+        if index == 0 {
+            BASENAME.to_string()
+        } else {
+            format!("{BASENAME}.{index}")
+        }
+    }
+
+    pub fn capture_file_name(index: usize) -> String {
+        if index == 0 {
+            CAPTURE_BASENAME.to_string()
+        } else {
+            format!("{CAPTURE_BASENAME}.{index}")
+        }
+    }
+
     pub fn create_channels(&self, unify_channels: bool) -> Channels {
         if unify_channels {
             // Returning the same channel is needed when unified scheduler supports block
@@ -380,6 +651,14 @@ impl BankingTracer {
     fn create_file_appender(
         path: &PathBuf,
         rotate_threshold_size: u64,
+    ) -> Result<RollingFileAppender<RollingConditionGrouped>, TraceError> {
+        Self::create_file_appender_with_basename(path, BASENAME, rotate_threshold_size)
+    }
+
+    fn create_file_appender_with_basename(
+        path: &PathBuf,
+        basename: &str,
+        rotate_threshold_size: u64,
     ) -> Result<RollingFileAppender<RollingConditionGrouped>, TraceError> {
         create_dir_all(path)?;
         let grouped = RollingConditionGrouped::new(
@@ -388,7 +667,7 @@ impl BankingTracer {
                 .max_size(rotate_threshold_size),
         );
         let appender = RollingFileAppender::new_with_buffer_capacity(
-            path.join(BASENAME),
+            path.join(basename),
             grouped,
             (TRACE_FILE_ROTATE_COUNT - 1).try_into()?,
             BUF_WRITER_CAPACITY,
@@ -398,27 +677,57 @@ impl BankingTracer {
 
     fn spawn_background_thread(
         trace_receiver: Receiver<TimedTracedEvent>,
-        mut file_appender: RollingFileAppender<RollingConditionGrouped>,
+        file_appender: RollingFileAppender<RollingConditionGrouped>,
+        capture_writer: Option<(
+            Receiver<TimedCapturedEvent>,
+            RollingFileAppender<RollingConditionGrouped>,
+        )>,
         exit: Arc<AtomicBool>,
     ) -> Result<JoinHandle<TracerThreadResult>, TraceError> {
         let thread = thread::Builder::new().name("solBanknTracer".into()).spawn(
             move || -> TracerThreadResult {
-                receiving_loop_with_minimized_sender_overhead::<_, _, TRACE_FILE_WRITE_INTERVAL_MS>(
-                    exit,
-                    trace_receiver,
-                    |event| -> Result<(), TraceError> {
-                        file_appender.condition_mut().reset();
-                        serialize_into(&mut GroupedWriter::new(&mut file_appender), &event)?;
-                        Ok(())
-                    },
-                )?;
-                file_appender.flush()?;
-                Ok(())
+                // Captured events are written by their own thread, which is owned by this one
+                // so that joining the returned TracerThread flushes both streams.
+                let capture_thread = capture_writer
+                    .map(|(capture_receiver, capture_appender)| {
+                        let exit = exit.clone();
+                        thread::Builder::new()
+                            .name("solBanknCapture".into())
+                            .spawn(move || {
+                                Self::write_events(exit, capture_receiver, capture_appender)
+                            })
+                    })
+                    .transpose()?;
+                let result = Self::write_events(exit, trace_receiver, file_appender);
+                if let Some(capture_thread) = capture_thread {
+                    capture_thread
+                        .join()
+                        .expect("capture thread shouldn't panic")?;
+                }
+                result
             },
         )?;
 
         Ok(thread)
     }
+
+    fn write_events<T: serde::Serialize>(
+        exit: Arc<AtomicBool>,
+        receiver: Receiver<T>,
+        mut file_appender: RollingFileAppender<RollingConditionGrouped>,
+    ) -> TracerThreadResult {
+        receiving_loop_with_minimized_sender_overhead::<_, _, TRACE_FILE_WRITE_INTERVAL_MS>(
+            exit,
+            receiver,
+            |event| -> Result<(), TraceError> {
+                file_appender.condition_mut().reset();
+                serialize_into(&mut GroupedWriter::new(&mut file_appender), &event)?;
+                Ok(())
+            },
+        )?;
+        file_appender.flush()?;
+        Ok(())
+    }
 }
 
 pub struct TracedSender {
@@ -481,6 +790,18 @@ pub mod for_test {
         BankingPacketBatch::new(to_packet_batches(&vec![test_tx(); 4], 10))
     }
 
+    pub fn capture_channel() -> (BankingCapture, Receiver<TimedCapturedEvent>) {
+        let (capture_sender, capture_receiver) = unbounded();
+        let capture = BankingCapture {
+            active_capture: Some(ActiveCapture {
+                capture_sender,
+                exit: Arc::default(),
+            }),
+            ingestion_log: None,
+        };
+        (capture, capture_receiver)
+    }
+
     pub fn drop_and_clean_temp_dir_unless_suppressed(temp_dir: TempDir) {
         std::env::var("BANKING_TRACE_LEAVE_FILES").is_ok().then(|| {
             warn!("prevented to remove {:?}", temp_dir.path());
@@ -646,6 +967,72 @@ mod tests {
         for_test::drop_and_clean_temp_dir_unless_suppressed(temp_dir);
     }
 
+    #[test]
+    fn test_record_and_restore_capture() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("banking-trace");
+        let exit = Arc::<AtomicBool>::default();
+        let (tracer, tracer_thread) =
+            BankingTracer::new_with_capture(Some((&path, exit.clone(), DirByteLimit::MAX)), true)
+                .unwrap();
+        assert!(tracer.is_capture_enabled());
+        let (non_vote_sender, non_vote_receiver) = tracer.create_channel_non_vote();
+
+        let dummy_main_thread = thread::spawn(move || {
+            receiving_loop_with_minimized_sender_overhead::<_, TraceError, 0>(
+                exit,
+                non_vote_receiver,
+                |_packet_batch| Ok(()),
+            )
+        });
+
+        let capture = tracer.capture();
+        capture.record(|| CapturedEvent::BankStart {
+            slot: 4,
+            parent_slot: 3,
+            lamports_per_signature: 5000,
+        });
+        capture.record(|| CapturedEvent::BankEnd { slot: 4 });
+        drop(capture);
+
+        for_test::terminate_tracer(
+            tracer,
+            tracer_thread,
+            dummy_main_thread,
+            non_vote_sender,
+            None,
+        );
+
+        let mut stream = BufReader::new(File::open(path.join(CAPTURE_BASENAME)).unwrap());
+        let results = (0..=2)
+            .map(|_| bincode::deserialize_from::<_, TimedCapturedEvent>(&mut stream))
+            .collect::<Vec<_>>();
+        assert_matches!(
+            results[0],
+            Ok(TimedCapturedEvent(
+                _,
+                CapturedEvent::BankStart {
+                    slot: 4,
+                    parent_slot: 3,
+                    lamports_per_signature: 5000,
+                }
+            ))
+        );
+        assert_matches!(
+            results[1],
+            Ok(TimedCapturedEvent(_, CapturedEvent::BankEnd { slot: 4 }))
+        );
+        assert_matches!(
+            results[2],
+            Err(ref err) if matches!(
+                **err,
+                BincodeIoError(ref error) if error.kind() == UnexpectedEof
+            )
+        );
+
+        for_test::drop_and_clean_temp_dir_unless_suppressed(temp_dir);
+    }
+
     #[test]
     fn test_spill_over_at_rotation() {
         let temp_dir = TempDir::new().unwrap();
@@ -616,10 +616,23 @@ impl ClusterInfoVoteListener {
         let mut gossip_vote_txn_processing_time = Measure::start("gossip_vote_processing_time");
         let votes = gossip_vote_txs
             .iter()
-            .filter_map(vote_parser::parse_vote_transaction)
-            .zip(repeat(/*is_gossip:*/ true))
-            .chain(replayed_votes.into_iter().zip(repeat(/*is_gossip:*/ false)));
-        for ((vote_pubkey, vote, _switch_proof, signature), is_gossip) in votes {
+            .filter_map(|tx| Some((vote_parser::parse_vote_transaction(tx)?, Some(tx))))
+            .chain(
+                replayed_votes
+                    .into_iter()
+                    .zip(repeat(/*gossip_vote_tx:*/ None)),
+            );
+        for ((vote_pubkey, vote, _switch_proof, signature), gossip_vote_tx) in votes {
+            let is_gossip = gossip_vote_tx.is_some();
+            if let Some(gossip_vote_tx) = gossip_vote_tx {
+                Self::record_attestation(
+                    root_bank,
+                    subscriptions,
+                    &vote_pubkey,
+                    &vote,
+                    gossip_vote_tx,
+                );
+            }
             Self::track_new_votes_and_notify_confirmations(
                 vote,
                 &vote_pubkey,
@@ -639,6 +652,10 @@ impl ClusterInfoVoteListener {
                 dumped_slot_subscription,
             );
         }
+        let attestations = subscriptions.optimistic_confirmation_attestations();
+        for (slot, hash) in &new_optimistic_confirmed_slots {
+            attestations.record_optimistic_confirmation(*slot, *hash);
+        }
         gossip_vote_txn_processing_time.stop();
         let gossip_vote_txn_processing_time_us = gossip_vote_txn_processing_time.as_us();
 
@@ -703,6 +720,41 @@ impl ClusterInfoVoteListener {
         new_optimistic_confirmed_slots
     }
 
+    /// Records a gossip vote as the voter's signed attestation of the bank hash of its last
+    /// voted slot, for light clients to verify optimistic confirmation with
+    fn record_attestation(
+        root_bank: &Bank,
+        subscriptions: &RpcSubscriptions,
+        vote_pubkey: &Pubkey,
+        vote: &VoteTransaction,
+        vote_transaction: &Transaction,
+    ) {
+        let Some((slot, hash)) = vote.last_voted_slot_hash() else {
+            return;
+        };
+        if slot <= root_bank.slot() {
+            return;
+        }
+        let epoch = root_bank.epoch_schedule().get_epoch(slot);
+        let Some(epoch_stakes) = root_bank.epoch_stakes(epoch) else {
+            return;
+        };
+        let stake = epoch_stakes
+            .stakes()
+            .vote_accounts()
+            .get_delegated_stake(vote_pubkey);
+        subscriptions
+            .optimistic_confirmation_attestations()
+            .record_vote(
+                slot,
+                hash,
+                *vote_pubkey,
+                stake,
+                epoch_stakes.total_stake(),
+                vote_transaction,
+            );
+    }
+
     // Returns if the slot was optimistically confirmed, and whether
     // the slot was new
     fn track_optimistic_confirmation_vote(
@@ -1456,6 +1508,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gossip_votes_are_recorded_as_attestations() {
+        let SetupComponents {
+            vote_tracker,
+            bank,
+            validator_voting_keypairs,
+            subscriptions,
+            bank_forks,
+        } = setup();
+        let mut bank_hash_cache = BankHashCache::new(bank_forks);
+        let voted_slot = bank.slot() + 1;
+        let voted_hash = Hash::new_unique();
+        let mut tower_sync = TowerSync::from(vec![(voted_slot, 1)]);
+        tower_sync.hash = voted_hash;
+        // 7 of 10 equally staked validators vote in gossip, 1 more only in replay
+        let vote_txs: Vec<_> = validator_voting_keypairs[..7]
+            .iter()
+            .map(|keypairs| {
+                vote_transaction::new_tower_sync_transaction(
+                    tower_sync.clone(),
+                    Hash::default(),
+                    &keypairs.node_keypair,
+                    &keypairs.vote_keypair,
+                    &keypairs.vote_keypair,
+                    None,
+                )
+            })
+            .collect();
+        let replayed_vote = (
+            validator_voting_keypairs[7].vote_keypair.pubkey(),
+            VoteTransaction::from(tower_sync),
+            None,
+            Signature::default(),
+        );
+
+        let (verified_vote_sender, _verified_vote_receiver) = unbounded();
+        let (gossip_verified_vote_hash_sender, _gossip_verified_vote_hash_receiver) = unbounded();
+        let confirmed_slots = ClusterInfoVoteListener::filter_and_confirm_with_new_votes(
+            &vote_tracker,
+            vote_txs.clone(),
+            vec![replayed_vote],
+            &bank,
+            &subscriptions,
+            &gossip_verified_vote_hash_sender,
+            &verified_vote_sender,
+            &None,
+            &None,
+            &mut None,
+            &mut HashMap::new(),
+            &mut bank_hash_cache,
+            &Mutex::new(false),
+        );
+        assert_eq!(confirmed_slots, vec![(voted_slot, voted_hash)]);
+
+        let attestations = subscriptions
+            .optimistic_confirmation_attestations()
+            .get(voted_slot)
+            .unwrap();
+        assert_eq!(attestations.bank_hash, voted_hash.to_string());
+        assert!(attestations.optimistically_confirmed);
+        assert_eq!(attestations.total_stake, 1_000);
+        assert_eq!(attestations.attested_stake, 700);
+        assert_eq!(attestations.threshold_stake, 667);
+        let mut signatures: Vec<_> = attestations
+            .attestations
+            .iter()
+            .map(|attestation| attestation.signature.clone())
+            .collect();
+        signatures.sort();
+        let mut expected_signatures: Vec<_> = vote_txs
+            .iter()
+            .map(|tx| tx.signatures[0].to_string())
+            .collect();
+        expected_signatures.sort();
+        assert_eq!(signatures, expected_signatures);
+    }
+
     struct SetupComponents {
         vote_tracker: Arc<VoteTracker>,
         bank: Arc<Bank>,
@@ -0,0 +1,388 @@
+//! The `identity_top_up_service` keeps the balance of the validator identity,
+//! which pays the fees of its votes, above a threshold by transferring from a
+//! treasury account.
+//!
+//! Transfers are capped to a maximum amount per day, so that a misconfigured
+//! threshold or a drained identity cannot empty the treasury, and conditions
+//! that prevent a top up are reported to metrics as alerts.
+
+use {
+    crate::next_leader::next_leader,
+    bincode::serialize,
+    solana_client::connection_cache::ConnectionCache,
+    solana_connection_cache::client_connection::ClientConnection,
+    solana_gossip::cluster_info::ClusterInfo,
+    solana_poh::poh_recorder::PohRecorder,
+    solana_runtime::{bank::Bank, bank_forks::BankForks},
+    solana_sdk::{
+        account_utils::StateMut,
+        hash::Hash,
+        message::Message,
+        nonce,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+        system_instruction,
+        transaction::Transaction,
+    },
+    std::{
+        collections::VecDeque,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+pub const DEFAULT_IDENTITY_TOP_UP_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const ALLOWANCE_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+const SLEEP_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone)]
+pub struct IdentityTopUpConfig {
+    /// Account the top ups are transferred from, which also pays their fees
+    pub treasury: Arc<Keypair>,
+    /// Durable nonce account of the treasury to use instead of recent blockhashes
+    pub nonce_account: Option<Pubkey>,
+    /// Balance of the identity under which it is topped up
+    pub threshold: u64,
+    /// Balance the identity is topped up to
+    pub target: u64,
+    /// Maximum lamports transferred over any 24 hours
+    pub max_per_day: u64,
+    pub check_interval: Duration,
+}
+
+/// Amount transferred over the last 24 hours, against the daily maximum
+struct TopUpAllowance {
+    max_per_day: u64,
+    transfers: VecDeque<(Instant, u64)>,
+}
+
+impl TopUpAllowance {
+    fn new(max_per_day: u64) -> Self {
+        Self {
+            max_per_day,
+            transfers: VecDeque::new(),
+        }
+    }
+
+    fn remaining(&mut self, now: Instant) -> u64 {
+        while let Some((time, _)) = self.transfers.front() {
+            if now.duration_since(*time) < ALLOWANCE_PERIOD {
+                break;
+            }
+            self.transfers.pop_front();
+        }
+        let transferred = self.transfers.iter().map(|(_, amount)| amount).sum::<u64>();
+        self.max_per_day.saturating_sub(transferred)
+    }
+
+    fn record(&mut self, now: Instant, amount: u64) {
+        self.transfers.push_back((now, amount));
+    }
+}
+
+/// A top up sent but not rooted yet
+struct PendingTopUp {
+    signature: Signature,
+    /// Recent blockhash or durable nonce of the transaction
+    blockhash: Hash,
+    amount: u64,
+    transaction: Transaction,
+    sent: Instant,
+}
+
+pub struct IdentityTopUpService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl IdentityTopUpService {
+    pub fn new(
+        config: IdentityTopUpConfig,
+        cluster_info: Arc<ClusterInfo>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        poh_recorder: Arc<RwLock<PohRecorder>>,
+        connection_cache: Arc<ConnectionCache>,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solIdTopUp".to_string())
+            .spawn(move || {
+                info!(
+                    "IdentityTopUpService has started, treasury {}",
+                    config.treasury.pubkey()
+                );
+                Self::run(
+                    config,
+                    cluster_info,
+                    bank_forks,
+                    poh_recorder,
+                    connection_cache,
+                    exit,
+                );
+                info!("IdentityTopUpService has stopped");
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    fn run(
+        config: IdentityTopUpConfig,
+        cluster_info: Arc<ClusterInfo>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        poh_recorder: Arc<RwLock<PohRecorder>>,
+        connection_cache: Arc<ConnectionCache>,
+        exit: Arc<AtomicBool>,
+    ) {
+        let mut allowance = TopUpAllowance::new(config.max_per_day);
+        let mut pending: Option<PendingTopUp> = None;
+        let mut last_check: Option<Instant> = None;
+        let send = |transaction: &Transaction| {
+            let protocol = connection_cache.protocol();
+            let tpu = next_leader(&cluster_info, &poh_recorder, |node| node.tpu(protocol))
+                .map(|(_, tpu)| tpu)
+                .or_else(|| cluster_info.my_contact_info().tpu(protocol));
+            let Some(tpu) = tpu else {
+                return;
+            };
+            let buf = serialize(transaction).unwrap();
+            if let Err(err) = connection_cache.get_connection(&tpu).send_data_async(buf) {
+                warn!("Failed to send identity top up to {tpu}: {err:?}");
+            }
+        };
+
+        while !exit.load(Ordering::Relaxed) {
+            if last_check.is_some_and(|last_check| last_check.elapsed() < config.check_interval) {
+                sleep(SLEEP_INTERVAL);
+                continue;
+            }
+            last_check = Some(Instant::now());
+            // Balances are read from the root so that a top up is only sent once the previous
+            // one can no longer land
+            let root_bank = bank_forks.read().unwrap().root_bank();
+            let identity = cluster_info.id();
+
+            if let Some(top_up) = pending.take() {
+                match root_bank.get_signature_status(&top_up.signature) {
+                    Some(Ok(())) => {
+                        info!(
+                            "Topped up identity {identity} with {} lamports",
+                            top_up.amount
+                        );
+                        datapoint_info!(
+                            "identity_top_up",
+                            ("identity", identity.to_string(), String),
+                            ("amount", top_up.amount, i64),
+                            ("balance", root_bank.get_balance(&identity), i64),
+                        );
+                        allowance.record(top_up.sent, top_up.amount);
+                    }
+                    Some(Err(err)) => {
+                        Self::alert(&identity, &format!("top up transaction failed: {err}"));
+                    }
+                    None if Self::is_expired(&root_bank, &config, &top_up.blockhash) => {
+                        warn!("Identity top up {} expired", top_up.signature);
+                    }
+                    None => {
+                        send(&top_up.transaction);
+                        pending = Some(top_up);
+                        continue;
+                    }
+                }
+            }
+
+            let balance = root_bank.get_balance(&identity);
+            if balance >= config.threshold {
+                continue;
+            }
+            let remaining = allowance.remaining(Instant::now());
+            let amount = config.target.saturating_sub(balance).min(remaining);
+            if amount == 0 {
+                Self::alert(
+                    &identity,
+                    &format!(
+                        "identity balance {balance} is below {} but the daily maximum of {} \
+                         lamports was transferred",
+                        config.threshold, config.max_per_day
+                    ),
+                );
+                continue;
+            }
+            let treasury_balance = root_bank.get_balance(&config.treasury.pubkey());
+            if treasury_balance < amount {
+                Self::alert(
+                    &identity,
+                    &format!(
+                        "treasury {} balance {treasury_balance} cannot cover a top up of \
+                         {amount} lamports",
+                        config.treasury.pubkey()
+                    ),
+                );
+                continue;
+            }
+            match Self::top_up_transaction(&root_bank, &config, &identity, amount) {
+                Ok((transaction, blockhash)) => {
+                    send(&transaction);
+                    pending = Some(PendingTopUp {
+                        signature: transaction.signatures[0],
+                        blockhash,
+                        amount,
+                        transaction,
+                        sent: Instant::now(),
+                    });
+                }
+                Err(err) => Self::alert(&identity, &err),
+            }
+        }
+    }
+
+    fn alert(identity: &Pubkey, reason: &str) {
+        error!("Cannot top up identity {identity}: {reason}");
+        datapoint_error!(
+            "identity_top_up_alert",
+            ("identity", identity.to_string(), String),
+            ("reason", reason, String),
+        );
+    }
+
+    fn nonce_blockhash(bank: &Bank, nonce_account: &Pubkey) -> Result<Hash, String> {
+        let account = bank
+            .get_account(nonce_account)
+            .ok_or_else(|| format!("nonce account {nonce_account} does not exist"))?;
+        match StateMut::<nonce::state::Versions>::state(&account)
+            .map_err(|err| format!("nonce account {nonce_account} is invalid: {err}"))?
+            .state()
+        {
+            nonce::State::Initialized(data) => Ok(data.blockhash()),
+            nonce::State::Uninitialized => {
+                Err(format!("nonce account {nonce_account} is not initialized"))
+            }
+        }
+    }
+
+    /// A top up can no longer land once its blockhash is too old, or its nonce was advanced
+    fn is_expired(bank: &Bank, config: &IdentityTopUpConfig, blockhash: &Hash) -> bool {
+        match &config.nonce_account {
+            Some(nonce_account) => {
+                Self::nonce_blockhash(bank, nonce_account).ok() != Some(*blockhash)
+            }
+            None => !bank.is_blockhash_valid(blockhash),
+        }
+    }
+
+    fn top_up_transaction(
+        bank: &Bank,
+        config: &IdentityTopUpConfig,
+        identity: &Pubkey,
+        amount: u64,
+    ) -> Result<(Transaction, Hash), String> {
+        let treasury = config.treasury.pubkey();
+        let transfer = system_instruction::transfer(&treasury, identity, amount);
+        let (message, blockhash) = match &config.nonce_account {
+            Some(nonce_account) => (
+                Message::new_with_nonce(vec![transfer], Some(&treasury), nonce_account, &treasury),
+                Self::nonce_blockhash(bank, nonce_account)?,
+            ),
+            None => (
+                Message::new(&[transfer], Some(&treasury)),
+                bank.last_blockhash(),
+            ),
+        };
+        let transaction = Transaction::new(&[config.treasury.as_ref()], message, blockhash);
+        Ok((transaction, blockhash))
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_runtime::genesis_utils::create_genesis_config,
+        solana_sdk::{account::ReadableAccount, nonce_account, system_program},
+    };
+
+    #[test]
+    fn test_top_up_allowance() {
+        let start = Instant::now();
+        let mut allowance = TopUpAllowance::new(1_000);
+        assert_eq!(allowance.remaining(start), 1_000);
+        allowance.record(start, 600);
+        assert_eq!(allowance.remaining(start), 400);
+        allowance.record(start + Duration::from_secs(3600), 400);
+        assert_eq!(allowance.remaining(start + Duration::from_secs(3600)), 0);
+        // Transfers leave the window a day after they were made
+        assert_eq!(allowance.remaining(start + ALLOWANCE_PERIOD), 600);
+        assert_eq!(
+            allowance.remaining(start + ALLOWANCE_PERIOD + Duration::from_secs(3600)),
+            1_000
+        );
+    }
+
+    #[test]
+    fn test_top_up_transaction() {
+        let genesis = create_genesis_config(1_000_000_000);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let treasury = Arc::new(Keypair::new());
+        let identity = Pubkey::new_unique();
+        let mut config = IdentityTopUpConfig {
+            treasury: treasury.clone(),
+            nonce_account: None,
+            threshold: 1_000,
+            target: 5_000,
+            max_per_day: 10_000,
+            check_interval: DEFAULT_IDENTITY_TOP_UP_CHECK_INTERVAL,
+        };
+
+        let (transaction, blockhash) =
+            IdentityTopUpService::top_up_transaction(&bank, &config, &identity, 4_000).unwrap();
+        assert_eq!(blockhash, bank.last_blockhash());
+        assert_eq!(transaction.message.account_keys[0], treasury.pubkey());
+        assert!(transaction.verify().is_ok());
+        assert!(!IdentityTopUpService::is_expired(
+            &bank, &config, &blockhash
+        ));
+        assert!(IdentityTopUpService::is_expired(
+            &bank,
+            &config,
+            &Hash::new_unique()
+        ));
+
+        // Top ups are signed over the durable nonce, and expire once it is advanced
+        let nonce_pubkey = Pubkey::new_unique();
+        config.nonce_account = Some(nonce_pubkey);
+        assert!(
+            IdentityTopUpService::top_up_transaction(&bank, &config, &identity, 4_000).is_err()
+        );
+        let previous_blockhash = Hash::new_unique();
+        let mut nonce_account = nonce_account::create_account(1_000_000).into_inner();
+        nonce_account
+            .set_state(&nonce::state::Versions::new(nonce::State::Initialized(
+                nonce::state::Data::new(
+                    treasury.pubkey(),
+                    nonce::state::DurableNonce::from_blockhash(&previous_blockhash),
+                    5_000,
+                ),
+            )))
+            .unwrap();
+        assert_eq!(*nonce_account.owner(), system_program::id());
+        bank.store_account(&nonce_pubkey, &nonce_account);
+        let (transaction, blockhash) =
+            IdentityTopUpService::top_up_transaction(&bank, &config, &identity, 4_000).unwrap();
+        assert_eq!(transaction.message.recent_blockhash, blockhash);
+        assert_eq!(transaction.message.instructions.len(), 2);
+        assert!(!IdentityTopUpService::is_expired(
+            &bank, &config, &blockhash
+        ));
+        assert!(IdentityTopUpService::is_expired(
+            &bank,
+            &config,
+            &bank.last_blockhash()
+        ));
+    }
+}
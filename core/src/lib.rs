@@ -23,6 +23,7 @@ pub mod drop_bank_service;
 pub mod fetch_stage;
 pub mod forwarding_stage;
 pub mod gen_keys;
+pub mod identity_top_up_service;
 pub mod next_leader;
 pub mod optimistic_confirmation_verifier;
 pub mod poh_timing_report_service;
@@ -34,6 +35,7 @@ pub mod sample_performance_service;
 mod shred_fetch_stage;
 pub mod sigverify;
 pub mod sigverify_stage;
+pub mod slashing_evidence;
 pub mod snapshot_packager_service;
 pub mod staked_nodes_updater_service;
 pub mod stats_reporter_service;
@@ -384,6 +384,8 @@ pub struct RepairInfo {
     pub repair_whitelist: Arc<RwLock<HashSet<Pubkey>>>,
     // A given list of slots to repair when in wen_restart
     pub wen_restart_repair_slots: Option<Arc<RwLock<Vec<Slot>>>>,
+    // Request repairs over QUIC from peers advertising a QUIC serve repair socket
+    pub repair_over_quic: bool,
 }
 
 pub struct RepairSlotRange {
@@ -496,7 +498,7 @@ impl RepairService {
             let mut handle_popular_pruned_forks;
 
             let root_bank = root_bank_cache.root_bank();
-            let repair_protocol = serve_repair::get_repair_protocol(root_bank.cluster_type());
+            let repair_protocol = serve_repair::get_repair_protocol(repair_info.repair_over_quic);
             let repairs = {
                 let new_root = root_bank.slot();
 
@@ -5,6 +5,8 @@ use {
 
 #[derive(Error, Debug)]
 pub enum RepairVerifyError {
+    #[error("Denied")]
+    Denied,
     #[error("IdMismatch")]
     IdMismatch,
     #[error("Malformed")]
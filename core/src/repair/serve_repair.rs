@@ -21,6 +21,7 @@ use {
     solana_gossip::{
         cluster_info::{ClusterInfo, ClusterInfoError},
         contact_info::{ContactInfo, Protocol},
+        peer_filter::PeerFilter,
         ping_pong::{self, Pong},
         weighted_shuffle::WeightedShuffle,
     },
@@ -36,7 +37,6 @@ use {
     solana_runtime::{bank_forks::BankForks, root_bank_cache::RootBankCache},
     solana_sdk::{
         clock::Slot,
-        genesis_config::ClusterType,
         hash::{Hash, HASH_BYTES},
         packet::PACKET_DATA_SIZE,
         pubkey::{Pubkey, PUBKEY_BYTES},
@@ -197,6 +197,7 @@ struct ServeRepairStats {
     err_sig_verify: usize,
     err_unsigned: usize,
     err_id_mismatch: usize,
+    err_denied: usize,
 }
 
 #[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
@@ -351,7 +352,7 @@ pub(crate) struct RepairPeers {
 struct Node {
     pubkey: Pubkey,
     serve_repair: SocketAddr,
-    serve_repair_quic: SocketAddr,
+    serve_repair_quic: Option<SocketAddr>,
 }
 
 impl RepairPeers {
@@ -366,7 +367,7 @@ impl RepairPeers {
                 let node = Node {
                     pubkey: *peer.pubkey(),
                     serve_repair: peer.serve_repair(Protocol::UDP)?,
-                    serve_repair_quic: peer.serve_repair(Protocol::QUIC)?,
+                    serve_repair_quic: peer.serve_repair(Protocol::QUIC),
                 };
                 Some((node, weight))
             })
@@ -525,6 +526,7 @@ impl ServeRepair {
         remote_request: RemoteRequest,
         epoch_staked_nodes: &Option<Arc<HashMap<Pubkey, u64>>>,
         whitelist: &HashSet<Pubkey>,
+        peer_filter: &PeerFilter,
         my_id: &Pubkey,
         socket_addr_space: &SocketAddrSpace,
     ) -> Result<RepairRequestWithMeta> {
@@ -548,6 +550,14 @@ impl ServeRepair {
             error!("self repair: from_addr={from_addr} my_id={my_id} request={request:?}");
             return Err(Error::from(RepairVerifyError::SelfRepair));
         }
+        if !peer_filter.is_allowed_ip(&from_addr.ip())
+            || !request
+                .sender()
+                .map(|pubkey| peer_filter.is_allowed_pubkey(pubkey))
+                .unwrap_or(true)
+        {
+            return Err(Error::from(RepairVerifyError::Denied));
+        }
         let stake = *epoch_staked_nodes
             .as_ref()
             .and_then(|stakes| stakes.get(request.sender()?))
@@ -569,6 +579,9 @@ impl ServeRepair {
 
     fn record_request_decode_error(error: &Error, stats: &mut ServeRepairStats) {
         match error {
+            Error::RepairVerify(RepairVerifyError::Denied) => {
+                stats.err_denied += 1;
+            }
             Error::RepairVerify(RepairVerifyError::IdMismatch) => {
                 stats.err_id_mismatch += 1;
             }
@@ -597,6 +610,7 @@ impl ServeRepair {
         requests: Vec<RemoteRequest>,
         epoch_staked_nodes: &Option<Arc<HashMap<Pubkey, u64>>>,
         whitelist: &HashSet<Pubkey>,
+        peer_filter: &PeerFilter,
         my_id: &Pubkey,
         socket_addr_space: &SocketAddrSpace,
         stats: &mut ServeRepairStats,
@@ -606,6 +620,7 @@ impl ServeRepair {
                 request,
                 epoch_staked_nodes,
                 whitelist,
+                peer_filter,
                 my_id,
                 socket_addr_space,
             );
@@ -687,6 +702,7 @@ impl ServeRepair {
                 requests,
                 &epoch_staked_nodes,
                 &whitelist,
+                &self.cluster_info.peer_filter(),
                 &my_id,
                 &socket_addr_space,
                 stats,
@@ -801,6 +817,7 @@ impl ServeRepair {
             ("err_sig_verify", stats.err_sig_verify, i64),
             ("err_unsigned", stats.err_unsigned, i64),
             ("err_id_mismatch", stats.err_id_mismatch, i64),
+            ("err_denied", stats.err_denied, i64),
         );
 
         *stats = ServeRepairStats::default();
@@ -1105,14 +1122,15 @@ impl ServeRepair {
             peer.pubkey,
             repair_request
         );
-        match repair_protocol {
-            Protocol::UDP => Ok(Some((peer.serve_repair, out))),
-            Protocol::QUIC => {
+        match (repair_protocol, peer.serve_repair_quic) {
+            (Protocol::QUIC, Some(serve_repair_quic)) => {
                 repair_request_quic_sender
-                    .blocking_send((peer.serve_repair_quic, Bytes::from(out)))
+                    .blocking_send((serve_repair_quic, Bytes::from(out)))
                     .map_err(|_| Error::SendError)?;
                 Ok(None)
             }
+            // Peers which do not advertise a QUIC repair socket are repaired over UDP.
+            (Protocol::UDP | Protocol::QUIC, _) => Ok(Some((peer.serve_repair, out))),
         }
     }
 
@@ -1122,7 +1140,7 @@ impl ServeRepair {
         cluster_slots: &ClusterSlots,
         repair_validators: &Option<HashSet<Pubkey>>,
         repair_protocol: Protocol,
-    ) -> Result<Vec<(Pubkey, SocketAddr)>> {
+    ) -> Result<Vec<(Pubkey, SocketAddr, Protocol)>> {
         let repair_peers: Vec<_> = self.repair_peers(repair_validators, slot);
         if repair_peers.is_empty() {
             return Err(ClusterInfoError::NoPeers.into());
@@ -1135,8 +1153,13 @@ impl ServeRepair {
             .shuffle(&mut rand::thread_rng())
             .map(|i| index[i])
             .filter_map(|i| {
-                let addr = repair_peers[i].serve_repair(repair_protocol)?;
-                Some((*repair_peers[i].pubkey(), addr))
+                let peer = &repair_peers[i];
+                // Peers which do not advertise a QUIC repair socket are sampled over UDP.
+                let (addr, protocol) = match peer.serve_repair(repair_protocol) {
+                    Some(addr) => (addr, repair_protocol),
+                    None => (peer.serve_repair(Protocol::UDP)?, Protocol::UDP),
+                };
+                Some((*peer.pubkey(), addr, protocol))
             })
             .take(get_ancestor_hash_repair_sample_size())
             .collect();
@@ -1277,7 +1300,7 @@ impl ServeRepair {
         repair_validators: &Option<HashSet<Pubkey>>,
         slot: Slot,
     ) -> Vec<ContactInfo> {
-        if let Some(repair_validators) = repair_validators {
+        let mut repair_peers = if let Some(repair_validators) = repair_validators {
             repair_validators
                 .iter()
                 .filter_map(|key| {
@@ -1290,7 +1313,18 @@ impl ServeRepair {
                 .collect()
         } else {
             self.cluster_info.repair_peers(slot)
+        };
+        let peer_filter = self.cluster_info.peer_filter();
+        if !peer_filter.is_empty() {
+            repair_peers.retain(|peer| {
+                peer_filter.is_allowed_pubkey(peer.pubkey())
+                    && peer
+                        .serve_repair(Protocol::UDP)
+                        .map(|addr| peer_filter.is_allowed_ip(&addr.ip()))
+                        .unwrap_or(true)
+            });
         }
+        repair_peers
     }
 
     fn run_window_request(
@@ -1407,9 +1441,15 @@ impl ServeRepair {
     }
 }
 
+/// Returns the protocol repair requests are sent over. Requests fall back to
+/// UDP for peers which do not advertise a QUIC repair socket in gossip.
 #[inline]
-pub(crate) fn get_repair_protocol(_: ClusterType) -> Protocol {
-    Protocol::UDP
+pub(crate) fn get_repair_protocol(repair_over_quic: bool) -> Protocol {
+    if repair_over_quic {
+        Protocol::QUIC
+    } else {
+        Protocol::UDP
+    }
 }
 
 pub(crate) fn deserialize_request<T>(
@@ -2001,6 +2041,60 @@ mod tests {
         ClusterInfo::new(contact_info, keypair, SocketAddrSpace::Unspecified)
     }
 
+    #[test]
+    fn test_repair_request_over_quic() {
+        use Protocol::{QUIC, UDP};
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank_forks = BankForks::new_rw_arc(Bank::new_for_tests(&genesis_config));
+        let (repair_request_quic_sender, mut repair_request_quic_receiver) =
+            tokio::sync::mpsc::channel(/*buffer:*/ 128);
+        let serve_repair_addr = socketaddr!(Ipv4Addr::LOCALHOST, 1243);
+        let serve_repair_quic_addr = socketaddr!(Ipv4Addr::LOCALHOST, 1244);
+        for advertises_quic in [true, false] {
+            let cluster_info = Arc::new(new_test_cluster_info());
+            let serve_repair = ServeRepair::new(
+                cluster_info.clone(),
+                bank_forks.clone(),
+                Arc::new(RwLock::new(HashSet::default())),
+            );
+            let mut peer = ContactInfo::new(
+                solana_pubkey::new_rand(),
+                timestamp(), // wallclock
+                0u16,        // shred_version
+            );
+            peer.set_gossip((Ipv4Addr::LOCALHOST, 1234)).unwrap();
+            peer.set_tvu(UDP, (Ipv4Addr::LOCALHOST, 1235)).unwrap();
+            peer.set_serve_repair(UDP, serve_repair_addr).unwrap();
+            if advertises_quic {
+                peer.set_serve_repair(QUIC, serve_repair_quic_addr).unwrap();
+            }
+            cluster_info.insert_info(peer);
+            let identity_keypair = cluster_info.keypair().clone();
+            let rv = serve_repair
+                .repair_request(
+                    &ClusterSlots::default(),
+                    ShredRepairType::Shred(0, 0),
+                    &mut LruCache::new(100),
+                    &mut RepairStats::default(),
+                    &None,
+                    &mut OutstandingShredRepairs::default(),
+                    &identity_keypair,
+                    &repair_request_quic_sender,
+                    QUIC, // repair_protocol
+                )
+                .unwrap();
+            if advertises_quic {
+                assert_matches!(rv, None);
+                let (addr, _bytes) = repair_request_quic_receiver.try_recv().unwrap();
+                assert_eq!(addr, serve_repair_quic_addr);
+            } else {
+                // Falls back to UDP
+                assert_eq!(rv.unwrap().0, serve_repair_addr);
+                assert!(repair_request_quic_receiver.try_recv().is_err());
+            }
+        }
+    }
+
     #[test]
     fn window_index_request() {
         use Protocol::{QUIC, UDP};
@@ -50,6 +50,7 @@ use {
         leader_schedule_utils::first_of_consecutive_leader_slots,
     },
     solana_measure::measure::Measure,
+    solana_metrics::prometheus,
     solana_poh::poh_recorder::{PohLeaderStatus, PohRecorder, GRACE_TICKS_FACTOR, MAX_GRACE_SLOTS},
     solana_rpc::{
         block_meta_service::BlockMetaSender,
@@ -3273,6 +3274,21 @@ impl ReplayStage {
                     ("slot", bank_slot, i64),
                     ("hash", bank.hash().to_string(), String),
                 );
+                if prometheus::is_enabled() {
+                    let leader = if is_leader_block { "true" } else { "false" };
+                    prometheus::gauge_set("replay_frozen_slot", &[], bank_slot as f64);
+                    prometheus::gauge_set(
+                        "replay_lag_seconds",
+                        &[],
+                        (timestamp() / 1000).saturating_sub(bank.clock().unix_timestamp as u64)
+                            as f64,
+                    );
+                    prometheus::counter_add(
+                        "bank_fee_revenue_lamports",
+                        &[("leader", leader)],
+                        bank.collector_fees(),
+                    );
+                }
                 // report cost tracker stats
                 cost_update_sender
                     .send(CostUpdate::FrozenBank {
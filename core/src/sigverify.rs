@@ -13,20 +13,33 @@ use {
         sigverify_stage::{SigVerifier, SigVerifyServiceError},
     },
     agave_banking_stage_ingress_types::BankingPacketBatch,
+    rayon::{ThreadPool, ThreadPoolBuilder},
     solana_perf::{cuda_runtime::PinnedVec, packet::PacketBatch, recycler::Recycler, sigverify},
 };
 
+/// Number of threads verifying vote packets. Kept apart from the shared sigverify pool so that
+/// bursts of user transactions can't delay vote verification.
+const VOTE_SIGVERIFY_THREADS: usize = 2;
+
 pub struct TransactionSigVerifier {
     packet_sender: BankingPacketSender,
     recycler: Recycler<TxOffset>,
     recycler_out: Recycler<PinnedVec<u8>>,
     reject_non_vote: bool,
+    thread_pool: Option<ThreadPool>,
 }
 
 impl TransactionSigVerifier {
     pub fn new_reject_non_vote(packet_sender: BankingPacketSender) -> Self {
         let mut new_self = Self::new(packet_sender);
         new_self.reject_non_vote = true;
+        new_self.thread_pool = Some(
+            ThreadPoolBuilder::new()
+                .num_threads(VOTE_SIGVERIFY_THREADS)
+                .thread_name(|i| format!("solVoteSigVfy{i:02}"))
+                .build()
+                .unwrap(),
+        );
         new_self
     }
 
@@ -37,6 +50,7 @@ impl TransactionSigVerifier {
             recycler: Recycler::warmed(50, 4096),
             recycler_out: Recycler::warmed(50, 4096),
             reject_non_vote: false,
+            thread_pool: None,
         }
     }
 }
@@ -58,13 +72,23 @@ impl SigVerifier for TransactionSigVerifier {
         mut batches: Vec<PacketBatch>,
         valid_packets: usize,
     ) -> Vec<PacketBatch> {
-        sigverify::ed25519_verify(
-            &mut batches,
-            &self.recycler,
-            &self.recycler_out,
-            self.reject_non_vote,
-            valid_packets,
-        );
+        match &self.thread_pool {
+            Some(thread_pool) => sigverify::ed25519_verify_in_pool(
+                thread_pool,
+                &mut batches,
+                &self.recycler,
+                &self.recycler_out,
+                self.reject_non_vote,
+                valid_packets,
+            ),
+            None => sigverify::ed25519_verify(
+                &mut batches,
+                &self.recycler,
+                &self.recycler_out,
+                self.reject_non_vote,
+                valid_packets,
+            ),
+        }
         batches
     }
 }
@@ -0,0 +1,333 @@
+//! The `slashing_evidence` module records evidence of validator misbehavior
+//! that could eventually be slashed.
+//!
+//! This is detection only, no penalties are applied. Two kinds of evidence
+//! are kept in blockstore:
+//! * Duplicate blocks: two conflicting shreds signed by the slot leader. These
+//!   are already persisted by the window service and the duplicate shred
+//!   listener as [`DuplicateSlotProof`]s.
+//! * Duplicate votes: two vote transactions signed by the authorized voter of
+//!   a vote account that vote for the same slot with different bank hashes.
+//!   These are detected here from gossip votes and persisted as
+//!   [`DuplicateVoteProof`]s.
+//!
+//! A validator may legitimately vote for a slot twice when it switches from a
+//! duplicate version of a block to the duplicate confirmed one, so duplicate
+//! vote evidence must be reviewed before acting on it.
+//!
+//! [`DuplicateSlotProof`]: solana_ledger::blockstore_meta::DuplicateSlotProof
+
+use {
+    solana_gossip::{
+        cluster_info::{ClusterInfo, GOSSIP_SLEEP_MILLIS},
+        crds::Cursor,
+    },
+    solana_ledger::{blockstore::Blockstore, blockstore_meta::DuplicateVoteProof},
+    solana_runtime::{bank::Bank, bank_forks::BankForks},
+    solana_sdk::{clock::Slot, hash::Hash, pubkey::Pubkey, transaction::Transaction},
+    solana_vote::vote_parser,
+    std::{
+        collections::{hash_map::Entry, BTreeMap, HashMap},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+/// Votes for slots further than this past the root are not tracked.
+const MAX_TRACKED_SLOTS_PAST_ROOT: Slot = 4 * 432_000 / 100;
+
+/// Keeps the first gossip vote seen for every (slot, vote account) and
+/// reports a conflicting second vote as a [`DuplicateVoteProof`].
+#[derive(Default)]
+pub struct DuplicateVoteDetector {
+    votes: BTreeMap<Slot, HashMap<Pubkey, VoteEntry>>,
+}
+
+enum VoteEntry {
+    Vote(Hash, Transaction),
+    // Evidence was already reported, ignore any further votes
+    Reported,
+}
+
+impl DuplicateVoteDetector {
+    /// Records a vote by `vote_account` for `hash` at `slot`, returning a
+    /// proof if it conflicts with a previously recorded vote.
+    ///
+    /// `verify` is only called once a conflict is found, so that signatures
+    /// are checked in proportion to conflicts rather than to all votes. A
+    /// recorded vote that fails verification is replaced by the new one.
+    pub fn check_vote(
+        &mut self,
+        vote_account: Pubkey,
+        slot: Slot,
+        hash: Hash,
+        transaction: &Transaction,
+        verify: impl Fn(&Transaction) -> bool,
+    ) -> Option<DuplicateVoteProof> {
+        match self.votes.entry(slot).or_default().entry(vote_account) {
+            Entry::Vacant(entry) => {
+                entry.insert(VoteEntry::Vote(hash, transaction.clone()));
+                None
+            }
+            Entry::Occupied(mut entry) => {
+                let VoteEntry::Vote(first_hash, first_transaction) = entry.get() else {
+                    return None;
+                };
+                if *first_hash == hash || !verify(transaction) {
+                    return None;
+                }
+                if !verify(first_transaction) {
+                    entry.insert(VoteEntry::Vote(hash, transaction.clone()));
+                    return None;
+                }
+                let proof = DuplicateVoteProof {
+                    hash1: *first_hash,
+                    transaction1: bincode::serialize(first_transaction).ok()?,
+                    hash2: hash,
+                    transaction2: bincode::serialize(transaction).ok()?,
+                };
+                entry.insert(VoteEntry::Reported);
+                Some(proof)
+            }
+        }
+    }
+
+    /// Drops the votes for slots at or below `root`.
+    pub fn set_root(&mut self, root: Slot) {
+        self.votes = self.votes.split_off(&root.saturating_add(1));
+    }
+
+    #[cfg(test)]
+    fn num_tracked_slots(&self) -> usize {
+        self.votes.len()
+    }
+}
+
+pub struct SlashingEvidenceService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl SlashingEvidenceService {
+    pub fn new(
+        exit: Arc<AtomicBool>,
+        cluster_info: Arc<ClusterInfo>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        blockstore: Arc<Blockstore>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solSlashEvid".to_string())
+            .spawn(move || {
+                info!("SlashingEvidenceService has started");
+                Self::run(&exit, &cluster_info, &bank_forks, &blockstore);
+                info!("SlashingEvidenceService has stopped");
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    fn run(
+        exit: &AtomicBool,
+        cluster_info: &ClusterInfo,
+        bank_forks: &RwLock<BankForks>,
+        blockstore: &Blockstore,
+    ) {
+        let mut cursor = Cursor::default();
+        let mut detector = DuplicateVoteDetector::default();
+        while !exit.load(Ordering::Relaxed) {
+            let votes = cluster_info.get_votes(&mut cursor);
+            if !votes.is_empty() {
+                let root_bank = bank_forks.read().unwrap().root_bank();
+                detector.set_root(root_bank.slot());
+                for transaction in &votes {
+                    Self::process_vote(&mut detector, &root_bank, blockstore, transaction);
+                }
+            }
+            sleep(Duration::from_millis(GOSSIP_SLEEP_MILLIS));
+        }
+    }
+
+    fn process_vote(
+        detector: &mut DuplicateVoteDetector,
+        root_bank: &Bank,
+        blockstore: &Blockstore,
+        transaction: &Transaction,
+    ) {
+        let Some((vote_account, vote, ..)) = vote_parser::parse_vote_transaction(transaction)
+        else {
+            return;
+        };
+        let Some(slot) = vote.last_voted_slot() else {
+            return;
+        };
+        if slot <= root_bank.slot()
+            || slot > root_bank.slot().saturating_add(MAX_TRACKED_SLOTS_PAST_ROOT)
+        {
+            return;
+        }
+        // Only votes signed by the authorized voter can serve as evidence
+        let epoch = root_bank.epoch_schedule().get_epoch(slot);
+        let Some(authorized_voter) = root_bank.epoch_stakes(epoch).and_then(|epoch_stakes| {
+            epoch_stakes
+                .epoch_authorized_voters()
+                .get(&vote_account)
+                .copied()
+        }) else {
+            return;
+        };
+        let message = &transaction.message;
+        if !message
+            .account_keys
+            .iter()
+            .enumerate()
+            .any(|(i, key)| message.is_signer(i) && *key == authorized_voter)
+        {
+            return;
+        }
+        // Crds values are signed by the node that pushed them, not by the
+        // voter, so the vote signatures still have to be verified.
+        let Some(proof) = detector.check_vote(
+            vote_account,
+            slot,
+            vote.hash(),
+            transaction,
+            |transaction| transaction.verify().is_ok(),
+        ) else {
+            return;
+        };
+        match blockstore.store_duplicate_vote(slot, vote_account, &proof) {
+            Ok(true) => {
+                warn!(
+                    "Duplicate vote evidence: vote account {vote_account} voted for slot {slot} \
+                     with hashes {} and {}",
+                    proof.hash1, proof.hash2
+                );
+                datapoint_warn!(
+                    "slashing_evidence-duplicate_vote",
+                    ("slot", slot, i64),
+                    ("vote_account", vote_account.to_string(), String),
+                );
+            }
+            Ok(false) => {}
+            Err(err) => error!("Failed to store duplicate vote evidence for slot {slot}: {err:?}"),
+        }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::signature::{Keypair, Signature, Signer},
+        solana_vote_program::{
+            vote_state::TowerSync, vote_transaction::new_tower_sync_transaction,
+        },
+    };
+
+    fn vote_transaction(slot: Slot, hash: Hash, keypair: &Keypair) -> Transaction {
+        let mut tower_sync = TowerSync::from(vec![(slot, 1)]);
+        tower_sync.hash = hash;
+        new_tower_sync_transaction(tower_sync, Hash::default(), keypair, keypair, keypair, None)
+    }
+
+    #[test]
+    fn test_duplicate_vote_detector() {
+        let keypair = Keypair::new();
+        let vote_account = keypair.pubkey();
+        let mut detector = DuplicateVoteDetector::default();
+
+        let (hash1, hash2) = (Hash::new_unique(), Hash::new_unique());
+        let vote1 = vote_transaction(5, hash1, &keypair);
+        let vote2 = vote_transaction(5, hash2, &keypair);
+
+        assert!(detector
+            .check_vote(vote_account, 5, hash1, &vote1, |_| true)
+            .is_none());
+        // Same vote seen again is not evidence
+        assert!(detector
+            .check_vote(vote_account, 5, hash1, &vote1, |_| true)
+            .is_none());
+        // Another vote account voting differently isn't either
+        assert!(detector
+            .check_vote(Pubkey::new_unique(), 5, hash2, &vote2, |_| true)
+            .is_none());
+
+        let proof = detector
+            .check_vote(vote_account, 5, hash2, &vote2, |_| true)
+            .unwrap();
+        assert_eq!(proof.hash1, hash1);
+        assert_eq!(proof.hash2, hash2);
+        assert_eq!(
+            bincode::deserialize::<Transaction>(&proof.transaction1).unwrap(),
+            vote1
+        );
+        assert_eq!(
+            bincode::deserialize::<Transaction>(&proof.transaction2).unwrap(),
+            vote2
+        );
+
+        // Evidence is only reported once
+        let hash3 = Hash::new_unique();
+        let vote3 = vote_transaction(5, hash3, &keypair);
+        assert!(detector
+            .check_vote(vote_account, 5, hash3, &vote3, |_| true)
+            .is_none());
+    }
+
+    #[test]
+    fn test_duplicate_vote_detector_invalid_signature() {
+        let keypair = Keypair::new();
+        let vote_account = keypair.pubkey();
+        let mut detector = DuplicateVoteDetector::default();
+        let hashes: Vec<_> = (0..3).map(|_| Hash::new_unique()).collect();
+        let votes: Vec<_> = hashes
+            .iter()
+            .map(|hash| vote_transaction(5, *hash, &keypair))
+            .collect();
+        let mut forged = votes[0].clone();
+        forged.signatures[0] = Signature::default();
+        let verify = |transaction: &Transaction| transaction.verify().is_ok();
+
+        // A forged vote can't be used as evidence, nor mask real evidence
+        assert!(detector
+            .check_vote(vote_account, 5, hashes[0], &forged, verify)
+            .is_none());
+        assert!(detector
+            .check_vote(vote_account, 5, hashes[1], &votes[1], verify)
+            .is_none());
+        assert!(detector
+            .check_vote(vote_account, 5, hashes[0], &forged, verify)
+            .is_none());
+        let proof = detector
+            .check_vote(vote_account, 5, hashes[2], &votes[2], verify)
+            .unwrap();
+        assert_eq!(proof.hash1, hashes[1]);
+        assert_eq!(proof.hash2, hashes[2]);
+    }
+
+    #[test]
+    fn test_duplicate_vote_detector_set_root() {
+        let keypair = Keypair::new();
+        let mut detector = DuplicateVoteDetector::default();
+        for slot in 1..=10 {
+            let hash = Hash::new_unique();
+            detector.check_vote(
+                keypair.pubkey(),
+                slot,
+                hash,
+                &vote_transaction(slot, hash, &keypair),
+                |_| true,
+            );
+        }
+        assert_eq!(detector.num_tracked_slots(), 10);
+        detector.set_root(7);
+        assert_eq!(detector.num_tracked_slots(), 3);
+    }
+}
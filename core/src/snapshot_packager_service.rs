@@ -5,6 +5,7 @@ use {
     snapshot_gossip_manager::SnapshotGossipManager,
     solana_gossip::cluster_info::ClusterInfo,
     solana_measure::{measure::Measure, measure_us},
+    solana_metrics::prometheus,
     solana_perf::thread::renice_this_thread,
     solana_runtime::{
         snapshot_config::SnapshotConfig, snapshot_hash::StartingSnapshotHashes,
@@ -41,8 +42,13 @@ impl SnapshotPackagerService {
             .spawn(move || {
                 info!("SnapshotPackagerService has started");
                 renice_this_thread(snapshot_config.packager_thread_niceness_adj).unwrap();
-                let mut snapshot_gossip_manager = enable_gossip_push
-                    .then(|| SnapshotGossipManager::new(cluster_info, starting_snapshot_hashes));
+                let mut snapshot_gossip_manager = enable_gossip_push.then(|| {
+                    SnapshotGossipManager::new(
+                        cluster_info,
+                        starting_snapshot_hashes,
+                        snapshot_config.gossip_quorum,
+                    )
+                });
 
                 loop {
                     if exit.load(Ordering::Relaxed) {
@@ -52,6 +58,9 @@ impl SnapshotPackagerService {
                     let Some(snapshot_package) =
                         Self::get_next_snapshot_package(&pending_snapshot_packages)
                     else {
+                        if let Some(snapshot_gossip_manager) = snapshot_gossip_manager.as_mut() {
+                            snapshot_gossip_manager.push_pending_snapshot_hashes();
+                        }
                         std::thread::sleep(Self::LOOP_LIMITER);
                         continue;
                     };
@@ -116,6 +125,21 @@ impl SnapshotPackagerService {
                         ),
                         ("purge_old_archives_time_us", purge_archives_time_us, i64),
                     );
+                    let kind = if snapshot_kind.is_full_snapshot() {
+                        "full"
+                    } else {
+                        "incremental"
+                    };
+                    prometheus::gauge_set(
+                        "snapshot_archived_slot",
+                        &[("kind", kind)],
+                        snapshot_slot as f64,
+                    );
+                    prometheus::histogram_observe(
+                        "snapshot_archive_time_us",
+                        &[("kind", kind)],
+                        archive_time_us as f64,
+                    );
                 }
                 info!("SnapshotPackagerService has stopped");
             })
@@ -1,5 +1,5 @@
 use {
-    solana_gossip::cluster_info::ClusterInfo,
+    solana_gossip::{cluster_info::ClusterInfo, crds_data::SnapshotHashes},
     solana_runtime::{
         snapshot_hash::{
             FullSnapshotHash, IncrementalSnapshotHash, SnapshotHash, StartingSnapshotHashes,
@@ -7,13 +7,25 @@ use {
         snapshot_package::SnapshotKind,
     },
     solana_sdk::{clock::Slot, hash::Hash},
-    std::sync::Arc,
+    std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    },
 };
 
+/// How often snapshot hashes waiting for a quorum are checked against gossip
+const QUORUM_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Manage pushing snapshot hash information to gossip
 pub struct SnapshotGossipManager {
     cluster_info: Arc<ClusterInfo>,
     latest_snapshot_hashes: Option<LatestSnapshotHashes>,
+    /// Number of other nodes which must gossip the same snapshot hash before it
+    /// is pushed, so that a node producing bad snapshots does not advertise them
+    quorum: usize,
+    /// Snapshot hashes last pushed to the cluster
+    pushed_snapshot_hashes: Option<LatestSnapshotHashes>,
+    last_quorum_check: Instant,
 }
 
 impl SnapshotGossipManager {
@@ -23,10 +35,14 @@ impl SnapshotGossipManager {
     pub fn new(
         cluster_info: Arc<ClusterInfo>,
         starting_snapshot_hashes: Option<StartingSnapshotHashes>,
+        quorum: usize,
     ) -> Self {
         let mut this = SnapshotGossipManager {
             cluster_info,
             latest_snapshot_hashes: None,
+            quorum,
+            pushed_snapshot_hashes: None,
+            last_quorum_check: Instant::now(),
         };
         if let Some(starting_snapshot_hashes) = starting_snapshot_hashes {
             this.push_starting_snapshot_hashes(starting_snapshot_hashes);
@@ -110,11 +126,65 @@ impl SnapshotGossipManager {
         latest_snapshot_hashes.incremental = Some(incremental_snapshot_hash);
     }
 
+    /// Push the latest snapshot hashes still waiting for a quorum, if other
+    /// nodes have since gossiped the same hashes
+    pub fn push_pending_snapshot_hashes(&mut self) {
+        if self.latest_snapshot_hashes == self.pushed_snapshot_hashes
+            || self.last_quorum_check.elapsed() < QUORUM_CHECK_INTERVAL
+        {
+            return;
+        }
+        self.push_latest_snapshot_hashes_to_cluster();
+    }
+
+    /// Returns the latest snapshot hashes which at least `quorum` other nodes
+    /// gossip. The incremental snapshot hash is left out if it lacks a quorum
+    /// while the full snapshot hash has one.
+    fn latest_snapshot_hashes_with_quorum(&mut self) -> Option<LatestSnapshotHashes> {
+        let latest_snapshot_hashes = self.latest_snapshot_hashes?;
+        if self.quorum == 0 {
+            return Some(latest_snapshot_hashes);
+        }
+        self.last_quorum_check = Instant::now();
+        let peers_snapshot_hashes: Vec<SnapshotHashes> = self
+            .cluster_info
+            .tvu_peers(|node| *node.pubkey())
+            .iter()
+            .filter_map(|pubkey| self.cluster_info.get_snapshot_hashes_for_node(pubkey))
+            .collect();
+        let full = latest_snapshot_hashes.full.clone_for_crds();
+        let num_peers_with_full = peers_snapshot_hashes
+            .iter()
+            .filter(|snapshot_hashes| snapshot_hashes.full == full)
+            .count();
+        if num_peers_with_full < self.quorum {
+            return None;
+        }
+        let incremental = latest_snapshot_hashes.incremental.filter(|incremental| {
+            let incremental = incremental.clone_for_crds();
+            let num_peers_with_incremental = peers_snapshot_hashes
+                .iter()
+                .filter(|snapshot_hashes| {
+                    snapshot_hashes.full == full
+                        && snapshot_hashes.incremental.contains(&incremental)
+                })
+                .count();
+            num_peers_with_incremental >= self.quorum
+        });
+        Some(LatestSnapshotHashes {
+            full: latest_snapshot_hashes.full,
+            incremental,
+        })
+    }
+
     /// Push the latest snapshot hashes to the cluster via CRDS
-    fn push_latest_snapshot_hashes_to_cluster(&self) {
-        let Some(latest_snapshot_hashes) = self.latest_snapshot_hashes.as_ref() else {
+    fn push_latest_snapshot_hashes_to_cluster(&mut self) {
+        let Some(latest_snapshot_hashes) = self.latest_snapshot_hashes_with_quorum() else {
             return;
         };
+        if self.quorum != 0 && self.pushed_snapshot_hashes == Some(latest_snapshot_hashes) {
+            return;
+        }
 
         // Pushing snapshot hashes to the cluster should never fail.  The only error case is when
         // the length of the incremental hashes is too big, (and we send a maximum of one here).
@@ -133,6 +203,7 @@ impl SnapshotGossipManager {
                 "Bug! The programmer contract has changed for push_snapshot_hashes() and a new \
                  error case has been added that has not been handled here.",
             );
+        self.pushed_snapshot_hashes = Some(latest_snapshot_hashes);
     }
 }
 
@@ -163,3 +234,107 @@ impl AsSnapshotHash for IncrementalSnapshotHash {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_gossip::{
+            contact_info::ContactInfo, crds::GossipRoute, crds_data::CrdsData,
+            crds_value::CrdsValue,
+        },
+        solana_sdk::{
+            signature::{Keypair, Signer},
+            timing::timestamp,
+        },
+        solana_streamer::socket::SocketAddrSpace,
+    };
+
+    fn insert_peer_snapshot_hashes(
+        cluster_info: &ClusterInfo,
+        full: (Slot, Hash),
+        incremental: Vec<(Slot, Hash)>,
+    ) {
+        let keypair = Keypair::new();
+        cluster_info.insert_info(ContactInfo::new_localhost(&keypair.pubkey(), timestamp()));
+        let snapshot_hashes = CrdsData::SnapshotHashes(SnapshotHashes {
+            from: keypair.pubkey(),
+            full,
+            incremental,
+            wallclock: timestamp(),
+        });
+        cluster_info
+            .gossip
+            .crds
+            .write()
+            .unwrap()
+            .insert(
+                CrdsValue::new(snapshot_hashes, &keypair),
+                timestamp(),
+                GossipRoute::LocalMessage,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_hashes_quorum() {
+        let keypair = Arc::new(Keypair::new());
+        let cluster_info = Arc::new(ClusterInfo::new(
+            ContactInfo::new_localhost(&keypair.pubkey(), timestamp()),
+            keypair,
+            SocketAddrSpace::Unspecified,
+        ));
+        let full = (100, Hash::new_unique());
+        let incremental = (150, Hash::new_unique());
+        let full_snapshot_hash = FullSnapshotHash((full.0, SnapshotHash(full.1)));
+        let mut snapshot_gossip_manager = SnapshotGossipManager::new(cluster_info.clone(), None, 2);
+        let push_pending_snapshot_hashes = |snapshot_gossip_manager: &mut SnapshotGossipManager| {
+            snapshot_gossip_manager.last_quorum_check = Instant::now() - QUORUM_CHECK_INTERVAL;
+            snapshot_gossip_manager.push_pending_snapshot_hashes();
+            snapshot_gossip_manager.pushed_snapshot_hashes
+        };
+
+        snapshot_gossip_manager
+            .push_snapshot_hash(SnapshotKind::FullSnapshot, (full.0, SnapshotHash(full.1)));
+        assert_eq!(snapshot_gossip_manager.pushed_snapshot_hashes, None);
+
+        // A peer gossiping a different hash for the slot does not count towards the quorum
+        insert_peer_snapshot_hashes(&cluster_info, full, vec![]);
+        insert_peer_snapshot_hashes(&cluster_info, (full.0, Hash::new_unique()), vec![]);
+        assert_eq!(
+            push_pending_snapshot_hashes(&mut snapshot_gossip_manager),
+            None
+        );
+
+        insert_peer_snapshot_hashes(&cluster_info, full, vec![incremental]);
+        let expected = LatestSnapshotHashes {
+            full: full_snapshot_hash,
+            incremental: None,
+        };
+        assert_eq!(
+            push_pending_snapshot_hashes(&mut snapshot_gossip_manager),
+            Some(expected)
+        );
+
+        // The incremental snapshot hash waits for its own quorum
+        snapshot_gossip_manager.push_snapshot_hash(
+            SnapshotKind::IncrementalSnapshot(full.0),
+            (incremental.0, SnapshotHash(incremental.1)),
+        );
+        assert_eq!(
+            push_pending_snapshot_hashes(&mut snapshot_gossip_manager),
+            Some(expected)
+        );
+        insert_peer_snapshot_hashes(&cluster_info, full, vec![incremental]);
+        assert_eq!(
+            push_pending_snapshot_hashes(&mut snapshot_gossip_manager),
+            Some(LatestSnapshotHashes {
+                full: full_snapshot_hash,
+                incremental: Some(IncrementalSnapshotHash((
+                    incremental.0,
+                    SnapshotHash(incremental.1)
+                ))),
+            })
+        );
+    }
+}
@@ -11,7 +11,7 @@ pub use solana_streamer::quic::DEFAULT_MAX_QUIC_CONNECTIONS_PER_PEER as MAX_QUIC
 use {
     crate::{
         banking_stage::BankingStage,
-        banking_trace::{Channels, TracerThread},
+        banking_trace::{BankingCapture, Channels, TracerThread},
         cluster_info_vote_listener::{
             ClusterInfoVoteListener, DuplicateConfirmedSlotsSender, GossipVerifiedVoteHashSender,
             VerifiedVoteSender, VoteTracker,
@@ -113,6 +113,7 @@ impl Tpu {
         staked_nodes: &Arc<RwLock<StakedNodes>>,
         shared_staked_nodes_overrides: Arc<RwLock<HashMap<Pubkey, u64>>>,
         banking_tracer_channels: Channels,
+        banking_capture: BankingCapture,
         tracer_thread_hdl: TracerThread,
         tpu_enable_udp: bool,
         tpu_quic_server_config: QuicServerParams,
@@ -264,6 +265,7 @@ impl Tpu {
             bank_forks.clone(),
             prioritization_fee_cache,
             enable_block_production_forwarding,
+            banking_capture,
         );
 
         let (entry_receiver, tpu_entry_notifier) =
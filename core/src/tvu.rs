@@ -85,6 +85,8 @@ pub struct TvuConfig {
     pub repair_validators: Option<HashSet<Pubkey>>,
     // Validators which should be given priority when serving repairs
     pub repair_whitelist: Arc<RwLock<HashSet<Pubkey>>>,
+    // Request repairs over QUIC from peers advertising a QUIC serve repair socket
+    pub repair_over_quic: bool,
     pub wait_for_vote_to_start_leader: bool,
     pub replay_forks_threads: NonZeroUsize,
     pub replay_transactions_threads: NonZeroUsize,
@@ -98,6 +100,7 @@ impl Default for TvuConfig {
             shred_version: 0,
             repair_validators: None,
             repair_whitelist: Arc::new(RwLock::new(HashSet::default())),
+            repair_over_quic: false,
             wait_for_vote_to_start_leader: false,
             replay_forks_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             replay_transactions_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
@@ -236,6 +239,7 @@ impl Tvu {
                 cluster_info: cluster_info.clone(),
                 cluster_slots: cluster_slots.clone(),
                 wen_restart_repair_slots,
+                repair_over_quic: tvu_config.repair_over_quic,
             };
             WindowService::new(
                 blockstore.clone(),
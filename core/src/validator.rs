@@ -13,6 +13,7 @@ use {
             tower_storage::{NullTowerStorage, TowerStorage},
             ExternalRootSource, Tower,
         },
+        identity_top_up_service::{IdentityTopUpConfig, IdentityTopUpService},
         poh_timing_report_service::PohTimingReportService,
         repair::{
             self,
@@ -22,6 +23,7 @@ use {
         },
         sample_performance_service::SamplePerformanceService,
         sigverify,
+        slashing_evidence::SlashingEvidenceService,
         snapshot_packager_service::{PendingSnapshotPackages, SnapshotPackagerService},
         stats_reporter_service::StatsReporterService,
         system_monitor_service::{
@@ -55,6 +57,7 @@ use {
         contact_info::ContactInfo,
         crds_gossip_pull::CRDS_GOSSIP_PULL_CRDS_TIMEOUT_MS,
         gossip_service::GossipService,
+        peer_filter::PeerFilter,
     },
     solana_ledger::{
         bank_forks_utils,
@@ -74,6 +77,7 @@ use {
     solana_measure::measure::Measure,
     solana_metrics::{
         datapoint_info, metrics::metrics_config_sanity_check, poh_timing_point::PohTimingSender,
+        prometheus::PrometheusService,
     },
     solana_poh::{
         poh_recorder::PohRecorder,
@@ -81,7 +85,7 @@ use {
     },
     solana_rayon_threadlimit::{get_max_thread_count, get_thread_count},
     solana_rpc::{
-        block_meta_service::{BlockMetaSender, BlockMetaService},
+        block_meta_service::{ArchivalAccountsConfig, BlockMetaSender, BlockMetaService},
         max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::{
             BankNotificationSenderConfig, OptimisticallyConfirmedBank,
@@ -92,6 +96,7 @@ use {
         rpc_pubsub_service::{PubSubConfig, PubSubService},
         rpc_service::JsonRpcService,
         rpc_subscriptions::RpcSubscriptions,
+        transaction_execution_details::TransactionExecutionDetailsCache,
         transaction_notifier_interface::TransactionNotifierArc,
         transaction_status_service::TransactionStatusService,
     },
@@ -100,10 +105,13 @@ use {
             AbsRequestHandlers, AbsRequestSender, AccountsBackgroundService, DroppedSlotsReceiver,
             PrunedBanksRequestHandler, SnapshotRequestHandler,
         },
-        bank::Bank,
+        bank::{freeze_hooks::BankFreezeHook, Bank},
         bank_forks::BankForks,
         commitment::BlockCommitmentCache,
-        prioritization_fee_cache::PrioritizationFeeCache,
+        prioritization_fee_cache::{PrioritizationFeeCache, MAX_NUM_RECENT_BLOCKS},
+        program_cache_warmup::{
+            warm_up_program_cache, ProgramCacheManifest, PROGRAM_CACHE_MANIFEST_FILE,
+        },
         runtime_config::RuntimeConfig,
         snapshot_archive_info::SnapshotArchiveInfoGetter,
         snapshot_bank_utils::{self, DISABLED_SNAPSHOT_ARCHIVE_INTERVAL},
@@ -123,7 +131,9 @@ use {
         signature::{Keypair, Signer},
         timing::timestamp,
     },
-    solana_send_transaction_service::send_transaction_service,
+    solana_send_transaction_service::{
+        compute_unit_price_escalation::ComputeUnitPriceEscalator, send_transaction_service,
+    },
     solana_streamer::{quic::QuicServerParams, socket::SocketAddrSpace, streamer::StakedNodes},
     solana_tpu_client::tpu_client::{
         DEFAULT_TPU_CONNECTION_POOL_SIZE, DEFAULT_TPU_USE_QUIC, DEFAULT_VOTE_USE_QUIC,
@@ -131,7 +141,10 @@ use {
     solana_turbine::{self, broadcast_stage::BroadcastStageType},
     solana_unified_scheduler_pool::DefaultSchedulerPool,
     solana_vote_program::vote_state,
-    solana_wen_restart::wen_restart::{wait_for_wen_restart, WenRestartConfig},
+    solana_wen_restart::wen_restart::{
+        wait_for_wen_restart, WenRestartConfig, DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
+        DEFAULT_REPAIR_THRESHOLD_PERCENT,
+    },
     std::{
         borrow::Cow,
         collections::{HashMap, HashSet},
@@ -186,10 +199,14 @@ impl BlockVerificationMethod {
     }
 }
 
+/// The transaction scheduling strategy of the banking stage's central scheduler thread, each
+/// variant being an implementation of its `TransactionScheduler` trait
 #[derive(Clone, EnumString, EnumVariantNames, Default, IntoStaticStr, Display)]
 #[strum(serialize_all = "kebab-case")]
 pub enum BlockProductionMethod {
+    /// Schedules by priority, following a graph of the conflicts between transactions
     CentralScheduler,
+    /// Schedules greedily by priority, skipping transactions conflicting with scheduled ones
     #[default]
     CentralSchedulerGreedy,
 }
@@ -268,7 +285,11 @@ pub struct ValidatorConfig {
     pub known_validators: Option<HashSet<Pubkey>>, // None = trust all
     pub repair_validators: Option<HashSet<Pubkey>>, // None = repair from all
     pub repair_whitelist: Arc<RwLock<HashSet<Pubkey>>>, // Empty = repair with all
+    /// Request repairs over QUIC from peers advertising a QUIC serve repair socket in gossip.
+    pub repair_over_quic: bool,
     pub gossip_validators: Option<HashSet<Pubkey>>, // None = gossip with all
+    /// Peers denied, or exclusively allowed, in gossip, repair and turbine retransmit
+    pub peer_filter: PeerFilter,
     pub accounts_hash_interval_slots: u64,
     pub max_genesis_archive_unpacked_size: u64,
     /// Run PoH, transaction signature and other transaction verifications during blockstore
@@ -280,11 +301,16 @@ pub struct ValidatorConfig {
     pub contact_debug_interval: u64,
     pub contact_save_interval: u64,
     pub send_transaction_service_config: send_transaction_service::Config,
+    /// Staked identity presented to leaders when forwarding RPC `sendTransaction` traffic over
+    /// QUIC, so that it is served from the stake-weighted QoS allotment of that identity.
+    pub rpc_send_transaction_staked_identity: Option<Arc<Keypair>>,
     pub no_poh_speed_test: bool,
     pub no_os_memory_stats_reporting: bool,
     pub no_os_network_stats_reporting: bool,
     pub no_os_cpu_stats_reporting: bool,
     pub no_os_disk_stats_reporting: bool,
+    /// Serves the metrics of the validator in the Prometheus text format at `/metrics`
+    pub prometheus_bind_address: Option<SocketAddr>,
     pub poh_pinned_cpu_core: usize,
     pub poh_hashes_per_batch: u64,
     pub process_ledger_before_services: bool,
@@ -300,6 +326,8 @@ pub struct ValidatorConfig {
     pub wait_to_vote_slot: Option<Slot>,
     pub runtime_config: RuntimeConfig,
     pub banking_trace_dir_byte_limit: banking_trace::DirByteLimit,
+    /// Also capture the banking stage's buffering decisions into the banking trace directory
+    pub banking_trace_capture: bool,
     pub block_verification_method: BlockVerificationMethod,
     pub block_production_method: BlockProductionMethod,
     pub transaction_struct: TransactionStructure,
@@ -308,6 +336,9 @@ pub struct ValidatorConfig {
     pub use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup,
     pub wen_restart_proto_path: Option<PathBuf>,
     pub wen_restart_coordinator: Option<Pubkey>,
+    pub wen_restart_repair_threshold_percent: u64,
+    pub wen_restart_heaviest_fork_threshold_delta_percent: u64,
+    pub wen_restart_last_voted_fork_slots_timeout: Option<Duration>,
     pub unified_scheduler_handler_threads: Option<usize>,
     pub ip_echo_server_threads: NonZeroUsize,
     pub rayon_global_threads: NonZeroUsize,
@@ -315,6 +346,18 @@ pub struct ValidatorConfig {
     pub replay_transactions_threads: NonZeroUsize,
     pub tvu_shred_sigverify_threads: NonZeroUsize,
     pub delay_leader_block_for_pending_fork: bool,
+    /// Programs loaded and compiled into the program cache before the validator starts, so
+    /// that the first leader slots after a restart don't spend their time compiling them
+    pub program_cache_warmup_programs: Vec<Pubkey>,
+    /// Number of the most used programs saved to the ledger directory on exit and warmed up on
+    /// the next start, zero to disable
+    pub program_cache_warmup_persist_count: usize,
+    /// Checks run on every frozen bank, with their time budget
+    pub bank_freeze_hooks: Vec<(Arc<dyn BankFreezeHook>, Duration)>,
+    /// Keeps the balance of the identity above a threshold from a treasury account
+    pub identity_top_up: Option<IdentityTopUpConfig>,
+    /// Records the CPI distributions of the executed transactions, per program
+    pub enable_cpi_stats: bool,
 }
 
 impl Default for ValidatorConfig {
@@ -343,7 +386,9 @@ impl Default for ValidatorConfig {
             known_validators: None,
             repair_validators: None,
             repair_whitelist: Arc::new(RwLock::new(HashSet::default())),
+            repair_over_quic: false,
             gossip_validators: None,
+            peer_filter: PeerFilter::default(),
             accounts_hash_interval_slots: u64::MAX,
             max_genesis_archive_unpacked_size: MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
             run_verification: true,
@@ -353,11 +398,13 @@ impl Default for ValidatorConfig {
             contact_debug_interval: DEFAULT_CONTACT_DEBUG_INTERVAL_MILLIS,
             contact_save_interval: DEFAULT_CONTACT_SAVE_INTERVAL_MILLIS,
             send_transaction_service_config: send_transaction_service::Config::default(),
+            rpc_send_transaction_staked_identity: None,
             no_poh_speed_test: true,
             no_os_memory_stats_reporting: true,
             no_os_network_stats_reporting: true,
             no_os_cpu_stats_reporting: true,
             no_os_disk_stats_reporting: true,
+            prometheus_bind_address: None,
             poh_pinned_cpu_core: poh_service::DEFAULT_PINNED_CPU_CORE,
             poh_hashes_per_batch: poh_service::DEFAULT_HASHES_PER_BATCH,
             process_ledger_before_services: false,
@@ -373,6 +420,7 @@ impl Default for ValidatorConfig {
             wait_to_vote_slot: None,
             runtime_config: RuntimeConfig::default(),
             banking_trace_dir_byte_limit: 0,
+            banking_trace_capture: false,
             block_verification_method: BlockVerificationMethod::default(),
             block_production_method: BlockProductionMethod::default(),
             transaction_struct: TransactionStructure::default(),
@@ -381,6 +429,10 @@ impl Default for ValidatorConfig {
             use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup::default(),
             wen_restart_proto_path: None,
             wen_restart_coordinator: None,
+            wen_restart_repair_threshold_percent: DEFAULT_REPAIR_THRESHOLD_PERCENT,
+            wen_restart_heaviest_fork_threshold_delta_percent:
+                DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
+            wen_restart_last_voted_fork_slots_timeout: None,
             unified_scheduler_handler_threads: None,
             ip_echo_server_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             rayon_global_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
@@ -388,6 +440,11 @@ impl Default for ValidatorConfig {
             replay_transactions_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             tvu_shred_sigverify_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             delay_leader_block_for_pending_fork: false,
+            program_cache_warmup_programs: Vec::new(),
+            bank_freeze_hooks: Vec::new(),
+            identity_top_up: None,
+            program_cache_warmup_persist_count: 0,
+            enable_cpi_stats: false,
         }
     }
 }
@@ -500,6 +557,7 @@ struct TransactionHistoryServices {
     transaction_status_service: Option<TransactionStatusService>,
     max_complete_transaction_status_slot: Arc<AtomicU64>,
     max_complete_rewards_slot: Arc<AtomicU64>,
+    transaction_execution_details: Arc<TransactionExecutionDetailsCache>,
     block_meta_sender: Option<BlockMetaSender>,
     block_meta_service: Option<BlockMetaService>,
 }
@@ -564,7 +622,10 @@ pub struct Validator {
     block_meta_service: Option<BlockMetaService>,
     entry_notifier_service: Option<EntryNotifierService>,
     system_monitor_service: Option<SystemMonitorService>,
+    prometheus_service: Option<PrometheusService>,
     sample_performance_service: Option<SamplePerformanceService>,
+    slashing_evidence_service: Option<SlashingEvidenceService>,
+    identity_top_up_service: Option<IdentityTopUpService>,
     poh_timing_report_service: PohTimingReportService,
     stats_reporter_service: StatsReporterService,
     gossip_service: GossipService,
@@ -589,6 +650,8 @@ pub struct Validator {
     repair_quic_endpoints: Option<[Endpoint; 3]>,
     repair_quic_endpoints_runtime: Option<TokioRuntime>,
     repair_quic_endpoints_join_handle: Option<repair::quic_endpoint::AsyncTryJoinHandle>,
+    /// Where to save the most used programs on exit, and how many
+    program_cache_warmup_persist: Option<(PathBuf, usize)>,
 }
 
 impl Validator {
@@ -795,6 +858,17 @@ impl Validator {
             },
         ));
 
+        let prometheus_service = config
+            .prometheus_bind_address
+            .map(|bind_address| {
+                PrometheusService::new(bind_address, exit.clone()).map_err(|err| {
+                    ValidatorError::Other(format!(
+                        "failed to start the Prometheus exporter on {bind_address}: {err}"
+                    ))
+                })
+            })
+            .transpose()?;
+
         let (poh_timing_point_sender, poh_timing_point_receiver) = unbounded();
         let poh_timing_report_service =
             PohTimingReportService::new(poh_timing_point_receiver, exit.clone());
@@ -811,6 +885,7 @@ impl Validator {
                 transaction_status_service,
                 max_complete_transaction_status_slot,
                 max_complete_rewards_slot,
+                transaction_execution_details,
                 block_meta_sender,
                 block_meta_service,
             },
@@ -835,6 +910,32 @@ impl Validator {
             check_poh_speed(&bank_forks.read().unwrap().root_bank(), None)?;
         }
 
+        let program_cache_warmup_path = ledger_path.join(PROGRAM_CACHE_MANIFEST_FILE);
+        let program_cache_warmup_persist =
+            (config.program_cache_warmup_persist_count > 0).then(|| {
+                (
+                    program_cache_warmup_path.clone(),
+                    config.program_cache_warmup_persist_count,
+                )
+            });
+        warm_up_program_cache_at_startup(
+            &bank_forks.read().unwrap().root_bank(),
+            &config.program_cache_warmup_programs,
+            program_cache_warmup_persist
+                .as_ref()
+                .map(|(path, _)| path.as_path()),
+        );
+
+        {
+            let root_bank = bank_forks.read().unwrap().root_bank();
+            for (hook, time_budget) in &config.bank_freeze_hooks {
+                root_bank
+                    .freeze_hooks()
+                    .register(hook.clone(), *time_budget);
+            }
+            root_bank.cpi_stats().set_enabled(config.enable_cpi_stats);
+        }
+
         let (root_slot, hard_forks) = {
             let root_bank = bank_forks.read().unwrap().root_bank();
             (root_bank.slot(), root_bank.hard_forks())
@@ -874,6 +975,13 @@ impl Validator {
 
         node.info.set_shred_version(shred_version);
         node.info.set_wallclock(timestamp());
+        // Peers only send repair requests over QUIC to nodes advertising a
+        // QUIC serve repair socket in gossip.
+        let repair_over_quic =
+            config.repair_over_quic || genesis_config.cluster_type != ClusterType::MainnetBeta;
+        if !repair_over_quic {
+            node.info.remove_serve_repair_quic();
+        }
         Self::print_node_info(&node);
 
         let mut cluster_info = ClusterInfo::new(
@@ -883,6 +991,7 @@ impl Validator {
         );
         cluster_info.set_contact_debug_interval(config.contact_debug_interval);
         cluster_info.set_entrypoints(cluster_entrypoints);
+        cluster_info.set_peer_filter(config.peer_filter.clone());
         cluster_info.restore_contact_info(ledger_path, config.contact_save_interval);
         let cluster_info = Arc::new(cluster_info);
 
@@ -946,7 +1055,11 @@ impl Validator {
 
         // block min prioritization fee cache should be readable by RPC, and writable by validator
         // (by both replay stage and banking stage)
-        let prioritization_fee_cache = Arc::new(PrioritizationFeeCache::default());
+        let prioritization_fee_cache = Arc::new(if config.rpc_config.enable_program_fee_market {
+            PrioritizationFeeCache::new_with_program_attribution(MAX_NUM_RECENT_BLOCKS)
+        } else {
+            PrioritizationFeeCache::default()
+        });
 
         let leader_schedule_cache = Arc::new(leader_schedule_cache);
         let startup_verification_complete;
@@ -970,16 +1083,19 @@ impl Validator {
         };
         let poh_recorder = Arc::new(RwLock::new(poh_recorder));
 
-        let (banking_tracer, tracer_thread) =
-            BankingTracer::new((config.banking_trace_dir_byte_limit > 0).then_some((
+        let (banking_tracer, tracer_thread) = BankingTracer::new_with_capture(
+            (config.banking_trace_dir_byte_limit > 0).then_some((
                 &blockstore.banking_trace_path(),
                 exit.clone(),
                 config.banking_trace_dir_byte_limit,
-            )))?;
+            )),
+            config.banking_trace_capture,
+        )?;
         if banking_tracer.is_enabled() {
             info!(
-                "Enabled banking trace (dir_byte_limit: {})",
-                config.banking_trace_dir_byte_limit
+                "Enabled banking trace (dir_byte_limit: {}, capture: {})",
+                config.banking_trace_dir_byte_limit,
+                banking_tracer.is_capture_enabled(),
             );
         } else {
             info!("Disabled banking trace");
@@ -1059,6 +1175,16 @@ impl Validator {
                 None
             };
 
+        // Evidence is only collected where it can be queried with getSlashingEvidence
+        let slashing_evidence_service = config.rpc_addrs.is_some().then(|| {
+            SlashingEvidenceService::new(
+                exit.clone(),
+                cluster_info.clone(),
+                bank_forks.clone(),
+                blockstore.clone(),
+            )
+        });
+
         let mut block_commitment_cache = BlockCommitmentCache::default();
         let bank_forks_guard = bank_forks.read().unwrap();
         block_commitment_cache.initialize_slots(
@@ -1081,6 +1207,7 @@ impl Validator {
             optimistically_confirmed_bank.clone(),
             &config.pubsub_config,
             None,
+            transaction_execution_details,
         ));
 
         let max_slots = Arc::new(MaxSlots::default());
@@ -1135,6 +1262,26 @@ impl Validator {
             ))
         };
 
+        let (rpc_connection_cache, rpc_forwards_with_staked_identity) = new_rpc_connection_cache(
+            config.rpc_send_transaction_staked_identity.as_deref(),
+            use_quic,
+            &connection_cache,
+            tpu_connection_pool_size,
+            &node.info,
+            &staked_nodes,
+        )?;
+
+        let identity_top_up_service = config.identity_top_up.clone().map(|top_up_config| {
+            IdentityTopUpService::new(
+                top_up_config,
+                cluster_info.clone(),
+                bank_forks.clone(),
+                poh_recorder.clone(),
+                connection_cache.clone(),
+                exit.clone(),
+            )
+        });
+
         let rpc_override_health_check =
             Arc::new(AtomicBool::new(config.rpc_config.disable_health_check));
         let (
@@ -1158,6 +1305,16 @@ impl Validator {
             } else {
                 None
             };
+            // Recommendations of higher compute unit prices are delivered to the senders of the
+            // transactions through the pubsub service
+            let send_transaction_service_config = send_transaction_service::Config {
+                compute_unit_price_escalator: Some(Arc::new(ComputeUnitPriceEscalator::new(
+                    prioritization_fee_cache.clone(),
+                    rpc_subscriptions.clone(),
+                ))),
+                forward_with_staked_identity: rpc_forwards_with_staked_identity,
+                ..config.send_transaction_service_config.clone()
+            };
 
             let json_rpc_service = JsonRpcService::new(
                 rpc_addr,
@@ -1175,13 +1332,20 @@ impl Validator {
                 rpc_override_health_check.clone(),
                 startup_verification_complete,
                 optimistically_confirmed_bank.clone(),
-                config.send_transaction_service_config.clone(),
+                send_transaction_service_config,
                 max_slots.clone(),
                 leader_schedule_cache.clone(),
-                connection_cache.clone(),
+                rpc_connection_cache.clone(),
                 max_complete_transaction_status_slot,
                 max_complete_rewards_slot,
                 prioritization_fee_cache.clone(),
+                rpc_subscriptions.confirmation_latency().clone(),
+                rpc_subscriptions
+                    .optimistic_confirmation_attestations()
+                    .clone(),
+                geyser_plugin_service
+                    .as_ref()
+                    .map(GeyserPluginService::get_plugin_health_reporter),
             )
             .map_err(ValidatorError::Other)?;
 
@@ -1390,8 +1554,7 @@ impl Validator {
         };
 
         // Repair quic endpoint.
-        let repair_quic_endpoints_runtime = (current_runtime_handle.is_err()
-            && genesis_config.cluster_type != ClusterType::MainnetBeta)
+        let repair_quic_endpoints_runtime = (current_runtime_handle.is_err() && repair_over_quic)
             .then(|| {
                 tokio::runtime::Builder::new_multi_thread()
                     .enable_all()
@@ -1400,7 +1563,7 @@ impl Validator {
                     .unwrap()
             });
         let (repair_quic_endpoints, repair_quic_async_senders, repair_quic_endpoints_join_handle) =
-            if genesis_config.cluster_type == ClusterType::MainnetBeta {
+            if !repair_over_quic {
                 (None, RepairQuicAsyncSenders::new_dummy(), None)
             } else {
                 let repair_quic_sockets = RepairQuicSockets {
@@ -1505,6 +1668,7 @@ impl Validator {
                 shred_version: node.info.shred_version(),
                 repair_validators: config.repair_validators.clone(),
                 repair_whitelist: config.repair_whitelist.clone(),
+                repair_over_quic: config.repair_over_quic,
                 wait_for_vote_to_start_leader,
                 replay_forks_threads: config.replay_forks_threads,
                 replay_transactions_threads: config.replay_transactions_threads,
@@ -1515,7 +1679,7 @@ impl Validator {
             config.wait_to_vote_slot,
             accounts_background_request_sender.clone(),
             config.runtime_config.log_messages_bytes_limit,
-            json_rpc_service.is_some().then_some(&connection_cache), // for the cache warmer only used for STS for RPC service
+            json_rpc_service.is_some().then_some(&rpc_connection_cache), // for the cache warmer only used for STS for RPC service
             &prioritization_fee_cache,
             banking_tracer.clone(),
             turbine_quic_endpoint_sender.clone(),
@@ -1528,7 +1692,7 @@ impl Validator {
             cluster_slots.clone(),
             wen_restart_repair_slots.clone(),
             slot_status_notifier,
-            vote_connection_cache,
+            vote_connection_cache.clone(),
         )
         .map_err(ValidatorError::Other)?;
 
@@ -1544,6 +1708,10 @@ impl Validator {
                 wen_restart_repair_slots: wen_restart_repair_slots.clone(),
                 wait_for_supermajority_threshold_percent:
                     WAIT_FOR_WEN_RESTART_SUPERMAJORITY_THRESHOLD_PERCENT,
+                repair_threshold_percent: config.wen_restart_repair_threshold_percent,
+                heaviest_fork_threshold_delta_percent: config
+                    .wen_restart_heaviest_fork_threshold_delta_percent,
+                last_voted_fork_slots_timeout: config.wen_restart_last_voted_fork_slots_timeout,
                 snapshot_config: config.snapshot_config.clone(),
                 accounts_background_request_sender: accounts_background_request_sender.clone(),
                 genesis_config_hash: genesis_config.hash(),
@@ -1589,6 +1757,9 @@ impl Validator {
             &staked_nodes,
             config.staked_nodes_overrides.clone(),
             banking_tracer_channels,
+            banking_tracer
+                .capture()
+                .with_ingestion_log(config.rpc_config.transaction_ingestion_log.clone()),
             tracer_thread,
             tpu_enable_udp,
             tpu_quic_server_config,
@@ -1612,6 +1783,10 @@ impl Validator {
         );
 
         *start_progress.write().unwrap() = ValidatorStartProgress::Running;
+        let mut connection_caches = vec![connection_cache.clone(), vote_connection_cache];
+        if !Arc::ptr_eq(&rpc_connection_cache, &connection_cache) {
+            connection_caches.push(rpc_connection_cache);
+        }
         key_notifies.push(connection_cache);
 
         *admin_rpc_service_post_init.write().unwrap() = Some(AdminRpcRequestMetadataPostInit {
@@ -1623,6 +1798,8 @@ impl Validator {
             repair_socket: Arc::new(node.sockets.repair),
             outstanding_repair_requests,
             cluster_slots,
+            connection_caches,
+            leader_schedule_cache: leader_schedule_cache.clone(),
         });
 
         Ok(Self {
@@ -1637,7 +1814,10 @@ impl Validator {
             block_meta_service,
             entry_notifier_service,
             system_monitor_service,
+            prometheus_service,
             sample_performance_service,
+            slashing_evidence_service,
+            identity_top_up_service,
             poh_timing_report_service,
             snapshot_packager_service,
             completed_data_sets_service,
@@ -1660,6 +1840,7 @@ impl Validator {
             repair_quic_endpoints,
             repair_quic_endpoints_runtime,
             repair_quic_endpoints_join_handle,
+            program_cache_warmup_persist,
         })
     }
 
@@ -1702,6 +1883,17 @@ impl Validator {
     }
 
     pub fn join(self) {
+        if let Some((path, count)) = &self.program_cache_warmup_persist {
+            let root_bank = self.bank_forks.read().unwrap().root_bank();
+            let manifest = ProgramCacheManifest::new(&root_bank, *count);
+            match manifest.write(path) {
+                Ok(()) => info!(
+                    "Saved the {} most used programs to {path:?}",
+                    manifest.entries.len()
+                ),
+                Err(err) => warn!("Failed to save the most used programs to {path:?}: {err}"),
+            }
+        }
         drop(self.bank_forks);
         drop(self.cluster_info);
 
@@ -1746,12 +1938,28 @@ impl Validator {
                 .expect("system_monitor_service");
         }
 
+        if let Some(prometheus_service) = self.prometheus_service {
+            prometheus_service.join().expect("prometheus_service");
+        }
+
         if let Some(sample_performance_service) = self.sample_performance_service {
             sample_performance_service
                 .join()
                 .expect("sample_performance_service");
         }
 
+        if let Some(slashing_evidence_service) = self.slashing_evidence_service {
+            slashing_evidence_service
+                .join()
+                .expect("slashing_evidence_service");
+        }
+
+        if let Some(identity_top_up_service) = self.identity_top_up_service {
+            identity_top_up_service
+                .join()
+                .expect("identity_top_up_service");
+        }
+
         if let Some(entry_notifier_service) = self.entry_notifier_service {
             entry_notifier_service
                 .join()
@@ -1818,6 +2026,51 @@ impl Validator {
     }
 }
 
+/// Returns the connection cache RPC transactions are forwarded with, and whether it presents the
+/// staked identity of `--rpc-send-transaction-staked-identity`.
+///
+/// RPC nodes are usually unstaked, so the transactions they forward compete for the leader's
+/// unstaked stream allotment. When a staked identity is configured, forward with a dedicated
+/// connection cache presenting that identity so the leader applies its stake-weighted QoS instead.
+fn new_rpc_connection_cache(
+    staked_identity: Option<&Keypair>,
+    use_quic: bool,
+    tpu_connection_cache: &Arc<ConnectionCache>,
+    tpu_connection_pool_size: usize,
+    contact_info: &ContactInfo,
+    staked_nodes: &Arc<RwLock<StakedNodes>>,
+) -> Result<(Arc<ConnectionCache>, bool), ValidatorError> {
+    match staked_identity {
+        Some(staked_identity) if use_quic => {
+            info!(
+                "Forwarding RPC transactions with staked identity {}",
+                staked_identity.pubkey()
+            );
+            let connection_cache = ConnectionCache::new_with_client_options(
+                "connection_cache_rpc_staked_quic",
+                tpu_connection_pool_size,
+                None, // client_endpoint
+                Some((
+                    staked_identity,
+                    contact_info
+                        .tpu(Protocol::UDP)
+                        .ok_or_else(|| {
+                            ValidatorError::Other(String::from("Invalid UDP address for TPU"))
+                        })?
+                        .ip(),
+                )),
+                Some((staked_nodes, &staked_identity.pubkey())),
+            );
+            Ok((Arc::new(connection_cache), true))
+        }
+        Some(_) => {
+            warn!("--rpc-send-transaction-staked-identity is ignored when QUIC is disabled");
+            Ok((tpu_connection_cache.clone(), false))
+        }
+        None => Ok((tpu_connection_cache.clone(), false)),
+    }
+}
+
 fn active_vote_account_exists_in_bank(bank: &Bank, vote_account: &Pubkey) -> bool {
     if let Some(account) = &bank.get_account(vote_account) {
         if let Some(vote_state) = vote_state::from(account) {
@@ -1827,6 +2080,26 @@ fn active_vote_account_exists_in_bank(bank: &Bank, vote_account: &Pubkey) -> boo
     false
 }
 
+/// Warms the program cache up with the configured programs and, if `persisted_path` is given,
+/// with the programs of the previous run's manifest which are unchanged since
+fn warm_up_program_cache_at_startup(
+    bank: &Bank,
+    programs: &[Pubkey],
+    persisted_path: Option<&Path>,
+) {
+    if !programs.is_empty() {
+        warm_up_program_cache(bank, programs);
+    }
+    if let Some(path) = persisted_path.filter(|path| path.exists()) {
+        match ProgramCacheManifest::read(path) {
+            Ok(manifest) => {
+                manifest.warm_up(bank);
+            }
+            Err(err) => warn!("Failed to read the program cache manifest {path:?}: {err}"),
+        }
+    }
+}
+
 fn check_poh_speed(bank: &Bank, maybe_hash_samples: Option<u64>) -> Result<(), ValidatorError> {
     let Some(hashes_per_tick) = bank.hashes_per_tick() else {
         warn!("Unable to read hashes per tick from Bank, skipping PoH speed check");
@@ -2046,6 +2319,10 @@ fn load_blockstore(
     let enable_rpc_transaction_history =
         config.rpc_addrs.is_some() && config.rpc_config.enable_rpc_transaction_history;
     let is_plugin_transaction_history_required = transaction_notifier.as_ref().is_some();
+    // Signature notifications include the fee and compute units of the transactions recorded
+    // by the transaction status service
+    let enable_transaction_execution_details =
+        config.rpc_addrs.is_some() && config.rpc_config.full_api;
     let transaction_history_services =
         if enable_rpc_transaction_history || is_plugin_transaction_history_required {
             initialize_rpc_transaction_history_services(
@@ -2053,7 +2330,9 @@ fn load_blockstore(
                 exit.clone(),
                 enable_rpc_transaction_history,
                 config.rpc_config.enable_extended_tx_metadata_storage,
+                config.rpc_config.archival_accounts.clone(),
                 transaction_notifier,
+                enable_transaction_execution_details,
             )
         } else {
             TransactionHistoryServices::default()
@@ -2484,9 +2763,12 @@ fn initialize_rpc_transaction_history_services(
     exit: Arc<AtomicBool>,
     enable_rpc_transaction_history: bool,
     enable_extended_tx_metadata_storage: bool,
+    archival_accounts_config: Option<ArchivalAccountsConfig>,
     transaction_notifier: Option<TransactionNotifierArc>,
+    enable_transaction_execution_details: bool,
 ) -> TransactionHistoryServices {
     let max_complete_transaction_status_slot = Arc::new(AtomicU64::new(blockstore.max_root()));
+    let transaction_execution_details = Arc::<TransactionExecutionDetailsCache>::default();
     let (transaction_status_sender, transaction_status_receiver) = unbounded();
     let transaction_status_sender = Some(TransactionStatusSender {
         sender: transaction_status_sender,
@@ -2496,6 +2778,7 @@ fn initialize_rpc_transaction_history_services(
         max_complete_transaction_status_slot.clone(),
         enable_rpc_transaction_history,
         transaction_notifier,
+        enable_transaction_execution_details.then(|| transaction_execution_details.clone()),
         blockstore.clone(),
         enable_extended_tx_metadata_storage,
         exit.clone(),
@@ -2508,6 +2791,7 @@ fn initialize_rpc_transaction_history_services(
         block_meta_receiver,
         blockstore,
         max_complete_rewards_slot.clone(),
+        archival_accounts_config,
         exit,
     ));
     TransactionHistoryServices {
@@ -2515,6 +2799,7 @@ fn initialize_rpc_transaction_history_services(
         transaction_status_service,
         max_complete_transaction_status_slot,
         max_complete_rewards_slot,
+        transaction_execution_details,
         block_meta_sender,
         block_meta_service,
     }
@@ -2842,6 +3127,56 @@ mod tests {
         remove_dir_all(validator_ledger_path).unwrap();
     }
 
+    #[test]
+    fn test_new_rpc_connection_cache() {
+        let identity = Keypair::new();
+        let staked_identity = Keypair::new();
+        let contact_info = ContactInfo::new_localhost(&identity.pubkey(), 0);
+        let staked_nodes = Arc::new(RwLock::new(StakedNodes::default()));
+        let tpu_connection_cache = Arc::new(ConnectionCache::new_quic("connection_cache_test", 1));
+
+        // Without a staked identity, RPC forwards through the TPU connection cache
+        let (connection_cache, staked) = new_rpc_connection_cache(
+            None,
+            true, // use_quic
+            &tpu_connection_cache,
+            1,
+            &contact_info,
+            &staked_nodes,
+        )
+        .unwrap();
+        assert!(Arc::ptr_eq(&connection_cache, &tpu_connection_cache));
+        assert!(!staked);
+
+        let (connection_cache, staked) = new_rpc_connection_cache(
+            Some(&staked_identity),
+            true, // use_quic
+            &tpu_connection_cache,
+            1,
+            &contact_info,
+            &staked_nodes,
+        )
+        .unwrap();
+        assert!(!Arc::ptr_eq(&connection_cache, &tpu_connection_cache));
+        assert_eq!(connection_cache.name(), "connection_cache_rpc_staked_quic");
+        assert!(connection_cache.use_quic());
+        assert!(staked);
+
+        // The staked identity is only presented over QUIC
+        let tpu_connection_cache = Arc::new(ConnectionCache::with_udp("connection_cache_test", 1));
+        let (connection_cache, staked) = new_rpc_connection_cache(
+            Some(&staked_identity),
+            false, // use_quic
+            &tpu_connection_cache,
+            1,
+            &contact_info,
+            &staked_nodes,
+        )
+        .unwrap();
+        assert!(Arc::ptr_eq(&connection_cache, &tpu_connection_cache));
+        assert!(!staked);
+    }
+
     #[test]
     fn test_should_cleanup_blockstore_incorrect_shred_versions() {
         solana_logger::setup();
@@ -27,6 +27,9 @@ use {
         MAX_PERMITTED_DATA_LENGTH,
     },
     std::num::Saturating,
+    tachyon_system_interface::transfer_many::{
+        TransferMany, TRANSFER_MANY_COMPUTE_UNITS_PER_RECIPIENT,
+    },
 };
 
 pub struct CostModel;
@@ -225,7 +228,11 @@ impl CostModel {
         for (program_id, instruction) in instructions {
             let ix_execution_cost =
                 if let Some(builtin_cost) = get_builtin_instruction_cost(program_id, feature_set) {
-                    builtin_cost
+                    builtin_cost.saturating_add(Self::get_transfer_many_cost(
+                        program_id,
+                        &instruction,
+                        feature_set,
+                    ))
                 } else {
                     has_user_space_instructions = true;
                     u64::from(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
@@ -285,6 +292,24 @@ impl CostModel {
         )
     }
 
+    /// Returns the execution cost of a system program `TransferMany` instruction on top of the
+    /// builtin cost of the system program, which grows with its number of recipients
+    fn get_transfer_many_cost(
+        program_id: &Pubkey,
+        instruction: &SVMInstruction,
+        feature_set: &FeatureSet,
+    ) -> u64 {
+        if system_program::check_id(program_id)
+            && TransferMany::is_transfer_many(instruction.data)
+            && feature_set.is_active(&feature_set::transfer_many::id())
+        {
+            let num_recipients = instruction.accounts.len().saturating_sub(1) as u64;
+            TRANSFER_MANY_COMPUTE_UNITS_PER_RECIPIENT.saturating_mul(num_recipients)
+        } else {
+            0
+        }
+    }
+
     /// Return (programs_execution_cost, loaded_accounts_data_size_cost)
     fn get_estimated_execution_cost(
         transaction: &impl StaticMeta,
@@ -808,6 +833,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cost_model_transfer_many_instruction() {
+        let (mint_keypair, start_hash) = test_setup();
+
+        let transfers: Vec<_> = (0..3).map(|i| (solana_pubkey::new_rand(), i)).collect();
+        let instruction = tachyon_system_interface::transfer_many::transfer_many(
+            &mint_keypair.pubkey(),
+            &transfers,
+        );
+        let message = Message::new(&[instruction], Some(&mint_keypair.pubkey()));
+        let tx = RuntimeTransaction::from_transaction_for_tests(Transaction::new(
+            &[&mint_keypair],
+            message,
+            start_hash,
+        ));
+
+        let mut feature_set = FeatureSet::all_enabled();
+        feature_set.deactivate(&feature_set::reserve_minimal_cus_for_builtin_instructions::id());
+        let (programs_execution_cost, _loaded_accounts_data_size_cost, _data_bytes_cost) =
+            CostModel::get_transaction_cost(&tx, tx.program_instructions_iter(), &feature_set);
+        assert_eq!(
+            solana_system_program::system_processor::DEFAULT_COMPUTE_UNITS
+                + 3 * TRANSFER_MANY_COMPUTE_UNITS_PER_RECIPIENT,
+            programs_execution_cost
+        );
+
+        feature_set.deactivate(&feature_set::transfer_many::id());
+        let (programs_execution_cost, _loaded_accounts_data_size_cost, _data_bytes_cost) =
+            CostModel::get_transaction_cost(&tx, tx.program_instructions_iter(), &feature_set);
+        assert_eq!(
+            solana_system_program::system_processor::DEFAULT_COMPUTE_UNITS,
+            programs_execution_cost
+        );
+    }
+
     #[test]
     fn test_cost_model_message_many_different_instructions() {
         let (mint_keypair, start_hash) = test_setup();
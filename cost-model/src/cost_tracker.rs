@@ -67,6 +67,8 @@ pub struct CostTracker {
     block_cost: u64,
     vote_cost: u64,
     transaction_count: Saturating<u64>,
+    vote_transaction_count: Saturating<u64>,
+    vote_transaction_signature_count: Saturating<u64>,
     allocated_accounts_data_size: Saturating<u64>,
     transaction_signature_count: Saturating<u64>,
     secp256k1_instruction_signature_count: Saturating<u64>,
@@ -97,6 +99,8 @@ impl Default for CostTracker {
             block_cost: 0,
             vote_cost: 0,
             transaction_count: Saturating(0),
+            vote_transaction_count: Saturating(0),
+            vote_transaction_signature_count: Saturating(0),
             allocated_accounts_data_size: Saturating(0),
             transaction_signature_count: Saturating(0),
             secp256k1_instruction_signature_count: Saturating(0),
@@ -123,6 +127,8 @@ impl CostTracker {
         self.block_cost = 0;
         self.vote_cost = 0;
         self.transaction_count = Saturating(0);
+        self.vote_transaction_count = Saturating(0);
+        self.vote_transaction_signature_count = Saturating(0);
         self.allocated_accounts_data_size = Saturating(0);
         self.transaction_signature_count = Saturating(0);
         self.secp256k1_instruction_signature_count = Saturating(0);
@@ -216,6 +222,20 @@ impl CostTracker {
         self.transaction_count.0
     }
 
+    /// Number of simple vote transactions, whose cost is tracked by `vote_cost()` even though
+    /// they pay no fees
+    pub fn vote_transaction_count(&self) -> u64 {
+        self.vote_transaction_count.0
+    }
+
+    pub fn vote_transaction_signature_count(&self) -> u64 {
+        self.vote_transaction_signature_count.0
+    }
+
+    pub fn transaction_signature_count(&self) -> u64 {
+        self.transaction_signature_count.0
+    }
+
     pub fn report_stats(&self, bank_slot: solana_clock::Slot, is_leader: bool) {
         // skip reporting if block is empty
         if self.transaction_count.0 == 0 {
@@ -231,6 +251,16 @@ impl CostTracker {
             ("block_cost", self.block_cost as i64, i64),
             ("vote_cost", self.vote_cost as i64, i64),
             ("transaction_count", self.transaction_count.0 as i64, i64),
+            (
+                "vote_transaction_count",
+                self.vote_transaction_count.0 as i64,
+                i64
+            ),
+            (
+                "vote_transaction_signature_count",
+                self.vote_transaction_signature_count.0 as i64,
+                i64
+            ),
             ("number_of_accounts", self.number_of_accounts() as i64, i64),
             ("costliest_account", costliest_account.to_string(), String),
             ("costliest_account_cost", costliest_account_cost as i64, i64),
@@ -327,6 +357,10 @@ impl CostTracker {
         self.allocated_accounts_data_size += tx_cost.allocated_accounts_data_size();
         self.transaction_count += 1;
         self.transaction_signature_count += tx_cost.num_transaction_signatures();
+        if tx_cost.is_simple_vote() {
+            self.vote_transaction_count += 1;
+            self.vote_transaction_signature_count += tx_cost.num_transaction_signatures();
+        }
         self.secp256k1_instruction_signature_count +=
             tx_cost.num_secp256k1_instruction_signatures();
         self.ed25519_instruction_signature_count += tx_cost.num_ed25519_instruction_signatures();
@@ -341,6 +375,10 @@ impl CostTracker {
         self.allocated_accounts_data_size -= tx_cost.allocated_accounts_data_size();
         self.transaction_count -= 1;
         self.transaction_signature_count -= tx_cost.num_transaction_signatures();
+        if tx_cost.is_simple_vote() {
+            self.vote_transaction_count -= 1;
+            self.vote_transaction_signature_count -= tx_cost.num_transaction_signatures();
+        }
         self.secp256k1_instruction_signature_count -=
             tx_cost.num_secp256k1_instruction_signatures();
         self.ed25519_instruction_signature_count -= tx_cost.num_ed25519_instruction_signatures();
@@ -503,8 +541,18 @@ mod tests {
         testee.add_transaction_cost(&tx_cost);
         assert_eq!(cost, testee.block_cost);
         assert_eq!(cost, testee.vote_cost);
+        assert_eq!(1, testee.vote_transaction_count());
+        assert_eq!(
+            tx_cost.num_transaction_signatures(),
+            testee.vote_transaction_signature_count()
+        );
         let (_costliest_account, costliest_account_cost) = testee.find_costliest_account();
         assert_eq!(cost, costliest_account_cost);
+
+        testee.remove(&tx_cost);
+        assert_eq!(0, testee.vote_cost);
+        assert_eq!(0, testee.vote_transaction_count());
+        assert_eq!(0, testee.vote_transaction_signature_count());
     }
 
     #[test]
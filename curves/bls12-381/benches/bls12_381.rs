@@ -0,0 +1,72 @@
+//! Benchmarks of the BLS12-381 syscall operations, from which their compute unit costs are
+//! derived at 33ns per compute unit, taking the median over several runs:
+//!
+//! * `bls12_381_g{1,2}_aggregate_incremental_cost`: `(aggregate/g{1,2}/32 - aggregate/g{1,2}/1)
+//!   / 31`
+//! * `bls12_381_g{1,2}_aggregate_base_cost`: `aggregate/g{1,2}/1` minus the incremental cost, or
+//!   `syscall_base_cost` when that difference is within the noise of the runs
+//! * `bls12_381_hash_to_g2_cost`: `hash_to_g2/32`, the message bytes are charged separately at
+//!   `sha256_byte_cost`
+//! * `bls12_381_pairing_one_pair_cost_other`: `(pairing/8 - pairing/1) / 7`
+//! * `bls12_381_pairing_one_pair_cost_first`: `pairing/1`
+
+use {
+    criterion::{criterion_group, criterion_main, BenchmarkId, Criterion},
+    tachyon_bls12_381::prelude::*,
+};
+
+fn hash_to_g2_input(message: &[u8]) -> Vec<u8> {
+    [
+        &[BLS_SIGNATURE_DST_POP.len() as u8][..],
+        BLS_SIGNATURE_DST_POP,
+        message,
+    ]
+    .concat()
+}
+
+fn g2_point(index: u64) -> Vec<u8> {
+    bls12_381_hash_to_g2(&hash_to_g2_input(&index.to_le_bytes())).unwrap()
+}
+
+fn bench_aggregate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("aggregate");
+    for num_points in [1, 2, 8, 32] {
+        let g1_input = BLS12_381_G1_NEG_GENERATOR.repeat(num_points);
+        group.bench_with_input(BenchmarkId::new("g1", num_points), &g1_input, |b, input| {
+            b.iter(|| bls12_381_g1_aggregate(input).unwrap())
+        });
+        let g2_input: Vec<u8> = (0..num_points as u64).flat_map(g2_point).collect();
+        group.bench_with_input(BenchmarkId::new("g2", num_points), &g2_input, |b, input| {
+            b.iter(|| bls12_381_g2_aggregate(input).unwrap())
+        });
+    }
+}
+
+fn bench_hash_to_g2(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash_to_g2");
+    for message_len in [32, 256, 1024] {
+        let input = hash_to_g2_input(&vec![7; message_len]);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(message_len),
+            &input,
+            |b, input| b.iter(|| bls12_381_hash_to_g2(input).unwrap()),
+        );
+    }
+}
+
+fn bench_pairing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pairing");
+    for num_pairs in [1, 2, 4, 8] {
+        let input: Vec<u8> = (0..num_pairs)
+            .flat_map(|index| [&BLS12_381_G1_NEG_GENERATOR[..], &g2_point(index)[..]].concat())
+            .collect();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(num_pairs),
+            &input,
+            |b, input| b.iter(|| bls12_381_pairing(input).unwrap()),
+        );
+    }
+}
+
+criterion_group!(benches, bench_aggregate, bench_hash_to_g2, bench_pairing);
+criterion_main!(benches);
@@ -0,0 +1,487 @@
+#![allow(clippy::arithmetic_side_effects)]
+//! Syscall operations for BLS12-381, for verifying BLS signatures such as those of Ethereum
+//! validators or of rollup and bridge committees.
+//!
+//! Points are in the compressed encoding of the zcash BLS12-381 serialization, which is the
+//! encoding of public keys (G1) and signatures (G2) used by the IETF BLS signature draft and
+//! Ethereum. Decoding a point checks that it is on the curve and in the prime order subgroup.
+//!
+//! The group operations are:
+//!
+//! * [`BLS12_381_G1_AGGREGATE`]: the sum of one or more G1 points, for aggregating public keys
+//! * [`BLS12_381_G2_AGGREGATE`]: the sum of one or more G2 points, for aggregating signatures
+//! * [`BLS12_381_HASH_TO_G2`]: the hash of a message to G2, using `hash_to_curve` with the
+//!   `BLS12381G2_XMD:SHA-256_SSWU_RO_` suite. The input is the length of the domain separation
+//!   tag as one byte, the tag, then the message.
+//! * [`BLS12_381_PAIRING`]: whether the product of the pairings of one or more (G1, G2) pairs
+//!   is the identity, as one byte
+//!
+//! [`verify`], [`fast_aggregate_verify`] and [`aggregate_verify`] build the signature schemes
+//! of the minimal public key size variant on top of them.
+
+pub mod prelude {
+    pub use crate::{consts::*, target_arch::*, Bls12381Error};
+}
+
+pub use prelude::*;
+use thiserror::Error;
+
+mod consts {
+    /// Size of a compressed G1 point
+    pub const BLS12_381_G1_POINT_LEN: usize = 48;
+    /// Size of a compressed G2 point
+    pub const BLS12_381_G2_POINT_LEN: usize = 96;
+    /// Size of a (G1, G2) pair of the pairing input
+    pub const BLS12_381_PAIRING_ELEMENT_LEN: usize =
+        BLS12_381_G1_POINT_LEN + BLS12_381_G2_POINT_LEN;
+    pub const BLS12_381_PAIRING_OUTPUT_LEN: usize = 1;
+
+    pub const BLS12_381_G1_AGGREGATE: u64 = 0;
+    pub const BLS12_381_G2_AGGREGATE: u64 = 1;
+    pub const BLS12_381_HASH_TO_G2: u64 = 2;
+    pub const BLS12_381_PAIRING: u64 = 3;
+
+    /// Domain separation tag of the proof of possession scheme of the IETF BLS signature draft,
+    /// which Ethereum uses
+    pub const BLS_SIGNATURE_DST_POP: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+    /// The compressed G1 point at infinity
+    pub const BLS12_381_G1_IDENTITY: [u8; BLS12_381_G1_POINT_LEN] = {
+        let mut point = [0; BLS12_381_G1_POINT_LEN];
+        point[0] = 0xc0;
+        point
+    };
+
+    /// The negation of the compressed G1 generator
+    pub const BLS12_381_G1_NEG_GENERATOR: [u8; BLS12_381_G1_POINT_LEN] = [
+        0xb7, 0xf1, 0xd3, 0xa7, 0x31, 0x97, 0xd7, 0x94, 0x26, 0x95, 0x63, 0x8c, 0x4f, 0xa9, 0xac,
+        0x0f, 0xc3, 0x68, 0x8c, 0x4f, 0x97, 0x74, 0xb9, 0x05, 0xa1, 0x4e, 0x3a, 0x3f, 0x17, 0x1b,
+        0xac, 0x58, 0x6c, 0x55, 0xe8, 0x3f, 0xf9, 0x7a, 0x1a, 0xef, 0xfb, 0x3a, 0xf0, 0x0a, 0xdb,
+        0x22, 0xc6, 0xbb,
+    ];
+}
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum Bls12381Error {
+    #[error("invalid input length")]
+    InvalidInputLength,
+    #[error("invalid point")]
+    InvalidPoint,
+    #[error("invalid domain separation tag")]
+    InvalidDomainSeparationTag,
+    #[error("the point at infinity is not a valid public key")]
+    IdentityPublicKey,
+    #[error("unexpected error")]
+    UnexpectedError,
+}
+
+fn pairing_input<'a>(pairs: impl IntoIterator<Item = (&'a [u8], &'a [u8])>) -> Vec<u8> {
+    pairs
+        .into_iter()
+        .flat_map(|(g1, g2)| g1.iter().chain(g2))
+        .copied()
+        .collect()
+}
+
+fn hash_to_g2_input(message: &[u8], dst: &[u8]) -> Result<Vec<u8>, Bls12381Error> {
+    let dst_len = u8::try_from(dst.len())
+        .ok()
+        .filter(|len| *len > 0)
+        .ok_or(Bls12381Error::InvalidDomainSeparationTag)?;
+    Ok([&[dst_len][..], dst, message].concat())
+}
+
+fn check_public_key(public_key: &[u8; BLS12_381_G1_POINT_LEN]) -> Result<(), Bls12381Error> {
+    if public_key == &BLS12_381_G1_IDENTITY {
+        return Err(Bls12381Error::IdentityPublicKey);
+    }
+    Ok(())
+}
+
+/// Returns whether `signature` is a signature of `message` by `public_key`
+pub fn verify(
+    public_key: &[u8; BLS12_381_G1_POINT_LEN],
+    message: &[u8],
+    dst: &[u8],
+    signature: &[u8; BLS12_381_G2_POINT_LEN],
+) -> Result<bool, Bls12381Error> {
+    fast_aggregate_verify(&[*public_key], message, dst, signature)
+}
+
+/// Returns whether `signature` is the aggregate of signatures of the same `message` by all of
+/// `public_keys`. The public keys must come with proofs of possession, or the signature can be
+/// forged by a rogue key.
+pub fn fast_aggregate_verify(
+    public_keys: &[[u8; BLS12_381_G1_POINT_LEN]],
+    message: &[u8],
+    dst: &[u8],
+    signature: &[u8; BLS12_381_G2_POINT_LEN],
+) -> Result<bool, Bls12381Error> {
+    if public_keys.is_empty() {
+        return Err(Bls12381Error::InvalidInputLength);
+    }
+    for public_key in public_keys {
+        check_public_key(public_key)?;
+    }
+    let aggregate_public_key = bls12_381_g1_aggregate(public_keys.as_flattened())?;
+    let message_point = bls12_381_hash_to_g2(&hash_to_g2_input(message, dst)?)?;
+    let result = bls12_381_pairing(&pairing_input([
+        (&aggregate_public_key[..], &message_point[..]),
+        (&BLS12_381_G1_NEG_GENERATOR[..], &signature[..]),
+    ]))?;
+    Ok(result == [1])
+}
+
+/// Returns whether `signature` is the aggregate of signatures of each of `messages` by the
+/// public key at the same index of `public_keys`
+pub fn aggregate_verify(
+    public_keys: &[[u8; BLS12_381_G1_POINT_LEN]],
+    messages: &[&[u8]],
+    dst: &[u8],
+    signature: &[u8; BLS12_381_G2_POINT_LEN],
+) -> Result<bool, Bls12381Error> {
+    if public_keys.is_empty() || public_keys.len() != messages.len() {
+        return Err(Bls12381Error::InvalidInputLength);
+    }
+    let message_points = messages
+        .iter()
+        .map(|message| bls12_381_hash_to_g2(&hash_to_g2_input(message, dst)?))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut pairs = Vec::with_capacity(public_keys.len().saturating_add(1));
+    for (public_key, message_point) in public_keys.iter().zip(&message_points) {
+        check_public_key(public_key)?;
+        pairs.push((&public_key[..], &message_point[..]));
+    }
+    pairs.push((&BLS12_381_G1_NEG_GENERATOR[..], &signature[..]));
+    Ok(bls12_381_pairing(&pairing_input(pairs))? == [1])
+}
+
+#[cfg(not(target_os = "solana"))]
+mod target_arch {
+    use {
+        super::*,
+        ark_bls12_381::{g2, Bls12_381, G1Affine, G1Projective, G2Affine, G2Projective},
+        ark_ec::{
+            hashing::{
+                curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve,
+            },
+            pairing::Pairing,
+            CurveGroup,
+        },
+        ark_ff::{field_hashers::DefaultFieldHasher, One, Zero},
+        ark_serialize::{CanonicalDeserialize, CanonicalSerialize},
+        sha2::Sha256,
+    };
+
+    fn serialize(point: &impl CanonicalSerialize) -> Result<Vec<u8>, Bls12381Error> {
+        let mut bytes = Vec::with_capacity(point.compressed_size());
+        point
+            .serialize_compressed(&mut bytes)
+            .map_err(|_| Bls12381Error::UnexpectedError)?;
+        Ok(bytes)
+    }
+
+    fn deserialize<T: CanonicalDeserialize + CanonicalSerialize>(
+        bytes: &[u8],
+    ) -> Result<T, Bls12381Error> {
+        let point = T::deserialize_compressed(bytes).map_err(|_| Bls12381Error::InvalidPoint)?;
+        // arkworks ignores the remaining bits when the infinity flag is set, so reject every
+        // encoding other than the canonical one
+        if serialize(&point)? != bytes {
+            return Err(Bls12381Error::InvalidPoint);
+        }
+        Ok(point)
+    }
+
+    fn points(
+        input: &[u8],
+        point_len: usize,
+    ) -> Result<std::slice::ChunksExact<'_, u8>, Bls12381Error> {
+        if input.is_empty() || input.len() % point_len != 0 {
+            return Err(Bls12381Error::InvalidInputLength);
+        }
+        Ok(input.chunks_exact(point_len))
+    }
+
+    pub fn bls12_381_g1_aggregate(input: &[u8]) -> Result<Vec<u8>, Bls12381Error> {
+        let sum = points(input, BLS12_381_G1_POINT_LEN)?
+            .try_fold(G1Projective::zero(), |sum, point| {
+                Ok::<_, Bls12381Error>(sum + deserialize::<G1Affine>(point)?)
+            })?;
+        serialize(&sum.into_affine())
+    }
+
+    pub fn bls12_381_g2_aggregate(input: &[u8]) -> Result<Vec<u8>, Bls12381Error> {
+        let sum = points(input, BLS12_381_G2_POINT_LEN)?
+            .try_fold(G2Projective::zero(), |sum, point| {
+                Ok::<_, Bls12381Error>(sum + deserialize::<G2Affine>(point)?)
+            })?;
+        serialize(&sum.into_affine())
+    }
+
+    pub fn bls12_381_hash_to_g2(input: &[u8]) -> Result<Vec<u8>, Bls12381Error> {
+        let (&dst_len, rest) = input
+            .split_first()
+            .ok_or(Bls12381Error::InvalidInputLength)?;
+        if dst_len == 0 {
+            return Err(Bls12381Error::InvalidDomainSeparationTag);
+        }
+        let (dst, message) = rest
+            .split_at_checked(usize::from(dst_len))
+            .ok_or(Bls12381Error::InvalidInputLength)?;
+        let hasher = MapToCurveBasedHasher::<
+            G2Projective,
+            DefaultFieldHasher<Sha256, 128>,
+            WBMap<g2::Config>,
+        >::new(dst)
+        .map_err(|_| Bls12381Error::InvalidDomainSeparationTag)?;
+        let point = hasher
+            .hash(message)
+            .map_err(|_| Bls12381Error::UnexpectedError)?;
+        serialize(&point)
+    }
+
+    pub fn bls12_381_pairing(input: &[u8]) -> Result<Vec<u8>, Bls12381Error> {
+        let (g1_points, g2_points): (Vec<_>, Vec<_>) =
+            points(input, BLS12_381_PAIRING_ELEMENT_LEN)?
+                .map(|pair| {
+                    let (g1, g2) = pair.split_at(BLS12_381_G1_POINT_LEN);
+                    Ok((deserialize::<G1Affine>(g1)?, deserialize::<G2Affine>(g2)?))
+                })
+                .collect::<Result<Vec<_>, Bls12381Error>>()?
+                .into_iter()
+                .unzip();
+        let result = Bls12_381::multi_pairing(g1_points, g2_points);
+        Ok(vec![u8::from(result.0.is_one())])
+    }
+}
+
+#[cfg(target_os = "solana")]
+mod target_arch {
+    use super::*;
+
+    solana_define_syscall::define_syscall!(fn sol_bls12_381_group_op(group_op: u64, input: *const u8, input_size: u64, result: *mut u8) -> u64);
+
+    fn group_op(op: u64, input: &[u8], output_len: usize) -> Result<Vec<u8>, Bls12381Error> {
+        let mut result = vec![0u8; output_len];
+        let ret = unsafe {
+            sol_bls12_381_group_op(op, input.as_ptr(), input.len() as u64, result.as_mut_ptr())
+        };
+        match ret {
+            0 => Ok(result),
+            _ => Err(Bls12381Error::UnexpectedError),
+        }
+    }
+
+    pub fn bls12_381_g1_aggregate(input: &[u8]) -> Result<Vec<u8>, Bls12381Error> {
+        group_op(BLS12_381_G1_AGGREGATE, input, BLS12_381_G1_POINT_LEN)
+    }
+
+    pub fn bls12_381_g2_aggregate(input: &[u8]) -> Result<Vec<u8>, Bls12381Error> {
+        group_op(BLS12_381_G2_AGGREGATE, input, BLS12_381_G2_POINT_LEN)
+    }
+
+    pub fn bls12_381_hash_to_g2(input: &[u8]) -> Result<Vec<u8>, Bls12381Error> {
+        group_op(BLS12_381_HASH_TO_G2, input, BLS12_381_G2_POINT_LEN)
+    }
+
+    pub fn bls12_381_pairing(input: &[u8]) -> Result<Vec<u8>, Bls12381Error> {
+        group_op(BLS12_381_PAIRING, input, BLS12_381_PAIRING_OUTPUT_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        ark_bls12_381::{Fr, G1Affine, G1Projective, G2Affine, G2Projective},
+        ark_ec::{AffineRepr, CurveGroup, Group},
+        ark_serialize::{CanonicalDeserialize, CanonicalSerialize},
+        std::ops::Neg,
+    };
+
+    fn compress(point: impl CanonicalSerialize) -> Vec<u8> {
+        let mut bytes = vec![];
+        point.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn keypair(seed: u64) -> (Fr, [u8; BLS12_381_G1_POINT_LEN]) {
+        let secret_key = Fr::from(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let public_key = (G1Projective::generator() * secret_key).into_affine();
+        (secret_key, compress(public_key).try_into().unwrap())
+    }
+
+    fn hash(message: &[u8], dst: &[u8]) -> G2Affine {
+        let point = bls12_381_hash_to_g2(&hash_to_g2_input(message, dst).unwrap()).unwrap();
+        G2Affine::deserialize_compressed(&point[..]).unwrap()
+    }
+
+    fn sign(secret_key: &Fr, message: &[u8]) -> G2Projective {
+        hash(message, BLS_SIGNATURE_DST_POP) * secret_key
+    }
+
+    fn aggregate(signatures: &[G2Projective]) -> [u8; BLS12_381_G2_POINT_LEN] {
+        let compressed: Vec<u8> = signatures
+            .iter()
+            .flat_map(|signature| compress(signature.into_affine()))
+            .collect();
+        bls12_381_g2_aggregate(&compressed)
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(
+            compress(G1Affine::generator().neg()),
+            BLS12_381_G1_NEG_GENERATOR
+        );
+        assert_eq!(compress(G1Affine::zero()), BLS12_381_G1_IDENTITY);
+    }
+
+    #[test]
+    fn test_hash_to_g2() {
+        let point = hash(b"message", BLS_SIGNATURE_DST_POP);
+        assert!(point.is_on_curve());
+        assert!(point.is_in_correct_subgroup_assuming_on_curve());
+        assert_eq!(point, hash(b"message", BLS_SIGNATURE_DST_POP));
+        assert_ne!(point, hash(b"other message", BLS_SIGNATURE_DST_POP));
+        assert_ne!(point, hash(b"message", b"OTHER_DST"));
+
+        assert_eq!(
+            bls12_381_hash_to_g2(&[0]),
+            Err(Bls12381Error::InvalidDomainSeparationTag)
+        );
+        assert_eq!(
+            bls12_381_hash_to_g2(&[4, 1, 2]),
+            Err(Bls12381Error::InvalidInputLength)
+        );
+        assert_eq!(
+            hash_to_g2_input(b"message", &[0; 256]),
+            Err(Bls12381Error::InvalidDomainSeparationTag)
+        );
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let p = (G1Projective::generator() * Fr::from(3u64)).into_affine();
+        let q = (G1Projective::generator() * Fr::from(5u64)).into_affine();
+        let input = [compress(p), compress(q)].concat();
+        assert_eq!(
+            bls12_381_g1_aggregate(&input).unwrap(),
+            compress((G1Projective::generator() * Fr::from(8u64)).into_affine())
+        );
+        let p = (G2Projective::generator() * Fr::from(3u64)).into_affine();
+        let q = (G2Projective::generator() * Fr::from(5u64)).into_affine();
+        let input = [compress(p), compress(q)].concat();
+        assert_eq!(
+            bls12_381_g2_aggregate(&input).unwrap(),
+            compress((G2Projective::generator() * Fr::from(8u64)).into_affine())
+        );
+
+        assert_eq!(
+            bls12_381_g1_aggregate(&[]),
+            Err(Bls12381Error::InvalidInputLength)
+        );
+        assert_eq!(
+            bls12_381_g2_aggregate(&input[..BLS12_381_G2_POINT_LEN + 1]),
+            Err(Bls12381Error::InvalidInputLength)
+        );
+        assert_eq!(
+            bls12_381_g1_aggregate(&[0xff; BLS12_381_G1_POINT_LEN]),
+            Err(Bls12381Error::InvalidPoint)
+        );
+    }
+
+    #[test]
+    fn test_pairing() {
+        let a = Fr::from(7u64);
+        // e(a * g1, g2) * e(-g1, a * g2) == 1
+        let input = [
+            compress((G1Projective::generator() * a).into_affine()),
+            compress(G2Affine::generator()),
+            compress(G1Affine::generator().neg()),
+            compress((G2Projective::generator() * a).into_affine()),
+        ]
+        .concat();
+        assert_eq!(bls12_381_pairing(&input).unwrap(), [1]);
+        assert_eq!(
+            bls12_381_pairing(&input[..BLS12_381_PAIRING_ELEMENT_LEN]).unwrap(),
+            [0]
+        );
+        assert_eq!(
+            bls12_381_pairing(&input[1..]),
+            Err(Bls12381Error::InvalidInputLength)
+        );
+        assert_eq!(
+            bls12_381_pairing(&[]),
+            Err(Bls12381Error::InvalidInputLength)
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let message = b"checkpoint";
+        let keypairs: Vec<_> = (1..=4).map(keypair).collect();
+        let public_keys: Vec<_> = keypairs.iter().map(|(_, public_key)| *public_key).collect();
+        let signatures: Vec<_> = keypairs
+            .iter()
+            .map(|(secret_key, _)| sign(secret_key, message))
+            .collect();
+
+        let signature = aggregate(&signatures[..1]);
+        assert!(verify(&public_keys[0], message, BLS_SIGNATURE_DST_POP, &signature).unwrap());
+        assert!(!verify(&public_keys[1], message, BLS_SIGNATURE_DST_POP, &signature).unwrap());
+        assert!(!verify(&public_keys[0], b"other", BLS_SIGNATURE_DST_POP, &signature).unwrap());
+        assert!(!verify(&public_keys[0], message, b"OTHER_DST", &signature).unwrap());
+
+        let signature = aggregate(&signatures);
+        assert!(
+            fast_aggregate_verify(&public_keys, message, BLS_SIGNATURE_DST_POP, &signature)
+                .unwrap()
+        );
+        assert!(!fast_aggregate_verify(
+            &public_keys[1..],
+            message,
+            BLS_SIGNATURE_DST_POP,
+            &signature
+        )
+        .unwrap());
+        assert_eq!(
+            fast_aggregate_verify(
+                &[BLS12_381_G1_IDENTITY],
+                message,
+                BLS_SIGNATURE_DST_POP,
+                &signature
+            ),
+            Err(Bls12381Error::IdentityPublicKey)
+        );
+
+        let messages: [&[u8]; 4] = [b"a", b"b", b"c", b"d"];
+        let signatures: Vec<_> = keypairs
+            .iter()
+            .zip(messages)
+            .map(|((secret_key, _), message)| sign(secret_key, message))
+            .collect();
+        let signature = aggregate(&signatures);
+        assert!(
+            aggregate_verify(&public_keys, &messages, BLS_SIGNATURE_DST_POP, &signature).unwrap()
+        );
+        let mut swapped = messages;
+        swapped.swap(0, 1);
+        assert!(
+            !aggregate_verify(&public_keys, &swapped, BLS_SIGNATURE_DST_POP, &signature).unwrap()
+        );
+        assert_eq!(
+            aggregate_verify(
+                &public_keys,
+                &messages[..3],
+                BLS_SIGNATURE_DST_POP,
+                &signature
+            ),
+            Err(Bls12381Error::InvalidInputLength)
+        );
+    }
+}
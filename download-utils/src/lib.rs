@@ -1,3 +1,5 @@
+pub mod snapshot_url;
+
 pub use solana_file_download::DownloadProgressRecord;
 use {
     log::*,
@@ -0,0 +1,340 @@
+//! Downloads of snapshot archives from mirrors run by node operators.
+//!
+//! A snapshot URL is an HTTPS (or S3) URL whose last path segment is the file name of a full or
+//! incremental snapshot archive, optionally followed by `#sha256=<HEX>` to pin the SHA-256 of
+//! the archive. Interrupted downloads resume where they stopped with a range request, and the
+//! archive is only moved into the snapshot archives directory once its hash has been verified.
+
+use {
+    log::*,
+    sha2::{Digest, Sha256},
+    std::{
+        fmt,
+        fs::{self, File, OpenOptions},
+        io::{Read, Write},
+        path::{Path, PathBuf},
+        str::FromStr,
+        thread::sleep,
+        time::{Duration, Instant},
+    },
+    url::Url,
+};
+
+const SHA256_PIN_PREFIX: &str = "sha256=";
+const DOWNLOAD_BUFFER_SIZE: usize = 1024 * 1024;
+const PROGRESS_LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotUrl {
+    /// HTTP(S) URL of the archive, `s3://` URLs being mapped to their public endpoint
+    pub url: Url,
+    /// SHA-256 the downloaded archive must hash to
+    pub expected_sha256: Option<[u8; 32]>,
+}
+
+impl SnapshotUrl {
+    /// Returns the file name of the archive, the last segment of the path of its URL
+    pub fn archive_file_name(&self) -> Option<&str> {
+        self.url
+            .path_segments()?
+            .next_back()
+            .filter(|name| !name.is_empty())
+    }
+}
+
+impl FromStr for SnapshotUrl {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (url, pin) = match s.split_once('#') {
+            Some((url, pin)) => (url, Some(pin)),
+            None => (s, None),
+        };
+        let expected_sha256 = pin
+            .map(|pin| {
+                let hex = pin
+                    .strip_prefix(SHA256_PIN_PREFIX)
+                    .ok_or_else(|| format!("unsupported integrity pin '{pin}'"))?;
+                parse_sha256(hex).ok_or_else(|| format!("invalid SHA-256 '{hex}'"))
+            })
+            .transpose()?;
+
+        let mut url = Url::parse(url).map_err(|err| format!("invalid URL '{url}': {err}"))?;
+        match url.scheme() {
+            "https" | "http" => {}
+            "s3" => {
+                let bucket = url
+                    .host_str()
+                    .ok_or_else(|| format!("missing bucket in '{url}'"))?;
+                let https_url = format!("https://{bucket}.s3.amazonaws.com{}", url.path());
+                url = Url::parse(&https_url)
+                    .map_err(|err| format!("invalid S3 URL '{https_url}': {err}"))?;
+            }
+            scheme => return Err(format!("unsupported URL scheme '{scheme}'")),
+        }
+        let snapshot_url = Self {
+            url,
+            expected_sha256,
+        };
+        if snapshot_url.archive_file_name().is_none() {
+            return Err(format!("'{}' does not name an archive", snapshot_url.url));
+        }
+        Ok(snapshot_url)
+    }
+}
+
+impl fmt::Display for SnapshotUrl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+fn parse_sha256(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut sha256 = [0; 32];
+    for (byte, digits) in sha256.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()?;
+    }
+    Some(sha256)
+}
+
+/// Downloads the archive of `snapshot_url` into `destination_dir`, returning its path.
+///
+/// The archive is first written to a temporary file, which a later call resumes from if the
+/// download is interrupted. `max_download_speed` caps the download speed, in bytes/second.
+pub fn download_snapshot_archive_from_url(
+    snapshot_url: &SnapshotUrl,
+    destination_dir: &Path,
+    max_download_speed: Option<u64>,
+) -> Result<PathBuf, String> {
+    let file_name = snapshot_url.archive_file_name().unwrap();
+    let destination_path = destination_dir.join(file_name);
+    if destination_path.is_file() {
+        if let Some(expected_sha256) = &snapshot_url.expected_sha256 {
+            verify_sha256(&destination_path, expected_sha256)?;
+        }
+        return Ok(destination_path);
+    }
+    fs::create_dir_all(destination_dir)
+        .map_err(|err| format!("Unable to create {}: {err}", destination_dir.display()))?;
+    let partial_path = destination_dir.join(format!("tmp-{file_name}.partial"));
+
+    download_with_resume(snapshot_url, &partial_path, max_download_speed)?;
+    if let Some(expected_sha256) = &snapshot_url.expected_sha256 {
+        if let Err(err) = verify_sha256(&partial_path, expected_sha256) {
+            // Start over next time rather than resuming a corrupt download
+            let _ignored = fs::remove_file(&partial_path);
+            return Err(err);
+        }
+    }
+    fs::rename(&partial_path, &destination_path).map_err(|err| {
+        format!(
+            "Unable to move {} to {}: {err}",
+            partial_path.display(),
+            destination_path.display()
+        )
+    })?;
+    Ok(destination_path)
+}
+
+fn download_with_resume(
+    snapshot_url: &SnapshotUrl,
+    partial_path: &Path,
+    max_download_speed: Option<u64>,
+) -> Result<(), String> {
+    let resume_from = fs::metadata(partial_path).map_or(0, |metadata| metadata.len());
+    let client = reqwest::blocking::Client::builder()
+        .connect_timeout(Duration::from_secs(30))
+        .timeout(None)
+        .build()
+        .map_err(|err| err.to_string())?;
+    let mut request = client.get(snapshot_url.url.clone());
+    if resume_from > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={resume_from}-"));
+    }
+    let mut response = request
+        .send()
+        .map_err(|err| format!("Unable to download {snapshot_url}: {err}"))?;
+
+    let (file, downloaded) = match response.status() {
+        reqwest::StatusCode::PARTIAL_CONTENT => {
+            info!("Resuming download of {snapshot_url} from byte {resume_from}");
+            let file = OpenOptions::new().append(true).open(partial_path);
+            (file, resume_from)
+        }
+        // The partial download is already complete
+        reqwest::StatusCode::RANGE_NOT_SATISFIABLE if resume_from > 0 => return Ok(()),
+        status if status.is_success() => (File::create(partial_path), 0),
+        status => return Err(format!("Unable to download {snapshot_url}: {status}")),
+    };
+    let mut file =
+        file.map_err(|err| format!("Unable to write {}: {err}", partial_path.display()))?;
+    let total = response
+        .content_length()
+        .map(|length| length.saturating_add(downloaded));
+
+    let start = Instant::now();
+    let mut last_progress_log = start;
+    let mut received = 0u64;
+    let mut buffer = vec![0; DOWNLOAD_BUFFER_SIZE];
+    loop {
+        let len = response
+            .read(&mut buffer)
+            .map_err(|err| format!("Unable to download {snapshot_url}: {err}"))?;
+        if len == 0 {
+            break;
+        }
+        file.write_all(&buffer[..len])
+            .map_err(|err| format!("Unable to write {}: {err}", partial_path.display()))?;
+        received = received.saturating_add(len as u64);
+
+        if let Some(max_download_speed) = max_download_speed.filter(|speed| *speed > 0) {
+            let expected_elapsed =
+                Duration::from_secs_f64(received as f64 / max_download_speed as f64);
+            if let Some(delay) = expected_elapsed.checked_sub(start.elapsed()) {
+                sleep(delay);
+            }
+        }
+        if last_progress_log.elapsed() >= PROGRESS_LOG_INTERVAL {
+            last_progress_log = Instant::now();
+            let speed = received as f64 / start.elapsed().as_secs_f64();
+            match total {
+                Some(total) => info!(
+                    "Downloading {snapshot_url}: {} of {total} bytes at {:.0} bytes/second",
+                    downloaded.saturating_add(received),
+                    speed
+                ),
+                None => info!(
+                    "Downloading {snapshot_url}: {} bytes at {:.0} bytes/second",
+                    downloaded.saturating_add(received),
+                    speed
+                ),
+            }
+        }
+    }
+    file.flush()
+        .map_err(|err| format!("Unable to write {}: {err}", partial_path.display()))?;
+    if let Some(total) = total {
+        if downloaded.saturating_add(received) < total {
+            return Err(format!("Download of {snapshot_url} ended early"));
+        }
+    }
+    Ok(())
+}
+
+fn verify_sha256(path: &Path, expected_sha256: &[u8; 32]) -> Result<(), String> {
+    let mut file =
+        File::open(path).map_err(|err| format!("Unable to read {}: {err}", path.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|err| format!("Unable to read {}: {err}", path.display()))?;
+    let sha256: [u8; 32] = hasher.finalize().into();
+    if &sha256 != expected_sha256 {
+        return Err(format!(
+            "SHA-256 mismatch for {}: expected {}, got {}",
+            path.display(),
+            to_hex(expected_sha256),
+            to_hex(&sha256)
+        ));
+    }
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Downloads the archive of the first of `snapshot_urls` that succeeds, trying the mirrors of
+/// an archive in order.
+pub fn download_snapshot_archive_from_mirrors<'a>(
+    snapshot_urls: impl IntoIterator<Item = &'a SnapshotUrl>,
+    destination_dir: &Path,
+    max_download_speed: Option<u64>,
+) -> Result<PathBuf, String> {
+    let mut errors = vec![];
+    for snapshot_url in snapshot_urls {
+        match download_snapshot_archive_from_url(snapshot_url, destination_dir, max_download_speed)
+        {
+            Ok(path) => return Ok(path),
+            Err(err) => {
+                warn!("{err}");
+                errors.push(err);
+            }
+        }
+    }
+    Err(format!(
+        "Failed to download the snapshot archive: {}",
+        errors.join("; ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn test_parse_snapshot_url() {
+        let snapshot_url: SnapshotUrl =
+            format!("https://mirror.example.com/x1/snapshot-100-abc.tar.zst#sha256={SHA256}")
+                .parse()
+                .unwrap();
+        assert_eq!(
+            snapshot_url.url.as_str(),
+            "https://mirror.example.com/x1/snapshot-100-abc.tar.zst"
+        );
+        assert_eq!(
+            snapshot_url.archive_file_name(),
+            Some("snapshot-100-abc.tar.zst")
+        );
+        assert_eq!(to_hex(&snapshot_url.expected_sha256.unwrap()), SHA256);
+
+        let snapshot_url: SnapshotUrl = "s3://bucket/x1/incremental-snapshot-100-200-abc.tar.zst"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            snapshot_url.url.as_str(),
+            "https://bucket.s3.amazonaws.com/x1/incremental-snapshot-100-200-abc.tar.zst"
+        );
+        assert_eq!(snapshot_url.expected_sha256, None);
+
+        // The query is not part of the file name
+        let snapshot_url: SnapshotUrl = "https://mirror.example.com/snapshot-100-abc.tar.zst?x=1"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            snapshot_url.archive_file_name(),
+            Some("snapshot-100-abc.tar.zst")
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_snapshot_url() {
+        for invalid in [
+            "ftp://mirror.example.com/snapshot-100-abc.tar.zst".to_string(),
+            "https://mirror.example.com/".to_string(),
+            "https://mirror.example.com/snapshot-100-abc.tar.zst#md5=00".to_string(),
+            "https://mirror.example.com/snapshot-100-abc.tar.zst#sha256=00".to_string(),
+            format!(
+                "https://mirror.example.com/snapshot-100-abc.tar.zst#sha256={}",
+                &SHA256[1..]
+            ),
+            "not a url".to_string(),
+        ] {
+            assert!(invalid.parse::<SnapshotUrl>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_verify_sha256() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("empty");
+        File::create(&path).unwrap();
+        let sha256 = parse_sha256(SHA256).unwrap();
+        assert!(verify_sha256(&path, &sha256).is_ok());
+        assert!(verify_sha256(&path, &[0; 32]).is_err());
+    }
+}
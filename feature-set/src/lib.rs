@@ -86,6 +86,13 @@ impl FeatureSet {
         hash_set
     }
 
+    /// Whether all of `ZK_PROOF_FEATURES` are active
+    pub fn zk_proof_features_enabled(&self) -> bool {
+        ZK_PROOF_FEATURES
+            .iter()
+            .all(|feature_id| self.is_active(feature_id))
+    }
+
     /// All features enabled, useful for testing
     pub fn all_enabled() -> Self {
         Self {
@@ -1017,6 +1024,70 @@ pub mod mask_out_rent_epoch_in_vm_serialization {
     solana_pubkey::declare_id!("PQ2UuqjikpyASB7hUQ14BJLmLpbiTpe7tMF5QgbihLp");
 }
 
+pub mod nonce_fees_ignore_stored_lamports_per_signature {
+    solana_pubkey::declare_id!("4FkqdB5VFevrSPDkmFiFWYkUZ5ZqmGBYV9jSNn1mcjHw");
+}
+
+pub mod governance_parameter_bundles {
+    solana_pubkey::declare_id!("Ht4mnbzcwk4JJQNVXaC55N2uFMDUZ74Pne3op54NKhsW");
+}
+
+pub mod storage_deposit {
+    solana_pubkey::declare_id!("5mUGsxq8DGxoqaiUS8QjajLA89uTpY4UPS34UEgJzurp");
+}
+
+pub mod enforce_min_compute_unit_price {
+    solana_pubkey::declare_id!("DdbJp7apmWB1GmztzaRjVTJgA6WpnW4LAVHGoXUHKVNu");
+}
+
+pub mod raise_tx_account_lock_limit {
+    solana_pubkey::declare_id!("G9PCDhrzh9A6uWKBmBL4z4C5BEXBhiTkBTQsQRW4T1vs");
+}
+
+pub mod transfer_many {
+    solana_pubkey::declare_id!("9sJaQn6trho5XhvzZng2vRTjKghc5AhuxN4yPAnaC7tc");
+}
+
+pub mod slot_seed_sysvar {
+    solana_pubkey::declare_id!("9DsfPrCdfCiJt4HSnUVZcX7xgPnsnuGnp2VuiEtS7T9U");
+}
+
+pub mod enable_bls12_381_syscall {
+    solana_pubkey::declare_id!("CeNmLeYDVuwtAxTfyx3rKC6xzGRiHHTpooAq8QWrsAHW");
+}
+
+pub mod fee_parameters_sysvar {
+    solana_pubkey::declare_id!("HtowPs2pfCmQ1V7pjYrJnmkVetA5TvPZ86vAYBSGXGdh");
+}
+
+pub mod raise_max_heap_frame_bytes {
+    solana_pubkey::declare_id!("BVsLkZ219CByStkZKY6QAN8PPVbACwKiCV56PbYYCHDr");
+}
+
+pub mod raise_max_instruction_stack_depth {
+    solana_pubkey::declare_id!("CvhpZigZAJannjQtpFuwpwd3h575zS7gHAPd4a3FG8H6");
+}
+
+pub mod enable_fee_payer_split {
+    solana_pubkey::declare_id!("5PeoggzcFu7h3DjuVZDJchgXVwnvrV59Cce6Sv3fV7a6");
+}
+
+pub mod charge_non_conforming_vote_transactions {
+    solana_pubkey::declare_id!("8cKcfENNvHhdYDazVCA5U92UyfKrCLocZWNdAbEMUk8y");
+}
+
+pub mod disable_rent_scanning {
+    solana_pubkey::declare_id!("28PQHkJSLPaUUxyKp7fSUJnMQ5PigzCYV9MDeHNwV6zn");
+}
+
+pub mod route_base_fees_to_treasury {
+    solana_pubkey::declare_id!("EnYVx4BzL9iCcWiEnQ9mfbAUfskik3Uskj8jg13U5S6f");
+}
+
+pub mod distinct_fee_errors {
+    solana_pubkey::declare_id!("B5TAKEjPzntShkUfuWF2xViPZWzkhJQkC9Df9k16WJ2J");
+}
+
 pub static FEATURE_NAMES: LazyLock<AHashMap<Pubkey, &'static str>> = LazyLock::new(|| {
     [
         (secp256k1_program_enabled::id(), "secp256k1 program"),
@@ -1247,6 +1318,22 @@ pub static FEATURE_NAMES: LazyLock<AHashMap<Pubkey, &'static str>> = LazyLock::n
         (disable_partitioned_rent_collection::id(), "SIMD-0175: Disable partitioned rent collection"),
         (raise_block_limits_to_60m::id(), "Raise block limit to 60M SIMD-0256"),
         (mask_out_rent_epoch_in_vm_serialization::id(), "SIMD-0267: Sets rent_epoch to a constant in the VM"),
+        (nonce_fees_ignore_stored_lamports_per_signature::id(), "X1: durable nonce transactions pay CU-derived fees regardless of the nonce's stored lamports_per_signature"),
+        (governance_parameter_bundles::id(), "X1: apply governance-staged cluster parameter bundles at their activation epoch"),
+        (storage_deposit::id(), "X1: require a refundable storage deposit when the system program allocates account data"),
+        (enforce_min_compute_unit_price::id(), "X1: enforce the minimum compute unit price for transactions deriving few compute units"),
+        (raise_tx_account_lock_limit::id(), "X1: raise the transaction account lock limit to 256"),
+        (transfer_many::id(), "X1: system program TransferMany instruction paying many recipients at once"),
+        (slot_seed_sysvar::id(), "X1: per-slot seed sysvar for on-chain randomness"),
+        (enable_bls12_381_syscall::id(), "X1: BLS12-381 aggregation, hash to curve and pairing syscall"),
+        (fee_parameters_sysvar::id(), "X1: fee parameters sysvar and syscall"),
+        (raise_max_heap_frame_bytes::id(), "X1: raise max heap frame to 1 MiB with a heap fee"),
+        (raise_max_instruction_stack_depth::id(), "X1: raise max instruction stack depth to 8"),
+        (enable_fee_payer_split::id(), "X1: split transaction fees with a co-payer"),
+        (charge_non_conforming_vote_transactions::id(), "X1: charge fees for vote transactions that are not simple votes"),
+        (disable_rent_scanning::id(), "X1: stop rent scanning and rent epoch rewrites"),
+        (route_base_fees_to_treasury::id(), "X1: credit a share of base fees to the fee treasury"),
+        (distinct_fee_errors::id(), "X1: report fee failures with distinct transaction errors"),
         /*************** ADD NEW FEATURES HERE ***************/
     ]
     .iter()
@@ -1265,6 +1352,14 @@ pub static ID: LazyLock<Hash> = LazyLock::new(|| {
     hasher.result()
 });
 
+/// Features enabling the zk proof builtins and the curve25519 syscalls, which confidential
+/// transfers of Token-2022 need. X1 activates them together.
+pub const ZK_PROOF_FEATURES: [Pubkey; 3] = [
+    zk_token_sdk_enabled::ID,
+    zk_elgamal_proof_program_enabled::ID,
+    curve25519_syscall_enabled::ID,
+];
+
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct FullInflationFeaturePair {
     pub vote_id: Pubkey, // Feature that grants the candidate the ability to enable full inflation
@@ -1341,4 +1436,17 @@ mod test {
                 .collect()
         );
     }
+
+    #[test]
+    fn test_zk_proof_features_enabled() {
+        let mut feature_set = FeatureSet::default();
+        assert!(!feature_set.zk_proof_features_enabled());
+        for feature_id in &ZK_PROOF_FEATURES[1..] {
+            feature_set.activate(feature_id, 0);
+        }
+        assert!(!feature_set.zk_proof_features_enabled());
+        feature_set.activate(&ZK_PROOF_FEATURES[0], 0);
+        assert!(feature_set.zk_proof_features_enabled());
+        assert!(FeatureSet::all_enabled().zk_proof_features_enabled());
+    }
 }
@@ -0,0 +1,73 @@
+//! The fee charged to a transaction, split into its components.
+//!
+//! [`FeeBreakdown`] is returned by the fee calculation and recorded in the transaction status
+//! metadata, so that consumers of RPC and geyser don't have to derive the components from the
+//! total fee as new components are added. Components not charged by the current fee model are
+//! zero.
+
+use {
+    serde_derive::{Deserialize, Serialize},
+    solana_fee_structure::FeeDetails,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeBreakdown {
+    /// Fee proportional to the compute units of the transaction
+    pub base: u64,
+    /// Fee from the compute unit price and limit set with the compute budget program
+    pub prioritization: u64,
+    /// Fee for the memory the transaction requests
+    pub memory: u64,
+    /// Fee for the size of the transaction
+    pub size: u64,
+    /// Fee from the compute unit price applied to the compute units of the transaction,
+    /// including the minimum price of transactions using few compute units
+    pub congestion: u64,
+}
+
+impl FeeBreakdown {
+    /// The fee of all components but the prioritization fee.
+    pub fn transaction_fee(&self) -> u64 {
+        self.base
+            .saturating_add(self.memory)
+            .saturating_add(self.size)
+            .saturating_add(self.congestion)
+    }
+
+    pub fn total_fee(&self) -> u64 {
+        self.transaction_fee().saturating_add(self.prioritization)
+    }
+}
+
+impl From<FeeBreakdown> for FeeDetails {
+    fn from(breakdown: FeeBreakdown) -> Self {
+        FeeDetails::new(breakdown.transaction_fee(), breakdown.prioritization)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_breakdown() {
+        let breakdown = FeeBreakdown {
+            base: 1_000,
+            prioritization: 300,
+            memory: 20,
+            size: 4,
+            congestion: 50,
+        };
+        assert_eq!(breakdown.transaction_fee(), 1_074);
+        assert_eq!(breakdown.total_fee(), 1_374);
+        let fee_details = FeeDetails::from(breakdown);
+        assert_eq!(fee_details.transaction_fee(), 1_074);
+        assert_eq!(fee_details.prioritization_fee(), 300);
+
+        assert_eq!(
+            serde_json::to_string(&breakdown).unwrap(),
+            r#"{"base":1000,"prioritization":300,"memory":20,"size":4,"congestion":50}"#
+        );
+    }
+}
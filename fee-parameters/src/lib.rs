@@ -0,0 +1,155 @@
+//! The fee parameters of the current slot, for on-chain programs.
+//!
+//! X1 charges a transaction a base fee of [`FeeParameters::base_fee_multiplier`] lamports per
+//! compute unit, which governance can change every epoch, and a congestion fee of its compute
+//! unit price per compute unit. Transactions deriving fewer than
+//! [`FeeParameters::min_compute_units_threshold`] compute units pay at least
+//! [`FeeParameters::min_compute_unit_price`] for the congestion fee. Paymasters and relayers
+//! paying the fees of their users need these to know what a transaction costs.
+//!
+//! With the `fee_parameters_sysvar` feature active, the bank records the parameters in the
+//! account at [`id`], which can be passed to a program like any sysvar, and programs can read
+//! them without the account with [`FeeParameters::get`].
+
+use {
+    serde_derive::{Deserialize, Serialize},
+    solana_account_info::AccountInfo,
+    solana_program_error::ProgramError,
+};
+
+solana_pubkey::declare_id!("FosDjkjEo9thdjdp4SXQCw6nMp43gbVmvR2heq128vDU");
+
+pub const MICROLAMPORTS_PER_LAMPORT: u64 = 1_000_000;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeParameters {
+    /// Lamports charged per compute unit of a transaction
+    pub base_fee_multiplier: u64,
+    /// Minimum compute unit price, in micro-lamports, of the congestion fee of transactions
+    /// deriving fewer than `min_compute_units_threshold` compute units
+    pub min_compute_unit_price: u64,
+    pub min_compute_units_threshold: u64,
+}
+
+impl FeeParameters {
+    /// Serialized size of the account data
+    pub const SIZE: usize = 24;
+
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        bincode::deserialize(data).ok()
+    }
+
+    /// Reads the parameters from the sysvar account passed to a program
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        if account_info.key != &id() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = account_info.try_borrow_data()?;
+        Self::from_account_data(&data).ok_or(ProgramError::InvalidAccountData)
+    }
+
+    /// Reads the parameters with the `sol_get_fee_parameters_sysvar` syscall
+    pub fn get() -> Result<Self, ProgramError> {
+        #[cfg(target_os = "solana")]
+        {
+            solana_define_syscall::define_syscall!(fn sol_get_fee_parameters_sysvar(addr: *mut u8) -> u64);
+
+            let mut fee_parameters = Self::default();
+            let result = unsafe {
+                sol_get_fee_parameters_sysvar(&mut fee_parameters as *mut Self as *mut u8)
+            };
+            match result {
+                0 => Ok(fee_parameters),
+                _ => Err(ProgramError::UnsupportedSysvar),
+            }
+        }
+        #[cfg(not(target_os = "solana"))]
+        Err(ProgramError::UnsupportedSysvar)
+    }
+
+    /// Returns the fee of a transaction deriving `compute_units` compute units at
+    /// `compute_unit_price` micro-lamports per compute unit, without its prioritization fee
+    pub fn transaction_fee(&self, compute_units: u64, compute_unit_price: u64) -> u64 {
+        let compute_unit_price = if compute_units < self.min_compute_units_threshold {
+            compute_unit_price.max(self.min_compute_unit_price)
+        } else {
+            compute_unit_price
+        };
+        let base_fee = compute_units.saturating_mul(self.base_fee_multiplier);
+        let congestion_fee =
+            compute_units.saturating_mul(compute_unit_price) / MICROLAMPORTS_PER_LAMPORT;
+        base_fee.saturating_add(congestion_fee)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_pubkey::Pubkey};
+
+    fn fee_parameters() -> FeeParameters {
+        FeeParameters {
+            base_fee_multiplier: 10,
+            min_compute_unit_price: 1_000_000,
+            min_compute_units_threshold: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_transaction_fee() {
+        let fee_parameters = fee_parameters();
+        // Small transactions pay at least the minimum price
+        assert_eq!(fee_parameters.transaction_fee(150, 0), 1_500 + 150);
+        assert_eq!(fee_parameters.transaction_fee(150, 2_000_000), 1_500 + 300);
+        assert_eq!(fee_parameters.transaction_fee(200_000, 0), 2_000_000);
+        assert_eq!(
+            fee_parameters.transaction_fee(200_000, 5_000),
+            2_000_000 + 1_000
+        );
+        assert_eq!(fee_parameters.transaction_fee(0, 0), 0);
+    }
+
+    #[test]
+    fn test_from_account_info() {
+        let fee_parameters = fee_parameters();
+        let mut data = bincode::serialize(&fee_parameters).unwrap();
+        assert_eq!(data.len(), FeeParameters::SIZE);
+        assert_eq!(FeeParameters::from_account_data(&data[..8]), None);
+
+        let mut lamports = 1;
+        let (key, owner) = (id(), Pubkey::new_unique());
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            FeeParameters::from_account_info(&account_info),
+            Ok(fee_parameters)
+        );
+
+        let mut data = bincode::serialize(&fee_parameters).unwrap();
+        let mut lamports = 1;
+        let key = Pubkey::new_unique();
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            FeeParameters::from_account_info(&account_info),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(FeeParameters::get(), Err(ProgramError::UnsupportedSysvar));
+    }
+}
@@ -0,0 +1,274 @@
+//! Stress test of the fee model with adversarial compute budget instructions.
+//!
+//! Generates transactions combining duplicate, conflicting, malformed and extreme compute
+//! budget instructions, and checks that the fee crate charges the compute unit price, the
+//! prioritization fee and the heap requested as parsed by `process_compute_budget_instructions`.
+//! Transactions on which they diverge are written to a corpus, `FEE_MODEL_CORPUS_DIR` or
+//! `fee-model-corpus` in the target directory, and replayed first by later runs.
+
+use {
+    agave_feature_set::FeatureSet,
+    criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput},
+    rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng},
+    solana_compute_budget::compute_budget_limits::MAX_HEAP_FRAME_BYTES,
+    solana_compute_budget_instruction::instructions_processor::process_compute_budget_instructions,
+    solana_fee::{
+        calculate_fee, calculate_fee_breakdown, calculate_fee_details, FeeFeatures,
+        BASE_FEE_MULTIPLIER, HEAP_FEE_LAMPORTS_PER_BYTE, MICROLAMPORTS_PER_LAMPORT,
+        MIN_COMPUTE_UNITS_THRESHOLD, MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+    },
+    solana_fee_structure::FeeBudgetLimits,
+    solana_sdk::{
+        compute_budget::{self, ComputeBudgetInstruction},
+        hash::hash,
+        instruction::Instruction,
+        message::{Message, SanitizedMessage},
+        pubkey::Pubkey,
+        reserved_account_keys::ReservedAccountKeys,
+        system_instruction,
+    },
+    solana_svm_transaction::svm_message::SVMMessage,
+    std::{fs, path::PathBuf},
+};
+
+const NUM_TRANSACTIONS: usize = 4_096;
+const MAX_INSTRUCTIONS: usize = 6;
+const SEED: u64 = 0x5eed_fee5;
+
+const FEE_FEATURES: FeeFeatures = FeeFeatures {
+    enable_secp256r1_precompile: true,
+    enforce_min_compute_unit_price: false,
+    raise_max_heap_frame_bytes: true,
+    charge_non_conforming_vote_transactions: true,
+    transfer_many: true,
+    base_fee_multiplier: BASE_FEE_MULTIPLIER,
+    min_compute_unit_price: MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+};
+
+fn corpus_dir() -> PathBuf {
+    std::env::var_os("FEE_MODEL_CORPUS_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("fee-model-corpus"))
+}
+
+fn pick<T: Copy>(rng: &mut StdRng, values: &[T]) -> T {
+    *values.choose(rng).unwrap()
+}
+
+fn adversarial_instruction(rng: &mut StdRng) -> Instruction {
+    match rng.gen_range(0..7) {
+        0 => {
+            let random = rng.gen();
+            ComputeBudgetInstruction::set_compute_unit_limit(pick(
+                rng,
+                &[0, 1, 150, 200_000, 1_400_000, 1_400_001, u32::MAX, random],
+            ))
+        }
+        1 => {
+            let random = rng.gen();
+            ComputeBudgetInstruction::set_compute_unit_price(pick(
+                rng,
+                &[
+                    0,
+                    1,
+                    MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS - 1,
+                    MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+                    u64::MAX / MICROLAMPORTS_PER_LAMPORT,
+                    u64::MAX,
+                    random,
+                ],
+            ))
+        }
+        2 => {
+            let random = rng.gen();
+            ComputeBudgetInstruction::request_heap_frame(pick(
+                rng,
+                &[
+                    0,
+                    1_024,
+                    32 * 1_024,
+                    32 * 1_024 + 1,
+                    MAX_HEAP_FRAME_BYTES,
+                    MAX_HEAP_FRAME_BYTES + 1_024,
+                    1_024 * 1_024,
+                    u32::MAX,
+                    random,
+                ],
+            ))
+        }
+        3 => {
+            let random = rng.gen();
+            ComputeBudgetInstruction::set_loaded_accounts_data_size_limit(pick(
+                rng,
+                &[0, 1, 64 * 1_024 * 1_024, u32::MAX, random],
+            ))
+        }
+        4 => {
+            // Malformed or truncated compute budget instruction data
+            let len = rng.gen_range(0..16);
+            Instruction::new_with_bytes(
+                compute_budget::id(),
+                &(0..len).map(|_| rng.gen()).collect::<Vec<u8>>(),
+                vec![],
+            )
+        }
+        5 => system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1),
+        _ => Instruction::new_with_bytes(Pubkey::new_unique(), &[0], vec![]),
+    }
+}
+
+fn adversarial_message(rng: &mut StdRng) -> Message {
+    let mut instructions: Vec<_> = (0..rng.gen_range(0..=MAX_INSTRUCTIONS))
+        .map(|_| adversarial_instruction(rng))
+        .collect();
+    // Duplicate an instruction, which compute budget processing must reject
+    if !instructions.is_empty() && rng.gen_bool(0.2) {
+        let duplicate = instructions.choose(rng).unwrap().clone();
+        instructions.push(duplicate);
+    }
+    Message::new(&instructions, Some(&Pubkey::new_unique()))
+}
+
+fn sanitize(message: Message) -> SanitizedMessage {
+    SanitizedMessage::try_from_legacy_message(message, &ReservedAccountKeys::empty_key_set())
+        .unwrap()
+}
+
+/// Returns how the fees of `message` diverge from its compute budget limits, if they do
+fn check_message(message: &SanitizedMessage) -> Option<String> {
+    let compute_budget_limits = process_compute_budget_instructions(
+        SVMMessage::program_instructions_iter(message),
+        &FeatureSet::all_enabled(),
+    );
+    let prioritization_fee = compute_budget_limits
+        .as_ref()
+        .map(|limits| FeeBudgetLimits::from(*limits).prioritization_fee)
+        .unwrap_or_default();
+    let breakdown = calculate_fee_breakdown(message, false, 0, prioritization_fee, FEE_FEATURES);
+
+    let total_fee = calculate_fee(message, false, 0, prioritization_fee, FEE_FEATURES);
+    let fee_details = calculate_fee_details(message, false, 0, prioritization_fee, FEE_FEATURES);
+    if total_fee != breakdown.total_fee() || fee_details.total_fee() != total_fee {
+        return Some(format!(
+            "total fee {total_fee} differs from the details {fee_details:?} or the breakdown \
+             {breakdown:?}"
+        ));
+    }
+
+    // Invalid compute budgets fail transactions before fees are charged
+    let Ok(compute_budget_limits) = compute_budget_limits else {
+        return None;
+    };
+    if breakdown.prioritization != prioritization_fee {
+        return Some(format!(
+            "prioritization fee {} differs from {prioritization_fee} for {compute_budget_limits:?}",
+            breakdown.prioritization
+        ));
+    }
+
+    let derived_compute_units = breakdown.base / BASE_FEE_MULTIPLIER;
+    let compute_unit_price = if derived_compute_units < MIN_COMPUTE_UNITS_THRESHOLD
+        && compute_budget_limits.compute_unit_price < MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS
+    {
+        MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS
+    } else {
+        compute_budget_limits.compute_unit_price
+    };
+    let congestion_fee =
+        derived_compute_units.saturating_mul(compute_unit_price) / MICROLAMPORTS_PER_LAMPORT;
+    if breakdown.congestion != congestion_fee {
+        return Some(format!(
+            "congestion fee {} differs from {congestion_fee} at {compute_unit_price} \
+             microlamports per compute unit for {compute_budget_limits:?}",
+            breakdown.congestion
+        ));
+    }
+
+    let memory_fee = u64::from(
+        compute_budget_limits
+            .updated_heap_bytes
+            .saturating_sub(MAX_HEAP_FRAME_BYTES),
+    )
+    .saturating_mul(HEAP_FEE_LAMPORTS_PER_BYTE);
+    if breakdown.memory != memory_fee {
+        return Some(format!(
+            "memory fee {} differs from {memory_fee} for {compute_budget_limits:?}",
+            breakdown.memory
+        ));
+    }
+    None
+}
+
+/// Writes `message` to the corpus, named by its hash
+fn add_to_corpus(message: &SanitizedMessage, divergence: &str) {
+    let dir = corpus_dir();
+    fs::create_dir_all(&dir).unwrap();
+    let bytes = message.legacy_message().unwrap().serialize();
+    let name = hash(&bytes).to_string();
+    fs::write(dir.join(format!("{name}.bin")), &bytes).unwrap();
+    fs::write(dir.join(format!("{name}.txt")), divergence).unwrap();
+}
+
+fn corpus_messages() -> Vec<SanitizedMessage> {
+    let Ok(entries) = fs::read_dir(corpus_dir()) else {
+        return vec![];
+    };
+    entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "bin" {
+                return None;
+            }
+            let message: Message = bincode::deserialize(&fs::read(path).ok()?).ok()?;
+            Some(sanitize(message))
+        })
+        .collect()
+}
+
+fn bench_fee_model_stress(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let messages: Vec<_> = corpus_messages()
+        .into_iter()
+        .chain((0..NUM_TRANSACTIONS).map(|_| sanitize(adversarial_message(&mut rng))))
+        .collect();
+
+    let divergences: Vec<_> = messages
+        .iter()
+        .filter_map(|message| {
+            let divergence = check_message(message)?;
+            add_to_corpus(message, &divergence);
+            Some(divergence)
+        })
+        .collect();
+    assert!(
+        divergences.is_empty(),
+        "{} of {} transactions diverge, written to {}: {divergences:#?}",
+        divergences.len(),
+        messages.len(),
+        corpus_dir().display(),
+    );
+
+    c.benchmark_group("bench_fee_model_stress")
+        .throughput(Throughput::Elements(messages.len() as u64))
+        .bench_function("adversarial compute budgets", |bencher| {
+            bencher.iter(|| {
+                for message in &messages {
+                    let compute_budget_limits = process_compute_budget_instructions(
+                        black_box(SVMMessage::program_instructions_iter(message)),
+                        black_box(&FeatureSet::all_enabled()),
+                    )
+                    .unwrap_or_default();
+                    black_box(calculate_fee_details(
+                        black_box(message),
+                        false,
+                        0,
+                        FeeBudgetLimits::from(compute_budget_limits).prioritization_fee,
+                        FEE_FEATURES,
+                    ));
+                }
+            });
+        });
+}
+
+criterion_group!(benches, bench_fee_model_stress);
+criterion_main!(benches);
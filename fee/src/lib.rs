@@ -1,18 +1,27 @@
+pub use tachyon_fee_breakdown::FeeBreakdown;
 use {
-    agave_feature_set::{enable_secp256r1_precompile, FeatureSet},
+    agave_feature_set::{
+        charge_non_conforming_vote_transactions, enable_secp256r1_precompile,
+        enforce_min_compute_unit_price, raise_max_heap_frame_bytes, transfer_many, FeatureSet,
+    },
     log::{debug, trace},
     solana_builtins_default_costs::get_builtin_instruction_cost,
+    solana_compute_budget::compute_budget_limits::{ComputeBudgetLimits, MAX_HEAP_FRAME_BYTES},
     solana_compute_budget_instruction::instructions_processor::process_compute_budget_instructions,
-    solana_fee_structure::FeeDetails,
+    solana_fee_structure::{FeeBudgetLimits, FeeDetails},
     solana_sdk::{
         borsh1::try_from_slice_unchecked,
         compute_budget::{check_id, ComputeBudgetInstruction},
     },
     solana_svm_transaction::svm_message::SVMMessage,
+    tachyon_system_interface::transfer_many::{
+        TransferMany, TRANSFER_MANY_COMPUTE_UNITS_PER_RECIPIENT,
+    },
 };
 
 /// Bools indicating the activation of features relevant
-/// to the fee calculation.
+/// to the fee calculation, along with the governance-set
+/// base fee multiplier and minimum compute unit price.
 // DEVELOPER NOTE:
 // This struct may become empty at some point. It is preferable to keep it
 // instead of removing, since fees will naturally be changed via feature-gates
@@ -20,6 +29,12 @@ use {
 #[derive(Copy, Clone)]
 pub struct FeeFeatures {
     pub enable_secp256r1_precompile: bool,
+    pub enforce_min_compute_unit_price: bool,
+    pub raise_max_heap_frame_bytes: bool,
+    pub charge_non_conforming_vote_transactions: bool,
+    pub transfer_many: bool,
+    pub base_fee_multiplier: u64,
+    pub min_compute_unit_price: u64,
 }
 
 pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
@@ -29,11 +44,22 @@ pub const MIN_COMPUTE_UNITS_THRESHOLD: u64 = 1_000;
 pub const MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS: u64 = 1_000_000;
 pub const BASE_FEE_MULTIPLIER: u64 = 10;
 pub const MICROLAMPORTS_PER_LAMPORT: u64 = 1_000_000;
+/// Lamports charged per byte of requested heap frame above `MAX_HEAP_FRAME_BYTES`, which
+/// transactions can only exceed with the `raise_max_heap_frame_bytes` feature active
+pub const HEAP_FEE_LAMPORTS_PER_BYTE: u64 = 1;
 
 impl From<&FeatureSet> for FeeFeatures {
     fn from(feature_set: &FeatureSet) -> Self {
         Self {
             enable_secp256r1_precompile: feature_set.is_active(&enable_secp256r1_precompile::ID),
+            enforce_min_compute_unit_price: feature_set
+                .is_active(&enforce_min_compute_unit_price::ID),
+            raise_max_heap_frame_bytes: feature_set.is_active(&raise_max_heap_frame_bytes::ID),
+            charge_non_conforming_vote_transactions: feature_set
+                .is_active(&charge_non_conforming_vote_transactions::ID),
+            transfer_many: feature_set.is_active(&transfer_many::ID),
+            base_fee_multiplier: BASE_FEE_MULTIPLIER,
+            min_compute_unit_price: MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
         }
     }
 }
@@ -59,21 +85,43 @@ pub fn calculate_fee(
 pub fn calculate_fee_details(
     message: &impl SVMMessage,
     zero_fees_for_test: bool,
-    _lamports_per_signature: u64,
+    lamports_per_signature: u64,
     prioritization_fee: u64,
-    _fee_features: FeeFeatures,
+    fee_features: FeeFeatures,
 ) -> FeeDetails {
+    calculate_fee_breakdown(
+        message,
+        zero_fees_for_test,
+        lamports_per_signature,
+        prioritization_fee,
+        fee_features,
+    )
+    .into()
+}
+
+/// Calculate the components of the fee for `SanitizedMessage`
+pub fn calculate_fee_breakdown(
+    message: &impl SVMMessage,
+    zero_fees_for_test: bool,
+    _lamports_per_signature: u64,
+    prioritization_fee: u64,
+    fee_features: FeeFeatures,
+) -> FeeBreakdown {
     if zero_fees_for_test {
-        return FeeDetails::default();
+        return FeeBreakdown::default();
     }
 
-    if is_vote_transaction(message) {
+    if is_vote_transaction(message)
+        && (!fee_features.charge_non_conforming_vote_transactions
+            || is_conforming_vote_transaction(message))
+    {
         trace!("Vote program detected, setting total_fee to 0");
-        return FeeDetails::default();
+        return FeeBreakdown::default();
     }
 
-    let derived_compute_units = get_transaction_cost(message);
-    let requested_cu_price = get_compute_unit_price_from_message(message);
+    let derived_compute_units = get_transaction_cost(message, fee_features.transfer_many);
+    let explicit_cu_price = get_compute_unit_price_from_message(message);
+    let requested_cu_price = explicit_cu_price.unwrap_or(0);
 
     trace!(
         "message: {:?}, derived_compute_units: {}, requested_cu_price: {}",
@@ -83,28 +131,55 @@ pub fn calculate_fee_details(
     );
 
     // Ensure minimum price when both CU and price are low
+    let min_cu_price = fee_features.min_compute_unit_price;
     let effective_cu_price = if derived_compute_units < MIN_COMPUTE_UNITS_THRESHOLD
-        && requested_cu_price < MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS
+        && requested_cu_price < min_cu_price
     {
-        MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS
+        min_cu_price
     } else {
         requested_cu_price
     };
 
     // Base fee: fixed multiplier + proportional to CU price
-    let base_fee = derived_compute_units.saturating_mul(BASE_FEE_MULTIPLIER);
+    let base_fee = derived_compute_units.saturating_mul(fee_features.base_fee_multiplier);
     let price_fee =
         derived_compute_units.saturating_mul(effective_cu_price) / MICROLAMPORTS_PER_LAMPORT;
 
-    let transaction_fee = base_fee.saturating_add(price_fee);
-    let fee_details = FeeDetails::new(transaction_fee, prioritization_fee);
+    // A priority bid below the minimum price is charged as if made at the minimum price
+    let prioritization_fee = if fee_features.enforce_min_compute_unit_price
+        && derived_compute_units < MIN_COMPUTE_UNITS_THRESHOLD
+        && explicit_cu_price.is_some_and(|price| price < min_cu_price)
+    {
+        prioritization_fee.max(get_prioritization_fee_at_price(message, min_cu_price))
+    } else {
+        prioritization_fee
+    };
+
+    // Heap above the historical maximum is paid for by the byte
+    let memory_fee = if fee_features.raise_max_heap_frame_bytes {
+        get_requested_heap_size_from_message(message).map_or(0, |heap_size| {
+            u64::from(heap_size.saturating_sub(MAX_HEAP_FRAME_BYTES))
+                .saturating_mul(HEAP_FEE_LAMPORTS_PER_BYTE)
+        })
+    } else {
+        0
+    };
+
+    let fee_breakdown = FeeBreakdown {
+        base: base_fee,
+        prioritization: prioritization_fee,
+        memory: memory_fee,
+        congestion: price_fee,
+        ..FeeBreakdown::default()
+    };
 
     debug!(
-        "Calculated transaction_fee: {transaction_fee} | total_fee: {} | compute_units: {derived_compute_units} | requested_cu_price: {requested_cu_price} | prioritization_fee: {prioritization_fee}",
-        fee_details.total_fee()
+        "Calculated transaction_fee: {} | total_fee: {} | compute_units: {derived_compute_units} | requested_cu_price: {requested_cu_price} | prioritization_fee: {prioritization_fee}",
+        fee_breakdown.transaction_fee(),
+        fee_breakdown.total_fee()
     );
 
-    fee_details
+    fee_breakdown
 }
 
 fn is_vote_transaction(message: &impl SVMMessage) -> bool {
@@ -115,21 +190,96 @@ fn is_vote_transaction(message: &impl SVMMessage) -> bool {
         .any(|key| key == vote_program_id)
 }
 
-fn get_compute_unit_price_from_message(message: &impl SVMMessage) -> u64 {
+/// Returns whether `message` is a simple vote: a single vote program instruction signed by at
+/// most the vote authority and the validator identity, without address lookup tables. Only
+/// simple votes are free once `charge_non_conforming_vote_transactions` is active; other
+/// transactions referencing the vote program pay the fees of any transaction.
+pub fn is_conforming_vote_transaction(message: &impl SVMMessage) -> bool {
+    let mut program_ids = message
+        .program_instructions_iter()
+        .map(|(program_id, _)| program_id);
+    message.num_transaction_signatures() <= 2
+        && message.num_lookup_tables() == 0
+        && program_ids
+            .next()
+            .is_some_and(|program_id| program_id == &solana_sdk_ids::vote::ID)
+        && program_ids.next().is_none()
+}
+
+/// Returns the compute unit price requested by `message` if it is below the
+/// minimum price of `fee_features` while the transaction derives fewer than
+/// `MIN_COMPUTE_UNITS_THRESHOLD` compute units, as the fee calculation derives
+/// them. Transactions that do not request a price are not bidding for priority
+/// and are never below the minimum.
+pub fn compute_unit_price_below_minimum(
+    message: &impl SVMMessage,
+    fee_features: FeeFeatures,
+) -> Option<u64> {
+    if is_vote_transaction(message) {
+        return None;
+    }
+    get_compute_unit_price_from_message(message).filter(|requested_cu_price| {
+        *requested_cu_price < fee_features.min_compute_unit_price
+            && get_transaction_cost(message, fee_features.transfer_many)
+                < MIN_COMPUTE_UNITS_THRESHOLD
+    })
+}
+
+/// Returns the compute unit price requested by `message`, if any
+pub fn get_compute_unit_price_from_message(message: &impl SVMMessage) -> Option<u64> {
     for (program_id, instruction) in message.program_instructions_iter() {
         if check_id(program_id) {
             if let Ok(ComputeBudgetInstruction::SetComputeUnitPrice(price)) =
                 try_from_slice_unchecked(instruction.data)
             {
-                return price;
+                return Some(price);
+            }
+        }
+    }
+
+    None
+}
+
+fn get_requested_heap_size_from_message(message: &impl SVMMessage) -> Option<u32> {
+    for (program_id, instruction) in message.program_instructions_iter() {
+        if check_id(program_id) {
+            if let Ok(ComputeBudgetInstruction::RequestHeapFrame(bytes)) =
+                try_from_slice_unchecked(instruction.data)
+            {
+                return Some(bytes);
             }
         }
     }
 
-    0
+    None
+}
+
+fn get_compute_budget_limits(message: &impl SVMMessage) -> ComputeBudgetLimits {
+    process_compute_budget_instructions(
+        message.program_instructions_iter(),
+        &FeatureSet::all_enabled(),
+    )
+    .unwrap_or_default()
+}
+
+/// The prioritization fee of `message` at the compute unit price it requests
+pub fn get_prioritization_fee(message: &impl SVMMessage) -> u64 {
+    FeeBudgetLimits::from(get_compute_budget_limits(message)).prioritization_fee
+}
+
+/// The prioritization fee of `message` had it requested `compute_unit_price`
+fn get_prioritization_fee_at_price(message: &impl SVMMessage, compute_unit_price: u64) -> u64 {
+    FeeBudgetLimits::from(ComputeBudgetLimits {
+        compute_unit_price,
+        ..get_compute_budget_limits(message)
+    })
+    .prioritization_fee
 }
 
-fn get_transaction_cost(message: &impl SVMMessage) -> u64 {
+/// The compute units `message` is charged for. With `charge_transfer_many_recipients`, a
+/// `TransferMany` instruction costs the units of each of its recipients on top of the units of
+/// the system program, as it does when executed.
+fn get_transaction_cost(message: &impl SVMMessage, charge_transfer_many_recipients: bool) -> u64 {
     let (mut builtin_costs, mut bpf_costs, mut data_bytes_len_total): (u64, u64, u64) = (0, 0, 0);
     let feature_set = &FeatureSet::all_enabled();
 
@@ -139,6 +289,15 @@ fn get_transaction_cost(message: &impl SVMMessage) -> u64 {
             .any(|(program_id, instruction)| {
                 if let Some(builtin_cost) = get_builtin_instruction_cost(program_id, feature_set) {
                     builtin_costs = builtin_costs.saturating_add(builtin_cost);
+                    if charge_transfer_many_recipients
+                        && solana_sdk_ids::system_program::check_id(program_id)
+                        && TransferMany::is_transfer_many(instruction.data)
+                    {
+                        let num_recipients = instruction.accounts.len().saturating_sub(1) as u64;
+                        builtin_costs = builtin_costs.saturating_add(
+                            TRANSFER_MANY_COMPUTE_UNITS_PER_RECIPIENT.saturating_mul(num_recipients),
+                        );
+                    }
                 } else {
                     bpf_costs = bpf_costs
                         .saturating_add(solana_compute_budget::compute_budget_limits::DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT.into())
@@ -0,0 +1,310 @@
+//! bootstrap validator set from a manifest
+//!
+//! `--bootstrap-validator` gives every bootstrap validator the same balance, stake and
+//! commission. A cluster launched with its initial validator set described by a YAML manifest
+//! instead starts from stakes weighted per validator, each with its own commission and
+//! optionally a vote authority other than its identity. The manifest states the total stake it
+//! intends to distribute, which must match the stakes of its validators.
+use {
+    serde::{Deserialize, Serialize},
+    solana_sdk::{
+        account::AccountSharedData, genesis_config::GenesisConfig, pubkey::Pubkey, rent::Rent,
+        stake::state::StakeStateV2, system_program,
+    },
+    solana_stake_program::stake_state,
+    solana_vote_program::vote_state::{self, VoteState},
+    std::{collections::HashSet, str::FromStr},
+};
+
+pub const MAX_COMMISSION: u8 = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BootstrapValidatorsManifest {
+    /// Sum of the stake lamports of the validators
+    pub total_stake_lamports: u64,
+    pub validators: Vec<BootstrapValidatorInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BootstrapValidatorInfo {
+    pub identity: String,
+    pub vote_account: String,
+    pub stake_account: String,
+    /// Balance of the identity account [default: --bootstrap-validator-lamports]
+    #[serde(default)]
+    pub balance_lamports: Option<u64>,
+    /// Balance of the stake account, including its rent exempt reserve
+    pub stake_lamports: u64,
+    pub commission: u8,
+    /// Authorized voter of the vote account [default: the identity]
+    #[serde(default)]
+    pub vote_authority: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapValidator {
+    pub identity: Pubkey,
+    pub vote_account: Pubkey,
+    pub stake_account: Pubkey,
+    pub balance_lamports: u64,
+    pub stake_lamports: u64,
+    pub commission: u8,
+    pub vote_authority: Pubkey,
+}
+
+impl BootstrapValidator {
+    /// The identity, vote and stake account pubkeys of the validator
+    pub fn pubkeys(&self) -> [Pubkey; 3] {
+        [self.identity, self.vote_account, self.stake_account]
+    }
+}
+
+fn parse_pubkey(pubkey: &str, what: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(pubkey).map_err(|err| format!("Invalid {what} {pubkey}: {err}"))
+}
+
+impl BootstrapValidatorsManifest {
+    /// Checks the manifest and returns the validators it describes, with identities funded with
+    /// `default_balance_lamports` unless specified
+    pub fn validators(
+        &self,
+        default_balance_lamports: u64,
+        rent: &Rent,
+    ) -> Result<Vec<BootstrapValidator>, String> {
+        if self.validators.is_empty() {
+            return Err("Bootstrap validator manifest has no validators".to_string());
+        }
+        let min_stake_lamports = rent.minimum_balance(StakeStateV2::size_of());
+        let mut pubkeys = HashSet::new();
+        let validators = self
+            .validators
+            .iter()
+            .map(|info| {
+                let identity = parse_pubkey(&info.identity, "bootstrap validator identity")?;
+                let vote_account = parse_pubkey(&info.vote_account, "bootstrap vote account")?;
+                let stake_account = parse_pubkey(&info.stake_account, "bootstrap stake account")?;
+                let vote_authority = info
+                    .vote_authority
+                    .as_ref()
+                    .map(|vote_authority| parse_pubkey(vote_authority, "vote authority"))
+                    .transpose()?
+                    .unwrap_or(identity);
+                for pubkey in [identity, vote_account, stake_account] {
+                    if !pubkeys.insert(pubkey) {
+                        return Err(format!(
+                            "Bootstrap validator pubkey {pubkey} cannot be duplicated"
+                        ));
+                    }
+                }
+                if info.commission > MAX_COMMISSION {
+                    return Err(format!(
+                        "Bootstrap validator {identity} has a commission of {}%, at most \
+                         {MAX_COMMISSION}% is allowed",
+                        info.commission
+                    ));
+                }
+                if info.stake_lamports < min_stake_lamports {
+                    return Err(format!(
+                        "Bootstrap validator {identity} has {} stake lamports, at least \
+                         {min_stake_lamports} are required for rent exemption",
+                        info.stake_lamports
+                    ));
+                }
+                Ok(BootstrapValidator {
+                    identity,
+                    vote_account,
+                    stake_account,
+                    balance_lamports: info.balance_lamports.unwrap_or(default_balance_lamports),
+                    stake_lamports: info.stake_lamports,
+                    commission: info.commission,
+                    vote_authority,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let total_stake_lamports = validators
+            .iter()
+            .try_fold(0u64, |total, validator| {
+                total.checked_add(validator.stake_lamports)
+            })
+            .ok_or_else(|| "Total stake of the bootstrap validators overflows".to_string())?;
+        if total_stake_lamports != self.total_stake_lamports {
+            return Err(format!(
+                "Bootstrap validators are staked {total_stake_lamports} lamports in total, but \
+                 the manifest distributes {}",
+                self.total_stake_lamports
+            ));
+        }
+        Ok(validators)
+    }
+}
+
+/// Adds the identity, vote and stake accounts of the validators to genesis. The stake accounts
+/// are authorized to `stake_authority`, or to the identity of their validator if `None`.
+pub fn add_bootstrap_validator_accounts(
+    genesis_config: &mut GenesisConfig,
+    validators: &[BootstrapValidator],
+    stake_authority: Option<&Pubkey>,
+) -> Result<(), String> {
+    let rent = genesis_config.rent.clone();
+    for validator in validators {
+        if let Some(pubkey) = validator
+            .pubkeys()
+            .iter()
+            .find(|pubkey| genesis_config.accounts.contains_key(pubkey))
+        {
+            return Err(format!(
+                "Bootstrap validator pubkey {pubkey} is already a genesis account"
+            ));
+        }
+
+        genesis_config.add_account(
+            validator.identity,
+            AccountSharedData::new(validator.balance_lamports, 0, &system_program::id()),
+        );
+        let vote_account = vote_state::create_account_with_authorized(
+            &validator.identity,
+            &validator.vote_authority,
+            &validator.identity,
+            validator.commission,
+            VoteState::get_rent_exempt_reserve(&rent).max(1),
+        );
+        genesis_config.add_account(
+            validator.stake_account,
+            stake_state::create_account(
+                stake_authority.unwrap_or(&validator.identity),
+                &validator.vote_account,
+                &vote_account,
+                &rent,
+                validator.stake_lamports,
+            ),
+        );
+        genesis_config.add_account(validator.vote_account, vote_account);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::native_token::LAMPORTS_PER_SOL};
+
+    fn new_validator_info(stake_lamports: u64, commission: u8) -> BootstrapValidatorInfo {
+        BootstrapValidatorInfo {
+            identity: Pubkey::new_unique().to_string(),
+            vote_account: Pubkey::new_unique().to_string(),
+            stake_account: Pubkey::new_unique().to_string(),
+            balance_lamports: None,
+            stake_lamports,
+            commission,
+            vote_authority: None,
+        }
+    }
+
+    fn new_manifest() -> BootstrapValidatorsManifest {
+        BootstrapValidatorsManifest {
+            total_stake_lamports: 600 * LAMPORTS_PER_SOL,
+            validators: vec![
+                new_validator_info(100 * LAMPORTS_PER_SOL, 10),
+                new_validator_info(200 * LAMPORTS_PER_SOL, 5),
+                new_validator_info(300 * LAMPORTS_PER_SOL, 0),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_add_bootstrap_validator_accounts() {
+        let mut manifest = new_manifest();
+        let vote_authority = Pubkey::new_unique();
+        manifest.validators[1].vote_authority = Some(vote_authority.to_string());
+        manifest.validators[2].balance_lamports = Some(42 * LAMPORTS_PER_SOL);
+        let yaml = serde_yaml::to_string(&manifest).unwrap();
+        let manifest: BootstrapValidatorsManifest = serde_yaml::from_str(&yaml).unwrap();
+
+        let mut genesis_config = GenesisConfig::default();
+        let validators = manifest
+            .validators(LAMPORTS_PER_SOL, &genesis_config.rent)
+            .unwrap();
+        add_bootstrap_validator_accounts(&mut genesis_config, &validators, None).unwrap();
+        assert_eq!(genesis_config.accounts.len(), 9);
+
+        let stake_reserve = genesis_config.rent.minimum_balance(StakeStateV2::size_of());
+        for (validator, (expected_stake, expected_commission)) in
+            validators.iter().zip([(100, 10), (200, 5), (300, 0)])
+        {
+            let stake_account = &genesis_config.accounts[&validator.stake_account];
+            assert_eq!(stake_account.lamports, expected_stake * LAMPORTS_PER_SOL);
+            let stake =
+                stake_state::stake_from(&AccountSharedData::from(stake_account.clone())).unwrap();
+            assert_eq!(stake.delegation.voter_pubkey, validator.vote_account);
+            assert_eq!(
+                stake.delegation.stake,
+                expected_stake * LAMPORTS_PER_SOL - stake_reserve
+            );
+
+            let vote_account = &genesis_config.accounts[&validator.vote_account];
+            let vote_state = VoteState::deserialize(&vote_account.data).unwrap();
+            assert_eq!(vote_state.node_pubkey, validator.identity);
+            assert_eq!(vote_state.commission, expected_commission);
+            assert_eq!(vote_state.authorized_withdrawer, validator.identity);
+        }
+
+        let authorized_voter = |validator: &BootstrapValidator| {
+            VoteState::deserialize(&genesis_config.accounts[&validator.vote_account].data)
+                .unwrap()
+                .authorized_voters()
+                .get_authorized_voter(0)
+        };
+        assert_eq!(
+            authorized_voter(&validators[0]),
+            Some(validators[0].identity)
+        );
+        assert_eq!(authorized_voter(&validators[1]), Some(vote_authority));
+        assert_eq!(
+            genesis_config.accounts[&validators[0].identity].lamports,
+            LAMPORTS_PER_SOL
+        );
+        assert_eq!(
+            genesis_config.accounts[&validators[2].identity].lamports,
+            42 * LAMPORTS_PER_SOL
+        );
+
+        // the accounts cannot be created twice
+        assert!(add_bootstrap_validator_accounts(&mut genesis_config, &validators, None).is_err());
+    }
+
+    #[test]
+    fn test_invalid_bootstrap_validators_manifest() {
+        let rent = Rent::default();
+        assert!(new_manifest().validators(LAMPORTS_PER_SOL, &rent).is_ok());
+
+        let mut manifest = new_manifest();
+        manifest.total_stake_lamports += 1;
+        assert!(manifest.validators(LAMPORTS_PER_SOL, &rent).is_err());
+
+        let mut manifest = new_manifest();
+        manifest.validators.clear();
+        manifest.total_stake_lamports = 0;
+        assert!(manifest.validators(LAMPORTS_PER_SOL, &rent).is_err());
+
+        let mut manifest = new_manifest();
+        manifest.validators[2].vote_account = manifest.validators[0].identity.clone();
+        assert!(manifest.validators(LAMPORTS_PER_SOL, &rent).is_err());
+
+        let mut manifest = new_manifest();
+        manifest.validators[0].commission = MAX_COMMISSION + 1;
+        assert!(manifest.validators(LAMPORTS_PER_SOL, &rent).is_err());
+
+        let mut manifest = new_manifest();
+        manifest.validators[0].stake_lamports = 1;
+        manifest.total_stake_lamports = 500 * LAMPORTS_PER_SOL + 1;
+        assert!(manifest.validators(LAMPORTS_PER_SOL, &rent).is_err());
+
+        let mut manifest = new_manifest();
+        manifest.validators[0].vote_authority = Some("not a pubkey".to_string());
+        assert!(manifest.validators(LAMPORTS_PER_SOL, &rent).is_err());
+
+        let mut manifest = new_manifest();
+        manifest.validators[0].stake_lamports = u64::MAX;
+        assert!(manifest.validators(LAMPORTS_PER_SOL, &rent).is_err());
+    }
+}
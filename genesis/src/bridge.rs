@@ -0,0 +1,280 @@
+//! bridge authority and wrapped asset registry accounts
+//!
+//! A bridge deployment is described by a YAML file listing the bridge program, the authorities
+//! allowed to attest to transfers from other chains, and the assets of other chains wrapped on
+//! this one. Genesis funds the authorities and creates the registry account, owned by the
+//! bridge program at a program derived address, so the bridge starts from accounts whose
+//! contents are known rather than ones created ad hoc after launch.
+use {
+    serde::{Deserialize, Serialize},
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        genesis_config::GenesisConfig,
+        pubkey::Pubkey,
+        system_program,
+    },
+    std::{collections::HashSet, str::FromStr},
+};
+
+/// Seed of the registry's program derived address under the bridge program
+pub const WRAPPED_ASSET_REGISTRY_SEED: &[u8] = b"wrapped_asset_registry";
+pub const WRAPPED_ASSET_REGISTRY_VERSION: u8 = 1;
+/// Wrapped tokens are SPL tokens, whose amounts are `u64`
+pub const MAX_WRAPPED_ASSET_DECIMALS: u8 = 18;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BridgeConfigFile {
+    pub program_id: String,
+    /// Number of authorities which must attest to a transfer
+    pub threshold: u8,
+    pub authorities: Vec<BridgeAuthorityInfo>,
+    #[serde(default)]
+    pub wrapped_assets: Vec<WrappedAssetInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BridgeAuthorityInfo {
+    pub pubkey: String,
+    pub balance_lamports: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WrappedAssetInfo {
+    /// Name of the chain the asset is native to, e.g. `ethereum`
+    pub source_chain: String,
+    /// Address of the asset on its native chain, in that chain's format
+    pub source_address: String,
+    /// Mint of the wrapped token on this chain
+    pub mint: String,
+    pub decimals: u8,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WrappedAsset {
+    pub source_chain: String,
+    pub source_address: String,
+    pub mint: Pubkey,
+    pub decimals: u8,
+}
+
+/// Contents of the wrapped asset registry account, bincode serialized
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WrappedAssetRegistry {
+    pub version: u8,
+    pub authorities: Vec<Pubkey>,
+    pub threshold: u8,
+    pub assets: Vec<WrappedAsset>,
+}
+
+impl WrappedAssetRegistry {
+    pub fn address(program_id: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[WRAPPED_ASSET_REGISTRY_SEED], program_id).0
+    }
+
+    pub fn deserialize(data: &[u8]) -> Result<Self, String> {
+        let registry: Self = bincode::deserialize(data)
+            .map_err(|err| format!("Invalid wrapped asset registry: {err}"))?;
+        if registry.version != WRAPPED_ASSET_REGISTRY_VERSION {
+            return Err(format!(
+                "Unsupported wrapped asset registry version {}",
+                registry.version
+            ));
+        }
+        Ok(registry)
+    }
+}
+
+fn parse_pubkey(pubkey: &str, what: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(pubkey).map_err(|err| format!("Invalid {what} {pubkey}: {err}"))
+}
+
+impl BridgeConfigFile {
+    /// Checks the file and builds the registry it describes
+    pub fn registry(&self) -> Result<WrappedAssetRegistry, String> {
+        let authorities = self
+            .authorities
+            .iter()
+            .map(|authority| parse_pubkey(&authority.pubkey, "bridge authority"))
+            .collect::<Result<Vec<_>, _>>()?;
+        if authorities.iter().collect::<HashSet<_>>().len() != authorities.len() {
+            return Err("Bridge authorities cannot be duplicated".to_string());
+        }
+        if self.threshold == 0 || usize::from(self.threshold) > authorities.len() {
+            return Err(format!(
+                "Bridge threshold must be between 1 and the number of authorities, {}",
+                authorities.len()
+            ));
+        }
+
+        let mut source_assets = HashSet::new();
+        let mut mints = HashSet::new();
+        let assets = self
+            .wrapped_assets
+            .iter()
+            .map(|asset| {
+                if asset.source_chain.is_empty() || asset.source_address.is_empty() {
+                    return Err(format!(
+                        "Wrapped asset {} is missing its source chain or address",
+                        asset.mint
+                    ));
+                }
+                if asset.decimals > MAX_WRAPPED_ASSET_DECIMALS {
+                    return Err(format!(
+                        "Wrapped asset {} has {} decimals, at most {MAX_WRAPPED_ASSET_DECIMALS} \
+                         are supported",
+                        asset.mint, asset.decimals
+                    ));
+                }
+                if !source_assets.insert((&asset.source_chain, &asset.source_address)) {
+                    return Err(format!(
+                        "Asset {} of {} is wrapped more than once",
+                        asset.source_address, asset.source_chain
+                    ));
+                }
+                let mint = parse_pubkey(&asset.mint, "wrapped asset mint")?;
+                if !mints.insert(mint) {
+                    return Err(format!("Mint {mint} wraps more than one asset"));
+                }
+                Ok(WrappedAsset {
+                    source_chain: asset.source_chain.clone(),
+                    source_address: asset.source_address.clone(),
+                    mint,
+                    decimals: asset.decimals,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(WrappedAssetRegistry {
+            version: WRAPPED_ASSET_REGISTRY_VERSION,
+            authorities,
+            threshold: self.threshold,
+            assets,
+        })
+    }
+}
+
+/// Adds the bridge authorities and the wrapped asset registry to genesis, returning the
+/// address of the registry
+pub fn add_bridge_accounts(
+    genesis_config: &mut GenesisConfig,
+    bridge_config: &BridgeConfigFile,
+) -> Result<Pubkey, String> {
+    let program_id = parse_pubkey(&bridge_config.program_id, "bridge program id")?;
+    let registry = bridge_config.registry()?;
+
+    for (pubkey, authority) in registry.authorities.iter().zip(&bridge_config.authorities) {
+        if genesis_config.accounts.contains_key(pubkey) {
+            return Err(format!(
+                "Bridge authority {pubkey} is already a genesis account"
+            ));
+        }
+        genesis_config.add_account(
+            *pubkey,
+            AccountSharedData::new(authority.balance_lamports, 0, &system_program::id()),
+        );
+    }
+
+    let registry_address = WrappedAssetRegistry::address(&program_id);
+    if genesis_config.accounts.contains_key(&registry_address) {
+        return Err(format!(
+            "Wrapped asset registry {registry_address} is already a genesis account"
+        ));
+    }
+    let data = bincode::serialize(&registry).unwrap();
+    genesis_config.add_account(
+        registry_address,
+        AccountSharedData::from(Account {
+            lamports: genesis_config.rent.minimum_balance(data.len()),
+            data,
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+    Ok(registry_address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_bridge_config() -> BridgeConfigFile {
+        BridgeConfigFile {
+            program_id: Pubkey::new_unique().to_string(),
+            threshold: 2,
+            authorities: (0..3)
+                .map(|_| BridgeAuthorityInfo {
+                    pubkey: Pubkey::new_unique().to_string(),
+                    balance_lamports: 1_000_000,
+                })
+                .collect(),
+            wrapped_assets: vec![WrappedAssetInfo {
+                source_chain: "ethereum".to_string(),
+                source_address: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".to_string(),
+                mint: Pubkey::new_unique().to_string(),
+                decimals: 8,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_add_bridge_accounts() {
+        let bridge_config = new_bridge_config();
+        let yaml = serde_yaml::to_string(&bridge_config).unwrap();
+        let bridge_config: BridgeConfigFile = serde_yaml::from_str(&yaml).unwrap();
+
+        let mut genesis_config = GenesisConfig::default();
+        let registry_address = add_bridge_accounts(&mut genesis_config, &bridge_config).unwrap();
+        let program_id = bridge_config.program_id.parse().unwrap();
+        assert_eq!(registry_address, WrappedAssetRegistry::address(&program_id));
+        assert_eq!(genesis_config.accounts.len(), 4);
+
+        let account = &genesis_config.accounts[&registry_address];
+        assert_eq!(account.owner, program_id);
+        assert!(genesis_config
+            .rent
+            .is_exempt(account.lamports, account.data.len()));
+        let registry = WrappedAssetRegistry::deserialize(&account.data).unwrap();
+        assert_eq!(registry, bridge_config.registry().unwrap());
+        assert_eq!(registry.threshold, 2);
+        assert_eq!(registry.authorities.len(), 3);
+        assert_eq!(registry.assets[0].source_chain, "ethereum");
+        assert_eq!(registry.assets[0].decimals, 8);
+        for authority in &bridge_config.authorities {
+            let pubkey = authority.pubkey.parse().unwrap();
+            assert_eq!(genesis_config.accounts[&pubkey].lamports, 1_000_000);
+        }
+
+        // the registry cannot be created twice
+        assert!(add_bridge_accounts(&mut genesis_config, &bridge_config).is_err());
+    }
+
+    #[test]
+    fn test_invalid_bridge_config() {
+        let mut bridge_config = new_bridge_config();
+        bridge_config.threshold = 4;
+        assert!(bridge_config.registry().is_err());
+        bridge_config.threshold = 0;
+        assert!(bridge_config.registry().is_err());
+
+        let mut bridge_config = new_bridge_config();
+        bridge_config.authorities[1].pubkey = bridge_config.authorities[0].pubkey.clone();
+        assert!(bridge_config.registry().is_err());
+
+        let mut bridge_config = new_bridge_config();
+        bridge_config.wrapped_assets[0].decimals = MAX_WRAPPED_ASSET_DECIMALS + 1;
+        assert!(bridge_config.registry().is_err());
+
+        let mut bridge_config = new_bridge_config();
+        let mut duplicate = bridge_config.wrapped_assets[0].clone();
+        duplicate.mint = Pubkey::new_unique().to_string();
+        bridge_config.wrapped_assets.push(duplicate);
+        assert!(bridge_config.registry().is_err());
+
+        let mut bridge_config = new_bridge_config();
+        bridge_config.wrapped_assets[0].mint = "not a pubkey".to_string();
+        assert!(bridge_config.registry().is_err());
+
+        assert!(WrappedAssetRegistry::deserialize(&[2, 0, 0]).is_err());
+    }
+}
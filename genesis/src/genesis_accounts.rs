@@ -1,7 +1,7 @@
 use {
     crate::{
         stakes::{create_and_add_stakes, StakerInfo},
-        unlocks::UnlockInfo,
+        unlocks::{UnlockInfo, UnlockSchedule},
     },
     solana_sdk::genesis_config::{ClusterType, GenesisConfig},
 };
@@ -13,6 +13,7 @@ const UNLOCKS_ALL_DAY_ZERO: UnlockInfo = UnlockInfo {
     unlocks: 0,
     unlock_years: 0.0,
     custodian: "Mc5XB47H3DKJHym5RLa9mPzWv5snERsF3KNv5AauXK8",
+    schedule: UnlockSchedule::Epochs,
 };
 
 pub const CREATOR_STAKER_INFOS: &[StakerInfo] = &[];
@@ -1,20 +1,40 @@
 #![allow(clippy::arithmetic_side_effects)]
 pub mod address_generator;
+pub mod bootstrap_validators;
+pub mod bridge;
 pub mod genesis_accounts;
+pub mod primordial_accounts;
 pub mod stakes;
 pub mod unlocks;
+pub mod upgradeable_programs;
 
-use serde::{Deserialize, Serialize};
+use {
+    serde::{Deserialize, Serialize},
+    solana_sdk::system_program,
+};
 
 /// An account where the data is encoded as a Base64 string.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Base64Account {
     pub balance: u64,
+    /// Defaults to the system program
+    #[serde(default = "default_owner")]
     pub owner: String,
+    /// Defaults to no data, `~`
+    #[serde(default = "default_data")]
     pub data: String,
+    #[serde(default)]
     pub executable: bool,
 }
 
+fn default_owner() -> String {
+    system_program::id().to_string()
+}
+
+fn default_data() -> String {
+    primordial_accounts::NO_DATA.to_string()
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ValidatorAccountsFile {
     pub validator_accounts: Vec<StakedValidatorAccountInfo>,
@@ -3,7 +3,6 @@
 
 use {
     agave_feature_set::FEATURE_NAMES,
-    base64::{prelude::BASE64_STANDARD, Engine},
     clap::{crate_description, crate_name, value_t, value_t_or_exit, App, Arg, ArgMatches},
     itertools::Itertools,
     solana_accounts_db::hardened_unpack::MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
@@ -18,15 +17,23 @@ use {
     },
     solana_entry::poh::compute_hashes_per_tick,
     solana_genesis::{
-        genesis_accounts::add_genesis_accounts, Base64Account, StakedValidatorAccountInfo,
-        ValidatorAccountsFile,
+        bootstrap_validators::{
+            add_bootstrap_validator_accounts, BootstrapValidator, BootstrapValidatorsManifest,
+        },
+        bridge::{add_bridge_accounts, BridgeConfigFile},
+        genesis_accounts::add_genesis_accounts,
+        primordial_accounts::{
+            check_primordial_account_fields, primordial_account, PrimordialAccountsOptions,
+        },
+        upgradeable_programs::{add_bpf_program_upgradeable, add_upgradeable_program},
+        Base64Account, StakedValidatorAccountInfo, ValidatorAccountsFile,
     },
     solana_ledger::{blockstore::create_new_ledger, blockstore_options::LedgerColumnOptions},
     solana_rpc_client::rpc_client::RpcClient,
     solana_rpc_client_api::request::MAX_MULTIPLE_ACCOUNTS,
+    solana_runtime::bank::fee_treasury::{self, FeeTreasuryConfig},
     solana_sdk::{
-        account::{Account, AccountSharedData, ReadableAccount, WritableAccount},
-        bpf_loader_upgradeable::UpgradeableLoaderState,
+        account::{Account, AccountSharedData, ReadableAccount},
         clock,
         commitment_config::CommitmentConfig,
         epoch_schedule::EpochSchedule,
@@ -73,13 +80,34 @@ fn pubkey_from_str(key_str: &str) -> Result<Pubkey, Box<dyn error::Error>> {
 }
 
 pub fn load_genesis_accounts(file: &str, genesis_config: &mut GenesisConfig) -> io::Result<u64> {
+    load_genesis_accounts_with_options(file, genesis_config, PrimordialAccountsOptions::default())
+}
+
+pub fn load_genesis_accounts_with_options(
+    file: &str,
+    genesis_config: &mut GenesisConfig,
+    options: PrimordialAccountsOptions,
+) -> io::Result<u64> {
     let mut lamports = 0;
-    let accounts_file = File::open(file)?;
+    let mut accounts_file = String::new();
+    File::open(file)?.read_to_string(&mut accounts_file)?;
 
-    let genesis_accounts: HashMap<String, Base64Account> =
-        serde_yaml::from_reader(accounts_file)
+    let genesis_accounts: HashMap<String, Base64Account> = serde_yaml::from_str(&accounts_file)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?;
+    if options.strict {
+        let entries: HashMap<String, serde_yaml::Value> = serde_yaml::from_str(&accounts_file)
             .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?;
+        for (key, entry) in &entries {
+            check_primordial_account_fields(entry).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("Invalid account {key}: {err}"),
+                )
+            })?;
+        }
+    }
 
+    let rent = genesis_config.rent.clone();
     for (key, account_details) in genesis_accounts {
         let pubkey = pubkey_from_str(key.as_str()).map_err(|err| {
             io::Error::new(
@@ -87,28 +115,18 @@ pub fn load_genesis_accounts(file: &str, genesis_config: &mut GenesisConfig) ->
                 format!("Invalid pubkey/keypair {key}: {err:?}"),
             )
         })?;
-
-        let owner_program_id = Pubkey::from_str(account_details.owner.as_str()).map_err(|err| {
-            io::Error::new(
+        if options.strict && genesis_config.accounts.contains_key(&pubkey) {
+            return Err(io::Error::new(
                 io::ErrorKind::Other,
-                format!("Invalid owner: {}: {:?}", account_details.owner, err),
-            )
-        })?;
+                format!("Account {pubkey} is already a genesis account"),
+            ));
+        }
 
-        let mut account = AccountSharedData::new(account_details.balance, 0, &owner_program_id);
-        if account_details.data != "~" {
-            account.set_data_from_slice(
-                &BASE64_STANDARD
-                    .decode(account_details.data.as_str())
-                    .map_err(|err| {
-                        io::Error::new(
-                            io::ErrorKind::Other,
-                            format!("Invalid account data: {}: {:?}", account_details.data, err),
-                        )
-                    })?,
-            );
+        let (account, top_up) = primordial_account(&pubkey, &account_details, &rent, options)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        if top_up > 0 {
+            println!("Topped up account {pubkey} by {top_up} lamports for rent exemption");
         }
-        account.set_executable(account_details.executable);
         lamports += account.lamports();
         genesis_config.add_account(pubkey, account);
     }
@@ -173,6 +191,42 @@ pub fn load_validator_accounts(
     Ok(())
 }
 
+pub fn load_bootstrap_validators_manifest(
+    file: &str,
+    default_balance_lamports: u64,
+    rent: &Rent,
+) -> io::Result<Vec<BootstrapValidator>> {
+    let manifest_file = File::open(file)?;
+    let manifest: BootstrapValidatorsManifest = serde_yaml::from_reader(manifest_file)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?;
+    manifest
+        .validators(default_balance_lamports, rent)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+}
+
+pub fn load_bridge_config(file: &str, genesis_config: &mut GenesisConfig) -> io::Result<()> {
+    let bridge_config_file = File::open(file)?;
+    let bridge_config: BridgeConfigFile = serde_yaml::from_reader(bridge_config_file)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?;
+    let registry_address = add_bridge_accounts(genesis_config, &bridge_config)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    println!("Wrapped asset registry: {registry_address}");
+    Ok(())
+}
+
+/// Creates the fee treasury config account, and the treasury account unless it is already in
+/// genesis, rent exempt so that it can be credited fees of any amount
+fn add_fee_treasury(genesis_config: &mut GenesisConfig, config: &FeeTreasuryConfig) {
+    let rent = genesis_config.rent.clone();
+    genesis_config.add_account(fee_treasury::config::id(), config.create_account(&rent));
+    if !genesis_config.accounts.contains_key(&config.treasury) {
+        genesis_config.add_account(
+            config.treasury,
+            AccountSharedData::new(rent.minimum_balance(0), 0, &system_program::id()),
+        );
+    }
+}
+
 fn check_rpc_genesis_hash(
     cluster_type: &ClusterType,
     rpc_client: &RpcClient,
@@ -362,9 +416,16 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .validator(is_pubkey_or_keypair)
                 .number_of_values(3)
                 .multiple(true)
-                .required(true)
+                .required_unless("bootstrap_validators_manifest")
                 .help("The bootstrap validator's identity, vote and stake pubkeys"),
         )
+        .arg(
+            Arg::with_name("bootstrap_validators_manifest")
+                .long("bootstrap-validators-manifest")
+                .value_name("FILENAME")
+                .takes_value(true)
+                .help("The location of a YAML file listing bootstrap validators with their own stake, commission and optional vote authority, and the total stake they distribute"),
+        )
         .arg(
             Arg::with_name("ledger_path")
                 .short("l")
@@ -471,6 +532,24 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .help("percentage of collected fee to burn")
                 .validator(is_valid_percentage),
         )
+        .arg(
+            Arg::with_name("fee_treasury")
+                .long("fee-treasury")
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .validator(is_pubkey)
+                .requires("fee_treasury_percentage")
+                .help("Treasury account credited with a share of the base fees instead of it being burned, once the route_base_fees_to_treasury feature is active"),
+        )
+        .arg(
+            Arg::with_name("fee_treasury_percentage")
+                .long("fee-treasury-percentage")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .requires("fee_treasury")
+                .validator(is_valid_percentage)
+                .help("percentage of the base fees credited to the fee treasury, at most the percentage burned"),
+        )
         .arg(
             Arg::with_name("vote_commission_percentage")
                 .long("vote-commission-percentage")
@@ -545,6 +624,18 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .multiple(true)
                 .help("The location of pubkey for primordial accounts and balance"),
         )
+        .arg(
+            Arg::with_name("primordial_accounts_top_up")
+                .long("primordial-accounts-top-up")
+                .requires("primordial_accounts_file")
+                .help("Raise the balances of primordial accounts below the rent exempt minimum to it"),
+        )
+        .arg(
+            Arg::with_name("primordial_accounts_strict")
+                .long("primordial-accounts-strict")
+                .requires("primordial_accounts_file")
+                .help("Reject primordial accounts with unknown fields, executable accounts without data, accounts below the rent exempt minimum unless topped up, and accounts already in genesis"),
+        )
         .arg(
             Arg::with_name("validator_accounts_file")
                 .long("validator-accounts-file")
@@ -553,6 +644,13 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .multiple(true)
                 .help("The location of a file containing a list of identity, vote, and stake pubkeys and balances for validator accounts to bake into genesis")
         )
+        .arg(
+            Arg::with_name("bridge_config")
+                .long("bridge-config")
+                .value_name("FILENAME")
+                .takes_value(true)
+                .help("The location of a file describing the bridge program, its authorities and the wrapped assets, to create the bridge authority and wrapped asset registry accounts in genesis")
+        )
         .arg(
             Arg::with_name("cluster_type")
                 .long("cluster-type")
@@ -600,6 +698,15 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .multiple(true)
                 .help("Install an upgradeable SBF program at the given address with the given upgrade authority (or \"none\")"),
         )
+        .arg(
+            Arg::with_name("bpf_program_upgradeable")
+                .long("bpf-program-upgradeable")
+                .value_name("ADDRESS UPGRADE_AUTHORITY SBF_PROGRAM.SO")
+                .takes_value(true)
+                .number_of_values(3)
+                .multiple(true)
+                .help("Install a SBF program owned by the upgradeable loader at the given address, upgradeable by the given authority pubkey or keypair (or \"none\" for an immutable program)"),
+        )
         .arg(
             Arg::with_name("inflation")
                 .required(false)
@@ -632,23 +739,37 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         burn_percent: value_t_or_exit!(matches, "rent_burn_percentage", u8),
     };
 
-    let bootstrap_validator_pubkeys = pubkeys_of(&matches, "bootstrap_validator").unwrap();
+    let bootstrap_validator_pubkeys =
+        pubkeys_of(&matches, "bootstrap_validator").unwrap_or_default();
     assert_eq!(bootstrap_validator_pubkeys.len() % 3, 0);
 
-    // Ensure there are no duplicated pubkeys in the --bootstrap-validator list
+    let bootstrap_validator_lamports =
+        value_t_or_exit!(matches, "bootstrap_validator_lamports", u64);
+
+    let bootstrap_validators = match matches.value_of("bootstrap_validators_manifest") {
+        Some(file) => {
+            load_bootstrap_validators_manifest(file, bootstrap_validator_lamports, &rent)?
+        }
+        None => vec![],
+    };
+
+    // Ensure there are no duplicated pubkeys in the --bootstrap-validator list and the manifest
     {
         let mut v = bootstrap_validator_pubkeys.clone();
+        v.extend(
+            bootstrap_validators
+                .iter()
+                .flat_map(BootstrapValidator::pubkeys),
+        );
+        let len = v.len();
         v.sort();
         v.dedup();
-        if v.len() != bootstrap_validator_pubkeys.len() {
+        if v.len() != len {
             eprintln!("Error: --bootstrap-validator pubkeys cannot be duplicated");
             process::exit(1);
         }
     }
 
-    let bootstrap_validator_lamports =
-        value_t_or_exit!(matches, "bootstrap_validator_lamports", u64);
-
     let bootstrap_validator_stake_lamports =
         value_t_or_exit!(matches, "bootstrap_validator_stake_lamports", u64);
 
@@ -751,6 +872,12 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         &rent,
         bootstrap_stake_authorized_pubkey.as_ref(),
     )?;
+    add_bootstrap_validator_accounts(
+        &mut genesis_config,
+        &bootstrap_validators,
+        bootstrap_stake_authorized_pubkey.as_ref(),
+    )
+    .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
 
     if let Some(creation_time) = unix_timestamp_from_rfc3339_datetime(&matches, "creation_time") {
         genesis_config.creation_time = creation_time;
@@ -773,8 +900,12 @@ fn main() -> Result<(), Box<dyn error::Error>> {
     }
 
     if let Some(files) = matches.values_of("primordial_accounts_file") {
+        let options = PrimordialAccountsOptions {
+            top_up_rent_exemption: matches.is_present("primordial_accounts_top_up"),
+            strict: matches.is_present("primordial_accounts_strict"),
+        };
         for file in files {
-            load_genesis_accounts(file, &mut genesis_config)?;
+            load_genesis_accounts_with_options(file, &mut genesis_config, options)?;
         }
     }
 
@@ -784,6 +915,17 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         }
     }
 
+    if let Some(file) = matches.value_of("bridge_config") {
+        load_bridge_config(file, &mut genesis_config)?;
+    }
+
+    if let Some(treasury) = pubkey_of(&matches, "fee_treasury") {
+        let base_fee_percentage = value_t_or_exit!(matches, "fee_treasury_percentage", u8);
+        let config = FeeTreasuryConfig::new(treasury, base_fee_percentage)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        add_fee_treasury(&mut genesis_config, &config);
+    }
+
     let max_genesis_archive_unpacked_size =
         value_t_or_exit!(matches, "max_genesis_archive_unpacked_size", u64);
 
@@ -831,59 +973,45 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         }
     }
 
+    let parse_upgrade_authority = |upgrade_authority: &str| {
+        if upgrade_authority == "none" {
+            return None;
+        }
+        Some(upgrade_authority.parse::<Pubkey>().unwrap_or_else(|_| {
+            read_keypair_file(upgrade_authority)
+                .map(|keypair| keypair.pubkey())
+                .unwrap_or_else(|err| {
+                    eprintln!("Error: invalid upgrade_authority {upgrade_authority}: {err}");
+                    process::exit(1);
+                })
+        }))
+    };
+
     if let Some(values) = matches.values_of("upgradeable_program") {
         for (address, loader, program, upgrade_authority) in values.tuples() {
             let address = parse_address(address, "address");
             let loader = parse_address(loader, "loader");
-            let program_data_elf = parse_program_data(program);
-            let upgrade_authority_address = if upgrade_authority == "none" {
-                Pubkey::default()
-            } else {
-                upgrade_authority.parse::<Pubkey>().unwrap_or_else(|_| {
-                    read_keypair_file(upgrade_authority)
-                        .map(|keypair| keypair.pubkey())
-                        .unwrap_or_else(|err| {
-                            eprintln!(
-                                "Error: invalid upgrade_authority {upgrade_authority}: {err}"
-                            );
-                            process::exit(1);
-                        })
-                })
-            };
-
-            let (programdata_address, _) =
-                Pubkey::find_program_address(&[address.as_ref()], &loader);
-            let mut program_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
-                slot: 0,
-                upgrade_authority_address: Some(upgrade_authority_address),
-            })
-            .unwrap();
-            program_data.extend_from_slice(&program_data_elf);
-            genesis_config.add_account(
-                programdata_address,
-                AccountSharedData::from(Account {
-                    lamports: genesis_config.rent.minimum_balance(program_data.len()),
-                    data: program_data,
-                    owner: loader,
-                    executable: false,
-                    rent_epoch: 0,
-                }),
-            );
+            add_upgradeable_program(
+                &mut genesis_config,
+                &loader,
+                &address,
+                parse_upgrade_authority(upgrade_authority),
+                &parse_program_data(program),
+            )
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        }
+    }
 
-            let program_data = bincode::serialize(&UpgradeableLoaderState::Program {
-                programdata_address,
-            })
-            .unwrap();
-            genesis_config.add_account(
-                address,
-                AccountSharedData::from(Account {
-                    lamports: genesis_config.rent.minimum_balance(program_data.len()),
-                    data: program_data,
-                    owner: loader,
-                    executable: true,
-                    rent_epoch: 0,
-                }),
-            );
+    if let Some(values) = matches.values_of("bpf_program_upgradeable") {
+        for (address, upgrade_authority, program) in values.tuples() {
+            let address = parse_address(address, "address");
+            add_bpf_program_upgradeable(
+                &mut genesis_config,
+                &address,
+                parse_upgrade_authority(upgrade_authority),
+                &parse_program_data(program),
+            )
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
         }
     }
 
@@ -903,6 +1031,7 @@ fn main() -> Result<(), Box<dyn error::Error>> {
 mod tests {
     use {
         super::*,
+        base64::{prelude::BASE64_STANDARD, Engine},
         solana_sdk::{borsh1, genesis_config::GenesisConfig, stake},
         std::{collections::HashMap, fs::remove_file, io::Write, path::Path},
     };
@@ -1363,4 +1492,80 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_add_fee_treasury() {
+        let mut genesis_config = GenesisConfig::default();
+        let config = FeeTreasuryConfig::new(solana_sdk::pubkey::new_rand(), 20).unwrap();
+        add_fee_treasury(&mut genesis_config, &config);
+        let config_account = &genesis_config.accounts[&fee_treasury::config::id()];
+        assert_eq!(
+            FeeTreasuryConfig::from_account(config_account),
+            Some(config)
+        );
+        let treasury_account = &genesis_config.accounts[&config.treasury];
+        assert_eq!(
+            treasury_account.lamports,
+            genesis_config.rent.minimum_balance(0)
+        );
+
+        // A treasury account already in genesis is left as is
+        let mut genesis_config = GenesisConfig::default();
+        genesis_config.add_account(
+            config.treasury,
+            AccountSharedData::new(42, 0, &system_program::id()),
+        );
+        add_fee_treasury(&mut genesis_config, &config);
+        assert_eq!(genesis_config.accounts[&config.treasury].lamports, 42);
+    }
+
+    #[test]
+    fn test_load_genesis_accounts_with_options() {
+        let pubkey = solana_sdk::pubkey::new_rand();
+        let write_accounts = |yaml: &str| {
+            let mut file = tempfile::NamedTempFile::new().unwrap();
+            file.write_all(yaml.as_bytes()).unwrap();
+            file
+        };
+        let strict = PrimordialAccountsOptions {
+            top_up_rent_exemption: false,
+            strict: true,
+        };
+        let top_up = PrimordialAccountsOptions {
+            top_up_rent_exemption: true,
+            strict: true,
+        };
+
+        // The owner, data and executable flag are optional
+        let file = write_accounts(&format!("{pubkey}:\n  balance: 1\n"));
+        let path = file.path().to_str().unwrap();
+        let mut genesis_config = GenesisConfig::default();
+        assert_eq!(load_genesis_accounts(path, &mut genesis_config).unwrap(), 1);
+        let account = &genesis_config.accounts[&pubkey];
+        assert_eq!(account.owner, system_program::id());
+        assert!(account.data.is_empty());
+        assert!(!account.executable);
+
+        // Strict mode rejects accounts below rent exemption unless they are topped up
+        let mut genesis_config = GenesisConfig::default();
+        assert!(load_genesis_accounts_with_options(path, &mut genesis_config, strict).is_err());
+        let minimum_balance = genesis_config.rent.minimum_balance(0);
+        assert_eq!(
+            load_genesis_accounts_with_options(path, &mut genesis_config, top_up).unwrap(),
+            minimum_balance
+        );
+        assert_eq!(genesis_config.accounts[&pubkey].lamports, minimum_balance);
+
+        // and accounts already in genesis
+        assert!(load_genesis_accounts_with_options(path, &mut genesis_config, top_up).is_err());
+
+        // and unknown fields
+        let file = write_accounts(&format!(
+            "{pubkey}:\n  balance: {minimum_balance}\n  rent_epoch: 0\n"
+        ));
+        let path = file.path().to_str().unwrap();
+        let mut genesis_config = GenesisConfig::default();
+        assert!(load_genesis_accounts_with_options(path, &mut genesis_config, strict).is_err());
+        assert!(load_genesis_accounts(path, &mut genesis_config).is_ok());
+    }
 }
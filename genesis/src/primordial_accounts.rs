@@ -0,0 +1,176 @@
+//! primordial accounts read from a YAML or JSON file
+//!
+//! Recreating the state of another network, such as a testnet, for a new cluster needs accounts
+//! with their owner, data and executable flag rather than plain balances. Their balances may
+//! predate changes of rent, so accounts below the rent exempt minimum can be topped up to it.
+//! The strict mode rejects entries which would otherwise be loaded with a guess: fields which
+//! are not account fields, executable accounts without data, balances below the rent exempt
+//! minimum that are not topped up, and accounts already in genesis.
+use {
+    crate::Base64Account,
+    base64::{prelude::BASE64_STANDARD, Engine},
+    solana_sdk::{
+        account::{AccountSharedData, WritableAccount},
+        pubkey::Pubkey,
+        rent::Rent,
+    },
+    std::str::FromStr,
+};
+
+/// Fields of an entry of the file, see [`Base64Account`]
+pub const PRIMORDIAL_ACCOUNT_FIELDS: &[&str] = &["balance", "owner", "data", "executable"];
+
+/// Data of an account without data
+pub const NO_DATA: &str = "~";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrimordialAccountsOptions {
+    /// Raise the balances of accounts below the rent exempt minimum to it
+    pub top_up_rent_exemption: bool,
+    pub strict: bool,
+}
+
+/// Rejects an entry of the file with fields other than [`PRIMORDIAL_ACCOUNT_FIELDS`]
+pub fn check_primordial_account_fields(entry: &serde_yaml::Value) -> Result<(), String> {
+    let fields = entry
+        .as_mapping()
+        .ok_or_else(|| "Primordial account is not a mapping".to_string())?;
+    for field in fields.keys() {
+        let field = field.as_str().unwrap_or_default();
+        if !PRIMORDIAL_ACCOUNT_FIELDS.contains(&field) {
+            return Err(format!("Unknown primordial account field {field:?}"));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the account described by `entry`, and the lamports its balance was topped up by to
+/// make it rent exempt
+pub fn primordial_account(
+    pubkey: &Pubkey,
+    entry: &Base64Account,
+    rent: &Rent,
+    options: PrimordialAccountsOptions,
+) -> Result<(AccountSharedData, u64), String> {
+    let owner = Pubkey::from_str(&entry.owner)
+        .map_err(|err| format!("Invalid owner of {pubkey}: {}: {err:?}", entry.owner))?;
+    let data = if entry.data == NO_DATA {
+        vec![]
+    } else {
+        BASE64_STANDARD
+            .decode(&entry.data)
+            .map_err(|err| format!("Invalid account data of {pubkey}: {}: {err:?}", entry.data))?
+    };
+    if options.strict && entry.executable && data.is_empty() {
+        return Err(format!("Executable account {pubkey} has no data"));
+    }
+
+    let minimum_balance = rent.minimum_balance(data.len());
+    let top_up = if entry.balance >= minimum_balance {
+        0
+    } else if options.top_up_rent_exemption {
+        minimum_balance - entry.balance
+    } else if options.strict {
+        return Err(format!(
+            "Account {pubkey} has a balance of {} lamports, at least {minimum_balance} are \
+             required for rent exemption",
+            entry.balance
+        ));
+    } else {
+        0
+    };
+
+    let mut account = AccountSharedData::new(entry.balance + top_up, 0, &owner);
+    account.set_data_from_slice(&data);
+    account.set_executable(entry.executable);
+    Ok((account, top_up))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::account::ReadableAccount};
+
+    fn entry(balance: u64, data: &str, executable: bool) -> Base64Account {
+        Base64Account {
+            balance,
+            owner: Pubkey::new_unique().to_string(),
+            data: data.to_string(),
+            executable,
+        }
+    }
+
+    #[test]
+    fn test_primordial_account() {
+        let rent = Rent::default();
+        let pubkey = Pubkey::new_unique();
+        let minimum_balance = rent.minimum_balance(5);
+        let lenient = PrimordialAccountsOptions::default();
+        let top_up = PrimordialAccountsOptions {
+            top_up_rent_exemption: true,
+            strict: false,
+        };
+        let strict = PrimordialAccountsOptions {
+            top_up_rent_exemption: false,
+            strict: true,
+        };
+
+        let rent_exempt = entry(minimum_balance, "aGVsbG8=", true);
+        for options in [lenient, top_up, strict] {
+            let (account, top_up_lamports) =
+                primordial_account(&pubkey, &rent_exempt, &rent, options).unwrap();
+            assert_eq!(top_up_lamports, 0);
+            assert_eq!(account.lamports(), minimum_balance);
+            assert_eq!(account.data(), b"hello");
+            assert!(account.executable());
+            assert_eq!(account.owner().to_string(), rent_exempt.owner);
+        }
+
+        // Accounts below rent exemption are kept as is, topped up or rejected
+        let rent_paying = entry(1, "aGVsbG8=", false);
+        let (account, top_up_lamports) =
+            primordial_account(&pubkey, &rent_paying, &rent, lenient).unwrap();
+        assert_eq!((account.lamports(), top_up_lamports), (1, 0));
+        let (account, top_up_lamports) =
+            primordial_account(&pubkey, &rent_paying, &rent, top_up).unwrap();
+        assert_eq!(
+            (account.lamports(), top_up_lamports),
+            (minimum_balance, minimum_balance - 1)
+        );
+        assert!(primordial_account(&pubkey, &rent_paying, &rent, strict).is_err());
+        let strict_top_up = PrimordialAccountsOptions {
+            top_up_rent_exemption: true,
+            strict: true,
+        };
+        assert!(primordial_account(&pubkey, &rent_paying, &rent, strict_top_up).is_ok());
+
+        // Executable accounts need data in strict mode
+        let no_data = entry(rent.minimum_balance(0), NO_DATA, true);
+        assert!(primordial_account(&pubkey, &no_data, &rent, lenient).is_ok());
+        assert!(primordial_account(&pubkey, &no_data, &rent, strict).is_err());
+
+        let invalid_data = entry(minimum_balance, "not base64!", false);
+        assert!(primordial_account(&pubkey, &invalid_data, &rent, lenient).is_err());
+        let mut invalid_owner = entry(minimum_balance, NO_DATA, false);
+        invalid_owner.owner = "not a pubkey".to_string();
+        assert!(primordial_account(&pubkey, &invalid_owner, &rent, lenient).is_err());
+    }
+
+    #[test]
+    fn test_check_primordial_account_fields() {
+        let entry: serde_yaml::Value = serde_yaml::from_str(
+            "balance: 3\nowner: 11111111111111111111111111111111\ndata: ~\nexecutable: false",
+        )
+        .unwrap();
+        assert!(check_primordial_account_fields(&entry).is_ok());
+
+        let entry: serde_yaml::Value = serde_yaml::from_str(
+            "balance: 3\nowner: 11111111111111111111111111111111\ndata: ~\nexecutable: false\n\
+             rent_epoch: 0",
+        )
+        .unwrap();
+        assert!(check_primordial_account_fields(&entry).is_err());
+
+        let entry: serde_yaml::Value = serde_yaml::from_str("[1, 2]").unwrap();
+        assert!(check_primordial_account_fields(&entry).is_err());
+    }
+}
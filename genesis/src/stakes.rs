@@ -95,15 +95,7 @@ pub fn create_and_add_stakes(
     // the staker account needs to be rent exempt *and* carry enough
     //  lamports to cover TX fees (delegation) for one year
     //  as we support one re-delegation per epoch
-    let unlocks = Unlocks::new(
-        unlock_info.cliff_fraction,
-        unlock_info.cliff_years,
-        unlock_info.unlocks,
-        unlock_info.unlock_years,
-        &genesis_config.epoch_schedule,
-        &genesis_config.poh_config.target_tick_duration,
-        genesis_config.ticks_per_slot,
-    );
+    let unlocks = Unlocks::from_unlock_info(unlock_info, genesis_config);
 
     let mut address_generator = AddressGenerator::new(&authorized.staker, &stake::program::id());
 
@@ -121,7 +113,7 @@ pub fn create_and_add_stakes(
         let lockup = Lockup {
             epoch: unlock.epoch,
             custodian,
-            unix_timestamp: 0,
+            unix_timestamp: unlock.unix_timestamp,
         };
         for _ in 0..(lamports / granularity).saturating_sub(1) {
             genesis_config.add_account(
@@ -165,7 +157,11 @@ pub fn create_and_add_stakes(
 
 #[cfg(test)]
 mod tests {
-    use {super::*, solana_sdk::rent::Rent};
+    use {
+        super::*,
+        crate::unlocks::{add_months, UnlockSchedule},
+        solana_sdk::rent::Rent,
+    };
 
     fn create_and_check_stakes(
         genesis_config: &mut GenesisConfig,
@@ -229,6 +225,7 @@ mod tests {
                 unlocks: 1,
                 unlock_years: 0.5,
                 custodian: "11111111111111111111111111111111",
+                schedule: UnlockSchedule::Epochs,
             },
             total_lamports,
             granularity,
@@ -255,6 +252,7 @@ mod tests {
                 unlocks: 1,
                 unlock_years: 0.5,
                 custodian: "11111111111111111111111111111111",
+                schedule: UnlockSchedule::Epochs,
             },
             total_lamports,
             granularity,
@@ -281,6 +279,7 @@ mod tests {
                 unlocks: 1,
                 unlock_years: 0.5,
                 custodian: "11111111111111111111111111111111",
+                schedule: UnlockSchedule::Epochs,
             },
             total_lamports,
             granularity,
@@ -306,10 +305,71 @@ mod tests {
                 unlocks: 1,
                 unlock_years: 0.5,
                 custodian: "11111111111111111111111111111111",
+                schedule: UnlockSchedule::Epochs,
             },
             total_lamports,
             granularity,
             4 + 1,
         );
     }
+    #[test]
+    fn test_create_stakes_calendar_lockups() {
+        let rent = Rent {
+            lamports_per_byte_year: 1,
+            exemption_threshold: 1.0,
+            ..Rent::default()
+        };
+        let reserve = rent.minimum_balance(StakeStateV2::size_of());
+        let staker_reserve = rent.minimum_balance(0);
+        // 2024-02-29T00:00:00Z
+        let creation_time = 1_709_164_800;
+        let mut genesis_config = GenesisConfig {
+            rent: rent.clone(),
+            creation_time,
+            ..GenesisConfig::default()
+        };
+        let total_lamports = staker_reserve + reserve * 4;
+        create_and_check_stakes(
+            &mut genesis_config,
+            &StakerInfo {
+                name: "fun",
+                staker: "P1aceHo1derPubkey11111111111111111111111111",
+                lamports: total_lamports,
+                withdrawer: None,
+            },
+            &UnlockInfo {
+                cliff_fraction: 0.5,
+                cliff_years: 1.0,
+                unlocks: 1,
+                unlock_years: 0.5,
+                custodian: "11111111111111111111111111111111",
+                schedule: UnlockSchedule::Calendar,
+            },
+            total_lamports,
+            reserve * 2,
+            2 + 1,
+        );
+
+        let mut lockups = genesis_config
+            .accounts
+            .values()
+            .filter_map(|account| match bincode::deserialize(&account.data) {
+                Ok(StakeStateV2::Initialized(meta)) => Some(meta.lockup),
+                _ => None,
+            })
+            .map(|lockup| (lockup.epoch, lockup.unix_timestamp))
+            .collect::<Vec<_>>();
+        lockups.sort_unstable();
+        assert_eq!(
+            lockups,
+            vec![
+                // 2025-02-28
+                (0, add_months(creation_time, 12).unwrap()),
+                // 2025-08-29
+                (0, add_months(creation_time, 18).unwrap()),
+            ]
+        );
+        assert_eq!(lockups[0].1, 1_740_700_800);
+        assert_eq!(lockups[1].1, 1_756_425_600);
+    }
 }
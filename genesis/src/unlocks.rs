@@ -1,6 +1,12 @@
 //! lockups generator
 use {
-    solana_sdk::{clock::Epoch, epoch_schedule::EpochSchedule, timing::years_as_slots},
+    chrono::{DateTime, Months},
+    solana_sdk::{
+        clock::{Epoch, UnixTimestamp},
+        epoch_schedule::EpochSchedule,
+        genesis_config::GenesisConfig,
+        timing::years_as_slots,
+    },
     std::time::Duration,
 };
 
@@ -11,6 +17,30 @@ pub struct UnlockInfo {
     pub unlocks: usize,
     pub unlock_years: f64,
     pub custodian: &'static str,
+    pub schedule: UnlockSchedule,
+}
+
+/// how the cliff and unlock years of an [`UnlockInfo`] are turned into lockups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnlockSchedule {
+    /// lockups expire at epochs, years being converted to slots at the target tick duration
+    Epochs,
+    /// lockups expire at unix timestamps, years being counted in calendar months from the
+    ///  creation time of genesis
+    Calendar,
+}
+
+/// returns `unix_timestamp` moved forward by calendar `months`, in UTC, the day of the month
+///  being clamped to the last day of shorter months, e.g. Jan 31 2024 + 1 month is Feb 29 2024
+pub fn add_months(unix_timestamp: UnixTimestamp, months: u32) -> Option<UnixTimestamp> {
+    DateTime::from_timestamp(unix_timestamp, 0)?
+        .checked_add_months(Months::new(months))
+        .map(|date_time| date_time.timestamp())
+}
+
+/// converts years to whole calendar months
+fn years_as_months(years: f64) -> u32 {
+    (years * 12.0).round() as u32
 }
 
 #[derive(Debug, Default, Clone)]
@@ -33,6 +63,28 @@ pub struct Unlocks {
     unlock_fraction: f64,
     /// time between each post-cliff unlock, in Epochs
     unlock_epochs: Epoch,
+
+    /// calendar-dated unlocks, if any
+    dates: Option<UnlockDates>,
+}
+
+/// unlock times in calendar months from the creation of genesis
+#[derive(Debug, Default, Clone, Copy)]
+struct UnlockDates {
+    creation_time: UnixTimestamp,
+    /// time of cliff, in months
+    cliff_months: u32,
+    /// time between each post-cliff unlock, in months
+    unlock_months: u32,
+}
+
+impl UnlockDates {
+    /// each unlock is counted from the creation time, so that clamping the day of the month
+    ///  for one unlock does not shift the following ones
+    fn unix_timestamp(&self, i: usize) -> UnixTimestamp {
+        let months = self.cliff_months + i as u32 * self.unlock_months;
+        add_months(self.creation_time, months).expect("unlock date out of range")
+    }
 }
 
 impl Unlocks {
@@ -60,6 +112,47 @@ impl Unlocks {
         Self::from_epochs(cliff_fraction, cliff_epoch, unlocks, unlock_epochs)
     }
 
+    /// unlocks at calendar dates rather than epochs, `cliff_year` and `unlock_years` being
+    ///  rounded to whole months counted from `creation_time`
+    pub fn from_creation_time(
+        cliff_fraction: f64,          // first cliff fraction
+        cliff_year: f64,              // first cliff time, starting from genesis, in years
+        unlocks: usize,               // number of follow-on unlocks
+        unlock_years: f64,            // years between each following unlock
+        creation_time: UnixTimestamp, // creation time of genesis
+    ) -> Self {
+        Self {
+            dates: Some(UnlockDates {
+                creation_time,
+                cliff_months: years_as_months(cliff_year),
+                unlock_months: years_as_months(unlock_years),
+            }),
+            ..Self::from_epochs(cliff_fraction, 0, unlocks, 0)
+        }
+    }
+
+    /// the unlocks described by `unlock_info` for a cluster with `genesis_config`
+    pub fn from_unlock_info(unlock_info: &UnlockInfo, genesis_config: &GenesisConfig) -> Self {
+        match unlock_info.schedule {
+            UnlockSchedule::Epochs => Self::new(
+                unlock_info.cliff_fraction,
+                unlock_info.cliff_years,
+                unlock_info.unlocks,
+                unlock_info.unlock_years,
+                &genesis_config.epoch_schedule,
+                &genesis_config.poh_config.target_tick_duration,
+                genesis_config.ticks_per_slot,
+            ),
+            UnlockSchedule::Calendar => Self::from_creation_time(
+                unlock_info.cliff_fraction,
+                unlock_info.cliff_years,
+                unlock_info.unlocks,
+                unlock_info.unlock_years,
+                genesis_config.creation_time,
+            ),
+        }
+    }
+
     pub fn from_epochs(
         cliff_fraction: f64,  // first cliff fraction
         cliff_epoch: Epoch,   // first cliff epoch
@@ -80,6 +173,7 @@ impl Unlocks {
             cliff_epoch,
             unlock_fraction,
             unlock_epochs,
+            dates: None,
         }
     }
 }
@@ -89,6 +183,8 @@ impl Iterator for Unlocks {
 
     fn next(&mut self) -> Option<Self::Item> {
         let i = self.i;
+        let dates = self.dates;
+        let unix_timestamp = |i| dates.map_or(0, |dates| dates.unix_timestamp(i));
         if i == 0 {
             self.i += 1;
             self.prev_fraction = self.cliff_fraction;
@@ -97,6 +193,7 @@ impl Iterator for Unlocks {
                 prev_fraction: 0.0,
                 fraction: self.cliff_fraction,
                 epoch: self.cliff_epoch,
+                unix_timestamp: unix_timestamp(0),
             })
         } else if i <= self.unlocks {
             self.i += 1;
@@ -110,6 +207,7 @@ impl Iterator for Unlocks {
                 prev_fraction,
                 fraction: self.prev_fraction,
                 epoch: self.cliff_epoch + i as u64 * self.unlock_epochs,
+                unix_timestamp: unix_timestamp(i),
             })
         } else {
             None
@@ -122,6 +220,8 @@ impl Iterator for Unlocks {
 pub struct Unlock {
     /// the epoch height at which this unlock occurs
     pub epoch: Epoch,
+    /// the unix timestamp at which this unlock occurs, 0 for epoch-based unlocks
+    pub unix_timestamp: UnixTimestamp,
     /// the fraction that was unlocked last iteration
     pub prev_fraction: f64,
     /// the fraction unlocked this iteration
@@ -209,4 +309,66 @@ mod tests {
             total_lamports
         );
     }
+    #[test]
+    fn test_add_months() {
+        // 2024-01-31T12:00:00Z
+        let jan_31_2024 = 1_706_702_400;
+        // clamped to the leap day
+        assert_eq!(add_months(jan_31_2024, 1), Some(1_709_208_000)); // 2024-02-29T12:00:00Z
+        assert_eq!(add_months(jan_31_2024, 2), Some(1_711_886_400)); // 2024-03-31T12:00:00Z
+                                                                     // clamped to Feb 28 of the following, common year
+        assert_eq!(add_months(jan_31_2024, 13), Some(1_740_744_000)); // 2025-02-28T12:00:00Z
+
+        // 2024-02-29T00:00:00Z
+        let feb_29_2024 = 1_709_164_800;
+        assert_eq!(add_months(feb_29_2024, 0), Some(feb_29_2024));
+        assert_eq!(add_months(feb_29_2024, 12), Some(1_740_700_800)); // 2025-02-28
+        assert_eq!(add_months(feb_29_2024, 48), Some(1_835_395_200)); // 2028-02-29
+
+        // 2023-03-01T00:00:00Z, a year spanning the leap day is 366 days long
+        let mar_1_2023 = 1_677_628_800;
+        assert_eq!(
+            add_months(mar_1_2023, 12),
+            Some(mar_1_2023 + 366 * 24 * 60 * 60)
+        );
+        assert_eq!(
+            add_months(mar_1_2023 + 366 * 24 * 60 * 60, 12),
+            Some(mar_1_2023 + (366 + 365) * 24 * 60 * 60)
+        );
+    }
+
+    #[test]
+    fn test_make_calendar_lockups() {
+        let total_lamports: u64 = 1_725_987_234_408_923;
+        // 2023-08-31T00:00:00Z
+        let creation_time = 1_693_440_000;
+
+        // 1/5th at 6 months, 1/24 monthly thereafter
+        let unlocks = Unlocks::from_creation_time(0.20, 0.5, 24, 1.0 / 12.0, creation_time)
+            .collect::<Vec<_>>();
+        assert_eq!(unlocks.len(), 25);
+        assert_eq!(
+            unlocks
+                .iter()
+                .map(|unlock| unlock.amount(total_lamports))
+                .sum::<u64>(),
+            total_lamports
+        );
+        assert!(unlocks.iter().all(|unlock| unlock.epoch == 0));
+        // the cliff is on the leap day, 2024-02-29
+        assert_eq!(unlocks[0].unix_timestamp, 1_709_164_800);
+        // the day of the month is clamped per unlock, not carried over: 2024-03-31, 2024-04-30
+        //  and 2024-05-31
+        assert_eq!(unlocks[1].unix_timestamp, 1_711_843_200);
+        assert_eq!(unlocks[2].unix_timestamp, 1_714_435_200);
+        assert_eq!(unlocks[3].unix_timestamp, 1_717_113_600);
+        // the last unlock is 2026-02-28
+        assert_eq!(unlocks[24].unix_timestamp, 1_772_236_800);
+        assert!(unlocks
+            .windows(2)
+            .all(|unlocks| unlocks[0].unix_timestamp < unlocks[1].unix_timestamp));
+
+        // epoch-based unlocks leave the unix timestamp unset
+        assert!(Unlocks::from_epochs(0.20, 12, 24, 2).all(|unlock| unlock.unix_timestamp == 0));
+    }
 }
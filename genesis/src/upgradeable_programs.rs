@@ -0,0 +1,190 @@
+//! upgradeable programs deployed at genesis
+//!
+//! Programs shipped with a cluster, such as its governance and registry programs, may need to
+//! be upgraded later. Genesis creates them the way the upgradeable loader deploys programs: a
+//! program account pointing to a program data account, which holds the upgrade authority
+//! followed by the ELF.
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    genesis_config::GenesisConfig,
+    pubkey::Pubkey,
+};
+
+const ELF_MAGIC: &[u8] = b"\x7fELF";
+
+/// Returns the address of the program data account of the program at `address`
+pub fn programdata_address(address: &Pubkey, loader: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[address.as_ref()], loader).0
+}
+
+/// Adds the program account at `address` and its program data account to genesis, the program
+/// being upgradeable by `upgrade_authority`, or immutable if `None`. Returns the address of the
+/// program data account.
+pub fn add_upgradeable_program(
+    genesis_config: &mut GenesisConfig,
+    loader: &Pubkey,
+    address: &Pubkey,
+    upgrade_authority: Option<Pubkey>,
+    elf: &[u8],
+) -> Result<Pubkey, String> {
+    if !elf.starts_with(ELF_MAGIC) {
+        return Err(format!("Program {address} is not an ELF file"));
+    }
+    let programdata_address = programdata_address(address, loader);
+    for pubkey in [address, &programdata_address] {
+        if genesis_config.accounts.contains_key(pubkey) {
+            return Err(format!(
+                "Program account {pubkey} is already a genesis account"
+            ));
+        }
+    }
+
+    // The ELF always starts after the metadata sized for an upgrade authority, even if there
+    // is none
+    let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    let mut programdata = vec![0; metadata_len + elf.len()];
+    bincode::serialize_into(
+        &mut programdata[..metadata_len],
+        &UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: upgrade_authority,
+        },
+    )
+    .unwrap();
+    programdata[metadata_len..].copy_from_slice(elf);
+    genesis_config.add_account(
+        programdata_address,
+        AccountSharedData::from(Account {
+            lamports: genesis_config.rent.minimum_balance(programdata.len()),
+            data: programdata,
+            owner: *loader,
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    let program = bincode::serialize(&UpgradeableLoaderState::Program {
+        programdata_address,
+    })
+    .unwrap();
+    genesis_config.add_account(
+        *address,
+        AccountSharedData::from(Account {
+            lamports: genesis_config.rent.minimum_balance(program.len()),
+            data: program,
+            owner: *loader,
+            executable: true,
+            rent_epoch: 0,
+        }),
+    );
+    Ok(programdata_address)
+}
+
+/// Adds a program owned by the upgradeable loader, see [`add_upgradeable_program`]
+pub fn add_bpf_program_upgradeable(
+    genesis_config: &mut GenesisConfig,
+    address: &Pubkey,
+    upgrade_authority: Option<Pubkey>,
+    elf: &[u8],
+) -> Result<Pubkey, String> {
+    add_upgradeable_program(
+        genesis_config,
+        &bpf_loader_upgradeable::id(),
+        address,
+        upgrade_authority,
+        elf,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elf() -> Vec<u8> {
+        [ELF_MAGIC, &[1, 2, 3, 4]].concat()
+    }
+
+    fn programdata_state(
+        genesis_config: &GenesisConfig,
+        address: &Pubkey,
+    ) -> UpgradeableLoaderState {
+        let account = &genesis_config.accounts[address];
+        bincode::deserialize(&account.data).unwrap()
+    }
+
+    #[test]
+    fn test_add_bpf_program_upgradeable() {
+        let mut genesis_config = GenesisConfig::default();
+        let address = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let programdata_address =
+            add_bpf_program_upgradeable(&mut genesis_config, &address, Some(authority), &elf())
+                .unwrap();
+        assert_eq!(
+            programdata_address,
+            super::programdata_address(&address, &bpf_loader_upgradeable::id())
+        );
+
+        let program = &genesis_config.accounts[&address];
+        assert!(program.executable);
+        assert_eq!(program.owner, bpf_loader_upgradeable::id());
+        assert_eq!(
+            bincode::deserialize::<UpgradeableLoaderState>(&program.data).unwrap(),
+            UpgradeableLoaderState::Program {
+                programdata_address
+            }
+        );
+
+        let programdata = &genesis_config.accounts[&programdata_address];
+        assert!(!programdata.executable);
+        assert_eq!(programdata.owner, bpf_loader_upgradeable::id());
+        assert!(genesis_config
+            .rent
+            .is_exempt(programdata.lamports, programdata.data.len()));
+        assert_eq!(
+            programdata_state(&genesis_config, &programdata_address),
+            UpgradeableLoaderState::ProgramData {
+                slot: 0,
+                upgrade_authority_address: Some(authority),
+            }
+        );
+        let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+        assert_eq!(&programdata.data[metadata_len..], elf());
+
+        // the program cannot be deployed twice
+        assert!(add_bpf_program_upgradeable(&mut genesis_config, &address, None, &elf()).is_err());
+    }
+
+    #[test]
+    fn test_add_immutable_bpf_program() {
+        let mut genesis_config = GenesisConfig::default();
+        let address = Pubkey::new_unique();
+        let programdata_address =
+            add_bpf_program_upgradeable(&mut genesis_config, &address, None, &elf()).unwrap();
+        assert_eq!(
+            programdata_state(&genesis_config, &programdata_address),
+            UpgradeableLoaderState::ProgramData {
+                slot: 0,
+                upgrade_authority_address: None,
+            }
+        );
+        // The ELF is at the same offset as for upgradeable programs
+        let programdata = &genesis_config.accounts[&programdata_address];
+        let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+        assert_eq!(&programdata.data[metadata_len..], elf());
+    }
+
+    #[test]
+    fn test_add_upgradeable_program_not_elf() {
+        let mut genesis_config = GenesisConfig::default();
+        assert!(add_bpf_program_upgradeable(
+            &mut genesis_config,
+            &Pubkey::new_unique(),
+            None,
+            &[1, 2, 3, 4]
+        )
+        .is_err());
+        assert!(genesis_config.accounts.is_empty());
+    }
+}
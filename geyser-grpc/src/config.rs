@@ -0,0 +1,77 @@
+use {
+    agave_geyser_plugin_interface::geyser_plugin_interface::{GeyserPluginError, Result},
+    serde_derive::Deserialize,
+    std::{fs, net::SocketAddr},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct GrpcConfig {
+    /// The address the gRPC server listens on
+    pub address: SocketAddr,
+    /// Number of updates buffered for all subscribers, subscribers lagging further behind the
+    /// validator are disconnected
+    pub channel_capacity: usize,
+    /// Number of matching updates buffered for each subscriber
+    pub subscriber_buffer_size: usize,
+    pub max_subscribers: usize,
+    /// Maximum number of account and of transaction filters of a subscription
+    pub max_filters: usize,
+    /// Maximum number of pubkeys of a filter
+    pub max_filter_pubkeys: usize,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            address: SocketAddr::from(([127, 0, 0, 1], 10_000)),
+            channel_capacity: 100_000,
+            subscriber_buffer_size: 10_000,
+            max_subscribers: 100,
+            max_filters: 16,
+            max_filter_pubkeys: 10_000,
+        }
+    }
+}
+
+impl GrpcConfig {
+    /// Reads the config from a Geyser plugin config file, ignoring the fields of the plugin
+    /// manager such as `builtin` and `name`.
+    pub fn load(config_file: &str) -> Result<Self> {
+        let contents = fs::read_to_string(config_file)?;
+        Self::from_json(&contents)
+    }
+
+    pub fn from_json(contents: &str) -> Result<Self> {
+        json5::from_str(contents).map_err(|err| GeyserPluginError::ConfigFileReadError {
+            msg: err.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_json() {
+        let config = GrpcConfig::from_json(
+            r#"{
+                "builtin": "grpc",
+                "address": "0.0.0.0:10001",
+                "max_subscribers": 5,
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            GrpcConfig {
+                address: "0.0.0.0:10001".parse().unwrap(),
+                max_subscribers: 5,
+                ..GrpcConfig::default()
+            }
+        );
+
+        assert!(GrpcConfig::from_json(r#"{"address": "nowhere"}"#).is_err());
+    }
+}
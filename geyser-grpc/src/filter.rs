@@ -0,0 +1,332 @@
+//! Server-side filtering of the updates sent to a subscriber.
+
+use {
+    crate::proto::{
+        self, subscribe_update::Update, AccountUpdate, SubscribeRequest, SubscribeUpdate,
+        TransactionUpdate,
+    },
+    solana_sdk::pubkey::Pubkey,
+    std::collections::HashSet,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FilterError {
+    #[error("too many filters, the maximum is {0}")]
+    TooManyFilters(usize),
+
+    #[error("too many pubkeys in a filter, the maximum is {0}")]
+    TooManyPubkeys(usize),
+
+    #[error("invalid pubkey, expected 32 bytes")]
+    InvalidPubkey,
+
+    #[error("the subscription matches no updates")]
+    EmptySubscription,
+}
+
+/// The filters of a subscription, an update is sent if any of them matches.
+#[derive(Debug)]
+pub struct SubscriptionFilter {
+    slots: bool,
+    blocks: bool,
+    accounts: Vec<AccountFilter>,
+    transactions: Vec<TransactionFilter>,
+}
+
+impl SubscriptionFilter {
+    pub fn new(
+        request: &SubscribeRequest,
+        max_filters: usize,
+        max_filter_pubkeys: usize,
+    ) -> Result<Self, FilterError> {
+        if request.accounts.len() > max_filters || request.transactions.len() > max_filters {
+            return Err(FilterError::TooManyFilters(max_filters));
+        }
+        let parse_pubkeys = |pubkeys: &[Vec<u8>]| parse_pubkeys(pubkeys, max_filter_pubkeys);
+        let accounts = request
+            .accounts
+            .iter()
+            .map(|filter| {
+                Ok(AccountFilter {
+                    pubkeys: parse_pubkeys(&filter.pubkeys)?,
+                    owners: parse_pubkeys(&filter.owners)?,
+                    data_size: filter.data_size,
+                    memcmp: filter.memcmp.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let transactions = request
+            .transactions
+            .iter()
+            .map(|filter| {
+                Ok(TransactionFilter {
+                    vote: filter.vote,
+                    failed: filter.failed,
+                    account_include: parse_pubkeys(&filter.account_include)?,
+                    account_required: parse_pubkeys(&filter.account_required)?,
+                    account_exclude: parse_pubkeys(&filter.account_exclude)?,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if !request.slots && !request.blocks && accounts.is_empty() && transactions.is_empty() {
+            return Err(FilterError::EmptySubscription);
+        }
+
+        Ok(Self {
+            slots: request.slots,
+            blocks: request.blocks,
+            accounts,
+            transactions,
+        })
+    }
+
+    pub fn matches(&self, update: &SubscribeUpdate) -> bool {
+        match &update.update {
+            Some(Update::Slot(_)) => self.slots,
+            Some(Update::Block(_)) => self.blocks,
+            Some(Update::Account(account)) => {
+                self.accounts.iter().any(|filter| filter.matches(account))
+            }
+            Some(Update::Transaction(transaction)) => self
+                .transactions
+                .iter()
+                .any(|filter| filter.matches(transaction)),
+            None => false,
+        }
+    }
+}
+
+fn parse_pubkeys(pubkeys: &[Vec<u8>], max_pubkeys: usize) -> Result<HashSet<Pubkey>, FilterError> {
+    if pubkeys.len() > max_pubkeys {
+        return Err(FilterError::TooManyPubkeys(max_pubkeys));
+    }
+    pubkeys
+        .iter()
+        .map(|pubkey| Pubkey::try_from(pubkey.as_slice()).map_err(|_| FilterError::InvalidPubkey))
+        .collect()
+}
+
+fn contains(pubkeys: &HashSet<Pubkey>, pubkey: &[u8]) -> bool {
+    Pubkey::try_from(pubkey).is_ok_and(|pubkey| pubkeys.contains(&pubkey))
+}
+
+#[derive(Debug)]
+struct AccountFilter {
+    pubkeys: HashSet<Pubkey>,
+    owners: HashSet<Pubkey>,
+    data_size: Option<u64>,
+    memcmp: Vec<proto::MemcmpFilter>,
+}
+
+impl AccountFilter {
+    fn matches(&self, account: &AccountUpdate) -> bool {
+        (self.pubkeys.is_empty() || contains(&self.pubkeys, &account.pubkey))
+            && (self.owners.is_empty() || contains(&self.owners, &account.owner))
+            && self
+                .data_size
+                .map_or(true, |data_size| account.data.len() as u64 == data_size)
+            && self.memcmp.iter().all(|memcmp| {
+                usize::try_from(memcmp.offset)
+                    .ok()
+                    .and_then(|offset| {
+                        account
+                            .data
+                            .get(offset..offset.checked_add(memcmp.bytes.len())?)
+                    })
+                    .is_some_and(|data| data == memcmp.bytes)
+            })
+    }
+}
+
+#[derive(Debug)]
+struct TransactionFilter {
+    vote: Option<bool>,
+    failed: Option<bool>,
+    account_include: HashSet<Pubkey>,
+    account_required: HashSet<Pubkey>,
+    account_exclude: HashSet<Pubkey>,
+}
+
+impl TransactionFilter {
+    fn matches(&self, transaction: &TransactionUpdate) -> bool {
+        if self.vote.is_some_and(|vote| vote != transaction.is_vote)
+            || self
+                .failed
+                .is_some_and(|failed| failed != transaction.err.is_some())
+        {
+            return false;
+        }
+        let account_keys: HashSet<_> = transaction
+            .account_keys
+            .iter()
+            .filter_map(|key| Pubkey::try_from(key.as_slice()).ok())
+            .collect();
+        (self.account_include.is_empty()
+            || self
+                .account_include
+                .iter()
+                .any(|key| account_keys.contains(key)))
+            && self.account_required.is_subset(&account_keys)
+            && self.account_exclude.is_disjoint(&account_keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::proto::{AccountFilter as ProtoAccountFilter, MemcmpFilter, SlotUpdate},
+    };
+
+    fn account_update(pubkey: &Pubkey, owner: &Pubkey, data: Vec<u8>) -> SubscribeUpdate {
+        SubscribeUpdate {
+            update: Some(Update::Account(AccountUpdate {
+                pubkey: pubkey.to_bytes().to_vec(),
+                owner: owner.to_bytes().to_vec(),
+                data,
+                ..AccountUpdate::default()
+            })),
+        }
+    }
+
+    fn transaction_update(account_keys: &[Pubkey], is_vote: bool, failed: bool) -> SubscribeUpdate {
+        SubscribeUpdate {
+            update: Some(Update::Transaction(TransactionUpdate {
+                is_vote,
+                err: failed.then(|| "InsufficientFundsForFee".to_string()),
+                account_keys: account_keys
+                    .iter()
+                    .map(|key| key.to_bytes().to_vec())
+                    .collect(),
+                ..TransactionUpdate::default()
+            })),
+        }
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        assert_eq!(
+            SubscriptionFilter::new(&SubscribeRequest::default(), 2, 2).unwrap_err(),
+            FilterError::EmptySubscription
+        );
+        let request = SubscribeRequest {
+            accounts: vec![ProtoAccountFilter::default(); 3],
+            ..SubscribeRequest::default()
+        };
+        assert_eq!(
+            SubscriptionFilter::new(&request, 2, 2).unwrap_err(),
+            FilterError::TooManyFilters(2)
+        );
+        let request = SubscribeRequest {
+            accounts: vec![ProtoAccountFilter {
+                pubkeys: vec![vec![0; 32]; 3],
+                ..ProtoAccountFilter::default()
+            }],
+            ..SubscribeRequest::default()
+        };
+        assert_eq!(
+            SubscriptionFilter::new(&request, 2, 2).unwrap_err(),
+            FilterError::TooManyPubkeys(2)
+        );
+        let request = SubscribeRequest {
+            accounts: vec![ProtoAccountFilter {
+                owners: vec![vec![0; 31]],
+                ..ProtoAccountFilter::default()
+            }],
+            ..SubscribeRequest::default()
+        };
+        assert_eq!(
+            SubscriptionFilter::new(&request, 2, 2).unwrap_err(),
+            FilterError::InvalidPubkey
+        );
+    }
+
+    #[test]
+    fn test_slot_and_block_filters() {
+        let request = SubscribeRequest {
+            slots: true,
+            ..SubscribeRequest::default()
+        };
+        let filter = SubscriptionFilter::new(&request, 2, 2).unwrap();
+        assert!(filter.matches(&SubscribeUpdate {
+            update: Some(Update::Slot(SlotUpdate::default())),
+        }));
+        assert!(!filter.matches(&SubscribeUpdate {
+            update: Some(Update::Block(proto::BlockUpdate::default())),
+        }));
+        assert!(!filter.matches(&account_update(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            vec![]
+        )));
+    }
+
+    #[test]
+    fn test_account_filters() {
+        let watched = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let request = SubscribeRequest {
+            accounts: vec![
+                ProtoAccountFilter {
+                    pubkeys: vec![watched.to_bytes().to_vec()],
+                    ..ProtoAccountFilter::default()
+                },
+                ProtoAccountFilter {
+                    owners: vec![owner.to_bytes().to_vec()],
+                    data_size: Some(4),
+                    memcmp: vec![MemcmpFilter {
+                        offset: 1,
+                        bytes: vec![2, 3],
+                    }],
+                    ..ProtoAccountFilter::default()
+                },
+            ],
+            ..SubscribeRequest::default()
+        };
+        let filter = SubscriptionFilter::new(&request, 2, 2).unwrap();
+
+        assert!(filter.matches(&account_update(&watched, &other, vec![])));
+        assert!(filter.matches(&account_update(&other, &owner, vec![1, 2, 3, 4])));
+        assert!(!filter.matches(&account_update(&other, &owner, vec![1, 2, 4, 4])));
+        assert!(!filter.matches(&account_update(&other, &owner, vec![1, 2, 3])));
+        assert!(!filter.matches(&account_update(&other, &other, vec![1, 2, 3, 4])));
+    }
+
+    #[test]
+    fn test_transaction_filters() {
+        let include = Pubkey::new_unique();
+        let required = Pubkey::new_unique();
+        let exclude = Pubkey::new_unique();
+        let request = SubscribeRequest {
+            transactions: vec![proto::TransactionFilter {
+                vote: Some(false),
+                failed: Some(false),
+                account_include: vec![include.to_bytes().to_vec()],
+                account_required: vec![required.to_bytes().to_vec()],
+                account_exclude: vec![exclude.to_bytes().to_vec()],
+            }],
+            ..SubscribeRequest::default()
+        };
+        let filter = SubscriptionFilter::new(&request, 2, 2).unwrap();
+
+        assert!(filter.matches(&transaction_update(&[include, required], false, false)));
+        assert!(!filter.matches(&transaction_update(&[include, required], true, false)));
+        assert!(!filter.matches(&transaction_update(&[include, required], false, true)));
+        assert!(!filter.matches(&transaction_update(&[include], false, false)));
+        assert!(!filter.matches(&transaction_update(&[required], false, false)));
+        assert!(!filter.matches(&transaction_update(
+            &[include, required, exclude],
+            false,
+            false
+        )));
+
+        let request = SubscribeRequest {
+            transactions: vec![proto::TransactionFilter::default()],
+            ..SubscribeRequest::default()
+        };
+        let filter = SubscriptionFilter::new(&request, 2, 2).unwrap();
+        assert!(filter.matches(&transaction_update(&[], true, true)));
+    }
+}
@@ -0,0 +1,31 @@
+//! A built-in gRPC service streaming the slots, blocks, transactions and account updates
+//! processed by the validator.
+//!
+//! The service is a Geyser plugin compiled into the validator, so indexers can follow the chain
+//! without building and loading a third-party plugin. It is enabled like any other plugin, with a
+//! `--geyser-plugin-config` file naming the `grpc` builtin in place of a `libpath`:
+//!
+//! ```json
+//! {
+//!   "builtin": "grpc",
+//!   "address": "0.0.0.0:10000",
+//!   "max_subscribers": 100
+//! }
+//! ```
+//!
+//! See [`GrpcConfig`] for the other settings. Clients subscribe with a [`proto::SubscribeRequest`]
+//! describing the updates they want, and filters are applied by the validator so that only
+//! matching updates are sent over the wire. Startup account updates from the snapshot are not
+//! streamed.
+
+pub mod config;
+pub mod filter;
+pub mod plugin;
+pub mod service;
+
+pub mod proto {
+    #![allow(clippy::derive_partial_eq_without_eq)]
+    tonic::include_proto!("tachyon.geyser");
+}
+
+pub use {config::GrpcConfig, plugin::GrpcGeyserPlugin};
@@ -0,0 +1,347 @@
+use {
+    crate::{
+        config::GrpcConfig,
+        proto::{
+            self, geyser_server::GeyserServer, subscribe_update::Update, AccountUpdate,
+            BlockUpdate, SlotUpdate, SubscribeUpdate, TransactionUpdate,
+        },
+        service::GeyserService,
+    },
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        GeyserPlugin, GeyserPluginError, ReplicaAccountInfoVersions, ReplicaBlockInfoVersions,
+        ReplicaTransactionInfoVersions, Result, SlotStatus,
+    },
+    log::*,
+    solana_sdk::clock::Slot,
+    std::{sync::Arc, time::Duration},
+    tokio::{
+        net::TcpListener,
+        runtime::{Builder, Runtime},
+        sync::{broadcast, oneshot},
+    },
+    tokio_stream::wrappers::TcpListenerStream,
+    tonic::transport::Server,
+};
+
+pub const PLUGIN_NAME: &str = "tachyon-grpc";
+
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The Geyser plugin serving the gRPC service, built into the validator.
+#[derive(Debug, Default)]
+pub struct GrpcGeyserPlugin {
+    inner: Option<PluginInner>,
+}
+
+#[derive(Debug)]
+struct PluginInner {
+    runtime: Runtime,
+    updates: broadcast::Sender<Arc<SubscribeUpdate>>,
+    shutdown: oneshot::Sender<()>,
+}
+
+impl GrpcGeyserPlugin {
+    /// Sends the update built by `update` to the subscribers, if there are any.
+    fn broadcast(&self, update: impl FnOnce() -> Option<Update>) {
+        let Some(inner) = &self.inner else {
+            return;
+        };
+        if inner.updates.receiver_count() == 0 {
+            return;
+        }
+        if let Some(update) = update() {
+            // Only fails if all subscribers disconnected in the meantime
+            let _ = inner.updates.send(Arc::new(SubscribeUpdate {
+                update: Some(update),
+            }));
+        }
+    }
+}
+
+impl GeyserPlugin for GrpcGeyserPlugin {
+    fn name(&self) -> &'static str {
+        PLUGIN_NAME
+    }
+
+    fn on_load(&mut self, config_file: &str, _is_reload: bool) -> Result<()> {
+        let config = GrpcConfig::load(config_file)?;
+        let runtime = Builder::new_multi_thread()
+            .thread_name("solGeyserGrpc")
+            .enable_all()
+            .build()
+            .map_err(|err| GeyserPluginError::Custom(Box::new(err)))?;
+        // Bind before returning so that an unavailable address fails the load
+        let listener = runtime
+            .block_on(TcpListener::bind(config.address))
+            .map_err(|err| GeyserPluginError::Custom(Box::new(err)))?;
+        let (updates, _) = broadcast::channel(config.channel_capacity);
+        let service = GeyserService::new(config.clone(), updates.clone());
+        let (shutdown, shutdown_receiver) = oneshot::channel();
+        runtime.spawn(async move {
+            let result = Server::builder()
+                .add_service(GeyserServer::new(service))
+                .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
+                    let _ = shutdown_receiver.await;
+                })
+                .await;
+            if let Err(err) = result {
+                error!("gRPC streaming service failed: {err}");
+            }
+        });
+        info!("gRPC streaming service listening on {}", config.address);
+
+        self.inner = Some(PluginInner {
+            runtime,
+            updates,
+            shutdown,
+        });
+        Ok(())
+    }
+
+    fn on_unload(&mut self) {
+        if let Some(PluginInner {
+            runtime, shutdown, ..
+        }) = self.inner.take()
+        {
+            let _ = shutdown.send(());
+            runtime.shutdown_timeout(SHUTDOWN_TIMEOUT);
+            info!("gRPC streaming service stopped");
+        }
+    }
+
+    fn update_account(
+        &self,
+        account: ReplicaAccountInfoVersions,
+        slot: Slot,
+        is_startup: bool,
+    ) -> Result<()> {
+        if !is_startup {
+            self.broadcast(|| Some(Update::Account(account_update(account, slot))));
+        }
+        Ok(())
+    }
+
+    fn update_slot_status(
+        &self,
+        slot: Slot,
+        parent: Option<u64>,
+        status: &SlotStatus,
+    ) -> Result<()> {
+        self.broadcast(|| Some(Update::Slot(slot_update(slot, parent, status))));
+        Ok(())
+    }
+
+    fn notify_transaction(
+        &self,
+        transaction: ReplicaTransactionInfoVersions,
+        slot: Slot,
+    ) -> Result<()> {
+        self.broadcast(|| transaction_update(transaction, slot).map(Update::Transaction));
+        Ok(())
+    }
+
+    fn notify_block_metadata(&self, blockinfo: ReplicaBlockInfoVersions) -> Result<()> {
+        self.broadcast(|| block_update(blockinfo).map(Update::Block));
+        Ok(())
+    }
+
+    fn account_data_notifications_enabled(&self) -> bool {
+        true
+    }
+
+    fn transaction_notifications_enabled(&self) -> bool {
+        true
+    }
+}
+
+fn account_update(account: ReplicaAccountInfoVersions, slot: Slot) -> AccountUpdate {
+    let (pubkey, lamports, owner, executable, rent_epoch, data, write_version, txn_signature) =
+        match account {
+            ReplicaAccountInfoVersions::V0_0_1(info) => (
+                info.pubkey,
+                info.lamports,
+                info.owner,
+                info.executable,
+                info.rent_epoch,
+                info.data,
+                info.write_version,
+                None,
+            ),
+            ReplicaAccountInfoVersions::V0_0_2(info) => (
+                info.pubkey,
+                info.lamports,
+                info.owner,
+                info.executable,
+                info.rent_epoch,
+                info.data,
+                info.write_version,
+                info.txn_signature.copied(),
+            ),
+            ReplicaAccountInfoVersions::V0_0_3(info) => (
+                info.pubkey,
+                info.lamports,
+                info.owner,
+                info.executable,
+                info.rent_epoch,
+                info.data,
+                info.write_version,
+                info.txn.map(|txn| *txn.signature()),
+            ),
+        };
+    AccountUpdate {
+        slot,
+        pubkey: pubkey.to_vec(),
+        lamports,
+        owner: owner.to_vec(),
+        executable,
+        rent_epoch,
+        data: data.to_vec(),
+        write_version,
+        txn_signature: txn_signature.map(|signature| signature.as_ref().to_vec()),
+    }
+}
+
+fn slot_update(slot: Slot, parent: Option<u64>, status: &SlotStatus) -> SlotUpdate {
+    let (status, dead_error) = match status {
+        SlotStatus::Processed => (proto::SlotStatus::Processed, None),
+        SlotStatus::Confirmed => (proto::SlotStatus::Confirmed, None),
+        SlotStatus::Rooted => (proto::SlotStatus::Rooted, None),
+        SlotStatus::FirstShredReceived => (proto::SlotStatus::FirstShredReceived, None),
+        SlotStatus::Completed => (proto::SlotStatus::Completed, None),
+        SlotStatus::CreatedBank => (proto::SlotStatus::CreatedBank, None),
+        SlotStatus::Dead(err) => (proto::SlotStatus::Dead, Some(err.clone())),
+    };
+    SlotUpdate {
+        slot,
+        parent,
+        status: status.into(),
+        dead_error,
+    }
+}
+
+fn transaction_update(
+    transaction: ReplicaTransactionInfoVersions,
+    slot: Slot,
+) -> Option<TransactionUpdate> {
+    let (signature, is_vote, transaction, meta, index) = match transaction {
+        ReplicaTransactionInfoVersions::V0_0_1(info) => (
+            info.signature,
+            info.is_vote,
+            info.transaction,
+            info.transaction_status_meta,
+            0,
+        ),
+        ReplicaTransactionInfoVersions::V0_0_2(info) => (
+            info.signature,
+            info.is_vote,
+            info.transaction,
+            info.transaction_status_meta,
+            info.index,
+        ),
+    };
+    let serialized = match bincode::serialize(&transaction.to_versioned_transaction()) {
+        Ok(serialized) => serialized,
+        Err(err) => {
+            warn!("failed to serialize transaction {signature}: {err}");
+            return None;
+        }
+    };
+    Some(TransactionUpdate {
+        slot,
+        signature: signature.as_ref().to_vec(),
+        is_vote,
+        index: index as u64,
+        err: meta.status.as_ref().err().map(|err| err.to_string()),
+        fee: meta.fee,
+        compute_units_consumed: meta.compute_units_consumed,
+        account_keys: transaction
+            .message()
+            .account_keys()
+            .iter()
+            .map(|key| key.to_bytes().to_vec())
+            .collect(),
+        transaction: serialized,
+    })
+}
+
+fn block_update(blockinfo: ReplicaBlockInfoVersions) -> Option<BlockUpdate> {
+    match blockinfo {
+        ReplicaBlockInfoVersions::V0_0_3(info) => Some(BlockUpdate {
+            slot: info.slot,
+            parent_slot: info.parent_slot,
+            blockhash: info.blockhash.to_string(),
+            parent_blockhash: info.parent_blockhash.to_string(),
+            block_time: info.block_time,
+            block_height: info.block_height,
+            executed_transaction_count: info.executed_transaction_count,
+            entry_count: info.entry_count,
+        }),
+        ReplicaBlockInfoVersions::V0_0_4(info) => Some(BlockUpdate {
+            slot: info.slot,
+            parent_slot: info.parent_slot,
+            blockhash: info.blockhash.to_string(),
+            parent_blockhash: info.parent_blockhash.to_string(),
+            block_time: info.block_time,
+            block_height: info.block_height,
+            executed_transaction_count: info.executed_transaction_count,
+            entry_count: info.entry_count,
+        }),
+        // Older versions lack the parent of the block
+        ReplicaBlockInfoVersions::V0_0_1(_) | ReplicaBlockInfoVersions::V0_0_2(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, agave_geyser_plugin_interface::geyser_plugin_interface::ReplicaAccountInfoV3,
+        solana_sdk::pubkey::Pubkey,
+    };
+
+    #[test]
+    fn test_account_update() {
+        let pubkey = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let info = ReplicaAccountInfoV3 {
+            pubkey: pubkey.as_ref(),
+            lamports: 42,
+            owner: owner.as_ref(),
+            executable: false,
+            rent_epoch: 1,
+            data: &[1, 2, 3],
+            write_version: 9,
+            txn: None,
+        };
+        assert_eq!(
+            account_update(ReplicaAccountInfoVersions::V0_0_3(&info), 5),
+            AccountUpdate {
+                slot: 5,
+                pubkey: pubkey.to_bytes().to_vec(),
+                lamports: 42,
+                owner: owner.to_bytes().to_vec(),
+                executable: false,
+                rent_epoch: 1,
+                data: vec![1, 2, 3],
+                write_version: 9,
+                txn_signature: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_slot_update() {
+        let update = slot_update(3, Some(2), &SlotStatus::Dead("bad block".to_string()));
+        assert_eq!(update.status(), proto::SlotStatus::Dead);
+        assert_eq!(update.dead_error.as_deref(), Some("bad block"));
+        let update = slot_update(3, Some(2), &SlotStatus::Confirmed);
+        assert_eq!(update.status(), proto::SlotStatus::Confirmed);
+        assert_eq!(update.dead_error, None);
+    }
+
+    #[test]
+    fn test_broadcast_without_subscribers() {
+        let plugin = GrpcGeyserPlugin::default();
+        // Not loaded, the update is never built
+        plugin.broadcast(|| unreachable!());
+    }
+}
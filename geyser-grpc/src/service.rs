@@ -0,0 +1,233 @@
+//! The gRPC service fanning out the updates of the validator to subscribers.
+
+use {
+    crate::{
+        config::GrpcConfig,
+        filter::SubscriptionFilter,
+        proto::{
+            geyser_server::Geyser, GetVersionRequest, GetVersionResponse, SubscribeRequest,
+            SubscribeUpdate,
+        },
+    },
+    log::*,
+    std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    tokio::sync::{broadcast, mpsc},
+    tokio_stream::wrappers::ReceiverStream,
+    tonic::{Request, Response, Status},
+};
+
+/// Counts a subscriber for as long as it is alive.
+struct SubscriberGuard(Arc<AtomicUsize>);
+
+impl SubscriberGuard {
+    fn try_new(subscribers: &Arc<AtomicUsize>, max_subscribers: usize) -> Option<Self> {
+        subscribers
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                (count < max_subscribers).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(subscribers)))
+    }
+}
+
+impl Drop for SubscriberGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub struct GeyserService {
+    config: GrpcConfig,
+    updates: broadcast::Sender<Arc<SubscribeUpdate>>,
+    subscribers: Arc<AtomicUsize>,
+}
+
+impl GeyserService {
+    /// Creates the service streaming the updates sent on `updates` to subscribers.
+    pub fn new(config: GrpcConfig, updates: broadcast::Sender<Arc<SubscribeUpdate>>) -> Self {
+        Self {
+            config,
+            updates,
+            subscribers: Arc::default(),
+        }
+    }
+
+    pub fn num_subscribers(&self) -> usize {
+        self.subscribers.load(Ordering::SeqCst)
+    }
+}
+
+#[tonic::async_trait]
+impl Geyser for GeyserService {
+    type SubscribeStream = ReceiverStream<Result<SubscribeUpdate, Status>>;
+
+    async fn subscribe(
+        &self,
+        request: Request<SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let filter = SubscriptionFilter::new(
+            request.get_ref(),
+            self.config.max_filters,
+            self.config.max_filter_pubkeys,
+        )
+        .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        let guard = SubscriberGuard::try_new(&self.subscribers, self.config.max_subscribers)
+            .ok_or_else(|| Status::resource_exhausted("too many subscribers"))?;
+        let peer = request.remote_addr();
+        debug!("gRPC subscriber {peer:?} connected");
+
+        let mut updates = self.updates.subscribe();
+        let (sender, receiver) = mpsc::channel(self.config.subscriber_buffer_size);
+        tokio::spawn(async move {
+            let _guard = guard;
+            loop {
+                let update = tokio::select! {
+                    _ = sender.closed() => break,
+                    update = updates.recv() => update,
+                };
+                match update {
+                    Ok(update) => {
+                        if filter.matches(&update)
+                            && sender
+                                .send(Ok(SubscribeUpdate::clone(&update)))
+                                .await
+                                .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("gRPC subscriber {peer:?} lagged behind by {skipped} updates");
+                        let _ = sender.try_send(Err(Status::data_loss(format!(
+                            "subscriber lagged behind by {skipped} updates"
+                        ))));
+                        break;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            debug!("gRPC subscriber {peer:?} disconnected");
+        });
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+
+    async fn get_version(
+        &self,
+        _request: Request<GetVersionRequest>,
+    ) -> Result<Response<GetVersionResponse>, Status> {
+        Ok(Response::new(GetVersionResponse {
+            version: solana_version::version!().to_string(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::proto::{subscribe_update::Update, BlockUpdate, SlotUpdate},
+        tokio_stream::StreamExt,
+    };
+
+    fn slot_update(slot: u64) -> Arc<SubscribeUpdate> {
+        Arc::new(SubscribeUpdate {
+            update: Some(Update::Slot(SlotUpdate {
+                slot,
+                ..SlotUpdate::default()
+            })),
+        })
+    }
+
+    fn slots_request() -> Request<SubscribeRequest> {
+        Request::new(SubscribeRequest {
+            slots: true,
+            ..SubscribeRequest::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_filters_updates() {
+        let (sender, _) = broadcast::channel(16);
+        let service = GeyserService::new(GrpcConfig::default(), sender.clone());
+        let mut stream = service
+            .subscribe(slots_request())
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(service.num_subscribers(), 1);
+
+        sender
+            .send(Arc::new(SubscribeUpdate {
+                update: Some(Update::Block(BlockUpdate::default())),
+            }))
+            .unwrap();
+        sender.send(slot_update(7)).unwrap();
+        let update = stream.next().await.unwrap().unwrap();
+        assert_eq!(update, *slot_update(7));
+
+        drop(stream);
+        sender.send(slot_update(8)).unwrap();
+        for _ in 0..100 {
+            if service.num_subscribers() == 0 {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(service.num_subscribers(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_limits() {
+        let (sender, _) = broadcast::channel(16);
+        let service = GeyserService::new(
+            GrpcConfig {
+                max_subscribers: 1,
+                ..GrpcConfig::default()
+            },
+            sender,
+        );
+
+        let status = service
+            .subscribe(Request::new(SubscribeRequest::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let _stream = service.subscribe(slots_request()).await.unwrap();
+        let status = service.subscribe(slots_request()).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_is_disconnected() {
+        let (sender, _) = broadcast::channel(2);
+        let service = GeyserService::new(
+            GrpcConfig {
+                subscriber_buffer_size: 1,
+                ..GrpcConfig::default()
+            },
+            sender.clone(),
+        );
+        let mut stream = service
+            .subscribe(slots_request())
+            .await
+            .unwrap()
+            .into_inner();
+
+        for slot in 0..8 {
+            sender.send(slot_update(slot)).unwrap();
+        }
+        let mut lagged = false;
+        while let Some(update) = stream.next().await {
+            if let Err(status) = update {
+                assert_eq!(status.code(), tonic::Code::DataLoss);
+                lagged = true;
+            }
+        }
+        assert!(lagged);
+    }
+}
@@ -77,6 +77,7 @@ impl AccountsUpdateNotifierInterface for AccountsUpdateNotifierImpl {
             let mut measure = Measure::start("geyser-plugin-end-of-restore-from-snapshot");
             match plugin.notify_end_of_startup() {
                 Err(err) => {
+                    plugin.record_notification_error(&err);
                     error!(
                         "Failed to notify the end of restore from snapshot, error: {} to plugin {}",
                         err,
@@ -172,6 +173,7 @@ impl AccountsUpdateNotifierImpl {
                 is_startup,
             ) {
                 Err(err) => {
+                    plugin.record_notification_error(&err);
                     error!(
                         "Failed to update account {} at slot {}, error: {} to plugin {}",
                         bs58::encode(account.pubkey).into_string(),
@@ -56,6 +56,7 @@ impl BlockMetadataNotifier for BlockMetadataNotifierImpl {
             let block_info = ReplicaBlockInfoVersions::V0_0_4(&block_info);
             match plugin.notify_block_metadata(block_info) {
                 Err(err) => {
+                    plugin.record_notification_error(&err);
                     error!(
                         "Failed to update block metadata at slot {}, error: {} to plugin {}",
                         slot,
@@ -41,6 +41,7 @@ impl EntryNotifier for EntryNotifierImpl {
             }
             match plugin.notify_entry(ReplicaEntryInfoVersions::V0_0_2(&entry_info)) {
                 Err(err) => {
+                    plugin.record_notification_error(&err);
                     error!(
                         "Failed to notify entry, error: ({}) to plugin {}",
                         err,
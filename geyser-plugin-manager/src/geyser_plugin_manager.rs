@@ -1,11 +1,17 @@
 use {
-    agave_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin,
+    agave_geyser_plugin_interface::geyser_plugin_interface::{GeyserPlugin, GeyserPluginError},
     jsonrpc_core::{ErrorCode, Result as JsonRpcResult},
     libloading::Library,
     log::*,
+    solana_rpc::rpc_health::PluginHealth,
     std::{
         ops::{Deref, DerefMut},
         path::Path,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex,
+        },
+        time::Instant,
     },
     tokio::sync::oneshot::Sender as OneShotSender,
 };
@@ -22,8 +28,12 @@ pub struct LoadedGeyserPlugin {
     // any code before returning from Drop. This means if the Geyser plugins
     // spawn threads that access the Library, those threads must be `join`ed
     // before the Geyser plugin returns from on_unload / Drop.
+    //
+    // Builtin plugins are compiled into the validator and have no library.
     #[allow(dead_code)]
-    library: Library,
+    library: Option<Library>,
+    notification_errors: AtomicU64,
+    last_error: Mutex<Option<(Instant, String)>>,
 }
 
 impl LoadedGeyserPlugin {
@@ -31,13 +41,43 @@ impl LoadedGeyserPlugin {
         Self {
             name: name.unwrap_or_else(|| plugin.name().to_owned()),
             plugin,
-            library,
+            library: Some(library),
+            notification_errors: AtomicU64::default(),
+            last_error: Mutex::default(),
+        }
+    }
+
+    pub fn new_builtin(plugin: Box<dyn GeyserPlugin>, name: Option<String>) -> Self {
+        Self {
+            name: name.unwrap_or_else(|| plugin.name().to_owned()),
+            plugin,
+            library: None,
+            notification_errors: AtomicU64::default(),
+            last_error: Mutex::default(),
         }
     }
 
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Records a notification the plugin failed to process
+    pub(crate) fn record_notification_error(&self, err: &GeyserPluginError) {
+        self.notification_errors.fetch_add(1, Ordering::Relaxed);
+        *self.last_error.lock().unwrap() = Some((Instant::now(), err.to_string()));
+    }
+
+    pub fn health(&self) -> PluginHealth {
+        let last_error = self.last_error.lock().unwrap().clone();
+        PluginHealth {
+            name: self.name.clone(),
+            notification_errors: self.notification_errors.load(Ordering::Relaxed),
+            last_error_age_secs: last_error
+                .as_ref()
+                .map(|(time, _)| time.elapsed().as_secs()),
+            last_error: last_error.map(|(_, err)| err),
+        }
+    }
 }
 
 impl Deref for LoadedGeyserPlugin {
@@ -115,6 +155,14 @@ impl GeyserPluginManager {
         false
     }
 
+    /// Returns the health of every plugin
+    pub fn plugin_health(&self) -> Vec<PluginHealth> {
+        self.plugins
+            .iter()
+            .map(LoadedGeyserPlugin::health)
+            .collect()
+    }
+
     /// Admin RPC request handler
     pub(crate) fn list_plugins(&self) -> JsonRpcResult<Vec<String>> {
         Ok(self.plugins.iter().map(|p| p.name().to_owned()).collect())
@@ -325,6 +373,9 @@ pub enum GeyserPluginManagerError {
     #[error("Plugin library path is not specified in the config file")]
     LibPathNotSet,
 
+    #[error("Unknown builtin plugin {0}")]
+    UnknownBuiltinPlugin(String),
+
     #[error("Invalid plugin path")]
     InvalidPluginPath,
 
@@ -378,6 +429,22 @@ pub(crate) fn load_plugin_from_config(
         }
     };
 
+    let plugin_name = result["name"].as_str().map(|s| s.to_owned());
+
+    let config_file = geyser_plugin_config_file
+        .as_os_str()
+        .to_str()
+        .ok_or(GeyserPluginManagerError::InvalidPluginPath)?;
+
+    if let Some(builtin) = result["builtin"].as_str() {
+        let plugin = builtin_plugin(builtin)
+            .ok_or_else(|| GeyserPluginManagerError::UnknownBuiltinPlugin(builtin.to_string()))?;
+        return Ok((
+            LoadedGeyserPlugin::new_builtin(plugin, plugin_name),
+            config_file,
+        ));
+    }
+
     let libpath = result["libpath"]
         .as_str()
         .ok_or(GeyserPluginManagerError::LibPathNotSet)?;
@@ -391,13 +458,6 @@ pub(crate) fn load_plugin_from_config(
         libpath = config_dir.join(libpath);
     }
 
-    let plugin_name = result["name"].as_str().map(|s| s.to_owned());
-
-    let config_file = geyser_plugin_config_file
-        .as_os_str()
-        .to_str()
-        .ok_or(GeyserPluginManagerError::InvalidPluginPath)?;
-
     let (plugin, lib) = unsafe {
         let lib = Library::new(libpath)
             .map_err(|e| GeyserPluginManagerError::PluginLoadError(e.to_string()))?;
@@ -413,6 +473,15 @@ pub(crate) fn load_plugin_from_config(
     ))
 }
 
+/// Returns the plugin compiled into the validator named `name` in the `builtin` field of a
+/// plugin config file, which replaces its `libpath`.
+pub fn builtin_plugin(name: &str) -> Option<Box<dyn GeyserPlugin>> {
+    match name {
+        "grpc" => Some(Box::<tachyon_geyser_grpc::GrpcGeyserPlugin>::default()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 const TESTPLUGIN_CONFIG: &str = "TESTPLUGIN_CONFIG";
 #[cfg(test)]
@@ -448,9 +517,10 @@ pub(crate) fn load_plugin_from_config(
 mod tests {
     use {
         crate::geyser_plugin_manager::{
-            GeyserPluginManager, LoadedGeyserPlugin, TESTPLUGIN2_CONFIG, TESTPLUGIN_CONFIG,
+            builtin_plugin, GeyserPluginManager, LoadedGeyserPlugin, TESTPLUGIN2_CONFIG,
+            TESTPLUGIN_CONFIG,
         },
-        agave_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin,
+        agave_geyser_plugin_interface::geyser_plugin_interface::{GeyserPlugin, GeyserPluginError},
         libloading::Library,
         std::sync::{Arc, RwLock},
     };
@@ -552,6 +622,28 @@ mod tests {
         assert!(plugins.iter().any(|name| name.eq(ANOTHER_DUMMY_NAME)));
     }
 
+    #[test]
+    fn test_plugin_health() {
+        let mut plugin_manager = GeyserPluginManager::new();
+        let (plugin, _config) = dummy_plugin_and_library(TestPlugin, DUMMY_CONFIG);
+        plugin_manager.plugins.push(plugin);
+        let health = plugin_manager.plugin_health();
+        assert_eq!(health.len(), 1);
+        assert_eq!(health[0].name, DUMMY_NAME);
+        assert_eq!(health[0].notification_errors, 0);
+        assert_eq!(health[0].last_error_age_secs, None);
+
+        let err = GeyserPluginError::SlotStatusUpdateError {
+            msg: "unavailable".to_string(),
+        };
+        plugin_manager.plugins[0].record_notification_error(&err);
+        plugin_manager.plugins[0].record_notification_error(&err);
+        let health = plugin_manager.plugin_health();
+        assert_eq!(health[0].notification_errors, 2);
+        assert!(health[0].last_error_age_secs.is_some());
+        assert_eq!(health[0].last_error, Some(err.to_string()));
+    }
+
     #[test]
     fn test_plugin_load_unload() {
         // Initialize empty manager
@@ -568,4 +660,12 @@ mod tests {
         assert!(unload_result.is_ok());
         assert_eq!(plugin_manager_lock.plugins.len(), 0);
     }
+
+    #[test]
+    fn test_builtin_plugin() {
+        let plugin = LoadedGeyserPlugin::new_builtin(builtin_plugin("grpc").unwrap(), None);
+        assert_eq!(plugin.name(), tachyon_geyser_grpc::plugin::PLUGIN_NAME);
+        assert!(plugin.account_data_notifications_enabled());
+        assert!(builtin_plugin("libpath").is_none());
+    }
 }
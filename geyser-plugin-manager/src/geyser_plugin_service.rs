@@ -15,6 +15,7 @@ use {
     solana_ledger::entry_notifier_interface::EntryNotifierArc,
     solana_rpc::{
         optimistically_confirmed_bank_tracker::SlotNotification,
+        rpc_health::{PluginHealth, PluginHealthReporter, PluginHealthReporterArc},
         slot_status_notifier::SlotStatusNotifier,
         transaction_notifier_interface::TransactionNotifierArc,
     },
@@ -30,6 +31,16 @@ use {
     thiserror::Error,
 };
 
+struct PluginHealthReporterImpl {
+    plugin_manager: Arc<RwLock<GeyserPluginManager>>,
+}
+
+impl PluginHealthReporter for PluginHealthReporterImpl {
+    fn plugin_health(&self) -> Vec<PluginHealth> {
+        self.plugin_manager.read().unwrap().plugin_health()
+    }
+}
+
 /// The service managing the Geyser plugin workflow.
 pub struct GeyserPluginService {
     slot_status_observer: Option<SlotStatusObserver>,
@@ -56,6 +67,8 @@ impl GeyserPluginService {
     ///    shall create the implementation of `GeyserPlugin` and returns to the caller.
     ///    The rest of the JSON fields' definition is up to to the concrete plugin implementation
     ///    It is usually used to configure the connection information for the external data store.
+    ///    Plugins compiled into the validator, such as the `grpc` streaming service, are loaded by
+    ///    naming them in a `builtin` field in place of `libpath`.
     pub fn new(
         confirmed_bank_receiver: Receiver<SlotNotification>,
         geyser_plugin_always_enabled: bool,
@@ -196,6 +209,13 @@ impl GeyserPluginService {
         self.slot_status_notifier.clone()
     }
 
+    /// Returns the reporter of the health of the plugins, served by the RPC `/health` endpoint
+    pub fn get_plugin_health_reporter(&self) -> PluginHealthReporterArc {
+        Arc::new(PluginHealthReporterImpl {
+            plugin_manager: self.plugin_manager.clone(),
+        })
+    }
+
     pub fn join(self) -> thread::Result<()> {
         if let Some(mut slot_status_observer) = self.slot_status_observer {
             slot_status_observer.join()?;
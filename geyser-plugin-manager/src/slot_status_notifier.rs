@@ -58,6 +58,7 @@ impl SlotStatusNotifierImpl {
             let mut measure = Measure::start("geyser-plugin-update-slot");
             match plugin.update_slot_status(slot, parent, &slot_status) {
                 Err(err) => {
+                    plugin.record_notification_error(&err);
                     error!(
                         "Failed to update slot status at slot {}, error: {} to plugin {}",
                         slot,
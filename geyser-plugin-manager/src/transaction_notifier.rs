@@ -55,6 +55,7 @@ impl TransactionNotifier for TransactionNotifierImpl {
                 slot,
             ) {
                 Err(err) => {
+                    plugin.record_notification_error(&err);
                     error!(
                         "Failed to notify transaction, error: ({}) to plugin {}",
                         err,
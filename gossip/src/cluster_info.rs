@@ -32,6 +32,7 @@ use {
         epoch_slots::EpochSlots,
         epoch_specs::EpochSpecs,
         gossip_error::GossipError,
+        peer_filter::PeerFilter,
         ping_pong::Pong,
         protocol::{
             split_gossip_messages, Ping, PingCache, Protocol, PruneData,
@@ -40,7 +41,8 @@ use {
             PULL_RESPONSE_MIN_SERIALIZED_SIZE, PUSH_MESSAGE_MAX_PAYLOAD_SIZE,
         },
         restart_crds_values::{
-            RestartHeaviestFork, RestartLastVotedForkSlots, RestartLastVotedForkSlotsError,
+            HardForkProposal, RestartHeaviestFork, RestartLastVotedForkSlots,
+            RestartLastVotedForkSlotsError,
         },
         weighted_shuffle::WeightedShuffle,
     },
@@ -158,6 +160,8 @@ pub struct ClusterInfo {
     contact_save_interval: u64,  // milliseconds, 0 = disabled
     contact_info_path: PathBuf,
     socket_addr_space: SocketAddrSpace,
+    /// Operator configured allow and deny lists of peers
+    peer_filter: RwLock<PeerFilter>,
 }
 
 struct PullData {
@@ -193,6 +197,7 @@ fn should_retain_crds_value(
         | CrdsData::DuplicateShred(_, _)
         | CrdsData::RestartHeaviestFork(_)
         | CrdsData::RestartLastVotedForkSlots(_)
+        | CrdsData::HardForkProposal(_)
         | CrdsData::NodeInstance(_) => {
             stakes.len() < MIN_NUM_STAKED_NODES || {
                 let stake = stakes.get(&value.pubkey()).copied();
@@ -228,6 +233,7 @@ impl ClusterInfo {
             contact_info_path: PathBuf::default(),
             contact_save_interval: 0, // disabled
             socket_addr_space,
+            peer_filter: RwLock::default(),
         };
         me.refresh_my_gossip_contact_info();
         me
@@ -241,6 +247,14 @@ impl ClusterInfo {
         &self.socket_addr_space
     }
 
+    pub fn peer_filter(&self) -> RwLockReadGuard<PeerFilter> {
+        self.peer_filter.read().unwrap()
+    }
+
+    pub fn set_peer_filter(&self, peer_filter: PeerFilter) {
+        *self.peer_filter.write().unwrap() = peer_filter;
+    }
+
     fn refresh_push_active_set(
         &self,
         recycler: &PacketBatchRecycler,
@@ -743,6 +757,26 @@ impl ClusterInfo {
         ));
     }
 
+    pub fn push_hard_fork_proposal(
+        &self,
+        slot: Slot,
+        bank_hash: Option<Hash>,
+        expected_shred_version: Option<u16>,
+    ) {
+        let proposal = HardForkProposal {
+            from: self.id(),
+            wallclock: timestamp(),
+            slot,
+            bank_hash,
+            expected_shred_version,
+            shred_version: self.my_shred_version(),
+        };
+        self.push_message(CrdsValue::new(
+            CrdsData::HardForkProposal(proposal),
+            &self.keypair(),
+        ));
+    }
+
     fn time_gossip_read_lock<'a>(
         &'a self,
         label: &'static str,
@@ -1010,6 +1044,23 @@ impl ClusterInfo {
             .collect()
     }
 
+    /// Returns hard fork proposals of nodes in the same cluster inserted
+    /// since the given cursor, including the proposal of this node.
+    pub fn get_hard_fork_proposals(&self, cursor: &mut Cursor) -> Vec<HardForkProposal> {
+        let self_shred_version = self.my_shred_version();
+        let gossip_crds = self.gossip.crds.read().unwrap();
+        gossip_crds
+            .get_entries(cursor)
+            .filter_map(|entry| {
+                let CrdsData::HardForkProposal(proposal) = entry.value.data() else {
+                    return None;
+                };
+                (proposal.shred_version == self_shred_version).then_some(proposal)
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Returns duplicate-shreds inserted since the given cursor.
     pub(crate) fn get_duplicate_shreds(&self, cursor: &mut Cursor) -> Vec<DuplicateShred> {
         let gossip_crds = self.gossip.crds.read().unwrap();
@@ -1414,8 +1465,11 @@ impl ClusterInfo {
     }
 
     // Trims the CRDS table by dropping all values associated with the pubkeys
-    // with the lowest stake, so that the number of unique pubkeys are bounded.
+    // with the lowest stake, so that the number of unique pubkeys are bounded,
+    // and the least recently updated values of the pubkeys exceeding their
+    // stake-weighted quota of values or bytes.
     fn trim_crds_table(&self, cap: usize, stakes: &HashMap<Pubkey, u64>) {
+        self.trim_crds_origins(stakes);
         if !self.gossip.crds.read().unwrap().should_trim(cap) {
             return;
         }
@@ -1445,6 +1499,25 @@ impl ClusterInfo {
         }
     }
 
+    fn trim_crds_origins(&self, stakes: &HashMap<Pubkey, u64>) {
+        if !self.gossip.crds.read().unwrap().should_trim_origins() {
+            return;
+        }
+        let self_pubkey = self.id();
+        let (num_origins, num_evicted) =
+            self.gossip
+                .crds
+                .write()
+                .unwrap()
+                .trim_origins(&[self_pubkey], stakes, timestamp());
+        self.stats
+            .trim_crds_origins_count
+            .add_relaxed(num_origins as u64);
+        self.stats
+            .trim_crds_origins_evicted_values_count
+            .add_relaxed(num_evicted as u64);
+    }
+
     /// randomly pick a node and ask them for updates asynchronously
     pub fn gossip(
         self: Arc<Self>,
@@ -2147,18 +2220,28 @@ impl ClusterInfo {
         fn verify_packet(
             packet: &Packet,
             stakes: &HashMap<Pubkey, u64>,
+            peer_filter: &PeerFilter,
             stats: &GossipStats,
         ) -> Option<(SocketAddr, Protocol)> {
+            if !peer_filter.is_allowed_ip(&packet.meta().addr) {
+                stats.packets_received_denied_count.add_relaxed(1);
+                return None;
+            }
             let mut protocol: Protocol =
                 stats.record_received_packet(packet.deserialize_slice::<Protocol, _>(..))?;
             protocol.sanitize().ok()?;
+            if !peer_filter.is_allowed_pubkey(&protocol.sender()) {
+                stats.packets_received_denied_count.add_relaxed(1);
+                return None;
+            }
             if let Protocol::PullResponse(_, values) | Protocol::PushMessage(_, values) =
                 &mut protocol
             {
                 values.retain(|value| {
-                    should_retain_crds_value(
-                        value, stakes, /*drop_unstaked_node_instance:*/ false,
-                    )
+                    peer_filter.is_allowed_pubkey(&value.pubkey())
+                        && should_retain_crds_value(
+                            value, stakes, /*drop_unstaked_node_instance:*/ false,
+                        )
                 });
                 if values.is_empty() {
                     return None;
@@ -2175,17 +2258,22 @@ impl ClusterInfo {
             .unwrap_or_default();
         let packets: Vec<_> = {
             let _st = ScopedTimer::from(&self.stats.verify_gossip_packets_time);
+            let peer_filter = self.peer_filter();
             thread_pool.install(|| {
                 if packets.len() == 1 {
                     packets[0]
                         .par_iter()
-                        .filter_map(|packet| verify_packet(packet, &stakes, &self.stats))
+                        .filter_map(|packet| {
+                            verify_packet(packet, &stakes, &peer_filter, &self.stats)
+                        })
                         .collect()
                 } else {
                     packets
                         .par_iter()
                         .flatten()
-                        .filter_map(|packet| verify_packet(packet, &stakes, &self.stats))
+                        .filter_map(|packet| {
+                            verify_packet(packet, &stakes, &peer_filter, &self.stats)
+                        })
                         .collect()
                 }
             })
@@ -4288,6 +4376,59 @@ mod tests {
         assert_eq!(heaviest_forks[0].from, pubkey2);
     }
 
+    #[test]
+    fn test_push_hard_fork_proposal() {
+        solana_logger::setup();
+        let keypair = Arc::new(Keypair::new());
+        let pubkey = keypair.pubkey();
+        let contact_info = ContactInfo::new_localhost(&pubkey, 0);
+        let cluster_info = ClusterInfo::new(contact_info, keypair, SocketAddrSpace::Unspecified);
+
+        let mut cursor = Cursor::default();
+        assert_eq!(cluster_info.get_hard_fork_proposals(&mut cursor), vec![]);
+
+        let bank_hash = Hash::new_unique();
+        cluster_info.push_hard_fork_proposal(53, Some(bank_hash), Some(42));
+        cluster_info.flush_push_queue();
+        let proposals = cluster_info.get_hard_fork_proposals(&mut cursor);
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].from, pubkey);
+        assert_eq!(proposals[0].slot, 53);
+        assert_eq!(proposals[0].bank_hash, Some(bank_hash));
+        assert_eq!(proposals[0].expected_shred_version, Some(42));
+        assert_eq!(cluster_info.get_hard_fork_proposals(&mut cursor), vec![]);
+
+        // A newer proposal of the same node overrides the previous one.
+        sleep(Duration::from_millis(1));
+        cluster_info.push_hard_fork_proposal(54, None, None);
+        cluster_info.flush_push_queue();
+        let proposals = cluster_info.get_hard_fork_proposals(&mut Cursor::default());
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].slot, 54);
+        assert_eq!(proposals[0].bank_hash, None);
+
+        // Proposals from nodes with a different shred version are excluded.
+        let pubkey2 = Pubkey::new_unique();
+        let proposal = CrdsValue::new_unsigned(CrdsData::HardForkProposal(HardForkProposal {
+            from: pubkey2,
+            wallclock: timestamp(),
+            slot: 54,
+            bank_hash: None,
+            expected_shred_version: None,
+            shred_version: 42,
+        }));
+        assert!(cluster_info
+            .gossip
+            .crds
+            .write()
+            .unwrap()
+            .insert(proposal, /*now=*/ 0, GossipRoute::LocalMessage)
+            .is_ok());
+        let proposals = cluster_info.get_hard_fork_proposals(&mut Cursor::default());
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].from, pubkey);
+    }
+
     #[test]
     fn test_contact_trace() {
         solana_logger::setup();
@@ -127,6 +127,7 @@ pub struct GossipStats {
     packets_received_pull_requests_count: Counter,
     packets_received_pull_responses_count: Counter,
     packets_received_push_messages_count: Counter,
+    pub(crate) packets_received_denied_count: Counter,
     packets_received_unknown_count: Counter,
     pub(crate) packets_received_verified_count: Counter,
     packets_sent_ping_messages_count: Counter,
@@ -163,6 +164,8 @@ pub struct GossipStats {
     pub(crate) trim_crds_table: Counter,
     pub(crate) trim_crds_table_failed: Counter,
     pub(crate) trim_crds_table_purged_values_count: Counter,
+    pub(crate) trim_crds_origins_count: Counter,
+    pub(crate) trim_crds_origins_evicted_values_count: Counter,
     pub(crate) tvu_peers: Counter,
     pub(crate) verify_gossip_packets_time: Counter,
     pub(crate) window_request_loopback: Counter,
@@ -232,11 +235,20 @@ pub(crate) fn submit_gossip_stats(
     gossip: &CrdsGossip,
     stakes: &HashMap<Pubkey, u64>,
 ) {
-    let (crds_stats, table_size, num_nodes, num_pubkeys, purged_values_size, failed_inserts_size) = {
+    let (
+        crds_stats,
+        table_size,
+        table_bytes,
+        num_nodes,
+        num_pubkeys,
+        purged_values_size,
+        failed_inserts_size,
+    ) = {
         let gossip_crds = gossip.crds.read().unwrap();
         (
             gossip_crds.take_stats(),
             gossip_crds.len(),
+            gossip_crds.num_bytes(),
             gossip_crds.num_nodes(),
             gossip_crds.num_pubkeys(),
             gossip_crds.num_purged(),
@@ -265,6 +277,7 @@ pub(crate) fn submit_gossip_stats(
         ("all_tvu_peers", stats.all_tvu_peers.clear(), i64),
         ("tvu_peers", stats.tvu_peers.clear(), i64),
         ("table_size", table_size as i64, i64),
+        ("table_bytes", table_bytes as i64, i64),
         ("purged_values_size", purged_values_size as i64, i64),
         ("failed_inserts_size", failed_inserts_size as i64, i64),
         ("num_nodes", num_nodes as i64, i64),
@@ -547,6 +560,11 @@ pub(crate) fn submit_gossip_stats(
             stats.packets_received_push_messages_count.clear(),
             i64
         ),
+        (
+            "packets_received_denied_count",
+            stats.packets_received_denied_count.clear(),
+            i64
+        ),
         (
             "packets_received_unknown_count",
             stats.packets_received_unknown_count.clear(),
@@ -603,6 +621,16 @@ pub(crate) fn submit_gossip_stats(
             stats.trim_crds_table_purged_values_count.clear(),
             i64
         ),
+        (
+            "trim_crds_origins_count",
+            stats.trim_crds_origins_count.clear(),
+            i64
+        ),
+        (
+            "trim_crds_origins_evicted_values_count",
+            stats.trim_crds_origins_evicted_values_count.clear(),
+            i64
+        ),
     );
     datapoint_info!(
         "cluster_info_crds_stats",
@@ -642,6 +670,8 @@ pub(crate) fn submit_gossip_stats(
         ),
         ("RestartHeaviestFork-push", crds_stats.push.counts[13], i64),
         ("RestartHeaviestFork-pull", crds_stats.pull.counts[13], i64),
+        ("HardForkProposal-push", crds_stats.push.counts[14], i64),
+        ("HardForkProposal-pull", crds_stats.pull.counts[14], i64),
         (
             "all-push",
             crds_stats.push.counts.iter().sum::<usize>(),
@@ -691,6 +721,8 @@ pub(crate) fn submit_gossip_stats(
         ),
         ("RestartHeaviestFork-push", crds_stats.push.fails[13], i64),
         ("RestartHeaviestFork-pull", crds_stats.pull.fails[13], i64),
+        ("HardForkProposal-push", crds_stats.push.fails[14], i64),
+        ("HardForkProposal-pull", crds_stats.pull.fails[14], i64),
         ("all-push", crds_stats.push.fails.iter().sum::<usize>(), i64),
         ("all-pull", crds_stats.pull.fails.iter().sum::<usize>(), i64),
     );
@@ -287,6 +287,7 @@ impl ContactInfo {
         SOCKET_TAG_SERVE_REPAIR,
         SOCKET_TAG_SERVE_REPAIR_QUIC
     );
+    remove_socket!(remove_serve_repair_quic, SOCKET_TAG_SERVE_REPAIR_QUIC);
     remove_socket!(remove_tpu, SOCKET_TAG_TPU, SOCKET_TAG_TPU_QUIC);
     remove_socket!(
         remove_tpu_forwards,
@@ -44,7 +44,7 @@ use {
     solana_sdk::{clock::Slot, hash::Hash, pubkey::Pubkey, signature::Signature},
     std::{
         cmp::Ordering,
-        collections::{hash_map, BTreeMap, HashMap, VecDeque},
+        collections::{hash_map, BTreeMap, HashMap, HashSet, VecDeque},
         ops::{Bound, Index, IndexMut},
         sync::Mutex,
     },
@@ -60,6 +60,16 @@ const VOTE_SLOTS_METRICS_CAP: usize = 100;
 // target: 1 signature reported per minute
 // log2(500k) = ~18.9.
 const SIGNATURE_SAMPLE_LEADING_ZEROS: u32 = 19;
+// Quotas of values and serialized bytes per origin in the table. Contact-infos,
+// votes and epoch-slots are bounded per origin by their index and are neither
+// counted against the quota nor evicted. Every origin is allowed the minimum
+// for its other values; staked origins up to the maximum in proportion to
+// their stake relative to the largest stake, which leaves room for a full set
+// of duplicate-shred proofs.
+const CRDS_ORIGIN_MIN_VALUES: usize = 320;
+const CRDS_ORIGIN_MAX_VALUES: usize = 1_024;
+const CRDS_ORIGIN_MIN_BYTES: usize = 384 * 1024;
+const CRDS_ORIGIN_MAX_BYTES: usize = 1024 * 1024;
 
 pub struct Crds {
     /// Stores the map of labels and values
@@ -75,6 +85,12 @@ pub struct Crds {
     duplicate_shreds: BTreeMap<u64 /*insert order*/, usize /*index*/>,
     // Indices of all crds values associated with a node.
     records: HashMap<Pubkey, IndexSet<usize>>,
+    // Serialized size of all crds values associated with a node.
+    record_bytes: HashMap<Pubkey, usize>,
+    // Serialized size of all crds values.
+    num_bytes: usize,
+    // Nodes which exceeded the minimum origin quota since the last trim.
+    oversized_origins: HashSet<Pubkey>,
     // Indices of all entries keyed by insert order.
     entries: BTreeMap<u64 /*insert order*/, usize /*index*/>,
     // Hash of recently purged values.
@@ -99,7 +115,7 @@ pub enum GossipRoute<'a> {
     PushMessage(/*from:*/ &'a Pubkey),
 }
 
-type CrdsCountsArray = [usize; 14];
+type CrdsCountsArray = [usize; 15];
 
 pub(crate) struct CrdsDataStats {
     pub(crate) counts: CrdsCountsArray,
@@ -175,6 +191,9 @@ impl Default for Crds {
             epoch_slots: BTreeMap::default(),
             duplicate_shreds: BTreeMap::default(),
             records: HashMap::default(),
+            record_bytes: HashMap::default(),
+            num_bytes: 0,
+            oversized_origins: HashSet::default(),
             entries: BTreeMap::default(),
             purged: VecDeque::default(),
             shred_versions: HashMap::default(),
@@ -236,6 +255,7 @@ impl Crds {
     ) -> Result<(), CrdsError> {
         let label = value.label();
         let pubkey = value.pubkey();
+        let num_bytes = value.bincode_serialized_size();
         let value = VersionedCrdsValue::new(value, self.cursor, now, route);
         let mut stats = self.stats.lock().unwrap();
         match self.table.entry(label) {
@@ -263,6 +283,8 @@ impl Crds {
                 self.records.entry(pubkey).or_default().insert(entry_index);
                 self.cursor.consume(value.ordinal);
                 entry.insert(value);
+                drop(stats);
+                self.add_record_bytes(pubkey, num_bytes, 0);
                 Ok(())
             }
             Entry::Occupied(mut entry) if overrides(&value.value, entry.get()) => {
@@ -298,7 +320,9 @@ impl Crds {
                 debug_assert_eq!(entry.get().value.pubkey(), pubkey);
                 self.cursor.consume(value.ordinal);
                 self.purged.push_back((*entry.get().value.hash(), now));
-                entry.insert(value);
+                let old_value = entry.insert(value);
+                drop(stats);
+                self.add_record_bytes(pubkey, num_bytes, old_value.value.bincode_serialized_size());
                 Ok(())
             }
             Entry::Occupied(mut entry) => {
@@ -330,6 +354,26 @@ impl Crds {
         }
     }
 
+    // Updates the serialized size of the values of the node, replacing a value
+    // of size old_num_bytes with one of size num_bytes, and tracks the nodes
+    // exceeding the minimum origin quota.
+    fn add_record_bytes(&mut self, pubkey: Pubkey, num_bytes: usize, old_num_bytes: usize) {
+        let record_bytes = self.record_bytes.entry(pubkey).or_default();
+        *record_bytes = record_bytes
+            .saturating_add(num_bytes)
+            .saturating_sub(old_num_bytes);
+        self.num_bytes = self
+            .num_bytes
+            .saturating_add(num_bytes)
+            .saturating_sub(old_num_bytes);
+        let num_values = self.records.get(&pubkey).map(IndexSet::len);
+        if *record_bytes > CRDS_ORIGIN_MIN_BYTES
+            || num_values.unwrap_or_default() > CRDS_ORIGIN_MIN_VALUES
+        {
+            self.oversized_origins.insert(pubkey);
+        }
+    }
+
     pub fn get<'a, 'b, V>(&'a self, key: V::Key) -> Option<V>
     where
         V: CrdsEntry<'a, 'b>,
@@ -430,6 +474,24 @@ impl Crds {
         self.table.len()
     }
 
+    /// Returns the serialized size of all values in the table.
+    pub(crate) fn num_bytes(&self) -> usize {
+        self.num_bytes
+    }
+
+    /// Returns the number of values and their serialized size associated with
+    /// a pubkey.
+    #[cfg(test)]
+    pub(crate) fn origin_usage(&self, pubkey: &Pubkey) -> (usize, usize) {
+        (
+            self.records
+                .get(pubkey)
+                .map(IndexSet::len)
+                .unwrap_or_default(),
+            self.record_bytes.get(pubkey).copied().unwrap_or_default(),
+        )
+    }
+
     pub fn is_empty(&self) -> bool {
         self.table.is_empty()
     }
@@ -573,9 +635,14 @@ impl Crds {
             panic!("this should not happen!");
         };
         records_entry.get_mut().swap_remove(&index);
+        let num_bytes = value.value.bincode_serialized_size();
+        self.num_bytes = self.num_bytes.saturating_sub(num_bytes);
         if records_entry.get().is_empty() {
             records_entry.remove();
             self.shred_versions.remove(&pubkey);
+            self.record_bytes.remove(&pubkey);
+        } else if let Some(record_bytes) = self.record_bytes.get_mut(&pubkey) {
+            *record_bytes = record_bytes.saturating_sub(num_bytes);
         }
         // If index == self.table.len(), then the removed entry was the last
         // entry in the table, in which case no other keys were modified.
@@ -672,11 +739,98 @@ impl Crds {
         Ok(keys.len())
     }
 
+    /// Returns true if some nodes exceeded the minimum origin quota since the
+    /// last call to trim_origins.
+    /// Allows skipping unnecessary calls to trim_origins without obtaining a
+    /// write lock on gossip.
+    pub(crate) fn should_trim_origins(&self) -> bool {
+        !self.oversized_origins.is_empty()
+    }
+
+    /// Evicts the least recently updated values of the nodes exceeding their
+    /// quota of values or serialized bytes, which grows with their stake.
+    /// Contact-infos, votes and epoch-slots are exempt from the quota, so that
+    /// the nodes are not purged and their consensus data is not evicted.
+    pub(crate) fn trim_origins(
+        &mut self,
+        // Set of pubkeys to never trim.
+        // e.g. self pubkey
+        keep: &[Pubkey],
+        stakes: &HashMap<Pubkey, u64>,
+        now: u64,
+    ) -> (/*num origins:*/ usize, /*num evicted:*/ usize) {
+        let max_stake = stakes.values().copied().max().unwrap_or_default();
+        let mut num_origins = 0;
+        let mut num_evicted = 0;
+        for pubkey in std::mem::take(&mut self.oversized_origins) {
+            if keep.contains(&pubkey) {
+                continue;
+            }
+            let stake = stakes.get(&pubkey).copied().unwrap_or_default();
+            let (max_values, max_bytes) = origin_quota(stake, max_stake);
+            let Some(indices) = self.records.get(&pubkey) else {
+                continue;
+            };
+            let mut entries: Vec<_> = indices
+                .iter()
+                .map(|&index| self.table.get_index(index).unwrap())
+                .filter(|(_, entry)| {
+                    !matches!(
+                        entry.value.data(),
+                        CrdsData::ContactInfo(_)
+                            | CrdsData::Vote(_, _)
+                            | CrdsData::EpochSlots(_, _)
+                    )
+                })
+                .map(|(label, entry)| {
+                    (
+                        entry.ordinal,
+                        label.clone(),
+                        entry.value.bincode_serialized_size(),
+                    )
+                })
+                .collect();
+            let mut num_values = entries.len();
+            let mut num_bytes: usize = entries.iter().map(|(_, _, size)| size).sum();
+            if num_values <= max_values && num_bytes <= max_bytes {
+                continue;
+            }
+            entries.sort_unstable_by_key(|(ordinal, _, _)| *ordinal);
+            num_origins += 1;
+            for (_, label, size) in entries {
+                if num_values <= max_values && num_bytes <= max_bytes {
+                    break;
+                }
+                self.remove(&label, now);
+                num_values -= 1;
+                num_bytes = num_bytes.saturating_sub(size);
+                num_evicted += 1;
+            }
+        }
+        (num_origins, num_evicted)
+    }
+
     pub(crate) fn take_stats(&self) -> CrdsStats {
         std::mem::take(&mut self.stats.lock().unwrap())
     }
 }
 
+// Returns the quota of values and serialized bytes of an origin in the table,
+// in proportion to its stake relative to the largest stake.
+fn origin_quota(stake: u64, max_stake: u64) -> (/*values:*/ usize, /*bytes:*/ usize) {
+    let scale = |min: usize, max: usize| {
+        if max_stake == 0 {
+            return min;
+        }
+        let extra = (max - min) as u128 * u128::from(stake.min(max_stake)) / u128::from(max_stake);
+        min + extra as usize
+    };
+    (
+        scale(CRDS_ORIGIN_MIN_VALUES, CRDS_ORIGIN_MAX_VALUES),
+        scale(CRDS_ORIGIN_MIN_BYTES, CRDS_ORIGIN_MAX_BYTES),
+    )
+}
+
 impl Default for CrdsDataStats {
     fn default() -> Self {
         Self {
@@ -743,6 +897,7 @@ impl CrdsDataStats {
             CrdsData::ContactInfo(_) => 11,
             CrdsData::RestartLastVotedForkSlots(_) => 12,
             CrdsData::RestartHeaviestFork(_) => 13,
+            CrdsData::HardForkProposal(_) => 14,
             // Update CrdsCountsArray if new items are added here.
         }
     }
@@ -781,7 +936,13 @@ fn should_report_message_signature(signature: &Signature) -> bool {
 mod tests {
     use {
         super::*,
-        crate::crds_data::{new_rand_timestamp, AccountsHashes, NodeInstance},
+        crate::{
+            crds_data::{
+                new_rand_timestamp, AccountsHashes, NodeInstance, Vote, MAX_EPOCH_SLOTS, MAX_VOTES,
+            },
+            duplicate_shred::{DuplicateShred, MAX_DUPLICATE_SHREDS},
+            epoch_slots::EpochSlots,
+        },
         rand::{thread_rng, Rng, SeedableRng},
         rand_chacha::ChaChaRng,
         rayon::ThreadPoolBuilder,
@@ -1298,7 +1459,17 @@ mod tests {
                     let value = crds.table.index(*index);
                     assert_eq!(*pubkey, value.value.pubkey());
                 }
+                let num_bytes: usize = indices
+                    .iter()
+                    .map(|index| crds.table.index(*index).value.bincode_serialized_size())
+                    .sum();
+                assert_eq!(crds.record_bytes[pubkey], num_bytes);
             }
+            assert_eq!(crds.record_bytes.len(), crds.records.len());
+            assert_eq!(
+                crds.num_bytes,
+                crds.record_bytes.values().copied().sum::<usize>()
+            );
         }
         let mut rng = thread_rng();
         let keypairs: Vec<_> = repeat_with(Keypair::new).take(128).collect();
@@ -1446,6 +1617,126 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_origin_quota() {
+        assert_eq!(
+            origin_quota(0, 0),
+            (CRDS_ORIGIN_MIN_VALUES, CRDS_ORIGIN_MIN_BYTES)
+        );
+        assert_eq!(
+            origin_quota(0, 1_000),
+            (CRDS_ORIGIN_MIN_VALUES, CRDS_ORIGIN_MIN_BYTES)
+        );
+        assert_eq!(
+            origin_quota(1_000, 1_000),
+            (CRDS_ORIGIN_MAX_VALUES, CRDS_ORIGIN_MAX_BYTES)
+        );
+        let (values, bytes) = origin_quota(500, 1_000);
+        assert_eq!(
+            values,
+            (CRDS_ORIGIN_MIN_VALUES + CRDS_ORIGIN_MAX_VALUES) / 2
+        );
+        assert_eq!(bytes, (CRDS_ORIGIN_MIN_BYTES + CRDS_ORIGIN_MAX_BYTES) / 2);
+    }
+
+    #[test]
+    fn test_trim_origins() {
+        let mut rng = ChaChaRng::from_seed([29u8; 32]);
+        let keypairs: Vec<_> = repeat_with(Keypair::new).take(3).collect();
+        let [unstaked, staked, kept] = [0, 1, 2].map(|k| keypairs[k].pubkey());
+        let stakes = HashMap::from([(staked, 1_000), (Pubkey::new_unique(), 1_000)]);
+        let mut crds = Crds::default();
+        for keypair in &keypairs {
+            let pubkey = keypair.pubkey();
+            let node = ContactInfo::new_localhost(&pubkey, /*wallclock:*/ 0);
+            let node = CrdsValue::new(CrdsData::ContactInfo(node), keypair);
+            crds.insert(node, /*now=*/ 0, GossipRoute::LocalMessage)
+                .unwrap();
+            // Votes and epoch-slots are inserted first, so that they are the
+            // least recently updated values of the origin.
+            for k in 0..MAX_VOTES {
+                let vote = Vote::new_rand(&mut rng, Some(pubkey));
+                let value = CrdsValue::new(CrdsData::Vote(k, vote), keypair);
+                crds.insert(value, /*now=*/ 0, GossipRoute::LocalMessage)
+                    .unwrap();
+            }
+            for k in 0..MAX_EPOCH_SLOTS {
+                let epoch_slots = EpochSlots::new_rand(&mut rng, Some(pubkey));
+                let value = CrdsValue::new(CrdsData::EpochSlots(k, epoch_slots), keypair);
+                crds.insert(value, /*now=*/ 0, GossipRoute::LocalMessage)
+                    .unwrap();
+            }
+            // A full set of duplicate-shred proofs.
+            for k in 0..MAX_DUPLICATE_SHREDS {
+                let shred = DuplicateShred::new_rand(&mut rng, pubkey, u64::from(k), 1_000);
+                let value = CrdsValue::new(CrdsData::DuplicateShred(k, shred), keypair);
+                crds.insert(value, /*now=*/ 0, GossipRoute::LocalMessage)
+                    .unwrap();
+            }
+        }
+        // Returns the number of votes, epoch-slots and duplicate-shreds of an
+        // origin, and the serialized size of its duplicate-shreds.
+        let count = |crds: &Crds, pubkey: &Pubkey| {
+            crds.get_records(pubkey).fold(
+                (0, 0, 0, 0),
+                |(votes, epoch_slots, shreds, bytes), entry| match entry.value.data() {
+                    CrdsData::Vote(_, _) => (votes + 1, epoch_slots, shreds, bytes),
+                    CrdsData::EpochSlots(_, _) => (votes, epoch_slots + 1, shreds, bytes),
+                    CrdsData::DuplicateShred(_, _) => (
+                        votes,
+                        epoch_slots,
+                        shreds + 1,
+                        bytes + entry.value.bincode_serialized_size(),
+                    ),
+                    _ => (votes, epoch_slots, shreds, bytes),
+                },
+            )
+        };
+        let usage = |crds: &Crds| [unstaked, staked, kept].map(|k| crds.origin_usage(&k));
+        let before = usage(&crds);
+        for pubkey in [unstaked, staked, kept] {
+            let (votes, epoch_slots, shreds, bytes) = count(&crds, &pubkey);
+            assert_eq!(votes, usize::from(MAX_VOTES));
+            assert_eq!(epoch_slots, usize::from(MAX_EPOCH_SLOTS));
+            assert_eq!(shreds, usize::from(MAX_DUPLICATE_SHREDS));
+            assert!(bytes > CRDS_ORIGIN_MIN_BYTES);
+            assert!(bytes < CRDS_ORIGIN_MAX_BYTES);
+        }
+        assert!(crds.should_trim_origins());
+
+        let num_purged = crds.num_purged();
+        let (num_origins, num_evicted) = crds.trim_origins(&[kept], &stakes, /*now=*/ 0);
+        assert!(!crds.should_trim_origins());
+        let after = usage(&crds);
+        // Only the unstaked origin exceeds its quota; the staked origin is
+        // allowed the maximum quota and the kept origin is not trimmed.
+        assert_eq!(num_origins, 1);
+        assert_eq!(num_evicted, before[0].0 - after[0].0);
+        assert_eq!(crds.num_purged(), num_purged + num_evicted);
+        assert_eq!(after[1], before[1]);
+        assert_eq!(after[2], before[2]);
+        // Only duplicate-shreds are evicted from the low-stake origin, and
+        // its contact-info, votes and epoch-slots are all retained even
+        // though they are its least recently updated values.
+        let (votes, epoch_slots, shreds, bytes) = count(&crds, &unstaked);
+        assert_eq!(votes, usize::from(MAX_VOTES));
+        assert_eq!(epoch_slots, usize::from(MAX_EPOCH_SLOTS));
+        assert_eq!(shreds, usize::from(MAX_DUPLICATE_SHREDS) - num_evicted);
+        assert!(shreds <= CRDS_ORIGIN_MIN_VALUES);
+        assert!(bytes <= CRDS_ORIGIN_MIN_BYTES);
+        assert!(crds.get::<&ContactInfo>(unstaked).is_some());
+        // The least recently updated duplicate-shreds are evicted first.
+        let oldest = crds
+            .get_records(&unstaked)
+            .filter_map(|value| match value.value.data() {
+                CrdsData::DuplicateShred(k, _) => Some(*k),
+                _ => None,
+            })
+            .min()
+            .unwrap();
+        assert_eq!(usize::from(oldest), num_evicted);
+    }
+
     #[test]
     fn test_remove_staked() {
         let thread_pool = ThreadPoolBuilder::new().build().unwrap();
@@ -5,7 +5,7 @@ use {
         duplicate_shred::{DuplicateShred, DuplicateShredIndex, MAX_DUPLICATE_SHREDS},
         epoch_slots::EpochSlots,
         legacy_contact_info::LegacyContactInfo,
-        restart_crds_values::{RestartHeaviestFork, RestartLastVotedForkSlots},
+        restart_crds_values::{HardForkProposal, RestartHeaviestFork, RestartLastVotedForkSlots},
     },
     rand::Rng,
     serde::de::{Deserialize, Deserializer},
@@ -31,7 +31,7 @@ const MAX_ACCOUNTS_HASHES: usize = 16;
 pub(crate) type VoteIndex = u8;
 // TODO: Remove this in favor of vote_state::MAX_LOCKOUT_HISTORY once
 // the fleet is updated to the new ClusterInfo::push_vote code.
-const MAX_VOTES: VoteIndex = 32;
+pub(crate) const MAX_VOTES: VoteIndex = 32;
 
 pub(crate) type EpochSlotsIndex = u8;
 pub(crate) const MAX_EPOCH_SLOTS: EpochSlotsIndex = 255;
@@ -63,6 +63,7 @@ pub enum CrdsData {
     ContactInfo(ContactInfo),
     RestartLastVotedForkSlots(RestartLastVotedForkSlots),
     RestartHeaviestFork(RestartHeaviestFork),
+    HardForkProposal(HardForkProposal),
 }
 
 impl Sanitize for CrdsData {
@@ -103,6 +104,7 @@ impl Sanitize for CrdsData {
             CrdsData::ContactInfo(node) => node.sanitize(),
             CrdsData::RestartLastVotedForkSlots(slots) => slots.sanitize(),
             CrdsData::RestartHeaviestFork(fork) => fork.sanitize(),
+            CrdsData::HardForkProposal(proposal) => proposal.sanitize(),
         }
     }
 }
@@ -116,7 +118,7 @@ pub(crate) fn new_rand_timestamp<R: Rng>(rng: &mut R) -> u64 {
 impl CrdsData {
     /// New random CrdsData for tests and benchmarks.
     pub(crate) fn new_rand<R: Rng>(rng: &mut R, pubkey: Option<Pubkey>) -> CrdsData {
-        let kind = rng.gen_range(0..8);
+        let kind = rng.gen_range(0..9);
         // TODO: Implement other kinds of CrdsData here.
         // TODO: Assign ranges to each arm proportional to their frequency in
         // the mainnet crds table.
@@ -131,6 +133,7 @@ impl CrdsData {
                 rng, pubkey,
             )),
             6 => CrdsData::RestartHeaviestFork(RestartHeaviestFork::new_rand(rng, pubkey)),
+            7 => CrdsData::HardForkProposal(HardForkProposal::new_rand(rng, pubkey)),
             _ => CrdsData::EpochSlots(
                 rng.gen_range(0..MAX_EPOCH_SLOTS),
                 EpochSlots::new_rand(rng, pubkey),
@@ -154,6 +157,7 @@ impl CrdsData {
             CrdsData::ContactInfo(node) => node.wallclock(),
             CrdsData::RestartLastVotedForkSlots(slots) => slots.wallclock,
             CrdsData::RestartHeaviestFork(fork) => fork.wallclock,
+            CrdsData::HardForkProposal(proposal) => proposal.wallclock,
         }
     }
 
@@ -173,6 +177,7 @@ impl CrdsData {
             CrdsData::ContactInfo(node) => *node.pubkey(),
             CrdsData::RestartLastVotedForkSlots(slots) => slots.from,
             CrdsData::RestartHeaviestFork(fork) => fork.from,
+            CrdsData::HardForkProposal(proposal) => proposal.from,
         }
     }
 
@@ -195,6 +200,7 @@ impl CrdsData {
             Self::ContactInfo(_) => false,
             Self::RestartLastVotedForkSlots(_) => false,
             Self::RestartHeaviestFork(_) => false,
+            Self::HardForkProposal(_) => false,
         }
     }
 }
@@ -366,7 +372,7 @@ impl Vote {
     }
 
     /// New random Vote for tests and benchmarks.
-    fn new_rand<R: Rng>(rng: &mut R, pubkey: Option<Pubkey>) -> Self {
+    pub(crate) fn new_rand<R: Rng>(rng: &mut R, pubkey: Option<Pubkey>) -> Self {
         Self {
             from: pubkey.unwrap_or_else(pubkey::new_rand),
             transaction: Transaction::default(),
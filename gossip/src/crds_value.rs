@@ -75,6 +75,7 @@ pub enum CrdsValueLabel {
     ContactInfo(Pubkey),
     RestartLastVotedForkSlots(Pubkey),
     RestartHeaviestFork(Pubkey),
+    HardForkProposal(Pubkey),
 }
 
 impl CrdsValueLabel {
@@ -94,6 +95,7 @@ impl CrdsValueLabel {
             CrdsValueLabel::ContactInfo(pubkey) => *pubkey,
             CrdsValueLabel::RestartLastVotedForkSlots(p) => *p,
             CrdsValueLabel::RestartHeaviestFork(p) => *p,
+            CrdsValueLabel::HardForkProposal(p) => *p,
         }
     }
 }
@@ -182,6 +184,7 @@ impl CrdsValue {
                 CrdsValueLabel::RestartLastVotedForkSlots(pubkey)
             }
             CrdsData::RestartHeaviestFork(_) => CrdsValueLabel::RestartHeaviestFork(pubkey),
+            CrdsData::HardForkProposal(_) => CrdsValueLabel::HardForkProposal(pubkey),
         }
     }
 
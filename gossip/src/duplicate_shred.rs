@@ -46,6 +46,26 @@ impl DuplicateShred {
     pub(crate) fn chunk_index(&self) -> u8 {
         self.chunk_index
     }
+
+    /// New random chunk of a duplicate slot proof for tests.
+    #[cfg(test)]
+    pub(crate) fn new_rand<R: rand::Rng>(
+        rng: &mut R,
+        from: Pubkey,
+        wallclock: u64,
+        chunk_size: usize,
+    ) -> Self {
+        Self {
+            from,
+            wallclock,
+            slot: rng.gen(),
+            _unused: 0,
+            _unused_shred_type: ShredType::Code,
+            num_chunks: 1,
+            chunk_index: 0,
+            chunk: (0..chunk_size).map(|_| rng.gen()).collect(),
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -23,6 +23,7 @@ pub mod gossip_error;
 pub mod gossip_service;
 #[macro_use]
 mod legacy_contact_info;
+pub mod peer_filter;
 pub mod ping_pong;
 mod protocol;
 mod push_active_set;
@@ -0,0 +1,285 @@
+//! Operator configured allow and deny lists of peers, by pubkey and by IP range.
+//!
+//! A peer is rejected if either its pubkey or its IP address is denied. If any pubkey is
+//! allowed, only allowed pubkeys are accepted, and likewise if any IP range is allowed, only
+//! addresses within an allowed range are accepted.
+
+use {
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::HashSet,
+        fmt,
+        net::{IpAddr, SocketAddr},
+        str::FromStr,
+    },
+};
+
+/// An IP address range in CIDR notation, e.g. `10.0.0.0/8`.
+/// A bare address is a range containing only that address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IpRange {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpRange {
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Self, String> {
+        let max_prefix_len = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix_len > max_prefix_len {
+            return Err(format!("Invalid prefix length for {addr}: {prefix_len}"));
+        }
+        Ok(Self { addr, prefix_len })
+    }
+
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        fn mask(bits: u32, prefix_len: u8) -> u128 {
+            match prefix_len {
+                0 => 0,
+                prefix_len => u128::MAX << (bits - u32::from(prefix_len)),
+            }
+        }
+        let addr = match (self.addr, addr) {
+            (IpAddr::V4(_), IpAddr::V6(addr)) => match addr.to_ipv4_mapped() {
+                Some(addr) => IpAddr::V4(addr),
+                None => return false,
+            },
+            _ => *addr,
+        };
+        match (self.addr, addr) {
+            (IpAddr::V4(range), IpAddr::V4(addr)) => {
+                let mask = mask(32, self.prefix_len);
+                u128::from(u32::from(range)) & mask == u128::from(u32::from(addr)) & mask
+            }
+            (IpAddr::V6(range), IpAddr::V6(addr)) => {
+                let mask = mask(128, self.prefix_len);
+                u128::from(range) & mask == u128::from(addr) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix_len)) => (
+                addr,
+                Some(
+                    prefix_len
+                        .parse()
+                        .map_err(|err| format!("Invalid prefix length {prefix_len}: {err}"))?,
+                ),
+            ),
+            None => (s, None),
+        };
+        let addr = IpAddr::from_str(addr).map_err(|err| format!("Invalid IP {addr}: {err}"))?;
+        let prefix_len = prefix_len.unwrap_or(match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        });
+        Self::new(addr, prefix_len)
+    }
+}
+
+impl fmt::Display for IpRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+/// A single allow or deny list entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PeerFilterEntry {
+    Pubkey(Pubkey),
+    IpRange(IpRange),
+}
+
+impl FromStr for PeerFilterEntry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Pubkey::from_str(s) {
+            Ok(pubkey) => Ok(Self::Pubkey(pubkey)),
+            Err(_) => IpRange::from_str(s)
+                .map(Self::IpRange)
+                .map_err(|err| format!("Neither a pubkey nor an IP range: {s}: {err}")),
+        }
+    }
+}
+
+impl fmt::Display for PeerFilterEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Pubkey(pubkey) => write!(f, "{pubkey}"),
+            Self::IpRange(ip_range) => write!(f, "{ip_range}"),
+        }
+    }
+}
+
+pub fn is_peer_filter_entry(string: String) -> Result<(), String> {
+    PeerFilterEntry::from_str(&string).map(|_| ())
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PeerFilter {
+    allowed_pubkeys: HashSet<Pubkey>,
+    denied_pubkeys: HashSet<Pubkey>,
+    allowed_ip_ranges: Vec<IpRange>,
+    denied_ip_ranges: Vec<IpRange>,
+}
+
+impl PeerFilter {
+    pub fn new(
+        allowed: impl IntoIterator<Item = PeerFilterEntry>,
+        denied: impl IntoIterator<Item = PeerFilterEntry>,
+    ) -> Self {
+        let mut peer_filter = Self::default();
+        for entry in allowed {
+            match entry {
+                PeerFilterEntry::Pubkey(pubkey) => {
+                    peer_filter.allowed_pubkeys.insert(pubkey);
+                }
+                PeerFilterEntry::IpRange(ip_range) => peer_filter.allowed_ip_ranges.push(ip_range),
+            }
+        }
+        for entry in denied {
+            match entry {
+                PeerFilterEntry::Pubkey(pubkey) => {
+                    peer_filter.denied_pubkeys.insert(pubkey);
+                }
+                PeerFilterEntry::IpRange(ip_range) => peer_filter.denied_ip_ranges.push(ip_range),
+            }
+        }
+        peer_filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allowed_pubkeys.is_empty()
+            && self.denied_pubkeys.is_empty()
+            && self.allowed_ip_ranges.is_empty()
+            && self.denied_ip_ranges.is_empty()
+    }
+
+    pub fn is_allowed_pubkey(&self, pubkey: &Pubkey) -> bool {
+        !self.denied_pubkeys.contains(pubkey)
+            && (self.allowed_pubkeys.is_empty() || self.allowed_pubkeys.contains(pubkey))
+    }
+
+    pub fn is_allowed_ip(&self, addr: &IpAddr) -> bool {
+        !self
+            .denied_ip_ranges
+            .iter()
+            .any(|ip_range| ip_range.contains(addr))
+            && (self.allowed_ip_ranges.is_empty()
+                || self
+                    .allowed_ip_ranges
+                    .iter()
+                    .any(|ip_range| ip_range.contains(addr)))
+    }
+
+    pub fn is_allowed(&self, pubkey: &Pubkey, addr: &SocketAddr) -> bool {
+        self.is_allowed_pubkey(pubkey) && self.is_allowed_ip(&addr.ip())
+    }
+
+    pub fn denied_pubkeys(&self) -> impl Iterator<Item = &Pubkey> {
+        self.denied_pubkeys.iter()
+    }
+
+    pub fn allowed(&self) -> Vec<PeerFilterEntry> {
+        entries(&self.allowed_pubkeys, &self.allowed_ip_ranges)
+    }
+
+    pub fn denied(&self) -> Vec<PeerFilterEntry> {
+        entries(&self.denied_pubkeys, &self.denied_ip_ranges)
+    }
+}
+
+fn entries(pubkeys: &HashSet<Pubkey>, ip_ranges: &[IpRange]) -> Vec<PeerFilterEntry> {
+    let mut pubkeys: Vec<_> = pubkeys.iter().copied().collect();
+    pubkeys.sort_unstable();
+    pubkeys
+        .into_iter()
+        .map(PeerFilterEntry::Pubkey)
+        .chain(ip_ranges.iter().copied().map(PeerFilterEntry::IpRange))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::net::Ipv4Addr};
+
+    #[test]
+    fn test_ip_range() {
+        let ip_range = IpRange::from_str("10.1.0.0/16").unwrap();
+        assert!(ip_range.contains(&"10.1.2.3".parse().unwrap()));
+        assert!(ip_range.contains(&"::ffff:10.1.2.3".parse().unwrap()));
+        assert!(!ip_range.contains(&"10.2.0.1".parse().unwrap()));
+        assert!(!ip_range.contains(&"::1".parse().unwrap()));
+        assert_eq!(ip_range.to_string(), "10.1.0.0/16");
+
+        let ip_range = IpRange::from_str("127.0.0.1").unwrap();
+        assert!(ip_range.contains(&IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        assert!(!ip_range.contains(&"127.0.0.2".parse().unwrap()));
+
+        assert!(IpRange::from_str("0.0.0.0/0")
+            .unwrap()
+            .contains(&"1.2.3.4".parse().unwrap()));
+        assert!(IpRange::from_str("2001:db8::/32")
+            .unwrap()
+            .contains(&"2001:db8::1".parse().unwrap()));
+        assert!(IpRange::from_str("10.0.0.0/33").is_err());
+        assert!(IpRange::from_str("10.0.0/8").is_err());
+    }
+
+    #[test]
+    fn test_peer_filter() {
+        let allowed_pubkey = Pubkey::new_unique();
+        let denied_pubkey = Pubkey::new_unique();
+        let other_pubkey = Pubkey::new_unique();
+        let addr: SocketAddr = "10.0.0.1:8001".parse().unwrap();
+        let denied_addr: SocketAddr = "192.168.1.1:8001".parse().unwrap();
+
+        let peer_filter = PeerFilter::default();
+        assert!(peer_filter.is_empty());
+        assert!(peer_filter.is_allowed(&other_pubkey, &denied_addr));
+
+        let peer_filter = PeerFilter::new(
+            [],
+            [
+                PeerFilterEntry::Pubkey(denied_pubkey),
+                PeerFilterEntry::from_str("192.168.0.0/16").unwrap(),
+            ],
+        );
+        assert!(peer_filter.is_allowed(&other_pubkey, &addr));
+        assert!(!peer_filter.is_allowed(&denied_pubkey, &addr));
+        assert!(!peer_filter.is_allowed(&other_pubkey, &denied_addr));
+
+        let peer_filter = PeerFilter::new(
+            [PeerFilterEntry::Pubkey(allowed_pubkey)],
+            [PeerFilterEntry::from_str("10.0.0.1").unwrap()],
+        );
+        assert!(!peer_filter.is_allowed_pubkey(&other_pubkey));
+        assert!(peer_filter.is_allowed_pubkey(&allowed_pubkey));
+        assert!(!peer_filter.is_allowed(&allowed_pubkey, &addr));
+        assert!(peer_filter.is_allowed(&allowed_pubkey, &denied_addr));
+
+        assert_eq!(
+            peer_filter.allowed(),
+            vec![PeerFilterEntry::Pubkey(allowed_pubkey)]
+        );
+        assert_eq!(
+            peer_filter
+                .denied()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["10.0.0.1/32".to_string()]
+        );
+        assert!(PeerFilterEntry::from_str("invalid").is_err());
+    }
+}
@@ -90,6 +90,18 @@ impl Protocol {
             .unwrap()
     }
 
+    /// Returns the pubkey of the node which sent the message.
+    pub(crate) fn sender(&self) -> Pubkey {
+        match self {
+            Self::PullRequest(_, caller) => caller.pubkey(),
+            Self::PullResponse(from, _)
+            | Self::PushMessage(from, _)
+            | Self::PruneMessage(from, _) => *from,
+            Self::PingMessage(ping) => ping.pubkey(),
+            Self::PongMessage(pong) => *pong.from(),
+        }
+    }
+
     // Returns true if all signatures verify.
     #[must_use]
     pub(crate) fn par_verify(&self) -> bool {
@@ -1,5 +1,5 @@
 use {
-    crate::crds_data::{new_rand_timestamp, sanitize_wallclock},
+    crate::crds_data::{new_rand_timestamp, sanitize_wallclock, MAX_SLOT},
     bv::BitVec,
     itertools::Itertools,
     rand::Rng,
@@ -37,6 +37,22 @@ pub struct RestartHeaviestFork {
     pub shred_version: u16,
 }
 
+/// Hard fork an operator intends to restart the cluster with, advertised so
+/// that the `--hard-fork` parameters of a coordinated restart can be
+/// cross-checked among operators.
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct HardForkProposal {
+    pub from: Pubkey,
+    pub wallclock: u64,
+    pub slot: Slot,
+    /// Expected bank hash of `slot`, if known to the proposer
+    pub bank_hash: Option<Hash>,
+    /// Shred version of the cluster after the hard fork, if known to the proposer
+    pub expected_shred_version: Option<u16>,
+    pub shred_version: u16,
+}
+
 #[cfg_attr(feature = "frozen-abi", derive(AbiExample, AbiEnumVisitor))]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 enum SlotsOffsets {
@@ -156,6 +172,33 @@ impl RestartHeaviestFork {
     }
 }
 
+impl Sanitize for HardForkProposal {
+    fn sanitize(&self) -> Result<(), SanitizeError> {
+        sanitize_wallclock(self.wallclock)?;
+        if self.slot >= MAX_SLOT {
+            return Err(SanitizeError::ValueOutOfBounds);
+        }
+        match &self.bank_hash {
+            Some(bank_hash) => bank_hash.sanitize(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl HardForkProposal {
+    pub(crate) fn new_rand<R: Rng>(rng: &mut R, from: Option<Pubkey>) -> Self {
+        let from = from.unwrap_or_else(solana_pubkey::new_rand);
+        Self {
+            from,
+            wallclock: new_rand_timestamp(rng),
+            slot: rng.gen_range(0..1000),
+            bank_hash: Some(Hash::new_unique()),
+            expected_shred_version: Some(rng.gen()),
+            shred_version: 1,
+        }
+    }
+}
+
 impl RunLengthEncoding {
     fn new(bits: &BitVec<u8>) -> Self {
         let encoded = (0..bits.len())
@@ -369,4 +412,26 @@ mod test {
         fork.wallclock = crate::crds_data::MAX_WALLCLOCK;
         assert_eq!(fork.sanitize(), Err(SanitizeError::ValueOutOfBounds));
     }
+
+    #[test]
+    fn test_hard_fork_proposal() {
+        let keypair = Keypair::new();
+        let mut proposal = HardForkProposal {
+            from: keypair.pubkey(),
+            wallclock: timestamp(),
+            slot: 53,
+            bank_hash: Some(Hash::default()),
+            expected_shred_version: Some(2),
+            shred_version: 1,
+        };
+        assert_eq!(proposal.sanitize(), Ok(()));
+        proposal.bank_hash = None;
+        proposal.expected_shred_version = None;
+        assert_eq!(proposal.sanitize(), Ok(()));
+        proposal.slot = MAX_SLOT;
+        assert_eq!(proposal.sanitize(), Err(SanitizeError::ValueOutOfBounds));
+        proposal.slot = 53;
+        proposal.wallclock = crate::crds_data::MAX_WALLCLOCK;
+        assert_eq!(proposal.sanitize(), Err(SanitizeError::ValueOutOfBounds));
+    }
 }
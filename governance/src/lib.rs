@@ -0,0 +1,283 @@
+//! Cluster parameter bundles staged by governance.
+//!
+//! The governance multisig ([`authority`]) stages a [`ParameterBundle`] in the config program
+//! account at [`pending_bundle`]. The account is only accepted if the governance authority is its
+//! sole config signer, which the config program then enforces for every later update.
+//!
+//! All parameters of a bundle take effect together in the first slot of its activation epoch.
+//! The bank then records the combined parameters in effect in the [`active_parameters`] account,
+//! so that they outlive the staged bundle being replaced and are restored along with snapshots.
+
+use {
+    bincode::serialized_size,
+    serde_derive::{Deserialize, Serialize},
+    solana_account::ReadableAccount,
+    solana_clock::Epoch,
+    solana_config_program::{get_config_data, ConfigKeys, ConfigState},
+    thiserror::Error,
+};
+
+/// The multisig allowed to stage parameter bundles.
+pub mod authority {
+    solana_pubkey::declare_id!("AV65ZV5xMmEQnEszrTBNPBkRS9ppmV2yJuzFyadEGRNT");
+}
+
+/// The config program account holding the staged parameter bundle.
+pub mod pending_bundle {
+    solana_pubkey::declare_id!("8ibXAH83YZvcNLJDaEVJAgybD516HR4PpkmUgrn34veo");
+}
+
+/// The bank maintained account recording the parameters currently in effect.
+pub mod active_parameters {
+    solana_pubkey::declare_id!("9Ph241DF77goVK3d8LDoF75jU72nwLeKDg2AEwyospVy");
+}
+
+/// Upper bound of the base fee multiplier, 100 times the default.
+pub const MAX_FEE_MULTIPLIER: u64 = 1_000;
+
+/// Upper bound of the block cost limit, four times the SIMD-0256 block limit.
+pub const MAX_BLOCK_COST_LIMIT: u64 = 240_000_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockCostLimits {
+    pub account_cost_limit: u64,
+    pub block_cost_limit: u64,
+    pub vote_cost_limit: u64,
+}
+
+/// A set of cluster parameter changes that take effect together at `activation_epoch`.
+/// Parameters left as `None` keep their current value.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParameterBundle {
+    pub activation_epoch: Epoch,
+    /// Compute units are multiplied by this to derive the base fee
+    pub fee_multiplier: Option<u64>,
+    pub block_cost_limits: Option<BlockCostLimits>,
+    /// Yearly reduction of the inflation rate
+    pub inflation_taper: Option<f64>,
+}
+
+impl ConfigState for ParameterBundle {
+    fn max_space() -> u64 {
+        serialized_size(&ParameterBundle {
+            activation_epoch: Epoch::MAX,
+            fee_multiplier: Some(u64::MAX),
+            block_cost_limits: Some(BlockCostLimits::default()),
+            inflation_taper: Some(f64::MAX),
+        })
+        .unwrap()
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParameterBundleError {
+    #[error("account is not owned by the config program")]
+    InvalidOwner,
+    #[error("account is not controlled by the governance authority")]
+    InvalidAuthority,
+    #[error("invalid account data: {0}")]
+    InvalidData(String),
+    #[error("bundle does not change any parameter")]
+    Empty,
+    #[error("fee multiplier {0} is out of range")]
+    InvalidFeeMultiplier(u64),
+    #[error("invalid block cost limits: {0:?}")]
+    InvalidBlockCostLimits(BlockCostLimits),
+    #[error("inflation taper {0} is out of range")]
+    InvalidInflationTaper(f64),
+}
+
+impl ParameterBundle {
+    /// Deserializes and validates the bundle staged in `account`.
+    pub fn from_account(account: &impl ReadableAccount) -> Result<Self, ParameterBundleError> {
+        if account.owner() != &solana_config_program::id() {
+            return Err(ParameterBundleError::InvalidOwner);
+        }
+        let data = account.data();
+        let config_keys: ConfigKeys = bincode::deserialize(data)
+            .map_err(|err| ParameterBundleError::InvalidData(err.to_string()))?;
+        if config_keys.keys != [(authority::id(), true)] {
+            return Err(ParameterBundleError::InvalidAuthority);
+        }
+        let bundle: Self = get_config_data(data)
+            .and_then(bincode::deserialize)
+            .map_err(|err| ParameterBundleError::InvalidData(err.to_string()))?;
+        bundle.validate()?;
+        Ok(bundle)
+    }
+
+    /// Checks that every parameter of the bundle is within its allowed range.
+    pub fn validate(&self) -> Result<(), ParameterBundleError> {
+        if self.fee_multiplier.is_none()
+            && self.block_cost_limits.is_none()
+            && self.inflation_taper.is_none()
+        {
+            return Err(ParameterBundleError::Empty);
+        }
+        if let Some(fee_multiplier) = self.fee_multiplier {
+            if !(1..=MAX_FEE_MULTIPLIER).contains(&fee_multiplier) {
+                return Err(ParameterBundleError::InvalidFeeMultiplier(fee_multiplier));
+            }
+        }
+        if let Some(limits) = self.block_cost_limits {
+            if limits.block_cost_limit > MAX_BLOCK_COST_LIMIT
+                || limits.account_cost_limit == 0
+                || limits.vote_cost_limit == 0
+                || limits.account_cost_limit > limits.block_cost_limit
+                || limits.vote_cost_limit > limits.block_cost_limit
+            {
+                return Err(ParameterBundleError::InvalidBlockCostLimits(limits));
+            }
+        }
+        if let Some(taper) = self.inflation_taper {
+            if !(0.0..=1.0).contains(&taper) {
+                return Err(ParameterBundleError::InvalidInflationTaper(taper));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the parameters in effect once `bundle` is applied on top of `self`.
+    pub fn merge(&self, bundle: &ParameterBundle) -> Self {
+        Self {
+            activation_epoch: bundle.activation_epoch,
+            fee_multiplier: bundle.fee_multiplier.or(self.fee_multiplier),
+            block_cost_limits: bundle.block_cost_limits.or(self.block_cost_limits),
+            inflation_taper: bundle.inflation_taper.or(self.inflation_taper),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_config_program::create_config_account, solana_pubkey::Pubkey};
+
+    fn bundle() -> ParameterBundle {
+        ParameterBundle {
+            activation_epoch: 42,
+            fee_multiplier: Some(20),
+            block_cost_limits: Some(BlockCostLimits {
+                account_cost_limit: 12_000_000,
+                block_cost_limit: 60_000_000,
+                vote_cost_limit: 36_000_000,
+            }),
+            inflation_taper: Some(0.2),
+        }
+    }
+
+    #[test]
+    fn test_from_account() {
+        let account = create_config_account(vec![(authority::id(), true)], &bundle(), 1);
+        assert_eq!(ParameterBundle::from_account(&account), Ok(bundle()));
+
+        let mut wrong_owner = account.clone();
+        wrong_owner.set_owner(Pubkey::new_unique());
+        assert_eq!(
+            ParameterBundle::from_account(&wrong_owner),
+            Err(ParameterBundleError::InvalidOwner)
+        );
+
+        for keys in [
+            vec![],
+            vec![(authority::id(), false)],
+            vec![(Pubkey::new_unique(), true)],
+            vec![(authority::id(), true), (Pubkey::new_unique(), true)],
+        ] {
+            let account = create_config_account(keys, &bundle(), 1);
+            assert_eq!(
+                ParameterBundle::from_account(&account),
+                Err(ParameterBundleError::InvalidAuthority)
+            );
+        }
+
+        let invalid = ParameterBundle {
+            fee_multiplier: Some(0),
+            ..bundle()
+        };
+        let account = create_config_account(vec![(authority::id(), true)], &invalid, 1);
+        assert_eq!(
+            ParameterBundle::from_account(&account),
+            Err(ParameterBundleError::InvalidFeeMultiplier(0))
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(bundle().validate(), Ok(()));
+        assert_eq!(
+            ParameterBundle {
+                activation_epoch: 42,
+                ..ParameterBundle::default()
+            }
+            .validate(),
+            Err(ParameterBundleError::Empty)
+        );
+        assert_eq!(
+            ParameterBundle {
+                fee_multiplier: Some(MAX_FEE_MULTIPLIER + 1),
+                ..bundle()
+            }
+            .validate(),
+            Err(ParameterBundleError::InvalidFeeMultiplier(
+                MAX_FEE_MULTIPLIER + 1
+            ))
+        );
+        for limits in [
+            BlockCostLimits {
+                account_cost_limit: 0,
+                block_cost_limit: 60_000_000,
+                vote_cost_limit: 36_000_000,
+            },
+            BlockCostLimits {
+                account_cost_limit: 12_000_000,
+                block_cost_limit: 10_000_000,
+                vote_cost_limit: 10_000_000,
+            },
+            BlockCostLimits {
+                account_cost_limit: 12_000_000,
+                block_cost_limit: MAX_BLOCK_COST_LIMIT + 1,
+                vote_cost_limit: 36_000_000,
+            },
+        ] {
+            assert_eq!(
+                ParameterBundle {
+                    block_cost_limits: Some(limits),
+                    ..bundle()
+                }
+                .validate(),
+                Err(ParameterBundleError::InvalidBlockCostLimits(limits))
+            );
+        }
+        for taper in [-0.1, 1.1, f64::NAN] {
+            assert!(matches!(
+                ParameterBundle {
+                    inflation_taper: Some(taper),
+                    ..bundle()
+                }
+                .validate(),
+                Err(ParameterBundleError::InvalidInflationTaper(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let active = ParameterBundle::default().merge(&bundle());
+        assert_eq!(active, bundle());
+
+        let update = ParameterBundle {
+            activation_epoch: 50,
+            fee_multiplier: Some(15),
+            block_cost_limits: None,
+            inflation_taper: None,
+        };
+        assert_eq!(
+            active.merge(&update),
+            ParameterBundle {
+                activation_epoch: 50,
+                fee_multiplier: Some(15),
+                ..bundle()
+            }
+        );
+    }
+}
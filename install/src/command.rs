@@ -16,12 +16,15 @@ use {
     solana_pubkey::Pubkey,
     solana_rpc_client::rpc_client::RpcClient,
     solana_sha256_hasher::Hasher,
+    solana_signature::Signature,
     solana_signer::Signer,
     solana_transaction::Transaction,
     std::{
+        collections::HashSet,
         fs::{self, File},
         io::{self, BufReader, Read},
         path::{Path, PathBuf},
+        str::FromStr,
         time::{Duration, Instant, SystemTime},
     },
     tempfile::TempDir,
@@ -526,6 +529,7 @@ fn add_to_path(new_path: &str) -> bool {
     modified_rcfiles
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn init(
     config_file: &str,
     data_dir: &str,
@@ -533,18 +537,22 @@ pub fn init(
     update_manifest_pubkey: &Pubkey,
     no_modify_path: bool,
     explicit_release: Option<ExplicitRelease>,
+    release_url: Option<String>,
+    release_signer: Option<Pubkey>,
 ) -> Result<(), String> {
     let config = {
         // Write new config file only if different, so that running |agave-install init|
         // repeatedly doesn't unnecessarily re-download
         let mut current_config = Config::load(config_file).unwrap_or_default();
         current_config.current_update_manifest = None;
-        let config = Config::new(
+        let mut config = Config::new(
             data_dir,
             json_rpc_url,
             update_manifest_pubkey,
             explicit_release,
         );
+        config.release_url = release_url;
+        config.release_signer = release_signer;
         if current_config != config {
             config.save(config_file)?;
         }
@@ -573,17 +581,19 @@ fn github_release_download_url(release_semver: &str) -> String {
     )
 }
 
-fn release_channel_download_url(release_channel: &str) -> String {
+fn release_channel_download_url(release_url: &str, release_channel: &str) -> String {
     format!(
-        "https://release.x1.xyz/{}/solana-release-{}.tar.bz2",
+        "{}/{}/solana-release-{}.tar.bz2",
+        release_url.trim_end_matches('/'),
         release_channel,
         crate::build_env::TARGET
     )
 }
 
-fn release_channel_version_url(release_channel: &str) -> String {
+fn release_channel_version_url(release_url: &str, release_channel: &str) -> String {
     format!(
-        "https://release.x1.xyz/{}/solana-release-{}.yml",
+        "{}/{}/solana-release-{}.yml",
+        release_url.trim_end_matches('/'),
         release_channel,
         crate::build_env::TARGET
     )
@@ -651,7 +661,7 @@ pub fn info(config_file: &str, local_info_only: bool, eval: bool) -> Result<(),
                 println_name_value(&format!("{BULLET}Release channel:"), release_channel);
                 println_name_value(
                     &format!("{BULLET}Release URL:"),
-                    &release_channel_download_url(release_channel),
+                    &release_channel_download_url(config.release_url(), release_channel),
                 );
             }
         }
@@ -960,9 +970,57 @@ pub fn update(config_file: &str, check_only: bool) -> Result<bool, String> {
 }
 
 pub fn init_or_update(config_file: &str, is_init: bool, check_only: bool) -> Result<bool, String> {
+    let Some(pending_update) = find_update(config_file, is_init)? else {
+        return Ok(false);
+    };
+    let updated_version = &pending_update.version;
+
+    if check_only {
+        println!(
+            "  {}{}",
+            WRAPPED_PRESENT,
+            style(format!("Update available: {updated_version}")).bold()
+        );
+        return Ok(true);
+    }
+
+    download_release(&pending_update)?;
+    activate_release(
+        config_file,
+        &pending_update.config,
+        &pending_update.release_dir,
+    )?;
+
+    if is_init {
+        println!(
+            "  {}{}",
+            SPARKLE,
+            style(format!("{updated_version} initialized")).bold()
+        );
+    } else {
+        println!(
+            "  {}{}",
+            SPARKLE,
+            style(format!("Update successful to {updated_version}")).bold()
+        );
+    }
+    Ok(true)
+}
+
+/// A newer release, to be downloaded into `release_dir` if `download_url_and_sha256` is set
+struct PendingUpdate {
+    /// The configuration to save once the release is active
+    config: Config,
+    version: String,
+    download_url_and_sha256: Option<(String, Option<Hash>)>,
+    release_dir: PathBuf,
+}
+
+/// Checks for a newer release, returns None if the install is up to date
+fn find_update(config_file: &str, is_init: bool) -> Result<Option<PendingUpdate>, String> {
     let mut config = Config::load(config_file)?;
 
-    let (updated_version, download_url_and_sha256, release_dir) = if let Some(explicit_release) =
+    let (version, download_url_and_sha256, release_dir) = if let Some(explicit_release) =
         &config.explicit_release
     {
         match explicit_release {
@@ -1002,7 +1060,7 @@ pub fn init_or_update(config_file: &str, is_init: bool, check_only: bool) -> Res
                                         println!(
                                         "Install is up to date. {release_semver} is the latest compatible release"
                                     );
-                                        return Ok(false);
+                                        return Ok(None);
                                     }
                                 }
                             }
@@ -1022,7 +1080,8 @@ pub fn init_or_update(config_file: &str, is_init: bool, check_only: bool) -> Res
                 }
             }
             ExplicitRelease::Channel(release_channel) => {
-                let version_url = release_channel_version_url(release_channel);
+                let version_url =
+                    release_channel_version_url(config.release_url(), release_channel);
 
                 let (_temp_dir, temp_file, _temp_archive_sha256) =
                     download_to_temp(&version_url, None)
@@ -1035,7 +1094,8 @@ pub fn init_or_update(config_file: &str, is_init: bool, check_only: bool) -> Res
                 let current_release_version_yml =
                     release_dir.join("solana-release").join("version.yml");
 
-                let download_url = release_channel_download_url(release_channel);
+                let download_url =
+                    release_channel_download_url(config.release_url(), release_channel);
 
                 if !current_release_version_yml.exists() {
                     (
@@ -1061,7 +1121,7 @@ pub fn init_or_update(config_file: &str, is_init: bool, check_only: bool) -> Res
                                     &active_release_version.commit[0..7],
                                     release_channel
                                 );
-                                return Ok(false);
+                                return Ok(None);
                             }
                         }
 
@@ -1098,7 +1158,7 @@ pub fn init_or_update(config_file: &str, is_init: bool, check_only: bool) -> Res
 
         if Some(&update_manifest) == config.current_update_manifest.as_ref() {
             println!("Install is up to date");
-            return Ok(false);
+            return Ok(None);
         }
         println!("\n{}", style("An update is available:").bold());
         print_update_manifest(&update_manifest);
@@ -1129,31 +1189,80 @@ pub fn init_or_update(config_file: &str, is_init: bool, check_only: bool) -> Res
         )
     };
 
-    if check_only {
-        println!(
-            "  {}{}",
-            WRAPPED_PRESENT,
-            style(format!("Update available: {updated_version}")).bold()
-        );
-        return Ok(true);
+    Ok(Some(PendingUpdate {
+        config,
+        version,
+        download_url_and_sha256,
+        release_dir,
+    }))
+}
+
+/// Verifies the detached signature published next to the release archive at `download_url`,
+/// which signs the SHA256 of the archive
+fn verify_release_signature(
+    download_url: &str,
+    release_signer: &Pubkey,
+    archive_sha256: &Hash,
+) -> Result<(), String> {
+    let signature_url = format!("{download_url}.sig");
+    let signature = reqwest::blocking::get(&signature_url)
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .map_err(|err| format!("Unable to download {signature_url}: {err}"))?;
+    check_release_signature(&signature, download_url, release_signer, archive_sha256)
+}
+
+/// Checks `signature`, the contents of the signature file of the release archive at
+/// `download_url`, against the SHA256 of the archive
+fn check_release_signature(
+    signature: &str,
+    download_url: &str,
+    release_signer: &Pubkey,
+    archive_sha256: &Hash,
+) -> Result<(), String> {
+    let signature = Signature::from_str(signature.trim())
+        .map_err(|err| format!("Invalid release signature of {download_url}: {err}"))?;
+    if signature.verify(release_signer.as_ref(), archive_sha256.as_ref()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Release archive {download_url} is not signed by {release_signer}"
+        ))
     }
+}
 
-    if let Some((download_url, archive_sha256)) = download_url_and_sha256 {
-        let (_temp_dir, temp_archive, _temp_archive_sha256) =
-            download_to_temp(&download_url, archive_sha256.as_ref())
+/// Downloads the release into the install cache, if not cached already
+fn download_release(pending_update: &PendingUpdate) -> Result<(), String> {
+    let PendingUpdate {
+        config,
+        release_dir,
+        ..
+    } = pending_update;
+    if let Some((download_url, archive_sha256)) = &pending_update.download_url_and_sha256 {
+        let (_temp_dir, temp_archive, temp_archive_sha256) =
+            download_to_temp(download_url, archive_sha256.as_ref())
                 .map_err(|err| format!("Unable to download {download_url}: {err}"))?;
-        extract_release_archive(&temp_archive, &release_dir).map_err(|err| {
+        // Updates from a manifest are authenticated by the signature of the manifest
+        if let (Some(release_signer), Some(_)) = (&config.release_signer, &config.explicit_release)
+        {
+            verify_release_signature(download_url, release_signer, &temp_archive_sha256)?;
+        }
+        extract_release_archive(&temp_archive, release_dir).map_err(|err| {
             format!("Unable to extract {temp_archive:?} to {release_dir:?}: {err}")
         })?;
     }
 
-    let release_target = load_release_target(&release_dir)
+    let release_target = load_release_target(release_dir)
         .map_err(|err| format!("Unable to load release target from {release_dir:?}: {err}"))?;
 
     if release_target != crate::build_env::TARGET {
         return Err(format!("Incompatible update target: {release_target}"));
     }
+    Ok(())
+}
 
+/// Makes the release in `release_dir` the active release and saves `config`
+fn activate_release(config_file: &str, config: &Config, release_dir: &Path) -> Result<(), String> {
     // Trigger an update to the modification time for `release_dir`
     {
         let path = &release_dir.join(".touch");
@@ -1183,28 +1292,117 @@ pub fn init_or_update(config_file: &str, is_init: bool, check_only: bool) -> Res
     })?;
 
     config.save(config_file)?;
-    gc(config_file)?;
+    gc(config_file)
+}
 
-    if is_init {
-        println!(
-            "  {}{}",
-            SPARKLE,
-            style(format!("{updated_version} initialized")).bold()
-        );
-    } else {
-        println!(
-            "  {}{}",
-            SPARKLE,
-            style(format!("Update successful to {updated_version}")).bold()
-        );
+/// How `run` applies the updates of the program it supervises
+pub struct UpdatePolicy {
+    /// Stage updates until the epoch reported by `health_check_url` changes
+    pub update_on_epoch_boundary: bool,
+    /// RPC URL of the supervised validator, an update is rolled back unless the validator reports
+    /// healthy within `health_check_timeout` of the update
+    pub health_check_url: Option<String>,
+    pub health_check_timeout: Duration,
+}
+
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A downloaded update waiting for the next epoch
+struct StagedUpdate {
+    pending_update: PendingUpdate,
+    /// The epoch the update was staged in
+    epoch: Option<u64>,
+    last_epoch_check: Option<Instant>,
+}
+
+/// An update rolled back unless the program reports healthy by `deadline`
+struct AppliedUpdate {
+    version: String,
+    release_dir: PathBuf,
+    previous_config: Config,
+    previous_release_dir: PathBuf,
+    deadline: Instant,
+    last_health_check: Option<Instant>,
+}
+
+/// State of an applied update after checking the health of the program
+#[derive(Debug, PartialEq, Eq)]
+enum UpdateHealth {
+    /// Not checked, or not healthy yet before the deadline
+    Pending,
+    Healthy,
+    /// Not healthy by the deadline, the previous release is active again
+    RolledBack,
+}
+
+/// Checks the health of the program after `update`, at most once per `HEALTH_CHECK_INTERVAL`,
+/// and rolls the update back if the program is not healthy by the deadline
+fn check_update_health(
+    config_file: &str,
+    update: &mut AppliedUpdate,
+    is_healthy: impl FnOnce() -> bool,
+) -> Result<UpdateHealth, String> {
+    if !check_due(&mut update.last_health_check) {
+        return Ok(UpdateHealth::Pending);
     }
-    Ok(true)
+    if is_healthy() {
+        return Ok(UpdateHealth::Healthy);
+    }
+    if Instant::now() < update.deadline {
+        return Ok(UpdateHealth::Pending);
+    }
+    activate_release(
+        config_file,
+        &update.previous_config,
+        &update.previous_release_dir,
+    )?;
+    Ok(UpdateHealth::RolledBack)
+}
+
+/// Returns true at most once per `HEALTH_CHECK_INTERVAL`
+fn check_due(last_check: &mut Option<Instant>) -> bool {
+    if last_check.is_some_and(|last_check| last_check.elapsed() < HEALTH_CHECK_INTERVAL) {
+        return false;
+    }
+    *last_check = Some(Instant::now());
+    true
+}
+
+fn apply_update(
+    config_file: &str,
+    pending_update: PendingUpdate,
+    health_check_timeout: Duration,
+) -> Result<AppliedUpdate, String> {
+    let previous_config = Config::load(config_file)?;
+    let previous_release_dir = fs::read_link(previous_config.active_release_dir())
+        .ok()
+        .and_then(|active_release| active_release.parent().map(Path::to_path_buf))
+        .ok_or_else(|| {
+            format!(
+                "Unable to resolve the active release {:?}",
+                previous_config.active_release_dir()
+            )
+        })?;
+    activate_release(
+        config_file,
+        &pending_update.config,
+        &pending_update.release_dir,
+    )?;
+    Ok(AppliedUpdate {
+        version: pending_update.version,
+        release_dir: pending_update.release_dir,
+        previous_config,
+        previous_release_dir,
+        deadline: Instant::now() + health_check_timeout,
+        last_health_check: None,
+    })
 }
 
 pub fn run(
     config_file: &str,
     program_name: &str,
     program_arguments: Vec<&str>,
+    update_policy: UpdatePolicy,
 ) -> Result<(), String> {
     let config = Config::load(config_file)?;
 
@@ -1223,12 +1421,31 @@ pub fn run(
     let mut child_option: Option<std::process::Child> = None;
     let mut now = Instant::now();
 
+    // Versioned releases are pinned, manifests and channels are followed
+    let auto_update = !matches!(config.explicit_release, Some(ExplicitRelease::Semver(_)));
+    let health_check_client = update_policy
+        .health_check_url
+        .as_ref()
+        .map(|url| RpcClient::new(url.clone()));
+    let mut staged_update: Option<StagedUpdate> = None;
+    let mut applied_update: Option<AppliedUpdate> = None;
+    // Releases rolled back by this process, which are not applied again
+    let mut rejected_release_dirs = HashSet::new();
+
     let (signal_sender, signal_receiver) = unbounded();
     ctrlc::set_handler(move || {
         let _ = signal_sender.send(());
     })
     .expect("Error setting Ctrl-C handler");
 
+    let stop_child = |child_option: &mut Option<std::process::Child>| {
+        if let Some(ref mut child) = child_option {
+            stop_process(child).unwrap_or_else(|err| {
+                eprintln!("Failed to stop child: {err:?}");
+            });
+        }
+    };
+
     loop {
         child_option = match child_option {
             Some(mut child) => match child.try_wait() {
@@ -1259,31 +1476,97 @@ pub fn run(
             }
         };
 
-        if config.explicit_release.is_none() && now.elapsed().as_secs() > config.update_poll_secs {
-            match update(config_file, false) {
-                Ok(true) => {
-                    // Update successful, kill current process so it will be restart
-                    if let Some(ref mut child) = child_option {
-                        stop_process(child).unwrap_or_else(|err| {
-                            eprintln!("Failed to stop child: {err:?}");
+        if auto_update
+            && staged_update.is_none()
+            && applied_update.is_none()
+            && now.elapsed().as_secs() > config.update_poll_secs
+        {
+            match find_update(config_file, false) {
+                Ok(Some(pending_update))
+                    if rejected_release_dirs.contains(&pending_update.release_dir) => {}
+                Ok(Some(pending_update)) => match download_release(&pending_update) {
+                    Ok(()) => {
+                        println_name_value("Update staged:", &pending_update.version);
+                        staged_update = Some(StagedUpdate {
+                            pending_update,
+                            epoch: None,
+                            last_epoch_check: None,
                         });
                     }
-                }
-                Ok(false) => {} // No update available
+                    Err(err) => eprintln!("Failed to download update: {err:?}"),
+                },
+                Ok(None) => {} // No update available
                 Err(err) => {
-                    eprintln!("Failed to apply update: {err:?}");
+                    eprintln!("Failed to check for updates: {err:?}");
                 }
             };
             now = Instant::now();
         }
 
+        if let Some(mut staged) = staged_update.take() {
+            let ready = match &health_check_client {
+                Some(rpc_client) if update_policy.update_on_epoch_boundary => {
+                    check_due(&mut staged.last_epoch_check)
+                        && match rpc_client.get_epoch_info() {
+                            Ok(epoch_info) => {
+                                epoch_info.epoch > *staged.epoch.get_or_insert(epoch_info.epoch)
+                            }
+                            Err(err) => {
+                                eprintln!("Failed to get the current epoch: {err}");
+                                false
+                            }
+                        }
+                }
+                _ => true,
+            };
+            if !ready {
+                staged_update = Some(staged);
+            } else {
+                match apply_update(
+                    config_file,
+                    staged.pending_update,
+                    update_policy.health_check_timeout,
+                ) {
+                    Ok(update) => {
+                        println_name_value("Update applied:", &update.version);
+                        // Kill the current process so it will be restarted
+                        stop_child(&mut child_option);
+                        if health_check_client.is_some() {
+                            applied_update = Some(update);
+                        }
+                    }
+                    Err(err) => eprintln!("Failed to apply update: {err:?}"),
+                }
+            }
+        }
+
+        if let (Some(update), Some(rpc_client)) = (&mut applied_update, &health_check_client) {
+            match check_update_health(config_file, update, || rpc_client.get_health().is_ok()) {
+                Ok(UpdateHealth::Pending) => {}
+                Ok(UpdateHealth::Healthy) => {
+                    println_name_value(&format!("{program_name} is healthy on"), &update.version);
+                    applied_update = None;
+                }
+                Ok(UpdateHealth::RolledBack) => {
+                    eprintln!(
+                        "{program_name} is not healthy after the update to {}, rolled back",
+                        update.version
+                    );
+                    rejected_release_dirs.insert(update.release_dir.clone());
+                    stop_child(&mut child_option);
+                    applied_update = None;
+                }
+                Err(err) => {
+                    eprintln!("Failed to roll back update to {}: {err:?}", update.version);
+                    rejected_release_dirs.insert(update.release_dir.clone());
+                    applied_update = None;
+                }
+            }
+        }
+
         if let Ok(()) = signal_receiver.recv_timeout(Duration::from_secs(1)) {
             // Handle SIGTERM...
-            if let Some(ref mut child) = child_option {
-                stop_process(child).unwrap_or_else(|err| {
-                    eprintln!("Failed to stop child: {err:?}");
-                });
-            }
+            stop_child(&mut child_option);
             std::process::exit(0);
         }
     }
@@ -1320,3 +1603,137 @@ pub fn list(config_file: &str) -> Result<(), String> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(data: &[u8]) -> Hash {
+        let mut hasher = Hasher::default();
+        hasher.hash(data);
+        hasher.result()
+    }
+
+    #[test]
+    fn test_check_release_signature() {
+        let download_url = "https://release.example.com/stable/solana-release.tar.bz2";
+        let release_signer = Keypair::new();
+        let archive_sha256 = hash(b"release archive");
+        let signature = release_signer.sign_message(archive_sha256.as_ref());
+
+        assert_eq!(
+            check_release_signature(
+                &format!("{signature}\n"),
+                download_url,
+                &release_signer.pubkey(),
+                &archive_sha256,
+            ),
+            Ok(())
+        );
+        // signed by another key
+        assert!(check_release_signature(
+            &signature.to_string(),
+            download_url,
+            &Keypair::new().pubkey(),
+            &archive_sha256,
+        )
+        .is_err());
+        // signature of another archive
+        assert!(check_release_signature(
+            &signature.to_string(),
+            download_url,
+            &release_signer.pubkey(),
+            &hash(b"tampered archive"),
+        )
+        .is_err());
+        // not a signature
+        assert!(check_release_signature(
+            "<html>Not Found</html>",
+            download_url,
+            &release_signer.pubkey(),
+            &archive_sha256,
+        )
+        .is_err());
+    }
+
+    /// Creates the release `name` in the releases directory of `config`
+    fn create_release(config: &Config, name: &str) -> PathBuf {
+        let release_dir = config.release_dir(name);
+        fs::create_dir_all(release_dir.join("solana-release")).unwrap();
+        release_dir
+    }
+
+    fn active_release(config: &Config) -> PathBuf {
+        fs::read_link(config.active_release_dir()).unwrap()
+    }
+
+    #[test]
+    fn test_update_health_check() {
+        let data_dir = TempDir::new().unwrap();
+        let data_dir_str = data_dir.path().to_str().unwrap();
+        let config_file = data_dir.path().join("config.yml");
+        let config_file = config_file.to_str().unwrap();
+        let new_config = |update_poll_secs| Config {
+            update_poll_secs,
+            ..Config::new(data_dir_str, "http://localhost", &Pubkey::default(), None)
+        };
+
+        let previous_config = new_config(1);
+        let previous_release_dir = create_release(&previous_config, "previous");
+        activate_release(config_file, &previous_config, &previous_release_dir).unwrap();
+        let release_dir = create_release(&previous_config, "update");
+        let apply = |health_check_timeout| {
+            apply_update(
+                config_file,
+                PendingUpdate {
+                    config: new_config(2),
+                    version: "update".to_string(),
+                    download_url_and_sha256: None,
+                    release_dir: release_dir.clone(),
+                },
+                health_check_timeout,
+            )
+            .unwrap()
+        };
+
+        // not healthy before the deadline
+        let mut update = apply(Duration::from_secs(3600));
+        assert_eq!(update.previous_release_dir, previous_release_dir);
+        assert_eq!(
+            active_release(&previous_config),
+            release_dir.join("solana-release")
+        );
+        assert_eq!(
+            check_update_health(config_file, &mut update, || false),
+            Ok(UpdateHealth::Pending)
+        );
+        // checked at most once per interval
+        assert_eq!(
+            check_update_health(config_file, &mut update, || true),
+            Ok(UpdateHealth::Pending)
+        );
+        update.last_health_check = None;
+        assert_eq!(
+            check_update_health(config_file, &mut update, || true),
+            Ok(UpdateHealth::Healthy)
+        );
+        assert_eq!(
+            active_release(&previous_config),
+            release_dir.join("solana-release")
+        );
+
+        // a failed health check at the deadline rolls the update back
+        activate_release(config_file, &previous_config, &previous_release_dir).unwrap();
+        let mut update = apply(Duration::ZERO);
+        assert_eq!(Config::load(config_file).unwrap().update_poll_secs, 2);
+        assert_eq!(
+            check_update_health(config_file, &mut update, || false),
+            Ok(UpdateHealth::RolledBack)
+        );
+        assert_eq!(
+            active_release(&previous_config),
+            previous_release_dir.join("solana-release")
+        );
+        assert_eq!(Config::load(config_file).unwrap(), previous_config);
+    }
+}
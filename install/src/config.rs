@@ -1,5 +1,5 @@
 use {
-    crate::update_manifest::UpdateManifest,
+    crate::{defaults, update_manifest::UpdateManifest},
     serde_derive::{Deserialize, Serialize},
     solana_pubkey::Pubkey,
     std::{
@@ -24,6 +24,13 @@ pub struct Config {
     pub explicit_release: Option<ExplicitRelease>,
     pub releases_dir: PathBuf,
     active_release_dir: PathBuf,
+    /// Endpoint serving the release channels, defaults to the X1 release endpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_url: Option<String>,
+    /// Signer of the release archives, when set the archives of versioned and channel releases
+    /// are only installed with a valid signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_signer: Option<Pubkey>,
 }
 
 const LEGACY_FMT_LOAD_ERR: &str =
@@ -44,6 +51,8 @@ impl Config {
             explicit_release,
             releases_dir: PathBuf::from(data_dir).join("releases"),
             active_release_dir: PathBuf::from(data_dir).join("active_release"),
+            release_url: None,
+            release_signer: None,
         }
     }
 
@@ -126,6 +135,10 @@ impl Config {
     pub fn release_dir(&self, release_id: &str) -> PathBuf {
         self.releases_dir.join(release_id)
     }
+
+    pub fn release_url(&self) -> &str {
+        self.release_url.as_deref().unwrap_or(defaults::RELEASE_URL)
+    }
 }
 
 #[cfg(test)]
@@ -202,6 +215,14 @@ active_release_dir: {root_dir}/active_release
         );
     }
 
+    #[test]
+    fn test_release_url() {
+        let mut config = Config::new("/tmp", "http://localhost", &Pubkey::default(), None);
+        assert_eq!(config.release_url(), defaults::RELEASE_URL);
+        config.release_url = Some("https://releases.example.com".to_string());
+        assert_eq!(config.release_url(), "https://releases.example.com");
+    }
+
     #[test]
     fn test_load_serde_yaml_v_0_8_config() {
         let file_name = "config.yml";
@@ -269,6 +290,8 @@ active_release_dir: {root_dir}/active_release
                 explicit_release: Some(ExplicitRelease::Semver(String::from("1.13.6"))),
                 releases_dir: PathBuf::from(format!("{root_dir}/releases")),
                 active_release_dir: PathBuf::from(format!("{root_dir}/active_release")),
+                release_url: None,
+                release_signer: None,
             },
         );
     }
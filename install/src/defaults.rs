@@ -1,4 +1,5 @@
 pub const JSON_RPC_URL: &str = "http://api.devnet.solana.com";
+pub const RELEASE_URL: &str = "https://release.x1.xyz";
 
 lazy_static! {
     pub static ref CONFIG_FILE: Option<String> = {
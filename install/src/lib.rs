@@ -6,8 +6,9 @@ use {
     clap::{crate_description, crate_name, App, AppSettings, Arg, ArgMatches, SubCommand},
     solana_clap_utils::{
         input_parsers::pubkey_of,
-        input_validators::{is_pubkey, is_url},
+        input_validators::{is_parsable, is_pubkey, is_url},
     },
+    std::time::Duration,
 };
 
 mod build_env;
@@ -64,6 +65,8 @@ fn handle_init(matches: &ArgMatches<'_>, config_file: &str) -> Result<(), String
     let data_dir = matches.value_of("data_dir").unwrap();
     let no_modify_path = matches.is_present("no_modify_path");
     let explicit_release = explicit_release_of(matches, "explicit_release");
+    let release_url = matches.value_of("release_url").map(ToString::to_string);
+    let release_signer = pubkey_of(matches, "release_signer");
 
     if update_manifest_pubkey.is_none() && explicit_release.is_none() {
         Err(format!(
@@ -78,10 +81,36 @@ fn handle_init(matches: &ArgMatches<'_>, config_file: &str) -> Result<(), String
             &update_manifest_pubkey.unwrap_or_default(),
             no_modify_path,
             explicit_release,
+            release_url,
+            release_signer,
         )
     }
 }
 
+fn release_url_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("release_url")
+        .long("release-url")
+        .value_name("URL")
+        .takes_value(true)
+        .validator(is_url)
+        .help(concat!(
+            "URL of the endpoint serving the edge, beta and stable release channels ",
+            "[default: https://release.x1.xyz]"
+        ))
+}
+
+fn release_signer_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("release_signer")
+        .long("release-signer")
+        .value_name("PUBKEY")
+        .takes_value(true)
+        .validator(is_pubkey)
+        .help(concat!(
+            "Only install versioned and channel releases whose archive is signed by this key. ",
+            "The signature of the SHA256 of an archive is expected next to it, with a .sig suffix"
+        ))
+}
+
 pub fn main() -> Result<(), String> {
     solana_logger::setup();
 
@@ -150,7 +179,9 @@ pub fn main() -> Result<(), String> {
                         .conflicts_with_all(&["json_rpc_url", "update_manifest_pubkey"])
                         .validator(is_explicit_release)
                         .help("The release version or channel to install"),
-                ),
+                )
+                .arg(release_url_arg())
+                .arg(release_signer_arg()),
         )
         .subcommand(
             SubCommand::with_name("info")
@@ -235,6 +266,35 @@ pub fn main() -> Result<(), String> {
                         .index(2)
                         .multiple(true)
                         .help("arguments to supply to the program"),
+                )
+                .arg(
+                    Arg::with_name("update_on_epoch_boundary")
+                        .long("update-on-epoch-boundary")
+                        .requires("health_check_url")
+                        .help(
+                            "Stage downloaded updates and only apply them once the epoch \
+                             reported by --health-check-url changes",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("health_check_url")
+                        .long("health-check-url")
+                        .value_name("URL")
+                        .takes_value(true)
+                        .validator(is_url)
+                        .help(
+                            "JSON RPC URL of the program. An update is rolled back if the \
+                             program does not report healthy within --health-check-timeout",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("health_check_timeout")
+                        .long("health-check-timeout")
+                        .value_name("SECONDS")
+                        .takes_value(true)
+                        .default_value("600")
+                        .validator(is_parsable::<u64>)
+                        .help("How long the program has to report healthy after an update"),
                 ),
         )
         .subcommand(SubCommand::with_name("list").about("List installed versions of solana cli"))
@@ -271,7 +331,21 @@ pub fn main() -> Result<(), String> {
                 .map(Iterator::collect)
                 .unwrap_or_else(Vec::new);
 
-            command::run(config_file, program_name, program_arguments)
+            let update_policy = command::UpdatePolicy {
+                update_on_epoch_boundary: matches.is_present("update_on_epoch_boundary"),
+                health_check_url: matches
+                    .value_of("health_check_url")
+                    .map(ToString::to_string),
+                health_check_timeout: Duration::from_secs(
+                    matches
+                        .value_of("health_check_timeout")
+                        .unwrap()
+                        .parse()
+                        .unwrap(),
+                ),
+            };
+
+            command::run(config_file, program_name, program_arguments, update_policy)
         }
         ("list", Some(_matches)) => command::list(config_file),
         _ => unreachable!(),
@@ -341,6 +415,8 @@ pub fn main_init() -> Result<(), String> {
                 .validator(is_explicit_release)
                 .help("The release version or channel to install"),
         )
+        .arg(release_url_arg())
+        .arg(release_signer_arg())
         .get_matches();
 
     let config_file = matches.value_of("config_file").unwrap();
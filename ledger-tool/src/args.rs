@@ -134,6 +134,16 @@ pub fn accounts_db_args<'a, 'b>() -> Box<[Arg<'a, 'b>]> {
             .long("accounts-db-verify-experimental-accumulator-hash")
             .help("Verifies the experimental accumulator hash")
             .hidden(hidden_unless_forced()),
+        Arg::with_name("accounts_db_experimental_accumulator_hash_dual_mode_epochs")
+            .long("accounts-db-experimental-accumulator-hash-dual-mode-epochs")
+            .value_name("EPOCHS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .help(
+                "Number of epochs in which the experimental accumulator hash is recalculated at \
+                 the epoch accounts hash and compared against the one maintained incrementally",
+            )
+            .hidden(hidden_unless_forced()),
         Arg::with_name("accounts_db_snapshots_use_experimental_accumulator_hash")
             .long("accounts-db-snapshots-use-experimental-accumulator-hash")
             .help("Snapshots use the experimental accumulator hash")
@@ -391,6 +401,12 @@ pub fn get_accounts_db_config(
             .is_present("no_accounts_db_experimental_accumulator_hash"),
         verify_experimental_accumulator_hash: arg_matches
             .is_present("accounts_db_verify_experimental_accumulator_hash"),
+        experimental_accumulator_hash_dual_mode_epochs: value_t!(
+            arg_matches,
+            "accounts_db_experimental_accumulator_hash_dual_mode_epochs",
+            u64
+        )
+        .unwrap_or_default(),
         snapshots_use_experimental_accumulator_hash: arg_matches
             .is_present("accounts_db_snapshots_use_experimental_accumulator_hash"),
         num_hash_threads,
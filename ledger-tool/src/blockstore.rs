@@ -13,6 +13,7 @@ use {
     },
     itertools::Itertools,
     log::*,
+    rayon::prelude::*,
     regex::Regex,
     serde_json::json,
     solana_clap_utils::{hidden_unless_forced, input_validators::is_slot},
@@ -21,7 +22,7 @@ use {
         ancestor_iterator::AncestorIterator,
         blockstore::{
             column::{Column, ColumnName},
-            Blockstore, PurgeType,
+            Blockstore, BlockstoreError, PurgeType,
         },
         blockstore_options::AccessType,
         shred::Shred,
@@ -30,10 +31,14 @@ use {
         clock::{Slot, UnixTimestamp},
         hash::Hash,
     },
+    solana_transaction_status::{
+        BlockEncodingOptions, ConfirmedBlock, TransactionDetails, UiTransactionEncoding,
+    },
     std::{
         collections::{BTreeMap, BTreeSet, HashMap},
-        fs::File,
-        io::{stdout, BufRead, BufReader, Write},
+        fs::{self, File, OpenOptions},
+        io::{stdout, BufRead, BufReader, BufWriter, Write},
+        ops::RangeInclusive,
         path::{Path, PathBuf},
         sync::atomic::AtomicBool,
         time::{Duration, UNIX_EPOCH},
@@ -125,6 +130,8 @@ fn analyze_storage(blockstore: &Blockstore) -> Result<()> {
     analyze_column(blockstore, ShredCode::NAME)?;
     analyze_column(blockstore, TransactionStatus::NAME)?;
     analyze_column(blockstore, AddressSignatures::NAME)?;
+    analyze_column(blockstore, AddressSignatureShards::NAME)?;
+    analyze_column(blockstore, AddressSignatureShardMarkers::NAME)?;
     analyze_column(blockstore, TransactionMemos::NAME)?;
     analyze_column(blockstore, TransactionStatusIndex::NAME)?;
     analyze_column(blockstore, Rewards::NAME)?;
@@ -154,6 +161,12 @@ fn raw_key_to_slot(key: &[u8], column_name: &str) -> Option<Slot> {
         cf::AddressSignatures::NAME => Some(cf::AddressSignatures::slot(
             cf::AddressSignatures::index(key),
         )),
+        cf::AddressSignatureShards::NAME => Some(cf::AddressSignatureShards::slot(
+            cf::AddressSignatureShards::index(key),
+        )),
+        cf::AddressSignatureShardMarkers::NAME => Some(cf::AddressSignatureShardMarkers::slot(
+            cf::AddressSignatureShardMarkers::index(key),
+        )),
         cf::TransactionMemos::NAME => None, // does not implement slot()
         cf::TransactionStatusIndex::NAME => None, // does not implement slot()
         cf::Rewards::NAME => Some(cf::Rewards::slot(cf::Rewards::index(key))),
@@ -266,6 +279,113 @@ fn print_blockstore_file_metadata(blockstore: &Blockstore, file_name: &Option<&s
     Ok(())
 }
 
+/// Parses a slot range of the form `START..END`, both ends inclusive
+pub(crate) fn parse_slot_range(range: &str) -> std::result::Result<RangeInclusive<Slot>, String> {
+    let (start, end) = range
+        .split_once("..")
+        .ok_or_else(|| format!("Invalid slot range {range}, expected START..END"))?;
+    let start = start
+        .parse::<Slot>()
+        .map_err(|err| format!("Invalid starting slot {start}: {err}"))?;
+    let end = end
+        .parse::<Slot>()
+        .map_err(|err| format!("Invalid ending slot {end}: {err}"))?;
+    if start > end {
+        return Err(format!(
+            "Starting slot {start} should be less than or equal to ending slot {end}"
+        ));
+    }
+    Ok(start..=end)
+}
+
+/// Encodes the rooted block at `slot`, with its transactions and fee summary, as one line of
+/// JSON. Returns `None` if the block is not available in the blockstore.
+fn export_block_json(blockstore: &Blockstore, slot: Slot) -> Result<Option<String>> {
+    let block = match blockstore.get_rooted_block(slot, false) {
+        Ok(block) => block,
+        Err(BlockstoreError::SlotUnavailable | BlockstoreError::SlotNotRooted) => {
+            warn!("Block for slot {slot} is not available, skipping");
+            return Ok(None);
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let block = ConfirmedBlock::from(block).encode_with_options(
+        UiTransactionEncoding::Json,
+        BlockEncodingOptions {
+            transaction_details: TransactionDetails::Full,
+            show_rewards: true,
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+    let fee_summary = blockstore.get_block_fee_summary(slot)?.map(|fee_summary| {
+        json!({
+            "leader": fee_summary.leader.to_string(),
+            "transactionFee": fee_summary.transaction_fee,
+            "priorityFee": fee_summary.priority_fee,
+            "burned": fee_summary.burned,
+            "leaderReward": fee_summary.leader_reward,
+        })
+    });
+    let line = json!({
+        "slot": slot,
+        "block": block,
+        "feeSummary": fee_summary,
+    });
+    Ok(Some(serde_json::to_string(&line)?))
+}
+
+/// Writes the rooted blocks in `slots` to `output` as newline-delimited JSON, in slot order.
+///
+/// The blocks of each batch are read in parallel. The last slot of each written batch is
+/// recorded in `checkpoint`, so an interrupted export can be resumed past it. Returns the
+/// number of blocks written.
+fn export_json(
+    blockstore: &Blockstore,
+    slots: RangeInclusive<Slot>,
+    batch_size: usize,
+    output: &mut dyn Write,
+    checkpoint: Option<&Path>,
+) -> Result<usize> {
+    let rooted_slots: Vec<_> = blockstore
+        .rooted_slot_iterator(*slots.start())?
+        .take_while(|slot| slot <= slots.end())
+        .collect();
+
+    let mut num_blocks = 0;
+    for batch in rooted_slots.chunks(batch_size.max(1)) {
+        let lines = batch
+            .par_iter()
+            .map(|slot| export_block_json(blockstore, *slot))
+            .collect::<Result<Vec<_>>>()?;
+        for line in lines.into_iter().flatten() {
+            writeln!(output, "{line}")?;
+            num_blocks += 1;
+        }
+        output.flush()?;
+        if let (Some(checkpoint), Some(last_slot)) = (checkpoint, batch.last()) {
+            fs::write(checkpoint, last_slot.to_string())?;
+        }
+    }
+    Ok(num_blocks)
+}
+
+/// Returns the last slot exported according to `checkpoint`, if the file exists
+fn read_export_checkpoint(checkpoint: &Path) -> Result<Option<Slot>> {
+    if !checkpoint.exists() {
+        return Ok(None);
+    }
+    fs::read_to_string(checkpoint)?
+        .trim()
+        .parse()
+        .map(Some)
+        .map_err(|err| {
+            LedgerToolError::BadArgument(format!(
+                "invalid checkpoint file {}: {err}",
+                checkpoint.display()
+            ))
+        })
+}
+
 pub trait BlockstoreSubCommand {
     fn blockstore_subcommand(self) -> Self;
 }
@@ -345,6 +465,47 @@ pub fn blockstore_subcommands<'a, 'b>(hidden: bool) -> Vec<App<'a, 'b>> {
             .about("Print all the duplicate slots in the ledger")
             .settings(&hidden)
             .arg(&starting_slot_arg),
+        SubCommand::with_name("export-json")
+            .about(
+                "Export the rooted blocks in a range of slots, with their transactions and fee \
+                 breakdowns, as newline-delimited JSON",
+            )
+            .settings(&hidden)
+            .arg(
+                Arg::with_name("slots")
+                    .long("slots")
+                    .value_name("START..END")
+                    .takes_value(true)
+                    .required(true)
+                    .validator(|range| parse_slot_range(&range).map(|_| ()))
+                    .help("The range of slots to export, both ends inclusive"),
+            )
+            .arg(
+                Arg::with_name("output")
+                    .long("output")
+                    .value_name("FILE")
+                    .takes_value(true)
+                    .help("Write the blocks to FILE [default: stdout]"),
+            )
+            .arg(
+                Arg::with_name("checkpoint")
+                    .long("checkpoint")
+                    .value_name("FILE")
+                    .takes_value(true)
+                    .help(
+                        "Record the last exported slot in FILE, and resume after the slot \
+                         recorded in it if it already exists. When resuming, the blocks are \
+                         appended to the output file",
+                    ),
+            )
+            .arg(
+                Arg::with_name("batch_size")
+                    .long("batch-size")
+                    .value_name("NUM")
+                    .takes_value(true)
+                    .default_value("64")
+                    .help("Number of slots read in parallel between checkpoints"),
+            ),
         SubCommand::with_name("latest-optimistic-slots")
             .about(
                 "Output up to the most recent <num-slots> optimistic slots with their hashes \
@@ -407,6 +568,10 @@ pub fn blockstore_subcommands<'a, 'b>(hidden: bool) -> Vec<App<'a, 'b>> {
                     .required(false)
                     .help("Number of roots in the output"),
             ),
+        SubCommand::with_name("migrate-address-signatures").about(
+            "Move the address signatures written before they were sharded to the address \
+                 signature shards. The validator must not be running",
+        ),
         SubCommand::with_name("parse_full_frozen")
             .about(
                 "Parses log for information about critical events about ancestors of the given \
@@ -704,6 +869,47 @@ fn do_blockstore_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -
                 }
             }
         }
+        ("export-json", Some(arg_matches)) => {
+            let slots = parse_slot_range(arg_matches.value_of("slots").unwrap())
+                .map_err(LedgerToolError::BadArgument)?;
+            let batch_size = value_t_or_exit!(arg_matches, "batch_size", usize);
+            let checkpoint = arg_matches.value_of("checkpoint").map(PathBuf::from);
+
+            let last_exported_slot = match &checkpoint {
+                Some(checkpoint) => read_export_checkpoint(checkpoint)?,
+                None => None,
+            };
+            let slots = match last_exported_slot {
+                Some(last_exported_slot) => {
+                    info!("Resuming export after slot {last_exported_slot}");
+                    last_exported_slot.saturating_add(1).max(*slots.start())..=*slots.end()
+                }
+                None => slots,
+            };
+
+            let mut output: Box<dyn Write> = match arg_matches.value_of("output") {
+                Some(path) => Box::new(BufWriter::new(
+                    OpenOptions::new()
+                        .create(true)
+                        .write(true)
+                        .append(last_exported_slot.is_some())
+                        .truncate(last_exported_slot.is_none())
+                        .open(path)?,
+                )),
+                None => Box::new(BufWriter::new(stdout())),
+            };
+
+            let blockstore =
+                crate::open_blockstore(&ledger_path, arg_matches, AccessType::Secondary);
+            let num_blocks = export_json(
+                &blockstore,
+                slots,
+                batch_size,
+                &mut output,
+                checkpoint.as_deref(),
+            )?;
+            info!("Exported {num_blocks} blocks");
+        }
         ("latest-optimistic-slots", Some(arg_matches)) => {
             let blockstore =
                 crate::open_blockstore(&ledger_path, arg_matches, AccessType::Secondary);
@@ -764,6 +970,11 @@ fn do_blockstore_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -
                 writeln!(output, "{slot}: {blockhash:?}").expect("failed to write");
             }
         }
+        ("migrate-address-signatures", Some(arg_matches)) => {
+            let blockstore = crate::open_blockstore(&ledger_path, arg_matches, AccessType::Primary);
+            let migrated = blockstore.migrate_address_signatures_to_shards()?;
+            println!("Migrated {migrated} address signatures to shards");
+        }
         ("parse_full_frozen", Some(arg_matches)) => {
             let starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
             let ending_slot = value_t_or_exit!(arg_matches, "ending_slot", Slot);
@@ -1039,6 +1250,53 @@ pub mod tests {
         solana_ledger::{blockstore::make_many_slot_entries, get_tmp_ledger_path_auto_delete},
     };
 
+    #[test]
+    fn test_parse_slot_range() {
+        assert_eq!(parse_slot_range("10..20"), Ok(10..=20));
+        assert_eq!(parse_slot_range("7..7"), Ok(7..=7));
+        assert!(parse_slot_range("20..10").is_err());
+        assert!(parse_slot_range("10").is_err());
+        assert!(parse_slot_range("a..10").is_err());
+        assert!(parse_slot_range("10..").is_err());
+    }
+
+    #[test]
+    fn test_export_json() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let checkpoint_dir = tempfile::TempDir::new().unwrap();
+        let checkpoint = checkpoint_dir.path().join("checkpoint");
+
+        let (shreds, _) = make_many_slot_entries(0, 6, 5);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+        blockstore.set_roots([0, 1, 2, 4, 5].iter()).unwrap();
+
+        let exported_slots = |output: &[u8]| -> Vec<Slot> {
+            output
+                .split(|byte| *byte == b'\n')
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    let line: serde_json::Value = serde_json::from_slice(line).unwrap();
+                    assert!(line["block"]["blockhash"].is_string());
+                    line["slot"].as_u64().unwrap()
+                })
+                .collect()
+        };
+
+        // Slot 3 is not rooted
+        let mut output = vec![];
+        let num_blocks =
+            export_json(&blockstore, 1..=4, 2, &mut output, Some(&checkpoint)).unwrap();
+        assert_eq!(num_blocks, 3);
+        assert_eq!(exported_slots(&output), vec![1, 2, 4]);
+        assert_eq!(read_export_checkpoint(&checkpoint).unwrap(), Some(4));
+
+        let mut output = vec![];
+        export_json(&blockstore, 5..=10, 2, &mut output, Some(&checkpoint)).unwrap();
+        assert_eq!(exported_slots(&output), vec![5]);
+        assert_eq!(read_export_checkpoint(&checkpoint).unwrap(), Some(5));
+    }
+
     #[test]
     fn test_latest_optimistic_ancestors() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
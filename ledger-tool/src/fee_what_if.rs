@@ -0,0 +1,501 @@
+//! The `fee-what-if` subcommand
+
+use {
+    crate::{blockstore::parse_slot_range, ledger_path::canonicalize_ledger_path, open_blockstore},
+    agave_feature_set::FeatureSet,
+    agave_reserved_account_keys::ReservedAccountKeys,
+    clap::{value_t, App, Arg, ArgMatches, SubCommand},
+    log::*,
+    rayon::prelude::*,
+    serde_derive::Serialize,
+    solana_clap_utils::input_validators::is_parsable,
+    solana_cli_output::{OutputFormat, QuietDisplay, VerboseDisplay},
+    solana_fee::{
+        FeeBreakdown, FeeFeatures, BASE_FEE_MULTIPLIER, MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+    },
+    solana_ledger::{
+        blockstore::{Blockstore, BlockstoreError},
+        blockstore_options::AccessType,
+    },
+    solana_sdk::{
+        clock::Slot,
+        pubkey::Pubkey,
+        transaction::{MessageHash, SanitizedTransaction, SimpleAddressLoader},
+    },
+    solana_transaction_status::VersionedTransactionWithStatusMeta,
+    std::{
+        collections::HashSet,
+        fmt::{self, Display, Formatter},
+        ops::RangeInclusive,
+        path::Path,
+        process::exit,
+    },
+};
+
+pub trait FeeWhatIfSubCommand {
+    fn fee_what_if_subcommand(self) -> Self;
+}
+
+impl FeeWhatIfSubCommand for App<'_, '_> {
+    fn fee_what_if_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("fee-what-if")
+                .about(
+                    "Re-price the transactions of historical blocks under hypothetical fee \
+                     parameters, and report the fee revenue and the distribution of the fees \
+                     paid by users",
+                )
+                .arg(
+                    Arg::with_name("slots")
+                        .long("slots")
+                        .value_name("START..END")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(|range| parse_slot_range(&range).map(|_| ()))
+                        .help("The range of rooted slots to re-price, both ends inclusive"),
+                )
+                .arg(
+                    Arg::with_name("multiplier")
+                        .long("multiplier")
+                        .value_name("LAMPORTS")
+                        .takes_value(true)
+                        .validator(is_parsable::<u64>)
+                        .help(
+                            "Base fee charged per compute unit of a transaction \
+                             [default: the current base fee multiplier]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("min_price")
+                        .long("min-price")
+                        .value_name("MICROLAMPORTS")
+                        .takes_value(true)
+                        .validator(is_parsable::<u64>)
+                        .help(
+                            "Minimum compute unit price charged to transactions using few \
+                             compute units [default: the current minimum price]",
+                        ),
+                ),
+        )
+    }
+}
+
+/// The fee of a transaction as recorded, and as re-priced under the current and the
+/// hypothetical fee parameters
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct TransactionFees {
+    recorded: u64,
+    current: FeeBreakdown,
+    hypothetical: FeeBreakdown,
+}
+
+/// Summary of the fees paid by a set of transactions, in lamports
+#[derive(Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeDistribution {
+    pub total: u64,
+    pub mean: f64,
+    pub min: u64,
+    pub p25: u64,
+    pub median: u64,
+    pub p75: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+impl FeeDistribution {
+    fn new(mut fees: Vec<u64>) -> Self {
+        if fees.is_empty() {
+            return Self::default();
+        }
+        fees.sort_unstable();
+        let percentile = |percentile: usize| fees[(fees.len() - 1) * percentile / 100];
+        let total = fees
+            .iter()
+            .fold(0u64, |total, fee| total.saturating_add(*fee));
+        Self {
+            total,
+            mean: total as f64 / fees.len() as f64,
+            min: fees[0],
+            p25: percentile(25),
+            median: percentile(50),
+            p75: percentile(75),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: fees[fees.len() - 1],
+        }
+    }
+}
+
+impl Display for FeeDistribution {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:>16} {:>12.0} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>12}",
+            self.total,
+            self.mean,
+            self.min,
+            self.p25,
+            self.median,
+            self.p75,
+            self.p90,
+            self.p99,
+            self.max,
+        )
+    }
+}
+
+/// Total fee revenue of the re-priced transactions, by component
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeRevenue {
+    pub base: u64,
+    pub prioritization: u64,
+    pub memory: u64,
+    pub size: u64,
+    pub congestion: u64,
+}
+
+impl FeeRevenue {
+    fn add(&mut self, fee_breakdown: &FeeBreakdown) {
+        self.base = self.base.saturating_add(fee_breakdown.base);
+        self.prioritization = self
+            .prioritization
+            .saturating_add(fee_breakdown.prioritization);
+        self.memory = self.memory.saturating_add(fee_breakdown.memory);
+        self.size = self.size.saturating_add(fee_breakdown.size);
+        self.congestion = self.congestion.saturating_add(fee_breakdown.congestion);
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeWhatIfReport {
+    pub first_slot: Slot,
+    pub last_slot: Slot,
+    pub base_fee_multiplier: u64,
+    pub min_compute_unit_price: u64,
+    pub blocks: usize,
+    /// Rooted slots of the range whose block or transaction statuses are not available
+    pub unavailable_blocks: usize,
+    pub transactions: usize,
+    /// Fees recorded in the transaction statuses
+    pub recorded: FeeDistribution,
+    /// Fees re-priced under the current fee parameters, which may differ from the recorded fees
+    /// of blocks produced under earlier fee rules
+    pub current: FeeDistribution,
+    pub hypothetical: FeeDistribution,
+    pub current_revenue: FeeRevenue,
+    pub hypothetical_revenue: FeeRevenue,
+    /// Change of the total fee revenue from the current to the hypothetical parameters
+    pub revenue_change_percent: f64,
+    pub transactions_paying_more: usize,
+    pub transactions_paying_less: usize,
+}
+
+impl QuietDisplay for FeeWhatIfReport {}
+impl VerboseDisplay for FeeWhatIfReport {}
+
+impl Display for FeeWhatIfReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "Slots: {}..{}", self.first_slot, self.last_slot)?;
+        writeln!(
+            f,
+            "Blocks: {} ({} unavailable), transactions: {}",
+            self.blocks, self.unavailable_blocks, self.transactions
+        )?;
+        writeln!(
+            f,
+            "Hypothetical parameters: base fee multiplier {} (current {BASE_FEE_MULTIPLIER}), \
+             minimum compute unit price {} (current {MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS})",
+            self.base_fee_multiplier, self.min_compute_unit_price,
+        )?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<14} {:>16} {:>12} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>12}",
+            "Fees", "Total", "Mean", "Min", "P25", "Median", "P75", "P90", "P99", "Max",
+        )?;
+        writeln!(f, "{:<14} {}", "Recorded", self.recorded)?;
+        writeln!(f, "{:<14} {}", "Current", self.current)?;
+        writeln!(f, "{:<14} {}", "Hypothetical", self.hypothetical)?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<14} {:>16} {:>16} {:>12} {:>12} {:>16}",
+            "Revenue", "Base", "Prioritization", "Memory", "Size", "Congestion",
+        )?;
+        for (name, revenue) in [
+            ("Current", &self.current_revenue),
+            ("Hypothetical", &self.hypothetical_revenue),
+        ] {
+            writeln!(
+                f,
+                "{:<14} {:>16} {:>16} {:>12} {:>12} {:>16}",
+                name,
+                revenue.base,
+                revenue.prioritization,
+                revenue.memory,
+                revenue.size,
+                revenue.congestion,
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Revenue change: {:+.2}%", self.revenue_change_percent)?;
+        write!(
+            f,
+            "Transactions paying more: {}, paying less: {}",
+            self.transactions_paying_more, self.transactions_paying_less,
+        )
+    }
+}
+
+/// Re-prices a transaction of a historical block. Simple votes are not charged fees and are
+/// left out.
+fn reprice_transaction(
+    transaction: VersionedTransactionWithStatusMeta,
+    reserved_account_keys: &HashSet<Pubkey>,
+    current: FeeFeatures,
+    hypothetical: FeeFeatures,
+) -> Option<TransactionFees> {
+    let VersionedTransactionWithStatusMeta { transaction, meta } = transaction;
+    let transaction = SanitizedTransaction::try_create(
+        transaction,
+        MessageHash::Compute,
+        None,
+        SimpleAddressLoader::Enabled(meta.loaded_addresses),
+        reserved_account_keys,
+    )
+    .inspect_err(|err| warn!("Failed to sanitize transaction: {err}"))
+    .ok()?;
+    if solana_fee::is_conforming_vote_transaction(&transaction) {
+        return None;
+    }
+    let prioritization_fee = solana_fee::get_prioritization_fee(&transaction);
+    let reprice = |fee_features| {
+        solana_fee::calculate_fee_breakdown(
+            &transaction,
+            false, /* zero_fees_for_test */
+            0,     /* lamports_per_signature, unused */
+            prioritization_fee,
+            fee_features,
+        )
+    };
+    Some(TransactionFees {
+        recorded: meta.fee,
+        current: reprice(current),
+        hypothetical: reprice(hypothetical),
+    })
+}
+
+/// Re-prices the transactions of the rooted blocks in `slots`
+fn fee_what_if_report(
+    blockstore: &Blockstore,
+    slots: RangeInclusive<Slot>,
+    base_fee_multiplier: u64,
+    min_compute_unit_price: u64,
+) -> Result<FeeWhatIfReport, BlockstoreError> {
+    let current = FeeFeatures::from(&FeatureSet::all_enabled());
+    let hypothetical = FeeFeatures {
+        base_fee_multiplier,
+        min_compute_unit_price,
+        ..current
+    };
+    let reserved_account_keys = ReservedAccountKeys::new_all_activated();
+
+    let rooted_slots: Vec<_> = blockstore
+        .rooted_slot_iterator(*slots.start())?
+        .take_while(|slot| slot <= slots.end())
+        .collect();
+    let blocks = rooted_slots
+        .par_iter()
+        .map(|slot| match blockstore.get_rooted_block(*slot, false) {
+            Ok(block) => Ok(Some(
+                block
+                    .transactions
+                    .into_iter()
+                    .filter_map(|transaction| {
+                        reprice_transaction(
+                            transaction,
+                            &reserved_account_keys.active,
+                            current,
+                            hypothetical,
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )),
+            Err(
+                BlockstoreError::SlotUnavailable
+                | BlockstoreError::SlotNotRooted
+                | BlockstoreError::MissingTransactionMetadata,
+            ) => {
+                warn!("Block for slot {slot} is not available, skipping");
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let unavailable_blocks = blocks.iter().filter(|block| block.is_none()).count();
+    let transactions: Vec<_> = blocks.into_iter().flatten().flatten().collect();
+    let mut current_revenue = FeeRevenue::default();
+    let mut hypothetical_revenue = FeeRevenue::default();
+    for fees in &transactions {
+        current_revenue.add(&fees.current);
+        hypothetical_revenue.add(&fees.hypothetical);
+    }
+    let distribution = |fee: fn(&TransactionFees) -> u64| {
+        FeeDistribution::new(transactions.iter().map(fee).collect())
+    };
+    let current_fees = distribution(|fees| fees.current.total_fee());
+    let hypothetical_fees = distribution(|fees| fees.hypothetical.total_fee());
+    let revenue_change_percent = if current_fees.total == 0 {
+        0.0
+    } else {
+        (hypothetical_fees.total as f64 - current_fees.total as f64) * 100.0
+            / current_fees.total as f64
+    };
+    Ok(FeeWhatIfReport {
+        first_slot: *slots.start(),
+        last_slot: *slots.end(),
+        base_fee_multiplier,
+        min_compute_unit_price,
+        blocks: rooted_slots.len() - unavailable_blocks,
+        unavailable_blocks,
+        transactions: transactions.len(),
+        recorded: distribution(|fees| fees.recorded),
+        current: current_fees,
+        hypothetical: hypothetical_fees,
+        current_revenue,
+        hypothetical_revenue,
+        revenue_change_percent,
+        transactions_paying_more: transactions
+            .iter()
+            .filter(|fees| fees.hypothetical.total_fee() > fees.current.total_fee())
+            .count(),
+        transactions_paying_less: transactions
+            .iter()
+            .filter(|fees| fees.hypothetical.total_fee() < fees.current.total_fee())
+            .count(),
+    })
+}
+
+pub fn fee_what_if(ledger_path: &Path, matches: &ArgMatches<'_>) {
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    let slots = parse_slot_range(matches.value_of("slots").unwrap()).unwrap();
+    let base_fee_multiplier = value_t!(matches, "multiplier", u64).unwrap_or(BASE_FEE_MULTIPLIER);
+    let min_compute_unit_price =
+        value_t!(matches, "min_price", u64).unwrap_or(MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS);
+    let output_format = OutputFormat::from_matches(matches, "output_format", false);
+
+    let blockstore = open_blockstore(&ledger_path, matches, AccessType::Secondary);
+    let report = fee_what_if_report(
+        &blockstore,
+        slots,
+        base_fee_multiplier,
+        min_compute_unit_price,
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("Failed to re-price the blocks: {err}");
+        exit(1);
+    });
+    println!("{}", output_format.formatted_string(&report));
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{
+            compute_budget::ComputeBudgetInstruction,
+            hash::Hash,
+            message::Message,
+            signature::{Keypair, Signer},
+            system_instruction,
+            transaction::{Transaction, VersionedTransaction},
+        },
+        solana_transaction_status::TransactionStatusMeta,
+    };
+
+    #[test]
+    fn test_fee_distribution() {
+        assert_eq!(FeeDistribution::new(vec![]), FeeDistribution::default());
+        let distribution = FeeDistribution::new((1..=100).rev().collect());
+        assert_eq!(distribution.total, 5_050);
+        assert_eq!(distribution.mean, 50.5);
+        assert_eq!(distribution.min, 1);
+        assert_eq!(distribution.p25, 25);
+        assert_eq!(distribution.median, 50);
+        assert_eq!(distribution.p99, 99);
+        assert_eq!(distribution.max, 100);
+    }
+
+    #[test]
+    fn test_reprice_transaction() {
+        let payer = Keypair::new();
+        let transaction = |instructions: &[_]| VersionedTransactionWithStatusMeta {
+            transaction: VersionedTransaction::from(Transaction::new(
+                &[&payer],
+                Message::new(instructions, Some(&payer.pubkey())),
+                Hash::default(),
+            )),
+            meta: TransactionStatusMeta {
+                fee: 42,
+                ..TransactionStatusMeta::default()
+            },
+        };
+        let transfer = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        let current = FeeFeatures::from(&FeatureSet::all_enabled());
+        let reserved_account_keys = ReservedAccountKeys::new_all_activated();
+        let reprice = |transaction, hypothetical| {
+            reprice_transaction(
+                transaction,
+                &reserved_account_keys.active,
+                current,
+                hypothetical,
+            )
+            .unwrap()
+        };
+
+        // Doubling the multiplier doubles the base fee
+        let fees = reprice(
+            transaction(&[transfer.clone()]),
+            FeeFeatures {
+                base_fee_multiplier: 2 * BASE_FEE_MULTIPLIER,
+                ..current
+            },
+        );
+        assert_eq!(fees.recorded, 42);
+        assert!(fees.current.base > 0);
+        assert_eq!(fees.hypothetical.base, 2 * fees.current.base);
+        assert_eq!(fees.hypothetical.congestion, fees.current.congestion);
+
+        // A lower minimum price lowers the fee of transactions using few compute units
+        let fees = reprice(
+            transaction(&[transfer.clone()]),
+            FeeFeatures {
+                min_compute_unit_price: 0,
+                ..current
+            },
+        );
+        assert!(fees.current.congestion > 0);
+        assert_eq!(fees.hypothetical.congestion, 0);
+        assert_eq!(fees.hypothetical.base, fees.current.base);
+
+        // Prices above the minimum are not affected
+        let price = 2 * MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS;
+        let fees = reprice(
+            transaction(&[
+                ComputeBudgetInstruction::set_compute_unit_price(price),
+                transfer,
+            ]),
+            FeeFeatures {
+                min_compute_unit_price: 0,
+                ..current
+            },
+        );
+        assert_eq!(fees.hypothetical, fees.current);
+        assert!(fees.current.prioritization > 0);
+    }
+}
@@ -314,6 +314,7 @@ pub fn load_and_process_ledger(
             Arc::default(),
             enable_rpc_transaction_history,
             transaction_notifier,
+            None,
             write_blockstore.clone(),
             arg_matches.is_present("enable_extended_tx_metadata_storage"),
             tss_exit.clone(),
@@ -326,6 +327,7 @@ pub fn load_and_process_ledger(
             block_meta_receiver,
             write_blockstore,
             max_complete_rewards_slot,
+            None,
             exit.clone(),
         );
 
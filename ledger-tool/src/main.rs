@@ -4,6 +4,7 @@ use {
         args::*,
         bigtable::*,
         blockstore::*,
+        fee_what_if::*,
         ledger_path::*,
         ledger_utils::*,
         output::{
@@ -11,6 +12,8 @@ use {
             SlotBankHash,
         },
         program::*,
+        snapshot_diff::*,
+        zk_proof::*,
     },
     agave_feature_set::{self as feature_set, FeatureSet},
     agave_reserved_account_keys::ReservedAccountKeys,
@@ -34,6 +37,7 @@ use {
     solana_cli_output::OutputFormat,
     solana_core::{
         banking_simulation::{BankingSimulator, BankingTraceEvents},
+        banking_trace::BankingTracer,
         system_monitor_service::{SystemMonitorService, SystemMonitorStatsReportConfig},
         validator::{BlockProductionMethod, BlockVerificationMethod, TransactionStructure},
     },
@@ -104,10 +108,13 @@ mod args;
 mod bigtable;
 mod blockstore;
 mod error;
+mod fee_what_if;
 mod ledger_path;
 mod ledger_utils;
 mod output;
 mod program;
+mod snapshot_diff;
+mod zk_proof;
 
 fn parse_encoding_format(matches: &ArgMatches<'_>) -> UiAccountEncoding {
     match matches.value_of("encoding") {
@@ -533,6 +540,44 @@ fn minimize_bank_for_snapshot(
     possibly_incomplete
 }
 
+fn parse_snapshot_archive_format(arg_matches: &ArgMatches<'_>) -> ArchiveFormat {
+    let archive_format_str = value_t_or_exit!(arg_matches, "snapshot_archive_format", String);
+    let mut archive_format = ArchiveFormat::from_cli_arg(&archive_format_str)
+        .unwrap_or_else(|| panic!("Archive format not recognized: {archive_format_str}"));
+    if let ArchiveFormat::TarZstd { config } = &mut archive_format {
+        config.compression_level =
+            value_t_or_exit!(arg_matches, "snapshot_zstd_compression_level", i32);
+    }
+    archive_format
+}
+
+/// Resolves the `snapshot_slot` argument, which accepts the keyword ROOT for the highest root,
+/// and checks that the slot is full in `blockstore`.
+fn parse_snapshot_slot_or_exit(arg_matches: &ArgMatches<'_>, blockstore: &Blockstore) -> Slot {
+    let snapshot_slot = if Some("ROOT") == arg_matches.value_of("snapshot_slot") {
+        blockstore
+            .rooted_slot_iterator(0)
+            .expect("Failed to get rooted slot iterator")
+            .last()
+            .expect("Failed to get root")
+    } else {
+        value_t_or_exit!(arg_matches, "snapshot_slot", Slot)
+    };
+
+    if blockstore
+        .meta(snapshot_slot)
+        .unwrap()
+        .filter(|m| m.is_full())
+        .is_none()
+    {
+        eprintln!(
+            "Error: snapshot slot {snapshot_slot} does not exist in blockstore or is not full.",
+        );
+        exit(1);
+    }
+    snapshot_slot
+}
+
 fn assert_capitalization(bank: &Bank) {
     let debug_verify = true;
     assert!(bank.calculate_and_verify_capitalization(debug_verify));
@@ -586,6 +631,13 @@ fn read_banking_trace_event_file_paths_or_exit(banking_trace_path: PathBuf) -> V
         warn!("Error: no event files found");
     }
 
+    // Captured scheduling decisions are only consumed by tachyon-banking-trace-replay
+    for index in 0.. {
+        if !entry_names.remove(&OsString::from(BankingTracer::capture_file_name(index))) {
+            break;
+        }
+    }
+
     if !entry_names.is_empty() {
         let full_names = entry_names
             .into_iter()
@@ -1475,6 +1527,82 @@ fn main() {
                         .help("If snapshot creation should succeed with a capitalization delta."),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("create-minimized-snapshot")
+                .about(
+                    "Create a snapshot that only contains the accounts of the given programs \
+                     and the accounts required by the runtime",
+                )
+                .arg(&load_genesis_config_arg)
+                .args(&accounts_db_config_args)
+                .args(&snapshot_config_args)
+                .arg(&hard_forks_arg)
+                .arg(&snapshot_version_arg)
+                .arg(&log_messages_bytes_limit_arg)
+                .arg(
+                    Arg::with_name("snapshot_slot")
+                        .index(1)
+                        .value_name("SLOT")
+                        .validator(|value| {
+                            if value.parse::<Slot>().is_ok() || value == "ROOT" {
+                                Ok(())
+                            } else {
+                                Err(format!(
+                                    "Unable to parse as a number or the keyword ROOT, provided: \
+                                     {value}"
+                                ))
+                            }
+                        })
+                        .takes_value(true)
+                        .required(true)
+                        .help(
+                            "Slot at which to create the snapshot; accepts keyword ROOT for the \
+                             highest root",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("output_directory")
+                        .index(2)
+                        .value_name("DIR")
+                        .takes_value(true)
+                        .help(
+                            "Output directory for the snapshot \
+                            [default: --snapshot-archive-path if present else --ledger directory]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("programs")
+                        .long("programs")
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .validator(is_pubkey)
+                        .multiple(true)
+                        .use_delimiter(true)
+                        .required(true)
+                        .help(
+                            "Programs whose accounts are kept in the snapshot. Sysvars, features, \
+                             vote and stake accounts, and the accounts the programs depend on, \
+                             like their program data, are always kept.",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("snapshot_archive_format")
+                        .long("snapshot-archive-format")
+                        .possible_values(SUPPORTED_ARCHIVE_COMPRESSION)
+                        .default_value(DEFAULT_ARCHIVE_COMPRESSION)
+                        .value_name("ARCHIVE_TYPE")
+                        .takes_value(true)
+                        .help("Snapshot archive format to use."),
+                )
+                .arg(
+                    Arg::with_name("snapshot_zstd_compression_level")
+                        .long("snapshot-zstd-compression-level")
+                        .default_value("0")
+                        .value_name("LEVEL")
+                        .takes_value(true)
+                        .help("The compression level to use when archiving with zstd"),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("simulate-block-production")
                 .about("Simulate producing blocks with banking trace event files in the ledger")
@@ -1634,6 +1762,9 @@ fn main() {
                 .arg(&allow_dead_slots_arg),
         )
         .program_subcommand()
+        .diff_snapshots_subcommand()
+        .fee_what_if_subcommand()
+        .zk_proof_features_subcommand()
         .get_matches();
 
     info!("{} {}", crate_name!(), solana_version::version!());
@@ -1651,6 +1782,9 @@ fn main() {
         ("bigtable", Some(arg_matches)) => bigtable_process_command(&ledger_path, arg_matches),
         ("blockstore", Some(arg_matches)) => blockstore_process_command(&ledger_path, arg_matches),
         ("program", Some(arg_matches)) => program(&ledger_path, arg_matches),
+        ("diff-snapshots", Some(arg_matches)) => diff_snapshots(arg_matches),
+        ("fee-what-if", Some(arg_matches)) => fee_what_if(&ledger_path, arg_matches),
+        ("zk-proof-features", Some(arg_matches)) => zk_proof_features(&ledger_path, arg_matches),
         // This match case provides legacy support for commands that were previously top level
         // subcommands of the binary, but have been moved under the blockstore subcommand.
         ("analyze-storage", Some(_))
@@ -1658,6 +1792,7 @@ fn main() {
         | ("copy", Some(_))
         | ("dead-slots", Some(_))
         | ("duplicate-slots", Some(_))
+        | ("export-json", Some(_))
         | ("latest-optimistic-slots", Some(_))
         | ("list-roots", Some(_))
         | ("parse_full_frozen", Some(_))
@@ -1992,22 +2127,7 @@ fn main() {
                         },
                     );
 
-                    let snapshot_archive_format = {
-                        let archive_format_str =
-                            value_t_or_exit!(arg_matches, "snapshot_archive_format", String);
-                        let mut archive_format = ArchiveFormat::from_cli_arg(&archive_format_str)
-                            .unwrap_or_else(|| {
-                                panic!("Archive format not recognized: {archive_format_str}")
-                            });
-                        if let ArchiveFormat::TarZstd { config } = &mut archive_format {
-                            config.compression_level = value_t_or_exit!(
-                                arg_matches,
-                                "snapshot_zstd_compression_level",
-                                i32
-                            );
-                        }
-                        archive_format
-                    };
+                    let snapshot_archive_format = parse_snapshot_archive_format(arg_matches);
 
                     let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
                     let mut process_options = parse_process_options(&ledger_path, arg_matches);
@@ -2018,28 +2138,7 @@ fn main() {
                         get_access_type(&process_options),
                     ));
 
-                    let snapshot_slot = if Some("ROOT") == arg_matches.value_of("snapshot_slot") {
-                        blockstore
-                            .rooted_slot_iterator(0)
-                            .expect("Failed to get rooted slot iterator")
-                            .last()
-                            .expect("Failed to get root")
-                    } else {
-                        value_t_or_exit!(arg_matches, "snapshot_slot", Slot)
-                    };
-
-                    if blockstore
-                        .meta(snapshot_slot)
-                        .unwrap()
-                        .filter(|m| m.is_full())
-                        .is_none()
-                    {
-                        eprintln!(
-                        "Error: snapshot slot {snapshot_slot} does not exist in blockstore or is \
-                         not full.",
-                    );
-                        exit(1);
-                    }
+                    let snapshot_slot = parse_snapshot_slot_or_exit(arg_matches, &blockstore);
                     process_options.halt_at_slot = Some(snapshot_slot);
 
                     let ending_slot = if is_minimized {
@@ -2491,6 +2590,103 @@ fn main() {
                         system_monitor_service.join().unwrap();
                     }
                 }
+                ("create-minimized-snapshot", Some(arg_matches)) => {
+                    let output_directory = value_t!(arg_matches, "output_directory", PathBuf)
+                        .unwrap_or_else(|_| {
+                            value_t!(arg_matches, "snapshots", String)
+                                .map(PathBuf::from)
+                                .unwrap_or_else(|_| ledger_path.clone())
+                        });
+                    let program_ids = pubkeys_of(arg_matches, "programs").unwrap();
+                    let snapshot_version = arg_matches.value_of("snapshot_version").map_or(
+                        SnapshotVersion::default(),
+                        |s| {
+                            s.parse::<SnapshotVersion>().unwrap_or_else(|e| {
+                                eprintln!("Error: {e}");
+                                exit(1)
+                            })
+                        },
+                    );
+                    let snapshot_archive_format = parse_snapshot_archive_format(arg_matches);
+
+                    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                    let mut process_options = parse_process_options(&ledger_path, arg_matches);
+                    let blockstore = Arc::new(open_blockstore(
+                        &ledger_path,
+                        arg_matches,
+                        get_access_type(&process_options),
+                    ));
+                    let snapshot_slot = parse_snapshot_slot_or_exit(arg_matches, &blockstore);
+                    process_options.halt_at_slot = Some(snapshot_slot);
+
+                    info!(
+                        "Creating snapshot of slot {} minimized to {} programs in {}",
+                        snapshot_slot,
+                        program_ids.len(),
+                        output_directory.display()
+                    );
+
+                    let LoadAndProcessLedgerOutput {
+                        bank_forks,
+                        accounts_background_service,
+                        ..
+                    } = load_and_process_ledger_or_exit(
+                        arg_matches,
+                        &genesis_config,
+                        blockstore.clone(),
+                        process_options,
+                        None,
+                    );
+                    let bank = bank_forks
+                        .read()
+                        .unwrap()
+                        .get(snapshot_slot)
+                        .unwrap_or_else(|| {
+                            eprintln!("Error: Slot {snapshot_slot} is not available");
+                            exit(1);
+                        });
+
+                    // Same as for create-snapshot, AccountsDb flush and clean operations must
+                    // not run concurrently with minimization and snapshot creation
+                    accounts_background_service.join().unwrap();
+                    bank.rc
+                        .accounts
+                        .accounts_db
+                        .verify_accounts_hash_in_bg
+                        .join_background_thread();
+
+                    SnapshotMinimizer::minimize_for_programs(&bank, &program_ids).unwrap_or_else(
+                        |err| {
+                            eprintln!("Unable to scan program accounts: {err}");
+                            exit(1);
+                        },
+                    );
+
+                    let full_snapshot_archive_info =
+                        snapshot_bank_utils::bank_to_full_snapshot_archive(
+                            ledger_path,
+                            &bank,
+                            Some(snapshot_version),
+                            output_directory.clone(),
+                            output_directory,
+                            snapshot_archive_format,
+                        )
+                        .unwrap_or_else(|err| {
+                            eprintln!("Unable to create snapshot: {err}");
+                            exit(1);
+                        });
+
+                    println!(
+                        "Successfully created minimized snapshot for slot {}, hash {}: {}",
+                        bank.slot(),
+                        bank.hash(),
+                        full_snapshot_archive_info.path().display(),
+                    );
+                    println!(
+                        "Shred version: {}",
+                        compute_shred_version(&genesis_config.hash(), Some(&bank.hard_forks()))
+                    );
+                }
                 ("simulate-block-production", Some(arg_matches)) => {
                     let mut process_options = parse_process_options(&ledger_path, arg_matches);
 
@@ -0,0 +1,365 @@
+//! The `diff-snapshots` subcommand
+
+use {
+    clap::{value_t, App, Arg, ArgMatches, SubCommand},
+    serde_derive::Serialize,
+    solana_accounts_db::{
+        account_storage::AccountStorageMap, accounts_file::StorageAccess,
+        utils::create_accounts_run_and_snapshot_dirs,
+    },
+    solana_clap_utils::input_validators::is_pubkey,
+    solana_cli_output::{OutputFormat, QuietDisplay, VerboseDisplay},
+    solana_runtime::{
+        snapshot_archive_info::{FullSnapshotArchiveInfo, SnapshotArchiveInfoGetter},
+        snapshot_utils,
+    },
+    solana_sdk::{
+        account::ReadableAccount,
+        clock::Slot,
+        hash::{hash, Hash},
+        pubkey::Pubkey,
+    },
+    std::{
+        collections::{BTreeSet, HashMap},
+        fmt::{self, Display, Formatter},
+        path::{Path, PathBuf},
+        process::exit,
+    },
+};
+
+pub trait DiffSnapshotsSubCommand {
+    fn diff_snapshots_subcommand(self) -> Self;
+}
+
+impl DiffSnapshotsSubCommand for App<'_, '_> {
+    fn diff_snapshots_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("diff-snapshots")
+                .about(
+                    "Report the accounts created, deleted and modified between two full snapshot \
+                     archives",
+                )
+                .arg(
+                    Arg::with_name("snapshot_a")
+                        .index(1)
+                        .value_name("ARCHIVE")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The earlier full snapshot archive"),
+                )
+                .arg(
+                    Arg::with_name("snapshot_b")
+                        .index(2)
+                        .value_name("ARCHIVE")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The later full snapshot archive"),
+                )
+                .arg(
+                    Arg::with_name("owner")
+                        .long("owner")
+                        .value_name("PROGRAM_ID")
+                        .takes_value(true)
+                        .validator(is_pubkey)
+                        .help("Only report the accounts owned by PROGRAM_ID in either snapshot"),
+                )
+                .arg(
+                    Arg::with_name("unpack_dir")
+                        .long("unpack-dir")
+                        .value_name("DIR")
+                        .takes_value(true)
+                        .help(
+                            "Unpack the archives into temporary directories under DIR \
+                             [default: the directory of the first archive]",
+                        ),
+                ),
+        )
+    }
+}
+
+/// The state of an account compared between the snapshots
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct AccountState {
+    lamports: u64,
+    owner: Pubkey,
+    executable: bool,
+    data_hash: Hash,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountChange {
+    Created,
+    Deleted,
+    Modified,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDiff {
+    pub pubkey: String,
+    pub change: AccountChange,
+    pub lamports_before: u64,
+    pub lamports_after: u64,
+    pub lamports_delta: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_after: Option<String>,
+    pub data_changed: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    pub slot_before: Slot,
+    pub slot_after: Slot,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    pub created: usize,
+    pub deleted: usize,
+    pub modified: usize,
+    pub lamports_delta: i64,
+    pub accounts: Vec<AccountDiff>,
+}
+
+impl QuietDisplay for SnapshotDiff {}
+impl VerboseDisplay for SnapshotDiff {}
+
+impl Display for SnapshotDiff {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for account in &self.accounts {
+            write!(
+                f,
+                "{:<8} {:<44} lamports: {} -> {} ({:+})",
+                format!("{:?}", account.change),
+                account.pubkey,
+                account.lamports_before,
+                account.lamports_after,
+                account.lamports_delta,
+            )?;
+            if account.owner_before != account.owner_after {
+                write!(
+                    f,
+                    ", owner: {} -> {}",
+                    account.owner_before.as_deref().unwrap_or("-"),
+                    account.owner_after.as_deref().unwrap_or("-"),
+                )?;
+            }
+            if account.data_changed {
+                write!(f, ", data changed")?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "Slot {} -> {}", self.slot_before, self.slot_after)?;
+        if let Some(owner) = &self.owner {
+            writeln!(f, "Owner: {owner}")?;
+        }
+        writeln!(
+            f,
+            "Created: {}, deleted: {}, modified: {}, lamports delta: {:+}",
+            self.created, self.deleted, self.modified, self.lamports_delta,
+        )
+    }
+}
+
+/// Returns the latest state of every account in `storage`. Accounts whose latest version has
+/// no lamports have been deleted, and are omitted.
+fn snapshot_accounts(storage: &AccountStorageMap) -> HashMap<Pubkey, AccountState> {
+    let slots: BTreeSet<Slot> = storage.iter().map(|entry| *entry.key()).collect();
+    let mut accounts = HashMap::new();
+    // Later slots supersede earlier ones, as do later versions within a slot
+    for slot in slots {
+        let storage = storage.get(&slot).unwrap().storage.clone();
+        storage.accounts.scan_accounts(|account| {
+            accounts.insert(
+                *account.pubkey(),
+                AccountState {
+                    lamports: account.lamports(),
+                    owner: *account.owner(),
+                    executable: account.executable(),
+                    data_hash: hash(account.data()),
+                },
+            );
+        });
+    }
+    accounts.retain(|_, account| account.lamports > 0);
+    accounts
+}
+
+/// Compares the accounts of two snapshots, ordered by pubkey. If `owner` is given, only the
+/// accounts owned by it in either snapshot are compared.
+fn diff_accounts(
+    before: &HashMap<Pubkey, AccountState>,
+    after: &HashMap<Pubkey, AccountState>,
+    owner: Option<&Pubkey>,
+) -> Vec<AccountDiff> {
+    let is_owned = |account: Option<&AccountState>| {
+        owner.is_none() || account.is_some_and(|account| Some(&account.owner) == owner)
+    };
+    let pubkeys: BTreeSet<&Pubkey> = before.keys().chain(after.keys()).collect();
+    pubkeys
+        .into_iter()
+        .filter_map(|pubkey| {
+            let before = before.get(pubkey);
+            let after = after.get(pubkey);
+            if !is_owned(before) && !is_owned(after) {
+                return None;
+            }
+            let change = match (before, after) {
+                (None, Some(_)) => AccountChange::Created,
+                (Some(_), None) => AccountChange::Deleted,
+                (Some(before), Some(after)) if before != after => AccountChange::Modified,
+                _ => return None,
+            };
+            let lamports_before = before.map(|account| account.lamports).unwrap_or_default();
+            let lamports_after = after.map(|account| account.lamports).unwrap_or_default();
+            Some(AccountDiff {
+                pubkey: pubkey.to_string(),
+                change,
+                lamports_before,
+                lamports_after,
+                lamports_delta: lamports_after as i64 - lamports_before as i64,
+                owner_before: before.map(|account| account.owner.to_string()),
+                owner_after: after.map(|account| account.owner.to_string()),
+                data_changed: before.map(|account| account.data_hash)
+                    != after.map(|account| account.data_hash),
+            })
+        })
+        .collect()
+}
+
+/// Unpacks a full snapshot archive under `unpack_dir` and returns the latest state of its
+/// accounts
+fn load_snapshot_accounts(
+    archive: &Path,
+    unpack_dir: &Path,
+) -> Result<(Slot, HashMap<Pubkey, AccountState>), String> {
+    let archive_info = FullSnapshotArchiveInfo::new_from_path(archive.to_path_buf())
+        .map_err(|err| format!("Invalid snapshot archive {}: {err}", archive.display()))?;
+    let unpack_dir = tempfile::Builder::new()
+        .prefix("tmp-diff-snapshots-")
+        .tempdir_in(unpack_dir)
+        .map_err(|err| format!("Unable to create a directory to unpack into: {err}"))?;
+    let (account_run_dir, _) = create_accounts_run_and_snapshot_dirs(unpack_dir.path())
+        .map_err(|err| format!("Unable to create the accounts directory: {err}"))?;
+    let (unarchived_snapshot, _, _) = snapshot_utils::verify_and_unarchive_snapshots(
+        unpack_dir.path(),
+        &archive_info,
+        None,
+        &[account_run_dir],
+        StorageAccess::Mmap,
+    )
+    .map_err(|err| format!("Unable to unpack {}: {err}", archive.display()))?;
+    Ok((
+        archive_info.slot(),
+        snapshot_accounts(&unarchived_snapshot.storage),
+    ))
+}
+
+pub fn diff_snapshots(matches: &ArgMatches<'_>) {
+    let snapshot_a = PathBuf::from(matches.value_of("snapshot_a").unwrap());
+    let snapshot_b = PathBuf::from(matches.value_of("snapshot_b").unwrap());
+    let owner = value_t!(matches, "owner", Pubkey).ok();
+    let unpack_dir = matches
+        .value_of("unpack_dir")
+        .map(PathBuf::from)
+        .or_else(|| snapshot_a.parent().map(Path::to_path_buf))
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| PathBuf::from("."));
+    let output_format = OutputFormat::from_matches(matches, "output_format", false);
+
+    let load = |archive: &Path| {
+        load_snapshot_accounts(archive, &unpack_dir).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            exit(1);
+        })
+    };
+    let (slot_before, before) = load(&snapshot_a);
+    let (slot_after, after) = load(&snapshot_b);
+
+    let accounts = diff_accounts(&before, &after, owner.as_ref());
+    let count = |change| {
+        accounts
+            .iter()
+            .filter(|account| account.change == change)
+            .count()
+    };
+    let diff = SnapshotDiff {
+        slot_before,
+        slot_after,
+        owner: owner.map(|owner| owner.to_string()),
+        created: count(AccountChange::Created),
+        deleted: count(AccountChange::Deleted),
+        modified: count(AccountChange::Modified),
+        lamports_delta: accounts.iter().map(|account| account.lamports_delta).sum(),
+        accounts,
+    };
+    println!("{}", output_format.formatted_string(&diff));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_accounts() {
+        let program_a = Pubkey::new_unique();
+        let program_b = Pubkey::new_unique();
+        let account = |lamports, owner, data: &[u8]| AccountState {
+            lamports,
+            owner,
+            executable: false,
+            data_hash: hash(data),
+        };
+        let unchanged = Pubkey::new_unique();
+        let created = Pubkey::new_unique();
+        let deleted = Pubkey::new_unique();
+        let funded = Pubkey::new_unique();
+        let reassigned = Pubkey::new_unique();
+
+        let before = HashMap::from([
+            (unchanged, account(10, program_a, &[1])),
+            (deleted, account(20, program_a, &[])),
+            (funded, account(30, program_b, &[2])),
+            (reassigned, account(40, program_a, &[3])),
+        ]);
+        let after = HashMap::from([
+            (unchanged, account(10, program_a, &[1])),
+            (created, account(5, program_b, &[])),
+            (funded, account(35, program_b, &[2])),
+            (reassigned, account(40, program_b, &[4])),
+        ]);
+
+        let diff = diff_accounts(&before, &after, None);
+        assert_eq!(diff.len(), 4);
+        let find = |pubkey: &Pubkey| {
+            diff.iter()
+                .find(|account| account.pubkey == pubkey.to_string())
+                .unwrap()
+        };
+        assert_eq!(find(&created).change, AccountChange::Created);
+        assert_eq!(find(&created).lamports_delta, 5);
+        assert_eq!(find(&deleted).change, AccountChange::Deleted);
+        assert_eq!(find(&deleted).lamports_delta, -20);
+        assert_eq!(find(&deleted).owner_after, None);
+        assert_eq!(find(&funded).change, AccountChange::Modified);
+        assert_eq!(find(&funded).lamports_delta, 5);
+        assert!(!find(&funded).data_changed);
+        let reassigned_diff = find(&reassigned);
+        assert_eq!(reassigned_diff.change, AccountChange::Modified);
+        assert_eq!(reassigned_diff.owner_before, Some(program_a.to_string()));
+        assert_eq!(reassigned_diff.owner_after, Some(program_b.to_string()));
+        assert!(reassigned_diff.data_changed);
+
+        // Accounts owned by the program in either snapshot are reported
+        let mut owned: Vec<_> = diff_accounts(&before, &after, Some(&program_a))
+            .into_iter()
+            .map(|account| account.pubkey)
+            .collect();
+        owned.sort();
+        let mut expected = vec![deleted.to_string(), reassigned.to_string()];
+        expected.sort();
+        assert_eq!(owned, expected);
+    }
+}
@@ -0,0 +1,266 @@
+//! The `zk-proof-features` subcommand
+
+use {
+    crate::{args::*, canonicalize_ledger_path, ledger_utils::*},
+    agave_feature_set::{FEATURE_NAMES, ZK_PROOF_FEATURES},
+    clap::{App, Arg, ArgMatches, SubCommand},
+    serde_derive::Serialize,
+    solana_cli_output::{OutputFormat, QuietDisplay, VerboseDisplay},
+    solana_ledger::blockstore_options::AccessType,
+    solana_runtime::bank::Bank,
+    solana_sdk::{
+        account::ReadableAccount,
+        clock::{Epoch, Slot},
+        feature::{self, Feature},
+        native_loader,
+        pubkey::Pubkey,
+    },
+    std::{
+        fmt::{self, Display, Formatter},
+        path::Path,
+        process::exit,
+        sync::Arc,
+    },
+};
+
+/// The builtins enabled by `ZK_PROOF_FEATURES`
+const ZK_PROOF_PROGRAMS: [(&str, Pubkey); 2] = [
+    (
+        "zk_token_proof_program",
+        solana_sdk_ids::zk_token_proof_program::ID,
+    ),
+    (
+        "zk_elgamal_proof_program",
+        solana_sdk_ids::zk_elgamal_proof_program::ID,
+    ),
+];
+
+pub trait ZkProofFeaturesSubCommand {
+    fn zk_proof_features_subcommand(self) -> Self;
+}
+
+impl ZkProofFeaturesSubCommand for App<'_, '_> {
+    fn zk_proof_features_subcommand(self) -> Self {
+        self.subcommand(
+            SubCommand::with_name("zk-proof-features")
+                .about(
+                    "Report the status of the features and builtins confidential transfers \
+                     depend on",
+                )
+                .arg(&load_genesis_arg())
+                .args(&snapshot_args())
+                .arg(
+                    Arg::with_name("dry_run_activation")
+                        .long("dry-run-activation")
+                        .takes_value(false)
+                        .help(
+                            "Activate the inactive features in a bank at the start of the next \
+                             epoch, without persisting it, and report the resulting status. \
+                             Exits with an error if the builtins are not available afterwards",
+                        ),
+                ),
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FeatureStatus {
+    Active(Slot),
+    Pending,
+    Inactive,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkProofFeature {
+    pub id: String,
+    pub description: String,
+    pub status: FeatureStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkProofProgram {
+    pub name: String,
+    pub program_id: String,
+    pub available: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkProofStatus {
+    pub slot: Slot,
+    pub epoch: Epoch,
+    pub features: Vec<ZkProofFeature>,
+    pub programs: Vec<ZkProofProgram>,
+}
+
+impl ZkProofStatus {
+    fn new(bank: &Bank) -> Self {
+        let features = ZK_PROOF_FEATURES
+            .iter()
+            .map(|feature_id| {
+                let status = match bank.feature_set.activated_slot(feature_id) {
+                    Some(slot) => FeatureStatus::Active(slot),
+                    None if bank.get_account(feature_id).is_some() => FeatureStatus::Pending,
+                    None => FeatureStatus::Inactive,
+                };
+                ZkProofFeature {
+                    id: feature_id.to_string(),
+                    description: FEATURE_NAMES
+                        .get(feature_id)
+                        .copied()
+                        .unwrap_or_default()
+                        .to_string(),
+                    status,
+                }
+            })
+            .collect();
+        let programs = ZK_PROOF_PROGRAMS
+            .iter()
+            .map(|(name, program_id)| ZkProofProgram {
+                name: name.to_string(),
+                program_id: program_id.to_string(),
+                available: bank.get_account(program_id).is_some_and(|account| {
+                    account.executable() && native_loader::check_id(account.owner())
+                }),
+            })
+            .collect();
+        Self {
+            slot: bank.slot(),
+            epoch: bank.epoch(),
+            features,
+            programs,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.features
+            .iter()
+            .all(|feature| matches!(feature.status, FeatureStatus::Active(_)))
+            && self.programs.iter().all(|program| program.available)
+    }
+}
+
+impl Display for ZkProofStatus {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "Slot {} (epoch {})", self.slot, self.epoch)?;
+        for feature in &self.features {
+            let status = match feature.status {
+                FeatureStatus::Active(slot) => format!("active since slot {slot}"),
+                FeatureStatus::Pending => "pending activation".to_string(),
+                FeatureStatus::Inactive => "inactive".to_string(),
+            };
+            writeln!(
+                f,
+                "{:<44} {:<30} {}",
+                feature.id, status, feature.description
+            )?;
+        }
+        for program in &self.programs {
+            writeln!(
+                f,
+                "{:<44} {:<30} {}",
+                program.program_id,
+                if program.available {
+                    "available"
+                } else {
+                    "unavailable"
+                },
+                program.name,
+            )?;
+        }
+        write!(
+            f,
+            "Confidential transfers {}",
+            if self.is_enabled() {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkProofFeatures {
+    pub current: ZkProofStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_activation: Option<ZkProofStatus>,
+}
+
+impl QuietDisplay for ZkProofFeatures {}
+impl VerboseDisplay for ZkProofFeatures {}
+
+impl Display for ZkProofFeatures {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.current)?;
+        if let Some(after_activation) = &self.after_activation {
+            writeln!(f, "\nAfter activation:")?;
+            writeln!(f, "{after_activation}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns a bank at the start of the epoch after `bank`'s, with every feature of
+/// `ZK_PROOF_FEATURES` not yet active having been activated at the epoch boundary
+fn activate_zk_proof_features(bank: Arc<Bank>) -> Bank {
+    let child_bank = Bank::new_from_parent(bank.clone(), bank.collector_id(), bank.slot() + 1);
+    let lamports = child_bank
+        .get_minimum_balance_for_rent_exemption(Feature::size_of())
+        .max(1);
+    for feature_id in ZK_PROOF_FEATURES {
+        if child_bank.get_account(&feature_id).is_none() {
+            child_bank.store_account(
+                &feature_id,
+                &feature::create_account(&Feature::default(), lamports),
+            );
+        }
+    }
+    let child_bank = Arc::new(child_bank);
+    let next_epoch_slot = child_bank
+        .epoch_schedule()
+        .get_first_slot_in_epoch(child_bank.epoch() + 1);
+    Bank::new_from_parent(
+        child_bank.clone(),
+        child_bank.collector_id(),
+        next_epoch_slot,
+    )
+}
+
+pub fn zk_proof_features(ledger_path: &Path, matches: &ArgMatches<'_>) {
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    let process_options = parse_process_options(&ledger_path, matches);
+    let genesis_config = open_genesis_config_by(&ledger_path, matches);
+    let blockstore = open_blockstore(&ledger_path, matches, AccessType::Secondary);
+    let LoadAndProcessLedgerOutput { bank_forks, .. } = load_and_process_ledger_or_exit(
+        matches,
+        &genesis_config,
+        Arc::new(blockstore),
+        process_options,
+        None,
+    );
+    let bank = bank_forks.read().unwrap().working_bank();
+    let output_format = OutputFormat::from_matches(matches, "output_format", false);
+
+    let current = ZkProofStatus::new(&bank);
+    let after_activation = matches
+        .is_present("dry_run_activation")
+        .then(|| ZkProofStatus::new(&activate_zk_proof_features(bank)));
+    let enabled = after_activation.as_ref().unwrap_or(&current).is_enabled();
+    let dry_run = after_activation.is_some();
+    println!(
+        "{}",
+        output_format.formatted_string(&ZkProofFeatures {
+            current,
+            after_activation,
+        })
+    );
+    if dry_run && !enabled {
+        eprintln!("Error: the zk proof builtins are not available after activation");
+        exit(1);
+    }
+}
@@ -1,9 +1,11 @@
 use {
+    agave_feature_set::ZK_PROOF_FEATURES,
     assert_cmd::prelude::*,
     solana_ledger::{
         blockstore, blockstore::Blockstore, create_new_tmp_ledger_auto_delete,
         genesis_utils::create_genesis_config, get_tmp_ledger_path_auto_delete,
     },
+    solana_runtime::genesis_utils::deactivate_features,
     std::{
         path::Path,
         process::{Command, Output},
@@ -87,3 +89,33 @@ fn ledger_tool_copy_test() {
         assert!(!src_slot_output.stdout.is_empty());
     }
 }
+
+#[test]
+fn zk_proof_features_dry_run_activation() {
+    let mut genesis_config = create_genesis_config(100).genesis_config;
+    deactivate_features(&mut genesis_config, &ZK_PROOF_FEATURES.to_vec());
+    let (ledger_path, _blockhash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+    let ledger_path = ledger_path.path().to_str().unwrap();
+
+    let output = run_ledger_tool(&["-l", ledger_path, "zk-proof-features"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Confidential transfers disabled"));
+    assert!(!stdout.contains("After activation"));
+
+    let output = run_ledger_tool(&[
+        "-l",
+        ledger_path,
+        "zk-proof-features",
+        "--dry-run-activation",
+    ]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (current, after_activation) = stdout.split_once("After activation").unwrap();
+    assert!(current.contains("Confidential transfers disabled"));
+    assert!(after_activation.contains("Confidential transfers enabled"));
+
+    // The dry run does not persist the activation
+    let output = run_ledger_tool(&["-l", ledger_path, "zk-proof-features"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Confidential transfers disabled"));
+}
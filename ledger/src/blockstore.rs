@@ -5,7 +5,9 @@
 use {
     crate::{
         ancestor_iterator::AncestorIterator,
-        blockstore::column::{columns as cf, Column, ColumnIndexDeprecation},
+        blockstore::column::{
+            address_signature_shard, columns as cf, Column, ColumnIndexDeprecation, TypedColumn,
+        },
         blockstore_db::{IteratorDirection, IteratorMode, LedgerColumn, Rocks, WriteBatch},
         blockstore_meta::*,
         blockstore_metrics::BlockstoreRpcApiMetrics,
@@ -41,7 +43,7 @@ use {
     },
     solana_runtime::bank::Bank,
     solana_sdk::{
-        account::ReadableAccount,
+        account::{Account, ReadableAccount},
         address_lookup_table::state::AddressLookupTable,
         clock::{Slot, UnixTimestamp, DEFAULT_TICKS_PER_SECOND},
         genesis_config::{GenesisConfig, DEFAULT_GENESIS_ARCHIVE, DEFAULT_GENESIS_FILE},
@@ -125,6 +127,94 @@ pub struct SignatureInfosForAddress {
     pub found_before: bool,
 }
 
+/// Filters applied to the signatures listed by `get_filtered_signatures_for_address()`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SignaturesForAddressFilter {
+    /// Only list signatures of blocks produced at or after this time
+    pub min_block_time: Option<UnixTimestamp>,
+    /// Only list signatures of blocks produced at or before this time
+    pub max_block_time: Option<UnixTimestamp>,
+    /// Only list signatures of transactions that also reference this program, as every program
+    /// a transaction invokes, directly or not, must be one of its accounts
+    pub program_id: Option<Pubkey>,
+}
+
+/// Evaluates a `SignaturesForAddressFilter` on signatures listed slot by slot, loading the block
+/// time and the signatures referencing the program of each slot once
+struct SignaturesForAddressFilterEvaluator<'a> {
+    blockstore: &'a Blockstore,
+    filter: &'a SignaturesForAddressFilter,
+    slot: Option<Slot>,
+    block_time: Option<UnixTimestamp>,
+    program_signatures: HashSet<Signature>,
+}
+
+impl<'a> SignaturesForAddressFilterEvaluator<'a> {
+    fn new(blockstore: &'a Blockstore, filter: &'a SignaturesForAddressFilter) -> Self {
+        Self {
+            blockstore,
+            filter,
+            slot: None,
+            block_time: None,
+            program_signatures: HashSet::new(),
+        }
+    }
+
+    fn load_slot(&mut self, slot: Slot) -> Result<()> {
+        if self.slot != Some(slot) {
+            self.slot = Some(slot);
+            self.block_time = self.blockstore.get_block_time(slot)?;
+            self.program_signatures = match self.filter.program_id {
+                Some(program_id) => self
+                    .blockstore
+                    .find_address_signatures_for_slot(program_id, slot)?
+                    .into_iter()
+                    .map(|(_, signature)| signature)
+                    .collect(),
+                None => HashSet::new(),
+            };
+        }
+        Ok(())
+    }
+
+    /// Whether `slot` and its ancestors were produced before the time range, as block times
+    /// never decrease from a block to its children
+    fn is_before_time_range(&mut self, slot: Slot) -> Result<bool> {
+        let Some(min_block_time) = self.filter.min_block_time else {
+            return Ok(false);
+        };
+        self.load_slot(slot)?;
+        Ok(self
+            .block_time
+            .is_some_and(|block_time| block_time < min_block_time))
+    }
+
+    fn matches(&mut self, slot: Slot, signature: &Signature) -> Result<bool> {
+        if self.filter == &SignaturesForAddressFilter::default() {
+            return Ok(true);
+        }
+        self.load_slot(slot)?;
+        let in_time_range = match self.block_time {
+            Some(block_time) => {
+                self.filter
+                    .min_block_time
+                    .map_or(true, |min_block_time| block_time >= min_block_time)
+                    && self
+                        .filter
+                        .max_block_time
+                        .map_or(true, |max_block_time| block_time <= max_block_time)
+            }
+            None => self.filter.min_block_time.is_none() && self.filter.max_block_time.is_none(),
+        };
+        Ok(in_time_range
+            && (self.filter.program_id.is_none() || self.program_signatures.contains(signature)))
+    }
+}
+
+/// The position of a signature in the address signatures of an address: its slot, the index of
+/// its transaction in the block, and the signature itself
+pub type AddressSignaturePosition = (Slot, u32, Signature);
+
 #[derive(Error, Debug)]
 enum InsertDataShredError {
     #[error("Data shred already exists in Blockstore")]
@@ -250,14 +340,20 @@ pub struct Blockstore {
     ledger_path: PathBuf,
     db: Arc<Rocks>,
     // Column families
+    address_signature_shard_markers_cf: LedgerColumn<cf::AddressSignatureShardMarkers>,
+    address_signature_shards_cf: LedgerColumn<cf::AddressSignatureShards>,
     address_signatures_cf: LedgerColumn<cf::AddressSignatures>,
+    archived_account_slots_cf: LedgerColumn<cf::ArchivedAccountSlots>,
+    archived_accounts_cf: LedgerColumn<cf::ArchivedAccounts>,
     bank_hash_cf: LedgerColumn<cf::BankHash>,
+    block_fee_summary_cf: LedgerColumn<cf::BlockFeeSummary>,
     block_height_cf: LedgerColumn<cf::BlockHeight>,
     blocktime_cf: LedgerColumn<cf::Blocktime>,
     code_shred_cf: LedgerColumn<cf::ShredCode>,
     data_shred_cf: LedgerColumn<cf::ShredData>,
     dead_slots_cf: LedgerColumn<cf::DeadSlots>,
     duplicate_slots_cf: LedgerColumn<cf::DuplicateSlots>,
+    duplicate_votes_cf: LedgerColumn<cf::DuplicateVotes>,
     erasure_meta_cf: LedgerColumn<cf::ErasureMeta>,
     index_cf: LedgerColumn<cf::Index>,
     merkle_root_meta_cf: LedgerColumn<cf::MerkleRootMeta>,
@@ -273,6 +369,9 @@ pub struct Blockstore {
     transaction_status_index_cf: LedgerColumn<cf::TransactionStatusIndex>,
 
     highest_primary_index_slot: RwLock<Option<Slot>>,
+    // Whether the address signatures column still holds entries written before the address
+    // signature shards column, in which case it keeps being written and is read instead
+    legacy_address_signatures: AtomicBool,
     max_root: AtomicU64,
     insert_shreds_lock: Mutex<()>,
     new_shreds_signals: Mutex<Vec<Sender<bool>>>,
@@ -399,14 +498,20 @@ impl Blockstore {
         info!("Opening blockstore at {:?}", blockstore_path);
         let db = Arc::new(Rocks::open(blockstore_path, options)?);
 
+        let address_signature_shard_markers_cf = db.column();
+        let address_signature_shards_cf = db.column();
         let address_signatures_cf = db.column();
+        let archived_account_slots_cf = db.column();
+        let archived_accounts_cf = db.column();
         let bank_hash_cf = db.column();
+        let block_fee_summary_cf = db.column();
         let block_height_cf = db.column();
         let blocktime_cf = db.column();
         let code_shred_cf = db.column();
         let data_shred_cf = db.column();
         let dead_slots_cf = db.column();
         let duplicate_slots_cf = db.column();
+        let duplicate_votes_cf = db.column();
         let erasure_meta_cf = db.column();
         let index_cf = db.column();
         let merkle_root_meta_cf = db.column();
@@ -434,14 +539,20 @@ impl Blockstore {
         let blockstore = Blockstore {
             ledger_path: ledger_path.to_path_buf(),
             db,
+            address_signature_shard_markers_cf,
+            address_signature_shards_cf,
             address_signatures_cf,
+            archived_account_slots_cf,
+            archived_accounts_cf,
             bank_hash_cf,
+            block_fee_summary_cf,
             block_height_cf,
             blocktime_cf,
             code_shred_cf,
             data_shred_cf,
             dead_slots_cf,
             duplicate_slots_cf,
+            duplicate_votes_cf,
             erasure_meta_cf,
             index_cf,
             merkle_root_meta_cf,
@@ -456,6 +567,7 @@ impl Blockstore {
             transaction_status_cf,
             transaction_status_index_cf,
             highest_primary_index_slot: RwLock::<Option<Slot>>::default(),
+            legacy_address_signatures: AtomicBool::default(),
             new_shreds_signals: Mutex::default(),
             completed_slots_senders: Mutex::default(),
             shred_timing_point_sender: None,
@@ -467,6 +579,7 @@ impl Blockstore {
         };
         blockstore.cleanup_old_entries()?;
         blockstore.update_highest_primary_index_slot()?;
+        blockstore.update_legacy_address_signatures()?;
 
         Ok(blockstore)
     }
@@ -857,6 +970,7 @@ impl Blockstore {
         self.meta_cf.submit_rocksdb_cf_metrics();
         self.dead_slots_cf.submit_rocksdb_cf_metrics();
         self.duplicate_slots_cf.submit_rocksdb_cf_metrics();
+        self.duplicate_votes_cf.submit_rocksdb_cf_metrics();
         self.roots_cf.submit_rocksdb_cf_metrics();
         self.erasure_meta_cf.submit_rocksdb_cf_metrics();
         self.orphans_cf.submit_rocksdb_cf_metrics();
@@ -865,16 +979,22 @@ impl Blockstore {
         self.code_shred_cf.submit_rocksdb_cf_metrics();
         self.transaction_status_cf.submit_rocksdb_cf_metrics();
         self.address_signatures_cf.submit_rocksdb_cf_metrics();
+        self.address_signature_shards_cf.submit_rocksdb_cf_metrics();
+        self.address_signature_shard_markers_cf
+            .submit_rocksdb_cf_metrics();
         self.transaction_memos_cf.submit_rocksdb_cf_metrics();
         self.transaction_status_index_cf.submit_rocksdb_cf_metrics();
         self.rewards_cf.submit_rocksdb_cf_metrics();
         self.blocktime_cf.submit_rocksdb_cf_metrics();
         self.perf_samples_cf.submit_rocksdb_cf_metrics();
         self.block_height_cf.submit_rocksdb_cf_metrics();
+        self.block_fee_summary_cf.submit_rocksdb_cf_metrics();
         self.program_costs_cf.submit_rocksdb_cf_metrics();
         self.bank_hash_cf.submit_rocksdb_cf_metrics();
         self.optimistic_slots_cf.submit_rocksdb_cf_metrics();
         self.merkle_root_meta_cf.submit_rocksdb_cf_metrics();
+        self.archived_accounts_cf.submit_rocksdb_cf_metrics();
+        self.archived_account_slots_cf.submit_rocksdb_cf_metrics();
     }
 
     /// Report the accumulated RPC API metrics
@@ -2612,6 +2732,63 @@ impl Blockstore {
         self.block_height_cf.put(slot, &block_height)
     }
 
+    /// Returns the fee summary recorded when the bank of `slot` was frozen.
+    pub fn get_block_fee_summary(&self, slot: Slot) -> Result<Option<BlockFeeSummary>> {
+        let _lock = self.check_lowest_cleanup_slot(slot)?;
+        self.block_fee_summary_cf.get(slot)
+    }
+
+    pub fn set_block_fee_summary(&self, slot: Slot, fee_summary: &BlockFeeSummary) -> Result<()> {
+        self.block_fee_summary_cf.put(slot, fee_summary)
+    }
+
+    /// Archives the versions of `accounts` written by the bank of `slot`.
+    pub fn write_archived_accounts<'a>(
+        &self,
+        slot: Slot,
+        accounts: impl IntoIterator<Item = (&'a Pubkey, &'a Account)>,
+    ) -> Result<()> {
+        let mut write_batch = self.get_write_batch()?;
+        for (pubkey, account) in accounts {
+            self.archived_accounts_cf
+                .put_in_batch(&mut write_batch, (*pubkey, slot), account)?;
+            self.archived_account_slots_cf.put_in_batch(
+                &mut write_batch,
+                (slot, *pubkey),
+                &true,
+            )?;
+        }
+        self.write_batch(write_batch)
+    }
+
+    /// Returns the latest archived version of the account of `pubkey` written in
+    /// a rooted slot no later than `slot`, along with the slot it was written in.
+    ///
+    /// Returns `None` if the account was not written by any rooted slot still
+    /// kept in the ledger.
+    pub fn get_archived_account(
+        &self,
+        pubkey: &Pubkey,
+        slot: Slot,
+    ) -> Result<Option<(Slot, Account)>> {
+        let lowest_cleanup_slot = self.check_lowest_cleanup_slot(slot)?;
+        let iterator = self.archived_accounts_cf.iter(IteratorMode::From(
+            (*pubkey, slot),
+            IteratorDirection::Reverse,
+        ))?;
+        for ((key_pubkey, written_slot), value) in iterator {
+            let cleaned_up = *lowest_cleanup_slot > 0 && written_slot <= *lowest_cleanup_slot;
+            if key_pubkey != *pubkey || cleaned_up {
+                break;
+            }
+            if self.is_root(written_slot) {
+                let account = cf::ArchivedAccounts::deserialize(&value)?;
+                return Ok(Some((written_slot, account)));
+            }
+        }
+        Ok(None)
+    }
+
     /// The first complete block that is available in the Blockstore ledger
     pub fn get_first_available_block(&self) -> Result<Slot> {
         let mut root_iterator = self.rooted_slot_iterator(self.lowest_slot_with_genesis())?;
@@ -2882,6 +3059,91 @@ impl Blockstore {
         Ok(())
     }
 
+    fn update_legacy_address_signatures(&self) -> Result<()> {
+        let legacy_address_signatures = self
+            .address_signatures_cf
+            .iter(IteratorMode::Start)?
+            .next()
+            .is_some();
+        if legacy_address_signatures {
+            info!(
+                "Blockstore holds address signatures of the legacy column, run `ledger-tool \
+                 blockstore migrate-address-signatures` to move them to shards"
+            );
+        }
+        self.legacy_address_signatures
+            .store(legacy_address_signatures, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Moves the entries of the legacy address signatures column to the address signature shards
+    /// column, returning how many were moved. Once the legacy column is empty, address signatures
+    /// are only written to and read from their shards.
+    ///
+    /// The Blockstore must not be written to by a validator at the same time.
+    pub fn migrate_address_signatures_to_shards(&self) -> Result<u64> {
+        const MIGRATION_BATCH_SIZE: u64 = 100_000;
+
+        let mut migrated = 0;
+        let mut write_batch = self.get_write_batch()?;
+        let mut migrate =
+            |write_batch: &mut WriteBatch,
+             (address, slot, transaction_index, signature): (Pubkey, Slot, u32, Signature),
+             value: &[u8]|
+             -> Result<()> {
+                let meta = cf::AddressSignatures::deserialize(value)?;
+                self.address_signature_shard_markers_cf.put_in_batch(
+                    write_batch,
+                    (address, address_signature_shard(slot)),
+                    &true,
+                )?;
+                self.address_signature_shards_cf.put_in_batch(
+                    write_batch,
+                    (
+                        address_signature_shard(slot),
+                        address,
+                        slot,
+                        transaction_index,
+                        signature,
+                    ),
+                    &meta,
+                )?;
+                migrated += 1;
+                if migrated % MIGRATION_BATCH_SIZE == 0 {
+                    let full_batch = std::mem::replace(write_batch, self.get_write_batch()?);
+                    self.write_batch(full_batch)?;
+                    info!("Migrated {migrated} address signatures to shards");
+                }
+                Ok(())
+            };
+
+        // Iterators read a snapshot of the column, unaffected by the deletes
+        for (index, value) in self
+            .address_signatures_cf
+            .iter_current_index_filtered(IteratorMode::Start)?
+        {
+            self.address_signatures_cf
+                .delete_in_batch(&mut write_batch, index)?;
+            migrate(&mut write_batch, index, &value)?;
+        }
+        for (index, value) in self
+            .address_signatures_cf
+            .iter_deprecated_index_filtered(IteratorMode::Start)?
+        {
+            self.address_signatures_cf
+                .delete_deprecated_in_batch(&mut write_batch, index)?;
+            migrate(
+                &mut write_batch,
+                cf::AddressSignatures::convert_index(index),
+                &value,
+            )?;
+        }
+        self.write_batch(write_batch)?;
+        self.legacy_address_signatures
+            .store(false, Ordering::Relaxed);
+        Ok(migrated)
+    }
+
     fn maybe_cleanup_highest_primary_index_slot(&self, oldest_slot: Slot) -> Result<()> {
         let mut w_highest_primary_index_slot = self.highest_primary_index_slot.write().unwrap();
         if let Some(highest_primary_index_slot) = *w_highest_primary_index_slot {
@@ -2972,9 +3234,22 @@ impl Blockstore {
             status,
             transaction_index,
             |address, slot, tx_index, signature, writeable| {
-                self.address_signatures_cf.put(
-                    (*address, slot, tx_index, signature),
-                    &AddressSignatureMeta { writeable },
+                let meta = AddressSignatureMeta { writeable };
+                if self.legacy_address_signatures.load(Ordering::Relaxed) {
+                    self.address_signatures_cf
+                        .put((*address, slot, tx_index, signature), &meta)?;
+                }
+                self.address_signature_shard_markers_cf
+                    .put((*address, address_signature_shard(slot)), &true)?;
+                self.address_signature_shards_cf.put(
+                    (
+                        address_signature_shard(slot),
+                        *address,
+                        slot,
+                        tx_index,
+                        signature,
+                    ),
+                    &meta,
                 )
             },
         )
@@ -2996,10 +3271,29 @@ impl Blockstore {
             status,
             transaction_index,
             |address, slot, tx_index, signature, writeable| {
-                self.address_signatures_cf.put_in_batch(
+                let meta = AddressSignatureMeta { writeable };
+                if self.legacy_address_signatures.load(Ordering::Relaxed) {
+                    self.address_signatures_cf.put_in_batch(
+                        db_write_batch,
+                        (*address, slot, tx_index, signature),
+                        &meta,
+                    )?;
+                }
+                self.address_signature_shard_markers_cf.put_in_batch(
+                    db_write_batch,
+                    (*address, address_signature_shard(slot)),
+                    &true,
+                )?;
+                self.address_signature_shards_cf.put_in_batch(
                     db_write_batch,
-                    (*address, slot, tx_index, signature),
-                    &AddressSignatureMeta { writeable },
+                    (
+                        address_signature_shard(slot),
+                        *address,
+                        slot,
+                        tx_index,
+                        signature,
+                    ),
+                    &meta,
                 )
             },
         )
@@ -3262,6 +3556,104 @@ impl Blockstore {
         Ok(vec![])
     }
 
+    /// Iterates the address signatures of `address` in `direction`, from `start` up to the end of
+    /// the shard of `end_slot`. Callers still have to stop at the slots they want, `end_slot`
+    /// only bounds the shards that get seeked. Only the shards marked as holding entries of
+    /// `address` are seeked, so sparse addresses don't cost a seek per shard in between.
+    ///
+    /// The address signatures column is read instead while it holds legacy entries.
+    fn address_signatures_iterator<'a>(
+        &'a self,
+        address: Pubkey,
+        start: AddressSignaturePosition,
+        end_slot: Slot,
+        direction: IteratorDirection,
+    ) -> Result<Box<dyn Iterator<Item = Result<AddressSignaturePosition>> + 'a>> {
+        let (start_slot, start_transaction_index, start_signature) = start;
+        let forward = matches!(direction, IteratorDirection::Forward);
+        if self.legacy_address_signatures.load(Ordering::Relaxed) {
+            let iterator = self
+                .address_signatures_cf
+                .iter_current_index_filtered(IteratorMode::From(
+                    (
+                        address,
+                        start_slot,
+                        start_transaction_index,
+                        start_signature,
+                    ),
+                    direction,
+                ))?
+                .map(|(index, _)| index)
+                .take_while(move |(key_address, ..)| *key_address == address)
+                .map(|(_, slot, transaction_index, signature)| {
+                    Ok((slot, transaction_index, signature))
+                });
+            return Ok(Box::new(iterator));
+        }
+
+        let start_shard = address_signature_shard(start_slot);
+        let end_shard = address_signature_shard(end_slot);
+        let shards = self
+            .address_signature_shard_markers_cf
+            .iter(IteratorMode::From((address, start_shard), direction))?
+            .map(|(index, _)| index)
+            .take_while(move |(key_address, shard)| {
+                *key_address == address
+                    && if forward {
+                        *shard <= end_shard
+                    } else {
+                        *shard >= end_shard
+                    }
+            })
+            .map(|(_, shard)| shard);
+        let iterator = shards.flat_map(move |shard| {
+            // Seek `start` in its shard, and the first or last entry of the address in the others
+            let seek = if shard == start_shard {
+                (
+                    shard,
+                    address,
+                    start_slot,
+                    start_transaction_index,
+                    start_signature,
+                )
+            } else if forward {
+                (shard, address, 0, 0, Signature::default())
+            } else {
+                (
+                    shard,
+                    address,
+                    Slot::MAX,
+                    u32::MAX,
+                    Signature::from([u8::MAX; 64]),
+                )
+            };
+            let direction = if forward {
+                IteratorDirection::Forward
+            } else {
+                IteratorDirection::Reverse
+            };
+            let entries: Box<dyn Iterator<Item = Result<AddressSignaturePosition>> + 'a> =
+                match self
+                    .address_signature_shards_cf
+                    .iter(IteratorMode::From(seek, direction))
+                {
+                    Ok(iterator) => Box::new(
+                        iterator
+                            .map(|(index, _)| index)
+                            .take_while(move |(key_shard, key_address, ..)| {
+                                *key_shard == shard && *key_address == address
+                            })
+                            .map(|(_, _, slot, transaction_index, signature)| {
+                                Ok((slot, transaction_index, signature))
+                            }),
+                    ),
+                    Err(err) => Box::new(std::iter::once(Err(err))),
+                };
+            entries
+        });
+        Ok(Box::new(iterator))
+    }
+
     // Returns all signatures for an address in a particular slot, regardless of whether that slot
     // has been rooted. The transactions will be ordered by their occurrence in the block
     fn find_address_signatures_for_slot(
@@ -3274,19 +3666,15 @@ impl Blockstore {
         if slot < lowest_available_slot {
             return Ok(signatures);
         }
-        let index_iterator =
-            self.address_signatures_cf
-                .iter_current_index_filtered(IteratorMode::From(
-                    (
-                        pubkey,
-                        slot.max(lowest_available_slot),
-                        0,
-                        Signature::default(),
-                    ),
-                    IteratorDirection::Forward,
-                ))?;
-        for ((address, transaction_slot, _transaction_index, signature), _) in index_iterator {
-            if transaction_slot > slot || address != pubkey {
+        let iterator = self.address_signatures_iterator(
+            pubkey,
+            (slot, 0, Signature::default()),
+            slot,
+            IteratorDirection::Forward,
+        )?;
+        for entry in iterator {
+            let (transaction_slot, _transaction_index, signature) = entry?;
+            if transaction_slot > slot {
                 break;
             }
             signatures.push((slot, signature));
@@ -3340,6 +3728,27 @@ impl Blockstore {
         before: Option<Signature>,
         until: Option<Signature>,
         limit: usize,
+    ) -> Result<SignatureInfosForAddress> {
+        self.get_filtered_signatures_for_address(
+            address,
+            highest_slot,
+            before,
+            until,
+            limit,
+            &SignaturesForAddressFilter::default(),
+        )
+    }
+
+    /// Like `get_confirmed_signatures_for_address2()`, only listing the signatures that pass
+    /// `filter`, up to `limit` of them
+    pub fn get_filtered_signatures_for_address(
+        &self,
+        address: Pubkey,
+        highest_slot: Slot, // highest_super_majority_root or highest_confirmed_slot
+        before: Option<Signature>,
+        until: Option<Signature>,
+        limit: usize,
+        filter: &SignaturesForAddressFilter,
     ) -> Result<SignatureInfosForAddress> {
         self.rpc_api_metrics
             .num_get_confirmed_signatures_for_address2
@@ -3404,49 +3813,52 @@ impl Blockstore {
 
         // Fetch the list of signatures that affect the given address
         let mut address_signatures = vec![];
+        let mut filter = SignaturesForAddressFilterEvaluator::new(self, filter);
 
         // Get signatures in `slot`
         let mut get_initial_slot_timer = Measure::start("get_initial_slot_timer");
         let mut signatures = self.find_address_signatures_for_slot(address, slot)?;
         signatures.reverse();
         if let Some(excluded_signatures) = before_excluded_signatures.take() {
-            address_signatures.extend(
-                signatures
-                    .into_iter()
-                    .filter(|(_, signature)| !excluded_signatures.contains(signature)),
-            )
-        } else {
-            address_signatures.append(&mut signatures);
+            signatures.retain(|(_, signature)| !excluded_signatures.contains(signature));
+        }
+        for (slot, signature) in signatures {
+            if filter.matches(slot, &signature)? {
+                address_signatures.push((slot, signature));
+            }
         }
         get_initial_slot_timer.stop();
 
         let mut address_signatures_iter_timer = Measure::start("iter_timer");
-        let mut iterator =
-            self.address_signatures_cf
-                .iter_current_index_filtered(IteratorMode::From(
-                    // Regardless of whether a `before` signature is provided, the latest relevant
-                    // `slot` is queried directly with the `find_address_signatures_for_slot()`
-                    // call above. Thus, this iterator starts at the lowest entry of `address,
-                    // slot` and iterates backwards to continue reporting the next earliest
-                    // signatures.
-                    (address, slot, 0, Signature::default()),
-                    IteratorDirection::Reverse,
-                ))?;
+        let mut iterator = self.address_signatures_iterator(
+            address,
+            // Regardless of whether a `before` signature is provided, the latest relevant
+            // `slot` is queried directly with the `find_address_signatures_for_slot()`
+            // call above. Thus, this iterator starts at the lowest entry of `address,
+            // slot` and iterates backwards to continue reporting the next earliest
+            // signatures.
+            (slot, 0, Signature::default()),
+            lowest_slot,
+            IteratorDirection::Reverse,
+        )?;
 
         // Iterate until limit is reached
         while address_signatures.len() < limit {
-            if let Some(((key_address, slot, _transaction_index, signature), _)) = iterator.next() {
-                if slot < lowest_slot {
+            let Some(entry) = iterator.next() else {
+                break;
+            };
+            let (slot, _transaction_index, signature) = entry?;
+            if slot < lowest_slot {
+                break;
+            }
+            if self.is_root(slot) || confirmed_unrooted_slots.contains(&slot) {
+                if filter.is_before_time_range(slot)? {
                     break;
                 }
-                if key_address == address {
-                    if self.is_root(slot) || confirmed_unrooted_slots.contains(&slot) {
-                        address_signatures.push((slot, signature));
-                    }
-                    continue;
+                if filter.matches(slot, &signature)? {
+                    address_signatures.push((slot, signature));
                 }
             }
-            break;
         }
         address_signatures_iter_timer.stop();
 
@@ -3460,18 +3872,11 @@ impl Blockstore {
         let mut get_status_info_timer = Measure::start("get_status_info_timer");
         let mut infos = vec![];
         for (slot, signature) in address_signatures.into_iter() {
-            let transaction_status =
-                self.get_transaction_status(signature, &confirmed_unrooted_slots)?;
-            let err = transaction_status.and_then(|(_slot, status)| status.status.err());
-            let memo = self.read_transaction_memos(signature, slot)?;
-            let block_time = self.get_block_time(slot)?;
-            infos.push(ConfirmedTransactionStatusWithSignature {
-                signature,
+            infos.push(self.get_transaction_status_with_signature(
                 slot,
-                err,
-                memo,
-                block_time,
-            });
+                signature,
+                &confirmed_unrooted_slots,
+            )?);
         }
         get_status_info_timer.stop();
 
@@ -3510,6 +3915,81 @@ impl Blockstore {
         })
     }
 
+    /// Returns up to `limit` signatures of transactions that affect `address` at rooted slots no
+    /// higher than `highest_root`, oldest first, starting after the signature at `after`.
+    ///
+    /// Unlike `get_confirmed_signatures_for_address2()`, which lists signatures from the newest,
+    /// the position of the last signature returned can be passed as `after` to resume the listing
+    /// as more slots are rooted, without ever missing or repeating a signature.
+    pub fn get_rooted_signatures_for_address_after(
+        &self,
+        address: Pubkey,
+        highest_root: Slot,
+        after: Option<AddressSignaturePosition>,
+        limit: usize,
+    ) -> Result<
+        Vec<(
+            AddressSignaturePosition,
+            ConfirmedTransactionStatusWithSignature,
+        )>,
+    > {
+        let (lock, lowest_available_slot) = self.ensure_lowest_cleanup_slot();
+        let start_slot = after
+            .map_or(0, |(slot, _, _)| slot)
+            .max(lowest_available_slot);
+        let iterator = self.address_signatures_iterator(
+            address,
+            (start_slot, 0, Signature::default()),
+            highest_root,
+            IteratorDirection::Forward,
+        )?;
+        let mut address_signatures = vec![];
+        for entry in iterator {
+            let (slot, transaction_index, signature) = entry?;
+            if slot > highest_root || address_signatures.len() >= limit {
+                break;
+            }
+            // Keys are ordered by their bytes, so compare signatures by theirs too
+            let is_after = after.map_or(true, |(after_slot, after_index, after_signature)| {
+                (slot, transaction_index, signature.as_ref())
+                    > (after_slot, after_index, after_signature.as_ref())
+            });
+            if is_after && self.is_root(slot) {
+                address_signatures.push((slot, transaction_index, signature));
+            }
+        }
+        drop(lock);
+
+        address_signatures
+            .into_iter()
+            .map(|position @ (slot, _, signature)| {
+                let info =
+                    self.get_transaction_status_with_signature(slot, signature, &HashSet::new())?;
+                Ok((position, info))
+            })
+            .collect()
+    }
+
+    fn get_transaction_status_with_signature(
+        &self,
+        slot: Slot,
+        signature: Signature,
+        confirmed_unrooted_slots: &HashSet<Slot>,
+    ) -> Result<ConfirmedTransactionStatusWithSignature> {
+        let transaction_status =
+            self.get_transaction_status(signature, confirmed_unrooted_slots)?;
+        let err = transaction_status.and_then(|(_slot, status)| status.status.err());
+        let memo = self.read_transaction_memos(signature, slot)?;
+        let block_time = self.get_block_time(slot)?;
+        Ok(ConfirmedTransactionStatusWithSignature {
+            signature,
+            slot,
+            err,
+            memo,
+            block_time,
+        })
+    }
+
     pub fn read_rewards(&self, index: Slot) -> Result<Option<Rewards>> {
         self.rewards_cf
             .get_protobuf_or_bincode::<Rewards>(index)
@@ -4137,6 +4617,46 @@ impl Blockstore {
         Ok(duplicate_slots_iterator.map(|(slot, _)| slot))
     }
 
+    /// Stores `proof` unless a proof already exists for `vote_account` at
+    /// `slot`. Returns whether the proof was stored.
+    pub fn store_duplicate_vote(
+        &self,
+        slot: Slot,
+        vote_account: Pubkey,
+        proof: &DuplicateVoteProof,
+    ) -> Result<bool> {
+        if self.duplicate_votes_cf.get((slot, vote_account))?.is_some() {
+            return Ok(false);
+        }
+        self.duplicate_votes_cf.put((slot, vote_account), proof)?;
+        Ok(true)
+    }
+
+    pub fn get_duplicate_vote(
+        &self,
+        slot: Slot,
+        vote_account: Pubkey,
+    ) -> Result<Option<DuplicateVoteProof>> {
+        self.duplicate_votes_cf.get((slot, vote_account))
+    }
+
+    /// Iterates over duplicate vote proofs at or above `slot`, in slot order.
+    pub fn duplicate_votes_iterator(
+        &self,
+        slot: Slot,
+    ) -> Result<impl Iterator<Item = (Slot, Pubkey, DuplicateVoteProof)> + '_> {
+        let duplicate_votes_iterator = self.duplicate_votes_cf.iter(IteratorMode::From(
+            (slot, Pubkey::default()),
+            IteratorDirection::Forward,
+        ))?;
+        Ok(
+            duplicate_votes_iterator.filter_map(|((slot, vote_account), proof_bytes)| {
+                let proof = deserialize(&proof_bytes).ok()?;
+                Some((slot, vote_account, proof))
+            }),
+        )
+    }
+
     pub fn has_existing_shreds_for_slot(&self, slot: Slot) -> bool {
         match self.meta(slot).unwrap() {
             Some(meta) => meta.received > 0,
@@ -5233,6 +5753,20 @@ pub fn test_all_empty_or_min(blockstore: &Blockstore, min_slot: Slot) {
             .next()
             .map(|(slot, _)| slot >= min_slot)
             .unwrap_or(true)
+        & blockstore
+            .duplicate_votes_cf
+            .iter(IteratorMode::Start)
+            .unwrap()
+            .next()
+            .map(|((slot, _), _)| slot >= min_slot)
+            .unwrap_or(true)
+        & blockstore
+            .block_fee_summary_cf
+            .iter(IteratorMode::Start)
+            .unwrap()
+            .next()
+            .map(|(slot, _)| slot >= min_slot)
+            .unwrap_or(true)
         & blockstore
             .erasure_meta_cf
             .iter(IteratorMode::Start)
@@ -5268,6 +5802,13 @@ pub fn test_all_empty_or_min(blockstore: &Blockstore, min_slot: Slot) {
             .next()
             .map(|((_, slot, _, _), _)| slot >= min_slot || slot == 0)
             .unwrap_or(true)
+        & blockstore
+            .address_signature_shards_cf
+            .iter(IteratorMode::Start)
+            .unwrap()
+            .next()
+            .map(|((_, _, slot, _, _), _)| slot >= min_slot || slot == 0)
+            .unwrap_or(true)
         & blockstore
             .rewards_cf
             .iter(IteratorMode::Start)
@@ -5376,6 +5917,7 @@ pub mod tests {
     use {
         super::*,
         crate::{
+            blockstore::column::ADDRESS_SIGNATURE_SHARD_SLOTS,
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
             leader_schedule::{FixedSchedule, LeaderSchedule},
             shred::{max_ticks_per_n_shreds, ShredFlags, LEGACY_SHRED_DATA_CAPACITY},
@@ -5404,7 +5946,8 @@ pub mod tests {
         solana_storage_proto::convert::generated,
         solana_transaction_context::TransactionReturnData,
         solana_transaction_status::{
-            InnerInstruction, InnerInstructions, Reward, Rewards, TransactionTokenBalance,
+            FeeBreakdown, InnerInstruction, InnerInstructions, Reward, Rewards,
+            TransactionTokenBalance,
         },
         std::{cmp::Ordering, thread::Builder, time::Duration},
         test_case::test_case,
@@ -8344,6 +8887,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
+                    fee_breakdown: None,
                 }
                 .into();
                 blockstore
@@ -8363,6 +8907,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
+                    fee_breakdown: None,
                 }
                 .into();
                 blockstore
@@ -8382,6 +8927,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
+                    fee_breakdown: None,
                 }
                 .into();
                 blockstore
@@ -8403,6 +8949,7 @@ pub mod tests {
                         loaded_addresses: LoadedAddresses::default(),
                         return_data: Some(TransactionReturnData::default()),
                         compute_units_consumed,
+                        fee_breakdown: None,
                     },
                 }
             })
@@ -8527,6 +9074,12 @@ pub mod tests {
         };
         let compute_units_consumed_1 = Some(3812649u64);
         let compute_units_consumed_2 = Some(42u64);
+        let test_fee_breakdown = FeeBreakdown {
+            base: 5_000,
+            prioritization: 300,
+            congestion: 50,
+            ..FeeBreakdown::default()
+        };
 
         // result not found
         assert!(transaction_status_cf
@@ -8548,6 +9101,7 @@ pub mod tests {
             loaded_addresses: test_loaded_addresses.clone(),
             return_data: Some(test_return_data.clone()),
             compute_units_consumed: compute_units_consumed_1,
+            fee_breakdown: Some(test_fee_breakdown),
         }
         .into();
         assert!(transaction_status_cf
@@ -8568,6 +9122,7 @@ pub mod tests {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            fee_breakdown,
         } = transaction_status_cf
             .get_protobuf((Signature::default(), 0))
             .unwrap()
@@ -8586,6 +9141,7 @@ pub mod tests {
         assert_eq!(loaded_addresses, test_loaded_addresses);
         assert_eq!(return_data.unwrap(), test_return_data);
         assert_eq!(compute_units_consumed, compute_units_consumed_1);
+        assert_eq!(fee_breakdown, Some(test_fee_breakdown));
 
         // insert value
         let status = TransactionStatusMeta {
@@ -8601,6 +9157,7 @@ pub mod tests {
             loaded_addresses: test_loaded_addresses.clone(),
             return_data: Some(test_return_data.clone()),
             compute_units_consumed: compute_units_consumed_2,
+            fee_breakdown: None,
         }
         .into();
         assert!(transaction_status_cf
@@ -8621,6 +9178,7 @@ pub mod tests {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            fee_breakdown,
         } = transaction_status_cf
             .get_protobuf((Signature::from([2u8; 64]), 9))
             .unwrap()
@@ -8641,6 +9199,7 @@ pub mod tests {
         assert_eq!(loaded_addresses, test_loaded_addresses);
         assert_eq!(return_data.unwrap(), test_return_data);
         assert_eq!(compute_units_consumed, compute_units_consumed_2);
+        assert_eq!(fee_breakdown, None);
     }
 
     #[test]
@@ -8737,6 +9296,7 @@ pub mod tests {
             loaded_addresses: LoadedAddresses::default(),
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
+            fee_breakdown: None,
         }
         .into();
 
@@ -8913,6 +9473,7 @@ pub mod tests {
             loaded_addresses: LoadedAddresses::default(),
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
+            fee_breakdown: None,
         }
         .into();
 
@@ -9040,6 +9601,7 @@ pub mod tests {
             loaded_addresses: LoadedAddresses::default(),
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
+            fee_breakdown: None,
         }
         .into();
 
@@ -9209,6 +9771,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: return_data.clone(),
                     compute_units_consumed: Some(42),
+                    fee_breakdown: None,
                 }
                 .into();
                 blockstore
@@ -9230,6 +9793,7 @@ pub mod tests {
                         loaded_addresses: LoadedAddresses::default(),
                         return_data,
                         compute_units_consumed: Some(42),
+                        fee_breakdown: None,
                     },
                 }
             })
@@ -9331,6 +9895,7 @@ pub mod tests {
                     loaded_addresses: LoadedAddresses::default(),
                     return_data: return_data.clone(),
                     compute_units_consumed: Some(42u64),
+                    fee_breakdown: None,
                 }
                 .into();
                 blockstore
@@ -9352,6 +9917,7 @@ pub mod tests {
                         loaded_addresses: LoadedAddresses::default(),
                         return_data,
                         compute_units_consumed: Some(42u64),
+                        fee_breakdown: None,
                     },
                 }
             })
@@ -9964,8 +10530,14 @@ pub mod tests {
 
         // Remove signature
         blockstore
-            .address_signatures_cf
-            .delete((address0, 2, 0, all0[0].signature))
+            .address_signature_shards_cf
+            .delete((
+                address_signature_shard(2),
+                address0,
+                2,
+                0,
+                all0[0].signature,
+            ))
             .unwrap();
         let sig_infos = blockstore
             .get_confirmed_signatures_for_address2(
@@ -9980,6 +10552,329 @@ pub mod tests {
         assert!(sig_infos.infos.is_empty());
     }
 
+    #[test]
+    fn test_get_rooted_signatures_for_address_after() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let address = solana_pubkey::new_rand();
+
+        // Two transactions mentioning the address in each of slots 1 to 5
+        let mut signatures = vec![];
+        for slot in 1..=5 {
+            for transaction_index in 0..2 {
+                let signature = Signature::new_unique();
+                blockstore
+                    .write_transaction_status(
+                        slot,
+                        signature,
+                        [(&address, true)].into_iter(),
+                        TransactionStatusMeta::default(),
+                        transaction_index,
+                    )
+                    .unwrap();
+                signatures.push((slot, transaction_index as u32, signature));
+            }
+        }
+        // Slot 3 is on a dead fork and slot 5 is not rooted yet
+        blockstore.set_roots([1, 2, 4].iter()).unwrap();
+        let rooted: Vec<_> = signatures
+            .iter()
+            .filter(|(slot, _, _)| [1, 2, 4].contains(slot))
+            .copied()
+            .collect();
+
+        let list = |after, limit| {
+            blockstore
+                .get_rooted_signatures_for_address_after(address, 5, after, limit)
+                .unwrap()
+                .into_iter()
+                .map(|(position, info)| {
+                    assert_eq!((position.0, position.2), (info.slot, info.signature));
+                    position
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(list(None, usize::MAX), rooted);
+
+        // Resuming after the last position returned lists every signature exactly once
+        let first = list(None, 3);
+        assert_eq!(first, rooted[..3]);
+        let second = list(Some(first[2]), 3);
+        assert_eq!(second, rooted[3..]);
+        assert!(list(Some(second[2]), 3).is_empty());
+
+        // Signatures appear once their slot is rooted
+        blockstore.set_roots([5].iter()).unwrap();
+        assert_eq!(list(Some(second[2]), 3), signatures[8..]);
+
+        // The highest root bounds the listing
+        assert_eq!(
+            blockstore
+                .get_rooted_signatures_for_address_after(address, 2, None, usize::MAX)
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_get_filtered_signatures_for_address() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let address = solana_pubkey::new_rand();
+        let program_id = solana_pubkey::new_rand();
+
+        // In each of slots 1 to 4, produced 100 seconds apart, a transaction invoking the program
+        // and one that doesn't reference it
+        let mut signatures = vec![];
+        for slot in 1..=4 {
+            blockstore
+                .set_block_time(slot, slot as UnixTimestamp * 100)
+                .unwrap();
+            for transaction_index in 0..2 {
+                let signature = Signature::new_unique();
+                let keys: &[(&Pubkey, bool)] = if transaction_index == 0 {
+                    &[(&address, true), (&program_id, false)]
+                } else {
+                    &[(&address, true)]
+                };
+                blockstore
+                    .write_transaction_status(
+                        slot,
+                        signature,
+                        keys.iter().copied(),
+                        TransactionStatusMeta::default(),
+                        transaction_index,
+                    )
+                    .unwrap();
+                signatures.push((slot, transaction_index, signature));
+            }
+        }
+        blockstore.set_roots([1, 2, 3, 4].iter()).unwrap();
+
+        let list = |filter: SignaturesForAddressFilter, limit| {
+            blockstore
+                .get_filtered_signatures_for_address(address, 4, None, None, limit, &filter)
+                .unwrap()
+                .infos
+                .into_iter()
+                .map(|info| info.signature)
+                .collect::<Vec<_>>()
+        };
+        // Signatures are listed from the latest one
+        let expected = |predicate: &dyn Fn(Slot, usize) -> bool| {
+            signatures
+                .iter()
+                .rev()
+                .filter(|(slot, transaction_index, _)| predicate(*slot, *transaction_index))
+                .map(|(_, _, signature)| *signature)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            list(SignaturesForAddressFilter::default(), usize::MAX),
+            expected(&|_, _| true)
+        );
+        let time_range = SignaturesForAddressFilter {
+            min_block_time: Some(200),
+            max_block_time: Some(300),
+            program_id: None,
+        };
+        assert_eq!(
+            list(time_range.clone(), usize::MAX),
+            expected(&|slot, _| (2..=3).contains(&slot))
+        );
+        let program = SignaturesForAddressFilter {
+            program_id: Some(program_id),
+            ..SignaturesForAddressFilter::default()
+        };
+        assert_eq!(
+            list(program.clone(), usize::MAX),
+            expected(&|_, transaction_index| transaction_index == 0)
+        );
+        assert_eq!(
+            list(program.clone(), 1),
+            expected(&|slot, transaction_index| slot == 4 && transaction_index == 0)
+        );
+        assert_eq!(
+            list(
+                SignaturesForAddressFilter {
+                    program_id: Some(program_id),
+                    ..time_range
+                },
+                usize::MAX
+            ),
+            expected(&|slot, transaction_index| (2..=3).contains(&slot) && transaction_index == 0)
+        );
+        // No block is produced in the time range
+        assert!(list(
+            SignaturesForAddressFilter {
+                min_block_time: Some(500),
+                ..program
+            },
+            usize::MAX
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_address_signature_shards() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let address = solana_pubkey::new_rand();
+
+        // Signatures in the first and fourth shards, with the shards in between empty
+        let slots = [
+            1,
+            2,
+            3 * ADDRESS_SIGNATURE_SHARD_SLOTS,
+            3 * ADDRESS_SIGNATURE_SHARD_SLOTS + 1,
+        ];
+        let mut signatures = vec![];
+        for slot in slots {
+            let signature = Signature::new_unique();
+            blockstore
+                .write_transaction_status(
+                    slot,
+                    signature,
+                    [(&address, true)].into_iter(),
+                    TransactionStatusMeta::default(),
+                    0,
+                )
+                .unwrap();
+            signatures.push((slot, 0, signature));
+        }
+        blockstore.set_roots(slots.iter()).unwrap();
+        assert!(blockstore
+            .address_signatures_cf
+            .iter(IteratorMode::Start)
+            .unwrap()
+            .next()
+            .is_none());
+
+        // Signatures of another address in the shards in between
+        let other_address = solana_pubkey::new_rand();
+        for shard in 1..3 {
+            blockstore
+                .write_transaction_status(
+                    shard * ADDRESS_SIGNATURE_SHARD_SLOTS,
+                    Signature::new_unique(),
+                    [(&other_address, true)].into_iter(),
+                    TransactionStatusMeta::default(),
+                    0,
+                )
+                .unwrap();
+        }
+        // Only the shards holding signatures of the address are marked, so
+        // only those are seeked
+        let marked_shards = |address: Pubkey| {
+            blockstore
+                .address_signature_shard_markers_cf
+                .iter(IteratorMode::Start)
+                .unwrap()
+                .map(|(index, _)| index)
+                .filter(|(key_address, _)| *key_address == address)
+                .map(|(_, shard)| shard)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(marked_shards(address), vec![0, 3]);
+        assert_eq!(marked_shards(other_address), vec![1, 2]);
+
+        let listed: Vec<_> = blockstore
+            .get_confirmed_signatures_for_address2(address, slots[3], None, None, usize::MAX)
+            .unwrap()
+            .infos
+            .into_iter()
+            .map(|info| (info.slot, 0, info.signature))
+            .collect();
+        assert_eq!(listed, signatures.iter().rev().copied().collect::<Vec<_>>());
+        let listed: Vec<_> = blockstore
+            .get_rooted_signatures_for_address_after(address, slots[3], None, usize::MAX)
+            .unwrap()
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect();
+        assert_eq!(listed, signatures);
+        assert_eq!(
+            blockstore
+                .find_address_signatures_for_slot(address, slots[2])
+                .unwrap(),
+            vec![(slots[2], signatures[2].2)]
+        );
+    }
+
+    #[test]
+    fn test_migrate_address_signatures_to_shards() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let address = solana_pubkey::new_rand();
+        let signatures: Vec<_> = (1..=3).map(|_| Signature::new_unique()).collect();
+        {
+            // Address signatures written before shards
+            let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+            for (slot, signature) in (1..).zip(&signatures[..2]) {
+                blockstore
+                    .write_transaction_status(
+                        slot,
+                        *signature,
+                        std::iter::empty(),
+                        TransactionStatusMeta::default(),
+                        0,
+                    )
+                    .unwrap();
+                blockstore
+                    .address_signatures_cf
+                    .put(
+                        (address, slot, 0, *signature),
+                        &AddressSignatureMeta { writeable: true },
+                    )
+                    .unwrap();
+            }
+        }
+
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        assert!(blockstore.legacy_address_signatures.load(Ordering::Relaxed));
+        // New address signatures are written to both columns until migrated
+        blockstore
+            .write_transaction_status(
+                3,
+                signatures[2],
+                [(&address, true)].into_iter(),
+                TransactionStatusMeta::default(),
+                0,
+            )
+            .unwrap();
+        blockstore.set_roots([1, 2, 3].iter()).unwrap();
+        let list = || {
+            blockstore
+                .get_confirmed_signatures_for_address2(address, 3, None, None, usize::MAX)
+                .unwrap()
+                .infos
+                .into_iter()
+                .map(|info| info.signature)
+                .collect::<Vec<_>>()
+        };
+        let expected: Vec<_> = signatures.iter().rev().copied().collect();
+        assert_eq!(list(), expected);
+
+        // Every legacy entry is moved, including the one already written to its shard
+        assert_eq!(
+            blockstore.migrate_address_signatures_to_shards().unwrap(),
+            3
+        );
+        assert!(!blockstore.legacy_address_signatures.load(Ordering::Relaxed));
+        assert!(blockstore
+            .address_signatures_cf
+            .iter(IteratorMode::Start)
+            .unwrap()
+            .next()
+            .is_none());
+        assert_eq!(list(), expected);
+        assert_eq!(
+            blockstore.migrate_address_signatures_to_shards().unwrap(),
+            0
+        );
+    }
+
     #[test]
     fn test_get_last_hash() {
         let entries: Vec<Entry> = vec![];
@@ -10028,6 +10923,7 @@ pub mod tests {
                 loaded_addresses: LoadedAddresses::default(),
                 return_data: Some(TransactionReturnData::default()),
                 compute_units_consumed: None,
+                fee_breakdown: None,
             }
             .into();
             transaction_status_cf
@@ -10596,6 +11492,199 @@ pub mod tests {
         assert_eq!(duplicate_proof.shred2, *duplicate_shred.payload());
     }
 
+    #[test]
+    fn test_duplicate_vote() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let vote_account = Pubkey::new_unique();
+        let other_vote_account = Pubkey::new_unique();
+        let proof = |byte: u8| DuplicateVoteProof {
+            hash1: Hash::new_unique(),
+            transaction1: vec![byte; 4],
+            hash2: Hash::new_unique(),
+            transaction2: vec![byte.wrapping_add(1); 4],
+        };
+        let first_proof = proof(1);
+
+        assert_eq!(
+            blockstore.get_duplicate_vote(5, vote_account).unwrap(),
+            None
+        );
+        assert!(blockstore
+            .store_duplicate_vote(5, vote_account, &first_proof)
+            .unwrap());
+        // Only the first proof for a (slot, vote account) is kept
+        assert!(!blockstore
+            .store_duplicate_vote(5, vote_account, &proof(2))
+            .unwrap());
+        assert!(blockstore
+            .store_duplicate_vote(3, other_vote_account, &proof(3))
+            .unwrap());
+        assert!(blockstore
+            .store_duplicate_vote(7, other_vote_account, &proof(4))
+            .unwrap());
+        assert_eq!(
+            blockstore.get_duplicate_vote(5, vote_account).unwrap(),
+            Some(first_proof.clone())
+        );
+
+        let evidence: Vec<_> = blockstore
+            .duplicate_votes_iterator(4)
+            .unwrap()
+            .map(|(slot, vote_account, _)| (slot, vote_account))
+            .collect();
+        assert_eq!(evidence, vec![(5, vote_account), (7, other_vote_account)]);
+
+        // Evidence is purged along with the rest of the slot
+        blockstore.purge_slots(0, 5, PurgeType::Exact);
+        assert_eq!(
+            blockstore.get_duplicate_vote(5, vote_account).unwrap(),
+            None
+        );
+        assert_eq!(blockstore.duplicate_votes_iterator(0).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_block_fee_summary() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let fee_summary = |slot: Slot| BlockFeeSummary {
+            leader: Pubkey::new_unique(),
+            transaction_fee: slot * 10_000,
+            priority_fee: slot * 1_000,
+            burned: slot * 5_000,
+            leader_reward: slot * 4_000,
+            treasury: slot * 2_000,
+        };
+        let fee_summaries: Vec<_> = (1..=3).map(fee_summary).collect();
+        for (slot, fee_summary) in (1..).zip(&fee_summaries) {
+            assert_eq!(blockstore.get_block_fee_summary(slot).unwrap(), None);
+            blockstore.set_block_fee_summary(slot, fee_summary).unwrap();
+        }
+        for (slot, fee_summary) in (1..).zip(&fee_summaries) {
+            assert_eq!(
+                blockstore.get_block_fee_summary(slot).unwrap(),
+                Some(*fee_summary)
+            );
+        }
+
+        // Fee summaries are purged along with the rest of the slot
+        blockstore.purge_slots(0, 2, PurgeType::Exact);
+        assert_eq!(blockstore.get_block_fee_summary(2).unwrap(), None);
+        assert_eq!(
+            blockstore.get_block_fee_summary(3).unwrap(),
+            Some(fee_summaries[2])
+        );
+
+        // Summaries recorded before the fee treasury split are still readable
+        let legacy_fee_summary = BlockFeeSummaryV1 {
+            leader: Pubkey::new_unique(),
+            transaction_fee: 10_000,
+            priority_fee: 1_000,
+            burned: 5_000,
+            leader_reward: 6_000,
+        };
+        blockstore
+            .block_fee_summary_cf
+            .put_bytes(4, &bincode::serialize(&legacy_fee_summary).unwrap())
+            .unwrap();
+        assert_eq!(
+            blockstore.get_block_fee_summary(4).unwrap(),
+            Some(BlockFeeSummary::from(legacy_fee_summary))
+        );
+
+        *blockstore.lowest_cleanup_slot.write().unwrap() = 2;
+        assert_matches!(
+            blockstore.get_block_fee_summary(2),
+            Err(BlockstoreError::SlotCleanedUp)
+        );
+    }
+
+    #[test]
+    fn test_archived_accounts() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let pubkey = Pubkey::new_unique();
+        let other_pubkey = Pubkey::new_unique();
+        let account = |lamports| Account {
+            lamports,
+            ..Account::default()
+        };
+        for slot in 1..=4 {
+            blockstore
+                .write_archived_accounts(slot, [(&pubkey, &account(slot))])
+                .unwrap();
+        }
+        blockstore
+            .write_archived_accounts(2, [(&other_pubkey, &account(20))])
+            .unwrap();
+        // Slot 3 is on a dead fork
+        blockstore.set_roots([1, 2, 4].iter()).unwrap();
+
+        assert_eq!(
+            blockstore.get_archived_account(&pubkey, 1).unwrap(),
+            Some((1, account(1)))
+        );
+        assert_eq!(
+            blockstore.get_archived_account(&pubkey, 3).unwrap(),
+            Some((2, account(2)))
+        );
+        assert_eq!(
+            blockstore.get_archived_account(&pubkey, 10).unwrap(),
+            Some((4, account(4)))
+        );
+        assert_eq!(
+            blockstore.get_archived_account(&other_pubkey, 1).unwrap(),
+            None
+        );
+        assert_eq!(
+            blockstore.get_archived_account(&other_pubkey, 4).unwrap(),
+            Some((2, account(20)))
+        );
+        assert_eq!(
+            blockstore
+                .get_archived_account(&Pubkey::new_unique(), 4)
+                .unwrap(),
+            None
+        );
+
+        // Archived accounts are purged along with the rest of the slot
+        blockstore.purge_slots(0, 2, PurgeType::Exact);
+        assert_eq!(
+            blockstore.get_archived_account(&other_pubkey, 4).unwrap(),
+            None
+        );
+        assert_eq!(
+            blockstore.get_archived_account(&pubkey, 4).unwrap(),
+            Some((4, account(4)))
+        );
+        // Only the entries of the purged slots are deleted, from both the
+        // column and its slot index
+        let archived: Vec<_> = blockstore
+            .archived_accounts_cf
+            .iter(IteratorMode::Start)
+            .unwrap()
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(archived, vec![(pubkey, 3), (pubkey, 4)]);
+        let archived_slots: Vec<_> = blockstore
+            .archived_account_slots_cf
+            .iter(IteratorMode::Start)
+            .unwrap()
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(archived_slots, vec![(3, pubkey), (4, pubkey)]);
+
+        *blockstore.lowest_cleanup_slot.write().unwrap() = 2;
+        assert_matches!(
+            blockstore.get_archived_account(&pubkey, 2),
+            Err(BlockstoreError::SlotCleanedUp)
+        );
+    }
+
     #[test]
     fn test_clear_unconfirmed_slot() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
@@ -10833,6 +11922,7 @@ pub mod tests {
                 data: vec![1, 2, 3],
             }),
             compute_units_consumed: Some(23456),
+            fee_breakdown: None,
         };
         let deprecated_status: StoredTransactionStatusMeta = status.clone().try_into().unwrap();
         let protobuf_status: generated::TransactionStatusMeta = status.into();
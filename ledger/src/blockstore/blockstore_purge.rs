@@ -1,5 +1,6 @@
 use {
     super::*,
+    crate::blockstore::column::{address_signature_shards_within, ADDRESS_SIGNATURE_SHARD_SLOTS},
     solana_sdk::message::AccountKeys,
     std::{cmp::max, time::Instant},
 };
@@ -281,6 +282,14 @@ impl Blockstore {
                 .duplicate_slots_cf
                 .delete_range_in_batch(write_batch, from_slot, to_slot)
                 .is_ok()
+            & self
+                .duplicate_votes_cf
+                .delete_range_in_batch(write_batch, from_slot, to_slot)
+                .is_ok()
+            & self
+                .block_fee_summary_cf
+                .delete_range_in_batch(write_batch, from_slot, to_slot)
+                .is_ok()
             & self
                 .erasure_meta_cf
                 .delete_range_in_batch(write_batch, from_slot, to_slot)
@@ -316,11 +325,19 @@ impl Blockstore {
             & self
                 .merkle_root_meta_cf
                 .delete_range_in_batch(write_batch, from_slot, to_slot)
+                .is_ok()
+            & self
+                .archived_account_slots_cf
+                .delete_range_in_batch(write_batch, from_slot, to_slot)
+                .is_ok()
+            & self
+                .purge_address_signature_shards(write_batch, from_slot, to_slot)
                 .is_ok();
 
         match purge_type {
             PurgeType::Exact => {
                 self.purge_special_columns_exact(write_batch, from_slot, to_slot)?;
+                self.purge_archived_accounts_exact(write_batch, from_slot, to_slot)?;
             }
             PurgeType::CompactionFilter => {
                 // No explicit action is required here because this purge type completely and
@@ -361,6 +378,14 @@ impl Blockstore {
                 .duplicate_slots_cf
                 .delete_file_in_range(from_slot, to_slot)
                 .is_ok()
+            & self
+                .duplicate_votes_cf
+                .delete_file_in_range(from_slot, to_slot)
+                .is_ok()
+            & self
+                .block_fee_summary_cf
+                .delete_file_in_range(from_slot, to_slot)
+                .is_ok()
             & self
                 .erasure_meta_cf
                 .delete_file_in_range(from_slot, to_slot)
@@ -397,10 +422,57 @@ impl Blockstore {
                 .merkle_root_meta_cf
                 .delete_file_in_range(from_slot, to_slot)
                 .is_ok()
+            & self
+                .archived_account_slots_cf
+                .delete_file_in_range(from_slot, to_slot)
+                .is_ok()
+            & self.purge_address_signature_shard_files(from_slot, to_slot)
+    }
+
+    /// Deletes the files of the AddressSignatureShards column only holding
+    /// entries of shards whose slots all lie in \[`from_slot`, `to_slot`\].
+    /// The range of a file delete starts at the first key of the shard of
+    /// `from_slot`, so it is clamped to whole shards not to drop the entries
+    /// of slots which are not purged.
+    fn purge_address_signature_shard_files(&self, from_slot: Slot, to_slot: Slot) -> bool {
+        let shards = address_signature_shards_within(from_slot, to_slot);
+        if shards.is_empty() {
+            return true;
+        }
+        // The first key of the shard following the range sorts after every
+        // entry of the range
+        self.address_signature_shards_cf
+            .delete_file_in_range(
+                shards.start * ADDRESS_SIGNATURE_SHARD_SLOTS,
+                shards.end * ADDRESS_SIGNATURE_SHARD_SLOTS,
+            )
+            .is_ok()
     }
 
-    /// Returns true if the special columns, TransactionStatus and
-    /// AddressSignatures, are both empty.
+    /// Range deletes the shards of the AddressSignatureShards column whose
+    /// slots all lie in \[`from_slot`, `to_slot`\]. The entries of the other
+    /// slots in range are deleted by `purge_special_columns_exact()` or, when
+    /// relying on the compaction filter, along with their shard once its last
+    /// slot is purged.
+    fn purge_address_signature_shards(
+        &self,
+        write_batch: &mut WriteBatch,
+        from_slot: Slot,
+        to_slot: Slot,
+    ) -> Result<()> {
+        let shards = address_signature_shards_within(from_slot, to_slot);
+        if shards.is_empty() {
+            return Ok(());
+        }
+        self.address_signature_shards_cf.delete_range_in_batch(
+            write_batch,
+            shards.start * ADDRESS_SIGNATURE_SHARD_SLOTS,
+            shards.end * ADDRESS_SIGNATURE_SHARD_SLOTS - 1,
+        )
+    }
+
+    /// Returns true if the special columns, TransactionStatus,
+    /// AddressSignatures and AddressSignatureShards, are all empty.
     ///
     /// It should not be the case that one is empty and the other is not, but
     /// just return false in this case.
@@ -414,11 +486,42 @@ impl Blockstore {
             .address_signatures_cf
             .iter(IteratorMode::Start)?
             .next()
-            .is_none();
+            .is_none()
+            && self
+                .address_signature_shards_cf
+                .iter(IteratorMode::Start)?
+                .next()
+                .is_none();
 
         Ok(transaction_status_empty && address_signatures_empty)
     }
 
+    /// Purges the ArchivedAccounts column exactly. The column is keyed by
+    /// pubkey first, so the accounts archived by the slots in range are found
+    /// from its slot index, which is range deleted along with the other slot
+    /// columns.
+    ///
+    /// The purge range applies to \[`from_slot`, `to_slot`\].
+    fn purge_archived_accounts_exact(
+        &self,
+        batch: &mut WriteBatch,
+        from_slot: Slot,
+        to_slot: Slot,
+    ) -> Result<()> {
+        let iterator = self.archived_account_slots_cf.iter(IteratorMode::From(
+            (from_slot, Pubkey::default()),
+            IteratorDirection::Forward,
+        ))?;
+        for ((slot, pubkey), _) in iterator {
+            if slot > to_slot {
+                break;
+            }
+            self.archived_accounts_cf
+                .delete_in_batch(batch, (pubkey, slot))?;
+        }
+        Ok(())
+    }
+
     /// Purges special columns (using a non-Slot primary-index) exactly, by
     /// deserializing each slot being purged and iterating through all
     /// transactions to determine the keys of individual records.
@@ -436,6 +539,9 @@ impl Blockstore {
             return Ok(());
         }
 
+        // The markers of the shards purged as a whole are deleted along with
+        // their entries
+        let purged_shards = address_signature_shards_within(from_slot, to_slot);
         let mut index0 = self.transaction_status_index_cf.get(0)?.unwrap_or_default();
         let mut index1 = self.transaction_status_index_cf.get(1)?.unwrap_or_default();
         let highest_primary_index_slot = self.get_highest_primary_index_slot();
@@ -490,6 +596,20 @@ impl Blockstore {
                             batch,
                             (*pubkey, slot, transaction_index, signature),
                         )?;
+                        self.address_signature_shards_cf.delete_in_batch(
+                            batch,
+                            (
+                                address_signature_shard(slot),
+                                *pubkey,
+                                slot,
+                                transaction_index,
+                                signature,
+                            ),
+                        )?;
+                        if purged_shards.contains(&address_signature_shard(slot)) {
+                            self.address_signature_shard_markers_cf
+                                .delete_in_batch(batch, (*pubkey, address_signature_shard(slot)))?;
+                        }
                         for primary_index in &primary_indexes {
                             self.address_signatures_cf.delete_deprecated_in_batch(
                                 batch,
@@ -537,6 +657,70 @@ pub mod tests {
         test_case::test_case,
     };
 
+    #[test]
+    fn test_address_signature_shards_within() {
+        const SHARD_SLOTS: Slot = ADDRESS_SIGNATURE_SHARD_SLOTS;
+        assert_eq!(address_signature_shards_within(0, SHARD_SLOTS - 1), 0..1);
+        // Ranges starting or ending mid-shard leave that shard out
+        assert!(address_signature_shards_within(1, SHARD_SLOTS - 1).is_empty());
+        assert!(address_signature_shards_within(0, SHARD_SLOTS - 2).is_empty());
+        assert!(address_signature_shards_within(SHARD_SLOTS + 1, 2 * SHARD_SLOTS).is_empty());
+        assert_eq!(
+            address_signature_shards_within(1, 3 * SHARD_SLOTS - 1),
+            1..3
+        );
+        assert_eq!(
+            address_signature_shards_within(SHARD_SLOTS, 3 * SHARD_SLOTS),
+            1..3
+        );
+    }
+
+    #[test]
+    fn test_purge_address_signature_shards() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let address = Pubkey::new_unique();
+        for slot in [1, ADDRESS_SIGNATURE_SHARD_SLOTS + 1] {
+            blockstore
+                .write_transaction_status(
+                    slot,
+                    Signature::new_unique(),
+                    [(&address, true)].into_iter(),
+                    TransactionStatusMeta::default(),
+                    0,
+                )
+                .unwrap();
+        }
+        let shard_slots = || {
+            blockstore
+                .address_signature_shards_cf
+                .iter(IteratorMode::Start)
+                .unwrap()
+                .map(|((shard, _, slot, _, _), _)| (shard, slot))
+                .collect::<Vec<_>>()
+        };
+
+        // Only the shards whose slots are all purged are deleted
+        blockstore.purge_slots(
+            0,
+            ADDRESS_SIGNATURE_SHARD_SLOTS,
+            PurgeType::CompactionFilter,
+        );
+        assert_eq!(shard_slots(), vec![(1, ADDRESS_SIGNATURE_SHARD_SLOTS + 1)]);
+        blockstore.purge_slots(
+            ADDRESS_SIGNATURE_SHARD_SLOTS + 1,
+            2 * ADDRESS_SIGNATURE_SHARD_SLOTS,
+            PurgeType::CompactionFilter,
+        );
+        assert_eq!(shard_slots(), vec![(1, ADDRESS_SIGNATURE_SHARD_SLOTS + 1)]);
+        blockstore.purge_slots(
+            0,
+            2 * ADDRESS_SIGNATURE_SHARD_SLOTS - 1,
+            PurgeType::CompactionFilter,
+        );
+        assert!(shard_slots().is_empty());
+    }
+
     #[test]
     fn test_purge_slots() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
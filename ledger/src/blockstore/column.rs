@@ -12,6 +12,7 @@ use {
         signature::{Signature, SIGNATURE_BYTES},
     },
     solana_storage_proto::convert::generated,
+    std::ops::Range,
 };
 
 // To add a new column, declare the type below and implement the applicable
@@ -147,6 +148,32 @@ pub mod columns {
     /// * value type: [`blockstore_meta::AddressSignatureMeta`]
     pub struct AddressSignatures;
 
+    #[derive(Debug)]
+    /// The address signature shards column
+    ///
+    /// This column holds the same entries as the address signatures column,
+    /// prefixed by the shard of their slot, see
+    /// [`address_signature_shard`](super::address_signature_shard). Entries of
+    /// a shard are contiguous, so a shard is pruned by a single range delete
+    /// once all of its slots are purged, instead of by a compaction filter
+    /// rewriting every key of the column.
+    ///
+    /// * index type: `(u64, `[`Pubkey`]`, `[`Slot`]`, u32, `[`Signature`]`)`
+    /// * value type: [`blockstore_meta::AddressSignatureMeta`]
+    pub struct AddressSignatureShards;
+
+    #[derive(Debug)]
+    /// The address signature shard markers column
+    ///
+    /// This column marks the shards of the address signature shards column
+    /// holding entries of an address, so that iterating over the signatures
+    /// of an address only seeks the shards holding some. A marker is removed
+    /// by the compaction filter once all slots of its shard are purged.
+    ///
+    /// * index type: `(`[`Pubkey`]`, u64)`
+    /// * value type: `bool`
+    pub struct AddressSignatureShardMarkers;
+
     #[derive(Debug)]
     /// The transaction memos column
     ///
@@ -215,6 +242,69 @@ pub mod columns {
     /// * index type: `crate::shred::ErasureSetId` `(Slot, fec_set_index: u32)`
     /// * value type: [`blockstore_meta::MerkleRootMeta`]`
     pub struct MerkleRootMeta;
+
+    #[derive(Debug)]
+    /// The duplicate votes column
+    ///
+    /// This column stores evidence of a vote account signing two different
+    /// votes for the same slot. Only the first proof observed for a given
+    /// (slot, vote account) pair is kept.
+    ///
+    /// * index type: `(`[`Slot`]`, `[`Pubkey`]`)`
+    /// * value type: [`blockstore_meta::DuplicateVoteProof`]
+    pub struct DuplicateVotes;
+
+    #[derive(Debug)]
+    /// The block fee summary column
+    ///
+    /// This column stores the transaction fees collected by the bank of each
+    /// slot and how they were split between the leader and the burn, so they
+    /// can be served after the accounts state has moved on.
+    ///
+    /// * index type: `u64` (see [`SlotColumn`])
+    /// * value type: [`blockstore_meta::BlockFeeSummary`]
+    pub struct BlockFeeSummary;
+
+    #[derive(Debug)]
+    /// The archived accounts column
+    ///
+    /// This column stores the version of each account written by the bank of
+    /// a slot, so the state of an account can be served as of a past slot. It
+    /// is only written when archival accounts are enabled, as it grows with
+    /// every account modified by every block.
+    ///
+    /// * index type: `(`[`Pubkey`]`, `[`Slot`]`)`
+    /// * value type: [`solana_sdk::account::Account`]
+    pub struct ArchivedAccounts;
+
+    #[derive(Debug)]
+    /// The archived account slots column
+    ///
+    /// This column indexes the archived accounts column by slot, so that the
+    /// accounts archived by the slots being purged are found without scanning
+    /// the archived accounts column, which is keyed by pubkey first.
+    ///
+    /// * index type: `(`[`Slot`]`, `[`Pubkey`]`)`
+    /// * value type: `bool`
+    pub struct ArchivedAccountSlots;
+}
+
+/// Number of slots of a shard of the address signature shards column, about a day of slots
+pub const ADDRESS_SIGNATURE_SHARD_SLOTS: Slot = 216_000;
+
+/// Returns the shard of the address signature shards column holding the entries of `slot`
+pub fn address_signature_shard(slot: Slot) -> u64 {
+    slot / ADDRESS_SIGNATURE_SHARD_SLOTS
+}
+
+/// Returns the shards of the address signature shards column whose slots all lie in
+/// \[`from_slot`, `to_slot`\]
+pub fn address_signature_shards_within(from_slot: Slot, to_slot: Slot) -> Range<u64> {
+    // The first shard starting at or after `from_slot`, and the first one ending after `to_slot`
+    let from_shard =
+        address_signature_shard(from_slot.saturating_add(ADDRESS_SIGNATURE_SHARD_SLOTS - 1));
+    let to_shard = address_signature_shard(to_slot.saturating_add(1));
+    from_shard..to_shard.max(from_shard)
 }
 
 macro_rules! convert_column_index_to_key_bytes {
@@ -313,6 +403,10 @@ impl TypedColumn for columns::AddressSignatures {
     type Type = blockstore_meta::AddressSignatureMeta;
 }
 
+impl TypedColumn for columns::AddressSignatureShards {
+    type Type = blockstore_meta::AddressSignatureMeta;
+}
+
 impl TypedColumn for columns::TransactionMemos {
     type Type = String;
 }
@@ -499,6 +593,102 @@ impl ColumnIndexDeprecation for columns::AddressSignatures {
     }
 }
 
+impl Column for columns::AddressSignatureShards {
+    type Index = (
+        /*shard:*/ u64,
+        Pubkey,
+        Slot,
+        /*transaction index:*/ u32,
+        Signature,
+    );
+    type Key = [u8; std::mem::size_of::<u64>()
+        + PUBKEY_BYTES
+        + std::mem::size_of::<Slot>()
+        + std::mem::size_of::<u32>()
+        + SIGNATURE_BYTES];
+
+    #[inline]
+    fn key((shard, pubkey, slot, transaction_index, signature): &Self::Index) -> Self::Key {
+        convert_column_index_to_key_bytes!(Key,
+              ..8  => &shard.to_be_bytes(),
+             8..40 => pubkey.as_ref(),
+            40..48 => &slot.to_be_bytes(),
+            48..52 => &transaction_index.to_be_bytes(),
+            52..   => signature.as_ref(),
+        )
+    }
+
+    fn index(key: &[u8]) -> Self::Index {
+        convert_column_key_bytes_to_index!(key,
+             0..8   => u64::from_be_bytes,  // shard
+             8..40  => Pubkey::from,
+            40..48  => Slot::from_be_bytes,
+            48..52  => u32::from_be_bytes,  // transaction index
+            52..116 => Signature::from,
+        )
+    }
+
+    fn slot(index: Self::Index) -> Slot {
+        index.2
+    }
+
+    // Returns the first key of the shard of `slot`, so that a range between
+    // the indexes of two slots covers whole shards
+    fn as_index(slot: Slot) -> Self::Index {
+        (
+            address_signature_shard(slot),
+            Pubkey::default(),
+            0,
+            0,
+            Signature::default(),
+        )
+    }
+}
+impl ColumnName for columns::AddressSignatureShards {
+    const NAME: &'static str = "address_signature_shards";
+}
+
+impl Column for columns::AddressSignatureShardMarkers {
+    type Index = (Pubkey, /*shard:*/ u64);
+    type Key = [u8; PUBKEY_BYTES + std::mem::size_of::<u64>()];
+
+    #[inline]
+    fn key((pubkey, shard): &Self::Index) -> Self::Key {
+        convert_column_index_to_key_bytes!(Key,
+            ..32 => pubkey.as_ref(),
+            32.. => &shard.to_be_bytes(),
+        )
+    }
+
+    fn index(key: &[u8]) -> Self::Index {
+        convert_column_key_bytes_to_index!(key,
+             0..32 => Pubkey::from,
+            32..40 => u64::from_be_bytes, // shard
+        )
+    }
+
+    // Returns the last slot of the shard, so that the compaction filter only
+    // removes the markers of shards whose slots are all purged
+    fn slot((_pubkey, shard): Self::Index) -> Slot {
+        shard
+            .saturating_add(1)
+            .saturating_mul(ADDRESS_SIGNATURE_SHARD_SLOTS)
+            .saturating_sub(1)
+    }
+
+    // The AddressSignatureShardMarkers column is not keyed by slot so this method is meaningless
+    // See Column::as_index() declaration for more details
+    fn as_index(_index: u64) -> Self::Index {
+        (Pubkey::default(), 0)
+    }
+}
+impl ColumnName for columns::AddressSignatureShardMarkers {
+    const NAME: &'static str = "address_signature_shard_markers";
+}
+impl TypedColumn for columns::AddressSignatureShardMarkers {
+    type Type = bool;
+}
+
 impl Column for columns::TransactionMemos {
     type Index = (Signature, Slot);
     type Key = [u8; SIGNATURE_BYTES + std::mem::size_of::<Slot>()];
@@ -848,3 +1038,124 @@ impl ColumnName for columns::MerkleRootMeta {
 impl TypedColumn for columns::MerkleRootMeta {
     type Type = blockstore_meta::MerkleRootMeta;
 }
+
+impl Column for columns::DuplicateVotes {
+    type Index = (Slot, /*vote account:*/ Pubkey);
+    type Key = [u8; std::mem::size_of::<Slot>() + PUBKEY_BYTES];
+
+    #[inline]
+    fn key((slot, vote_account): &Self::Index) -> Self::Key {
+        convert_column_index_to_key_bytes!(Key,
+            ..8 => &slot.to_be_bytes(),
+            8.. => vote_account.as_ref(),
+        )
+    }
+
+    fn index(key: &[u8]) -> Self::Index {
+        convert_column_key_bytes_to_index!(key,
+            0..8  => Slot::from_be_bytes,
+            8..40 => Pubkey::from,  // vote account
+        )
+    }
+
+    fn slot((slot, _vote_account): Self::Index) -> Slot {
+        slot
+    }
+
+    fn as_index(slot: Slot) -> Self::Index {
+        (slot, Pubkey::default())
+    }
+}
+impl ColumnName for columns::DuplicateVotes {
+    const NAME: &'static str = "duplicate_votes";
+}
+impl TypedColumn for columns::DuplicateVotes {
+    type Type = blockstore_meta::DuplicateVoteProof;
+}
+
+impl SlotColumn for columns::BlockFeeSummary {}
+impl ColumnName for columns::BlockFeeSummary {
+    const NAME: &'static str = "block_fee_summary";
+}
+impl TypedColumn for columns::BlockFeeSummary {
+    type Type = blockstore_meta::BlockFeeSummary;
+
+    fn deserialize(data: &[u8]) -> Result<Self::Type> {
+        // Summaries recorded before the fee treasury split lack the treasury field
+        bincode::deserialize(data)
+            .or_else(|_| {
+                bincode::deserialize::<blockstore_meta::BlockFeeSummaryV1>(data).map(Into::into)
+            })
+            .map_err(Into::into)
+    }
+}
+
+impl Column for columns::ArchivedAccounts {
+    type Index = (Pubkey, Slot);
+    type Key = [u8; PUBKEY_BYTES + std::mem::size_of::<Slot>()];
+
+    #[inline]
+    fn key((pubkey, slot): &Self::Index) -> Self::Key {
+        convert_column_index_to_key_bytes!(Key,
+            ..32 => pubkey.as_ref(),
+            32.. => &slot.to_be_bytes(),
+        )
+    }
+
+    fn index(key: &[u8]) -> Self::Index {
+        convert_column_key_bytes_to_index!(key,
+             0..32 => Pubkey::from,
+            32..40 => Slot::from_be_bytes,
+        )
+    }
+
+    fn slot((_pubkey, slot): Self::Index) -> Slot {
+        slot
+    }
+
+    // The ArchivedAccounts column is not keyed by slot so this method is meaningless
+    // See Column::as_index() declaration for more details
+    fn as_index(_index: u64) -> Self::Index {
+        (Pubkey::default(), 0)
+    }
+}
+impl ColumnName for columns::ArchivedAccounts {
+    const NAME: &'static str = "archived_accounts";
+}
+impl TypedColumn for columns::ArchivedAccounts {
+    type Type = solana_sdk::account::Account;
+}
+
+impl Column for columns::ArchivedAccountSlots {
+    type Index = (Slot, Pubkey);
+    type Key = [u8; std::mem::size_of::<Slot>() + PUBKEY_BYTES];
+
+    #[inline]
+    fn key((slot, pubkey): &Self::Index) -> Self::Key {
+        convert_column_index_to_key_bytes!(Key,
+            ..8 => &slot.to_be_bytes(),
+            8.. => pubkey.as_ref(),
+        )
+    }
+
+    fn index(key: &[u8]) -> Self::Index {
+        convert_column_key_bytes_to_index!(key,
+            0..8  => Slot::from_be_bytes,
+            8..40 => Pubkey::from,
+        )
+    }
+
+    fn slot((slot, _pubkey): Self::Index) -> Slot {
+        slot
+    }
+
+    fn as_index(slot: Slot) -> Self::Index {
+        (slot, Pubkey::default())
+    }
+}
+impl ColumnName for columns::ArchivedAccountSlots {
+    const NAME: &'static str = "archived_account_slots";
+}
+impl TypedColumn for columns::ArchivedAccountSlots {
+    type Type = bool;
+}
@@ -188,6 +188,12 @@ impl Rocks {
             new_cf_descriptor::<columns::ProgramCosts>(options, oldest_slot),
             new_cf_descriptor::<columns::OptimisticSlots>(options, oldest_slot),
             new_cf_descriptor::<columns::MerkleRootMeta>(options, oldest_slot),
+            new_cf_descriptor::<columns::DuplicateVotes>(options, oldest_slot),
+            new_cf_descriptor::<columns::BlockFeeSummary>(options, oldest_slot),
+            new_cf_descriptor::<columns::ArchivedAccounts>(options, oldest_slot),
+            new_cf_descriptor::<columns::ArchivedAccountSlots>(options, oldest_slot),
+            new_cf_descriptor::<columns::AddressSignatureShards>(options, oldest_slot),
+            new_cf_descriptor::<columns::AddressSignatureShardMarkers>(options, oldest_slot),
         ];
 
         // If the access type is Secondary, we don't need to open all of the
@@ -236,7 +242,7 @@ impl Rocks {
         cf_descriptors
     }
 
-    const fn columns() -> [&'static str; 21] {
+    const fn columns() -> [&'static str; 27] {
         [
             columns::ErasureMeta::NAME,
             columns::DeadSlots::NAME,
@@ -259,6 +265,12 @@ impl Rocks {
             columns::ProgramCosts::NAME,
             columns::OptimisticSlots::NAME,
             columns::MerkleRootMeta::NAME,
+            columns::DuplicateVotes::NAME,
+            columns::BlockFeeSummary::NAME,
+            columns::ArchivedAccounts::NAME,
+            columns::ArchivedAccountSlots::NAME,
+            columns::AddressSignatureShards::NAME,
+            columns::AddressSignatureShardMarkers::NAME,
         ]
     }
 
@@ -1220,11 +1232,21 @@ fn should_enable_cf_compaction(cf_name: &str) -> bool {
     // configured to run as part of rocksdb's automatic compactions. Storage
     // space is reclaimed on this class of columns once compaction has
     // completed on a given range or file.
+    //
+    // The AddressSignatureShards column is prefixed by the shard of the slot
+    // instead, so it is cleaned by range deleting whole shards, sparing its
+    // keys from being rewritten by the compaction filter. Its markers, one
+    // per address and shard, are few enough to be filtered.
+    //
+    // The ArchivedAccounts column is keyed by pubkey first, so it is cleaned
+    // by the compaction filter too, while its slot index is range deleted.
     matches!(
         cf_name,
         columns::TransactionStatus::NAME
             | columns::TransactionMemos::NAME
             | columns::AddressSignatures::NAME
+            | columns::AddressSignatureShardMarkers::NAME
+            | columns::ArchivedAccounts::NAME
     )
 }
 
@@ -1316,10 +1338,18 @@ pub mod tests {
         let columns_to_compact = [
             columns::TransactionStatus::NAME,
             columns::AddressSignatures::NAME,
+            columns::AddressSignatureShardMarkers::NAME,
+            columns::ArchivedAccounts::NAME,
         ];
         columns_to_compact.iter().for_each(|cf_name| {
             assert!(should_enable_cf_compaction(cf_name));
         });
+        assert!(!should_enable_cf_compaction(
+            columns::AddressSignatureShards::NAME
+        ));
+        assert!(!should_enable_cf_compaction(
+            columns::ArchivedAccountSlots::NAME
+        ));
         assert!(!should_enable_cf_compaction("something else"));
     }
 
@@ -8,6 +8,7 @@ use {
     solana_sdk::{
         clock::{Slot, UnixTimestamp},
         hash::Hash,
+        pubkey::Pubkey,
     },
     std::{
         collections::BTreeSet,
@@ -230,6 +231,64 @@ pub struct DuplicateSlotProof {
     pub shred2: shred::Payload,
 }
 
+/// Two signed vote transactions from the same vote account that vote for the
+/// same slot with different bank hashes.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DuplicateVoteProof {
+    /// Bank hash voted for by `transaction1`
+    pub hash1: Hash,
+    /// Bincode serialized vote transaction
+    pub transaction1: Vec<u8>,
+    /// Bank hash voted for by `transaction2`
+    pub hash2: Hash,
+    /// Bincode serialized vote transaction
+    pub transaction2: Vec<u8>,
+}
+
+/// Transaction fees collected by the bank of a slot, recorded when the bank is
+/// frozen.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BlockFeeSummary {
+    /// Leader of the slot, which is credited with `leader_reward`
+    pub leader: Pubkey,
+    pub transaction_fee: u64,
+    pub priority_fee: u64,
+    pub burned: u64,
+    pub leader_reward: u64,
+    /// Base fees deposited into the fee treasury instead of being burned
+    pub treasury: u64,
+}
+
+/// Fee summaries recorded before the fee treasury split
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BlockFeeSummaryV1 {
+    pub leader: Pubkey,
+    pub transaction_fee: u64,
+    pub priority_fee: u64,
+    pub burned: u64,
+    pub leader_reward: u64,
+}
+
+impl From<BlockFeeSummaryV1> for BlockFeeSummary {
+    fn from(fee_summary: BlockFeeSummaryV1) -> Self {
+        let BlockFeeSummaryV1 {
+            leader,
+            transaction_fee,
+            priority_fee,
+            burned,
+            leader_reward,
+        } = fee_summary;
+        Self {
+            leader,
+            transaction_fee,
+            priority_fee,
+            burned,
+            leader_reward,
+            treasury: 0,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
 pub enum FrozenHashVersioned {
     Current(FrozenHashStatus),
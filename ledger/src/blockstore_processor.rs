@@ -3517,6 +3517,9 @@ pub mod tests {
             genesis_config
                 .accounts
                 .remove(&agave_feature_set::disable_partitioned_rent_collection::id());
+            genesis_config
+                .accounts
+                .remove(&agave_feature_set::disable_rent_scanning::id());
         }
 
         fn get_instruction_errors() -> Vec<InstructionError> {
@@ -29,7 +29,9 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         known_validators: config.known_validators.clone(),
         repair_validators: config.repair_validators.clone(),
         repair_whitelist: config.repair_whitelist.clone(),
+        repair_over_quic: config.repair_over_quic,
         gossip_validators: config.gossip_validators.clone(),
+        peer_filter: config.peer_filter.clone(),
         accounts_hash_interval_slots: config.accounts_hash_interval_slots,
         max_genesis_archive_unpacked_size: config.max_genesis_archive_unpacked_size,
         run_verification: config.run_verification,
@@ -39,11 +41,13 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         contact_debug_interval: config.contact_debug_interval,
         contact_save_interval: config.contact_save_interval,
         send_transaction_service_config: config.send_transaction_service_config.clone(),
+        rpc_send_transaction_staked_identity: config.rpc_send_transaction_staked_identity.clone(),
         no_poh_speed_test: config.no_poh_speed_test,
         no_os_memory_stats_reporting: config.no_os_memory_stats_reporting,
         no_os_network_stats_reporting: config.no_os_network_stats_reporting,
         no_os_cpu_stats_reporting: config.no_os_cpu_stats_reporting,
         no_os_disk_stats_reporting: config.no_os_disk_stats_reporting,
+        prometheus_bind_address: config.prometheus_bind_address,
         poh_pinned_cpu_core: config.poh_pinned_cpu_core,
         warp_slot: config.warp_slot,
         accounts_db_test_hash_calculation: config.accounts_db_test_hash_calculation,
@@ -59,6 +63,7 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         wait_to_vote_slot: config.wait_to_vote_slot,
         runtime_config: config.runtime_config.clone(),
         banking_trace_dir_byte_limit: config.banking_trace_dir_byte_limit,
+        banking_trace_capture: config.banking_trace_capture,
         block_verification_method: config.block_verification_method.clone(),
         block_production_method: config.block_production_method.clone(),
         transaction_struct: config.transaction_struct.clone(),
@@ -67,6 +72,10 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         use_snapshot_archives_at_startup: config.use_snapshot_archives_at_startup,
         wen_restart_proto_path: config.wen_restart_proto_path.clone(),
         wen_restart_coordinator: config.wen_restart_coordinator,
+        wen_restart_repair_threshold_percent: config.wen_restart_repair_threshold_percent,
+        wen_restart_heaviest_fork_threshold_delta_percent: config
+            .wen_restart_heaviest_fork_threshold_delta_percent,
+        wen_restart_last_voted_fork_slots_timeout: config.wen_restart_last_voted_fork_slots_timeout,
         unified_scheduler_handler_threads: config.unified_scheduler_handler_threads,
         ip_echo_server_threads: config.ip_echo_server_threads,
         rayon_global_threads: config.rayon_global_threads,
@@ -74,6 +83,11 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         replay_transactions_threads: config.replay_transactions_threads,
         tvu_shred_sigverify_threads: config.tvu_shred_sigverify_threads,
         delay_leader_block_for_pending_fork: config.delay_leader_block_for_pending_fork,
+        program_cache_warmup_programs: config.program_cache_warmup_programs.clone(),
+        program_cache_warmup_persist_count: config.program_cache_warmup_persist_count,
+        bank_freeze_hooks: config.bank_freeze_hooks.clone(),
+        identity_top_up: config.identity_top_up.clone(),
+        enable_cpi_stats: config.enable_cpi_stats,
     }
 }
 
@@ -1,7 +1,7 @@
 //! The `metrics` module enables sending measurements to an `InfluxDB` instance
 
 use {
-    crate::{counter::CounterPoint, datapoint::DataPoint},
+    crate::{counter::CounterPoint, datapoint::DataPoint, prometheus},
     crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender},
     gethostname::gethostname,
     lazy_static::lazy_static,
@@ -416,6 +416,7 @@ pub fn set_host_id(host_id: String) {
 /// Submits a new point from any thread.  Note that points are internally queued
 /// and transmitted periodically in batches.
 pub fn submit(point: DataPoint, level: log::Level) {
+    prometheus::record_datapoint(&point);
     let agent = get_singleton_agent();
     agent.submit(point, level);
 }
@@ -423,6 +424,7 @@ pub fn submit(point: DataPoint, level: log::Level) {
 /// Submits a new counter or updates an existing counter from any thread.  Note that points are
 /// internally queued and transmitted periodically in batches.
 pub(crate) fn submit_counter(point: CounterPoint, level: log::Level, bucket: u64) {
+    prometheus::record_counter(&point);
     let agent = get_singleton_agent();
     agent.submit_counter(point, level, bucket);
 }
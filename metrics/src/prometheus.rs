@@ -0,0 +1,470 @@
+//! Prometheus exposition of the metrics of the process.
+//!
+//! Once [`enable`]d, every datapoint and counter submitted to the metrics agent is also recorded
+//! in a process-wide registry: the numeric and boolean fields of a datapoint become gauges named
+//! `tachyon_<datapoint>_<field>` labelled with the tags of the datapoint, and counters become
+//! Prometheus counters named `tachyon_<counter>_total`. Components may also maintain gauges,
+//! counters and histograms of their own with [`gauge_set`], [`counter_add`] and
+//! [`histogram_observe`].
+//!
+//! The registry is rendered in the Prometheus text format by [`render`] and served over HTTP at
+//! `/metrics` by the [`PrometheusService`].
+
+use {
+    crate::{counter::CounterPoint, datapoint::DataPoint},
+    lazy_static::lazy_static,
+    log::*,
+    std::{
+        collections::BTreeMap,
+        fmt::Write as _,
+        io::{self, BufRead, BufReader, Write},
+        net::{SocketAddr, TcpListener, TcpStream},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        thread::{self, Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+pub const METRICS_PATH: &str = "/metrics";
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+const METRIC_PREFIX: &str = "tachyon";
+/// Caps the number of label sets of a metric, so that a datapoint tagged with unbounded values
+/// cannot grow the registry without limit
+const MAX_SERIES_PER_METRIC: usize = 1_000;
+/// Upper bounds of the buckets of histograms, covering durations in microseconds as well as
+/// sizes and counts
+const HISTOGRAM_BUCKETS: &[f64] = &[
+    1.0,
+    5.0,
+    10.0,
+    50.0,
+    100.0,
+    500.0,
+    1_000.0,
+    5_000.0,
+    10_000.0,
+    50_000.0,
+    100_000.0,
+    500_000.0,
+    1_000_000.0,
+    5_000_000.0,
+    10_000_000.0,
+];
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref REGISTRY: Mutex<Registry> = Mutex::default();
+}
+
+type Labels = Vec<(String, String)>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl Kind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+    /// Number of observations in each bucket of `HISTOGRAM_BUCKETS`, not cumulative
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+#[derive(Debug)]
+enum Series {
+    Value(f64),
+    Histogram(Histogram),
+}
+
+#[derive(Debug)]
+struct Metric {
+    kind: Kind,
+    series: BTreeMap<Labels, Series>,
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    metrics: BTreeMap<String, Metric>,
+}
+
+impl Registry {
+    /// Returns the series of `name` with `labels`, or None if the metric is of another kind or
+    /// has too many series already.
+    fn series(&mut self, name: String, kind: Kind, labels: Labels) -> Option<&mut Series> {
+        let metric = self.metrics.entry(name).or_insert_with(|| Metric {
+            kind,
+            series: BTreeMap::new(),
+        });
+        if metric.kind != kind
+            || (metric.series.len() >= MAX_SERIES_PER_METRIC
+                && !metric.series.contains_key(&labels))
+        {
+            return None;
+        }
+        Some(metric.series.entry(labels).or_insert_with(|| match kind {
+            Kind::Counter | Kind::Gauge => Series::Value(0.0),
+            Kind::Histogram => Series::Histogram(Histogram {
+                buckets: vec![0; HISTOGRAM_BUCKETS.len()],
+                ..Histogram::default()
+            }),
+        }))
+    }
+
+    fn set(&mut self, name: String, labels: Labels, value: f64) {
+        if let Some(Series::Value(current)) = self.series(name, Kind::Gauge, labels) {
+            *current = value;
+        }
+    }
+
+    fn add(&mut self, name: String, labels: Labels, value: f64) {
+        if let Some(Series::Value(current)) = self.series(name, Kind::Counter, labels) {
+            *current += value;
+        }
+    }
+
+    fn observe(&mut self, name: String, labels: Labels, value: f64) {
+        if let Some(Series::Histogram(histogram)) = self.series(name, Kind::Histogram, labels) {
+            if let Some(bucket) = HISTOGRAM_BUCKETS.iter().position(|bound| value <= *bound) {
+                histogram.buckets[bucket] += 1;
+            }
+            histogram.sum += value;
+            histogram.count += 1;
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, metric) in &self.metrics {
+            let _ = writeln!(out, "# TYPE {name} {}", metric.kind.as_str());
+            for (labels, series) in &metric.series {
+                match series {
+                    Series::Value(value) => {
+                        let _ = writeln!(out, "{name}{} {value}", format_labels(labels, None));
+                    }
+                    Series::Histogram(histogram) => {
+                        let mut cumulative = 0;
+                        for (bound, count) in HISTOGRAM_BUCKETS.iter().zip(&histogram.buckets) {
+                            cumulative += count;
+                            let _ = writeln!(
+                                out,
+                                "{name}_bucket{} {cumulative}",
+                                format_labels(labels, Some(&bound.to_string()))
+                            );
+                        }
+                        let _ = writeln!(
+                            out,
+                            "{name}_bucket{} {}",
+                            format_labels(labels, Some("+Inf")),
+                            histogram.count
+                        );
+                        let labels = format_labels(labels, None);
+                        let _ = writeln!(out, "{name}_sum{labels} {}", histogram.sum);
+                        let _ = writeln!(out, "{name}_count{labels} {}", histogram.count);
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Turns `parts` into a metric or label name, replacing the characters Prometheus does not allow.
+fn sanitize_name(parts: &[&str]) -> String {
+    let mut name = String::new();
+    for part in parts.iter().filter(|part| !part.is_empty()) {
+        if !name.is_empty() {
+            name.push('_');
+        }
+        name.extend(part.chars().map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        }));
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_labels(labels: &Labels, le: Option<&str>) -> String {
+    let mut pairs: Vec<_> = labels
+        .iter()
+        .map(|(name, value)| format!("{name}=\"{}\"", escape_label_value(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{le}\""));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+fn to_labels(labels: &[(&str, &str)]) -> Labels {
+    let mut labels: Labels = labels
+        .iter()
+        .map(|(name, value)| (sanitize_name(&[name]), value.to_string()))
+        .collect();
+    labels.sort();
+    labels
+}
+
+/// Parses a field of a datapoint as formatted for InfluxDB, strings are not numeric.
+fn parse_field_value(value: &str) -> Option<f64> {
+    if let Some(value) = value.strip_suffix('i') {
+        return value.parse::<i64>().ok().map(|value| value as f64);
+    }
+    match value {
+        "true" => Some(1.0),
+        "false" => Some(0.0),
+        _ => value.parse::<f64>().ok().filter(|value| value.is_finite()),
+    }
+}
+
+/// Starts recording the submitted metrics for exposition.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Sets the gauge `tachyon_<name>` to `value`.
+pub fn gauge_set(name: &str, labels: &[(&str, &str)], value: f64) {
+    if is_enabled() {
+        let name = sanitize_name(&[METRIC_PREFIX, name]);
+        REGISTRY.lock().unwrap().set(name, to_labels(labels), value);
+    }
+}
+
+/// Adds `value` to the counter `tachyon_<name>_total`.
+pub fn counter_add(name: &str, labels: &[(&str, &str)], value: u64) {
+    if is_enabled() {
+        let name = sanitize_name(&[METRIC_PREFIX, name, "total"]);
+        REGISTRY
+            .lock()
+            .unwrap()
+            .add(name, to_labels(labels), value as f64);
+    }
+}
+
+/// Records `value` in the histogram `tachyon_<name>`.
+pub fn histogram_observe(name: &str, labels: &[(&str, &str)], value: f64) {
+    if is_enabled() {
+        let name = sanitize_name(&[METRIC_PREFIX, name]);
+        REGISTRY
+            .lock()
+            .unwrap()
+            .observe(name, to_labels(labels), value);
+    }
+}
+
+pub(crate) fn record_datapoint(point: &DataPoint) {
+    if !is_enabled() {
+        return;
+    }
+    let labels: Vec<_> = point
+        .tags
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+    let labels = to_labels(&labels);
+    let mut registry = REGISTRY.lock().unwrap();
+    for (field, value) in &point.fields {
+        if let Some(value) = parse_field_value(value) {
+            let name = sanitize_name(&[METRIC_PREFIX, point.name, field]);
+            registry.set(name, labels.clone(), value);
+        }
+    }
+}
+
+pub(crate) fn record_counter(point: &CounterPoint) {
+    if is_enabled() {
+        counter_add(point.name, &[], point.count.max(0) as u64);
+    }
+}
+
+/// Renders the registry in the Prometheus text exposition format.
+pub fn render() -> String {
+    REGISTRY.lock().unwrap().render()
+}
+
+/// Serves the registry at `/metrics` over HTTP.
+pub struct PrometheusService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl PrometheusService {
+    pub fn new(bind_address: SocketAddr, exit: Arc<AtomicBool>) -> io::Result<Self> {
+        let listener = TcpListener::bind(bind_address)?;
+        listener.set_nonblocking(true)?;
+        enable();
+        info!("Prometheus metrics exposed at http://{bind_address}{METRICS_PATH}");
+        let thread_hdl = Builder::new()
+            .name("solPrometheus".to_string())
+            .spawn(move || {
+                while !exit.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            if let Err(err) = Self::serve(stream) {
+                                debug!("failed to serve metrics request: {err}");
+                            }
+                        }
+                        Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                            thread::sleep(ACCEPT_POLL_INTERVAL);
+                        }
+                        Err(err) => warn!("failed to accept metrics connection: {err}"),
+                    }
+                }
+            })?;
+        Ok(Self { thread_hdl })
+    }
+
+    fn serve(mut stream: TcpStream) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+        stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+        let mut request_line = String::new();
+        BufReader::new(&stream).read_line(&mut request_line)?;
+        let mut parts = request_line.split_whitespace();
+        let (status, body) = match (parts.next(), parts.next()) {
+            (Some("GET"), Some(METRICS_PATH)) => ("200 OK", render()),
+            _ => ("404 Not Found", String::new()),
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{body}",
+            body.len()
+        )?;
+        stream.flush()
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::io::Read};
+
+    #[test]
+    fn test_parse_field_value() {
+        assert_eq!(parse_field_value("42i"), Some(42.0));
+        assert_eq!(parse_field_value("-3i"), Some(-3.0));
+        assert_eq!(parse_field_value("1.5"), Some(1.5));
+        assert_eq!(parse_field_value("true"), Some(1.0));
+        assert_eq!(parse_field_value("false"), Some(0.0));
+        assert_eq!(parse_field_value("\"text\""), None);
+        assert_eq!(parse_field_value("NaN"), None);
+    }
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(
+            sanitize_name(&[METRIC_PREFIX, "banking_stage-loop-stats", "count"]),
+            "tachyon_banking_stage_loop_stats_count"
+        );
+        assert_eq!(sanitize_name(&["1st", ""]), "_1st");
+    }
+
+    #[test]
+    fn test_render() {
+        let mut registry = Registry::default();
+        let labels = vec![("id".to_string(), "a\"b".to_string())];
+        registry.set("tachyon_queue".to_string(), labels.clone(), 3.0);
+        registry.set("tachyon_queue".to_string(), labels.clone(), 5.0);
+        registry.add("tachyon_fees_total".to_string(), vec![], 2.0);
+        registry.add("tachyon_fees_total".to_string(), vec![], 3.0);
+        // A metric keeps the kind it was created with
+        registry.set("tachyon_fees_total".to_string(), vec![], 1.0);
+        registry.observe("tachyon_latency".to_string(), vec![], 3.0);
+        registry.observe("tachyon_latency".to_string(), vec![], 20_000_000.0);
+
+        let rendered = registry.render();
+        assert!(rendered.contains("# TYPE tachyon_queue gauge\ntachyon_queue{id=\"a\\\"b\"} 5\n"));
+        assert!(rendered.contains("# TYPE tachyon_fees_total counter\ntachyon_fees_total 5\n"));
+        assert!(rendered.contains("tachyon_latency_bucket{le=\"1\"} 0\n"));
+        assert!(rendered.contains("tachyon_latency_bucket{le=\"5\"} 1\n"));
+        assert!(rendered.contains("tachyon_latency_bucket{le=\"10000000\"} 1\n"));
+        assert!(rendered.contains("tachyon_latency_bucket{le=\"+Inf\"} 2\n"));
+        assert!(rendered.contains("tachyon_latency_count 2\n"));
+    }
+
+    #[test]
+    fn test_max_series_per_metric() {
+        let mut registry = Registry::default();
+        for i in 0..=MAX_SERIES_PER_METRIC {
+            let labels = vec![("slot".to_string(), i.to_string())];
+            registry.set("tachyon_slot".to_string(), labels, 1.0);
+        }
+        assert_eq!(
+            registry.metrics["tachyon_slot"].series.len(),
+            MAX_SERIES_PER_METRIC
+        );
+    }
+
+    #[test]
+    fn test_service() {
+        let exit = Arc::new(AtomicBool::new(false));
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+        let service = PrometheusService::new(address, exit.clone()).unwrap();
+
+        let mut point = DataPoint::new("test-service");
+        point.add_tag("kind", "a");
+        point.add_field_i64("queued", 7);
+        point.add_field_str("name", "ignored");
+        record_datapoint(&point);
+
+        let get = |path: &str| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(stream, "GET {path} HTTP/1.1\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let response = get(METRICS_PATH);
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("tachyon_test_service_queued{kind=\"a\"} 7\n"));
+        assert!(!response.contains("tachyon_test_service_name"));
+        assert!(get("/other").starts_with("HTTP/1.1 404 Not Found"));
+
+        exit.store(true, Ordering::Relaxed);
+        service.join().unwrap();
+    }
+}
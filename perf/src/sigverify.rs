@@ -477,8 +477,18 @@ pub fn shrink_batches(batches: &mut Vec<PacketBatch>) {
 }
 
 pub fn ed25519_verify_cpu(batches: &mut [PacketBatch], reject_non_vote: bool, packet_count: usize) {
+    ed25519_verify_cpu_in_pool(&PAR_THREAD_POOL, batches, reject_non_vote, packet_count)
+}
+
+/// Like `ed25519_verify_cpu`, but verifies on `thread_pool` instead of the shared sigverify pool
+pub fn ed25519_verify_cpu_in_pool(
+    thread_pool: &ThreadPool,
+    batches: &mut [PacketBatch],
+    reject_non_vote: bool,
+    packet_count: usize,
+) {
     debug!("CPU ECDSA for {}", packet_count);
-    PAR_THREAD_POOL.install(|| {
+    thread_pool.install(|| {
         batches.par_iter_mut().flatten().for_each(|packet| {
             if !packet.meta().discard() && !verify_packet(packet, reject_non_vote) {
                 packet.meta_mut().set_discard(true);
@@ -556,9 +566,34 @@ pub fn ed25519_verify(
     recycler_out: &Recycler<PinnedVec<u8>>,
     reject_non_vote: bool,
     valid_packet_count: usize,
+) {
+    ed25519_verify_in_pool(
+        &PAR_THREAD_POOL,
+        batches,
+        recycler,
+        recycler_out,
+        reject_non_vote,
+        valid_packet_count,
+    )
+}
+
+/// Like `ed25519_verify`, but any CPU verification runs on `thread_pool` instead of the shared
+/// sigverify pool
+pub fn ed25519_verify_in_pool(
+    thread_pool: &ThreadPool,
+    batches: &mut [PacketBatch],
+    recycler: &Recycler<TxOffset>,
+    recycler_out: &Recycler<PinnedVec<u8>>,
+    reject_non_vote: bool,
+    valid_packet_count: usize,
 ) {
     let Some(api) = perf_libs::api() else {
-        return ed25519_verify_cpu(batches, reject_non_vote, valid_packet_count);
+        return ed25519_verify_cpu_in_pool(
+            thread_pool,
+            batches,
+            reject_non_vote,
+            valid_packet_count,
+        );
     };
     let total_packet_count = count_packets_in_batches(batches);
     // micro-benchmarks show GPU time for smallest batch around 15-20ms
@@ -573,7 +608,7 @@ pub fn ed25519_verify(
         return;
     };
     if valid_percentage < 90 || valid_packet_count < 64 {
-        ed25519_verify_cpu(batches, reject_non_vote, valid_packet_count);
+        ed25519_verify_cpu_in_pool(thread_pool, batches, reject_non_vote, valid_packet_count);
         return;
     }
 
@@ -1214,6 +1249,36 @@ mod tests {
         test_verify_n(5, true);
     }
 
+    #[test]
+    fn test_verify_in_pool() {
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let recycler = Recycler::default();
+        let recycler_out = Recycler::default();
+        for modify_data in [false, true] {
+            let mut packet = Packet::from_data(None, test_tx()).unwrap();
+            if modify_data {
+                packet.buffer_mut()[20] = packet.data(20).unwrap().wrapping_add(10);
+            }
+            let mut batches = generate_packet_batches(&packet, 5, 2);
+            let packet_count = sigverify::count_packets_in_batches(&batches);
+            sigverify::ed25519_verify_in_pool(
+                &thread_pool,
+                &mut batches,
+                &recycler,
+                &recycler_out,
+                false,
+                packet_count,
+            );
+            assert!(batches
+                .iter()
+                .flat_map(|batch| batch.iter())
+                .all(|p| p.meta().discard() == modify_data));
+        }
+    }
+
     #[test]
     fn test_get_checked_scalar() {
         solana_logger::setup();
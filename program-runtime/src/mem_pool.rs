@@ -1,7 +1,9 @@
 use {
     solana_compute_budget::{
         compute_budget::{MAX_CALL_DEPTH, MAX_INSTRUCTION_STACK_DEPTH, STACK_FRAME_SIZE},
-        compute_budget_limits::{MAX_HEAP_FRAME_BYTES, MIN_HEAP_FRAME_BYTES},
+        compute_budget_limits::{
+            MAX_HEAP_FRAME_BYTES, MIN_HEAP_FRAME_BYTES, X1_MAX_HEAP_FRAME_BYTES,
+        },
     },
     solana_sbpf::{aligned_memory::AlignedMemory, ebpf::HOST_ALIGN},
     std::array,
@@ -94,7 +96,12 @@ impl VmMemoryPool {
     }
 
     pub fn get_heap(&mut self, heap_size: u32) -> AlignedMemory<{ HOST_ALIGN }> {
-        debug_assert!((MIN_HEAP_FRAME_BYTES..=MAX_HEAP_FRAME_BYTES).contains(&heap_size));
+        debug_assert!((MIN_HEAP_FRAME_BYTES..=X1_MAX_HEAP_FRAME_BYTES).contains(&heap_size));
+        // The pool only holds heaps of MAX_HEAP_FRAME_BYTES, the rare larger heaps are
+        // allocated for each use rather than preallocated for every frame
+        if heap_size > MAX_HEAP_FRAME_BYTES {
+            return AlignedMemory::zero_filled(heap_size as usize);
+        }
         self.heap
             .get()
             .unwrap_or_else(|| AlignedMemory::zero_filled(MAX_HEAP_FRAME_BYTES as usize))
@@ -104,8 +111,11 @@ impl VmMemoryPool {
         let heap_size = heap.len();
         debug_assert!(
             heap_size >= MIN_HEAP_FRAME_BYTES as usize
-                && heap_size <= MAX_HEAP_FRAME_BYTES as usize
+                && heap_size <= X1_MAX_HEAP_FRAME_BYTES as usize
         );
+        if heap_size > MAX_HEAP_FRAME_BYTES as usize {
+            return false;
+        }
         self.heap.put(heap)
     }
 }
@@ -143,4 +153,16 @@ mod test {
         assert_eq!(pool.get(), Some(Item(2, 0)));
         assert_eq!(pool.get(), None);
     }
+
+    #[test]
+    fn test_large_heap_not_pooled() {
+        let mut pool = VmMemoryPool::new();
+        let heap = pool.get_heap(X1_MAX_HEAP_FRAME_BYTES);
+        assert_eq!(heap.len(), X1_MAX_HEAP_FRAME_BYTES as usize);
+        assert!(!pool.put_heap(heap));
+
+        let heap = pool.get_heap(MAX_HEAP_FRAME_BYTES);
+        assert_eq!(heap.len(), MAX_HEAP_FRAME_BYTES as usize);
+        assert!(pool.put_heap(heap));
+    }
 }
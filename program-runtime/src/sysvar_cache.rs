@@ -16,6 +16,7 @@ use {
     solana_sysvar_id::SysvarId,
     solana_transaction_context::{IndexOfAccount, InstructionContext, TransactionContext},
     solana_type_overrides::sync::Arc,
+    tachyon_fee_parameters::FeeParameters,
 };
 
 #[cfg(feature = "frozen-abi")]
@@ -36,6 +37,7 @@ pub struct SysvarCache {
     slot_hashes: Option<Vec<u8>>,
     stake_history: Option<Vec<u8>>,
     last_restart_slot: Option<Vec<u8>>,
+    fee_parameters: Option<Vec<u8>>,
 
     // object representations of large sysvars for convenience
     // these are used by the stake and vote builtin programs
@@ -104,6 +106,11 @@ impl SysvarCache {
         }
     }
 
+    /// Overwrite the fee parameters. For testing purposes only.
+    pub fn set_fee_parameters_for_tests(&mut self, fee_parameters: &FeeParameters) {
+        self.fee_parameters = Some(bincode::serialize(fee_parameters).unwrap());
+    }
+
     // this is exposed for SyscallGetSysvar and should not otherwise be used
     pub fn sysvar_id_to_buffer(&self, sysvar_id: &Pubkey) -> &Option<Vec<u8>> {
         if Clock::check_id(sysvar_id) {
@@ -120,6 +127,8 @@ impl SysvarCache {
             &self.stake_history
         } else if LastRestartSlot::check_id(sysvar_id) {
             &self.last_restart_slot
+        } else if tachyon_fee_parameters::check_id(sysvar_id) {
+            &self.fee_parameters
         } else {
             &None
         }
@@ -160,6 +169,10 @@ impl SysvarCache {
         self.get_sysvar_obj(&LastRestartSlot::id())
     }
 
+    pub fn get_fee_parameters(&self) -> Result<Arc<FeeParameters>, InstructionError> {
+        self.get_sysvar_obj(&tachyon_fee_parameters::id())
+    }
+
     pub fn get_stake_history(&self) -> Result<Arc<StakeHistory>, InstructionError> {
         self.stake_history_obj
             .clone()
@@ -252,6 +265,14 @@ impl SysvarCache {
             });
         }
 
+        if self.fee_parameters.is_none() {
+            get_account_data(&tachyon_fee_parameters::id(), &mut |data: &[u8]| {
+                if FeeParameters::from_account_data(data).is_some() {
+                    self.fee_parameters = Some(data.to_vec());
+                }
+            });
+        }
+
         #[allow(deprecated)]
         if self.fees.is_none() {
             get_account_data(&Fees::id(), &mut |data: &[u8]| {
@@ -12,6 +12,7 @@ use {
         system_program,
         transaction::{Transaction, TransactionError},
     },
+    tachyon_system_interface::storage_deposit::minimum_balance_with_storage_deposit,
 };
 
 mod common;
@@ -423,9 +424,12 @@ async fn test_extend_program_without_payer() {
     let recent_blockhash = context.last_blockhash;
 
     const ADDITIONAL_BYTES: u32 = 42;
-    let min_balance_increase_for_extend = rent
-        .minimum_balance(ADDITIONAL_BYTES as usize)
-        .saturating_sub(rent.minimum_balance(0));
+    // The extended account must also hold the storage deposit for all of its data
+    let min_balance_increase_for_extend = minimum_balance_with_storage_deposit(
+        &rent,
+        (program_data_len + ADDITIONAL_BYTES as usize) as u64,
+    )
+    .saturating_sub(rent.minimum_balance(program_data_len));
 
     let transaction = Transaction::new_signed_with_payer(
         &[
@@ -10,7 +10,7 @@ use {
     agave_feature_set::{
         bpf_account_data_direct_mapping, enable_bpf_loader_set_authority_checked_ix,
         enable_loader_v4, mask_out_rent_epoch_in_vm_serialization,
-        remove_accounts_executable_flag_checks,
+        remove_accounts_executable_flag_checks, storage_deposit,
     },
     solana_bincode::limited_deserialize,
     solana_clock::Slot,
@@ -51,6 +51,7 @@ use {
     solana_type_overrides::sync::{atomic::Ordering, Arc},
     std::{cell::RefCell, mem, rc::Rc},
     syscalls::morph_into_deployment_environment_v1,
+    tachyon_system_interface::storage_deposit::minimum_balance_with_storage_deposit,
 };
 
 #[cfg_attr(feature = "svm-internal", qualifiers(pub))]
@@ -1260,7 +1261,16 @@ fn process_loader_upgradeable_instruction(
             let required_payment = {
                 let balance = programdata_account.get_lamports();
                 let rent = invoke_context.get_sysvar_cache().get_rent()?;
-                let min_balance = rent.minimum_balance(new_len).max(1);
+                // The runtime requires resized accounts to hold their storage deposit
+                let min_balance = if invoke_context
+                    .get_feature_set()
+                    .is_active(&storage_deposit::id())
+                {
+                    minimum_balance_with_storage_deposit(&rent, new_len as u64)
+                } else {
+                    rent.minimum_balance(new_len)
+                }
+                .max(1);
                 min_balance.saturating_sub(balance)
             };
 
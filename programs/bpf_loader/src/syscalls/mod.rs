@@ -6,8 +6,8 @@ pub use self::{
     mem_ops::{SyscallMemcmp, SyscallMemcpy, SyscallMemmove, SyscallMemset},
     sysvar::{
         SyscallGetClockSysvar, SyscallGetEpochRewardsSysvar, SyscallGetEpochScheduleSysvar,
-        SyscallGetFeesSysvar, SyscallGetLastRestartSlotSysvar, SyscallGetRentSysvar,
-        SyscallGetSysvar,
+        SyscallGetFeeParametersSysvar, SyscallGetFeesSysvar, SyscallGetLastRestartSlotSysvar,
+        SyscallGetRentSysvar, SyscallGetSysvar,
     },
 };
 #[allow(deprecated)]
@@ -18,9 +18,9 @@ use {
         bpf_account_data_direct_mapping, curve25519_syscall_enabled,
         disable_deploy_of_alloc_free_syscall, disable_fees_sysvar, disable_sbpf_v0_execution,
         enable_alt_bn128_compression_syscall, enable_alt_bn128_syscall, enable_big_mod_exp_syscall,
-        enable_get_epoch_stake_syscall, enable_poseidon_syscall,
+        enable_bls12_381_syscall, enable_get_epoch_stake_syscall, enable_poseidon_syscall,
         enable_sbpf_v1_deployment_and_execution, enable_sbpf_v2_deployment_and_execution,
-        enable_sbpf_v3_deployment_and_execution, get_sysvar_syscall_enabled,
+        enable_sbpf_v3_deployment_and_execution, fee_parameters_sysvar, get_sysvar_syscall_enabled,
         last_restart_slot_sysvar, reenable_sbpf_v0_execution,
         remaining_compute_units_syscall_enabled, FeatureSet,
     },
@@ -67,6 +67,7 @@ use {
         slice::from_raw_parts_mut,
         str::{from_utf8, Utf8Error},
     },
+    tachyon_fee_parameters::FeeParameters,
     thiserror::Error as ThisError,
 };
 
@@ -351,6 +352,8 @@ pub fn create_program_runtime_environment_v1<'a>(
     let get_sysvar_syscall_enabled = feature_set.is_active(&get_sysvar_syscall_enabled::id());
     let enable_get_epoch_stake_syscall =
         feature_set.is_active(&enable_get_epoch_stake_syscall::id());
+    let enable_bls12_381_syscall = feature_set.is_active(&enable_bls12_381_syscall::id());
+    let fee_parameters_sysvar_enabled = feature_set.is_active(&fee_parameters_sysvar::id());
     let min_sbpf_version = if !feature_set.is_active(&disable_sbpf_v0_execution::id())
         || feature_set.is_active(&reenable_sbpf_v0_execution::id())
     {
@@ -474,6 +477,13 @@ pub fn create_program_runtime_environment_v1<'a>(
         SyscallGetEpochRewardsSysvar::vm,
     )?;
 
+    register_feature_gated_function!(
+        result,
+        fee_parameters_sysvar_enabled,
+        "sol_get_fee_parameters_sysvar",
+        SyscallGetFeeParametersSysvar::vm,
+    )?;
+
     // Memory ops
     result.register_function("sol_memcpy_", SyscallMemcpy::vm)?;
     result.register_function("sol_memmove_", SyscallMemmove::vm)?;
@@ -561,6 +571,14 @@ pub fn create_program_runtime_environment_v1<'a>(
         SyscallGetEpochStake::vm,
     )?;
 
+    // BLS12-381
+    register_feature_gated_function!(
+        result,
+        enable_bls12_381_syscall,
+        "sol_bls12_381_group_op",
+        SyscallBls12381::vm,
+    )?;
+
     // Log data
     result.register_function("sol_log_data", SyscallLogData::vm)?;
 
@@ -1815,6 +1833,95 @@ declare_builtin_function!(
     }
 );
 
+declare_builtin_function!(
+    /// BLS12-381 aggregation, hash to curve and pairing
+    SyscallBls12381,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        group_op: u64,
+        input_addr: u64,
+        input_size: u64,
+        result_addr: u64,
+        _arg5: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Error> {
+        use tachyon_bls12_381::prelude::*;
+        let budget = invoke_context.get_compute_budget();
+        let (cost, output): (u64, usize) = match group_op {
+            BLS12_381_G1_AGGREGATE => {
+                let num_points = input_size / BLS12_381_G1_POINT_LEN as u64;
+                let cost = budget.bls12_381_g1_aggregate_base_cost.saturating_add(
+                    budget
+                        .bls12_381_g1_aggregate_incremental_cost
+                        .saturating_mul(num_points),
+                );
+                (cost, BLS12_381_G1_POINT_LEN)
+            }
+            BLS12_381_G2_AGGREGATE => {
+                let num_points = input_size / BLS12_381_G2_POINT_LEN as u64;
+                let cost = budget.bls12_381_g2_aggregate_base_cost.saturating_add(
+                    budget
+                        .bls12_381_g2_aggregate_incremental_cost
+                        .saturating_mul(num_points),
+                );
+                (cost, BLS12_381_G2_POINT_LEN)
+            }
+            BLS12_381_HASH_TO_G2 => {
+                let cost = budget
+                    .bls12_381_hash_to_g2_cost
+                    .saturating_add(budget.sha256_byte_cost.saturating_mul(input_size));
+                (cost, BLS12_381_G2_POINT_LEN)
+            }
+            BLS12_381_PAIRING => {
+                let num_pairs = input_size / BLS12_381_PAIRING_ELEMENT_LEN as u64;
+                let cost = budget.bls12_381_pairing_one_pair_cost_first.saturating_add(
+                    budget
+                        .bls12_381_pairing_one_pair_cost_other
+                        .saturating_mul(num_pairs.saturating_sub(1)),
+                );
+                (cost, BLS12_381_PAIRING_OUTPUT_LEN)
+            }
+            _ => {
+                return Err(SyscallError::InvalidAttribute.into());
+            }
+        };
+
+        consume_compute_meter(invoke_context, cost)?;
+
+        let input = translate_slice::<u8>(
+            memory_mapping,
+            input_addr,
+            input_size,
+            invoke_context.get_check_aligned(),
+        )?;
+
+        let call_result = translate_slice_mut::<u8>(
+            memory_mapping,
+            result_addr,
+            output as u64,
+            invoke_context.get_check_aligned(),
+        )?;
+
+        let calculation = match group_op {
+            BLS12_381_G1_AGGREGATE => bls12_381_g1_aggregate,
+            BLS12_381_G2_AGGREGATE => bls12_381_g2_aggregate,
+            BLS12_381_HASH_TO_G2 => bls12_381_hash_to_g2,
+            BLS12_381_PAIRING => bls12_381_pairing,
+            _ => {
+                return Err(SyscallError::InvalidAttribute.into());
+            }
+        };
+
+        match calculation(input) {
+            Ok(result) if result.len() == output => {
+                call_result.copy_from_slice(&result);
+                Ok(SUCCESS)
+            }
+            _ => Ok(1),
+        }
+    }
+);
+
 declare_builtin_function!(
     /// Big integer modular exponentiation
     SyscallBigModExp,
@@ -4016,6 +4123,98 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_syscall_get_fee_parameters_sysvar() {
+        use solana_account::Account;
+        let config = Config::default();
+        let src_fee_parameters = FeeParameters {
+            base_fee_multiplier: 10,
+            min_compute_unit_price: 1_000_000,
+            min_compute_units_threshold: 1_000,
+        };
+        let transaction_accounts = vec![(
+            tachyon_fee_parameters::id(),
+            AccountSharedData::from(Account {
+                lamports: 1,
+                data: bincode::serialize(&src_fee_parameters).unwrap(),
+                owner: sysvar::id(),
+                executable: false,
+                rent_epoch: 0,
+            }),
+        )];
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+
+        let mut got_fee_parameters_obj = FeeParameters::default();
+        let got_fee_parameters_obj_va = 0x100000000;
+        let mut got_fee_parameters_buf = vec![0; FeeParameters::SIZE];
+        let got_fee_parameters_buf_va = 0x200000000;
+        let fee_parameters_id_va = 0x300000000;
+        let mut memory_mapping = MemoryMapping::new(
+            vec![
+                MemoryRegion::new_writable(
+                    bytes_of_mut(&mut got_fee_parameters_obj),
+                    got_fee_parameters_obj_va,
+                ),
+                MemoryRegion::new_writable(&mut got_fee_parameters_buf, got_fee_parameters_buf_va),
+                MemoryRegion::new_readonly(
+                    &tachyon_fee_parameters::id().to_bytes(),
+                    fee_parameters_id_va,
+                ),
+            ],
+            &config,
+            SBPFVersion::V3,
+        )
+        .unwrap();
+
+        let budget = invoke_context.get_compute_budget();
+        invoke_context.mock_set_remaining(budget.sysvar_base_cost + FeeParameters::SIZE as u64);
+        let result = SyscallGetFeeParametersSysvar::rust(
+            &mut invoke_context,
+            got_fee_parameters_obj_va,
+            0,
+            0,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        result.unwrap();
+        assert_eq!(got_fee_parameters_obj, src_fee_parameters);
+        assert_eq!(invoke_context.get_remaining(), 0);
+
+        // The sysvar can also be read with sol_get_sysvar
+        invoke_context.mock_set_remaining(u64::MAX);
+        let result = SyscallGetSysvar::rust(
+            &mut invoke_context,
+            fee_parameters_id_va,
+            got_fee_parameters_buf_va,
+            0,
+            FeeParameters::SIZE as u64,
+            0,
+            &mut memory_mapping,
+        );
+        assert_eq!(result.unwrap(), 0);
+        assert_eq!(
+            FeeParameters::from_account_data(&got_fee_parameters_buf),
+            Some(src_fee_parameters)
+        );
+
+        // Without the sysvar account, the syscall aborts
+        with_mock_invoke_context!(invoke_context, transaction_context, vec![]);
+        let result = SyscallGetFeeParametersSysvar::rust(
+            &mut invoke_context,
+            got_fee_parameters_obj_va,
+            0,
+            0,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_matches!(
+            result,
+            Result::Err(error) if error.downcast_ref::<InstructionError>().unwrap() == &InstructionError::UnsupportedSysvar
+        );
+    }
+
     #[test_case(false; "partial")]
     #[test_case(true; "full")]
     fn test_syscall_get_stake_history(filled: bool) {
@@ -4896,6 +5095,123 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_syscall_bls12_381() {
+        use tachyon_bls12_381::prelude::*;
+        let config = Config::default();
+        prepare_mockup!(invoke_context, program_id, bpf_loader::id());
+
+        const VADDR_INPUT: u64 = 0x100000000;
+        const VADDR_OUT: u64 = 0x200000000;
+        let message = b"checkpoint";
+        let hash_input = [
+            &[BLS_SIGNATURE_DST_POP.len() as u8][..],
+            BLS_SIGNATURE_DST_POP,
+            &message[..],
+        ]
+        .concat();
+
+        // Hash to G2 consumes its cost and writes the point
+        let mut message_point = [0; BLS12_381_G2_POINT_LEN];
+        let mut memory_mapping = MemoryMapping::new(
+            vec![
+                MemoryRegion::new_readonly(&hash_input, VADDR_INPUT),
+                MemoryRegion::new_writable(&mut message_point, VADDR_OUT),
+            ],
+            &config,
+            SBPFVersion::V3,
+        )
+        .unwrap();
+        let budget = invoke_context.get_compute_budget();
+        invoke_context.mock_set_remaining(
+            budget.bls12_381_hash_to_g2_cost + budget.sha256_byte_cost * hash_input.len() as u64,
+        );
+        let result = SyscallBls12381::rust(
+            &mut invoke_context,
+            BLS12_381_HASH_TO_G2,
+            VADDR_INPUT,
+            hash_input.len() as u64,
+            VADDR_OUT,
+            0,
+            &mut memory_mapping,
+        );
+        assert_eq!(result.unwrap(), 0);
+        assert_eq!(invoke_context.get_remaining(), 0);
+        assert_eq!(
+            message_point.to_vec(),
+            bls12_381_hash_to_g2(&hash_input).unwrap()
+        );
+
+        // e(0, H(m)) is the identity, e(-g1, H(m)) is not
+        for (g1_point, expected) in [(BLS12_381_G1_IDENTITY, 1), (BLS12_381_G1_NEG_GENERATOR, 0)] {
+            let pairing_input = [&g1_point[..], &message_point[..]].concat();
+            let mut pairing_result = [0xff; BLS12_381_PAIRING_OUTPUT_LEN];
+            let mut memory_mapping = MemoryMapping::new(
+                vec![
+                    MemoryRegion::new_readonly(&pairing_input, VADDR_INPUT),
+                    MemoryRegion::new_writable(&mut pairing_result, VADDR_OUT),
+                ],
+                &config,
+                SBPFVersion::V3,
+            )
+            .unwrap();
+            invoke_context.mock_set_remaining(
+                invoke_context
+                    .get_compute_budget()
+                    .bls12_381_pairing_one_pair_cost_first,
+            );
+            let result = SyscallBls12381::rust(
+                &mut invoke_context,
+                BLS12_381_PAIRING,
+                VADDR_INPUT,
+                pairing_input.len() as u64,
+                VADDR_OUT,
+                0,
+                &mut memory_mapping,
+            );
+            assert_eq!(result.unwrap(), 0);
+            assert_eq!(pairing_result, [expected]);
+        }
+
+        // Invalid points fail without writing the result, unknown operations abort
+        let invalid_input = [0xff; BLS12_381_G1_POINT_LEN];
+        let mut aggregate = [0; BLS12_381_G1_POINT_LEN];
+        let mut memory_mapping = MemoryMapping::new(
+            vec![
+                MemoryRegion::new_readonly(&invalid_input, VADDR_INPUT),
+                MemoryRegion::new_writable(&mut aggregate, VADDR_OUT),
+            ],
+            &config,
+            SBPFVersion::V3,
+        )
+        .unwrap();
+        invoke_context.mock_set_remaining(u64::MAX);
+        let result = SyscallBls12381::rust(
+            &mut invoke_context,
+            BLS12_381_G1_AGGREGATE,
+            VADDR_INPUT,
+            invalid_input.len() as u64,
+            VADDR_OUT,
+            0,
+            &mut memory_mapping,
+        );
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(aggregate, [0; BLS12_381_G1_POINT_LEN]);
+        let result = SyscallBls12381::rust(
+            &mut invoke_context,
+            4,
+            VADDR_INPUT,
+            invalid_input.len() as u64,
+            VADDR_OUT,
+            0,
+            &mut memory_mapping,
+        );
+        assert_matches!(
+            result,
+            Result::Err(error) if error.downcast_ref::<SyscallError>().unwrap() == &SyscallError::InvalidAttribute
+        );
+    }
+
     #[test]
     fn test_syscall_get_epoch_stake_total_stake() {
         let config = Config::default();
@@ -161,6 +161,37 @@ declare_builtin_function!(
     }
 );
 
+declare_builtin_function!(
+    /// Get the X1 fee parameters sysvar
+    SyscallGetFeeParametersSysvar,
+    fn rust(
+        invoke_context: &mut InvokeContext,
+        var_addr: u64,
+        _arg2: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &mut MemoryMapping,
+    ) -> Result<u64, Error> {
+        consume_compute_meter(
+            invoke_context,
+            invoke_context
+                .get_compute_budget()
+                .sysvar_base_cost
+                .saturating_add(size_of::<FeeParameters>() as u64),
+        )?;
+        let var = translate_type_mut::<FeeParameters>(
+            memory_mapping,
+            var_addr,
+            invoke_context.get_check_aligned(),
+        )?;
+        let fee_parameters = invoke_context.get_sysvar_cache().get_fee_parameters()?;
+        *var = *fee_parameters;
+
+        Ok(SUCCESS)
+    }
+);
+
 const SYSVAR_NOT_FOUND: u64 = 2;
 const OFFSET_LENGTH_EXCEEDS_SYSVAR: u64 = 1;
 
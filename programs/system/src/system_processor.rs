@@ -3,6 +3,7 @@ use {
         advance_nonce_account, authorize_nonce_account, initialize_nonce_account,
         withdraw_nonce_account,
     },
+    agave_feature_set as feature_set,
     log::*,
     solana_bincode::limited_deserialize,
     solana_instruction::error::InstructionError,
@@ -21,6 +22,10 @@ use {
         BorrowedAccount, IndexOfAccount, InstructionContext, TransactionContext,
     },
     std::collections::HashSet,
+    tachyon_system_interface::{
+        storage_deposit::{minimum_balance_with_storage_deposit, storage_deposit},
+        transfer_many::{TransferMany, TRANSFER_MANY_COMPUTE_UNITS_PER_RECIPIENT},
+    },
 };
 
 // represents an address that may or may not have been generated
@@ -110,6 +115,37 @@ fn allocate(
     Ok(())
 }
 
+fn is_storage_deposit_enforced(invoke_context: &InvokeContext) -> bool {
+    invoke_context
+        .get_feature_set()
+        .is_active(&feature_set::storage_deposit::id())
+}
+
+fn check_storage_deposit(
+    account: &BorrowedAccount,
+    address: &Address,
+    space: u64,
+    invoke_context: &InvokeContext,
+) -> Result<(), InstructionError> {
+    if space == 0 || !is_storage_deposit_enforced(invoke_context) {
+        return Ok(());
+    }
+    let rent = invoke_context.get_sysvar_cache().get_rent()?;
+    let minimum_balance = minimum_balance_with_storage_deposit(&rent, space);
+    if account.get_lamports() < minimum_balance {
+        ic_msg!(
+            invoke_context,
+            "Allocate: account {:?} has {} lamports, need {} including a storage deposit of {}",
+            address,
+            account.get_lamports(),
+            minimum_balance,
+            storage_deposit(space)
+        );
+        return Err(InstructionError::InsufficientFunds);
+    }
+    Ok(())
+}
+
 fn assign(
     account: &mut BorrowedAccount,
     address: &Address,
@@ -170,6 +206,13 @@ fn create_account(
 
         allocate_and_assign(&mut to, to_address, space, owner, signers, invoke_context)?;
     }
+    let lamports = if is_storage_deposit_enforced(invoke_context) {
+        lamports
+            .checked_add(storage_deposit(space))
+            .ok_or(InstructionError::ArithmeticOverflow)?
+    } else {
+        lamports
+    };
     transfer(
         from_account_index,
         to_account_index,
@@ -296,12 +339,67 @@ fn transfer_with_seed(
     )
 }
 
+fn transfer_many(
+    lamports: &[u64],
+    invoke_context: &InvokeContext,
+    transaction_context: &TransactionContext,
+    instruction_context: &InstructionContext,
+) -> Result<(), InstructionError> {
+    let num_recipients = lamports.len() as IndexOfAccount;
+    if num_recipients == 0 {
+        ic_msg!(invoke_context, "TransferMany: no recipients");
+        return Err(InstructionError::InvalidInstructionData);
+    }
+    if instruction_context.get_number_of_instruction_accounts() != num_recipients + 1 {
+        ic_msg!(
+            invoke_context,
+            "TransferMany: {} amounts given for {} recipient accounts",
+            num_recipients,
+            instruction_context
+                .get_number_of_instruction_accounts()
+                .saturating_sub(1)
+        );
+        return Err(InstructionError::NotEnoughAccountKeys);
+    }
+    invoke_context
+        .consume_checked(
+            TRANSFER_MANY_COMPUTE_UNITS_PER_RECIPIENT.saturating_mul(u64::from(num_recipients)),
+        )
+        .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
+
+    for (to_account_index, lamports) in (1..).zip(lamports) {
+        transfer(
+            0,
+            to_account_index,
+            *lamports,
+            invoke_context,
+            transaction_context,
+            instruction_context,
+        )?;
+    }
+    Ok(())
+}
+
 pub const DEFAULT_COMPUTE_UNITS: u64 = 150;
 
 declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context| {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
     let instruction_data = instruction_context.get_instruction_data();
+    if TransferMany::is_transfer_many(instruction_data)
+        && invoke_context
+            .get_feature_set()
+            .is_active(&feature_set::transfer_many::id())
+    {
+        let TransferMany { lamports } = TransferMany::deserialize(instruction_data)?;
+        trace!("process_instruction: TransferMany {:?}", lamports);
+        return transfer_many(
+            &lamports,
+            invoke_context,
+            transaction_context,
+            instruction_context,
+        );
+    }
     let instruction =
         limited_deserialize(instruction_data, solana_packet::PACKET_DATA_SIZE as u64)?;
 
@@ -497,7 +595,8 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
                 None,
                 invoke_context,
             )?;
-            allocate(&mut account, &address, space, &signers, invoke_context)
+            allocate(&mut account, &address, space, &signers, invoke_context)?;
+            check_storage_deposit(&account, &address, space, invoke_context)
         }
         SystemInstruction::AllocateWithSeed {
             base,
@@ -522,7 +621,8 @@ declare_process_instruction!(Entrypoint, DEFAULT_COMPUTE_UNITS, |invoke_context|
                 &owner,
                 &signers,
                 invoke_context,
-            )
+            )?;
+            check_storage_deposit(&account, &address, space, invoke_context)
         }
         SystemInstruction::AssignWithSeed { base, seed, owner } => {
             instruction_context.check_number_of_instruction_accounts(1)?;
@@ -566,12 +666,14 @@ mod tests {
     };
     use {
         super::*,
+        agave_feature_set::FeatureSet,
         bincode::serialize,
         solana_nonce_account::{get_system_account_kind, SystemAccountKind},
         solana_program_runtime::{
             invoke_context::mock_process_instruction, with_mock_invoke_context,
         },
-        std::collections::BinaryHeap,
+        std::{collections::BinaryHeap, sync::Arc},
+        tachyon_system_interface::storage_deposit::STORAGE_DEPOSIT_LAMPORTS_PER_BYTE,
     };
 
     impl From<Pubkey> for Address {
@@ -588,6 +690,26 @@ mod tests {
         transaction_accounts: Vec<(Pubkey, AccountSharedData)>,
         instruction_accounts: Vec<AccountMeta>,
         expected_result: Result<(), InstructionError>,
+    ) -> Vec<AccountSharedData> {
+        // Storage deposits change the balances of created accounts, they are covered by the
+        // test_storage_deposit_* tests
+        let mut features = FeatureSet::all_enabled();
+        features.deactivate(&feature_set::storage_deposit::id());
+        process_instruction_with_feature_set(
+            instruction_data,
+            transaction_accounts,
+            instruction_accounts,
+            expected_result,
+            features,
+        )
+    }
+
+    fn process_instruction_with_feature_set(
+        instruction_data: &[u8],
+        transaction_accounts: Vec<(Pubkey, AccountSharedData)>,
+        instruction_accounts: Vec<AccountMeta>,
+        expected_result: Result<(), InstructionError>,
+        features: FeatureSet,
     ) -> Vec<AccountSharedData> {
         mock_process_instruction(
             &system_program::id(),
@@ -597,7 +719,7 @@ mod tests {
             instruction_accounts,
             expected_result,
             Entrypoint::vm,
-            |_invoke_context| {},
+            |invoke_context| invoke_context.mock_set_feature_set(Arc::new(features.clone())),
             |_invoke_context| {},
         )
     }
@@ -755,6 +877,111 @@ mod tests {
         assert_eq!(accounts[1].data(), &[0, 0]);
     }
 
+    #[test]
+    fn test_storage_deposit_create_account() {
+        let new_owner = Pubkey::from([9; 32]);
+        let from = Pubkey::new_unique();
+        let to = Pubkey::new_unique();
+        let space = 10;
+        let deposit = space * STORAGE_DEPOSIT_LAMPORTS_PER_BYTE;
+        let instruction_data = bincode::serialize(&SystemInstruction::CreateAccount {
+            lamports: 50,
+            space,
+            owner: new_owner,
+        })
+        .unwrap();
+        let instruction_accounts = vec![
+            AccountMeta {
+                pubkey: from,
+                is_signer: true,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: to,
+                is_signer: true,
+                is_writable: true,
+            },
+        ];
+
+        // The deposit is taken from the funding account on top of the requested lamports
+        let accounts = process_instruction_with_feature_set(
+            &instruction_data,
+            vec![
+                (
+                    from,
+                    AccountSharedData::new(100 + deposit, 0, &system_program::id()),
+                ),
+                (to, AccountSharedData::new(0, 0, &Pubkey::default())),
+            ],
+            instruction_accounts.clone(),
+            Ok(()),
+            FeatureSet::all_enabled(),
+        );
+        assert_eq!(accounts[0].lamports(), 50);
+        assert_eq!(accounts[1].lamports(), 50 + deposit);
+        assert_eq!(accounts[1].owner(), &new_owner);
+        assert_eq!(accounts[1].data().len(), space as usize);
+
+        // Fails if the funding account can't afford the deposit
+        process_instruction_with_feature_set(
+            &instruction_data,
+            vec![
+                (from, AccountSharedData::new(100, 0, &system_program::id())),
+                (to, AccountSharedData::new(0, 0, &Pubkey::default())),
+            ],
+            instruction_accounts,
+            Err(SystemError::ResultWithNegativeLamports.into()),
+            FeatureSet::all_enabled(),
+        );
+    }
+
+    #[test]
+    fn test_storage_deposit_allocate() {
+        let rent = Rent::default();
+        let address = Pubkey::new_unique();
+        let space = 10;
+        let instruction_data = bincode::serialize(&SystemInstruction::Allocate { space }).unwrap();
+        let instruction_accounts = vec![AccountMeta {
+            pubkey: address,
+            is_signer: true,
+            is_writable: true,
+        }];
+        let rent_account = account::create_account_shared_data_for_test(&rent);
+
+        // Rent exemption alone is not enough
+        let rent_exempt_balance = rent.minimum_balance(space as usize);
+        process_instruction_with_feature_set(
+            &instruction_data,
+            vec![
+                (
+                    address,
+                    AccountSharedData::new(rent_exempt_balance, 0, &system_program::id()),
+                ),
+                (sysvar::rent::id(), rent_account.clone()),
+            ],
+            instruction_accounts.clone(),
+            Err(InstructionError::InsufficientFunds),
+            FeatureSet::all_enabled(),
+        );
+
+        let balance = rent_exempt_balance + space * STORAGE_DEPOSIT_LAMPORTS_PER_BYTE;
+        let accounts = process_instruction_with_feature_set(
+            &instruction_data,
+            vec![
+                (
+                    address,
+                    AccountSharedData::new(balance, 0, &system_program::id()),
+                ),
+                (sysvar::rent::id(), rent_account),
+            ],
+            instruction_accounts,
+            Ok(()),
+            FeatureSet::all_enabled(),
+        );
+        assert_eq!(accounts[0].lamports(), balance);
+        assert_eq!(accounts[0].data().len(), space as usize);
+    }
+
     #[test]
     fn test_address_create_with_seed_mismatch() {
         with_mock_invoke_context!(invoke_context, transaction_context, Vec::new());
@@ -1369,6 +1596,69 @@ mod tests {
         assert_eq!(accounts[1].lamports(), 1);
     }
 
+    #[test]
+    fn test_transfer_many() {
+        let from = Pubkey::new_unique();
+        let to1 = Pubkey::new_unique();
+        let to2 = Pubkey::new_unique();
+        let transaction_accounts = vec![
+            (from, AccountSharedData::new(100, 0, &system_program::id())),
+            (to1, AccountSharedData::new(1, 0, &system_program::id())),
+            (to2, AccountSharedData::new(0, 0, &Pubkey::new_unique())),
+        ];
+        let instruction =
+            tachyon_system_interface::transfer_many::transfer_many(&from, &[(to1, 30), (to2, 20)]);
+
+        let accounts = process_instruction(
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts.clone(),
+            Ok(()),
+        );
+        assert_eq!(accounts[0].lamports(), 50);
+        assert_eq!(accounts[1].lamports(), 31);
+        assert_eq!(accounts[2].lamports(), 20);
+
+        // the whole batch fails if any transfer does
+        let overdrawn =
+            tachyon_system_interface::transfer_many::transfer_many(&from, &[(to1, 90), (to2, 20)]);
+        process_instruction(
+            &overdrawn.data,
+            transaction_accounts.clone(),
+            overdrawn.accounts,
+            Err(SystemError::ResultWithNegativeLamports.into()),
+        );
+
+        // every amount needs a recipient account
+        process_instruction(
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction.accounts[..2].to_vec(),
+            Err(InstructionError::NotEnoughAccountKeys),
+        );
+
+        // the funding account must sign
+        let mut instruction_accounts = instruction.accounts.clone();
+        instruction_accounts[0].is_signer = false;
+        process_instruction(
+            &instruction.data,
+            transaction_accounts.clone(),
+            instruction_accounts,
+            Err(InstructionError::MissingRequiredSignature),
+        );
+
+        // not a system instruction before the feature activation
+        let mut features = FeatureSet::all_enabled();
+        features.deactivate(&feature_set::transfer_many::id());
+        process_instruction_with_feature_set(
+            &instruction.data,
+            transaction_accounts,
+            instruction.accounts,
+            Err(InstructionError::InvalidInstructionData),
+            features,
+        );
+    }
+
     #[test]
     fn test_transfer_with_seed() {
         let base = Pubkey::new_unique();
@@ -170,6 +170,44 @@ fn process_close_proof_context(invoke_context: &mut InvokeContext) -> Result<(),
     Ok(())
 }
 
+/// Returns the compute units consumed by `instruction`, which clients should include in the
+/// compute unit limit of transactions verifying proofs
+pub fn proof_instruction_compute_units(instruction: &ProofInstruction) -> u64 {
+    match instruction {
+        ProofInstruction::CloseContextState => CLOSE_CONTEXT_STATE_COMPUTE_UNITS,
+        ProofInstruction::VerifyZeroCiphertext => VERIFY_ZERO_CIPHERTEXT_COMPUTE_UNITS,
+        ProofInstruction::VerifyCiphertextCiphertextEquality => {
+            VERIFY_CIPHERTEXT_CIPHERTEXT_EQUALITY_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyCiphertextCommitmentEquality => {
+            VERIFY_CIPHERTEXT_COMMITMENT_EQUALITY_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyPubkeyValidity => VERIFY_PUBKEY_VALIDITY_COMPUTE_UNITS,
+        ProofInstruction::VerifyPercentageWithCap => VERIFY_PERCENTAGE_WITH_CAP_COMPUTE_UNITS,
+        ProofInstruction::VerifyBatchedRangeProofU64 => {
+            VERIFY_BATCHED_RANGE_PROOF_U64_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyBatchedRangeProofU128 => {
+            VERIFY_BATCHED_RANGE_PROOF_U128_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyBatchedRangeProofU256 => {
+            VERIFY_BATCHED_RANGE_PROOF_U256_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyGroupedCiphertext2HandlesValidity => {
+            VERIFY_GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyBatchedGroupedCiphertext2HandlesValidity => {
+            VERIFY_BATCHED_GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyGroupedCiphertext3HandlesValidity => {
+            VERIFY_GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_COMPUTE_UNITS
+        }
+        ProofInstruction::VerifyBatchedGroupedCiphertext3HandlesValidity => {
+            VERIFY_BATCHED_GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_COMPUTE_UNITS
+        }
+    }
+}
+
 declare_process_instruction!(Entrypoint, 0, |invoke_context| {
     let transaction_context = &invoke_context.transaction_context;
     let instruction_context = transaction_context.get_current_instruction_context()?;
@@ -177,27 +215,22 @@ declare_process_instruction!(Entrypoint, 0, |invoke_context| {
     let instruction = ProofInstruction::instruction_type(instruction_data)
         .ok_or(InstructionError::InvalidInstructionData)?;
 
+    invoke_context
+        .consume_checked(proof_instruction_compute_units(&instruction))
+        .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
+
     match instruction {
         ProofInstruction::CloseContextState => {
-            invoke_context
-                .consume_checked(CLOSE_CONTEXT_STATE_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "CloseContextState");
             process_close_proof_context(invoke_context)
         }
         ProofInstruction::VerifyZeroCiphertext => {
-            invoke_context
-                .consume_checked(VERIFY_ZERO_CIPHERTEXT_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "VerifyZeroCiphertext");
             process_verify_proof::<ZeroCiphertextProofData, ZeroCiphertextProofContext>(
                 invoke_context,
             )
         }
         ProofInstruction::VerifyCiphertextCiphertextEquality => {
-            invoke_context
-                .consume_checked(VERIFY_CIPHERTEXT_CIPHERTEXT_EQUALITY_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "VerifyCiphertextCiphertextEquality");
             process_verify_proof::<
                 CiphertextCiphertextEqualityProofData,
@@ -205,9 +238,6 @@ declare_process_instruction!(Entrypoint, 0, |invoke_context| {
             >(invoke_context)
         }
         ProofInstruction::VerifyCiphertextCommitmentEquality => {
-            invoke_context
-                .consume_checked(VERIFY_CIPHERTEXT_COMMITMENT_EQUALITY_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "VerifyCiphertextCommitmentEquality");
             process_verify_proof::<
                 CiphertextCommitmentEqualityProofData,
@@ -215,54 +245,36 @@ declare_process_instruction!(Entrypoint, 0, |invoke_context| {
             >(invoke_context)
         }
         ProofInstruction::VerifyPubkeyValidity => {
-            invoke_context
-                .consume_checked(VERIFY_PUBKEY_VALIDITY_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "VerifyPubkeyValidity");
             process_verify_proof::<PubkeyValidityProofData, PubkeyValidityProofContext>(
                 invoke_context,
             )
         }
         ProofInstruction::VerifyPercentageWithCap => {
-            invoke_context
-                .consume_checked(VERIFY_PERCENTAGE_WITH_CAP_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "VerifyPercentageWithCap");
             process_verify_proof::<PercentageWithCapProofData, PercentageWithCapProofContext>(
                 invoke_context,
             )
         }
         ProofInstruction::VerifyBatchedRangeProofU64 => {
-            invoke_context
-                .consume_checked(VERIFY_BATCHED_RANGE_PROOF_U64_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "VerifyBatchedRangeProofU64");
             process_verify_proof::<BatchedRangeProofU64Data, BatchedRangeProofContext>(
                 invoke_context,
             )
         }
         ProofInstruction::VerifyBatchedRangeProofU128 => {
-            invoke_context
-                .consume_checked(VERIFY_BATCHED_RANGE_PROOF_U128_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "VerifyBatchedRangeProofU128");
             process_verify_proof::<BatchedRangeProofU128Data, BatchedRangeProofContext>(
                 invoke_context,
             )
         }
         ProofInstruction::VerifyBatchedRangeProofU256 => {
-            invoke_context
-                .consume_checked(VERIFY_BATCHED_RANGE_PROOF_U256_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "VerifyBatchedRangeProofU256");
             process_verify_proof::<BatchedRangeProofU256Data, BatchedRangeProofContext>(
                 invoke_context,
             )
         }
         ProofInstruction::VerifyGroupedCiphertext2HandlesValidity => {
-            invoke_context
-                .consume_checked(VERIFY_GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "VerifyGroupedCiphertext2HandlesValidity");
             process_verify_proof::<
                 GroupedCiphertext2HandlesValidityProofData,
@@ -270,9 +282,6 @@ declare_process_instruction!(Entrypoint, 0, |invoke_context| {
             >(invoke_context)
         }
         ProofInstruction::VerifyBatchedGroupedCiphertext2HandlesValidity => {
-            invoke_context
-                .consume_checked(VERIFY_BATCHED_GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(
                 invoke_context,
                 "VerifyBatchedGroupedCiphertext2HandlesValidity"
@@ -283,9 +292,6 @@ declare_process_instruction!(Entrypoint, 0, |invoke_context| {
             >(invoke_context)
         }
         ProofInstruction::VerifyGroupedCiphertext3HandlesValidity => {
-            invoke_context
-                .consume_checked(VERIFY_GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(invoke_context, "VerifyGroupedCiphertext3HandlesValidity");
             process_verify_proof::<
                 GroupedCiphertext3HandlesValidityProofData,
@@ -293,9 +299,6 @@ declare_process_instruction!(Entrypoint, 0, |invoke_context| {
             >(invoke_context)
         }
         ProofInstruction::VerifyBatchedGroupedCiphertext3HandlesValidity => {
-            invoke_context
-                .consume_checked(VERIFY_BATCHED_GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_COMPUTE_UNITS)
-                .map_err(|_| InstructionError::ComputationalBudgetExceeded)?;
             ic_msg!(
                 invoke_context,
                 "VerifyBatchedGroupedCiphertext3HandlesValidity"
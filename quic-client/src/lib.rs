@@ -17,8 +17,8 @@ use {
     quinn::Endpoint,
     solana_connection_cache::{
         connection_cache::{
-            BaseClientConnection, ClientError, ConnectionCache, ConnectionManager, ConnectionPool,
-            ConnectionPoolError, NewConnectionConfig, Protocol,
+            BaseClientConnection, ClientError, ConnectionCache, ConnectionInfo, ConnectionManager,
+            ConnectionPool, ConnectionPoolError, NewConnectionConfig, Protocol,
         },
         connection_cache_stats::ConnectionCacheStats,
     },
@@ -156,6 +156,15 @@ impl BaseClientConnection for Quic {
     type BlockingClientConnection = BlockingQuicClientConnection;
     type NonblockingClientConnection = NonblockingQuicClientConnection;
 
+    fn connection_info(&self) -> ConnectionInfo {
+        ConnectionInfo {
+            connected: self.0.is_connected(),
+            open_streams: self.0.open_streams(),
+            sent_batches: self.0.sent_batches(),
+            failed_batches: self.0.failed_batches(),
+        }
+    }
+
     fn new_blocking_connection(
         &self,
         _addr: SocketAddr,
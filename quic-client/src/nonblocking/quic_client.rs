@@ -29,7 +29,10 @@ use {
     solana_transaction_error::TransportResult,
     std::{
         net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
-        sync::{atomic::Ordering, Arc},
+        sync::{
+            atomic::{AtomicBool, AtomicU64, Ordering},
+            Arc,
+        },
         thread,
     },
     thiserror::Error,
@@ -224,6 +227,27 @@ pub struct QuicClient {
     connection: Arc<Mutex<Option<QuicNewConnection>>>,
     addr: SocketAddr,
     stats: Arc<ClientStats>,
+    connected: AtomicBool,
+    open_streams: AtomicU64,
+    sent_batches: AtomicU64,
+    failed_batches: AtomicU64,
+}
+
+/// Decrements the open stream count of a [`QuicClient`] when dropped, so that
+/// streams abandoned mid-write are accounted for as well.
+struct OpenStreamGuard<'a>(&'a AtomicU64);
+
+impl<'a> OpenStreamGuard<'a> {
+    fn new(open_streams: &'a AtomicU64) -> Self {
+        open_streams.fetch_add(1, Ordering::Relaxed);
+        Self(open_streams)
+    }
+}
+
+impl Drop for OpenStreamGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 impl QuicClient {
@@ -233,13 +257,19 @@ impl QuicClient {
             connection: Arc::new(Mutex::new(None)),
             addr,
             stats: Arc::new(ClientStats::default()),
+            connected: AtomicBool::new(false),
+            open_streams: AtomicU64::new(0),
+            sent_batches: AtomicU64::new(0),
+            failed_batches: AtomicU64::new(0),
         }
     }
 
     async fn _send_buffer_using_conn(
+        &self,
         data: &[u8],
         connection: &Connection,
     ) -> Result<(), QuicError> {
+        let _open_stream = OpenStreamGuard::new(&self.open_streams);
         let mut send_stream = connection.open_uni().await?;
         send_stream.write_all(data).await?;
         Ok(())
@@ -286,6 +316,7 @@ impl QuicClient {
                                         "Cannot make 0rtt connection to {}, error {:}",
                                         self.addr, err
                                     );
+                                    self.connected.store(false, Ordering::Relaxed);
                                     return Err(err);
                                 }
                             }
@@ -317,6 +348,7 @@ impl QuicClient {
                             Err(err) => {
                                 info!("Cannot make connection to {}, error {:}, from connection cache warming?: {}",
                                     self.addr, err, data.is_empty());
+                                self.connected.store(false, Ordering::Relaxed);
                                 return Err(err);
                             }
                         }
@@ -324,6 +356,7 @@ impl QuicClient {
                 }
             };
 
+            self.connected.store(true, Ordering::Relaxed);
             let new_stats = connection.stats();
 
             connection_stats
@@ -360,7 +393,7 @@ impl QuicClient {
             last_connection_id = connection.stable_id();
             measure_prepare_connection.stop();
 
-            match Self::_send_buffer_using_conn(data, &connection).await {
+            match self._send_buffer_using_conn(data, &connection).await {
                 Ok(()) => {
                     measure_send_packet.stop();
                     stats.successful_packets.fetch_add(1, Ordering::Relaxed);
@@ -419,9 +452,13 @@ impl QuicClient {
     where
         T: AsRef<[u8]>,
     {
-        self._send_buffer(data.as_ref(), stats, connection_stats)
-            .await
-            .map_err(Into::<ClientErrorKind>::into)?;
+        let data = data.as_ref();
+        let result = self._send_buffer(data, stats, connection_stats).await;
+        // Empty buffers are only used to warm up the connection
+        if !data.is_empty() {
+            self.record_batch_result(result.is_ok());
+        }
+        result.map_err(Into::<ClientErrorKind>::into)?;
         Ok(())
     }
 
@@ -448,15 +485,28 @@ impl QuicClient {
         if buffers.is_empty() {
             return Ok(());
         }
-        let connection = self
-            ._send_buffer(buffers[0].as_ref(), stats, connection_stats)
-            .await
-            .map_err(Into::<ClientErrorKind>::into)?;
+        let result = async {
+            let connection = self
+                ._send_buffer(buffers[0].as_ref(), stats, connection_stats)
+                .await?;
+
+            for data in buffers[1..buffers.len()].iter() {
+                self._send_buffer_using_conn(data.as_ref(), &connection)
+                    .await?;
+            }
+            Ok::<(), QuicError>(())
+        }
+        .await;
+        self.record_batch_result(result.is_ok());
+        result.map_err(Into::<ClientErrorKind>::into)
+    }
 
-        for data in buffers[1..buffers.len()].iter() {
-            Self::_send_buffer_using_conn(data.as_ref(), &connection).await?;
+    fn record_batch_result(&self, success: bool) {
+        if success {
+            self.sent_batches.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.failed_batches.fetch_add(1, Ordering::Relaxed);
         }
-        Ok(())
     }
 
     pub fn server_addr(&self) -> &SocketAddr {
@@ -466,6 +516,27 @@ impl QuicClient {
     pub fn stats(&self) -> Arc<ClientStats> {
         self.stats.clone()
     }
+
+    /// Whether the most recent attempt to use the connection succeeded in
+    /// establishing it.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Number of uni streams currently being written to the server.
+    pub fn open_streams(&self) -> u64 {
+        self.open_streams.load(Ordering::Relaxed)
+    }
+
+    /// Number of batches (or single buffers) sent successfully.
+    pub fn sent_batches(&self) -> u64 {
+        self.sent_batches.load(Ordering::Relaxed)
+    }
+
+    /// Number of batches (or single buffers) that failed to send.
+    pub fn failed_batches(&self) -> u64 {
+        self.failed_batches.load(Ordering::Relaxed)
+    }
 }
 
 pub struct QuicClientConnection {
@@ -339,6 +339,22 @@ impl LedgerWallet {
         })
     }
 
+    /// The Solana app rejects a message it cannot display unless blind signing is enabled, so
+    /// point at the setting rather than reporting an invalid message
+    fn explain_sign_error(&self, err: RemoteWalletError) -> RemoteWalletError {
+        match &err {
+            RemoteWalletError::LedgerError(
+                LedgerError::SolanaInvalidMessage | LedgerError::SolanaInvalidMessageFormat,
+            ) if matches!(self.get_settings(), Ok(settings) if !settings.enable_blind_signing) => {
+                RemoteWalletError::InvalidInput(format!(
+                    "{err}: the Solana app cannot display this transaction, enable blind \
+                     signing in the app settings to sign it"
+                ))
+            }
+            _ => err,
+        }
+    }
+
     fn get_configuration_vector(&self) -> Result<ConfigurationVersion, RemoteWalletError> {
         if let Ok(config) = self._send_apdu(commands::GET_APP_CONFIGURATION, 0, 0, &[], false) {
             if config.len() != 5 {
@@ -483,16 +499,18 @@ impl RemoteWallet<hidapi::DeviceInfo> for LedgerWallet {
         };
 
         let p1 = P1_CONFIRM;
-        let mut result = self.send_apdu(
-            if self.outdated_app() {
-                commands::DEPRECATED_SIGN_MESSAGE
-            } else {
-                commands::SIGN_MESSAGE
-            },
-            p1,
-            p2,
-            &payload,
-        )?;
+        let mut result = self
+            .send_apdu(
+                if self.outdated_app() {
+                    commands::DEPRECATED_SIGN_MESSAGE
+                } else {
+                    commands::SIGN_MESSAGE
+                },
+                p1,
+                p2,
+                &payload,
+            )
+            .map_err(|err| self.explain_sign_error(err))?;
 
         // Pack and send the remaining chunks
         if !remaining_data.is_empty() {
@@ -514,16 +532,18 @@ impl RemoteWallet<hidapi::DeviceInfo> for LedgerWallet {
             chunks.last_mut().unwrap().0 &= !P2_MORE;
 
             for (p2, payload) in chunks {
-                result = self.send_apdu(
-                    if self.outdated_app() {
-                        commands::DEPRECATED_SIGN_MESSAGE
-                    } else {
-                        commands::SIGN_MESSAGE
-                    },
-                    p1,
-                    p2,
-                    &payload,
-                )?;
+                result = self
+                    .send_apdu(
+                        if self.outdated_app() {
+                            commands::DEPRECATED_SIGN_MESSAGE
+                        } else {
+                            commands::SIGN_MESSAGE
+                        },
+                        p1,
+                        p2,
+                        &payload,
+                    )
+                    .map_err(|err| self.explain_sign_error(err))?;
             }
         }
 
@@ -16,6 +16,13 @@ use {
     std::collections::{HashMap, HashSet},
 };
 
+/// The account holding the fee treasury config created at genesis, read by the runtime when
+/// routing base fees to the treasury. It is declared here, rather than next to the runtime code
+/// reading it, so that it can be reserved.
+pub mod fee_treasury_config {
+    solana_pubkey::declare_id!("DrsSxHiCJxWLbme5GE2YLn8FZkdcJ3ZSv1bYuQi9ANQn");
+}
+
 // ReservedAccountKeys is not serialized into or deserialized from bank
 // snapshots but the bank requires this trait to be implemented anyways.
 #[cfg(feature = "frozen-abi")]
@@ -175,10 +182,12 @@ lazy_static! {
         ReservedAccount::new_active(sysvar::slot_hashes::id()),
         ReservedAccount::new_active(sysvar::slot_history::id()),
         ReservedAccount::new_active(sysvar::stake_history::id()),
+        ReservedAccount::new_pending(tachyon_slot_seed::id(), feature_set::slot_seed_sysvar::id()),
 
         // other
         ReservedAccount::new_active(native_loader::id()),
         ReservedAccount::new_pending(sysvar::id(), feature_set::add_new_reserved_account_keys::id()),
+        ReservedAccount::new_pending(fee_treasury_config::id(), feature_set::route_base_fees_to_treasury::id()),
     ].to_vec();
 }
 
@@ -247,6 +256,27 @@ mod tests {
         assert!(reserved_account_keys.is_reserved(&pending_reserved_keys[1]));
     }
 
+    #[test]
+    fn test_x1_reserved_accounts() {
+        let mut reserved_account_keys = ReservedAccountKeys::default();
+        assert!(!reserved_account_keys.is_reserved(&tachyon_slot_seed::id()));
+        assert!(!reserved_account_keys.is_reserved(&fee_treasury_config::id()));
+
+        let mut feature_set = FeatureSet::default();
+        feature_set
+            .active_mut()
+            .insert(feature_set::slot_seed_sysvar::id(), 0);
+        reserved_account_keys.update_active_set(&feature_set);
+        assert!(reserved_account_keys.is_reserved(&tachyon_slot_seed::id()));
+        assert!(!reserved_account_keys.is_reserved(&fee_treasury_config::id()));
+
+        feature_set
+            .active_mut()
+            .insert(feature_set::route_base_fees_to_treasury::id(), 0);
+        reserved_account_keys.update_active_set(&feature_set);
+        assert!(reserved_account_keys.is_reserved(&fee_treasury_config::id()));
+    }
+
     #[test]
     fn test_static_list_compat() {
         let mut static_set = HashSet::new();
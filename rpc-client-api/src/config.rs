@@ -1,9 +1,10 @@
 use {
     crate::filter::RpcFilterType,
     solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig},
-    solana_clock::{Epoch, Slot},
+    solana_clock::{Epoch, Slot, UnixTimestamp},
     solana_commitment_config::{CommitmentConfig, CommitmentLevel},
     solana_transaction_status_client_types::{TransactionDetails, UiTransactionEncoding},
+    std::collections::HashMap,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -21,6 +22,10 @@ pub struct RpcSendTransactionConfig {
     pub encoding: Option<UiTransactionEncoding>,
     pub max_retries: Option<usize>,
     pub min_context_slot: Option<Slot>,
+    /// Recommend a higher compute unit price through `computeUnitPriceSubscribe` every time
+    /// the transaction did not land after this many more leader rotations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compute_unit_price_escalation_rotations: Option<u64>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -30,6 +35,17 @@ pub struct RpcSimulateTransactionAccountsConfig {
     pub addresses: Vec<String>,
 }
 
+/// Replacement state of an account for a simulation. Fields which are left out keep the
+/// account's current value, or the value of a new system account if it doesn't exist.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountOverride {
+    pub lamports: Option<u64>,
+    /// The data and its encoding, base64 or base64+zstd
+    pub data: Option<(String, UiAccountEncoding)>,
+    pub owner: Option<String>, // base-58 encoded Pubkey
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcSimulateTransactionConfig {
@@ -44,6 +60,25 @@ pub struct RpcSimulateTransactionConfig {
     pub min_context_slot: Option<Slot>,
     #[serde(default)]
     pub inner_instructions: bool,
+    /// Accounts to load in place of the bank's, keyed by base-58 encoded Pubkey
+    pub account_overrides: Option<HashMap<String, RpcAccountOverride>>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulateBundleConfig {
+    #[serde(default)]
+    pub sig_verify: bool,
+    #[serde(default)]
+    pub replace_recent_blockhash: bool,
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+    pub encoding: Option<UiTransactionEncoding>,
+    pub min_context_slot: Option<Slot>,
+    #[serde(default)]
+    pub inner_instructions: bool,
+    /// Accounts to load in place of the bank's, keyed by base-58 encoded Pubkey
+    pub account_overrides: Option<HashMap<String, RpcAccountOverride>>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -144,6 +179,7 @@ pub enum RpcAccountIndex {
     ProgramId,
     SplTokenMint,
     SplTokenOwner,
+    StakeVoter,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -171,14 +207,19 @@ pub struct RpcProgramAccountsConfig {
 pub enum RpcTransactionLogsFilter {
     All,
     AllWithVotes,
-    Mentions(Vec<String>), // base58-encoded list of addresses
+    Mentions(Vec<String>), // base58-encoded list of addresses, any of which is mentioned
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcTransactionLogsConfig {
     #[serde(flatten)]
     pub commitment: Option<CommitmentConfig>,
+    /// Only notify transactions with a log line containing this substring
+    pub log_contains: Option<String>,
+    /// Only notify transactions with a log line matching this regular expression, and the
+    /// substring if both are given
+    pub log_regex: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -214,6 +255,14 @@ pub struct RpcBlockSubscribeConfig {
     pub max_supported_transaction_version: Option<u8>,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFinalizedSignaturesForAddressConfig {
+    /// Cursor returned by a previous call, to list the signatures after it
+    pub after: Option<String>,
+    pub limit: Option<usize>,
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcSignaturesForAddressConfig {
@@ -223,6 +272,33 @@ pub struct RpcSignaturesForAddressConfig {
     #[serde(flatten)]
     pub commitment: Option<CommitmentConfig>,
     pub min_context_slot: Option<Slot>,
+    /// Only return signatures of blocks produced at or after this time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_block_time: Option<UnixTimestamp>,
+    /// Only return signatures of blocks produced at or before this time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_block_time: Option<UnixTimestamp>,
+    /// Only return signatures of transactions that also reference this program, as base-58
+    /// string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSlashingEvidenceConfig {
+    /// Only return evidence for slots at or above this slot
+    pub start_slot: Option<Slot>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcRegisterWebhookConfig {
+    /// The commitment a block must reach before its transactions are delivered, `confirmed` or
+    /// `finalized`
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
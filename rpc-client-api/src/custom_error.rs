@@ -27,6 +27,7 @@ pub const JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED: i64 = -32016;
 pub const JSON_RPC_SERVER_ERROR_EPOCH_REWARDS_PERIOD_ACTIVE: i64 = -32017;
 pub const JSON_RPC_SERVER_ERROR_SLOT_NOT_EPOCH_BOUNDARY: i64 = -32018;
 pub const JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_UNREACHABLE: i64 = -32019;
+pub const JSON_RPC_SERVER_ERROR_COMPUTE_UNIT_PRICE_BELOW_MINIMUM: i64 = -32020;
 
 #[derive(Error, Debug)]
 pub enum RpcCustomError {
@@ -78,6 +79,12 @@ pub enum RpcCustomError {
     SlotNotEpochBoundary { slot: Slot },
     #[error("LongTermStorageUnreachable")]
     LongTermStorageUnreachable,
+    #[error("ComputeUnitPriceBelowMinimum")]
+    ComputeUnitPriceBelowMinimum {
+        compute_unit_price: u64,
+        min_compute_unit_price: u64,
+        compute_units_threshold: u64,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -99,6 +106,14 @@ pub struct EpochRewardsPeriodActiveErrorData {
     pub rewards_complete_block_height: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeUnitPriceBelowMinimumErrorData {
+    pub compute_unit_price: u64,
+    pub min_compute_unit_price: u64,
+    pub compute_units_threshold: u64,
+}
+
 impl From<EncodeError> for RpcCustomError {
     fn from(err: EncodeError) -> Self {
         match err {
@@ -252,6 +267,26 @@ impl From<RpcCustomError> for Error {
                 message: "Failed to query long-term storage; please try again".to_string(),
                 data: None,
             },
+            RpcCustomError::ComputeUnitPriceBelowMinimum {
+                compute_unit_price,
+                min_compute_unit_price,
+                compute_units_threshold,
+            } => Self {
+                code: ErrorCode::ServerError(
+                    JSON_RPC_SERVER_ERROR_COMPUTE_UNIT_PRICE_BELOW_MINIMUM,
+                ),
+                message: format!(
+                    "Compute unit price {compute_unit_price} is below the minimum of \
+                     {min_compute_unit_price} micro-lamports for transactions using fewer than \
+                     {compute_units_threshold} compute units; raise the price to at least the \
+                     minimum or remove the SetComputeUnitPrice instruction"
+                ),
+                data: Some(serde_json::json!(ComputeUnitPriceBelowMinimumErrorData {
+                    compute_unit_price,
+                    min_compute_unit_price,
+                    compute_units_threshold,
+                })),
+            },
         }
     }
 }
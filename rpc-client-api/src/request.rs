@@ -12,17 +12,23 @@ pub enum RpcRequest {
     Custom { method: &'static str },
     DeregisterNode,
     GetAccountInfo,
+    GetAccountInfoAtSlot,
+    GetAccountProof,
     GetBalance,
     GetBlock,
+    GetBlockFeeSummary,
     GetBlockHeight,
     GetBlockProduction,
     GetBlocks,
     GetBlocksWithLimit,
     GetBlockTime,
     GetClusterNodes,
+    GetConfirmationLatencyStats,
     GetEpochInfo,
+    GetEpochStakes,
     GetEpochSchedule,
     GetFeeForMessage,
+    GetFinalizedSignaturesForAddress,
     GetFirstAvailableBlock,
     GetGenesisHash,
     GetHealth,
@@ -33,25 +39,31 @@ pub enum RpcRequest {
     GetLargestAccounts,
     GetLatestBlockhash,
     GetLeaderSchedule,
+    GetLeaderScheduleWithStakes,
     GetMaxRetransmitSlot,
     GetMaxShredInsertSlot,
     GetMinimumBalanceForRentExemption,
     GetMultipleAccounts,
+    GetOptimisticConfirmationAttestations,
     GetProgramAccounts,
+    GetProgramFeeMarket,
     GetRecentPerformanceSamples,
     GetRecentPrioritizationFees,
     GetHighestSnapshotSlot,
     GetSignaturesForAddress,
     GetSignatureStatuses,
+    GetSlashingEvidence,
     GetSlot,
     GetSlotLeader,
     GetSlotLeaders,
     GetStorageTurn,
     GetStorageTurnRate,
     GetSlotsPerSegment,
+    GetStakeAccountsByVoteAccount,
     GetStakeMinimumDelegation,
     GetStoragePubkeysForSlot,
     GetSupply,
+    GetSupplyDeltas,
     GetTokenAccountBalance,
     GetTokenAccountsByDelegate,
     GetTokenAccountsByOwner,
@@ -59,15 +71,20 @@ pub enum RpcRequest {
     GetTokenSupply,
     GetTransaction,
     GetTransactionCount,
+    GetTransactionIngestionStatus,
     GetVersion,
     GetVoteAccounts,
+    GetVoteOverhead,
     IsBlockhashValid,
     MinimumLedgerSlot,
     RegisterNode,
+    RegisterWebhook,
     RequestAirdrop,
     SendTransaction,
+    SimulateBundle,
     SimulateTransaction,
     SignVote,
+    UnregisterWebhook,
 }
 
 #[allow(deprecated)]
@@ -77,17 +94,23 @@ impl fmt::Display for RpcRequest {
             RpcRequest::Custom { method } => method,
             RpcRequest::DeregisterNode => "deregisterNode",
             RpcRequest::GetAccountInfo => "getAccountInfo",
+            RpcRequest::GetAccountInfoAtSlot => "getAccountInfoAtSlot",
+            RpcRequest::GetAccountProof => "getAccountProof",
             RpcRequest::GetBalance => "getBalance",
             RpcRequest::GetBlock => "getBlock",
+            RpcRequest::GetBlockFeeSummary => "getBlockFeeSummary",
             RpcRequest::GetBlockHeight => "getBlockHeight",
             RpcRequest::GetBlockProduction => "getBlockProduction",
             RpcRequest::GetBlocks => "getBlocks",
             RpcRequest::GetBlocksWithLimit => "getBlocksWithLimit",
             RpcRequest::GetBlockTime => "getBlockTime",
             RpcRequest::GetClusterNodes => "getClusterNodes",
+            RpcRequest::GetConfirmationLatencyStats => "getConfirmationLatencyStats",
             RpcRequest::GetEpochInfo => "getEpochInfo",
+            RpcRequest::GetEpochStakes => "getEpochStakes",
             RpcRequest::GetEpochSchedule => "getEpochSchedule",
             RpcRequest::GetFeeForMessage => "getFeeForMessage",
+            RpcRequest::GetFinalizedSignaturesForAddress => "getFinalizedSignaturesForAddress",
             RpcRequest::GetFirstAvailableBlock => "getFirstAvailableBlock",
             RpcRequest::GetGenesisHash => "getGenesisHash",
             RpcRequest::GetHealth => "getHealth",
@@ -98,25 +121,33 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetLargestAccounts => "getLargestAccounts",
             RpcRequest::GetLatestBlockhash => "getLatestBlockhash",
             RpcRequest::GetLeaderSchedule => "getLeaderSchedule",
+            RpcRequest::GetLeaderScheduleWithStakes => "getLeaderScheduleWithStakes",
             RpcRequest::GetMaxRetransmitSlot => "getMaxRetransmitSlot",
             RpcRequest::GetMaxShredInsertSlot => "getMaxShredInsertSlot",
             RpcRequest::GetMinimumBalanceForRentExemption => "getMinimumBalanceForRentExemption",
             RpcRequest::GetMultipleAccounts => "getMultipleAccounts",
+            RpcRequest::GetOptimisticConfirmationAttestations => {
+                "getOptimisticConfirmationAttestations"
+            }
             RpcRequest::GetProgramAccounts => "getProgramAccounts",
+            RpcRequest::GetProgramFeeMarket => "getProgramFeeMarket",
             RpcRequest::GetRecentPerformanceSamples => "getRecentPerformanceSamples",
             RpcRequest::GetRecentPrioritizationFees => "getRecentPrioritizationFees",
             RpcRequest::GetHighestSnapshotSlot => "getHighestSnapshotSlot",
             RpcRequest::GetSignaturesForAddress => "getSignaturesForAddress",
             RpcRequest::GetSignatureStatuses => "getSignatureStatuses",
+            RpcRequest::GetSlashingEvidence => "getSlashingEvidence",
             RpcRequest::GetSlot => "getSlot",
             RpcRequest::GetSlotLeader => "getSlotLeader",
             RpcRequest::GetSlotLeaders => "getSlotLeaders",
+            RpcRequest::GetStakeAccountsByVoteAccount => "getStakeAccountsByVoteAccount",
             RpcRequest::GetStakeMinimumDelegation => "getStakeMinimumDelegation",
             RpcRequest::GetStorageTurn => "getStorageTurn",
             RpcRequest::GetStorageTurnRate => "getStorageTurnRate",
             RpcRequest::GetSlotsPerSegment => "getSlotsPerSegment",
             RpcRequest::GetStoragePubkeysForSlot => "getStoragePubkeysForSlot",
             RpcRequest::GetSupply => "getSupply",
+            RpcRequest::GetSupplyDeltas => "getSupplyDeltas",
             RpcRequest::GetTokenAccountBalance => "getTokenAccountBalance",
             RpcRequest::GetTokenAccountsByDelegate => "getTokenAccountsByDelegate",
             RpcRequest::GetTokenAccountsByOwner => "getTokenAccountsByOwner",
@@ -124,15 +155,20 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetTokenLargestAccounts => "getTokenLargestAccounts",
             RpcRequest::GetTransaction => "getTransaction",
             RpcRequest::GetTransactionCount => "getTransactionCount",
+            RpcRequest::GetTransactionIngestionStatus => "getTransactionIngestionStatus",
             RpcRequest::GetVersion => "getVersion",
             RpcRequest::GetVoteAccounts => "getVoteAccounts",
+            RpcRequest::GetVoteOverhead => "getVoteOverhead",
             RpcRequest::IsBlockhashValid => "isBlockhashValid",
             RpcRequest::MinimumLedgerSlot => "minimumLedgerSlot",
             RpcRequest::RegisterNode => "registerNode",
+            RpcRequest::RegisterWebhook => "registerWebhook",
             RpcRequest::RequestAirdrop => "requestAirdrop",
             RpcRequest::SendTransaction => "sendTransaction",
+            RpcRequest::SimulateBundle => "simulateBundle",
             RpcRequest::SimulateTransaction => "simulateTransaction",
             RpcRequest::SignVote => "signVote",
+            RpcRequest::UnregisterWebhook => "unregisterWebhook",
         };
 
         write!(f, "{method}")
@@ -148,6 +184,8 @@ pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
 pub const NUM_LARGEST_ACCOUNTS: usize = 20;
 pub const MAX_GET_PROGRAM_ACCOUNT_FILTERS: usize = 4;
 pub const MAX_GET_SLOT_LEADERS: usize = 5000;
+pub const MAX_GET_SUPPLY_DELTAS_LIMIT: usize = 1_000;
+pub const MAX_SIMULATE_BUNDLE_TRANSACTIONS: usize = 16;
 
 // Limit the length of the `epoch_credits` array for each validator in a `get_vote_accounts`
 // response
@@ -3,12 +3,13 @@ use {
     serde::{Deserialize, Deserializer, Serialize, Serializer},
     solana_account_decoder_client_types::{token::UiTokenAmount, UiAccount},
     solana_clock::{Epoch, Slot, UnixTimestamp},
+    solana_commitment_config::CommitmentLevel,
     solana_fee_calculator::{FeeCalculator, FeeRateGovernor},
     solana_inflation::Inflation,
     solana_transaction_error::{TransactionError, TransactionResult as Result},
     solana_transaction_status_client_types::{
-        ConfirmedTransactionStatusWithSignature, TransactionConfirmationStatus, UiConfirmedBlock,
-        UiInnerInstructions, UiTransactionReturnData,
+        ConfirmedTransactionStatusWithSignature, FeeBreakdown, TransactionConfirmationStatus,
+        UiConfirmedBlock, UiInnerInstructions, UiTransactionReturnData,
     },
     std::{collections::HashMap, fmt, net::SocketAddr, str::FromStr},
     thiserror::Error,
@@ -251,6 +252,13 @@ pub struct RpcLogsResponse {
 #[serde(rename_all = "camelCase")]
 pub struct ProcessedSignatureResult {
     pub err: Option<TransactionError>,
+    /// Fee charged to the transaction, left out if the node did not record its execution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_breakdown: Option<FeeBreakdown>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compute_units_consumed: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -310,6 +318,114 @@ pub struct RpcBlockProduction {
     pub range: RpcBlockProductionRange,
 }
 
+/// Transaction fees collected in a block, in lamports
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockFeeSummary {
+    /// Base58 identity pubkey of the leader of the block
+    pub leader: String,
+    pub transaction_fee: u64,
+    pub priority_fee: u64,
+    pub burned: u64,
+    /// Portion of the fees deposited into the account of the leader
+    pub leader_reward: u64,
+    /// Portion of the base fees deposited into the fee treasury instead of being burned
+    #[serde(default)]
+    pub treasury: u64,
+}
+
+/// The change of supply made by a frozen bank, by cause, in lamports
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSupplyDelta {
+    pub slot: Slot,
+    pub parent_slot: Slot,
+    pub capitalization: u64,
+    pub parent_capitalization: u64,
+    pub inflation_issued: u64,
+    pub fees_burned: u64,
+    pub rent_burned: u64,
+    pub incinerated: u64,
+    /// Minted by genesis accounts, feature activations and program migrations
+    pub other_minted: u64,
+    /// Removed by feature activations and program migrations
+    pub other_removed: u64,
+    /// Change of capitalization not attributed to any cause
+    pub unexplained: i64,
+    /// Inconsistencies found between the change of supply and the rewards, fees and rent of
+    /// the bank
+    pub violations: Vec<String>,
+}
+
+/// Costs of the blocks of an epoch and of their vote transactions, which pay no fees
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEpochVoteOverhead {
+    pub epoch: Epoch,
+    /// Number of blocks the costs are accounted over
+    pub slots: u64,
+    pub transaction_count: u64,
+    pub vote_transaction_count: u64,
+    pub signature_count: u64,
+    pub vote_signature_count: u64,
+    /// Compute units of the blocks
+    pub block_cost: u64,
+    /// Compute units of the vote transactions
+    pub vote_cost: u64,
+    /// Sum of the block cost limits of the blocks
+    pub block_cost_limit: u64,
+    /// Base fees the vote transactions would have paid at the current lamports per signature
+    pub implied_vote_fees: u64,
+}
+
+/// The vote overhead of the current epoch up to a slot, and of the previous epoch
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVoteOverhead {
+    pub slot: Slot,
+    pub current_epoch: RpcEpochVoteOverhead,
+    pub previous_epoch: Option<RpcEpochVoteOverhead>,
+}
+
+/// The nodes hashed together with a node at one level of a merkle tree
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMerkleProofLevel {
+    /// Position of the node among the nodes hashed together
+    pub index: usize,
+    /// Base58 hashes of the other nodes hashed together with the node, in order
+    pub siblings: Vec<String>,
+}
+
+/// Proof that an account had specific contents at a bank hash.
+///
+/// The account hash is folded with `proof`, from the lowest level up, into the accounts delta
+/// hash. The bank hash is the SHA-256 of the parent hash, the accounts delta hash, the
+/// little-endian signature count and the last blockhash; hashed again with the accounts
+/// lattice hash or the epoch accounts hash if present, then extended with the hard fork data
+/// if present.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountProof {
+    pub slot: Slot,
+    pub bank_hash: String,
+    pub parent_hash: String,
+    pub signature_count: u64,
+    pub last_blockhash: String,
+    pub accounts_delta_hash: String,
+    /// Base64 encoded accounts lattice hash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accounts_lt_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_accounts_hash: Option<String>,
+    /// Base64 encoded hard fork data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hard_fork_data: Option<String>,
+    /// The account as written in `slot`, base64 encoded. A deleted account has no lamports.
+    pub account: UiAccount,
+    pub proof: Vec<RpcMerkleProofLevel>,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct RpcVersionInfo {
@@ -409,6 +525,31 @@ pub struct RpcSimulateTransactionResult {
     pub replacement_blockhash: Option<RpcBlockhash>,
 }
 
+/// The simulation of a transaction of a bundle, fees in lamports
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulateBundleTransactionResult {
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: u64,
+    pub transaction_fee: u64,
+    pub priority_fee: u64,
+    pub return_data: Option<UiTransactionReturnData>,
+    pub inner_instructions: Option<Vec<UiInnerInstructions>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulateBundleResult {
+    /// The error of the first transaction which failed. A bundle only succeeds if all of its
+    /// transactions do.
+    pub err: Option<TransactionError>,
+    /// In the order of the bundle. Transactions after a failed one are still simulated, seeing
+    /// only the fees charged to it.
+    pub transaction_results: Vec<RpcSimulateBundleTransactionResult>,
+    pub replacement_blockhash: Option<RpcBlockhash>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcStorageTurn {
@@ -470,6 +611,176 @@ pub struct RpcPerfSample {
     pub sample_period_secs: u16,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLatencyStats {
+    pub num_slots: u64,
+    pub min_ms: u64,
+    pub mean_ms: u64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Latencies of recent slots, measured from the time the node received the
+/// first shred of each slot
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcConfirmationLatencyStats {
+    pub optimistic_confirmation: RpcLatencyStats,
+    pub finalization: RpcLatencyStats,
+}
+
+/// A gossiped vote transaction attesting to the bank hash of the last slot it votes for
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAttestation {
+    pub vote_pubkey: String,
+    /// Epoch stake of the vote account
+    pub stake: u64,
+    pub signature: String,
+    /// The signed vote transaction, base64 encoded
+    pub transaction: String,
+}
+
+/// The signed votes attesting to the bank hash of a slot, for light clients to verify its
+/// optimistic confirmation
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcOptimisticConfirmationAttestations {
+    pub slot: Slot,
+    pub bank_hash: String,
+    pub optimistically_confirmed: bool,
+    /// Total epoch stake of the slot's epoch
+    pub total_stake: u64,
+    pub attested_stake: u64,
+    /// Stake which must attest to the bank hash to optimistically confirm it
+    pub threshold_stake: u64,
+    pub attestations: Vec<RpcAttestation>,
+}
+
+/// Stake of a vote account in the stakes of an epoch
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEpochVoteAccountStake {
+    pub vote_pubkey: String,
+    pub node_pubkey: String,
+    pub stake: u64,
+    pub commission: u8,
+}
+
+/// The stake distribution used for the leader schedule and the votes of an epoch
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEpochStakes {
+    pub epoch: Epoch,
+    pub total_stake: u64,
+    /// Sorted by descending stake
+    pub vote_accounts: Vec<RpcEpochVoteAccountStake>,
+}
+
+/// A leader of an epoch, with its slots and stake
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLeaderStake {
+    pub identity: String,
+    /// Slot indices relative to the first slot of the epoch
+    pub slots: Vec<usize>,
+    /// Total stake of the vote accounts of the leader
+    pub stake: u64,
+    /// Vote accounts of the leader, by descending stake
+    pub vote_accounts: Vec<RpcEpochVoteAccountStake>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLeaderScheduleWithStakes {
+    pub epoch: Epoch,
+    pub first_slot: Slot,
+    pub total_stake: u64,
+    /// Sorted by descending stake
+    pub leaders: Vec<RpcLeaderStake>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcSlashingEvidenceKind {
+    /// The slot leader produced two conflicting shreds
+    DuplicateBlock,
+    /// A vote account voted for the slot with two different bank hashes
+    DuplicateVote,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSlashingEvidence {
+    pub slot: Slot,
+    pub kind: RpcSlashingEvidenceKind,
+    /// The slot leader for duplicate blocks, the vote account for duplicate
+    /// votes. `None` if the leader of the slot is unknown.
+    pub offender: Option<String>,
+    /// The two conflicting shreds or vote transactions, base64 encoded
+    pub proof: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcTransactionIngestionStage {
+    ReceivedViaRpc,
+    ReceivedViaTpu,
+    /// Dropped before execution, see the reason of the event
+    Filtered,
+    Forwarded,
+    Scheduled,
+    Executed,
+    Expired,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionIngestionEvent {
+    /// Milliseconds since the UNIX epoch
+    pub timestamp: u64,
+    pub stage: RpcTransactionIngestionStage,
+    pub slot: Option<Slot>,
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionIngestionStatus {
+    /// The most recent event, `None` if the node has no record of the transaction
+    pub last_seen: Option<RpcTransactionIngestionEvent>,
+    /// All recorded events, oldest first
+    pub events: Vec<RpcTransactionIngestionEvent>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcWebhookRegistration {
+    /// The id of the webhook, sent in the `X-Tachyon-Webhook-Id` header of every notification
+    pub id: String,
+    /// Hex encoded secret keying the HMAC-SHA256 signature of every notification, sent in the
+    /// `X-Tachyon-Signature` header
+    pub secret: String,
+}
+
+/// The body of a webhook notification, one per transaction referencing a watched address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcWebhookNotification {
+    pub webhook_id: String,
+    pub slot: Slot,
+    pub commitment: CommitmentLevel,
+    pub block_time: Option<UnixTimestamp>,
+    pub signature: String,
+    pub err: Option<TransactionError>,
+    pub fee: u64,
+    /// The watched addresses referenced by the transaction
+    pub addresses: Vec<String>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcInflationReward {
@@ -497,6 +808,55 @@ pub struct RpcBlockUpdate {
     pub err: Option<RpcBlockUpdateError>,
 }
 
+/// The change of the balance of a token account, in base units of its mint
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTokenBalanceChange {
+    pub account: String,
+    pub owner: String,
+    pub mint: String,
+    pub program_id: Option<String>,
+    pub decimals: u8,
+    pub pre_amount: String,
+    pub post_amount: String,
+}
+
+/// The token balances of subscribed owners changed by a finalized transaction
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTokenBalanceUpdate {
+    pub signature: String,
+    pub slot: Slot,
+    pub block_time: Option<UnixTimestamp>,
+    pub err: Option<TransactionError>,
+    pub changes: Vec<RpcTokenBalanceChange>,
+}
+
+/// A higher compute unit price recommended for a sent transaction which did not land
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcComputeUnitPriceRecommendation {
+    pub signature: String,
+    /// Leader rotations since the transaction was first retried
+    pub leader_rotations: u64,
+    pub compute_unit_price: u64,
+    pub recommended_compute_unit_price: u64,
+}
+
+/// Signatures of transactions at finalized slots, oldest first
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFinalizedSignaturesForAddress {
+    /// Highest finalized slot searched, no signature of a later slot is listed
+    pub finalized_slot: Slot,
+    /// Block height of `finalized_slot`
+    pub finalized_block_height: Option<u64>,
+    pub signatures: Vec<RpcConfirmedTransactionStatusWithSignature>,
+    /// Cursor to pass as `after` to list the signatures after the last one listed, or the given
+    /// cursor if no signature was listed
+    pub cursor: Option<String>,
+}
+
 impl From<ConfirmedTransactionStatusWithSignature> for RpcConfirmedTransactionStatusWithSignature {
     fn from(value: ConfirmedTransactionStatusWithSignature) -> Self {
         let ConfirmedTransactionStatusWithSignature {
@@ -530,6 +890,40 @@ pub struct RpcPrioritizationFee {
     pub prioritization_fee: u64,
 }
 
+/// Compute unit prices paid in a recent block by the transactions writing to the accounts owned by
+/// a program
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcProgramFee {
+    pub slot: Slot,
+    pub transactions: u64,
+    pub min_compute_unit_price: u64,
+    pub median_compute_unit_price: u64,
+    pub max_compute_unit_price: u64,
+}
+
+/// Compute unit prices paid in the recent blocks by the transactions writing to the accounts owned
+/// by a program
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcProgramFeeMarket {
+    pub program_id: String,
+    /// Number of recent blocks
+    pub blocks: u64,
+    /// Number of recent blocks including transactions writing to the accounts of the program
+    pub blocks_with_transactions: u64,
+    /// Share of the recent blocks including transactions writing to the accounts of the program
+    pub inclusion_rate: f64,
+    pub transactions: u64,
+    pub min_compute_unit_price: u64,
+    pub median_compute_unit_price: u64,
+    pub p90_compute_unit_price: u64,
+    pub max_compute_unit_price: u64,
+    /// The recent blocks including transactions writing to the accounts of the program, oldest
+    /// first
+    pub slots: Vec<RpcProgramFee>,
+}
+
 #[cfg(test)]
 pub mod tests {
 
@@ -593,4 +987,36 @@ pub mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    // Make sure that the fee failure errors are encoded by name, like the other unit variants.
+    #[test]
+    fn rpc_logs_response_fee_errors() {
+        for (err, name) in [
+            (TransactionError::FeeExceedsCap, "FeeExceedsCap"),
+            (
+                TransactionError::FeePayerInsufficientForBaseFee,
+                "FeePayerInsufficientForBaseFee",
+            ),
+            (
+                TransactionError::CongestionFeeRequired,
+                "CongestionFeeRequired",
+            ),
+        ] {
+            let response = RpcLogsResponse {
+                signature: "signature".to_string(),
+                err: Some(err),
+                logs: vec![],
+            };
+            let json = json!({
+                "signature": "signature",
+                "err": name,
+                "logs": [],
+            });
+            assert_eq!(serde_json::to_value(&response).unwrap(), json);
+            assert_eq!(
+                serde_json::from_value::<RpcLogsResponse>(json).unwrap(),
+                response
+            );
+        }
+    }
 }
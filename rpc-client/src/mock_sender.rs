@@ -17,12 +17,18 @@ use {
         config::RpcBlockProductionConfig,
         request::RpcRequest,
         response::{
-            Response, RpcAccountBalance, RpcBlockProduction, RpcBlockProductionRange, RpcBlockhash,
-            RpcConfirmedTransactionStatusWithSignature, RpcContactInfo, RpcIdentity,
-            RpcInflationGovernor, RpcInflationRate, RpcInflationReward, RpcKeyedAccount,
-            RpcPerfSample, RpcPrioritizationFee, RpcResponseContext, RpcSimulateTransactionResult,
-            RpcSnapshotSlotInfo, RpcSupply, RpcVersionInfo, RpcVoteAccountInfo,
-            RpcVoteAccountStatus,
+            Response, RpcAccountBalance, RpcAccountProof, RpcAttestation, RpcBlockFeeSummary,
+            RpcBlockProduction, RpcBlockProductionRange, RpcBlockhash, RpcConfirmationLatencyStats,
+            RpcConfirmedTransactionStatusWithSignature, RpcContactInfo, RpcEpochStakes,
+            RpcEpochVoteAccountStake, RpcEpochVoteOverhead, RpcFinalizedSignaturesForAddress,
+            RpcIdentity, RpcInflationGovernor, RpcInflationRate, RpcInflationReward,
+            RpcKeyedAccount, RpcLatencyStats, RpcLeaderScheduleWithStakes, RpcLeaderStake,
+            RpcMerkleProofLevel, RpcOptimisticConfirmationAttestations, RpcPerfSample,
+            RpcPrioritizationFee, RpcProgramFee, RpcProgramFeeMarket, RpcResponseContext,
+            RpcSimulateBundleResult, RpcSimulateTransactionResult, RpcSlashingEvidence,
+            RpcSnapshotSlotInfo, RpcSupply, RpcSupplyDelta, RpcTransactionIngestionStatus,
+            RpcVersionInfo, RpcVoteAccountInfo, RpcVoteAccountStatus, RpcVoteOverhead,
+            RpcWebhookRegistration,
         },
     },
     solana_signature::Signature,
@@ -110,6 +116,33 @@ impl RpcSender for MockSender {
                 context: RpcResponseContext { slot: 1, api_version: None },
                 value: Value::Null,
             })?,
+            "getAccountInfoAtSlot" => serde_json::to_value(Response {
+                context: RpcResponseContext { slot: 1, api_version: None },
+                value: Value::Null,
+            })?,
+            "getAccountProof" => serde_json::to_value(RpcAccountProof {
+                slot: 1,
+                bank_hash: "3kJ5rj5yKzoSm4kwEXZ4XRC5VfzBQy5pFQGmLqn5y1Rg".to_string(),
+                parent_hash: "8ejZq4dGD2aPvLMxkXkBpyGTDo5uVfrsiC1vYgz5qP9L".to_string(),
+                signature_count: 1,
+                last_blockhash: "4k5hKuyvnrKMhL7jdAe9yy4D4MQLG4GzSWTCxxCKGPrj".to_string(),
+                accounts_delta_hash: "6sEK6mH1t8aGmx9qFUNoJ1qWyvZZcHhSHYPU7mhzzY4N".to_string(),
+                accounts_lt_hash: None,
+                epoch_accounts_hash: None,
+                hard_fork_data: None,
+                account: UiAccount {
+                    lamports: 1_000_000,
+                    data: UiAccountData::Binary(String::new(), UiAccountEncoding::Base64),
+                    owner: PUBKEY.to_string(),
+                    executable: false,
+                    rent_epoch: 0,
+                    space: Some(0),
+                },
+                proof: vec![RpcMerkleProofLevel {
+                    index: 0,
+                    siblings: vec![],
+                }],
+            })?,
             "getBalance" => serde_json::to_value(Response {
                 context: RpcResponseContext { slot: 1, api_version: None },
                 value: Value::Number(Number::from(50)),
@@ -199,6 +232,7 @@ impl RpcSender for MockSender {
                             loaded_addresses: OptionSerializer::Skip,
                             return_data: OptionSerializer::Skip,
                             compute_units_consumed: OptionSerializer::Skip,
+                            fee_breakdown: OptionSerializer::Skip,
                         }),
                 },
                 block_time: Some(1628633791),
@@ -309,6 +343,14 @@ impl RpcSender for MockSender {
                     replacement_blockhash: None
                 },
             })?,
+            "simulateBundle" => serde_json::to_value(Response {
+                context: RpcResponseContext { slot: 1, api_version: None },
+                value: RpcSimulateBundleResult {
+                    err: None,
+                    transaction_results: vec![],
+                    replacement_blockhash: None,
+                },
+            })?,
             "getMinimumBalanceForRentExemption" => json![20],
             "getVersion" => {
                 let version = Version::default();
@@ -377,6 +419,21 @@ impl RpcSender for MockSender {
                     confirmation_status: Some(TransactionConfirmationStatus::Finalized),
                 }])?
             }
+            "getFinalizedSignaturesForAddress" => {
+                serde_json::to_value(RpcFinalizedSignaturesForAddress {
+                    finalized_slot: 123,
+                    finalized_block_height: Some(120),
+                    signatures: vec![RpcConfirmedTransactionStatusWithSignature {
+                        signature: crate::mock_sender_for_cli::SIGNATURE.to_string(),
+                        slot: 123,
+                        err: None,
+                        memo: None,
+                        block_time: None,
+                        confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+                    }],
+                    cursor: Some(format!("123:0:{}", crate::mock_sender_for_cli::SIGNATURE)),
+                })?
+            }
             "getBlockTime" => serde_json::to_value(UnixTimestamp::default())?,
             "getEpochSchedule" => serde_json::to_value(EpochSchedule::default())?,
             "getRecentPerformanceSamples" => serde_json::to_value(vec![RpcPerfSample {
@@ -390,6 +447,124 @@ impl RpcSender for MockSender {
                 slot: 123_456_789,
                 prioritization_fee: 10_000,
             }])?,
+            "getProgramFeeMarket" => serde_json::to_value(RpcProgramFeeMarket {
+                program_id: PUBKEY.to_string(),
+                blocks: 150,
+                blocks_with_transactions: 1,
+                inclusion_rate: 1.0 / 150.0,
+                transactions: 2,
+                min_compute_unit_price: 1_000,
+                median_compute_unit_price: 1_000,
+                p90_compute_unit_price: 1_000,
+                max_compute_unit_price: 5_000,
+                slots: vec![RpcProgramFee {
+                    slot: 123_456_789,
+                    transactions: 2,
+                    min_compute_unit_price: 1_000,
+                    median_compute_unit_price: 1_000,
+                    max_compute_unit_price: 5_000,
+                }],
+            })?,
+            "getSlashingEvidence" => serde_json::to_value(Vec::<RpcSlashingEvidence>::new())?,
+            "getTransactionIngestionStatus" => {
+                serde_json::to_value(RpcTransactionIngestionStatus::default())?
+            }
+            "getSupplyDeltas" => serde_json::to_value(vec![RpcSupplyDelta {
+                slot: 123,
+                parent_slot: 122,
+                capitalization: 1_000_000,
+                parent_capitalization: 1_000_005,
+                fees_burned: 5,
+                ..RpcSupplyDelta::default()
+            }])?,
+            "getVoteOverhead" => serde_json::to_value(RpcVoteOverhead {
+                slot: 123,
+                current_epoch: RpcEpochVoteOverhead {
+                    slots: 100,
+                    vote_transaction_count: 1_000,
+                    vote_signature_count: 1_000,
+                    vote_cost: 3_428_000,
+                    ..RpcEpochVoteOverhead::default()
+                },
+                previous_epoch: None,
+            })?,
+            "getBlockFeeSummary" => serde_json::to_value(Some(RpcBlockFeeSummary {
+                leader: PUBKEY.to_string(),
+                transaction_fee: 10_000,
+                priority_fee: 2_000,
+                burned: 4_000,
+                leader_reward: 7_000,
+                treasury: 1_000,
+            }))?,
+            "getConfirmationLatencyStats" => serde_json::to_value(RpcConfirmationLatencyStats {
+                optimistic_confirmation: RpcLatencyStats {
+                    num_slots: 1,
+                    min_ms: 800,
+                    mean_ms: 800,
+                    p50_ms: 800,
+                    p90_ms: 800,
+                    p99_ms: 800,
+                    max_ms: 800,
+                },
+                finalization: RpcLatencyStats {
+                    num_slots: 1,
+                    min_ms: 12_800,
+                    mean_ms: 12_800,
+                    p50_ms: 12_800,
+                    p90_ms: 12_800,
+                    p99_ms: 12_800,
+                    max_ms: 12_800,
+                },
+            })?,
+            "getOptimisticConfirmationAttestations" => {
+                serde_json::to_value(Some(RpcOptimisticConfirmationAttestations {
+                    slot: 1,
+                    bank_hash: "3kJ5rj5yKzoSm4kwEXZ4XRC5VfzBQy5pFQGmLqn5y1Rg".to_string(),
+                    optimistically_confirmed: true,
+                    total_stake: 100,
+                    attested_stake: 70,
+                    threshold_stake: 67,
+                    attestations: vec![RpcAttestation {
+                        vote_pubkey: PUBKEY.to_string(),
+                        stake: 70,
+                        signature: "43yNSFC6fYTuPgTNFFhF4axw7AfWxB2BPdurme8yrsWEYwm8299xh8n6TAHjGymiSub1XtyxTNyd9GBfY2hxoBw8".to_string(),
+                        transaction: String::new(),
+                    }],
+                }))?
+            }
+            "getEpochStakes" => serde_json::to_value(RpcEpochStakes {
+                epoch: 1,
+                total_stake: 100,
+                vote_accounts: vec![RpcEpochVoteAccountStake {
+                    vote_pubkey: PUBKEY.to_string(),
+                    node_pubkey: PUBKEY.to_string(),
+                    stake: 100,
+                    commission: 0,
+                }],
+            })?,
+            "getLeaderScheduleWithStakes" => {
+                serde_json::to_value(Some(RpcLeaderScheduleWithStakes {
+                    epoch: 1,
+                    first_slot: 432_000,
+                    total_stake: 100,
+                    leaders: vec![RpcLeaderStake {
+                        identity: PUBKEY.to_string(),
+                        slots: vec![0, 1, 2, 3],
+                        stake: 100,
+                        vote_accounts: vec![RpcEpochVoteAccountStake {
+                            vote_pubkey: PUBKEY.to_string(),
+                            node_pubkey: PUBKEY.to_string(),
+                            stake: 100,
+                            commission: 0,
+                        }],
+                    }],
+                }))?
+            }
+            "registerWebhook" => serde_json::to_value(RpcWebhookRegistration {
+                id: "42".to_string(),
+                secret: "00".repeat(32),
+            })?,
+            "unregisterWebhook" => Value::Bool(true),
             "getIdentity" => serde_json::to_value(RpcIdentity {
                 identity: PUBKEY.to_string(),
             })?,
@@ -422,6 +597,16 @@ impl RpcSender for MockSender {
                 context: RpcResponseContext { slot: 1, api_version: None },
                 value: vec![Value::Null, Value::Null]
             })?,
+            "getStakeAccountsByVoteAccount" => {
+                let pubkey = Pubkey::from_str(PUBKEY).unwrap();
+                serde_json::to_value(Response {
+                    context: RpcResponseContext { slot: 1, api_version: None },
+                    value: vec![RpcKeyedAccount {
+                        pubkey: PUBKEY.to_string(),
+                        account: mock_encoded_account(&pubkey),
+                    }],
+                })?
+            },
             "getProgramAccounts" => {
                 let pubkey = Pubkey::from_str(PUBKEY).unwrap();
                 serde_json::to_value(vec![
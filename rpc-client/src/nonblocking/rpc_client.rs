@@ -1303,6 +1303,87 @@ impl RpcClient {
         .await
     }
 
+    /// Simulates sending an ordered bundle of transactions.
+    ///
+    /// Each transaction sees the accounts written by the transactions before
+    /// it. The [`err`] field of the returned [`RpcSimulateBundleResult`] is the
+    /// error of the first transaction which failed, and the logs, compute units
+    /// and fees of each transaction are returned in the
+    /// [`transaction_results`] field.
+    ///
+    /// [`err`]: solana_rpc_client_api::response::RpcSimulateBundleResult::err
+    /// [`transaction_results`]: solana_rpc_client_api::response::RpcSimulateBundleResult::transaction_results
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `simulateBundle` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_keypair::Keypair;
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_signer::Signer;
+    /// # use solana_system_transaction as system_transaction;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// // Alice funds Bob, who pays Carol
+    /// #     let alice = Keypair::new();
+    /// #     let bob = Keypair::new();
+    /// #     let carol = Keypair::new();
+    /// let latest_blockhash = rpc_client.get_latest_blockhash().await?;
+    /// let fund_bob = system_transaction::transfer(&alice, &bob.pubkey(), 100, latest_blockhash);
+    /// let pay_carol = system_transaction::transfer(&bob, &carol.pubkey(), 50, latest_blockhash);
+    /// let result = rpc_client.simulate_bundle(&[fund_bob, pay_carol]).await?;
+    /// assert!(result.value.err.is_none());
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn simulate_bundle(
+        &self,
+        transactions: &[impl SerializableTransaction],
+    ) -> RpcResult<RpcSimulateBundleResult> {
+        self.simulate_bundle_with_config(
+            transactions,
+            RpcSimulateBundleConfig {
+                commitment: Some(self.commitment()),
+                ..RpcSimulateBundleConfig::default()
+            },
+        )
+        .await
+    }
+
+    /// Simulates sending an ordered bundle of transactions, with the
+    /// options of [`RpcSimulateBundleConfig`].
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `simulateBundle` RPC method.
+    pub async fn simulate_bundle_with_config(
+        &self,
+        transactions: &[impl SerializableTransaction],
+        config: RpcSimulateBundleConfig,
+    ) -> RpcResult<RpcSimulateBundleResult> {
+        let encoding = config.encoding.unwrap_or(UiTransactionEncoding::Base64);
+        let commitment = config.commitment.unwrap_or_default();
+        let config = RpcSimulateBundleConfig {
+            encoding: Some(encoding),
+            commitment: Some(commitment),
+            ..config
+        };
+        let serialized_encoded = transactions
+            .iter()
+            .map(|transaction| serialize_and_encode(transaction, encoding))
+            .collect::<ClientResult<Vec<_>>>()?;
+        self.send(
+            RpcRequest::SimulateBundle,
+            json!([serialized_encoded, config]),
+        )
+        .await
+    }
+
     /// Returns the highest slot information that the node has snapshots for.
     ///
     /// This will find the highest full snapshot slot, and the highest incremental snapshot slot
@@ -2683,6 +2764,9 @@ impl RpcClient {
             limit: config.limit,
             commitment: config.commitment,
             min_context_slot: None,
+            min_block_time: None,
+            max_block_time: None,
+            program_id: None,
         };
 
         let result: Vec<RpcConfirmedTransactionStatusWithSignature> = self
@@ -2695,6 +2779,55 @@ impl RpcClient {
         Ok(result)
     }
 
+    /// Returns the signatures of transactions at finalized slots that
+    /// reference `address`, oldest first.
+    ///
+    /// Passing the `cursor` of a response as the `after` of the next call
+    /// lists the signatures that follow. As signatures are only listed once
+    /// their slot is finalized, a deposit scanner resuming from its last
+    /// cursor never misses or repeats a signature, nor sees one that is rolled
+    /// back afterwards.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getFinalizedSignaturesForAddress`
+    /// RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{
+    /// #     client_error::Error,
+    /// #     config::RpcFinalizedSignaturesForAddressConfig,
+    /// # };
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let alice = Keypair::new();
+    /// let mut config = RpcFinalizedSignaturesForAddressConfig::default();
+    /// let signatures = rpc_client
+    ///     .get_finalized_signatures_for_address(&alice.pubkey(), config.clone())
+    ///     .await?;
+    /// // Resume after the last signature on the next call
+    /// config.after = signatures.cursor;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_finalized_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        config: RpcFinalizedSignaturesForAddressConfig,
+    ) -> ClientResult<RpcFinalizedSignaturesForAddress> {
+        self.send(
+            RpcRequest::GetFinalizedSignaturesForAddress,
+            json!([address.to_string(), config]),
+        )
+        .await
+    }
+
     /// Returns transaction details for a confirmed transaction.
     ///
     /// This method uses the [`Finalized`] [commitment level][cl].
@@ -3129,6 +3262,613 @@ impl RpcClient {
             .await
     }
 
+    /// Returns the compute unit prices paid in the recent blocks by the
+    /// transactions writing to the accounts owned by `program_id`, and the
+    /// share of these blocks including such transactions.
+    ///
+    /// The node must be started with `--enable-rpc-program-fee-market`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getProgramFeeMarket` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_pubkey::Pubkey;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let program_id = Pubkey::new_unique();
+    /// let fee_market = rpc_client.get_program_fee_market(&program_id).await?;
+    /// println!(
+    ///     "p90 price: {}, inclusion rate: {}",
+    ///     fee_market.p90_compute_unit_price, fee_market.inclusion_rate,
+    /// );
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_program_fee_market(
+        &self,
+        program_id: &Pubkey,
+    ) -> ClientResult<RpcProgramFeeMarket> {
+        self.send(
+            RpcRequest::GetProgramFeeMarket,
+            json!([program_id.to_string()]),
+        )
+        .await
+    }
+
+    /// Returns evidence of duplicate blocks and duplicate votes recorded by
+    /// the node, in slot order.
+    ///
+    /// Evidence is only recorded, no penalties are applied.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getSlashingEvidence` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{client_error::Error, config::RpcSlashingEvidenceConfig};
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let evidence = rpc_client.get_slashing_evidence(
+    ///     RpcSlashingEvidenceConfig {
+    ///         start_slot: Some(1_000),
+    ///         limit: Some(10),
+    ///     },
+    /// ).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_slashing_evidence(
+        &self,
+        config: RpcSlashingEvidenceConfig,
+    ) -> ClientResult<Vec<RpcSlashingEvidence>> {
+        self.send(RpcRequest::GetSlashingEvidence, json!([config]))
+            .await
+    }
+
+    /// Returns where the node last saw a transaction, and the lifecycle events
+    /// it recorded for it.
+    ///
+    /// Useful to find out why a transaction never landed. The node keeps a
+    /// bounded log of recent events and must be started with
+    /// `--enable-transaction-ingestion-log`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getTransactionIngestionStatus` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_signature::Signature;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let signature = Signature::default();
+    /// let status = rpc_client.get_transaction_ingestion_status(&signature).await?;
+    /// if let Some(event) = status.last_seen {
+    ///     println!("last seen: {:?} {:?}", event.stage, event.reason);
+    /// }
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_transaction_ingestion_status(
+        &self,
+        signature: &Signature,
+    ) -> ClientResult<RpcTransactionIngestionStatus> {
+        self.send(
+            RpcRequest::GetTransactionIngestionStatus,
+            json!([signature.to_string()]),
+        )
+        .await
+    }
+
+    /// Returns the audited change of supply of the recent frozen banks from
+    /// `start_slot` on, oldest first, up to `limit` of them.
+    ///
+    /// The node keeps the audits of a bounded number of banks and must be
+    /// started with `--supply-audit`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getSupplyDeltas` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// for delta in rpc_client.get_supply_deltas(None, Some(10)).await? {
+    ///     println!("slot {}: {} lamports burned", delta.slot, delta.fees_burned);
+    /// }
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_supply_deltas(
+        &self,
+        start_slot: Option<Slot>,
+        limit: Option<usize>,
+    ) -> ClientResult<Vec<RpcSupplyDelta>> {
+        self.send(RpcRequest::GetSupplyDeltas, json!([start_slot, limit]))
+            .await
+    }
+
+    /// Returns the costs of the vote transactions of the current and previous
+    /// epochs, which pay no fees, against the costs of the blocks.
+    ///
+    /// This method uses the configured default [commitment level][cl].
+    ///
+    /// [cl]: https://solana.com/docs/rpc#configuring-state-commitment
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getVoteOverhead` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let vote_overhead = rpc_client.get_vote_overhead().await?;
+    /// println!(
+    ///     "{} of {} CUs used by votes",
+    ///     vote_overhead.current_epoch.vote_cost,
+    ///     vote_overhead.current_epoch.block_cost,
+    /// );
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_vote_overhead(&self) -> ClientResult<RpcVoteOverhead> {
+        self.get_vote_overhead_with_commitment(self.commitment())
+            .await
+    }
+
+    /// Returns the costs of the vote transactions of the current and previous
+    /// epochs, which pay no fees, against the costs of the blocks.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getVoteOverhead` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_commitment_config::CommitmentConfig;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let vote_overhead = rpc_client
+    ///     .get_vote_overhead_with_commitment(CommitmentConfig::finalized())
+    ///     .await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_vote_overhead_with_commitment(
+        &self,
+        commitment_config: CommitmentConfig,
+    ) -> ClientResult<RpcVoteOverhead> {
+        self.send(RpcRequest::GetVoteOverhead, json!([commitment_config]))
+            .await
+    }
+
+    /// Returns the account of `pubkey` as it was at the end of `slot`, which
+    /// must be rooted.
+    ///
+    /// The node must archive the accounts written by each block; archival is
+    /// disabled by default because of its storage cost. Returns `None` if the
+    /// account did not exist at `slot`, or was not written by any block still
+    /// kept in the ledger of the node.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getAccountInfoAtSlot` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let alice = Keypair::new();
+    /// let slot = 100;
+    /// let account = rpc_client.get_account_at_slot(&alice.pubkey(), slot).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_account_at_slot(
+        &self,
+        pubkey: &Pubkey,
+        slot: Slot,
+    ) -> ClientResult<Option<Account>> {
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64Zstd),
+            ..RpcAccountInfoConfig::default()
+        };
+        let Response { value, .. } = self
+            .send::<Response<Option<UiAccount>>>(
+                RpcRequest::GetAccountInfoAtSlot,
+                json!([pubkey.to_string(), slot, config]),
+            )
+            .await?;
+        Ok(value.and_then(|account| account.decode()))
+    }
+
+    /// Returns the transaction fees collected in a block, and how they were
+    /// split between the leader and the burn.
+    ///
+    /// The summary is recorded when the bank of the slot is frozen, so it
+    /// remains available for as long as the node keeps the slot in its ledger.
+    /// Returns `None` if the node has no summary for `slot`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getBlockFeeSummary` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let slot = rpc_client.get_slot().await?;
+    /// if let Some(fee_summary) = rpc_client.get_block_fee_summary(slot).await? {
+    ///     println!("burned {} lamports", fee_summary.burned);
+    /// }
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_block_fee_summary(
+        &self,
+        slot: Slot,
+    ) -> ClientResult<Option<RpcBlockFeeSummary>> {
+        self.send(RpcRequest::GetBlockFeeSummary, json!([slot]))
+            .await
+    }
+
+    /// Returns the proof that the account of `pubkey` had its contents at the
+    /// bank hash of `slot`, for light clients and bridges to verify without a
+    /// full node.
+    ///
+    /// The bank hash of a slot only commits to the accounts written in that
+    /// slot, so the account must have been written in `slot`: an account which
+    /// was not modified is proven at the last slot which wrote it. The bank of
+    /// `slot` must also still be held by the node, so proofs are only available
+    /// for recent slots, and none are available once the
+    /// `remove_accounts_delta_hash` feature is active.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getAccountProof` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let alice = Keypair::new();
+    /// let slot = rpc_client.get_slot().await?;
+    /// let proof = rpc_client.get_account_proof(&alice.pubkey(), slot).await?;
+    /// println!("bank hash: {}", proof.bank_hash);
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_account_proof(
+        &self,
+        pubkey: &Pubkey,
+        slot: Slot,
+    ) -> ClientResult<RpcAccountProof> {
+        self.send(
+            RpcRequest::GetAccountProof,
+            json!([pubkey.to_string(), slot]),
+        )
+        .await
+    }
+
+    /// Returns the signed gossip votes attesting to the bank hash of `slot`,
+    /// for light clients and bridges to verify its optimistic confirmation
+    /// without trusting the node.
+    ///
+    /// Each attestation is a vote transaction whose last voted slot is `slot`.
+    /// Clients should verify the signatures of the vote transactions, and
+    /// that the attested stake reaches the threshold stake. Returns `None` if
+    /// the node has no attestations for `slot`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getOptimisticConfirmationAttestations` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let slot = rpc_client.get_slot().await?;
+    /// if let Some(attestations) = rpc_client
+    ///     .get_optimistic_confirmation_attestations(slot)
+    ///     .await?
+    /// {
+    ///     println!(
+    ///         "{} of {} stake attests to {}",
+    ///         attestations.attested_stake, attestations.total_stake, attestations.bank_hash,
+    ///     );
+    /// }
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_optimistic_confirmation_attestations(
+        &self,
+        slot: Slot,
+    ) -> ClientResult<Option<RpcOptimisticConfirmationAttestations>> {
+        self.send(
+            RpcRequest::GetOptimisticConfirmationAttestations,
+            json!([slot]),
+        )
+        .await
+    }
+
+    /// Returns the stake distribution of `epoch`, or of the current epoch, as
+    /// held by the node for the leader schedule and the votes of the epoch.
+    ///
+    /// Only the stakes of recent and upcoming epochs are held.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getEpochStakes` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let epoch_stakes = rpc_client.get_epoch_stakes(None).await?;
+    /// for vote_account in &epoch_stakes.vote_accounts {
+    ///     println!("{}: {}", vote_account.vote_pubkey, vote_account.stake);
+    /// }
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_epoch_stakes(&self, epoch: Option<Epoch>) -> ClientResult<RpcEpochStakes> {
+        self.send(RpcRequest::GetEpochStakes, json!([epoch])).await
+    }
+
+    /// Returns the leader schedule of `epoch`, or of the current epoch, with
+    /// the stake and the vote accounts of each leader. Returns `None` if the
+    /// leader schedule of the epoch is not known.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getLeaderScheduleWithStakes` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// if let Some(schedule) = rpc_client.get_leader_schedule_with_stakes(None).await? {
+    ///     for leader in &schedule.leaders {
+    ///         println!(
+    ///             "{}: {} slots, {} stake",
+    ///             leader.identity,
+    ///             leader.slots.len(),
+    ///             leader.stake,
+    ///         );
+    ///     }
+    /// }
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_leader_schedule_with_stakes(
+        &self,
+        epoch: Option<Epoch>,
+    ) -> ClientResult<Option<RpcLeaderScheduleWithStakes>> {
+        self.send(RpcRequest::GetLeaderScheduleWithStakes, json!([epoch]))
+            .await
+    }
+
+    /// Returns the stake accounts delegated to a vote account.
+    ///
+    /// Nodes with the `stake-voter` account index enabled serve this method
+    /// without scanning every stake account.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getStakeAccountsByVoteAccount` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_pubkey::Pubkey;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let vote_pubkey = Pubkey::new_unique();
+    /// let stake_accounts = rpc_client
+    ///     .get_stake_accounts_by_vote_account(&vote_pubkey)
+    ///     .await?;
+    /// for (stake_pubkey, stake_account) in &stake_accounts {
+    ///     println!("{stake_pubkey}: {}", stake_account.lamports);
+    /// }
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_stake_accounts_by_vote_account(
+        &self,
+        vote_pubkey: &Pubkey,
+    ) -> ClientResult<Vec<(Pubkey, Account)>> {
+        Ok(self
+            .get_stake_accounts_by_vote_account_with_config(
+                vote_pubkey,
+                RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64Zstd),
+                    ..RpcAccountInfoConfig::default()
+                },
+            )
+            .await?
+            .value)
+    }
+
+    /// Returns the stake accounts delegated to a vote account, with the
+    /// context of the response.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getStakeAccountsByVoteAccount` RPC method.
+    pub async fn get_stake_accounts_by_vote_account_with_config(
+        &self,
+        vote_pubkey: &Pubkey,
+        mut config: RpcAccountInfoConfig,
+    ) -> RpcResult<Vec<(Pubkey, Account)>> {
+        config.commitment = Some(config.commitment.unwrap_or_else(|| self.commitment()));
+        let Response { context, value } = self
+            .send::<Response<Vec<RpcKeyedAccount>>>(
+                RpcRequest::GetStakeAccountsByVoteAccount,
+                json!([vote_pubkey.to_string(), config]),
+            )
+            .await?;
+        Ok(Response {
+            context,
+            value: parse_keyed_accounts(value, RpcRequest::GetStakeAccountsByVoteAccount)?,
+        })
+    }
+
+    /// Returns the latencies of recent slots to reach optimistic confirmation
+    /// and to be finalized, measured from the time the node received the first
+    /// shred of each slot.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getConfirmationLatencyStats` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let stats = rpc_client.get_confirmation_latency_stats().await?;
+    /// println!(
+    ///     "p90 time to optimistic confirmation: {}ms",
+    ///     stats.optimistic_confirmation.p90_ms,
+    /// );
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_confirmation_latency_stats(
+        &self,
+    ) -> ClientResult<RpcConfirmationLatencyStats> {
+        self.send(RpcRequest::GetConfirmationLatencyStats, Value::Null)
+            .await
+    }
+
+    /// Registers a webhook that is sent a notification for every transaction referencing one of
+    /// `addresses`, once its block reaches the commitment of `config`.
+    ///
+    /// Notifications are signed with the returned secret, which is also needed to unregister the
+    /// webhook. Webhooks are only available on nodes that enable them.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `registerWebhook` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{client_error::Error, config::RpcRegisterWebhookConfig};
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_commitment_config::CommitmentConfig;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let registration = rpc_client.register_webhook(
+    ///     "https://example.com/webhook",
+    ///     &[solana_pubkey::new_rand()],
+    ///     RpcRegisterWebhookConfig {
+    ///         commitment: Some(CommitmentConfig::confirmed()),
+    ///     },
+    /// ).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn register_webhook(
+        &self,
+        url: &str,
+        addresses: &[Pubkey],
+        config: RpcRegisterWebhookConfig,
+    ) -> ClientResult<RpcWebhookRegistration> {
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.to_string())
+            .collect();
+        self.send(RpcRequest::RegisterWebhook, json!([url, addresses, config]))
+            .await
+    }
+
+    /// Unregisters a webhook, returning `false` if no webhook matches `id` and `secret`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `unregisterWebhook` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let unregistered = rpc_client.unregister_webhook("42", "00").await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn unregister_webhook(&self, id: &str, secret: &str) -> ClientResult<bool> {
+        self.send(RpcRequest::UnregisterWebhook, json!([id, secret]))
+            .await
+    }
+
     /// Returns the identity pubkey for the current node.
     ///
     /// # RPC Reference
@@ -1129,6 +1129,62 @@ impl RpcClient {
         )
     }
 
+    /// Simulates sending an ordered bundle of transactions.
+    ///
+    /// Each transaction sees the accounts written by the transactions before
+    /// it. The [`err`] field of the returned [`RpcSimulateBundleResult`] is the
+    /// error of the first transaction which failed, and the logs, compute units
+    /// and fees of each transaction are returned in the
+    /// [`transaction_results`] field.
+    ///
+    /// [`err`]: solana_rpc_client_api::response::RpcSimulateBundleResult::err
+    /// [`transaction_results`]: solana_rpc_client_api::response::RpcSimulateBundleResult::transaction_results
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `simulateBundle` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_keypair::Keypair;
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_signer::Signer;
+    /// # use solana_system_transaction as system_transaction;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// // Alice funds Bob, who pays Carol
+    /// # let alice = Keypair::new();
+    /// # let bob = Keypair::new();
+    /// # let carol = Keypair::new();
+    /// let latest_blockhash = rpc_client.get_latest_blockhash()?;
+    /// let fund_bob = system_transaction::transfer(&alice, &bob.pubkey(), 100, latest_blockhash);
+    /// let pay_carol = system_transaction::transfer(&bob, &carol.pubkey(), 50, latest_blockhash);
+    /// let result = rpc_client.simulate_bundle(&[fund_bob, pay_carol])?;
+    /// assert!(result.value.err.is_none());
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn simulate_bundle(
+        &self,
+        transactions: &[impl SerializableTransaction],
+    ) -> RpcResult<RpcSimulateBundleResult> {
+        self.invoke((self.rpc_client.as_ref()).simulate_bundle(transactions))
+    }
+
+    /// Simulates sending an ordered bundle of transactions, with the
+    /// options of [`RpcSimulateBundleConfig`].
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `simulateBundle` RPC method.
+    pub fn simulate_bundle_with_config(
+        &self,
+        transactions: &[impl SerializableTransaction],
+        config: RpcSimulateBundleConfig,
+    ) -> RpcResult<RpcSimulateBundleResult> {
+        self.invoke((self.rpc_client.as_ref()).simulate_bundle_with_config(transactions, config))
+    }
+
     /// Returns the highest slot information that the node has snapshots for.
     ///
     /// This will find the highest full snapshot slot, and the highest incremental snapshot slot
@@ -2300,6 +2356,49 @@ impl RpcClient {
         )
     }
 
+    /// Returns the signatures of transactions at finalized slots that
+    /// reference `address`, oldest first.
+    ///
+    /// Passing the `cursor` of a response as the `after` of the next call
+    /// lists the signatures that follow. As signatures are only listed once
+    /// their slot is finalized, a deposit scanner resuming from its last
+    /// cursor never misses or repeats a signature, nor sees one that is rolled
+    /// back afterwards.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getFinalizedSignaturesForAddress`
+    /// RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{
+    /// #     client_error::Error,
+    /// #     config::RpcFinalizedSignaturesForAddressConfig,
+    /// # };
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let alice = Keypair::new();
+    /// let mut config = RpcFinalizedSignaturesForAddressConfig::default();
+    /// let signatures =
+    ///     rpc_client.get_finalized_signatures_for_address(&alice.pubkey(), config.clone())?;
+    /// // Resume after the last signature on the next call
+    /// config.after = signatures.cursor;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_finalized_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        config: RpcFinalizedSignaturesForAddressConfig,
+    ) -> ClientResult<RpcFinalizedSignaturesForAddress> {
+        self.invoke(
+            (self.rpc_client.as_ref()).get_finalized_signatures_for_address(address, config),
+        )
+    }
+
     /// Returns transaction details for a confirmed transaction.
     ///
     /// This method uses the [`Finalized`] [commitment level][cl].
@@ -2666,6 +2765,492 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_recent_prioritization_fees(addresses))
     }
 
+    /// Returns the compute unit prices paid in the recent blocks by the
+    /// transactions writing to the accounts owned by `program_id`, and the
+    /// share of these blocks including such transactions.
+    ///
+    /// The node must be started with `--enable-rpc-program-fee-market`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getProgramFeeMarket` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_pubkey::Pubkey;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let program_id = Pubkey::new_unique();
+    /// let fee_market = rpc_client.get_program_fee_market(&program_id)?;
+    /// println!(
+    ///     "p90 price: {}, inclusion rate: {}",
+    ///     fee_market.p90_compute_unit_price, fee_market.inclusion_rate,
+    /// );
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_program_fee_market(&self, program_id: &Pubkey) -> ClientResult<RpcProgramFeeMarket> {
+        self.invoke((self.rpc_client.as_ref()).get_program_fee_market(program_id))
+    }
+
+    /// Returns evidence of duplicate blocks and duplicate votes recorded by
+    /// the node, in slot order.
+    ///
+    /// Evidence is only recorded, no penalties are applied.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getSlashingEvidence` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{client_error::Error, config::RpcSlashingEvidenceConfig};
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let evidence = rpc_client.get_slashing_evidence(
+    ///     RpcSlashingEvidenceConfig {
+    ///         start_slot: Some(1_000),
+    ///         limit: Some(10),
+    ///     },
+    /// )?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_slashing_evidence(
+        &self,
+        config: RpcSlashingEvidenceConfig,
+    ) -> ClientResult<Vec<RpcSlashingEvidence>> {
+        self.invoke((self.rpc_client.as_ref()).get_slashing_evidence(config))
+    }
+
+    /// Returns where the node last saw a transaction, and the lifecycle events
+    /// it recorded for it.
+    ///
+    /// Useful to find out why a transaction never landed. The node keeps a
+    /// bounded log of recent events and must be started with
+    /// `--enable-transaction-ingestion-log`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getTransactionIngestionStatus` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_signature::Signature;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let signature = Signature::default();
+    /// let status = rpc_client.get_transaction_ingestion_status(&signature)?;
+    /// if let Some(event) = status.last_seen {
+    ///     println!("last seen: {:?} {:?}", event.stage, event.reason);
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_transaction_ingestion_status(
+        &self,
+        signature: &Signature,
+    ) -> ClientResult<RpcTransactionIngestionStatus> {
+        self.invoke((self.rpc_client.as_ref()).get_transaction_ingestion_status(signature))
+    }
+
+    /// Returns the audited change of supply of the recent frozen banks from
+    /// `start_slot` on, oldest first, up to `limit` of them.
+    ///
+    /// The node keeps the audits of a bounded number of banks and must be
+    /// started with `--supply-audit`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getSupplyDeltas` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// for delta in rpc_client.get_supply_deltas(None, Some(10))? {
+    ///     println!("slot {}: {} lamports burned", delta.slot, delta.fees_burned);
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_supply_deltas(
+        &self,
+        start_slot: Option<Slot>,
+        limit: Option<usize>,
+    ) -> ClientResult<Vec<RpcSupplyDelta>> {
+        self.invoke((self.rpc_client.as_ref()).get_supply_deltas(start_slot, limit))
+    }
+
+    /// Returns the costs of the vote transactions of the current and previous
+    /// epochs, which pay no fees, against the costs of the blocks.
+    ///
+    /// This method uses the configured default [commitment level][cl].
+    ///
+    /// [cl]: https://solana.com/docs/rpc#configuring-state-commitment
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getVoteOverhead` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let vote_overhead = rpc_client.get_vote_overhead()?;
+    /// println!(
+    ///     "{} of {} CUs used by votes",
+    ///     vote_overhead.current_epoch.vote_cost,
+    ///     vote_overhead.current_epoch.block_cost,
+    /// );
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_vote_overhead(&self) -> ClientResult<RpcVoteOverhead> {
+        self.get_vote_overhead_with_commitment(self.commitment())
+    }
+
+    /// Returns the costs of the vote transactions of the current and previous
+    /// epochs, which pay no fees, against the costs of the blocks.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getVoteOverhead` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_commitment_config::CommitmentConfig;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let vote_overhead =
+    ///     rpc_client.get_vote_overhead_with_commitment(CommitmentConfig::finalized())?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_vote_overhead_with_commitment(
+        &self,
+        commitment_config: CommitmentConfig,
+    ) -> ClientResult<RpcVoteOverhead> {
+        self.invoke((self.rpc_client.as_ref()).get_vote_overhead_with_commitment(commitment_config))
+    }
+
+    /// Returns the account of `pubkey` as it was at the end of `slot`, which
+    /// must be rooted.
+    ///
+    /// The node must archive the accounts written by each block; archival is
+    /// disabled by default because of its storage cost. Returns `None` if the
+    /// account did not exist at `slot`, or was not written by any block still
+    /// kept in the ledger of the node.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getAccountInfoAtSlot` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let alice = Keypair::new();
+    /// let slot = 100;
+    /// let account = rpc_client.get_account_at_slot(&alice.pubkey(), slot)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_account_at_slot(
+        &self,
+        pubkey: &Pubkey,
+        slot: Slot,
+    ) -> ClientResult<Option<Account>> {
+        self.invoke((self.rpc_client.as_ref()).get_account_at_slot(pubkey, slot))
+    }
+
+    /// Returns the transaction fees collected in a block, and how they were
+    /// split between the leader and the burn.
+    ///
+    /// The summary is recorded when the bank of the slot is frozen, so it
+    /// remains available for as long as the node keeps the slot in its ledger.
+    /// Returns `None` if the node has no summary for `slot`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getBlockFeeSummary` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let slot = rpc_client.get_slot()?;
+    /// if let Some(fee_summary) = rpc_client.get_block_fee_summary(slot)? {
+    ///     println!("burned {} lamports", fee_summary.burned);
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_block_fee_summary(&self, slot: Slot) -> ClientResult<Option<RpcBlockFeeSummary>> {
+        self.invoke((self.rpc_client.as_ref()).get_block_fee_summary(slot))
+    }
+
+    /// Returns the proof that the account of `pubkey` had its contents at the
+    /// bank hash of `slot`, for light clients and bridges to verify without a
+    /// full node.
+    ///
+    /// The bank hash of a slot only commits to the accounts written in that
+    /// slot, so the account must have been written in `slot`: an account which
+    /// was not modified is proven at the last slot which wrote it. The bank of
+    /// `slot` must also still be held by the node, so proofs are only available
+    /// for recent slots, and none are available once the
+    /// `remove_accounts_delta_hash` feature is active.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getAccountProof` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let alice = Keypair::new();
+    /// let slot = rpc_client.get_slot()?;
+    /// let proof = rpc_client.get_account_proof(&alice.pubkey(), slot)?;
+    /// println!("bank hash: {}", proof.bank_hash);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_account_proof(&self, pubkey: &Pubkey, slot: Slot) -> ClientResult<RpcAccountProof> {
+        self.invoke((self.rpc_client.as_ref()).get_account_proof(pubkey, slot))
+    }
+
+    /// Returns the signed gossip votes attesting to the bank hash of `slot`,
+    /// for light clients and bridges to verify its optimistic confirmation
+    /// without trusting the node.
+    ///
+    /// Each attestation is a vote transaction whose last voted slot is `slot`.
+    /// Clients should verify the signatures of the vote transactions, and
+    /// that the attested stake reaches the threshold stake. Returns `None` if
+    /// the node has no attestations for `slot`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getOptimisticConfirmationAttestations` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let slot = rpc_client.get_slot()?;
+    /// if let Some(attestations) = rpc_client.get_optimistic_confirmation_attestations(slot)? {
+    ///     println!(
+    ///         "{} of {} stake attests to {}",
+    ///         attestations.attested_stake, attestations.total_stake, attestations.bank_hash,
+    ///     );
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_optimistic_confirmation_attestations(
+        &self,
+        slot: Slot,
+    ) -> ClientResult<Option<RpcOptimisticConfirmationAttestations>> {
+        self.invoke((self.rpc_client.as_ref()).get_optimistic_confirmation_attestations(slot))
+    }
+
+    /// Returns the stake distribution of `epoch`, or of the current epoch, as
+    /// held by the node for the leader schedule and the votes of the epoch.
+    ///
+    /// Only the stakes of recent and upcoming epochs are held.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getEpochStakes` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let epoch_stakes = rpc_client.get_epoch_stakes(None)?;
+    /// for vote_account in &epoch_stakes.vote_accounts {
+    ///     println!("{}: {}", vote_account.vote_pubkey, vote_account.stake);
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_epoch_stakes(&self, epoch: Option<Epoch>) -> ClientResult<RpcEpochStakes> {
+        self.invoke((self.rpc_client.as_ref()).get_epoch_stakes(epoch))
+    }
+
+    /// Returns the leader schedule of `epoch`, or of the current epoch, with
+    /// the stake and the vote accounts of each leader. Returns `None` if the
+    /// leader schedule of the epoch is not known.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getLeaderScheduleWithStakes` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// if let Some(schedule) = rpc_client.get_leader_schedule_with_stakes(None)? {
+    ///     for leader in &schedule.leaders {
+    ///         println!(
+    ///             "{}: {} slots, {} stake",
+    ///             leader.identity,
+    ///             leader.slots.len(),
+    ///             leader.stake,
+    ///         );
+    ///     }
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_leader_schedule_with_stakes(
+        &self,
+        epoch: Option<Epoch>,
+    ) -> ClientResult<Option<RpcLeaderScheduleWithStakes>> {
+        self.invoke((self.rpc_client.as_ref()).get_leader_schedule_with_stakes(epoch))
+    }
+
+    /// Returns the stake accounts delegated to a vote account.
+    ///
+    /// Nodes with the `stake-voter` account index enabled serve this method
+    /// without scanning every stake account.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getStakeAccountsByVoteAccount` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_pubkey::Pubkey;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let vote_pubkey = Pubkey::new_unique();
+    /// let stake_accounts = rpc_client.get_stake_accounts_by_vote_account(&vote_pubkey)?;
+    /// for (stake_pubkey, stake_account) in &stake_accounts {
+    ///     println!("{stake_pubkey}: {}", stake_account.lamports);
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_stake_accounts_by_vote_account(
+        &self,
+        vote_pubkey: &Pubkey,
+    ) -> ClientResult<Vec<(Pubkey, Account)>> {
+        self.invoke((self.rpc_client.as_ref()).get_stake_accounts_by_vote_account(vote_pubkey))
+    }
+
+    /// Returns the stake accounts delegated to a vote account, with the
+    /// context of the response.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getStakeAccountsByVoteAccount` RPC method.
+    pub fn get_stake_accounts_by_vote_account_with_config(
+        &self,
+        vote_pubkey: &Pubkey,
+        config: RpcAccountInfoConfig,
+    ) -> RpcResult<Vec<(Pubkey, Account)>> {
+        self.invoke(
+            (self.rpc_client.as_ref())
+                .get_stake_accounts_by_vote_account_with_config(vote_pubkey, config),
+        )
+    }
+
+    /// Returns the latencies of recent slots to reach optimistic confirmation
+    /// and to be finalized, measured from the time the node received the first
+    /// shred of each slot.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getConfirmationLatencyStats` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let stats = rpc_client.get_confirmation_latency_stats()?;
+    /// println!(
+    ///     "p90 time to optimistic confirmation: {}ms",
+    ///     stats.optimistic_confirmation.p90_ms,
+    /// );
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_confirmation_latency_stats(&self) -> ClientResult<RpcConfirmationLatencyStats> {
+        self.invoke((self.rpc_client.as_ref()).get_confirmation_latency_stats())
+    }
+
+    /// Registers a webhook that is sent a notification for every transaction referencing one of
+    /// `addresses`, once its block reaches the commitment of `config`.
+    ///
+    /// Notifications are signed with the returned secret, which is also needed to unregister the
+    /// webhook. Webhooks are only available on nodes that enable them.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `registerWebhook` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{client_error::Error, config::RpcRegisterWebhookConfig};
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_commitment_config::CommitmentConfig;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let registration = rpc_client.register_webhook(
+    ///     "https://example.com/webhook",
+    ///     &[solana_pubkey::new_rand()],
+    ///     RpcRegisterWebhookConfig {
+    ///         commitment: Some(CommitmentConfig::confirmed()),
+    ///     },
+    /// )?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn register_webhook(
+        &self,
+        url: &str,
+        addresses: &[Pubkey],
+        config: RpcRegisterWebhookConfig,
+    ) -> ClientResult<RpcWebhookRegistration> {
+        self.invoke((self.rpc_client.as_ref()).register_webhook(url, addresses, config))
+    }
+
+    /// Unregisters a webhook, returning `false` if no webhook matches `id` and `secret`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `unregisterWebhook` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let unregistered = rpc_client.unregister_webhook("42", "00")?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn unregister_webhook(&self, id: &str, secret: &str) -> ClientResult<bool> {
+        self.invoke((self.rpc_client.as_ref()).unregister_webhook(id, secret))
+    }
+
     /// Returns the identity pubkey for the current node.
     ///
     /// # RPC Reference
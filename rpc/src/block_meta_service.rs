@@ -4,10 +4,18 @@
 pub use solana_ledger::blockstore_processor::BlockMetaSender;
 use {
     crossbeam_channel::{Receiver, RecvTimeoutError},
-    solana_ledger::blockstore::{Blockstore, BlockstoreError},
+    solana_ledger::{
+        blockstore::{Blockstore, BlockstoreError},
+        blockstore_meta::BlockFeeSummary,
+    },
     solana_runtime::bank::{Bank, KeyedRewardsAndNumPartitions},
+    solana_sdk::{
+        account::{Account, ReadableAccount},
+        pubkey::Pubkey,
+    },
     solana_transaction_status::{Reward, RewardsAndNumPartitions},
     std::{
+        collections::HashSet,
         sync::{
             atomic::{AtomicBool, AtomicU64, Ordering},
             Arc,
@@ -19,6 +27,21 @@ use {
 
 pub type BlockMetaReceiver = Receiver<Arc<Bank>>;
 
+/// Selects the accounts whose versions written by each bank are archived in the
+/// blockstore, to be served by `getAccountInfoAtSlot`
+#[derive(Clone, Debug, Default)]
+pub struct ArchivalAccountsConfig {
+    /// Only archive the accounts owned by these programs, or every account if empty
+    pub owners: HashSet<Pubkey>,
+}
+
+impl ArchivalAccountsConfig {
+    fn should_archive(&self, account: &impl ReadableAccount) -> bool {
+        // Closed accounts no longer have their owner, archive them regardless
+        self.owners.is_empty() || account.lamports() == 0 || self.owners.contains(account.owner())
+    }
+}
+
 pub struct BlockMetaService {
     thread_hdl: JoinHandle<()>,
 }
@@ -28,6 +51,7 @@ impl BlockMetaService {
         block_meta_receiver: BlockMetaReceiver,
         blockstore: Arc<Blockstore>,
         max_complete_rewards_slot: Arc<AtomicU64>,
+        archival_accounts_config: Option<ArchivalAccountsConfig>,
         exit: Arc<AtomicBool>,
     ) -> Self {
         let thread_hdl = Builder::new()
@@ -48,9 +72,12 @@ impl BlockMetaService {
                         }
                     };
 
-                    if let Err(err) =
-                        Self::write_block_meta(&bank, &blockstore, &max_complete_rewards_slot)
-                    {
+                    if let Err(err) = Self::write_block_meta(
+                        &bank,
+                        &blockstore,
+                        &max_complete_rewards_slot,
+                        archival_accounts_config.as_ref(),
+                    ) {
                         error!("BlockMetaService is stopping because: {err}");
                         // Set the exit flag to allow other services to gracefully stop
                         exit.store(true, Ordering::Relaxed);
@@ -67,12 +94,26 @@ impl BlockMetaService {
         bank: &Bank,
         blockstore: &Blockstore,
         max_complete_rewards_slot: &Arc<AtomicU64>,
+        archival_accounts_config: Option<&ArchivalAccountsConfig>,
     ) -> Result<(), BlockstoreError> {
         let slot = bank.slot();
 
         blockstore.set_block_time(slot, bank.clock().unix_timestamp)?;
         blockstore.set_block_height(slot, bank.block_height())?;
 
+        let fee_summary = bank.fee_summary();
+        blockstore.set_block_fee_summary(
+            slot,
+            &BlockFeeSummary {
+                leader: *bank.collector_id(),
+                transaction_fee: fee_summary.transaction_fee,
+                priority_fee: fee_summary.priority_fee,
+                burned: fee_summary.burned,
+                leader_reward: fee_summary.leader_reward,
+                treasury: fee_summary.treasury,
+            },
+        )?;
+
         let rewards = bank.get_rewards_and_num_partitions();
         if rewards.should_record() {
             let KeyedRewardsAndNumPartitions {
@@ -98,6 +139,19 @@ impl BlockMetaService {
         }
         max_complete_rewards_slot.fetch_max(slot, Ordering::SeqCst);
 
+        if let Some(archival_accounts_config) = archival_accounts_config {
+            let accounts: Vec<_> = bank
+                .get_all_accounts_modified_since_parent()
+                .into_iter()
+                .filter(|(_, account)| archival_accounts_config.should_archive(account))
+                .map(|(pubkey, account)| (pubkey, Account::from(account)))
+                .collect();
+            blockstore.write_archived_accounts(
+                slot,
+                accounts.iter().map(|(pubkey, account)| (pubkey, account)),
+            )?;
+        }
+
         Ok(())
     }
 
@@ -0,0 +1,199 @@
+//! The `confirmation_latency` module tracks how long slots take to be optimistically confirmed
+//! and to be rooted, measured from the time this node received the first shred of each slot.
+
+use {
+    solana_rpc_client_api::response::{RpcConfirmationLatencyStats, RpcLatencyStats},
+    solana_sdk::clock::Slot,
+    std::{
+        collections::{BTreeMap, VecDeque},
+        sync::Mutex,
+    },
+};
+
+/// Number of most recent samples the latency stats are computed over.
+/// At 400ms slots, this covers roughly the last 7 minutes.
+pub const MAX_LATENCY_SAMPLES: usize = 1_024;
+
+/// Upper bound on the number of unrooted slots being tracked, so that slots on abandoned forks
+/// are dropped even if the root stops advancing.
+const MAX_PENDING_SLOTS: usize = 4 * MAX_LATENCY_SAMPLES;
+
+#[derive(Debug, Default)]
+struct PendingSlot {
+    first_shred_timestamp: Option<u64>,
+    optimistically_confirmed: bool,
+}
+
+#[derive(Debug, Default)]
+struct LatencySamples(VecDeque<u64>);
+
+impl LatencySamples {
+    fn push(&mut self, latency_ms: u64) {
+        if self.0.len() == MAX_LATENCY_SAMPLES {
+            self.0.pop_front();
+        }
+        self.0.push_back(latency_ms);
+    }
+
+    fn stats(&self) -> RpcLatencyStats {
+        let mut latencies: Vec<u64> = self.0.iter().copied().collect();
+        if latencies.is_empty() {
+            return RpcLatencyStats::default();
+        }
+        latencies.sort_unstable();
+        let percentile = |p: usize| latencies[(latencies.len() - 1) * p / 100];
+        RpcLatencyStats {
+            num_slots: latencies.len() as u64,
+            min_ms: latencies[0],
+            mean_ms: latencies.iter().sum::<u64>() / latencies.len() as u64,
+            p50_ms: percentile(50),
+            p90_ms: percentile(90),
+            p99_ms: percentile(99),
+            max_ms: latencies[latencies.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ConfirmationLatencyTrackerInner {
+    pending: BTreeMap<Slot, PendingSlot>,
+    optimistic_confirmation: LatencySamples,
+    finalization: LatencySamples,
+}
+
+#[derive(Debug, Default)]
+pub struct ConfirmationLatencyTracker {
+    inner: Mutex<ConfirmationLatencyTrackerInner>,
+}
+
+impl ConfirmationLatencyTracker {
+    pub fn record_first_shred_received(&self, slot: Slot, timestamp: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        let pending_slot = inner.pending.entry(slot).or_default();
+        if pending_slot.first_shred_timestamp.is_none() {
+            pending_slot.first_shred_timestamp = Some(timestamp);
+        }
+        while inner.pending.len() > MAX_PENDING_SLOTS {
+            inner.pending.pop_first();
+        }
+    }
+
+    pub fn record_optimistic_confirmation(&self, slot: Slot, timestamp: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(pending_slot) = inner.pending.get_mut(&slot) else {
+            return;
+        };
+        if pending_slot.optimistically_confirmed {
+            return;
+        }
+        pending_slot.optimistically_confirmed = true;
+        if let Some(first_shred_timestamp) = pending_slot.first_shred_timestamp {
+            let latency_ms = timestamp.saturating_sub(first_shred_timestamp);
+            inner.optimistic_confirmation.push(latency_ms);
+            datapoint_info!(
+                "confirmation_latency-optimistic",
+                ("slot", slot, i64),
+                ("latency_ms", latency_ms, i64),
+            );
+        }
+    }
+
+    /// Records `root` as finalized, and stops tracking any older slot, which can no longer be
+    /// rooted.
+    pub fn record_root(&self, root: Slot, timestamp: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        let pending_slot = inner.pending.remove(&root);
+        inner.pending.retain(|slot, _| *slot > root);
+        if let Some(first_shred_timestamp) =
+            pending_slot.and_then(|pending_slot| pending_slot.first_shred_timestamp)
+        {
+            let latency_ms = timestamp.saturating_sub(first_shred_timestamp);
+            inner.finalization.push(latency_ms);
+            datapoint_info!(
+                "confirmation_latency-finalized",
+                ("slot", root, i64),
+                ("latency_ms", latency_ms, i64),
+            );
+        }
+    }
+
+    pub fn stats(&self) -> RpcConfirmationLatencyStats {
+        let inner = self.inner.lock().unwrap();
+        RpcConfirmationLatencyStats {
+            optimistic_confirmation: inner.optimistic_confirmation.stats(),
+            finalization: inner.finalization.stats(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirmation_latency_tracker() {
+        let tracker = ConfirmationLatencyTracker::default();
+        assert_eq!(
+            tracker.stats(),
+            RpcConfirmationLatencyStats {
+                optimistic_confirmation: RpcLatencyStats::default(),
+                finalization: RpcLatencyStats::default(),
+            }
+        );
+
+        tracker.record_first_shred_received(1, 1_000);
+        tracker.record_first_shred_received(1, 1_100);
+        tracker.record_first_shred_received(2, 1_400);
+        tracker.record_first_shred_received(3, 1_800);
+
+        // unknown slots and repeated confirmations are ignored
+        tracker.record_optimistic_confirmation(4, 2_000);
+        tracker.record_optimistic_confirmation(1, 2_000);
+        tracker.record_optimistic_confirmation(1, 3_000);
+        tracker.record_optimistic_confirmation(2, 2_600);
+
+        tracker.record_root(1, 13_000);
+        // slot 2 was on an abandoned fork
+        tracker.record_root(3, 14_800);
+        tracker.record_optimistic_confirmation(2, 15_000);
+
+        let stats = tracker.stats();
+        assert_eq!(
+            stats.optimistic_confirmation,
+            RpcLatencyStats {
+                num_slots: 2,
+                min_ms: 1_000,
+                mean_ms: 1_100,
+                p50_ms: 1_000,
+                p90_ms: 1_000,
+                p99_ms: 1_000,
+                max_ms: 1_200,
+            }
+        );
+        assert_eq!(
+            stats.finalization,
+            RpcLatencyStats {
+                num_slots: 2,
+                min_ms: 12_000,
+                mean_ms: 12_500,
+                p50_ms: 12_000,
+                p90_ms: 12_000,
+                p99_ms: 12_000,
+                max_ms: 13_000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_latency_samples_are_bounded() {
+        let tracker = ConfirmationLatencyTracker::default();
+        for slot in 0..(2 * MAX_LATENCY_SAMPLES as Slot) {
+            tracker.record_first_shred_received(slot, slot * 400);
+            tracker.record_optimistic_confirmation(slot, slot * 400 + slot);
+        }
+        let stats = tracker.stats().optimistic_confirmation;
+        assert_eq!(stats.num_slots, MAX_LATENCY_SAMPLES as u64);
+        assert_eq!(stats.min_ms, MAX_LATENCY_SAMPLES as u64);
+        assert_eq!(stats.max_ms, 2 * MAX_LATENCY_SAMPLES as u64 - 1);
+    }
+}
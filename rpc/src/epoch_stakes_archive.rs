@@ -0,0 +1,244 @@
+//! Archive on disk of the stakes of past epochs.
+//!
+//! Banks only hold the stakes of the few epochs around their own, so `getEpochStakes` can't serve
+//! older epochs from them. The [`EpochStakesArchiveService`] writes the stakes of every epoch held
+//! by the root bank into an [`EpochStakesArchive`], one JSON file per epoch, from which archival
+//! RPC nodes keep serving them, e.g. to audit the inflation rewards of old epochs.
+
+use {
+    crate::rpc::JsonRpcRequestProcessor,
+    solana_rpc_client_api::response::RpcEpochStakes,
+    solana_runtime::{bank::Bank, bank_forks::BankForks},
+    solana_sdk::clock::Epoch,
+    std::{
+        fs,
+        io::{self, BufReader, BufWriter},
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+pub const DEFAULT_EPOCH_STAKES_ARCHIVE_MAX_EPOCHS: u64 = 1_000;
+
+/// Stakes of past epochs, kept on disk up to `max_epochs` epochs before the latest archived
+#[derive(Debug)]
+pub struct EpochStakesArchive {
+    path: PathBuf,
+    max_epochs: u64,
+}
+
+impl EpochStakesArchive {
+    pub fn new(path: impl Into<PathBuf>, max_epochs: u64) -> io::Result<Self> {
+        let path = path.into();
+        fs::create_dir_all(&path)?;
+        Ok(Self {
+            path,
+            max_epochs: max_epochs.max(1),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn epoch_path(&self, epoch: Epoch) -> PathBuf {
+        self.path.join(format!("{epoch}.json"))
+    }
+
+    pub fn contains(&self, epoch: Epoch) -> bool {
+        self.epoch_path(epoch).is_file()
+    }
+
+    /// Returns the archived stakes of `epoch`, if any
+    pub fn get(&self, epoch: Epoch) -> Option<RpcEpochStakes> {
+        let file = match fs::File::open(self.epoch_path(epoch)) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                warn!("Failed to open the archived stakes of epoch {epoch}: {err}");
+                return None;
+            }
+        };
+        serde_json::from_reader(BufReader::new(file))
+            .inspect_err(|err| warn!("Failed to read the archived stakes of epoch {epoch}: {err}"))
+            .ok()
+    }
+
+    /// Archives `epoch_stakes`, replacing the stakes previously archived for its epoch
+    pub fn insert(&self, epoch_stakes: &RpcEpochStakes) -> io::Result<()> {
+        let path = self.epoch_path(epoch_stakes.epoch);
+        // Write to a temporary file first so readers never see a partially written epoch
+        let tmp_path = path.with_extension("json.tmp");
+        let mut writer = BufWriter::new(fs::File::create(&tmp_path)?);
+        serde_json::to_writer(&mut writer, epoch_stakes)?;
+        writer
+            .into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+        fs::rename(tmp_path, path)
+    }
+
+    /// Returns the archived epochs, in ascending order
+    pub fn epochs(&self) -> io::Result<Vec<Epoch>> {
+        let mut epochs = vec![];
+        for entry in fs::read_dir(&self.path)? {
+            let file_name = entry?.file_name();
+            if let Some(epoch) = file_name
+                .to_str()
+                .and_then(|file_name| file_name.strip_suffix(".json"))
+                .and_then(|epoch| epoch.parse().ok())
+            {
+                epochs.push(epoch);
+            }
+        }
+        epochs.sort_unstable();
+        Ok(epochs)
+    }
+
+    /// Removes the epochs which fell out of the retention behind `latest_epoch`
+    pub fn purge(&self, latest_epoch: Epoch) -> io::Result<()> {
+        let min_epoch = latest_epoch.saturating_sub(self.max_epochs.saturating_sub(1));
+        for epoch in self.epochs()? {
+            if epoch >= min_epoch {
+                break;
+            }
+            fs::remove_file(self.epoch_path(epoch))?;
+        }
+        Ok(())
+    }
+
+    /// Archives the stakes of the epochs held by `bank` which are not archived yet, and returns
+    /// the number of epochs archived
+    pub fn archive_bank_epochs(&self, bank: &Bank) -> io::Result<usize> {
+        let mut archived = 0;
+        let mut epochs: Vec<_> = bank.epoch_stakes_map().keys().copied().collect();
+        epochs.sort_unstable();
+        for &epoch in &epochs {
+            if self.contains(epoch) {
+                continue;
+            }
+            let Ok((total_stake, vote_accounts)) =
+                JsonRpcRequestProcessor::epoch_vote_account_stakes(bank, epoch)
+            else {
+                continue;
+            };
+            self.insert(&RpcEpochStakes {
+                epoch,
+                total_stake,
+                vote_accounts,
+            })?;
+            archived += 1;
+        }
+        if let Some(latest_epoch) = epochs.last() {
+            self.purge(*latest_epoch)?;
+        }
+        Ok(archived)
+    }
+}
+
+/// Keeps an [`EpochStakesArchive`] up to date with the epochs held by the root bank
+pub struct EpochStakesArchiveService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl EpochStakesArchiveService {
+    pub fn new(
+        archive: Arc<EpochStakesArchive>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solEpochStakes".to_string())
+            .spawn(move || {
+                info!(
+                    "EpochStakesArchiveService has started, archiving into {}",
+                    archive.path().display()
+                );
+                let mut last_epoch = None;
+                while !exit.load(Ordering::Relaxed) {
+                    let root_bank = bank_forks.read().unwrap().root_bank();
+                    // The stakes of an epoch are fixed once computed, only new epochs need to
+                    // be archived
+                    if last_epoch != Some(root_bank.epoch()) {
+                        match archive.archive_bank_epochs(&root_bank) {
+                            Ok(archived) => {
+                                if archived > 0 {
+                                    info!("Archived the stakes of {archived} epochs");
+                                }
+                                last_epoch = Some(root_bank.epoch());
+                            }
+                            Err(err) => warn!("Failed to archive the epoch stakes: {err}"),
+                        }
+                    }
+                    sleep(Duration::from_secs(1));
+                }
+                info!("EpochStakesArchiveService has stopped");
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_ledger::{
+            genesis_utils::{create_genesis_config, GenesisConfigInfo},
+            get_tmp_ledger_path_auto_delete,
+        },
+        solana_sdk::epoch_schedule::EpochSchedule,
+    };
+
+    #[test]
+    fn test_epoch_stakes_archive() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let archive = EpochStakesArchive::new(ledger_path.path().join("epoch_stakes"), 3).unwrap();
+        let GenesisConfigInfo {
+            mut genesis_config, ..
+        } = create_genesis_config(1_000_000_000);
+        genesis_config.epoch_schedule = EpochSchedule::without_warmup();
+        let bank = Bank::new_for_tests(&genesis_config);
+        let held_epochs = bank.epoch_stakes_map().len();
+        assert!(held_epochs > 0);
+
+        assert_eq!(archive.archive_bank_epochs(&bank).unwrap(), held_epochs);
+        // Already archived epochs are not archived again
+        assert_eq!(archive.archive_bank_epochs(&bank).unwrap(), 0);
+        let epoch_stakes = archive.get(bank.epoch()).unwrap();
+        let bank_epoch_stakes = bank.epoch_stakes(bank.epoch()).unwrap();
+        assert_eq!(epoch_stakes.epoch, bank.epoch());
+        assert_eq!(epoch_stakes.total_stake, bank_epoch_stakes.total_stake());
+        assert_eq!(
+            epoch_stakes
+                .vote_accounts
+                .iter()
+                .map(|vote_account| vote_account.stake)
+                .sum::<u64>(),
+            epoch_stakes.total_stake
+        );
+        assert!(archive.get(bank.epoch() + 100).is_none());
+
+        // Only the latest epochs are retained
+        for epoch in 10..15 {
+            archive
+                .insert(&RpcEpochStakes {
+                    epoch,
+                    ..epoch_stakes.clone()
+                })
+                .unwrap();
+        }
+        archive.purge(14).unwrap();
+        assert_eq!(archive.epochs().unwrap(), vec![12, 13, 14]);
+        assert!(!archive.contains(bank.epoch()));
+    }
+}
@@ -1,8 +1,11 @@
 #![allow(clippy::arithmetic_side_effects)]
 pub mod block_meta_service;
 mod cluster_tpu_info;
+pub mod confirmation_latency;
+pub mod epoch_stakes_archive;
 pub mod filter;
 pub mod max_slots;
+pub mod optimistic_confirmation_attestations;
 pub mod optimistically_confirmed_bank_tracker;
 pub mod parsed_token_accounts;
 pub mod rpc;
@@ -11,10 +14,13 @@ pub mod rpc_completed_slots_service;
 pub mod rpc_health;
 pub mod rpc_pubsub;
 pub mod rpc_pubsub_service;
+pub mod rpc_request_stats;
 pub mod rpc_service;
 pub mod rpc_subscription_tracker;
 pub mod rpc_subscriptions;
+pub mod rpc_webhooks;
 pub mod slot_status_notifier;
+pub mod transaction_execution_details;
 pub mod transaction_notifier_interface;
 pub mod transaction_status_service;
 
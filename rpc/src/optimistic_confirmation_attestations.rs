@@ -0,0 +1,251 @@
+//! The `optimistic_confirmation_attestations` module keeps the signed votes which attest to
+//! optimistically confirmed bank hashes, so light clients and bridges can verify finality
+//! without trusting the RPC node.
+//!
+//! A vote transaction gossiped by a validator is a signed attestation of the bank hash of the
+//! last slot it votes for. The vote transactions seen in gossip are collected per (slot, bank
+//! hash), together with the epoch stake of the vote account. A client verifies the signatures
+//! of the vote transactions and checks the stake attesting to the hash against the threshold.
+//! Votes only seen in replayed blocks are not collected, so the attested stake may be lower
+//! than the stake which optimistically confirmed the slot.
+
+use {
+    base64::{prelude::BASE64_STANDARD, Engine},
+    solana_rpc_client_api::response::{RpcAttestation, RpcOptimisticConfirmationAttestations},
+    solana_runtime::commitment::VOTE_THRESHOLD_SIZE,
+    solana_sdk::{clock::Slot, hash::Hash, pubkey::Pubkey, transaction::Transaction},
+    std::{
+        collections::{BTreeMap, HashMap},
+        sync::Mutex,
+    },
+};
+
+/// Number of most recent optimistically confirmed slots whose attestations are kept
+pub const MAX_CONFIRMED_SLOTS: usize = 512;
+
+/// Upper bound on the number of unconfirmed slots being tracked, so that slots on abandoned
+/// forks are dropped even if the root stops advancing
+const MAX_PENDING_SLOTS: usize = 1_024;
+
+#[derive(Debug, Default)]
+struct HashAttestations {
+    stake: u64,
+    votes: HashMap<Pubkey, (u64, Transaction)>,
+}
+
+#[derive(Debug, Default)]
+struct SlotAttestations {
+    total_stake: u64,
+    hashes: HashMap<Hash, HashAttestations>,
+    confirmed_hash: Option<Hash>,
+}
+
+#[derive(Debug, Default)]
+struct OptimisticConfirmationAttestationsInner {
+    slots: BTreeMap<Slot, SlotAttestations>,
+}
+
+impl OptimisticConfirmationAttestationsInner {
+    fn prune(&mut self, root: Option<Slot>) {
+        if let Some(root) = root {
+            // Slots which were not confirmed by the time the root passed them never will be
+            self.slots.retain(|slot, attestations| {
+                *slot >= root || attestations.confirmed_hash.is_some()
+            });
+        }
+        let mut num_confirmed = 0;
+        let mut num_pending = 0;
+        for attestations in self.slots.values_mut().rev() {
+            if attestations.confirmed_hash.is_some() {
+                num_confirmed += 1;
+                if num_confirmed > MAX_CONFIRMED_SLOTS {
+                    attestations.hashes.clear();
+                }
+            } else {
+                num_pending += 1;
+                if num_pending > MAX_PENDING_SLOTS {
+                    attestations.hashes.clear();
+                }
+            }
+        }
+        self.slots
+            .retain(|_slot, attestations| !attestations.hashes.is_empty());
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OptimisticConfirmationAttestations {
+    inner: Mutex<OptimisticConfirmationAttestationsInner>,
+}
+
+impl OptimisticConfirmationAttestations {
+    /// Records `vote_transaction`, signed for `vote_pubkey`, as attesting to `hash` at `slot`.
+    /// Only the first vote of a vote account for a bank hash is kept.
+    pub fn record_vote(
+        &self,
+        slot: Slot,
+        hash: Hash,
+        vote_pubkey: Pubkey,
+        stake: u64,
+        total_stake: u64,
+        vote_transaction: &Transaction,
+    ) {
+        if stake == 0 {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        let new_slot = !inner.slots.contains_key(&slot);
+        let attestations = inner.slots.entry(slot).or_default();
+        attestations.total_stake = total_stake;
+        let hash_attestations = attestations.hashes.entry(hash).or_default();
+        if hash_attestations.votes.contains_key(&vote_pubkey) {
+            return;
+        }
+        hash_attestations.stake += stake;
+        hash_attestations
+            .votes
+            .insert(vote_pubkey, (stake, vote_transaction.clone()));
+        if new_slot {
+            inner.prune(None);
+        }
+    }
+
+    pub fn record_optimistic_confirmation(&self, slot: Slot, hash: Hash) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(attestations) = inner.slots.get_mut(&slot) {
+            attestations.confirmed_hash = Some(hash);
+            inner.prune(None);
+        }
+    }
+
+    /// Stops tracking unconfirmed slots older than `root`, which can no longer be confirmed
+    pub fn record_root(&self, root: Slot) {
+        self.inner.lock().unwrap().prune(Some(root));
+    }
+
+    /// Returns the attestations of the optimistically confirmed hash of `slot`, or if the slot
+    /// is not confirmed yet, of the hash with the most attested stake
+    pub fn get(&self, slot: Slot) -> Option<RpcOptimisticConfirmationAttestations> {
+        let inner = self.inner.lock().unwrap();
+        let attestations = inner.slots.get(&slot)?;
+        let (hash, hash_attestations) = match attestations.confirmed_hash {
+            Some(hash) => (hash, attestations.hashes.get(&hash)?),
+            None => attestations
+                .hashes
+                .iter()
+                .max_by_key(|(hash, hash_attestations)| (hash_attestations.stake, **hash))
+                .map(|(hash, hash_attestations)| (*hash, hash_attestations))?,
+        };
+
+        let mut votes: Vec<_> = hash_attestations
+            .votes
+            .iter()
+            .map(|(vote_pubkey, (stake, vote_transaction))| RpcAttestation {
+                vote_pubkey: vote_pubkey.to_string(),
+                stake: *stake,
+                signature: vote_transaction.signatures[0].to_string(),
+                transaction: BASE64_STANDARD
+                    .encode(bincode::serialize(vote_transaction).expect("transaction serializes")),
+            })
+            .collect();
+        votes.sort_by(|a, b| {
+            b.stake
+                .cmp(&a.stake)
+                .then_with(|| a.vote_pubkey.cmp(&b.vote_pubkey))
+        });
+
+        Some(RpcOptimisticConfirmationAttestations {
+            slot,
+            bank_hash: hash.to_string(),
+            optimistically_confirmed: attestations.confirmed_hash.is_some(),
+            total_stake: attestations.total_stake,
+            attested_stake: hash_attestations.stake,
+            threshold_stake: (attestations.total_stake as f64 * VOTE_THRESHOLD_SIZE).ceil() as u64,
+            attestations: votes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{signature::Keypair, signer::Signer, system_transaction},
+    };
+
+    fn vote_transaction() -> Transaction {
+        let keypair = Keypair::new();
+        system_transaction::transfer(&keypair, &keypair.pubkey(), 1, Hash::default())
+    }
+
+    #[test]
+    fn test_optimistic_confirmation_attestations() {
+        let attestations = OptimisticConfirmationAttestations::default();
+        assert_eq!(attestations.get(1), None);
+
+        let (hash, other_hash) = (Hash::new_unique(), Hash::new_unique());
+        let (voter_a, voter_b, voter_c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let vote_a = vote_transaction();
+        attestations.record_vote(1, hash, voter_a, 40, 100, &vote_a);
+        // repeated votes and votes without stake are ignored
+        attestations.record_vote(1, hash, voter_a, 40, 100, &vote_transaction());
+        attestations.record_vote(1, hash, Pubkey::new_unique(), 0, 100, &vote_transaction());
+        attestations.record_vote(1, other_hash, voter_b, 10, 100, &vote_transaction());
+
+        let result = attestations.get(1).unwrap();
+        assert_eq!(result.bank_hash, hash.to_string());
+        assert!(!result.optimistically_confirmed);
+        assert_eq!(result.attested_stake, 40);
+        assert_eq!(result.threshold_stake, 67);
+        assert_eq!(result.attestations.len(), 1);
+        assert_eq!(result.attestations[0].vote_pubkey, voter_a.to_string());
+        assert_eq!(
+            result.attestations[0].signature,
+            vote_a.signatures[0].to_string()
+        );
+        let transaction: Transaction = bincode::deserialize(
+            &BASE64_STANDARD
+                .decode(&result.attestations[0].transaction)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(transaction, vote_a);
+
+        // the confirmed hash is returned even with less attested stake
+        attestations.record_vote(1, other_hash, voter_c, 60, 100, &vote_transaction());
+        attestations.record_optimistic_confirmation(1, other_hash);
+        let result = attestations.get(1).unwrap();
+        assert_eq!(result.bank_hash, other_hash.to_string());
+        assert!(result.optimistically_confirmed);
+        assert_eq!(result.attested_stake, 70);
+        assert_eq!(result.attestations[0].vote_pubkey, voter_c.to_string());
+        assert_eq!(result.attestations[1].vote_pubkey, voter_b.to_string());
+
+        // unconfirmed slots are dropped once rooted past, confirmed slots are kept
+        attestations.record_vote(2, hash, voter_a, 40, 100, &vote_transaction());
+        attestations.record_root(3);
+        assert!(attestations.get(1).is_some());
+        assert_eq!(attestations.get(2), None);
+    }
+
+    #[test]
+    fn test_confirmed_slots_are_bounded() {
+        let attestations = OptimisticConfirmationAttestations::default();
+        let vote = vote_transaction();
+        for slot in 0..(2 * MAX_CONFIRMED_SLOTS as Slot) {
+            let hash = Hash::new_unique();
+            attestations.record_vote(slot, hash, Pubkey::new_unique(), 1, 1, &vote);
+            attestations.record_optimistic_confirmation(slot, hash);
+        }
+        assert_eq!(attestations.get(MAX_CONFIRMED_SLOTS as Slot - 1), None);
+        assert!(attestations.get(MAX_CONFIRMED_SLOTS as Slot).is_some());
+        assert_eq!(
+            attestations.inner.lock().unwrap().slots.len(),
+            MAX_CONFIRMED_SLOTS
+        );
+    }
+}
@@ -3,9 +3,17 @@
 use solana_runtime::installed_scheduler_pool::BankWithScheduler;
 use {
     crate::{
-        filter::filter_allows, max_slots::MaxSlots,
+        block_meta_service::ArchivalAccountsConfig,
+        confirmation_latency::ConfirmationLatencyTracker,
+        epoch_stakes_archive::{EpochStakesArchive, EpochStakesArchiveService},
+        filter::filter_allows,
+        max_slots::MaxSlots,
+        optimistic_confirmation_attestations::OptimisticConfirmationAttestations,
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
-        parsed_token_accounts::*, rpc_cache::LargestAccountsCache, rpc_health::*,
+        parsed_token_accounts::*,
+        rpc_cache::LargestAccountsCache,
+        rpc_health::*,
+        rpc_webhooks::{WebhookConfig, WebhookRegistry, WebhookService},
     },
     agave_feature_set as feature_set,
     base64::{prelude::BASE64_STANDARD, Engine},
@@ -25,7 +33,10 @@ use {
     },
     solana_accounts_db::{
         accounts::AccountAddressFilter,
-        accounts_index::{AccountIndex, AccountSecondaryIndexes, IndexKey, ScanConfig, ScanResult},
+        accounts_index::{
+            AccountIndex, AccountSecondaryIndexes, IndexKey, ScanConfig, ScanResult,
+            STAKE_ACCOUNT_VOTER_OFFSET,
+        },
     },
     solana_client::connection_cache::Protocol,
     solana_entry::entry::Entry,
@@ -36,8 +47,11 @@ use {
         token_2022::{self, ACCOUNTTYPE_ACCOUNT},
     },
     solana_ledger::{
-        blockstore::{Blockstore, BlockstoreError, SignatureInfosForAddress},
-        blockstore_meta::{PerfSample, PerfSampleV1, PerfSampleV2},
+        blockstore::{
+            AddressSignaturePosition, Blockstore, BlockstoreError, SignatureInfosForAddress,
+            SignaturesForAddressFilter,
+        },
+        blockstore_meta::{BlockFeeSummary, PerfSample, PerfSampleV1, PerfSampleV2},
         leader_schedule_cache::LeaderScheduleCache,
     },
     solana_metrics::inc_new_counter_info,
@@ -50,13 +64,18 @@ use {
             TokenAccountsFilter, DELINQUENT_VALIDATOR_SLOT_DISTANCE,
             MAX_GET_CONFIRMED_BLOCKS_RANGE, MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT,
             MAX_GET_PROGRAM_ACCOUNT_FILTERS, MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
-            MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
-            MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY, NUM_LARGEST_ACCOUNTS,
+            MAX_GET_SLOT_LEADERS, MAX_GET_SUPPLY_DELTAS_LIMIT, MAX_MULTIPLE_ACCOUNTS,
+            MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY, MAX_SIMULATE_BUNDLE_TRANSACTIONS,
+            NUM_LARGEST_ACCOUNTS,
         },
         response::{Response as RpcResponse, *},
     },
     solana_runtime::{
-        bank::{Bank, TransactionSimulationResult},
+        bank::{
+            supply_audit::{SupplyAudit, SupplyAuditEntry},
+            vote_overhead::VoteOverheadStats,
+            Bank, TransactionSimulationResult,
+        },
         bank_forks::BankForks,
         commitment::{BlockCommitmentArray, BlockCommitmentCache},
         non_circulating_supply::{calculate_non_circulating_supply, NonCirculatingSupply},
@@ -67,8 +86,8 @@ use {
     },
     solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
     solana_sdk::{
-        account::{AccountSharedData, ReadableAccount},
-        clock::{Slot, UnixTimestamp, MAX_PROCESSING_AGE},
+        account::{AccountSharedData, ReadableAccount, WritableAccount},
+        clock::{Epoch, Slot, UnixTimestamp, MAX_PROCESSING_AGE},
         commitment_config::{CommitmentConfig, CommitmentLevel},
         epoch_info::EpochInfo,
         epoch_rewards_hasher::EpochRewardsHasher,
@@ -78,15 +97,22 @@ use {
         message::SanitizedMessage,
         pubkey::{Pubkey, PUBKEY_BYTES},
         signature::{Keypair, Signature, Signer},
-        system_instruction,
+        stake::{self, state::StakeStateV2},
+        system_instruction::{self, MAX_PERMITTED_DATA_LENGTH},
+        system_program, sysvar,
         transaction::{
             self, AddressLoader, MessageHash, SanitizedTransaction, TransactionError,
-            VersionedTransaction, MAX_TX_ACCOUNT_LOCKS,
+            VersionedTransaction,
         },
     },
-    solana_send_transaction_service::send_transaction_service::TransactionInfo,
+    solana_send_transaction_service::{
+        compute_unit_price_escalation::ComputeUnitPriceEscalation,
+        send_transaction_service::TransactionInfo,
+        transaction_ingestion_log::{IngestionEvent, IngestionStage, TransactionIngestionLog},
+    },
     solana_stake_program,
     solana_storage_bigtable::Error as StorageError,
+    solana_svm::account_overrides::AccountOverrides,
     solana_transaction_context::TransactionAccount,
     solana_transaction_status::{
         map_inner_instructions, BlockEncodingOptions, ConfirmedBlock,
@@ -139,6 +165,7 @@ type RpcCustomResult<T> = std::result::Result<T, RpcCustomError>;
 
 pub const MAX_REQUEST_BODY_SIZE: usize = 50 * (1 << 10); // 50kB
 pub const PERFORMANCE_SAMPLES_LIMIT: usize = 720;
+pub const SLASHING_EVIDENCE_LIMIT: usize = 1_000;
 
 fn new_response<T>(bank: &Bank, value: T) -> RpcResponse<T> {
     RpcResponse {
@@ -175,6 +202,21 @@ pub struct JsonRpcConfig {
     pub max_request_body_size: Option<usize>,
     /// Disable the health check, used for tests and TestValidator
     pub disable_health_check: bool,
+    /// Enables webhook notifications for address activity, requires transaction history
+    pub webhook_config: Option<WebhookConfig>,
+    /// Records the lifecycle of transactions for `getTransactionIngestionStatus`, if enabled
+    pub transaction_ingestion_log: Option<Arc<TransactionIngestionLog>>,
+    /// Archives the accounts written by each bank for `getAccountInfoAtSlot`, if enabled.
+    /// Requires transaction history
+    pub archival_accounts: Option<ArchivalAccountsConfig>,
+    /// Audits the change of supply of every frozen bank for `getSupplyDeltas`, if enabled
+    pub supply_audit: Option<Arc<SupplyAudit>>,
+    /// Archives the stakes of every epoch on disk, so `getEpochStakes` keeps serving the epochs
+    /// banks no longer hold, if enabled
+    pub epoch_stakes_archive: Option<Arc<EpochStakesArchive>>,
+    /// Attributes the prioritization fees of transactions to the programs owning their writable
+    /// accounts for `getProgramFeeMarket`
+    pub enable_program_fee_market: bool,
 }
 
 impl Default for JsonRpcConfig {
@@ -195,6 +237,12 @@ impl Default for JsonRpcConfig {
             rpc_scan_and_fix_roots: Default::default(),
             max_request_body_size: Option::default(),
             disable_health_check: Default::default(),
+            webhook_config: Option::default(),
+            transaction_ingestion_log: Option::default(),
+            archival_accounts: Option::default(),
+            supply_audit: Option::default(),
+            epoch_stakes_archive: Option::default(),
+            enable_program_fee_market: Default::default(),
         }
     }
 }
@@ -253,7 +301,10 @@ pub struct JsonRpcRequestProcessor {
     max_complete_transaction_status_slot: Arc<AtomicU64>,
     max_complete_rewards_slot: Arc<AtomicU64>,
     prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+    confirmation_latency: Arc<ConfirmationLatencyTracker>,
+    optimistic_confirmation_attestations: Arc<OptimisticConfirmationAttestations>,
     runtime: Arc<Runtime>,
+    webhook_registry: Option<Arc<WebhookRegistry>>,
 }
 impl Metadata for JsonRpcRequestProcessor {}
 
@@ -405,9 +456,16 @@ impl JsonRpcRequestProcessor {
         max_complete_transaction_status_slot: Arc<AtomicU64>,
         max_complete_rewards_slot: Arc<AtomicU64>,
         prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+        confirmation_latency: Arc<ConfirmationLatencyTracker>,
+        optimistic_confirmation_attestations: Arc<OptimisticConfirmationAttestations>,
         runtime: Arc<Runtime>,
     ) -> (Self, Receiver<TransactionInfo>) {
         let (transaction_sender, transaction_receiver) = unbounded();
+        let webhook_registry = config
+            .webhook_config
+            .clone()
+            .filter(|_| config.enable_rpc_transaction_history)
+            .map(|webhook_config| Arc::new(WebhookRegistry::new(webhook_config)));
         (
             Self {
                 config,
@@ -428,7 +486,10 @@ impl JsonRpcRequestProcessor {
                 max_complete_transaction_status_slot,
                 max_complete_rewards_slot,
                 prioritization_fee_cache,
+                confirmation_latency,
+                optimistic_confirmation_attestations,
                 runtime,
+                webhook_registry,
             },
             transaction_receiver,
         )
@@ -522,10 +583,36 @@ impl JsonRpcRequestProcessor {
             max_complete_transaction_status_slot: Arc::new(AtomicU64::default()),
             max_complete_rewards_slot: Arc::new(AtomicU64::default()),
             prioritization_fee_cache: Arc::new(PrioritizationFeeCache::default()),
+            confirmation_latency: Arc::default(),
+            optimistic_confirmation_attestations: Arc::default(),
             runtime: service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
+            webhook_registry: None,
         }
     }
 
+    /// Spawns the service delivering webhook notifications, if webhooks are enabled.
+    pub(crate) fn spawn_epoch_stakes_archive_service(
+        &self,
+        exit: Arc<AtomicBool>,
+    ) -> Option<EpochStakesArchiveService> {
+        self.config.epoch_stakes_archive.as_ref().map(|archive| {
+            EpochStakesArchiveService::new(Arc::clone(archive), Arc::clone(&self.bank_forks), exit)
+        })
+    }
+
+    pub(crate) fn spawn_webhook_service(&self, exit: Arc<AtomicBool>) -> Option<WebhookService> {
+        self.webhook_registry.as_ref().map(|registry| {
+            WebhookService::new(
+                Arc::clone(registry),
+                Arc::clone(&self.blockstore),
+                Arc::clone(&self.block_commitment_cache),
+                Arc::clone(&self.max_complete_transaction_status_slot),
+                Arc::clone(&self.runtime),
+                exit,
+            )
+        })
+    }
+
     pub async fn get_account_info(
         &self,
         pubkey: Pubkey,
@@ -1832,6 +1919,7 @@ impl JsonRpcRequestProcessor {
         before: Option<Signature>,
         until: Option<Signature>,
         mut limit: usize,
+        filter: SignaturesForAddressFilter,
         config: RpcContextConfig,
     ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
         let commitment = config.commitment.unwrap_or_default();
@@ -1865,7 +1953,14 @@ impl JsonRpcRequestProcessor {
             found_before,
         } = self
             .blockstore
-            .get_confirmed_signatures_for_address2(address, highest_slot, before, until, limit)
+            .get_filtered_signatures_for_address(
+                address,
+                highest_slot,
+                before,
+                until,
+                limit,
+                &filter,
+            )
             .map_err(|err| Error::invalid_params(format!("{err}")))?;
 
         let map_results = |results: Vec<ConfirmedTransactionStatusWithSignature>| {
@@ -1889,7 +1984,9 @@ impl JsonRpcRequestProcessor {
                 .collect()
         };
 
-        if results.len() < limit {
+        // Long-term storage can't evaluate the filters, so filtered listings stop at the oldest
+        // signature in Blockstore
+        if results.len() < limit && filter == SignaturesForAddressFilter::default() {
             if let Some(bigtable_ledger_storage) = &self.bigtable_ledger_storage {
                 let mut bigtable_before = before;
                 if !results.is_empty() {
@@ -1951,6 +2048,60 @@ impl JsonRpcRequestProcessor {
         Ok(map_results(results))
     }
 
+    /// Lists the signatures for `address` at finalized slots in ledger order, so that exchanges
+    /// scanning for deposits can resume after the cursor of the last call without missing or
+    /// repeating a signature, nor seeing one that is rolled back. Long-term storage is not
+    /// searched.
+    pub fn get_finalized_signatures_for_address(
+        &self,
+        address: Pubkey,
+        after: Option<AddressSignaturePosition>,
+        limit: usize,
+    ) -> Result<RpcFinalizedSignaturesForAddress> {
+        if !self.config.enable_rpc_transaction_history {
+            return Err(RpcCustomError::TransactionHistoryNotAvailable.into());
+        }
+
+        let finalized_slot = self
+            .block_commitment_cache
+            .read()
+            .unwrap()
+            .highest_super_majority_root();
+        let finalized_bank = self.bank_forks.read().unwrap().get(finalized_slot);
+        let finalized_block_height = match finalized_bank {
+            Some(bank) => Some(bank.block_height()),
+            None => self
+                .blockstore
+                .get_block_height(finalized_slot)
+                .ok()
+                .flatten(),
+        };
+
+        let results = self
+            .blockstore
+            .get_rooted_signatures_for_address_after(address, finalized_slot, after, limit)
+            .map_err(|err| Error::invalid_params(format!("{err}")))?;
+        let cursor = results
+            .last()
+            .map(|(position, _)| *position)
+            .or(after)
+            .map(format_address_signature_cursor);
+        let signatures = results
+            .into_iter()
+            .map(|(_, info)| {
+                let mut item: RpcConfirmedTransactionStatusWithSignature = info.into();
+                item.confirmation_status = Some(TransactionConfirmationStatus::Finalized);
+                item
+            })
+            .collect();
+        Ok(RpcFinalizedSignaturesForAddress {
+            finalized_slot,
+            finalized_block_height,
+            signatures,
+            cursor,
+        })
+    }
+
     pub async fn get_first_available_block(&self) -> Slot {
         let slot = self
             .blockstore
@@ -2210,6 +2361,39 @@ impl JsonRpcRequestProcessor {
         Ok(new_response(&bank, accounts))
     }
 
+    pub async fn get_stake_accounts_by_vote_account(
+        &self,
+        vote_pubkey: Pubkey,
+        config: Option<RpcAccountInfoConfig>,
+        sort_results: bool,
+    ) -> Result<RpcResponse<Vec<RpcKeyedAccount>>> {
+        let RpcAccountInfoConfig {
+            encoding,
+            data_slice: data_slice_config,
+            commitment,
+            min_context_slot,
+        } = config.unwrap_or_default();
+        let bank = self.get_bank_with_config(RpcContextConfig {
+            commitment,
+            min_context_slot,
+        })?;
+        let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
+
+        let keyed_accounts = self
+            .get_filtered_stake_accounts_by_voter(Arc::clone(&bank), vote_pubkey, sort_results)
+            .await?;
+        let accounts = keyed_accounts
+            .into_iter()
+            .map(|(pubkey, account)| {
+                Ok(RpcKeyedAccount {
+                    pubkey: pubkey.to_string(),
+                    account: encode_account(&account, &pubkey, encoding, data_slice_config)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(new_response(&bank, accounts))
+    }
+
     /// Use a set of filters to get an iterator of keyed program accounts from a bank
     async fn get_filtered_program_accounts(
         &self,
@@ -2359,6 +2543,54 @@ impl JsonRpcRequestProcessor {
         }
     }
 
+    /// Get an iterator of the stake accounts delegated to a vote account
+    async fn get_filtered_stake_accounts_by_voter(
+        &self,
+        bank: Arc<Bank>,
+        voter_key: Pubkey,
+        sort_results: bool,
+    ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
+        // The by-voter accounts index checks for Stake state and voter address on inclusion, but
+        // keeps the stake accounts which were since redelegated, closed or reinitialized until
+        // they are purged. We include the redundant filters here to avoid returning these
+        // accounts.
+        let filters = vec![
+            // Filter on Stake state
+            RpcFilterType::DataSize(StakeStateV2::size_of() as u64),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, bincode::serialize(&2u32).unwrap())),
+            // Filter on voter address
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                STAKE_ACCOUNT_VOTER_OFFSET,
+                voter_key.to_bytes().into(),
+            )),
+        ];
+        if self
+            .config
+            .account_indexes
+            .contains(&AccountIndex::StakeVoter)
+        {
+            if !self.config.account_indexes.include_key(&voter_key) {
+                return Err(RpcCustomError::KeyExcludedFromSecondaryIndex {
+                    index_key: voter_key.to_string(),
+                });
+            }
+            self.get_filtered_indexed_accounts(
+                &bank,
+                &IndexKey::StakeVoter(voter_key),
+                &stake::program::id(),
+                filters,
+                sort_results,
+            )
+            .await
+            .map_err(|e| RpcCustomError::ScanError {
+                message: e.to_string(),
+            })
+        } else {
+            self.get_filtered_program_accounts(bank, stake::program::id(), filters, sort_results)
+                .await
+        }
+    }
+
     fn get_latest_blockhash(&self, config: RpcContextConfig) -> Result<RpcResponse<RpcBlockhash>> {
         let bank = self.get_bank_with_config(config)?;
         let blockhash = bank.last_blockhash();
@@ -2405,6 +2637,454 @@ impl JsonRpcRequestProcessor {
             })
             .collect())
     }
+
+    fn get_program_fee_market(&self, program_id: Pubkey) -> Result<RpcProgramFeeMarket> {
+        if !self.prioritization_fee_cache.has_program_attribution() {
+            return Err(error::Error {
+                code: error::ErrorCode::InvalidRequest,
+                message: "Program fee market is not enabled on this node".to_string(),
+                data: None,
+            });
+        }
+        let program_fees = self.prioritization_fee_cache.get_program_fees(&program_id);
+        let blocks = program_fees.len() as u64;
+        let mut transactions = 0u64;
+        let mut compute_unit_prices = vec![];
+        let slots: Vec<_> = program_fees
+            .into_iter()
+            .filter(|(_, program_fees)| program_fees.transactions > 0)
+            .map(|(slot, mut program_fees)| {
+                transactions = transactions.saturating_add(program_fees.transactions);
+                compute_unit_prices.extend_from_slice(&program_fees.compute_unit_prices);
+                program_fees.compute_unit_prices.sort_unstable();
+                RpcProgramFee {
+                    slot,
+                    transactions: program_fees.transactions,
+                    min_compute_unit_price: compute_unit_price_percentile(
+                        &program_fees.compute_unit_prices,
+                        0,
+                    ),
+                    median_compute_unit_price: compute_unit_price_percentile(
+                        &program_fees.compute_unit_prices,
+                        50,
+                    ),
+                    max_compute_unit_price: compute_unit_price_percentile(
+                        &program_fees.compute_unit_prices,
+                        100,
+                    ),
+                }
+            })
+            .collect();
+        compute_unit_prices.sort_unstable();
+        let blocks_with_transactions = slots.len() as u64;
+        Ok(RpcProgramFeeMarket {
+            program_id: program_id.to_string(),
+            blocks,
+            blocks_with_transactions,
+            inclusion_rate: if blocks == 0 {
+                0.0
+            } else {
+                blocks_with_transactions as f64 / blocks as f64
+            },
+            transactions,
+            min_compute_unit_price: compute_unit_price_percentile(&compute_unit_prices, 0),
+            median_compute_unit_price: compute_unit_price_percentile(&compute_unit_prices, 50),
+            p90_compute_unit_price: compute_unit_price_percentile(&compute_unit_prices, 90),
+            max_compute_unit_price: compute_unit_price_percentile(&compute_unit_prices, 100),
+            slots,
+        })
+    }
+
+    fn get_slashing_evidence(
+        &self,
+        config: RpcSlashingEvidenceConfig,
+    ) -> Result<Vec<RpcSlashingEvidence>> {
+        let RpcSlashingEvidenceConfig { start_slot, limit } = config;
+        let start_slot = start_slot.unwrap_or_default();
+        let limit = limit.unwrap_or(SLASHING_EVIDENCE_LIMIT);
+        if limit > SLASHING_EVIDENCE_LIMIT {
+            return Err(Error::invalid_params(format!(
+                "Invalid limit; max {SLASHING_EVIDENCE_LIMIT}"
+            )));
+        }
+        let map_err = |err| {
+            warn!("get_slashing_evidence failed: {err:?}");
+            Error::invalid_request()
+        };
+
+        let bank = self.bank(Some(CommitmentConfig::finalized()));
+        let duplicate_blocks = self
+            .blockstore
+            .duplicate_slots_iterator(start_slot)
+            .map_err(map_err)?
+            .filter_map(|slot| {
+                let proof = self.blockstore.get_duplicate_slot(slot)?;
+                Some(RpcSlashingEvidence {
+                    slot,
+                    kind: RpcSlashingEvidenceKind::DuplicateBlock,
+                    offender: self
+                        .leader_schedule_cache
+                        .slot_leader_at(slot, Some(&bank))
+                        .map(|leader| leader.to_string()),
+                    proof: vec![
+                        BASE64_STANDARD.encode(&proof.shred1),
+                        BASE64_STANDARD.encode(&proof.shred2),
+                    ],
+                })
+            })
+            .take(limit);
+        let duplicate_votes = self
+            .blockstore
+            .duplicate_votes_iterator(start_slot)
+            .map_err(map_err)?
+            .map(|(slot, vote_account, proof)| RpcSlashingEvidence {
+                slot,
+                kind: RpcSlashingEvidenceKind::DuplicateVote,
+                offender: Some(vote_account.to_string()),
+                proof: vec![
+                    BASE64_STANDARD.encode(&proof.transaction1),
+                    BASE64_STANDARD.encode(&proof.transaction2),
+                ],
+            })
+            .take(limit);
+
+        let mut evidence: Vec<_> = duplicate_blocks.chain(duplicate_votes).collect();
+        evidence.sort_by_key(|evidence| evidence.slot);
+        evidence.truncate(limit);
+        Ok(evidence)
+    }
+
+    fn webhook_registry_or_err(&self) -> Result<&Arc<WebhookRegistry>> {
+        self.webhook_registry.as_ref().ok_or_else(|| error::Error {
+            code: error::ErrorCode::InvalidRequest,
+            message: "Webhooks are not enabled on this node".to_string(),
+            data: None,
+        })
+    }
+
+    fn register_webhook(
+        &self,
+        url: String,
+        addresses: Vec<Pubkey>,
+        config: RpcRegisterWebhookConfig,
+    ) -> Result<RpcWebhookRegistration> {
+        let registry = self.webhook_registry_or_err()?;
+        let commitment = config.commitment.unwrap_or_default().commitment;
+        registry
+            .register(&url, addresses, commitment)
+            .map_err(|err| Error::invalid_params(err.to_string()))
+    }
+
+    fn unregister_webhook(&self, id: String, secret: String) -> Result<bool> {
+        let registry = self.webhook_registry_or_err()?;
+        let id = id
+            .parse()
+            .map_err(|_| Error::invalid_params("Invalid webhook id"))?;
+        let secret =
+            hex::decode(secret).map_err(|_| Error::invalid_params("Invalid webhook secret"))?;
+        Ok(registry.unregister(id, &secret))
+    }
+
+    fn record_ingestion_event(&self, signature: Signature, event: IngestionEvent) {
+        if let Some(ingestion_log) = &self.config.transaction_ingestion_log {
+            ingestion_log.record(signature, event);
+        }
+    }
+
+    fn get_transaction_ingestion_status(
+        &self,
+        signature: Signature,
+    ) -> Result<RpcTransactionIngestionStatus> {
+        let ingestion_log = self
+            .config
+            .transaction_ingestion_log
+            .as_ref()
+            .ok_or_else(|| error::Error {
+                code: error::ErrorCode::InvalidRequest,
+                message: "Transaction ingestion log is not enabled on this node".to_string(),
+                data: None,
+            })?;
+        let events: Vec<_> = ingestion_log
+            .get_events(&signature)
+            .into_iter()
+            .map(rpc_transaction_ingestion_event_from_ingestion_event)
+            .collect();
+        Ok(RpcTransactionIngestionStatus {
+            last_seen: events.last().cloned(),
+            events,
+        })
+    }
+
+    fn get_block_fee_summary(&self, slot: Slot) -> Result<Option<RpcBlockFeeSummary>> {
+        let result = self.blockstore.get_block_fee_summary(slot);
+        self.check_slot_cleaned_up(&result, slot)?;
+        if let Some(fee_summary) = result.ok().flatten() {
+            return Ok(Some(rpc_block_fee_summary_from_block_fee_summary(
+                fee_summary,
+            )));
+        }
+        // The summary of a frozen bank may not have been written to the blockstore yet
+        let bank = self.bank_forks.read().unwrap().get(slot);
+        Ok(bank.filter(|bank| bank.is_frozen()).map(|bank| {
+            let fee_summary = bank.fee_summary();
+            rpc_block_fee_summary_from_block_fee_summary(BlockFeeSummary {
+                leader: *bank.collector_id(),
+                transaction_fee: fee_summary.transaction_fee,
+                priority_fee: fee_summary.priority_fee,
+                burned: fee_summary.burned,
+                leader_reward: fee_summary.leader_reward,
+                treasury: fee_summary.treasury,
+            })
+        }))
+    }
+
+    fn get_supply_deltas(
+        &self,
+        start_slot: Option<Slot>,
+        limit: Option<usize>,
+    ) -> Result<Vec<RpcSupplyDelta>> {
+        let supply_audit = self
+            .config
+            .supply_audit
+            .as_ref()
+            .ok_or_else(|| error::Error {
+                code: error::ErrorCode::InvalidRequest,
+                message: "Supply audit is not enabled on this node".to_string(),
+                data: None,
+            })?;
+        let limit = limit.unwrap_or(MAX_GET_SUPPLY_DELTAS_LIMIT);
+        if limit > MAX_GET_SUPPLY_DELTAS_LIMIT {
+            return Err(Error::invalid_params(format!(
+                "Limit too large; max {MAX_GET_SUPPLY_DELTAS_LIMIT}"
+            )));
+        }
+        Ok(supply_audit
+            .entries(start_slot.unwrap_or_default(), limit)
+            .into_iter()
+            .map(rpc_supply_delta_from_supply_audit_entry)
+            .collect())
+    }
+
+    fn get_vote_overhead(&self, config: RpcContextConfig) -> Result<RpcVoteOverhead> {
+        let bank = self.get_bank_with_config(config)?;
+        let vote_overhead = bank.vote_overhead();
+        let lamports_per_signature = bank.get_lamports_per_signature();
+        Ok(RpcVoteOverhead {
+            slot: bank.slot(),
+            current_epoch: rpc_epoch_vote_overhead(
+                vote_overhead.epoch,
+                vote_overhead.stats,
+                lamports_per_signature,
+            ),
+            previous_epoch: vote_overhead.previous_epoch.map(|(epoch, stats)| {
+                rpc_epoch_vote_overhead(epoch, stats, lamports_per_signature)
+            }),
+        })
+    }
+
+    fn get_confirmation_latency_stats(&self) -> RpcConfirmationLatencyStats {
+        self.confirmation_latency.stats()
+    }
+
+    fn get_account_info_at_slot(
+        &self,
+        pubkey: &Pubkey,
+        slot: Slot,
+        config: Option<RpcAccountInfoConfig>,
+    ) -> Result<RpcResponse<Option<UiAccount>>> {
+        if self.config.archival_accounts.is_none() {
+            return Err(error::Error {
+                code: error::ErrorCode::InvalidRequest,
+                message: "Archival accounts are not enabled on this node".to_string(),
+                data: None,
+            });
+        }
+        // The slot is given explicitly, so the commitment of the config is not used
+        let RpcAccountInfoConfig {
+            encoding,
+            data_slice,
+            ..
+        } = config.unwrap_or_default();
+        let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
+        let max_root = self.blockstore.max_root();
+        if slot > max_root {
+            return Err(Error::invalid_params(format!(
+                "Slot {slot} is not rooted yet; the highest rooted slot is {max_root}"
+            )));
+        }
+
+        let result = self.blockstore.get_archived_account(pubkey, slot);
+        self.check_slot_cleaned_up(&result, slot)?;
+        let value = match result.map_err(|_| Error::internal_error())? {
+            Some((_written_slot, account)) if account.lamports > 0 => {
+                Some(encode_account(&account, pubkey, encoding, data_slice)?)
+            }
+            _ => None,
+        };
+        Ok(RpcResponse {
+            context: RpcResponseContext::new(slot),
+            value,
+        })
+    }
+
+    fn get_account_proof(&self, pubkey: &Pubkey, slot: Slot) -> Result<RpcAccountProof> {
+        let bank = self.bank_forks.read().unwrap().get(slot).ok_or_else(|| {
+            Error::invalid_params(format!(
+                "Bank for slot {slot} is not available; proofs can only be produced for \
+                 recent slots"
+            ))
+        })?;
+        let proof = bank
+            .account_proof(pubkey)
+            .map_err(|err| Error::invalid_params(err.to_string()))?;
+        Ok(RpcAccountProof {
+            slot: proof.slot,
+            bank_hash: proof.bank_hash.to_string(),
+            parent_hash: proof.parent_hash.to_string(),
+            signature_count: proof.signature_count,
+            last_blockhash: proof.last_blockhash.to_string(),
+            accounts_delta_hash: proof.accounts_delta_hash.to_string(),
+            accounts_lt_hash: proof
+                .accounts_lt_hash
+                .map(|accounts_lt_hash| BASE64_STANDARD.encode(accounts_lt_hash)),
+            epoch_accounts_hash: proof
+                .epoch_accounts_hash
+                .map(|epoch_accounts_hash| epoch_accounts_hash.to_string()),
+            hard_fork_data: proof
+                .hard_fork_data
+                .map(|hard_fork_data| BASE64_STANDARD.encode(hard_fork_data)),
+            account: encode_account(&proof.account, pubkey, UiAccountEncoding::Base64, None)?,
+            proof: proof
+                .merkle_proof
+                .into_iter()
+                .map(|level| RpcMerkleProofLevel {
+                    index: level.index,
+                    siblings: level.siblings.iter().map(Hash::to_string).collect(),
+                })
+                .collect(),
+        })
+    }
+
+    fn get_optimistic_confirmation_attestations(
+        &self,
+        slot: Slot,
+    ) -> Option<RpcOptimisticConfirmationAttestations> {
+        self.optimistic_confirmation_attestations.get(slot)
+    }
+
+    /// Returns the staked vote accounts of the epoch stakes of `epoch`, held by the bank, by
+    /// descending stake
+    pub(crate) fn epoch_vote_account_stakes(
+        bank: &Bank,
+        epoch: Epoch,
+    ) -> Result<(u64, Vec<RpcEpochVoteAccountStake>)> {
+        let epoch_stakes = bank.epoch_stakes(epoch).ok_or_else(|| {
+            Error::invalid_params(format!(
+                "Stakes of epoch {epoch} are not available; only the stakes of recent epochs, \
+                 and of the epochs archived when enabled, are held"
+            ))
+        })?;
+        let mut vote_accounts: Vec<_> = epoch_stakes
+            .stakes()
+            .vote_accounts()
+            .as_ref()
+            .iter()
+            .filter(|(_, (stake, _))| *stake > 0)
+            .map(
+                |(vote_pubkey, (stake, vote_account))| RpcEpochVoteAccountStake {
+                    vote_pubkey: vote_pubkey.to_string(),
+                    node_pubkey: vote_account.node_pubkey().to_string(),
+                    stake: *stake,
+                    commission: vote_account.vote_state().commission,
+                },
+            )
+            .collect();
+        vote_accounts.sort_by(|a, b| {
+            b.stake
+                .cmp(&a.stake)
+                .then_with(|| a.vote_pubkey.cmp(&b.vote_pubkey))
+        });
+        Ok((epoch_stakes.total_stake(), vote_accounts))
+    }
+
+    fn get_epoch_stakes(
+        &self,
+        epoch: Option<Epoch>,
+        config: RpcContextConfig,
+    ) -> Result<RpcEpochStakes> {
+        let bank = self.get_bank_with_config(config)?;
+        let epoch = epoch.unwrap_or_else(|| bank.epoch());
+        if bank.epoch_stakes(epoch).is_none() {
+            if let Some(epoch_stakes) = self
+                .config
+                .epoch_stakes_archive
+                .as_ref()
+                .and_then(|archive| archive.get(epoch))
+            {
+                return Ok(epoch_stakes);
+            }
+        }
+        let (total_stake, vote_accounts) = Self::epoch_vote_account_stakes(&bank, epoch)?;
+        Ok(RpcEpochStakes {
+            epoch,
+            total_stake,
+            vote_accounts,
+        })
+    }
+
+    fn get_leader_schedule_with_stakes(
+        &self,
+        epoch: Option<Epoch>,
+        config: RpcContextConfig,
+    ) -> Result<Option<RpcLeaderScheduleWithStakes>> {
+        let bank = self.get_bank_with_config(config)?;
+        let epoch = epoch.unwrap_or_else(|| bank.epoch());
+        let Some(leader_schedule) = self.leader_schedule_cache.get_epoch_leader_schedule(epoch)
+        else {
+            return Ok(None);
+        };
+        let (total_stake, vote_accounts) = Self::epoch_vote_account_stakes(&bank, epoch)?;
+
+        let mut vote_accounts_by_identity: HashMap<String, Vec<RpcEpochVoteAccountStake>> =
+            HashMap::new();
+        for vote_account in vote_accounts {
+            vote_accounts_by_identity
+                .entry(vote_account.node_pubkey.clone())
+                .or_default()
+                .push(vote_account);
+        }
+        let mut leaders: Vec<_> =
+            solana_ledger::leader_schedule_utils::leader_schedule_by_identity(
+                leader_schedule.get_slot_leaders().iter().enumerate(),
+            )
+            .into_iter()
+            .map(|(identity, slots)| {
+                let vote_accounts = vote_accounts_by_identity
+                    .remove(&identity)
+                    .unwrap_or_default();
+                RpcLeaderStake {
+                    stake: vote_accounts
+                        .iter()
+                        .map(|vote_account| vote_account.stake)
+                        .sum(),
+                    identity,
+                    slots,
+                    vote_accounts,
+                }
+            })
+            .collect();
+        leaders.sort_by(|a, b| {
+            b.stake
+                .cmp(&a.stake)
+                .then_with(|| a.identity.cmp(&b.identity))
+        });
+        Ok(Some(RpcLeaderScheduleWithStakes {
+            epoch,
+            first_slot: bank.epoch_schedule().get_first_slot_in_epoch(epoch),
+            total_stake,
+            leaders,
+        }))
+    }
 }
 
 pub(crate) fn optimize_filters(filters: &mut [RpcFilterType]) {
@@ -2438,6 +3118,24 @@ fn verify_transaction(
     Ok(())
 }
 
+fn verify_compute_unit_price(
+    transaction: &SanitizedTransaction,
+    fee_features: solana_fee::FeeFeatures,
+) -> Result<()> {
+    if !fee_features.enforce_min_compute_unit_price {
+        return Ok(());
+    }
+    match solana_fee::compute_unit_price_below_minimum(transaction, fee_features) {
+        Some(compute_unit_price) => Err(RpcCustomError::ComputeUnitPriceBelowMinimum {
+            compute_unit_price,
+            min_compute_unit_price: fee_features.min_compute_unit_price,
+            compute_units_threshold: solana_fee::MIN_COMPUTE_UNITS_THRESHOLD,
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
 pub(crate) fn verify_filters(filters: &[RpcFilterType]) -> Result<()> {
     if filters.len() > MAX_GET_PROGRAM_ACCOUNT_FILTERS {
         return Err(Error::invalid_params(format!(
@@ -2474,12 +3172,67 @@ fn verify_signature(input: &str) -> Result<Signature> {
         .map_err(|e| Error::invalid_params(format!("Invalid param: {e:?}")))
 }
 
-fn verify_token_account_filter(
-    token_account_filter: RpcTokenAccountsFilter,
-) -> Result<TokenAccountsFilter> {
-    match token_account_filter {
-        RpcTokenAccountsFilter::Mint(mint_str) => {
-            let mint = verify_pubkey(&mint_str)?;
+/// Builds the accounts a simulation loads in place of the bank's. Programs and sysvars are
+/// loaded from caches which the overrides don't reach, so they can't be overridden.
+fn verify_account_overrides(
+    bank: &Bank,
+    account_overrides: HashMap<String, RpcAccountOverride>,
+) -> Result<AccountOverrides> {
+    if account_overrides.len() > MAX_MULTIPLE_ACCOUNTS {
+        return Err(Error::invalid_params(format!(
+            "Too many account overrides provided; max {MAX_MULTIPLE_ACCOUNTS}"
+        )));
+    }
+    let mut overrides = AccountOverrides::default();
+    for (address, account_override) in account_overrides {
+        let pubkey = verify_pubkey(&address)?;
+        let mut account = bank
+            .get_account(&pubkey)
+            .unwrap_or_else(|| AccountSharedData::new(0, 0, &system_program::id()));
+        if account.executable() || sysvar::check_id(account.owner()) {
+            return Err(Error::invalid_params(format!(
+                "Account {pubkey} is a program or sysvar and can't be overridden"
+            )));
+        }
+        if let Some(lamports) = account_override.lamports {
+            account.set_lamports(lamports);
+        }
+        if let Some((data, encoding)) = account_override.data {
+            if encoding != UiAccountEncoding::Base64 {
+                return Err(Error::invalid_params(format!(
+                    "Unsupported encoding of the data of account override {pubkey}: {encoding:?}"
+                )));
+            }
+            let data = BASE64_STANDARD.decode(data).map_err(|err| {
+                Error::invalid_params(format!("Invalid data of account override {pubkey}: {err}"))
+            })?;
+            if data.len() > MAX_PERMITTED_DATA_LENGTH as usize {
+                return Err(Error::invalid_params(format!(
+                    "Data of account override {pubkey} exceeds {MAX_PERMITTED_DATA_LENGTH} bytes"
+                )));
+            }
+            account.set_data_from_slice(&data);
+        }
+        if let Some(owner) = account_override.owner {
+            let owner = verify_pubkey(&owner)?;
+            if sysvar::check_id(&owner) {
+                return Err(Error::invalid_params(format!(
+                    "Account override {pubkey} can't be owned by the sysvar program"
+                )));
+            }
+            account.set_owner(owner);
+        }
+        overrides.set_account(&pubkey, Some(account));
+    }
+    Ok(overrides)
+}
+
+fn verify_token_account_filter(
+    token_account_filter: RpcTokenAccountsFilter,
+) -> Result<TokenAccountsFilter> {
+    match token_account_filter {
+        RpcTokenAccountsFilter::Mint(mint_str) => {
+            let mint = verify_pubkey(&mint_str)?;
             Ok(TokenAccountsFilter::Mint(mint))
         }
         RpcTokenAccountsFilter::ProgramId(program_id_str) => {
@@ -2510,6 +3263,70 @@ fn verify_and_parse_signatures_for_address_params(
     Ok((address, before, until, limit))
 }
 
+fn verify_and_parse_signatures_for_address_filter(
+    min_block_time: Option<UnixTimestamp>,
+    max_block_time: Option<UnixTimestamp>,
+    program_id: Option<String>,
+) -> Result<SignaturesForAddressFilter> {
+    if let (Some(min_block_time), Some(max_block_time)) = (min_block_time, max_block_time) {
+        if min_block_time > max_block_time {
+            return Err(Error::invalid_params(
+                "Invalid block time range: minBlockTime is greater than maxBlockTime",
+            ));
+        }
+    }
+    let program_id = program_id
+        .map(|ref program_id| verify_pubkey(program_id))
+        .transpose()?;
+    Ok(SignaturesForAddressFilter {
+        min_block_time,
+        max_block_time,
+        program_id,
+    })
+}
+
+fn verify_and_parse_finalized_signatures_for_address_params(
+    address: String,
+    after: Option<String>,
+    limit: Option<usize>,
+) -> Result<(Pubkey, Option<AddressSignaturePosition>, usize)> {
+    let address = verify_pubkey(&address)?;
+    let after = after
+        .map(|ref after| parse_address_signature_cursor(after))
+        .transpose()?;
+    let limit = limit.unwrap_or(MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT);
+
+    if limit == 0 || limit > MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT {
+        return Err(Error::invalid_params(format!(
+            "Invalid limit; max {MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT}"
+        )));
+    }
+    Ok((address, after, limit))
+}
+
+/// Cursors of `getFinalizedSignaturesForAddress` are the slot, transaction index and signature
+/// of a signature, separated by colons
+fn format_address_signature_cursor(
+    (slot, transaction_index, signature): AddressSignaturePosition,
+) -> String {
+    format!("{slot}:{transaction_index}:{signature}")
+}
+
+fn parse_address_signature_cursor(input: &str) -> Result<AddressSignaturePosition> {
+    let invalid_cursor = || Error::invalid_params(format!("Invalid cursor: {input}"));
+    let mut parts = input.splitn(3, ':');
+    let (Some(slot), Some(transaction_index), Some(signature)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid_cursor());
+    };
+    Ok((
+        slot.parse().map_err(|_| invalid_cursor())?,
+        transaction_index.parse().map_err(|_| invalid_cursor())?,
+        signature.parse().map_err(|_| invalid_cursor())?,
+    ))
+}
+
 pub(crate) fn check_is_at_least_confirmed(commitment: CommitmentConfig) -> Result<()> {
     if !commitment.is_at_least_confirmed() {
         return Err(Error::invalid_params(
@@ -2704,6 +3521,7 @@ fn _send_transaction(
     last_valid_block_height: u64,
     durable_nonce_info: Option<(Pubkey, Hash)>,
     max_retries: Option<usize>,
+    compute_unit_price_escalation: Option<ComputeUnitPriceEscalation>,
 ) -> Result<String> {
     let transaction_info = TransactionInfo::new(
         signature,
@@ -2712,7 +3530,8 @@ fn _send_transaction(
         durable_nonce_info,
         max_retries,
         None,
-    );
+    )
+    .with_compute_unit_price_escalation(compute_unit_price_escalation);
     meta.transaction_sender
         .send(transaction_info)
         .unwrap_or_else(|err| warn!("Failed to enqueue transaction: {}", err));
@@ -3368,6 +4187,14 @@ pub mod rpc_accounts_scan {
             token_account_filter: RpcTokenAccountsFilter,
             config: Option<RpcAccountInfoConfig>,
         ) -> BoxFuture<Result<RpcResponse<Vec<RpcKeyedAccount>>>>;
+
+        #[rpc(meta, name = "getStakeAccountsByVoteAccount")]
+        fn get_stake_accounts_by_vote_account(
+            &self,
+            meta: Self::Metadata,
+            vote_pubkey_str: String,
+            config: Option<RpcAccountInfoConfig>,
+        ) -> BoxFuture<Result<RpcResponse<Vec<RpcKeyedAccount>>>>;
     }
 
     pub struct AccountsScanImpl;
@@ -3477,6 +4304,24 @@ pub mod rpc_accounts_scan {
             }
             .boxed()
         }
+
+        fn get_stake_accounts_by_vote_account(
+            &self,
+            meta: Self::Metadata,
+            vote_pubkey_str: String,
+            config: Option<RpcAccountInfoConfig>,
+        ) -> BoxFuture<Result<RpcResponse<Vec<RpcKeyedAccount>>>> {
+            debug!(
+                "get_stake_accounts_by_vote_account rpc request received: {:?}",
+                vote_pubkey_str
+            );
+            async move {
+                let vote_pubkey = verify_pubkey(&vote_pubkey_str)?;
+                meta.get_stake_accounts_by_vote_account(vote_pubkey, config, true)
+                    .await
+            }
+            .boxed()
+        }
     }
 }
 
@@ -3549,6 +4394,14 @@ pub mod rpc_full {
             config: Option<RpcSimulateTransactionConfig>,
         ) -> Result<RpcResponse<RpcSimulateTransactionResult>>;
 
+        #[rpc(meta, name = "simulateBundle")]
+        fn simulate_bundle(
+            &self,
+            meta: Self::Metadata,
+            data: Vec<String>,
+            config: Option<RpcSimulateBundleConfig>,
+        ) -> Result<RpcResponse<RpcSimulateBundleResult>>;
+
         #[rpc(meta, name = "minimumLedgerSlot")]
         fn minimum_ledger_slot(&self, meta: Self::Metadata) -> Result<Slot>;
 
@@ -3601,6 +4454,14 @@ pub mod rpc_full {
             config: Option<RpcSignaturesForAddressConfig>,
         ) -> BoxFuture<Result<Vec<RpcConfirmedTransactionStatusWithSignature>>>;
 
+        #[rpc(meta, name = "getFinalizedSignaturesForAddress")]
+        fn get_finalized_signatures_for_address(
+            &self,
+            meta: Self::Metadata,
+            address: String,
+            config: Option<RpcFinalizedSignaturesForAddressConfig>,
+        ) -> Result<RpcFinalizedSignaturesForAddress>;
+
         #[rpc(meta, name = "getFirstAvailableBlock")]
         fn get_first_available_block(&self, meta: Self::Metadata) -> BoxFuture<Result<Slot>>;
 
@@ -3640,6 +4501,112 @@ pub mod rpc_full {
             meta: Self::Metadata,
             pubkey_strs: Option<Vec<String>>,
         ) -> Result<Vec<RpcPrioritizationFee>>;
+
+        #[rpc(meta, name = "getProgramFeeMarket")]
+        fn get_program_fee_market(
+            &self,
+            meta: Self::Metadata,
+            program_id_str: String,
+        ) -> Result<RpcProgramFeeMarket>;
+
+        #[rpc(meta, name = "getSlashingEvidence")]
+        fn get_slashing_evidence(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcSlashingEvidenceConfig>,
+        ) -> Result<Vec<RpcSlashingEvidence>>;
+
+        #[rpc(meta, name = "registerWebhook")]
+        fn register_webhook(
+            &self,
+            meta: Self::Metadata,
+            url: String,
+            address_strs: Vec<String>,
+            config: Option<RpcRegisterWebhookConfig>,
+        ) -> Result<RpcWebhookRegistration>;
+
+        #[rpc(meta, name = "unregisterWebhook")]
+        fn unregister_webhook(
+            &self,
+            meta: Self::Metadata,
+            id: String,
+            secret: String,
+        ) -> Result<bool>;
+
+        #[rpc(meta, name = "getTransactionIngestionStatus")]
+        fn get_transaction_ingestion_status(
+            &self,
+            meta: Self::Metadata,
+            signature_str: String,
+        ) -> Result<RpcTransactionIngestionStatus>;
+
+        #[rpc(meta, name = "getBlockFeeSummary")]
+        fn get_block_fee_summary(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+        ) -> Result<Option<RpcBlockFeeSummary>>;
+
+        #[rpc(meta, name = "getSupplyDeltas")]
+        fn get_supply_deltas(
+            &self,
+            meta: Self::Metadata,
+            start_slot: Option<Slot>,
+            limit: Option<usize>,
+        ) -> Result<Vec<RpcSupplyDelta>>;
+
+        #[rpc(meta, name = "getVoteOverhead")]
+        fn get_vote_overhead(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcContextConfig>,
+        ) -> Result<RpcVoteOverhead>;
+
+        #[rpc(meta, name = "getAccountInfoAtSlot")]
+        fn get_account_info_at_slot(
+            &self,
+            meta: Self::Metadata,
+            pubkey_str: String,
+            slot: Slot,
+            config: Option<RpcAccountInfoConfig>,
+        ) -> Result<RpcResponse<Option<UiAccount>>>;
+
+        #[rpc(meta, name = "getConfirmationLatencyStats")]
+        fn get_confirmation_latency_stats(
+            &self,
+            meta: Self::Metadata,
+        ) -> Result<RpcConfirmationLatencyStats>;
+
+        #[rpc(meta, name = "getAccountProof")]
+        fn get_account_proof(
+            &self,
+            meta: Self::Metadata,
+            pubkey_str: String,
+            slot: Slot,
+        ) -> Result<RpcAccountProof>;
+
+        #[rpc(meta, name = "getOptimisticConfirmationAttestations")]
+        fn get_optimistic_confirmation_attestations(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+        ) -> Result<Option<RpcOptimisticConfirmationAttestations>>;
+
+        #[rpc(meta, name = "getEpochStakes")]
+        fn get_epoch_stakes(
+            &self,
+            meta: Self::Metadata,
+            epoch: Option<Epoch>,
+            config: Option<RpcContextConfig>,
+        ) -> Result<RpcEpochStakes>;
+
+        #[rpc(meta, name = "getLeaderScheduleWithStakes")]
+        fn get_leader_schedule_with_stakes(
+            &self,
+            meta: Self::Metadata,
+            epoch: Option<Epoch>,
+            config: Option<RpcContextConfig>,
+        ) -> Result<Option<RpcLeaderScheduleWithStakes>>;
     }
 
     pub struct FullImpl;
@@ -3829,6 +4796,7 @@ pub mod rpc_full {
                 last_valid_block_height,
                 None,
                 None,
+                None,
             )
         }
 
@@ -3845,6 +4813,7 @@ pub mod rpc_full {
                 encoding,
                 max_retries,
                 min_context_slot,
+                compute_unit_price_escalation_rotations,
             } = config.unwrap_or_default();
             let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
             let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
@@ -3871,6 +4840,21 @@ pub mod rpc_full {
                 preflight_bank.get_reserved_account_keys(),
             )?;
             let signature = *transaction.signature();
+            meta.record_ingestion_event(
+                signature,
+                IngestionEvent::new(IngestionStage::ReceivedViaRpc)
+                    .with_slot(preflight_bank.slot()),
+            );
+            let filtered = |err: Error| {
+                meta.record_ingestion_event(
+                    signature,
+                    IngestionEvent::new(IngestionStage::Filtered).with_reason(err.message.clone()),
+                );
+                err
+            };
+            // Leaders drop these regardless of preflight, so reject them up front
+            verify_compute_unit_price(&transaction, preflight_bank.fee_features())
+                .map_err(filtered)?;
 
             let mut last_valid_block_height = preflight_bank
                 .get_blockhash_last_valid_block_height(transaction.message().recent_blockhash())
@@ -3888,24 +4872,28 @@ pub mod rpc_full {
             }
 
             if !skip_preflight {
-                verify_transaction(&transaction, &preflight_bank.feature_set)?;
+                verify_transaction(&transaction, &preflight_bank.feature_set).map_err(filtered)?;
 
                 if !meta.config.skip_preflight_health_check {
                     match meta.health.check() {
                         RpcHealthStatus::Ok => (),
                         RpcHealthStatus::Unknown => {
                             inc_new_counter_info!("rpc-send-tx_health-unknown", 1);
-                            return Err(RpcCustomError::NodeUnhealthy {
-                                num_slots_behind: None,
-                            }
-                            .into());
+                            return Err(filtered(
+                                RpcCustomError::NodeUnhealthy {
+                                    num_slots_behind: None,
+                                }
+                                .into(),
+                            ));
                         }
                         RpcHealthStatus::Behind { num_slots } => {
                             inc_new_counter_info!("rpc-send-tx_health-behind", 1);
-                            return Err(RpcCustomError::NodeUnhealthy {
-                                num_slots_behind: Some(num_slots),
-                            }
-                            .into());
+                            return Err(filtered(
+                                RpcCustomError::NodeUnhealthy {
+                                    num_slots_behind: Some(num_slots),
+                                }
+                                .into(),
+                            ));
                         }
                     }
                 }
@@ -3923,26 +4911,50 @@ pub mod rpc_full {
                         TransactionError::BlockhashNotFound => {
                             inc_new_counter_info!("rpc-send-tx_err-blockhash-not-found", 1);
                         }
+                        TransactionError::FeeExceedsCap
+                        | TransactionError::FeePayerInsufficientForBaseFee
+                        | TransactionError::CongestionFeeRequired => {
+                            inc_new_counter_info!("rpc-send-tx_err-fee", 1);
+                        }
                         _ => {
                             inc_new_counter_info!("rpc-send-tx_err-other", 1);
                         }
                     }
-                    return Err(RpcCustomError::SendTransactionPreflightFailure {
-                        message: format!("Transaction simulation failed: {err}"),
-                        result: RpcSimulateTransactionResult {
-                            err: Some(err),
-                            logs: Some(logs),
-                            accounts: None,
-                            units_consumed: Some(units_consumed),
-                            return_data: return_data.map(|return_data| return_data.into()),
-                            inner_instructions: None,
-                            replacement_blockhash: None,
-                        },
-                    }
-                    .into());
+                    return Err(filtered(
+                        RpcCustomError::SendTransactionPreflightFailure {
+                            message: format!("Transaction simulation failed: {err}"),
+                            result: RpcSimulateTransactionResult {
+                                err: Some(err),
+                                logs: Some(logs),
+                                accounts: None,
+                                units_consumed: Some(units_consumed),
+                                return_data: return_data.map(|return_data| return_data.into()),
+                                inner_instructions: None,
+                                replacement_blockhash: None,
+                            },
+                        }
+                        .into(),
+                    ));
                 }
             }
 
+            let compute_unit_price_escalation =
+                compute_unit_price_escalation_rotations.map(|leader_rotations| {
+                    let message = transaction.message();
+                    ComputeUnitPriceEscalation::new(
+                        leader_rotations,
+                        solana_fee::get_compute_unit_price_from_message(&transaction)
+                            .unwrap_or_default(),
+                        message
+                            .account_keys()
+                            .iter()
+                            .enumerate()
+                            .filter(|(index, _)| message.is_writable(*index))
+                            .map(|(_, key)| *key)
+                            .collect(),
+                    )
+                });
+
             _send_transaction(
                 meta,
                 signature,
@@ -3950,6 +4962,7 @@ pub mod rpc_full {
                 last_valid_block_height,
                 durable_nonce_info,
                 max_retries,
+                compute_unit_price_escalation,
             )
         }
 
@@ -3968,6 +4981,7 @@ pub mod rpc_full {
                 accounts: config_accounts,
                 min_context_slot,
                 inner_instructions: enable_cpi_recording,
+                account_overrides,
             } = config.unwrap_or_default();
             let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
             let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
@@ -4007,6 +5021,10 @@ pub mod rpc_full {
             if sig_verify {
                 verify_transaction(&transaction, &bank.feature_set)?;
             }
+            verify_compute_unit_price(&transaction, bank.fee_features())?;
+            let account_overrides = account_overrides
+                .map(|account_overrides| verify_account_overrides(bank, account_overrides))
+                .transpose()?;
 
             let TransactionSimulationResult {
                 result,
@@ -4015,7 +5033,14 @@ pub mod rpc_full {
                 units_consumed,
                 return_data,
                 inner_instructions,
-            } = bank.simulate_transaction(&transaction, enable_cpi_recording);
+            } = match account_overrides {
+                Some(account_overrides) => bank.simulate_transaction_with_account_overrides(
+                    &transaction,
+                    enable_cpi_recording,
+                    account_overrides,
+                ),
+                None => bank.simulate_transaction(&transaction, enable_cpi_recording),
+            };
 
             let account_keys = transaction.message().account_keys();
             let number_of_accounts = account_keys.len();
@@ -4086,6 +5111,128 @@ pub mod rpc_full {
             ))
         }
 
+        fn simulate_bundle(
+            &self,
+            meta: Self::Metadata,
+            data: Vec<String>,
+            config: Option<RpcSimulateBundleConfig>,
+        ) -> Result<RpcResponse<RpcSimulateBundleResult>> {
+            debug!("simulate_bundle rpc request received");
+            let RpcSimulateBundleConfig {
+                sig_verify,
+                replace_recent_blockhash,
+                commitment,
+                encoding,
+                min_context_slot,
+                inner_instructions: enable_cpi_recording,
+                account_overrides,
+            } = config.unwrap_or_default();
+            if data.is_empty() || data.len() > MAX_SIMULATE_BUNDLE_TRANSACTIONS {
+                return Err(Error::invalid_params(format!(
+                    "Bundles must contain between 1 and {MAX_SIMULATE_BUNDLE_TRANSACTIONS} \
+                     transactions"
+                )));
+            }
+            let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
+            let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
+                Error::invalid_params(format!(
+                    "unsupported encoding: {tx_encoding}. Supported encodings: base58, base64"
+                ))
+            })?;
+
+            let bank = &*meta.get_bank_with_config(RpcContextConfig {
+                commitment,
+                min_context_slot,
+            })?;
+            let mut blockhash: Option<RpcBlockhash> = None;
+            if replace_recent_blockhash {
+                if sig_verify {
+                    return Err(Error::invalid_params(
+                        "sigVerify may not be used with replaceRecentBlockhash",
+                    ));
+                }
+                let recent_blockhash = bank.last_blockhash();
+                let last_valid_block_height = bank
+                    .get_blockhash_last_valid_block_height(&recent_blockhash)
+                    .expect("bank blockhash queue should contain blockhash");
+                blockhash.replace(RpcBlockhash {
+                    blockhash: recent_blockhash.to_string(),
+                    last_valid_block_height,
+                });
+            }
+
+            let mut signatures = HashSet::with_capacity(data.len());
+            let transactions = data
+                .into_iter()
+                .map(|data| {
+                    let (_, mut unsanitized_tx) =
+                        decode_and_deserialize::<VersionedTransaction>(data, binary_encoding)?;
+                    if replace_recent_blockhash {
+                        unsanitized_tx
+                            .message
+                            .set_recent_blockhash(bank.last_blockhash());
+                    }
+                    let transaction = sanitize_transaction(
+                        unsanitized_tx,
+                        bank,
+                        bank.get_reserved_account_keys(),
+                    )?;
+                    if sig_verify {
+                        verify_transaction(&transaction, &bank.feature_set)?;
+                    }
+                    verify_compute_unit_price(&transaction, bank.fee_features())?;
+                    if !signatures.insert(*transaction.signature()) {
+                        return Err(Error::invalid_params(format!(
+                            "Duplicate transaction {} in bundle",
+                            transaction.signature()
+                        )));
+                    }
+                    Ok(transaction)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let account_overrides = account_overrides
+                .map(|account_overrides| verify_account_overrides(bank, account_overrides))
+                .transpose()?
+                .unwrap_or_default();
+
+            let transaction_results: Vec<_> = bank
+                .simulate_bundle(&transactions, enable_cpi_recording, account_overrides)
+                .into_iter()
+                .zip(&transactions)
+                .map(|((simulation_result, fee_details), transaction)| {
+                    let account_keys = transaction.message().account_keys();
+                    RpcSimulateBundleTransactionResult {
+                        err: simulation_result.result.err(),
+                        logs: simulation_result.logs,
+                        units_consumed: simulation_result.units_consumed,
+                        transaction_fee: fee_details.transaction_fee(),
+                        priority_fee: fee_details.prioritization_fee(),
+                        return_data: simulation_result
+                            .return_data
+                            .map(|return_data| return_data.into()),
+                        inner_instructions: simulation_result.inner_instructions.map(|info| {
+                            map_inner_instructions(info)
+                                .map(|converted| {
+                                    parse_ui_inner_instructions(converted, &account_keys)
+                                })
+                                .collect()
+                        }),
+                    }
+                })
+                .collect();
+
+            Ok(new_response(
+                bank,
+                RpcSimulateBundleResult {
+                    err: transaction_results
+                        .iter()
+                        .find_map(|result| result.err.clone()),
+                    transaction_results,
+                    replacement_blockhash: blockhash,
+                },
+            ))
+        }
+
         fn minimum_ledger_slot(&self, meta: Self::Metadata) -> Result<Slot> {
             debug!("minimum_ledger_slot rpc request received");
             meta.minimum_ledger_slot()
@@ -4168,18 +5315,30 @@ pub mod rpc_full {
                 limit,
                 commitment,
                 min_context_slot,
+                min_block_time,
+                max_block_time,
+                program_id,
             } = config.unwrap_or_default();
             let verification =
-                verify_and_parse_signatures_for_address_params(address, before, until, limit);
+                verify_and_parse_signatures_for_address_params(address, before, until, limit)
+                    .and_then(|params| {
+                        verify_and_parse_signatures_for_address_filter(
+                            min_block_time,
+                            max_block_time,
+                            program_id,
+                        )
+                        .map(|filter| (params, filter))
+                    });
 
             match verification {
                 Err(err) => Box::pin(future::err(err)),
-                Ok((address, before, until, limit)) => Box::pin(async move {
+                Ok(((address, before, until, limit), filter)) => Box::pin(async move {
                     meta.get_signatures_for_address(
                         address,
                         before,
                         until,
                         limit,
+                        filter,
                         RpcContextConfig {
                             commitment,
                             min_context_slot,
@@ -4190,6 +5349,20 @@ pub mod rpc_full {
             }
         }
 
+        fn get_finalized_signatures_for_address(
+            &self,
+            meta: Self::Metadata,
+            address: String,
+            config: Option<RpcFinalizedSignaturesForAddressConfig>,
+        ) -> Result<RpcFinalizedSignaturesForAddress> {
+            debug!("get_finalized_signatures_for_address rpc request received: {address}");
+            let RpcFinalizedSignaturesForAddressConfig { after, limit } =
+                config.unwrap_or_default();
+            let (address, after, limit) =
+                verify_and_parse_finalized_signatures_for_address_params(address, after, limit)?;
+            meta.get_finalized_signatures_for_address(address, after, limit)
+        }
+
         fn get_first_available_block(&self, meta: Self::Metadata) -> BoxFuture<Result<Slot>> {
             debug!("get_first_available_block rpc request received");
             Box::pin(async move { Ok(meta.get_first_available_block().await) })
@@ -4284,9 +5457,10 @@ pub mod rpc_full {
                 "get_recent_prioritization_fees rpc request received: {:?} pubkeys",
                 pubkey_strs.len()
             );
-            if pubkey_strs.len() > MAX_TX_ACCOUNT_LOCKS {
+            let max_pubkeys = meta.bank(None).get_transaction_account_lock_limit();
+            if pubkey_strs.len() > max_pubkeys {
                 return Err(Error::invalid_params(format!(
-                    "Too many inputs provided; max {MAX_TX_ACCOUNT_LOCKS}"
+                    "Too many inputs provided; max {max_pubkeys}"
                 )));
             }
             let pubkeys = pubkey_strs
@@ -4295,32 +5469,284 @@ pub mod rpc_full {
                 .collect::<Result<Vec<_>>>()?;
             meta.get_recent_prioritization_fees(pubkeys)
         }
-    }
-}
 
-fn rpc_perf_sample_from_perf_sample(slot: u64, sample: PerfSample) -> RpcPerfSample {
-    match sample {
-        PerfSample::V1(PerfSampleV1 {
-            num_transactions,
-            num_slots,
-            sample_period_secs,
-        }) => RpcPerfSample {
-            slot,
-            num_transactions,
-            num_non_vote_transactions: None,
-            num_slots,
-            sample_period_secs,
-        },
-        PerfSample::V2(PerfSampleV2 {
-            num_transactions,
-            num_non_vote_transactions,
-            num_slots,
-            sample_period_secs,
-        }) => RpcPerfSample {
-            slot,
-            num_transactions,
-            num_non_vote_transactions: Some(num_non_vote_transactions),
-            num_slots,
+        fn get_program_fee_market(
+            &self,
+            meta: Self::Metadata,
+            program_id_str: String,
+        ) -> Result<RpcProgramFeeMarket> {
+            debug!("get_program_fee_market rpc request received: {program_id_str:?}");
+            let program_id = verify_pubkey(&program_id_str)?;
+            meta.get_program_fee_market(program_id)
+        }
+
+        fn get_slashing_evidence(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcSlashingEvidenceConfig>,
+        ) -> Result<Vec<RpcSlashingEvidence>> {
+            debug!("get_slashing_evidence rpc request received");
+            meta.get_slashing_evidence(config.unwrap_or_default())
+        }
+
+        fn register_webhook(
+            &self,
+            meta: Self::Metadata,
+            url: String,
+            address_strs: Vec<String>,
+            config: Option<RpcRegisterWebhookConfig>,
+        ) -> Result<RpcWebhookRegistration> {
+            debug!("register_webhook rpc request received: {url}");
+            let addresses = address_strs
+                .iter()
+                .map(|address_str| verify_pubkey(address_str))
+                .collect::<Result<Vec<_>>>()?;
+            meta.register_webhook(url, addresses, config.unwrap_or_default())
+        }
+
+        fn unregister_webhook(
+            &self,
+            meta: Self::Metadata,
+            id: String,
+            secret: String,
+        ) -> Result<bool> {
+            debug!("unregister_webhook rpc request received: {id}");
+            meta.unregister_webhook(id, secret)
+        }
+
+        fn get_transaction_ingestion_status(
+            &self,
+            meta: Self::Metadata,
+            signature_str: String,
+        ) -> Result<RpcTransactionIngestionStatus> {
+            debug!("get_transaction_ingestion_status rpc request received: {signature_str}");
+            let signature = verify_signature(&signature_str)?;
+            meta.get_transaction_ingestion_status(signature)
+        }
+
+        fn get_block_fee_summary(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+        ) -> Result<Option<RpcBlockFeeSummary>> {
+            debug!("get_block_fee_summary rpc request received: {slot}");
+            meta.get_block_fee_summary(slot)
+        }
+
+        fn get_supply_deltas(
+            &self,
+            meta: Self::Metadata,
+            start_slot: Option<Slot>,
+            limit: Option<usize>,
+        ) -> Result<Vec<RpcSupplyDelta>> {
+            debug!("get_supply_deltas rpc request received: {start_slot:?} {limit:?}");
+            meta.get_supply_deltas(start_slot, limit)
+        }
+
+        fn get_vote_overhead(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcContextConfig>,
+        ) -> Result<RpcVoteOverhead> {
+            debug!("get_vote_overhead rpc request received");
+            meta.get_vote_overhead(config.unwrap_or_default())
+        }
+
+        fn get_account_info_at_slot(
+            &self,
+            meta: Self::Metadata,
+            pubkey_str: String,
+            slot: Slot,
+            config: Option<RpcAccountInfoConfig>,
+        ) -> Result<RpcResponse<Option<UiAccount>>> {
+            debug!("get_account_info_at_slot rpc request received: {pubkey_str} {slot}");
+            let pubkey = verify_pubkey(&pubkey_str)?;
+            meta.get_account_info_at_slot(&pubkey, slot, config)
+        }
+
+        fn get_confirmation_latency_stats(
+            &self,
+            meta: Self::Metadata,
+        ) -> Result<RpcConfirmationLatencyStats> {
+            debug!("get_confirmation_latency_stats rpc request received");
+            Ok(meta.get_confirmation_latency_stats())
+        }
+
+        fn get_account_proof(
+            &self,
+            meta: Self::Metadata,
+            pubkey_str: String,
+            slot: Slot,
+        ) -> Result<RpcAccountProof> {
+            debug!("get_account_proof rpc request received: {pubkey_str} {slot}");
+            let pubkey = verify_pubkey(&pubkey_str)?;
+            meta.get_account_proof(&pubkey, slot)
+        }
+
+        fn get_optimistic_confirmation_attestations(
+            &self,
+            meta: Self::Metadata,
+            slot: Slot,
+        ) -> Result<Option<RpcOptimisticConfirmationAttestations>> {
+            debug!("get_optimistic_confirmation_attestations rpc request received: {slot}");
+            Ok(meta.get_optimistic_confirmation_attestations(slot))
+        }
+
+        fn get_epoch_stakes(
+            &self,
+            meta: Self::Metadata,
+            epoch: Option<Epoch>,
+            config: Option<RpcContextConfig>,
+        ) -> Result<RpcEpochStakes> {
+            debug!("get_epoch_stakes rpc request received: {epoch:?}");
+            meta.get_epoch_stakes(epoch, config.unwrap_or_default())
+        }
+
+        fn get_leader_schedule_with_stakes(
+            &self,
+            meta: Self::Metadata,
+            epoch: Option<Epoch>,
+            config: Option<RpcContextConfig>,
+        ) -> Result<Option<RpcLeaderScheduleWithStakes>> {
+            debug!("get_leader_schedule_with_stakes rpc request received: {epoch:?}");
+            meta.get_leader_schedule_with_stakes(epoch, config.unwrap_or_default())
+        }
+    }
+}
+
+fn rpc_block_fee_summary_from_block_fee_summary(
+    fee_summary: BlockFeeSummary,
+) -> RpcBlockFeeSummary {
+    let BlockFeeSummary {
+        leader,
+        transaction_fee,
+        priority_fee,
+        burned,
+        leader_reward,
+        treasury,
+    } = fee_summary;
+    RpcBlockFeeSummary {
+        leader: leader.to_string(),
+        transaction_fee,
+        priority_fee,
+        burned,
+        leader_reward,
+        treasury,
+    }
+}
+
+/// Returns the `percentile`th of the sorted `compute_unit_prices`, or 0 if there are none
+fn compute_unit_price_percentile(compute_unit_prices: &[u64], percentile: usize) -> u64 {
+    compute_unit_prices
+        .get(compute_unit_prices.len().saturating_sub(1) * percentile / 100)
+        .copied()
+        .unwrap_or_default()
+}
+
+fn rpc_supply_delta_from_supply_audit_entry(entry: SupplyAuditEntry) -> RpcSupplyDelta {
+    let SupplyAuditEntry {
+        slot,
+        parent_slot,
+        capitalization,
+        parent_capitalization,
+        delta,
+        unexplained,
+        violations,
+    } = entry;
+    RpcSupplyDelta {
+        slot,
+        parent_slot,
+        capitalization,
+        parent_capitalization,
+        inflation_issued: delta.inflation_issued,
+        fees_burned: delta.fees_burned,
+        rent_burned: delta.rent_burned,
+        incinerated: delta.incinerated,
+        other_minted: delta.other_minted,
+        other_removed: delta.other_removed,
+        unexplained,
+        violations,
+    }
+}
+
+fn rpc_epoch_vote_overhead(
+    epoch: Epoch,
+    stats: VoteOverheadStats,
+    lamports_per_signature: u64,
+) -> RpcEpochVoteOverhead {
+    let VoteOverheadStats {
+        slots,
+        transaction_count,
+        vote_transaction_count,
+        signature_count,
+        vote_signature_count,
+        block_cost,
+        vote_cost,
+        block_cost_limit,
+    } = stats;
+    RpcEpochVoteOverhead {
+        epoch,
+        slots,
+        transaction_count,
+        vote_transaction_count,
+        signature_count,
+        vote_signature_count,
+        block_cost,
+        vote_cost,
+        block_cost_limit,
+        implied_vote_fees: vote_signature_count.saturating_mul(lamports_per_signature),
+    }
+}
+
+fn rpc_transaction_ingestion_event_from_ingestion_event(
+    event: IngestionEvent,
+) -> RpcTransactionIngestionEvent {
+    let IngestionEvent {
+        timestamp,
+        stage,
+        slot,
+        reason,
+    } = event;
+    let stage = match stage {
+        IngestionStage::ReceivedViaRpc => RpcTransactionIngestionStage::ReceivedViaRpc,
+        IngestionStage::ReceivedViaTpu => RpcTransactionIngestionStage::ReceivedViaTpu,
+        IngestionStage::Filtered => RpcTransactionIngestionStage::Filtered,
+        IngestionStage::Forwarded => RpcTransactionIngestionStage::Forwarded,
+        IngestionStage::Scheduled => RpcTransactionIngestionStage::Scheduled,
+        IngestionStage::Executed => RpcTransactionIngestionStage::Executed,
+        IngestionStage::Expired => RpcTransactionIngestionStage::Expired,
+    };
+    RpcTransactionIngestionEvent {
+        timestamp,
+        stage,
+        slot,
+        reason,
+    }
+}
+
+fn rpc_perf_sample_from_perf_sample(slot: u64, sample: PerfSample) -> RpcPerfSample {
+    match sample {
+        PerfSample::V1(PerfSampleV1 {
+            num_transactions,
+            num_slots,
+            sample_period_secs,
+        }) => RpcPerfSample {
+            slot,
+            num_transactions,
+            num_non_vote_transactions: None,
+            num_slots,
+            sample_period_secs,
+        },
+        PerfSample::V2(PerfSampleV2 {
+            num_transactions,
+            num_non_vote_transactions,
+            num_slots,
+            sample_period_secs,
+        }) => RpcPerfSample {
+            slot,
+            num_transactions,
+            num_non_vote_transactions: Some(num_non_vote_transactions),
+            num_slots,
             sample_period_secs,
         },
     }
@@ -4474,6 +5900,7 @@ pub fn populate_blockstore_for_tests(
             max_complete_transaction_status_slot,
             true,
             None,
+            None,
             blockstore,
             false,
             tss_exit.clone(),
@@ -4520,10 +5947,10 @@ pub mod tests {
         solana_entry::entry::next_versioned_entry,
         solana_gossip::{contact_info::ContactInfo, socketaddr},
         solana_ledger::{
-            blockstore_meta::PerfSampleV2,
+            blockstore_meta::{DuplicateVoteProof, PerfSampleV2},
             blockstore_processor::fill_blockstore_slot_with_ticks,
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
-            get_tmp_ledger_path,
+            get_tmp_ledger_path, get_tmp_ledger_path_auto_delete,
         },
         solana_rpc_client_api::{
             custom_error::{
@@ -4535,7 +5962,11 @@ pub mod tests {
         },
         solana_runtime::{
             accounts_background_service::AbsRequestSender,
-            bank::BankTestConfig,
+            bank::{
+                freeze_hooks::{BankFreezeHook, BankFreezeSummary},
+                supply_audit::{CapitalizationDelta, SupplyAuditAction},
+                BankTestConfig,
+            },
             commitment::{BlockCommitment, CommitmentSlots},
             non_circulating_supply::non_circulating_accounts,
         },
@@ -4567,7 +5998,7 @@ pub mod tests {
         solana_send_transaction_service::tpu_info::NullTpuInfo,
         solana_transaction_status::{
             EncodedConfirmedBlock, EncodedTransaction, EncodedTransactionWithStatusMeta,
-            TransactionDetails,
+            TransactionDetails, TransactionStatusMeta,
         },
         solana_vote_program::{
             vote_instruction,
@@ -4710,6 +6141,8 @@ pub mod tests {
                 max_complete_transaction_status_slot.clone(),
                 max_complete_rewards_slot,
                 Arc::new(PrioritizationFeeCache::default()),
+                Arc::default(),
+                Arc::default(),
                 service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
             )
             .0;
@@ -5041,89 +6474,688 @@ pub mod tests {
         assert_eq!(response, 20);
     }
 
-    #[test]
-    fn test_rpc_get_cluster_nodes() {
-        let rpc = RpcHandler::start();
-        let version = solana_version::Version::default();
-        let request = create_test_request("getClusterNodes", None);
+    #[test]
+    fn test_rpc_get_cluster_nodes() {
+        let rpc = RpcHandler::start();
+        let version = solana_version::Version::default();
+        let request = create_test_request("getClusterNodes", None);
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let expected = json!([{
+            "pubkey": rpc.identity.to_string(),
+            "gossip": "127.0.0.1:8000",
+            "shredVersion": 0u16,
+            "tvu": "127.0.0.1:8001",
+            "tpu": "127.0.0.1:8003",
+            "tpuQuic": "127.0.0.1:8009",
+            "tpuForwards": "127.0.0.1:8004",
+            "tpuForwardsQuic": "127.0.0.1:8010",
+            "tpuVote": "127.0.0.1:8005",
+            "serveRepair": "127.0.0.1:8008",
+            "rpc": format!("127.0.0.1:{}", rpc_port::DEFAULT_RPC_PORT),
+            "pubsub": format!("127.0.0.1:{}", rpc_port::DEFAULT_RPC_PUBSUB_PORT),
+            "version": format!("{version}"),
+            "featureSet": version.feature_set,
+        }, {
+            "pubkey": rpc.leader_pubkey().to_string(),
+            "gossip": "127.0.0.1:1235",
+            "shredVersion": 0u16,
+            "tvu": "127.0.0.1:1236",
+            "tpu": "127.0.0.1:1234",
+            "tpuQuic": "127.0.0.1:1240",
+            "tpuForwards": "127.0.0.1:1239",
+            "tpuForwardsQuic": "127.0.0.1:1245",
+            "tpuVote": "127.0.0.1:1241",
+            "serveRepair": "127.0.0.1:1242",
+            "rpc": format!("127.0.0.1:{}", rpc_port::DEFAULT_RPC_PORT),
+            "pubsub": format!("127.0.0.1:{}", rpc_port::DEFAULT_RPC_PUBSUB_PORT),
+            "version": format!("{version}"),
+            "featureSet": version.feature_set,
+        }]);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_rpc_get_recent_performance_samples() {
+        let rpc = RpcHandler::start();
+
+        let slot = 0;
+        let num_slots = 1;
+        let num_transactions = 4;
+        let num_non_vote_transactions = 1;
+        let sample_period_secs = 60;
+        rpc.blockstore
+            .write_perf_sample(
+                slot,
+                &PerfSampleV2 {
+                    num_slots,
+                    num_transactions,
+                    num_non_vote_transactions,
+                    sample_period_secs,
+                },
+            )
+            .expect("write to blockstore");
+
+        let request = create_test_request("getRecentPerformanceSamples", None);
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let expected = json!([{
+            "slot": slot,
+            "numSlots": num_slots,
+            "numTransactions": num_transactions,
+            "numNonVoteTransactions": num_non_vote_transactions,
+            "samplePeriodSecs": sample_period_secs,
+        }]);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_rpc_get_confirmation_latency_stats() {
+        let rpc = RpcHandler::start();
+
+        let request = create_test_request("getConfirmationLatencyStats", None);
+        let result: RpcConfirmationLatencyStats =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, RpcConfirmationLatencyStats::default());
+
+        let confirmation_latency = &rpc.meta.confirmation_latency;
+        confirmation_latency.record_first_shred_received(1, 1_000);
+        confirmation_latency.record_optimistic_confirmation(1, 1_800);
+        confirmation_latency.record_root(1, 13_000);
+
+        let request = create_test_request("getConfirmationLatencyStats", None);
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let expected = json!({
+            "optimisticConfirmation": {
+                "numSlots": 1,
+                "minMs": 800,
+                "meanMs": 800,
+                "p50Ms": 800,
+                "p90Ms": 800,
+                "p99Ms": 800,
+                "maxMs": 800,
+            },
+            "finalization": {
+                "numSlots": 1,
+                "minMs": 12_000,
+                "meanMs": 12_000,
+                "p50Ms": 12_000,
+                "p90Ms": 12_000,
+                "p99Ms": 12_000,
+                "maxMs": 12_000,
+            },
+        });
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_rpc_get_recent_performance_samples_invalid_limit() {
+        let rpc = RpcHandler::start();
+        let request = create_test_request("getRecentPerformanceSamples", Some(json!([10_000])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            ErrorCode::InvalidParams.code(),
+            String::from("Invalid limit; max 720"),
+        );
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_rpc_get_slashing_evidence() {
+        let rpc = RpcHandler::start();
+        let vote_account = Pubkey::new_unique();
+        rpc.blockstore
+            .store_duplicate_slot(3, vec![1u8; 4], vec![2u8; 4])
+            .unwrap();
+        rpc.blockstore
+            .store_duplicate_vote(
+                2,
+                vote_account,
+                &DuplicateVoteProof {
+                    hash1: Hash::new_unique(),
+                    transaction1: vec![3u8; 4],
+                    hash2: Hash::new_unique(),
+                    transaction2: vec![4u8; 4],
+                },
+            )
+            .unwrap();
+
+        let request = create_test_request("getSlashingEvidence", None);
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let expected = json!([{
+            "slot": 2,
+            "kind": "duplicateVote",
+            "offender": vote_account.to_string(),
+            "proof": [BASE64_STANDARD.encode([3u8; 4]), BASE64_STANDARD.encode([4u8; 4])],
+        }, {
+            "slot": 3,
+            "kind": "duplicateBlock",
+            "offender": rpc.leader_pubkey().to_string(),
+            "proof": [BASE64_STANDARD.encode([1u8; 4]), BASE64_STANDARD.encode([2u8; 4])],
+        }]);
+        assert_eq!(result, expected);
+
+        let request = create_test_request(
+            "getSlashingEvidence",
+            Some(json!([{"startSlot": 3, "limit": 1}])),
+        );
+        let result: Vec<RpcSlashingEvidence> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].kind, RpcSlashingEvidenceKind::DuplicateBlock);
+
+        let request = create_test_request("getSlashingEvidence", Some(json!([{"limit": 10_000}])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            ErrorCode::InvalidParams.code(),
+            String::from("Invalid limit; max 1000"),
+        );
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn test_rpc_register_webhook() {
+        let address = Pubkey::new_unique().to_string();
+        let rpc = RpcHandler::start();
+        let request = create_test_request(
+            "registerWebhook",
+            Some(json!(["https://example.com/webhook", [address]])),
+        );
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            ErrorCode::InvalidRequest.code(),
+            String::from("Webhooks are not enabled on this node"),
+        );
+        assert_eq!(response, expected);
+
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            enable_rpc_transaction_history: true,
+            webhook_config: Some(WebhookConfig::default()),
+            ..JsonRpcConfig::default()
+        });
+        let request = create_test_request(
+            "registerWebhook",
+            Some(json!([
+                "https://example.com/webhook",
+                [address],
+                {"commitment": "processed"},
+            ])),
+        );
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            ErrorCode::InvalidParams.code(),
+            String::from("invalid commitment, expected confirmed or finalized"),
+        );
+        assert_eq!(response, expected);
+
+        let request = create_test_request(
+            "registerWebhook",
+            Some(json!([
+                "https://example.com/webhook",
+                [address],
+                {"commitment": "confirmed"},
+            ])),
+        );
+        let RpcWebhookRegistration { id, secret } =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(secret.len(), 64);
+
+        let request = create_test_request("unregisterWebhook", Some(json!([id, "00".repeat(32)])));
+        let result: bool = parse_success_result(rpc.handle_request_sync(request));
+        assert!(!result);
+        let request = create_test_request("unregisterWebhook", Some(json!([id, secret])));
+        let result: bool = parse_success_result(rpc.handle_request_sync(request));
+        assert!(result);
+    }
+
+    #[test]
+    fn test_rpc_get_account_info_at_slot() {
+        let pubkey = Pubkey::new_unique();
+        let request =
+            create_test_request("getAccountInfoAtSlot", Some(json!([pubkey.to_string(), 1])));
+        let rpc = RpcHandler::start();
+        let response = parse_failure_response(rpc.handle_request_sync(request.clone()));
+        assert_eq!(
+            response,
+            (
+                ErrorCode::InvalidRequest.code(),
+                "Archival accounts are not enabled on this node".to_string(),
+            )
+        );
+
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            enable_rpc_transaction_history: true,
+            archival_accounts: Some(ArchivalAccountsConfig::default()),
+            ..JsonRpcConfig::default()
+        });
+        let account = |lamports| Account {
+            lamports,
+            data: vec![1, 2, 3],
+            ..Account::default()
+        };
+        rpc.blockstore
+            .write_archived_accounts(1, [(&pubkey, &account(100))])
+            .unwrap();
+        rpc.blockstore
+            .write_archived_accounts(3, [(&pubkey, &account(0))])
+            .unwrap();
+        rpc.blockstore.set_roots([1, 2, 3].iter()).unwrap();
+
+        let result: RpcResponse<Option<UiAccount>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.context.slot, 1);
+        let ui_account = result.value.unwrap();
+        assert_eq!(ui_account.lamports, 100);
+        assert_eq!(ui_account.data.decode(), Some(vec![1, 2, 3]));
+
+        let request = create_test_request(
+            "getAccountInfoAtSlot",
+            Some(json!([pubkey.to_string(), 2, {"encoding": "base64"}])),
+        );
+        let result: RpcResponse<Option<UiAccount>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value.unwrap().lamports, 100);
+
+        // Closed at slot 3
+        let request =
+            create_test_request("getAccountInfoAtSlot", Some(json!([pubkey.to_string(), 3])));
+        let result: RpcResponse<Option<UiAccount>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value, None);
+
+        let request =
+            create_test_request("getAccountInfoAtSlot", Some(json!([pubkey.to_string(), 4])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(response.0, ErrorCode::InvalidParams.code());
+    }
+
+    #[test]
+    fn test_rpc_get_finalized_signatures_for_address() {
+        let rpc = RpcHandler::start();
+        let address = Pubkey::new_unique();
+        let signatures: Vec<_> = (1..=3)
+            .map(|slot| {
+                let signature = Signature::new_unique();
+                rpc.blockstore
+                    .write_transaction_status(
+                        slot,
+                        signature,
+                        [(&address, true)].into_iter(),
+                        TransactionStatusMeta::default(),
+                        0,
+                    )
+                    .unwrap();
+                signature
+            })
+            .collect();
+        rpc.blockstore.set_roots([1, 2, 3].iter()).unwrap();
+        rpc.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_highest_super_majority_root(2);
+
+        let get_signatures = |config: Value| -> RpcFinalizedSignaturesForAddress {
+            let request = create_test_request(
+                "getFinalizedSignaturesForAddress",
+                Some(json!([address.to_string(), config])),
+            );
+            parse_success_result(rpc.handle_request_sync(request))
+        };
+
+        let result = get_signatures(json!({"limit": 1}));
+        assert_eq!(result.finalized_slot, 2);
+        assert_eq!(result.signatures.len(), 1);
+        assert_eq!(result.signatures[0].signature, signatures[0].to_string());
+        assert_eq!(
+            result.signatures[0].confirmation_status,
+            Some(TransactionConfirmationStatus::Finalized)
+        );
+        let cursor = result.cursor.unwrap();
+        assert_eq!(cursor, format!("1:0:{}", signatures[0]));
+
+        // Resumes after the cursor, up to the finalized slot
+        let result = get_signatures(json!({"after": cursor}));
+        assert_eq!(result.signatures.len(), 1);
+        assert_eq!(result.signatures[0].signature, signatures[1].to_string());
+        let cursor = result.cursor.unwrap();
+        let result = get_signatures(json!({"after": cursor}));
+        assert!(result.signatures.is_empty());
+        assert_eq!(result.cursor, Some(cursor.clone()));
+
+        rpc.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_highest_super_majority_root(3);
+        let result = get_signatures(json!({"after": cursor}));
+        assert_eq!(result.finalized_slot, 3);
+        assert_eq!(result.signatures.len(), 1);
+        assert_eq!(result.signatures[0].signature, signatures[2].to_string());
+
+        let request = create_test_request(
+            "getFinalizedSignaturesForAddress",
+            Some(json!([address.to_string(), {"after": "1:0"}])),
+        );
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(
+            response,
+            (
+                ErrorCode::InvalidParams.code(),
+                String::from("Invalid cursor: 1:0"),
+            )
+        );
+    }
+
+    #[test]
+    fn test_rpc_get_block_fee_summary() {
+        let rpc = RpcHandler::start();
+        let leader = Pubkey::new_unique();
+        rpc.blockstore
+            .set_block_fee_summary(
+                3,
+                &BlockFeeSummary {
+                    leader,
+                    transaction_fee: 10_000,
+                    priority_fee: 2_000,
+                    burned: 4_000,
+                    leader_reward: 7_000,
+                    treasury: 1_000,
+                },
+            )
+            .unwrap();
+
+        let request = create_test_request("getBlockFeeSummary", Some(json!([3])));
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let expected = json!({
+            "leader": leader.to_string(),
+            "transactionFee": 10_000,
+            "priorityFee": 2_000,
+            "burned": 4_000,
+            "leaderReward": 7_000,
+            "treasury": 1_000,
+        });
+        assert_eq!(result, expected);
+
+        let request = create_test_request("getBlockFeeSummary", Some(json!([4])));
+        let result: Option<RpcBlockFeeSummary> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, None);
+
+        // Falls back to frozen banks whose summary is not in the blockstore yet
+        let bank = rpc.working_bank();
+        bank.freeze();
+        let request = create_test_request("getBlockFeeSummary", Some(json!([bank.slot()])));
+        let result: Option<RpcBlockFeeSummary> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(
+            result,
+            Some(RpcBlockFeeSummary {
+                leader: bank.collector_id().to_string(),
+                ..RpcBlockFeeSummary::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_rpc_get_account_proof() {
+        let rpc = RpcHandler::start();
+        let slot = rpc.working_bank().slot() + 1;
+        let mut bank = Bank::new_from_parent(rpc.working_bank(), &Pubkey::default(), slot);
+        bank.deactivate_feature(&feature_set::remove_accounts_delta_hash::id());
+        let bank = rpc
+            .bank_forks
+            .write()
+            .unwrap()
+            .insert(bank)
+            .clone_without_scheduler();
+        let recipient = Pubkey::new_unique();
+        bank.transfer(1_000_000, &rpc.mint_keypair, &recipient)
+            .unwrap();
+
+        let request = create_test_request(
+            "getAccountProof",
+            Some(json!([recipient.to_string(), slot])),
+        );
+        let response = parse_failure_response(rpc.handle_request_sync(request.clone()));
+        assert_eq!(
+            response,
+            (
+                ErrorCode::InvalidParams.code(),
+                "bank is not frozen".to_string()
+            )
+        );
+
+        bank.freeze();
+        let result: RpcAccountProof = parse_success_result(rpc.handle_request_sync(request));
+        let proof = bank.account_proof(&recipient).unwrap();
+        assert_eq!(result.slot, slot);
+        assert_eq!(result.bank_hash, bank.hash().to_string());
+        assert_eq!(
+            result.accounts_delta_hash,
+            proof.accounts_delta_hash.to_string()
+        );
+        assert_eq!(result.account.lamports, 1_000_000);
+        assert_eq!(result.proof.len(), proof.merkle_proof.len());
+
+        let unwritten = Pubkey::new_unique();
+        let request = create_test_request(
+            "getAccountProof",
+            Some(json!([unwritten.to_string(), slot])),
+        );
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(
+            response,
+            (
+                ErrorCode::InvalidParams.code(),
+                format!(
+                    "account {unwritten} was not written in slot {slot}, proofs are only \
+                     available for the slots which wrote the account"
+                ),
+            )
+        );
+
+        let request = create_test_request(
+            "getAccountProof",
+            Some(json!([recipient.to_string(), slot + 1])),
+        );
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(response.0, ErrorCode::InvalidParams.code());
+    }
+
+    #[test]
+    fn test_rpc_get_optimistic_confirmation_attestations() {
+        let rpc = RpcHandler::start();
+        let request =
+            create_test_request("getOptimisticConfirmationAttestations", Some(json!([1])));
+        let result: Option<RpcOptimisticConfirmationAttestations> =
+            parse_success_result(rpc.handle_request_sync(request.clone()));
+        assert_eq!(result, None);
+
+        let hash = Hash::new_unique();
+        let vote_pubkey = Pubkey::new_unique();
+        let vote_transaction =
+            system_transaction::transfer(&rpc.mint_keypair, &vote_pubkey, 1, Hash::default());
+        let attestations = &rpc.meta.optimistic_confirmation_attestations;
+        attestations.record_vote(1, hash, vote_pubkey, 70, 100, &vote_transaction);
+        attestations.record_optimistic_confirmation(1, hash);
+
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let expected = json!({
+            "slot": 1,
+            "bankHash": hash.to_string(),
+            "optimisticallyConfirmed": true,
+            "totalStake": 100,
+            "attestedStake": 70,
+            "thresholdStake": 67,
+            "attestations": [{
+                "votePubkey": vote_pubkey.to_string(),
+                "stake": 70,
+                "signature": vote_transaction.signatures[0].to_string(),
+                "transaction": BASE64_STANDARD.encode(serialize(&vote_transaction).unwrap()),
+            }],
+        });
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_rpc_get_transaction_ingestion_status() {
+        let signature = Signature::new_unique().to_string();
+        let rpc = RpcHandler::start();
+        let request =
+            create_test_request("getTransactionIngestionStatus", Some(json!([signature])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            ErrorCode::InvalidRequest.code(),
+            String::from("Transaction ingestion log is not enabled on this node"),
+        );
+        assert_eq!(response, expected);
+
+        let ingestion_log = Arc::new(TransactionIngestionLog::new(16));
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            transaction_ingestion_log: Some(ingestion_log.clone()),
+            ..JsonRpcConfig::default()
+        });
+        let request =
+            create_test_request("getTransactionIngestionStatus", Some(json!([signature])));
+        let result: RpcTransactionIngestionStatus =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, RpcTransactionIngestionStatus::default());
+
+        let bank = rpc.working_bank();
+        let tx = system_transaction::transfer(
+            &rpc.mint_keypair,
+            &Pubkey::new_unique(),
+            1,
+            bank.last_blockhash(),
+        );
+        let signature = tx.signatures[0];
+        let request = create_test_request(
+            "sendTransaction",
+            Some(json!([
+                bs58::encode(serialize(&tx).unwrap()).into_string(),
+                {"skipPreflight": true},
+            ])),
+        );
+        let result: String = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, signature.to_string());
+        ingestion_log.record(
+            signature,
+            IngestionEvent::new(IngestionStage::Executed).with_slot(bank.slot()),
+        );
+
+        let request = create_test_request(
+            "getTransactionIngestionStatus",
+            Some(json!([signature.to_string()])),
+        );
         let result: Value = parse_success_result(rpc.handle_request_sync(request));
-        let expected = json!([{
-            "pubkey": rpc.identity.to_string(),
-            "gossip": "127.0.0.1:8000",
-            "shredVersion": 0u16,
-            "tvu": "127.0.0.1:8001",
-            "tpu": "127.0.0.1:8003",
-            "tpuQuic": "127.0.0.1:8009",
-            "tpuForwards": "127.0.0.1:8004",
-            "tpuForwardsQuic": "127.0.0.1:8010",
-            "tpuVote": "127.0.0.1:8005",
-            "serveRepair": "127.0.0.1:8008",
-            "rpc": format!("127.0.0.1:{}", rpc_port::DEFAULT_RPC_PORT),
-            "pubsub": format!("127.0.0.1:{}", rpc_port::DEFAULT_RPC_PUBSUB_PORT),
-            "version": format!("{version}"),
-            "featureSet": version.feature_set,
-        }, {
-            "pubkey": rpc.leader_pubkey().to_string(),
-            "gossip": "127.0.0.1:1235",
-            "shredVersion": 0u16,
-            "tvu": "127.0.0.1:1236",
-            "tpu": "127.0.0.1:1234",
-            "tpuQuic": "127.0.0.1:1240",
-            "tpuForwards": "127.0.0.1:1239",
-            "tpuForwardsQuic": "127.0.0.1:1245",
-            "tpuVote": "127.0.0.1:1241",
-            "serveRepair": "127.0.0.1:1242",
-            "rpc": format!("127.0.0.1:{}", rpc_port::DEFAULT_RPC_PORT),
-            "pubsub": format!("127.0.0.1:{}", rpc_port::DEFAULT_RPC_PUBSUB_PORT),
-            "version": format!("{version}"),
-            "featureSet": version.feature_set,
-        }]);
-        assert_eq!(result, expected);
+        assert_eq!(result["events"].as_array().unwrap().len(), 2);
+        assert_eq!(result["events"][0]["stage"], "receivedViaRpc");
+        assert_eq!(result["lastSeen"]["stage"], "executed");
+        assert_eq!(result["lastSeen"]["slot"], bank.slot());
+        assert_eq!(result["lastSeen"]["reason"], Value::Null);
     }
 
     #[test]
-    fn test_rpc_get_recent_performance_samples() {
+    fn test_rpc_get_supply_deltas() {
         let rpc = RpcHandler::start();
+        let request = create_test_request("getSupplyDeltas", None);
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            ErrorCode::InvalidRequest.code(),
+            String::from("Supply audit is not enabled on this node"),
+        );
+        assert_eq!(response, expected);
 
-        let slot = 0;
-        let num_slots = 1;
-        let num_transactions = 4;
-        let num_non_vote_transactions = 1;
-        let sample_period_secs = 60;
-        rpc.blockstore
-            .write_perf_sample(
+        let supply_audit = Arc::new(SupplyAudit::new(SupplyAuditAction::Alarm, 16));
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            supply_audit: Some(supply_audit.clone()),
+            ..JsonRpcConfig::default()
+        });
+        for (slot, capitalization) in [(1, 995), (2, 996)] {
+            supply_audit.check(&BankFreezeSummary {
                 slot,
-                &PerfSampleV2 {
-                    num_slots,
-                    num_transactions,
-                    num_non_vote_transactions,
-                    sample_period_secs,
+                epoch: 0,
+                bank_hash: Hash::default(),
+                capitalization,
+                capitalization_delta: CapitalizationDelta {
+                    fees_burned: 5,
+                    ..CapitalizationDelta::default()
                 },
-            )
-            .expect("write to blockstore");
+                collected_fees: 5,
+                treasury_fees: 0,
+                collected_rent: 0,
+                transaction_count: 0,
+                signature_count: 0,
+                rewards: vec![],
+                parent_slot: slot - 1,
+                parent_epoch: Some(0),
+                parent_capitalization: Some(1_000),
+            });
+        }
 
-        let request = create_test_request("getRecentPerformanceSamples", None);
+        let request = create_test_request("getSupplyDeltas", Some(json!([2])));
         let result: Value = parse_success_result(rpc.handle_request_sync(request));
         let expected = json!([{
-            "slot": slot,
-            "numSlots": num_slots,
-            "numTransactions": num_transactions,
-            "numNonVoteTransactions": num_non_vote_transactions,
-            "samplePeriodSecs": sample_period_secs,
+            "slot": 2,
+            "parentSlot": 1,
+            "capitalization": 996,
+            "parentCapitalization": 1_000,
+            "inflationIssued": 0,
+            "feesBurned": 5,
+            "rentBurned": 0,
+            "incinerated": 0,
+            "otherMinted": 0,
+            "otherRemoved": 0,
+            "unexplained": 1,
+            "violations": [
+                "capitalization changed by -4 lamports from 1000 to 996, 1 lamports of which are \
+                 unexplained"
+            ],
         }]);
         assert_eq!(result, expected);
+
+        let request = create_test_request("getSupplyDeltas", Some(json!([0, 1])));
+        let result: Vec<RpcSupplyDelta> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].slot, 1);
+        assert_eq!(result[0].unexplained, 0);
+        assert!(result[0].violations.is_empty());
+
+        let request = create_test_request(
+            "getSupplyDeltas",
+            Some(json!([0, MAX_GET_SUPPLY_DELTAS_LIMIT + 1])),
+        );
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(response.0, ErrorCode::InvalidParams.code());
     }
 
     #[test]
-    fn test_rpc_get_recent_performance_samples_invalid_limit() {
+    fn test_rpc_get_vote_overhead() {
         let rpc = RpcHandler::start();
-        let request = create_test_request("getRecentPerformanceSamples", Some(json!([10_000])));
-        let response = parse_failure_response(rpc.handle_request_sync(request));
-        let expected = (
-            ErrorCode::InvalidParams.code(),
-            String::from("Invalid limit; max 720"),
-        );
-        assert_eq!(response, expected);
+        let bank = rpc.working_bank();
+        let request = create_test_request("getVoteOverhead", None);
+        let result: RpcVoteOverhead = parse_success_result(rpc.handle_request_sync(request));
+        let vote_overhead = bank.vote_overhead();
+        assert_eq!(result.slot, bank.slot());
+        assert_eq!(result.current_epoch.epoch, vote_overhead.epoch);
+        assert_eq!(result.current_epoch.slots, vote_overhead.stats.slots);
+        assert_eq!(
+            result.current_epoch.implied_vote_fees,
+            vote_overhead.stats.vote_signature_count * bank.get_lamports_per_signature()
+        );
+        assert_eq!(result.previous_epoch, None);
+
+        let stats = VoteOverheadStats {
+            slots: 10,
+            transaction_count: 30,
+            vote_transaction_count: 20,
+            signature_count: 35,
+            vote_signature_count: 20,
+            block_cost: 100_000,
+            vote_cost: 60_000,
+            block_cost_limit: 480_000_000,
+        };
+        let result = rpc_epoch_vote_overhead(3, stats, 5_000);
+        assert_eq!(result.epoch, 3);
+        assert_eq!(result.vote_cost, 60_000);
+        assert_eq!(result.implied_vote_fees, 100_000);
     }
 
     #[test]
@@ -5379,6 +7411,106 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_get_epoch_stakes() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let epoch_stakes = bank.epoch_stakes(bank.epoch()).unwrap();
+
+        for params in [None, Some(json!([bank.epoch()])), Some(json!([null, {}]))] {
+            let request = create_test_request("getEpochStakes", params);
+            let result: RpcEpochStakes = parse_success_result(rpc.handle_request_sync(request));
+            assert_eq!(result.epoch, bank.epoch());
+            assert_eq!(result.total_stake, epoch_stakes.total_stake());
+            assert_eq!(
+                result
+                    .vote_accounts
+                    .iter()
+                    .map(|vote_account| vote_account.stake)
+                    .sum::<u64>(),
+                epoch_stakes.total_stake()
+            );
+            assert!(result
+                .vote_accounts
+                .iter()
+                .any(|vote_account| vote_account.node_pubkey == rpc.leader_pubkey().to_string()));
+            assert!(result
+                .vote_accounts
+                .windows(2)
+                .all(|vote_accounts| vote_accounts[0].stake >= vote_accounts[1].stake));
+        }
+
+        let request = create_test_request("getEpochStakes", Some(json!([42424242])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(response.0, ErrorCode::InvalidParams.code());
+    }
+
+    #[test]
+    fn test_rpc_get_epoch_stakes_archived() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let archive =
+            Arc::new(EpochStakesArchive::new(ledger_path.path().join("epoch_stakes"), 10).unwrap());
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            epoch_stakes_archive: Some(archive.clone()),
+            ..JsonRpcConfig::default()
+        });
+        let bank = rpc.working_bank();
+        archive.archive_bank_epochs(&bank).unwrap();
+
+        // Epochs the bank no longer holds are served from the archive
+        let old_epoch = 42424242;
+        let archived = RpcEpochStakes {
+            epoch: old_epoch,
+            ..archive.get(bank.epoch()).unwrap()
+        };
+        archive.insert(&archived).unwrap();
+        let request = create_test_request("getEpochStakes", Some(json!([old_epoch])));
+        let result: RpcEpochStakes = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, archived);
+
+        let request = create_test_request("getEpochStakes", Some(json!([old_epoch - 1])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(response.0, ErrorCode::InvalidParams.code());
+    }
+
+    #[test]
+    fn test_rpc_get_leader_schedule_with_stakes() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        let request = create_test_request("getLeaderScheduleWithStakes", None);
+        let result: Option<RpcLeaderScheduleWithStakes> =
+            parse_success_result(rpc.handle_request_sync(request));
+        let result = result.unwrap();
+        assert_eq!(result.epoch, bank.epoch());
+        assert_eq!(
+            result.first_slot,
+            bank.epoch_schedule().get_first_slot_in_epoch(bank.epoch())
+        );
+        assert_eq!(
+            result.total_stake,
+            bank.epoch_stakes(bank.epoch()).unwrap().total_stake()
+        );
+        assert_eq!(result.leaders.len(), 1);
+        let leader = &result.leaders[0];
+        assert_eq!(leader.identity, rpc.leader_pubkey().to_string());
+        assert_eq!(leader.slots, Vec::from_iter(0..=128));
+        assert!(leader.stake > 0);
+        assert_eq!(
+            leader.stake,
+            leader
+                .vote_accounts
+                .iter()
+                .map(|vote_account| vote_account.stake)
+                .sum::<u64>()
+        );
+
+        let request = create_test_request("getLeaderScheduleWithStakes", Some(json!([42424242])));
+        let result: Option<RpcLeaderScheduleWithStakes> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, None);
+    }
+
     #[test]
     fn test_rpc_get_slot_leaders() {
         let rpc = RpcHandler::start();
@@ -5683,6 +7815,92 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_rpc_get_stake_accounts_by_vote_account() {
+        for account_indexes in [
+            AccountSecondaryIndexes::default(),
+            AccountSecondaryIndexes {
+                indexes: HashSet::from([AccountIndex::StakeVoter]),
+                keys: None,
+            },
+        ] {
+            let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+                account_indexes,
+                ..JsonRpcConfig::default()
+            });
+            let bank = rpc.working_bank();
+            let rent = bank.rent_collector().rent.clone();
+            let vote_pubkey = Pubkey::new_unique();
+            let vote_account =
+                vote_state::create_account(&vote_pubkey, &Pubkey::new_unique(), 0, 100);
+            let other_vote_pubkey = Pubkey::new_unique();
+            let other_vote_account =
+                vote_state::create_account(&other_vote_pubkey, &Pubkey::new_unique(), 0, 100);
+            bank.store_account(&vote_pubkey, &vote_account);
+            bank.store_account(&other_vote_pubkey, &other_vote_account);
+
+            let stake_lamports = rent.minimum_balance(StakeStateV2::size_of()) + 1_000_000;
+            let mut stake_pubkeys: Vec<_> = (0..3)
+                .map(|_| {
+                    let stake_pubkey = Pubkey::new_unique();
+                    let stake_account = solana_stake_program::stake_state::create_account(
+                        &Pubkey::new_unique(),
+                        &vote_pubkey,
+                        &vote_account,
+                        &rent,
+                        stake_lamports,
+                    );
+                    bank.store_account(&stake_pubkey, &stake_account);
+                    stake_pubkey
+                })
+                .collect();
+            // Stake accounts redelegated to another vote account are no longer returned
+            let redelegated_pubkey = stake_pubkeys.pop().unwrap();
+            bank.store_account(
+                &redelegated_pubkey,
+                &solana_stake_program::stake_state::create_account(
+                    &Pubkey::new_unique(),
+                    &other_vote_pubkey,
+                    &other_vote_account,
+                    &rent,
+                    stake_lamports,
+                ),
+            );
+            stake_pubkeys.sort();
+
+            let request = create_test_request(
+                "getStakeAccountsByVoteAccount",
+                Some(json!([vote_pubkey.to_string(), {"encoding": "base64"}])),
+            );
+            let result: RpcResponse<Vec<RpcKeyedAccount>> =
+                parse_success_result(rpc.handle_request_sync(request));
+            let mut result_pubkeys: Vec<_> = result
+                .value
+                .iter()
+                .map(|keyed_account| Pubkey::from_str(&keyed_account.pubkey).unwrap())
+                .collect();
+            result_pubkeys.sort();
+            assert_eq!(result_pubkeys, stake_pubkeys);
+
+            let request = create_test_request(
+                "getStakeAccountsByVoteAccount",
+                Some(json!([other_vote_pubkey.to_string()])),
+            );
+            let result: RpcResponse<Vec<RpcKeyedAccount>> =
+                parse_success_result(rpc.handle_request_sync(request));
+            assert_eq!(result.value.len(), 1);
+            assert_eq!(result.value[0].pubkey, redelegated_pubkey.to_string());
+
+            let request = create_test_request(
+                "getStakeAccountsByVoteAccount",
+                Some(json!([Pubkey::new_unique().to_string()])),
+            );
+            let result: RpcResponse<Vec<RpcKeyedAccount>> =
+                parse_success_result(rpc.handle_request_sync(request));
+            assert!(result.value.is_empty());
+        }
+    }
+
     #[test]
     fn test_rpc_get_program_accounts() {
         let rpc = RpcHandler::start();
@@ -6483,6 +8701,148 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_simulate_transaction_with_account_overrides() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let RpcHandler {
+            ref meta, ref io, ..
+        } = rpc;
+
+        let payer = Keypair::new();
+        let bob_pubkey = solana_pubkey::new_rand();
+        let tx =
+            system_transaction::transfer(&payer, &bob_pubkey, rent_exempt_amount, recent_blockhash);
+        let tx_serialized_encoded = bs58::encode(serialize(&tx).unwrap()).into_string();
+        bank.freeze();
+
+        let simulate = |config: Value| {
+            let req = json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "simulateTransaction",
+                "params": [tx_serialized_encoded, config],
+            });
+            let res = io.handle_request_sync(&req.to_string(), meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response")).unwrap()
+        };
+
+        // The payer doesn't exist in the bank
+        let result = simulate(json!({}));
+        assert_eq!(result["result"]["value"]["err"], json!("AccountNotFound"));
+
+        let account_overrides = HashMap::from([(
+            payer.pubkey().to_string(),
+            json!({"lamports": 10 * rent_exempt_amount}),
+        )]);
+        let result = simulate(json!({
+            "accountOverrides": account_overrides,
+            "accounts": {"encoding": "base64", "addresses": [bob_pubkey.to_string()]},
+        }));
+        assert_eq!(result["result"]["value"]["err"], Value::Null);
+        assert_eq!(
+            result["result"]["value"]["accounts"][0]["lamports"],
+            json!(rent_exempt_amount)
+        );
+        // The override is not written to the bank
+        assert_eq!(bank.get_balance(&payer.pubkey()), 0);
+
+        let account_overrides = HashMap::from([(
+            sysvar::clock::id().to_string(),
+            json!({"data": ["AQID", "base64"]}),
+        )]);
+        let result = simulate(json!({"accountOverrides": account_overrides}));
+        assert_eq!(
+            result["error"]["code"],
+            json!(ErrorCode::InvalidParams.code())
+        );
+
+        let account_overrides = HashMap::from([(
+            payer.pubkey().to_string(),
+            json!({"data": ["AQID", "base58"]}),
+        )]);
+        let result = simulate(json!({"accountOverrides": account_overrides}));
+        assert_eq!(
+            result["error"]["code"],
+            json!(ErrorCode::InvalidParams.code())
+        );
+    }
+
+    #[test]
+    fn test_rpc_simulate_bundle() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let RpcHandler {
+            ref meta, ref io, ..
+        } = rpc;
+
+        let alice = Keypair::new();
+        let bob_pubkey = solana_pubkey::new_rand();
+        let fund_alice = system_transaction::transfer(
+            &rpc.mint_keypair,
+            &alice.pubkey(),
+            10 * rent_exempt_amount,
+            recent_blockhash,
+        );
+        let pay_bob =
+            system_transaction::transfer(&alice, &bob_pubkey, rent_exempt_amount, recent_blockhash);
+        let encode = |tx: &Transaction| bs58::encode(serialize(tx).unwrap()).into_string();
+        bank.freeze();
+
+        let simulate = |transactions: Vec<String>| {
+            let req = json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "simulateBundle",
+                "params": [transactions, {"sigVerify": true}],
+            });
+            let res = io.handle_request_sync(&req.to_string(), meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response")).unwrap()
+        };
+
+        // Bob is paid with the lamports of the first transaction
+        let result = simulate(vec![encode(&fund_alice), encode(&pay_bob)]);
+        let value = &result["result"]["value"];
+        assert_eq!(value["err"], Value::Null);
+        let transaction_results = value["transactionResults"].as_array().unwrap();
+        assert_eq!(transaction_results.len(), 2);
+        for transaction_result in transaction_results {
+            assert_eq!(transaction_result["err"], Value::Null);
+            assert_eq!(transaction_result["unitsConsumed"], json!(150));
+            assert_eq!(
+                transaction_result["transactionFee"],
+                json!(TEST_SIGNATURE_FEE)
+            );
+            assert_eq!(transaction_result["priorityFee"], json!(0));
+            assert_eq!(transaction_result["logs"].as_array().unwrap().len(), 2);
+        }
+
+        // In the opposite order, Alice can't pay
+        let result = simulate(vec![encode(&pay_bob), encode(&fund_alice)]);
+        let value = &result["result"]["value"];
+        assert_eq!(value["err"], json!("AccountNotFound"));
+        assert_eq!(
+            value["transactionResults"][0]["err"],
+            json!("AccountNotFound")
+        );
+        assert_eq!(value["transactionResults"][1]["err"], Value::Null);
+
+        let result = simulate(vec![encode(&pay_bob), encode(&pay_bob)]);
+        assert_eq!(
+            result["error"]["code"],
+            json!(ErrorCode::InvalidParams.code())
+        );
+        let result = simulate(vec![]);
+        assert_eq!(
+            result["error"]["code"],
+            json!(ErrorCode::InvalidParams.code())
+        );
+    }
+
     #[test]
     #[should_panic(expected = "simulation bank must be frozen")]
     fn test_rpc_simulate_transaction_panic_on_unfrozen_bank() {
@@ -6684,6 +9044,8 @@ pub mod tests {
             Arc::new(AtomicU64::default()),
             Arc::new(AtomicU64::default()),
             Arc::new(PrioritizationFeeCache::default()),
+            Arc::default(),
+            Arc::default(),
             service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
         );
 
@@ -6975,6 +9337,8 @@ pub mod tests {
             Arc::new(AtomicU64::default()),
             Arc::new(AtomicU64::default()),
             Arc::new(PrioritizationFeeCache::default()),
+            Arc::default(),
+            Arc::default(),
             service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
         );
 
@@ -8675,6 +11039,8 @@ pub mod tests {
             max_complete_transaction_status_slot,
             max_complete_rewards_slot,
             Arc::new(PrioritizationFeeCache::default()),
+            Arc::default(),
+            Arc::default(),
             service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
         );
 
@@ -9151,4 +11517,93 @@ pub mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_rpc_get_program_fee_market() {
+        let mut rpc = RpcHandler::start();
+        let system_program = system_program::id().to_string();
+        let request = create_test_request("getProgramFeeMarket", Some(json!([system_program])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(
+            response,
+            (
+                ErrorCode::InvalidRequest.code(),
+                "Program fee market is not enabled on this node".to_string()
+            )
+        );
+
+        rpc.meta.prioritization_fee_cache =
+            Arc::new(PrioritizationFeeCache::new_with_program_attribution(
+                solana_runtime::prioritization_fee_cache::MAX_NUM_RECENT_BLOCKS,
+            ));
+        let mint = rpc.mint_keypair.pubkey();
+        let slot0 = rpc.working_bank().slot();
+        let bank0_id = rpc.working_bank().bank_id();
+        // Only the transactions writing to the mint account are attributed to the system program
+        let transactions = [10, 30, 20]
+            .into_iter()
+            .enumerate()
+            .map(|(i, price)| {
+                let from = if i < 2 { mint } else { Pubkey::new_unique() };
+                Transaction::new_unsigned(Message::new(
+                    &[
+                        system_instruction::transfer(&from, &Pubkey::new_unique(), 1),
+                        ComputeBudgetInstruction::set_compute_unit_price(price),
+                    ],
+                    Some(&from),
+                ))
+            })
+            .collect();
+        rpc.update_prioritization_fee_cache(transactions);
+        let cache = rpc.get_prioritization_fee_cache();
+        cache.finalize_priority_fee(slot0, bank0_id);
+        while cache.available_block_count() < 1 {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+
+        rpc.advance_bank_to_confirmed_slot(1);
+        let slot1 = rpc.working_bank().slot();
+        let bank1_id = rpc.working_bank().bank_id();
+        rpc.update_prioritization_fee_cache(vec![Transaction::new_unsigned(Message::new(
+            &[system_instruction::transfer(
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&Pubkey::new_unique()),
+        ))]);
+        let cache = rpc.get_prioritization_fee_cache();
+        cache.finalize_priority_fee(slot1, bank1_id);
+        while cache.available_block_count() < 2 {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+
+        let request = create_test_request("getProgramFeeMarket", Some(json!([system_program])));
+        let response: RpcProgramFeeMarket = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(
+            response,
+            RpcProgramFeeMarket {
+                program_id: system_program,
+                blocks: 2,
+                blocks_with_transactions: 1,
+                inclusion_rate: 0.5,
+                transactions: 2,
+                min_compute_unit_price: 10,
+                median_compute_unit_price: 10,
+                p90_compute_unit_price: 10,
+                max_compute_unit_price: 30,
+                slots: vec![RpcProgramFee {
+                    slot: slot0,
+                    transactions: 2,
+                    min_compute_unit_price: 10,
+                    median_compute_unit_price: 10,
+                    max_compute_unit_price: 30,
+                }],
+            }
+        );
+
+        let request = create_test_request("getProgramFeeMarket", Some(json!(["invalid"])));
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
 }
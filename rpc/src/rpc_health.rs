@@ -8,6 +8,68 @@ use {
     },
 };
 
+/// Health of a Geyser plugin, as reported by the plugin manager
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginHealth {
+    pub name: String,
+    /// Notifications the plugin failed to process since it was loaded
+    pub notification_errors: u64,
+    /// Seconds since the last failed notification
+    pub last_error_age_secs: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+pub trait PluginHealthReporter {
+    fn plugin_health(&self) -> Vec<PluginHealth>;
+}
+
+pub type PluginHealthReporterArc = Arc<dyn PluginHealthReporter + Sync + Send>;
+
+/// Age of the latest snapshot archives
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotHealth {
+    pub full_snapshot_slot: Option<Slot>,
+    pub incremental_snapshot_slot: Option<Slot>,
+    /// Slots between the latest snapshot and the root
+    pub slots_behind_root: Option<Slot>,
+    /// Seconds since the latest snapshot archive was written
+    pub age_secs: Option<u64>,
+}
+
+/// Backlog of the accounts write cache
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsDbHealth {
+    pub cached_slots: usize,
+    pub cache_bytes: u64,
+    /// Rooted slots not yet flushed to storage
+    pub unflushed_root_slots: Slot,
+}
+
+/// Connectivity of the BigTable long-term storage
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BigTableHealth {
+    pub connected: bool,
+    pub first_available_block: Option<Slot>,
+    pub error: Option<String>,
+}
+
+/// Structured health, served by `/health?detail`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcHealthDetail {
+    pub status: String,
+    pub behind_slots: Option<Slot>,
+    pub root_slot: Slot,
+    pub snapshot: Option<SnapshotHealth>,
+    pub accounts_db: AccountsDbHealth,
+    pub bigtable: Option<BigTableHealth>,
+    pub geyser_plugins: Option<Vec<PluginHealth>>,
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum RpcHealthStatus {
     Ok,
@@ -15,6 +77,16 @@ pub enum RpcHealthStatus {
     Unknown,
 }
 
+impl RpcHealthStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Behind { .. } => "behind",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
 pub struct RpcHealth {
     optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
     blockstore: Arc<Blockstore>,
@@ -7,15 +7,17 @@ use {
         rpc_pubsub_service::PubSubConfig,
         rpc_subscription_tracker::{
             AccountSubscriptionParams, BlockSubscriptionKind, BlockSubscriptionParams,
-            LogsSubscriptionKind, LogsSubscriptionParams, ProgramSubscriptionParams,
-            SignatureSubscriptionParams, SubscriptionControl, SubscriptionId, SubscriptionParams,
-            SubscriptionToken,
+            ComputeUnitPriceSubscriptionParams, LogLineRegex, LogsSubscriptionKind,
+            LogsSubscriptionParams, ProgramSubscriptionParams, SignatureSubscriptionParams,
+            SubscriptionControl, SubscriptionId, SubscriptionParams, SubscriptionToken,
+            TokenBalanceSubscriptionParams,
         },
     },
     dashmap::DashMap,
     jsonrpc_core::{Error, ErrorCode, Result},
     jsonrpc_derive::rpc,
     jsonrpc_pubsub::{typed::Subscriber, SubscriptionId as PubSubSubscriptionId},
+    regex::RegexBuilder,
     solana_account_decoder::{UiAccount, UiAccountEncoding},
     solana_rpc_client_api::{
         config::{
@@ -24,8 +26,9 @@ use {
             RpcTransactionLogsFilter,
         },
         response::{
-            Response as RpcResponse, RpcBlockUpdate, RpcKeyedAccount, RpcLogsResponse,
-            RpcSignatureResult, RpcVersionInfo, RpcVote, SlotInfo, SlotUpdate,
+            Response as RpcResponse, RpcBlockUpdate, RpcComputeUnitPriceRecommendation,
+            RpcKeyedAccount, RpcLogsResponse, RpcSignatureResult, RpcTokenBalanceUpdate,
+            RpcVersionInfo, RpcVote, SlotInfo, SlotUpdate,
         },
     },
     solana_sdk::{clock::Slot, pubkey::Pubkey, signature::Signature},
@@ -152,6 +155,33 @@ pub trait RpcSolPubSub {
         id: PubSubSubscriptionId,
     ) -> Result<bool>;
 
+    // Get notification of the higher compute unit prices recommended for a transaction sent
+    // with a compute unit price escalation, while it does not land
+    // Accepts signature parameter as base-58 encoded string
+    #[pubsub(
+        subscription = "computeUnitPriceNotification",
+        subscribe,
+        name = "computeUnitPriceSubscribe"
+    )]
+    fn compute_unit_price_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<RpcResponse<RpcComputeUnitPriceRecommendation>>,
+        signature_str: String,
+    );
+
+    // Unsubscribe from compute unit price notification subscription.
+    #[pubsub(
+        subscription = "computeUnitPriceNotification",
+        unsubscribe,
+        name = "computeUnitPriceUnsubscribe"
+    )]
+    fn compute_unit_price_unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        id: PubSubSubscriptionId,
+    ) -> Result<bool>;
+
     // Get notification when slot is encountered
     #[pubsub(subscription = "slotNotification", subscribe, name = "slotSubscribe")]
     fn slot_subscribe(&self, meta: Self::Metadata, subscriber: Subscriber<SlotInfo>);
@@ -214,6 +244,32 @@ pub trait RpcSolPubSub {
         id: PubSubSubscriptionId,
     ) -> Result<bool>;
 
+    // Get notification of the token balance changes of finalized transactions
+    // Accepts a list of token account owners as base-58 encoded strings
+    #[pubsub(
+        subscription = "tokenBalanceNotification",
+        subscribe,
+        name = "tokenBalanceSubscribe"
+    )]
+    fn token_balance_subscribe(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<RpcResponse<RpcTokenBalanceUpdate>>,
+        owners: Vec<String>,
+    );
+
+    // Unsubscribe from token balance notification subscription.
+    #[pubsub(
+        subscription = "tokenBalanceNotification",
+        unsubscribe,
+        name = "tokenBalanceUnsubscribe"
+    )]
+    fn token_balance_unsubscribe(
+        &self,
+        meta: Option<Self::Metadata>,
+        id: PubSubSubscriptionId,
+    ) -> Result<bool>;
+
     // Get notification when vote is encountered
     #[pubsub(subscription = "voteNotification", subscribe, name = "voteSubscribe")]
     fn vote_subscribe(&self, meta: Self::Metadata, subscriber: Subscriber<RpcVote>);
@@ -306,6 +362,14 @@ mod internal {
         #[rpc(name = "signatureUnsubscribe")]
         fn signature_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
 
+        // Get notification of the higher compute unit prices recommended for a transaction
+        #[rpc(name = "computeUnitPriceSubscribe")]
+        fn compute_unit_price_subscribe(&self, signature_str: String) -> Result<SubscriptionId>;
+
+        // Unsubscribe from compute unit price notification subscription.
+        #[rpc(name = "computeUnitPriceUnsubscribe")]
+        fn compute_unit_price_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
+
         // Get notification when slot is encountered
         #[rpc(name = "slotSubscribe")]
         fn slot_subscribe(&self) -> Result<SubscriptionId>;
@@ -334,6 +398,14 @@ mod internal {
         #[rpc(name = "blockUnsubscribe")]
         fn block_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
 
+        // Get notification of the token balance changes of finalized transactions
+        #[rpc(name = "tokenBalanceSubscribe")]
+        fn token_balance_subscribe(&self, owners: Vec<String>) -> Result<SubscriptionId>;
+
+        // Unsubscribe from token balance notification subscription.
+        #[rpc(name = "tokenBalanceUnsubscribe")]
+        fn token_balance_unsubscribe(&self, id: SubscriptionId) -> Result<bool>;
+
         // Get notification when vote is encountered
         #[rpc(name = "voteSubscribe")]
         fn vote_subscribe(&self) -> Result<SubscriptionId>;
@@ -411,6 +483,14 @@ impl RpcSolPubSubImpl {
     }
 }
 
+/// Most addresses a `logsSubscribe` subscription can mention
+const MAX_LOGS_MENTIONS: usize = 32;
+/// Largest compiled size of the `logRegex` of a `logsSubscribe` subscription, matched against
+/// every log line of every notified transaction
+const MAX_LOGS_REGEX_SIZE: usize = 1 << 20;
+/// Most owners a `tokenBalanceSubscribe` subscription can watch
+const MAX_TOKEN_BALANCE_OWNERS: usize = 256;
+
 fn param<T: FromStr>(param_str: &str, thing: &str) -> Result<T> {
     param_str.parse::<T>().map_err(|_e| Error {
         code: ErrorCode::InvalidParams,
@@ -482,22 +562,51 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         filter: RpcTransactionLogsFilter,
         config: Option<RpcTransactionLogsConfig>,
     ) -> Result<SubscriptionId> {
+        let RpcTransactionLogsConfig {
+            commitment,
+            log_contains,
+            log_regex,
+        } = config.unwrap_or_default();
+        let log_regex = log_regex
+            .map(|pattern| {
+                RegexBuilder::new(&pattern)
+                    .size_limit(MAX_LOGS_REGEX_SIZE)
+                    .build()
+                    .map(LogLineRegex)
+                    .map_err(|err| Error {
+                        code: ErrorCode::InvalidParams,
+                        message: format!("Invalid Request: Invalid logRegex provided: {err}"),
+                        data: None,
+                    })
+            })
+            .transpose()?;
         let params = LogsSubscriptionParams {
             kind: match filter {
                 RpcTransactionLogsFilter::All => LogsSubscriptionKind::All,
                 RpcTransactionLogsFilter::AllWithVotes => LogsSubscriptionKind::AllWithVotes,
                 RpcTransactionLogsFilter::Mentions(keys) => {
-                    if keys.len() != 1 {
+                    if keys.is_empty() || keys.len() > MAX_LOGS_MENTIONS {
                         return Err(Error {
                             code: ErrorCode::InvalidParams,
-                            message: "Invalid Request: Only 1 address supported".into(),
+                            message: format!(
+                                "Invalid Request: Between 1 and {MAX_LOGS_MENTIONS} addresses \
+                                 supported"
+                            ),
                             data: None,
                         });
                     }
-                    LogsSubscriptionKind::Single(param::<Pubkey>(&keys[0], "mentions")?)
+                    let mut pubkeys = keys
+                        .iter()
+                        .map(|key| param::<Pubkey>(key, "mentions"))
+                        .collect::<Result<Vec<_>>>()?;
+                    pubkeys.sort_unstable();
+                    pubkeys.dedup();
+                    LogsSubscriptionKind::Mentions(pubkeys)
                 }
             },
-            commitment: config.and_then(|c| c.commitment).unwrap_or_default(),
+            commitment: commitment.unwrap_or_default(),
+            log_contains,
+            log_regex,
         };
         self.subscribe(SubscriptionParams::Logs(params))
     }
@@ -524,6 +633,17 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         self.unsubscribe(id)
     }
 
+    fn compute_unit_price_subscribe(&self, signature_str: String) -> Result<SubscriptionId> {
+        let params = ComputeUnitPriceSubscriptionParams {
+            signature: param::<Signature>(&signature_str, "signature")?,
+        };
+        self.subscribe(SubscriptionParams::ComputeUnitPrice(params))
+    }
+
+    fn compute_unit_price_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
+        self.unsubscribe(id)
+    }
+
     fn slot_subscribe(&self) -> Result<SubscriptionId> {
         self.subscribe(SubscriptionParams::Slot)
     }
@@ -577,6 +697,39 @@ impl RpcSolPubSubInternal for RpcSolPubSubImpl {
         self.unsubscribe(id)
     }
 
+    fn token_balance_subscribe(&self, owners: Vec<String>) -> Result<SubscriptionId> {
+        // Token balances are read from the transaction statuses of complete blocks, which are
+        // only available with block subscriptions enabled
+        if !self.config.enable_block_subscription {
+            return Err(Error::new(jsonrpc_core::ErrorCode::MethodNotFound));
+        }
+        if owners.is_empty() || owners.len() > MAX_TOKEN_BALANCE_OWNERS {
+            return Err(Error {
+                code: ErrorCode::InvalidParams,
+                message: format!(
+                    "Invalid Request: Between 1 and {MAX_TOKEN_BALANCE_OWNERS} owners supported"
+                ),
+                data: None,
+            });
+        }
+        let mut owners = owners
+            .iter()
+            .map(|owner| param::<Pubkey>(owner, "owner"))
+            .collect::<Result<Vec<_>>>()?;
+        owners.sort_unstable();
+        owners.dedup();
+        self.subscribe(SubscriptionParams::TokenBalances(
+            TokenBalanceSubscriptionParams { owners },
+        ))
+    }
+
+    fn token_balance_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
+        if !self.config.enable_block_subscription {
+            return Err(Error::new(jsonrpc_core::ErrorCode::MethodNotFound));
+        }
+        self.unsubscribe(id)
+    }
+
     fn vote_subscribe(&self) -> Result<SubscriptionId> {
         if !self.config.enable_vote_subscription {
             return Err(Error::new(jsonrpc_core::ErrorCode::MethodNotFound));
@@ -648,6 +801,9 @@ mod tests {
             system_instruction, system_program, system_transaction,
             transaction::{self, Transaction},
         },
+        solana_send_transaction_service::compute_unit_price_escalation::{
+            ComputeUnitPriceNotifier, ComputeUnitPriceRecommendation,
+        },
         solana_stake_program::stake_state,
         solana_vote::vote_transaction::VoteTransaction,
         solana_vote_program::vote_state::Vote,
@@ -723,8 +879,12 @@ mod tests {
 
         // Test signature confirmation notification
         let response = receiver.recv();
-        let expected_res =
-            RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult { err: None });
+        let expected_res = RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult {
+            err: None,
+            fee: None,
+            fee_breakdown: None,
+            compute_units_consumed: None,
+        });
         let expected = json!({
            "jsonrpc": "2.0",
            "method": "signatureNotification",
@@ -811,6 +971,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compute_unit_price_subscribe() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let rpc_subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(AtomicU64::default()),
+            Arc::new(AtomicU64::default()),
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests())),
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+        ));
+
+        let signature = Signature::new_unique();
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&rpc_subscriptions);
+        let sub_id = rpc
+            .compute_unit_price_subscribe(signature.to_string())
+            .unwrap();
+        assert!(rpc
+            .compute_unit_price_subscribe("invalid".to_string())
+            .is_err());
+
+        // Recommendations for other transactions are not notified
+        rpc_subscriptions.notify_compute_unit_price_recommendation(
+            ComputeUnitPriceRecommendation {
+                signature: Signature::new_unique(),
+                slot: 7,
+                leader_rotations: 1,
+                compute_unit_price: 100,
+                recommended_compute_unit_price: 125,
+            },
+        );
+        rpc_subscriptions.notify_compute_unit_price_recommendation(
+            ComputeUnitPriceRecommendation {
+                signature,
+                slot: 8,
+                leader_rotations: 2,
+                compute_unit_price: 1_000,
+                recommended_compute_unit_price: 1_250,
+            },
+        );
+        let response = receiver.recv();
+        let expected = json!({
+           "jsonrpc": "2.0",
+           "method": "computeUnitPriceNotification",
+           "params": {
+               "result": {
+                   "context": { "slot": 8 },
+                   "value": {
+                       "signature": signature.to_string(),
+                       "leaderRotations": 2,
+                       "computeUnitPrice": 1_000,
+                       "recommendedComputeUnitPrice": 1_250,
+                   },
+               },
+               "subscription": 0,
+           }
+        });
+        assert_eq!(
+            expected,
+            serde_json::from_str::<serde_json::Value>(&response).unwrap(),
+        );
+
+        assert!(rpc.compute_unit_price_unsubscribe(sub_id).unwrap());
+        assert!(rpc.compute_unit_price_unsubscribe(sub_id).is_err());
+    }
+
     #[test]
     #[serial]
     fn test_signature_unsubscribe() {
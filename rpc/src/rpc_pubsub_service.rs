@@ -137,6 +137,8 @@ struct SentNotificationStats {
     num_root: AtomicUsize,
     num_vote: AtomicUsize,
     num_block: AtomicUsize,
+    num_token_balance: AtomicUsize,
+    num_compute_unit_price: AtomicUsize,
     total_creation_to_queue_time_us: AtomicU64,
     last_report: AtomicInterval,
 }
@@ -191,6 +193,16 @@ impl SentNotificationStats {
                     self.num_block.swap(0, Ordering::Relaxed) as i64,
                     i64
                 ),
+                (
+                    "num_token_balance",
+                    self.num_token_balance.swap(0, Ordering::Relaxed) as i64,
+                    i64
+                ),
+                (
+                    "num_compute_unit_price",
+                    self.num_compute_unit_price.swap(0, Ordering::Relaxed) as i64,
+                    i64
+                ),
                 (
                     "total_creation_to_queue_time_us",
                     self.total_creation_to_queue_time_us
@@ -240,6 +252,12 @@ fn increment_sent_notification_stats(
         SubscriptionParams::Block(_) => {
             stats.num_block.fetch_add(1, Ordering::Relaxed);
         }
+        SubscriptionParams::TokenBalances(_) => {
+            stats.num_token_balance.fetch_add(1, Ordering::Relaxed);
+        }
+        SubscriptionParams::ComputeUnitPrice(_) => {
+            stats.num_compute_unit_price.fetch_add(1, Ordering::Relaxed);
+        }
     }
     stats.total_creation_to_queue_time_us.fetch_add(
         notification.created_at.elapsed().as_micros() as u64,
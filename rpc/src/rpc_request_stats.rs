@@ -0,0 +1,333 @@
+//! Per-method latency and error statistics of the JSON RPC service, and the slowest requests it
+//! recently served.
+//!
+//! [`RpcRequestTracer`] is a JSON RPC middleware timing every call served by the RPC service into
+//! process-wide stats, which the admin RPC service exposes so operators can find expensive or
+//! abusive query patterns without an external APM. Latencies are also observed in the
+//! `tachyon_rpc_request_duration_us` Prometheus histogram when the exposition is enabled.
+
+use {
+    jsonrpc_core::{
+        futures::future::Either, middleware, Call, ErrorCode, Metadata, Middleware, Output, Params,
+    },
+    solana_metrics::prometheus,
+    std::{
+        collections::HashMap,
+        future::Future,
+        pin::Pin,
+        sync::{LazyLock, Mutex},
+        time::{Duration, Instant},
+    },
+};
+
+/// Upper bounds of the latency buckets, in microseconds; the last bucket is unbounded
+pub const LATENCY_BUCKETS_US: [u64; 12] = [
+    100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 500_000, 1_000_000,
+];
+/// Number of slowest requests kept
+const MAX_SLOWEST_REQUESTS: usize = 32;
+/// Requests older than this are forgotten from the slowest requests
+const SLOWEST_REQUESTS_WINDOW: Duration = Duration::from_secs(10 * 60);
+/// Parameters of the slowest requests are truncated to this many bytes
+const MAX_TRACED_PARAMS_LEN: usize = 256;
+/// Bounds the number of methods tracked; calls to unknown methods are not tracked by name
+const MAX_METHODS: usize = 256;
+const UNKNOWN_METHOD: &str = "unknown";
+
+#[derive(Default)]
+struct MethodStats {
+    count: u64,
+    errors: u64,
+    total_us: u64,
+    max_us: u64,
+    buckets: [u64; LATENCY_BUCKETS_US.len() + 1],
+}
+
+impl MethodStats {
+    fn record(&mut self, duration_us: u64, is_error: bool) {
+        self.count = self.count.saturating_add(1);
+        if is_error {
+            self.errors = self.errors.saturating_add(1);
+        }
+        self.total_us = self.total_us.saturating_add(duration_us);
+        self.max_us = self.max_us.max(duration_us);
+        let bucket = LATENCY_BUCKETS_US.partition_point(|bound| *bound < duration_us);
+        self.buckets[bucket] = self.buckets[bucket].saturating_add(1);
+    }
+
+    /// Returns the upper bound of the bucket of the `percentile`th latency
+    fn percentile_us(&self, percentile: u64) -> u64 {
+        let rank = self.count.saturating_mul(percentile).div_ceil(100).max(1);
+        let mut count = 0u64;
+        for (bucket, bucket_count) in self.buckets.iter().enumerate() {
+            count = count.saturating_add(*bucket_count);
+            if count >= rank {
+                return LATENCY_BUCKETS_US
+                    .get(bucket)
+                    .map_or(self.max_us, |bound| (*bound).min(self.max_us));
+            }
+        }
+        self.max_us
+    }
+}
+
+/// Latency and errors of the calls to a method
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMethodStats {
+    pub method: String,
+    pub count: u64,
+    pub errors: u64,
+    pub mean_us: u64,
+    /// Percentiles are the upper bounds of the latency buckets they fall in
+    pub p50_us: u64,
+    pub p90_us: u64,
+    pub p99_us: u64,
+    pub max_us: u64,
+    /// Calls per latency bucket, bounded by `LATENCY_BUCKETS_US` and then unbounded
+    pub buckets: Vec<u64>,
+}
+
+/// A request among the slowest recently served
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTracedRequest {
+    pub method: String,
+    /// Parameters serialized as JSON, truncated to a few hundred bytes
+    pub params: String,
+    pub duration_us: u64,
+    pub is_error: bool,
+    pub age_secs: u64,
+}
+
+#[derive(Default)]
+struct RpcRequestStatsInner {
+    methods: HashMap<String, MethodStats>,
+    slowest: Vec<(Instant, RpcTracedRequest)>,
+}
+
+#[derive(Default)]
+pub struct RpcRequestStats {
+    inner: Mutex<RpcRequestStatsInner>,
+}
+
+impl RpcRequestStats {
+    pub fn record(&self, method: &str, params: &Params, duration: Duration, is_error: bool) {
+        let duration_us = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        let method = if inner.methods.contains_key(method) || inner.methods.len() < MAX_METHODS {
+            method
+        } else {
+            UNKNOWN_METHOD
+        };
+        inner
+            .methods
+            .entry(method.to_string())
+            .or_default()
+            .record(duration_us, is_error);
+
+        inner
+            .slowest
+            .retain(|(time, _)| now.duration_since(*time) < SLOWEST_REQUESTS_WINDOW);
+        if inner.slowest.len() >= MAX_SLOWEST_REQUESTS {
+            let (fastest, _) = inner
+                .slowest
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (_, request))| request.duration_us)
+                .unwrap();
+            if inner.slowest[fastest].1.duration_us >= duration_us {
+                return;
+            }
+            inner.slowest.swap_remove(fastest);
+        }
+        inner.slowest.push((
+            now,
+            RpcTracedRequest {
+                method: method.to_string(),
+                params: truncated_params(params),
+                duration_us,
+                is_error,
+                age_secs: 0,
+            },
+        ));
+    }
+
+    /// Returns the stats of every method, by descending total latency
+    pub fn method_stats(&self) -> Vec<RpcMethodStats> {
+        let inner = self.inner.lock().unwrap();
+        let mut method_stats: Vec<_> = inner
+            .methods
+            .iter()
+            .map(|(method, stats)| RpcMethodStats {
+                method: method.clone(),
+                count: stats.count,
+                errors: stats.errors,
+                mean_us: stats.total_us.checked_div(stats.count).unwrap_or_default(),
+                p50_us: stats.percentile_us(50),
+                p90_us: stats.percentile_us(90),
+                p99_us: stats.percentile_us(99),
+                max_us: stats.max_us,
+                buckets: stats.buckets.to_vec(),
+            })
+            .collect();
+        method_stats
+            .sort_by_key(|stats| std::cmp::Reverse(stats.mean_us.saturating_mul(stats.count)));
+        method_stats
+    }
+
+    /// Returns the slowest requests of the last minutes, slowest first
+    pub fn slowest_requests(&self) -> Vec<RpcTracedRequest> {
+        let now = Instant::now();
+        let inner = self.inner.lock().unwrap();
+        let mut slowest: Vec<_> = inner
+            .slowest
+            .iter()
+            .filter(|(time, _)| now.duration_since(*time) < SLOWEST_REQUESTS_WINDOW)
+            .map(|(time, request)| RpcTracedRequest {
+                age_secs: now.duration_since(*time).as_secs(),
+                ..request.clone()
+            })
+            .collect();
+        slowest.sort_by_key(|request| std::cmp::Reverse(request.duration_us));
+        slowest
+    }
+
+    pub fn reset(&self) {
+        *self.inner.lock().unwrap() = RpcRequestStatsInner::default();
+    }
+}
+
+fn truncated_params(params: &Params) -> String {
+    let mut params = serde_json::to_string(params).unwrap_or_default();
+    if params.len() > MAX_TRACED_PARAMS_LEN {
+        let mut len = MAX_TRACED_PARAMS_LEN;
+        while !params.is_char_boundary(len) {
+            len -= 1;
+        }
+        params.truncate(len);
+        params.push_str("...");
+    }
+    params
+}
+
+static RPC_REQUEST_STATS: LazyLock<RpcRequestStats> = LazyLock::new(RpcRequestStats::default);
+
+/// The RPC request stats of the process
+pub fn rpc_request_stats() -> &'static RpcRequestStats {
+    &RPC_REQUEST_STATS
+}
+
+/// JSON RPC middleware recording the latency and outcome of every call in [`rpc_request_stats`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RpcRequestTracer;
+
+impl<M: Metadata> Middleware<M> for RpcRequestTracer {
+    type Future = middleware::NoopFuture;
+    type CallFuture = Pin<Box<dyn Future<Output = Option<Output>> + Send>>;
+
+    fn on_call<F, X>(&self, call: Call, meta: M, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, M) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let Call::MethodCall(method_call) = &call else {
+            return Either::Right(next(call, meta));
+        };
+        let method = method_call.method.clone();
+        let params = method_call.params.clone();
+        let start = Instant::now();
+        let output = next(call, meta);
+        Either::Left(Box::pin(async move {
+            let output = output.await;
+            let duration = start.elapsed();
+            let (method, is_error) = match &output {
+                Some(Output::Failure(failure))
+                    if failure.error.code == ErrorCode::MethodNotFound =>
+                {
+                    (UNKNOWN_METHOD, true)
+                }
+                Some(Output::Failure(_)) => (method.as_str(), true),
+                Some(Output::Success(_)) | None => (method.as_str(), false),
+            };
+            rpc_request_stats().record(method, &params, duration, is_error);
+            let labels = [("method", method)];
+            prometheus::histogram_observe(
+                "rpc_request_duration_us",
+                &labels,
+                duration.as_micros() as f64,
+            );
+            if is_error {
+                prometheus::counter_add("rpc_request_errors", &labels, 1);
+            }
+            output
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::Value};
+
+    #[test]
+    fn test_rpc_request_stats() {
+        let stats = RpcRequestStats::default();
+        let params = Params::Array(vec![Value::from("a".repeat(1_000))]);
+        for duration_us in [50, 300, 2_000, 40_000] {
+            stats.record(
+                "getBalance",
+                &Params::None,
+                Duration::from_micros(duration_us),
+                false,
+            );
+        }
+        stats.record("getBlock", &params, Duration::from_secs(2), true);
+
+        let method_stats = stats.method_stats();
+        assert_eq!(method_stats.len(), 2);
+        assert_eq!(method_stats[0].method, "getBlock");
+        assert_eq!(method_stats[0].errors, 1);
+        assert_eq!(method_stats[0].p50_us, 2_000_000);
+        assert_eq!(*method_stats[0].buckets.last().unwrap(), 1);
+        let get_balance = &method_stats[1];
+        assert_eq!(get_balance.count, 4);
+        assert_eq!(get_balance.errors, 0);
+        assert_eq!(get_balance.mean_us, (50 + 300 + 2_000 + 40_000) / 4);
+        assert_eq!(get_balance.p50_us, 500);
+        assert_eq!(get_balance.p99_us, 40_000);
+        assert_eq!(get_balance.max_us, 40_000);
+
+        let slowest = stats.slowest_requests();
+        assert_eq!(slowest.len(), 5);
+        assert_eq!(slowest[0].method, "getBlock");
+        assert!(slowest[0].is_error);
+        assert_eq!(slowest[0].params.len(), MAX_TRACED_PARAMS_LEN + 3);
+        assert!(slowest
+            .windows(2)
+            .all(|requests| requests[0].duration_us >= requests[1].duration_us));
+
+        // Only the slowest requests are kept
+        for _ in 0..MAX_SLOWEST_REQUESTS {
+            stats.record("getSlot", &Params::None, Duration::from_millis(10), false);
+        }
+        let slowest = stats.slowest_requests();
+        assert_eq!(slowest.len(), MAX_SLOWEST_REQUESTS);
+        assert_eq!(slowest[0].method, "getBlock");
+        assert_eq!(slowest[1].duration_us, 40_000);
+        assert!(!slowest.iter().any(|request| request.duration_us < 10_000));
+
+        stats.reset();
+        assert!(stats.method_stats().is_empty());
+        assert!(stats.slowest_requests().is_empty());
+    }
+
+    #[test]
+    fn test_truncated_params() {
+        assert!(!truncated_params(&Params::None).ends_with("..."));
+        let params = Params::Array(vec![Value::from("é".repeat(MAX_TRACED_PARAMS_LEN))]);
+        let truncated = truncated_params(&params);
+        assert!(truncated.len() <= MAX_TRACED_PARAMS_LEN + 3);
+        assert!(truncated.ends_with("..."));
+    }
+}
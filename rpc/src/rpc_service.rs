@@ -3,11 +3,14 @@
 use {
     crate::{
         cluster_tpu_info::ClusterTpuInfo,
+        confirmation_latency::ConfirmationLatencyTracker,
         max_slots::MaxSlots,
+        optimistic_confirmation_attestations::OptimisticConfirmationAttestations,
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
         rpc::{rpc_accounts::*, rpc_accounts_scan::*, rpc_bank::*, rpc_full::*, rpc_minimal::*, *},
         rpc_cache::LargestAccountsCache,
         rpc_health::*,
+        rpc_request_stats::RpcRequestTracer,
     },
     crossbeam_channel::unbounded,
     jsonrpc_core::{futures::prelude::*, MetaIoHandler},
@@ -23,7 +26,7 @@ use {
         bigtable_upload_service::BigTableUploadService, blockstore::Blockstore,
         leader_schedule_cache::LeaderScheduleCache,
     },
-    solana_metrics::inc_new_counter_info,
+    solana_metrics::{inc_new_counter_info, prometheus},
     solana_perf::thread::renice_this_thread,
     solana_poh::poh_recorder::PohRecorder,
     solana_runtime::{
@@ -35,7 +38,7 @@ use {
         snapshot_utils,
     },
     solana_sdk::{
-        exit::Exit, genesis_config::DEFAULT_GENESIS_DOWNLOAD_PATH, hash::Hash,
+        clock::Slot, exit::Exit, genesis_config::DEFAULT_GENESIS_DOWNLOAD_PATH, hash::Hash,
         native_token::lamports_to_sol,
     },
     solana_send_transaction_service::{
@@ -64,6 +67,7 @@ use {
 const FULL_SNAPSHOT_REQUEST_PATH: &str = "/snapshot.tar.bz2";
 const INCREMENTAL_SNAPSHOT_REQUEST_PATH: &str = "/incremental-snapshot.tar.bz2";
 const LARGEST_ACCOUNTS_CACHE_DURATION: u64 = 60 * 60 * 2;
+const BIGTABLE_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
 /// Default minimum snapshot download speed is 10 MB/s
 /// Full snapshots are ~90 GB, incremental are ~1 GB today but both will increase over time
 /// Full: 120 GB / 10 MB/s = 12,000 seconds -> ~30k slots
@@ -123,6 +127,8 @@ struct RpcRequestMiddleware {
     snapshot_config: Option<SnapshotConfig>,
     bank_forks: Arc<RwLock<BankForks>>,
     health: Arc<RpcHealth>,
+    bigtable_ledger_storage: Option<solana_storage_bigtable::LedgerStorage>,
+    plugin_health_reporter: Option<PluginHealthReporterArc>,
 }
 
 impl RpcRequestMiddleware {
@@ -131,6 +137,8 @@ impl RpcRequestMiddleware {
         snapshot_config: Option<SnapshotConfig>,
         bank_forks: Arc<RwLock<BankForks>>,
         health: Arc<RpcHealth>,
+        bigtable_ledger_storage: Option<solana_storage_bigtable::LedgerStorage>,
+        plugin_health_reporter: Option<PluginHealthReporterArc>,
     ) -> Self {
         Self {
             ledger_path,
@@ -145,6 +153,8 @@ impl RpcRequestMiddleware {
             snapshot_config,
             bank_forks,
             health,
+            bigtable_ledger_storage,
+            plugin_health_reporter,
         }
     }
 
@@ -321,14 +331,111 @@ impl RpcRequestMiddleware {
     }
 
     fn health_check(&self) -> &'static str {
-        let response = match self.health.check() {
-            RpcHealthStatus::Ok => "ok",
-            RpcHealthStatus::Behind { .. } => "behind",
-            RpcHealthStatus::Unknown => "unknown",
-        };
+        let response = self.health.check().as_str();
         info!("health check: {}", response);
         response
     }
+
+    fn snapshot_health(&self, root_slot: Slot) -> Option<SnapshotHealth> {
+        let snapshot_config = self.snapshot_config.as_ref()?;
+        let full_snapshot_archive_info = snapshot_utils::get_highest_full_snapshot_archive_info(
+            &snapshot_config.full_snapshot_archives_dir,
+        );
+        let incremental_snapshot_archive_info =
+            full_snapshot_archive_info.as_ref().and_then(|info| {
+                snapshot_utils::get_highest_incremental_snapshot_archive_info(
+                    &snapshot_config.incremental_snapshot_archives_dir,
+                    info.slot(),
+                )
+            });
+        let latest_snapshot_archive_info = incremental_snapshot_archive_info
+            .as_ref()
+            .map(|info| info.snapshot_archive_info())
+            .or(full_snapshot_archive_info
+                .as_ref()
+                .map(|info| info.snapshot_archive_info()));
+        let age_secs = latest_snapshot_archive_info
+            .and_then(|info| std::fs::metadata(&info.path).ok())
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|modified| modified.elapsed().ok())
+            .map(|age| age.as_secs());
+        Some(SnapshotHealth {
+            full_snapshot_slot: full_snapshot_archive_info.as_ref().map(|info| info.slot()),
+            incremental_snapshot_slot: incremental_snapshot_archive_info
+                .as_ref()
+                .map(|info| info.slot()),
+            slots_behind_root: latest_snapshot_archive_info
+                .map(|info| root_slot.saturating_sub(info.slot)),
+            age_secs,
+        })
+    }
+
+    /// Responds with the structured health of the node and of its dependencies
+    fn process_health_detail(&self) -> RequestMiddlewareAction {
+        let status = self.health.check();
+        let root_bank = self.bank_forks.read().unwrap().root_bank();
+        let root_slot = root_bank.slot();
+        let accounts_cache = &root_bank.rc.accounts.accounts_db.accounts_cache;
+        let accounts_db = AccountsDbHealth {
+            cached_slots: accounts_cache.num_slots(),
+            cache_bytes: accounts_cache.size(),
+            unflushed_root_slots: root_slot.saturating_sub(accounts_cache.fetch_max_flush_root()),
+        };
+        let mut health_detail = RpcHealthDetail {
+            status: status.as_str().to_string(),
+            behind_slots: match status {
+                RpcHealthStatus::Behind { num_slots } => Some(num_slots),
+                RpcHealthStatus::Ok | RpcHealthStatus::Unknown => None,
+            },
+            root_slot,
+            snapshot: self.snapshot_health(root_slot),
+            accounts_db,
+            bigtable: None,
+            geyser_plugins: self
+                .plugin_health_reporter
+                .as_ref()
+                .map(|reporter| reporter.plugin_health()),
+        };
+        info!("health check: {}", health_detail.status);
+        let bigtable_ledger_storage = self.bigtable_ledger_storage.clone();
+
+        RequestMiddlewareAction::Respond {
+            should_validate_hosts: true,
+            response: Box::pin(async move {
+                if let Some(bigtable_ledger_storage) = bigtable_ledger_storage {
+                    let result = tokio::time::timeout(
+                        BIGTABLE_HEALTH_CHECK_TIMEOUT,
+                        bigtable_ledger_storage.get_first_available_block(),
+                    )
+                    .await;
+                    health_detail.bigtable = Some(match result {
+                        Ok(Ok(first_available_block)) => BigTableHealth {
+                            connected: true,
+                            first_available_block,
+                            error: None,
+                        },
+                        Ok(Err(err)) => BigTableHealth {
+                            connected: false,
+                            first_available_block: None,
+                            error: Some(err.to_string()),
+                        },
+                        Err(_) => BigTableHealth {
+                            connected: false,
+                            first_available_block: None,
+                            error: Some("timed out".to_string()),
+                        },
+                    });
+                }
+                Ok(hyper::Response::builder()
+                    .status(hyper::StatusCode::OK)
+                    .header(hyper::header::CONTENT_TYPE, "application/json")
+                    .body(hyper::Body::from(
+                        serde_json::to_string(&health_detail).unwrap(),
+                    ))
+                    .unwrap())
+            }),
+        }
+    }
 }
 
 impl RequestMiddleware for RpcRequestMiddleware {
@@ -383,18 +490,38 @@ impl RequestMiddleware for RpcRequestMiddleware {
             process_rest(&self.bank_forks, path)
         } else if self.is_file_get_path(request.uri().path()) {
             self.process_file_get(request.uri().path())
+        } else if request.uri().path() == "/health" && is_health_detail_query(request.uri().query())
+        {
+            self.process_health_detail()
         } else if request.uri().path() == "/health" {
             hyper::Response::builder()
                 .status(hyper::StatusCode::OK)
                 .body(hyper::Body::from(self.health_check()))
                 .unwrap()
                 .into()
+        } else if request.uri().path() == prometheus::METRICS_PATH && prometheus::is_enabled() {
+            hyper::Response::builder()
+                .status(hyper::StatusCode::OK)
+                .header(hyper::header::CONTENT_TYPE, prometheus::CONTENT_TYPE)
+                .body(hyper::Body::from(prometheus::render()))
+                .unwrap()
+                .into()
         } else {
             request.into()
         }
     }
 }
 
+/// Returns whether `/health` is queried for the structured health, with `?detail` or
+/// `?detail=true`
+fn is_health_detail_query(query: Option<&str>) -> bool {
+    query.is_some_and(|query| {
+        query
+            .split('&')
+            .any(|param| param == "detail" || param == "detail=true")
+    })
+}
+
 fn match_supply_path(path: &str) -> Option<&str> {
     match path {
         "/v0/circulating-supply" | "/v0/total-supply" => Some(path),
@@ -482,6 +609,9 @@ impl JsonRpcService {
         max_complete_transaction_status_slot: Arc<AtomicU64>,
         max_complete_rewards_slot: Arc<AtomicU64>,
         prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+        confirmation_latency: Arc<ConfirmationLatencyTracker>,
+        optimistic_confirmation_attestations: Arc<OptimisticConfirmationAttestations>,
+        plugin_health_reporter: Option<PluginHealthReporterArc>,
     ) -> Result<Self, String> {
         info!("rpc bound to {:?}", rpc_addr);
         info!("rpc configuration: {:?}", config);
@@ -567,6 +697,7 @@ impl JsonRpcService {
                 (None, None)
             };
 
+        let health_bigtable_ledger_storage = bigtable_ledger_storage.clone();
         let full_api = config.full_api;
         let max_request_body_size = config
             .max_request_body_size
@@ -589,6 +720,8 @@ impl JsonRpcService {
             max_complete_transaction_status_slot,
             max_complete_rewards_slot,
             prioritization_fee_cache,
+            confirmation_latency,
+            optimistic_confirmation_attestations,
             Arc::clone(&runtime),
         );
 
@@ -600,14 +733,18 @@ impl JsonRpcService {
             send_transaction_service_config.tpu_peers.clone(),
             leader_info,
             send_transaction_service_config.leader_forward_count,
-        );
+        )
+        .with_staked_identity(send_transaction_service_config.forward_with_staked_identity);
         let _send_transaction_service = Arc::new(SendTransactionService::new_with_client(
             &bank_forks,
             receiver,
             client,
             send_transaction_service_config,
-            exit,
+            exit.clone(),
         ));
+        let _epoch_stakes_archive_service =
+            request_processor.spawn_epoch_stakes_archive_service(exit.clone());
+        let _webhook_service = request_processor.spawn_webhook_service(exit);
 
         #[cfg(test)]
         let test_request_processor = request_processor.clone();
@@ -620,7 +757,7 @@ impl JsonRpcService {
             .spawn(move || {
                 renice_this_thread(rpc_niceness_adj).unwrap();
 
-                let mut io = MetaIoHandler::default();
+                let mut io = MetaIoHandler::with_middleware(RpcRequestTracer);
 
                 io.extend_with(rpc_minimal::MinimalImpl.to_delegate());
                 if full_api {
@@ -635,6 +772,8 @@ impl JsonRpcService {
                     snapshot_config,
                     bank_forks.clone(),
                     health.clone(),
+                    health_bigtable_ledger_storage,
+                    plugin_health_reporter,
                 );
                 let server = ServerBuilder::with_meta_extractor(
                     io,
@@ -809,6 +948,9 @@ mod tests {
             Arc::new(AtomicU64::default()),
             Arc::new(AtomicU64::default()),
             Arc::new(PrioritizationFeeCache::default()),
+            Arc::default(),
+            Arc::default(),
+            None,
         )
         .expect("assume successful JsonRpcService start");
         let thread = rpc_service.thread_hdl.thread();
@@ -903,12 +1045,16 @@ mod tests {
             None,
             bank_forks.clone(),
             health.clone(),
+            None,
+            None,
         );
         let rrm_with_snapshot_config = RpcRequestMiddleware::new(
             ledger_path.path().to_path_buf(),
             Some(SnapshotConfig::default()),
             bank_forks,
             health,
+            None,
+            None,
         );
 
         assert!(rrm.is_file_get_path(DEFAULT_GENESIS_DOWNLOAD_PATH));
@@ -1011,6 +1157,8 @@ mod tests {
             None,
             bank_forks,
             RpcHealth::stub(optimistically_confirmed_bank, blockstore),
+            None,
+            None,
         );
 
         // File does not exist => request should fail.
@@ -1055,4 +1203,61 @@ mod tests {
             panic!("Unexpected RequestMiddlewareAction variant");
         }
     }
+
+    #[test]
+    fn test_health_detail() {
+        assert!(!is_health_detail_query(None));
+        assert!(!is_health_detail_query(Some("")));
+        assert!(!is_health_detail_query(Some("detail=false")));
+        assert!(is_health_detail_query(Some("detail")));
+        assert!(is_health_detail_query(Some("foo=bar&detail=true")));
+
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let bank_forks = create_bank_forks();
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let health = RpcHealth::stub(optimistically_confirmed_bank, blockstore);
+        health.stub_set_health_status(Some(RpcHealthStatus::Behind { num_slots: 42 }));
+
+        struct TestPluginHealthReporter;
+        impl PluginHealthReporter for TestPluginHealthReporter {
+            fn plugin_health(&self) -> Vec<PluginHealth> {
+                vec![PluginHealth {
+                    name: "test".to_string(),
+                    notification_errors: 1,
+                    last_error_age_secs: Some(0),
+                    last_error: Some("error".to_string()),
+                }]
+            }
+        }
+
+        let rrm = RpcRequestMiddleware::new(
+            ledger_path.path().to_path_buf(),
+            None,
+            bank_forks,
+            health,
+            None,
+            Some(Arc::new(TestPluginHealthReporter)),
+        );
+        let runtime = Runtime::new().unwrap();
+        let RequestMiddlewareAction::Respond { response, .. } = rrm.process_health_detail() else {
+            panic!("Unexpected RequestMiddlewareAction variant");
+        };
+        let response = runtime.block_on(response).unwrap();
+        assert_eq!(response.status(), 200);
+        let body = runtime
+            .block_on(hyper::body::to_bytes(response.into_body()))
+            .unwrap();
+        let health_detail: RpcHealthDetail = serde_json::from_slice(&body).unwrap();
+        assert_eq!(health_detail.status, "behind");
+        assert_eq!(health_detail.behind_slots, Some(42));
+        assert_eq!(health_detail.root_slot, 0);
+        assert_eq!(health_detail.snapshot, None);
+        assert_eq!(health_detail.bigtable, None);
+        assert_eq!(
+            health_detail.geyser_plugins.unwrap()[0].notification_errors,
+            1
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use {
     crate::rpc_subscriptions::{NotificationEntry, RpcNotification, TimestampedNotificationEntry},
     dashmap::{mapref::entry::Entry as DashEntry, DashMap},
+    regex::Regex,
     solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig},
     solana_metrics::{CounterToken, TokenCounter},
     solana_rpc_client_api::filter::RpcFilterType,
@@ -15,6 +16,7 @@ use {
     std::{
         collections::hash_map::{Entry, HashMap},
         fmt,
+        hash::{Hash, Hasher},
         sync::{
             atomic::{AtomicU64, Ordering},
             Arc, RwLock, Weak,
@@ -43,9 +45,11 @@ impl From<SubscriptionId> for u64 {
 pub enum SubscriptionParams {
     Account(AccountSubscriptionParams),
     Block(BlockSubscriptionParams),
+    ComputeUnitPrice(ComputeUnitPriceSubscriptionParams),
     Logs(LogsSubscriptionParams),
     Program(ProgramSubscriptionParams),
     Signature(SignatureSubscriptionParams),
+    TokenBalances(TokenBalanceSubscriptionParams),
     Slot,
     SlotsUpdates,
     Root,
@@ -56,9 +60,11 @@ impl SubscriptionParams {
     fn method(&self) -> &'static str {
         match self {
             SubscriptionParams::Account(_) => "accountNotification",
+            SubscriptionParams::ComputeUnitPrice(_) => "computeUnitPriceNotification",
             SubscriptionParams::Logs(_) => "logsNotification",
             SubscriptionParams::Program(_) => "programNotification",
             SubscriptionParams::Signature(_) => "signatureNotification",
+            SubscriptionParams::TokenBalances(_) => "tokenBalanceNotification",
             SubscriptionParams::Slot => "slotNotification",
             SubscriptionParams::SlotsUpdates => "slotsUpdatesNotification",
             SubscriptionParams::Block(_) => "blockNotification",
@@ -74,7 +80,9 @@ impl SubscriptionParams {
             SubscriptionParams::Program(params) => Some(params.commitment),
             SubscriptionParams::Signature(params) => Some(params.commitment),
             SubscriptionParams::Block(params) => Some(params.commitment),
-            SubscriptionParams::Slot
+            SubscriptionParams::TokenBalances(_) => Some(CommitmentConfig::finalized()),
+            SubscriptionParams::ComputeUnitPrice(_)
+            | SubscriptionParams::Slot
             | SubscriptionParams::SlotsUpdates
             | SubscriptionParams::Root
             | SubscriptionParams::Vote => None,
@@ -88,7 +96,9 @@ impl SubscriptionParams {
             SubscriptionParams::Logs(params) => &params.commitment,
             SubscriptionParams::Program(params) => &params.commitment,
             SubscriptionParams::Signature(params) => &params.commitment,
-            SubscriptionParams::Root
+            SubscriptionParams::TokenBalances(_) => return true,
+            SubscriptionParams::ComputeUnitPrice(_)
+            | SubscriptionParams::Root
             | SubscriptionParams::Slot
             | SubscriptionParams::SlotsUpdates
             | SubscriptionParams::Vote => return false,
@@ -103,7 +113,9 @@ impl SubscriptionParams {
             SubscriptionParams::Logs(params) => &params.commitment,
             SubscriptionParams::Program(params) => &params.commitment,
             SubscriptionParams::Signature(params) => &params.commitment,
-            SubscriptionParams::Root
+            SubscriptionParams::ComputeUnitPrice(_)
+            | SubscriptionParams::TokenBalances(_)
+            | SubscriptionParams::Root
             | SubscriptionParams::Slot
             | SubscriptionParams::SlotsUpdates
             | SubscriptionParams::Vote => return false,
@@ -150,13 +162,53 @@ pub enum BlockSubscriptionKind {
 pub struct LogsSubscriptionParams {
     pub kind: LogsSubscriptionKind,
     pub commitment: CommitmentConfig,
+    pub log_contains: Option<String>,
+    pub log_regex: Option<LogLineRegex>,
+}
+
+impl LogsSubscriptionParams {
+    /// Whether a line of `log_messages` contains the substring and matches the regex of the
+    /// subscription, if it has either
+    pub fn matches_logs(&self, log_messages: &[String]) -> bool {
+        if self.log_contains.is_none() && self.log_regex.is_none() {
+            return true;
+        }
+        log_messages.iter().any(|line| {
+            self.log_contains
+                .as_ref()
+                .map_or(true, |substring| line.contains(substring.as_str()))
+                && self
+                    .log_regex
+                    .as_ref()
+                    .map_or(true, |regex| regex.0.is_match(line))
+        })
+    }
+}
+
+/// A regex compared by its pattern, so that subscriptions with the same pattern are shared
+#[derive(Debug, Clone)]
+pub struct LogLineRegex(pub Regex);
+
+impl PartialEq for LogLineRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for LogLineRegex {}
+
+impl Hash for LogLineRegex {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_str().hash(state)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum LogsSubscriptionKind {
     All,
     AllWithVotes,
-    Single(Pubkey),
+    /// Transactions mentioning any of the addresses, which are sorted and deduplicated
+    Mentions(Vec<Pubkey>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -176,6 +228,21 @@ pub struct SignatureSubscriptionParams {
     pub enable_received_notification: bool,
 }
 
+/// Notifies the token balance changes of the accounts owned by any of the owners, always at
+/// finalized commitment
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenBalanceSubscriptionParams {
+    /// Sorted and deduplicated
+    pub owners: Vec<Pubkey>,
+}
+
+/// Notifies the higher compute unit prices recommended for a transaction which did not land, if
+/// its sender requested them
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ComputeUnitPriceSubscriptionParams {
+    pub signature: Signature,
+}
+
 #[derive(Clone)]
 pub struct SubscriptionControl(Arc<SubscriptionControlInner>);
 pub struct WeakSubscriptionTokenRef(Weak<SubscriptionTokenInner>, SubscriptionId);
@@ -291,11 +358,13 @@ impl SubscriptionControl {
     pub fn logs_subscribed(&self, pubkey: Option<&Pubkey>) -> bool {
         self.0.subscriptions.iter().any(|item| {
             if let SubscriptionParams::Logs(params) = item.key() {
-                let subscribed_pubkey = match &params.kind {
-                    LogsSubscriptionKind::All | LogsSubscriptionKind::AllWithVotes => None,
-                    LogsSubscriptionKind::Single(pubkey) => Some(pubkey),
-                };
-                subscribed_pubkey == pubkey
+                match (&params.kind, pubkey) {
+                    (LogsSubscriptionKind::All | LogsSubscriptionKind::AllWithVotes, None) => true,
+                    (LogsSubscriptionKind::Mentions(pubkeys), Some(pubkey)) => {
+                        pubkeys.contains(pubkey)
+                    }
+                    _ => false,
+                }
             } else {
                 false
             }
@@ -357,29 +426,35 @@ struct LogsSubscriptionsIndex {
 
 impl LogsSubscriptionsIndex {
     fn add(&mut self, params: &LogsSubscriptionParams) {
-        match params.kind {
+        match &params.kind {
             LogsSubscriptionKind::All => self.all_count += 1,
             LogsSubscriptionKind::AllWithVotes => self.all_with_votes_count += 1,
-            LogsSubscriptionKind::Single(key) => {
-                *self.single_count.entry(key).or_default() += 1;
+            LogsSubscriptionKind::Mentions(keys) => {
+                for key in keys {
+                    *self.single_count.entry(*key).or_default() += 1;
+                }
             }
         }
         self.update_config();
     }
 
     fn remove(&mut self, params: &LogsSubscriptionParams) {
-        match params.kind {
+        match &params.kind {
             LogsSubscriptionKind::All => self.all_count -= 1,
             LogsSubscriptionKind::AllWithVotes => self.all_with_votes_count -= 1,
-            LogsSubscriptionKind::Single(key) => match self.single_count.entry(key) {
-                Entry::Occupied(mut entry) => {
-                    *entry.get_mut() -= 1;
-                    if *entry.get() == 0 {
-                        entry.remove();
+            LogsSubscriptionKind::Mentions(keys) => {
+                for key in keys {
+                    match self.single_count.entry(*key) {
+                        Entry::Occupied(mut entry) => {
+                            *entry.get_mut() -= 1;
+                            if *entry.get() == 0 {
+                                entry.remove();
+                            }
+                        }
+                        Entry::Vacant(_) => error!("missing entry in single_count"),
                     }
                 }
-                Entry::Vacant(_) => error!("missing entry in single_count"),
-            },
+            }
         }
         self.update_config();
     }
@@ -417,7 +492,8 @@ impl LogsSubscriptionsIndex {
 pub struct SubscriptionsTracker {
     logs_subscriptions_index: LogsSubscriptionsIndex,
     by_signature: HashMap<Signature, HashMap<SubscriptionId, Arc<SubscriptionInfo>>>,
-    // Accounts, logs, programs, signatures (not gossip)
+    compute_unit_price_watchers: HashMap<Signature, HashMap<SubscriptionId, Arc<SubscriptionInfo>>>,
+    // Accounts, logs, programs, signatures (not gossip), token balances
     commitment_watchers: HashMap<SubscriptionId, Arc<SubscriptionInfo>>,
     // Accounts, logs, programs, signatures (gossip)
     gossip_watchers: HashMap<SubscriptionId, Arc<SubscriptionInfo>>,
@@ -435,6 +511,7 @@ impl SubscriptionsTracker {
                 bank_forks,
             },
             by_signature: HashMap::new(),
+            compute_unit_price_watchers: HashMap::new(),
             commitment_watchers: HashMap::new(),
             gossip_watchers: HashMap::new(),
             node_progress_watchers: HashMap::new(),
@@ -464,6 +541,12 @@ impl SubscriptionsTracker {
                     .or_default()
                     .insert(id, Arc::clone(&info));
             }
+            SubscriptionParams::ComputeUnitPrice(params) => {
+                self.compute_unit_price_watchers
+                    .entry(params.signature)
+                    .or_default()
+                    .insert(id, Arc::clone(&info));
+            }
             _ => {}
         }
         if info.params.is_commitment_watcher() {
@@ -496,6 +579,20 @@ impl SubscriptionsTracker {
                     warn!("Subscriptions inconsistency (missing entry in by_signature)");
                 }
             }
+            SubscriptionParams::ComputeUnitPrice(params) => {
+                if let Entry::Occupied(mut entry) =
+                    self.compute_unit_price_watchers.entry(params.signature)
+                {
+                    if entry.get_mut().remove(&id).is_none() {
+                        warn!("Subscriptions inconsistency (missing entry in compute_unit_price_watchers)");
+                    }
+                    if entry.get_mut().is_empty() {
+                        entry.remove();
+                    }
+                } else {
+                    warn!("Subscriptions inconsistency (missing entry in compute_unit_price_watchers)");
+                }
+            }
             _ => {}
         }
         if params.is_commitment_watcher() {
@@ -521,6 +618,12 @@ impl SubscriptionsTracker {
         &self.by_signature
     }
 
+    pub fn compute_unit_price_watchers(
+        &self,
+    ) -> &HashMap<Signature, HashMap<SubscriptionId, Arc<SubscriptionInfo>>> {
+        &self.compute_unit_price_watchers
+    }
+
     pub fn commitment_watchers(&self) -> &HashMap<SubscriptionId, Arc<SubscriptionInfo>> {
         &self.commitment_watchers
     }
@@ -788,4 +891,31 @@ mod tests {
         tracker.unsubscribe(signature_params, 3.into());
         assert_eq!(counts(&tracker), (0, 0, 0, 0));
     }
+
+    #[test]
+    fn test_logs_subscription_matches_logs() {
+        let logs = vec![
+            "Program 11111111111111111111111111111111 invoke [1]".to_string(),
+            "Program log: Instruction: Swap amount_in=42".to_string(),
+        ];
+        let params = |log_contains: Option<&str>, log_regex: Option<&str>| LogsSubscriptionParams {
+            kind: LogsSubscriptionKind::All,
+            commitment: CommitmentConfig::processed(),
+            log_contains: log_contains.map(str::to_string),
+            log_regex: log_regex.map(|regex| LogLineRegex(Regex::new(regex).unwrap())),
+        };
+
+        assert!(params(None, None).matches_logs(&logs));
+        assert!(params(None, None).matches_logs(&[]));
+        assert!(params(Some("Instruction: Swap"), None).matches_logs(&logs));
+        assert!(!params(Some("Instruction: Route"), None).matches_logs(&logs));
+        assert!(params(None, Some(r"amount_in=\d+")).matches_logs(&logs));
+        assert!(!params(None, Some(r"amount_out=\d+")).matches_logs(&logs));
+        // Both filters must match the same line
+        assert!(params(Some("Swap"), Some(r"amount_in=\d+")).matches_logs(&logs));
+        assert!(!params(Some("invoke"), Some(r"amount_in=\d+")).matches_logs(&logs));
+
+        assert_eq!(params(None, Some("a+")), params(None, Some("a+")),);
+        assert_ne!(params(None, Some("a+")), params(None, Some("b+")));
+    }
 }
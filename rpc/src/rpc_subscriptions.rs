@@ -2,7 +2,9 @@
 
 use {
     crate::{
+        confirmation_latency::ConfirmationLatencyTracker,
         filter::filter_allows,
+        optimistic_confirmation_attestations::OptimisticConfirmationAttestations,
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
         parsed_token_accounts::{get_parsed_token_account, get_parsed_token_accounts},
         rpc_pubsub_service::PubSubConfig,
@@ -10,7 +12,10 @@ use {
             AccountSubscriptionParams, BlockSubscriptionKind, BlockSubscriptionParams,
             LogsSubscriptionKind, LogsSubscriptionParams, ProgramSubscriptionParams,
             SignatureSubscriptionParams, SubscriptionControl, SubscriptionId, SubscriptionInfo,
-            SubscriptionParams, SubscriptionsTracker,
+            SubscriptionParams, SubscriptionsTracker, TokenBalanceSubscriptionParams,
+        },
+        transaction_execution_details::{
+            TransactionExecutionDetails, TransactionExecutionDetailsCache,
         },
     },
     crossbeam_channel::{Receiver, RecvTimeoutError, SendError, Sender},
@@ -24,8 +29,9 @@ use {
     solana_measure::measure::Measure,
     solana_rpc_client_api::response::{
         ProcessedSignatureResult, ReceivedSignatureResult, Response as RpcResponse, RpcBlockUpdate,
-        RpcBlockUpdateError, RpcKeyedAccount, RpcLogsResponse, RpcResponseContext,
-        RpcSignatureResult, RpcVote, SlotInfo, SlotUpdate,
+        RpcBlockUpdateError, RpcComputeUnitPriceRecommendation, RpcKeyedAccount, RpcLogsResponse,
+        RpcResponseContext, RpcSignatureResult, RpcTokenBalanceChange, RpcTokenBalanceUpdate,
+        RpcVote, SlotInfo, SlotUpdate,
     },
     solana_runtime::{
         bank::{Bank, TransactionLogInfo},
@@ -40,15 +46,19 @@ use {
         timing::timestamp,
         transaction,
     },
+    solana_send_transaction_service::compute_unit_price_escalation::{
+        ComputeUnitPriceNotifier, ComputeUnitPriceRecommendation,
+    },
     solana_transaction_status::{
-        BlockEncodingOptions, ConfirmedBlock, EncodeError, VersionedConfirmedBlock,
+        BlockEncodingOptions, ConfirmedBlock, EncodeError, TransactionTokenBalance,
+        VersionedConfirmedBlock, VersionedTransactionWithStatusMeta,
     },
     solana_vote::vote_transaction::VoteTransaction,
     std::{
         cell::RefCell,
-        collections::{HashMap, VecDeque},
+        collections::{BTreeMap, HashMap, VecDeque},
         io::Cursor,
-        str,
+        str::{self, FromStr},
         sync::{
             atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
             Arc, Mutex, RwLock, Weak,
@@ -65,16 +75,21 @@ fn get_transaction_logs(
     bank: &Bank,
     params: &LogsSubscriptionParams,
 ) -> Option<Vec<TransactionLogInfo>> {
-    let pubkey = match &params.kind {
-        LogsSubscriptionKind::All | LogsSubscriptionKind::AllWithVotes => None,
-        LogsSubscriptionKind::Single(pubkey) => Some(pubkey),
+    let mut logs = match &params.kind {
+        LogsSubscriptionKind::All | LogsSubscriptionKind::AllWithVotes => {
+            bank.get_transaction_logs(None)
+        }
+        LogsSubscriptionKind::Mentions(pubkeys) => match pubkeys.as_slice() {
+            [pubkey] => bank.get_transaction_logs(Some(pubkey)),
+            pubkeys => bank.get_transaction_logs_for_addresses(pubkeys),
+        },
     };
-    let mut logs = bank.get_transaction_logs(pubkey);
-    if matches!(params.kind, LogsSubscriptionKind::All) {
+    if let Some(logs) = &mut logs {
         // Filter out votes if the subscriber doesn't want them
-        if let Some(logs) = &mut logs {
-            logs.retain(|log| !log.is_vote);
-        }
+        let exclude_votes = matches!(params.kind, LogsSubscriptionKind::All);
+        logs.retain(|log| {
+            !(exclude_votes && log.is_vote) && params.matches_logs(&log.log_messages)
+        });
     }
     logs
 }
@@ -101,6 +116,7 @@ pub enum NotificationEntry {
     Bank(CommitmentSlots),
     Gossip(Slot),
     SignaturesReceived((Slot, Vec<Signature>)),
+    ComputeUnitPriceRecommendation(ComputeUnitPriceRecommendation),
     Subscribed(SubscriptionParams, SubscriptionId),
     Unsubscribed(SubscriptionParams, SubscriptionId),
 }
@@ -121,6 +137,9 @@ impl std::fmt::Debug for NotificationEntry {
                 write!(f, "SignaturesReceived({slot_signatures:?})")
             }
             NotificationEntry::Gossip(slot) => write!(f, "Gossip({slot:?})"),
+            NotificationEntry::ComputeUnitPriceRecommendation(recommendation) => {
+                write!(f, "ComputeUnitPriceRecommendation({recommendation:?})")
+            }
             NotificationEntry::Subscribed(params, id) => {
                 write!(f, "Subscribed({params:?}, {id:?})")
             }
@@ -369,6 +388,131 @@ fn filter_block_result_txs(
     }))
 }
 
+/// Returns the token balance changes of the accounts owned by the subscribed owners, for every
+/// transaction of `block` that changed at least one of them
+fn filter_token_balance_results(
+    block: VersionedConfirmedBlock,
+    slot: Slot,
+    params: &TokenBalanceSubscriptionParams,
+) -> Vec<RpcTokenBalanceUpdate> {
+    let block_time = block.block_time;
+    block
+        .transactions
+        .into_iter()
+        .filter_map(|tx| {
+            let changes = token_balance_changes(&tx, &params.owners);
+            (!changes.is_empty()).then(|| RpcTokenBalanceUpdate {
+                signature: tx.transaction.signatures[0].to_string(),
+                slot,
+                block_time,
+                err: tx.meta.status.err(),
+                changes,
+            })
+        })
+        .collect()
+}
+
+fn token_balance_changes(
+    tx: &VersionedTransactionWithStatusMeta,
+    owners: &[Pubkey],
+) -> Vec<RpcTokenBalanceChange> {
+    // Pre and post balances of each token account, by account index
+    let mut balances: BTreeMap<
+        u8,
+        (
+            Option<&TransactionTokenBalance>,
+            Option<&TransactionTokenBalance>,
+        ),
+    > = BTreeMap::new();
+    for balance in tx.meta.pre_token_balances.iter().flatten() {
+        balances.entry(balance.account_index).or_default().0 = Some(balance);
+    }
+    for balance in tx.meta.post_token_balances.iter().flatten() {
+        balances.entry(balance.account_index).or_default().1 = Some(balance);
+    }
+
+    let is_subscribed_owner = |balance: &TransactionTokenBalance| {
+        Pubkey::from_str(&balance.owner).is_ok_and(|owner| owners.binary_search(&owner).is_ok())
+    };
+    let amount = |balance: Option<&TransactionTokenBalance>| {
+        balance.map_or_else(
+            || "0".to_string(),
+            |balance| balance.ui_token_amount.amount.clone(),
+        )
+    };
+    let account_keys = tx.account_keys();
+    balances
+        .into_iter()
+        .filter_map(|(account_index, (pre, post))| {
+            let balance = post.or(pre)?;
+            if !pre.into_iter().chain(post).any(is_subscribed_owner) {
+                return None;
+            }
+            let (pre_amount, post_amount) = (amount(pre), amount(post));
+            if pre_amount == post_amount {
+                return None;
+            }
+            Some(RpcTokenBalanceChange {
+                account: account_keys.get(usize::from(account_index))?.to_string(),
+                owner: balance.owner.clone(),
+                mint: balance.mint.clone(),
+                program_id: (!balance.program_id.is_empty()).then(|| balance.program_id.clone()),
+                decimals: balance.ui_token_amount.decimals,
+                pre_amount,
+                post_amount,
+            })
+        })
+        .collect()
+}
+
+/// Notifies the token balance changes of every rooted slot since the last one notified up to
+/// `slot`, returning the number of notifications
+fn notify_token_balances(
+    params: &TokenBalanceSubscriptionParams,
+    subscription: &SubscriptionInfo,
+    blockstore: &Blockstore,
+    slot: Slot,
+    max_complete_transaction_status_slot: &AtomicU64,
+    notifier: &RpcNotifier,
+) -> usize {
+    let mut w_last_unnotified_slot = subscription.last_notified_slot.write().unwrap();
+    // would mean it's the first notification for this subscription connection
+    if *w_last_unnotified_slot == 0 {
+        *w_last_unnotified_slot = slot;
+    }
+    let mut num_notified = 0;
+    for s in *w_last_unnotified_slot..=slot {
+        // Stop at the first slot whose transaction statuses are not all written yet, to resume
+        // from it on the next notification
+        if s > max_complete_transaction_status_slot.load(Ordering::SeqCst) {
+            break;
+        }
+        if blockstore.is_root(s) {
+            match blockstore.get_complete_block(s, false) {
+                Ok(block) => {
+                    for update in filter_token_balance_results(block, s, params) {
+                        notifier.notify(
+                            RpcResponse::from(RpcNotificationResponse {
+                                context: RpcNotificationContext { slot: s },
+                                value: update,
+                            }),
+                            subscription,
+                            false,
+                        );
+                        num_notified += 1;
+                    }
+                }
+                Err(err) => {
+                    error!("get_complete_block error: {}", err);
+                    break;
+                }
+            }
+        }
+        *w_last_unnotified_slot = s + 1;
+    }
+    num_notified
+}
+
 fn filter_account_result(
     result: Option<(AccountSharedData, Slot)>,
     params: &AccountSubscriptionParams,
@@ -394,14 +538,19 @@ fn filter_account_result(
 }
 
 fn filter_signature_result(
-    result: Option<transaction::Result<()>>,
+    result: Option<(transaction::Result<()>, Option<TransactionExecutionDetails>)>,
     _params: &SignatureSubscriptionParams,
     last_notified_slot: Slot,
     _bank: Arc<Bank>,
 ) -> (Option<RpcSignatureResult>, Slot) {
     (
-        result.map(|result| {
-            RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult { err: result.err() })
+        result.map(|(result, details)| {
+            RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult {
+                err: result.err(),
+                fee: details.map(|details| details.fee_breakdown.total_fee()),
+                fee_breakdown: details.map(|details| details.fee_breakdown),
+                compute_units_consumed: details.map(|details| details.compute_units_consumed),
+            })
         }),
         last_notified_slot,
     )
@@ -517,6 +666,20 @@ pub struct RpcSubscriptions {
 
     exit: Arc<AtomicBool>,
     control: SubscriptionControl,
+    confirmation_latency: Arc<ConfirmationLatencyTracker>,
+    optimistic_confirmation_attestations: Arc<OptimisticConfirmationAttestations>,
+    transaction_execution_details: Arc<TransactionExecutionDetailsCache>,
+}
+
+impl ComputeUnitPriceNotifier for RpcSubscriptions {
+    fn notify_compute_unit_price_recommendation(
+        &self,
+        recommendation: ComputeUnitPriceRecommendation,
+    ) {
+        self.enqueue_notification(NotificationEntry::ComputeUnitPriceRecommendation(
+            recommendation,
+        ));
+    }
 }
 
 impl Drop for RpcSubscriptions {
@@ -547,6 +710,7 @@ impl RpcSubscriptions {
             optimistically_confirmed_bank,
             &PubSubConfig::default(),
             None,
+            Arc::default(),
         )
     }
 
@@ -594,6 +758,7 @@ impl RpcSubscriptions {
             optimistically_confirmed_bank,
             &PubSubConfig::default_for_tests(),
             Some(rpc_notifier_ready.clone()),
+            Arc::default(),
         );
 
         // Ensure RPC notifier is ready to receive notifications before proceeding
@@ -619,6 +784,7 @@ impl RpcSubscriptions {
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
         config: &PubSubConfig,
         rpc_notifier_ready: Option<Arc<AtomicBool>>,
+        transaction_execution_details: Arc<TransactionExecutionDetailsCache>,
     ) -> Self {
         let (notification_sender, notification_receiver) = crossbeam_channel::unbounded();
 
@@ -636,6 +802,7 @@ impl RpcSubscriptions {
 
         let t_cleanup = config.notification_threads.map(|notification_threads| {
             let exit = exit.clone();
+            let transaction_execution_details = transaction_execution_details.clone();
             Builder::new()
                 .name("solRpcNotifier".to_string())
                 .spawn(move || {
@@ -659,6 +826,7 @@ impl RpcSubscriptions {
                             bank_forks,
                             block_commitment_cache,
                             optimistically_confirmed_bank,
+                            transaction_execution_details,
                         )
                     });
                 })
@@ -676,6 +844,9 @@ impl RpcSubscriptions {
             t_cleanup,
             exit,
             control,
+            confirmation_latency: Arc::default(),
+            optimistic_confirmation_attestations: Arc::default(),
+            transaction_execution_details,
         }
     }
 
@@ -705,6 +876,21 @@ impl RpcSubscriptions {
         &self.control
     }
 
+    /// Latencies to confirm and root slots, from the slot updates seen by the subscriptions
+    pub fn confirmation_latency(&self) -> &Arc<ConfirmationLatencyTracker> {
+        &self.confirmation_latency
+    }
+
+    /// Signed gossip votes attesting to the bank hashes of recent slots
+    pub fn optimistic_confirmation_attestations(&self) -> &Arc<OptimisticConfirmationAttestations> {
+        &self.optimistic_confirmation_attestations
+    }
+
+    /// Fee and compute units of recently executed transactions, for the signature notifications
+    pub fn transaction_execution_details(&self) -> &Arc<TransactionExecutionDetailsCache> {
+        &self.transaction_execution_details
+    }
+
     /// Notify subscribers of changes to any accounts or new signatures since
     /// the bank's last checkpoint.
     pub fn notify_subscribers(&self, commitment_slots: CommitmentSlots) {
@@ -718,6 +904,15 @@ impl RpcSubscriptions {
     }
 
     pub fn notify_slot_update(&self, slot_update: SlotUpdate) {
+        match slot_update {
+            SlotUpdate::FirstShredReceived { slot, timestamp } => self
+                .confirmation_latency
+                .record_first_shred_received(slot, timestamp),
+            SlotUpdate::OptimisticConfirmation { slot, timestamp } => self
+                .confirmation_latency
+                .record_optimistic_confirmation(slot, timestamp),
+            _ => {}
+        }
         self.enqueue_notification(NotificationEntry::SlotUpdate(slot_update));
     }
 
@@ -741,9 +936,12 @@ impl RpcSubscriptions {
     pub fn notify_roots(&self, mut rooted_slots: Vec<Slot>) {
         rooted_slots.sort_unstable();
         rooted_slots.into_iter().for_each(|root| {
+            let now = timestamp();
+            self.confirmation_latency.record_root(root, now);
+            self.optimistic_confirmation_attestations.record_root(root);
             self.enqueue_notification(NotificationEntry::SlotUpdate(SlotUpdate::Root {
                 slot: root,
-                timestamp: timestamp(),
+                timestamp: now,
             }));
             self.enqueue_notification(NotificationEntry::Root(root));
         });
@@ -775,6 +973,7 @@ impl RpcSubscriptions {
         bank_forks: Arc<RwLock<BankForks>>,
         block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
+        transaction_execution_details: Arc<TransactionExecutionDetailsCache>,
     ) {
         let mut stats = PubsubNotificationStats::default();
 
@@ -857,6 +1056,7 @@ impl RpcSubscriptions {
                                 subscriptions.commitment_watchers(),
                                 &bank_forks,
                                 &blockstore,
+                                &transaction_execution_details,
                                 &commitment_slots,
                                 &notifier,
                                 SOURCE,
@@ -874,6 +1074,7 @@ impl RpcSubscriptions {
                                 subscriptions.gossip_watchers(),
                                 &bank_forks,
                                 &blockstore,
+                                &transaction_execution_details,
                                 &commitment_slots,
                                 &notifier,
                                 SOURCE,
@@ -906,6 +1107,32 @@ impl RpcSubscriptions {
                                 }
                             }
                         }
+                        NotificationEntry::ComputeUnitPriceRecommendation(recommendation) => {
+                            if let Some(subs) = subscriptions
+                                .compute_unit_price_watchers()
+                                .get(&recommendation.signature)
+                            {
+                                let value = RpcComputeUnitPriceRecommendation {
+                                    signature: recommendation.signature.to_string(),
+                                    leader_rotations: recommendation.leader_rotations,
+                                    compute_unit_price: recommendation.compute_unit_price,
+                                    recommended_compute_unit_price: recommendation
+                                        .recommended_compute_unit_price,
+                                };
+                                for subscription in subs.values() {
+                                    notifier.notify(
+                                        RpcResponse::from(RpcNotificationResponse {
+                                            context: RpcNotificationContext {
+                                                slot: recommendation.slot,
+                                            },
+                                            value: value.clone(),
+                                        }),
+                                        subscription,
+                                        false,
+                                    );
+                                }
+                            }
+                        }
                     }
                     stats.notification_entry_processing_time_us +=
                         queued_at.elapsed().as_micros() as u64;
@@ -929,6 +1156,7 @@ impl RpcSubscriptions {
         subscriptions: &HashMap<SubscriptionId, Arc<SubscriptionInfo>>,
         bank_forks: &Arc<RwLock<BankForks>>,
         blockstore: &Blockstore,
+        transaction_execution_details: &TransactionExecutionDetailsCache,
         commitment_slots: &CommitmentSlots,
         notifier: &RpcNotifier,
         source: &'static str,
@@ -950,6 +1178,9 @@ impl RpcSubscriptions {
         let num_signatures_found = AtomicUsize::new(0);
         let num_signatures_notified = AtomicUsize::new(0);
 
+        let num_token_balances_found = AtomicUsize::new(0);
+        let num_token_balances_notified = AtomicUsize::new(0);
+
         let subscriptions = subscriptions.into_par_iter();
         subscriptions.for_each(|(_id, subscription)| {
             let slot = if let Some(commitment) = subscription.commitment() {
@@ -1121,6 +1352,13 @@ impl RpcSubscriptions {
                             slot,
                             |bank, params| {
                                 bank.get_signature_status_processed_since_parent(&params.signature)
+                                    .map(|result| {
+                                        (
+                                            result,
+                                            transaction_execution_details
+                                                .get(bank.slot(), &params.signature),
+                                        )
+                                    })
                             },
                             filter_signature_result,
                             notifier,
@@ -1132,6 +1370,20 @@ impl RpcSubscriptions {
                         }
                     }
                 }
+                SubscriptionParams::TokenBalances(params) => {
+                    num_token_balances_found.fetch_add(1, Ordering::Relaxed);
+                    if let Some(slot) = slot {
+                        let notified = notify_token_balances(
+                            params,
+                            subscription,
+                            blockstore,
+                            slot,
+                            &max_complete_transaction_status_slot,
+                            notifier,
+                        );
+                        num_token_balances_notified.fetch_add(notified, Ordering::Relaxed);
+                    }
+                }
                 _ => error!("wrong subscription type in alps map"),
             }
         });
@@ -1141,7 +1393,8 @@ impl RpcSubscriptions {
         let total_notified = num_accounts_notified.load(Ordering::Relaxed)
             + num_logs_notified.load(Ordering::Relaxed)
             + num_programs_notified.load(Ordering::Relaxed)
-            + num_signatures_notified.load(Ordering::Relaxed);
+            + num_signatures_notified.load(Ordering::Relaxed)
+            + num_token_balances_notified.load(Ordering::Relaxed);
         let total_ms = total_time.as_ms();
         if total_notified > 0 || total_ms > 10 {
             debug!(
@@ -1199,6 +1452,16 @@ impl RpcSubscriptions {
                     num_signatures_notified.load(Ordering::Relaxed),
                     i64
                 ),
+                (
+                    "num_token_balance_subscriptions",
+                    num_token_balances_found.load(Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "num_token_balances_notified",
+                    num_token_balances_notified.load(Ordering::Relaxed),
+                    i64
+                ),
                 ("notifications_time", total_time.as_us() as i64, i64),
             );
         }
@@ -1236,6 +1499,7 @@ pub(crate) mod tests {
             rpc_pubsub_service,
         },
         serial_test::serial,
+        solana_account_decoder::parse_token::UiTokenAmount,
         solana_ledger::get_tmp_ledger_path_auto_delete,
         solana_rpc_client_api::config::{
             RpcAccountInfoConfig, RpcBlockSubscribeConfig, RpcBlockSubscribeFilter,
@@ -1249,12 +1513,16 @@ pub(crate) mod tests {
         },
         solana_sdk::{
             commitment_config::CommitmentConfig,
+            hash::Hash,
+            instruction::{AccountMeta, Instruction},
             message::Message,
             signature::{Keypair, Signer},
             stake, system_instruction, system_program, system_transaction,
             transaction::Transaction,
         },
-        solana_transaction_status::{TransactionDetails, UiTransactionEncoding},
+        solana_transaction_status::{
+            FeeBreakdown, TransactionDetails, TransactionStatusMeta, UiTransactionEncoding,
+        },
         std::{
             collections::HashSet,
             sync::atomic::{AtomicU64, Ordering::Relaxed},
@@ -2548,8 +2816,12 @@ pub(crate) mod tests {
         subscriptions
             .notify_signatures_received((received_slot, vec![unprocessed_tx.signatures[0]]));
         subscriptions.notify_subscribers(commitment_slots);
-        let expected_res =
-            RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult { err: None });
+        let expected_res = RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult {
+            err: None,
+            fee: None,
+            fee_breakdown: None,
+            compute_units_consumed: None,
+        });
         let received_expected_res =
             RpcSignatureResult::ReceivedSignature(ReceivedSignatureResult::ReceivedSignature);
         struct Notification {
@@ -2633,6 +2905,91 @@ pub(crate) mod tests {
             .signature_subscribed(&unprocessed_tx.signatures[0]));
     }
 
+    #[test]
+    #[serial]
+    fn test_check_signature_subscribe_execution_details() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(100);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let blockhash = bank.last_blockhash();
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let tx =
+            system_transaction::transfer(&mint_keypair, &solana_pubkey::new_rand(), 1, blockhash);
+
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(AtomicU64::default()),
+            Arc::new(AtomicU64::default()),
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests())),
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks),
+        ));
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&subscriptions);
+        let sub_id = rpc
+            .signature_subscribe(
+                tx.signatures[0].to_string(),
+                Some(RpcSignatureSubscribeConfig {
+                    commitment: Some(CommitmentConfig::processed()),
+                    enable_received_notification: Some(false),
+                }),
+            )
+            .unwrap();
+
+        bank_forks
+            .read()
+            .unwrap()
+            .get(0)
+            .unwrap()
+            .process_transaction(&tx)
+            .unwrap();
+        // Recorded by the transaction status service as the transaction executes
+        subscriptions.transaction_execution_details().insert(
+            0,
+            [(
+                tx.signatures[0],
+                TransactionExecutionDetails {
+                    fee_breakdown: FeeBreakdown {
+                        base: 5_000,
+                        prioritization: 300,
+                        ..FeeBreakdown::default()
+                    },
+                    compute_units_consumed: 150,
+                },
+            )],
+        );
+        subscriptions.notify_subscribers(CommitmentSlots::default());
+
+        let expected = json!({
+            "jsonrpc": "2.0",
+            "method": "signatureNotification",
+            "params": {
+                "result": {
+                    "context": { "slot": 0 },
+                    "value": {
+                        "err": null,
+                        "fee": 5_300,
+                        "feeBreakdown": {
+                            "base": 5_000,
+                            "prioritization": 300,
+                            "memory": 0,
+                            "size": 0,
+                            "congestion": 0,
+                        },
+                        "computeUnitsConsumed": 150,
+                    },
+                },
+                "subscription": u64::from(sub_id),
+            }
+        });
+        assert_eq!(
+            expected,
+            serde_json::from_str::<serde_json::Value>(&receiver.recv()).unwrap(),
+        );
+    }
+
     #[test]
     #[serial]
     fn test_check_slot_subscribe() {
@@ -2969,6 +3326,7 @@ pub(crate) mod tests {
 
         let sub_config = RpcTransactionLogsConfig {
             commitment: Some(CommitmentConfig::processed()),
+            ..RpcTransactionLogsConfig::default()
         };
 
         let (rpc_all, mut receiver_all) = rpc_pubsub_service::test_connection(&subscriptions);
@@ -3027,6 +3385,214 @@ pub(crate) mod tests {
         assert!(!subscriptions.control.logs_subscribed(Some(&alice.pubkey())));
     }
 
+    #[test]
+    #[serial]
+    fn test_logs_subscribe_mentions_and_log_filters() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(100);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let blockhash = bank.last_blockhash();
+        let bank_forks = BankForks::new_rw_arc(bank);
+
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+
+        let exit = Arc::new(AtomicBool::new(false));
+        let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
+        let max_complete_rewards_slot = Arc::new(AtomicU64::default());
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            exit,
+            max_complete_transaction_status_slot,
+            max_complete_rewards_slot,
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests())),
+            optimistically_confirmed_bank,
+        ));
+
+        let (rpc_mentions, mut receiver_mentions) =
+            rpc_pubsub_service::test_connection(&subscriptions);
+        let sub_id_for_mentions = rpc_mentions
+            .logs_subscribe(
+                RpcTransactionLogsFilter::Mentions(vec![
+                    bob.pubkey().to_string(),
+                    alice.pubkey().to_string(),
+                ]),
+                Some(RpcTransactionLogsConfig {
+                    commitment: Some(CommitmentConfig::processed()),
+                    log_contains: Some("success".to_string()),
+                    log_regex: None,
+                }),
+            )
+            .unwrap();
+        assert!(subscriptions.control.logs_subscribed(Some(&alice.pubkey())));
+        assert!(subscriptions.control.logs_subscribed(Some(&bob.pubkey())));
+
+        let (rpc_filtered, mut receiver_filtered) =
+            rpc_pubsub_service::test_connection(&subscriptions);
+        let sub_id_for_filtered = rpc_filtered
+            .logs_subscribe(
+                RpcTransactionLogsFilter::All,
+                Some(RpcTransactionLogsConfig {
+                    commitment: Some(CommitmentConfig::processed()),
+                    log_contains: None,
+                    log_regex: Some("^Program log: ".to_string()),
+                }),
+            )
+            .unwrap();
+        assert!(rpc_filtered
+            .logs_subscribe(
+                RpcTransactionLogsFilter::All,
+                Some(RpcTransactionLogsConfig {
+                    log_regex: Some("(".to_string()),
+                    ..RpcTransactionLogsConfig::default()
+                }),
+            )
+            .is_err());
+        assert!(rpc_filtered
+            .logs_subscribe(RpcTransactionLogsFilter::Mentions(vec![]), None)
+            .is_err());
+        rpc_mentions.block_until_processed(&subscriptions);
+
+        let tx = system_transaction::create_account(
+            &mint_keypair,
+            &alice,
+            blockhash,
+            1,
+            0,
+            &system_program::id(),
+        );
+        assert!(bank_forks
+            .read()
+            .unwrap()
+            .get(0)
+            .unwrap()
+            .process_transaction_with_metadata(tx.clone())
+            .is_ok());
+
+        subscriptions.notify_subscribers(CommitmentSlots::new_from_slot(0));
+
+        let expected_response = make_logs_result(
+            &tx.signatures[0].to_string(),
+            u64::from(sub_id_for_mentions),
+        );
+        let response = receiver_mentions.recv();
+        assert_eq!(
+            expected_response,
+            serde_json::from_str::<serde_json::Value>(&response).unwrap(),
+        );
+        // The system program doesn't log any line matching the regex
+        assert!(receiver_filtered
+            .recv_timeout(Duration::from_millis(500))
+            .is_err());
+
+        rpc_mentions.logs_unsubscribe(sub_id_for_mentions).unwrap();
+        assert!(!subscriptions.control.logs_subscribed(Some(&alice.pubkey())));
+        assert!(!subscriptions.control.logs_subscribed(Some(&bob.pubkey())));
+        rpc_filtered.logs_unsubscribe(sub_id_for_filtered).unwrap();
+    }
+
+    #[test]
+    fn test_filter_token_balance_results() {
+        let owner = Pubkey::new_unique();
+        let other_owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let token_program = Pubkey::new_unique();
+        let payer = Keypair::new();
+        let token_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                token_program,
+                &[],
+                token_accounts
+                    .iter()
+                    .map(|account| AccountMeta::new(*account, false))
+                    .collect(),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            Hash::default(),
+        );
+        let token_balance = |account_index, owner: &Pubkey, amount: u64| TransactionTokenBalance {
+            account_index,
+            mint: mint.to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: Some(amount as f64 / 100.0),
+                decimals: 2,
+                amount: amount.to_string(),
+                ui_amount_string: (amount as f64 / 100.0).to_string(),
+            },
+            owner: owner.to_string(),
+            program_id: token_program.to_string(),
+        };
+        let block = |pre_token_balances, post_token_balances| VersionedConfirmedBlock {
+            previous_blockhash: Hash::default().to_string(),
+            blockhash: Hash::default().to_string(),
+            parent_slot: 0,
+            transactions: vec![VersionedTransactionWithStatusMeta {
+                transaction: transaction.clone().into(),
+                meta: TransactionStatusMeta {
+                    pre_token_balances: Some(pre_token_balances),
+                    post_token_balances: Some(post_token_balances),
+                    ..TransactionStatusMeta::default()
+                },
+            }],
+            rewards: vec![],
+            num_partitions: None,
+            block_time: Some(42),
+            block_height: None,
+        };
+        let params = TokenBalanceSubscriptionParams {
+            owners: vec![owner],
+        };
+
+        // A deposit to a new account of the owner, and a withdrawal of another owner
+        let updates = filter_token_balance_results(
+            block(
+                vec![token_balance(2, &other_owner, 500)],
+                vec![
+                    token_balance(1, &owner, 300),
+                    token_balance(2, &other_owner, 200),
+                ],
+            ),
+            7,
+            &params,
+        );
+        assert_eq!(
+            updates,
+            vec![RpcTokenBalanceUpdate {
+                signature: transaction.signatures[0].to_string(),
+                slot: 7,
+                block_time: Some(42),
+                err: None,
+                changes: vec![RpcTokenBalanceChange {
+                    account: token_accounts[0].to_string(),
+                    owner: owner.to_string(),
+                    mint: mint.to_string(),
+                    program_id: Some(token_program.to_string()),
+                    decimals: 2,
+                    pre_amount: "0".to_string(),
+                    post_amount: "300".to_string(),
+                }],
+            }]
+        );
+
+        // Transactions leaving the balances of the owner unchanged are not notified
+        let updates = filter_token_balance_results(
+            block(
+                vec![token_balance(1, &owner, 300)],
+                vec![token_balance(1, &owner, 300)],
+            ),
+            8,
+            &params,
+        );
+        assert!(updates.is_empty());
+    }
+
     #[test]
     fn test_total_subscriptions() {
         let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(100);
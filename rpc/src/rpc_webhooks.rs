@@ -0,0 +1,909 @@
+//! Webhook push notifications for address activity.
+//!
+//! Clients register a webhook, a URL along with a set of addresses and a commitment, with the
+//! `registerWebhook` RPC method. Once a block reaches the commitment of a webhook, the node POSTs
+//! an [`RpcWebhookNotification`] to its URL for every transaction of the block that references one
+//! of the addresses. This lets small services follow a handful of addresses without keeping a
+//! websocket subscription open.
+//!
+//! Every notification carries the id of its webhook in the [`WEBHOOK_ID_HEADER`] header and the
+//! hex encoded HMAC-SHA256 of its body, keyed with the secret returned at registration, in the
+//! [`SIGNATURE_HEADER`] header. Failed deliveries are retried with exponential backoff.
+//!
+//! Webhooks are grouped by client, the host and port of their URL. Clients are limited in the
+//! number of webhooks they register and in the number of notifications they are sent per minute,
+//! notifications beyond that quota are dropped. Webhooks are only kept in memory, clients have to
+//! register them again when the node restarts.
+//!
+//! Any RPC client can register a webhook, so webhooks cannot target the network of the node:
+//! URLs whose host resolves to a loopback, private, link-local, unspecified or otherwise
+//! non-routable address are rejected, both at registration and at every delivery, and redirects
+//! are not followed. Operators can restrict webhooks to a list of hosts, which are trusted and not
+//! checked.
+
+use {
+    hmac::{Hmac, Mac},
+    reqwest::{
+        dns::{Addrs, Name, Resolve, Resolving},
+        header::CONTENT_TYPE,
+        redirect, Url,
+    },
+    sha2::Sha256,
+    solana_ledger::blockstore::Blockstore,
+    solana_rpc_client_api::response::{RpcWebhookNotification, RpcWebhookRegistration},
+    solana_runtime::commitment::BlockCommitmentCache,
+    solana_sdk::{clock::Slot, commitment_config::CommitmentLevel, pubkey::Pubkey},
+    solana_transaction_status::{VersionedConfirmedBlock, VersionedTransactionWithStatusMeta},
+    std::{
+        collections::{HashMap, HashSet},
+        net::{IpAddr, SocketAddr, ToSocketAddrs},
+        sync::{
+            atomic::{AtomicBool, AtomicU64, Ordering},
+            Arc, Mutex, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+    thiserror::Error,
+    tokio::runtime::Runtime,
+};
+
+pub const SIGNATURE_HEADER: &str = "X-Tachyon-Signature";
+pub const WEBHOOK_ID_HEADER: &str = "X-Tachyon-Webhook-Id";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(400);
+const METRICS_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+const QUOTA_WINDOW: Duration = Duration::from_secs(60);
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfig {
+    /// Maximum number of webhooks registered on the node
+    pub max_webhooks: usize,
+    /// Maximum number of webhooks registered by a single client
+    pub max_webhooks_per_client: usize,
+    pub max_addresses_per_webhook: usize,
+    /// Maximum number of notifications sent to a single client per minute
+    pub max_notifications_per_client_per_minute: u64,
+    /// Number of times a notification is sent before it is given up on
+    pub max_delivery_attempts: u32,
+    pub delivery_timeout: Duration,
+    /// Hosts webhooks may be registered for, any host with a public address if None. The
+    /// addresses of these hosts are not checked.
+    pub allowed_hosts: Option<HashSet<String>>,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            max_webhooks: 1_000,
+            max_webhooks_per_client: 10,
+            max_addresses_per_webhook: 1_000,
+            max_notifications_per_client_per_minute: 600,
+            max_delivery_attempts: 5,
+            delivery_timeout: Duration::from_secs(10),
+            allowed_hosts: None,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WebhookError {
+    #[error("invalid webhook url {0}, expected an http or https url with a host")]
+    InvalidUrl(String),
+
+    #[error("webhook host {0} is not allowed")]
+    HostNotAllowed(String),
+
+    #[error("unable to resolve webhook host {0}")]
+    UnresolvableHost(String),
+
+    #[error("webhook host {0} resolves to {1}, which is not a public address")]
+    ForbiddenAddress(String, IpAddr),
+
+    #[error("invalid commitment, expected confirmed or finalized")]
+    InvalidCommitment,
+
+    #[error("a webhook must watch between 1 and {0} addresses")]
+    InvalidAddressCount(usize),
+
+    #[error("client {0} has already registered {1} webhooks")]
+    ClientLimitReached(String, usize),
+
+    #[error("the node has already registered {0} webhooks")]
+    LimitReached(usize),
+}
+
+/// Returns the hex encoded HMAC-SHA256 of a notification `body`, keyed with the `secret` of its
+/// webhook, as sent in the [`SIGNATURE_HEADER`] header.
+pub fn sign_notification(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Returns whether `ip` is not a public address: loopback, private, shared, link-local,
+/// unspecified, broadcast, multicast or documentation addresses, and their IPv4-mapped IPv6 forms
+fn is_forbidden_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // "this network", 0.0.0.0/8
+                || a == 0
+                // shared address space, 100.64.0.0/10
+                || (a == 100 && b & 0xc0 == 64)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_forbidden_address(IpAddr::V4(ip)),
+            None => {
+                let segment = ip.segments()[0];
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    // unique local, fc00::/7
+                    || segment & 0xfe00 == 0xfc00
+                    // link-local, fe80::/10
+                    || segment & 0xffc0 == 0xfe80
+            }
+        },
+    }
+}
+
+/// Resolves the hosts of webhooks for delivery, dropping the addresses webhooks may not be sent
+/// to, so that a host resolving to a public address at registration cannot later be pointed at
+/// the network of the node
+struct WebhookResolver {
+    allowed_hosts: Option<HashSet<String>>,
+}
+
+impl Resolve for WebhookResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let trusted = self
+            .allowed_hosts
+            .as_ref()
+            .is_some_and(|allowed_hosts| allowed_hosts.contains(name.as_str()));
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+                .await?
+                .filter(|addr| trusted || !is_forbidden_address(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(WebhookError::UnresolvableHost(host.to_string()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+struct Webhook {
+    id: u64,
+    url: Url,
+    client: String,
+    addresses: HashSet<Pubkey>,
+    commitment: CommitmentLevel,
+    secret: [u8; 32],
+}
+
+struct ClientQuota {
+    window_start: Instant,
+    notifications: u64,
+}
+
+#[derive(Default)]
+struct WebhookStats {
+    delivered: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl WebhookStats {
+    fn report(&self, num_webhooks: usize) {
+        datapoint_info!(
+            "rpc-webhooks",
+            ("webhooks", num_webhooks, i64),
+            ("delivered", self.delivered.swap(0, Ordering::Relaxed), i64),
+            ("failed", self.failed.swap(0, Ordering::Relaxed), i64),
+            ("dropped", self.dropped.swap(0, Ordering::Relaxed), i64),
+        );
+    }
+}
+
+/// The webhooks registered on the node, along with the notification quotas of their clients.
+pub struct WebhookRegistry {
+    config: WebhookConfig,
+    webhooks: RwLock<HashMap<u64, Arc<Webhook>>>,
+    quotas: Mutex<HashMap<String, ClientQuota>>,
+    stats: WebhookStats,
+}
+
+impl WebhookRegistry {
+    pub fn new(config: WebhookConfig) -> Self {
+        Self {
+            config,
+            webhooks: RwLock::default(),
+            quotas: Mutex::default(),
+            stats: WebhookStats::default(),
+        }
+    }
+
+    pub fn config(&self) -> &WebhookConfig {
+        &self.config
+    }
+
+    pub fn register(
+        &self,
+        url: &str,
+        addresses: Vec<Pubkey>,
+        commitment: CommitmentLevel,
+    ) -> Result<RpcWebhookRegistration, WebhookError> {
+        let invalid_url = || WebhookError::InvalidUrl(url.to_string());
+        let parsed_url = Url::parse(url).map_err(|_| invalid_url())?;
+        if !matches!(parsed_url.scheme(), "http" | "https") {
+            return Err(invalid_url());
+        }
+        let host = parsed_url.host_str().ok_or_else(invalid_url)?;
+        let port = parsed_url.port_or_known_default().ok_or_else(invalid_url)?;
+        let client = format!("{host}:{port}");
+        match &self.config.allowed_hosts {
+            Some(allowed_hosts) if allowed_hosts.contains(host) => (),
+            Some(_) => return Err(WebhookError::HostNotAllowed(host.to_string())),
+            None => Self::check_host_addresses(&parsed_url, port)?,
+        }
+        if !matches!(
+            commitment,
+            CommitmentLevel::Confirmed | CommitmentLevel::Finalized
+        ) {
+            return Err(WebhookError::InvalidCommitment);
+        }
+        let addresses: HashSet<_> = addresses.into_iter().collect();
+        if addresses.is_empty() || addresses.len() > self.config.max_addresses_per_webhook {
+            return Err(WebhookError::InvalidAddressCount(
+                self.config.max_addresses_per_webhook,
+            ));
+        }
+
+        let mut webhooks = self.webhooks.write().unwrap();
+        if webhooks.len() >= self.config.max_webhooks {
+            return Err(WebhookError::LimitReached(self.config.max_webhooks));
+        }
+        let client_webhooks = webhooks
+            .values()
+            .filter(|webhook| webhook.client == client)
+            .count();
+        if client_webhooks >= self.config.max_webhooks_per_client {
+            return Err(WebhookError::ClientLimitReached(
+                client,
+                self.config.max_webhooks_per_client,
+            ));
+        }
+
+        let id = loop {
+            let id = rand::random();
+            if !webhooks.contains_key(&id) {
+                break id;
+            }
+        };
+        let secret: [u8; 32] = rand::random();
+        webhooks.insert(
+            id,
+            Arc::new(Webhook {
+                id,
+                url: parsed_url,
+                client,
+                addresses,
+                commitment,
+                secret,
+            }),
+        );
+        Ok(RpcWebhookRegistration {
+            id: id.to_string(),
+            secret: hex::encode(secret),
+        })
+    }
+
+    /// Checks that the host of `url` only resolves to public addresses
+    fn check_host_addresses(url: &Url, port: u16) -> Result<(), WebhookError> {
+        let host = url.host_str().unwrap_or_default();
+        let addrs: Vec<IpAddr> = match url.host() {
+            Some(url::Host::Ipv4(ip)) => vec![IpAddr::V4(ip)],
+            Some(url::Host::Ipv6(ip)) => vec![IpAddr::V6(ip)],
+            Some(url::Host::Domain(domain)) => (domain, port)
+                .to_socket_addrs()
+                .map_err(|_| WebhookError::UnresolvableHost(host.to_string()))?
+                .map(|addr| addr.ip())
+                .collect(),
+            None => vec![],
+        };
+        if addrs.is_empty() {
+            return Err(WebhookError::UnresolvableHost(host.to_string()));
+        }
+        match addrs.into_iter().find(|ip| is_forbidden_address(*ip)) {
+            Some(ip) => Err(WebhookError::ForbiddenAddress(host.to_string(), ip)),
+            None => Ok(()),
+        }
+    }
+
+    /// Removes the webhook `id`, returns false unless it exists and `secret` is its secret.
+    pub fn unregister(&self, id: u64, secret: &[u8]) -> bool {
+        let mut webhooks = self.webhooks.write().unwrap();
+        match webhooks.get(&id) {
+            Some(webhook) if webhook.secret.as_slice() == secret => {
+                webhooks.remove(&id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn is_registered(&self, id: u64) -> bool {
+        self.webhooks.read().unwrap().contains_key(&id)
+    }
+
+    fn has_webhooks(&self, commitment: CommitmentLevel) -> bool {
+        self.webhooks
+            .read()
+            .unwrap()
+            .values()
+            .any(|webhook| webhook.commitment == commitment)
+    }
+
+    /// Returns the webhooks of `commitment` watching any of `account_keys`, along with the
+    /// addresses they watch.
+    fn matching_webhooks<'a>(
+        &self,
+        commitment: CommitmentLevel,
+        account_keys: impl Iterator<Item = &'a Pubkey> + Clone,
+    ) -> Vec<(Arc<Webhook>, Vec<Pubkey>)> {
+        self.webhooks
+            .read()
+            .unwrap()
+            .values()
+            .filter(|webhook| webhook.commitment == commitment)
+            .filter_map(|webhook| {
+                let addresses: Vec<_> = account_keys
+                    .clone()
+                    .filter(|key| webhook.addresses.contains(key))
+                    .copied()
+                    .collect();
+                (!addresses.is_empty()).then(|| (Arc::clone(webhook), addresses))
+            })
+            .collect()
+    }
+
+    /// Counts a notification against the quota of `client`, returns false if the quota is spent.
+    fn try_consume_quota(&self, client: &str, now: Instant) -> bool {
+        let mut quotas = self.quotas.lock().unwrap();
+        let quota = quotas
+            .entry(client.to_string())
+            .or_insert_with(|| ClientQuota {
+                window_start: now,
+                notifications: 0,
+            });
+        if now.duration_since(quota.window_start) >= QUOTA_WINDOW {
+            quota.window_start = now;
+            quota.notifications = 0;
+        }
+        if quota.notifications >= self.config.max_notifications_per_client_per_minute {
+            return false;
+        }
+        quota.notifications += 1;
+        true
+    }
+
+    fn prune_quotas(&self, now: Instant) {
+        self.quotas
+            .lock()
+            .unwrap()
+            .retain(|_, quota| now.duration_since(quota.window_start) < QUOTA_WINDOW);
+    }
+}
+
+/// Follows the blocks reaching confirmed and finalized commitment and notifies the webhooks
+/// watching the addresses their transactions reference.
+pub struct WebhookService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl WebhookService {
+    pub fn new(
+        registry: Arc<WebhookRegistry>,
+        blockstore: Arc<Blockstore>,
+        block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
+        max_complete_transaction_status_slot: Arc<AtomicU64>,
+        runtime: Arc<Runtime>,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solRpcWebhooks".to_string())
+            .spawn(move || {
+                info!("WebhookService has started");
+                Self::run(
+                    &registry,
+                    &blockstore,
+                    &block_commitment_cache,
+                    &max_complete_transaction_status_slot,
+                    &runtime,
+                    &exit,
+                );
+                info!("WebhookService has stopped");
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    fn run(
+        registry: &Arc<WebhookRegistry>,
+        blockstore: &Blockstore,
+        block_commitment_cache: &RwLock<BlockCommitmentCache>,
+        max_complete_transaction_status_slot: &AtomicU64,
+        runtime: &Runtime,
+        exit: &AtomicBool,
+    ) {
+        let client = reqwest::Client::builder()
+            .timeout(registry.config.delivery_timeout)
+            .redirect(redirect::Policy::none())
+            .dns_resolver(Arc::new(WebhookResolver {
+                allowed_hosts: registry.config.allowed_hosts.clone(),
+            }))
+            .build()
+            .expect("webhook http client");
+        let commitment_slots = || {
+            let max_complete_slot = max_complete_transaction_status_slot.load(Ordering::SeqCst);
+            let cache = block_commitment_cache.read().unwrap();
+            (
+                cache.highest_confirmed_slot().min(max_complete_slot),
+                cache.highest_super_majority_root().min(max_complete_slot),
+            )
+        };
+        // Only blocks reaching their commitment from now on are notified
+        let (mut last_confirmed_slot, mut last_finalized_slot) = commitment_slots();
+        let mut last_report = Instant::now();
+
+        while !exit.load(Ordering::Relaxed) {
+            let (confirmed_slot, finalized_slot) = commitment_slots();
+
+            if confirmed_slot > last_confirmed_slot {
+                if registry.has_webhooks(CommitmentLevel::Confirmed) {
+                    let mut slots = vec![];
+                    let mut slot = confirmed_slot;
+                    while slot > last_confirmed_slot {
+                        slots.push(slot);
+                        match blockstore.meta(slot) {
+                            Ok(Some(meta)) => match meta.parent_slot {
+                                Some(parent_slot) => slot = parent_slot,
+                                None => break,
+                            },
+                            _ => break,
+                        }
+                    }
+                    for slot in slots.into_iter().rev() {
+                        let block = blockstore.get_complete_block(slot, false);
+                        Self::notify_block(
+                            registry,
+                            &client,
+                            runtime,
+                            CommitmentLevel::Confirmed,
+                            slot,
+                            block,
+                        );
+                    }
+                }
+                last_confirmed_slot = confirmed_slot;
+            }
+
+            if finalized_slot > last_finalized_slot {
+                if registry.has_webhooks(CommitmentLevel::Finalized) {
+                    let slots: Vec<_> = blockstore
+                        .rooted_slot_iterator(last_finalized_slot + 1)
+                        .map(|slots| slots.take_while(|slot| *slot <= finalized_slot).collect())
+                        .unwrap_or_default();
+                    for slot in slots {
+                        let block = blockstore.get_rooted_block(slot, false);
+                        Self::notify_block(
+                            registry,
+                            &client,
+                            runtime,
+                            CommitmentLevel::Finalized,
+                            slot,
+                            block,
+                        );
+                    }
+                }
+                last_finalized_slot = finalized_slot;
+            }
+
+            if last_report.elapsed() >= METRICS_REPORT_INTERVAL {
+                registry
+                    .stats
+                    .report(registry.webhooks.read().unwrap().len());
+                registry.prune_quotas(Instant::now());
+                last_report = Instant::now();
+            }
+            sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn notify_block(
+        registry: &Arc<WebhookRegistry>,
+        client: &reqwest::Client,
+        runtime: &Runtime,
+        commitment: CommitmentLevel,
+        slot: Slot,
+        block: solana_ledger::blockstore::Result<VersionedConfirmedBlock>,
+    ) {
+        let block = match block {
+            Ok(block) => block,
+            Err(err) => {
+                debug!("webhooks skipped slot {slot}: {err:?}");
+                return;
+            }
+        };
+        for VersionedTransactionWithStatusMeta { transaction, meta } in &block.transactions {
+            let account_keys = transaction
+                .message
+                .static_account_keys()
+                .iter()
+                .chain(&meta.loaded_addresses.writable)
+                .chain(&meta.loaded_addresses.readonly);
+            for (webhook, addresses) in registry.matching_webhooks(commitment, account_keys) {
+                if !registry.try_consume_quota(&webhook.client, Instant::now()) {
+                    registry.stats.dropped.fetch_add(1, Ordering::Relaxed);
+                    continue;
+                }
+                let notification = RpcWebhookNotification {
+                    webhook_id: webhook.id.to_string(),
+                    slot,
+                    commitment,
+                    block_time: block.block_time,
+                    signature: transaction.signatures[0].to_string(),
+                    err: meta.status.clone().err(),
+                    fee: meta.fee,
+                    addresses: addresses
+                        .iter()
+                        .map(|address| address.to_string())
+                        .collect(),
+                };
+                runtime.spawn(deliver(
+                    Arc::clone(registry),
+                    client.clone(),
+                    webhook,
+                    notification,
+                ));
+            }
+        }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+async fn deliver(
+    registry: Arc<WebhookRegistry>,
+    client: reqwest::Client,
+    webhook: Arc<Webhook>,
+    notification: RpcWebhookNotification,
+) {
+    let body = serde_json::to_vec(&notification).expect("serialize webhook notification");
+    let signature = format!("sha256={}", sign_notification(&webhook.secret, &body));
+    let mut retry_delay = INITIAL_RETRY_DELAY;
+    for attempt in 1..=registry.config.max_delivery_attempts {
+        let response = client
+            .post(webhook.url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_ID_HEADER, webhook.id.to_string())
+            .header(SIGNATURE_HEADER, &signature)
+            .body(body.clone())
+            .send()
+            .await;
+        match response {
+            Ok(response) if response.status().is_success() => {
+                registry.stats.delivered.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+            Ok(response) => debug!(
+                "webhook {} attempt {attempt} failed with status {}",
+                webhook.id,
+                response.status()
+            ),
+            Err(err) => debug!("webhook {} attempt {attempt} failed: {err}", webhook.id),
+        }
+        if attempt == registry.config.max_delivery_attempts || !registry.is_registered(webhook.id) {
+            break;
+        }
+        tokio::time::sleep(retry_delay).await;
+        retry_delay = retry_delay.saturating_mul(2);
+    }
+    registry.stats.failed.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> WebhookRegistry {
+        WebhookRegistry::new(WebhookConfig {
+            max_webhooks: 3,
+            max_webhooks_per_client: 2,
+            max_addresses_per_webhook: 2,
+            max_notifications_per_client_per_minute: 2,
+            allowed_hosts: Some(
+                ["a.example", "b.example"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            ),
+            ..WebhookConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_register_validation() {
+        let registry = registry();
+        let address = Pubkey::new_unique();
+        assert_eq!(
+            registry.register("ftp://a.example", vec![address], CommitmentLevel::Confirmed),
+            Err(WebhookError::InvalidUrl("ftp://a.example".to_string()))
+        );
+        assert_eq!(
+            registry.register("not a url", vec![address], CommitmentLevel::Confirmed),
+            Err(WebhookError::InvalidUrl("not a url".to_string()))
+        );
+        assert_eq!(
+            registry.register(
+                "https://a.example",
+                vec![address],
+                CommitmentLevel::Processed
+            ),
+            Err(WebhookError::InvalidCommitment)
+        );
+        assert_eq!(
+            registry.register("https://a.example", vec![], CommitmentLevel::Confirmed),
+            Err(WebhookError::InvalidAddressCount(2))
+        );
+        assert_eq!(
+            registry.register(
+                "https://a.example",
+                vec![address, Pubkey::new_unique(), Pubkey::new_unique()],
+                CommitmentLevel::Confirmed
+            ),
+            Err(WebhookError::InvalidAddressCount(2))
+        );
+        assert!(registry.webhooks.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_register_allowed_hosts() {
+        let registry = registry();
+        let address = Pubkey::new_unique();
+        assert_eq!(
+            registry.register(
+                "https://c.example",
+                vec![address],
+                CommitmentLevel::Confirmed
+            ),
+            Err(WebhookError::HostNotAllowed("c.example".to_string()))
+        );
+        // Allowed hosts are trusted
+        let registry = WebhookRegistry::new(WebhookConfig {
+            allowed_hosts: Some(HashSet::from(["127.0.0.1".to_string()])),
+            ..WebhookConfig::default()
+        });
+        assert!(registry
+            .register(
+                "http://127.0.0.1:8080",
+                vec![address],
+                CommitmentLevel::Confirmed
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_register_forbidden_addresses() {
+        let registry = WebhookRegistry::new(WebhookConfig::default());
+        let address = Pubkey::new_unique();
+        let register = |url| registry.register(url, vec![address], CommitmentLevel::Confirmed);
+        let forbidden = |host: &str, ip: &str| -> Result<RpcWebhookRegistration, WebhookError> {
+            Err(WebhookError::ForbiddenAddress(
+                host.to_string(),
+                ip.parse().unwrap(),
+            ))
+        };
+
+        // loopback
+        assert_eq!(
+            register("http://127.0.0.1:8899"),
+            forbidden("127.0.0.1", "127.0.0.1")
+        );
+        assert_eq!(register("http://[::1]:8899"), forbidden("[::1]", "::1"));
+        assert!(matches!(
+            register("http://localhost:8899"),
+            Err(WebhookError::ForbiddenAddress(_, ip)) if ip.is_loopback()
+        ));
+        // private
+        assert_eq!(
+            register("http://10.0.0.1"),
+            forbidden("10.0.0.1", "10.0.0.1")
+        );
+        assert_eq!(
+            register("http://172.16.5.4"),
+            forbidden("172.16.5.4", "172.16.5.4")
+        );
+        assert_eq!(
+            register("https://192.168.1.1"),
+            forbidden("192.168.1.1", "192.168.1.1")
+        );
+        assert_eq!(
+            register("http://[fd00::1]"),
+            forbidden("[fd00::1]", "fd00::1")
+        );
+        assert_eq!(
+            register("http://100.64.0.1"),
+            forbidden("100.64.0.1", "100.64.0.1")
+        );
+        // link-local, including cloud metadata services
+        assert_eq!(
+            register("http://169.254.169.254/latest/meta-data"),
+            forbidden("169.254.169.254", "169.254.169.254")
+        );
+        assert_eq!(
+            register("http://[fe80::1]"),
+            forbidden("[fe80::1]", "fe80::1")
+        );
+        // unspecified
+        assert_eq!(
+            register("http://0.0.0.0:8899"),
+            forbidden("0.0.0.0", "0.0.0.0")
+        );
+        assert_eq!(register("http://[::]:8899"), forbidden("[::]", "::"));
+        // IPv4 mapped
+        assert_eq!(
+            register("http://[::ffff:127.0.0.1]"),
+            forbidden("[::ffff:7f00:1]", "::ffff:127.0.0.1")
+        );
+        // URLs with numeric hosts in other notations are normalized by the parser
+        assert_eq!(
+            register("http://2130706433"),
+            forbidden("127.0.0.1", "127.0.0.1")
+        );
+        assert!(registry.webhooks.read().unwrap().is_empty());
+
+        assert!(register("https://8.8.8.8").is_ok());
+        assert!(register("https://[2001:4860:4860::8888]").is_ok());
+    }
+
+    #[test]
+    fn test_is_forbidden_address() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.31.255.255",
+            "192.168.0.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "0.1.2.3",
+            "100.127.0.1",
+            "255.255.255.255",
+            "224.0.0.1",
+            "::1",
+            "::",
+            "fc00::1",
+            "fe80::1",
+            "ff02::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(is_forbidden_address(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in [
+            "8.8.8.8",
+            "100.128.0.1",
+            "172.32.0.1",
+            "2001:4860:4860::8888",
+        ] {
+            assert!(!is_forbidden_address(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn test_register_limits() {
+        let registry = registry();
+        let addresses = vec![Pubkey::new_unique()];
+        let register = |url| registry.register(url, addresses.clone(), CommitmentLevel::Finalized);
+
+        register("https://a.example/1").unwrap();
+        register("https://a.example:443/2").unwrap();
+        assert_eq!(
+            register("https://a.example/3"),
+            Err(WebhookError::ClientLimitReached(
+                "a.example:443".to_string(),
+                2
+            ))
+        );
+        // Another port is another client
+        register("http://a.example:8080").unwrap();
+        assert_eq!(
+            register("https://b.example"),
+            Err(WebhookError::LimitReached(3))
+        );
+    }
+
+    #[test]
+    fn test_unregister() {
+        let registry = registry();
+        let RpcWebhookRegistration { id, secret } = registry
+            .register(
+                "https://a.example",
+                vec![Pubkey::new_unique()],
+                CommitmentLevel::Confirmed,
+            )
+            .unwrap();
+        let id = id.parse().unwrap();
+        let secret = hex::decode(secret).unwrap();
+
+        assert!(!registry.unregister(id, &[0; 32]));
+        assert!(!registry.unregister(id.wrapping_add(1), &secret));
+        assert!(registry.is_registered(id));
+        assert!(registry.unregister(id, &secret));
+        assert!(!registry.is_registered(id));
+        assert!(!registry.unregister(id, &secret));
+    }
+
+    #[test]
+    fn test_matching_webhooks() {
+        let registry = registry();
+        let watched = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        registry
+            .register(
+                "https://a.example",
+                vec![watched],
+                CommitmentLevel::Confirmed,
+            )
+            .unwrap();
+
+        assert!(registry.has_webhooks(CommitmentLevel::Confirmed));
+        assert!(!registry.has_webhooks(CommitmentLevel::Finalized));
+        let keys = [other, watched];
+        let matches = registry.matching_webhooks(CommitmentLevel::Confirmed, keys.iter());
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].1, vec![watched]);
+        assert!(registry
+            .matching_webhooks(CommitmentLevel::Finalized, keys.iter())
+            .is_empty());
+        assert!(registry
+            .matching_webhooks(CommitmentLevel::Confirmed, [other].iter())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_client_quota() {
+        let registry = registry();
+        let now = Instant::now();
+        assert!(registry.try_consume_quota("a.example:443", now));
+        assert!(registry.try_consume_quota("a.example:443", now));
+        assert!(!registry.try_consume_quota("a.example:443", now));
+        assert!(registry.try_consume_quota("b.example:443", now));
+
+        let next_window = now + QUOTA_WINDOW;
+        assert!(registry.try_consume_quota("a.example:443", next_window));
+        registry.prune_quotas(next_window);
+        assert_eq!(registry.quotas.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_sign_notification() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign_notification(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
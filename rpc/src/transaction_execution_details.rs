@@ -0,0 +1,87 @@
+//! The `transaction_execution_details` module keeps the fee and compute units of recently
+//! executed transactions, so signature notifications can include them and clients don't need a
+//! follow-up `getTransaction`.
+//!
+//! The transaction status service records the details of the transactions it receives from the
+//! transaction status sender, as banks are replayed or produced. Only the most recent slots are
+//! kept, which covers the slots of the banks notified at any commitment while the root advances.
+
+use {
+    solana_sdk::{clock::Slot, signature::Signature},
+    solana_transaction_status::FeeBreakdown,
+    std::{
+        collections::{BTreeMap, HashMap},
+        sync::RwLock,
+    },
+};
+
+/// Number of most recent slots whose transaction details are kept
+pub const MAX_SLOTS: usize = 128;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransactionExecutionDetails {
+    pub fee_breakdown: FeeBreakdown,
+    pub compute_units_consumed: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionExecutionDetailsCache {
+    slots: RwLock<BTreeMap<Slot, HashMap<Signature, TransactionExecutionDetails>>>,
+}
+
+impl TransactionExecutionDetailsCache {
+    /// Records the details of the transactions executed in `slot`
+    pub fn insert(
+        &self,
+        slot: Slot,
+        details: impl IntoIterator<Item = (Signature, TransactionExecutionDetails)>,
+    ) {
+        let mut slots = self.slots.write().unwrap();
+        slots.entry(slot).or_default().extend(details);
+        while slots.len() > MAX_SLOTS {
+            slots.pop_first();
+        }
+    }
+
+    /// Returns the details of the transaction with `signature` executed in `slot`, if recorded
+    pub fn get(&self, slot: Slot, signature: &Signature) -> Option<TransactionExecutionDetails> {
+        self.slots
+            .read()
+            .unwrap()
+            .get(&slot)
+            .and_then(|details| details.get(signature))
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_execution_details_cache() {
+        let cache = TransactionExecutionDetailsCache::default();
+        let signature = Signature::new_unique();
+        let details = TransactionExecutionDetails {
+            fee_breakdown: FeeBreakdown {
+                base: 5_000,
+                prioritization: 300,
+                ..FeeBreakdown::default()
+            },
+            compute_units_consumed: 450,
+        };
+        cache.insert(1, [(signature, details)]);
+        assert_eq!(cache.get(1, &signature), Some(details));
+        // Details are per slot, the same transaction may execute on another fork
+        assert_eq!(cache.get(2, &signature), None);
+        assert_eq!(cache.get(1, &Signature::new_unique()), None);
+
+        // Only the most recent slots are kept
+        for slot in 2..=MAX_SLOTS as Slot {
+            cache.insert(slot, []);
+        }
+        assert_eq!(cache.get(1, &signature), Some(details));
+        cache.insert(MAX_SLOTS as Slot + 1, []);
+        assert_eq!(cache.get(1, &signature), None);
+    }
+}
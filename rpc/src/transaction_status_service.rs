@@ -1,5 +1,10 @@
 use {
-    crate::transaction_notifier_interface::TransactionNotifierArc,
+    crate::{
+        transaction_execution_details::{
+            TransactionExecutionDetails, TransactionExecutionDetailsCache,
+        },
+        transaction_notifier_interface::TransactionNotifierArc,
+    },
     crossbeam_channel::{Receiver, RecvTimeoutError},
     itertools::izip,
     solana_ledger::{
@@ -38,6 +43,7 @@ impl TransactionStatusService {
         max_complete_transaction_status_slot: Arc<AtomicU64>,
         enable_rpc_transaction_history: bool,
         transaction_notifier: Option<TransactionNotifierArc>,
+        transaction_execution_details: Option<Arc<TransactionExecutionDetailsCache>>,
         blockstore: Arc<Blockstore>,
         enable_extended_tx_metadata_storage: bool,
         exit: Arc<AtomicBool>,
@@ -71,6 +77,7 @@ impl TransactionStatusService {
                         &max_complete_transaction_status_slot,
                         enable_rpc_transaction_history,
                         transaction_notifier.clone(),
+                        transaction_execution_details.as_deref(),
                         &blockstore,
                         enable_extended_tx_metadata_storage,
                     ) {
@@ -97,6 +104,7 @@ impl TransactionStatusService {
         max_complete_transaction_status_slot: &Arc<AtomicU64>,
         enable_rpc_transaction_history: bool,
         transaction_notifier: Option<TransactionNotifierArc>,
+        transaction_execution_details: Option<&TransactionExecutionDetailsCache>,
         blockstore: &Blockstore,
         enable_extended_tx_metadata_storage: bool,
     ) -> Result<(), BlockstoreError> {
@@ -110,6 +118,7 @@ impl TransactionStatusService {
                 transaction_indexes,
             }) => {
                 let mut status_and_memos_batch = blockstore.get_write_batch()?;
+                let mut execution_details = vec![];
 
                 for (
                     transaction,
@@ -139,11 +148,21 @@ impl TransactionStatusService {
                         return_data,
                         executed_units,
                         fee_details,
+                        fee_breakdown,
                         rent_debits,
                         ..
                     } = committed_tx;
 
                     let fee = fee_details.total_fee();
+                    if transaction_execution_details.is_some() {
+                        execution_details.push((
+                            *transaction.signature(),
+                            TransactionExecutionDetails {
+                                fee_breakdown,
+                                compute_units_consumed: executed_units,
+                            },
+                        ));
+                    }
                     let inner_instructions = inner_instructions.map(|inner_instructions| {
                         map_inner_instructions(inner_instructions).collect()
                     });
@@ -176,6 +195,7 @@ impl TransactionStatusService {
                         loaded_addresses,
                         return_data,
                         compute_units_consumed: Some(executed_units),
+                        fee_breakdown: Some(fee_breakdown),
                     };
 
                     if let Some(transaction_notifier) = transaction_notifier.as_ref() {
@@ -225,6 +245,9 @@ impl TransactionStatusService {
                 if enable_rpc_transaction_history {
                     blockstore.write_batch(status_and_memos_batch)?;
                 }
+                if let Some(transaction_execution_details) = transaction_execution_details {
+                    transaction_execution_details.insert(slot, execution_details);
+                }
             }
             TransactionStatusMessage::Freeze(slot) => {
                 max_complete_transaction_status_slot.fetch_max(slot, Ordering::SeqCst);
@@ -287,7 +310,7 @@ pub(crate) mod tests {
         },
         solana_svm::transaction_execution_result::TransactionLoadedAccountsStats,
         solana_transaction_status::{
-            token_balances::TransactionTokenBalancesSet, TransactionStatusMeta,
+            token_balances::TransactionTokenBalancesSet, FeeBreakdown, TransactionStatusMeta,
             TransactionTokenBalance,
         },
         std::sync::{atomic::AtomicBool, Arc},
@@ -389,8 +412,13 @@ pub(crate) mod tests {
             log_messages: None,
             inner_instructions: None,
             return_data: None,
-            executed_units: 0,
-            fee_details: FeeDetails::default(),
+            executed_units: 1_234,
+            fee_details: FeeDetails::new(5_000, 300),
+            fee_breakdown: FeeBreakdown {
+                base: 5_000,
+                prioritization: 300,
+                ..FeeBreakdown::default()
+            },
             rent_debits,
             loaded_account_stats: TransactionLoadedAccountsStats::default(),
         });
@@ -442,6 +470,7 @@ pub(crate) mod tests {
         };
 
         let test_notifier = Arc::new(TestTransactionNotifier::new());
+        let transaction_execution_details = Arc::new(TransactionExecutionDetailsCache::default());
 
         let exit = Arc::new(AtomicBool::new(false));
         let transaction_status_service = TransactionStatusService::new(
@@ -449,6 +478,7 @@ pub(crate) mod tests {
             Arc::new(AtomicU64::default()),
             false,
             Some(test_notifier.clone()),
+            Some(transaction_execution_details.clone()),
             blockstore,
             false,
             exit.clone(),
@@ -472,6 +502,18 @@ pub(crate) mod tests {
             expected_transaction.signature(),
             result.transaction.signature()
         );
+
+        assert_eq!(
+            transaction_execution_details.get(slot, &signature),
+            Some(TransactionExecutionDetails {
+                fee_breakdown: FeeBreakdown {
+                    base: 5_000,
+                    prioritization: 300,
+                    ..FeeBreakdown::default()
+                },
+                compute_units_consumed: 1_234,
+            })
+        );
     }
 
     #[test]
@@ -517,6 +559,7 @@ pub(crate) mod tests {
             return_data: None,
             executed_units: 0,
             fee_details: FeeDetails::default(),
+            fee_breakdown: FeeBreakdown::default(),
             rent_debits: RentDebits::default(),
             loaded_account_stats: TransactionLoadedAccountsStats::default(),
         });
@@ -552,6 +595,7 @@ pub(crate) mod tests {
             Arc::new(AtomicU64::default()),
             true,
             Some(test_notifier.clone()),
+            None,
             blockstore,
             false,
             exit.clone(),
@@ -84,6 +84,10 @@ impl<D: TransactionData> RuntimeTransaction<ResolvedTransactionView<D>> {
         reserved_account_keys: &HashSet<Pubkey>,
     ) -> Result<Self> {
         let RuntimeTransaction { transaction, meta } = statically_loaded_runtime_tx;
+        // v1 messages cannot be converted to a `VersionedTransaction`, which entries are made of.
+        if matches!(transaction.version(), TransactionVersion::V1) {
+            return Err(TransactionError::UnsupportedVersion);
+        }
         // transaction-view does not distinguish between different types of errors here.
         // return generic sanitize failure error here.
         // these transactions should be immediately dropped, and we generally
@@ -179,6 +183,9 @@ impl<D: TransactionData> TransactionWithMeta for RuntimeTransaction<ResolvedTran
                     })
                     .collect(),
             }),
+            TransactionVersion::V1 => {
+                unreachable!("v1 transactions are rejected when resolving their addresses")
+            }
         };
 
         VersionedTransaction {
@@ -240,6 +247,51 @@ mod tests {
         assert!(!dynamic_runtime_transaction.is_simple_vote_transaction());
     }
 
+    #[test]
+    fn test_v1_transaction_unsupported() {
+        let payer = Pubkey::new_unique();
+        let transaction = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(
+                v0::Message::try_compile(
+                    &payer,
+                    &[system_instruction::transfer(
+                        &payer,
+                        &Pubkey::new_unique(),
+                        1,
+                    )],
+                    &[],
+                    Hash::default(),
+                )
+                .unwrap(),
+            ),
+        };
+        let mut serialized_transaction = bincode::serialize(&transaction).unwrap();
+        // Bump the version byte following the signature.
+        serialized_transaction[1 + core::mem::size_of::<Signature>()] =
+            solana_message::MESSAGE_VERSION_PREFIX | 1;
+
+        let transaction =
+            SanitizedTransactionView::try_new_sanitized(&serialized_transaction[..]).unwrap();
+        assert!(matches!(transaction.version(), TransactionVersion::V1));
+        let static_runtime_transaction =
+            RuntimeTransaction::<SanitizedTransactionView<_>>::try_from(
+                transaction,
+                MessageHash::Compute,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            RuntimeTransaction::<ResolvedTransactionView<_>>::try_from(
+                static_runtime_transaction,
+                Some(LoadedAddresses::default()),
+                &ReservedAccountKeys::empty_key_set(),
+            )
+            .err(),
+            Some(TransactionError::UnsupportedVersion)
+        );
+    }
+
     #[test]
     fn test_to_versioned_transaction() {
         fn assert_translation(
@@ -9,14 +9,18 @@ use {
     solana_runtime::{
         bank::{test_utils::goto_end_of_slot, *},
         bank_client::BankClient,
+        genesis_utils::activate_feature,
         loader_utils::create_invoke_instruction,
     },
     solana_sdk::{
+        account::AccountSharedData,
         client::{AsyncClient, SyncClient},
         clock::MAX_RECENT_BLOCKHASHES,
+        epoch_schedule::EpochSchedule,
         genesis_config::create_genesis_config,
         message::Message,
         pubkey::Pubkey,
+        rent::Rent,
         signature::{Keypair, Signer},
         transaction::Transaction,
     },
@@ -202,3 +206,53 @@ fn bench_bank_update_recent_blockhashes(bencher: &mut Bencher) {
         bank.update_recent_blockhashes();
     });
 }
+
+/// Replays empty slots over a bank holding many accounts, each slot scanning its partition of the
+/// accounts for rent unless rent scanning is disabled
+fn do_bench_rent_scanning(bencher: &mut Bencher, disable_rent_scanning: bool) {
+    solana_logger::setup();
+    let (mut genesis_config, _mint_keypair) = create_genesis_config(100_000_000_000_000);
+    genesis_config.rent = Rent::default();
+    // A short epoch so that every slot scans a large partition of the accounts
+    genesis_config.epoch_schedule = EpochSchedule::custom(32, 32, false);
+    let rent_exempt_balance = genesis_config.rent.minimum_balance(0);
+    for i in 0..100_000 {
+        // Mix rent exempt and rent paying accounts
+        let lamports = if i % 2 == 0 { rent_exempt_balance } else { 1 };
+        genesis_config.add_account(
+            Pubkey::new_unique(),
+            AccountSharedData::new(lamports, 0, &Pubkey::default()),
+        );
+    }
+    if disable_rent_scanning {
+        activate_feature(
+            &mut genesis_config,
+            agave_feature_set::disable_rent_scanning::id(),
+        );
+    }
+
+    let mut bank = Arc::new(Bank::new_for_benches(&genesis_config));
+    bank.freeze();
+    bencher.iter(|| {
+        let slot = bank.slot() + 1;
+        bank = Arc::new(Bank::new_from_parent(
+            bank.clone(),
+            &Pubkey::default(),
+            slot,
+        ));
+        bank.freeze();
+        bank.squash();
+    });
+}
+
+#[bench]
+#[ignore]
+fn bench_bank_replay_slot_with_rent_scanning(bencher: &mut Bencher) {
+    do_bench_rent_scanning(bencher, false);
+}
+
+#[bench]
+#[ignore]
+fn bench_bank_replay_slot_without_rent_scanning(bencher: &mut Bencher) {
+    do_bench_rent_scanning(bencher, true);
+}
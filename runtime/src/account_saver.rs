@@ -168,6 +168,23 @@ fn collect_accounts_for_failed_tx<'a, T: SVMMessage>(
                     .push(transaction_ref.expect("transaction ref must exist if collecting"));
             }
         }
+        RollbackAccounts::FeePayerAndCoPayer {
+            fee_payer_account,
+            co_payer_address,
+            co_payer_account,
+        } => {
+            collected_accounts.push((fee_payer_address, fee_payer_account));
+            if let Some(collected_account_transactions) = collected_account_transactions {
+                collected_account_transactions
+                    .push(transaction_ref.expect("transaction ref must exist if collecting"));
+            }
+
+            collected_accounts.push((co_payer_address, co_payer_account));
+            if let Some(collected_account_transactions) = collected_account_transactions {
+                collected_account_transactions
+                    .push(transaction_ref.expect("transaction ref must exist if collecting"));
+            }
+        }
     }
 }
 
@@ -364,6 +364,19 @@ impl SnapshotRequestHandler {
             this_accounts_hash
         });
 
+        // While rolling out the accounts lt hash, the epoch accounts hash is computed with the
+        // legacy accounts hash, and the accounts lt hash is checked against a full recalculation.
+        if request_kind == SnapshotRequestKind::EpochAccountsHash
+            && snapshot_root_bank.is_accounts_lt_hash_enabled()
+            && snapshot_root_bank
+                .rc
+                .accounts
+                .accounts_db
+                .is_experimental_accumulator_hash_dual_mode_epoch(snapshot_root_bank.epoch())
+        {
+            snapshot_root_bank.verify_accounts_lt_hash_from_index();
+        }
+
         let mut clean_time = Measure::start("clean_time");
         snapshot_root_bank.clean_accounts();
         clean_time.stop();
@@ -37,8 +37,11 @@ use {
     crate::{
         account_saver::collect_accounts_to_store,
         bank::{
+            freeze_hooks::BankFreezeHooks,
             metrics::*,
             partitioned_epoch_rewards::{EpochRewardStatus, StakeRewards, VoteRewardsAccounts},
+            supply_audit::{CapitalizationChange, CapitalizationDeltaTracker},
+            vote_overhead::VoteOverhead,
         },
         bank_forks::BankForks,
         epoch_stakes::{split_epoch_stakes, EpochStakes, NodeVoteAccounts, VersionedEpochStakes},
@@ -100,7 +103,7 @@ use {
         block_cost_limits::{simd_0207_block_limits, simd_0256_block_limits},
         cost_tracker::CostTracker,
     },
-    solana_fee::{calculate_fee, FeeFeatures},
+    solana_fee::{calculate_fee_breakdown, FeeBreakdown, FeeFeatures, BASE_FEE_MULTIPLIER},
     solana_lattice_hash::lt_hash::LtHash,
     solana_measure::{meas_dur, measure::Measure, measure_time, measure_us},
     solana_program_runtime::{
@@ -157,6 +160,7 @@ use {
     solana_svm::{
         account_loader::{collect_rent_from_account, LoadedTransaction},
         account_overrides::AccountOverrides,
+        cpi_stats::CpiStats,
         transaction_commit_result::{CommittedTransaction, TransactionCommitResult},
         transaction_error_metrics::TransactionErrorMetrics,
         transaction_execution_result::{
@@ -195,7 +199,8 @@ use {
     },
 };
 pub use {
-    partitioned_epoch_rewards::KeyedRewardsAndNumPartitions, solana_sdk::reward_type::RewardType,
+    fee_distribution::BankFeeSummary, partitioned_epoch_rewards::KeyedRewardsAndNumPartitions,
+    solana_sdk::reward_type::RewardType,
 };
 #[cfg(feature = "dev-context-only-utils")]
 use {
@@ -217,6 +222,7 @@ struct VerifyAccountsHashConfig {
     store_hash_raw_data_for_debug: bool,
 }
 
+pub mod account_proof;
 mod accounts_lt_hash;
 mod address_lookup_table;
 pub mod bank_hash_details;
@@ -225,17 +231,29 @@ pub mod builtins;
 mod check_transactions;
 pub mod epoch_accounts_hash_utils;
 mod fee_distribution;
+mod fee_parameters;
+pub mod fee_treasury;
+pub mod freeze_hooks;
+mod governance;
 mod metrics;
 pub(crate) mod partitioned_epoch_rewards;
 mod recent_blockhashes_account;
 mod serde_snapshot;
+mod slot_seed;
+pub mod state_migrations;
+pub mod supply_audit;
 mod sysvar_cache;
 pub(crate) mod tests;
+pub mod vote_overhead;
 
 pub const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
 
 pub const MAX_LEADER_SCHEDULE_STAKES: Epoch = 5;
 
+/// Max number of accounts a transaction may lock once the `raise_tx_account_lock_limit` feature
+/// is active, which is as many accounts as a versioned message can address
+pub const RAISED_MAX_TX_ACCOUNT_LOCKS: usize = 256;
+
 #[derive(Default)]
 struct RentMetrics {
     hold_range_us: AtomicU64,
@@ -416,6 +434,28 @@ impl TransactionLogCollector {
             }),
         }
     }
+
+    /// Returns the logs of the transactions which mentioned any of `addresses`, in the order
+    /// they were collected, or `None` if none of the addresses were mentioned
+    pub fn get_logs_for_addresses(&self, addresses: &[Pubkey]) -> Option<Vec<TransactionLogInfo>> {
+        let mut log_indices: Vec<usize> = addresses
+            .iter()
+            .filter_map(|address| self.mentioned_address_map.get(address))
+            .flatten()
+            .copied()
+            .collect();
+        if log_indices.is_empty() {
+            return None;
+        }
+        log_indices.sort_unstable();
+        log_indices.dedup();
+        Some(
+            log_indices
+                .into_iter()
+                .filter_map(|i| self.logs.get(i).cloned())
+                .collect(),
+        )
+    }
 }
 
 /// Bank's common fields shared by all supported snapshot versions for deserialization.
@@ -572,6 +612,9 @@ impl PartialEq for Bank {
             reserved_account_keys: _,
             drop_callback: _,
             freeze_started: _,
+            freeze_hooks: _,
+            capitalization_delta: _,
+            vote_overhead: _,
             vote_only_bank: _,
             cost_tracker: _,
             accounts_data_size_initial: _,
@@ -581,9 +624,11 @@ impl PartialEq for Bank {
             transaction_processor: _,
             check_program_modification_slot: _,
             collector_fee_details: _,
+            treasury_fees: _,
             compute_budget: _,
             transaction_account_lock_limit: _,
             fee_structure: _,
+            base_fee_multiplier: _,
             cache_for_accounts_lt_hash: _,
             stats_for_accounts_lt_hash: _,
             block_id,
@@ -885,6 +930,15 @@ pub struct Bank {
 
     pub freeze_started: AtomicBool,
 
+    /// Hooks run when this bank is frozen, shared with its parent and children
+    freeze_hooks: Arc<BankFreezeHooks>,
+
+    /// Changes of capitalization made by this bank, by cause
+    capitalization_delta: CapitalizationDeltaTracker,
+
+    /// Costs of the vote transactions of the epoch of this bank, up to this bank
+    vote_overhead: RwLock<VoteOverhead>,
+
     vote_only_bank: bool,
 
     cost_tracker: RwLock<CostTracker>,
@@ -909,6 +963,9 @@ pub struct Bank {
     /// Collected fee details
     collector_fee_details: RwLock<CollectorFeeDetails>,
 
+    /// Base fees deposited into the fee treasury instead of being burned
+    treasury_fees: AtomicU64,
+
     /// The compute budget to use for transaction execution.
     compute_budget: Option<ComputeBudget>,
 
@@ -918,6 +975,10 @@ pub struct Bank {
     /// Fee structure to use for assessing transaction fees.
     fee_structure: FeeStructure,
 
+    /// Compute units are multiplied by this to derive the base fee. Set by governance parameter
+    /// bundles.
+    base_fee_multiplier: u64,
+
     /// blockhash and bank_hash overrides keyed by slot for simulated block production.
     /// This _field_ was needed to be DCOU-ed to avoid 2 locks per bank freezing...
     #[cfg(feature = "dev-context-only-utils")]
@@ -1128,6 +1189,9 @@ impl Bank {
             reserved_account_keys: Arc::<ReservedAccountKeys>::default(),
             drop_callback: RwLock::new(OptionalDropCallback(None)),
             freeze_started: AtomicBool::default(),
+            freeze_hooks: Arc::<BankFreezeHooks>::default(),
+            capitalization_delta: CapitalizationDeltaTracker::default(),
+            vote_overhead: Self::new_vote_overhead(0),
             vote_only_bank: false,
             cost_tracker: RwLock::<CostTracker>::default(),
             accounts_data_size_initial: 0,
@@ -1137,9 +1201,11 @@ impl Bank {
             transaction_processor: TransactionBatchProcessor::default(),
             check_program_modification_slot: false,
             collector_fee_details: RwLock::new(CollectorFeeDetails::default()),
+            treasury_fees: AtomicU64::default(),
             compute_budget: None,
             transaction_account_lock_limit: None,
             fee_structure: FeeStructure::default(),
+            base_fee_multiplier: BASE_FEE_MULTIPLIER,
             #[cfg(feature = "dev-context-only-utils")]
             hash_overrides: Arc::new(Mutex::new(HashOverrides::default())),
             accounts_lt_hash: Mutex::new(AccountsLtHash(LtHash::identity())),
@@ -1385,6 +1451,9 @@ impl Bank {
                     .map(|drop_callback| drop_callback.clone_box()),
             )),
             freeze_started: AtomicBool::new(false),
+            freeze_hooks: parent.freeze_hooks.clone(),
+            capitalization_delta: CapitalizationDeltaTracker::default(),
+            vote_overhead: parent.new_vote_overhead_from_parent(epoch),
             cost_tracker: RwLock::new(parent.read_cost_tracker().unwrap().new_from_parent_limits()),
             accounts_data_size_initial,
             accounts_data_size_delta_on_chain: AtomicI64::new(0),
@@ -1393,9 +1462,11 @@ impl Bank {
             transaction_processor,
             check_program_modification_slot: false,
             collector_fee_details: RwLock::new(CollectorFeeDetails::default()),
+            treasury_fees: AtomicU64::default(),
             compute_budget: parent.compute_budget,
             transaction_account_lock_limit: parent.transaction_account_lock_limit,
             fee_structure: parent.fee_structure.clone(),
+            base_fee_multiplier: parent.base_fee_multiplier,
             #[cfg(feature = "dev-context-only-utils")]
             hash_overrides: parent.hash_overrides.clone(),
             accounts_lt_hash: Mutex::new(parent.accounts_lt_hash.lock().unwrap().clone()),
@@ -1449,7 +1520,9 @@ impl Bank {
             new.update_slot_hashes();
             new.update_stake_history(Some(parent.epoch()));
             new.update_clock(Some(parent.epoch()));
-            new.update_last_restart_slot()
+            new.update_last_restart_slot();
+            new.update_slot_seed();
+            new.update_fee_parameters();
         });
 
         let (_, fill_sysvar_cache_time_us) = measure_us!(new
@@ -1604,6 +1677,11 @@ impl Bank {
                 &mut rewards_metrics,
             ));
 
+        // Governance parameters take effect after the rewards for the previous epoch were
+        // calculated, and override any limits updated by feature activations
+        self.activate_parameter_bundle();
+        self.apply_active_parameters();
+
         report_new_epoch_metrics(
             epoch,
             slot,
@@ -1782,6 +1860,9 @@ impl Bank {
             reserved_account_keys: Arc::<ReservedAccountKeys>::default(),
             drop_callback: RwLock::new(OptionalDropCallback(None)),
             freeze_started: AtomicBool::new(fields.hash != Hash::default()),
+            freeze_hooks: Arc::<BankFreezeHooks>::default(),
+            capitalization_delta: CapitalizationDeltaTracker::default(),
+            vote_overhead: Self::new_vote_overhead(fields.epoch),
             vote_only_bank: false,
             cost_tracker: RwLock::new(CostTracker::default()),
             accounts_data_size_initial,
@@ -1792,9 +1873,11 @@ impl Bank {
             check_program_modification_slot: false,
             // collector_fee_details is not serialized to snapshot
             collector_fee_details: RwLock::new(CollectorFeeDetails::default()),
+            treasury_fees: AtomicU64::default(),
             compute_budget: runtime_config.compute_budget,
             transaction_account_lock_limit: runtime_config.transaction_account_lock_limit,
             fee_structure: FeeStructure::default(),
+            base_fee_multiplier: BASE_FEE_MULTIPLIER,
             #[cfg(feature = "dev-context-only-utils")]
             hash_overrides: Arc::new(Mutex::new(HashOverrides::default())),
             accounts_lt_hash: Mutex::new(AccountsLtHash(LtHash([0xBAD1; LtHash::NUM_ELEMENTS]))),
@@ -2604,6 +2687,7 @@ impl Bank {
             self.distribute_rent_fees();
             self.update_slot_history();
             self.run_incinerator();
+            self.update_vote_overhead();
 
             // freeze is a one-way trip, idempotent
             self.freeze_started.store(true, Relaxed);
@@ -2642,6 +2726,7 @@ impl Bank {
             }
             *hash = self.hash_internal_state();
             self.rc.accounts.accounts_db.mark_slot_frozen(self.slot());
+            self.run_freeze_hooks(*hash);
         }
     }
 
@@ -2727,7 +2812,7 @@ impl Bank {
                 "{pubkey} repeated in genesis config"
             );
             self.store_account(pubkey, &account.to_account_shared_data());
-            self.capitalization.fetch_add(account.lamports(), Relaxed);
+            self.change_capitalization(CapitalizationChange::OtherMinted(account.lamports()));
             self.accounts_data_size_initial += account.data().len() as u64;
         }
 
@@ -2786,7 +2871,7 @@ impl Bank {
 
     fn burn_and_purge_account(&self, program_id: &Pubkey, mut account: AccountSharedData) {
         let old_data_size = account.data().len();
-        self.capitalization.fetch_sub(account.lamports(), Relaxed);
+        self.change_capitalization(CapitalizationChange::OtherRemoved(account.lamports()));
         // Both resetting account balance to 0 and zeroing the account data
         // is needed to really purge from AccountsDb and flush the Stakes cache
         account.set_lamports(0);
@@ -2871,14 +2956,40 @@ impl Bank {
     }
 
     pub fn get_fee_for_message(&self, message: &SanitizedMessage) -> Option<u64> {
+        Some(self.get_fee_details_for_message(message).total_fee())
+    }
+
+    /// Like `get_fee_for_message`, but returns the components of the fee.
+    pub fn get_fee_details_for_message(&self, message: &SanitizedMessage) -> FeeDetails {
+        self.get_fee_breakdown_for_message(message).into()
+    }
+
+    /// Like `get_fee_for_message`, but returns all components of the fee.
+    pub fn get_fee_breakdown_for_message(&self, message: &SanitizedMessage) -> FeeBreakdown {
         let fee_budget_limits = self.get_fee_budget_limits(message);
-        Some(calculate_fee(
+        calculate_fee_breakdown(
             message,
             false,
             self.fee_rate_governor.lamports_per_signature,
             fee_budget_limits.prioritization_fee,
-            FeeFeatures::from(self.feature_set.as_ref()),
-        ))
+            self.fee_features(),
+        )
+    }
+
+    /// Splits the fee charged to a processed transaction into its components.
+    fn fee_breakdown(&self, message: &impl SVMMessage, fee_details: &FeeDetails) -> FeeBreakdown {
+        if fee_details.total_fee() == 0 {
+            return FeeBreakdown::default();
+        }
+        let fee_breakdown = calculate_fee_breakdown(
+            message,
+            false,
+            self.fee_rate_governor.lamports_per_signature,
+            fee_details.prioritization_fee(),
+            self.fee_features(),
+        );
+        debug_assert_eq!(FeeDetails::from(fee_breakdown), *fee_details);
+        fee_breakdown
     }
 
     /// Returns true when startup accounts hash verification has completed or never had to run in background.
@@ -2921,7 +3032,7 @@ impl Bank {
             lamports_per_signature == 0,
             self.fee_structure().lamports_per_signature,
             fee_budget_limits.prioritization_fee,
-            FeeFeatures::from(self.feature_set.as_ref()),
+            self.fee_features(),
         )
     }
 
@@ -3110,6 +3221,11 @@ impl Bank {
     pub fn get_transaction_account_lock_limit(&self) -> usize {
         if let Some(transaction_account_lock_limit) = self.transaction_account_lock_limit {
             transaction_account_lock_limit
+        } else if self
+            .feature_set
+            .is_active(&feature_set::raise_tx_account_lock_limit::id())
+        {
+            RAISED_MAX_TX_ACCOUNT_LOCKS
         } else if self
             .feature_set
             .is_active(&feature_set::increase_tx_account_lock_limit::id())
@@ -3214,16 +3330,47 @@ impl Bank {
         self.simulate_transaction_unchecked(transaction, enable_cpi_recording)
     }
 
+    /// Run transactions against a frozen bank without committing the results, loading the
+    /// accounts of `account_overrides` in place of the bank's
+    pub fn simulate_transaction_with_account_overrides(
+        &self,
+        transaction: &impl TransactionWithMeta,
+        enable_cpi_recording: bool,
+        account_overrides: AccountOverrides,
+    ) -> TransactionSimulationResult {
+        assert!(self.is_frozen(), "simulation bank must be frozen");
+
+        self.simulate_transaction_with_account_overrides_unchecked(
+            transaction,
+            enable_cpi_recording,
+            account_overrides,
+        )
+    }
+
     /// Run transactions against a bank without committing the results; does not check if the bank
     /// is frozen, enabling use in single-Bank test frameworks
     pub fn simulate_transaction_unchecked(
         &self,
         transaction: &impl TransactionWithMeta,
         enable_cpi_recording: bool,
+    ) -> TransactionSimulationResult {
+        self.simulate_transaction_with_account_overrides_unchecked(
+            transaction,
+            enable_cpi_recording,
+            AccountOverrides::default(),
+        )
+    }
+
+    fn simulate_transaction_with_account_overrides_unchecked(
+        &self,
+        transaction: &impl TransactionWithMeta,
+        enable_cpi_recording: bool,
+        account_overrides: AccountOverrides,
     ) -> TransactionSimulationResult {
         let account_keys = transaction.account_keys();
         let number_of_accounts = account_keys.len();
-        let account_overrides = self.get_account_overrides_for_simulation(&account_keys);
+        let account_overrides =
+            self.get_account_overrides_for_simulation(&account_keys, account_overrides);
         let batch = self.prepare_unlocked_batch_from_single_tx(transaction);
         let mut timings = ExecuteTimings::default();
 
@@ -3270,6 +3417,88 @@ impl Bank {
         let processing_result = processing_results
             .pop()
             .unwrap_or(Err(TransactionError::InvalidProgramForExecution));
+        let (simulation_result, _fee_details) =
+            Self::simulation_result(processing_result, number_of_accounts, units_consumed);
+        simulation_result
+    }
+
+    /// Run transactions in order against a frozen bank without committing the results, loading
+    /// the accounts of `account_overrides` in place of the bank's. Each transaction loads the
+    /// accounts written by the transactions before it, or only the fees charged to them if they
+    /// failed. Programs deployed by a transaction are not visible to the transactions after it.
+    pub fn simulate_bundle(
+        &self,
+        transactions: &[impl TransactionWithMeta],
+        enable_cpi_recording: bool,
+        mut account_overrides: AccountOverrides,
+    ) -> Vec<(TransactionSimulationResult, FeeDetails)> {
+        assert!(self.is_frozen(), "simulation bank must be frozen");
+
+        for transaction in transactions {
+            account_overrides = self.get_account_overrides_for_simulation(
+                &transaction.account_keys(),
+                account_overrides,
+            );
+        }
+        let tx_account_lock_limit = self.get_transaction_account_lock_limit();
+        let lock_results = transactions
+            .iter()
+            .map(|transaction| {
+                validate_account_locks(transaction.account_keys(), tx_account_lock_limit)
+            })
+            .collect();
+        let mut batch =
+            TransactionBatch::new(lock_results, self, OwnedOrBorrowed::Borrowed(transactions));
+        batch.set_needs_unlock(false);
+
+        let LoadAndExecuteTransactionsOutput {
+            processing_results, ..
+        } = self.load_and_execute_transactions(
+            &batch,
+            MAX_PROCESSING_AGE - MAX_TRANSACTION_FORWARDING_DELAY,
+            &mut ExecuteTimings::default(),
+            &mut TransactionErrorMetrics::default(),
+            TransactionProcessingConfig {
+                account_overrides: Some(&account_overrides),
+                check_program_modification_slot: self.check_program_modification_slot,
+                compute_budget: self.compute_budget(),
+                log_messages_bytes_limit: None,
+                limit_to_load_programs: true,
+                recording_config: ExecutionRecordingConfig {
+                    enable_cpi_recording,
+                    enable_log_recording: true,
+                    enable_return_data_recording: true,
+                },
+                transaction_account_lock_limit: Some(tx_account_lock_limit),
+            },
+        );
+
+        processing_results
+            .into_iter()
+            .zip(transactions)
+            .map(|(processing_result, transaction)| {
+                let units_consumed = processing_result
+                    .as_ref()
+                    .map(|processed_tx| processed_tx.executed_units())
+                    .unwrap_or_default();
+                Self::simulation_result(
+                    processing_result,
+                    transaction.account_keys().len(),
+                    units_consumed,
+                )
+            })
+            .collect()
+    }
+
+    fn simulation_result(
+        processing_result: TransactionProcessingResult,
+        number_of_accounts: usize,
+        units_consumed: u64,
+    ) -> (TransactionSimulationResult, FeeDetails) {
+        let fee_details = processing_result
+            .as_ref()
+            .map(|processed_tx| processed_tx.fee_details())
+            .unwrap_or_default();
         let (post_simulation_accounts, result, logs, return_data, inner_instructions) =
             match processing_result {
                 Ok(processed_tx) => match processed_tx {
@@ -3297,18 +3526,24 @@ impl Bank {
             };
         let logs = logs.unwrap_or_default();
 
-        TransactionSimulationResult {
-            result,
-            logs,
-            post_simulation_accounts,
-            units_consumed,
-            return_data,
-            inner_instructions,
-        }
+        (
+            TransactionSimulationResult {
+                result,
+                logs,
+                post_simulation_accounts,
+                units_consumed,
+                return_data,
+                inner_instructions,
+            },
+            fee_details,
+        )
     }
 
-    fn get_account_overrides_for_simulation(&self, account_keys: &AccountKeys) -> AccountOverrides {
-        let mut account_overrides = AccountOverrides::default();
+    fn get_account_overrides_for_simulation(
+        &self,
+        account_keys: &AccountKeys,
+        mut account_overrides: AccountOverrides,
+    ) -> AccountOverrides {
         let slot_history_id = sysvar::slot_history::id();
         if account_keys.iter().any(|pubkey| *pubkey == slot_history_id) {
             let current_account = self.get_account_with_fixed_root(&slot_history_id);
@@ -3385,8 +3620,11 @@ impl Bank {
 
         let (blockhash, blockhash_lamports_per_signature) =
             self.last_blockhash_and_lamports_per_signature();
-        let rent_collector_with_metrics =
-            RentCollectorWithMetrics::new(self.rent_collector.clone());
+        let rent_collector_with_metrics = RentCollectorWithMetrics::new(
+            self.rent_collector.clone(),
+            self.feature_set
+                .is_active(&feature_set::storage_deposit::id()),
+        );
         let processing_environment = TransactionProcessingEnvironment {
             blockhash,
             blockhash_lamports_per_signature,
@@ -3791,15 +4029,18 @@ impl Bank {
             update_transaction_statuses_us,
         );
 
-        Self::create_commit_results(processing_results)
+        self.create_commit_results(sanitized_txs, processing_results)
     }
 
     fn create_commit_results(
+        &self,
+        sanitized_txs: &[impl TransactionWithMeta],
         processing_results: Vec<TransactionProcessingResult>,
     ) -> Vec<TransactionCommitResult> {
-        processing_results
-            .into_iter()
-            .map(|processing_result| {
+        sanitized_txs
+            .iter()
+            .zip(processing_results)
+            .map(|(tx, processing_result)| {
                 let processing_result = processing_result?;
                 let executed_units = processing_result.executed_units();
                 let loaded_accounts_data_size = processing_result.loaded_accounts_data_size();
@@ -3828,6 +4069,7 @@ impl Bank {
                             return_data: execution_details.return_data,
                             executed_units,
                             fee_details,
+                            fee_breakdown: self.fee_breakdown(tx, &fee_details),
                             rent_debits,
                             loaded_account_stats: TransactionLoadedAccountsStats {
                                 loaded_accounts_count: loaded_accounts.len(),
@@ -3843,6 +4085,7 @@ impl Bank {
                         executed_units,
                         rent_debits: RentDebits::default(),
                         fee_details: fees_only_tx.fee_details,
+                        fee_breakdown: self.fee_breakdown(tx, &fees_only_tx.fee_details),
                         loaded_account_stats: TransactionLoadedAccountsStats {
                             loaded_accounts_count: fees_only_tx.rollback_accounts.count(),
                             loaded_accounts_data_size,
@@ -3868,7 +4111,7 @@ impl Bank {
         if let Some((account, _)) =
             self.get_account_modified_since_parent_with_fixed_root(&incinerator::id())
         {
-            self.capitalization.fetch_sub(account.lamports(), Relaxed);
+            self.change_capitalization(CapitalizationChange::Incinerated(account.lamports()));
             self.store_account(&incinerator::id(), &AccountSharedData::default());
         }
     }
@@ -4001,6 +4244,9 @@ impl Bank {
         accounts_written_this_slot
     }
 
+    /// Deprecated: rent is not collected anymore on X1, and once `disable_rent_scanning` is
+    /// active the partitions of accounts are not scanned at all. Rent exemption is still enforced
+    /// when accounts are created or resized, by the rent state transition checks of the SVM.
     fn collect_rent_eagerly(&self) {
         if self.lazy_rent_collection.load(Relaxed) {
             return;
@@ -4009,6 +4255,9 @@ impl Bank {
         if self
             .feature_set
             .is_active(&feature_set::disable_partitioned_rent_collection::id())
+            || self
+                .feature_set
+                .is_active(&feature_set::disable_rent_scanning::id())
         {
             return;
         }
@@ -4111,11 +4360,15 @@ impl Bank {
             .is_active(&feature_set::skip_rent_rewrites::id())
     }
 
-    /// true if rent fees should be collected (i.e. disable_rent_fees_collection is NOT enabled)
+    /// true if rent fees should be collected (i.e. neither disable_rent_fees_collection nor
+    /// disable_rent_scanning is enabled)
     fn should_collect_rent(&self) -> bool {
         !self
             .feature_set
             .is_active(&feature_set::disable_rent_fees_collection::id())
+            && !self
+                .feature_set
+                .is_active(&feature_set::disable_rent_scanning::id())
     }
 
     /// Collect rent from `accounts`
@@ -4808,40 +5061,40 @@ impl Bank {
         pubkey: &Pubkey,
         new_account: &AccountSharedData,
     ) {
-        let old_account_data_size =
-            if let Some(old_account) = self.get_account_with_fixed_root_no_cache(pubkey) {
-                match new_account.lamports().cmp(&old_account.lamports()) {
-                    std::cmp::Ordering::Greater => {
-                        let increased = new_account.lamports() - old_account.lamports();
-                        trace!(
-                            "store_account_and_update_capitalization: increased: {} {}",
-                            pubkey,
-                            increased
-                        );
-                        self.capitalization.fetch_add(increased, Relaxed);
-                    }
-                    std::cmp::Ordering::Less => {
-                        let decreased = old_account.lamports() - new_account.lamports();
-                        trace!(
-                            "store_account_and_update_capitalization: decreased: {} {}",
-                            pubkey,
-                            decreased
-                        );
-                        self.capitalization.fetch_sub(decreased, Relaxed);
-                    }
-                    std::cmp::Ordering::Equal => {}
+        let old_account_data_size = if let Some(old_account) =
+            self.get_account_with_fixed_root_no_cache(pubkey)
+        {
+            match new_account.lamports().cmp(&old_account.lamports()) {
+                std::cmp::Ordering::Greater => {
+                    let increased = new_account.lamports() - old_account.lamports();
+                    trace!(
+                        "store_account_and_update_capitalization: increased: {} {}",
+                        pubkey,
+                        increased
+                    );
+                    self.change_capitalization(CapitalizationChange::OtherMinted(increased));
                 }
-                old_account.data().len()
-            } else {
-                trace!(
-                    "store_account_and_update_capitalization: created: {} {}",
-                    pubkey,
-                    new_account.lamports()
-                );
-                self.capitalization
-                    .fetch_add(new_account.lamports(), Relaxed);
-                0
-            };
+                std::cmp::Ordering::Less => {
+                    let decreased = old_account.lamports() - new_account.lamports();
+                    trace!(
+                        "store_account_and_update_capitalization: decreased: {} {}",
+                        pubkey,
+                        decreased
+                    );
+                    self.change_capitalization(CapitalizationChange::OtherRemoved(decreased));
+                }
+                std::cmp::Ordering::Equal => {}
+            }
+            old_account.data().len()
+        } else {
+            trace!(
+                "store_account_and_update_capitalization: created: {} {}",
+                pubkey,
+                new_account.lamports()
+            );
+            self.change_capitalization(CapitalizationChange::OtherMinted(new_account.lamports()));
+            0
+        };
 
         self.store_account(pubkey, new_account);
         self.calculate_and_update_accounts_data_size_delta_off_chain(
@@ -4896,6 +5149,9 @@ impl Bank {
             );
         }
 
+        // Same for the parameters set by governance, which take precedence over the above
+        self.apply_active_parameters();
+
         // If the accounts delta hash is still in use, start the background account hasher
         if !self
             .feature_set
@@ -5137,6 +5393,17 @@ impl Bank {
             .get_logs_for_address(address)
     }
 
+    /// Returns the logs of the transactions which mentioned any of `addresses`
+    pub fn get_transaction_logs_for_addresses(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Option<Vec<TransactionLogInfo>> {
+        self.transaction_log_collector
+            .read()
+            .unwrap()
+            .get_logs_for_addresses(addresses)
+    }
+
     /// Returns all the accounts stored in this slot
     pub fn get_all_accounts_modified_since_parent(&self) -> Vec<TransactionAccount> {
         self.rc.accounts.load_by_program_slot(self.slot(), None)
@@ -6499,6 +6766,8 @@ impl Bank {
             );
         }
 
+        self.apply_state_migrations(&new_feature_activations);
+
         if new_feature_activations.contains(&feature_set::update_hashes_per_tick::id()) {
             self.apply_updated_hashes_per_tick(DEFAULT_HASHES_PER_TICK);
         }
@@ -6765,8 +7034,9 @@ impl Bank {
                 datapoint_info!(datapoint_name, ("slot", self.slot, i64));
 
                 // Burn lamports in the old account
-                self.capitalization
-                    .fetch_sub(old_account.lamports(), Relaxed);
+                self.change_capitalization(CapitalizationChange::OtherRemoved(
+                    old_account.lamports(),
+                ));
 
                 // Transfer new account to old account
                 self.store_account(old_address, &new_account);
@@ -6874,6 +7144,14 @@ impl Bank {
         &self.fee_structure
     }
 
+    /// Fee calculation inputs for this bank's feature set and governance parameters.
+    pub fn fee_features(&self) -> FeeFeatures {
+        FeeFeatures {
+            base_fee_multiplier: self.base_fee_multiplier,
+            ..FeeFeatures::from(self.feature_set.as_ref())
+        }
+    }
+
     pub fn block_id(&self) -> Option<Hash> {
         *self.block_id.read().unwrap()
     }
@@ -6976,7 +7254,10 @@ impl TransactionProcessingCallback for Bank {
             false, /* zero_fees_for_test */
             lamports_per_signature,
             prioritization_fee,
-            FeeFeatures::from(feature_set),
+            FeeFeatures {
+                base_fee_multiplier: self.base_fee_multiplier,
+                ..FeeFeatures::from(feature_set)
+            },
         )
     }
 }
@@ -7178,6 +7459,12 @@ impl Bank {
         &self.transaction_processor
     }
 
+    /// CPI distributions of the transactions executed by this bank and the banks sharing its
+    /// ancestry
+    pub fn cpi_stats(&self) -> &CpiStats {
+        &self.transaction_processor.cpi_stats
+    }
+
     pub fn set_fee_structure(&mut self, fee_structure: &FeeStructure) {
         self.fee_structure = fee_structure.clone();
     }
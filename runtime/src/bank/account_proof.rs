@@ -0,0 +1,229 @@
+//! Proofs that an account had specific contents at a bank hash, so light clients and bridges
+//! can verify the state of an account without replaying the ledger.
+//!
+//! The bank hash commits to the accounts delta hash, the merkle root of the hashes of the
+//! accounts written in the slot. A proof can therefore only be produced for a slot which wrote
+//! the account, and only while the bank hash still includes the accounts delta hash. The state
+//! of an account not written in a slot is not committed to by that slot's bank hash alone, so it
+//! cannot be proven at that bank hash; it is proven at the last slot which wrote it instead.
+
+use {
+    super::Bank,
+    agave_feature_set as feature_set,
+    solana_accounts_db::{
+        accounts_db::AccountsDb,
+        accounts_hash::{AccountsHasher, MerkleProofLevel},
+    },
+    solana_sdk::{
+        account::AccountSharedData,
+        clock::Slot,
+        hash::{extend_and_hash, hashv, Hash},
+        pubkey::Pubkey,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AccountProofError {
+    #[error("bank is not frozen")]
+    BankNotFrozen,
+    #[error(
+        "the bank hash does not include the accounts delta hash, proofs are not available once \
+         the remove_accounts_delta_hash feature is active"
+    )]
+    AccountsDeltaHashRemoved,
+    #[error(
+        "account {0} was not written in slot {1}, proofs are only available for the slots \
+         which wrote the account"
+    )]
+    AccountNotWritten(Pubkey, Slot),
+}
+
+/// Proof that `account` had its contents at `bank_hash`.
+///
+/// The proof is verified by hashing the account as `AccountsDb::hash_account()` does, folding
+/// the account hash with `merkle_proof` into `accounts_delta_hash`, and recomputing the bank
+/// hash from it as `Bank::hash_internal_state()` does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountProof {
+    pub slot: Slot,
+    pub bank_hash: Hash,
+    pub parent_hash: Hash,
+    pub signature_count: u64,
+    pub last_blockhash: Hash,
+    pub accounts_delta_hash: Hash,
+    /// The accounts lattice hash, hashed into the bank hash once it replaced the epoch
+    /// accounts hash
+    pub accounts_lt_hash: Option<Vec<u8>>,
+    pub epoch_accounts_hash: Option<Hash>,
+    pub hard_fork_data: Option<Vec<u8>>,
+    /// The account as written in `slot`. A deleted account has no lamports.
+    pub account: AccountSharedData,
+    pub merkle_proof: Vec<MerkleProofLevel>,
+}
+
+impl AccountProof {
+    /// Returns whether the proof shows `account` at `pubkey` is committed to by `bank_hash`
+    pub fn verify(&self, pubkey: &Pubkey) -> bool {
+        let account_hash = AccountsDb::hash_account(&self.account, pubkey);
+        if AccountsHasher::merkle_root_from_proof(account_hash.0, &self.merkle_proof)
+            != Some(self.accounts_delta_hash)
+        {
+            return false;
+        }
+
+        let mut hash = hashv(&[
+            self.parent_hash.as_ref(),
+            self.accounts_delta_hash.as_ref(),
+            &self.signature_count.to_le_bytes(),
+            self.last_blockhash.as_ref(),
+        ]);
+        if let Some(accounts_lt_hash) = &self.accounts_lt_hash {
+            hash = hashv(&[hash.as_ref(), accounts_lt_hash]);
+        } else if let Some(epoch_accounts_hash) = &self.epoch_accounts_hash {
+            hash = hashv(&[hash.as_ref(), epoch_accounts_hash.as_ref()]);
+        }
+        if let Some(hard_fork_data) = &self.hard_fork_data {
+            hash = extend_and_hash(&hash, hard_fork_data);
+        }
+        hash == self.bank_hash
+    }
+}
+
+impl Bank {
+    /// Returns the proof that the account at `pubkey` had its current contents at this bank's
+    /// hash. The account must have been written in this bank's slot.
+    pub fn account_proof(&self, pubkey: &Pubkey) -> Result<AccountProof, AccountProofError> {
+        if !self.is_frozen() {
+            return Err(AccountProofError::BankNotFrozen);
+        }
+        if self
+            .feature_set
+            .is_active(&feature_set::remove_accounts_delta_hash::id())
+        {
+            return Err(AccountProofError::AccountsDeltaHashRemoved);
+        }
+
+        let slot = self.slot();
+        let accounts_db = &self.rc.accounts.accounts_db;
+        let skipped_rewrites = self.skipped_rewrites.lock().unwrap().clone();
+        let (account_hash, merkle_proof) = accounts_db
+            .accounts_delta_hash_proof(slot, pubkey, skipped_rewrites)
+            .ok_or(AccountProofError::AccountNotWritten(*pubkey, slot))?;
+        let accounts_delta_hash =
+            AccountsHasher::merkle_root_from_proof(account_hash.0, &merkle_proof)
+                .expect("proof is well formed");
+
+        let (accounts_lt_hash, epoch_accounts_hash) = if self
+            .feature_set
+            .is_active(&feature_set::accounts_lt_hash::id())
+        {
+            let accounts_lt_hash = self.accounts_lt_hash.lock().unwrap();
+            let lt_hash_bytes: &[u8] = bytemuck::must_cast_slice(&accounts_lt_hash.0 .0);
+            (Some(lt_hash_bytes.to_vec()), None)
+        } else if self.should_include_epoch_accounts_hash() {
+            let epoch_accounts_hash = accounts_db
+                .epoch_accounts_hash_manager
+                .try_get_epoch_accounts_hash()
+                .map(|epoch_accounts_hash| *epoch_accounts_hash.as_ref());
+            (None, epoch_accounts_hash)
+        } else {
+            (None, None)
+        };
+
+        Ok(AccountProof {
+            slot,
+            bank_hash: self.hash(),
+            parent_hash: self.parent_hash(),
+            signature_count: self.signature_count(),
+            last_blockhash: self.last_blockhash(),
+            accounts_delta_hash,
+            accounts_lt_hash,
+            epoch_accounts_hash,
+            hard_fork_data: self
+                .hard_forks
+                .read()
+                .unwrap()
+                .get_hash_data(slot, self.parent_slot()),
+            // A deleted account is not loaded, and hashes the same as a default account
+            account: self.get_account(pubkey).unwrap_or_default(),
+            merkle_proof,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::{create_genesis_config, deactivate_features, GenesisConfigInfo},
+        solana_sdk::{
+            account::{ReadableAccount, WritableAccount},
+            signature::Signer,
+        },
+        std::sync::Arc,
+    };
+
+    #[test]
+    fn test_account_proof() {
+        let GenesisConfigInfo {
+            mut genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(1_000_000_000);
+        deactivate_features(
+            &mut genesis_config,
+            &vec![feature_set::remove_accounts_delta_hash::id()],
+        );
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+        let recipient = Pubkey::new_unique();
+        bank1
+            .transfer(1_000_000, &mint_keypair, &recipient)
+            .unwrap();
+        assert_eq!(
+            bank1.account_proof(&recipient),
+            Err(AccountProofError::BankNotFrozen)
+        );
+        bank1.freeze();
+
+        let proof = bank1.account_proof(&recipient).unwrap();
+        assert_eq!(proof.slot, 1);
+        assert_eq!(proof.bank_hash, bank1.hash());
+        assert_eq!(proof.account.lamports(), 1_000_000);
+        assert!(proof.verify(&recipient));
+        assert!(!proof.verify(&mint_keypair.pubkey()));
+        assert!(bank1
+            .account_proof(&mint_keypair.pubkey())
+            .unwrap()
+            .verify(&mint_keypair.pubkey()));
+
+        let mut tampered = proof.clone();
+        tampered.account.set_lamports(2_000_000);
+        assert!(!tampered.verify(&recipient));
+        let mut tampered = proof;
+        tampered.signature_count += 1;
+        assert!(!tampered.verify(&recipient));
+
+        let unwritten = Pubkey::new_unique();
+        assert_eq!(
+            bank1.account_proof(&unwritten),
+            Err(AccountProofError::AccountNotWritten(unwritten, 1))
+        );
+    }
+
+    #[test]
+    fn test_account_proof_without_accounts_delta_hash() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(1_000_000_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        bank.freeze();
+        assert_eq!(
+            bank.account_proof(&mint_keypair.pubkey()),
+            Err(AccountProofError::AccountsDeltaHashRemoved)
+        );
+    }
+}
@@ -71,6 +71,50 @@ impl Bank {
         }
     }
 
+    /// Recalculates the accounts lt hash from the index and compares it against the one
+    /// maintained incrementally
+    ///
+    /// Used while rolling out the accounts lt hash, so a divergence raises an alarm instead of
+    /// halting the node.  Returns if the accounts lt hashes match.
+    pub fn verify_accounts_lt_hash_from_index(&self) -> bool {
+        debug_assert!(self.is_frozen());
+        let slot = self.slot();
+        let (calculated_accounts_lt_hash, duration) = meas_dur!({
+            self.rc
+                .accounts
+                .accounts_db
+                .calculate_accounts_lt_hash_at_startup_from_index(&self.ancestors, slot)
+        });
+        let actual_accounts_lt_hash = self.accounts_lt_hash.lock().unwrap();
+        let calculated_checksum = calculated_accounts_lt_hash.0.checksum();
+        let actual_checksum = actual_accounts_lt_hash.0.checksum();
+        let is_match = calculated_accounts_lt_hash == *actual_accounts_lt_hash;
+        if is_match {
+            datapoint_info!(
+                "accounts_lt_hash_dual_mode",
+                ("slot", slot, i64),
+                ("duration_us", duration.as_micros(), i64),
+                ("checksum", actual_checksum.to_string(), String),
+            );
+        } else {
+            log::error!(
+                "accounts lattice hash divergence at slot {slot}! calculated checksum: \
+                 {calculated_checksum}, actual checksum: {actual_checksum}",
+            );
+            datapoint_error!(
+                "accounts_lt_hash_divergence",
+                ("slot", slot, i64),
+                (
+                    "calculated_checksum",
+                    calculated_checksum.to_string(),
+                    String
+                ),
+                ("actual_checksum", actual_checksum.to_string(), String),
+            );
+        }
+        is_match
+    }
+
     /// Calculates the lt hash *of only this slot*
     ///
     /// This can be thought of as akin to the accounts delta hash.
@@ -871,6 +915,61 @@ mod tests {
         assert_eq!(expected_accounts_lt_hash, calculated_accounts_lt_hash);
     }
 
+    #[test_case(Features::None; "no features")]
+    #[test_case(Features::All; "all features")]
+    fn test_verify_accounts_lt_hash_from_index(features: Features) {
+        let (genesis_config, mint_keypair) = genesis_config_with(features);
+        let (mut bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+        bank.rc
+            .accounts
+            .accounts_db
+            .set_is_experimental_accumulator_hash_enabled(features == Features::None);
+        assert!(bank.is_accounts_lt_hash_enabled());
+
+        let amount = cmp::max(
+            bank.get_minimum_balance_for_rent_exemption(0),
+            LAMPORTS_PER_SOL,
+        );
+        for _ in 0..3 {
+            let slot = bank.slot() + 1;
+            bank =
+                new_bank_from_parent_with_bank_forks(&bank_forks, bank, &Pubkey::default(), slot);
+            bank.register_unique_recent_blockhash_for_test();
+            bank.transfer(amount, &mint_keypair, &pubkey::new_rand())
+                .unwrap();
+            bank.freeze();
+        }
+        assert!(bank.verify_accounts_lt_hash_from_index());
+
+        // a divergence of the incrementally maintained accounts lt hash must be caught
+        bank.accounts_lt_hash
+            .lock()
+            .unwrap()
+            .0
+            .mix_in(&LtHash([0x1b; LtHash::NUM_ELEMENTS]));
+        assert!(!bank.verify_accounts_lt_hash_from_index());
+    }
+
+    #[test]
+    fn test_experimental_accumulator_hash_dual_mode_epoch() {
+        let accounts_db = AccountsDb::new_with_config(
+            Vec::new(),
+            Some(AccountsDbConfig {
+                experimental_accumulator_hash_dual_mode_epochs: 2,
+                ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+            }),
+            None,
+            Arc::default(),
+        );
+        // the epochs are counted from the first epoch asked about
+        assert!(accounts_db.is_experimental_accumulator_hash_dual_mode_epoch(5));
+        assert!(accounts_db.is_experimental_accumulator_hash_dual_mode_epoch(6));
+        assert!(!accounts_db.is_experimental_accumulator_hash_dual_mode_epoch(7));
+
+        let accounts_db = AccountsDb::new_single_for_tests();
+        assert!(!accounts_db.is_experimental_accumulator_hash_dual_mode_epoch(0));
+    }
+
     #[test_case(Features::None; "no features")]
     #[test_case(Features::All; "all features")]
     fn test_calculate_accounts_lt_hash_at_startup_from_storages(features: Features) {
@@ -4,7 +4,7 @@ mod target_builtin;
 mod target_core_bpf;
 
 use {
-    crate::bank::Bank,
+    crate::bank::{supply_audit::CapitalizationChange, Bank},
     error::CoreBpfMigrationError,
     num_traits::{CheckedAdd, CheckedSub},
     solana_builtins::core_bpf_migration::CoreBpfMigrationConfig,
@@ -22,7 +22,7 @@ use {
     },
     solana_transaction_context::TransactionContext,
     source_buffer::SourceBuffer,
-    std::{cmp::Ordering, sync::atomic::Ordering::Relaxed},
+    std::cmp::Ordering,
     target_builtin::TargetBuiltin,
     target_core_bpf::TargetCoreBpf,
 };
@@ -260,12 +260,16 @@ impl Bank {
         // Update the bank's capitalization.
         match lamports_to_burn.cmp(&lamports_to_fund) {
             Ordering::Greater => {
-                self.capitalization
-                    .fetch_sub(checked_sub(lamports_to_burn, lamports_to_fund)?, Relaxed);
+                self.change_capitalization(CapitalizationChange::OtherRemoved(checked_sub(
+                    lamports_to_burn,
+                    lamports_to_fund,
+                )?));
             }
             Ordering::Less => {
-                self.capitalization
-                    .fetch_add(checked_sub(lamports_to_fund, lamports_to_burn)?, Relaxed);
+                self.change_capitalization(CapitalizationChange::OtherMinted(checked_sub(
+                    lamports_to_fund,
+                    lamports_to_burn,
+                )?));
             }
             Ordering::Equal => (),
         }
@@ -354,12 +358,16 @@ impl Bank {
         // Update the bank's capitalization.
         match lamports_to_burn.cmp(&lamports_to_fund) {
             Ordering::Greater => {
-                self.capitalization
-                    .fetch_sub(checked_sub(lamports_to_burn, lamports_to_fund)?, Relaxed);
+                self.change_capitalization(CapitalizationChange::OtherRemoved(checked_sub(
+                    lamports_to_burn,
+                    lamports_to_fund,
+                )?));
             }
             Ordering::Less => {
-                self.capitalization
-                    .fetch_add(checked_sub(lamports_to_fund, lamports_to_burn)?, Relaxed);
+                self.change_capitalization(CapitalizationChange::OtherMinted(checked_sub(
+                    lamports_to_fund,
+                    lamports_to_burn,
+                )?));
             }
             Ordering::Equal => (),
         }
@@ -392,7 +400,7 @@ pub(crate) mod tests {
             clock::Slot,
             native_loader,
         },
-        std::{fs::File, io::Read},
+        std::{fs::File, io::Read, sync::atomic::Ordering::Relaxed},
         test_case::test_case,
     };
 
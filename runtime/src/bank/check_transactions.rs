@@ -1,5 +1,6 @@
 use {
     super::{Bank, BankStatusCache},
+    agave_feature_set as feature_set,
     solana_accounts_db::blockhash_queue::BlockhashQueue,
     solana_perf::perf_libs,
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
@@ -84,6 +85,9 @@ impl Bank {
         let next_lamports_per_signature = hash_queue
             .get_lamports_per_signature(&last_blockhash)
             .unwrap();
+        let nonce_fees_ignore_stored_lamports_per_signature = self
+            .feature_set
+            .is_active(&feature_set::nonce_fees_ignore_stored_lamports_per_signature::id());
 
         sanitized_txs
             .iter()
@@ -95,6 +99,7 @@ impl Bank {
                     &next_durable_nonce,
                     &hash_queue,
                     next_lamports_per_signature,
+                    nonce_fees_ignore_stored_lamports_per_signature,
                     error_counters,
                 ),
                 Err(e) => Err(e.clone()),
@@ -109,6 +114,7 @@ impl Bank {
         next_durable_nonce: &DurableNonce,
         hash_queue: &BlockhashQueue,
         next_lamports_per_signature: u64,
+        nonce_fees_ignore_stored_lamports_per_signature: bool,
         error_counters: &mut TransactionErrorMetrics,
     ) -> TransactionCheckResult {
         let recent_blockhash = tx.recent_blockhash();
@@ -124,9 +130,19 @@ impl Bank {
                 next_lamports_per_signature,
             )
         {
+            // Fees are derived from compute units, so the only meaning left in
+            // lamports_per_signature is whether fees are charged at all. A nonce
+            // that was last advanced while fees were disabled (eg. in the first
+            // slots after genesis) stores zero, which would otherwise let every
+            // transaction using it skip fees entirely.
+            let lamports_per_signature = if nonce_fees_ignore_stored_lamports_per_signature {
+                next_lamports_per_signature
+            } else {
+                previous_lamports_per_signature
+            };
             Ok(CheckedTransactionDetails::new(
                 Some(nonce),
-                previous_lamports_per_signature,
+                lamports_per_signature,
             ))
         } else {
             error_counters.blockhash_not_found += 1;
@@ -294,6 +310,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_check_transaction_age_nonce_with_zero_stored_lamports_per_signature() {
+        let (bank, _mint_keypair, custodian_keypair, nonce_keypair, _) = setup_nonce_with_bank(
+            10_000_000,
+            |_| {},
+            5_000_000,
+            250_000,
+            None,
+            FeatureSet::all_enabled(),
+        )
+        .unwrap();
+        let custodian_pubkey = custodian_keypair.pubkey();
+        let nonce_pubkey = nonce_keypair.pubkey();
+
+        // store zero lamports_per_signature, as if the nonce was last advanced while fees were
+        // disabled
+        let mut nonce_account = bank.get_account(&nonce_pubkey).unwrap();
+        let nonce_data = get_nonce_data_from_account(&nonce_account).unwrap();
+        nonce_account
+            .set_state(&NonceVersions::new(NonceState::new_initialized(
+                &nonce_data.authority,
+                nonce_data.durable_nonce,
+                0,
+            )))
+            .unwrap();
+        bank.store_account(&nonce_pubkey, &nonce_account);
+
+        let nonce_hash = get_nonce_blockhash(&bank, &nonce_pubkey).unwrap();
+        let message = new_sanitized_message(Message::new_with_blockhash(
+            &[
+                system_instruction::advance_nonce_account(&nonce_pubkey, &nonce_pubkey),
+                system_instruction::transfer(&custodian_pubkey, &nonce_pubkey, 100_000),
+            ],
+            Some(&custodian_pubkey),
+            &nonce_hash,
+        ));
+        let (_, next_lamports_per_signature) = bank.last_blockhash_and_lamports_per_signature();
+        assert_ne!(next_lamports_per_signature, 0);
+        let next_durable_nonce = bank.next_durable_nonce();
+        let (nonce_info, stored_lamports_per_signature) = bank
+            .check_load_and_advance_message_nonce_account(
+                &message,
+                &next_durable_nonce,
+                next_lamports_per_signature,
+            )
+            .unwrap();
+        assert_eq!(stored_lamports_per_signature, 0);
+
+        let hash_queue = bank.blockhash_queue.read().unwrap();
+        for (nonce_fees_ignore_stored_lamports_per_signature, expected_lamports_per_signature) in
+            [(false, 0), (true, next_lamports_per_signature)]
+        {
+            assert_eq!(
+                bank.check_transaction_age(
+                    &message,
+                    MAX_PROCESSING_AGE,
+                    &next_durable_nonce,
+                    &hash_queue,
+                    next_lamports_per_signature,
+                    nonce_fees_ignore_stored_lamports_per_signature,
+                    &mut TransactionErrorMetrics::default(),
+                ),
+                Ok(CheckedTransactionDetails::new(
+                    Some(nonce_info.clone()),
+                    expected_lamports_per_signature,
+                )),
+            );
+        }
+    }
+
     #[test]
     fn test_check_and_load_message_nonce_account_not_nonce_fail() {
         let (bank, _mint_keypair, custodian_keypair, nonce_keypair, _) = setup_nonce_with_bank(
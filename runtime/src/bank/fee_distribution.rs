@@ -1,9 +1,8 @@
 use {
-    super::Bank,
+    super::{supply_audit::CapitalizationChange, Bank},
     crate::bank::CollectorFeeDetails,
     agave_feature_set::reward_full_priority_fee,
     log::{debug, warn},
-    solana_fee::FeeFeatures,
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
     solana_sdk::{
         account::{ReadableAccount, WritableAccount},
@@ -29,6 +28,27 @@ enum DepositFeeError {
     InvalidAccountOwner,
 }
 
+/// Transaction fees collected by a bank and how they were split between the leader, the fee
+/// treasury and the burn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BankFeeSummary {
+    /// Signature fees, which also include the priority fees until `reward_full_priority_fee` is
+    /// active
+    pub transaction_fee: u64,
+    pub priority_fee: u64,
+    pub burned: u64,
+    /// Lamports deposited into the account of the leader
+    pub leader_reward: u64,
+    /// Lamports deposited into the fee treasury instead of being burned
+    pub treasury: u64,
+}
+
+impl BankFeeSummary {
+    pub fn total(&self) -> u64 {
+        self.transaction_fee.saturating_add(self.priority_fee)
+    }
+}
+
 impl Bank {
     // Distribute collected transaction fees for this slot to collector_id (= current leader).
     //
@@ -48,10 +68,11 @@ impl Bank {
         let collector_fees = self.collector_fees.load(Relaxed);
         if collector_fees != 0 {
             let (deposit, mut burn) = self.calculate_reward_and_burn_fees(collector_fees);
+            self.deposit_treasury_fee(collector_fees, &mut burn);
             if deposit > 0 {
                 self.deposit_or_burn_fee(deposit, &mut burn);
             }
-            self.capitalization.fetch_sub(burn, Relaxed);
+            self.change_capitalization(CapitalizationChange::FeesBurned(burn));
         }
     }
 
@@ -65,11 +86,63 @@ impl Bank {
         }
 
         let (deposit, mut burn) = self.calculate_reward_and_burn_fee_details(&fee_details);
+        self.deposit_treasury_fee(fee_details.transaction_fee, &mut burn);
 
         if deposit > 0 {
             self.deposit_or_burn_fee(deposit, &mut burn);
         }
-        self.capitalization.fetch_sub(burn, Relaxed);
+        self.change_capitalization(CapitalizationChange::FeesBurned(burn));
+    }
+
+    /// Returns the fees collected by this bank. The split between the leader, the fee treasury and
+    /// the burn is only known once the bank is frozen.
+    pub fn fee_summary(&self) -> BankFeeSummary {
+        let (transaction_fee, priority_fee) = self.collected_transaction_and_priority_fees();
+        let leader_reward = self
+            .rewards
+            .read()
+            .unwrap()
+            .iter()
+            .find(|(pubkey, reward_info)| {
+                pubkey == &self.collector_id && reward_info.reward_type == RewardType::Fee
+            })
+            .map(|(_, reward_info)| reward_info.lamports as u64)
+            .unwrap_or_default();
+        let treasury = self.treasury_fees();
+        let total = transaction_fee.saturating_add(priority_fee);
+        BankFeeSummary {
+            transaction_fee,
+            priority_fee,
+            burned: if self.is_frozen() {
+                total.saturating_sub(leader_reward).saturating_sub(treasury)
+            } else {
+                0
+            },
+            leader_reward,
+            treasury,
+        }
+    }
+
+    /// Returns the transaction fees and the priority fees collected by this bank, the former
+    /// including the latter until `reward_full_priority_fee` is active
+    fn collected_transaction_and_priority_fees(&self) -> (u64, u64) {
+        if self.feature_set.is_active(&reward_full_priority_fee::id()) {
+            let fee_details = self.collector_fee_details.read().unwrap();
+            (fee_details.transaction_fee, fee_details.priority_fee)
+        } else {
+            (self.collector_fees.load(Relaxed), 0)
+        }
+    }
+
+    /// Returns the base fees this bank deposited into the fee treasury
+    pub(super) fn treasury_fees(&self) -> u64 {
+        self.treasury_fees.load(Relaxed)
+    }
+
+    /// Returns the fees collected by this bank, including priority fees
+    pub(super) fn collected_fees(&self) -> u64 {
+        let (transaction_fee, priority_fee) = self.collected_transaction_and_priority_fees();
+        transaction_fee.saturating_add(priority_fee)
     }
 
     pub fn calculate_reward_for_transaction(
@@ -82,7 +155,7 @@ impl Bank {
             self.get_lamports_per_signature() == 0,
             self.fee_structure().lamports_per_signature,
             fee_budget_limits.prioritization_fee,
-            FeeFeatures::from(self.feature_set.as_ref()),
+            self.fee_features(),
         );
         let (reward, _burn) = if self.feature_set.is_active(&reward_full_priority_fee::id()) {
             self.calculate_reward_and_burn_fee_details(&CollectorFeeDetails::from(fee_details))
@@ -109,6 +182,36 @@ impl Bank {
         (deposit.saturating_add(fee_details.priority_fee), burn)
     }
 
+    /// Deposits the share of `base_fee` of the fee treasury into its account, out of the fees
+    /// which would otherwise be burned
+    fn deposit_treasury_fee(&self, base_fee: u64, burn: &mut u64) {
+        let Some(config) = self.fee_treasury_config() else {
+            return;
+        };
+        let treasury_fee = config.treasury_fee(base_fee).min(*burn);
+        if treasury_fee == 0 {
+            return;
+        }
+        match self.deposit_fees(&config.treasury, treasury_fee) {
+            Ok(_) => {
+                self.treasury_fees.store(treasury_fee, Relaxed);
+                *burn -= treasury_fee;
+            }
+            Err(err) => {
+                warn!(
+                    "Burned {} lamport tx fee instead of sending to treasury {} due to {}",
+                    treasury_fee, config.treasury, err
+                );
+                datapoint_warn!(
+                    "bank-burned_treasury_fee",
+                    ("slot", self.slot(), i64),
+                    ("num_lamports", treasury_fee, i64),
+                    ("error", err.to_string(), String),
+                );
+            }
+        }
+    }
+
     fn deposit_or_burn_fee(&self, deposit: u64, burn: &mut u64) {
         match self.deposit_fees(&self.collector_id, deposit) {
             Ok(post_balance) => {
@@ -211,8 +314,7 @@ impl Bank {
         #[cfg(test)]
         if validator_stakes.is_empty() {
             // some tests bank.freezes() with bad staking state
-            self.capitalization
-                .fetch_sub(rent_to_be_distributed, Relaxed);
+            self.change_capitalization(CapitalizationChange::RentBurned(rent_to_be_distributed));
             return;
         }
         #[cfg(not(test))]
@@ -282,7 +384,7 @@ impl Bank {
         self.rewards.write().unwrap().append(&mut rewards);
 
         if rent_to_burn > 0 {
-            self.capitalization.fetch_sub(rent_to_burn, Relaxed);
+            self.change_capitalization(CapitalizationChange::RentBurned(rent_to_burn));
             datapoint_warn!(
                 "bank-burned_rent",
                 ("slot", self.slot(), i64),
@@ -312,7 +414,7 @@ impl Bank {
             "distributed rent: {} (rounded from: {}, burned: {})",
             rent_to_be_distributed, total_rent_collected, burned_portion
         );
-        self.capitalization.fetch_sub(burned_portion, Relaxed);
+        self.change_capitalization(CapitalizationChange::RentBurned(burned_portion));
 
         if rent_to_be_distributed == 0 {
             return;
@@ -326,9 +428,12 @@ impl Bank {
 pub mod tests {
     use {
         super::*,
-        crate::genesis_utils::{
-            create_genesis_config, create_genesis_config_with_leader,
-            create_genesis_config_with_vote_accounts, ValidatorVoteKeypairs,
+        crate::{
+            bank::fee_treasury::{self, FeeTreasuryConfig},
+            genesis_utils::{
+                create_genesis_config, create_genesis_config_with_leader,
+                create_genesis_config_with_vote_accounts, ValidatorVoteKeypairs,
+            },
         },
         solana_sdk::{
             account::AccountSharedData, native_token::sol_to_lamports, pubkey, rent::Rent,
@@ -849,6 +954,100 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_fee_summary() {
+        let genesis = create_genesis_config(0);
+        let mut bank = Bank::new_for_tests(&genesis.genesis_config);
+        let transaction_fee = 100;
+        let priority_fee = 200;
+        bank.collector_fee_details = RwLock::new(CollectorFeeDetails {
+            transaction_fee,
+            priority_fee,
+        });
+        let (expected_deposit, expected_burn) = bank.fee_rate_governor.burn(transaction_fee);
+        assert_eq!(
+            bank.fee_summary(),
+            BankFeeSummary {
+                transaction_fee,
+                priority_fee,
+                burned: 0,
+                leader_reward: 0,
+                treasury: 0,
+            }
+        );
+
+        bank.freeze();
+        let fee_summary = bank.fee_summary();
+        assert_eq!(
+            fee_summary,
+            BankFeeSummary {
+                transaction_fee,
+                priority_fee,
+                burned: expected_burn,
+                leader_reward: expected_deposit + priority_fee,
+                treasury: 0,
+            }
+        );
+        assert_eq!(fee_summary.total(), transaction_fee + priority_fee);
+    }
+
+    #[test]
+    fn test_distribute_transaction_fee_details_to_treasury() {
+        let mut genesis = create_genesis_config(0);
+        let treasury = Pubkey::new_unique();
+        let config = FeeTreasuryConfig::new(treasury, 30).unwrap();
+        genesis.genesis_config.add_account(
+            fee_treasury::config::id(),
+            config.create_account(&genesis.genesis_config.rent),
+        );
+        let mut bank = Bank::new_for_tests(&genesis.genesis_config);
+        let transaction_fee = 1_000;
+        let priority_fee = 200;
+        bank.collector_fee_details = RwLock::new(CollectorFeeDetails {
+            transaction_fee,
+            priority_fee,
+        });
+        let (expected_deposit, expected_burn) = bank.fee_rate_governor.burn(transaction_fee);
+        let expected_treasury = 300;
+        assert!(expected_burn >= expected_treasury);
+
+        let initial_capitalization = bank.capitalization();
+        bank.freeze();
+        assert_eq!(bank.get_balance(&treasury), expected_treasury);
+        assert_eq!(
+            bank.capitalization(),
+            initial_capitalization - (expected_burn - expected_treasury)
+        );
+        assert_eq!(
+            bank.fee_summary(),
+            BankFeeSummary {
+                transaction_fee,
+                priority_fee,
+                burned: expected_burn - expected_treasury,
+                leader_reward: expected_deposit + priority_fee,
+                treasury: expected_treasury,
+            }
+        );
+
+        // The treasury is credited at most the fees which would otherwise be burned
+        let config = FeeTreasuryConfig::new(treasury, 100).unwrap();
+        genesis.genesis_config.add_account(
+            fee_treasury::config::id(),
+            config.create_account(&genesis.genesis_config.rent),
+        );
+        let mut bank = Bank::new_for_tests(&genesis.genesis_config);
+        bank.collector_fee_details = RwLock::new(CollectorFeeDetails {
+            transaction_fee,
+            priority_fee,
+        });
+        bank.freeze();
+        assert_eq!(bank.get_balance(&treasury), expected_burn);
+        let fee_summary = bank.fee_summary();
+        assert_eq!(fee_summary.burned, 0);
+        assert_eq!(fee_summary.leader_reward, expected_deposit + priority_fee);
+        assert_eq!(fee_summary.treasury, expected_burn);
+    }
+
     #[test]
     fn test_distribute_transaction_fee_details_zero() {
         let genesis = create_genesis_config(0);
@@ -0,0 +1,113 @@
+use {
+    super::Bank,
+    agave_feature_set as feature_set,
+    solana_fee::{MIN_COMPUTE_UNITS_THRESHOLD, MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS},
+    solana_sdk::{
+        account::{Account, AccountSharedData, ReadableAccount},
+        sysvar,
+    },
+    tachyon_fee_parameters::FeeParameters,
+};
+
+impl Bank {
+    /// The parameters this bank charges transaction fees with
+    pub fn fee_parameters(&self) -> FeeParameters {
+        FeeParameters {
+            base_fee_multiplier: self.base_fee_multiplier,
+            min_compute_unit_price: MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+            min_compute_units_threshold: MIN_COMPUTE_UNITS_THRESHOLD,
+        }
+    }
+
+    /// Records the fee parameters in their sysvar account if they changed. Must be called after
+    /// the governance parameters of the slot were applied and before any transaction of the
+    /// slot executes.
+    pub(super) fn update_fee_parameters(&self) {
+        if !self
+            .feature_set
+            .is_active(&feature_set::fee_parameters_sysvar::id())
+        {
+            return;
+        }
+        let fee_parameters = self.fee_parameters();
+        let recorded = self
+            .get_account_with_fixed_root(&tachyon_fee_parameters::id())
+            .and_then(|account| FeeParameters::from_account_data(account.data()));
+        if recorded == Some(fee_parameters) {
+            return;
+        }
+        self.update_sysvar_account(&tachyon_fee_parameters::id(), |account| {
+            let (lamports, rent_epoch) = self.inherit_specially_retained_account_fields(account);
+            AccountSharedData::from(Account {
+                lamports,
+                data: bincode::serialize(&fee_parameters).unwrap(),
+                owner: sysvar::id(),
+                executable: false,
+                rent_epoch,
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::{create_genesis_config, deactivate_features},
+        solana_fee::BASE_FEE_MULTIPLIER,
+        solana_sdk::pubkey::Pubkey,
+        std::sync::Arc,
+    };
+
+    fn recorded_fee_parameters(bank: &Bank) -> Option<FeeParameters> {
+        bank.get_account(&tachyon_fee_parameters::id())
+            .and_then(|account| FeeParameters::from_account_data(account.data()))
+    }
+
+    #[test]
+    fn test_update_fee_parameters() {
+        let genesis_config = create_genesis_config(1_000_000_000).genesis_config;
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+        let fee_parameters = recorded_fee_parameters(&bank1).unwrap();
+        assert_eq!(fee_parameters, bank1.fee_parameters());
+        assert_eq!(fee_parameters.base_fee_multiplier, BASE_FEE_MULTIPLIER);
+        assert_eq!(
+            *bank1
+                .transaction_processor
+                .sysvar_cache()
+                .get_fee_parameters()
+                .unwrap(),
+            fee_parameters
+        );
+
+        // Unchanged parameters are not rewritten
+        let mut bank2 = Bank::new_from_parent(Arc::new(bank1), &Pubkey::default(), 2);
+        assert_eq!(
+            bank2.get_account_modified_slot(&tachyon_fee_parameters::id()),
+            bank2
+                .get_account(&tachyon_fee_parameters::id())
+                .map(|account| (account, 1))
+        );
+
+        // A multiplier set by governance is recorded in the next slot
+        bank2.base_fee_multiplier = 25;
+        let bank3 = Bank::new_from_parent(Arc::new(bank2), &Pubkey::default(), 3);
+        assert_eq!(
+            recorded_fee_parameters(&bank3).unwrap().base_fee_multiplier,
+            25
+        );
+    }
+
+    #[test]
+    fn test_fee_parameters_inactive() {
+        let mut genesis_config = create_genesis_config(1_000_000_000).genesis_config;
+        deactivate_features(
+            &mut genesis_config,
+            &vec![feature_set::fee_parameters_sysvar::id()],
+        );
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), 1);
+        assert_eq!(recorded_fee_parameters(&bank1), None);
+    }
+}
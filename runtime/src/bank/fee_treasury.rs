@@ -0,0 +1,125 @@
+//! Routing of a share of the base fees to the fee treasury.
+//!
+//! Genesis may define a treasury account and the percentage of the base fees of every block it
+//! is credited with, in the account at [`config::id`]. With the `route_base_fees_to_treasury`
+//! feature active, that share of the base fees is deposited into the treasury instead of being
+//! burned. The leader's share is unchanged, so the treasury is credited at most the portion of
+//! the base fees which would otherwise be burned.
+
+/// The account holding the [`FeeTreasuryConfig`], created at genesis. It is owned by the sysvar
+/// program so that transactions cannot change it, and reserved once the feature is active so
+/// that transactions cannot write lock it either.
+pub use agave_reserved_account_keys::fee_treasury_config as config;
+use {
+    super::Bank,
+    agave_feature_set::route_base_fees_to_treasury,
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar,
+    },
+};
+
+pub const MAX_BASE_FEE_PERCENTAGE: u8 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeTreasuryConfig {
+    pub treasury: Pubkey,
+    /// Percentage of the base fees credited to the treasury instead of being burned
+    pub base_fee_percentage: u8,
+}
+
+impl FeeTreasuryConfig {
+    pub fn new(treasury: Pubkey, base_fee_percentage: u8) -> Result<Self, String> {
+        if base_fee_percentage > MAX_BASE_FEE_PERCENTAGE {
+            return Err(format!(
+                "fee treasury percentage {base_fee_percentage} exceeds \
+                 {MAX_BASE_FEE_PERCENTAGE}"
+            ));
+        }
+        Ok(Self {
+            treasury,
+            base_fee_percentage,
+        })
+    }
+
+    pub fn from_account(account: &impl ReadableAccount) -> Option<Self> {
+        if !sysvar::check_id(account.owner()) {
+            return None;
+        }
+        let config: Self = bincode::deserialize(account.data()).ok()?;
+        Self::new(config.treasury, config.base_fee_percentage).ok()
+    }
+
+    /// Returns the rent exempt account at [`config::id`] holding this config
+    pub fn create_account(&self, rent: &Rent) -> AccountSharedData {
+        let data = bincode::serialize(self).unwrap();
+        let mut account =
+            AccountSharedData::new(rent.minimum_balance(data.len()), data.len(), &sysvar::id());
+        account.set_data_from_slice(&data);
+        account
+    }
+
+    /// Returns the share of `base_fee` credited to the treasury
+    pub fn treasury_fee(&self, base_fee: u64) -> u64 {
+        (u128::from(base_fee) * u128::from(self.base_fee_percentage) / 100) as u64
+    }
+}
+
+impl Bank {
+    /// The fee treasury this bank credits base fees to, if any
+    pub fn fee_treasury_config(&self) -> Option<FeeTreasuryConfig> {
+        if !self
+            .feature_set
+            .is_active(&route_base_fees_to_treasury::id())
+        {
+            return None;
+        }
+        self.get_account_with_fixed_root(&config::id())
+            .and_then(|account| FeeTreasuryConfig::from_account(&account))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::{create_genesis_config, deactivate_features},
+        solana_sdk::account::WritableAccount,
+    };
+
+    #[test]
+    fn test_fee_treasury_config() {
+        let treasury = Pubkey::new_unique();
+        assert!(FeeTreasuryConfig::new(treasury, 101).is_err());
+        let config = FeeTreasuryConfig::new(treasury, 30).unwrap();
+        assert_eq!(config.treasury_fee(10_000), 3_000);
+        assert_eq!(config.treasury_fee(1), 0);
+        let config_100 = FeeTreasuryConfig::new(treasury, 100).unwrap();
+        assert_eq!(config_100.treasury_fee(u64::MAX), u64::MAX);
+
+        let mut account = config.create_account(&Rent::default());
+        assert_eq!(FeeTreasuryConfig::from_account(&account), Some(config));
+        account.set_owner(Pubkey::new_unique());
+        assert_eq!(FeeTreasuryConfig::from_account(&account), None);
+    }
+
+    #[test]
+    fn test_bank_fee_treasury_config() {
+        let config = FeeTreasuryConfig::new(Pubkey::new_unique(), 50).unwrap();
+        let mut genesis_config = create_genesis_config(1_000_000).genesis_config;
+        genesis_config.add_account(config::id(), config.create_account(&genesis_config.rent));
+        let bank = Bank::new_for_tests(&genesis_config);
+        assert_eq!(bank.fee_treasury_config(), Some(config));
+        assert!(bank.get_reserved_account_keys().contains(&config::id()));
+
+        deactivate_features(
+            &mut genesis_config,
+            &vec![route_base_fees_to_treasury::id()],
+        );
+        let bank = Bank::new_for_tests(&genesis_config);
+        assert_eq!(bank.fee_treasury_config(), None);
+        assert!(!bank.get_reserved_account_keys().contains(&config::id()));
+    }
+}
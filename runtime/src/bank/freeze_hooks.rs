@@ -0,0 +1,437 @@
+//! Hooks run when a bank is frozen, so that optional components such as invariant checkers and
+//! supply auditors can watch X1 specific runtime changes without participating in consensus.
+//!
+//! Hooks are registered on a bank and shared by every bank descending from it. Each hook runs on
+//! its own thread, from a summary of the frozen bank, and never delays freezing: summaries are
+//! dropped while the hook is still busy with earlier banks. Violations found by a hook, as well
+//! as hooks panicking or exceeding their time budget, are only logged and reported to metrics.
+
+use {
+    super::{supply_audit::CapitalizationDelta, Bank},
+    crossbeam_channel::{bounded, Sender, TrySendError},
+    log::*,
+    solana_measure::measure::Measure,
+    solana_sdk::{
+        clock::{Epoch, Slot},
+        hash::Hash,
+        pubkey::Pubkey,
+        reward_info::RewardInfo,
+        reward_type::RewardType,
+    },
+    std::{
+        fmt,
+        panic::{catch_unwind, AssertUnwindSafe},
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, JoinHandle},
+        time::Duration,
+    },
+};
+
+/// Frozen banks queued to a hook still busy with an earlier one before summaries are dropped
+const MAX_QUEUED_SUMMARIES: usize = 4;
+
+/// Time budget of the hooks registered by the validator, the duration of a slot
+pub const DEFAULT_FREEZE_HOOK_TIME_BUDGET: Duration = Duration::from_millis(400);
+
+/// The state of a bank at the time it was frozen
+#[derive(Debug, Clone)]
+pub struct BankFreezeSummary {
+    pub slot: Slot,
+    pub epoch: Epoch,
+    pub bank_hash: Hash,
+    pub capitalization: u64,
+    /// Changes of capitalization made by the bank, by cause
+    pub capitalization_delta: CapitalizationDelta,
+    /// Transaction fees collected by the bank, including priority fees
+    pub collected_fees: u64,
+    /// Base fees deposited into the fee treasury instead of being burned
+    pub treasury_fees: u64,
+    pub collected_rent: u64,
+    pub transaction_count: u64,
+    pub signature_count: u64,
+    /// Rewards credited or debited by the bank
+    pub rewards: Vec<(Pubkey, RewardInfo)>,
+    pub parent_slot: Slot,
+    /// Epoch of the parent, `None` if the parent bank is no longer available
+    pub parent_epoch: Option<Epoch>,
+    /// Capitalization of the parent, `None` if the parent bank is no longer available
+    pub parent_capitalization: Option<u64>,
+}
+
+impl BankFreezeSummary {
+    pub(super) fn new(bank: &Bank, bank_hash: Hash) -> Self {
+        let parent = bank.parent();
+        Self {
+            slot: bank.slot(),
+            epoch: bank.epoch(),
+            bank_hash,
+            capitalization: bank.capitalization(),
+            capitalization_delta: bank.capitalization_delta(),
+            collected_fees: bank.collected_fees(),
+            treasury_fees: bank.treasury_fees(),
+            collected_rent: bank.collected_rent.load(Ordering::Relaxed),
+            transaction_count: bank.transaction_count(),
+            signature_count: bank.signature_count(),
+            rewards: bank.rewards.read().unwrap().clone(),
+            parent_slot: bank.parent_slot(),
+            parent_epoch: parent.as_ref().map(|parent| parent.epoch()),
+            parent_capitalization: parent.as_ref().map(|parent| parent.capitalization()),
+        }
+    }
+
+    /// Returns whether the bank is the first of its epoch
+    pub fn is_epoch_boundary(&self) -> bool {
+        self.parent_epoch
+            .is_some_and(|parent_epoch| parent_epoch != self.epoch)
+    }
+}
+
+/// A check of frozen banks.
+///
+/// Hooks only see a summary of the bank, and the panics of a hook are caught, but processes
+/// installing a panic hook which exits, such as the validator, still exit when a hook panics.
+pub trait BankFreezeHook: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Checks a frozen bank, returning a description of every violation found
+    fn check(&self, summary: &BankFreezeSummary) -> Vec<String>;
+}
+
+struct RegisteredHook {
+    name: String,
+    sender: Sender<Arc<BankFreezeSummary>>,
+    dropped_summaries: AtomicU64,
+    _thread: JoinHandle<()>,
+}
+
+/// The hooks run when the banks sharing them are frozen
+#[derive(Default)]
+pub struct BankFreezeHooks {
+    hooks: RwLock<Vec<RegisteredHook>>,
+}
+
+impl fmt::Debug for BankFreezeHooks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hooks = self.hooks.read().unwrap();
+        f.debug_list()
+            .entries(hooks.iter().map(|hook| &hook.name))
+            .finish()
+    }
+}
+
+impl BankFreezeHooks {
+    /// Starts running `hook` on every bank frozen from now on, on a dedicated thread.
+    ///
+    /// Running longer than `time_budget` does not interrupt a hook but is reported, and the
+    /// banks frozen while the hook is busy are checked once it catches up, up to a few.
+    pub fn register(&self, hook: Arc<dyn BankFreezeHook>, time_budget: Duration) {
+        let name = hook.name().to_string();
+        let (sender, receiver) = bounded::<Arc<BankFreezeSummary>>(MAX_QUEUED_SUMMARIES);
+        let mut hooks = self.hooks.write().unwrap();
+        let thread = thread::Builder::new()
+            .name(format!("solFrzHook{:02}", hooks.len()))
+            .spawn(move || {
+                for summary in receiver.iter() {
+                    run_hook(hook.as_ref(), &summary, time_budget);
+                }
+            })
+            .unwrap();
+        info!("Registered bank freeze hook {name}, time budget {time_budget:?}");
+        hooks.push(RegisteredHook {
+            name,
+            sender,
+            dropped_summaries: AtomicU64::default(),
+            _thread: thread,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.read().unwrap().is_empty()
+    }
+
+    /// Returns the names of the registered hooks
+    pub fn names(&self) -> Vec<String> {
+        let hooks = self.hooks.read().unwrap();
+        hooks.iter().map(|hook| hook.name.clone()).collect()
+    }
+
+    /// Queues `summary` to every registered hook, dropping it for the hooks too far behind
+    fn notify(&self, summary: BankFreezeSummary) {
+        let summary = Arc::new(summary);
+        let hooks = self.hooks.read().unwrap();
+        for hook in hooks.iter() {
+            match hook.sender.try_send(summary.clone()) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => {
+                    let dropped = hook.dropped_summaries.fetch_add(1, Ordering::Relaxed) + 1;
+                    warn!(
+                        "Bank freeze hook {} is behind, skipping slot {}",
+                        hook.name, summary.slot
+                    );
+                    datapoint_warn!(
+                        "bank_freeze_hook_behind",
+                        "hook" => hook.name.clone(),
+                        ("slot", summary.slot, i64),
+                        ("dropped_summaries", dropped, i64),
+                    );
+                }
+                Err(TrySendError::Disconnected(_)) => {}
+            }
+        }
+    }
+}
+
+fn run_hook(hook: &dyn BankFreezeHook, summary: &BankFreezeSummary, time_budget: Duration) {
+    let name = hook.name();
+    let mut measure = Measure::start("bank_freeze_hook");
+    let result = catch_unwind(AssertUnwindSafe(|| hook.check(summary)));
+    measure.stop();
+
+    match result {
+        Ok(violations) => {
+            for violation in &violations {
+                error!(
+                    "Bank freeze hook {name} found a violation at slot {}: {violation}",
+                    summary.slot
+                );
+                datapoint_error!(
+                    "bank_freeze_hook_violation",
+                    "hook" => name.to_string(),
+                    ("slot", summary.slot, i64),
+                    ("violation", violation.clone(), String),
+                );
+            }
+        }
+        Err(_) => {
+            error!(
+                "Bank freeze hook {name} panicked checking slot {}",
+                summary.slot
+            );
+            datapoint_error!(
+                "bank_freeze_hook_panic",
+                "hook" => name.to_string(),
+                ("slot", summary.slot, i64),
+            );
+        }
+    }
+    if measure.as_duration() > time_budget {
+        warn!(
+            "Bank freeze hook {name} took {:?} checking slot {}, over its budget of {time_budget:?}",
+            measure.as_duration(),
+            summary.slot
+        );
+        datapoint_warn!(
+            "bank_freeze_hook_over_budget",
+            "hook" => name.to_string(),
+            ("slot", summary.slot, i64),
+            ("elapsed_us", measure.as_us(), i64),
+            ("budget_us", time_budget.as_micros() as i64, i64),
+        );
+    }
+}
+
+/// Checks that the capitalization of a bank only grows by the inflation rewards it credits.
+///
+/// The first bank of an epoch is not checked, as feature activations and builtin migrations at
+/// the epoch boundary may legitimately mint lamports.
+#[derive(Debug, Default)]
+pub struct CapitalizationHook;
+
+impl BankFreezeHook for CapitalizationHook {
+    fn name(&self) -> &str {
+        "capitalization"
+    }
+
+    fn check(&self, summary: &BankFreezeSummary) -> Vec<String> {
+        let Some(parent_capitalization) = summary.parent_capitalization else {
+            return vec![];
+        };
+        if summary.is_epoch_boundary() {
+            return vec![];
+        }
+        let minted = summary.capitalization.saturating_sub(parent_capitalization);
+        let inflation_rewards = summary
+            .rewards
+            .iter()
+            .filter(|(_, reward)| {
+                matches!(reward.reward_type, RewardType::Staking | RewardType::Voting)
+            })
+            .map(|(_, reward)| u64::try_from(reward.lamports).unwrap_or_default())
+            .fold(0u64, u64::saturating_add);
+        if minted > inflation_rewards {
+            vec![format!(
+                "capitalization grew by {minted} lamports from {parent_capitalization} to {}, \
+                 but only {inflation_rewards} lamports of inflation rewards were credited",
+                summary.capitalization
+            )]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl Bank {
+    /// The hooks run when this bank, or any bank descending from it, is frozen
+    pub fn freeze_hooks(&self) -> &Arc<BankFreezeHooks> {
+        &self.freeze_hooks
+    }
+
+    pub(super) fn run_freeze_hooks(&self, bank_hash: Hash) {
+        if self.freeze_hooks.is_empty() {
+            return;
+        }
+        self.freeze_hooks
+            .notify(BankFreezeSummary::new(self, bank_hash));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::create_genesis_config,
+        crossbeam_channel::{unbounded, Receiver},
+        solana_sdk::native_token::LAMPORTS_PER_SOL,
+    };
+
+    struct ForwardingHook {
+        sender: Sender<BankFreezeSummary>,
+        panic: bool,
+    }
+
+    impl BankFreezeHook for ForwardingHook {
+        fn name(&self) -> &str {
+            "forwarding"
+        }
+
+        fn check(&self, summary: &BankFreezeSummary) -> Vec<String> {
+            self.sender.send(summary.clone()).unwrap();
+            if self.panic {
+                panic!("hook failure");
+            }
+            vec![]
+        }
+    }
+
+    fn register_forwarding_hook(bank: &Bank, panic: bool) -> Receiver<BankFreezeSummary> {
+        let (sender, receiver) = unbounded();
+        bank.freeze_hooks().register(
+            Arc::new(ForwardingHook { sender, panic }),
+            DEFAULT_FREEZE_HOOK_TIME_BUDGET,
+        );
+        receiver
+    }
+
+    fn summary(capitalization: u64, rewards: Vec<(Pubkey, RewardInfo)>) -> BankFreezeSummary {
+        BankFreezeSummary {
+            slot: 2,
+            epoch: 0,
+            bank_hash: Hash::default(),
+            capitalization,
+            capitalization_delta: CapitalizationDelta::default(),
+            collected_fees: 0,
+            treasury_fees: 0,
+            collected_rent: 0,
+            transaction_count: 0,
+            signature_count: 0,
+            rewards,
+            parent_slot: 1,
+            parent_epoch: Some(0),
+            parent_capitalization: Some(1_000),
+        }
+    }
+
+    #[test]
+    fn test_freeze_hooks_run_on_descendants() {
+        let (genesis_config, _mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let receiver = register_forwarding_hook(&bank0, false);
+        assert_eq!(bank0.freeze_hooks().names(), vec!["forwarding"]);
+
+        bank0.freeze();
+        let summary = receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(summary.slot, 0);
+        assert_eq!(summary.bank_hash, bank0.hash());
+        assert_eq!(summary.parent_capitalization, None);
+
+        let bank1 = Bank::new_from_parent(bank0.clone(), &Pubkey::new_unique(), 1);
+        bank1.freeze();
+        let summary = receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(summary.slot, 1);
+        assert_eq!(summary.parent_slot, 0);
+        assert_eq!(summary.bank_hash, bank1.hash());
+        assert_eq!(summary.capitalization, bank1.capitalization());
+        assert_eq!(summary.parent_capitalization, Some(bank0.capitalization()));
+
+        // Freezing is idempotent and so are the hooks
+        bank1.freeze();
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    fn test_freeze_hook_panic_is_contained() {
+        let (genesis_config, _mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let receiver = register_forwarding_hook(&bank0, true);
+        bank0.freeze();
+        assert_eq!(
+            receiver.recv_timeout(Duration::from_secs(10)).unwrap().slot,
+            0
+        );
+
+        // The hook keeps running after panicking
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::new_unique(), 1);
+        bank1.freeze();
+        assert_eq!(
+            receiver.recv_timeout(Duration::from_secs(10)).unwrap().slot,
+            1
+        );
+    }
+
+    #[test]
+    fn test_capitalization_hook() {
+        let hook = CapitalizationHook;
+        let reward = |reward_type, lamports| {
+            (
+                Pubkey::new_unique(),
+                RewardInfo {
+                    reward_type,
+                    lamports,
+                    post_balance: 0,
+                    commission: None,
+                },
+            )
+        };
+
+        // Burning lamports is expected
+        assert!(hook.check(&summary(900, vec![])).is_empty());
+        // So is minting inflation rewards
+        assert!(hook
+            .check(&summary(
+                1_100,
+                vec![
+                    reward(RewardType::Staking, 60),
+                    reward(RewardType::Voting, 40)
+                ]
+            ))
+            .is_empty());
+        // Fee rewards are not minted
+        assert_eq!(
+            hook.check(&summary(1_100, vec![reward(RewardType::Fee, 100)]))
+                .len(),
+            1
+        );
+        assert_eq!(hook.check(&summary(1_001, vec![])).len(), 1);
+
+        // Epoch boundaries and banks without parents are not checked
+        let mut epoch_boundary = summary(1_100, vec![]);
+        epoch_boundary.epoch = 1;
+        assert!(hook.check(&epoch_boundary).is_empty());
+        let mut no_parent = summary(1_100, vec![]);
+        no_parent.parent_capitalization = None;
+        assert!(hook.check(&no_parent).is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+use {
+    super::Bank,
+    agave_feature_set as feature_set,
+    log::*,
+    solana_sdk::{
+        account::{Account, AccountSharedData, ReadableAccount},
+        inflation::Inflation,
+        sysvar,
+    },
+    std::sync::{Arc, RwLock},
+    tachyon_governance::{active_parameters, pending_bundle, ParameterBundle},
+};
+
+impl Bank {
+    /// The governance parameters in effect, as recorded by the last applied parameter bundle.
+    pub fn active_parameters(&self) -> Option<ParameterBundle> {
+        self.get_account_with_fixed_root(&active_parameters::id())
+            .and_then(|account| bincode::deserialize(account.data()).ok())
+    }
+
+    /// Records the staged parameter bundle as active if it is valid and activates in this
+    /// bank's epoch. Must only be called once, from the first bank of the epoch.
+    pub(super) fn activate_parameter_bundle(&mut self) {
+        if !self
+            .feature_set
+            .is_active(&feature_set::governance_parameter_bundles::id())
+        {
+            return;
+        }
+        let Some(account) = self.get_account_with_fixed_root(&pending_bundle::id()) else {
+            return;
+        };
+        let bundle = match ParameterBundle::from_account(&account) {
+            Ok(bundle) => bundle,
+            Err(err) => {
+                warn!(
+                    "Ignoring staged parameter bundle at epoch {}: {err}",
+                    self.epoch()
+                );
+                datapoint_warn!(
+                    "bank-governance-invalid_bundle",
+                    ("epoch", self.epoch(), i64),
+                    ("error", err.to_string(), String),
+                );
+                return;
+            }
+        };
+        if bundle.activation_epoch != self.epoch() {
+            return;
+        }
+
+        let active = self.active_parameters().unwrap_or_default().merge(&bundle);
+        info!(
+            "Activating parameter bundle at slot {}: {bundle:?}, now in effect: {active:?}",
+            self.slot()
+        );
+        let data = bincode::serialize(&active).unwrap();
+        self.update_sysvar_account(&active_parameters::id(), |account| {
+            let (lamports, rent_epoch) = self.inherit_specially_retained_account_fields(account);
+            AccountSharedData::from(Account {
+                lamports,
+                data: data.clone(),
+                owner: sysvar::id(),
+                executable: false,
+                rent_epoch,
+            })
+        });
+        datapoint_info!(
+            "bank-governance-activated_bundle",
+            ("slot", self.slot(), i64),
+            ("epoch", self.epoch(), i64),
+        );
+    }
+
+    /// Applies the recorded governance parameters to this bank. Applying them is idempotent, so
+    /// this is redone whenever a bank is restored, including for the inflation that snapshots
+    /// already carry, and after anything else, like feature activations, updated the same
+    /// parameters.
+    pub(super) fn apply_active_parameters(&mut self) {
+        let Some(active) = self.active_parameters() else {
+            return;
+        };
+        if let Some(fee_multiplier) = active.fee_multiplier {
+            self.base_fee_multiplier = fee_multiplier;
+        }
+        if let Some(limits) = active.block_cost_limits {
+            self.write_cost_tracker().unwrap().set_limits(
+                limits.account_cost_limit,
+                limits.block_cost_limit,
+                limits.vote_cost_limit,
+            );
+        }
+        if let Some(taper) = active.inflation_taper {
+            let inflation = *self.inflation.read().unwrap();
+            if inflation.taper != taper {
+                // The inflation is shared with the parent bank, replace it rather than updating
+                // it in place
+                self.inflation = Arc::new(RwLock::new(Inflation { taper, ..inflation }));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::{activate_all_features, create_genesis_config},
+        solana_config_program::create_config_account,
+        solana_fee::BASE_FEE_MULTIPLIER,
+        solana_sdk::pubkey::Pubkey,
+        tachyon_governance::{authority, BlockCostLimits},
+    };
+
+    fn new_bank_with_bundle(bundle: &ParameterBundle) -> Arc<Bank> {
+        let mut genesis_config = create_genesis_config(1_000_000_000).genesis_config;
+        activate_all_features(&mut genesis_config);
+        let bank = Bank::new_for_tests(&genesis_config);
+        bank.store_account(
+            &pending_bundle::id(),
+            &create_config_account(vec![(authority::id(), true)], bundle, 1_000_000),
+        );
+        Arc::new(bank)
+    }
+
+    fn new_bank_in_next_epoch(parent: Arc<Bank>) -> Arc<Bank> {
+        let slot = parent
+            .epoch_schedule()
+            .get_first_slot_in_epoch(parent.epoch() + 1);
+        Arc::new(Bank::new_from_parent(parent, &Pubkey::default(), slot))
+    }
+
+    #[test]
+    fn test_activate_parameter_bundle() {
+        let limits = BlockCostLimits {
+            account_cost_limit: 20_000_000,
+            block_cost_limit: 100_000_000,
+            vote_cost_limit: 50_000_000,
+        };
+        let bundle = ParameterBundle {
+            activation_epoch: 2,
+            fee_multiplier: Some(25),
+            block_cost_limits: Some(limits),
+            inflation_taper: Some(0.3),
+        };
+        let bank = new_bank_with_bundle(&bundle);
+        let initial_taper = bank.inflation().taper;
+
+        // Nothing changes before the activation epoch
+        let bank = new_bank_in_next_epoch(bank);
+        assert_eq!(bank.epoch(), 1);
+        assert_eq!(bank.active_parameters(), None);
+        assert_eq!(bank.base_fee_multiplier, BASE_FEE_MULTIPLIER);
+        assert_eq!(bank.inflation().taper, initial_taper);
+
+        // Everything changes at once in the first slot of the activation epoch
+        let bank = new_bank_in_next_epoch(bank);
+        assert_eq!(bank.epoch(), 2);
+        assert_eq!(bank.active_parameters(), Some(bundle.clone()));
+        assert_eq!(bank.base_fee_multiplier, 25);
+        assert_eq!(
+            bank.read_cost_tracker().unwrap().get_block_limit(),
+            limits.block_cost_limit
+        );
+        assert_eq!(bank.inflation().taper, 0.3);
+
+        // Children inherit the parameters
+        let child = Bank::new_from_parent(bank.clone(), &Pubkey::default(), bank.slot() + 1);
+        assert_eq!(child.base_fee_multiplier, 25);
+        assert_eq!(
+            child.read_cost_tracker().unwrap().get_block_limit(),
+            limits.block_cost_limit
+        );
+
+        // A later bundle only replaces the parameters it sets
+        let update = ParameterBundle {
+            activation_epoch: 3,
+            fee_multiplier: Some(15),
+            block_cost_limits: None,
+            inflation_taper: None,
+        };
+        child.store_account(
+            &pending_bundle::id(),
+            &create_config_account(vec![(authority::id(), true)], &update, 1_000_000),
+        );
+        let bank = new_bank_in_next_epoch(Arc::new(child));
+        assert_eq!(bank.epoch(), 3);
+        assert_eq!(
+            bank.active_parameters(),
+            Some(ParameterBundle {
+                activation_epoch: 3,
+                fee_multiplier: Some(15),
+                ..bundle
+            })
+        );
+        assert_eq!(bank.base_fee_multiplier, 15);
+        assert_eq!(bank.inflation().taper, 0.3);
+    }
+
+    #[test]
+    fn test_activate_parameter_bundle_invalid() {
+        let bundle = ParameterBundle {
+            activation_epoch: 1,
+            fee_multiplier: Some(0),
+            block_cost_limits: None,
+            inflation_taper: None,
+        };
+        let bank = new_bank_in_next_epoch(new_bank_with_bundle(&bundle));
+        assert_eq!(bank.epoch(), 1);
+        assert_eq!(bank.active_parameters(), None);
+        assert_eq!(bank.base_fee_multiplier, BASE_FEE_MULTIPLIER);
+
+        // Bundles not staged by the governance authority are ignored
+        let bundle = ParameterBundle {
+            activation_epoch: 2,
+            fee_multiplier: Some(25),
+            ..bundle
+        };
+        bank.store_account(
+            &pending_bundle::id(),
+            &create_config_account(vec![(Pubkey::new_unique(), true)], &bundle, 1_000_000),
+        );
+        let bank = new_bank_in_next_epoch(bank);
+        assert_eq!(bank.epoch(), 2);
+        assert_eq!(bank.active_parameters(), None);
+        assert_eq!(bank.base_fee_multiplier, BASE_FEE_MULTIPLIER);
+    }
+}
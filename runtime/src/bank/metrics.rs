@@ -236,11 +236,16 @@ pub(crate) fn report_loaded_programs_stats(stats: &ProgramCacheStats, slot: Slot
     let prunes_environment = stats.prunes_environment.load(Ordering::Relaxed);
     let empty_entries = stats.empty_entries.load(Ordering::Relaxed);
     let water_level = stats.water_level.load(Ordering::Relaxed);
+    let hit_rate_percent = hits
+        .saturating_mul(100)
+        .checked_div(hits.saturating_add(misses))
+        .unwrap_or_default();
     datapoint_info!(
         "loaded-programs-cache-stats",
         ("slot", slot, i64),
         ("hits", hits, i64),
         ("misses", misses, i64),
+        ("hit_rate_percent", hit_rate_percent, i64),
         ("evictions", evictions, i64),
         ("reloads", reloads, i64),
         ("insertions", insertions, i64),
@@ -8,8 +8,8 @@ use {
     },
     crate::{
         bank::{
-            PrevEpochInflationRewards, RewardCalcTracer, RewardCalculationEvent, RewardsMetrics,
-            VoteAccount, VoteReward, VoteRewards,
+            supply_audit::CapitalizationChange, PrevEpochInflationRewards, RewardCalcTracer,
+            RewardCalculationEvent, RewardsMetrics, VoteAccount, VoteReward, VoteRewards,
         },
         stake_account::StakeAccount,
         stakes::Stakes,
@@ -142,8 +142,9 @@ impl Bank {
                 stakes.vote_accounts().len(),
             )
         };
-        self.capitalization
-            .fetch_add(validator_rewards_paid, Relaxed);
+        self.change_capitalization(CapitalizationChange::InflationIssued(
+            validator_rewards_paid,
+        ));
 
         let active_stake = if let Some(stake_history_entry) =
             self.stakes_cache.stakes().history().get(prev_epoch)
@@ -3,7 +3,10 @@ use {
         Bank, EpochRewardStatus, PartitionedStakeReward, PartitionedStakeRewards, StakeRewards,
     },
     crate::{
-        bank::metrics::{report_partitioned_reward_metrics, RewardsStoreMetrics},
+        bank::{
+            metrics::{report_partitioned_reward_metrics, RewardsStoreMetrics},
+            supply_audit::CapitalizationChange,
+        },
         stake_account::StakeAccount,
     },
     log::error,
@@ -15,7 +18,6 @@ use {
         pubkey::Pubkey,
         stake::state::{Delegation, StakeStateV2},
     },
-    std::sync::atomic::Ordering::Relaxed,
     thiserror::Error,
 };
 
@@ -103,7 +105,7 @@ impl Bank {
         ) = measure_us!(self.store_stake_accounts_in_partition(this_partition_stake_rewards));
 
         // increase total capitalization by the distributed rewards
-        self.capitalization.fetch_add(lamports_distributed, Relaxed);
+        self.change_capitalization(CapitalizationChange::InflationIssued(lamports_distributed));
 
         // decrease distributed capital from epoch rewards sysvar
         self.update_epoch_rewards_sysvar(lamports_distributed + lamports_burned);
@@ -0,0 +1,93 @@
+use {
+    super::Bank,
+    agave_feature_set as feature_set,
+    solana_sdk::{
+        account::{Account, AccountSharedData, ReadableAccount},
+        sysvar,
+    },
+    tachyon_slot_seed::SlotSeed,
+};
+
+impl Bank {
+    /// The seed recorded for this bank's slot, if the slot seed sysvar is active.
+    pub fn slot_seed(&self) -> Option<SlotSeed> {
+        self.get_account_with_fixed_root(&tachyon_slot_seed::id())
+            .and_then(|account| SlotSeed::from_account_data(account.data()))
+    }
+
+    /// Chains the seed of the parent into the seed of this slot. Must be called before any
+    /// transaction of the slot executes.
+    pub(super) fn update_slot_seed(&self) {
+        if !self
+            .feature_set
+            .is_active(&feature_set::slot_seed_sysvar::id())
+        {
+            return;
+        }
+        self.update_sysvar_account(&tachyon_slot_seed::id(), |account| {
+            // The first seed after the feature activates is chained from the default seed
+            let parent_seed = account
+                .as_ref()
+                .and_then(|account| SlotSeed::from_account_data(account.data()))
+                .unwrap_or_default();
+            let slot_seed = parent_seed.next(self.slot(), &self.parent_hash(), self.collector_id());
+            let (lamports, rent_epoch) = self.inherit_specially_retained_account_fields(account);
+            AccountSharedData::from(Account {
+                lamports,
+                data: bincode::serialize(&slot_seed).unwrap(),
+                owner: sysvar::id(),
+                executable: false,
+                rent_epoch,
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::{create_genesis_config, deactivate_features},
+        solana_sdk::pubkey::Pubkey,
+        std::sync::Arc,
+    };
+
+    #[test]
+    fn test_update_slot_seed() {
+        let genesis_config = create_genesis_config(1_000_000_000).genesis_config;
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        assert_eq!(bank0.slot_seed(), None);
+
+        let leader = Pubkey::new_unique();
+        bank0.freeze();
+        let bank1 = Arc::new(Bank::new_from_parent(bank0.clone(), &leader, 1));
+        let seed1 = bank1.slot_seed().unwrap();
+        assert_eq!(seed1, SlotSeed::default().next(1, &bank0.hash(), &leader));
+
+        bank1.freeze();
+        let bank3 = Bank::new_from_parent(bank1.clone(), &leader, 3);
+        assert_eq!(
+            bank3.slot_seed().unwrap(),
+            seed1.next(3, &bank1.hash(), &leader)
+        );
+        // Transactions cannot write lock the seed
+        assert!(bank3
+            .get_reserved_account_keys()
+            .contains(&tachyon_slot_seed::id()));
+    }
+
+    #[test]
+    fn test_slot_seed_inactive() {
+        let mut genesis_config = create_genesis_config(1_000_000_000).genesis_config;
+        deactivate_features(
+            &mut genesis_config,
+            &vec![feature_set::slot_seed_sysvar::id()],
+        );
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::new_unique(), 1);
+        assert_eq!(bank1.slot_seed(), None);
+        assert!(!bank1
+            .get_reserved_account_keys()
+            .contains(&tachyon_slot_seed::id()));
+    }
+}
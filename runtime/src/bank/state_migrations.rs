@@ -0,0 +1,365 @@
+//! Feature-gated one-off changes of account state, like reintroducing an account or adjusting a
+//! balance.
+//!
+//! A migration lists the accounts it writes along with the hash of those accounts before and
+//! after the migration. Both hashes are checked when the feature gating the migration is
+//! activated and before anything is stored, so a migration that finds a different state than
+//! the one it was written against is skipped as a whole instead of being partially applied.
+
+use {
+    super::Bank,
+    ahash::AHashSet,
+    log::*,
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount, WritableAccount},
+        hash::{Hash, Hasher},
+        pubkey::Pubkey,
+    },
+    std::collections::HashSet,
+    thiserror::Error,
+};
+
+/// Migrations applied by the bank when their feature is activated, in order.
+pub(crate) const STATE_MIGRATIONS: &[StateMigration] = &[];
+
+/// A write to a single account.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccountWrite {
+    /// Stores the account, creating it if it does not exist
+    Store(Pubkey, AccountSharedData),
+    /// Adds lamports to an existing account, or removes them if negative
+    AdjustLamports(Pubkey, i64),
+    /// Removes the account
+    Remove(Pubkey),
+}
+
+impl AccountWrite {
+    pub fn pubkey(&self) -> &Pubkey {
+        match self {
+            Self::Store(pubkey, _) | Self::AdjustLamports(pubkey, _) | Self::Remove(pubkey) => {
+                pubkey
+            }
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum StateMigrationError {
+    #[error("account {0} is written more than once")]
+    DuplicateWrite(Pubkey),
+    #[error("account {0} does not exist")]
+    AccountNotFound(Pubkey),
+    #[error("lamports of account {0} out of range")]
+    LamportsOutOfRange(Pubkey),
+    #[error("pre-state hash mismatch: expected {expected}, found {found}")]
+    PreStateMismatch { expected: Hash, found: Hash },
+    #[error("post-state hash mismatch: expected {expected}, found {found}")]
+    PostStateMismatch { expected: Hash, found: Hash },
+}
+
+pub struct StateMigration {
+    /// Feature whose activation applies the migration
+    pub feature_id: Pubkey,
+    /// Incremented whenever the migration is revised, to tell revisions apart in logs
+    pub version: u32,
+    pub description: &'static str,
+    pub writes: fn() -> Vec<AccountWrite>,
+    /// [`state_hash`] of the written accounts before the migration
+    pub pre_state_hash: Hash,
+    /// [`state_hash`] of the written accounts after the migration
+    pub post_state_hash: Hash,
+}
+
+/// Hash of the given accounts in order, where `None` is an account that does not exist. The
+/// rent epoch is left out, as it is not known when the migration is written.
+pub fn state_hash<'a>(
+    accounts: impl IntoIterator<Item = (&'a Pubkey, Option<&'a AccountSharedData>)>,
+) -> Hash {
+    let mut hasher = Hasher::default();
+    for (pubkey, account) in accounts {
+        hasher.hash(pubkey.as_ref());
+        match account {
+            None => hasher.hash(&[0]),
+            Some(account) => {
+                hasher.hash(&[1]);
+                hasher.hash(&account.lamports().to_le_bytes());
+                hasher.hash(account.owner().as_ref());
+                hasher.hash(&[account.executable() as u8]);
+                hasher.hash(&(account.data().len() as u64).to_le_bytes());
+                hasher.hash(account.data());
+            }
+        }
+    }
+    hasher.result()
+}
+
+impl StateMigration {
+    /// Returns the written accounts and their state after the migration, where `None` is a
+    /// removed account, after checking the state before and after the migration.
+    pub fn migrate(
+        &self,
+        load: impl Fn(&Pubkey) -> Option<AccountSharedData>,
+    ) -> Result<Vec<(Pubkey, Option<AccountSharedData>)>, StateMigrationError> {
+        let writes = (self.writes)();
+        let mut pubkeys = HashSet::with_capacity(writes.len());
+        if let Some(write) = writes.iter().find(|write| !pubkeys.insert(*write.pubkey())) {
+            return Err(StateMigrationError::DuplicateWrite(*write.pubkey()));
+        }
+
+        let pre_state: Vec<_> = writes
+            .iter()
+            .map(|write| {
+                let pubkey = write.pubkey();
+                (
+                    *pubkey,
+                    load(pubkey).filter(|account| account.lamports() > 0),
+                )
+            })
+            .collect();
+        let found = state_hash(
+            pre_state
+                .iter()
+                .map(|(pubkey, account)| (pubkey, account.as_ref())),
+        );
+        if found != self.pre_state_hash {
+            return Err(StateMigrationError::PreStateMismatch {
+                expected: self.pre_state_hash,
+                found,
+            });
+        }
+
+        let post_state = writes
+            .into_iter()
+            .zip(pre_state)
+            .map(|(write, (pubkey, account))| {
+                let account = match write {
+                    AccountWrite::Store(_, account) => Some(account),
+                    AccountWrite::AdjustLamports(_, lamports) => {
+                        let mut account =
+                            account.ok_or(StateMigrationError::AccountNotFound(pubkey))?;
+                        let lamports = account
+                            .lamports()
+                            .checked_add_signed(lamports)
+                            .ok_or(StateMigrationError::LamportsOutOfRange(pubkey))?;
+                        account.set_lamports(lamports);
+                        Some(account)
+                    }
+                    AccountWrite::Remove(_) => None,
+                };
+                Ok((pubkey, account.filter(|account| account.lamports() > 0)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let found = state_hash(
+            post_state
+                .iter()
+                .map(|(pubkey, account)| (pubkey, account.as_ref())),
+        );
+        if found != self.post_state_hash {
+            return Err(StateMigrationError::PostStateMismatch {
+                expected: self.post_state_hash,
+                found,
+            });
+        }
+        Ok(post_state)
+    }
+}
+
+impl Bank {
+    pub(super) fn apply_state_migrations(&self, new_feature_activations: &AHashSet<Pubkey>) {
+        self.apply_state_migrations_from(STATE_MIGRATIONS, new_feature_activations);
+    }
+
+    fn apply_state_migrations_from(
+        &self,
+        migrations: &[StateMigration],
+        new_feature_activations: &AHashSet<Pubkey>,
+    ) {
+        for migration in migrations
+            .iter()
+            .filter(|migration| new_feature_activations.contains(&migration.feature_id))
+        {
+            match migration.migrate(|pubkey| self.get_account_with_fixed_root(pubkey)) {
+                Ok(accounts) => {
+                    for (pubkey, account) in &accounts {
+                        self.store_account_and_update_capitalization(
+                            pubkey,
+                            &account.clone().unwrap_or_default(),
+                        );
+                    }
+                    info!(
+                        "Applied state migration {} v{} at slot {}, writing {} accounts",
+                        migration.description,
+                        migration.version,
+                        self.slot(),
+                        accounts.len(),
+                    );
+                    datapoint_info!(
+                        "bank-state_migration-applied",
+                        ("slot", self.slot(), i64),
+                        ("feature_id", migration.feature_id.to_string(), String),
+                        ("version", migration.version, i64),
+                        ("accounts", accounts.len(), i64),
+                    );
+                }
+                Err(err) => {
+                    error!(
+                        "Skipping state migration {} v{} at slot {}: {err}",
+                        migration.description,
+                        migration.version,
+                        self.slot(),
+                    );
+                    datapoint_error!(
+                        "bank-state_migration-skipped",
+                        ("slot", self.slot(), i64),
+                        ("feature_id", migration.feature_id.to_string(), String),
+                        ("version", migration.version, i64),
+                        ("error", err.to_string(), String),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::create_genesis_config,
+        solana_sdk::{native_token::LAMPORTS_PER_SOL, system_program},
+        std::collections::HashMap,
+    };
+
+    const FUNDED: Pubkey = Pubkey::new_from_array([1; 32]);
+    const REINTRODUCED: Pubkey = Pubkey::new_from_array([2; 32]);
+
+    fn writes() -> Vec<AccountWrite> {
+        vec![
+            AccountWrite::AdjustLamports(FUNDED, -(LAMPORTS_PER_SOL as i64)),
+            AccountWrite::Store(
+                REINTRODUCED,
+                AccountSharedData::new(LAMPORTS_PER_SOL, 8, &system_program::id()),
+            ),
+        ]
+    }
+
+    fn funded_account(lamports: u64) -> AccountSharedData {
+        AccountSharedData::new(lamports, 0, &system_program::id())
+    }
+
+    fn new_migration(pre_state_hash: Hash, post_state_hash: Hash) -> StateMigration {
+        StateMigration {
+            feature_id: Pubkey::new_unique(),
+            version: 1,
+            description: "test",
+            writes,
+            pre_state_hash,
+            post_state_hash,
+        }
+    }
+
+    fn expected_hashes() -> (Hash, Hash) {
+        let pre_state_hash = state_hash([
+            (&FUNDED, Some(&funded_account(2 * LAMPORTS_PER_SOL))),
+            (&REINTRODUCED, None),
+        ]);
+        let reintroduced = AccountSharedData::new(LAMPORTS_PER_SOL, 8, &system_program::id());
+        let post_state_hash = state_hash([
+            (&FUNDED, Some(&funded_account(LAMPORTS_PER_SOL))),
+            (&REINTRODUCED, Some(&reintroduced)),
+        ]);
+        (pre_state_hash, post_state_hash)
+    }
+
+    #[test]
+    fn test_state_hash() {
+        let account = funded_account(1);
+        assert_ne!(
+            state_hash([(&FUNDED, Some(&account))]),
+            state_hash([(&FUNDED, None)])
+        );
+        assert_ne!(
+            state_hash([(&FUNDED, None), (&REINTRODUCED, None)]),
+            state_hash([(&REINTRODUCED, None), (&FUNDED, None)])
+        );
+        // The rent epoch is not part of the state
+        let mut other = account.clone();
+        other.set_rent_epoch(42);
+        assert_eq!(
+            state_hash([(&FUNDED, Some(&account))]),
+            state_hash([(&FUNDED, Some(&other))])
+        );
+    }
+
+    #[test]
+    fn test_migrate() {
+        let (pre_state_hash, post_state_hash) = expected_hashes();
+        let accounts = HashMap::from([(FUNDED, funded_account(2 * LAMPORTS_PER_SOL))]);
+        let load = |pubkey: &Pubkey| accounts.get(pubkey).cloned();
+
+        let migration = new_migration(pre_state_hash, post_state_hash);
+        let post_state = migration.migrate(load).unwrap();
+        assert_eq!(post_state.len(), 2);
+        assert_eq!(
+            post_state[0].1.as_ref().unwrap().lamports(),
+            LAMPORTS_PER_SOL
+        );
+        assert_eq!(post_state[1].0, REINTRODUCED);
+
+        let migration = new_migration(Hash::default(), post_state_hash);
+        assert_eq!(
+            migration.migrate(load),
+            Err(StateMigrationError::PreStateMismatch {
+                expected: Hash::default(),
+                found: pre_state_hash,
+            })
+        );
+        let migration = new_migration(pre_state_hash, Hash::default());
+        assert_eq!(
+            migration.migrate(load),
+            Err(StateMigrationError::PostStateMismatch {
+                expected: Hash::default(),
+                found: post_state_hash,
+            })
+        );
+
+        let migration = StateMigration {
+            writes: || vec![AccountWrite::Remove(FUNDED), AccountWrite::Remove(FUNDED)],
+            ..new_migration(pre_state_hash, post_state_hash)
+        };
+        assert_eq!(
+            migration.migrate(load),
+            Err(StateMigrationError::DuplicateWrite(FUNDED))
+        );
+    }
+
+    #[test]
+    fn test_apply_state_migrations() {
+        let (pre_state_hash, post_state_hash) = expected_hashes();
+        let bank = Bank::new_for_tests(&create_genesis_config(LAMPORTS_PER_SOL).genesis_config);
+        bank.store_account_and_update_capitalization(
+            &FUNDED,
+            &funded_account(2 * LAMPORTS_PER_SOL),
+        );
+        let capitalization = bank.capitalization();
+
+        // Migrations are only applied along with their feature
+        let migration = new_migration(pre_state_hash, post_state_hash);
+        bank.apply_state_migrations_from(&[migration], &AHashSet::new());
+        assert_eq!(bank.get_account(&REINTRODUCED), None);
+
+        // Nothing is written if a hash does not match
+        let migration = new_migration(pre_state_hash, Hash::default());
+        let new_feature_activations = [migration.feature_id].into_iter().collect();
+        bank.apply_state_migrations_from(&[migration], &new_feature_activations);
+        assert_eq!(bank.get_balance(&FUNDED), 2 * LAMPORTS_PER_SOL);
+        assert_eq!(bank.get_account(&REINTRODUCED), None);
+
+        let migration = new_migration(pre_state_hash, post_state_hash);
+        let new_feature_activations = [migration.feature_id].into_iter().collect();
+        bank.apply_state_migrations_from(&[migration], &new_feature_activations);
+        assert_eq!(bank.get_balance(&FUNDED), LAMPORTS_PER_SOL);
+        assert_eq!(bank.get_balance(&REINTRODUCED), LAMPORTS_PER_SOL);
+        assert_eq!(bank.capitalization(), capitalization);
+    }
+}
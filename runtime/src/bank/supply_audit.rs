@@ -0,0 +1,463 @@
+//! Audit of the changes to the total supply made by every frozen bank.
+//!
+//! Banks attribute every change of their capitalization to a cause: inflation issuance, fee and
+//! rent burns, the incinerator, or other mints and removals such as program migrations. When a
+//! bank is frozen, the supply audit checks that the attributed changes add up to the actual
+//! change of capitalization since the parent bank, and that they agree with the rewards, fees
+//! and rent the bank recorded. The audited deltas of the most recent banks are kept for RPC.
+
+use {
+    super::{
+        freeze_hooks::{BankFreezeHook, BankFreezeSummary},
+        Bank,
+    },
+    log::*,
+    solana_sdk::{clock::Slot, reward_type::RewardType},
+    std::{
+        collections::VecDeque,
+        str::FromStr,
+        sync::{
+            atomic::{AtomicU64, Ordering::Relaxed},
+            RwLock,
+        },
+    },
+};
+
+/// Audited banks kept by default
+pub const DEFAULT_SUPPLY_AUDIT_MAX_ENTRIES: usize = 4096;
+
+/// A change of the capitalization of a bank, and its cause
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CapitalizationChange {
+    /// Inflation rewards credited to vote and stake accounts
+    InflationIssued(u64),
+    FeesBurned(u64),
+    RentBurned(u64),
+    Incinerated(u64),
+    /// Lamports minted by genesis accounts, feature activations and program migrations
+    OtherMinted(u64),
+    /// Lamports removed by feature activations and program migrations
+    OtherRemoved(u64),
+}
+
+/// The changes of the capitalization of a bank, by cause
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapitalizationDelta {
+    pub inflation_issued: u64,
+    pub fees_burned: u64,
+    pub rent_burned: u64,
+    pub incinerated: u64,
+    pub other_minted: u64,
+    pub other_removed: u64,
+}
+
+impl CapitalizationDelta {
+    /// Returns the net change of capitalization explained by the causes
+    pub fn net(&self) -> i128 {
+        i128::from(self.inflation_issued) + i128::from(self.other_minted)
+            - i128::from(self.fees_burned)
+            - i128::from(self.rent_burned)
+            - i128::from(self.incinerated)
+            - i128::from(self.other_removed)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CapitalizationDeltaTracker {
+    inflation_issued: AtomicU64,
+    fees_burned: AtomicU64,
+    rent_burned: AtomicU64,
+    incinerated: AtomicU64,
+    other_minted: AtomicU64,
+    other_removed: AtomicU64,
+}
+
+impl CapitalizationDeltaTracker {
+    fn record(&self, change: CapitalizationChange) {
+        let (counter, lamports) = match change {
+            CapitalizationChange::InflationIssued(lamports) => (&self.inflation_issued, lamports),
+            CapitalizationChange::FeesBurned(lamports) => (&self.fees_burned, lamports),
+            CapitalizationChange::RentBurned(lamports) => (&self.rent_burned, lamports),
+            CapitalizationChange::Incinerated(lamports) => (&self.incinerated, lamports),
+            CapitalizationChange::OtherMinted(lamports) => (&self.other_minted, lamports),
+            CapitalizationChange::OtherRemoved(lamports) => (&self.other_removed, lamports),
+        };
+        counter.fetch_add(lamports, Relaxed);
+    }
+
+    pub(crate) fn load(&self) -> CapitalizationDelta {
+        CapitalizationDelta {
+            inflation_issued: self.inflation_issued.load(Relaxed),
+            fees_burned: self.fees_burned.load(Relaxed),
+            rent_burned: self.rent_burned.load(Relaxed),
+            incinerated: self.incinerated.load(Relaxed),
+            other_minted: self.other_minted.load(Relaxed),
+            other_removed: self.other_removed.load(Relaxed),
+        }
+    }
+}
+
+impl Bank {
+    /// Changes the capitalization of this bank, attributing the change to its cause
+    pub(crate) fn change_capitalization(&self, change: CapitalizationChange) {
+        match change {
+            CapitalizationChange::InflationIssued(lamports)
+            | CapitalizationChange::OtherMinted(lamports) => {
+                self.capitalization.fetch_add(lamports, Relaxed);
+            }
+            CapitalizationChange::FeesBurned(lamports)
+            | CapitalizationChange::RentBurned(lamports)
+            | CapitalizationChange::Incinerated(lamports)
+            | CapitalizationChange::OtherRemoved(lamports) => {
+                self.capitalization.fetch_sub(lamports, Relaxed);
+            }
+        }
+        self.capitalization_delta.record(change);
+    }
+
+    /// Returns the changes of capitalization made by this bank so far, by cause
+    pub fn capitalization_delta(&self) -> CapitalizationDelta {
+        self.capitalization_delta.load()
+    }
+}
+
+/// What the supply audit does when a bank fails it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SupplyAuditAction {
+    /// Log the violations and report them to metrics
+    #[default]
+    Alarm,
+    /// Log the violations, then exit the process
+    Abort,
+}
+
+impl FromStr for SupplyAuditAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "alarm" => Ok(Self::Alarm),
+            "abort" => Ok(Self::Abort),
+            _ => Err(format!("unknown supply audit action '{s}'")),
+        }
+    }
+}
+
+/// The audited change of supply of a frozen bank
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplyAuditEntry {
+    pub slot: Slot,
+    pub parent_slot: Slot,
+    pub capitalization: u64,
+    pub parent_capitalization: u64,
+    #[serde(flatten)]
+    pub delta: CapitalizationDelta,
+    /// Change of capitalization not attributed to any cause
+    pub unexplained: i64,
+    pub violations: Vec<String>,
+}
+
+impl SupplyAuditEntry {
+    fn new(summary: &BankFreezeSummary, parent_capitalization: u64) -> Self {
+        let delta = summary.capitalization_delta;
+        let actual = i128::from(summary.capitalization) - i128::from(parent_capitalization);
+        let unexplained = i64::try_from(actual - delta.net()).unwrap_or(if actual < delta.net() {
+            i64::MIN
+        } else {
+            i64::MAX
+        });
+        let mut violations = vec![];
+        if unexplained != 0 {
+            violations.push(format!(
+                "capitalization changed by {actual} lamports from {parent_capitalization} to {}, \
+                 {unexplained} lamports of which are unexplained",
+                summary.capitalization
+            ));
+        }
+
+        let rewards = |reward_types: &[RewardType]| {
+            summary
+                .rewards
+                .iter()
+                .filter(|(_, reward)| reward_types.contains(&reward.reward_type))
+                .map(|(_, reward)| u64::try_from(reward.lamports).unwrap_or_default())
+                .fold(0u64, u64::saturating_add)
+        };
+        let inflation_rewards = rewards(&[RewardType::Staking, RewardType::Voting]);
+        if delta.inflation_issued != inflation_rewards {
+            violations.push(format!(
+                "{} lamports of inflation were issued but {inflation_rewards} lamports of \
+                 inflation rewards were credited",
+                delta.inflation_issued
+            ));
+        }
+        let fee_rewards = rewards(&[RewardType::Fee]);
+        let fees_distributed = fee_rewards.saturating_add(summary.treasury_fees);
+        let fees_not_distributed = summary.collected_fees.saturating_sub(fees_distributed);
+        if fees_distributed > summary.collected_fees || delta.fees_burned != fees_not_distributed {
+            violations.push(format!(
+                "{} lamports of fees were collected, {fee_rewards} rewarded, {} deposited into \
+                 the treasury and {} burned",
+                summary.collected_fees, summary.treasury_fees, delta.fees_burned
+            ));
+        }
+        let rent_rewards = rewards(&[RewardType::Rent]);
+        if rent_rewards.saturating_add(delta.rent_burned) > summary.collected_rent {
+            violations.push(format!(
+                "{} lamports of rent were collected, but {rent_rewards} rewarded and {} burned",
+                summary.collected_rent, delta.rent_burned
+            ));
+        }
+
+        Self {
+            slot: summary.slot,
+            parent_slot: summary.parent_slot,
+            capitalization: summary.capitalization,
+            parent_capitalization,
+            delta,
+            unexplained,
+            violations,
+        }
+    }
+}
+
+/// Bank freeze hook auditing the change of supply of every bank, and keeping the audits of the
+/// most recent ones
+#[derive(Debug)]
+pub struct SupplyAudit {
+    action: SupplyAuditAction,
+    max_entries: usize,
+    entries: RwLock<VecDeque<SupplyAuditEntry>>,
+}
+
+impl SupplyAudit {
+    pub fn new(action: SupplyAuditAction, max_entries: usize) -> Self {
+        Self {
+            action,
+            max_entries: max_entries.max(1),
+            entries: RwLock::default(),
+        }
+    }
+
+    /// Returns up to `limit` of the audits kept, of the banks from `start_slot` on, oldest first
+    pub fn entries(&self, start_slot: Slot, limit: usize) -> Vec<SupplyAuditEntry> {
+        let entries = self.entries.read().unwrap();
+        entries
+            .iter()
+            .filter(|entry| entry.slot >= start_slot)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    fn record(&self, entry: SupplyAuditEntry) {
+        let mut entries = self.entries.write().unwrap();
+        if entries.len() >= self.max_entries {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+impl BankFreezeHook for SupplyAudit {
+    fn name(&self) -> &str {
+        "supply-audit"
+    }
+
+    fn check(&self, summary: &BankFreezeSummary) -> Vec<String> {
+        // Without the parent, the change of capitalization of the bank is unknown
+        let Some(parent_capitalization) = summary.parent_capitalization else {
+            return vec![];
+        };
+        let entry = SupplyAuditEntry::new(summary, parent_capitalization);
+        let violations = entry.violations.clone();
+        datapoint_info!(
+            "bank_supply_audit",
+            ("slot", entry.slot, i64),
+            ("capitalization", entry.capitalization, i64),
+            ("inflation_issued", entry.delta.inflation_issued, i64),
+            ("fees_burned", entry.delta.fees_burned, i64),
+            ("rent_burned", entry.delta.rent_burned, i64),
+            ("incinerated", entry.delta.incinerated, i64),
+            ("other_minted", entry.delta.other_minted, i64),
+            ("other_removed", entry.delta.other_removed, i64),
+            ("unexplained", entry.unexplained, i64),
+            ("violations", violations.len(), i64),
+        );
+        self.record(entry);
+
+        if !violations.is_empty() && self.action == SupplyAuditAction::Abort {
+            for violation in &violations {
+                error!("Supply audit failed at slot {}: {violation}", summary.slot);
+            }
+            solana_metrics::flush();
+            error!("Exiting as the supply audit is configured to abort");
+            std::process::exit(1);
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::create_genesis_config,
+        solana_sdk::{
+            hash::Hash, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, reward_info::RewardInfo,
+            signature::Signer, system_transaction,
+        },
+        std::sync::Arc,
+    };
+
+    fn reward(reward_type: RewardType, lamports: i64) -> (Pubkey, RewardInfo) {
+        (
+            Pubkey::new_unique(),
+            RewardInfo {
+                reward_type,
+                lamports,
+                post_balance: 0,
+                commission: None,
+            },
+        )
+    }
+
+    fn summary(capitalization: u64, delta: CapitalizationDelta) -> BankFreezeSummary {
+        BankFreezeSummary {
+            slot: 2,
+            epoch: 0,
+            bank_hash: Hash::default(),
+            capitalization,
+            capitalization_delta: delta,
+            collected_fees: 0,
+            treasury_fees: 0,
+            collected_rent: 0,
+            transaction_count: 0,
+            signature_count: 0,
+            rewards: vec![],
+            parent_slot: 1,
+            parent_epoch: Some(0),
+            parent_capitalization: Some(1_000),
+        }
+    }
+
+    #[test]
+    fn test_supply_audit_entry() {
+        // Fees split between the leader and the burn
+        let mut fees = summary(
+            990,
+            CapitalizationDelta {
+                fees_burned: 10,
+                ..CapitalizationDelta::default()
+            },
+        );
+        fees.collected_fees = 30;
+        fees.rewards = vec![reward(RewardType::Fee, 20)];
+        let entry = SupplyAuditEntry::new(&fees, 1_000);
+        assert_eq!(entry.unexplained, 0);
+        assert!(entry.violations.is_empty(), "{:?}", entry.violations);
+
+        // Burning more fees than collected
+        fees.rewards = vec![reward(RewardType::Fee, 25)];
+        assert_eq!(SupplyAuditEntry::new(&fees, 1_000).violations.len(), 1);
+
+        // Fees split between the leader, the treasury and the burn
+        let mut treasury = summary(
+            994,
+            CapitalizationDelta {
+                fees_burned: 6,
+                ..CapitalizationDelta::default()
+            },
+        );
+        treasury.collected_fees = 30;
+        treasury.treasury_fees = 4;
+        treasury.rewards = vec![reward(RewardType::Fee, 20)];
+        assert!(SupplyAuditEntry::new(&treasury, 1_000)
+            .violations
+            .is_empty());
+        // Fees deposited into the treasury are not burned
+        treasury.treasury_fees = 0;
+        assert_eq!(SupplyAuditEntry::new(&treasury, 1_000).violations.len(), 1);
+
+        // Issuing inflation without crediting rewards
+        let mut inflation = summary(
+            1_100,
+            CapitalizationDelta {
+                inflation_issued: 100,
+                ..CapitalizationDelta::default()
+            },
+        );
+        assert_eq!(SupplyAuditEntry::new(&inflation, 1_000).violations.len(), 1);
+        inflation.rewards = vec![
+            reward(RewardType::Staking, 70),
+            reward(RewardType::Voting, 30),
+        ];
+        assert!(SupplyAuditEntry::new(&inflation, 1_000)
+            .violations
+            .is_empty());
+
+        // Unattributed change of capitalization
+        let entry = SupplyAuditEntry::new(&summary(1_005, CapitalizationDelta::default()), 1_000);
+        assert_eq!(entry.unexplained, 5);
+        assert_eq!(entry.violations.len(), 1);
+
+        // Burning more rent than collected
+        let mut rent = summary(
+            990,
+            CapitalizationDelta {
+                rent_burned: 10,
+                ..CapitalizationDelta::default()
+            },
+        );
+        assert_eq!(SupplyAuditEntry::new(&rent, 1_000).violations.len(), 1);
+        rent.collected_rent = 20;
+        rent.rewards = vec![reward(RewardType::Rent, 10)];
+        assert!(SupplyAuditEntry::new(&rent, 1_000).violations.is_empty());
+    }
+
+    #[test]
+    fn test_supply_audit_entries() {
+        let audit = SupplyAudit::new(SupplyAuditAction::Alarm, 2);
+        for slot in 1..=3 {
+            let mut summary = summary(1_000, CapitalizationDelta::default());
+            summary.slot = slot;
+            assert!(audit.check(&summary).is_empty());
+        }
+        let slots = |entries: Vec<SupplyAuditEntry>| -> Vec<Slot> {
+            entries.iter().map(|entry| entry.slot).collect()
+        };
+        assert_eq!(slots(audit.entries(0, 10)), vec![2, 3]);
+        assert_eq!(slots(audit.entries(3, 10)), vec![3]);
+        assert_eq!(slots(audit.entries(0, 1)), vec![2]);
+
+        let mut summary = summary(1_001, CapitalizationDelta::default());
+        summary.slot = 4;
+        assert_eq!(audit.check(&summary).len(), 1);
+        assert_eq!(audit.entries(4, 1)[0].unexplained, 1);
+    }
+
+    #[test]
+    fn test_supply_audit_of_bank() {
+        let (genesis_config, mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        bank0.freeze();
+        let bank1 = Bank::new_from_parent(bank0.clone(), &Pubkey::new_unique(), 1);
+        let tx = system_transaction::transfer(
+            &mint_keypair,
+            &mint_keypair.pubkey(),
+            1,
+            bank1.last_blockhash(),
+        );
+        bank1.process_transaction(&tx).unwrap();
+        bank1.freeze();
+
+        let fees_burned = bank1.capitalization_delta().fees_burned;
+        assert_eq!(bank1.capitalization(), bank0.capitalization() - fees_burned);
+        let summary = BankFreezeSummary::new(&bank1, bank1.hash());
+        let entry = SupplyAuditEntry::new(&summary, bank0.capitalization());
+        assert_eq!(entry.unexplained, 0);
+        assert!(entry.violations.is_empty(), "{:?}", entry.violations);
+    }
+}
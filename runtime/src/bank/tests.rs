@@ -38,7 +38,10 @@ use {
     },
     solana_compute_budget::{
         compute_budget::ComputeBudget,
-        compute_budget_limits::{self, ComputeBudgetLimits, MAX_COMPUTE_UNIT_LIMIT},
+        compute_budget_limits::{
+            self, ComputeBudgetLimits, MAX_COMPUTE_UNIT_LIMIT, MAX_HEAP_FRAME_BYTES,
+            MIN_HEAP_FRAME_BYTES, X1_MAX_HEAP_FRAME_BYTES,
+        },
     },
     solana_cost_model::block_cost_limits::{
         MAX_BLOCK_UNITS, MAX_BLOCK_UNITS_SIMD_0207, MAX_BLOCK_UNITS_SIMD_0256,
@@ -55,6 +58,10 @@ use {
             Account, AccountSharedData, ReadableAccount, WritableAccount,
         },
         account_utils::StateMut,
+        address_lookup_table::{
+            self,
+            state::{AddressLookupTable, LookupTableMeta},
+        },
         bpf_loader,
         bpf_loader_upgradeable::{self, UpgradeableLoaderState},
         client::SyncClient,
@@ -76,7 +83,10 @@ use {
         instruction::{AccountMeta, CompiledInstruction, Instruction, InstructionError},
         loader_upgradeable_instruction::UpgradeableLoaderInstruction,
         loader_v4::{self, LoaderV4State},
-        message::{Message, MessageHeader, SanitizedMessage},
+        message::{
+            v0, Message, MessageAddressTableLookup, MessageHeader, SanitizedMessage,
+            VersionedMessage,
+        },
         native_loader,
         native_token::{sol_to_lamports, LAMPORTS_PER_SOL},
         nonce::{self, state::DurableNonce},
@@ -122,6 +132,7 @@ use {
         },
     },
     std::{
+        borrow::Cow,
         collections::{HashMap, HashSet},
         convert::TryInto,
         fs::File,
@@ -1663,6 +1674,10 @@ fn test_rent_eager_collect_rent_in_partition(should_collect_rent: bool) {
         .accounts
         .remove(&feature_set::disable_partitioned_rent_collection::id())
         .unwrap();
+    genesis_config
+        .accounts
+        .remove(&feature_set::disable_rent_scanning::id())
+        .unwrap();
     if should_collect_rent {
         genesis_config
             .accounts
@@ -3346,6 +3361,9 @@ fn test_load_and_execute_commit_transactions_fees_only(enable_fees_only_txs: boo
         .0;
 
     if enable_fees_only_txs {
+        let fee_breakdown =
+            bank.get_fee_breakdown_for_message(batch.sanitized_transactions()[0].message());
+        assert_eq!(fee_breakdown.total_fee(), 2001500);
         assert_eq!(
             commit_results,
             vec![Ok(CommittedTransaction {
@@ -3355,6 +3373,7 @@ fn test_load_and_execute_commit_transactions_fees_only(enable_fees_only_txs: boo
                 return_data: None,
                 executed_units: 0,
                 fee_details: FeeDetails::new(2001500, 0),
+                fee_breakdown,
                 rent_debits: RentDebits::default(),
                 loaded_account_stats: TransactionLoadedAccountsStats {
                     loaded_accounts_count: 2,
@@ -5804,6 +5823,124 @@ fn test_nonce_payer_tx_wide_cap() {
     );
 }
 
+#[test]
+fn test_nonce_high_cu_derived_fee_insufficient_payer() {
+    solana_logger::setup();
+    let custodian_starting_balance = 5_000_000;
+    let nonce_starting_balance = 250_000;
+    let (mut bank, _mint_keypair, custodian_keypair, nonce_keypair, bank_forks) =
+        setup_nonce_with_bank(
+            10_000_000,
+            |_| {},
+            custodian_starting_balance,
+            nonce_starting_balance,
+            None,
+            FeatureSet::all_enabled(),
+        )
+        .unwrap();
+    let custodian_pubkey = custodian_keypair.pubkey();
+    let nonce_pubkey = nonce_keypair.pubkey();
+    let nonce_hash = get_nonce_blockhash(&bank, &nonce_pubkey).unwrap();
+
+    for _ in 0..MAX_RECENT_BLOCKHASHES + 1 {
+        goto_end_of_slot(bank.clone());
+        bank = new_from_parent_with_fork_next_slot(bank, bank_forks.as_ref());
+    }
+
+    // A compute unit price this high derives a fee well above what either the
+    // custodian or the nonce account can pay
+    let instructions = [
+        system_instruction::advance_nonce_account(&nonce_pubkey, &nonce_pubkey),
+        ComputeBudgetInstruction::set_compute_unit_price(1_000_000_000_000),
+        system_instruction::transfer(&custodian_pubkey, &Pubkey::new_unique(), 1),
+    ];
+    for (fee_payer, starting_balance) in [
+        (&custodian_keypair, custodian_starting_balance),
+        (&nonce_keypair, nonce_starting_balance),
+    ] {
+        let nonce_tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&fee_payer.pubkey()),
+            &[&custodian_keypair, &nonce_keypair],
+            nonce_hash,
+        );
+        let mut recent_message = nonce_tx.message.clone();
+        recent_message.recent_blockhash = bank.last_blockhash();
+        assert!(
+            bank.get_fee_for_message(&new_sanitized_message(recent_message))
+                .unwrap()
+                > starting_balance
+        );
+
+        // The transaction is rejected outright: no fee is debited and the
+        // nonce is left untouched so it can be reused with an affordable fee
+        assert_eq!(
+            bank.process_transaction(&nonce_tx),
+            Err(TransactionError::FeePayerInsufficientForBaseFee)
+        );
+        assert_eq!(
+            bank.get_balance(&custodian_pubkey),
+            custodian_starting_balance
+        );
+        assert_eq!(bank.get_balance(&nonce_pubkey), nonce_starting_balance);
+        assert_eq!(
+            nonce_hash,
+            get_nonce_blockhash(&bank, &nonce_pubkey).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_nonce_high_cu_derived_fee_charged_in_full() {
+    solana_logger::setup();
+    let custodian_starting_balance = 5_000_000;
+    let (mut bank, _mint_keypair, custodian_keypair, nonce_keypair, bank_forks) =
+        setup_nonce_with_bank(
+            10_000_000,
+            |_| {},
+            custodian_starting_balance,
+            250_000,
+            None,
+            FeatureSet::all_enabled(),
+        )
+        .unwrap();
+    let custodian_pubkey = custodian_keypair.pubkey();
+    let nonce_pubkey = nonce_keypair.pubkey();
+    let nonce_hash = get_nonce_blockhash(&bank, &nonce_pubkey).unwrap();
+
+    for _ in 0..MAX_RECENT_BLOCKHASHES + 1 {
+        goto_end_of_slot(bank.clone());
+        bank = new_from_parent_with_fork_next_slot(bank, bank_forks.as_ref());
+    }
+
+    let nonce_tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::advance_nonce_account(&nonce_pubkey, &nonce_pubkey),
+            ComputeBudgetInstruction::set_compute_unit_price(5_000_000),
+            system_instruction::transfer(&custodian_pubkey, &Pubkey::new_unique(), 100_000),
+        ],
+        Some(&custodian_pubkey),
+        &[&custodian_keypair, &nonce_keypair],
+        nonce_hash,
+    );
+    let mut recent_message = nonce_tx.message.clone();
+    recent_message.recent_blockhash = bank.last_blockhash();
+    let expected_fee = bank
+        .get_fee_for_message(&new_sanitized_message(recent_message))
+        .unwrap();
+    assert!(expected_fee > bank.get_lamports_per_signature());
+
+    assert_eq!(bank.process_transaction(&nonce_tx), Ok(()));
+    assert_eq!(
+        bank.get_balance(&custodian_pubkey),
+        custodian_starting_balance - expected_fee - 100_000
+    );
+    assert_ne!(
+        nonce_hash,
+        get_nonce_blockhash(&bank, &nonce_pubkey).unwrap()
+    );
+}
+
 #[test]
 fn test_nonce_fee_calculator_updates() {
     let (mut genesis_config, mint_keypair) = create_genesis_config(1_000_000);
@@ -6341,6 +6478,115 @@ fn test_process_transaction_with_too_many_account_locks() {
     assert_eq!(result, Err(TransactionError::TooManyAccountLocks));
 }
 
+#[test]
+fn test_raise_tx_account_lock_limit() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let mut bank = Bank::new_for_tests(&genesis_config);
+
+    // a transaction locking more accounts than allowed before the feature activation
+    let payer = Keypair::new();
+    let mut account_keys = vec![payer.pubkey()];
+    account_keys.extend((1..200).map(|_| Pubkey::new_unique()));
+    let message = Message {
+        header: MessageHeader {
+            num_required_signatures: 1,
+            num_readonly_signed_accounts: 0,
+            num_readonly_unsigned_accounts: 100,
+        },
+        account_keys,
+        recent_blockhash: Hash::default(),
+        instructions: vec![],
+    };
+    let tx = Transaction::new(&[&payer], message, genesis_config.hash());
+
+    bank.deactivate_feature(&feature_set::raise_tx_account_lock_limit::id());
+    assert_eq!(
+        bank.get_transaction_account_lock_limit(),
+        MAX_TX_ACCOUNT_LOCKS
+    );
+    let batch = bank.prepare_batch_for_tests(vec![tx.clone()]);
+    assert_eq!(
+        batch.lock_results()[0],
+        Err(TransactionError::TooManyAccountLocks)
+    );
+    drop(batch);
+
+    bank.activate_feature(&feature_set::raise_tx_account_lock_limit::id());
+    assert_eq!(
+        bank.get_transaction_account_lock_limit(),
+        RAISED_MAX_TX_ACCOUNT_LOCKS
+    );
+    let batch = bank.prepare_batch_for_tests(vec![tx]);
+    assert!(batch.lock_results()[0].is_ok());
+}
+
+#[test]
+fn test_raise_tx_account_lock_limit_with_address_lookup_table() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let mut bank = Bank::new_for_tests(&genesis_config);
+
+    // a lookup table holding enough addresses to exceed the limit before the feature activation
+    let table_key = Pubkey::new_unique();
+    let table_addresses: Vec<_> = (0..199).map(|_| Pubkey::new_unique()).collect();
+    let table_state = AddressLookupTable {
+        meta: LookupTableMeta {
+            // all addresses are usable in the slot the table was extended in
+            last_extended_slot_start_index: table_addresses.len() as u8,
+            ..LookupTableMeta::default()
+        },
+        addresses: Cow::Owned(table_addresses),
+    };
+    let table_account = AccountSharedData::create(
+        1,
+        table_state.serialize_for_tests().unwrap(),
+        address_lookup_table::program::id(),
+        false,
+        0,
+    );
+    bank.store_account(&table_key, &table_account);
+
+    // only the fee payer is a static key, the other locks come from the lookup table
+    let payer = Keypair::new();
+    let message = v0::Message {
+        header: MessageHeader {
+            num_required_signatures: 1,
+            num_readonly_signed_accounts: 0,
+            num_readonly_unsigned_accounts: 0,
+        },
+        account_keys: vec![payer.pubkey()],
+        recent_blockhash: genesis_config.hash(),
+        instructions: vec![],
+        address_table_lookups: vec![MessageAddressTableLookup {
+            account_key: table_key,
+            writable_indexes: vec![],
+            readonly_indexes: (0..199).collect(),
+        }],
+    };
+    let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), &[&payer]).unwrap();
+    let sanitized_tx = bank.fully_verify_transaction(tx).unwrap();
+    assert_eq!(sanitized_tx.account_keys().len(), 200);
+
+    bank.deactivate_feature(&feature_set::raise_tx_account_lock_limit::id());
+    assert_eq!(
+        bank.get_transaction_account_lock_limit(),
+        MAX_TX_ACCOUNT_LOCKS
+    );
+    let batch = bank.prepare_sanitized_batch(std::slice::from_ref(&sanitized_tx));
+    assert_eq!(
+        batch.lock_results()[0],
+        Err(TransactionError::TooManyAccountLocks)
+    );
+    drop(batch);
+
+    bank.activate_feature(&feature_set::raise_tx_account_lock_limit::id());
+    assert_eq!(
+        bank.get_transaction_account_lock_limit(),
+        RAISED_MAX_TX_ACCOUNT_LOCKS
+    );
+    let batch = bank.prepare_sanitized_batch(std::slice::from_ref(&sanitized_tx));
+    assert!(batch.lock_results()[0].is_ok());
+}
+
 #[test]
 fn test_program_id_as_payer() {
     solana_logger::setup();
@@ -10636,6 +10882,12 @@ fn calculate_test_fee(
         fee_budget_limits.prioritization_fee,
         FeeFeatures {
             enable_secp256r1_precompile: true,
+            enforce_min_compute_unit_price: false,
+            raise_max_heap_frame_bytes: false,
+            charge_non_conforming_vote_transactions: false,
+            transfer_many: false,
+            base_fee_multiplier: solana_fee::BASE_FEE_MULTIPLIER,
+            min_compute_unit_price: solana_fee::MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
         },
     )
 }
@@ -10752,6 +11004,279 @@ fn test_calculate_prioritization_fee() {
     assert_eq!(fee, 2000301500);
 }
 
+#[test]
+fn test_calculate_fee_min_compute_unit_price() {
+    let fee_features = FeeFeatures {
+        enable_secp256r1_precompile: true,
+        enforce_min_compute_unit_price: true,
+        raise_max_heap_frame_bytes: false,
+        charge_non_conforming_vote_transactions: false,
+        transfer_many: false,
+        base_fee_multiplier: solana_fee::BASE_FEE_MULTIPLIER,
+        min_compute_unit_price: solana_fee::MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+    };
+    let calculate_fee_breakdown = |message: &SanitizedMessage, enforce_min_compute_unit_price| {
+        let fee_budget_limits = FeeBudgetLimits::from(
+            process_compute_budget_instructions(
+                message.program_instructions_iter(),
+                &FeatureSet::default(),
+            )
+            .unwrap_or_default(),
+        );
+        solana_fee::calculate_fee_breakdown(
+            message,
+            false,
+            1,
+            fee_budget_limits.prioritization_fee,
+            FeeFeatures {
+                enforce_min_compute_unit_price,
+                ..fee_features
+            },
+        )
+    };
+    let transfer = system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1);
+    let new_message = |compute_unit_price| {
+        new_sanitized_message(Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_limit(10_000),
+                ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+                transfer.clone(),
+            ],
+            Some(&Pubkey::new_unique()),
+        ))
+    };
+
+    // A bid below the minimum price is charged at the minimum price once enforced
+    let message = new_message(1_000);
+    assert_eq!(
+        solana_fee::compute_unit_price_below_minimum(&message, fee_features),
+        Some(1_000)
+    );
+    let fee_breakdown = calculate_fee_breakdown(&message, false);
+    assert_eq!(fee_breakdown.prioritization, 10);
+    let enforced_fee_breakdown = calculate_fee_breakdown(&message, true);
+    assert_eq!(enforced_fee_breakdown.prioritization, 10_000);
+    assert_eq!(
+        enforced_fee_breakdown.transaction_fee(),
+        fee_breakdown.transaction_fee()
+    );
+
+    // A bid at the minimum price is unaffected
+    let message = new_message(solana_fee::MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS);
+    assert_eq!(
+        solana_fee::compute_unit_price_below_minimum(&message, fee_features),
+        None
+    );
+    assert_eq!(
+        calculate_fee_breakdown(&message, true),
+        calculate_fee_breakdown(&message, false)
+    );
+
+    // The minimum price is the one the fee is calculated with
+    assert_eq!(
+        solana_fee::compute_unit_price_below_minimum(
+            &message,
+            FeeFeatures {
+                min_compute_unit_price: 2 * solana_fee::MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+                ..fee_features
+            }
+        ),
+        Some(solana_fee::MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS)
+    );
+
+    // So is a transaction that does not bid
+    let message = new_sanitized_message(Message::new(&[transfer], Some(&Pubkey::new_unique())));
+    assert_eq!(
+        solana_fee::compute_unit_price_below_minimum(&message, fee_features),
+        None
+    );
+    assert_eq!(
+        calculate_fee_breakdown(&message, true),
+        calculate_fee_breakdown(&message, false)
+    );
+
+    // TransferMany recipients count towards the threshold only once `transfer_many` is active
+    let payer = Pubkey::new_unique();
+    let recipients: Vec<_> = (0..30).map(|_| (Pubkey::new_unique(), 1)).collect();
+    let message = new_sanitized_message(Message::new(
+        &[
+            tachyon_system_interface::transfer_many::transfer_many(&payer, &recipients),
+            ComputeBudgetInstruction::set_compute_unit_price(1_000),
+        ],
+        Some(&payer),
+    ));
+    assert_eq!(
+        solana_fee::compute_unit_price_below_minimum(&message, fee_features),
+        Some(1_000)
+    );
+    assert_eq!(
+        solana_fee::compute_unit_price_below_minimum(
+            &message,
+            FeeFeatures {
+                transfer_many: true,
+                ..fee_features
+            }
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_calculate_fee_heap_frame() {
+    let calculate_fee_breakdown = |message: &SanitizedMessage, raise_max_heap_frame_bytes| {
+        solana_fee::calculate_fee_breakdown(
+            message,
+            false,
+            1,
+            0,
+            FeeFeatures {
+                enable_secp256r1_precompile: true,
+                enforce_min_compute_unit_price: true,
+                raise_max_heap_frame_bytes,
+                charge_non_conforming_vote_transactions: false,
+                transfer_many: false,
+                base_fee_multiplier: solana_fee::BASE_FEE_MULTIPLIER,
+                min_compute_unit_price: solana_fee::MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+            },
+        )
+    };
+    let transfer = system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1);
+    let new_message = |heap_size| {
+        new_sanitized_message(Message::new(
+            &[
+                ComputeBudgetInstruction::request_heap_frame(heap_size),
+                transfer.clone(),
+            ],
+            Some(&Pubkey::new_unique()),
+        ))
+    };
+
+    // Heaps up to MAX_HEAP_FRAME_BYTES are covered by the compute unit fees
+    for heap_size in [MIN_HEAP_FRAME_BYTES, MAX_HEAP_FRAME_BYTES] {
+        let message = new_message(heap_size);
+        assert_eq!(calculate_fee_breakdown(&message, true).memory, 0);
+    }
+
+    // Larger heaps pay for every byte above it
+    let message = new_message(X1_MAX_HEAP_FRAME_BYTES);
+    let fee_breakdown = calculate_fee_breakdown(&message, true);
+    let expected_memory_fee = u64::from(X1_MAX_HEAP_FRAME_BYTES - MAX_HEAP_FRAME_BYTES)
+        * solana_fee::HEAP_FEE_LAMPORTS_PER_BYTE;
+    assert_eq!(fee_breakdown.memory, expected_memory_fee);
+    let no_heap_fee_breakdown = calculate_fee_breakdown(&message, false);
+    assert_eq!(no_heap_fee_breakdown.memory, 0);
+    assert_eq!(
+        fee_breakdown.transaction_fee(),
+        no_heap_fee_breakdown.transaction_fee() + expected_memory_fee
+    );
+}
+
+#[test]
+fn test_calculate_fee_non_conforming_vote() {
+    let calculate_fee = |message: &SanitizedMessage, charge_non_conforming_vote_transactions| {
+        solana_fee::calculate_fee(
+            message,
+            false,
+            1,
+            0,
+            FeeFeatures {
+                enable_secp256r1_precompile: true,
+                enforce_min_compute_unit_price: true,
+                raise_max_heap_frame_bytes: true,
+                charge_non_conforming_vote_transactions,
+                transfer_many: false,
+                base_fee_multiplier: solana_fee::BASE_FEE_MULTIPLIER,
+                min_compute_unit_price: solana_fee::MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+            },
+        )
+    };
+    let node_pubkey = Pubkey::new_unique();
+    let authorized_voter = Pubkey::new_unique();
+    let vote = vote_instruction::vote(
+        &Pubkey::new_unique(),
+        &authorized_voter,
+        Vote::new(vec![1], Hash::default()),
+    );
+
+    // Simple votes remain free
+    let message = new_sanitized_message(Message::new(&[vote.clone()], Some(&node_pubkey)));
+    assert!(solana_fee::is_conforming_vote_transaction(&message));
+    assert_eq!(calculate_fee(&message, false), 0);
+    assert_eq!(calculate_fee(&message, true), 0);
+
+    // Votes bundling other instructions are charged once the feature is active
+    let transfer = system_instruction::transfer(&node_pubkey, &Pubkey::new_unique(), 1);
+    let message = new_sanitized_message(Message::new(
+        &[vote.clone(), transfer.clone()],
+        Some(&node_pubkey),
+    ));
+    assert!(!solana_fee::is_conforming_vote_transaction(&message));
+    assert_eq!(calculate_fee(&message, false), 0);
+    let transfer_fee = calculate_fee(
+        &new_sanitized_message(Message::new(&[transfer], Some(&node_pubkey))),
+        true,
+    );
+    assert!(calculate_fee(&message, true) > transfer_fee);
+
+    // As are votes with signers beyond the vote authority and the identity
+    let mut vote_with_extra_signer = vote;
+    vote_with_extra_signer
+        .accounts
+        .push(AccountMeta::new_readonly(Pubkey::new_unique(), true));
+    let message =
+        new_sanitized_message(Message::new(&[vote_with_extra_signer], Some(&node_pubkey)));
+    assert_eq!(message.num_transaction_signatures(), 3);
+    assert!(!solana_fee::is_conforming_vote_transaction(&message));
+    assert_eq!(calculate_fee(&message, false), 0);
+    assert!(calculate_fee(&message, true) > 0);
+}
+
+#[test]
+fn test_calculate_fee_transfer_many() {
+    let calculate_fee = |message: &SanitizedMessage, transfer_many| {
+        solana_fee::calculate_fee(
+            message,
+            false,
+            1,
+            0,
+            FeeFeatures {
+                enable_secp256r1_precompile: true,
+                enforce_min_compute_unit_price: true,
+                raise_max_heap_frame_bytes: true,
+                charge_non_conforming_vote_transactions: true,
+                transfer_many,
+                base_fee_multiplier: solana_fee::BASE_FEE_MULTIPLIER,
+                min_compute_unit_price: solana_fee::MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+            },
+        )
+    };
+    let from = Pubkey::new_unique();
+    let new_message = |num_recipients| {
+        let transfers: Vec<_> = (0..num_recipients)
+            .map(|_| (Pubkey::new_unique(), 1))
+            .collect();
+        let instruction = tachyon_system_interface::transfer_many::transfer_many(&from, &transfers);
+        new_sanitized_message(Message::new(&[instruction], Some(&from)))
+    };
+
+    // Every recipient adds to the fee once the feature is active
+    let one_recipient_fee = calculate_fee(&new_message(1), true);
+    let fifty_recipients_fee = calculate_fee(&new_message(50), true);
+    assert!(fifty_recipients_fee > one_recipient_fee);
+    assert!(
+        fifty_recipients_fee
+            >= 50
+                * tachyon_system_interface::transfer_many::TRANSFER_MANY_COMPUTE_UNITS_PER_RECIPIENT
+                * solana_fee::BASE_FEE_MULTIPLIER
+    );
+
+    // Without it, only the system program is charged
+    assert_eq!(
+        calculate_fee(&new_message(50), false),
+        calculate_fee(&new_message(1), false)
+    );
+}
+
 #[test]
 fn test_calculate_fee_secp256k1() {
     let fee_structure = FeeStructure {
@@ -11657,6 +12182,10 @@ fn test_resize_and_rent() {
     } = create_genesis_config_with_leader(1_000_000_000, &Pubkey::new_unique(), 42);
     genesis_config.rent = Rent::default();
     activate_all_features(&mut genesis_config);
+    // Resizes without the storage deposit are covered by the rent collector tests
+    genesis_config
+        .accounts
+        .remove(&feature_set::storage_deposit::id());
 
     let mock_program_id = Pubkey::new_unique();
     let (bank, _bank_forks) = Bank::new_with_mockup_builtin_for_tests(
@@ -11929,10 +12458,14 @@ fn test_resize_and_rent() {
 #[test]
 fn test_accounts_data_size_and_resize_transactions() {
     let GenesisConfigInfo {
-        genesis_config,
+        mut genesis_config,
         mint_keypair,
         ..
     } = genesis_utils::create_genesis_config(100 * LAMPORTS_PER_SOL);
+    // Grown accounts would otherwise need a storage deposit for all of their data
+    genesis_config
+        .accounts
+        .remove(&feature_set::storage_deposit::id());
     let mock_program_id = Pubkey::new_unique();
     let (bank, _bank_forks) = Bank::new_with_mockup_builtin_for_tests(
         &genesis_config,
@@ -12078,6 +12611,9 @@ fn test_partitioned_rent_collection(should_run_partitioned_rent_collection: bool
         genesis_config
             .accounts
             .remove(&agave_feature_set::disable_partitioned_rent_collection::id());
+        genesis_config
+            .accounts
+            .remove(&agave_feature_set::disable_rent_scanning::id());
     }
 
     let bank = Arc::new(Bank::new_for_tests(&genesis_config));
@@ -12108,6 +12644,49 @@ fn test_partitioned_rent_collection(should_run_partitioned_rent_collection: bool
     }
 }
 
+/// Ensure that rent scanning stays disabled even if partitioned rent collection is not
+#[test]
+fn test_disable_rent_scanning() {
+    let GenesisConfigInfo {
+        mut genesis_config, ..
+    } = genesis_utils::create_genesis_config(100 * LAMPORTS_PER_SOL);
+    genesis_config.rent = Rent::default();
+    genesis_config
+        .accounts
+        .remove(&agave_feature_set::disable_partitioned_rent_collection::id());
+    genesis_config
+        .accounts
+        .remove(&agave_feature_set::disable_rent_fees_collection::id());
+
+    let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+    assert!(!bank.should_collect_rent());
+    let slot = bank.slot() + bank.slot_count_per_normal_epoch();
+    let bank = Arc::new(Bank::new_from_parent(bank, &Pubkey::default(), slot));
+
+    // Rent exempt accounts are not rewritten and rent paying accounts keep their lamports
+    let rent_exempt_pubkey = Pubkey::new_unique();
+    let rent_exempt_account = AccountSharedData::new(
+        genesis_config.rent.minimum_balance(0),
+        0,
+        &Pubkey::default(),
+    );
+    bank.store_account(&rent_exempt_pubkey, &rent_exempt_account);
+    let rent_paying_pubkey = Pubkey::new_unique();
+    let rent_paying_account = AccountSharedData::new(1, 0, &Pubkey::default());
+    bank.store_account(&rent_paying_pubkey, &rent_paying_account);
+
+    bank.collect_rent_eagerly();
+    assert_eq!(
+        bank.get_account(&rent_exempt_pubkey).unwrap(),
+        rent_exempt_account
+    );
+    assert_eq!(
+        bank.get_account(&rent_paying_pubkey).unwrap(),
+        rent_paying_account
+    );
+    assert_eq!(bank.collected_rent.load(Relaxed), 0);
+}
+
 /// Ensure that accounts data size is updated correctly by rent collection
 #[test_case(true; "enable rent fees collection")]
 #[test_case(false; "disable rent fees collection")]
@@ -12123,6 +12702,9 @@ fn test_accounts_data_size_and_rent_collection(should_collect_rent: bool) {
         genesis_config
             .accounts
             .remove(&agave_feature_set::disable_partitioned_rent_collection::id());
+        genesis_config
+            .accounts
+            .remove(&agave_feature_set::disable_rent_scanning::id());
     }
 
     let bank = Arc::new(Bank::new_for_tests(&genesis_config));
@@ -12807,6 +13389,9 @@ where
         genesis_config
             .accounts
             .remove(&agave_feature_set::disable_partitioned_rent_collection::id());
+        genesis_config
+            .accounts
+            .remove(&agave_feature_set::disable_rent_scanning::id());
     }
     let (bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
     let bank_client = BankClient::new_shared(bank.clone());
@@ -13509,6 +14094,90 @@ fn test_failed_simulation_load_error() {
     );
 }
 
+/// Test that simulations load overridden accounts in place of the bank's
+#[test]
+fn test_simulation_with_account_overrides() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let (bank, _bank_forks) = bank.wrap_with_bank_forks_for_tests();
+    let payer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let transaction =
+        system_transaction::transfer(&payer, &recipient, LAMPORTS_PER_SOL, bank.last_blockhash());
+
+    bank.freeze();
+    let sanitized = RuntimeTransaction::from_transaction_for_tests(transaction);
+    let simulation = bank.simulate_transaction(&sanitized, false);
+    assert_eq!(simulation.result, Err(TransactionError::AccountNotFound));
+
+    let mut account_overrides = AccountOverrides::default();
+    account_overrides.set_account(
+        &payer.pubkey(),
+        Some(AccountSharedData::new(
+            10 * LAMPORTS_PER_SOL,
+            0,
+            &system_program::id(),
+        )),
+    );
+    let simulation =
+        bank.simulate_transaction_with_account_overrides(&sanitized, false, account_overrides);
+    assert_eq!(simulation.result, Ok(()));
+    let (_, recipient_account) = simulation
+        .post_simulation_accounts
+        .iter()
+        .find(|(pubkey, _)| *pubkey == recipient)
+        .unwrap();
+    assert_eq!(recipient_account.lamports(), LAMPORTS_PER_SOL);
+    // The override is not written to the bank
+    assert_eq!(bank.get_balance(&payer.pubkey()), 0);
+}
+
+/// Test that bundle simulations load the accounts written by the transactions before
+#[test]
+fn test_simulate_bundle() {
+    let (genesis_config, mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let (bank, _bank_forks) = bank.wrap_with_bank_forks_for_tests();
+    let alice = Keypair::new();
+    let bob = Pubkey::new_unique();
+    let fund_alice = system_transaction::transfer(
+        &mint_keypair,
+        &alice.pubkey(),
+        LAMPORTS_PER_SOL / 2,
+        bank.last_blockhash(),
+    );
+    let pay_bob =
+        system_transaction::transfer(&alice, &bob, LAMPORTS_PER_SOL / 4, bank.last_blockhash());
+
+    bank.freeze();
+    let transactions = [fund_alice, pay_bob]
+        .into_iter()
+        .map(RuntimeTransaction::from_transaction_for_tests)
+        .collect::<Vec<_>>();
+    let results = bank.simulate_bundle(&transactions, false, AccountOverrides::default());
+    assert_eq!(results.len(), 2);
+    for ((simulation, fee_details), transaction) in results.iter().zip(&transactions) {
+        assert_eq!(simulation.result, Ok(()));
+        assert_eq!(simulation.units_consumed, 150);
+        assert_eq!(
+            Some(fee_details.total_fee()),
+            bank.get_fee_for_message(transaction.message())
+        );
+    }
+    let (_, bob_account) = results[1]
+        .0
+        .post_simulation_accounts
+        .iter()
+        .find(|(pubkey, _)| *pubkey == bob)
+        .unwrap();
+    assert_eq!(bob_account.lamports(), LAMPORTS_PER_SOL / 4);
+
+    // Alice can't pay without the first transaction
+    let results = bank.simulate_bundle(&transactions[1..], false, AccountOverrides::default());
+    assert_eq!(results[0].0.result, Err(TransactionError::AccountNotFound));
+    assert_eq!(bank.get_balance(&alice.pubkey()), 0);
+}
+
 #[test]
 fn test_filter_program_errors_and_collect_fee_details() {
     // TX  | PROCESSING RESULT           | COLLECT            | COLLECT
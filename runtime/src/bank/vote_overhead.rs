@@ -0,0 +1,215 @@
+//! Accounting of the cost of vote transactions per epoch.
+//!
+//! Votes pay no fees on X1, so their use of the block is not visible in the fees collected by
+//! the cluster. Every frozen bank adds the vote costs recorded by its cost tracker to the totals
+//! of its epoch, which are inherited by its children, so that the overhead of votes can be
+//! weighed when deciding whether to reintroduce vote fees.
+//!
+//! The totals are not part of snapshots: after a restart, the totals of the current epoch only
+//! cover the banks replayed since.
+
+use {
+    super::Bank, solana_cost_model::cost_tracker::CostTracker, solana_sdk::clock::Epoch,
+    std::sync::RwLock,
+};
+
+/// Costs of the blocks of an epoch, and of their vote transactions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoteOverheadStats {
+    pub slots: u64,
+    pub transaction_count: u64,
+    pub vote_transaction_count: u64,
+    pub signature_count: u64,
+    pub vote_signature_count: u64,
+    /// Compute units of the blocks
+    pub block_cost: u64,
+    /// Compute units of the vote transactions of the blocks
+    pub vote_cost: u64,
+    /// Sum of the block cost limits of the blocks
+    pub block_cost_limit: u64,
+}
+
+impl VoteOverheadStats {
+    fn add_block(&mut self, cost_tracker: &CostTracker) {
+        self.slots = self.slots.saturating_add(1);
+        self.transaction_count = self
+            .transaction_count
+            .saturating_add(cost_tracker.transaction_count());
+        self.vote_transaction_count = self
+            .vote_transaction_count
+            .saturating_add(cost_tracker.vote_transaction_count());
+        self.signature_count = self
+            .signature_count
+            .saturating_add(cost_tracker.transaction_signature_count());
+        self.vote_signature_count = self
+            .vote_signature_count
+            .saturating_add(cost_tracker.vote_transaction_signature_count());
+        self.block_cost = self.block_cost.saturating_add(cost_tracker.block_cost());
+        self.vote_cost = self.vote_cost.saturating_add(cost_tracker.vote_cost());
+        self.block_cost_limit = self
+            .block_cost_limit
+            .saturating_add(cost_tracker.get_block_limit());
+    }
+}
+
+/// Vote overhead of the epoch of a bank up to and including the bank, and of the previous epoch
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VoteOverhead {
+    pub epoch: Epoch,
+    pub stats: VoteOverheadStats,
+    pub previous_epoch: Option<(Epoch, VoteOverheadStats)>,
+}
+
+impl VoteOverhead {
+    fn new(epoch: Epoch) -> Self {
+        Self {
+            epoch,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the totals a child bank in `epoch` starts from
+    fn for_child(&self, epoch: Epoch) -> Self {
+        if epoch == self.epoch {
+            *self
+        } else {
+            Self {
+                epoch,
+                stats: VoteOverheadStats::default(),
+                previous_epoch: Some((self.epoch, self.stats)),
+            }
+        }
+    }
+}
+
+impl Bank {
+    pub(super) fn new_vote_overhead(epoch: Epoch) -> RwLock<VoteOverhead> {
+        RwLock::new(VoteOverhead::new(epoch))
+    }
+
+    pub(super) fn new_vote_overhead_from_parent(&self, epoch: Epoch) -> RwLock<VoteOverhead> {
+        let vote_overhead = self.vote_overhead().for_child(epoch);
+        if epoch != self.epoch() {
+            let stats = self.vote_overhead().stats;
+            datapoint_info!(
+                "bank-vote_overhead",
+                ("epoch", self.epoch(), i64),
+                ("slot", self.slot(), i64),
+                ("slots", stats.slots, i64),
+                ("transaction_count", stats.transaction_count, i64),
+                ("vote_transaction_count", stats.vote_transaction_count, i64),
+                ("signature_count", stats.signature_count, i64),
+                ("vote_signature_count", stats.vote_signature_count, i64),
+                ("block_cost", stats.block_cost, i64),
+                ("vote_cost", stats.vote_cost, i64),
+                ("block_cost_limit", stats.block_cost_limit, i64),
+            );
+        }
+        RwLock::new(vote_overhead)
+    }
+
+    /// Adds the costs of the block of the bank to the totals of its epoch, when the bank is
+    /// frozen
+    pub(super) fn update_vote_overhead(&self) {
+        let cost_tracker = self.read_cost_tracker().unwrap();
+        self.vote_overhead
+            .write()
+            .unwrap()
+            .stats
+            .add_block(&cost_tracker);
+    }
+
+    /// Returns the vote overhead of the epoch of the bank, up to and including the bank
+    pub fn vote_overhead(&self) -> VoteOverhead {
+        *self.vote_overhead.read().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::{create_genesis_config_with_leader, GenesisConfigInfo},
+        solana_cost_model::cost_model::CostModel,
+        solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
+        solana_sdk::{
+            epoch_schedule::EpochSchedule, hash::Hash, pubkey::Pubkey, system_transaction,
+        },
+        solana_vote_program::{vote_state::TowerSync, vote_transaction},
+        std::sync::Arc,
+    };
+
+    #[test]
+    fn test_vote_overhead() {
+        let GenesisConfigInfo {
+            mut genesis_config,
+            mint_keypair,
+            voting_keypair,
+            ..
+        } = create_genesis_config_with_leader(1_000_000_000, &Pubkey::new_unique(), 100);
+        genesis_config.epoch_schedule = EpochSchedule::custom(32, 32, false);
+        let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+        let slots_in_epoch = bank.get_slots_in_epoch(0);
+        let bank = Bank::new_from_parent(bank, &Pubkey::default(), 1);
+
+        // Costs are tracked by banking stage and replay, as the block is produced or replayed
+        let transfer = RuntimeTransaction::from_transaction_for_tests(
+            system_transaction::transfer(&mint_keypair, &Pubkey::new_unique(), 1, Hash::default()),
+        );
+        let vote = RuntimeTransaction::from_transaction_for_tests(
+            vote_transaction::new_tower_sync_transaction(
+                TowerSync::from(vec![(0, 1)]),
+                Hash::default(),
+                &voting_keypair,
+                &voting_keypair,
+                &voting_keypair,
+                None,
+            ),
+        );
+        let transfer_cost = CostModel::calculate_cost(&transfer, &bank.feature_set);
+        let vote_cost = CostModel::calculate_cost(&vote, &bank.feature_set);
+        assert!(vote_cost.is_simple_vote());
+        {
+            let mut cost_tracker = bank.write_cost_tracker().unwrap();
+            cost_tracker.try_add(&transfer_cost).unwrap();
+            cost_tracker.try_add(&vote_cost).unwrap();
+        }
+        bank.freeze();
+
+        // The genesis bank was frozen as the parent of the bank
+        let vote_overhead = bank.vote_overhead();
+        assert_eq!(vote_overhead.epoch, 0);
+        assert_eq!(vote_overhead.previous_epoch, None);
+        let stats = vote_overhead.stats;
+        assert_eq!(
+            stats,
+            VoteOverheadStats {
+                slots: 2,
+                transaction_count: 2,
+                vote_transaction_count: 1,
+                signature_count: 2,
+                vote_signature_count: 1,
+                block_cost: transfer_cost.sum() + vote_cost.sum(),
+                vote_cost: vote_cost.sum(),
+                block_cost_limit: 2 * bank.read_cost_tracker().unwrap().get_block_limit(),
+            }
+        );
+
+        // Children inherit the totals of their epoch, and start over in a new epoch
+        let bank = Arc::new(bank);
+        let child = Bank::new_from_parent(bank.clone(), &Pubkey::default(), 2);
+        child.freeze();
+        assert_eq!(child.vote_overhead().stats.slots, 3);
+        assert_eq!(child.vote_overhead().stats.vote_transaction_count, 1);
+        let child = Bank::new_from_parent(bank, &Pubkey::default(), slots_in_epoch);
+        assert_eq!(
+            child.vote_overhead(),
+            VoteOverhead {
+                epoch: 1,
+                stats: VoteOverheadStats::default(),
+                previous_epoch: Some((0, stats)),
+            }
+        );
+    }
+}
@@ -19,6 +19,7 @@ pub mod loader_utils;
 pub mod non_circulating_supply;
 pub mod prioritization_fee;
 pub mod prioritization_fee_cache;
+pub mod program_cache_warmup;
 pub mod rent_collector;
 pub mod root_bank_cache;
 pub mod serde_snapshot;
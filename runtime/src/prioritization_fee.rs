@@ -132,6 +132,18 @@ pub enum PrioritizationFeeError {
     BlockIsAlreadyFinalized,
 }
 
+/// Maximum number of compute unit prices kept per program and block, further transactions are only
+/// counted
+pub const MAX_PROGRAM_COMPUTE_UNIT_PRICES: usize = 256;
+
+/// Transactions of a block writing to the accounts of a program
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProgramFees {
+    pub transactions: u64,
+    /// Compute unit prices of the first `MAX_PROGRAM_COMPUTE_UNIT_PRICES` transactions
+    pub compute_unit_prices: Vec<u64>,
+}
+
 /// Block minimum prioritization fee stats, includes the minimum prioritization fee for a transaction in this
 /// block; and the minimum fee for each writable account in all transactions in this block. The only relevant
 /// write account minimum fees are those greater than the block minimum transaction fee, because the minimum fee needed to land
@@ -144,6 +156,10 @@ pub struct PrioritizationFee {
     // The minimum prioritization fee of each writable account in transactions in this block.
     min_writable_account_fees: HashMap<Pubkey, u64>,
 
+    // The transactions of this block writing to the accounts of each program, if programs are
+    // attributed.
+    program_fees: HashMap<Pubkey, ProgramFees>,
+
     // Default to `false`, set to `true` when a block is completed, therefore the minimum fees recorded
     // are finalized, and can be made available for use (e.g., RPC query)
     is_finalized: bool,
@@ -157,6 +173,7 @@ impl Default for PrioritizationFee {
         PrioritizationFee {
             min_transaction_fee: u64::MAX,
             min_writable_account_fees: HashMap::new(),
+            program_fees: HashMap::new(),
             is_finalized: false,
             metrics: PrioritizationFeeMetrics::default(),
         }
@@ -193,6 +210,20 @@ impl PrioritizationFee {
         self.metrics.accumulate_total_update_elapsed_us(update_us);
     }
 
+    /// Update self for the fees of the programs owning the writable accounts of a transaction.
+    pub fn update_program_fees(&mut self, transaction_fee: u64, programs: &[Pubkey]) {
+        if self.is_finalized {
+            return;
+        }
+        for program in programs {
+            let program_fees = self.program_fees.entry(*program).or_default();
+            saturating_add_assign!(program_fees.transactions, 1);
+            if program_fees.compute_unit_prices.len() < MAX_PROGRAM_COMPUTE_UNIT_PRICES {
+                program_fees.compute_unit_prices.push(transaction_fee);
+            }
+        }
+    }
+
     /// Accounts that have minimum fees lesser or equal to the minimum fee in the block are redundant, they are
     /// removed to reduce memory footprint when mark_block_completed() is called.
     fn prune_irrelevant_writable_accounts(&mut self) {
@@ -223,6 +254,10 @@ impl PrioritizationFee {
         self.min_writable_account_fees.iter()
     }
 
+    pub fn get_program_fees(&self, program: &Pubkey) -> Option<&ProgramFees> {
+        self.program_fees.get(program)
+    }
+
     pub fn get_writable_accounts_count(&self) -> usize {
         self.min_writable_account_fees.len()
     }
@@ -374,4 +409,47 @@ mod tests {
         assert!(prioritization_fee.mark_block_completed().is_ok());
         assert!(prioritization_fee.mark_block_completed().is_err());
     }
+
+    #[test]
+    fn test_update_program_fees() {
+        let program_a = Pubkey::new_unique();
+        let program_b = Pubkey::new_unique();
+        let mut prioritization_fee = PrioritizationFee::default();
+
+        prioritization_fee.update_program_fees(5, &[program_a, program_b]);
+        prioritization_fee.update_program_fees(3, &[program_a]);
+        for fee in 0..MAX_PROGRAM_COMPUTE_UNIT_PRICES as u64 {
+            prioritization_fee.update_program_fees(fee, &[program_b]);
+        }
+        assert_eq!(
+            prioritization_fee.get_program_fees(&program_a),
+            Some(&ProgramFees {
+                transactions: 2,
+                compute_unit_prices: vec![5, 3],
+            })
+        );
+        let program_b_fees = prioritization_fee.get_program_fees(&program_b).unwrap();
+        assert_eq!(
+            program_b_fees.transactions,
+            MAX_PROGRAM_COMPUTE_UNIT_PRICES as u64 + 1
+        );
+        assert_eq!(
+            program_b_fees.compute_unit_prices.len(),
+            MAX_PROGRAM_COMPUTE_UNIT_PRICES
+        );
+        assert!(prioritization_fee
+            .get_program_fees(&Pubkey::new_unique())
+            .is_none());
+
+        // Finalized blocks are not updated anymore
+        prioritization_fee.mark_block_completed().unwrap();
+        prioritization_fee.update_program_fees(7, &[program_a]);
+        assert_eq!(
+            prioritization_fee
+                .get_program_fees(&program_a)
+                .unwrap()
+                .transactions,
+            2
+        );
+    }
 }
@@ -1,11 +1,13 @@
 use {
     crate::{bank::Bank, prioritization_fee::*},
     crossbeam_channel::{unbounded, Receiver, Sender, TryRecvError},
+    itertools::Itertools,
     log::*,
     solana_accounts_db::account_locks::validate_account_locks,
     solana_measure::measure_us,
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
     solana_sdk::{
+        account::ReadableAccount,
         clock::{BankId, Slot},
         pubkey::Pubkey,
     },
@@ -23,7 +25,7 @@ use {
 /// The maximum number of blocks to keep in `PrioritizationFeeCache`, ie.
 /// the amount of history generally desired to estimate the prioritization fee needed to
 /// land a transaction in the current block.
-const MAX_NUM_RECENT_BLOCKS: u64 = 150;
+pub const MAX_NUM_RECENT_BLOCKS: u64 = 150;
 
 /// Thers is no guarantee that slots coming in order, we keep extra slots in the buffer.
 const MAX_UNFINALIZED_SLOTS: u64 = 128;
@@ -130,6 +132,7 @@ enum CacheServiceUpdate {
         bank_id: BankId,
         transaction_fee: u64,
         writable_accounts: Vec<Pubkey>,
+        programs: Vec<Pubkey>,
     },
     BankFinalized {
         slot: Slot,
@@ -147,6 +150,7 @@ pub struct PrioritizationFeeCache {
     service_thread: Option<JoinHandle<()>>,
     sender: Sender<CacheServiceUpdate>,
     metrics: Arc<PrioritizationFeeCacheMetrics>,
+    program_attribution: bool,
 }
 
 impl Default for PrioritizationFeeCache {
@@ -168,6 +172,16 @@ impl Drop for PrioritizationFeeCache {
 
 impl PrioritizationFeeCache {
     pub fn new(capacity: u64) -> Self {
+        Self::new_with_config(capacity, false)
+    }
+
+    /// Also attributes the prioritization fees of transactions to the programs owning their
+    /// writable accounts, which costs a lookup of these accounts per transaction
+    pub fn new_with_program_attribution(capacity: u64) -> Self {
+        Self::new_with_config(capacity, true)
+    }
+
+    fn new_with_config(capacity: u64, program_attribution: bool) -> Self {
         let cache = Arc::new(RwLock::new(BTreeMap::new()));
         let (sender, receiver) = unbounded();
         let metrics = Arc::new(PrioritizationFeeCacheMetrics::default());
@@ -188,9 +202,14 @@ impl PrioritizationFeeCache {
             service_thread,
             sender,
             metrics,
+            program_attribution,
         }
     }
 
+    pub fn has_program_attribution(&self) -> bool {
+        self.program_attribution
+    }
+
     /// Update with a list of non-vote transactions' compute_budget_details and account_locks; Only
     /// transactions have both valid compute_budget_details and account_locks will be used to update
     /// fee_cache asynchronously.
@@ -227,7 +246,7 @@ impl PrioritizationFeeCache {
                     continue;
                 }
 
-                let writable_accounts = sanitized_transaction
+                let writable_accounts: Vec<_> = sanitized_transaction
                     .account_keys()
                     .iter()
                     .enumerate()
@@ -235,12 +254,24 @@ impl PrioritizationFeeCache {
                     .map(|(_, key)| *key)
                     .collect();
 
+                let programs = if self.program_attribution {
+                    writable_accounts
+                        .iter()
+                        .filter_map(|key| bank.get_account_with_fixed_root(key))
+                        .map(|account| *account.owner())
+                        .unique()
+                        .collect()
+                } else {
+                    vec![]
+                };
+
                 self.sender
                     .send(CacheServiceUpdate::TransactionUpdate {
                         slot: bank.slot(),
                         bank_id: bank.bank_id(),
                         transaction_fee: compute_budget_limits.compute_unit_price,
                         writable_accounts,
+                        programs,
                     })
                     .unwrap_or_else(|err| {
                         warn!(
@@ -275,14 +306,20 @@ impl PrioritizationFeeCache {
         bank_id: BankId,
         transaction_fee: u64,
         writable_accounts: Vec<Pubkey>,
+        programs: Vec<Pubkey>,
         metrics: &PrioritizationFeeCacheMetrics,
     ) {
-        let (_, entry_update_us) = measure_us!(unfinalized
-            .entry(slot)
-            .or_default()
-            .entry(bank_id)
-            .or_default()
-            .update(transaction_fee, writable_accounts));
+        let (_, entry_update_us) = measure_us!({
+            let prioritization_fee = unfinalized
+                .entry(slot)
+                .or_default()
+                .entry(bank_id)
+                .or_default();
+            prioritization_fee.update(transaction_fee, writable_accounts);
+            if !programs.is_empty() {
+                prioritization_fee.update_program_fees(transaction_fee, &programs);
+            }
+        });
         metrics.accumulate_total_entry_update_elapsed_us(entry_update_us);
         metrics.accumulate_successful_transaction_update_count(1);
     }
@@ -378,12 +415,14 @@ impl PrioritizationFeeCache {
                     bank_id,
                     transaction_fee,
                     writable_accounts,
+                    programs,
                 } => Self::update_cache(
                     &mut unfinalized,
                     slot,
                     bank_id,
                     transaction_fee,
                     writable_accounts,
+                    programs,
                     &metrics,
                 ),
                 CacheServiceUpdate::BankFinalized { slot, bank_id } => {
@@ -429,6 +468,23 @@ impl PrioritizationFeeCache {
             })
             .collect()
     }
+
+    /// Returns the transactions of every block writing to the accounts owned by `program`, which
+    /// are only attributed if the cache was created with program attribution
+    pub fn get_program_fees(&self, program: &Pubkey) -> Vec<(Slot, ProgramFees)> {
+        self.cache
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(slot, slot_prioritization_fee)| {
+                let program_fees = slot_prioritization_fee
+                    .get_program_fees(program)
+                    .cloned()
+                    .unwrap_or_default();
+                (*slot, program_fees)
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -445,6 +501,7 @@ mod tests {
             compute_budget::ComputeBudgetInstruction,
             message::Message,
             pubkey::Pubkey,
+            signature::Signer,
             system_instruction,
             transaction::{SanitizedTransaction, Transaction},
         },
@@ -859,6 +916,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_program_fees() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(10_000);
+        let bank0 = Bank::new_for_benches(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank0);
+        let bank = bank_forks.read().unwrap().working_bank();
+        let collector = solana_pubkey::new_rand();
+        let bank1 = Arc::new(Bank::new_from_parent(bank.clone(), &collector, 1));
+        let bank2 = Arc::new(Bank::new_from_parent(bank, &collector, 2));
+        let mint = mint_keypair.pubkey();
+        let system_program = solana_sdk::system_program::id();
+
+        // Programs are not attributed by default
+        let prioritization_fee_cache = PrioritizationFeeCache::default();
+        assert!(!prioritization_fee_cache.has_program_attribution());
+        let txs = vec![build_sanitized_transaction_for_test(
+            5,
+            &mint,
+            &Pubkey::new_unique(),
+        )];
+        sync_update(&prioritization_fee_cache, bank1.clone(), txs.iter());
+        sync_finalize_priority_fee_for_test(&prioritization_fee_cache, 1, bank1.bank_id());
+        assert_eq!(
+            prioritization_fee_cache.get_program_fees(&system_program),
+            vec![(1, ProgramFees::default())]
+        );
+
+        let prioritization_fee_cache =
+            PrioritizationFeeCache::new_with_program_attribution(MAX_NUM_RECENT_BLOCKS);
+        assert!(prioritization_fee_cache.has_program_attribution());
+        // Only the mint account exists, the fees of the transactions are attributed once to the
+        // system program owning it
+        let txs = vec![
+            build_sanitized_transaction_for_test(5, &mint, &Pubkey::new_unique()),
+            build_sanitized_transaction_for_test(2, &mint, &Pubkey::new_unique()),
+            build_sanitized_transaction_for_test(9, &Pubkey::new_unique(), &Pubkey::new_unique()),
+        ];
+        sync_update(&prioritization_fee_cache, bank1.clone(), txs.iter());
+        sync_finalize_priority_fee_for_test(&prioritization_fee_cache, 1, bank1.bank_id());
+        sync_update(&prioritization_fee_cache, bank2.clone(), txs[2..].iter());
+        sync_finalize_priority_fee_for_test(&prioritization_fee_cache, 2, bank2.bank_id());
+        assert_eq!(
+            prioritization_fee_cache.get_program_fees(&system_program),
+            vec![
+                (
+                    1,
+                    ProgramFees {
+                        transactions: 2,
+                        compute_unit_prices: vec![5, 2],
+                    }
+                ),
+                (2, ProgramFees::default()),
+            ]
+        );
+        assert_eq!(
+            prioritization_fee_cache.get_program_fees(&Pubkey::new_unique()),
+            vec![(1, ProgramFees::default()), (2, ProgramFees::default())]
+        );
+    }
+
     #[test]
     fn test_purge_duplicated_bank() {
         // duplicated bank can exists for same slot before OC.
@@ -0,0 +1,360 @@
+//! Warms the program cache up at startup.
+//!
+//! After a restart the program cache is empty, so the first transactions invoking each program
+//! also pay for loading, verifying and JIT compiling it, which slows down the first leader slots
+//! and the simulations served by RPC nodes. Programs listed by the operator, or the programs
+//! compiled by the previous run, are compiled in parallel before the validator starts replaying
+//! instead.
+//!
+//! The compiled programs of a run are persisted as a manifest keyed by program hash, SBPF
+//! version and runtime version, so that programs which were redeployed, or which a different
+//! runtime would compile differently, are not warmed up on the strength of a stale entry. The JIT
+//! output itself is not persisted: it embeds the addresses of the runtime's functions in the
+//! process which compiled it, so it can't be reused by another process.
+
+use {
+    crate::bank::Bank,
+    log::*,
+    rayon::prelude::*,
+    serde::{Deserialize, Serialize},
+    solana_measure::measure_us,
+    solana_program_runtime::loaded_programs::{ProgramCacheEntry, ProgramCacheEntryType},
+    solana_sdk::{
+        account::ReadableAccount,
+        account_utils::StateMut,
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        hash::{hash, Hash},
+        pubkey::Pubkey,
+    },
+    std::{
+        collections::{HashMap, HashSet},
+        fs,
+        io::{self, BufReader, BufWriter},
+        path::Path,
+        str::FromStr,
+        sync::Arc,
+    },
+};
+
+/// Name of the file in the ledger directory holding the manifest of the previous run
+pub const PROGRAM_CACHE_MANIFEST_FILE: &str = "program_cache_manifest";
+
+/// Loads and compiles `program_ids` into the program cache of `bank`, in parallel, returning
+/// the number of programs which were compiled. Accounts which are not executable programs are
+/// skipped.
+pub fn warm_up_program_cache(bank: &Bank, program_ids: &[Pubkey]) -> usize {
+    let program_ids: HashSet<_> = program_ids.iter().copied().collect();
+    let (compiled, warmup_us) = measure_us!(assign_programs(
+        bank,
+        compile_programs(bank, program_ids.iter())
+    ));
+    info!(
+        "Program cache warm-up compiled {compiled} of {} programs in {warmup_us}us",
+        program_ids.len()
+    );
+    datapoint_info!(
+        "program_cache_warmup",
+        ("requested", program_ids.len(), i64),
+        ("compiled", compiled, i64),
+        ("warmup_us", warmup_us, i64),
+    );
+    compiled
+}
+
+fn compile_programs<'a>(
+    bank: &Bank,
+    program_ids: impl Iterator<Item = &'a Pubkey>,
+) -> Vec<(Pubkey, Arc<ProgramCacheEntry>)> {
+    program_ids
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .filter_map(
+            |program_id| match bank.load_program(program_id, false, bank.epoch()) {
+                Some(program) if matches!(program.program, ProgramCacheEntryType::Loaded(_)) => {
+                    Some((*program_id, program))
+                }
+                _ => {
+                    debug!("Program cache warm-up skipped {program_id}, not a valid program");
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+fn assign_programs(bank: &Bank, programs: Vec<(Pubkey, Arc<ProgramCacheEntry>)>) -> usize {
+    let mut program_cache = bank.transaction_processor.program_cache.write().unwrap();
+    let compiled = programs.len();
+    for (program_id, program) in programs {
+        program_cache.assign_program(program_id, program);
+    }
+    compiled
+}
+
+/// Returns the ids of up to `count` compiled programs of `bank`'s program cache, most used first
+pub fn hottest_programs(bank: &Bank, count: usize) -> Vec<Pubkey> {
+    let mut entries = bank
+        .transaction_processor
+        .program_cache
+        .read()
+        .unwrap()
+        .get_flattened_entries(true, true);
+    entries.sort_by_cached_key(|(_, program)| {
+        std::cmp::Reverse(program.decayed_usage_counter(bank.slot()))
+    });
+    let mut seen = HashSet::new();
+    entries
+        .into_iter()
+        .map(|(program_id, _)| program_id)
+        .filter(|program_id| seen.insert(*program_id))
+        .take(count)
+        .collect()
+}
+
+/// Hash of the executable data of a program, which changes whenever it is redeployed
+fn program_hash(bank: &Bank, program_id: &Pubkey) -> Option<Hash> {
+    let account = bank.get_account(program_id)?;
+    if bpf_loader_upgradeable::check_id(account.owner()) {
+        let Ok(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) = account.state()
+        else {
+            return None;
+        };
+        Some(hash(bank.get_account(&programdata_address)?.data()))
+    } else {
+        Some(hash(account.data()))
+    }
+}
+
+fn sbpf_version(program: &ProgramCacheEntry) -> Option<String> {
+    match &program.program {
+        ProgramCacheEntryType::Loaded(executable) => {
+            Some(format!("{:?}", executable.get_sbpf_version()))
+        }
+        _ => None,
+    }
+}
+
+fn runtime_version() -> String {
+    solana_version::version!().to_string()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramCacheManifestEntry {
+    pub program_id: Pubkey,
+    pub program_hash: Hash,
+    pub sbpf_version: String,
+}
+
+/// The programs compiled by a run of the validator, keyed by what determines their compilation
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramCacheManifest {
+    /// Version, commit and feature set of the runtime which compiled the programs
+    pub runtime_version: String,
+    /// Most used first
+    pub entries: Vec<ProgramCacheManifestEntry>,
+}
+
+impl ProgramCacheManifest {
+    /// Records up to `count` of the compiled programs of `bank`, most used first
+    pub fn new(bank: &Bank, count: usize) -> Self {
+        let program_cache = bank.transaction_processor.program_cache.read().unwrap();
+        let sbpf_versions: HashMap<_, _> = program_cache
+            .get_flattened_entries(true, true)
+            .into_iter()
+            .filter_map(|(program_id, program)| Some((program_id, sbpf_version(&program)?)))
+            .collect();
+        drop(program_cache);
+
+        let entries = hottest_programs(bank, count)
+            .into_iter()
+            .filter_map(|program_id| {
+                Some(ProgramCacheManifestEntry {
+                    program_id,
+                    program_hash: program_hash(bank, &program_id)?,
+                    sbpf_version: sbpf_versions.get(&program_id)?.clone(),
+                })
+            })
+            .collect();
+        Self {
+            runtime_version: runtime_version(),
+            entries,
+        }
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        bincode::deserialize_from(BufReader::new(fs::File::open(path)?))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        // Written aside then renamed, so that a crash doesn't leave a truncated manifest
+        let tmp_path = path.with_extension("tmp");
+        let file = fs::File::create(&tmp_path)?;
+        bincode::serialize_into(BufWriter::new(&file), self)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        file.sync_all()?;
+        fs::rename(tmp_path, path)
+    }
+
+    /// Compiles the programs of the manifest which are unchanged since it was written, returning
+    /// the number of programs which were compiled
+    pub fn warm_up(&self, bank: &Bank) -> usize {
+        if self.runtime_version != runtime_version() {
+            info!(
+                "Program cache manifest written by runtime {}, not warming up",
+                self.runtime_version
+            );
+            return 0;
+        }
+        let (compiled, warmup_us) = measure_us!({
+            let current: Vec<_> = self
+                .entries
+                .iter()
+                .filter(|entry| program_hash(bank, &entry.program_id) == Some(entry.program_hash))
+                .collect();
+            let programs = compile_programs(bank, current.iter().map(|entry| &entry.program_id));
+            let sbpf_versions: HashMap<_, _> = current
+                .iter()
+                .map(|entry| (entry.program_id, entry.sbpf_version.as_str()))
+                .collect();
+            let programs: Vec<_> = programs
+                .into_iter()
+                .filter(|(program_id, program)| {
+                    sbpf_version(program).as_deref() == sbpf_versions.get(program_id).copied()
+                })
+                .collect();
+            assign_programs(bank, programs)
+        });
+        let stale = self.entries.len().saturating_sub(compiled);
+        info!(
+            "Program cache warm-up compiled {compiled} programs of the manifest, {stale} were \
+             stale, in {warmup_us}us"
+        );
+        datapoint_info!(
+            "program_cache_manifest_warmup",
+            ("entries", self.entries.len(), i64),
+            ("compiled", compiled, i64),
+            ("stale", stale, i64),
+            ("warmup_us", warmup_us, i64),
+        );
+        compiled
+    }
+}
+
+/// Reads a list of program ids, one per line. Empty lines and lines starting with `#` are
+/// ignored.
+pub fn read_program_list(path: &Path) -> io::Result<Vec<Pubkey>> {
+    fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            Pubkey::from_str(line).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid program id {line} in {path:?}: {err}"),
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crate::genesis_utils::create_genesis_config,
+        solana_sdk::account::AccountSharedData,
+    };
+
+    #[test]
+    fn test_program_list() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("programs");
+        let program_ids = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+        fs::write(
+            &path,
+            format!(
+                "# hot programs\n{}\n\n {}\n",
+                program_ids[0], program_ids[1]
+            ),
+        )
+        .unwrap();
+        assert_eq!(read_program_list(&path).unwrap(), program_ids);
+
+        fs::write(&path, "not-a-pubkey\n").unwrap();
+        assert_eq!(
+            read_program_list(&path).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert!(read_program_list(&dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_warm_up_program_cache_skips_non_programs() {
+        let (genesis_config, _mint_keypair) = create_genesis_config(1_000_000_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+
+        let missing = Pubkey::new_unique();
+        let not_executable = Pubkey::new_unique();
+        bank.store_account(
+            &not_executable,
+            &AccountSharedData::new(1_000_000, 0, &bpf_loader_upgradeable::id()),
+        );
+        assert_eq!(
+            warm_up_program_cache(&bank, &[missing, not_executable, missing]),
+            0
+        );
+        assert!(hottest_programs(&bank, 10).is_empty());
+    }
+
+    #[test]
+    fn test_program_cache_manifest() {
+        let (genesis_config, _mint_keypair) = create_genesis_config(1_000_000_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(PROGRAM_CACHE_MANIFEST_FILE);
+
+        let manifest = ProgramCacheManifest::new(&bank, 10);
+        assert_eq!(manifest.runtime_version, runtime_version());
+        assert!(manifest.entries.is_empty());
+
+        let program_id = Pubkey::new_unique();
+        let program_data = vec![1, 2, 3];
+        bank.store_account(
+            &program_id,
+            &AccountSharedData::from(solana_sdk::account::Account {
+                lamports: 1_000_000,
+                data: program_data.clone(),
+                owner: solana_sdk::bpf_loader::id(),
+                executable: true,
+                rent_epoch: 0,
+            }),
+        );
+        assert_eq!(program_hash(&bank, &program_id), Some(hash(&program_data)));
+
+        let manifest = ProgramCacheManifest {
+            runtime_version: runtime_version(),
+            entries: vec![ProgramCacheManifestEntry {
+                program_id,
+                program_hash: Hash::new_unique(),
+                sbpf_version: "V0".to_string(),
+            }],
+        };
+        manifest.write(&path).unwrap();
+        assert_eq!(ProgramCacheManifest::read(&path).unwrap(), manifest);
+        // The program was redeployed since the manifest was written
+        assert_eq!(manifest.warm_up(&bank), 0);
+
+        let manifest = ProgramCacheManifest {
+            runtime_version: "another runtime".to_string(),
+            ..manifest
+        };
+        assert_eq!(manifest.warm_up(&bank), 0);
+
+        fs::write(&path, [0xff; 3]).unwrap();
+        assert!(ProgramCacheManifest::read(&path).is_err());
+    }
+}
@@ -7,11 +7,14 @@
 //! of the `SVMRentCollector` trait. This wrapper allows all `SVMRentCollector`
 //! methods to be passed through to the underlying `RentCollector`, except for
 //! those which require additional logging and metrics.
+//!
+//! The wrapper also enforces storage deposits once the `storage_deposit` feature is active, see
+//! `tachyon_system_interface::storage_deposit`.
 
 use {
     log::*,
     solana_sdk::{
-        account::AccountSharedData,
+        account::{AccountSharedData, ReadableAccount},
         clock::Epoch,
         pubkey::Pubkey,
         rent::{Rent, RentDue},
@@ -20,6 +23,7 @@ use {
     },
     solana_svm_rent_collector::{rent_state::RentState, svm_rent_collector::SVMRentCollector},
     solana_transaction_context::IndexOfAccount,
+    tachyon_system_interface::storage_deposit::minimum_balance_with_storage_deposit,
 };
 
 /// Wrapper around `RentCollector` to allow for overriding of some
@@ -27,26 +31,96 @@ use {
 /// `RentCollector` directly.
 ///
 /// Overrides inject logging and metrics submission into the rent state
-/// assessment process.
-pub struct RentCollectorWithMetrics(RentCollector);
+/// assessment process, and require the storage deposit of accounts when
+/// `storage_deposit` is set.
+pub struct RentCollectorWithMetrics {
+    rent_collector: RentCollector,
+    storage_deposit: bool,
+}
 
 impl RentCollectorWithMetrics {
-    pub fn new(rent_collector: RentCollector) -> Self {
-        Self(rent_collector)
+    pub fn new(rent_collector: RentCollector, storage_deposit: bool) -> Self {
+        Self {
+            rent_collector,
+            storage_deposit,
+        }
+    }
+
+    /// Returns if `rent_state` is only rent paying for lack of its storage deposit
+    fn is_short_of_storage_deposit(&self, rent_state: &RentState) -> bool {
+        matches!(
+            rent_state,
+            RentState::RentPaying { lamports, data_size }
+                if self.get_rent().is_exempt(*lamports, *data_size)
+        )
+    }
+
+    /// The rent state of an account holding its rent-exempt minimum but not its storage deposit
+    /// is `RentExempt` when the deposit is ignored
+    fn without_storage_deposit<'a>(&self, rent_state: &'a RentState) -> &'a RentState {
+        if self.is_short_of_storage_deposit(rent_state) {
+            &RentState::RentExempt
+        } else {
+            rent_state
+        }
     }
 }
 
 impl SVMRentCollector for RentCollectorWithMetrics {
     fn collect_rent(&self, address: &Pubkey, account: &mut AccountSharedData) -> CollectedInfo {
-        self.0.collect_rent(address, account)
+        self.rent_collector.collect_rent(address, account)
     }
 
     fn get_rent(&self) -> &Rent {
-        self.0.get_rent()
+        self.rent_collector.get_rent()
     }
 
     fn get_rent_due(&self, lamports: u64, data_len: usize, account_rent_epoch: Epoch) -> RentDue {
-        self.0.get_rent_due(lamports, data_len, account_rent_epoch)
+        self.rent_collector
+            .get_rent_due(lamports, data_len, account_rent_epoch)
+    }
+
+    // Overridden to count the storage deposit towards the rent-exempt minimum.
+    fn get_account_rent_state(&self, account: &AccountSharedData) -> RentState {
+        let rent_state = self.rent_collector.get_account_rent_state(account);
+        if self.storage_deposit
+            && rent_state == RentState::RentExempt
+            && account.lamports()
+                < minimum_balance_with_storage_deposit(self.get_rent(), account.data().len() as u64)
+        {
+            RentState::RentPaying {
+                data_size: account.data().len(),
+                lamports: account.lamports(),
+            }
+        } else {
+            rent_state
+        }
+    }
+
+    // Overridden so that accounts allocated before storage deposits were required keep their data
+    // size without one, while no account can be left short of its deposit otherwise.
+    fn transition_allowed(&self, pre_rent_state: &RentState, post_rent_state: &RentState) -> bool {
+        if !self.storage_deposit {
+            return self
+                .rent_collector
+                .transition_allowed(pre_rent_state, post_rent_state);
+        }
+        let short_of_storage_deposit_allowed = match post_rent_state {
+            RentState::RentPaying {
+                data_size: post_data_size,
+                ..
+            } if self.is_short_of_storage_deposit(post_rent_state) => matches!(
+                pre_rent_state,
+                RentState::RentPaying { data_size: pre_data_size, .. }
+                    if pre_data_size == post_data_size
+            ),
+            _ => true,
+        };
+        short_of_storage_deposit_allowed
+            && self.rent_collector.transition_allowed(
+                self.without_storage_deposit(pre_rent_state),
+                self.without_storage_deposit(post_rent_state),
+            )
     }
 
     // Overriden to inject logging and metrics.
@@ -88,3 +162,62 @@ fn submit_rent_state_metrics(pre_rent_state: &RentState, post_rent_state: &RentS
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_deposit_rent_state() {
+        let rent_collector = RentCollectorWithMetrics::new(RentCollector::default(), true);
+        let rent = rent_collector.get_rent().clone();
+        let data_size = 165;
+        let rent_exempt_minimum = rent.minimum_balance(data_size);
+        let account = |lamports, data_size| {
+            rent_collector.get_account_rent_state(&AccountSharedData::new(
+                lamports,
+                data_size,
+                &Pubkey::default(),
+            ))
+        };
+
+        let deposit_minimum = minimum_balance_with_storage_deposit(&rent, data_size as u64);
+        assert_eq!(account(deposit_minimum, data_size), RentState::RentExempt);
+        let short = account(rent_exempt_minimum, data_size);
+        assert_eq!(
+            short,
+            RentState::RentPaying {
+                lamports: rent_exempt_minimum,
+                data_size,
+            }
+        );
+
+        // Accounts cannot be left short of their deposit
+        assert!(!rent_collector.transition_allowed(&RentState::RentExempt, &short));
+        assert!(!rent_collector.transition_allowed(&RentState::Uninitialized, &short));
+
+        // Accounts allocated without a deposit keep their data size, debited or credited, as long
+        // as they hold their rent-exempt minimum
+        let legacy = account(rent_exempt_minimum + 1, data_size);
+        assert!(rent_collector.transition_allowed(&legacy, &short));
+        assert!(rent_collector.transition_allowed(&short, &legacy));
+        assert!(rent_collector.transition_allowed(&legacy, &RentState::RentExempt));
+        let rent_paying = account(rent_exempt_minimum - 1, data_size);
+        assert!(!rent_collector.transition_allowed(&legacy, &rent_paying));
+
+        // But they need the deposit to be resized
+        let resized = account(rent.minimum_balance(2 * data_size), 2 * data_size);
+        assert!(!rent_collector.transition_allowed(&legacy, &resized));
+
+        // Without storage deposits, the rent-exempt minimum suffices
+        let rent_collector = RentCollectorWithMetrics::new(RentCollector::default(), false);
+        assert_eq!(
+            rent_collector.get_account_rent_state(&AccountSharedData::new(
+                rent_exempt_minimum,
+                data_size,
+                &Pubkey::default(),
+            )),
+            RentState::RentExempt
+        );
+    }
+}
@@ -46,6 +46,10 @@ pub struct SnapshotConfig {
 
     // Thread niceness adjustment for snapshot packager service
     pub packager_thread_niceness_adj: i8,
+
+    /// Number of other nodes which must gossip the same hash for a snapshot
+    /// before its hash is pushed to gossip, 0 to push it right away
+    pub gossip_quorum: usize,
 }
 
 impl Default for SnapshotConfig {
@@ -69,6 +73,7 @@ impl Default for SnapshotConfig {
                 snapshot_utils::DEFAULT_MAX_INCREMENTAL_SNAPSHOT_ARCHIVES_TO_RETAIN,
             accounts_hash_debug_verify: false,
             packager_thread_niceness_adj: 0,
+            gossip_quorum: 0,
         }
     }
 }
@@ -13,6 +13,7 @@ use {
         accounts_db::{
             stats::PurgeStats, AccountStorageEntry, AccountsDb, GetUniqueAccountsResult,
         },
+        accounts_index::{ScanConfig, ScanResult},
         accounts_partition,
         storable_accounts::StorableAccountsBySlot,
     },
@@ -60,26 +61,58 @@ impl<'a> SnapshotMinimizer<'a> {
             ending_slot,
             minimized_account_set: transaction_account_set,
         };
+        minimizer.run();
+    }
+
+    /// Removes all accounts except the programs in `program_ids`, the accounts owned by them, and
+    /// the accounts needed to keep using the bank (features, sysvars, vote and stake accounts,
+    /// etc).
+    ///
+    /// This is used to create snapshots for nodes that only serve a few applications, like
+    /// indexers, and for test fixtures. Unlike `minimize`, the resulting snapshot is not meant
+    /// for replaying the cluster's transactions.
+    pub fn minimize_for_programs(bank: &'a Bank, program_ids: &[Pubkey]) -> ScanResult<()> {
+        let minimizer = SnapshotMinimizer {
+            bank,
+            starting_slot: bank.slot(),
+            ending_slot: bank.slot(),
+            minimized_account_set: DashSet::new(),
+        };
+
+        let (result, measure) = measure_time!(
+            minimizer.get_program_owned_accounts(program_ids),
+            "get program owned accounts"
+        );
+        result?;
+        info!(
+            "Added {} program owned accounts. get {measure}",
+            minimizer.minimized_account_set.len()
+        );
+
+        minimizer.run();
+        Ok(())
+    }
 
-        minimizer.add_accounts(Self::get_active_bank_features, "active bank features");
-        minimizer.add_accounts(Self::get_inactive_bank_features, "inactive bank features");
-        minimizer.add_accounts(Self::get_static_runtime_accounts, "static runtime accounts");
-        minimizer.add_accounts(Self::get_reserved_accounts, "reserved accounts");
+    fn run(self) {
+        self.add_accounts(Self::get_active_bank_features, "active bank features");
+        self.add_accounts(Self::get_inactive_bank_features, "inactive bank features");
+        self.add_accounts(Self::get_static_runtime_accounts, "static runtime accounts");
+        self.add_accounts(Self::get_reserved_accounts, "reserved accounts");
 
-        minimizer.add_accounts(
+        self.add_accounts(
             Self::get_rent_collection_accounts,
             "rent collection accounts",
         );
-        minimizer.add_accounts(Self::get_vote_accounts, "vote accounts");
-        minimizer.add_accounts(Self::get_stake_accounts, "stake accounts");
-        minimizer.add_accounts(Self::get_owner_accounts, "owner accounts");
-        minimizer.add_accounts(Self::get_programdata_accounts, "programdata accounts");
+        self.add_accounts(Self::get_vote_accounts, "vote accounts");
+        self.add_accounts(Self::get_stake_accounts, "stake accounts");
+        self.add_accounts(Self::get_owner_accounts, "owner accounts");
+        self.add_accounts(Self::get_programdata_accounts, "programdata accounts");
 
-        minimizer.minimize_accounts_db();
+        self.minimize_accounts_db();
 
         // Update accounts_cache and capitalization
-        minimizer.bank.force_flush_accounts_cache();
-        minimizer.bank.set_capitalization();
+        self.bank.force_flush_accounts_cache();
+        self.bank.set_capitalization();
     }
 
     /// Helper function to measure time and number of accounts added
@@ -97,6 +130,21 @@ impl<'a> SnapshotMinimizer<'a> {
         );
     }
 
+    /// Used to get program owned accounts in `minimize_for_programs`
+    /// Add the programs and all accounts owned by them to `minimized_account_set`
+    fn get_program_owned_accounts(&self, program_ids: &[Pubkey]) -> ScanResult<()> {
+        for program_id in program_ids {
+            self.minimized_account_set.insert(*program_id);
+            self.bank
+                .get_program_accounts(program_id, &ScanConfig::default())?
+                .into_iter()
+                .for_each(|(pubkey, _account)| {
+                    self.minimized_account_set.insert(pubkey);
+                });
+        }
+        Ok(())
+    }
+
     /// Used to get active bank feature accounts in `minimize`.
     fn get_active_bank_features(&self) {
         self.bank
@@ -565,6 +613,40 @@ mod tests {
         assert!(minimizer.minimized_account_set.contains(&owner_pubkey));
     }
 
+    #[test]
+    fn test_minimization_get_program_owned_accounts() {
+        solana_logger::setup();
+
+        let (genesis_config, _) = create_genesis_config(1_000_000);
+        let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+
+        let program_id = solana_pubkey::new_rand();
+        let other_program_id = solana_pubkey::new_rand();
+        let owned_pubkeys: Vec<_> = (0..3).map(|_| solana_pubkey::new_rand()).collect();
+        let other_pubkey = solana_pubkey::new_rand();
+        for pubkey in &owned_pubkeys {
+            bank.store_account(pubkey, &AccountSharedData::new(1, 8, &program_id));
+        }
+        bank.store_account(
+            &other_pubkey,
+            &AccountSharedData::new(1, 8, &other_program_id),
+        );
+
+        let minimizer = SnapshotMinimizer {
+            bank: &bank,
+            starting_slot: 0,
+            ending_slot: 0,
+            minimized_account_set: DashSet::new(),
+        };
+        minimizer.get_program_owned_accounts(&[program_id]).unwrap();
+        assert_eq!(minimizer.minimized_account_set.len(), 4);
+        assert!(minimizer.minimized_account_set.contains(&program_id));
+        for pubkey in &owned_pubkeys {
+            assert!(minimizer.minimized_account_set.contains(pubkey));
+        }
+        assert!(!minimizer.minimized_account_set.contains(&other_pubkey));
+    }
+
     #[test]
     fn test_minimization_add_programdata_accounts() {
         solana_logger::setup();
@@ -62,8 +62,10 @@ use {
 };
 
 mod archive_format;
+mod seekable_zstd;
 pub mod snapshot_storage_rebuilder;
 pub use archive_format::*;
+use seekable_zstd::{SeekableZstdEncoder, SEEKABLE_ZSTD_FRAME_SIZE};
 
 pub const SNAPSHOT_STATUS_CACHE_FILENAME: &str = "status_cache";
 pub const SNAPSHOT_VERSION_FILENAME: &str = "version";
@@ -966,6 +968,24 @@ fn serialize_snapshot(
     do_serialize_snapshot().map_err(|err| SnapshotError::AddBankSnapshot(err, slot))
 }
 
+/// A writer of snapshot archives, told when an entry of the archive has been written
+trait ArchiveWriter: Write {
+    fn end_of_entry(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ArchiveWriter for bzip2::write::BzEncoder<W> {}
+impl<W: Write> ArchiveWriter for flate2::write::GzEncoder<W> {}
+impl<W: Write> ArchiveWriter for lz4::Encoder<W> {}
+impl ArchiveWriter for fs::File {}
+
+impl<W: Write> ArchiveWriter for SeekableZstdEncoder<W> {
+    fn end_of_entry(&mut self) -> IoResult<()> {
+        SeekableZstdEncoder::end_of_entry(self)
+    }
+}
+
 /// Archives a snapshot into `archive_path`
 fn archive_snapshot(
     snapshot_kind: SnapshotKind,
@@ -1038,7 +1058,7 @@ fn archive_snapshot(
         let mut archive_file = fs::File::create(&staging_archive_path)
             .map_err(|err| E::CreateArchiveFile(err, staging_archive_path.clone()))?;
 
-        let do_archive_files = |encoder: &mut dyn Write| -> std::result::Result<(), E> {
+        let do_archive_files = |encoder: &mut dyn ArchiveWriter| -> std::result::Result<(), E> {
             let mut archive = tar::Builder::new(encoder);
             // Disable sparse file handling.  This seems to be the root cause of an issue when
             // upgrading v2.0 to v2.1, and the tar crate from 0.4.41 to 0.4.42.
@@ -1075,6 +1095,7 @@ fn archive_snapshot(
                         archive.append_path_with_name(path, path_in_archive)
                     }
                 }
+                .and_then(|()| archive.get_mut().end_of_entry())
                 .map_err(|err| E::ArchiveAccountStorageFile(err, storage.path().to_path_buf()))?;
             }
 
@@ -1096,9 +1117,12 @@ fn archive_snapshot(
                 encoder.finish().map_err(E::FinishEncoder)?;
             }
             ArchiveFormat::TarZstd { config } => {
-                let mut encoder =
-                    zstd::stream::Encoder::new(archive_file, config.compression_level)
-                        .map_err(E::CreateEncoder)?;
+                let mut encoder = SeekableZstdEncoder::new(
+                    archive_file,
+                    config.compression_level,
+                    SEEKABLE_ZSTD_FRAME_SIZE,
+                )
+                .map_err(E::CreateEncoder)?;
                 do_archive_files(&mut encoder)?;
                 encoder.finish().map_err(E::FinishEncoder)?;
             }
@@ -1583,7 +1607,7 @@ fn spawn_unpack_snapshot_thread(
     file_sender: Sender<PathBuf>,
     account_paths: Arc<Vec<PathBuf>>,
     ledger_dir: Arc<PathBuf>,
-    mut archive: Archive<SharedBufferReader>,
+    mut archive: Archive<impl Read + Send + 'static>,
     parallel_selector: Option<ParallelSelector>,
     thread_index: usize,
 ) -> JoinHandle<()> {
@@ -1613,6 +1637,38 @@ fn streaming_unarchive_snapshot(
 ) -> Vec<JoinHandle<()>> {
     let account_paths = Arc::new(account_paths);
     let ledger_dir = Arc::new(ledger_dir);
+    if let Some(frames) = read_snapshot_archive_seek_table(&snapshot_archive_path, archive_format) {
+        // Each thread decompresses and unpacks its own range of frames
+        let ranges = seekable_zstd::split_frames(&frames, num_threads);
+        info!(
+            "Unpacking seekable snapshot archive {} with {} threads",
+            snapshot_archive_path.display(),
+            ranges.len(),
+        );
+        return ranges
+            .into_iter()
+            .enumerate()
+            .map(|(thread_index, frames)| {
+                let decoder = seekable_zstd::open_frames(&snapshot_archive_path, frames)
+                    .map_err(|err| {
+                        IoError::other(format!(
+                            "failed to open snapshot archive '{}': {err}",
+                            snapshot_archive_path.display(),
+                        ))
+                    })
+                    .unwrap();
+                spawn_unpack_snapshot_thread(
+                    file_sender.clone(),
+                    account_paths.clone(),
+                    ledger_dir.clone(),
+                    Archive::new(decoder),
+                    None,
+                    thread_index,
+                )
+            })
+            .collect();
+    }
+
     let shared_buffer = untar_snapshot_create_shared_buffer(&snapshot_archive_path, archive_format);
 
     // All shared buffer readers need to be created before the threads are spawned
@@ -1644,6 +1700,29 @@ fn streaming_unarchive_snapshot(
         .collect()
 }
 
+/// Returns the frames of a snapshot archive in the seekable zstd format, or `None` if the
+/// archive is in another format and must be decompressed as a single stream
+fn read_snapshot_archive_seek_table(
+    snapshot_archive_path: &Path,
+    archive_format: ArchiveFormat,
+) -> Option<Vec<seekable_zstd::SeekableFrame>> {
+    if !matches!(archive_format, ArchiveFormat::TarZstd { .. }) {
+        return None;
+    }
+    let result = fs::File::open(snapshot_archive_path)
+        .and_then(|mut file| seekable_zstd::read_seek_table(&mut file));
+    match result {
+        Ok(frames) => frames.filter(|frames| !frames.is_empty()),
+        Err(err) => {
+            warn!(
+                "Failed to read the seek table of snapshot archive '{}': {err}",
+                snapshot_archive_path.display(),
+            );
+            None
+        }
+    }
+}
+
 /// BankSnapshotInfo::new_from_dir() requires a few meta files to accept a snapshot dir
 /// as a valid one.  A dir unpacked from an archive lacks these files.  Fill them here to
 /// allow new_from_dir() checks to pass.  These checks are not needed for unpacked dirs,
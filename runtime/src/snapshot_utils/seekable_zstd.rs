@@ -0,0 +1,331 @@
+//! Seekable zstd snapshot archives.
+//!
+//! A seekable archive is a sequence of independent zstd frames, each starting at the header of
+//! a tar entry, followed by a seek table of the sizes of the frames in a skippable frame. As
+//! decoders skip skippable frames, the archive is still a valid zstd stream for tools and nodes
+//! unaware of the seek table. Nodes aware of it decode disjoint ranges of frames in parallel,
+//! each range being a valid tar stream on its own.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek, SeekFrom, Take, Write},
+    path::Path,
+};
+
+/// Default target size of the tar data of a frame
+pub const SEEKABLE_ZSTD_FRAME_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Magic number of the skippable frame holding the seek table
+const SEEK_TABLE_FRAME_MAGIC: u32 = 0x184D_2A5C;
+/// Magic number ending the seek table
+const SEEK_TABLE_FOOTER_MAGIC: u32 = 0x5831_534B;
+/// Size of a seek table entry: the compressed and decompressed sizes of a frame
+const SEEK_TABLE_ENTRY_SIZE: u64 = 16;
+/// Size of the footer: the number of frames and the footer magic number
+const SEEK_TABLE_FOOTER_SIZE: u64 = 8;
+/// Size of the header of a skippable frame: the magic number and the frame size
+const SKIPPABLE_FRAME_HEADER_SIZE: u64 = 8;
+
+/// A zstd frame of a seekable archive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeekableFrame {
+    /// Offset of the frame in the archive
+    pub offset: u64,
+    pub compressed_size: u64,
+    pub decompressed_size: u64,
+}
+
+/// Counts the bytes written to the archive, to compute the compressed size of frames
+struct CountingWriter<W> {
+    inner: W,
+    count: u64,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.count += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Compresses an archive into frames ending at entry boundaries, see `end_of_entry()`
+pub struct SeekableZstdEncoder<W: Write> {
+    encoder: Option<zstd::stream::Encoder<'static, CountingWriter<W>>>,
+    compression_level: i32,
+    /// A frame ends after the first entry making it reach this many bytes of tar data
+    target_frame_size: u64,
+    frame_size: u64,
+    /// Offset of the current frame in the archive
+    frame_offset: u64,
+    /// Compressed and decompressed sizes of the frames written so far
+    frames: Vec<(u64, u64)>,
+}
+
+impl<W: Write> SeekableZstdEncoder<W> {
+    pub fn new(writer: W, compression_level: i32, target_frame_size: u64) -> io::Result<Self> {
+        let encoder = Self::new_frame_encoder(
+            CountingWriter {
+                inner: writer,
+                count: 0,
+            },
+            compression_level,
+        )?;
+        Ok(Self {
+            encoder: Some(encoder),
+            compression_level,
+            target_frame_size,
+            frame_size: 0,
+            frame_offset: 0,
+            frames: vec![],
+        })
+    }
+
+    fn new_frame_encoder(
+        writer: CountingWriter<W>,
+        compression_level: i32,
+    ) -> io::Result<zstd::stream::Encoder<'static, CountingWriter<W>>> {
+        let mut encoder = zstd::stream::Encoder::new(writer, compression_level)?;
+        // Lets the unpacker detect corrupt frames without a hash of the whole archive
+        encoder.include_checksum(true)?;
+        Ok(encoder)
+    }
+
+    fn end_frame(&mut self) -> io::Result<CountingWriter<W>> {
+        let writer = self.encoder.take().unwrap().finish()?;
+        self.frames
+            .push((writer.count - self.frame_offset, self.frame_size));
+        self.frame_offset = writer.count;
+        self.frame_size = 0;
+        Ok(writer)
+    }
+
+    /// Must be called after writing each entry of the archive. Ends the current frame if it
+    /// reached the target size, so that the next entry starts a frame
+    pub fn end_of_entry(&mut self) -> io::Result<()> {
+        if self.frame_size >= self.target_frame_size {
+            let writer = self.end_frame()?;
+            self.encoder = Some(Self::new_frame_encoder(writer, self.compression_level)?);
+        }
+        Ok(())
+    }
+
+    /// Ends the last frame and writes the seek table
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut writer = self.end_frame()?;
+        let table_size = self.frames.len() as u64 * SEEK_TABLE_ENTRY_SIZE + SEEK_TABLE_FOOTER_SIZE;
+        let mut table = Vec::with_capacity((SKIPPABLE_FRAME_HEADER_SIZE + table_size) as usize);
+        table.extend_from_slice(&SEEK_TABLE_FRAME_MAGIC.to_le_bytes());
+        table.extend_from_slice(
+            &u32::try_from(table_size)
+                .map_err(io::Error::other)?
+                .to_le_bytes(),
+        );
+        for (compressed_size, decompressed_size) in &self.frames {
+            table.extend_from_slice(&compressed_size.to_le_bytes());
+            table.extend_from_slice(&decompressed_size.to_le_bytes());
+        }
+        table.extend_from_slice(&(self.frames.len() as u32).to_le_bytes());
+        table.extend_from_slice(&SEEK_TABLE_FOOTER_MAGIC.to_le_bytes());
+        writer.write_all(&table)?;
+        writer.flush()?;
+        Ok(writer.inner)
+    }
+}
+
+impl<W: Write> Write for SeekableZstdEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.encoder.as_mut().unwrap().write(buf)?;
+        self.frame_size += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.encoder.as_mut().unwrap().flush()
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes.try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes.try_into().unwrap())
+}
+
+/// Reads the seek table of a zstd archive, returning `None` if the archive is not seekable
+pub fn read_seek_table(archive: &mut (impl Read + Seek)) -> io::Result<Option<Vec<SeekableFrame>>> {
+    let archive_size = archive.seek(SeekFrom::End(0))?;
+    if archive_size < SKIPPABLE_FRAME_HEADER_SIZE + SEEK_TABLE_FOOTER_SIZE {
+        return Ok(None);
+    }
+    let mut footer = [0; SEEK_TABLE_FOOTER_SIZE as usize];
+    archive.seek(SeekFrom::End(-(SEEK_TABLE_FOOTER_SIZE as i64)))?;
+    archive.read_exact(&mut footer)?;
+    if read_u32(&footer[4..]) != SEEK_TABLE_FOOTER_MAGIC {
+        return Ok(None);
+    }
+    let num_frames = u64::from(read_u32(&footer[..4]));
+    let table_size = num_frames * SEEK_TABLE_ENTRY_SIZE + SEEK_TABLE_FOOTER_SIZE;
+    let Some(table_offset) = archive_size.checked_sub(SKIPPABLE_FRAME_HEADER_SIZE + table_size)
+    else {
+        return Ok(None);
+    };
+    let mut table = vec![0; (SKIPPABLE_FRAME_HEADER_SIZE + table_size) as usize];
+    archive.seek(SeekFrom::Start(table_offset))?;
+    archive.read_exact(&mut table)?;
+    if read_u32(&table[..4]) != SEEK_TABLE_FRAME_MAGIC
+        || u64::from(read_u32(&table[4..8])) != table_size
+    {
+        return Ok(None);
+    }
+
+    let mut offset = 0;
+    let frames = table[SKIPPABLE_FRAME_HEADER_SIZE as usize..]
+        .chunks_exact(SEEK_TABLE_ENTRY_SIZE as usize)
+        .map(|entry| {
+            let frame = SeekableFrame {
+                offset,
+                compressed_size: read_u64(&entry[..8]),
+                decompressed_size: read_u64(&entry[8..]),
+            };
+            offset = offset.saturating_add(frame.compressed_size);
+            frame
+        })
+        .collect();
+    if offset != table_offset {
+        return Err(io::Error::other(format!(
+            "seek table covers {offset} bytes instead of {table_offset}"
+        )));
+    }
+    Ok(Some(frames))
+}
+
+/// Splits the frames of an archive into at most `divisions` ranges of consecutive frames
+/// holding similar amounts of decompressed data
+pub fn split_frames(frames: &[SeekableFrame], divisions: usize) -> Vec<&[SeekableFrame]> {
+    let total_size: u64 = frames.iter().map(|frame| frame.decompressed_size).sum();
+    let target_size = total_size.div_ceil(divisions.max(1) as u64).max(1);
+    let mut ranges = vec![];
+    let mut start = 0;
+    let mut size = 0;
+    for (index, frame) in frames.iter().enumerate() {
+        size += frame.decompressed_size;
+        if size >= target_size {
+            ranges.push(&frames[start..=index]);
+            start = index + 1;
+            size = 0;
+        }
+    }
+    if start < frames.len() {
+        ranges.push(&frames[start..]);
+    }
+    ranges
+}
+
+/// Opens a reader of the decompressed data of a range of consecutive frames of an archive
+pub fn open_frames(
+    archive_path: &Path,
+    frames: &[SeekableFrame],
+) -> io::Result<zstd::stream::read::Decoder<'static, BufReader<Take<File>>>> {
+    let (Some(first), Some(last)) = (frames.first(), frames.last()) else {
+        return Err(io::Error::other("no frames to open"));
+    };
+    let mut file = File::open(archive_path)?;
+    file.seek(SeekFrom::Start(first.offset))?;
+    let len = last.offset + last.compressed_size - first.offset;
+    zstd::stream::read::Decoder::new(file.take(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tar::Archive, tempfile::TempDir};
+
+    const TARGET_FRAME_SIZE: u64 = 4096;
+
+    /// Archives entries of the given sizes, returning the path of the archive
+    fn write_archive(dir: &Path, entry_sizes: &[usize]) -> std::path::PathBuf {
+        let path = dir.join("archive.tar.zst");
+        let encoder =
+            SeekableZstdEncoder::new(File::create(&path).unwrap(), 1, TARGET_FRAME_SIZE).unwrap();
+        let mut archive = tar::Builder::new(encoder);
+        for (index, size) in entry_sizes.iter().enumerate() {
+            let data = vec![index as u8; *size];
+            let mut header = tar::Header::new_gnu();
+            header
+                .set_path(format!("accounts/{index}.{index}"))
+                .unwrap();
+            header.set_size(*size as u64);
+            header.set_cksum();
+            archive.append(&header, data.as_slice()).unwrap();
+            archive.get_mut().end_of_entry().unwrap();
+        }
+        archive.into_inner().unwrap().finish().unwrap();
+        path
+    }
+
+    fn entry_names(reader: impl Read) -> Vec<String> {
+        Archive::new(reader)
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                entry.path().unwrap().display().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_seekable_archive() {
+        let dir = TempDir::new().unwrap();
+        let path = write_archive(dir.path(), &[4096, 1024, 2048, 4096, 7]);
+
+        let frames = read_seek_table(&mut File::open(&path).unwrap())
+            .unwrap()
+            .unwrap();
+        // Frames end after the first entry reaching the target size, counting the 512 byte
+        // headers of the entries, and the last frame holds the end of the tar stream
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0].decompressed_size, 512 + 4096);
+        assert_eq!(frames[1].decompressed_size, 512 + 1024 + 512 + 2048);
+        assert_eq!(frames[0].offset, 0);
+        assert_eq!(frames[1].offset, frames[0].compressed_size);
+
+        // The archive is a regular zstd stream
+        let all_entries =
+            entry_names(zstd::stream::read::Decoder::new(File::open(&path).unwrap()).unwrap());
+        assert_eq!(all_entries.len(), 5);
+
+        // Each range of frames is a tar stream of its own
+        for divisions in 1..=4 {
+            let ranges = split_frames(&frames, divisions);
+            assert!(ranges.len() <= divisions);
+            let entries: Vec<_> = ranges
+                .iter()
+                .flat_map(|range| entry_names(open_frames(&path, range).unwrap()))
+                .collect();
+            assert_eq!(entries, all_entries);
+        }
+    }
+
+    #[test]
+    fn test_read_seek_table_of_regular_archive() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("archive.tar.zst");
+        let mut encoder = zstd::stream::Encoder::new(File::create(&path).unwrap(), 1).unwrap();
+        encoder.write_all(&[0; 4096]).unwrap();
+        encoder.finish().unwrap();
+        assert_eq!(
+            read_seek_table(&mut File::open(&path).unwrap()).unwrap(),
+            None
+        );
+        assert_eq!(
+            read_seek_table(&mut io::Cursor::new(vec![1, 2, 3])).unwrap(),
+            None
+        );
+    }
+}
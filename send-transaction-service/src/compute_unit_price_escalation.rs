@@ -0,0 +1,231 @@
+//! Recommendations of higher compute unit prices for transactions which do not land.
+//!
+//! Transactions are signed by their senders, so the send-transaction-service can't raise the
+//! compute unit price of the transactions it retries. Instead, when a transaction which opted in
+//! has not landed after a number of leader rotations, the [`ComputeUnitPriceEscalator`] recommends
+//! a higher price to its sender, which can re-sign the transaction at that price.
+
+use {
+    solana_runtime::prioritization_fee_cache::PrioritizationFeeCache,
+    solana_sdk::{
+        clock::{Slot, NUM_CONSECUTIVE_LEADER_SLOTS},
+        pubkey::Pubkey,
+        signature::Signature,
+    },
+    std::{fmt, sync::Arc},
+};
+
+/// Increase of the recommended price over the price of the transaction per escalation, in percent
+pub const ESCALATION_STEP_PERCENT: u64 = 25;
+/// The recommended price at least matches this percentile of the recent minimum prices needed to
+/// write the accounts of the transaction
+const RECENT_FEES_PERCENTILE: usize = 75;
+
+/// Compute unit price escalation requested by the sender of a transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComputeUnitPriceEscalation {
+    /// A higher price is recommended every time the transaction did not land after this many
+    /// more leader rotations
+    pub leader_rotations: u64,
+    /// Price requested by the transaction, in micro-lamports per compute unit
+    pub compute_unit_price: u64,
+    pub writable_accounts: Vec<Pubkey>,
+    /// Slot at which the transaction was first retried
+    first_slot: Option<Slot>,
+    escalations: u64,
+}
+
+impl ComputeUnitPriceEscalation {
+    pub fn new(
+        leader_rotations: u64,
+        compute_unit_price: u64,
+        writable_accounts: Vec<Pubkey>,
+    ) -> Self {
+        Self {
+            leader_rotations: leader_rotations.max(1),
+            compute_unit_price,
+            writable_accounts,
+            first_slot: None,
+            escalations: 0,
+        }
+    }
+}
+
+/// A higher compute unit price recommended for a transaction which did not land
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComputeUnitPriceRecommendation {
+    pub signature: Signature,
+    pub slot: Slot,
+    /// Leader rotations since the transaction was first retried
+    pub leader_rotations: u64,
+    pub compute_unit_price: u64,
+    pub recommended_compute_unit_price: u64,
+}
+
+/// Receives the recommendations, to deliver them to the senders of the transactions
+pub trait ComputeUnitPriceNotifier: Send + Sync {
+    fn notify_compute_unit_price_recommendation(
+        &self,
+        recommendation: ComputeUnitPriceRecommendation,
+    );
+}
+
+/// Returns the price recommended after `escalations` escalations of `compute_unit_price`: the
+/// price raised by `ESCALATION_STEP_PERCENT` percent per escalation, and at least the
+/// `RECENT_FEES_PERCENTILE`th of `recent_fees`
+pub fn recommend_compute_unit_price(
+    compute_unit_price: u64,
+    escalations: u64,
+    mut recent_fees: Vec<u64>,
+) -> u64 {
+    let mut escalated_price = compute_unit_price;
+    for _ in 0..escalations {
+        escalated_price = escalated_price
+            .saturating_mul(100 + ESCALATION_STEP_PERCENT)
+            .saturating_div(100)
+            .max(escalated_price.saturating_add(1));
+    }
+    recent_fees.sort_unstable();
+    let recent_fee = recent_fees
+        .get(recent_fees.len().saturating_sub(1) * RECENT_FEES_PERCENTILE / 100)
+        .copied()
+        .unwrap_or_default();
+    escalated_price.max(recent_fee)
+}
+
+/// Recommends higher compute unit prices for the transactions which requested an escalation,
+/// based on the recent prioritization fees of the accounts they write
+pub struct ComputeUnitPriceEscalator {
+    prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+    notifier: Arc<dyn ComputeUnitPriceNotifier>,
+}
+
+impl fmt::Debug for ComputeUnitPriceEscalator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComputeUnitPriceEscalator")
+            .field("prioritization_fee_cache", &self.prioritization_fee_cache)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ComputeUnitPriceEscalator {
+    pub fn new(
+        prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+        notifier: Arc<dyn ComputeUnitPriceNotifier>,
+    ) -> Self {
+        Self {
+            prioritization_fee_cache,
+            notifier,
+        }
+    }
+
+    /// Recommends a higher price for the transaction if it did not land after another
+    /// `leader_rotations` leader rotations, `slot` being the slot of the working bank
+    pub fn escalate(
+        &self,
+        signature: Signature,
+        escalation: &mut ComputeUnitPriceEscalation,
+        slot: Slot,
+    ) -> Option<ComputeUnitPriceRecommendation> {
+        let first_slot = *escalation.first_slot.get_or_insert(slot);
+        let leader_rotations = slot.saturating_sub(first_slot) / NUM_CONSECUTIVE_LEADER_SLOTS;
+        let escalations = leader_rotations / escalation.leader_rotations;
+        if escalations <= escalation.escalations {
+            return None;
+        }
+        escalation.escalations = escalations;
+
+        let recent_fees = self
+            .prioritization_fee_cache
+            .get_prioritization_fees(&escalation.writable_accounts)
+            .into_iter()
+            .map(|(_, fee)| fee)
+            .collect();
+        let recommendation = ComputeUnitPriceRecommendation {
+            signature,
+            slot,
+            leader_rotations,
+            compute_unit_price: escalation.compute_unit_price,
+            recommended_compute_unit_price: recommend_compute_unit_price(
+                escalation.compute_unit_price,
+                escalations,
+                recent_fees,
+            ),
+        };
+        self.notifier
+            .notify_compute_unit_price_recommendation(recommendation.clone());
+        Some(recommendation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::sync::Mutex};
+
+    #[derive(Default)]
+    struct TestNotifier(Mutex<Vec<ComputeUnitPriceRecommendation>>);
+
+    impl ComputeUnitPriceNotifier for TestNotifier {
+        fn notify_compute_unit_price_recommendation(
+            &self,
+            recommendation: ComputeUnitPriceRecommendation,
+        ) {
+            self.0.lock().unwrap().push(recommendation);
+        }
+    }
+
+    #[test]
+    fn test_recommend_compute_unit_price() {
+        assert_eq!(recommend_compute_unit_price(1_000, 0, vec![]), 1_000);
+        assert_eq!(recommend_compute_unit_price(1_000, 1, vec![]), 1_250);
+        assert_eq!(recommend_compute_unit_price(1_000, 2, vec![]), 1_562);
+        // Low prices still increase
+        assert_eq!(recommend_compute_unit_price(0, 1, vec![]), 1);
+        assert_eq!(recommend_compute_unit_price(1, 2, vec![]), 3);
+        // The recent fees of the accounts raise the recommendation
+        assert_eq!(
+            recommend_compute_unit_price(1_000, 1, vec![5_000, 0, 0, 100, 2_000]),
+            2_000
+        );
+        assert_eq!(recommend_compute_unit_price(u64::MAX, 3, vec![]), u64::MAX);
+    }
+
+    #[test]
+    fn test_escalate() {
+        let notifier = Arc::new(TestNotifier::default());
+        let escalator = ComputeUnitPriceEscalator::new(
+            Arc::new(PrioritizationFeeCache::default()),
+            notifier.clone(),
+        );
+        let signature = Signature::new_unique();
+        let mut escalation = ComputeUnitPriceEscalation::new(2, 1_000, vec![Pubkey::new_unique()]);
+
+        let rotation = NUM_CONSECUTIVE_LEADER_SLOTS;
+        assert!(escalator.escalate(signature, &mut escalation, 10).is_none());
+        assert!(escalator
+            .escalate(signature, &mut escalation, 10 + rotation)
+            .is_none());
+        let recommendation = escalator
+            .escalate(signature, &mut escalation, 10 + 2 * rotation)
+            .unwrap();
+        assert_eq!(
+            recommendation,
+            ComputeUnitPriceRecommendation {
+                signature,
+                slot: 10 + 2 * rotation,
+                leader_rotations: 2,
+                compute_unit_price: 1_000,
+                recommended_compute_unit_price: 1_250,
+            }
+        );
+        // Only recommended once per escalation
+        assert!(escalator
+            .escalate(signature, &mut escalation, 10 + 3 * rotation)
+            .is_none());
+        let recommendation = escalator
+            .escalate(signature, &mut escalation, 10 + 4 * rotation)
+            .unwrap();
+        assert_eq!(recommendation.recommended_compute_unit_price, 1_562);
+        assert_eq!(notifier.0.lock().unwrap().len(), 2);
+    }
+}
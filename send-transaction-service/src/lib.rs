@@ -1,10 +1,12 @@
 #![allow(clippy::arithmetic_side_effects)]
+pub mod compute_unit_price_escalation;
 pub mod send_transaction_service;
 pub mod send_transaction_service_stats;
 #[cfg(any(test, feature = "dev-context-only-utils"))]
 pub mod test_utils;
 pub mod tpu_info;
 pub mod transaction_client;
+pub mod transaction_ingestion_log;
 
 pub use {
     send_transaction_service_stats::SendTransactionServiceStats,
@@ -8,9 +8,11 @@ pub use crate::{
 };
 use {
     crate::{
+        compute_unit_price_escalation::{ComputeUnitPriceEscalation, ComputeUnitPriceEscalator},
         send_transaction_service_stats::SendTransactionServiceStatsReport,
         tpu_info::TpuInfo,
         transaction_client::{ConnectionCacheClient, TransactionClient},
+        transaction_ingestion_log::{IngestionEvent, IngestionStage, TransactionIngestionLog},
     },
     crossbeam_channel::{Receiver, RecvTimeoutError},
     itertools::Itertools,
@@ -78,6 +80,7 @@ pub struct TransactionInfo {
     retries: usize,
     /// Last time the transaction was sent
     last_sent_time: Option<Instant>,
+    compute_unit_price_escalation: Option<ComputeUnitPriceEscalation>,
 }
 
 impl TransactionInfo {
@@ -97,8 +100,18 @@ impl TransactionInfo {
             max_retries,
             retries: 0,
             last_sent_time,
+            compute_unit_price_escalation: None,
         }
     }
+
+    /// Requests higher compute unit prices to be recommended while the transaction does not land
+    pub fn with_compute_unit_price_escalation(
+        mut self,
+        compute_unit_price_escalation: Option<ComputeUnitPriceEscalation>,
+    ) -> Self {
+        self.compute_unit_price_escalation = compute_unit_price_escalation;
+        self
+    }
 }
 
 #[derive(Default, Debug, PartialEq, Eq)]
@@ -124,6 +137,13 @@ pub struct Config {
     /// When the retry pool exceeds this max size, new transactions are dropped after their first broadcast attempt
     pub retry_pool_max_size: usize,
     pub tpu_peers: Option<Vec<SocketAddr>>,
+    /// Where forwarded and expired transactions are recorded, if enabled
+    pub ingestion_log: Option<Arc<TransactionIngestionLog>>,
+    /// Recommends higher compute unit prices for the transactions which request it, if enabled
+    pub compute_unit_price_escalator: Option<Arc<ComputeUnitPriceEscalator>>,
+    /// Whether the connection cache presents a staked identity to the leaders, which selects the
+    /// metrics its sends are counted under
+    pub forward_with_staked_identity: bool,
 }
 
 impl Default for Config {
@@ -137,6 +157,9 @@ impl Default for Config {
             batch_send_rate_ms: DEFAULT_BATCH_SEND_RATE_MS,
             retry_pool_max_size: MAX_TRANSACTION_RETRY_POOL_SIZE,
             tpu_peers: None,
+            ingestion_log: None,
+            compute_unit_price_escalator: None,
+            forward_with_staked_identity: false,
         }
     }
 }
@@ -190,7 +213,8 @@ impl SendTransactionService {
             config.tpu_peers.clone(),
             leader_info,
             config.leader_forward_count,
-        );
+        )
+        .with_staked_identity(config.forward_with_staked_identity);
 
         Self::new_with_client(bank_forks, receiver, client, config, exit)
     }
@@ -239,6 +263,7 @@ impl SendTransactionService {
             batch_send_rate_ms,
             batch_size,
             retry_pool_max_size,
+            ingestion_log,
             ..
         }: Config,
         stats_report: Arc<SendTransactionServiceStatsReport>,
@@ -297,6 +322,11 @@ impl SendTransactionService {
                         .map(|transaction_info| transaction_info.wire_transaction.clone())
                         .collect::<Vec<Vec<u8>>>();
                     client.send_transactions_in_batch(wire_transactions, stats);
+                    if let Some(ingestion_log) = &ingestion_log {
+                        ingestion_log.record_batch(transactions.keys().map(|signature| {
+                            (*signature, IngestionEvent::new(IngestionStage::Forwarded))
+                        }));
+                    }
                     let last_sent_time = Instant::now();
                     {
                         // take a lock of retry_transactions and move the batch to the retry set.
@@ -388,6 +418,8 @@ impl SendTransactionService {
             service_max_retries,
             default_max_retries,
             batch_size,
+            ref ingestion_log,
+            ref compute_unit_price_escalator,
             ..
         }: &Config,
         stats: &SendTransactionServiceStats,
@@ -396,6 +428,7 @@ impl SendTransactionService {
 
         let mut batched_transactions = HashSet::new();
         let retry_rate = Duration::from_millis(retry_rate_ms);
+        let mut ingestion_events = vec![];
 
         transactions.retain(|signature, transaction_info| {
             if transaction_info.durable_nonce_info.is_some() {
@@ -419,6 +452,11 @@ impl SendTransactionService {
                     nonce_account::verify_nonce_account(&nonce_account, &durable_nonce);
                 if verify_nonce_account.is_none() && signature_status.is_none() && expired {
                     info!("Dropping expired durable-nonce transaction: {}", signature);
+                    ingestion_events.push((
+                        *signature,
+                        IngestionEvent::new(IngestionStage::Expired)
+                            .with_reason("durable nonce advanced"),
+                    ));
                     result.expired += 1;
                     stats.expired_transactions.fetch_add(1, Ordering::Relaxed);
                     return false;
@@ -426,6 +464,10 @@ impl SendTransactionService {
             }
             if transaction_info.last_valid_block_height < root_bank.block_height() {
                 info!("Dropping expired transaction: {}", signature);
+                ingestion_events.push((
+                    *signature,
+                    IngestionEvent::new(IngestionStage::Expired).with_reason("blockhash expired"),
+                ));
                 result.expired += 1;
                 stats.expired_transactions.fetch_add(1, Ordering::Relaxed);
                 return false;
@@ -439,6 +481,11 @@ impl SendTransactionService {
             if let Some(max_retries) = max_retries {
                 if transaction_info.retries >= max_retries {
                     info!("Dropping transaction due to max retries: {}", signature);
+                    ingestion_events.push((
+                        *signature,
+                        IngestionEvent::new(IngestionStage::Expired)
+                            .with_reason("max retries elapsed"),
+                    ));
                     result.max_retries_elapsed += 1;
                     stats
                         .transactions_exceeding_max_retries
@@ -449,6 +496,19 @@ impl SendTransactionService {
 
             match signature_status {
                 None => {
+                    if let (Some(escalator), Some(escalation)) = (
+                        compute_unit_price_escalator,
+                        &mut transaction_info.compute_unit_price_escalation,
+                    ) {
+                        if let Some(recommendation) =
+                            escalator.escalate(*signature, escalation, working_bank.slot())
+                        {
+                            info!(
+                                "Recommending compute unit price {} for transaction: {}",
+                                recommendation.recommended_compute_unit_price, signature
+                            );
+                        }
+                    }
                     let now = Instant::now();
                     let need_send = transaction_info
                         .last_sent_time
@@ -470,9 +530,15 @@ impl SendTransactionService {
                     }
                     true
                 }
-                Some((_slot, status)) => {
-                    if status.is_err() {
+                Some((slot, status)) => {
+                    if let Err(err) = status {
                         info!("Dropping failed transaction: {}", signature);
+                        ingestion_events.push((
+                            *signature,
+                            IngestionEvent::new(IngestionStage::Executed)
+                                .with_slot(slot)
+                                .with_reason(format!("failed: {err}")),
+                        ));
                         result.failed += 1;
                         stats.failed_transactions.fetch_add(1, Ordering::Relaxed);
                         false
@@ -484,6 +550,16 @@ impl SendTransactionService {
             }
         });
 
+        if let Some(ingestion_log) = ingestion_log {
+            ingestion_log.record_batch(
+                ingestion_events.into_iter().chain(
+                    batched_transactions.iter().map(|signature| {
+                        (*signature, IngestionEvent::new(IngestionStage::Forwarded))
+                    }),
+                ),
+            );
+        }
+
         if !batched_transactions.is_empty() {
             // Processing the transactions in batch
             let wire_transactions = transactions
@@ -565,6 +641,7 @@ mod test {
             max_retries: None,
             retries: 0,
             last_sent_time: None,
+            compute_unit_price_escalation: None,
         };
 
         let exit = Arc::new(AtomicBool::new(false));
@@ -34,6 +34,18 @@ pub struct SendTransactionServiceStats {
     /// Send failure count
     pub send_failure_count: AtomicU64,
 
+    /// Count of send attempts through a connection cache presenting a staked identity
+    pub staked_send_attempt_count: AtomicU64,
+
+    /// Send failure count through a connection cache presenting a staked identity
+    pub staked_send_failure_count: AtomicU64,
+
+    /// Count of send attempts through a connection cache presenting an unstaked identity
+    pub unstaked_send_attempt_count: AtomicU64,
+
+    /// Send failure count through a connection cache presenting an unstaked identity
+    pub unstaked_send_failure_count: AtomicU64,
+
     /// Count of nonced transactions
     pub nonced_transactions: AtomicU64,
 
@@ -110,6 +122,34 @@ impl SendTransactionServiceStatsReport {
                     self.stats.send_failure_count.swap(0, Ordering::Relaxed),
                     i64
                 ),
+                (
+                    "staked-send-attempt-count",
+                    self.stats
+                        .staked_send_attempt_count
+                        .swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "staked-send-failure-count",
+                    self.stats
+                        .staked_send_failure_count
+                        .swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "unstaked-send-attempt-count",
+                    self.stats
+                        .unstaked_send_attempt_count
+                        .swap(0, Ordering::Relaxed),
+                    i64
+                ),
+                (
+                    "unstaked-send-failure-count",
+                    self.stats
+                        .unstaked_send_failure_count
+                        .swap(0, Ordering::Relaxed),
+                    i64
+                ),
                 (
                     "nonced-tx",
                     self.stats.nonced_transactions.swap(0, Ordering::Relaxed),
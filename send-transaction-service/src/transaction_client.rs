@@ -29,6 +29,8 @@ pub struct ConnectionCacheClient<T: TpuInfoWithSendStatic> {
     tpu_peers: Option<Vec<SocketAddr>>,
     leader_info_provider: Arc<Mutex<CurrentLeaderInfo<T>>>,
     leader_forward_count: u64,
+    /// Whether `connection_cache` presents a staked identity to the leaders
+    staked_identity: bool,
 }
 
 // Manual implementation of Clone without requiring T to be Clone
@@ -43,6 +45,7 @@ where
             tpu_peers: self.tpu_peers.clone(),
             leader_info_provider: Arc::clone(&self.leader_info_provider),
             leader_forward_count: self.leader_forward_count,
+            staked_identity: self.staked_identity,
         }
     }
 }
@@ -65,9 +68,17 @@ where
             tpu_peers,
             leader_info_provider,
             leader_forward_count,
+            staked_identity: false,
         }
     }
 
+    /// Marks the connection cache as presenting a staked identity to the leaders, so that its
+    /// sends are counted separately from the unstaked ones
+    pub fn with_staked_identity(mut self, staked_identity: bool) -> Self {
+        self.staked_identity = staked_identity;
+        self
+    }
+
     fn get_tpu_addresses<'a>(&'a self, leader_info: Option<&'a T>) -> Vec<&'a SocketAddr> {
         leader_info
             .map(|leader_info| {
@@ -87,6 +98,17 @@ where
         let mut measure = Measure::start("send-us");
         let conn = self.connection_cache.get_connection(peer);
         let result = conn.send_data_batch_async(wire_transactions);
+        let (forward_attempt_count, forward_failure_count) = if self.staked_identity {
+            (
+                &stats.staked_send_attempt_count,
+                &stats.staked_send_failure_count,
+            )
+        } else {
+            (
+                &stats.unstaked_send_attempt_count,
+                &stats.unstaked_send_failure_count,
+            )
+        };
 
         if let Err(err) = result {
             warn!(
@@ -94,11 +116,13 @@ where
                 self.tpu_address, err
             );
             stats.send_failure_count.fetch_add(1, Ordering::Relaxed);
+            forward_failure_count.fetch_add(1, Ordering::Relaxed);
         }
 
         measure.stop();
         stats.send_us.fetch_add(measure.as_us(), Ordering::Relaxed);
         stats.send_attempt_count.fetch_add(1, Ordering::Relaxed);
+        forward_attempt_count.fetch_add(1, Ordering::Relaxed);
     }
 }
 
@@ -176,3 +200,34 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::tpu_info::NullTpuInfo,
+        std::net::{Ipv4Addr, UdpSocket},
+    };
+
+    #[test]
+    fn test_send_stats_by_identity() {
+        let receiver = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let tpu_address = receiver.local_addr().unwrap();
+        let connection_cache = Arc::new(ConnectionCache::with_udp("connection_cache_test", 1));
+        let stats = SendTransactionServiceStats::default();
+
+        let client =
+            ConnectionCacheClient::<NullTpuInfo>::new(connection_cache, tpu_address, None, None, 1);
+        client.send_transactions_in_batch(vec![vec![0; 8]], &stats);
+        assert_eq!(stats.unstaked_send_attempt_count.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.staked_send_attempt_count.load(Ordering::Relaxed), 0);
+
+        let client = client.with_staked_identity(true);
+        client.send_transactions_in_batch(vec![vec![0; 8]], &stats);
+        assert_eq!(stats.unstaked_send_attempt_count.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.staked_send_attempt_count.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.send_attempt_count.load(Ordering::Relaxed), 2);
+        assert_eq!(stats.send_failure_count.load(Ordering::Relaxed), 0);
+        assert_eq!(stats.staked_send_failure_count.load(Ordering::Relaxed), 0);
+    }
+}
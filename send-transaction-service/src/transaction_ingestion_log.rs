@@ -0,0 +1,204 @@
+//! In-memory record of the most recent lifecycle events of transactions seen by this node.
+//!
+//! Events are kept in a ring buffer so the memory used is bounded: once full, recording an event
+//! evicts the oldest one. It backs the `getTransactionIngestionStatus` RPC method, which reports
+//! where the node last saw a transaction that never landed.
+
+use {
+    solana_sdk::{clock::Slot, signature::Signature, timing::timestamp},
+    std::{
+        collections::{hash_map::Entry, HashMap, VecDeque},
+        sync::Mutex,
+    },
+};
+
+/// Default number of events kept by the log
+pub const DEFAULT_TRANSACTION_INGESTION_LOG_CAPACITY: usize = 100_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IngestionStage {
+    /// Received by `sendTransaction`
+    ReceivedViaRpc,
+    /// Received from the TPU and buffered by the banking stage
+    ReceivedViaTpu,
+    /// Dropped before execution, see the reason of the event
+    Filtered,
+    /// Sent to the TPU of upcoming leaders
+    Forwarded,
+    /// Handed to a banking stage worker for execution
+    Scheduled,
+    /// Executed and committed into a leader block
+    Executed,
+    /// No longer retried since its blockhash expired or it ran out of retries
+    Expired,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IngestionEvent {
+    /// Milliseconds since the UNIX epoch
+    pub timestamp: u64,
+    pub stage: IngestionStage,
+    pub slot: Option<Slot>,
+    pub reason: Option<String>,
+}
+
+impl IngestionEvent {
+    pub fn new(stage: IngestionStage) -> Self {
+        Self {
+            timestamp: timestamp(),
+            stage,
+            slot: None,
+            reason: None,
+        }
+    }
+
+    pub fn with_slot(self, slot: Slot) -> Self {
+        Self {
+            slot: Some(slot),
+            ..self
+        }
+    }
+
+    pub fn with_reason(self, reason: impl Into<String>) -> Self {
+        Self {
+            reason: Some(reason.into()),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct IngestionEvents {
+    /// Signatures of the recorded events, oldest first
+    order: VecDeque<Signature>,
+    /// Recorded events of each signature, oldest first
+    events: HashMap<Signature, VecDeque<IngestionEvent>>,
+}
+
+#[derive(Debug)]
+pub struct TransactionIngestionLog {
+    capacity: usize,
+    inner: Mutex<IngestionEvents>,
+}
+
+impl TransactionIngestionLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::default(),
+        }
+    }
+
+    pub fn record(&self, signature: Signature, event: IngestionEvent) {
+        self.record_batch(std::iter::once((signature, event)));
+    }
+
+    /// Records several events while taking the lock once.
+    pub fn record_batch(&self, events: impl IntoIterator<Item = (Signature, IngestionEvent)>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        for (signature, event) in events {
+            if inner.order.len() == self.capacity {
+                let oldest = inner.order.pop_front().unwrap();
+                // The oldest event overall is also the oldest one of its signature
+                if let Entry::Occupied(mut entry) = inner.events.entry(oldest) {
+                    entry.get_mut().pop_front();
+                    if entry.get().is_empty() {
+                        entry.remove();
+                    }
+                }
+            }
+            inner.order.push_back(signature);
+            inner.events.entry(signature).or_default().push_back(event);
+        }
+    }
+
+    /// Returns the recorded events of `signature`, oldest first.
+    pub fn get_events(&self, signature: &Signature) -> Vec<IngestionEvent> {
+        self.inner
+            .lock()
+            .unwrap()
+            .events
+            .get(signature)
+            .map(|events| events.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_get_events() {
+        let log = TransactionIngestionLog::new(10);
+        let signature = Signature::new_unique();
+        assert!(log.get_events(&signature).is_empty());
+
+        log.record(
+            signature,
+            IngestionEvent::new(IngestionStage::ReceivedViaRpc),
+        );
+        log.record(
+            signature,
+            IngestionEvent::new(IngestionStage::Filtered).with_reason("fee payer check"),
+        );
+        log.record(
+            Signature::new_unique(),
+            IngestionEvent::new(IngestionStage::ReceivedViaTpu).with_slot(1),
+        );
+
+        let events = log.get_events(&signature);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].stage, IngestionStage::ReceivedViaRpc);
+        assert_eq!(events[1].stage, IngestionStage::Filtered);
+        assert_eq!(events[1].reason.as_deref(), Some("fee payer check"));
+        assert_eq!(events[1].slot, None);
+    }
+
+    #[test]
+    fn test_evicts_oldest_events() {
+        let log = TransactionIngestionLog::new(3);
+        let signature1 = Signature::new_unique();
+        let signature2 = Signature::new_unique();
+        log.record_batch([
+            (
+                signature1,
+                IngestionEvent::new(IngestionStage::ReceivedViaTpu),
+            ),
+            (
+                signature2,
+                IngestionEvent::new(IngestionStage::ReceivedViaTpu),
+            ),
+            (signature1, IngestionEvent::new(IngestionStage::Scheduled)),
+        ]);
+        assert_eq!(log.get_events(&signature1).len(), 2);
+
+        log.record(signature2, IngestionEvent::new(IngestionStage::Scheduled));
+        let events = log.get_events(&signature1);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].stage, IngestionStage::Scheduled);
+        assert_eq!(log.get_events(&signature2).len(), 2);
+
+        log.record_batch([
+            (signature2, IngestionEvent::new(IngestionStage::Executed)),
+            (signature2, IngestionEvent::new(IngestionStage::Executed)),
+        ]);
+        assert!(log.get_events(&signature1).is_empty());
+        assert_eq!(log.get_events(&signature2).len(), 3);
+        assert_eq!(log.inner.lock().unwrap().events.len(), 1);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let log = TransactionIngestionLog::new(0);
+        let signature = Signature::new_unique();
+        log.record(
+            signature,
+            IngestionEvent::new(IngestionStage::ReceivedViaRpc),
+        );
+        assert!(log.get_events(&signature).is_empty());
+    }
+}
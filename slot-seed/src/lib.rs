@@ -0,0 +1,173 @@
+//! A seed that changes every slot, for on-chain randomness.
+//!
+//! With the `slot_seed_sysvar` feature active, the bank records a [`SlotSeed`] in the account at
+//! [`id`] before executing the transactions of every slot. The seed of a slot is hashed from the
+//! seed of its parent, the parent's bank hash and the slot's leader:
+//!
+//! `seed = sha256("x1-slot-seed" || parent seed || parent bank hash || slot || leader)`
+//!
+//! The parent bank hash commits to the parent's last blockhash, and so to the PoH chain, as well
+//! as to every account written by the parent. Anyone holding the bank hashes of the chain can
+//! recompute and verify the seed of every slot.
+//!
+//! # Manipulation resistance
+//!
+//! The seed is deterministic, not secret, and must not be the only source of randomness for
+//! anything of value:
+//!
+//! * The seed of a slot is known once its parent is frozen, so the leader of the slot, and
+//!   anyone watching the cluster, knows it before any transaction of the slot executes. A leader
+//!   can reorder, include or censor transactions knowing the outcome.
+//! * The leader of the parent slot influences the seed through the transactions it includes and
+//!   can try many blocks before choosing which to broadcast, or skip its slot to reroll the seed.
+//! * Transactions are free to abort when the outcome does not suit them, so a program must
+//!   commit to an action in one transaction and resolve it with the seed of a later slot.
+//!
+//! The seed does not include a signature of the leader, as the bank executing the slot has no
+//! signature from the leader over data it could not have chosen. Programs needing unbiasable
+//! randomness should combine the seed of a future slot with a commit-reveal scheme or an oracle.
+
+use {
+    serde_derive::{Deserialize, Serialize},
+    solana_account_info::AccountInfo,
+    solana_clock::Slot,
+    solana_hash::Hash,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_sha256_hasher::hashv,
+};
+
+solana_pubkey::declare_id!("4BtGAEkX1wXP7G44GiKBkMSuMcvATJZcxuxdmgVZeBsa");
+
+/// Domain separator of the seed derivation
+const SLOT_SEED_DOMAIN: &[u8] = b"x1-slot-seed";
+
+/// The seed of `slot`, as recorded in the account at [`id`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotSeed {
+    pub slot: Slot,
+    pub seed: Hash,
+}
+
+impl SlotSeed {
+    /// Serialized size of the account data
+    pub const SIZE: usize = 40;
+
+    /// Returns the seed of `slot`, whose parent had this seed and `parent_bank_hash`, and whose
+    /// leader is `leader`
+    pub fn next(&self, slot: Slot, parent_bank_hash: &Hash, leader: &Pubkey) -> Self {
+        Self {
+            slot,
+            seed: hashv(&[
+                SLOT_SEED_DOMAIN,
+                self.seed.as_ref(),
+                parent_bank_hash.as_ref(),
+                &slot.to_le_bytes(),
+                leader.as_ref(),
+            ]),
+        }
+    }
+
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        bincode::deserialize(data).ok()
+    }
+
+    /// Reads the seed from the sysvar account passed to a program
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        if account_info.key != &id() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let data = account_info.try_borrow_data()?;
+        Self::from_account_data(&data).ok_or(ProgramError::InvalidAccountData)
+    }
+
+    /// Returns a hash of the seed specific to `domain`, so unrelated uses of the seed in the
+    /// same slot, such as two lotteries, do not draw the same values
+    pub fn derive(&self, domain: &[u8]) -> Hash {
+        hashv(&[self.seed.as_ref(), domain])
+    }
+
+    /// Returns a number in `0..bound` drawn from the seed for `domain`. The modulo bias is
+    /// negligible for bounds much smaller than `u64::MAX`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is 0.
+    pub fn random_below(&self, domain: &[u8], bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+        let hash = self.derive(domain);
+        let value = u64::from_le_bytes(hash.as_ref()[..8].try_into().unwrap());
+        value % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot_seed() {
+        let leader = Pubkey::new_unique();
+        let parent_bank_hash = Hash::new_unique();
+        let seed = SlotSeed::default().next(1, &parent_bank_hash, &leader);
+        assert_eq!(seed.slot, 1);
+        assert_eq!(
+            seed,
+            SlotSeed::default().next(1, &parent_bank_hash, &leader)
+        );
+        assert_ne!(
+            seed,
+            SlotSeed::default().next(1, &parent_bank_hash, &Pubkey::new_unique())
+        );
+        assert_ne!(
+            seed.seed,
+            SlotSeed::default().next(2, &parent_bank_hash, &leader).seed
+        );
+        assert_ne!(seed.next(2, &parent_bank_hash, &leader).seed, seed.seed);
+
+        let data = bincode::serialize(&seed).unwrap();
+        assert_eq!(data.len(), SlotSeed::SIZE);
+        assert_eq!(SlotSeed::from_account_data(&data), Some(seed));
+        assert_eq!(SlotSeed::from_account_data(&data[..8]), None);
+
+        assert_ne!(seed.derive(b"lottery"), seed.derive(b"raffle"));
+        assert!(seed.random_below(b"dice", 6) < 6);
+    }
+
+    #[test]
+    fn test_from_account_info() {
+        let seed = SlotSeed::default().next(1, &Hash::new_unique(), &Pubkey::new_unique());
+        let mut data = bincode::serialize(&seed).unwrap();
+        let mut lamports = 1;
+        let (key, owner) = (id(), Pubkey::new_unique());
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(SlotSeed::from_account_info(&account_info), Ok(seed));
+
+        let mut data = bincode::serialize(&seed).unwrap();
+        let mut lamports = 1;
+        let key = Pubkey::new_unique();
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            SlotSeed::from_account_info(&account_info),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+}
@@ -1025,6 +1025,7 @@ mod tests {
                 loaded_addresses: LoadedAddresses::default(),
                 return_data: Some(TransactionReturnData::default()),
                 compute_units_consumed: Some(1234),
+                fee_breakdown: None,
             },
         });
         let expected_block = ConfirmedBlock {
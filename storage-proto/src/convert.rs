@@ -15,8 +15,8 @@ use {
     solana_transaction_context::TransactionReturnData,
     solana_transaction_error::TransactionError,
     solana_transaction_status::{
-        ConfirmedBlock, EntrySummary, InnerInstruction, InnerInstructions, Reward, RewardType,
-        RewardsAndNumPartitions, TransactionByAddrInfo, TransactionStatusMeta,
+        ConfirmedBlock, EntrySummary, FeeBreakdown, InnerInstruction, InnerInstructions, Reward,
+        RewardType, RewardsAndNumPartitions, TransactionByAddrInfo, TransactionStatusMeta,
         TransactionTokenBalance, TransactionWithStatusMeta, VersionedConfirmedBlock,
         VersionedTransactionWithStatusMeta,
     },
@@ -407,6 +407,7 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            fee_breakdown,
         } = value;
         let err = match status {
             Ok(()) => None,
@@ -467,6 +468,7 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             return_data,
             return_data_none,
             compute_units_consumed,
+            fee_breakdown: fee_breakdown.map(|fee_breakdown| fee_breakdown.into()),
         }
     }
 }
@@ -499,6 +501,7 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
             return_data,
             return_data_none,
             compute_units_consumed,
+            fee_breakdown,
         } = value;
         let status = match &err {
             None => Ok(()),
@@ -568,6 +571,7 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            fee_breakdown: fee_breakdown.map(|fee_breakdown| fee_breakdown.into()),
         })
     }
 }
@@ -674,6 +678,30 @@ impl From<generated::ReturnData> for TransactionReturnData {
     }
 }
 
+impl From<FeeBreakdown> for generated::FeeBreakdown {
+    fn from(value: FeeBreakdown) -> Self {
+        Self {
+            base: value.base,
+            prioritization: value.prioritization,
+            memory: value.memory,
+            size: value.size,
+            congestion: value.congestion,
+        }
+    }
+}
+
+impl From<generated::FeeBreakdown> for FeeBreakdown {
+    fn from(value: generated::FeeBreakdown) -> Self {
+        Self {
+            base: value.base,
+            prioritization: value.prioritization,
+            memory: value.memory,
+            size: value.size,
+            congestion: value.congestion,
+        }
+    }
+}
+
 impl From<CompiledInstruction> for generated::CompiledInstruction {
     fn from(value: CompiledInstruction) -> Self {
         Self {
@@ -853,6 +881,9 @@ impl TryFrom<tx_by_addr::TransactionError> for TransactionError {
             36 => TransactionError::UnbalancedTransaction,
             37 => TransactionError::ProgramCacheHitMaxLimit,
             38 => TransactionError::CommitCancelled,
+            39 => TransactionError::FeeExceedsCap,
+            40 => TransactionError::FeePayerInsufficientForBaseFee,
+            41 => TransactionError::CongestionFeeRequired,
             _ => return Err("Invalid TransactionError"),
         })
     }
@@ -977,6 +1008,15 @@ impl From<TransactionError> for tx_by_addr::TransactionError {
                 TransactionError::CommitCancelled => {
                     tx_by_addr::TransactionErrorType::CommitCancelled
                 }
+                TransactionError::FeeExceedsCap => {
+                    tx_by_addr::TransactionErrorType::FeeExceedsCap
+                }
+                TransactionError::FeePayerInsufficientForBaseFee => {
+                    tx_by_addr::TransactionErrorType::FeePayerInsufficientForBaseFee
+                }
+                TransactionError::CongestionFeeRequired => {
+                    tx_by_addr::TransactionErrorType::CongestionFeeRequired
+                }
             } as i32,
             instruction_error: match transaction_error {
                 TransactionError::InstructionError(index, ref instruction_error) => {
@@ -1385,6 +1425,33 @@ mod test {
             tx_by_addr_transaction_error.try_into().unwrap()
         );
 
+        let transaction_error = TransactionError::FeeExceedsCap;
+        let tx_by_addr_transaction_error: tx_by_addr::TransactionError =
+            transaction_error.clone().into();
+        assert_eq!(tx_by_addr_transaction_error.transaction_error, 39);
+        assert_eq!(
+            transaction_error,
+            tx_by_addr_transaction_error.try_into().unwrap()
+        );
+
+        let transaction_error = TransactionError::FeePayerInsufficientForBaseFee;
+        let tx_by_addr_transaction_error: tx_by_addr::TransactionError =
+            transaction_error.clone().into();
+        assert_eq!(tx_by_addr_transaction_error.transaction_error, 40);
+        assert_eq!(
+            transaction_error,
+            tx_by_addr_transaction_error.try_into().unwrap()
+        );
+
+        let transaction_error = TransactionError::CongestionFeeRequired;
+        let tx_by_addr_transaction_error: tx_by_addr::TransactionError =
+            transaction_error.clone().into();
+        assert_eq!(tx_by_addr_transaction_error.transaction_error, 41);
+        assert_eq!(
+            transaction_error,
+            tx_by_addr_transaction_error.try_into().unwrap()
+        );
+
         let transaction_error = TransactionError::InvalidAccountForFee;
         let tx_by_addr_transaction_error: tx_by_addr::TransactionError =
             transaction_error.clone().into();
@@ -211,6 +211,7 @@ impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
             loaded_addresses: LoadedAddresses::default(),
             return_data,
             compute_units_consumed,
+            fee_breakdown: None,
         }
     }
 }
@@ -231,6 +232,9 @@ impl TryFrom<TransactionStatusMeta> for StoredTransactionStatusMeta {
             loaded_addresses,
             return_data,
             compute_units_consumed,
+            // Deprecated bincode serialized status metadata doesn't support
+            // fee breakdowns, which are only informational.
+            fee_breakdown: _,
         } = value;
 
         if !loaded_addresses.is_empty() {
@@ -2,6 +2,7 @@
 pub mod msghdr;
 pub mod nonblocking;
 pub mod packet;
+pub mod packet_drop_stats;
 pub mod quic;
 pub mod recvmmsg;
 pub mod sendmmsg;
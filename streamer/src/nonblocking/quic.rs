@@ -7,7 +7,11 @@ use {
                 STREAM_THROTTLING_INTERVAL_MS,
             },
         },
-        quic::{configure_server, QuicServerError, QuicServerParams, StreamerStats},
+        packet_drop_stats::{packet_drop_stats, PacketDropReason},
+        quic::{
+            configure_server, update_connection_table_summary, ConnectionTableSummary,
+            QuicServerError, QuicServerParams, StakedEvictionPolicy, StreamerStats,
+        },
         streamer::StakedNodes,
     },
     async_channel::{bounded as async_bounded, Receiver as AsyncReceiver, Sender as AsyncSender},
@@ -37,13 +41,14 @@ use {
     solana_transaction_metrics_tracker::signature_if_should_track_packet,
     std::{
         array,
+        collections::HashMap,
         fmt,
         iter::repeat_with,
         net::{IpAddr, SocketAddr, UdpSocket},
         pin::Pin,
         // CAUTION: be careful not to introduce any awaits while holding an RwLock.
         sync::{
-            atomic::{AtomicBool, AtomicU64, Ordering},
+            atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
             Arc, RwLock,
         },
         task::Poll,
@@ -100,16 +105,15 @@ pub use crate::quic::DEFAULT_MAX_CONNECTIONS_PER_IPADDR_PER_MINUTE;
 )]
 pub use crate::quic::DEFAULT_MAX_STREAMS_PER_MS;
 
-/// Total new connection counts per second. Heuristically taken from
-/// the default staked and unstaked connection limits. Might be adjusted
-/// later.
-const TOTAL_CONNECTIONS_PER_SECOND: u64 = 2500;
-
 /// The threshold of the size of the connection rate limiter map. When
 /// the map size is above this, we will trigger a cleanup of older
 /// entries used by past requests.
 const CONNECTION_RATE_LIMITER_CLEANUP_SIZE_THRESHOLD: usize = 100_000;
 
+/// The number of IP addresses with the most connections reported in the
+/// connection table summary
+const CONNECTION_TABLE_SUMMARY_TOP_IPADDRS: usize = 10;
+
 // A struct to accumulate the bytes making up
 // a packet, along with their offsets, and the
 // packet metadata. We use this accumulator to avoid
@@ -187,6 +191,9 @@ pub fn spawn_server_multi(
         max_connections_per_peer,
         max_streams_per_ms,
         max_connections_per_ipaddr_per_min,
+        max_connections_per_ipaddr,
+        max_connections_per_second,
+        staked_eviction_policy,
         wait_for_chunk_timeout,
         coalesce,
         coalesce_channel_size,
@@ -219,6 +226,9 @@ pub fn spawn_server_multi(
         max_unstaked_connections,
         max_streams_per_ms,
         max_connections_per_ipaddr_per_min,
+        max_connections_per_ipaddr,
+        max_connections_per_second,
+        staked_eviction_policy,
         stats.clone(),
         wait_for_chunk_timeout,
         coalesce,
@@ -239,6 +249,9 @@ pub fn spawn_server_multi(
 /// reduced when it is dropped.
 struct ClientConnectionTracker {
     stats: Arc<StreamerStats>,
+    // Counts the connection against the limit of its IP address, until the peer
+    // turns out to be staked
+    ipaddr_connection: Option<IpAddrConnection>,
 }
 
 /// This is required by ConnectionEntry for supporting debug format.
@@ -274,7 +287,72 @@ impl ClientConnectionTracker {
             return Err(());
         }
 
-        Ok(Self { stats })
+        Ok(Self {
+            stats,
+            ipaddr_connection: None,
+        })
+    }
+}
+
+/// Counts the concurrent connections of every IP address
+struct ConnectionsPerIpAddr {
+    max_connections_per_ipaddr: usize,
+    counts: std::sync::Mutex<HashMap<IpAddr, usize>>,
+}
+
+impl ConnectionsPerIpAddr {
+    fn new(max_connections_per_ipaddr: usize) -> Self {
+        Self {
+            max_connections_per_ipaddr,
+            counts: std::sync::Mutex::default(),
+        }
+    }
+
+    /// Counts a connection from `ip`, unless it has too many connections already
+    fn try_add(self: &Arc<Self>, ip: IpAddr) -> Option<IpAddrConnection> {
+        let mut counts = self.counts.lock().unwrap();
+        if counts.get(&ip).copied().unwrap_or_default() >= self.max_connections_per_ipaddr {
+            return None;
+        }
+        *counts.entry(ip).or_default() += 1;
+        Some(IpAddrConnection {
+            connections_per_ipaddr: self.clone(),
+            ip,
+        })
+    }
+
+    /// Returns the IP addresses with the most connections, in decreasing order
+    fn top(&self, num_ipaddrs: usize) -> Vec<(IpAddr, usize)> {
+        let mut counts: Vec<_> = self
+            .counts
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(ip, count)| (*ip, *count))
+            .collect();
+        counts.sort_unstable_by(|(ip, count), (other_ip, other_count)| {
+            other_count.cmp(count).then(ip.cmp(other_ip))
+        });
+        counts.truncate(num_ipaddrs);
+        counts
+    }
+}
+
+/// A connection counted against the limit of its IP address, until dropped
+struct IpAddrConnection {
+    connections_per_ipaddr: Arc<ConnectionsPerIpAddr>,
+    ip: IpAddr,
+}
+
+impl Drop for IpAddrConnection {
+    fn drop(&mut self) {
+        let mut counts = self.connections_per_ipaddr.counts.lock().unwrap();
+        if let Some(count) = counts.get_mut(&self.ip) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                counts.remove(&self.ip);
+            }
+        }
     }
 }
 
@@ -290,6 +368,9 @@ async fn run_server(
     max_unstaked_connections: usize,
     max_streams_per_ms: u64,
     max_connections_per_ipaddr_per_min: u64,
+    max_connections_per_ipaddr: usize,
+    max_connections_per_second: u64,
+    staked_eviction_policy: StakedEvictionPolicy,
     stats: Arc<StreamerStats>,
     wait_for_chunk_timeout: Duration,
     coalesce: Duration,
@@ -298,7 +379,9 @@ async fn run_server(
 ) {
     let rate_limiter = ConnectionRateLimiter::new(max_connections_per_ipaddr_per_min);
     let overall_connection_rate_limiter =
-        TotalConnectionRateLimiter::new(TOTAL_CONNECTIONS_PER_SECOND);
+        TotalConnectionRateLimiter::new(max_connections_per_second);
+    let connections_per_ipaddr = Arc::new(ConnectionsPerIpAddr::new(max_connections_per_ipaddr));
+    let mut connection_table_summary = ConnectionTableSummary::default();
 
     const WAIT_FOR_CONNECTION_TIMEOUT: Duration = Duration::from_secs(1);
     debug!("spawn quic server");
@@ -357,6 +440,17 @@ async fn run_server(
         };
 
         if last_datapoint.elapsed().as_secs() >= 5 {
+            // Before the counters are reset by the report
+            update_connection_table_summary(
+                name,
+                summarize_connection_tables(
+                    &mut connection_table_summary,
+                    &*staked_connection_table.lock().await,
+                    &*unstaked_connection_table.lock().await,
+                    &connections_per_ipaddr,
+                    &stats,
+                ),
+            );
             stats.report(name);
             last_datapoint = Instant::now();
         }
@@ -384,6 +478,7 @@ async fn run_server(
                 stats
                     .connection_rate_limited_per_ipaddr
                     .fetch_add(1, Ordering::Relaxed);
+                packet_drop_stats().record(PacketDropReason::RateLimit, 1);
                 incoming.ignore();
                 continue;
             }
@@ -397,19 +492,36 @@ async fn run_server(
                 stats
                     .connection_rate_limited_across_all
                     .fetch_add(1, Ordering::Relaxed);
+                packet_drop_stats().record(PacketDropReason::RateLimit, 1);
                 incoming.ignore();
                 continue;
             }
 
-            let Ok(client_connection_tracker) =
+            let Some(ipaddr_connection) = connections_per_ipaddr.try_add(remote_address.ip())
+            else {
+                debug!(
+                    "Reject connection from {:?} -- too many connections from the IP address",
+                    remote_address.ip()
+                );
+                stats
+                    .refused_connections_too_many_per_ipaddr
+                    .fetch_add(1, Ordering::Relaxed);
+                packet_drop_stats().record(PacketDropReason::RateLimit, 1);
+                incoming.refuse();
+                continue;
+            };
+
+            let Ok(mut client_connection_tracker) =
                 ClientConnectionTracker::new(stats.clone(), max_concurrent_connections)
             else {
                 stats
                     .refused_connections_too_many_open_connections
                     .fetch_add(1, Ordering::Relaxed);
+                packet_drop_stats().record(PacketDropReason::RateLimit, 1);
                 incoming.refuse();
                 continue;
             };
+            client_connection_tracker.ipaddr_connection = Some(ipaddr_connection);
 
             stats
                 .outstanding_incoming_connection_attempts
@@ -428,6 +540,7 @@ async fn run_server(
                         max_staked_connections,
                         max_unstaked_connections,
                         max_streams_per_ms,
+                        staked_eviction_policy,
                         stats.clone(),
                         wait_for_chunk_timeout,
                         stream_load_ema.clone(),
@@ -443,6 +556,35 @@ async fn run_server(
     }
 }
 
+/// Updates `summary` with the current state of the connection tables, and the
+/// counters of `stats` accumulated since the last report
+fn summarize_connection_tables(
+    summary: &mut ConnectionTableSummary,
+    staked_connection_table: &ConnectionTable,
+    unstaked_connection_table: &ConnectionTable,
+    connections_per_ipaddr: &ConnectionsPerIpAddr,
+    stats: &StreamerStats,
+) -> ConnectionTableSummary {
+    let load = |counter: &AtomicUsize| counter.load(Ordering::Relaxed) as u64;
+    summary.staked_peers = staked_connection_table.table.len();
+    summary.staked_connections = staked_connection_table.total_size;
+    summary.unstaked_peers = unstaked_connection_table.table.len();
+    summary.unstaked_connections = unstaked_connection_table.total_size;
+    summary.handshakes_in_progress = stats
+        .outstanding_incoming_connection_attempts
+        .load(Ordering::Relaxed);
+    summary.top_ipaddrs = connections_per_ipaddr.top(CONNECTION_TABLE_SUMMARY_TOP_IPADDRS);
+    summary.evictions = summary.evictions.saturating_add(load(&stats.num_evictions));
+    summary.rate_limited = summary.rate_limited.saturating_add(
+        load(&stats.connection_rate_limited_per_ipaddr)
+            .saturating_add(load(&stats.connection_rate_limited_across_all)),
+    );
+    summary.refused_per_ipaddr = summary
+        .refused_per_ipaddr
+        .saturating_add(load(&stats.refused_connections_too_many_per_ipaddr));
+    summary.clone()
+}
+
 fn prune_unstaked_connection_table(
     unstaked_connection_table: &mut ConnectionTable,
     max_unstaked_connections: usize,
@@ -708,7 +850,7 @@ fn compute_recieve_window(
 #[allow(clippy::too_many_arguments)]
 async fn setup_connection(
     connecting: Connecting,
-    client_connection_tracker: ClientConnectionTracker,
+    mut client_connection_tracker: ClientConnectionTracker,
     unstaked_connection_table: Arc<Mutex<ConnectionTable>>,
     staked_connection_table: Arc<Mutex<ConnectionTable>>,
     packet_sender: AsyncSender<PacketAccumulator>,
@@ -717,6 +859,7 @@ async fn setup_connection(
     max_staked_connections: usize,
     max_unstaked_connections: usize,
     max_streams_per_ms: u64,
+    staked_eviction_policy: StakedEvictionPolicy,
     stats: Arc<StreamerStats>,
     wait_for_chunk_timeout: Duration,
     stream_load_ema: Arc<StakedStreamLoadEMA>,
@@ -765,11 +908,18 @@ async fn setup_connection(
 
                 match params.peer_type {
                     ConnectionPeerType::Staked(stake) => {
+                        // Staked peers are not limited per IP address
+                        client_connection_tracker.ipaddr_connection = None;
                         let mut connection_table_l = staked_connection_table.lock().await;
 
                         if connection_table_l.total_size >= max_staked_connections {
-                            let num_pruned =
-                                connection_table_l.prune_random(PRUNE_RANDOM_SAMPLE_SIZE, stake);
+                            let num_pruned = match staked_eviction_policy {
+                                StakedEvictionPolicy::RandomSample => {
+                                    connection_table_l.prune_random(PRUNE_RANDOM_SAMPLE_SIZE, stake)
+                                }
+                                StakedEvictionPolicy::StakeWeightedLru => connection_table_l
+                                    .prune_stake_weighted_lru(stake, timing::timestamp()),
+                            };
                             stats.num_evictions.fetch_add(num_pruned, Ordering::Relaxed);
                         }
 
@@ -1429,6 +1579,34 @@ impl ConnectionTable {
         num_pruned
     }
 
+    // Evicts the least recently used connection among the connections with
+    // less stake than threshold_stake, with idle times scaled by threshold_stake
+    // over the stake of the connection, and returns the number of pruned
+    // connections. Connections with stake close to threshold_stake must be idle
+    // for much longer than connections with little stake to be evicted first.
+    fn prune_stake_weighted_lru(&mut self, threshold_stake: u64, now: u64) -> usize {
+        let num_pruned = self
+            .table
+            .values()
+            .enumerate()
+            .filter_map(|(index, connections)| {
+                let stake = connections.first()?.stake();
+                let last_update = connections.iter().map(ConnectionEntry::last_update).max()?;
+                let idle = u128::from(now.saturating_sub(last_update));
+                (stake < threshold_stake).then(|| {
+                    let weighted_idle =
+                        idle * u128::from(threshold_stake) / u128::from(stake.max(1));
+                    (index, weighted_idle)
+                })
+            })
+            .max_by_key(|&(_, weighted_idle)| weighted_idle)
+            .and_then(|(index, _)| self.table.swap_remove_index(index))
+            .map(|(_, connections)| connections.len())
+            .unwrap_or_default();
+        self.total_size = self.total_size.saturating_sub(num_pruned);
+        num_pruned
+    }
+
     fn try_add_connection(
         &mut self,
         key: ConnectionTableKey,
@@ -2231,6 +2409,73 @@ pub mod test {
         assert_eq!(stats.open_connections.load(Ordering::Relaxed), 4);
     }
 
+    #[test]
+    fn test_prune_table_stake_weighted_lru() {
+        use std::net::Ipv4Addr;
+        solana_logger::setup();
+        let mut table = ConnectionTable::new();
+        let stats: Arc<StreamerStats> = Arc::new(StreamerStats::default());
+        // (stake, last_update) of every peer
+        let peers = [(100, 900), (10, 950), (1_000, 0), (50, 990)];
+        for (i, (stake, last_update)) in peers.iter().enumerate() {
+            table
+                .try_add_connection(
+                    ConnectionTableKey::IP(IpAddr::V4(Ipv4Addr::new(i as u8, 0, 0, 0))),
+                    0,
+                    ClientConnectionTracker::new(stats.clone(), 1000).unwrap(),
+                    None,
+                    ConnectionPeerType::Staked(*stake),
+                    *last_update,
+                    10,
+                )
+                .unwrap();
+        }
+        let has_stake = |table: &ConnectionTable, stake| {
+            table
+                .table
+                .values()
+                .any(|connections| connections[0].stake() == stake)
+        };
+
+        // No peer has less stake than the threshold
+        assert_eq!(table.prune_stake_weighted_lru(10, 1_000), 0);
+        assert_eq!(table.total_size, 4);
+
+        // At 1000, the peer with stake 10 was idle 50 and the peer with stake 100
+        // was idle 100, but idle times are scaled by the stake: 50 * 200 / 10 is
+        // more than 100 * 200 / 100 and 10 * 200 / 50
+        assert_eq!(table.prune_stake_weighted_lru(200, 1_000), 1);
+        assert_eq!(table.total_size, 3);
+        assert!(!has_stake(&table, 10));
+
+        // The peer with stake 1000 was idle the longest, but does not have less
+        // stake than the threshold
+        assert_eq!(table.prune_stake_weighted_lru(200, 1_000), 1);
+        assert!(!has_stake(&table, 100));
+        assert!(has_stake(&table, 1_000));
+        assert_eq!(stats.open_connections.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_connections_per_ipaddr() {
+        let connections_per_ipaddr = Arc::new(ConnectionsPerIpAddr::new(2));
+        let ip = IpAddr::from([10, 0, 0, 1]);
+        let other_ip = IpAddr::from([10, 0, 0, 2]);
+
+        let first = connections_per_ipaddr.try_add(ip).unwrap();
+        let second = connections_per_ipaddr.try_add(ip).unwrap();
+        assert!(connections_per_ipaddr.try_add(ip).is_none());
+        let other = connections_per_ipaddr.try_add(other_ip).unwrap();
+        assert_eq!(connections_per_ipaddr.top(1), vec![(ip, 2)]);
+        assert_eq!(connections_per_ipaddr.top(10), vec![(ip, 2), (other_ip, 1)]);
+
+        // Dropped connections free room for the IP address
+        drop(first);
+        let third = connections_per_ipaddr.try_add(ip).unwrap();
+        drop((second, third, other));
+        assert!(connections_per_ipaddr.top(10).is_empty());
+    }
+
     #[test]
     fn test_remove_connections() {
         use std::net::Ipv4Addr;
@@ -6,9 +6,10 @@ use {
     },
     crate::{
         quic::{
-            QuicServerParams, StreamerStats, DEFAULT_MAX_CONNECTIONS_PER_IPADDR_PER_MINUTE,
-            DEFAULT_MAX_STAKED_CONNECTIONS, DEFAULT_MAX_STREAMS_PER_MS,
-            DEFAULT_MAX_UNSTAKED_CONNECTIONS, DEFAULT_TPU_COALESCE,
+            QuicServerParams, StakedEvictionPolicy, StreamerStats,
+            DEFAULT_MAX_CONNECTIONS_PER_IPADDR, DEFAULT_MAX_CONNECTIONS_PER_IPADDR_PER_MINUTE,
+            DEFAULT_MAX_CONNECTIONS_PER_SECOND, DEFAULT_MAX_STAKED_CONNECTIONS,
+            DEFAULT_MAX_STREAMS_PER_MS, DEFAULT_MAX_UNSTAKED_CONNECTIONS, DEFAULT_TPU_COALESCE,
         },
         streamer::StakedNodes,
     },
@@ -138,6 +139,9 @@ pub fn setup_quic_server_with_sockets(
         max_unstaked_connections,
         max_streams_per_ms,
         max_connections_per_ipaddr_per_min,
+        max_connections_per_ipaddr: DEFAULT_MAX_CONNECTIONS_PER_IPADDR,
+        max_connections_per_second: DEFAULT_MAX_CONNECTIONS_PER_SECOND,
+        staked_eviction_policy: StakedEvictionPolicy::default(),
         wait_for_chunk_timeout: DEFAULT_WAIT_FOR_CHUNK_TIMEOUT,
         coalesce: DEFAULT_TPU_COALESCE,
         coalesce_channel_size,
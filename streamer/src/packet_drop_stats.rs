@@ -0,0 +1,158 @@
+//! Process-wide counts of the packets dropped by the TPU before reaching a block, by reason.
+//!
+//! Metrics already report the drops of every stage per interval; these counters accumulate
+//! since the validator started, or since they were last reset, so operators can tell why
+//! transactions are not landing without a metrics pipeline.
+
+use std::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Why a packet was dropped
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PacketDropReason {
+    /// A QUIC connection was refused by the connection rate limits or the connection limit.
+    /// Counts connections, which may each have carried any number of packets.
+    RateLimit,
+    /// The signatures of the transaction failed verification
+    Sigverify,
+    /// The transaction could not be deserialized or sanitized, or was not accepted by the bank
+    Sanitization,
+    /// The compute unit limit is below the cost of the builtin instructions of the transaction
+    InsufficientComputeUnitLimit,
+    /// The compute budget instructions of the transaction are invalid
+    InvalidComputeBudget,
+    /// The transaction verifies too many precompile signatures
+    ExcessivePrecompiles,
+    /// The compute unit price is below the minimum price
+    FeeFloor,
+    /// The transaction locks too many or duplicate accounts
+    AccountLocks,
+    /// The transaction was already processed, or its blockhash expired
+    TransactionChecks,
+    /// The fee payer cannot pay the fee
+    FeePayer,
+    /// The transaction was evicted from a full buffer by transactions of higher priority
+    Capacity,
+}
+
+impl PacketDropReason {
+    pub const ALL: [Self; 11] = [
+        Self::RateLimit,
+        Self::Sigverify,
+        Self::Sanitization,
+        Self::InsufficientComputeUnitLimit,
+        Self::InvalidComputeBudget,
+        Self::ExcessivePrecompiles,
+        Self::FeeFloor,
+        Self::AccountLocks,
+        Self::TransactionChecks,
+        Self::FeePayer,
+        Self::Capacity,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RateLimit => "rate_limit",
+            Self::Sigverify => "sigverify",
+            Self::Sanitization => "sanitization",
+            Self::InsufficientComputeUnitLimit => "insufficient_compute_unit_limit",
+            Self::InvalidComputeBudget => "invalid_compute_budget",
+            Self::ExcessivePrecompiles => "excessive_precompiles",
+            Self::FeeFloor => "fee_floor",
+            Self::AccountLocks => "account_locks",
+            Self::TransactionChecks => "transaction_checks",
+            Self::FeePayer => "fee_payer",
+            Self::Capacity => "capacity",
+        }
+    }
+}
+
+impl fmt::Display for PacketDropReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Counts of dropped packets, by reason
+pub struct PacketDropStats {
+    counts: [AtomicU64; PacketDropReason::ALL.len()],
+}
+
+impl Default for PacketDropStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PacketDropStats {
+    pub const fn new() -> Self {
+        Self {
+            counts: [const { AtomicU64::new(0) }; PacketDropReason::ALL.len()],
+        }
+    }
+
+    pub fn record(&self, reason: PacketDropReason, count: u64) {
+        if count > 0 {
+            self.counts[reason as usize].fetch_add(count, Ordering::Relaxed);
+        }
+    }
+
+    pub fn count(&self, reason: PacketDropReason) -> u64 {
+        self.counts[reason as usize].load(Ordering::Relaxed)
+    }
+
+    /// Returns the count of every reason
+    pub fn counts(&self) -> Vec<(PacketDropReason, u64)> {
+        PacketDropReason::ALL
+            .iter()
+            .map(|reason| (*reason, self.count(*reason)))
+            .collect()
+    }
+
+    /// Returns the count of every reason and resets them
+    pub fn take(&self) -> Vec<(PacketDropReason, u64)> {
+        PacketDropReason::ALL
+            .iter()
+            .map(|reason| {
+                (
+                    *reason,
+                    self.counts[*reason as usize].swap(0, Ordering::Relaxed),
+                )
+            })
+            .collect()
+    }
+}
+
+static PACKET_DROP_STATS: PacketDropStats = PacketDropStats::new();
+
+/// The packet drops of the process
+pub fn packet_drop_stats() -> &'static PacketDropStats {
+    &PACKET_DROP_STATS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packet_drop_stats() {
+        for (index, reason) in PacketDropReason::ALL.iter().enumerate() {
+            assert_eq!(*reason as usize, index);
+        }
+
+        let stats = PacketDropStats::new();
+        stats.record(PacketDropReason::FeeFloor, 3);
+        stats.record(PacketDropReason::FeeFloor, 2);
+        stats.record(PacketDropReason::RateLimit, 1);
+        assert_eq!(stats.count(PacketDropReason::FeeFloor), 5);
+        assert_eq!(stats.count(PacketDropReason::Capacity), 0);
+
+        let counts = stats.take();
+        assert_eq!(counts.len(), PacketDropReason::ALL.len());
+        assert!(counts.contains(&(PacketDropReason::FeeFloor, 5)));
+        assert!(counts.contains(&(PacketDropReason::RateLimit, 1)));
+        assert!(stats.counts().iter().all(|(_, count)| *count == 0));
+    }
+}
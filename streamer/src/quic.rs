@@ -18,10 +18,13 @@ use {
     },
     solana_tls_utils::{new_dummy_x509_certificate, tls_server_config_builder},
     std::{
-        net::UdpSocket,
+        collections::HashMap,
+        fmt,
+        net::{IpAddr, UdpSocket},
+        str::FromStr,
         sync::{
             atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
-            Arc, Mutex, RwLock,
+            Arc, LazyLock, Mutex, RwLock,
         },
         thread,
         time::Duration,
@@ -56,6 +59,16 @@ pub const DEFAULT_MAX_STREAMS_PER_MS: u64 = 250;
 /// per IP address. Might be adjusted later.
 pub const DEFAULT_MAX_CONNECTIONS_PER_IPADDR_PER_MINUTE: u64 = 8;
 
+/// The concurrent connections from a particular IP address, counting the
+/// handshakes in progress and the connections of unstaked peers. Leaves room
+/// for DEFAULT_MAX_QUIC_CONNECTIONS_PER_PEER unstaked connections and as many
+/// handshakes.
+pub const DEFAULT_MAX_CONNECTIONS_PER_IPADDR: usize = 16;
+
+/// Total new connections per second, i.e. handshakes started. Heuristically
+/// taken from the default staked and unstaked connection limits.
+pub const DEFAULT_MAX_CONNECTIONS_PER_SECOND: u64 = 2500;
+
 // This will be adjusted and parameterized in follow-on PRs.
 pub const DEFAULT_QUIC_ENDPOINTS: usize = 1;
 // inlined to avoid solana-sdk dep
@@ -208,6 +221,9 @@ pub struct StreamerStats {
     // All connections in various states such as Incoming, Connecting, Connection
     pub(crate) open_connections: AtomicUsize,
     pub(crate) refused_connections_too_many_open_connections: AtomicUsize,
+    // Connections refused because the IP address has too many concurrent
+    // connections already.
+    pub(crate) refused_connections_too_many_per_ipaddr: AtomicUsize,
     pub(crate) outstanding_incoming_connection_attempts: AtomicUsize,
     pub(crate) total_incoming_connection_attempts: AtomicUsize,
     pub(crate) quic_endpoints_count: AtomicUsize,
@@ -562,10 +578,61 @@ impl StreamerStats {
                     .swap(0, Ordering::Relaxed),
                 i64
             ),
+            (
+                "refused_connections_too_many_per_ipaddr",
+                self.refused_connections_too_many_per_ipaddr
+                    .swap(0, Ordering::Relaxed),
+                i64
+            ),
         );
     }
 }
 
+/// The state of the connection tables of a QUIC server
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionTableSummary {
+    pub staked_peers: usize,
+    pub staked_connections: usize,
+    pub unstaked_peers: usize,
+    pub unstaked_connections: usize,
+    pub handshakes_in_progress: usize,
+    /// The IP addresses with the most connections counted against
+    /// `max_connections_per_ipaddr`, in decreasing order
+    pub top_ipaddrs: Vec<(IpAddr, usize)>,
+    /// Connections evicted since the server started
+    pub evictions: u64,
+    /// Connections refused by the rate limits since the server started
+    pub rate_limited: u64,
+    /// Connections refused by `max_connections_per_ipaddr` since the server
+    /// started
+    pub refused_per_ipaddr: u64,
+}
+
+static CONNECTION_TABLE_SUMMARIES: LazyLock<Mutex<HashMap<&'static str, ConnectionTableSummary>>> =
+    LazyLock::new(Mutex::default);
+
+/// Publishes the summary of the connection tables of the server reporting
+/// metrics as `name`
+pub(crate) fn update_connection_table_summary(name: &'static str, summary: ConnectionTableSummary) {
+    CONNECTION_TABLE_SUMMARIES
+        .lock()
+        .unwrap()
+        .insert(name, summary);
+}
+
+/// Returns the last summary of the connection tables of every QUIC server of
+/// the process, by metrics name, refreshed as the servers report metrics
+pub fn connection_table_summaries() -> Vec<(&'static str, ConnectionTableSummary)> {
+    let mut summaries: Vec<_> = CONNECTION_TABLE_SUMMARIES
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(name, summary)| (*name, summary.clone()))
+        .collect();
+    summaries.sort_unstable_by_key(|(name, _)| *name);
+    summaries
+}
+
 pub fn spawn_server(
     thread_name: &'static str,
     metrics_name: &'static str,
@@ -588,6 +655,47 @@ pub fn spawn_server(
     )
 }
 
+/// How to make room in a full staked connection table for the connection of
+/// a staked peer. Only peers with less stake than the new peer are evicted;
+/// if there are none, the connection goes to the unstaked connection table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StakedEvictionPolicy {
+    /// Evicts the peer with the least stake of a random sample of peers
+    RandomSample,
+    /// Evicts the peer idle the longest, with idle times scaled by the stake of
+    /// the new peer over the stake of the peer
+    #[default]
+    StakeWeightedLru,
+}
+
+impl StakedEvictionPolicy {
+    pub const ALL: [Self; 2] = [Self::RandomSample, Self::StakeWeightedLru];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RandomSample => "random-sample",
+            Self::StakeWeightedLru => "stake-weighted-lru",
+        }
+    }
+}
+
+impl fmt::Display for StakedEvictionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for StakedEvictionPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|policy| policy.name() == s)
+            .ok_or_else(|| format!("unknown staked eviction policy: {s}"))
+    }
+}
+
 #[derive(Clone)]
 pub struct QuicServerParams {
     pub max_connections_per_peer: usize,
@@ -595,6 +703,13 @@ pub struct QuicServerParams {
     pub max_unstaked_connections: usize,
     pub max_streams_per_ms: u64,
     pub max_connections_per_ipaddr_per_min: u64,
+    /// Concurrent connections from an IP address, counting the handshakes in
+    /// progress and the connections of unstaked peers. Staked peers are exempt
+    /// once their handshake completes.
+    pub max_connections_per_ipaddr: usize,
+    /// New connections per second, across all IP addresses
+    pub max_connections_per_second: u64,
+    pub staked_eviction_policy: StakedEvictionPolicy,
     pub wait_for_chunk_timeout: Duration,
     pub coalesce: Duration,
     pub coalesce_channel_size: usize,
@@ -608,6 +723,9 @@ impl Default for QuicServerParams {
             max_unstaked_connections: DEFAULT_MAX_UNSTAKED_CONNECTIONS,
             max_streams_per_ms: DEFAULT_MAX_STREAMS_PER_MS,
             max_connections_per_ipaddr_per_min: DEFAULT_MAX_CONNECTIONS_PER_IPADDR_PER_MINUTE,
+            max_connections_per_ipaddr: DEFAULT_MAX_CONNECTIONS_PER_IPADDR,
+            max_connections_per_second: DEFAULT_MAX_CONNECTIONS_PER_SECOND,
+            staked_eviction_policy: StakedEvictionPolicy::default(),
             wait_for_chunk_timeout: DEFAULT_WAIT_FOR_CHUNK_TIMEOUT,
             coalesce: DEFAULT_TPU_COALESCE,
             coalesce_channel_size: DEFAULT_MAX_COALESCE_CHANNEL_SIZE,
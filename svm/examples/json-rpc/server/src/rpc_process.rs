@@ -661,6 +661,7 @@ pub mod rpc {
                 accounts: config_accounts,
                 min_context_slot: _,
                 inner_instructions: enable_cpi_recording,
+                account_overrides: _,
             } = config.unwrap_or_default();
             let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
             let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
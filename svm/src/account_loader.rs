@@ -24,10 +24,7 @@ use {
     solana_rent::RentDue,
     solana_rent_debits::RentDebits,
     solana_sdk::rent_collector::{CollectedInfo, RENT_EXEMPT_RENT_EPOCH},
-    solana_sdk_ids::{
-        native_loader,
-        sysvar::{self, slot_history},
-    },
+    solana_sdk_ids::{native_loader, sysvar},
     solana_svm_rent_collector::svm_rent_collector::SVMRentCollector,
     solana_svm_transaction::svm_message::SVMMessage,
     solana_transaction_context::{IndexOfAccount, TransactionAccount},
@@ -80,6 +77,8 @@ pub(crate) struct ValidatedTransactionDetails {
     pub(crate) compute_budget_limits: ComputeBudgetLimits,
     pub(crate) fee_details: FeeDetails,
     pub(crate) loaded_fee_payer_account: LoadedTransactionAccount,
+    /// Index and fee-subtracted account of the co-payer splitting the fee, if any
+    pub(crate) loaded_co_payer_account: Option<(usize, AccountSharedData)>,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -133,12 +132,14 @@ impl<'a, CB: TransactionProcessingCallback> AccountLoader<'a, CB> {
     ) -> AccountLoader<'a, CB> {
         let mut account_cache = AHashMap::with_capacity(capacity);
 
-        // SlotHistory may be overridden for simulation.
-        // No other uses of AccountOverrides are expected.
-        if let Some(slot_history) =
-            account_overrides.and_then(|overrides| overrides.get(&slot_history::id()))
-        {
-            account_cache.insert(slot_history::id(), slot_history.clone());
+        // Accounts, such as SlotHistory, may be overridden for simulation.
+        // Seeding the cache with them makes them take precedence over accounts-db.
+        if let Some(account_overrides) = account_overrides {
+            account_cache.extend(
+                account_overrides
+                    .iter()
+                    .map(|(pubkey, account)| (*pubkey, account.clone())),
+            );
         }
 
         Self {
@@ -229,6 +230,16 @@ impl<'a, CB: TransactionProcessingCallback> AccountLoader<'a, CB> {
                 self.account_cache
                     .insert(*fee_payer_address, fee_payer_account.clone());
             }
+            RollbackAccounts::FeePayerAndCoPayer {
+                fee_payer_account,
+                co_payer_address,
+                co_payer_account,
+            } => {
+                self.account_cache
+                    .insert(*fee_payer_address, fee_payer_account.clone());
+                self.account_cache
+                    .insert(*co_payer_address, co_payer_account.clone());
+            }
         }
     }
 
@@ -257,14 +268,19 @@ impl<'a, CB: TransactionProcessingCallback> AccountLoader<'a, CB> {
 
 /// Collect rent from an account if rent is still enabled and regardless of
 /// whether rent is enabled, set the rent epoch to u64::MAX if the account is
-/// rent exempt.
+/// rent exempt. Once rent scanning is disabled, the account is left untouched.
 pub fn collect_rent_from_account(
     feature_set: &FeatureSet,
     rent_collector: &dyn SVMRentCollector,
     address: &Pubkey,
     account: &mut AccountSharedData,
 ) -> CollectedInfo {
-    if !feature_set.is_active(&feature_set::disable_rent_fees_collection::id()) {
+    if feature_set.is_active(&feature_set::disable_rent_scanning::id()) {
+        // Rent is neither collected nor tracked anymore, so there is no need to compute the rent
+        // due by every loaded account. Rent exemption of new and resized accounts is enforced by
+        // the rent state transition checks.
+        CollectedInfo::default()
+    } else if !feature_set.is_active(&feature_set::disable_rent_fees_collection::id()) {
         rent_collector.collect_rent(address, account)
     } else {
         // When rent fee collection is disabled, we won't collect rent for any account. If there
@@ -285,6 +301,26 @@ pub fn collect_rent_from_account(
     }
 }
 
+/// Returns the balance the payer_account must keep after paying the fee, or
+/// None if it can't pay fees.
+pub(crate) fn get_fee_payer_min_balance(
+    payer_account: &AccountSharedData,
+    rent_collector: &dyn SVMRentCollector,
+) -> Option<u64> {
+    match get_system_account_kind(payer_account)? {
+        SystemAccountKind::System => Some(0),
+        SystemAccountKind::Nonce => {
+            // Should we ever allow a fees charge to zero a nonce account's
+            // balance. The state MUST be set to uninitialized in that case
+            Some(
+                rent_collector
+                    .get_rent()
+                    .minimum_balance(NonceState::size()),
+            )
+        }
+    }
+}
+
 /// Check whether the payer_account is capable of paying the fee. The
 /// side effect is to subtract the fee amount from the payer_account
 /// balance of lamports. If the payer_acount is not able to pay the
@@ -302,20 +338,11 @@ pub fn validate_fee_payer(
         error_metrics.account_not_found += 1;
         return Err(TransactionError::AccountNotFound);
     }
-    let system_account_kind = get_system_account_kind(payer_account).ok_or_else(|| {
-        error_metrics.invalid_account_for_fee += 1;
-        TransactionError::InvalidAccountForFee
-    })?;
-    let min_balance = match system_account_kind {
-        SystemAccountKind::System => 0,
-        SystemAccountKind::Nonce => {
-            // Should we ever allow a fees charge to zero a nonce account's
-            // balance. The state MUST be set to uninitialized in that case
-            rent_collector
-                .get_rent()
-                .minimum_balance(NonceState::size())
-        }
-    };
+    let min_balance =
+        get_fee_payer_min_balance(payer_account, rent_collector).ok_or_else(|| {
+            error_metrics.invalid_account_for_fee += 1;
+            TransactionError::InvalidAccountForFee
+        })?;
 
     payer_account
         .lamports()
@@ -361,16 +388,25 @@ pub(crate) fn load_transaction<CB: TransactionProcessingCallback>(
             );
 
             match load_result {
-                Ok(loaded_tx_accounts) => TransactionLoadResult::Loaded(LoadedTransaction {
-                    accounts: loaded_tx_accounts.accounts,
-                    program_indices: loaded_tx_accounts.program_indices,
-                    fee_details: tx_details.fee_details,
-                    rent: loaded_tx_accounts.rent,
-                    rent_debits: loaded_tx_accounts.rent_debits,
-                    rollback_accounts: tx_details.rollback_accounts,
-                    compute_budget_limits: tx_details.compute_budget_limits,
-                    loaded_accounts_data_size: loaded_tx_accounts.loaded_accounts_data_size,
-                }),
+                Ok(mut loaded_tx_accounts) => {
+                    // The co-payer was loaded again with the other accounts, replace it with the
+                    // account its share of the fee was subtracted from during validation.
+                    if let Some((co_payer_index, co_payer_account)) =
+                        tx_details.loaded_co_payer_account
+                    {
+                        loaded_tx_accounts.accounts[co_payer_index].1 = co_payer_account;
+                    }
+                    TransactionLoadResult::Loaded(LoadedTransaction {
+                        accounts: loaded_tx_accounts.accounts,
+                        program_indices: loaded_tx_accounts.program_indices,
+                        fee_details: tx_details.fee_details,
+                        rent: loaded_tx_accounts.rent,
+                        rent_debits: loaded_tx_accounts.rent_debits,
+                        rollback_accounts: tx_details.rollback_accounts,
+                        compute_budget_limits: tx_details.compute_budget_limits,
+                        loaded_accounts_data_size: loaded_tx_accounts.loaded_accounts_data_size,
+                    })
+                }
                 Err(err) => TransactionLoadResult::FeesOnly(FeesOnlyTransaction {
                     load_error: err,
                     fee_details: tx_details.fee_details,
@@ -727,6 +763,7 @@ mod tests {
         feature_set: &mut FeatureSet,
     ) -> TransactionLoadResult {
         feature_set.deactivate(&feature_set::disable_rent_fees_collection::id());
+        feature_set.deactivate(&feature_set::disable_rent_scanning::id());
         let sanitized_tx = SanitizedTransaction::from_transaction_for_tests(tx);
         let fee_payer_account = accounts[0].1.clone();
         let mut accounts_map = HashMap::new();
@@ -2055,7 +2092,7 @@ mod tests {
 
     #[test]
     fn test_collect_rent_from_account() {
-        let feature_set = FeatureSet::all_enabled();
+        let feature_set = all_features_except(Some(&[feature_set::disable_rent_scanning::id()]));
         let rent_collector = RentCollector {
             epoch: 1,
             ..RentCollector::default()
@@ -2097,10 +2134,37 @@ mod tests {
         assert_eq!(account.lamports(), 1);
     }
 
+    #[test]
+    fn test_collect_rent_from_account_rent_scanning_disabled() {
+        let feature_set = FeatureSet::all_enabled();
+        let rent_collector = RentCollector {
+            epoch: 1,
+            ..RentCollector::default()
+        };
+
+        // Neither the rent epoch of rent exempt accounts nor rent paying accounts are updated
+        let address = Pubkey::new_unique();
+        let min_exempt_balance = rent_collector.rent.minimum_balance(0);
+        for lamports in [min_exempt_balance, 1] {
+            let mut account = AccountSharedData::from(Account {
+                lamports,
+                ..Account::default()
+            });
+            assert_eq!(
+                collect_rent_from_account(&feature_set, &rent_collector, &address, &mut account),
+                CollectedInfo::default()
+            );
+            assert_eq!(account.rent_epoch(), 0);
+            assert_eq!(account.lamports(), lamports);
+        }
+    }
+
     #[test]
     fn test_collect_rent_from_account_rent_enabled() {
-        let feature_set =
-            all_features_except(Some(&[feature_set::disable_rent_fees_collection::id()]));
+        let feature_set = all_features_except(Some(&[
+            feature_set::disable_rent_fees_collection::id(),
+            feature_set::disable_rent_scanning::id(),
+        ]));
         let rent_collector = RentCollector {
             epoch: 1,
             ..RentCollector::default()
@@ -5,15 +5,18 @@ use {
 
 /// Encapsulates overridden accounts, typically used for transaction
 /// simulations. Account overrides are currently not used when loading the
-/// durable nonce account or when constructing the instructions sysvar account.
-#[derive(Default)]
+/// durable nonce account or when constructing the instructions sysvar account,
+/// nor when loading programs into the program cache.
+#[derive(Debug, Default)]
 pub struct AccountOverrides {
     accounts: HashMap<Pubkey, AccountSharedData>,
 }
 
 impl AccountOverrides {
     /// Insert or remove an account with a given pubkey to/from the list of overrides.
-    fn set_account(&mut self, pubkey: &Pubkey, account: Option<AccountSharedData>) {
+    ///
+    /// An account with zero lamports is loaded as if it didn't exist.
+    pub fn set_account(&mut self, pubkey: &Pubkey, account: Option<AccountSharedData>) {
         match account {
             Some(account) => self.accounts.insert(*pubkey, account),
             None => self.accounts.remove(pubkey),
@@ -31,6 +34,18 @@ impl AccountOverrides {
     pub(crate) fn get(&self, pubkey: &Pubkey) -> Option<&AccountSharedData> {
         self.accounts.get(pubkey)
     }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Pubkey, &AccountSharedData)> {
+        self.accounts.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -47,9 +62,12 @@ mod test {
         let key = Pubkey::new_unique();
         accounts.set_account(&key, Some(data.clone()));
         assert_eq!(accounts.get(&key), Some(&data));
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts.iter().collect::<Vec<_>>(), vec![(&key, &data)]);
 
         accounts.set_account(&key, None);
         assert!(accounts.get(&key).is_none());
+        assert!(accounts.is_empty());
     }
 
     #[test]
@@ -0,0 +1,365 @@
+//! Distributions of the cross-program invocations made by the instructions of each program.
+//!
+//! The stats are aggregated from the instruction trace of every executed transaction, under the
+//! program of the top-level instruction, and are shared by the transaction processors of all
+//! banks descending from the same root. Instructions aborted by exceeding the max instruction
+//! stack depth are counted separately, as they show how much demand a deeper stack would meet.
+//!
+//! Collection is off until enabled. Each thread accumulates into one of a fixed set of shards,
+//! so that executing threads rarely contend on a lock, and every shard tracks a bounded number
+//! of programs, counting the instructions of the programs beyond it together.
+
+use {
+    serde_derive::{Deserialize, Serialize},
+    solana_compute_budget::compute_budget::X1_MAX_INSTRUCTION_STACK_DEPTH,
+    solana_instruction::{error::InstructionError, TRANSACTION_LEVEL_STACK_HEIGHT},
+    solana_pubkey::Pubkey,
+    solana_transaction_context::TransactionContext,
+    solana_transaction_error::TransactionError,
+    std::{
+        cell::Cell,
+        collections::{hash_map::Entry, HashMap},
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Mutex,
+        },
+    },
+};
+
+/// Number of shards the stats are accumulated into
+const NUM_SHARDS: usize = 16;
+
+/// Max number of programs tracked by each shard
+pub const MAX_PROGRAMS_PER_SHARD: usize = 1_024;
+
+/// Buckets of the max stack height reached by instructions, from 1 (no CPI) to
+/// `X1_MAX_INSTRUCTION_STACK_DEPTH`
+pub const CPI_DEPTH_BUCKETS: usize = X1_MAX_INSTRUCTION_STACK_DEPTH;
+
+/// Power of two buckets of the number of CPIs made by instructions: 0, 1, 2-3, 4-7, 8-15, 16-31
+/// and 32 or more
+pub const CPI_COUNT_BUCKETS: usize = 7;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramCpiStats {
+    /// Top-level instructions executed
+    pub instructions: u64,
+    /// CPIs made by the instructions, at any depth
+    pub cpis: u64,
+    /// Instructions by the max stack height they reached, the first bucket being instructions
+    /// without CPIs
+    pub max_depth: [u64; CPI_DEPTH_BUCKETS],
+    /// Instructions by the number of CPIs they made, see `CPI_COUNT_BUCKETS`
+    pub cpi_count: [u64; CPI_COUNT_BUCKETS],
+    /// Instructions that failed by exceeding the max instruction stack depth
+    pub depth_exceeded: u64,
+}
+
+impl ProgramCpiStats {
+    fn record(&mut self, max_stack_height: usize, cpis: usize, depth_exceeded: bool) {
+        self.instructions = self.instructions.saturating_add(1);
+        self.cpis = self.cpis.saturating_add(cpis as u64);
+        let depth_bucket = max_stack_height.clamp(1, CPI_DEPTH_BUCKETS) - 1;
+        self.max_depth[depth_bucket] = self.max_depth[depth_bucket].saturating_add(1);
+        let count_bucket = match cpis {
+            0 => 0,
+            cpis => (cpis.ilog2() as usize + 1).min(CPI_COUNT_BUCKETS - 1),
+        };
+        self.cpi_count[count_bucket] = self.cpi_count[count_bucket].saturating_add(1);
+        if depth_exceeded {
+            self.depth_exceeded = self.depth_exceeded.saturating_add(1);
+        }
+    }
+
+    pub fn accumulate(&mut self, other: &Self) {
+        self.instructions = self.instructions.saturating_add(other.instructions);
+        self.cpis = self.cpis.saturating_add(other.cpis);
+        for (count, other) in self.max_depth.iter_mut().zip(other.max_depth) {
+            *count = count.saturating_add(other);
+        }
+        for (count, other) in self.cpi_count.iter_mut().zip(other.cpi_count) {
+            *count = count.saturating_add(other);
+        }
+        self.depth_exceeded = self.depth_exceeded.saturating_add(other.depth_exceeded);
+    }
+}
+
+#[cfg(feature = "frozen-abi")]
+impl ::solana_frozen_abi::abi_example::AbiExample for CpiStats {
+    fn example() -> Self {
+        // CpiStats is not Serialize so just rely on Default.
+        CpiStats::default()
+    }
+}
+
+/// Stats of the programs recorded so far
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CpiStatsSnapshot {
+    pub programs: HashMap<Pubkey, ProgramCpiStats>,
+    /// Combined stats of the programs beyond `MAX_PROGRAMS_PER_SHARD`
+    pub other: ProgramCpiStats,
+}
+
+impl CpiStatsSnapshot {
+    /// Returns the stats of all programs combined
+    pub fn total(&self) -> ProgramCpiStats {
+        self.programs
+            .values()
+            .fold(self.other.clone(), |mut total, stats| {
+                total.accumulate(stats);
+                total
+            })
+    }
+
+    fn accumulate(&mut self, other: Self) {
+        for (program_id, stats) in other.programs {
+            self.programs
+                .entry(program_id)
+                .or_default()
+                .accumulate(&stats);
+        }
+        self.other.accumulate(&other.other);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CpiStats {
+    enabled: AtomicBool,
+    shards: [Mutex<CpiStatsSnapshot>; NUM_SHARDS],
+}
+
+impl CpiStats {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Starts or stops recording transactions, keeping the stats recorded so far
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns the shard of the calling thread
+    fn shard(&self) -> &Mutex<CpiStatsSnapshot> {
+        static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+        thread_local! {
+            static SHARD: Cell<usize> =
+                Cell::new(NEXT_SHARD.fetch_add(1, Ordering::Relaxed) % NUM_SHARDS);
+        }
+        &self.shards[SHARD.with(Cell::get)]
+    }
+
+    /// Records the CPIs of every top-level instruction in the trace of an executed transaction,
+    /// whose execution ended with `result`, if enabled
+    pub fn record_transaction(
+        &self,
+        transaction_context: &TransactionContext,
+        result: &Result<(), TransactionError>,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+        let failed_instruction = match result {
+            Err(TransactionError::InstructionError(index, InstructionError::CallDepth)) => {
+                Some(usize::from(*index))
+            }
+            _ => None,
+        };
+
+        // (program id, max stack height, CPIs) of each top-level instruction
+        let mut instructions: Vec<(Pubkey, usize, usize)> = Vec::new();
+        for index_in_trace in 0..transaction_context.get_instruction_trace_length() {
+            let Ok(instruction_context) =
+                transaction_context.get_instruction_context_at_index_in_trace(index_in_trace)
+            else {
+                continue;
+            };
+            let stack_height = instruction_context.get_stack_height();
+            if stack_height == TRANSACTION_LEVEL_STACK_HEIGHT {
+                let program_id = instruction_context
+                    .get_last_program_key(transaction_context)
+                    .copied()
+                    .unwrap_or_default();
+                instructions.push((program_id, stack_height, 0));
+            } else if let Some((_, max_stack_height, cpis)) = instructions.last_mut() {
+                *max_stack_height = (*max_stack_height).max(stack_height);
+                *cpis += 1;
+            }
+        }
+        if instructions.is_empty() {
+            return;
+        }
+
+        let mut shard = self.shard().lock().unwrap();
+        let CpiStatsSnapshot { programs, other } = &mut *shard;
+        for (index, (program_id, max_stack_height, cpis)) in instructions.into_iter().enumerate() {
+            let num_programs = programs.len();
+            let stats = match programs.entry(program_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(_) if num_programs >= MAX_PROGRAMS_PER_SHARD => &mut *other,
+                Entry::Vacant(entry) => entry.insert(ProgramCpiStats::default()),
+            };
+            stats.record(max_stack_height, cpis, failed_instruction == Some(index));
+        }
+    }
+
+    /// Returns the stats recorded so far
+    pub fn snapshot(&self) -> CpiStatsSnapshot {
+        self.shards
+            .iter()
+            .fold(CpiStatsSnapshot::default(), |mut snapshot, shard| {
+                snapshot.accumulate(shard.lock().unwrap().clone());
+                snapshot
+            })
+    }
+
+    /// Returns the stats recorded so far and starts over
+    pub fn take(&self) -> CpiStatsSnapshot {
+        self.shards
+            .iter()
+            .fold(CpiStatsSnapshot::default(), |mut snapshot, shard| {
+                snapshot.accumulate(std::mem::take(&mut shard.lock().unwrap()));
+                snapshot
+            })
+    }
+
+    pub fn clear(&self) {
+        for shard in &self.shards {
+            *shard.lock().unwrap() = CpiStatsSnapshot::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, solana_account::AccountSharedData, solana_rent::Rent,
+        solana_transaction_context::InstructionAccount,
+    };
+
+    /// Builds the trace of top-level instructions invoking programs up to the given stack heights
+    fn transaction_context(programs: &[Pubkey], stack_heights: &[&[usize]]) -> TransactionContext {
+        let accounts = programs
+            .iter()
+            .map(|program_id| (*program_id, AccountSharedData::default()))
+            .collect();
+        let mut transaction_context = TransactionContext::new(
+            accounts,
+            Rent::default(),
+            X1_MAX_INSTRUCTION_STACK_DEPTH,
+            64,
+        );
+        for (program_index, stack_heights) in stack_heights.iter().enumerate() {
+            let configure_and_push = |transaction_context: &mut TransactionContext| {
+                transaction_context
+                    .get_next_instruction_context()
+                    .unwrap()
+                    .configure(&[program_index as u16], &[] as &[InstructionAccount], &[]);
+                transaction_context.push()
+            };
+            configure_and_push(&mut transaction_context).unwrap();
+            for stack_height in stack_heights.iter() {
+                // Unwind to the caller of the invoked program, then invoke it
+                while transaction_context.get_instruction_context_stack_height() >= *stack_height {
+                    transaction_context.pop().unwrap();
+                }
+                configure_and_push(&mut transaction_context).unwrap();
+            }
+            while transaction_context.get_instruction_context_stack_height() > 0 {
+                transaction_context.pop().unwrap();
+            }
+        }
+        transaction_context
+    }
+
+    #[test]
+    fn test_record_transaction() {
+        let program_a = Pubkey::new_unique();
+        let program_b = Pubkey::new_unique();
+        let cpi_stats = CpiStats::default();
+
+        // Nothing is recorded until enabled
+        let transaction_context = transaction_context(&[program_a], &[&[2]]);
+        cpi_stats.record_transaction(&transaction_context, &Ok(()));
+        assert_eq!(cpi_stats.snapshot(), CpiStatsSnapshot::default());
+        cpi_stats.set_enabled(true);
+
+        // Program A invokes twice at depth 2 and once at depth 3, program B makes no CPI
+        let transaction_context = transaction_context(&[program_a, program_b], &[&[2, 3, 2], &[]]);
+        cpi_stats.record_transaction(&transaction_context, &Ok(()));
+        let programs = cpi_stats.snapshot().programs;
+        let stats_a = &programs[&program_a];
+        assert_eq!(stats_a.instructions, 1);
+        assert_eq!(stats_a.cpis, 3);
+        assert_eq!(stats_a.max_depth[2], 1);
+        assert_eq!(stats_a.cpi_count[2], 1);
+        assert_eq!(stats_a.depth_exceeded, 0);
+        let stats_b = &programs[&program_b];
+        assert_eq!(stats_b.max_depth[0], 1);
+        assert_eq!(stats_b.cpi_count[0], 1);
+
+        // The failing instruction is attributed the stack depth error
+        let transaction_context = transaction_context(&[program_a, program_b], &[&[2], &[]]);
+        cpi_stats.record_transaction(
+            &transaction_context,
+            &Err(TransactionError::InstructionError(
+                1,
+                InstructionError::CallDepth,
+            )),
+        );
+        let programs = cpi_stats.snapshot().programs;
+        assert_eq!(programs[&program_a].instructions, 2);
+        assert_eq!(programs[&program_a].cpi_count[1], 1);
+        assert_eq!(programs[&program_a].depth_exceeded, 0);
+        assert_eq!(programs[&program_b].depth_exceeded, 1);
+
+        let total = cpi_stats.snapshot().total();
+        assert_eq!(total.instructions, 4);
+        assert_eq!(total.cpis, 4);
+        assert_eq!(total.depth_exceeded, 1);
+
+        assert_eq!(cpi_stats.take().programs.len(), 2);
+        assert_eq!(cpi_stats.snapshot(), CpiStatsSnapshot::default());
+    }
+
+    #[test]
+    fn test_record_transaction_max_programs() {
+        let programs: Vec<_> = std::iter::repeat_with(Pubkey::new_unique)
+            .take(MAX_PROGRAMS_PER_SHARD + 2)
+            .collect();
+        let cpi_stats = CpiStats::default();
+        cpi_stats.set_enabled(true);
+        for program_id in &programs {
+            let transaction_context = transaction_context(&[*program_id], &[&[]]);
+            cpi_stats.record_transaction(&transaction_context, &Ok(()));
+        }
+        // The programs beyond the max are counted together
+        let snapshot = cpi_stats.snapshot();
+        assert_eq!(snapshot.programs.len(), MAX_PROGRAMS_PER_SHARD);
+        assert!(!snapshot
+            .programs
+            .contains_key(&programs[MAX_PROGRAMS_PER_SHARD]));
+        assert_eq!(snapshot.other.instructions, 2);
+        assert_eq!(snapshot.total().instructions, programs.len() as u64);
+
+        // Programs already tracked are still recorded
+        let transaction_context = transaction_context(&[programs[0]], &[&[]]);
+        cpi_stats.record_transaction(&transaction_context, &Ok(()));
+        let snapshot = cpi_stats.snapshot();
+        assert_eq!(snapshot.programs[&programs[0]].instructions, 2);
+        assert_eq!(snapshot.other.instructions, 2);
+    }
+
+    #[test]
+    fn test_cpi_count_buckets() {
+        let mut stats = ProgramCpiStats::default();
+        for cpis in [0, 1, 2, 3, 4, 31, 32, 63] {
+            stats.record(1, cpis, false);
+        }
+        assert_eq!(stats.cpi_count, [1, 1, 2, 1, 0, 1, 2]);
+        // Stack heights beyond the buckets are counted in the last one
+        stats.record(X1_MAX_INSTRUCTION_STACK_DEPTH + 1, 0, true);
+        assert_eq!(stats.max_depth[CPI_DEPTH_BUCKETS - 1], 1);
+        assert_eq!(stats.depth_exceeded, 1);
+    }
+}
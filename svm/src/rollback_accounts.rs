@@ -19,6 +19,12 @@ pub enum RollbackAccounts {
         nonce: NonceInfo,
         fee_payer_account: AccountSharedData,
     },
+    /// The fee is split between the fee payer and a co-payer, which nonce transactions cannot do
+    FeePayerAndCoPayer {
+        fee_payer_account: AccountSharedData,
+        co_payer_address: Pubkey,
+        co_payer_account: AccountSharedData,
+    },
 }
 
 #[cfg(feature = "dev-context-only-utils")]
@@ -76,11 +82,37 @@ impl RollbackAccounts {
         }
     }
 
+    /// Adds the co-payer of the fee to the accounts of a transaction without nonce, the
+    /// rent debited from it being credited back as for the fee payer
+    pub(crate) fn with_co_payer(
+        self,
+        co_payer_address: Pubkey,
+        mut co_payer_account: AccountSharedData,
+        co_payer_rent_debit: u64,
+        co_payer_loaded_rent_epoch: Epoch,
+    ) -> Self {
+        let Self::FeePayerOnly { fee_payer_account } = self else {
+            debug_assert!(false, "nonce transactions cannot split their fee");
+            return self;
+        };
+        co_payer_account.set_lamports(
+            co_payer_account
+                .lamports()
+                .saturating_add(co_payer_rent_debit),
+        );
+        co_payer_account.set_rent_epoch(co_payer_loaded_rent_epoch);
+        Self::FeePayerAndCoPayer {
+            fee_payer_account,
+            co_payer_address,
+            co_payer_account,
+        }
+    }
+
     /// Number of accounts tracked for rollback
     pub fn count(&self) -> usize {
         match self {
             Self::FeePayerOnly { .. } | Self::SameNonceAndFeePayer { .. } => 1,
-            Self::SeparateNonceAndFeePayer { .. } => 2,
+            Self::SeparateNonceAndFeePayer { .. } | Self::FeePayerAndCoPayer { .. } => 2,
         }
     }
 
@@ -97,6 +129,14 @@ impl RollbackAccounts {
                 .data()
                 .len()
                 .saturating_add(nonce.account().data().len()),
+            Self::FeePayerAndCoPayer {
+                fee_payer_account,
+                co_payer_account,
+                ..
+            } => fee_payer_account
+                .data()
+                .len()
+                .saturating_add(co_payer_account.data().len()),
         }
     }
 }
@@ -234,4 +274,44 @@ mod tests {
             _ => panic!("Expected SeparateNonceAndFeePayer variant"),
         }
     }
+
+    #[test]
+    fn test_fee_payer_and_co_payer() {
+        let fee_payer_address = Pubkey::new_unique();
+        let fee_payer_account = AccountSharedData::new(100, 0, &Pubkey::default());
+        let co_payer_address = Pubkey::new_unique();
+        let co_payer_account = AccountSharedData::new(200, 0, &Pubkey::default());
+        let co_payer_rent_epoch = co_payer_account.rent_epoch();
+
+        const TEST_RENT_DEBIT: u64 = 1;
+        let rent_collected_co_payer_account = {
+            let mut account = co_payer_account.clone();
+            account.set_lamports(co_payer_account.lamports() - TEST_RENT_DEBIT);
+            account.set_rent_epoch(co_payer_rent_epoch + 1);
+            account
+        };
+
+        let rollback_accounts = RollbackAccounts::new(
+            None,
+            fee_payer_address,
+            fee_payer_account.clone(),
+            0,
+            fee_payer_account.rent_epoch(),
+        )
+        .with_co_payer(
+            co_payer_address,
+            rent_collected_co_payer_account,
+            TEST_RENT_DEBIT,
+            co_payer_rent_epoch,
+        );
+        assert_eq!(rollback_accounts.count(), 2);
+        assert_eq!(
+            rollback_accounts,
+            RollbackAccounts::FeePayerAndCoPayer {
+                fee_payer_account,
+                co_payer_address,
+                co_payer_account,
+            }
+        );
+    }
 }
@@ -3,6 +3,7 @@ use {
     solana_fee_structure::FeeDetails, solana_rent_debits::RentDebits,
     solana_sdk::inner_instruction::InnerInstructionsList,
     solana_transaction_context::TransactionReturnData, solana_transaction_error::TransactionResult,
+    tachyon_fee_breakdown::FeeBreakdown,
 };
 
 pub type TransactionCommitResult = TransactionResult<CommittedTransaction>;
@@ -16,6 +17,8 @@ pub struct CommittedTransaction {
     pub return_data: Option<TransactionReturnData>,
     pub executed_units: u64,
     pub fee_details: FeeDetails,
+    /// The components of `fee_details`
+    pub fee_breakdown: FeeBreakdown,
     pub rent_debits: RentDebits,
     pub loaded_account_stats: TransactionLoadedAccountsStats,
 }
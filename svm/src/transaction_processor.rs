@@ -3,11 +3,13 @@ use qualifier_attr::{field_qualifiers, qualifiers};
 use {
     crate::{
         account_loader::{
-            collect_rent_from_account, load_transaction, validate_fee_payer, AccountLoader,
-            CheckedTransactionDetails, LoadedTransaction, TransactionCheckResult,
-            TransactionLoadResult, ValidatedTransactionDetails,
+            collect_rent_from_account, get_fee_payer_min_balance, load_transaction,
+            validate_fee_payer, AccountLoader, CheckedTransactionDetails, LoadedTransaction,
+            LoadedTransactionAccount, TransactionCheckResult, TransactionLoadResult,
+            ValidatedTransactionDetails,
         },
         account_overrides::AccountOverrides,
+        cpi_stats::CpiStats,
         message_processor::process_message,
         nonce_info::NonceInfo,
         program_loader::{get_program_modification_slot, load_program_with_pubkey},
@@ -19,7 +21,8 @@ use {
         transaction_processing_result::{ProcessedTransaction, TransactionProcessingResult},
     },
     agave_feature_set::{
-        enable_transaction_loading_failure_fees, remove_accounts_executable_flag_checks, FeatureSet,
+        distinct_fee_errors, enable_transaction_loading_failure_fees,
+        raise_max_instruction_stack_depth, remove_accounts_executable_flag_checks, FeatureSet,
     },
     log::debug,
     percentage::Percentage,
@@ -28,8 +31,11 @@ use {
         create_program_runtime_environment_v1, create_program_runtime_environment_v2,
     },
     solana_clock::{Epoch, Slot},
-    solana_compute_budget::compute_budget::ComputeBudget,
-    solana_compute_budget_instruction::instructions_processor::process_compute_budget_instructions,
+    solana_compute_budget::compute_budget::{ComputeBudget, X1_MAX_INSTRUCTION_STACK_DEPTH},
+    solana_compute_budget_instruction::{
+        fee_payer_split::{get_fee_payer_split, FeePayerSplit},
+        instructions_processor::process_compute_budget_instructions,
+    },
     solana_fee_structure::{FeeBudgetLimits, FeeDetails, FeeStructure},
     solana_hash::Hash,
     solana_instruction::TRANSACTION_LEVEL_STACK_HEIGHT,
@@ -58,7 +64,7 @@ use {
     solana_svm_rent_collector::svm_rent_collector::SVMRentCollector,
     solana_svm_transaction::{svm_message::SVMMessage, svm_transaction::SVMTransaction},
     solana_timings::{ExecuteTimingType, ExecuteTimings},
-    solana_transaction_context::{ExecutionRecord, TransactionContext},
+    solana_transaction_context::{ExecutionRecord, IndexOfAccount, TransactionContext},
     solana_transaction_error::{TransactionError, TransactionResult},
     solana_type_overrides::sync::{atomic::Ordering, Arc, RwLock, RwLockReadGuard},
     std::{
@@ -159,6 +165,76 @@ impl Default for TransactionProcessingEnvironment<'_> {
     }
 }
 
+/// The share of the fee a fee payer or co-payer pays, along with its share of the parts of the
+/// fee, which tell what it couldn't pay when it can't pay its share.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct FeeShare {
+    /// Share of the fee
+    fee: u64,
+    /// Share of the fee every transaction is charged, before any prioritization fee
+    transaction_fee: u64,
+    /// Share of the fee at the compute unit price the transaction requested
+    requested_fee: u64,
+}
+
+impl FeeShare {
+    /// Splits the fee between the fee payer and the co-payer of `fee_payer_split`, if any.
+    /// `requested_prioritization_fee` is the prioritization fee at the compute unit price the
+    /// transaction requested, which the fee may have raised to the minimum price.
+    fn split(
+        fee_details: &FeeDetails,
+        requested_prioritization_fee: u64,
+        fee_payer_split: Option<&FeePayerSplit>,
+    ) -> (Self, Self) {
+        let split = |fee| fee_payer_split.map_or((fee, 0), |split| split.split(fee));
+        let (fee_payer_fee, co_payer_fee) = split(fee_details.total_fee());
+        let (fee_payer_transaction_fee, co_payer_transaction_fee) =
+            split(fee_details.transaction_fee());
+        let (fee_payer_requested_fee, co_payer_requested_fee) = split(
+            fee_details
+                .transaction_fee()
+                .saturating_add(requested_prioritization_fee),
+        );
+        (
+            Self {
+                fee: fee_payer_fee,
+                transaction_fee: fee_payer_transaction_fee,
+                requested_fee: fee_payer_requested_fee,
+            },
+            Self {
+                fee: co_payer_fee,
+                transaction_fee: co_payer_transaction_fee,
+                requested_fee: co_payer_requested_fee,
+            },
+        )
+    }
+
+    /// With `distinct_fee_errors`, replaces the `InsufficientFundsForFee` error of `payer_account`
+    /// by the error of the part of the fee it can't pay.
+    fn refine_error(
+        &self,
+        err: TransactionError,
+        distinct_fee_errors: bool,
+        payer_account: &AccountSharedData,
+        rent_collector: &dyn SVMRentCollector,
+    ) -> TransactionError {
+        if !distinct_fee_errors || err != TransactionError::InsufficientFundsForFee {
+            return err;
+        }
+        let spendable_lamports = payer_account.lamports().saturating_sub(
+            get_fee_payer_min_balance(payer_account, rent_collector).unwrap_or_default(),
+        );
+        if spendable_lamports < self.transaction_fee {
+            TransactionError::FeePayerInsufficientForBaseFee
+        } else if spendable_lamports >= self.requested_fee {
+            // The minimum compute unit price raised the fee beyond its balance
+            TransactionError::CongestionFeeRequired
+        } else {
+            err
+        }
+    }
+}
+
 #[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
 #[cfg_attr(
     feature = "dev-context-only-utils",
@@ -181,6 +257,10 @@ pub struct TransactionBatchProcessor<FG: ForkGraph> {
 
     /// Builtin program ids
     pub builtin_program_ids: RwLock<HashSet<Pubkey>>,
+
+    /// CPI distributions of the executed transactions, shared with the processors created
+    /// from this one
+    pub cpi_stats: Arc<CpiStats>,
 }
 
 impl<FG: ForkGraph> Debug for TransactionBatchProcessor<FG> {
@@ -205,6 +285,7 @@ impl<FG: ForkGraph> Default for TransactionBatchProcessor<FG> {
                 Epoch::default(),
             ))),
             builtin_program_ids: RwLock::new(HashSet::new()),
+            cpi_stats: Arc::<CpiStats>::default(),
         }
     }
 }
@@ -269,6 +350,7 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             sysvar_cache: RwLock::<SysvarCache>::default(),
             program_cache: self.program_cache.clone(),
             builtin_program_ids: RwLock::new(self.builtin_program_ids.read().unwrap().clone()),
+            cpi_stats: self.cpi_stats.clone(),
         }
     }
 
@@ -605,6 +687,22 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             )
         };
 
+        // A co-payer splitting the fee pays its share out of its own balance
+        let fee_payer_split = get_fee_payer_split(message.program_instructions_iter());
+        let (fee_payer_share, co_payer_share) = FeeShare::split(
+            &fee_details,
+            fee_budget_limits.prioritization_fee,
+            fee_payer_split.as_ref(),
+        );
+
+        let distinct_fee_errors = account_loader
+            .feature_set
+            .is_active(&distinct_fee_errors::id());
+        if distinct_fee_errors && fee_details.total_fee() == u64::MAX {
+            error_counters.insufficient_funds += 1;
+            return Err(TransactionError::FeeExceedsCap);
+        }
+
         let fee_payer_index = 0;
         validate_fee_payer(
             fee_payer_address,
@@ -612,8 +710,34 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             fee_payer_index,
             error_counters,
             rent_collector,
-            fee_details.total_fee(),
-        )?;
+            fee_payer_share.fee,
+        )
+        .map_err(|err| {
+            fee_payer_share.refine_error(
+                err,
+                distinct_fee_errors,
+                &loaded_fee_payer.account,
+                rent_collector,
+            )
+        })?;
+
+        let loaded_co_payer = fee_payer_split
+            .map(|fee_payer_split| {
+                if nonce.is_some() {
+                    error_counters.invalid_account_for_fee += 1;
+                    return Err(TransactionError::InvalidAccountForFee);
+                }
+                Self::validate_transaction_co_payer(
+                    account_loader,
+                    message,
+                    usize::from(fee_payer_split.co_payer_index),
+                    &co_payer_share,
+                    distinct_fee_errors,
+                    rent_collector,
+                    error_counters,
+                )
+            })
+            .transpose()?;
 
         // Capture fee-subtracted fee payer account and next nonce account state
         // to commit if transaction execution fails.
@@ -624,15 +748,87 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             loaded_fee_payer.rent_collected,
             fee_payer_loaded_rent_epoch,
         );
+        let (rollback_accounts, loaded_co_payer_account) = match loaded_co_payer {
+            Some((co_payer_index, loaded_co_payer, co_payer_loaded_rent_epoch)) => (
+                rollback_accounts.with_co_payer(
+                    message.account_keys()[co_payer_index],
+                    loaded_co_payer.account.clone(),
+                    loaded_co_payer.rent_collected,
+                    co_payer_loaded_rent_epoch,
+                ),
+                Some((co_payer_index, loaded_co_payer.account)),
+            ),
+            None => (rollback_accounts, None),
+        };
 
         Ok(ValidatedTransactionDetails {
             fee_details,
             rollback_accounts,
             compute_budget_limits,
             loaded_fee_payer_account: loaded_fee_payer,
+            loaded_co_payer_account,
         })
     }
 
+    // Loads the co-payer splitting the fee of a transaction, collects rent if
+    // necessary, then deducts its share of the fee from its balance. The
+    // co-payer must be a writable signer other than the fee payer.
+    fn validate_transaction_co_payer<CB: TransactionProcessingCallback>(
+        account_loader: &mut AccountLoader<CB>,
+        message: &impl SVMMessage,
+        co_payer_index: usize,
+        co_payer_share: &FeeShare,
+        distinct_fee_errors: bool,
+        rent_collector: &dyn SVMRentCollector,
+        error_counters: &mut TransactionErrorMetrics,
+    ) -> TransactionResult<(usize, LoadedTransactionAccount, Epoch)> {
+        let co_payer_address = message
+            .account_keys()
+            .get(co_payer_index)
+            .filter(|_| {
+                co_payer_index != 0
+                    && message.is_signer(co_payer_index)
+                    && message.is_writable(co_payer_index)
+            })
+            .ok_or_else(|| {
+                error_counters.invalid_account_for_fee += 1;
+                TransactionError::InvalidAccountForFee
+            })?;
+
+        let Some(mut loaded_co_payer) = account_loader.load_account(co_payer_address, true) else {
+            error_counters.account_not_found += 1;
+            return Err(TransactionError::AccountNotFound);
+        };
+
+        let co_payer_loaded_rent_epoch = loaded_co_payer.account.rent_epoch();
+        loaded_co_payer.rent_collected = collect_rent_from_account(
+            &account_loader.feature_set,
+            rent_collector,
+            co_payer_address,
+            &mut loaded_co_payer.account,
+        )
+        .rent_amount;
+
+        validate_fee_payer(
+            co_payer_address,
+            &mut loaded_co_payer.account,
+            co_payer_index as IndexOfAccount,
+            error_counters,
+            rent_collector,
+            co_payer_share.fee,
+        )
+        .map_err(|err| {
+            co_payer_share.refine_error(
+                err,
+                distinct_fee_errors,
+                &loaded_co_payer.account,
+                rent_collector,
+            )
+        })?;
+
+        Ok((co_payer_index, loaded_co_payer, co_payer_loaded_rent_epoch))
+    }
+
     fn validate_transaction_nonce<CB: TransactionProcessingCallback>(
         account_loader: &mut AccountLoader<CB>,
         message: &impl SVMMessage,
@@ -944,9 +1140,16 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         let lamports_before_tx =
             transaction_accounts_lamports_sum(&transaction_accounts).unwrap_or(0);
 
-        let compute_budget = config
-            .compute_budget
-            .unwrap_or_else(|| ComputeBudget::from(loaded_transaction.compute_budget_limits));
+        let compute_budget = config.compute_budget.unwrap_or_else(|| {
+            let mut compute_budget = ComputeBudget::from(loaded_transaction.compute_budget_limits);
+            if environment
+                .feature_set
+                .is_active(&raise_max_instruction_stack_depth::id())
+            {
+                compute_budget.max_instruction_stack_depth = X1_MAX_INSTRUCTION_STACK_DEPTH;
+            }
+            compute_budget
+        });
 
         let mut transaction_context = TransactionContext::new(
             transaction_accounts,
@@ -1008,6 +1211,8 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
 
         drop(invoke_context);
 
+        self.cpi_stats
+            .record_transaction(&transaction_context, &process_result);
         execute_timings.execute_accessories.process_message_us += process_message_time.as_us();
 
         let mut status = process_result
@@ -2213,6 +2418,7 @@ mod tests {
                 ),
                 compute_budget_limits,
                 fee_details: FeeDetails::new(transaction_fee, priority_fee),
+                loaded_co_payer_account: None,
                 loaded_fee_payer_account: LoadedTransactionAccount {
                     loaded_size: fee_payer_account.data().len(),
                     account: post_validation_fee_payer_account,
@@ -2291,6 +2497,7 @@ mod tests {
                 ),
                 compute_budget_limits,
                 fee_details: FeeDetails::new(transaction_fee, 0),
+                loaded_co_payer_account: None,
                 loaded_fee_payer_account: LoadedTransactionAccount {
                     loaded_size: fee_payer_account.data().len(),
                     account: post_validation_fee_payer_account,
@@ -2457,6 +2664,265 @@ mod tests {
         assert_eq!(result, Err(TransactionError::DuplicateInstruction(1u8)));
     }
 
+    #[test]
+    fn test_validate_transaction_fee_payer_split() {
+        let lamports_per_signature = 5000;
+        let fee_payer_address = Pubkey::new_unique();
+        let co_payer_address = Pubkey::new_unique();
+        let message = new_unchecked_sanitized_message(Message::new_with_blockhash(
+            &[
+                solana_compute_budget_instruction::fee_payer_split::set_fee_payer_split(
+                    &co_payer_address,
+                    40,
+                ),
+            ],
+            Some(&fee_payer_address),
+            &Hash::new_unique(),
+        ));
+        let feature_set = Arc::new(FeatureSet::all_enabled());
+        let rent_collector = RentCollector::default();
+        let min_balance = rent_collector.rent.minimum_balance(0);
+        // Two signatures, 40% of which are paid by the co-payer
+        let (fee_payer_fee, co_payer_fee) = (6_000, 4_000);
+
+        let validate = |co_payer_balance: Option<u64>| {
+            let mut mock_accounts = HashMap::new();
+            mock_accounts.insert(
+                fee_payer_address,
+                AccountSharedData::new(min_balance + fee_payer_fee, 0, &Pubkey::default()),
+            );
+            if let Some(co_payer_balance) = co_payer_balance {
+                mock_accounts.insert(
+                    co_payer_address,
+                    AccountSharedData::new(co_payer_balance, 0, &Pubkey::default()),
+                );
+            }
+            let mock_bank = MockBankCallback {
+                account_shared_data: Arc::new(RwLock::new(mock_accounts)),
+                ..Default::default()
+            };
+            let mut account_loader = AccountLoader::new_with_account_cache_capacity(
+                None,
+                &mock_bank,
+                feature_set.clone(),
+                0,
+            );
+            let mut error_counters = TransactionErrorMetrics::default();
+            TransactionBatchProcessor::<TestForkGraph>::validate_transaction_nonce_and_fee_payer(
+                &mut account_loader,
+                &message,
+                CheckedTransactionDetails::new(None, lamports_per_signature),
+                &Hash::default(),
+                FeeStructure::default().lamports_per_signature,
+                &rent_collector,
+                &mut error_counters,
+                &mock_bank,
+            )
+        };
+
+        let validated = validate(Some(min_balance + co_payer_fee)).unwrap();
+        assert_eq!(
+            validated.fee_details.total_fee(),
+            fee_payer_fee + co_payer_fee
+        );
+        assert_eq!(
+            validated.loaded_fee_payer_account.account.lamports(),
+            min_balance
+        );
+        let (co_payer_index, co_payer_account) = validated.loaded_co_payer_account.unwrap();
+        assert_eq!(message.account_keys()[co_payer_index], co_payer_address);
+        assert_eq!(co_payer_account.lamports(), min_balance);
+        assert_eq!(validated.rollback_accounts.count(), 2);
+
+        assert_eq!(
+            validate(Some(min_balance + co_payer_fee - 1)).unwrap_err(),
+            TransactionError::InsufficientFundsForRent { account_index: 1 }
+        );
+        assert_eq!(
+            validate(Some(co_payer_fee - 1)).unwrap_err(),
+            TransactionError::FeePayerInsufficientForBaseFee
+        );
+        assert_eq!(
+            validate(None).unwrap_err(),
+            TransactionError::AccountNotFound
+        );
+    }
+
+    #[test]
+    fn test_validate_transaction_fee_payer_distinct_fee_errors() {
+        let lamports_per_signature = 5000;
+        let fee_payer_address = Pubkey::new_unique();
+        let message = |compute_unit_price| {
+            new_unchecked_sanitized_message(Message::new(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+                    ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+                ],
+                Some(&fee_payer_address),
+            ))
+        };
+        // One signature, and 1_400_000 compute units at 1_000 micro-lamports per compute unit
+        let (transaction_fee, prioritization_fee) = (5_000, 1_400);
+
+        let validate = |feature_set: FeatureSet, compute_unit_price, balance| {
+            let mut mock_accounts = HashMap::new();
+            mock_accounts.insert(
+                fee_payer_address,
+                AccountSharedData::new(balance, 0, &Pubkey::default()),
+            );
+            let mock_bank = MockBankCallback {
+                account_shared_data: Arc::new(RwLock::new(mock_accounts)),
+                ..Default::default()
+            };
+            let mut account_loader = AccountLoader::new_with_account_cache_capacity(
+                None,
+                &mock_bank,
+                Arc::new(feature_set),
+                0,
+            );
+            let mut error_counters = TransactionErrorMetrics::default();
+            let result =
+                TransactionBatchProcessor::<TestForkGraph>::validate_transaction_nonce_and_fee_payer(
+                    &mut account_loader,
+                    &message(compute_unit_price),
+                    CheckedTransactionDetails::new(None, lamports_per_signature),
+                    &Hash::default(),
+                    FeeStructure::default().lamports_per_signature,
+                    &RentCollector::default(),
+                    &mut error_counters,
+                    &mock_bank,
+                );
+            assert_eq!(error_counters.insufficient_funds.0, 1);
+            result.unwrap_err()
+        };
+
+        assert_eq!(
+            validate(FeatureSet::default(), 1_000, transaction_fee - 1),
+            TransactionError::InsufficientFundsForFee
+        );
+        assert_eq!(
+            validate(FeatureSet::all_enabled(), 1_000, transaction_fee - 1),
+            TransactionError::FeePayerInsufficientForBaseFee
+        );
+        assert_eq!(
+            validate(
+                FeatureSet::all_enabled(),
+                1_000,
+                transaction_fee + prioritization_fee - 1
+            ),
+            TransactionError::InsufficientFundsForFee
+        );
+        assert_eq!(
+            validate(FeatureSet::default(), u64::MAX, u64::MAX / 2),
+            TransactionError::InsufficientFundsForFee
+        );
+        assert_eq!(
+            validate(FeatureSet::all_enabled(), u64::MAX, u64::MAX / 2),
+            TransactionError::FeeExceedsCap
+        );
+    }
+
+    #[test]
+    fn test_fee_share() {
+        let fee_details = FeeDetails::new(1_000, 500);
+        let fee_payer_split = FeePayerSplit {
+            co_payer_index: 1,
+            co_payer_percent: 40,
+        };
+        assert_eq!(
+            FeeShare::split(&fee_details, 100, None),
+            (
+                FeeShare {
+                    fee: 1_500,
+                    transaction_fee: 1_000,
+                    requested_fee: 1_100,
+                },
+                FeeShare::default(),
+            )
+        );
+        let (fee_payer_share, co_payer_share) =
+            FeeShare::split(&fee_details, 100, Some(&fee_payer_split));
+        assert_eq!(
+            fee_payer_share,
+            FeeShare {
+                fee: 900,
+                transaction_fee: 600,
+                requested_fee: 660,
+            }
+        );
+        assert_eq!(
+            co_payer_share,
+            FeeShare {
+                fee: 600,
+                transaction_fee: 400,
+                requested_fee: 440,
+            }
+        );
+
+        let rent_collector = RentCollector::default();
+        let refine_error = |err, distinct_fee_errors, balance| {
+            fee_payer_share.refine_error(
+                err,
+                distinct_fee_errors,
+                &AccountSharedData::new(balance, 0, &Pubkey::default()),
+                &rent_collector,
+            )
+        };
+        let insufficient_funds = TransactionError::InsufficientFundsForFee;
+        assert_eq!(
+            refine_error(insufficient_funds.clone(), false, 599),
+            TransactionError::InsufficientFundsForFee
+        );
+        assert_eq!(
+            refine_error(insufficient_funds.clone(), true, 599),
+            TransactionError::FeePayerInsufficientForBaseFee
+        );
+        assert_eq!(
+            refine_error(insufficient_funds.clone(), true, 659),
+            TransactionError::InsufficientFundsForFee
+        );
+        // Could pay its requested fee, but not the fee at the minimum compute unit price
+        assert_eq!(
+            refine_error(insufficient_funds, true, 660),
+            TransactionError::CongestionFeeRequired
+        );
+        assert_eq!(
+            refine_error(TransactionError::AccountNotFound, true, 0),
+            TransactionError::AccountNotFound
+        );
+
+        // A nonce account can only spend the lamports above its rent exempt minimum
+        let min_balance = Rent::default().minimum_balance(nonce::state::State::size());
+        let nonce_account = |balance| {
+            AccountSharedData::new_data(
+                balance,
+                &nonce::versions::Versions::new(nonce::state::State::Initialized(
+                    nonce::state::Data::new(Pubkey::new_unique(), DurableNonce::default(), 5000),
+                )),
+                &system_program::id(),
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            co_payer_share.refine_error(
+                TransactionError::InsufficientFundsForFee,
+                true,
+                &nonce_account(min_balance + 399),
+                &rent_collector,
+            ),
+            TransactionError::FeePayerInsufficientForBaseFee
+        );
+        assert_eq!(
+            co_payer_share.refine_error(
+                TransactionError::InsufficientFundsForFee,
+                true,
+                &nonce_account(min_balance + 440),
+                &rent_collector,
+            ),
+            TransactionError::CongestionFeeRequired
+        );
+    }
+
     #[test]
     fn test_validate_transaction_fee_payer_is_nonce() {
         let lamports_per_signature = 5000;
@@ -2546,6 +3012,7 @@ mod tests {
                     ),
                     compute_budget_limits,
                     fee_details: FeeDetails::new(transaction_fee, priority_fee),
+                    loaded_co_payer_account: None,
                     loaded_fee_payer_account: LoadedTransactionAccount {
                         loaded_size: fee_payer_account.data().len(),
                         account: post_validation_fee_payer_account,
@@ -0,0 +1,4 @@
+//! Instructions the X1 system program accepts on top of the upstream `SystemInstruction`s
+
+pub mod storage_deposit;
+pub mod transfer_many;
@@ -0,0 +1,56 @@
+//! Refundable storage deposits for account data.
+//!
+//! Rent is no longer collected, so rent exemption alone does little to discourage state growth.
+//! With the `storage_deposit` feature active, allocating account data through the system program
+//! additionally requires a deposit proportional to the data size. The deposit is held by the
+//! account itself, separately from its rent-exempt minimum, and is refunded along with the rest of
+//! the account's balance when the account is closed.
+//!
+//! The runtime enforces the deposit like the rent-exempt minimum: a transaction may not leave an
+//! account holding less than [`minimum_balance_with_storage_deposit`], whether by withdrawing from
+//! it or by growing its data. Accounts allocated before the feature keep their data size without
+//! a deposit, but must hold the full minimum once resized.
+//!
+//! * `CreateAccount` and `CreateAccountWithSeed` take the deposit from the funding account on top
+//!   of the requested lamports, so existing callers keep working as long as the funding account
+//!   can afford it.
+//! * `Allocate` and `AllocateWithSeed` require the account to already hold its rent-exempt
+//!   minimum plus the deposit, see [`minimum_balance_with_storage_deposit`].
+
+use solana_rent::Rent;
+
+/// Lamports deposited per byte of account data, half of the default rent-exempt cost per byte.
+pub const STORAGE_DEPOSIT_LAMPORTS_PER_BYTE: u64 = 3_480;
+
+/// The storage deposit required for `space` bytes of account data.
+pub fn storage_deposit(space: u64) -> u64 {
+    space.saturating_mul(STORAGE_DEPOSIT_LAMPORTS_PER_BYTE)
+}
+
+/// The balance an account with `space` bytes of data must hold before it can be allocated while
+/// storage deposits are enforced.
+pub fn minimum_balance_with_storage_deposit(rent: &Rent, space: u64) -> u64 {
+    rent.minimum_balance(space as usize)
+        .saturating_add(storage_deposit(space))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_deposit() {
+        assert_eq!(storage_deposit(0), 0);
+        assert_eq!(
+            storage_deposit(165),
+            165 * STORAGE_DEPOSIT_LAMPORTS_PER_BYTE
+        );
+        assert_eq!(storage_deposit(u64::MAX), u64::MAX);
+
+        let rent = Rent::default();
+        assert_eq!(
+            minimum_balance_with_storage_deposit(&rent, 165),
+            rent.minimum_balance(165) + storage_deposit(165)
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Batched transfers from one account to many recipients.
+//!
+//! With the `transfer_many` feature active, a single `TransferMany` instruction moves lamports
+//! from a funding account to every one of its recipients, sparing payout services the
+//! per-instruction overhead of individual transfers.
+//!
+//! `SystemInstruction` is defined upstream, so `TransferMany` is not one of its variants. Its
+//! data is instead prefixed by [`TRANSFER_MANY_DISCRIMINANT`], which lies far past the upstream
+//! variants, followed by the bincode encoded amounts.
+//!
+//! Accounts:
+//! 0. `[WRITE, SIGNER]` Funding account
+//! 1. `[WRITE]` One recipient account per amount, in the order of the amounts
+
+use {
+    serde_derive::{Deserialize, Serialize},
+    solana_bincode::limited_deserialize,
+    solana_instruction::{error::InstructionError, AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_sdk_ids::system_program,
+};
+
+/// Leading bytes of the data of a `TransferMany` instruction, encoded as a little endian u32 like
+/// the variant index of a `SystemInstruction`
+pub const TRANSFER_MANY_DISCRIMINANT: u32 = 0x5831_0000;
+
+/// Compute units consumed per recipient, on top of the default units of the system program
+pub const TRANSFER_MANY_COMPUTE_UNITS_PER_RECIPIENT: u64 = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferMany {
+    /// Lamports sent to each recipient
+    pub lamports: Vec<u64>,
+}
+
+impl TransferMany {
+    /// Returns if `data` is the data of a `TransferMany` instruction
+    pub fn is_transfer_many(data: &[u8]) -> bool {
+        data.get(..4) == Some(&TRANSFER_MANY_DISCRIMINANT.to_le_bytes())
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        bincode::serialize(&(TRANSFER_MANY_DISCRIMINANT, self)).unwrap()
+    }
+
+    pub fn deserialize(data: &[u8]) -> Result<Self, InstructionError> {
+        if !Self::is_transfer_many(data) {
+            return Err(InstructionError::InvalidInstructionData);
+        }
+        limited_deserialize(&data[4..], solana_packet::PACKET_DATA_SIZE as u64)
+    }
+}
+
+/// Builds a `TransferMany` instruction paying each `(recipient, lamports)` of `transfers` from
+/// `from_pubkey`
+pub fn transfer_many(from_pubkey: &Pubkey, transfers: &[(Pubkey, u64)]) -> Instruction {
+    let account_metas = std::iter::once(AccountMeta::new(*from_pubkey, true))
+        .chain(
+            transfers
+                .iter()
+                .map(|(recipient, _)| AccountMeta::new(*recipient, false)),
+        )
+        .collect();
+    let data = TransferMany {
+        lamports: transfers.iter().map(|(_, lamports)| *lamports).collect(),
+    }
+    .serialize();
+    Instruction::new_with_bytes(system_program::id(), &data, account_metas)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_system_interface::instruction::SystemInstruction};
+
+    #[test]
+    fn test_transfer_many_serialization() {
+        let from = Pubkey::new_unique();
+        let transfers = [(Pubkey::new_unique(), 1), (Pubkey::new_unique(), 2)];
+        let instruction = transfer_many(&from, &transfers);
+        assert_eq!(instruction.program_id, system_program::id());
+        assert_eq!(instruction.accounts.len(), 3);
+        assert!(instruction.accounts[0].is_signer);
+        assert_eq!(instruction.accounts[2].pubkey, transfers[1].0);
+
+        assert!(TransferMany::is_transfer_many(&instruction.data));
+        assert_eq!(
+            TransferMany::deserialize(&instruction.data),
+            Ok(TransferMany {
+                lamports: vec![1, 2]
+            })
+        );
+        // Not a system instruction, so it cannot be mistaken for one
+        assert!(bincode::deserialize::<SystemInstruction>(&instruction.data).is_err());
+
+        let transfer = bincode::serialize(&SystemInstruction::Transfer { lamports: 1 }).unwrap();
+        assert!(!TransferMany::is_transfer_many(&transfer));
+        assert_eq!(
+            TransferMany::deserialize(&transfer),
+            Err(InstructionError::InvalidInstructionData)
+        );
+    }
+}
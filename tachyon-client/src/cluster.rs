@@ -0,0 +1,126 @@
+//! The X1 clusters and their RPC endpoints.
+
+pub use solana_clap_utils::input_validators::{X1_MAINNET_RPC_URL, X1_TESTNET_RPC_URL};
+use {
+    solana_cli_config::Config,
+    solana_client::{client_error::Result as ClientResult, rpc_client::RpcClient},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient as NonblockingRpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, hash::Hash},
+    std::{fmt, str::FromStr, sync::Arc},
+    tachyon_tpu_client::{FeeAwareTpuClient, FeeAwareTpuClientConfig},
+};
+
+pub const LOCALNET_RPC_URL: &str = "http://localhost:8899";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cluster {
+    X1Mainnet,
+    X1Testnet,
+    Localnet,
+    Custom { url: String, ws_url: String },
+}
+
+impl Cluster {
+    /// A cluster served at `url`, with the websocket URL derived the way the CLI does
+    pub fn custom(url: impl Into<String>) -> Self {
+        let url = url.into();
+        let ws_url = Config::compute_websocket_url(&url);
+        Self::Custom { url, ws_url }
+    }
+
+    pub fn url(&self) -> &str {
+        match self {
+            Self::X1Mainnet => X1_MAINNET_RPC_URL,
+            Self::X1Testnet => X1_TESTNET_RPC_URL,
+            Self::Localnet => LOCALNET_RPC_URL,
+            Self::Custom { url, .. } => url,
+        }
+    }
+
+    pub fn ws_url(&self) -> String {
+        match self {
+            Self::Custom { ws_url, .. } => ws_url.clone(),
+            cluster => Config::compute_websocket_url(cluster.url()),
+        }
+    }
+
+    pub fn rpc_client(&self, commitment_config: CommitmentConfig) -> RpcClient {
+        RpcClient::new_with_commitment(self.url().to_string(), commitment_config)
+    }
+
+    pub fn nonblocking_rpc_client(
+        &self,
+        commitment_config: CommitmentConfig,
+    ) -> NonblockingRpcClient {
+        NonblockingRpcClient::new_with_commitment(self.url().to_string(), commitment_config)
+    }
+
+    /// A TPU client sending to the leaders of this cluster with recommended priority fees
+    pub async fn fee_aware_tpu_client(
+        &self,
+        commitment_config: CommitmentConfig,
+        config: FeeAwareTpuClientConfig,
+    ) -> tachyon_tpu_client::tpu_client::Result<FeeAwareTpuClient> {
+        FeeAwareTpuClient::new(
+            Arc::new(self.nonblocking_rpc_client(commitment_config)),
+            &self.ws_url(),
+            config,
+        )
+        .await
+    }
+
+    /// Fetches the genesis hash of the cluster, to check that an RPC node serves the expected
+    /// cluster
+    pub fn genesis_hash(&self) -> ClientResult<Hash> {
+        self.rpc_client(CommitmentConfig::finalized())
+            .get_genesis_hash()
+    }
+}
+
+impl FromStr for Cluster {
+    type Err = String;
+
+    /// Parses the same monikers as the CLI's `--url` argument, or a URL
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "m" | "mainnet" | "x1-mainnet" => Ok(Self::X1Mainnet),
+            "t" | "testnet" | "x1-testnet" => Ok(Self::X1Testnet),
+            "l" | "localhost" | "localnet" => Ok(Self::Localnet),
+            url if url.starts_with("http://") || url.starts_with("https://") => {
+                Ok(Self::custom(url))
+            }
+            _ => Err(format!("Unknown cluster: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::X1Mainnet => write!(f, "x1-mainnet"),
+            Self::X1Testnet => write!(f, "x1-testnet"),
+            Self::Localnet => write!(f, "localnet"),
+            Self::Custom { url, .. } => write!(f, "{url}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster() {
+        assert_eq!("m".parse::<Cluster>(), Ok(Cluster::X1Mainnet));
+        assert_eq!("testnet".parse::<Cluster>(), Ok(Cluster::X1Testnet));
+        assert_eq!(Cluster::X1Testnet.url(), "https://rpc.testnet.x1.xyz");
+        assert_eq!(Cluster::X1Testnet.ws_url(), "wss://rpc.testnet.x1.xyz/");
+        assert_eq!(Cluster::Localnet.ws_url(), "ws://localhost:8900/");
+
+        let cluster: Cluster = "http://127.0.0.1:8899".parse().unwrap();
+        assert_eq!(cluster.url(), "http://127.0.0.1:8899");
+        assert_eq!(cluster.ws_url(), "ws://127.0.0.1:8900/");
+        assert_eq!(cluster.to_string(), "http://127.0.0.1:8899");
+        assert!("devnet".parse::<Cluster>().is_err());
+    }
+}
@@ -0,0 +1,77 @@
+//! Offline estimation of X1 transaction fees.
+//!
+//! The estimate assumes every fee feature is active on the cluster. The fee actually charged
+//! is quoted by the `getFeeForMessage` RPC method.
+
+pub use solana_fee::{MIN_COMPUTE_UNITS_THRESHOLD, MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS};
+use {
+    agave_feature_set::FeatureSet,
+    solana_compute_budget_instruction::instructions_processor::process_compute_budget_instructions,
+    solana_fee_structure::FeeBudgetLimits,
+    solana_sdk::{
+        message::{Message, SanitizeMessageError, SanitizedMessage},
+        reserved_account_keys::ReservedAccountKeys,
+    },
+    solana_svm_transaction::svm_message::SVMMessage,
+    tachyon_fee_breakdown::FeeBreakdown,
+};
+
+/// Estimates the fee of `message` from its compute units and compute unit price
+pub fn estimate_fee(message: &Message) -> Result<FeeBreakdown, SanitizeMessageError> {
+    let message = SanitizedMessage::try_from_legacy_message(
+        message.clone(),
+        &ReservedAccountKeys::empty_key_set(),
+    )?;
+    let feature_set = FeatureSet::all_enabled();
+    let fee_budget_limits = FeeBudgetLimits::from(
+        process_compute_budget_instructions(message.program_instructions_iter(), &feature_set)
+            .unwrap_or_default(),
+    );
+    Ok(solana_fee::calculate_fee_breakdown(
+        &message,
+        false,
+        0,
+        fee_budget_limits.prioritization_fee,
+        (&feature_set).into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{
+            compute_budget::ComputeBudgetInstruction, pubkey::Pubkey, system_instruction,
+        },
+    };
+
+    #[test]
+    fn test_estimate_fee() {
+        let payer = Pubkey::new_unique();
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let new_message = |compute_unit_price| {
+            Message::new(
+                &[
+                    ComputeBudgetInstruction::set_compute_unit_limit(10_000),
+                    ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+                    transfer.clone(),
+                ],
+                Some(&payer),
+            )
+        };
+
+        let fee = estimate_fee(&Message::new(&[transfer.clone()], Some(&payer))).unwrap();
+        assert!(fee.base > 0);
+        assert_eq!(fee.prioritization, 0);
+
+        // A bid below the minimum price is charged at the minimum price
+        let below_minimum = estimate_fee(&new_message(1_000)).unwrap();
+        let at_minimum = estimate_fee(&new_message(MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS)).unwrap();
+        assert_eq!(below_minimum.prioritization, at_minimum.prioritization);
+
+        let above_minimum =
+            estimate_fee(&new_message(2 * MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS)).unwrap();
+        assert_eq!(above_minimum.prioritization, 2 * at_minimum.prioritization);
+        assert!(above_minimum.total_fee() > at_minimum.total_fee());
+    }
+}
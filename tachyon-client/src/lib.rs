@@ -0,0 +1,15 @@
+//! Client SDK for X1.
+//!
+//! This re-exports `solana-client` and `tachyon-tpu-client` along with the X1 specific pieces applications otherwise
+//! maintain by hand: the URLs of the X1 clusters in [`cluster`], and in [`fees`] an offline
+//! estimate of the fee of a message, which on X1 is derived from its compute units and compute
+//! unit price rather than from its number of signatures. [`secp256r1`] builds instructions for the
+//! secp256r1 precompile from the signatures of WebAuthn authenticators such as passkeys.
+
+pub use {solana_client, tachyon_fee_breakdown::FeeBreakdown, tachyon_tpu_client};
+
+pub mod cluster;
+pub mod fees;
+pub mod secp256r1;
+
+pub use crate::cluster::Cluster;
@@ -0,0 +1,241 @@
+//! Instructions for the secp256r1 (P-256) precompile from signatures made elsewhere.
+//!
+//! Passkeys and other WebAuthn authenticators never reveal their private keys, so the
+//! precompile instruction has to be built from the signature they return. An authenticator
+//! signs `authenticatorData || sha256(clientDataJSON)` and returns an ASN.1 DER encoded
+//! signature, whose `s` may be in the upper half of the curve order. The precompile only
+//! accepts the 64 byte `r || s` encoding with a low `s`, which [`signature_from_der`] converts
+//! to.
+//!
+//! The precompile is only available once the `enable_secp256r1_precompile` feature is active.
+
+use {
+    bytemuck::bytes_of,
+    solana_sdk::{hash::hash, instruction::Instruction},
+    solana_secp256r1_program::{
+        Secp256r1SignatureOffsets, COMPRESSED_PUBKEY_SERIALIZED_SIZE, DATA_START, FIELD_SIZE,
+        SECP256R1_HALF_ORDER, SECP256R1_ORDER, SIGNATURE_SERIALIZED_SIZE,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Secp256r1InstructionError {
+    #[error("invalid DER encoded signature")]
+    InvalidDerSignature,
+    #[error("message of {0} bytes does not fit in the instruction")]
+    MessageTooLong(usize),
+}
+
+/// Reads one DER integer of at most `FIELD_SIZE` bytes, returning it left padded to
+/// `FIELD_SIZE` bytes and the remaining input
+fn parse_der_integer(der: &[u8]) -> Option<([u8; FIELD_SIZE], &[u8])> {
+    let (&[0x02, len], rest) = der.split_first_chunk()?;
+    let (integer, rest) = rest.split_at_checked(usize::from(len))?;
+    // Integers are signed, so a leading zero byte keeps a set high bit positive
+    let first_nonzero = integer.iter().position(|byte| *byte != 0)?;
+    let integer = &integer[first_nonzero..];
+    if integer.len() > FIELD_SIZE {
+        return None;
+    }
+    let mut field = [0; FIELD_SIZE];
+    field[FIELD_SIZE - integer.len()..].copy_from_slice(integer);
+    Some((field, rest))
+}
+
+/// Returns `a - b` of two big endian numbers, `a` being at least `b`
+fn sub_be(a: &[u8; FIELD_SIZE], b: &[u8; FIELD_SIZE]) -> [u8; FIELD_SIZE] {
+    let mut result = [0; FIELD_SIZE];
+    let mut borrow = 0;
+    for i in (0..FIELD_SIZE).rev() {
+        let difference = i16::from(a[i]) - i16::from(b[i]) - borrow;
+        borrow = i16::from(difference < 0);
+        result[i] = (difference + (borrow << 8)) as u8;
+    }
+    result
+}
+
+/// Converts an ASN.1 DER encoded ECDSA signature to the `r || s` encoding of the precompile,
+/// replacing a high `s` with its low equivalent `order - s`
+pub fn signature_from_der(
+    der: &[u8],
+) -> Result<[u8; SIGNATURE_SERIALIZED_SIZE], Secp256r1InstructionError> {
+    let parse = || {
+        let (&[0x30, len], sequence) = der.split_first_chunk()?;
+        if usize::from(len) != sequence.len() {
+            return None;
+        }
+        let (r, rest) = parse_der_integer(sequence)?;
+        let (mut s, rest) = parse_der_integer(rest)?;
+        if !rest.is_empty() || r >= SECP256R1_ORDER || s >= SECP256R1_ORDER {
+            return None;
+        }
+        if s > SECP256R1_HALF_ORDER {
+            s = sub_be(&SECP256R1_ORDER, &s);
+        }
+        let mut signature = [0; SIGNATURE_SERIALIZED_SIZE];
+        signature[..FIELD_SIZE].copy_from_slice(&r);
+        signature[FIELD_SIZE..].copy_from_slice(&s);
+        Some(signature)
+    };
+    parse().ok_or(Secp256r1InstructionError::InvalidDerSignature)
+}
+
+/// Returns the message a WebAuthn authenticator signs for an assertion
+pub fn webauthn_message(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+    [authenticator_data, hash(client_data_json).as_ref()].concat()
+}
+
+/// Builds a secp256r1 precompile instruction verifying `signature` of `message` by the
+/// compressed public key `pubkey`
+pub fn new_secp256r1_instruction_with_signature(
+    message: &[u8],
+    signature: &[u8; SIGNATURE_SERIALIZED_SIZE],
+    pubkey: &[u8; COMPRESSED_PUBKEY_SERIALIZED_SIZE],
+) -> Result<Instruction, Secp256r1InstructionError> {
+    let public_key_offset = DATA_START;
+    let signature_offset = public_key_offset + COMPRESSED_PUBKEY_SERIALIZED_SIZE;
+    let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+    let message_data_size = u16::try_from(message.len())
+        .ok()
+        .filter(|size| usize::from(*size) + message_data_offset <= usize::from(u16::MAX))
+        .ok_or(Secp256r1InstructionError::MessageTooLong(message.len()))?;
+
+    let offsets = Secp256r1SignatureOffsets {
+        signature_offset: signature_offset as u16,
+        signature_instruction_index: u16::MAX,
+        public_key_offset: public_key_offset as u16,
+        public_key_instruction_index: u16::MAX,
+        message_data_offset: message_data_offset as u16,
+        message_data_size,
+        message_instruction_index: u16::MAX,
+    };
+    let mut data = Vec::with_capacity(message_data_offset + message.len());
+    // The number of signatures, followed by a padding byte
+    data.extend_from_slice(&[1, 0]);
+    data.extend_from_slice(bytes_of(&offsets));
+    data.extend_from_slice(pubkey);
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Ok(Instruction {
+        program_id: solana_sdk_ids::secp256r1_program::id(),
+        accounts: vec![],
+        data,
+    })
+}
+
+/// Builds a secp256r1 precompile instruction verifying a WebAuthn assertion, from the
+/// authenticator data, client data JSON and DER encoded signature returned by the
+/// authenticator, and the compressed public key of the passkey
+pub fn new_webauthn_instruction(
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+    der_signature: &[u8],
+    pubkey: &[u8; COMPRESSED_PUBKEY_SERIALIZED_SIZE],
+) -> Result<Instruction, Secp256r1InstructionError> {
+    new_secp256r1_instruction_with_signature(
+        &webauthn_message(authenticator_data, client_data_json),
+        &signature_from_der(der_signature)?,
+        pubkey,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        agave_feature_set::FeatureSet,
+        openssl::{
+            bn::{BigNum, BigNumContext},
+            ec::{EcGroup, EcKey, PointConversionForm},
+            ecdsa::EcdsaSig,
+            nid::Nid,
+        },
+    };
+
+    fn verify(instruction: &Instruction) -> bool {
+        agave_precompiles::secp256r1::verify(
+            &instruction.data,
+            &[&instruction.data],
+            &FeatureSet::all_enabled(),
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn test_new_webauthn_instruction() {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = EcKey::generate(&group).unwrap();
+        let mut ctx = BigNumContext::new().unwrap();
+        let pubkey: [u8; COMPRESSED_PUBKEY_SERIALIZED_SIZE] = key
+            .public_key()
+            .to_bytes(&group, PointConversionForm::COMPRESSED, &mut ctx)
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let authenticator_data = [7; 37];
+        let client_data_json = br#"{"type":"webauthn.get","challenge":"AAAA"}"#;
+        let message = webauthn_message(&authenticator_data, client_data_json);
+        let digest = hash(&message);
+        let signature = EcdsaSig::sign(digest.as_ref(), &key).unwrap();
+
+        // Test both the low and the high s encodings of the signature
+        let order = BigNum::from_slice(&SECP256R1_ORDER).unwrap();
+        let high_s = {
+            let mut s = BigNum::new().unwrap();
+            s.checked_sub(&order, signature.s()).unwrap();
+            s
+        };
+        for s in [signature.s().to_owned().unwrap(), high_s] {
+            let der = EcdsaSig::from_private_components(signature.r().to_owned().unwrap(), s)
+                .unwrap()
+                .to_der()
+                .unwrap();
+            let instruction =
+                new_webauthn_instruction(&authenticator_data, client_data_json, &der, &pubkey)
+                    .unwrap();
+            assert!(verify(&instruction));
+        }
+
+        let der = signature.to_der().unwrap();
+        let instruction =
+            new_webauthn_instruction(&authenticator_data, b"{}", &der, &pubkey).unwrap();
+        assert!(!verify(&instruction));
+    }
+
+    #[test]
+    fn test_signature_from_der() {
+        let mut der = vec![0x30, 6, 0x02, 1, 5, 0x02, 1, 9];
+        let signature = signature_from_der(&der).unwrap();
+        assert_eq!(signature[FIELD_SIZE - 1], 5);
+        assert_eq!(signature[SIGNATURE_SERIALIZED_SIZE - 1], 9);
+        assert!(signature[..FIELD_SIZE - 1].iter().all(|byte| *byte == 0));
+
+        der[1] = 7;
+        assert_eq!(
+            signature_from_der(&der),
+            Err(Secp256r1InstructionError::InvalidDerSignature)
+        );
+        assert_eq!(
+            signature_from_der(&[0x30, 3, 0x02, 1, 0]),
+            Err(Secp256r1InstructionError::InvalidDerSignature)
+        );
+
+        assert_eq!(sub_be(&SECP256R1_ORDER, &SECP256R1_HALF_ORDER), {
+            let mut half_plus_one = SECP256R1_HALF_ORDER;
+            half_plus_one[FIELD_SIZE - 1] += 1;
+            half_plus_one
+        });
+    }
+
+    #[test]
+    fn test_message_too_long() {
+        let message = vec![0; usize::from(u16::MAX)];
+        assert_eq!(
+            new_secp256r1_instruction_with_signature(&message, &[0; 64], &[0; 33]),
+            Err(Secp256r1InstructionError::MessageTooLong(message.len()))
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Capturing and asserting on the fees charged to transactions.
+
+pub use solana_banks_interface::TransactionFeeDetails;
+use {
+    solana_banks_client::{BanksClient, BanksClientError},
+    solana_banks_interface::BanksTransactionResultWithMetadata,
+    solana_sdk::{
+        commitment_config::CommitmentLevel,
+        transaction::{self, Transaction},
+    },
+};
+
+/// The outcome of a transaction processed by [`process_transaction_with_fee`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeReceipt {
+    pub result: transaction::Result<()>,
+    /// The fee charged to the fee payer, zero if the transaction was not committed
+    pub fee_details: TransactionFeeDetails,
+    /// The fee quoted for the transaction's message before it was processed
+    pub quoted_fee_details: TransactionFeeDetails,
+    pub compute_units_consumed: u64,
+    /// The change of the fee payer's balance, including the fee and any lamports the
+    /// transaction moved in or out of the fee payer
+    pub payer_balance_change: i128,
+}
+
+impl FeeReceipt {
+    pub fn total_fee(&self) -> u64 {
+        self.fee_details.total_fee()
+    }
+
+    /// Panics unless the transaction succeeded.
+    #[track_caller]
+    pub fn assert_success(&self) -> &Self {
+        if let Err(err) = &self.result {
+            panic!("transaction failed: {err}");
+        }
+        self
+    }
+
+    /// Panics unless the fee charged is `expected`.
+    #[track_caller]
+    pub fn assert_fee(&self, expected: TransactionFeeDetails) -> &Self {
+        assert_eq!(
+            self.fee_details, expected,
+            "charged fee {:?} differs from expected fee {expected:?}",
+            self.fee_details
+        );
+        self
+    }
+
+    /// Panics unless the fee charged is the fee quoted by `get_fee_details_for_message`.
+    #[track_caller]
+    pub fn assert_fee_matches_quote(&self) -> &Self {
+        assert_eq!(
+            self.fee_details, self.quoted_fee_details,
+            "charged fee {:?} differs from quoted fee {:?}",
+            self.fee_details, self.quoted_fee_details
+        );
+        self
+    }
+
+    /// Panics unless the fee payer's balance changed by `lamports` on top of the fee, e.g.
+    /// `-1_000` for a transaction that transferred 1_000 lamports out of the fee payer.
+    #[track_caller]
+    pub fn assert_payer_balance_change_excluding_fee(&self, lamports: i128) -> &Self {
+        let expected = lamports - i128::from(self.total_fee());
+        assert_eq!(
+            self.payer_balance_change,
+            expected,
+            "fee payer balance changed by {} lamports, expected {expected} ({lamports} and a fee \
+             of {})",
+            self.payer_balance_change,
+            self.total_fee()
+        );
+        self
+    }
+}
+
+/// Processes `transaction` and captures the fee it was charged along with the fee quoted for it
+/// beforehand.
+pub async fn process_transaction_with_fee(
+    banks_client: &BanksClient,
+    transaction: Transaction,
+) -> Result<FeeReceipt, BanksClientError> {
+    let payer = transaction.message.account_keys[0];
+    let quoted_fee_details = banks_client
+        .get_fee_details_for_message_with_commitment(
+            transaction.message.clone(),
+            CommitmentLevel::Processed,
+        )
+        .await?
+        .ok_or(BanksClientError::ClientError(
+            "failed to quote the transaction fee",
+        ))?;
+    let balance_before = banks_client
+        .get_balance_with_commitment(payer, CommitmentLevel::Processed)
+        .await?;
+
+    let BanksTransactionResultWithMetadata { result, metadata } = banks_client
+        .process_transaction_with_metadata(transaction)
+        .await?;
+
+    let balance_after = banks_client
+        .get_balance_with_commitment(payer, CommitmentLevel::Processed)
+        .await?;
+    let (fee_details, compute_units_consumed) = metadata
+        .map(|metadata| (metadata.fee_details, metadata.compute_units_consumed))
+        .unwrap_or_default();
+
+    Ok(FeeReceipt {
+        result,
+        fee_details,
+        quoted_fee_details,
+        compute_units_consumed,
+        payer_balance_change: i128::from(balance_after) - i128::from(balance_before),
+    })
+}
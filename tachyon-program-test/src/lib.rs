@@ -0,0 +1,11 @@
+//! The program test framework, with helpers for X1 transaction fees.
+//!
+//! This re-exports `solana-program-test`. On X1 the fee of a transaction is derived from its
+//! compute units and compute unit price by `solana_fee::calculate_fee_details`, not from its
+//! number of signatures, and the `BanksClient` of a [`ProgramTest`] charges exactly that fee.
+//! The [`fees`] module helps tests capture the fee a transaction was charged and assert on its
+//! components, so that they reflect what users of a program will actually pay.
+
+pub use solana_program_test::*;
+
+pub mod fees;
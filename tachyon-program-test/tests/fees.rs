@@ -0,0 +1,106 @@
+use {
+    solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        pubkey::Pubkey,
+        signature::Signer,
+        system_instruction,
+        transaction::{Transaction, TransactionError},
+    },
+    tachyon_program_test::{fees::process_transaction_with_fee, ProgramTest},
+};
+
+#[tokio::test]
+async fn transfer_is_charged_quoted_fee() {
+    let context = ProgramTest::default().start_with_context().await;
+    let payer = &context.payer;
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            1_000_000,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        context.last_blockhash,
+    );
+
+    let receipt = process_transaction_with_fee(&context.banks_client, transaction)
+        .await
+        .unwrap();
+    receipt
+        .assert_success()
+        .assert_fee_matches_quote()
+        .assert_payer_balance_change_excluding_fee(-1_000_000);
+    assert!(receipt.total_fee() > 0);
+    assert_eq!(receipt.fee_details.prioritization_fee, 0);
+}
+
+#[tokio::test]
+async fn compute_unit_price_increases_fee() {
+    let context = ProgramTest::default().start_with_context().await;
+    let payer = &context.payer;
+    let transfer =
+        |lamports| system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), lamports);
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[transfer(1)],
+        Some(&payer.pubkey()),
+        &[payer],
+        context.last_blockhash,
+    );
+    let base = process_transaction_with_fee(&context.banks_client, transaction)
+        .await
+        .unwrap();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(10_000),
+            ComputeBudgetInstruction::set_compute_unit_price(100_000_000),
+            transfer(2),
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+        context.last_blockhash,
+    );
+    let priced = process_transaction_with_fee(&context.banks_client, transaction)
+        .await
+        .unwrap();
+    priced
+        .assert_success()
+        .assert_fee_matches_quote()
+        .assert_payer_balance_change_excluding_fee(-2);
+    assert!(priced.total_fee() > base.total_fee());
+    assert!(priced.fee_details.prioritization_fee > 0);
+}
+
+#[tokio::test]
+async fn failed_transaction_is_charged_fee() {
+    let context = ProgramTest::default().start_with_context().await;
+    let payer = &context.payer;
+    let balance = context
+        .banks_client
+        .get_balance(payer.pubkey())
+        .await
+        .unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            balance,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        context.last_blockhash,
+    );
+
+    let receipt = process_transaction_with_fee(&context.banks_client, transaction)
+        .await
+        .unwrap();
+    assert!(matches!(
+        receipt.result,
+        Err(TransactionError::InstructionError(..))
+    ));
+    receipt
+        .assert_fee_matches_quote()
+        .assert_payer_balance_change_excluding_fee(0);
+}
@@ -0,0 +1,15 @@
+//! A TPU client for X1 which attaches a recommended priority fee to transactions.
+//!
+//! Transactions are sent over QUIC directly to the current and upcoming leaders. Before each
+//! blockhash lifetime, the compute unit price is estimated from the recent prioritization fees
+//! paid to write lock the accounts the transaction writes, and if the transaction is not
+//! confirmed before its blockhash expires, it is signed again with a fresh blockhash and a
+//! higher compute unit price.
+
+pub mod priority_fee;
+pub mod tpu_client;
+
+pub use crate::{
+    priority_fee::PriorityFeeConfig,
+    tpu_client::{FeeAwareTpuClient, FeeAwareTpuClientConfig, TpuClientError},
+};
@@ -0,0 +1,120 @@
+//! Compute unit price recommendation from recent prioritization fees, and its escalation
+//! across blockhash lifetimes.
+
+use {
+    solana_fee::MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+    solana_rpc_client_api::response::RpcPrioritizationFee,
+};
+
+pub const DEFAULT_PRIORITY_FEE_PERCENTILE: u8 = 75;
+/// Each blockhash lifetime bids 50% more than the previous one
+pub const DEFAULT_ESCALATION_PERCENT: u64 = 150;
+pub const DEFAULT_MAX_COMPUTE_UNIT_PRICE: u64 = 100 * MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PriorityFeeConfig {
+    /// Percentile of the recent prioritization fees to bid, from 0 to 100
+    pub percentile: u8,
+    /// Lowest compute unit price to bid, in micro-lamports. Defaults to the minimum price
+    /// below which small transactions are rejected by RPC nodes.
+    pub min_compute_unit_price: u64,
+    /// Highest compute unit price to bid, in micro-lamports, including after escalation
+    pub max_compute_unit_price: u64,
+    /// Compute unit price of each blockhash lifetime as a percentage of the previous one
+    pub escalation_percent: u64,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            percentile: DEFAULT_PRIORITY_FEE_PERCENTILE,
+            min_compute_unit_price: MIN_COMPUTE_UNIT_PRICE_MICROLAMPORTS,
+            max_compute_unit_price: DEFAULT_MAX_COMPUTE_UNIT_PRICE,
+            escalation_percent: DEFAULT_ESCALATION_PERCENT,
+        }
+    }
+}
+
+impl PriorityFeeConfig {
+    fn clamp(&self, compute_unit_price: u64) -> u64 {
+        compute_unit_price
+            .min(self.max_compute_unit_price)
+            .max(self.min_compute_unit_price)
+    }
+
+    /// Recommends a compute unit price from the prioritization fees paid in recent slots
+    pub fn recommend(&self, recent_fees: &[RpcPrioritizationFee]) -> u64 {
+        let mut fees: Vec<u64> = recent_fees
+            .iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect();
+        if fees.is_empty() {
+            return self.min_compute_unit_price;
+        }
+        fees.sort_unstable();
+        let percentile = usize::from(self.percentile.min(100));
+        self.clamp(fees[(fees.len() - 1) * percentile / 100])
+    }
+
+    /// The compute unit price to bid after `compute_unit_price` failed to land within a
+    /// blockhash lifetime
+    pub fn escalate(&self, compute_unit_price: u64) -> u64 {
+        self.clamp(compute_unit_price.saturating_mul(self.escalation_percent) / 100)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recent_fees(fees: &[u64]) -> Vec<RpcPrioritizationFee> {
+        fees.iter()
+            .enumerate()
+            .map(|(slot, prioritization_fee)| RpcPrioritizationFee {
+                slot: slot as u64,
+                prioritization_fee: *prioritization_fee,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recommend() {
+        let config = PriorityFeeConfig {
+            percentile: 50,
+            min_compute_unit_price: 10,
+            max_compute_unit_price: 1_000,
+            escalation_percent: 200,
+        };
+        assert_eq!(config.recommend(&[]), 10);
+        assert_eq!(config.recommend(&recent_fees(&[0, 0, 0])), 10);
+        assert_eq!(
+            config.recommend(&recent_fees(&[500, 0, 100, 300, 200])),
+            200
+        );
+        assert_eq!(config.recommend(&recent_fees(&[5_000, 5_000])), 1_000);
+
+        let config = PriorityFeeConfig {
+            percentile: 100,
+            ..config
+        };
+        assert_eq!(
+            config.recommend(&recent_fees(&[500, 0, 100, 300, 200])),
+            500
+        );
+    }
+
+    #[test]
+    fn test_escalate() {
+        let config = PriorityFeeConfig {
+            percentile: 50,
+            min_compute_unit_price: 10,
+            max_compute_unit_price: 1_000,
+            escalation_percent: 150,
+        };
+        assert_eq!(config.escalate(0), 10);
+        assert_eq!(config.escalate(100), 150);
+        assert_eq!(config.escalate(150), 225);
+        assert_eq!(config.escalate(900), 1_000);
+        assert_eq!(config.escalate(u64::MAX), 1_000);
+    }
+}
@@ -0,0 +1,255 @@
+//! The `tpu_client` module sends transactions to upcoming leaders with a recommended priority
+//! fee, and rebroadcasts them with escalating fees until they are confirmed.
+
+use {
+    crate::priority_fee::PriorityFeeConfig,
+    log::*,
+    solana_client::{
+        nonblocking::tpu_client::{TpuClient, TpuSenderError},
+        tpu_client::TpuClientConfig,
+    },
+    solana_compute_budget_interface::ComputeBudgetInstruction,
+    solana_quic_client::{QuicConfig, QuicConnectionManager, QuicPool},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::client_error::Error as ClientError,
+    solana_sdk::{
+        instruction::Instruction,
+        message::Message,
+        pubkey::Pubkey,
+        signature::Signature,
+        signer::{signers::Signers, SignerError},
+        transaction::{Transaction, TransactionError},
+    },
+    std::{collections::BTreeSet, sync::Arc, time::Duration},
+    thiserror::Error,
+};
+
+pub const DEFAULT_MAX_BLOCKHASH_LIFETIMES: usize = 3;
+pub const DEFAULT_REBROADCAST_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Error)]
+pub enum TpuClientError {
+    #[error("rpc error: {0}")]
+    Rpc(#[from] ClientError),
+    #[error("tpu error: {0}")]
+    TpuSender(#[from] TpuSenderError),
+    #[error("signer error: {0}")]
+    Signer(#[from] SignerError),
+    #[error("transaction failed: {0}")]
+    Transaction(TransactionError),
+    #[error("transaction not confirmed within {0} blockhash lifetimes")]
+    Expired(usize),
+}
+
+pub type Result<T> = std::result::Result<T, TpuClientError>;
+
+#[derive(Clone, Debug)]
+pub struct FeeAwareTpuClientConfig {
+    pub tpu_client_config: TpuClientConfig,
+    pub priority_fee: PriorityFeeConfig,
+    /// Compute unit limit to request, if any. Without a limit the price is bid on the compute
+    /// units derived from the instructions.
+    pub compute_unit_limit: Option<u32>,
+    /// Number of blockhashes to sign the transaction with before giving up
+    pub max_blockhash_lifetimes: usize,
+    /// How often the transaction is sent again while its blockhash is valid
+    pub rebroadcast_interval: Duration,
+}
+
+impl Default for FeeAwareTpuClientConfig {
+    fn default() -> Self {
+        Self {
+            tpu_client_config: TpuClientConfig::default(),
+            priority_fee: PriorityFeeConfig::default(),
+            compute_unit_limit: None,
+            max_blockhash_lifetimes: DEFAULT_MAX_BLOCKHASH_LIFETIMES,
+            rebroadcast_interval: DEFAULT_REBROADCAST_INTERVAL,
+        }
+    }
+}
+
+/// Client which sends transactions to the current and upcoming leaders over QUIC with a
+/// recommended compute unit price, escalating it across blockhash lifetimes
+pub struct FeeAwareTpuClient {
+    rpc_client: Arc<RpcClient>,
+    tpu_client: TpuClient<QuicPool, QuicConnectionManager, QuicConfig>,
+    config: FeeAwareTpuClientConfig,
+}
+
+impl FeeAwareTpuClient {
+    pub async fn new(
+        rpc_client: Arc<RpcClient>,
+        websocket_url: &str,
+        config: FeeAwareTpuClientConfig,
+    ) -> Result<Self> {
+        let tpu_client = TpuClient::new(
+            "tachyon-tpu-client",
+            rpc_client.clone(),
+            websocket_url,
+            config.tpu_client_config.clone(),
+        )
+        .await?;
+        Ok(Self {
+            rpc_client,
+            tpu_client,
+            config,
+        })
+    }
+
+    pub fn rpc_client(&self) -> &RpcClient {
+        &self.rpc_client
+    }
+
+    /// Recommends a compute unit price for a transaction write locking `writable_accounts`
+    pub async fn recommend_compute_unit_price(&self, writable_accounts: &[Pubkey]) -> Result<u64> {
+        let recent_fees = self
+            .rpc_client
+            .get_recent_prioritization_fees(writable_accounts)
+            .await?;
+        Ok(self.config.priority_fee.recommend(&recent_fees))
+    }
+
+    /// Sends a transaction made of `instructions` until it is confirmed at the commitment of
+    /// the RPC client.
+    ///
+    /// The compute budget instructions are added by the client and must not be included in
+    /// `instructions`. A transaction which lands is never signed again, so that it cannot be
+    /// executed twice; only one which is dropped before its blockhash expires is signed again,
+    /// with a fresh blockhash and a higher compute unit price.
+    pub async fn send_and_confirm_instructions<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+    ) -> Result<Signature> {
+        let writable_accounts = writable_accounts(instructions, payer);
+        let mut compute_unit_price = None;
+        for lifetime in 0..self.config.max_blockhash_lifetimes {
+            let recommended = self
+                .recommend_compute_unit_price(&writable_accounts)
+                .await?;
+            let price = compute_unit_price.map_or(recommended, |price| {
+                self.config.priority_fee.escalate(price).max(recommended)
+            });
+            compute_unit_price = Some(price);
+            debug!("sending transaction, lifetime: {lifetime}, compute_unit_price: {price}");
+            let message = Message::new(&self.with_compute_budget(instructions, price), Some(payer));
+            if let Some(signature) = self.send_until_expired(message, signers).await? {
+                return Ok(signature);
+            }
+            warn!("transaction expired at compute unit price {price}");
+        }
+        Err(TpuClientError::Expired(self.config.max_blockhash_lifetimes))
+    }
+
+    fn with_compute_budget(&self, instructions: &[Instruction], price: u64) -> Vec<Instruction> {
+        self.config
+            .compute_unit_limit
+            .map(ComputeBudgetInstruction::set_compute_unit_limit)
+            .into_iter()
+            .chain(Some(ComputeBudgetInstruction::set_compute_unit_price(
+                price,
+            )))
+            .chain(instructions.iter().cloned())
+            .collect()
+    }
+
+    /// Signs `message` with the latest blockhash and rebroadcasts it until it is confirmed,
+    /// returning `None` if it did not land before the blockhash expired.
+    async fn send_until_expired<T: Signers + ?Sized>(
+        &self,
+        message: Message,
+        signers: &T,
+    ) -> Result<Option<Signature>> {
+        let commitment = self.rpc_client.commitment();
+        let (blockhash, last_valid_block_height) = self
+            .rpc_client
+            .get_latest_blockhash_with_commitment(commitment)
+            .await?;
+        let transaction = Transaction::try_new(signers, message, blockhash)?;
+        let signature = transaction.signatures[0];
+        let wire_transaction =
+            bincode::serialize(&transaction).expect("serialize Transaction in send_until_expired");
+        loop {
+            if let Err(err) = self
+                .tpu_client
+                .try_send_wire_transaction(wire_transaction.clone())
+                .await
+            {
+                warn!("failed to send transaction {signature}: {err}");
+            }
+            tokio::time::sleep(self.config.rebroadcast_interval).await;
+
+            let status = self
+                .rpc_client
+                .get_signature_statuses(&[signature])
+                .await?
+                .value
+                .pop()
+                .flatten();
+            match status {
+                Some(status) => {
+                    if let Some(err) = status.err {
+                        return Err(TpuClientError::Transaction(err));
+                    }
+                    if status.satisfies_commitment(commitment) {
+                        return Ok(Some(signature));
+                    }
+                    // Landed, wait for it to be confirmed or dropped along with its fork
+                }
+                None => {
+                    if self.rpc_client.get_block_height().await? > last_valid_block_height {
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The accounts the transaction write locks, whose recent prioritization fees determine the
+/// price to bid
+fn writable_accounts(instructions: &[Instruction], payer: &Pubkey) -> Vec<Pubkey> {
+    instructions
+        .iter()
+        .flat_map(|instruction| &instruction.accounts)
+        .filter(|account_meta| account_meta.is_writable)
+        .map(|account_meta| account_meta.pubkey)
+        .chain(Some(*payer))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::instruction::AccountMeta};
+
+    #[test]
+    fn test_writable_accounts() {
+        let payer = Pubkey::new_unique();
+        let writable = Pubkey::new_unique();
+        let readonly = Pubkey::new_unique();
+        let instructions = [
+            Instruction::new_with_bytes(
+                Pubkey::new_unique(),
+                &[],
+                vec![
+                    AccountMeta::new(writable, false),
+                    AccountMeta::new_readonly(readonly, false),
+                ],
+            ),
+            Instruction::new_with_bytes(
+                Pubkey::new_unique(),
+                &[],
+                vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(writable, false),
+                ],
+            ),
+        ];
+        let mut expected = vec![payer, writable];
+        expected.sort_unstable();
+        assert_eq!(writable_accounts(&instructions, &payer), expected);
+    }
+}
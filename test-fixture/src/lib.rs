@@ -0,0 +1,511 @@
+//! Deterministic single node ledgers for integration tests.
+//!
+//! A [`FixtureManifest`] describes the genesis of a local test cluster: a seed from which all
+//! keypairs are derived, the genesis creation time, the enabled feature gates and the accounts to
+//! prefund. Building the same manifest always produces the same genesis config, and therefore the
+//! same genesis hash and blockhashes, so tests of X1 dApps can hardcode keys and hashes.
+//!
+//! Manifests are usually written in YAML (or JSON):
+//!
+//! ```yaml
+//! seed: my-dapp
+//! features:
+//!   default: all
+//!   deactivate:
+//!     - 5mUGsxq8DGxoqaiUS8QjajLA89uTpY4UPS34UEgJzurp
+//! accounts:
+//!   # The keypair is derived from the seed and name
+//!   - name: alice
+//!     lamports: 1000000000000
+//!   - pubkey: 9Ph241DF77goVK3d8LDoF75jU72nwLeKDg2AEwyospVy
+//!     lamports: 1000000
+//!     owner: Config1111111111111111111111111111111111111
+//!     data: AAAA
+//! ```
+
+use {
+    agave_feature_set::{FeatureSet, FEATURE_NAMES},
+    base64::{prelude::BASE64_STANDARD, Engine},
+    log::*,
+    serde_derive::{Deserialize, Serialize},
+    solana_accounts_db::hardened_unpack::MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
+    solana_ledger::{blockstore::create_new_ledger, blockstore_options::LedgerColumnOptions},
+    solana_runtime::genesis_utils::{self, create_genesis_config_with_leader_ex_no_features},
+    solana_sdk::{
+        account::{Account, AccountSharedData, ReadableAccount},
+        clock::{UnixTimestamp, DEFAULT_TICKS_PER_SLOT},
+        epoch_schedule::{EpochSchedule, MINIMUM_SLOTS_PER_EPOCH},
+        fee_calculator::FeeRateGovernor,
+        genesis_config::{ClusterType, GenesisConfig},
+        hash::{hashv, Hash},
+        native_token::LAMPORTS_PER_SOL,
+        pubkey::{ParsePubkeyError, Pubkey},
+        rent::Rent,
+        signature::{keypair_from_seed, write_keypair_file, Keypair, Signer},
+        system_program,
+    },
+    std::{
+        collections::{BTreeMap, HashSet},
+        fs, io,
+        path::Path,
+    },
+    thiserror::Error,
+};
+
+pub const DEFAULT_SEED: &str = "tachyon-test-fixture";
+
+/// Names of the keypairs every fixture derives, and the files they are written to in the ledger
+/// directory. The file names match the ones used by `solana-test-validator`.
+pub const VALIDATOR_IDENTITY: &str = "validator";
+pub const VALIDATOR_VOTE_ACCOUNT: &str = "vote-account";
+pub const VALIDATOR_STAKE_ACCOUNT: &str = "stake-account";
+pub const FAUCET: &str = "faucet";
+
+const FAUCET_LAMPORTS: u64 = 500_000_000 * LAMPORTS_PER_SOL;
+const VALIDATOR_IDENTITY_LAMPORTS: u64 = 500 * LAMPORTS_PER_SOL;
+const VALIDATOR_STAKE_LAMPORTS: u64 = 1_000_000 * LAMPORTS_PER_SOL;
+
+#[derive(Debug, Error)]
+pub enum FixtureError {
+    #[error("invalid manifest: {0}")]
+    InvalidManifest(#[from] serde_yaml::Error),
+    #[error("invalid pubkey {0}: {1}")]
+    InvalidPubkey(String, ParsePubkeyError),
+    #[error("account must have exactly one of name and pubkey")]
+    InvalidAccountAddress,
+    #[error("invalid data for account {0}: {1}")]
+    InvalidAccountData(Pubkey, base64::DecodeError),
+    #[error("account {0} is defined more than once")]
+    DuplicateAccount(Pubkey),
+    #[error("{0} is not a known feature")]
+    UnknownFeature(Pubkey),
+    #[error("slots per epoch {0} is less than the minimum")]
+    InvalidSlotsPerEpoch(u64),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("failed to create ledger: {0}")]
+    Ledger(String),
+}
+
+/// Feature gates activated at genesis, starting from `default` and then applying `activate` and
+/// `deactivate`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureSelection {
+    pub default: DefaultFeatures,
+    pub activate: Vec<String>,
+    pub deactivate: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DefaultFeatures {
+    /// Every known feature is active, like on a fresh `solana-test-validator`
+    #[default]
+    All,
+    /// No feature is active, like on a cluster that has not activated any
+    None,
+}
+
+/// An account to create at genesis.
+///
+/// Accounts given by `name` are funded system accounts whose keypair is derived from the
+/// fixture's seed, accounts given by `pubkey` may hold arbitrary data.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FixtureAccount {
+    pub name: Option<String>,
+    pub pubkey: Option<String>,
+    pub lamports: u64,
+    /// Defaults to the system program
+    pub owner: Option<String>,
+    /// Base64 encoded account data
+    pub data: Option<String>,
+    pub executable: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FixtureManifest {
+    /// All keypairs of the fixture are derived from this seed
+    pub seed: String,
+    pub creation_time: UnixTimestamp,
+    pub ticks_per_slot: u64,
+    /// Epochs have a fixed length, without warmup. Defaults to the standard epoch length
+    pub slots_per_epoch: Option<u64>,
+    /// Proof of history hashes per tick, `None` for the low power mode used by test validators
+    pub hashes_per_tick: Option<u64>,
+    pub features: FeatureSelection,
+    pub accounts: Vec<FixtureAccount>,
+}
+
+impl Default for FixtureManifest {
+    fn default() -> Self {
+        Self {
+            seed: DEFAULT_SEED.to_string(),
+            creation_time: 0,
+            ticks_per_slot: DEFAULT_TICKS_PER_SLOT,
+            slots_per_epoch: None,
+            hashes_per_tick: None,
+            features: FeatureSelection::default(),
+            accounts: vec![],
+        }
+    }
+}
+
+impl FixtureManifest {
+    pub fn from_yaml(yaml: &str) -> Result<Self, FixtureError> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, FixtureError> {
+        Self::from_yaml(&fs::read_to_string(path)?)
+    }
+
+    /// Adds a funded system account whose keypair is derived from the fixture's seed and `name`.
+    pub fn add_funded_keypair(&mut self, name: &str, lamports: u64) -> &mut Self {
+        self.accounts.push(FixtureAccount {
+            name: Some(name.to_string()),
+            lamports,
+            ..FixtureAccount::default()
+        });
+        self
+    }
+
+    /// Adds an account at `pubkey`.
+    pub fn add_account(&mut self, pubkey: &Pubkey, account: &AccountSharedData) -> &mut Self {
+        self.accounts.push(FixtureAccount {
+            name: None,
+            pubkey: Some(pubkey.to_string()),
+            lamports: account.lamports(),
+            owner: Some(account.owner().to_string()),
+            data: Some(BASE64_STANDARD.encode(account.data())),
+            executable: account.executable(),
+        });
+        self
+    }
+
+    pub fn activate_feature(&mut self, feature_id: &Pubkey) -> &mut Self {
+        self.features.activate.push(feature_id.to_string());
+        self
+    }
+
+    pub fn deactivate_feature(&mut self, feature_id: &Pubkey) -> &mut Self {
+        self.features.deactivate.push(feature_id.to_string());
+        self
+    }
+}
+
+/// Derives the keypair named `name` of the fixture with `seed`.
+pub fn derive_keypair(seed: &str, name: &str) -> Keypair {
+    let hash = hashv(&[DEFAULT_SEED.as_bytes(), seed.as_bytes(), name.as_bytes()]);
+    keypair_from_seed(hash.as_ref()).unwrap()
+}
+
+fn parse_pubkey(pubkey: &str) -> Result<Pubkey, FixtureError> {
+    pubkey
+        .parse()
+        .map_err(|err| FixtureError::InvalidPubkey(pubkey.to_string(), err))
+}
+
+fn parse_features(pubkeys: &[String]) -> Result<Vec<Pubkey>, FixtureError> {
+    pubkeys
+        .iter()
+        .map(|pubkey| {
+            let feature_id = parse_pubkey(pubkey)?;
+            if FEATURE_NAMES.contains_key(&feature_id) {
+                Ok(feature_id)
+            } else {
+                Err(FixtureError::UnknownFeature(feature_id))
+            }
+        })
+        .collect()
+}
+
+/// A genesis config built from a [`FixtureManifest`], along with the keypairs derived for it.
+pub struct Fixture {
+    genesis_config: GenesisConfig,
+    keypairs: BTreeMap<String, Keypair>,
+}
+
+impl Fixture {
+    pub fn new(manifest: &FixtureManifest) -> Result<Self, FixtureError> {
+        let mut keypairs = BTreeMap::new();
+        for name in [
+            VALIDATOR_IDENTITY,
+            VALIDATOR_VOTE_ACCOUNT,
+            VALIDATOR_STAKE_ACCOUNT,
+            FAUCET,
+        ] {
+            keypairs.insert(name.to_string(), derive_keypair(&manifest.seed, name));
+        }
+
+        let mut pubkeys: HashSet<_> = keypairs.values().map(|keypair| keypair.pubkey()).collect();
+        let mut accounts = vec![];
+        for account in &manifest.accounts {
+            let pubkey = match (&account.name, &account.pubkey) {
+                (Some(name), None) => {
+                    let keypair = derive_keypair(&manifest.seed, name);
+                    let pubkey = keypair.pubkey();
+                    keypairs.insert(name.clone(), keypair);
+                    pubkey
+                }
+                (None, Some(pubkey)) => parse_pubkey(pubkey)?,
+                _ => return Err(FixtureError::InvalidAccountAddress),
+            };
+            if !pubkeys.insert(pubkey) {
+                return Err(FixtureError::DuplicateAccount(pubkey));
+            }
+            let owner = account
+                .owner
+                .as_deref()
+                .map(parse_pubkey)
+                .transpose()?
+                .unwrap_or(system_program::id());
+            let data = account
+                .data
+                .as_deref()
+                .map(|data| BASE64_STANDARD.decode(data))
+                .transpose()
+                .map_err(|err| FixtureError::InvalidAccountData(pubkey, err))?
+                .unwrap_or_default();
+            accounts.push((
+                pubkey,
+                AccountSharedData::from(Account {
+                    lamports: account.lamports,
+                    data,
+                    owner,
+                    executable: account.executable,
+                    rent_epoch: 0,
+                }),
+            ));
+        }
+
+        let mut genesis_config = create_genesis_config_with_leader_ex_no_features(
+            FAUCET_LAMPORTS,
+            &keypairs[FAUCET].pubkey(),
+            &keypairs[VALIDATOR_IDENTITY].pubkey(),
+            &keypairs[VALIDATOR_VOTE_ACCOUNT].pubkey(),
+            &keypairs[VALIDATOR_STAKE_ACCOUNT].pubkey(),
+            VALIDATOR_STAKE_LAMPORTS,
+            VALIDATOR_IDENTITY_LAMPORTS,
+            FeeRateGovernor::default(),
+            Rent::default(),
+            ClusterType::Development,
+            accounts,
+        );
+        genesis_config.creation_time = manifest.creation_time;
+        genesis_config.ticks_per_slot = manifest.ticks_per_slot;
+        genesis_config.poh_config.hashes_per_tick = manifest.hashes_per_tick;
+        genesis_config.epoch_schedule = match manifest.slots_per_epoch {
+            Some(slots_per_epoch) if slots_per_epoch < MINIMUM_SLOTS_PER_EPOCH => {
+                return Err(FixtureError::InvalidSlotsPerEpoch(slots_per_epoch));
+            }
+            Some(slots_per_epoch) => EpochSchedule::custom(slots_per_epoch, slots_per_epoch, false),
+            None => EpochSchedule::without_warmup(),
+        };
+
+        let mut features: HashSet<_> = match manifest.features.default {
+            DefaultFeatures::All => FeatureSet::default().inactive().iter().copied().collect(),
+            DefaultFeatures::None => HashSet::new(),
+        };
+        features.extend(parse_features(&manifest.features.activate)?);
+        for feature_id in parse_features(&manifest.features.deactivate)? {
+            features.remove(&feature_id);
+        }
+        for feature_id in features {
+            genesis_utils::activate_feature(&mut genesis_config, feature_id);
+        }
+
+        Ok(Self {
+            genesis_config,
+            keypairs,
+        })
+    }
+
+    pub fn genesis_config(&self) -> &GenesisConfig {
+        &self.genesis_config
+    }
+
+    pub fn genesis_hash(&self) -> Hash {
+        self.genesis_config.hash()
+    }
+
+    /// Returns one of the fixed keypairs, like [`VALIDATOR_IDENTITY`] or [`FAUCET`], or the
+    /// keypair of an account given by name in the manifest.
+    pub fn keypair(&self, name: &str) -> Option<&Keypair> {
+        self.keypairs.get(name)
+    }
+
+    pub fn keypairs(&self) -> impl Iterator<Item = (&str, &Keypair)> {
+        self.keypairs
+            .iter()
+            .map(|(name, keypair)| (name.as_str(), keypair))
+    }
+
+    /// Creates a ledger from the fixture's genesis config in `ledger_path` and writes every
+    /// keypair of the fixture there as `<name>-keypair.json`. The ledger can then be used with
+    /// `tachyon-validator` or `solana-test-validator --ledger`.
+    pub fn create_ledger(&self, ledger_path: &Path) -> Result<Hash, FixtureError> {
+        let genesis_hash = create_new_ledger(
+            ledger_path,
+            &self.genesis_config,
+            MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
+            LedgerColumnOptions::default(),
+        )
+        .map_err(|err| FixtureError::Ledger(err.to_string()))?;
+
+        for (name, keypair) in &self.keypairs {
+            let path = ledger_path.join(format!("{name}-keypair.json"));
+            write_keypair_file(keypair, &path)
+                .map_err(|err| io::Error::other(format!("{}: {err}", path.display())))?;
+        }
+        info!(
+            "Created fixture ledger in {} with genesis hash {genesis_hash}",
+            ledger_path.display()
+        );
+        Ok(genesis_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::native_token::sol_to_lamports};
+
+    #[test]
+    fn test_fixture_is_deterministic() {
+        let mut manifest = FixtureManifest::default();
+        manifest.add_funded_keypair("alice", sol_to_lamports(10.));
+
+        let fixture = Fixture::new(&manifest).unwrap();
+        let again = Fixture::new(&manifest).unwrap();
+        assert_eq!(fixture.genesis_hash(), again.genesis_hash());
+        assert_eq!(
+            fixture.keypair("alice").unwrap().pubkey(),
+            derive_keypair(DEFAULT_SEED, "alice").pubkey()
+        );
+        let alice = &fixture.genesis_config().accounts[&fixture.keypair("alice").unwrap().pubkey()];
+        assert_eq!(alice.lamports, sol_to_lamports(10.));
+
+        manifest.seed = "other".to_string();
+        let other = Fixture::new(&manifest).unwrap();
+        assert_ne!(fixture.genesis_hash(), other.genesis_hash());
+        assert_ne!(
+            fixture.keypair(FAUCET).unwrap().pubkey(),
+            other.keypair(FAUCET).unwrap().pubkey()
+        );
+    }
+
+    #[test]
+    fn test_fixture_manifest() {
+        let feature_id = agave_feature_set::storage_deposit::id();
+        let manifest = FixtureManifest::from_yaml(&format!(
+            "
+seed: my-dapp
+slots_per_epoch: 64
+features:
+  deactivate:
+    - {feature_id}
+accounts:
+  - name: alice
+    lamports: 1000
+  - pubkey: {}
+    lamports: 2000
+    owner: {}
+    data: AQID
+",
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+        ))
+        .unwrap();
+        assert_eq!(manifest.seed, "my-dapp");
+        assert_eq!(manifest.creation_time, 0);
+
+        let fixture = Fixture::new(&manifest).unwrap();
+        let genesis_config = fixture.genesis_config();
+        assert_eq!(genesis_config.epoch_schedule.slots_per_epoch, 64);
+        assert!(!genesis_config.accounts.contains_key(&feature_id));
+        assert!(genesis_config
+            .accounts
+            .contains_key(&agave_feature_set::governance_parameter_bundles::id()));
+        let account = &genesis_config.accounts[&Pubkey::new_from_array([1; 32])];
+        assert_eq!(account.lamports, 2000);
+        assert_eq!(account.owner, Pubkey::new_from_array([2; 32]));
+        assert_eq!(account.data, vec![1, 2, 3]);
+
+        assert!(matches!(
+            FixtureManifest::from_yaml("unknown_field: 1"),
+            Err(FixtureError::InvalidManifest(_))
+        ));
+    }
+
+    #[test]
+    fn test_fixture_features() {
+        let feature_id = agave_feature_set::storage_deposit::id();
+        let mut manifest = FixtureManifest {
+            features: FeatureSelection {
+                default: DefaultFeatures::None,
+                ..FeatureSelection::default()
+            },
+            ..FixtureManifest::default()
+        };
+        manifest.activate_feature(&feature_id);
+        let fixture = Fixture::new(&manifest).unwrap();
+        let feature_accounts: Vec<_> = fixture
+            .genesis_config()
+            .accounts
+            .iter()
+            .filter(|(_, account)| account.owner() == &solana_sdk::feature::id())
+            .map(|(pubkey, _)| *pubkey)
+            .collect();
+        assert_eq!(feature_accounts, vec![feature_id]);
+
+        let mut manifest = FixtureManifest::default();
+        manifest.activate_feature(&Pubkey::new_unique());
+        assert!(matches!(
+            Fixture::new(&manifest),
+            Err(FixtureError::UnknownFeature(_))
+        ));
+    }
+
+    #[test]
+    fn test_fixture_invalid_accounts() {
+        let mut manifest = FixtureManifest::default();
+        manifest
+            .add_funded_keypair("alice", 1)
+            .add_funded_keypair("alice", 1);
+        assert!(matches!(
+            Fixture::new(&manifest),
+            Err(FixtureError::DuplicateAccount(_))
+        ));
+
+        let manifest = FixtureManifest {
+            accounts: vec![FixtureAccount {
+                lamports: 1,
+                ..FixtureAccount::default()
+            }],
+            ..FixtureManifest::default()
+        };
+        assert!(matches!(
+            Fixture::new(&manifest),
+            Err(FixtureError::InvalidAccountAddress)
+        ));
+    }
+
+    #[test]
+    fn test_fixture_create_ledger() {
+        let ledger_path = tempfile::tempdir().unwrap();
+        let mut manifest = FixtureManifest::default();
+        manifest.add_funded_keypair("alice", 1);
+        let fixture = Fixture::new(&manifest).unwrap();
+
+        let genesis_hash = fixture.create_ledger(ledger_path.path()).unwrap();
+        assert_eq!(genesis_hash, fixture.genesis_hash());
+        for name in ["alice", VALIDATOR_IDENTITY, VALIDATOR_VOTE_ACCOUNT, FAUCET] {
+            assert!(ledger_path
+                .path()
+                .join(format!("{name}-keypair.json"))
+                .exists());
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! A command-line executable for creating deterministic test ledgers.
+
+use {
+    clap::{crate_description, crate_name, App, Arg},
+    solana_clap_utils::{input_parsers::pubkeys_of, input_validators::is_pubkey},
+    solana_sdk::signature::Signer,
+    std::{error, path::PathBuf, process::exit},
+    tachyon_test_fixture::{Fixture, FixtureManifest},
+};
+
+fn main() -> Result<(), Box<dyn error::Error>> {
+    solana_logger::setup_with_default_filter();
+
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(solana_version::version!())
+        .arg(
+            Arg::with_name("ledger_path")
+                .short("l")
+                .long("ledger")
+                .value_name("DIR")
+                .takes_value(true)
+                .required(true)
+                .help("Directory to create the ledger in, must not contain a ledger already"),
+        )
+        .arg(
+            Arg::with_name("manifest")
+                .long("manifest")
+                .value_name("FILE")
+                .takes_value(true)
+                .help(
+                    "YAML or JSON manifest of the fixture: seed, creation time, feature gates \
+                     and prefunded accounts [default: an empty manifest]",
+                ),
+        )
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .value_name("SEED")
+                .takes_value(true)
+                .help("Seed to derive all keypairs from, overrides the manifest's seed"),
+        )
+        .arg(
+            Arg::with_name("deactivate_feature")
+                .long("deactivate-feature")
+                .value_name("FEATURE_PUBKEY")
+                .takes_value(true)
+                .multiple(true)
+                .validator(is_pubkey)
+                .help("Deactivate this feature in addition to those of the manifest"),
+        )
+        .arg(
+            Arg::with_name("print_manifest")
+                .long("print-manifest")
+                .takes_value(false)
+                .help("Print the effective manifest as YAML"),
+        )
+        .get_matches();
+
+    let ledger_path = PathBuf::from(matches.value_of("ledger_path").unwrap());
+    let mut manifest = match matches.value_of("manifest") {
+        Some(path) => FixtureManifest::from_file(&PathBuf::from(path))?,
+        None => FixtureManifest::default(),
+    };
+    if let Some(seed) = matches.value_of("seed") {
+        manifest.seed = seed.to_string();
+    }
+    for feature_id in pubkeys_of(&matches, "deactivate_feature").unwrap_or_default() {
+        manifest.deactivate_feature(&feature_id);
+    }
+    if matches.is_present("print_manifest") {
+        print!("{}", serde_yaml::to_string(&manifest)?);
+    }
+
+    if ledger_path.join("genesis.bin").exists() {
+        eprintln!("Error: {} already contains a ledger", ledger_path.display());
+        exit(1);
+    }
+
+    let fixture = Fixture::new(&manifest)?;
+    let genesis_hash = fixture.create_ledger(&ledger_path)?;
+
+    println!("Ledger: {}", ledger_path.display());
+    println!("Genesis hash: {genesis_hash}");
+    for (name, keypair) in fixture.keypairs() {
+        println!("{name}: {}", keypair.pubkey());
+    }
+    Ok(())
+}
@@ -1,4 +1,5 @@
 //! Core types for solana-transaction-status
+pub use tachyon_fee_breakdown::FeeBreakdown;
 use {
     crate::option_serializer::OptionSerializer,
     base64::{prelude::BASE64_STANDARD, Engine},
@@ -275,6 +276,11 @@ pub struct UiTransactionStatusMeta {
         skip_serializing_if = "OptionSerializer::should_skip"
     )]
     pub compute_units_consumed: OptionSerializer<u64>,
+    #[serde(
+        default = "OptionSerializer::skip",
+        skip_serializing_if = "OptionSerializer::should_skip"
+    )]
+    pub fee_breakdown: OptionSerializer<FeeBreakdown>,
 }
 
 impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
@@ -304,6 +310,7 @@ impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
                 meta.return_data.map(|return_data| return_data.into()),
             ),
             compute_units_consumed: OptionSerializer::or_skip(meta.compute_units_consumed),
+            fee_breakdown: OptionSerializer::or_skip(meta.fee_breakdown),
         }
     }
 }
@@ -547,6 +554,8 @@ pub struct TransactionStatusMeta {
     pub loaded_addresses: LoadedAddresses,
     pub return_data: Option<TransactionReturnData>,
     pub compute_units_consumed: Option<u64>,
+    /// The components of `fee`, not recorded for transactions processed by earlier versions
+    pub fee_breakdown: Option<FeeBreakdown>,
 }
 
 impl Default for TransactionStatusMeta {
@@ -564,6 +573,7 @@ impl Default for TransactionStatusMeta {
             loaded_addresses: LoadedAddresses::default(),
             return_data: None,
             compute_units_consumed: None,
+            fee_breakdown: None,
         }
     }
 }
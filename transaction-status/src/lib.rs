@@ -6,13 +6,13 @@ pub use {
     solana_transaction_status_client_types::{
         option_serializer, ConfirmedTransactionStatusWithSignature, EncodeError,
         EncodedConfirmedBlock, EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
-        EncodedTransactionWithStatusMeta, InnerInstruction, InnerInstructions, Reward, Rewards,
-        TransactionBinaryEncoding, TransactionConfirmationStatus, TransactionDetails,
-        TransactionStatus, TransactionStatusMeta, TransactionTokenBalance, UiAccountsList,
-        UiAddressTableLookup, UiCompiledInstruction, UiConfirmedBlock, UiInnerInstructions,
-        UiInstruction, UiLoadedAddresses, UiMessage, UiParsedInstruction, UiParsedMessage,
-        UiPartiallyDecodedInstruction, UiRawMessage, UiReturnDataEncoding, UiTransaction,
-        UiTransactionEncoding, UiTransactionReturnData, UiTransactionStatusMeta,
+        EncodedTransactionWithStatusMeta, FeeBreakdown, InnerInstruction, InnerInstructions,
+        Reward, Rewards, TransactionBinaryEncoding, TransactionConfirmationStatus,
+        TransactionDetails, TransactionStatus, TransactionStatusMeta, TransactionTokenBalance,
+        UiAccountsList, UiAddressTableLookup, UiCompiledInstruction, UiConfirmedBlock,
+        UiInnerInstructions, UiInstruction, UiLoadedAddresses, UiMessage, UiParsedInstruction,
+        UiParsedMessage, UiPartiallyDecodedInstruction, UiRawMessage, UiReturnDataEncoding,
+        UiTransaction, UiTransactionEncoding, UiTransactionReturnData, UiTransactionStatusMeta,
         UiTransactionTokenBalance,
     },
 };
@@ -57,6 +57,7 @@ pub mod parse_stake;
 pub mod parse_system;
 pub mod parse_token;
 pub mod parse_vote;
+pub mod parse_zk_elgamal_proof;
 pub mod token_balances;
 
 pub struct BlockEncodingOptions {
@@ -187,6 +188,7 @@ fn build_simple_ui_transaction_status_meta(
         loaded_addresses: OptionSerializer::Skip,
         return_data: OptionSerializer::Skip,
         compute_units_consumed: OptionSerializer::Skip,
+        fee_breakdown: OptionSerializer::Skip,
     }
 }
 
@@ -225,6 +227,7 @@ fn parse_ui_transaction_status_meta(
             meta.return_data.map(|return_data| return_data.into()),
         ),
         compute_units_consumed: OptionSerializer::or_skip(meta.compute_units_consumed),
+        fee_breakdown: OptionSerializer::or_skip(meta.fee_breakdown),
     }
 }
 
@@ -876,6 +879,7 @@ mod test {
             },
             return_data: None,
             compute_units_consumed: None,
+            fee_breakdown: None,
         };
         let expected_json_output_value: serde_json::Value = serde_json::from_str(
             "{\
@@ -9,13 +9,14 @@ use {
         parse_system::parse_system,
         parse_token::parse_token,
         parse_vote::parse_vote,
+        parse_zk_elgamal_proof::parse_zk_elgamal_proof,
     },
     inflector::Inflector,
     serde_json::Value,
     solana_account_decoder::parse_token::spl_token_ids,
     solana_message::{compiled_instruction::CompiledInstruction, AccountKeys},
     solana_pubkey::Pubkey,
-    solana_sdk_ids::{address_lookup_table, stake, system_program, vote},
+    solana_sdk_ids::{address_lookup_table, stake, system_program, vote, zk_elgamal_proof_program},
     std::{
         collections::HashMap,
         str::{from_utf8, Utf8Error},
@@ -34,6 +35,7 @@ lazy_static! {
     static ref STAKE_PROGRAM_ID: Pubkey = stake::id();
     static ref SYSTEM_PROGRAM_ID: Pubkey = system_program::id();
     static ref VOTE_PROGRAM_ID: Pubkey = vote::id();
+    static ref ZK_ELGAMAL_PROOF_PROGRAM_ID: Pubkey = zk_elgamal_proof_program::id();
     static ref PARSABLE_PROGRAM_IDS: HashMap<Pubkey, ParsableProgram> = {
         let mut m = HashMap::new();
         m.insert(
@@ -57,6 +59,10 @@ lazy_static! {
         m.insert(*STAKE_PROGRAM_ID, ParsableProgram::Stake);
         m.insert(*SYSTEM_PROGRAM_ID, ParsableProgram::System);
         m.insert(*VOTE_PROGRAM_ID, ParsableProgram::Vote);
+        m.insert(
+            *ZK_ELGAMAL_PROOF_PROGRAM_ID,
+            ParsableProgram::ZkElGamalProof,
+        );
         m
     };
 }
@@ -97,6 +103,7 @@ pub enum ParsableProgram {
     Stake,
     System,
     Vote,
+    ZkElGamalProof,
 }
 
 pub fn parse(
@@ -126,6 +133,9 @@ pub fn parse(
         ParsableProgram::Stake => serde_json::to_value(parse_stake(instruction, account_keys)?)?,
         ParsableProgram::System => serde_json::to_value(parse_system(instruction, account_keys)?)?,
         ParsableProgram::Vote => serde_json::to_value(parse_vote(instruction, account_keys)?)?,
+        ParsableProgram::ZkElGamalProof => {
+            serde_json::to_value(parse_zk_elgamal_proof(instruction, account_keys)?)?
+        }
     };
     Ok(ParsedInstruction {
         program: format!("{program_name:?}").to_kebab_case(),
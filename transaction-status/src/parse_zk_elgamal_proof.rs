@@ -0,0 +1,415 @@
+use {
+    crate::parse_instruction::{
+        check_num_accounts, ParsableProgram, ParseInstructionError, ParsedInstructionEnum,
+    },
+    serde_json::{json, Value},
+    solana_message::{compiled_instruction::CompiledInstruction, AccountKeys},
+    spl_token_2022::solana_zk_sdk::zk_elgamal_proof_program::{
+        instruction::ProofInstruction, proof_data::*,
+    },
+};
+
+/// Length of the data of a proof verification instruction reading its proof from an account: the
+/// instruction discriminator followed by the u32 offset of the proof in the account
+const PROOF_FROM_ACCOUNT_DATA_LEN: usize = 5;
+
+pub fn parse_zk_elgamal_proof(
+    instruction: &CompiledInstruction,
+    account_keys: &AccountKeys,
+) -> Result<ParsedInstructionEnum, ParseInstructionError> {
+    let proof_instruction = ProofInstruction::instruction_type(&instruction.data).ok_or(
+        ParseInstructionError::InstructionNotParsable(ParsableProgram::ZkElGamalProof),
+    )?;
+    match instruction.accounts.iter().max() {
+        Some(index) if (*index as usize) < account_keys.len() => {}
+        None => {}
+        _ => {
+            // Runtime should prevent this from ever happening
+            return Err(ParseInstructionError::InstructionKeyMismatch(
+                ParsableProgram::ZkElGamalProof,
+            ));
+        }
+    }
+    let mut accounts = instruction
+        .accounts
+        .iter()
+        .map(|index| account_keys[*index as usize].to_string());
+
+    if proof_instruction == ProofInstruction::CloseContextState {
+        check_num_zk_elgamal_proof_accounts(&instruction.accounts, 3)?;
+        return Ok(ParsedInstructionEnum {
+            instruction_type: "closeContextState".to_string(),
+            info: json!({
+                "contextStateAccount": accounts.next(),
+                "destination": accounts.next(),
+                "contextStateAuthority": accounts.next(),
+            }),
+        });
+    }
+
+    let mut value = json!({});
+    let map = value.as_object_mut().unwrap();
+    if instruction.data.len() == PROOF_FROM_ACCOUNT_DATA_LEN {
+        check_num_zk_elgamal_proof_accounts(&instruction.accounts, 1)?;
+        let offset = u32::from_le_bytes(instruction.data[1..].try_into().unwrap());
+        map.insert("proofAccount".to_string(), json!(accounts.next()));
+        map.insert("proofAccountOffset".to_string(), json!(offset));
+    } else {
+        map.insert(
+            "proofContext".to_string(),
+            parse_proof_context(proof_instruction, &instruction.data)?,
+        );
+    }
+    if let (Some(context_state_account), Some(context_state_authority)) =
+        (accounts.next(), accounts.next())
+    {
+        map.insert(
+            "contextStateAccount".to_string(),
+            json!(context_state_account),
+        );
+        map.insert(
+            "contextStateAuthority".to_string(),
+            json!(context_state_authority),
+        );
+    }
+
+    let instruction_type = match proof_instruction {
+        ProofInstruction::CloseContextState => unreachable!(),
+        ProofInstruction::VerifyZeroCiphertext => "verifyZeroCiphertext",
+        ProofInstruction::VerifyCiphertextCiphertextEquality => {
+            "verifyCiphertextCiphertextEquality"
+        }
+        ProofInstruction::VerifyCiphertextCommitmentEquality => {
+            "verifyCiphertextCommitmentEquality"
+        }
+        ProofInstruction::VerifyPubkeyValidity => "verifyPubkeyValidity",
+        ProofInstruction::VerifyPercentageWithCap => "verifyPercentageWithCap",
+        ProofInstruction::VerifyBatchedRangeProofU64 => "verifyBatchedRangeProofU64",
+        ProofInstruction::VerifyBatchedRangeProofU128 => "verifyBatchedRangeProofU128",
+        ProofInstruction::VerifyBatchedRangeProofU256 => "verifyBatchedRangeProofU256",
+        ProofInstruction::VerifyGroupedCiphertext2HandlesValidity => {
+            "verifyGroupedCiphertext2HandlesValidity"
+        }
+        ProofInstruction::VerifyBatchedGroupedCiphertext2HandlesValidity => {
+            "verifyBatchedGroupedCiphertext2HandlesValidity"
+        }
+        ProofInstruction::VerifyGroupedCiphertext3HandlesValidity => {
+            "verifyGroupedCiphertext3HandlesValidity"
+        }
+        ProofInstruction::VerifyBatchedGroupedCiphertext3HandlesValidity => {
+            "verifyBatchedGroupedCiphertext3HandlesValidity"
+        }
+    };
+    Ok(ParsedInstructionEnum {
+        instruction_type: instruction_type.to_string(),
+        info: value,
+    })
+}
+
+/// Decodes the public context of a proof given as instruction data. For confidential transfers,
+/// the grouped ciphertext validity contexts include the amount encrypted under the auditor
+/// ElGamal pubkey of the mint.
+fn parse_proof_context(
+    proof_instruction: ProofInstruction,
+    data: &[u8],
+) -> Result<Value, ParseInstructionError> {
+    let not_parsable =
+        || ParseInstructionError::InstructionNotParsable(ParsableProgram::ZkElGamalProof);
+    let value = match proof_instruction {
+        ProofInstruction::CloseContextState => return Err(not_parsable()),
+        ProofInstruction::VerifyZeroCiphertext => {
+            let context = ProofInstruction::proof_data::<
+                ZeroCiphertextProofData,
+                ZeroCiphertextProofContext,
+            >(data)
+            .ok_or_else(not_parsable)?
+            .context;
+            json!({
+                "pubkey": context.pubkey.to_string(),
+                "ciphertext": context.ciphertext.to_string(),
+            })
+        }
+        ProofInstruction::VerifyCiphertextCiphertextEquality => {
+            let context = ProofInstruction::proof_data::<
+                CiphertextCiphertextEqualityProofData,
+                CiphertextCiphertextEqualityProofContext,
+            >(data)
+            .ok_or_else(not_parsable)?
+            .context;
+            json!({
+                "firstPubkey": context.first_pubkey.to_string(),
+                "secondPubkey": context.second_pubkey.to_string(),
+                "firstCiphertext": context.first_ciphertext.to_string(),
+                "secondCiphertext": context.second_ciphertext.to_string(),
+            })
+        }
+        ProofInstruction::VerifyCiphertextCommitmentEquality => {
+            let context = ProofInstruction::proof_data::<
+                CiphertextCommitmentEqualityProofData,
+                CiphertextCommitmentEqualityProofContext,
+            >(data)
+            .ok_or_else(not_parsable)?
+            .context;
+            json!({
+                "pubkey": context.pubkey.to_string(),
+                "ciphertext": context.ciphertext.to_string(),
+                "commitment": context.commitment.to_string(),
+            })
+        }
+        ProofInstruction::VerifyPubkeyValidity => {
+            let context = ProofInstruction::proof_data::<
+                PubkeyValidityProofData,
+                PubkeyValidityProofContext,
+            >(data)
+            .ok_or_else(not_parsable)?
+            .context;
+            json!({
+                "pubkey": context.pubkey.to_string(),
+            })
+        }
+        ProofInstruction::VerifyPercentageWithCap => {
+            let context = ProofInstruction::proof_data::<
+                PercentageWithCapProofData,
+                PercentageWithCapProofContext,
+            >(data)
+            .ok_or_else(not_parsable)?
+            .context;
+            json!({
+                "percentageCommitment": context.percentage_commitment.to_string(),
+                "deltaCommitment": context.delta_commitment.to_string(),
+                "claimedCommitment": context.claimed_commitment.to_string(),
+                "maxValue": u64::from(context.max_value),
+            })
+        }
+        ProofInstruction::VerifyBatchedRangeProofU64 => parse_batched_range_proof_context(
+            &ProofInstruction::proof_data::<BatchedRangeProofU64Data, BatchedRangeProofContext>(
+                data,
+            )
+            .ok_or_else(not_parsable)?
+            .context,
+        ),
+        ProofInstruction::VerifyBatchedRangeProofU128 => parse_batched_range_proof_context(
+            &ProofInstruction::proof_data::<BatchedRangeProofU128Data, BatchedRangeProofContext>(
+                data,
+            )
+            .ok_or_else(not_parsable)?
+            .context,
+        ),
+        ProofInstruction::VerifyBatchedRangeProofU256 => parse_batched_range_proof_context(
+            &ProofInstruction::proof_data::<BatchedRangeProofU256Data, BatchedRangeProofContext>(
+                data,
+            )
+            .ok_or_else(not_parsable)?
+            .context,
+        ),
+        ProofInstruction::VerifyGroupedCiphertext2HandlesValidity => {
+            let context = ProofInstruction::proof_data::<
+                GroupedCiphertext2HandlesValidityProofData,
+                GroupedCiphertext2HandlesValidityProofContext,
+            >(data)
+            .ok_or_else(not_parsable)?
+            .context;
+            json!({
+                "firstPubkey": context.first_pubkey.to_string(),
+                "secondPubkey": context.second_pubkey.to_string(),
+                "groupedCiphertext": context.grouped_ciphertext.to_string(),
+            })
+        }
+        ProofInstruction::VerifyBatchedGroupedCiphertext2HandlesValidity => {
+            let context = ProofInstruction::proof_data::<
+                BatchedGroupedCiphertext2HandlesValidityProofData,
+                BatchedGroupedCiphertext2HandlesValidityProofContext,
+            >(data)
+            .ok_or_else(not_parsable)?
+            .context;
+            json!({
+                "firstPubkey": context.first_pubkey.to_string(),
+                "secondPubkey": context.second_pubkey.to_string(),
+                "groupedCiphertextLo": context.grouped_ciphertext_lo.to_string(),
+                "groupedCiphertextHi": context.grouped_ciphertext_hi.to_string(),
+            })
+        }
+        ProofInstruction::VerifyGroupedCiphertext3HandlesValidity => {
+            let context = ProofInstruction::proof_data::<
+                GroupedCiphertext3HandlesValidityProofData,
+                GroupedCiphertext3HandlesValidityProofContext,
+            >(data)
+            .ok_or_else(not_parsable)?
+            .context;
+            json!({
+                "firstPubkey": context.first_pubkey.to_string(),
+                "secondPubkey": context.second_pubkey.to_string(),
+                "thirdPubkey": context.third_pubkey.to_string(),
+                "groupedCiphertext": context.grouped_ciphertext.to_string(),
+            })
+        }
+        ProofInstruction::VerifyBatchedGroupedCiphertext3HandlesValidity => {
+            let context = ProofInstruction::proof_data::<
+                BatchedGroupedCiphertext3HandlesValidityProofData,
+                BatchedGroupedCiphertext3HandlesValidityProofContext,
+            >(data)
+            .ok_or_else(not_parsable)?
+            .context;
+            json!({
+                "firstPubkey": context.first_pubkey.to_string(),
+                "secondPubkey": context.second_pubkey.to_string(),
+                "thirdPubkey": context.third_pubkey.to_string(),
+                "groupedCiphertextLo": context.grouped_ciphertext_lo.to_string(),
+                "groupedCiphertextHi": context.grouped_ciphertext_hi.to_string(),
+            })
+        }
+    };
+    Ok(value)
+}
+
+fn parse_batched_range_proof_context(context: &BatchedRangeProofContext) -> Value {
+    // Unused commitment slots have a bit length of zero
+    let (commitments, bit_lengths): (Vec<_>, Vec<_>) = context
+        .commitments
+        .iter()
+        .zip(context.bit_lengths)
+        .filter(|(_, bit_length)| *bit_length > 0)
+        .map(|(commitment, bit_length)| (commitment.to_string(), bit_length))
+        .unzip();
+    json!({
+        "commitments": commitments,
+        "bitLengths": bit_lengths,
+    })
+}
+
+fn check_num_zk_elgamal_proof_accounts(
+    accounts: &[u8],
+    num: usize,
+) -> Result<(), ParseInstructionError> {
+    check_num_accounts(accounts, num, ParsableProgram::ZkElGamalProof)
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        solana_message::Message,
+        solana_pubkey::Pubkey,
+        spl_token_2022::solana_zk_sdk::{
+            encryption::elgamal::ElGamalKeypair,
+            zk_elgamal_proof_program::instruction::{close_context_state, ContextStateInfo},
+        },
+    };
+
+    #[test]
+    fn test_parse_zk_elgamal_proof_instruction_data_ix() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let proof_data = ZeroCiphertextProofData::new(&keypair, &ciphertext).unwrap();
+
+        let instruction =
+            ProofInstruction::VerifyZeroCiphertext.encode_verify_proof(None, &proof_data);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_zk_elgamal_proof(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "verifyZeroCiphertext".to_string(),
+                info: json!({
+                    "proofContext": {
+                        "pubkey": proof_data.context.pubkey.to_string(),
+                        "ciphertext": proof_data.context.ciphertext.to_string(),
+                    },
+                }),
+            }
+        );
+
+        let context_state_account = Pubkey::new_unique();
+        let context_state_authority = Pubkey::new_unique();
+        let instruction = ProofInstruction::VerifyZeroCiphertext.encode_verify_proof(
+            Some(ContextStateInfo {
+                context_state_account: &context_state_account,
+                context_state_authority: &context_state_authority,
+            }),
+            &proof_data,
+        );
+        let mut message = Message::new(&[instruction], None);
+        let parsed = parse_zk_elgamal_proof(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys, None),
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.info["contextStateAccount"],
+            json!(context_state_account.to_string())
+        );
+        assert_eq!(
+            parsed.info["contextStateAuthority"],
+            json!(context_state_authority.to_string())
+        );
+
+        // Truncated proof data
+        message.instructions[0].data.pop();
+        assert!(parse_zk_elgamal_proof(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys, None)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_zk_elgamal_proof_from_account_ix() {
+        let proof_account = Pubkey::new_unique();
+        let instruction = ProofInstruction::VerifyBatchedGroupedCiphertext3HandlesValidity
+            .encode_verify_proof_from_account(None, &proof_account, 42);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_zk_elgamal_proof(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "verifyBatchedGroupedCiphertext3HandlesValidity".to_string(),
+                info: json!({
+                    "proofAccount": proof_account.to_string(),
+                    "proofAccountOffset": 42,
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_zk_elgamal_proof_close_context_state_ix() {
+        let context_state_account = Pubkey::new_unique();
+        let context_state_authority = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let instruction = close_context_state(
+            ContextStateInfo {
+                context_state_account: &context_state_account,
+                context_state_authority: &context_state_authority,
+            },
+            &destination,
+        );
+        let mut message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_zk_elgamal_proof(
+                &message.instructions[0],
+                &AccountKeys::new(&message.account_keys, None)
+            )
+            .unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "closeContextState".to_string(),
+                info: json!({
+                    "contextStateAccount": context_state_account.to_string(),
+                    "destination": destination.to_string(),
+                    "contextStateAuthority": context_state_authority.to_string(),
+                }),
+            }
+        );
+        message.instructions[0].accounts.pop();
+        assert!(parse_zk_elgamal_proof(
+            &message.instructions[0],
+            &AccountKeys::new(&message.account_keys, None)
+        )
+        .is_err());
+    }
+}
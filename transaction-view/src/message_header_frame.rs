@@ -34,7 +34,7 @@ impl MessageHeaderFrame {
         let message_offset = *offset as u16;
 
         // Read the message prefix byte if present. This byte is present in V0
-        // transactions but not in legacy transactions.
+        // and V1 transactions but not in legacy transactions.
         // The message header begins immediately after the message prefix byte
         // if present.
         let message_prefix = read_byte(bytes, offset)?;
@@ -42,6 +42,7 @@ impl MessageHeaderFrame {
             let version = message_prefix & !MESSAGE_VERSION_PREFIX;
             match version {
                 0 => (TransactionVersion::V0, read_byte(bytes, offset)?),
+                1 => (TransactionVersion::V1, read_byte(bytes, offset)?),
                 _ => return Err(TransactionViewError::ParseError),
             }
         } else {
@@ -69,7 +70,7 @@ mod tests {
 
     #[test]
     fn test_invalid_version() {
-        let bytes = [0b1000_0001];
+        let bytes = [0b1000_0010];
         let mut offset = 0;
         assert!(MessageHeaderFrame::try_new(&bytes, &mut offset).is_err());
     }
@@ -109,4 +110,15 @@ mod tests {
         assert_eq!(header.num_readonly_signed_accounts, 1);
         assert_eq!(header.num_readonly_unsigned_accounts, 2);
     }
+
+    #[test]
+    fn test_v1_transaction_valid() {
+        let bytes = [MESSAGE_VERSION_PREFIX | 1, 5, 1, 2];
+        let mut offset = 0;
+        let header = MessageHeaderFrame::try_new(&bytes, &mut offset).unwrap();
+        assert!(matches!(header.version, TransactionVersion::V1));
+        assert_eq!(header.num_required_signatures, 5);
+        assert_eq!(header.num_readonly_signed_accounts, 1);
+        assert_eq!(header.num_readonly_unsigned_accounts, 2);
+    }
 }
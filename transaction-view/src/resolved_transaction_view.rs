@@ -55,9 +55,13 @@ impl<D: TransactionData> ResolvedTransactionView<D> {
         // verify that the number of readable and writable match up.
         // This is a basic sanity check to make sure we're not passing a totally
         // invalid set of resolved addresses.
-        // Additionally if it is a v0 transaction it *must* have resolved
+        // Additionally if it is a v0 or v1 transaction it *must* have resolved
         // addresses, even if they are empty.
-        if matches!(view.version(), TransactionVersion::V0) && resolved_addresses_ref.is_none() {
+        if matches!(
+            view.version(),
+            TransactionVersion::V0 | TransactionVersion::V1
+        ) && resolved_addresses_ref.is_none()
+        {
             return Err(TransactionViewError::AddressLookupMismatch);
         }
         if let Some(loaded_addresses) = resolved_addresses_ref {
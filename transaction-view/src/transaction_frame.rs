@@ -53,7 +53,9 @@ impl TransactionFrame {
                 total_writable_lookup_accounts: 0,
                 total_readonly_lookup_accounts: 0,
             },
-            TransactionVersion::V0 => AddressTableLookupFrame::try_new(bytes, &mut offset)?,
+            TransactionVersion::V0 | TransactionVersion::V1 => {
+                AddressTableLookupFrame::try_new(bytes, &mut offset)?
+            }
         };
 
         // Verify that the entire transaction was parsed.
@@ -260,7 +262,10 @@ impl TransactionFrame {
 mod tests {
     use {
         super::*,
-        solana_message::{v0, AddressLookupTableAccount, Message, MessageHeader, VersionedMessage},
+        solana_message::{
+            v0, AddressLookupTableAccount, Message, MessageHeader, VersionedMessage,
+            MESSAGE_VERSION_PREFIX,
+        },
         solana_pubkey::Pubkey,
         solana_signature::Signature,
         solana_system_interface::instruction::{self as system_instruction, SystemInstruction},
@@ -439,6 +444,22 @@ mod tests {
         verify_transaction_view_frame(&v0_with_single_lookup());
     }
 
+    #[test]
+    fn test_v1_with_lookup() {
+        let tx = v0_with_single_lookup();
+        let mut bytes = bincode::serialize(&tx).unwrap();
+        // Bump the version byte following the signatures.
+        let version_offset = 1 + core::mem::size_of::<Signature>();
+        assert_eq!(bytes[version_offset], MESSAGE_VERSION_PREFIX);
+        bytes[version_offset] = MESSAGE_VERSION_PREFIX | 1;
+
+        let frame = TransactionFrame::try_new(&bytes).unwrap();
+        assert!(matches!(frame.version(), TransactionVersion::V1));
+        assert_eq!(frame.num_static_account_keys(), 2);
+        assert_eq!(frame.num_address_table_lookups(), 1);
+        assert_eq!(frame.total_writable_lookup_accounts(), 1);
+    }
+
     #[test]
     fn test_trailing_byte() {
         let tx = simple_transfer();
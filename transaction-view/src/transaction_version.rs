@@ -5,4 +5,8 @@ pub enum TransactionVersion {
     #[default]
     Legacy = u8::MAX,
     V0 = 0,
+    /// Shares the layout of `V0`, with version byte 1. Reserved for the expanded account
+    /// addressing of the next message version, and rejected by the runtime until it can be
+    /// recorded in entries.
+    V1 = 1,
 }
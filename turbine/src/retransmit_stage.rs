@@ -7,7 +7,7 @@ use {
     lru::LruCache,
     rand::Rng,
     rayon::{prelude::*, ThreadPool, ThreadPoolBuilder},
-    solana_gossip::{cluster_info::ClusterInfo, contact_info::Protocol},
+    solana_gossip::{cluster_info::ClusterInfo, contact_info::Protocol, peer_filter::PeerFilter},
     solana_ledger::{
         leader_schedule_cache::LeaderScheduleCache,
         shred::{self, ShredId},
@@ -35,7 +35,7 @@ use {
         ops::AddAssign,
         sync::{
             atomic::{AtomicU64, AtomicUsize, Ordering},
-            Arc, RwLock,
+            Arc, RwLock, RwLockReadGuard,
         },
         thread::{self, Builder, JoinHandle},
         time::{Duration, Instant},
@@ -68,6 +68,7 @@ struct RetransmitStats {
     since: Instant,
     num_nodes: AtomicUsize,
     num_addrs_failed: AtomicUsize,
+    num_addrs_denied: AtomicUsize,
     num_loopback_errs: AtomicUsize,
     num_shreds: usize,
     num_shreds_skipped: AtomicUsize,
@@ -106,6 +107,7 @@ impl RetransmitStats {
             ("num_small_batches", self.num_small_batches, i64),
             ("num_nodes", *self.num_nodes.get_mut(), i64),
             ("num_addrs_failed", *self.num_addrs_failed.get_mut(), i64),
+            ("num_addrs_denied", *self.num_addrs_denied.get_mut(), i64),
             ("num_loopback_errs", *self.num_loopback_errs.get_mut(), i64),
             ("num_shreds", self.num_shreds, i64),
             (
@@ -243,6 +245,7 @@ fn retransmit(
         })
         .collect();
     let socket_addr_space = cluster_info.socket_addr_space();
+    let denied_peers = DeniedPeers::new(cluster_info);
     let record = |mut stats: HashMap<Slot, RetransmitSlotStats>,
                   (slot, root_distance, num_nodes)| {
         let now = timestamp();
@@ -262,6 +265,7 @@ fn retransmit(
                     shred_deduper,
                     &cache,
                     socket_addr_space,
+                    &denied_peers,
                     &retransmit_sockets[index % retransmit_sockets.len()],
                     quic_endpoint_sender,
                     stats,
@@ -280,6 +284,7 @@ fn retransmit(
                         shred_deduper,
                         &cache,
                         socket_addr_space,
+                        &denied_peers,
                         &retransmit_sockets[index % retransmit_sockets.len()],
                         quic_endpoint_sender,
                         stats,
@@ -301,13 +306,43 @@ fn retransmit(
     Ok(())
 }
 
+/// Retransmit destinations rejected by the operator's peer filter.
+/// Denied pubkeys are mapped to their TVU addresses, since the turbine tree only yields addresses.
+struct DeniedPeers<'a> {
+    peer_filter: RwLockReadGuard<'a, PeerFilter>,
+    addrs: HashSet<SocketAddr>,
+}
+
+impl<'a> DeniedPeers<'a> {
+    fn new(cluster_info: &'a ClusterInfo) -> Self {
+        let peer_filter = cluster_info.peer_filter();
+        let addrs = peer_filter
+            .denied_pubkeys()
+            .filter_map(|pubkey| {
+                cluster_info.lookup_contact_info(pubkey, |node| {
+                    [node.tvu(Protocol::UDP), node.tvu(Protocol::QUIC)]
+                })
+            })
+            .flatten()
+            .flatten()
+            .collect();
+        Self { peer_filter, addrs }
+    }
+
+    fn is_denied(&self, addr: &SocketAddr) -> bool {
+        self.addrs.contains(addr) || !self.peer_filter.is_allowed_ip(&addr.ip())
+    }
+}
+
 // Retransmit a single shred to all downstream nodes
+#[allow(clippy::too_many_arguments)]
 fn retransmit_shred(
     shred: shred::Payload,
     root_bank: &Bank,
     shred_deduper: &ShredDeduper,
     cache: &HashMap<Slot, (/*leader:*/ Pubkey, Arc<ClusterNodes<RetransmitStage>>)>,
     socket_addr_space: &SocketAddrSpace,
+    denied_peers: &DeniedPeers,
     socket: &UdpSocket,
     quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
     stats: &RetransmitStats,
@@ -324,7 +359,7 @@ fn retransmit_shred(
     }
     let mut compute_turbine_peers = Measure::start("turbine_start");
     let data_plane_fanout = cluster_nodes::get_data_plane_fanout(key.slot(), root_bank);
-    let (root_distance, addrs) = cluster_nodes
+    let (root_distance, mut addrs) = cluster_nodes
         .get_retransmit_addrs(slot_leader, &key, data_plane_fanout, socket_addr_space)
         .inspect_err(|err| match err {
             Error::Loopback { .. } => {
@@ -333,6 +368,13 @@ fn retransmit_shred(
             }
         })
         .ok()?;
+    if !denied_peers.peer_filter.is_empty() {
+        let num_addrs = addrs.len();
+        addrs.retain(|addr| !denied_peers.is_denied(addr));
+        stats
+            .num_addrs_denied
+            .fetch_add(num_addrs - addrs.len(), Ordering::Relaxed);
+    }
     compute_turbine_peers.stop();
     stats
         .compute_turbine_peers_total
@@ -477,6 +519,7 @@ impl RetransmitStats {
             since: now,
             num_nodes: AtomicUsize::default(),
             num_addrs_failed: AtomicUsize::default(),
+            num_addrs_denied: AtomicUsize::default(),
             num_loopback_errs: AtomicUsize::default(),
             num_shreds: 0usize,
             num_shreds_skipped: AtomicUsize::default(),
@@ -0,0 +1,472 @@
+#![allow(clippy::arithmetic_side_effects)]
+//! Checks that a host meets the system prerequisites of a validator before it is started.
+//!
+//! Every check prints a PASS, WARN, FAIL or SKIP line. The process exits with a non-zero status
+//! if any check failed, so that the checker can gate the validator's service unit.
+
+use {
+    clap::{crate_description, crate_name, value_t_or_exit, values_t, App, Arg},
+    solana_clap_utils::input_validators::is_parsable,
+    solana_entry::poh::compute_hash_time,
+    solana_sdk::clock::{DEFAULT_HASHES_PER_TICK, DEFAULT_MS_PER_SLOT, DEFAULT_TICKS_PER_SLOT},
+    std::{
+        fmt,
+        fs::{self, OpenOptions},
+        io::Write,
+        net::{SocketAddr, TcpListener, TcpStream, UdpSocket},
+        path::Path,
+        process::exit,
+        time::{Duration, Instant},
+    },
+};
+
+/// Should be kept in sync with the limit the blockstore raises the soft limit to
+const RECOMMENDED_NOFILE: u64 = 1_000_000;
+
+/// Should be kept in sync with the recommendations of the system monitor service
+const RECOMMENDED_SYSCTLS: &[(&str, u64)] = &[
+    ("net.core.rmem_max", 134_217_728),
+    ("net.core.wmem_max", 134_217_728),
+    ("vm.max_map_count", 1_000_000),
+];
+
+/// Largest clock error estimated by the kernel which is still considered synchronized
+const MAX_CLOCK_ERROR: Duration = Duration::from_millis(100);
+
+const ENTRYPOINT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "PASS"),
+            Self::Warn => write!(f, "WARN"),
+            Self::Fail => write!(f, "FAIL"),
+            Self::Skip => write!(f, "SKIP"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct CheckResult {
+    name: String,
+    status: Status,
+    detail: String,
+}
+
+impl CheckResult {
+    fn new(name: impl Into<String>, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+
+    /// Passes if `value` is at least `minimum`, failing otherwise
+    fn at_least(name: impl Into<String>, value: u64, minimum: u64, unit: &str) -> Self {
+        let status = if value >= minimum {
+            Status::Pass
+        } else {
+            Status::Fail
+        };
+        Self::new(
+            name,
+            status,
+            format!("{value}{unit}, minimum {minimum}{unit}"),
+        )
+    }
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.status, self.name, self.detail)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn check_clock_sync() -> CheckResult {
+    const NAME: &str = "clock sync";
+    // SAFETY: a zeroed timex with no modes set only queries the kernel clock state
+    let mut timex: libc::timex = unsafe { std::mem::zeroed() };
+    let state = unsafe { libc::adjtimex(&mut timex) };
+    if state < 0 {
+        return CheckResult::new(
+            NAME,
+            Status::Warn,
+            format!("adjtimex failed: {}", std::io::Error::last_os_error()),
+        );
+    }
+    evaluate_clock_state(
+        state == libc::TIME_ERROR,
+        Duration::from_micros(timex.maxerror.max(0) as u64),
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+fn check_clock_sync() -> CheckResult {
+    CheckResult::new("clock sync", Status::Skip, "only supported on Linux")
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn evaluate_clock_state(unsynchronized: bool, max_error: Duration) -> CheckResult {
+    const NAME: &str = "clock sync";
+    if unsynchronized {
+        CheckResult::new(
+            NAME,
+            Status::Fail,
+            "clock is not synchronized, is an NTP daemon running?",
+        )
+    } else if max_error > MAX_CLOCK_ERROR {
+        CheckResult::new(
+            NAME,
+            Status::Warn,
+            format!(
+                "synchronized, but the estimated error of {}ms exceeds {}ms",
+                max_error.as_millis(),
+                MAX_CLOCK_ERROR.as_millis()
+            ),
+        )
+    } else {
+        CheckResult::new(
+            NAME,
+            Status::Pass,
+            format!("synchronized, estimated error {}ms", max_error.as_millis()),
+        )
+    }
+}
+
+#[cfg(unix)]
+fn check_open_file_limit() -> CheckResult {
+    const NAME: &str = "open file limit";
+    let mut nofile = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut nofile) } != 0 {
+        return CheckResult::new(NAME, Status::Fail, "getrlimit(RLIMIT_NOFILE) failed");
+    }
+    // The validator raises the soft limit itself, it only needs the hard limit to allow it
+    CheckResult::at_least(NAME, nofile.rlim_max as u64, RECOMMENDED_NOFILE, "")
+}
+
+#[cfg(not(unix))]
+fn check_open_file_limit() -> CheckResult {
+    CheckResult::new("open file limit", Status::Skip, "only supported on Unix")
+}
+
+#[cfg(target_os = "linux")]
+fn check_sysctls() -> Vec<CheckResult> {
+    RECOMMENDED_SYSCTLS
+        .iter()
+        .map(|(key, recommended)| {
+            let path = Path::new("/proc/sys").join(key.replace('.', "/"));
+            match fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|value| value.trim().parse::<u64>().map_err(|err| err.to_string()))
+            {
+                Ok(value) => CheckResult::at_least(*key, value, *recommended, ""),
+                Err(err) => CheckResult::new(
+                    *key,
+                    Status::Warn,
+                    format!("failed to read {path:?}: {err}"),
+                ),
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn check_sysctls() -> Vec<CheckResult> {
+    RECOMMENDED_SYSCTLS
+        .iter()
+        .map(|(key, _)| CheckResult::new(*key, Status::Skip, "only supported on Linux"))
+        .collect()
+}
+
+fn check_disk_throughput(dir: &Path, test_size_mb: u64, min_mb_per_second: u64) -> CheckResult {
+    let name = format!("disk throughput of {dir:?}");
+    let path = dir.join("tachyon-validator-check.tmp");
+    let chunk = vec![0xa5u8; 1024 * 1024];
+    let start = Instant::now();
+    let result = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&path)
+        .and_then(|mut file| {
+            for _ in 0..test_size_mb {
+                file.write_all(&chunk)?;
+            }
+            file.sync_all()
+        });
+    let elapsed = start.elapsed();
+    let _ = fs::remove_file(&path);
+    match result {
+        Ok(()) => {
+            let mb_per_second = (test_size_mb as f64 / elapsed.as_secs_f64()) as u64;
+            CheckResult::at_least(name, mb_per_second, min_mb_per_second, "MB/s")
+        }
+        Err(err) => CheckResult::new(
+            name,
+            Status::Fail,
+            format!("failed to write {path:?}: {err}"),
+        ),
+    }
+}
+
+fn check_poh_speed(hash_samples: u64, target_hashes_per_second: u64) -> CheckResult {
+    let hash_time = compute_hash_time(hash_samples);
+    let hashes_per_second = (hash_samples as f64 / hash_time.as_secs_f64()) as u64;
+    CheckResult::at_least(
+        "PoH speed",
+        hashes_per_second,
+        target_hashes_per_second,
+        " hashes/s",
+    )
+}
+
+/// Checks that the entrypoint's gossip port accepts connections and reports its shred version,
+/// then optionally that the entrypoint can reach the local gossip port
+fn check_entrypoint(entrypoint: &SocketAddr, gossip_port: Option<u16>) -> Vec<CheckResult> {
+    let name = format!("entrypoint {entrypoint}");
+    if let Err(err) = TcpStream::connect_timeout(entrypoint, ENTRYPOINT_CONNECT_TIMEOUT) {
+        return vec![CheckResult::new(
+            name,
+            Status::Fail,
+            format!("unable to connect: {err}"),
+        )];
+    }
+    let mut results = vec![
+        match solana_net_utils::get_cluster_shred_version(entrypoint) {
+            Ok(shred_version) => CheckResult::new(
+                name,
+                Status::Pass,
+                format!("reachable, shred version {shred_version}"),
+            ),
+            Err(err) => CheckResult::new(
+                name,
+                Status::Fail,
+                format!("reachable, but the IP echo request failed: {err}"),
+            ),
+        },
+    ];
+
+    if let Some(gossip_port) = gossip_port {
+        let name = format!("gossip port {gossip_port} from {entrypoint}");
+        let bind_addr = SocketAddr::from(([0, 0, 0, 0], gossip_port));
+        results.push(
+            match (TcpListener::bind(bind_addr), UdpSocket::bind(bind_addr)) {
+                (Ok(tcp_listener), Ok(udp_socket)) => {
+                    let reachable =
+                        solana_net_utils::verify_all_reachable_tcp(entrypoint, vec![tcp_listener])
+                            && solana_net_utils::verify_all_reachable_udp(
+                                entrypoint,
+                                &[&udp_socket],
+                            );
+                    if reachable {
+                        CheckResult::new(name, Status::Pass, "reachable over TCP and UDP")
+                    } else {
+                        CheckResult::new(
+                            name,
+                            Status::Fail,
+                            "unreachable, check the firewall and port forwarding",
+                        )
+                    }
+                }
+                (Err(err), _) | (_, Err(err)) => CheckResult::new(
+                    name,
+                    Status::Fail,
+                    format!("unable to bind, is a validator already running? {err}"),
+                ),
+            },
+        );
+    }
+    results
+}
+
+fn main() {
+    solana_logger::setup_with_default("solana=info");
+
+    let default_hashes_per_second =
+        (DEFAULT_HASHES_PER_TICK * DEFAULT_TICKS_PER_SLOT * 1000 / DEFAULT_MS_PER_SLOT).to_string();
+    let default_hash_samples = (DEFAULT_HASHES_PER_TICK * DEFAULT_TICKS_PER_SLOT).to_string();
+
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(solana_version::version!())
+        .arg(
+            Arg::with_name("entrypoint")
+                .long("entrypoint")
+                .value_name("HOST:PORT")
+                .takes_value(true)
+                .multiple(true)
+                .validator(solana_net_utils::is_host_port)
+                .help("Check that the gossip entrypoint at this address is reachable"),
+        )
+        .arg(
+            Arg::with_name("gossip_port")
+                .long("gossip-port")
+                .value_name("PORT")
+                .takes_value(true)
+                .requires("entrypoint")
+                .validator(is_parsable::<u16>)
+                .help("Check that the entrypoints can reach this local gossip port"),
+        )
+        .arg(
+            Arg::with_name("ledger_path")
+                .long("ledger")
+                .value_name("DIR")
+                .takes_value(true)
+                .help("Measure the write throughput of the disk holding this directory"),
+        )
+        .arg(
+            Arg::with_name("disk_test_size_mb")
+                .long("disk-test-size")
+                .value_name("MEGABYTES")
+                .takes_value(true)
+                .default_value("1024")
+                .validator(is_parsable::<u64>)
+                .help("Size of the file written to measure the disk throughput"),
+        )
+        .arg(
+            Arg::with_name("min_disk_throughput")
+                .long("min-disk-throughput")
+                .value_name("MB_PER_SECOND")
+                .takes_value(true)
+                .default_value("500")
+                .validator(is_parsable::<u64>)
+                .help("Lowest acceptable disk write throughput"),
+        )
+        .arg(
+            Arg::with_name("target_hashes_per_second")
+                .long("target-hashes-per-second")
+                .value_name("NUM")
+                .takes_value(true)
+                .default_value(&default_hashes_per_second)
+                .validator(is_parsable::<u64>)
+                .help("PoH hash rate the cluster's genesis configuration requires"),
+        )
+        .arg(
+            Arg::with_name("poh_hash_samples")
+                .long("poh-hash-samples")
+                .value_name("NUM")
+                .takes_value(true)
+                .default_value(&default_hash_samples)
+                .validator(is_parsable::<u64>)
+                .help("Number of hashes computed to measure the PoH hash rate"),
+        )
+        .get_matches();
+
+    let mut results = vec![
+        check_clock_sync(),
+        check_open_file_limit(),
+        check_poh_speed(
+            value_t_or_exit!(matches, "poh_hash_samples", u64),
+            value_t_or_exit!(matches, "target_hashes_per_second", u64),
+        ),
+    ];
+    results.extend(check_sysctls());
+
+    match matches.value_of("ledger_path") {
+        Some(ledger_path) => results.push(check_disk_throughput(
+            Path::new(ledger_path),
+            value_t_or_exit!(matches, "disk_test_size_mb", u64),
+            value_t_or_exit!(matches, "min_disk_throughput", u64),
+        )),
+        None => results.push(CheckResult::new(
+            "disk throughput",
+            Status::Skip,
+            "no --ledger directory given",
+        )),
+    }
+
+    let gossip_port = matches
+        .value_of("gossip_port")
+        .map(|port| port.parse().unwrap());
+    let entrypoints = values_t!(matches, "entrypoint", String).unwrap_or_default();
+    if entrypoints.is_empty() {
+        results.push(CheckResult::new(
+            "entrypoints",
+            Status::Skip,
+            "no --entrypoint given",
+        ));
+    }
+    for entrypoint in entrypoints {
+        match solana_net_utils::parse_host_port(&entrypoint) {
+            Ok(entrypoint) => results.extend(check_entrypoint(&entrypoint, gossip_port)),
+            Err(err) => results.push(CheckResult::new(
+                format!("entrypoint {entrypoint}"),
+                Status::Fail,
+                err,
+            )),
+        }
+    }
+
+    for result in &results {
+        println!("{result}");
+    }
+    let failures = results
+        .iter()
+        .filter(|result| result.status == Status::Fail)
+        .count();
+    if failures > 0 {
+        eprintln!("{failures} of {} checks failed", results.len());
+        exit(1);
+    }
+    println!("All checks passed");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_least() {
+        let result = CheckResult::at_least("disk", 600, 500, "MB/s");
+        assert_eq!(result.status, Status::Pass);
+        assert_eq!(result.to_string(), "[PASS] disk: 600MB/s, minimum 500MB/s");
+        assert_eq!(
+            CheckResult::at_least("disk", 500, 500, "").status,
+            Status::Pass
+        );
+        assert_eq!(
+            CheckResult::at_least("disk", 499, 500, "").status,
+            Status::Fail
+        );
+    }
+
+    #[test]
+    fn test_evaluate_clock_state() {
+        assert_eq!(
+            evaluate_clock_state(true, Duration::ZERO).status,
+            Status::Fail
+        );
+        assert_eq!(
+            evaluate_clock_state(false, Duration::from_millis(5)).status,
+            Status::Pass
+        );
+        assert_eq!(
+            evaluate_clock_state(false, Duration::from_secs(1)).status,
+            Status::Warn
+        );
+    }
+
+    #[test]
+    fn test_check_disk_throughput() {
+        let dir = std::env::temp_dir();
+        let result = check_disk_throughput(&dir, 1, 0);
+        assert_eq!(result.status, Status::Pass);
+        assert!(!dir.join("tachyon-validator-check.tmp").exists());
+
+        let result = check_disk_throughput(&dir.join("nonexistent-dir"), 1, 0);
+        assert_eq!(result.status, Status::Fail);
+    }
+}
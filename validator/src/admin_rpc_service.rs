@@ -1,4 +1,5 @@
 use {
+    crate::{logger, self_profile},
     crossbeam_channel::Sender,
     jsonrpc_core::{BoxFuture, ErrorCode, MetaIoHandler, Metadata, Result},
     jsonrpc_core_client::{transports::ipc, RpcError},
@@ -8,28 +9,43 @@ use {
     },
     log::*,
     serde::{de::Deserializer, Deserialize, Serialize},
-    solana_accounts_db::accounts_index::AccountIndex,
+    solana_accounts_db::{accounts_index::AccountIndex, ReadOnlyCacheForkStats},
     solana_core::{
         admin_rpc_post_init::AdminRpcRequestMetadataPostInit,
+        banking_stage::conflict_graph::{
+            conflict_graph_sampler, ConflictGraphSummary, DEFAULT_MAX_SAMPLED_TRANSACTIONS,
+        },
         consensus::{tower_storage::TowerStorage, Tower},
         repair::repair_service,
         validator::ValidatorStartProgress,
     },
     solana_geyser_plugin_manager::GeyserPluginManagerRequest,
-    solana_gossip::contact_info::{ContactInfo, Protocol, SOCKET_ADDR_UNSPECIFIED},
-    solana_rpc::rpc::verify_pubkey,
+    solana_gossip::{
+        contact_info::{ContactInfo, Protocol, SOCKET_ADDR_UNSPECIFIED},
+        crds::Cursor,
+        peer_filter::{PeerFilter, PeerFilterEntry},
+    },
+    solana_rpc::{
+        rpc::verify_pubkey,
+        rpc_request_stats::{rpc_request_stats, RpcMethodStats, RpcTracedRequest},
+    },
     solana_rpc_client_api::{config::RpcAccountIndex, custom_error::RpcCustomError},
     solana_sdk::{
+        clock::{Slot, NUM_CONSECUTIVE_LEADER_SLOTS},
         exit::Exit,
+        hash::Hash,
         pubkey::Pubkey,
         signature::{read_keypair_file, Keypair, Signer},
     },
+    solana_streamer::{packet_drop_stats::packet_drop_stats, quic::connection_table_summaries},
+    solana_svm::cpi_stats::{ProgramCpiStats, CPI_COUNT_BUCKETS},
     std::{
         collections::{HashMap, HashSet},
         env, error,
         fmt::{self, Display},
         net::SocketAddr,
         path::{Path, PathBuf},
+        str::FromStr,
         sync::{Arc, RwLock},
         thread::{self, Builder},
         time::{Duration, SystemTime},
@@ -37,6 +53,9 @@ use {
     tokio::runtime::Runtime,
 };
 
+/// How far ahead of the working bank the `connections` query looks for upcoming leaders
+const CONNECTIONS_LEADER_LOOKAHEAD_SLOTS: u64 = 50 * NUM_CONSECUTIVE_LEADER_SLOTS;
+
 #[derive(Clone)]
 pub struct AdminRpcRequestMetadata {
     pub rpc_addr: Option<SocketAddr>,
@@ -89,6 +108,215 @@ pub struct AdminRpcRepairWhitelist {
     pub whitelist: Vec<Pubkey>,
 }
 
+/// Peer allow and deny lists, as pubkeys and IP ranges
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct AdminRpcPeerFilter {
+    pub allowed: Vec<String>,
+    pub denied: Vec<String>,
+}
+
+impl From<&PeerFilter> for AdminRpcPeerFilter {
+    fn from(peer_filter: &PeerFilter) -> Self {
+        Self {
+            allowed: peer_filter
+                .allowed()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            denied: peer_filter
+                .denied()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcConnectionInfo {
+    pub cache: String,
+    pub address: SocketAddr,
+    /// Identity of the node advertising `address` in gossip, if known
+    pub identity: Option<String>,
+    /// First upcoming slot in which `identity` is the leader
+    pub next_leader_slot: Option<Slot>,
+    pub stake: u64,
+    pub pool_size: usize,
+    pub connected: usize,
+    pub open_streams: u64,
+    pub sent_batches: u64,
+    pub failed_batches: u64,
+}
+
+impl AdminRpcConnectionInfo {
+    pub fn error_rate(&self) -> f64 {
+        let total = self.sent_batches.saturating_add(self.failed_batches);
+        if total == 0 {
+            0.0
+        } else {
+            self.failed_batches as f64 / total as f64
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct AdminRpcConnections {
+    pub connections: Vec<AdminRpcConnectionInfo>,
+}
+
+/// CPU time and disk IO of a group of threads sharing a name but for a numeric suffix
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcThreadProfile {
+    pub name: String,
+    pub threads: usize,
+    /// Share of one core, so a group of threads may exceed 100%
+    pub cpu_percent: f64,
+    pub read_bytes_per_second: u64,
+    pub write_bytes_per_second: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcComponentIo {
+    pub component: String,
+    pub read_bytes_per_second: u64,
+    pub write_bytes_per_second: u64,
+}
+
+/// Allocator statistics, only reported when built with the `jemalloc-profiling` feature
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcAllocationStats {
+    pub allocated_bytes: u64,
+    pub resident_bytes: u64,
+    /// Growth of the allocated bytes over the sample, negative when more memory was freed
+    pub net_allocated_bytes_per_second: i64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct AdminRpcSelfProfile {
+    pub interval_ms: u64,
+    pub total_cpu_percent: f64,
+    /// Sorted by descending CPU usage
+    pub threads: Vec<AdminRpcThreadProfile>,
+    pub io: Vec<AdminRpcComponentIo>,
+    pub allocation: Option<AdminRpcAllocationStats>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcHardForkProposal {
+    pub identity: String,
+    pub slot: Slot,
+    pub bank_hash: Option<String>,
+    pub expected_shred_version: Option<u16>,
+    pub wallclock: u64,
+    pub stake: u64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct AdminRpcHardForkProposals {
+    pub total_stake: u64,
+    pub proposals: Vec<AdminRpcHardForkProposal>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcProgramCpiStats {
+    pub program_id: String,
+    #[serde(flatten)]
+    pub stats: ProgramCpiStats,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcCpiStats {
+    pub total: ProgramCpiStats,
+    /// Sorted by descending number of instructions
+    pub programs: Vec<AdminRpcProgramCpiStats>,
+    /// Combined stats of the programs beyond the tracked ones
+    pub other: ProgramCpiStats,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcReadOnlyCacheForkStats {
+    pub fork: Slot,
+    #[serde(flatten)]
+    pub stats: ReadOnlyCacheForkStats,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcReadOnlyAccountsCache {
+    pub entries: usize,
+    pub data_size: usize,
+    pub max_data_size_lo: usize,
+    pub max_data_size_hi: usize,
+    /// Sorted by ascending fork slot
+    pub forks: Vec<AdminRpcReadOnlyCacheForkStats>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcPacketDrops {
+    pub reason: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcPacketDropStats {
+    /// Dropped packets, including the connections refused by the rate limits
+    pub total: u64,
+    /// Sorted by descending count
+    pub drops: Vec<AdminRpcPacketDrops>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcIpAddrConnections {
+    pub ip: String,
+    pub connections: usize,
+}
+
+/// The connection tables of an inbound QUIC server
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcQuicServer {
+    /// Metrics name of the server
+    pub name: String,
+    pub staked_peers: usize,
+    pub staked_connections: usize,
+    pub unstaked_peers: usize,
+    pub unstaked_connections: usize,
+    pub handshakes_in_progress: usize,
+    /// Sorted by descending connections
+    pub top_ipaddrs: Vec<AdminRpcIpAddrConnections>,
+    pub evictions: u64,
+    pub rate_limited: u64,
+    pub refused_per_ipaddr: u64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct AdminRpcQuicServers {
+    pub servers: Vec<AdminRpcQuicServer>,
+}
+
+/// A sample of the conflict graph of a leader slot, written to DOT and JSON files
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcConflictGraph {
+    pub summary: ConflictGraphSummary,
+    pub dot_path: String,
+    pub json_path: String,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct AdminRpcRequestStats {
+    /// Sorted by descending total latency
+    pub methods: Vec<RpcMethodStats>,
+    /// Slowest requests of the last minutes, slowest first
+    pub slowest: Vec<RpcTracedRequest>,
+}
+
 impl From<ContactInfo> for AdminRpcContactInfo {
     fn from(node: ContactInfo) -> Self {
         macro_rules! unwrap_socket {
@@ -134,12 +362,395 @@ impl Display for AdminRpcContactInfo {
     }
 }
 
+impl Display for AdminRpcConnections {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<32} {:<22} {:<44} {:>11} {:>20} {:>5} {:>7} {:>7} {:>10} {:>8} {:>6}",
+            "Cache",
+            "Address",
+            "Identity",
+            "Next Leader",
+            "Stake",
+            "Pool",
+            "Conns",
+            "Streams",
+            "Sent",
+            "Failed",
+            "Err %",
+        )?;
+        for connection in &self.connections {
+            writeln!(
+                f,
+                "{:<32} {:<22} {:<44} {:>11} {:>20} {:>5} {:>7} {:>7} {:>10} {:>8} {:>6.2}",
+                connection.cache,
+                connection.address,
+                connection.identity.as_deref().unwrap_or("-"),
+                connection
+                    .next_leader_slot
+                    .map(|slot| slot.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                connection.stake,
+                connection.pool_size,
+                connection.connected,
+                connection.open_streams,
+                connection.sent_batches,
+                connection.failed_batches,
+                connection.error_rate() * 100.0,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcSelfProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const KIB: f64 = 1024.0;
+        writeln!(
+            f,
+            "CPU: {:.1}% over {}ms",
+            self.total_cpu_percent, self.interval_ms
+        )?;
+        for io in &self.io {
+            writeln!(
+                f,
+                "IO {:<12} read {:>10.1} KiB/s  write {:>10.1} KiB/s",
+                io.component,
+                io.read_bytes_per_second as f64 / KIB,
+                io.write_bytes_per_second as f64 / KIB,
+            )?;
+        }
+        match &self.allocation {
+            Some(allocation) => writeln!(
+                f,
+                "Memory: {:.1} MiB allocated, {:.1} MiB resident, {:+.1} MiB/s",
+                allocation.allocated_bytes as f64 / KIB / KIB,
+                allocation.resident_bytes as f64 / KIB / KIB,
+                allocation.net_allocated_bytes_per_second as f64 / KIB / KIB,
+            )?,
+            None => writeln!(
+                f,
+                "Memory: unavailable, requires the jemalloc-profiling feature"
+            )?,
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<16} {:>7} {:>8} {:>14} {:>14}",
+            "Thread", "Count", "CPU %", "Read KiB/s", "Write KiB/s",
+        )?;
+        for thread in &self.threads {
+            writeln!(
+                f,
+                "{:<16} {:>7} {:>8.1} {:>14.1} {:>14.1}",
+                thread.name,
+                thread.threads,
+                thread.cpu_percent,
+                thread.read_bytes_per_second as f64 / KIB,
+                thread.write_bytes_per_second as f64 / KIB,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcHardForkProposals {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.proposals.is_empty() {
+            return writeln!(f, "No hard fork proposals");
+        }
+        // Group proposals with identical restart parameters
+        let mut groups: Vec<(_, Vec<&AdminRpcHardForkProposal>)> = vec![];
+        for proposal in &self.proposals {
+            let key = (
+                proposal.slot,
+                proposal.bank_hash.as_deref(),
+                proposal.expected_shred_version,
+            );
+            match groups.iter_mut().find(|(group_key, _)| *group_key == key) {
+                Some((_, group)) => group.push(proposal),
+                None => groups.push((key, vec![proposal])),
+            }
+        }
+        let group_stake =
+            |group: &[&AdminRpcHardForkProposal]| group.iter().map(|p| p.stake).sum::<u64>();
+        groups.sort_by_key(|(_, group)| std::cmp::Reverse(group_stake(group)));
+
+        for ((slot, bank_hash, expected_shred_version), group) in &groups {
+            let stake = group_stake(group);
+            writeln!(
+                f,
+                "Hard fork at slot {slot}, bank hash {}, shred version {}: {:.2}% of stake, {} \
+                 proposal(s)",
+                bank_hash.unwrap_or("unknown"),
+                expected_shred_version
+                    .map(|shred_version| shred_version.to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                100. * stake as f64 / self.total_stake.max(1) as f64,
+                group.len(),
+            )?;
+            for proposal in group {
+                writeln!(f, "  {:<44} {:>20}", proposal.identity, proposal.stake)?;
+            }
+        }
+        if groups.len() > 1 {
+            writeln!(
+                f,
+                "WARNING: {} conflicting hard fork proposals are advertised",
+                groups.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcCpiStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const COUNT_BUCKETS: [&str; CPI_COUNT_BUCKETS] =
+            ["0", "1", "2-3", "4-7", "8-15", "16-31", "32+"];
+
+        if self.total.instructions == 0 {
+            return writeln!(f, "No instructions executed");
+        }
+        let percent = |count: u64| 100. * count as f64 / self.total.instructions as f64;
+        writeln!(
+            f,
+            "{} instructions, {} CPIs, {} exceeded the max instruction stack depth",
+            self.total.instructions, self.total.cpis, self.total.depth_exceeded,
+        )?;
+        writeln!(f, "Max stack depth:")?;
+        for (index, count) in self.total.max_depth.iter().enumerate() {
+            writeln!(
+                f,
+                "  {:>5} {count:>14} {:>7.3}%",
+                index + 1,
+                percent(*count)
+            )?;
+        }
+        writeln!(f, "CPIs per instruction:")?;
+        for (bucket, count) in COUNT_BUCKETS.iter().zip(self.total.cpi_count) {
+            writeln!(f, "  {bucket:>5} {count:>14} {:>7.3}%", percent(count))?;
+        }
+        writeln!(
+            f,
+            "{:<44} {:>14} {:>14} {:>9} {:>14}",
+            "Program", "Instructions", "CPIs", "Max depth", "Depth exceeded"
+        )?;
+        for program in &self.programs {
+            let max_depth = program
+                .stats
+                .max_depth
+                .iter()
+                .rposition(|count| *count > 0)
+                .map_or(0, |index| index + 1);
+            writeln!(
+                f,
+                "{:<44} {:>14} {:>14} {:>9} {:>14}",
+                program.program_id,
+                program.stats.instructions,
+                program.stats.cpis,
+                max_depth,
+                program.stats.depth_exceeded,
+            )?;
+        }
+        if self.other.instructions > 0 {
+            let max_depth = self
+                .other
+                .max_depth
+                .iter()
+                .rposition(|count| *count > 0)
+                .map_or(0, |index| index + 1);
+            writeln!(
+                f,
+                "{:<44} {:>14} {:>14} {:>9} {:>14}",
+                "Other programs",
+                self.other.instructions,
+                self.other.cpis,
+                max_depth,
+                self.other.depth_exceeded,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcReadOnlyAccountsCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} entries, {} bytes, watermarks {} and {} bytes",
+            self.entries, self.data_size, self.max_data_size_lo, self.max_data_size_hi,
+        )?;
+        writeln!(
+            f,
+            "{:>12} {:>14} {:>14} {:>14} {:>14} {:>8}",
+            "Fork", "Hits", "Misses", "Stores", "Evictions", "Hit rate"
+        )?;
+        for AdminRpcReadOnlyCacheForkStats { fork, stats } in &self.forks {
+            let loads = stats.hits.saturating_add(stats.misses);
+            let hit_rate = if loads == 0 {
+                0.
+            } else {
+                100. * stats.hits as f64 / loads as f64
+            };
+            writeln!(
+                f,
+                "{fork:>12} {:>14} {:>14} {:>14} {:>14} {hit_rate:>7.2}%",
+                stats.hits, stats.misses, stats.stores, stats.evicts,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcPacketDropStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.total == 0 {
+            return writeln!(f, "No packets dropped");
+        }
+        writeln!(f, "{:<32} {:>14} {:>8}", "Reason", "Dropped", "Share")?;
+        for AdminRpcPacketDrops { reason, count } in &self.drops {
+            writeln!(
+                f,
+                "{reason:<32} {count:>14} {:>7.2}%",
+                100. * *count as f64 / self.total as f64
+            )?;
+        }
+        writeln!(f, "{:<32} {:>14}", "total", self.total)?;
+        writeln!(
+            f,
+            "rate_limit counts connections refused by the QUIC servers, which may each have \
+             carried any number of packets"
+        )
+    }
+}
+
+impl Display for AdminRpcQuicServers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.servers.is_empty() {
+            return writeln!(f, "No QUIC servers have reported yet");
+        }
+        for server in &self.servers {
+            writeln!(f, "{}:", server.name)?;
+            writeln!(
+                f,
+                "  Staked:     {} connections from {} peers",
+                server.staked_connections, server.staked_peers
+            )?;
+            writeln!(
+                f,
+                "  Unstaked:   {} connections from {} peers",
+                server.unstaked_connections, server.unstaked_peers
+            )?;
+            writeln!(f, "  Handshakes: {}", server.handshakes_in_progress)?;
+            writeln!(
+                f,
+                "  Since start: {} evicted, {} rate limited, {} refused per IP address",
+                server.evictions, server.rate_limited, server.refused_per_ipaddr
+            )?;
+            if !server.top_ipaddrs.is_empty() {
+                writeln!(f, "  {:<40} {:>11}", "IP address", "Connections")?;
+                for AdminRpcIpAddrConnections { ip, connections } in &server.top_ipaddrs {
+                    writeln!(f, "  {ip:<40} {connections:>11}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcRequestStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.methods.is_empty() {
+            return writeln!(f, "No RPC requests served");
+        }
+        writeln!(
+            f,
+            "{:<40} {:>12} {:>8} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "Method", "Requests", "Errors", "Mean(us)", "p50(us)", "p90(us)", "p99(us)", "Max(us)"
+        )?;
+        for stats in &self.methods {
+            writeln!(
+                f,
+                "{:<40} {:>12} {:>7.2}% {:>10} {:>10} {:>10} {:>10} {:>10}",
+                stats.method,
+                stats.count,
+                100. * stats.errors as f64 / stats.count.max(1) as f64,
+                stats.mean_us,
+                stats.p50_us,
+                stats.p90_us,
+                stats.p99_us,
+                stats.max_us,
+            )?;
+        }
+        if !self.slowest.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Slowest recent requests:")?;
+            for request in &self.slowest {
+                writeln!(
+                    f,
+                    "  {:>10}us {:>5}s ago{} {} {}",
+                    request.duration_us,
+                    request.age_secs,
+                    if request.is_error { " (error)" } else { "" },
+                    request.method,
+                    request.params,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcConflictGraph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let summary = &self.summary;
+        writeln!(f, "Conflict graph of slot {}:", summary.slot)?;
+        writeln!(f, "  Transactions:           {}", summary.transactions)?;
+        writeln!(f, "  Conflicts:              {}", summary.conflicts)?;
+        writeln!(f, "  Longest conflict chain: {}", summary.depth)?;
+        writeln!(
+            f,
+            "  Average parallel width: {:.2}",
+            summary.average_parallel_width
+        )?;
+        writeln!(
+            f,
+            "  Shared read accounts:   {} accounts, {} read locks",
+            summary.shared_read_accounts, summary.shared_read_locks
+        )?;
+        if !summary.hot_accounts.is_empty() {
+            writeln!(
+                f,
+                "  {:<44} {:>9} {:>11} {:>10}",
+                "Hot account", "Conflicts", "Write locks", "Read locks"
+            )?;
+            for account in &summary.hot_accounts {
+                writeln!(
+                    f,
+                    "  {:<44} {:>9} {:>11} {:>10}",
+                    account.pubkey, account.conflicts, account.write_locks, account.read_locks
+                )?;
+            }
+        }
+        writeln!(f, "DOT: {}", self.dot_path)?;
+        writeln!(f, "JSON: {}", self.json_path)
+    }
+}
+
 impl Display for AdminRpcRepairWhitelist {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "Repair whitelist: {:?}", &self.whitelist)
     }
 }
 
+impl Display for AdminRpcPeerFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Allowed peers: {:?}", &self.allowed)?;
+        writeln!(f, "Denied peers: {:?}", &self.denied)
+    }
+}
+
 #[rpc]
 pub trait AdminRpc {
     type Metadata;
@@ -208,6 +819,9 @@ pub trait AdminRpc {
     #[rpc(meta, name = "contactInfo")]
     fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo>;
 
+    #[rpc(meta, name = "connections")]
+    fn connections(&self, meta: Self::Metadata) -> Result<AdminRpcConnections>;
+
     #[rpc(meta, name = "repairShredFromPeer")]
     fn repair_shred_from_peer(
         &self,
@@ -223,6 +837,17 @@ pub trait AdminRpc {
     #[rpc(meta, name = "setRepairWhitelist")]
     fn set_repair_whitelist(&self, meta: Self::Metadata, whitelist: Vec<Pubkey>) -> Result<()>;
 
+    #[rpc(meta, name = "peerFilter")]
+    fn peer_filter(&self, meta: Self::Metadata) -> Result<AdminRpcPeerFilter>;
+
+    #[rpc(meta, name = "setPeerFilter")]
+    fn set_peer_filter(
+        &self,
+        meta: Self::Metadata,
+        allowed: Vec<String>,
+        denied: Vec<String>,
+    ) -> Result<()>;
+
     #[rpc(meta, name = "getSecondaryIndexKeySize")]
     fn get_secondary_index_key_size(
         &self,
@@ -243,6 +868,61 @@ pub trait AdminRpc {
         meta: Self::Metadata,
         public_tpu_forwards_addr: SocketAddr,
     ) -> Result<()>;
+
+    #[rpc(meta, name = "proposeHardFork")]
+    fn propose_hard_fork(
+        &self,
+        meta: Self::Metadata,
+        slot: Slot,
+        bank_hash: Option<String>,
+        expected_shred_version: Option<u16>,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "hardForkProposals")]
+    fn hard_fork_proposals(&self, meta: Self::Metadata) -> Result<AdminRpcHardForkProposals>;
+
+    #[rpc(meta, name = "cpiStats")]
+    fn cpi_stats(&self, meta: Self::Metadata, reset: Option<bool>) -> Result<AdminRpcCpiStats>;
+
+    #[rpc(name = "selfProfile")]
+    fn self_profile(&self, interval_ms: Option<u64>) -> Result<AdminRpcSelfProfile>;
+
+    #[rpc(meta, name = "readOnlyAccountsCache")]
+    fn read_only_accounts_cache(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcReadOnlyAccountsCache>;
+
+    #[rpc(meta, name = "setReadOnlyAccountsCacheLimit")]
+    fn set_read_only_accounts_cache_limit(
+        &self,
+        meta: Self::Metadata,
+        max_data_size_lo: usize,
+        max_data_size_hi: usize,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "flushReadOnlyAccountsCache")]
+    fn flush_read_only_accounts_cache(&self, meta: Self::Metadata) -> Result<usize>;
+
+    #[rpc(name = "packetDropStats")]
+    fn packet_drop_stats(&self, reset: Option<bool>) -> Result<AdminRpcPacketDropStats>;
+
+    #[rpc(name = "quicServers")]
+    fn quic_servers(&self) -> Result<AdminRpcQuicServers>;
+
+    #[rpc(name = "rpcRequestStats")]
+    fn rpc_request_stats(&self, reset: Option<bool>) -> Result<AdminRpcRequestStats>;
+
+    #[rpc(meta, name = "sampleConflictGraph")]
+    fn sample_conflict_graph(
+        &self,
+        meta: Self::Metadata,
+        min_slot: Option<Slot>,
+        max_transactions: Option<usize>,
+    ) -> Result<Slot>;
+
+    #[rpc(name = "conflictGraph")]
+    fn conflict_graph(&self, output_dir: String) -> Result<Option<AdminRpcConflictGraph>>;
 }
 
 pub struct AdminRpcImpl;
@@ -401,7 +1081,7 @@ impl AdminRpc for AdminRpcImpl {
 
     fn set_log_filter(&self, filter: String) -> Result<()> {
         debug!("set_log_filter admin rpc request received");
-        solana_logger::setup_with(&filter);
+        logger::set_filter(&filter);
         Ok(())
     }
 
@@ -502,6 +1182,72 @@ impl AdminRpc for AdminRpcImpl {
         meta.with_post_init(|post_init| Ok(post_init.cluster_info.my_contact_info().into()))
     }
 
+    fn connections(&self, meta: Self::Metadata) -> Result<AdminRpcConnections> {
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().working_bank();
+            let staked_nodes = bank.current_epoch_staked_nodes();
+
+            // Map every advertised TPU address back to the node advertising it
+            let mut identities = HashMap::new();
+            let mut peers = post_init.cluster_info.tpu_peers();
+            peers.push(post_init.cluster_info.my_contact_info());
+            for node in &peers {
+                for protocol in [Protocol::QUIC, Protocol::UDP] {
+                    let addrs = [
+                        node.tpu(protocol),
+                        node.tpu_forwards(protocol),
+                        node.tpu_vote(protocol),
+                    ];
+                    for addr in addrs.into_iter().flatten() {
+                        identities.insert(addr, *node.pubkey());
+                    }
+                }
+            }
+
+            let mut next_leader_slots = HashMap::new();
+            for slot in bank.slot()..bank.slot() + CONNECTIONS_LEADER_LOOKAHEAD_SLOTS {
+                if let Some(leader) = post_init
+                    .leader_schedule_cache
+                    .slot_leader_at(slot, Some(&bank))
+                {
+                    next_leader_slots.entry(leader).or_insert(slot);
+                }
+            }
+
+            let mut connections = vec![];
+            for cache in &post_init.connection_caches {
+                for (address, infos) in cache.connection_infos() {
+                    let identity = identities.get(&address);
+                    connections.push(AdminRpcConnectionInfo {
+                        cache: cache.name().to_string(),
+                        address,
+                        identity: identity.map(|pubkey| pubkey.to_string()),
+                        next_leader_slot: identity
+                            .and_then(|pubkey| next_leader_slots.get(pubkey))
+                            .copied(),
+                        stake: identity
+                            .and_then(|pubkey| staked_nodes.get(pubkey))
+                            .copied()
+                            .unwrap_or_default(),
+                        pool_size: infos.len(),
+                        connected: infos.iter().filter(|info| info.connected).count(),
+                        open_streams: infos.iter().map(|info| info.open_streams).sum(),
+                        sent_batches: infos.iter().map(|info| info.sent_batches).sum(),
+                        failed_batches: infos.iter().map(|info| info.failed_batches).sum(),
+                    });
+                }
+            }
+            // Upcoming leaders first, then everything else by stake
+            connections.sort_by(|a, b| {
+                a.next_leader_slot
+                    .unwrap_or(Slot::MAX)
+                    .cmp(&b.next_leader_slot.unwrap_or(Slot::MAX))
+                    .then(b.stake.cmp(&a.stake))
+            });
+            Ok(AdminRpcConnections { connections })
+        })
+    }
+
     fn repair_shred_from_peer(
         &self,
         meta: Self::Metadata,
@@ -554,6 +1300,39 @@ impl AdminRpc for AdminRpcImpl {
         })
     }
 
+    fn peer_filter(&self, meta: Self::Metadata) -> Result<AdminRpcPeerFilter> {
+        debug!("peer_filter request received");
+
+        meta.with_post_init(|post_init| {
+            Ok(AdminRpcPeerFilter::from(
+                &*post_init.cluster_info.peer_filter(),
+            ))
+        })
+    }
+
+    fn set_peer_filter(
+        &self,
+        meta: Self::Metadata,
+        allowed: Vec<String>,
+        denied: Vec<String>,
+    ) -> Result<()> {
+        debug!("set_peer_filter request received");
+
+        let parse_entries = |entries: Vec<String>| {
+            entries
+                .iter()
+                .map(|entry| PeerFilterEntry::from_str(entry))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(jsonrpc_core::error::Error::invalid_params)
+        };
+        let peer_filter = PeerFilter::new(parse_entries(allowed)?, parse_entries(denied)?);
+        meta.with_post_init(|post_init| {
+            warn!("Peer filter set to {peer_filter:?}");
+            post_init.cluster_info.set_peer_filter(peer_filter);
+            Ok(())
+        })
+    }
+
     fn get_secondary_index_key_size(
         &self,
         meta: Self::Metadata,
@@ -679,6 +1458,291 @@ impl AdminRpc for AdminRpcImpl {
             Ok(())
         })
     }
+
+    fn propose_hard_fork(
+        &self,
+        meta: Self::Metadata,
+        slot: Slot,
+        bank_hash: Option<String>,
+        expected_shred_version: Option<u16>,
+    ) -> Result<()> {
+        debug!("propose_hard_fork request received");
+
+        let bank_hash = bank_hash
+            .map(|bank_hash| Hash::from_str(&bank_hash))
+            .transpose()
+            .map_err(|err| {
+                jsonrpc_core::error::Error::invalid_params(format!("invalid bank hash: {err}"))
+            })?;
+        meta.with_post_init(|post_init| {
+            // Fill in the bank hash if the slot has been replayed locally
+            let bank_hash = bank_hash.or_else(|| {
+                let bank_forks = post_init.bank_forks.read().unwrap();
+                let bank = bank_forks.get(slot)?;
+                bank.is_frozen().then(|| bank.hash())
+            });
+            post_init
+                .cluster_info
+                .push_hard_fork_proposal(slot, bank_hash, expected_shred_version);
+            warn!(
+                "Proposed hard fork at slot {slot}, bank hash {bank_hash:?}, expected shred \
+                 version {expected_shred_version:?}"
+            );
+            Ok(())
+        })
+    }
+
+    fn hard_fork_proposals(&self, meta: Self::Metadata) -> Result<AdminRpcHardForkProposals> {
+        debug!("hard_fork_proposals request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let staked_nodes = bank.current_epoch_staked_nodes();
+            let mut proposals: Vec<_> = post_init
+                .cluster_info
+                .get_hard_fork_proposals(&mut Cursor::default())
+                .into_iter()
+                .map(|proposal| AdminRpcHardForkProposal {
+                    identity: proposal.from.to_string(),
+                    slot: proposal.slot,
+                    bank_hash: proposal.bank_hash.map(|bank_hash| bank_hash.to_string()),
+                    expected_shred_version: proposal.expected_shred_version,
+                    wallclock: proposal.wallclock,
+                    stake: staked_nodes
+                        .get(&proposal.from)
+                        .copied()
+                        .unwrap_or_default(),
+                })
+                .collect();
+            proposals.sort_by(|a, b| b.stake.cmp(&a.stake));
+            Ok(AdminRpcHardForkProposals {
+                total_stake: staked_nodes.values().sum(),
+                proposals,
+            })
+        })
+    }
+
+    fn cpi_stats(&self, meta: Self::Metadata, reset: Option<bool>) -> Result<AdminRpcCpiStats> {
+        debug!("cpi_stats request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            if !bank.cpi_stats().is_enabled() {
+                return Err(jsonrpc_core::Error {
+                    code: ErrorCode::InvalidRequest,
+                    message: "CPI stats are not collected, restart the validator with \
+                              --enable-cpi-stats"
+                        .to_string(),
+                    data: None,
+                });
+            }
+            let cpi_stats = if reset.unwrap_or_default() {
+                bank.cpi_stats().take()
+            } else {
+                bank.cpi_stats().snapshot()
+            };
+            let total = cpi_stats.total();
+            let mut programs: Vec<_> = cpi_stats
+                .programs
+                .into_iter()
+                .map(|(program_id, stats)| AdminRpcProgramCpiStats {
+                    program_id: program_id.to_string(),
+                    stats,
+                })
+                .collect();
+            programs.sort_by(|a, b| b.stats.instructions.cmp(&a.stats.instructions));
+            Ok(AdminRpcCpiStats {
+                total,
+                programs,
+                other: cpi_stats.other,
+            })
+        })
+    }
+
+    fn read_only_accounts_cache(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcReadOnlyAccountsCache> {
+        debug!("read_only_accounts_cache request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let accounts_db = &bank.accounts().accounts_db;
+            let (entries, data_size) = accounts_db.read_only_accounts_cache_usage();
+            let (max_data_size_lo, max_data_size_hi) =
+                accounts_db.read_only_accounts_cache_max_data_size();
+            let forks = accounts_db
+                .read_only_accounts_cache_fork_stats()
+                .into_iter()
+                .map(|(fork, stats)| AdminRpcReadOnlyCacheForkStats { fork, stats })
+                .collect();
+            Ok(AdminRpcReadOnlyAccountsCache {
+                entries,
+                data_size,
+                max_data_size_lo,
+                max_data_size_hi,
+                forks,
+            })
+        })
+    }
+
+    fn set_read_only_accounts_cache_limit(
+        &self,
+        meta: Self::Metadata,
+        max_data_size_lo: usize,
+        max_data_size_hi: usize,
+    ) -> Result<()> {
+        debug!(
+            "set_read_only_accounts_cache_limit request received: {max_data_size_lo} \
+             {max_data_size_hi}"
+        );
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            bank.accounts()
+                .accounts_db
+                .set_read_only_accounts_cache_max_data_size(max_data_size_lo, max_data_size_hi)
+                .map_err(jsonrpc_core::error::Error::invalid_params)
+        })
+    }
+
+    fn flush_read_only_accounts_cache(&self, meta: Self::Metadata) -> Result<usize> {
+        debug!("flush_read_only_accounts_cache request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            Ok(bank.accounts().accounts_db.flush_read_only_accounts_cache())
+        })
+    }
+
+    fn packet_drop_stats(&self, reset: Option<bool>) -> Result<AdminRpcPacketDropStats> {
+        debug!("packet_drop_stats request received");
+
+        let counts = if reset.unwrap_or_default() {
+            packet_drop_stats().take()
+        } else {
+            packet_drop_stats().counts()
+        };
+        let mut drops: Vec<_> = counts
+            .into_iter()
+            .map(|(reason, count)| AdminRpcPacketDrops {
+                reason: reason.to_string(),
+                count,
+            })
+            .collect();
+        drops.sort_by(|a, b| b.count.cmp(&a.count));
+        Ok(AdminRpcPacketDropStats {
+            total: drops.iter().map(|drops| drops.count).sum(),
+            drops,
+        })
+    }
+
+    fn rpc_request_stats(&self, reset: Option<bool>) -> Result<AdminRpcRequestStats> {
+        debug!("rpc_request_stats request received");
+
+        let stats = rpc_request_stats();
+        let rpc_request_stats = AdminRpcRequestStats {
+            methods: stats.method_stats(),
+            slowest: stats.slowest_requests(),
+        };
+        if reset.unwrap_or_default() {
+            stats.reset();
+        }
+        Ok(rpc_request_stats)
+    }
+
+    fn sample_conflict_graph(
+        &self,
+        meta: Self::Metadata,
+        min_slot: Option<Slot>,
+        max_transactions: Option<usize>,
+    ) -> Result<Slot> {
+        debug!("sample_conflict_graph request received");
+
+        meta.with_post_init(|post_init| {
+            // Sampling from the middle of the current leader slot would miss its first
+            // transactions
+            let next_slot = post_init.bank_forks.read().unwrap().working_bank().slot() + 1;
+            let min_slot = min_slot.unwrap_or(next_slot).max(next_slot);
+            conflict_graph_sampler().arm(
+                min_slot,
+                max_transactions.unwrap_or(DEFAULT_MAX_SAMPLED_TRANSACTIONS),
+            );
+            Ok(min_slot)
+        })
+    }
+
+    fn conflict_graph(&self, output_dir: String) -> Result<Option<AdminRpcConflictGraph>> {
+        debug!("conflict_graph request received");
+
+        let sampler = conflict_graph_sampler();
+        let Some(graph) = sampler.take_sample() else {
+            if sampler.is_sampling() {
+                return Ok(None);
+            }
+            return Err(jsonrpc_core::error::Error::invalid_params(
+                "No conflict graph sample was requested",
+            ));
+        };
+        let output_dir = PathBuf::from(output_dir);
+        let dot_path = output_dir.join(format!("conflict-graph-{}.dot", graph.slot()));
+        let json_path = output_dir.join(format!("conflict-graph-{}.json", graph.slot()));
+        graph
+            .write_dot(&dot_path)
+            .and_then(|()| graph.write_json(&json_path))
+            .map_err(|err| {
+                jsonrpc_core::error::Error::invalid_params(format!(
+                    "Failed to write the conflict graph of slot {} into {}: {err}",
+                    graph.slot(),
+                    output_dir.display(),
+                ))
+            })?;
+        Ok(Some(AdminRpcConflictGraph {
+            summary: graph.summary(),
+            dot_path: dot_path.display().to_string(),
+            json_path: json_path.display().to_string(),
+        }))
+    }
+
+    fn quic_servers(&self) -> Result<AdminRpcQuicServers> {
+        debug!("quic_servers request received");
+
+        let servers = connection_table_summaries()
+            .into_iter()
+            .map(|(name, summary)| AdminRpcQuicServer {
+                name: name.to_string(),
+                staked_peers: summary.staked_peers,
+                staked_connections: summary.staked_connections,
+                unstaked_peers: summary.unstaked_peers,
+                unstaked_connections: summary.unstaked_connections,
+                handshakes_in_progress: summary.handshakes_in_progress,
+                top_ipaddrs: summary
+                    .top_ipaddrs
+                    .into_iter()
+                    .map(|(ip, connections)| AdminRpcIpAddrConnections {
+                        ip: ip.to_string(),
+                        connections,
+                    })
+                    .collect(),
+                evictions: summary.evictions,
+                rate_limited: summary.rate_limited,
+                refused_per_ipaddr: summary.refused_per_ipaddr,
+            })
+            .collect();
+        Ok(AdminRpcQuicServers { servers })
+    }
+
+    fn self_profile(&self, interval_ms: Option<u64>) -> Result<AdminRpcSelfProfile> {
+        debug!("self_profile request received");
+
+        let interval = interval_ms
+            .map(Duration::from_millis)
+            .unwrap_or(self_profile::DEFAULT_SAMPLE_INTERVAL);
+        self_profile::profile(interval).map_err(|err| {
+            error!("Failed to sample the validator's threads: {err}");
+            jsonrpc_core::error::Error::internal_error()
+        })
+    }
 }
 
 impl AdminRpcImpl {
@@ -739,6 +1803,7 @@ fn rpc_account_index_from_account_index(account_index: &AccountIndex) -> RpcAcco
         AccountIndex::ProgramId => RpcAccountIndex::ProgramId,
         AccountIndex::SplTokenOwner => RpcAccountIndex::SplTokenOwner,
         AccountIndex::SplTokenMint => RpcAccountIndex::SplTokenMint,
+        AccountIndex::StakeVoter => RpcAccountIndex::StakeVoter,
     }
 }
 
@@ -880,6 +1945,7 @@ mod tests {
             genesis_utils::{
                 create_genesis_config, create_genesis_config_with_leader, GenesisConfigInfo,
             },
+            leader_schedule_cache::LeaderScheduleCache,
         },
         solana_net_utils::bind_to_unspecified,
         solana_rpc::rpc::create_validator_exit,
@@ -890,9 +1956,10 @@ mod tests {
         solana_sdk::{
             account::{Account, AccountSharedData},
             pubkey::Pubkey,
-            system_program,
+            system_program, system_transaction,
+            transaction::SanitizedTransaction,
         },
-        solana_streamer::socket::SocketAddrSpace,
+        solana_streamer::{packet_drop_stats::PacketDropReason, socket::SocketAddrSpace},
         solana_tpu_client::tpu_client::DEFAULT_TPU_ENABLE_UDP,
         spl_token_2022::{
             solana_program::{program_option::COption, program_pack::Pack},
@@ -959,6 +2026,10 @@ mod tests {
                     cluster_slots: Arc::new(
                         solana_core::cluster_slots_service::cluster_slots::ClusterSlots::default(),
                     ),
+                    connection_caches: Vec::new(),
+                    leader_schedule_cache: Arc::new(LeaderScheduleCache::new_from_bank(
+                        &bank_forks.read().unwrap().root_bank(),
+                    )),
                 }))),
                 staked_nodes_overrides: Arc::new(RwLock::new(HashMap::new())),
                 rpc_to_plugin_manager_sender: None,
@@ -1297,6 +2368,295 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_connections() {
+        let RpcHandler { io, meta, .. } = RpcHandler::_start();
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"connections"}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let connections: AdminRpcConnections =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert!(connections.connections.is_empty());
+    }
+
+    #[test]
+    fn test_packet_drop_stats() {
+        let rpc = RpcHandler::_start();
+        let RpcHandler { io, meta, .. } = rpc;
+        let packet_drop_stats = |reset: bool| {
+            let req = format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"packetDropStats","params":[{reset}]}}"#
+            );
+            let res = io.handle_request_sync(&req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            serde_json::from_value::<AdminRpcPacketDropStats>(result["result"].clone()).unwrap()
+        };
+
+        // The counters are shared by the tests of the process, so only those of this test are
+        // checked
+        solana_streamer::packet_drop_stats::packet_drop_stats()
+            .record(PacketDropReason::FeeFloor, 7);
+        let stats = packet_drop_stats(false);
+        assert_eq!(stats.drops.len(), PacketDropReason::ALL.len());
+        assert!(stats
+            .drops
+            .windows(2)
+            .all(|drops| drops[0].count >= drops[1].count));
+        assert_eq!(
+            stats.total,
+            stats.drops.iter().map(|drops| drops.count).sum::<u64>()
+        );
+        let fee_floor = |stats: &AdminRpcPacketDropStats| {
+            stats
+                .drops
+                .iter()
+                .find(|drops| drops.reason == "fee_floor")
+                .unwrap()
+                .count
+        };
+        assert!(fee_floor(&stats) >= 7);
+
+        packet_drop_stats(true);
+        assert!(fee_floor(&packet_drop_stats(false)) < 7);
+    }
+
+    #[test]
+    fn test_conflict_graph() {
+        let rpc = RpcHandler::_start();
+        let RpcHandler { io, meta, .. } = rpc;
+        let output_dir = tempfile::TempDir::new().unwrap();
+        let conflict_graph = || {
+            let req = format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"conflictGraph","params":["{}"]}}"#,
+                output_dir.path().display()
+            );
+            let res = io.handle_request_sync(&req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            result
+        };
+
+        // A slot the test validator doesn't reach, so that only this test records transactions
+        let slot = 1_000_000;
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"sampleConflictGraph","params":[{slot}, 10]}}"#
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["result"], slot);
+        assert_eq!(conflict_graph()["result"], Value::Null);
+
+        let sampler = conflict_graph_sampler();
+        let payer = Keypair::new();
+        for _ in 0..2 {
+            let transaction = SanitizedTransaction::from_transaction_for_tests(
+                system_transaction::transfer(&payer, &Pubkey::new_unique(), 1, Hash::default()),
+            );
+            sampler.record(slot, &transaction);
+        }
+        sampler.end_slot(None);
+
+        let graph: AdminRpcConflictGraph =
+            serde_json::from_value(conflict_graph()["result"].clone()).unwrap();
+        assert_eq!(graph.summary.slot, slot);
+        assert_eq!(graph.summary.transactions, 2);
+        assert_eq!(graph.summary.depth, 2);
+        assert!(Path::new(&graph.dot_path).is_file());
+        assert!(Path::new(&graph.json_path).is_file());
+        // The sample was taken
+        assert!(conflict_graph()["error"].is_object());
+    }
+
+    #[test]
+    fn test_quic_servers() {
+        let rpc = RpcHandler::_start();
+        let RpcHandler { io, meta, .. } = rpc;
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"quicServers"}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        // The test validator does not run QUIC servers long enough to report
+        let servers: AdminRpcQuicServers =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert!(servers
+            .servers
+            .windows(2)
+            .all(|servers| servers[0].name < servers[1].name));
+    }
+
+    #[test]
+    fn test_rpc_request_stats() {
+        let rpc = RpcHandler::_start();
+        let RpcHandler { io, meta, .. } = rpc;
+        let rpc_request_stats = |reset: bool| {
+            let req = format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"rpcRequestStats","params":[{reset}]}}"#
+            );
+            let res = io.handle_request_sync(&req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            serde_json::from_value::<AdminRpcRequestStats>(result["result"].clone()).unwrap()
+        };
+
+        solana_rpc::rpc_request_stats::rpc_request_stats().record(
+            "getBalance",
+            &jsonrpc_core::Params::None,
+            Duration::from_millis(3),
+            true,
+        );
+        let stats = rpc_request_stats(true);
+        let get_balance = stats
+            .methods
+            .iter()
+            .find(|stats| stats.method == "getBalance")
+            .unwrap();
+        assert!(get_balance.count >= 1);
+        assert!(get_balance.errors >= 1);
+        assert!(stats
+            .slowest
+            .iter()
+            .any(|request| request.method == "getBalance" && request.is_error));
+
+        let stats = rpc_request_stats(false);
+        assert!(stats.methods.is_empty());
+        assert!(stats.slowest.is_empty());
+    }
+
+    #[test]
+    fn test_cpi_stats() {
+        let rpc = RpcHandler::_start();
+        let bank = rpc.root_bank();
+        let RpcHandler { io, meta, .. } = rpc;
+        let payer = Keypair::new();
+        bank.store_account(
+            &payer.pubkey(),
+            &AccountSharedData::new(1_000_000_000, 0, &system_program::id()),
+        );
+
+        let cpi_stats = |reset: bool| {
+            let req =
+                format!(r#"{{"jsonrpc":"2.0","id":1,"method":"cpiStats","params":[{reset}]}}"#);
+            let res = io.handle_request_sync(&req, meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+        // The stats are not collected by default
+        bank.transfer(1_000_000, &payer, &Pubkey::new_unique())
+            .unwrap();
+        let result = cpi_stats(false);
+        assert_eq!(
+            result["error"]["code"].as_i64(),
+            Some(ErrorCode::InvalidRequest.code())
+        );
+
+        bank.cpi_stats().set_enabled(true);
+        bank.transfer(1_000_000, &payer, &Pubkey::new_unique())
+            .unwrap();
+        let cpi_stats = |reset: bool| {
+            serde_json::from_value::<AdminRpcCpiStats>(cpi_stats(reset)["result"].clone()).unwrap()
+        };
+        let stats = cpi_stats(true);
+        assert_eq!(stats.total.instructions, 1);
+        assert_eq!(stats.total.cpis, 0);
+        assert_eq!(stats.programs.len(), 1);
+        assert_eq!(
+            stats.programs[0].program_id,
+            system_program::id().to_string()
+        );
+        assert_eq!(stats.programs[0].stats.max_depth[0], 1);
+
+        // The stats were reset by the previous request
+        let stats = cpi_stats(false);
+        assert_eq!(stats.total.instructions, 0);
+        assert!(stats.programs.is_empty());
+    }
+
+    #[test]
+    fn test_read_only_accounts_cache() {
+        let RpcHandler { io, meta, .. } = RpcHandler::_start();
+        let request = |method: &str, params: &str| {
+            let req =
+                format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{method}","params":[{params}]}}"#);
+            let res = io.handle_request_sync(&req, meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+
+        let result = request("setReadOnlyAccountsCacheLimit", "1024, 2048");
+        assert_eq!(result["result"], Value::Null);
+        let cache = serde_json::from_value::<AdminRpcReadOnlyAccountsCache>(
+            request("readOnlyAccountsCache", "")["result"].clone(),
+        )
+        .unwrap();
+        assert_eq!(cache.max_data_size_lo, 1024);
+        assert_eq!(cache.max_data_size_hi, 2048);
+
+        // The low watermark cannot exceed the high watermark
+        let result = request("setReadOnlyAccountsCacheLimit", "2048, 1024");
+        assert_eq!(result["error"]["code"], ErrorCode::InvalidParams.code());
+
+        assert!(request("flushReadOnlyAccountsCache", "")["result"].is_u64());
+        let cache = serde_json::from_value::<AdminRpcReadOnlyAccountsCache>(
+            request("readOnlyAccountsCache", "")["result"].clone(),
+        )
+        .unwrap();
+        assert_eq!(cache.entries, 0);
+        assert_eq!(cache.data_size, 0);
+    }
+
+    #[test]
+    fn test_connection_error_rate() {
+        let mut connection = AdminRpcConnectionInfo {
+            cache: "connection_cache_tpu_quic".to_string(),
+            address: SocketAddr::from(([127, 0, 0, 1], 8009)),
+            identity: None,
+            next_leader_slot: None,
+            stake: 0,
+            pool_size: 1,
+            connected: 1,
+            open_streams: 0,
+            sent_batches: 0,
+            failed_batches: 0,
+        };
+        assert_eq!(connection.error_rate(), 0.0);
+        connection.sent_batches = 3;
+        connection.failed_batches = 1;
+        assert_eq!(connection.error_rate(), 0.25);
+    }
+
+    #[test]
+    fn test_peer_filter() {
+        let RpcHandler { io, meta, .. } = RpcHandler::_start();
+        let denied_pubkey = Pubkey::new_unique();
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"setPeerFilter","params":[["10.0.0.0/8"],["{denied_pubkey}"]]}}"#,
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["result"], Value::Null);
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"peerFilter"}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let peer_filter: AdminRpcPeerFilter =
+            serde_json::from_value(result["result"].clone()).unwrap();
+        assert_eq!(peer_filter.allowed, vec!["10.0.0.0/8".to_string()]);
+        assert_eq!(peer_filter.denied, vec![denied_pubkey.to_string()]);
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setPeerFilter","params":[["invalid"],[]]}"#;
+        let res = io.handle_request_sync(req, meta);
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["error"]["code"], ErrorCode::InvalidParams.code());
+    }
+
     // This test checks that the rpc call to `set_identity` works a expected with
     // Bank but without validator.
     #[test]
@@ -38,7 +38,7 @@ use {
     },
     tachyon_validator::{
         admin_rpc_service, cli, dashboard::Dashboard, ledger_lockfile, lock_ledger,
-        println_name_value, redirect_stderr_to_file,
+        logger::LogFormat, println_name_value, redirect_stderr_to_file,
     },
 };
 
@@ -72,6 +72,7 @@ fn main() {
             "program-id" => AccountIndex::ProgramId,
             "spl-token-mint" => AccountIndex::SplTokenMint,
             "spl-token-owner" => AccountIndex::SplTokenOwner,
+            "stake-voter" => AccountIndex::StakeVoter,
             _ => unreachable!(),
         })
         .collect();
@@ -126,7 +127,7 @@ fn main() {
     } else {
         None
     };
-    let _logger_thread = redirect_stderr_to_file(logfile);
+    let _logger_thread = redirect_stderr_to_file(logfile, LogFormat::default());
 
     info!("{} {}", crate_name!(), solana_version::version!());
     info!("Starting validator with: {:#?}", std::env::args_os());
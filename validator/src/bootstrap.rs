@@ -1,21 +1,30 @@
 use {
+    crate::entrypoints::Entrypoints,
     itertools::Itertools,
     log::*,
     rand::{seq::SliceRandom, thread_rng, Rng},
     rayon::prelude::*,
     solana_core::validator::{ValidatorConfig, ValidatorStartProgress},
-    solana_download_utils::{download_snapshot_archive, DownloadProgressRecord},
+    solana_download_utils::{
+        download_snapshot_archive,
+        snapshot_url::{download_snapshot_archive_from_mirrors, SnapshotUrl},
+        DownloadProgressRecord,
+    },
     solana_genesis_utils::download_then_check_genesis_hash,
     solana_gossip::{
         cluster_info::{ClusterInfo, Node},
         contact_info::{ContactInfo, Protocol},
         crds_data,
         gossip_service::GossipService,
+        peer_filter::PeerFilter,
     },
     solana_metrics::datapoint_info,
     solana_rpc_client::rpc_client::RpcClient,
     solana_runtime::{
-        snapshot_archive_info::SnapshotArchiveInfoGetter, snapshot_package::SnapshotKind,
+        snapshot_archive_info::{
+            FullSnapshotArchiveInfo, IncrementalSnapshotArchiveInfo, SnapshotArchiveInfoGetter,
+        },
+        snapshot_package::SnapshotKind,
         snapshot_utils,
     },
     solana_sdk::{
@@ -27,9 +36,9 @@ use {
     },
     solana_streamer::socket::SocketAddrSpace,
     std::{
-        collections::{hash_map::RandomState, HashMap, HashSet},
+        collections::{hash_map::RandomState, BTreeMap, HashMap, HashSet},
         net::{SocketAddr, TcpListener, TcpStream, UdpSocket},
-        path::Path,
+        path::{Path, PathBuf},
         process::exit,
         sync::{
             atomic::{AtomicBool, Ordering},
@@ -65,6 +74,11 @@ pub struct RpcBootstrapConfig {
     pub max_genesis_archive_unpacked_size: u64,
     pub check_vote_account: Option<String>,
     pub incremental_snapshot_fetch: bool,
+    /// Snapshot archives to download instead of discovering snapshots through gossip. URLs
+    /// sharing a file name are mirrors of the same archive
+    pub snapshot_urls: Vec<SnapshotUrl>,
+    /// Maximum speed of downloads from `snapshot_urls`, in bytes/second
+    pub max_snapshot_download_speed: Option<u64>,
 }
 
 fn verify_reachable_ports(
@@ -137,6 +151,7 @@ fn is_known_validator(id: &Pubkey, known_validators: &Option<HashSet<Pubkey>>) -
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn start_gossip_node(
     identity_keypair: Arc<Keypair>,
     cluster_entrypoints: &[ContactInfo],
@@ -145,6 +160,7 @@ fn start_gossip_node(
     gossip_socket: UdpSocket,
     expected_shred_version: Option<u16>,
     gossip_validators: Option<HashSet<Pubkey>>,
+    peer_filter: PeerFilter,
     should_check_duplicate_instance: bool,
     socket_addr_space: SocketAddrSpace,
 ) -> (Arc<ClusterInfo>, Arc<AtomicBool>, GossipService) {
@@ -155,6 +171,7 @@ fn start_gossip_node(
     );
     let mut cluster_info = ClusterInfo::new(contact_info, identity_keypair, socket_addr_space);
     cluster_info.set_entrypoints(cluster_entrypoints.to_vec());
+    cluster_info.set_peer_filter(peer_filter);
     cluster_info.restore_contact_info(ledger_path, 0);
     let cluster_info = Arc::new(cluster_info);
 
@@ -571,6 +588,70 @@ fn get_vetted_rpc_nodes(
     }
 }
 
+/// Downloads the highest full snapshot archive of `bootstrap_config.snapshot_urls`, and the
+/// highest incremental snapshot archive based on it if incremental snapshots are fetched
+fn download_snapshots_from_urls(
+    full_snapshot_archives_dir: &Path,
+    incremental_snapshot_archives_dir: &Path,
+    bootstrap_config: &RpcBootstrapConfig,
+) -> Result<(), String> {
+    // Mirrors of each archive, by slot
+    let mut full_snapshots: BTreeMap<Slot, (String, Vec<&SnapshotUrl>)> = BTreeMap::new();
+    let mut incremental_snapshots: BTreeMap<(Slot, Slot), (String, Vec<&SnapshotUrl>)> =
+        BTreeMap::new();
+    for snapshot_url in &bootstrap_config.snapshot_urls {
+        let file_name = snapshot_url.archive_file_name().unwrap().to_string();
+        let path = PathBuf::from(&file_name);
+        let mirrors = if let Ok(info) = FullSnapshotArchiveInfo::new_from_path(path.clone()) {
+            full_snapshots.entry(info.slot()).or_default()
+        } else if let Ok(info) = IncrementalSnapshotArchiveInfo::new_from_path(path) {
+            incremental_snapshots
+                .entry((info.base_slot(), info.slot()))
+                .or_default()
+        } else {
+            return Err(format!(
+                "--snapshot-url {snapshot_url} does not name a snapshot archive"
+            ));
+        };
+        if mirrors.0.is_empty() {
+            mirrors.0 = file_name;
+        } else if mirrors.0 != file_name {
+            return Err(format!(
+                "--snapshot-url {snapshot_url} conflicts with the archive {} of the same slot",
+                mirrors.0
+            ));
+        }
+        mirrors.1.push(snapshot_url);
+    }
+
+    let (full_snapshot_slot, (_, full_snapshot_mirrors)) = full_snapshots
+        .pop_last()
+        .ok_or("No --snapshot-url names a full snapshot archive")?;
+    let path = download_snapshot_archive_from_mirrors(
+        full_snapshot_mirrors,
+        &snapshot_utils::build_snapshot_archives_remote_dir(full_snapshot_archives_dir),
+        bootstrap_config.max_snapshot_download_speed,
+    )?;
+    info!("Downloaded full snapshot archive {}", path.display());
+
+    if bootstrap_config.incremental_snapshot_fetch {
+        if let Some((_, (_, incremental_snapshot_mirrors))) = incremental_snapshots
+            .range((full_snapshot_slot, 0)..=(full_snapshot_slot, Slot::MAX))
+            .next_back()
+        {
+            let path = download_snapshot_archive_from_mirrors(
+                incremental_snapshot_mirrors.iter().copied(),
+                &snapshot_utils::build_snapshot_archives_remote_dir(
+                    incremental_snapshot_archives_dir,
+                ),
+                bootstrap_config.max_snapshot_download_speed,
+            )?;
+            info!("Downloaded incremental snapshot archive {}", path.display());
+        }
+    }
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn rpc_bootstrap(
     node: &Node,
@@ -581,8 +662,10 @@ pub fn rpc_bootstrap(
     vote_account: &Pubkey,
     authorized_voter_keypairs: Arc<RwLock<Vec<Arc<Keypair>>>>,
     cluster_entrypoints: &[ContactInfo],
+    entrypoints: &Arc<Entrypoints>,
+    entrypoint_refresh_interval: Option<Duration>,
     validator_config: &mut ValidatorConfig,
-    bootstrap_config: RpcBootstrapConfig,
+    mut bootstrap_config: RpcBootstrapConfig,
     do_port_check: bool,
     use_progress_bar: bool,
     maximum_local_snapshot_age: Slot,
@@ -607,6 +690,19 @@ pub fn rpc_bootstrap(
         }
     }
 
+    if !bootstrap_config.snapshot_urls.is_empty() && !bootstrap_config.no_snapshot_fetch {
+        if let Err(err) = download_snapshots_from_urls(
+            full_snapshot_archives_dir,
+            incremental_snapshot_archives_dir,
+            &bootstrap_config,
+        ) {
+            error!("{err}");
+            exit(1);
+        }
+        // The snapshots are in place, only the genesis may still need to be fetched
+        bootstrap_config.no_snapshot_fetch = true;
+    }
+
     if bootstrap_config.no_genesis_fetch && bootstrap_config.no_snapshot_fetch {
         return;
     }
@@ -618,6 +714,7 @@ pub fn rpc_bootstrap(
     let mut gossip = None;
     let mut vetted_rpc_nodes = vec![];
     let mut download_abort_count = 0;
+    let mut entrypoint_refreshes = vec![];
     loop {
         if gossip.is_none() {
             *start_progress.write().unwrap() = ValidatorStartProgress::SearchingForRpcService;
@@ -633,9 +730,20 @@ pub fn rpc_bootstrap(
                 node.sockets.gossip.try_clone().unwrap(),
                 validator_config.expected_shred_version,
                 validator_config.gossip_validators.clone(),
+                validator_config.peer_filter.clone(),
                 should_check_duplicate_instance,
                 socket_addr_space,
             ));
+            // The refresh exits along with the gossip node
+            if let Some(refresh_interval) = entrypoint_refresh_interval {
+                let (cluster_info, gossip_exit_flag, _) = gossip.as_ref().unwrap();
+                entrypoint_refreshes.push(entrypoints.start_refresh(
+                    cluster_info.clone(),
+                    cluster_entrypoints,
+                    refresh_interval,
+                    gossip_exit_flag.clone(),
+                ));
+            }
         }
 
         let get_rpc_nodes_start = Instant::now();
@@ -688,6 +796,9 @@ pub fn rpc_bootstrap(
     if let Some(gossip) = gossip.take() {
         shutdown_gossip_service(gossip);
     }
+    for entrypoint_refresh in entrypoint_refreshes {
+        entrypoint_refresh.join().unwrap();
+    }
 
     datapoint_info!(
         "bootstrap-snapshot-download",
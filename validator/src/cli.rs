@@ -1,5 +1,9 @@
 use {
-    crate::commands,
+    crate::{
+        commands,
+        entrypoints::{is_entrypoint, DEFAULT_ENTRYPOINT_REFRESH_INTERVAL_SECS},
+        logger::LOG_FORMATS,
+    },
     clap::{crate_description, crate_name, App, AppSettings, Arg, ArgMatches, SubCommand},
     log::warn,
     solana_accounts_db::{
@@ -11,24 +15,31 @@ use {
     solana_clap_utils::{
         hidden_unless_forced,
         input_validators::{
-            is_keypair_or_ask_keyword, is_parsable, is_pow2, is_pubkey, is_pubkey_or_keypair,
-            is_slot, is_url_or_moniker, is_within_range,
+            is_amount, is_keypair_or_ask_keyword, is_parsable, is_pow2, is_pubkey,
+            is_pubkey_or_keypair, is_slot, is_url_or_moniker, is_within_range,
             validate_maximum_full_snapshot_archives_to_retain,
             validate_maximum_incremental_snapshot_archives_to_retain,
         },
         keypair::SKIP_SEED_PHRASE_VALIDATION_ARG,
     },
     solana_core::{
+        banking_stage::conflict_graph::DEFAULT_MAX_SAMPLED_TRANSACTIONS,
         banking_trace::{DirByteLimit, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT},
         validator::{BlockProductionMethod, BlockVerificationMethod, TransactionStructure},
     },
+    solana_download_utils::snapshot_url::SnapshotUrl,
     solana_faucet::faucet::{self, FAUCET_PORT},
+    solana_gossip::peer_filter::is_peer_filter_entry,
     solana_ledger::use_snapshot_archives_at_startup,
     solana_net_utils::{MINIMUM_VALIDATOR_PORT_RANGE_WIDTH, VALIDATOR_PORT_RANGE},
     solana_rayon_threadlimit::get_thread_count,
-    solana_rpc::{rpc::MAX_REQUEST_BODY_SIZE, rpc_pubsub_service::PubSubConfig},
+    solana_rpc::{
+        epoch_stakes_archive::DEFAULT_EPOCH_STAKES_ARCHIVE_MAX_EPOCHS, rpc::MAX_REQUEST_BODY_SIZE,
+        rpc_pubsub_service::PubSubConfig, rpc_webhooks::WebhookConfig,
+    },
     solana_rpc_client_api::request::{DELINQUENT_VALIDATOR_SLOT_DISTANCE, MAX_MULTIPLE_ACCOUNTS},
     solana_runtime::{
+        bank::supply_audit::DEFAULT_SUPPLY_AUDIT_MAX_ENTRIES,
         snapshot_bank_utils::{
             DEFAULT_FULL_SNAPSHOT_ARCHIVE_INTERVAL_SLOTS,
             DEFAULT_INCREMENTAL_SNAPSHOT_ARCHIVE_INTERVAL_SLOTS,
@@ -43,16 +54,21 @@ use {
         clock::Slot, epoch_schedule::MINIMUM_SLOTS_PER_EPOCH, hash::Hash, quic::QUIC_PORT_OFFSET,
         rpc_port,
     },
-    solana_send_transaction_service::send_transaction_service::{
-        self, MAX_BATCH_SEND_RATE_MS, MAX_TRANSACTION_BATCH_SIZE,
+    solana_send_transaction_service::{
+        send_transaction_service::{self, MAX_BATCH_SEND_RATE_MS, MAX_TRANSACTION_BATCH_SIZE},
+        transaction_ingestion_log::DEFAULT_TRANSACTION_INGESTION_LOG_CAPACITY,
     },
     solana_streamer::quic::{
-        DEFAULT_MAX_CONNECTIONS_PER_IPADDR_PER_MINUTE, DEFAULT_MAX_QUIC_CONNECTIONS_PER_PEER,
-        DEFAULT_MAX_STAKED_CONNECTIONS, DEFAULT_MAX_STREAMS_PER_MS,
-        DEFAULT_MAX_UNSTAKED_CONNECTIONS, DEFAULT_QUIC_ENDPOINTS,
+        StakedEvictionPolicy, DEFAULT_MAX_CONNECTIONS_PER_IPADDR,
+        DEFAULT_MAX_CONNECTIONS_PER_IPADDR_PER_MINUTE, DEFAULT_MAX_CONNECTIONS_PER_SECOND,
+        DEFAULT_MAX_QUIC_CONNECTIONS_PER_PEER, DEFAULT_MAX_STAKED_CONNECTIONS,
+        DEFAULT_MAX_STREAMS_PER_MS, DEFAULT_MAX_UNSTAKED_CONNECTIONS, DEFAULT_QUIC_ENDPOINTS,
     },
     solana_tpu_client::tpu_client::{DEFAULT_TPU_CONNECTION_POOL_SIZE, DEFAULT_VOTE_USE_QUIC},
     solana_unified_scheduler_pool::DefaultSchedulerPool,
+    solana_wen_restart::wen_restart::{
+        DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT, DEFAULT_REPAIR_THRESHOLD_PERCENT,
+    },
     std::{path::PathBuf, str::FromStr},
 };
 
@@ -141,11 +157,27 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
             Arg::with_name("entrypoint")
                 .short("n")
                 .long("entrypoint")
-                .value_name("HOST:PORT")
+                .value_name("HOST:PORT[@PRIORITY]")
                 .takes_value(true)
                 .multiple(true)
-                .validator(solana_net_utils::is_host_port)
-                .help("Rendezvous with the cluster at this gossip entrypoint"),
+                .validator(is_entrypoint)
+                .help(
+                    "Rendezvous with the cluster at this gossip entrypoint. \
+                     Reachable entrypoints with the lowest PRIORITY are used, \
+                     failing over to higher ones when they are unreachable [default priority: 0]",
+                ),
+        )
+        .arg(
+            Arg::with_name("entrypoint_refresh_interval")
+                .long("entrypoint-refresh-interval")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .default_value(&default_args.entrypoint_refresh_interval)
+                .validator(|s| is_within_range(s, 0..))
+                .help(
+                    "Re-resolve and health check the entrypoints at this interval, \
+                     rotating to reachable ones. 0 disables the refresh",
+                ),
         )
         .arg(
             Arg::with_name("no_snapshot_fetch")
@@ -156,6 +188,32 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                      snapshot if present",
                 ),
         )
+        .arg(
+            Arg::with_name("snapshot_url")
+                .long("snapshot-url")
+                .value_name("URL")
+                .takes_value(true)
+                .multiple(true)
+                .validator(is_parsable::<SnapshotUrl>)
+                .help(
+                    "Download snapshot archives from this HTTPS or S3 URL instead of from the \
+                     RPC nodes found through gossip. The URL must end with the file name of a \
+                     full or incremental snapshot archive, and may be followed by \
+                     #sha256=<HEX> to pin the hash of the archive. URLs with the same file name \
+                     are tried in order as mirrors. The highest full snapshot is downloaded, \
+                     along with the highest incremental snapshot based on it. Interrupted \
+                     downloads are resumed on restart",
+                ),
+        )
+        .arg(
+            Arg::with_name("maximum_snapshot_download_speed")
+                .long("maximum-snapshot-download-speed")
+                .value_name("BYTES_PER_SECOND")
+                .takes_value(true)
+                .requires("snapshot_url")
+                .validator(is_parsable::<u64>)
+                .help("Maximum speed of the downloads of --snapshot-url, in bytes/second"),
+        )
         .arg(
             Arg::with_name("no_genesis_fetch")
                 .long("no-genesis-fetch")
@@ -263,6 +321,41 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                      transaction info stored",
                 ),
         )
+        .arg(
+            Arg::with_name("enable_rpc_program_fee_market")
+                .long("enable-rpc-program-fee-market")
+                .takes_value(false)
+                .help(
+                    "Attribute the compute unit prices of the recent transactions to the \
+                     programs owning the accounts they write, enabling the \
+                     'getProgramFeeMarket' API. This costs an account lookup per writable \
+                     account of every transaction replayed or produced",
+                ),
+        )
+        .arg(
+            Arg::with_name("enable_archival_accounts")
+                .long("enable-archival-accounts")
+                .requires("enable_rpc_transaction_history")
+                .takes_value(false)
+                .help(
+                    "Store a copy of every account written by each block, enabling the \
+                     'getAccountInfoAtSlot' API. This greatly increases disk usage; use \
+                     --archival-accounts-owner to limit the accounts stored",
+                ),
+        )
+        .arg(
+            Arg::with_name("archival_accounts_owner")
+                .long("archival-accounts-owner")
+                .requires("enable_archival_accounts")
+                .takes_value(true)
+                .validator(is_pubkey)
+                .value_name("PROGRAM_ID")
+                .multiple(true)
+                .help(
+                    "Only archive accounts owned by this program. May be specified multiple \
+                     times. [default: all accounts]",
+                ),
+        )
         .arg(
             Arg::with_name("rpc_max_multiple_accounts")
                 .long("rpc-max-multiple-accounts")
@@ -529,6 +622,19 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                      increases priority, positive value decreases priority.",
                 ),
         )
+        .arg(
+            Arg::with_name("snapshot_hash_gossip_quorum")
+                .long("snapshot-hash-gossip-quorum")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .default_value(&default_args.snapshot_hash_gossip_quorum)
+                .help(
+                    "Only advertise the hash of a snapshot in gossip once this many other nodes \
+                     gossip the same hash for its slot. At least this many nodes of the cluster \
+                     must keep the default of 0 so that hashes can be observed.",
+                ),
+        )
         .arg(
             Arg::with_name("minimal_snapshot_download_speed")
                 .long("minimal-snapshot-download-speed")
@@ -560,6 +666,122 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .default_value(&default_args.contact_debug_interval)
                 .help("Milliseconds between printing contact debug from gossip."),
         )
+        .arg(
+            Arg::with_name("program_cache_warmup_file")
+                .long("program-cache-warmup-file")
+                .value_name("FILE")
+                .takes_value(true)
+                .help(
+                    "File listing program ids, one per line, to load and compile into the \
+                     program cache at startup",
+                ),
+        )
+        .arg(
+            Arg::with_name("program_cache_warmup_persist_count")
+                .long("program-cache-warmup-persist")
+                .value_name("COUNT")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .help(
+                    "Save the COUNT most used programs to the ledger directory on exit, and \
+                     load and compile those which were not redeployed into the program cache on \
+                     the next start of the same validator version",
+                ),
+        )
+        .arg(
+            Arg::with_name("enable_cpi_stats")
+                .long("enable-cpi-stats")
+                .takes_value(false)
+                .help(
+                    "Record the CPI depth and count distributions of the executed \
+                     instructions, per program, served by the cpi-stats command",
+                ),
+        )
+        .arg(
+            Arg::with_name("check_bank_capitalization")
+                .long("check-bank-capitalization")
+                .takes_value(false)
+                .help(
+                    "Check, without affecting consensus, that the capitalization of every \
+                     frozen bank only grows by the inflation rewards it credits, and log any \
+                     violation",
+                ),
+        )
+        .arg(
+            Arg::with_name("supply_audit")
+                .long("supply-audit")
+                .value_name("ACTION")
+                .takes_value(true)
+                .possible_values(&["alarm", "abort"])
+                .help(
+                    "Audit the change of supply of every frozen bank against the inflation it \
+                     issued and the fees and rent it burned, and enable the 'getSupplyDeltas' \
+                     API. On unexplained supply changes, either log them and report them to \
+                     metrics [alarm], or exit [abort]",
+                ),
+        )
+        .arg(
+            Arg::with_name("supply_audit_max_entries")
+                .long("supply-audit-max-entries")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .default_value(&default_args.supply_audit_max_entries)
+                .help("The number of recent banks whose supply audit is kept for RPC"),
+        )
+        .arg(
+            Arg::with_name("identity_top_up_treasury")
+                .long("identity-top-up-treasury")
+                .value_name("KEYPAIR")
+                .takes_value(true)
+                .validator(is_keypair_or_ask_keyword)
+                .help(
+                    "Keep the balance of the identity, which pays the fees of the votes, above \
+                     --identity-top-up-threshold by transferring from this treasury account",
+                ),
+        )
+        .arg(
+            Arg::with_name("identity_top_up_nonce_account")
+                .long("identity-top-up-nonce-account")
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .validator(is_pubkey)
+                .requires("identity_top_up_treasury")
+                .help(
+                    "Durable nonce account of the treasury to sign top ups with, instead of \
+                     recent blockhashes",
+                ),
+        )
+        .arg(
+            Arg::with_name("identity_top_up_threshold")
+                .long("identity-top-up-threshold")
+                .value_name("SOL")
+                .takes_value(true)
+                .validator(is_amount)
+                .default_value(&default_args.identity_top_up_threshold)
+                .help("Balance of the identity under which it is topped up"),
+        )
+        .arg(
+            Arg::with_name("identity_top_up_target")
+                .long("identity-top-up-target")
+                .value_name("SOL")
+                .takes_value(true)
+                .validator(is_amount)
+                .default_value(&default_args.identity_top_up_target)
+                .help("Balance the identity is topped up to"),
+        )
+        .arg(
+            Arg::with_name("identity_top_up_max_per_day")
+                .long("identity-top-up-max-per-day")
+                .value_name("SOL")
+                .takes_value(true)
+                .validator(is_amount)
+                .default_value(&default_args.identity_top_up_max_per_day)
+                .help(
+                    "Maximum amount transferred to the identity over any 24 hours. Top ups \
+                     beyond it are not sent and reported to metrics as alerts",
+                ),
+        )
         .arg(
             Arg::with_name("no_poh_speed_test")
                 .long("no-poh-speed-test")
@@ -596,6 +818,18 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .hidden(hidden_unless_forced())
                 .help("Disable reporting of OS disk statistics."),
         )
+        .arg(
+            Arg::with_name("prometheus_bind_address")
+                .long("prometheus-bind-address")
+                .value_name("HOST:PORT")
+                .takes_value(true)
+                .validator(solana_net_utils::is_host_port)
+                .help(
+                    "Serve the metrics of the validator in the Prometheus text format at \
+                     http://HOST:PORT/metrics. The metrics are also served at /metrics by the \
+                     RPC service when it is enabled",
+                ),
+        )
         .arg(
             Arg::with_name("snapshot_version")
                 .long("snapshot-version")
@@ -707,6 +941,19 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                      SIGUSR1 signal to the validator process will cause it to re-open the log file",
                 ),
         )
+        .arg(
+            Arg::with_name("log_format")
+                .long("log-format")
+                .value_name("FORMAT")
+                .takes_value(true)
+                .possible_values(LOG_FORMATS)
+                .default_value(&default_args.log_format)
+                .help(
+                    "Format of the log: plain text, or one JSON object per line with the \
+                     timestamp, module, level and fields of each record. The log filter can be \
+                     changed at runtime with the set-log-filter command",
+                ),
+        )
         .arg(
             Arg::with_name("wait_for_supermajority")
                 .long("wait-for-supermajority")
@@ -795,6 +1042,15 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                      other validators. [default: all validators]",
                 ),
         )
+        .arg(
+            Arg::with_name("repair_over_quic")
+                .long("repair-over-quic")
+                .takes_value(false)
+                .help(
+                    "Send repair requests over QUIC to validators advertising a QUIC repair \
+                     socket in gossip, falling back to UDP for the others",
+                ),
+        )
         .arg(
             Arg::with_name("gossip_validators")
                 .long("gossip-validator")
@@ -807,6 +1063,34 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                      push/pull from from validators outside this set. [default: all validators]",
                 ),
         )
+        .arg(
+            Arg::with_name("peer_allow")
+                .long("peer-allow")
+                .validator(is_peer_filter_entry)
+                .value_name("PUBKEY OR IP RANGE")
+                .multiple(true)
+                .takes_value(true)
+                .help(
+                    "A list of peer identities and IP ranges (e.g. 10.0.0.0/8) to exclusively \
+                     accept in gossip, repair and turbine retransmit. If any identity is listed, \
+                     peers with other identities are rejected, and likewise for IP ranges. \
+                     Can be changed at runtime with the peer-filter command \
+                     [default: all peers]",
+                ),
+        )
+        .arg(
+            Arg::with_name("peer_deny")
+                .long("peer-deny")
+                .validator(is_peer_filter_entry)
+                .value_name("PUBKEY OR IP RANGE")
+                .multiple(true)
+                .takes_value(true)
+                .help(
+                    "A list of peer identities and IP ranges (e.g. 10.0.0.0/8) to reject in \
+                     gossip, repair and turbine retransmit. Can be changed at runtime with the \
+                     peer-filter command",
+                ),
+        )
         .arg(
             Arg::with_name("tpu_coalesce_ms")
                 .long("tpu-coalesce-ms")
@@ -852,6 +1136,41 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .hidden(hidden_unless_forced())
                 .help("Controls the rate of the clients connections per IpAddr per minute."),
         )
+        .arg(
+            Arg::with_name("tpu_max_connections_per_ipaddr")
+                .long("tpu-max-connections-per-ipaddr")
+                .takes_value(true)
+                .default_value(&default_args.tpu_max_connections_per_ipaddr)
+                .validator(is_parsable::<u32>)
+                .hidden(hidden_unless_forced())
+                .help(
+                    "Controls the max concurrent connections per IpAddr, counting handshakes in \
+                     progress and connections from unstaked nodes.",
+                ),
+        )
+        .arg(
+            Arg::with_name("tpu_max_connections_per_second")
+                .long("tpu-max-connections-per-second")
+                .takes_value(true)
+                .default_value(&default_args.tpu_max_connections_per_second)
+                .validator(is_parsable::<u64>)
+                .hidden(hidden_unless_forced())
+                .help("Controls the rate of new connections across all IpAddrs per second."),
+        )
+        .arg(
+            Arg::with_name("tpu_staked_eviction_policy")
+                .long("tpu-staked-eviction-policy")
+                .takes_value(true)
+                .possible_values(&["random-sample", "stake-weighted-lru"])
+                .default_value(&default_args.tpu_staked_eviction_policy)
+                .hidden(hidden_unless_forced())
+                .help(
+                    "Controls which connection is evicted when the staked connection table is \
+                     full: the lowest staked of a random sample of peers, or the peer idle the \
+                     longest weighted by stake. Only peers with less stake than the new peer are \
+                     evicted.",
+                ),
+        )
         .arg(
             Arg::with_name("vote_use_quic")
                 .long("vote-use-quic")
@@ -1198,6 +1517,18 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .requires("rpc_send_transaction_tpu_peer")
                 .help("With `--rpc-send-transaction-tpu-peer HOST:PORT`, also send to the current leader")
         )
+        .arg(
+            Arg::with_name("rpc_send_transaction_staked_identity")
+                .long("rpc-send-transaction-staked-identity")
+                .value_name("KEYPAIR")
+                .takes_value(true)
+                .validator(is_keypair_or_ask_keyword)
+                .help(
+                    "Staked identity keypair presented to leaders when forwarding RPC \
+                     sendTransaction traffic over QUIC, so that it is served from the \
+                     stake-weighted QoS allotment of that identity rather than the unstaked one",
+                ),
+        )
         .arg(
             Arg::with_name("rpc_scan_and_fix_roots")
                 .long("rpc-scan-and-fix-roots")
@@ -1214,6 +1545,86 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .default_value(&default_args.rpc_max_request_body_size)
                 .help("The maximum request body size accepted by rpc service"),
         )
+        .arg(
+            Arg::with_name("enable_rpc_webhooks")
+                .long("enable-rpc-webhooks")
+                .takes_value(false)
+                .requires("enable_rpc_transaction_history")
+                .help(
+                    "Enable the 'registerWebhook' API, which POSTs the transactions referencing \
+                     a set of addresses to a client URL once they reach the requested commitment",
+                ),
+        )
+        .arg(
+            Arg::with_name("rpc_webhook_max_per_client")
+                .long("rpc-webhook-max-per-client")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .default_value(&default_args.rpc_webhook_max_per_client)
+                .help("The maximum number of webhooks registered by a single client host"),
+        )
+        .arg(
+            Arg::with_name("rpc_webhook_allowed_hosts")
+                .long("rpc-webhook-allowed-hosts")
+                .value_name("HOST")
+                .takes_value(true)
+                .multiple(true)
+                .requires("enable_rpc_webhooks")
+                .help(
+                    "Only allow webhooks to be registered for these hosts, which may resolve to \
+                     private addresses. [default: any host resolving only to public addresses]",
+                ),
+        )
+        .arg(
+            Arg::with_name("rpc_webhook_max_notifications_per_minute")
+                .long("rpc-webhook-max-notifications-per-minute")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .default_value(&default_args.rpc_webhook_max_notifications_per_minute)
+                .help(
+                    "The maximum number of webhook notifications sent to a single client host \
+                     per minute, further notifications are dropped",
+                ),
+        )
+        .arg(
+            Arg::with_name("enable_transaction_ingestion_log")
+                .long("enable-transaction-ingestion-log")
+                .takes_value(false)
+                .help(
+                    "Record where recent transactions were received, filtered, forwarded, \
+                     scheduled and executed, and enable the 'getTransactionIngestionStatus' API",
+                ),
+        )
+        .arg(
+            Arg::with_name("transaction_ingestion_log_capacity")
+                .long("transaction-ingestion-log-capacity")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .default_value(&default_args.transaction_ingestion_log_capacity)
+                .help("The maximum number of transaction events kept in the ingestion log"),
+        )
+        .arg(
+            Arg::with_name("enable_epoch_stakes_archive")
+                .long("enable-epoch-stakes-archive")
+                .takes_value(false)
+                .help(
+                    "Archive the stakes of every epoch into the ledger directory, so \
+                     'getEpochStakes' keeps serving the epochs older than those held by the \
+                     banks",
+                ),
+        )
+        .arg(
+            Arg::with_name("epoch_stakes_archive_max_epochs")
+                .long("epoch-stakes-archive-max-epochs")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .default_value(&default_args.epoch_stakes_archive_max_epochs)
+                .help("The number of most recent epochs whose stakes are kept in the archive"),
+        )
         .arg(
             Arg::with_name("geyser_plugin_config")
                 .long("geyser-plugin-config")
@@ -1312,7 +1723,12 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .long("account-index")
                 .takes_value(true)
                 .multiple(true)
-                .possible_values(&["program-id", "spl-token-owner", "spl-token-mint"])
+                .possible_values(&[
+                    "program-id",
+                    "spl-token-owner",
+                    "spl-token-mint",
+                    "stake-voter",
+                ])
                 .value_name("INDEX")
                 .help("Enable an accounts index, indexed by the selected account field"),
         )
@@ -1477,6 +1893,20 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .help("Verifies the experimental accumulator hash")
                 .hidden(hidden_unless_forced()),
         )
+        .arg(
+            Arg::with_name("accounts_db_experimental_accumulator_hash_dual_mode_epochs")
+                .long("accounts-db-experimental-accumulator-hash-dual-mode-epochs")
+                .value_name("EPOCHS")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .help(
+                    "Number of epochs, starting at startup, in which the experimental \
+                     accumulator hash is recalculated at the epoch accounts hash and compared \
+                     against the one maintained incrementally. A divergence is reported as an \
+                     error, without halting the validator",
+                )
+                .hidden(hidden_unless_forced()),
+        )
         .arg(
             Arg::with_name("accounts_db_snapshots_use_experimental_accumulator_hash")
                 .long("accounts-db-snapshots-use-experimental-accumulator-hash")
@@ -1502,6 +1932,19 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .takes_value(true)
                 .help("Number of bins to divide the accounts index into"),
         )
+        .arg(
+            Arg::with_name("accounts_index_memory_budget_mb")
+                .long("accounts-index-memory-budget-mb")
+                .value_name("MEGABYTES")
+                .validator(is_parsable::<usize>)
+                .takes_value(true)
+                .conflicts_with("disable_accounts_disk_index")
+                .help(
+                    "Target memory for the in-memory accounts index. While the index is \
+                     estimated to exceed this, entries are flushed to the disk index more \
+                     aggressively. [default: unlimited]",
+                ),
+        )
         .arg(
             Arg::with_name("accounts_index_path")
                 .long("accounts-index-path")
@@ -1591,6 +2034,18 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .takes_value(false)
                 .help("Disables the banking trace"),
         )
+        .arg(
+            Arg::with_name("capture_banking_trace")
+                .long("capture-banking-trace")
+                .conflicts_with("disable_banking_trace")
+                .takes_value(false)
+                .help(
+                    "Additionally record the banking stage's leader slot boundaries, per \
+                     transaction filter decisions and fee computations into the banking trace, \
+                     so that scheduling can be re-run offline with \
+                     tachyon-banking-trace-replay. Shares the banking trace byte limit.",
+                ),
+        )
         .arg(
             Arg::with_name("delay_leader_block_for_pending_fork")
                 .hidden(hidden_unless_forced())
@@ -1683,24 +2138,79 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                     May get stuck if the leader used is different from others.",
                 ),
         )
+        .arg(
+            Arg::with_name("wen_restart_repair_threshold_percent")
+                .long("wen-restart-repair-threshold-percent")
+                .hidden(hidden_unless_forced())
+                .value_name("PERCENT")
+                .takes_value(true)
+                .requires("wen_restart")
+                .default_value(&default_args.wen_restart_repair_threshold_percent)
+                .validator(|s| is_within_range(s, 0..=100))
+                .help(
+                    "Only used during coordinated cluster restarts. \
+                    Slots on the last voted forks of at least this percentage of the stake \
+                    are repaired.",
+                ),
+        )
+        .arg(
+            Arg::with_name("wen_restart_heaviest_fork_threshold_delta_percent")
+                .long("wen-restart-heaviest-fork-threshold-delta-percent")
+                .hidden(hidden_unless_forced())
+                .value_name("PERCENT")
+                .takes_value(true)
+                .requires("wen_restart")
+                .default_value(&default_args.wen_restart_heaviest_fork_threshold_delta_percent)
+                .validator(|s| is_within_range(s, 0..=100))
+                .help(
+                    "Only used during coordinated cluster restarts. \
+                    A slot is selected on the heaviest fork when its stake is at least the \
+                    active stake minus this percentage of the total stake.",
+                ),
+        )
+        .arg(
+            Arg::with_name("wen_restart_last_voted_fork_slots_timeout")
+                .long("wen-restart-last-voted-fork-slots-timeout")
+                .hidden(hidden_unless_forced())
+                .value_name("SECONDS")
+                .takes_value(true)
+                .requires("wen_restart")
+                .validator(|s| is_within_range(s, 1..))
+                .help(
+                    "Only used during coordinated cluster restarts. \
+                    Give up on the wen_restart if not enough stake has reported its last voted \
+                    fork within this many seconds [default: wait forever]",
+                ),
+        )
         .args(&thread_args(&default_args.thread_args))
         .args(&get_deprecated_arguments())
         .after_help("The default subcommand is run")
         .subcommand(commands::exit::command(default_args))
         .subcommand(commands::authorized_voter::command(default_args))
+        .subcommand(commands::conflict_graph::command(default_args))
+        .subcommand(commands::connections::command(default_args))
         .subcommand(commands::contact_info::command(default_args))
+        .subcommand(commands::cpi_stats::command(default_args))
+        .subcommand(commands::hard_fork::command(default_args))
         .subcommand(commands::repair_shred_from_peer::command(default_args))
         .subcommand(commands::repair_whitelist::command(default_args))
         .subcommand(
             SubCommand::with_name("init").about("Initialize the ledger directory then exit"),
         )
         .subcommand(commands::monitor::command(default_args))
+        .subcommand(commands::packet_stats::command(default_args))
+        .subcommand(commands::peer_filter::command(default_args))
         .subcommand(SubCommand::with_name("run").about("Run the validator"))
         .subcommand(commands::plugin::command(default_args))
+        .subcommand(commands::quic_servers::command(default_args))
+        .subcommand(commands::read_only_accounts_cache::command(default_args))
+        .subcommand(commands::rpc_requests::command(default_args))
         .subcommand(commands::set_identity::command(default_args))
         .subcommand(commands::set_log_filter::command(default_args))
         .subcommand(commands::staked_nodes_overrides::command(default_args))
+        .subcommand(commands::top::command(default_args))
         .subcommand(commands::wait_for_restart_window::command(default_args))
+        .subcommand(commands::wen_restart::command(default_args))
         .subcommand(commands::set_public_address::command(default_args));
 }
 
@@ -2003,8 +2513,10 @@ pub fn warn_for_deprecated_arguments(matches: &ArgMatches) {
 
 pub struct DefaultArgs {
     pub bind_address: String,
+    pub entrypoint_refresh_interval: String,
     pub dynamic_port_range: String,
     pub ledger_path: String,
+    pub log_format: String,
 
     pub genesis_archive_unpacked_size: String,
     pub health_check_slot_distance: String,
@@ -2030,6 +2542,14 @@ pub struct DefaultArgs {
     pub rpc_bigtable_app_profile_id: String,
     pub rpc_bigtable_max_message_size: String,
     pub rpc_max_request_body_size: String,
+    pub rpc_webhook_max_per_client: String,
+    pub rpc_webhook_max_notifications_per_minute: String,
+    pub transaction_ingestion_log_capacity: String,
+    pub epoch_stakes_archive_max_epochs: String,
+    pub supply_audit_max_entries: String,
+    pub identity_top_up_threshold: String,
+    pub identity_top_up_target: String,
+    pub identity_top_up_max_per_day: String,
     pub rpc_pubsub_worker_threads: String,
     pub rpc_pubsub_notification_threads: String,
 
@@ -2037,6 +2557,7 @@ pub struct DefaultArgs {
     pub maximum_full_snapshot_archives_to_retain: String,
     pub maximum_incremental_snapshot_archives_to_retain: String,
     pub snapshot_packager_niceness_adjustment: String,
+    pub snapshot_hash_gossip_quorum: String,
     pub full_snapshot_archive_interval_slots: String,
     pub incremental_snapshot_archive_interval_slots: String,
     pub min_snapshot_download_speed: String,
@@ -2058,6 +2579,9 @@ pub struct DefaultArgs {
 
     pub tpu_max_connections_per_peer: String,
     pub tpu_max_connections_per_ipaddr_per_minute: String,
+    pub tpu_max_connections_per_ipaddr: String,
+    pub tpu_max_connections_per_second: String,
+    pub tpu_staked_eviction_policy: String,
     pub tpu_max_staked_connections: String,
     pub tpu_max_unstaked_connections: String,
     pub tpu_max_fwd_staked_connections: String,
@@ -2077,7 +2601,12 @@ pub struct DefaultArgs {
 
     pub banking_trace_dir_byte_limit: String,
 
+    // Conflict graph subcommand
+    pub conflict_graph_max_transactions: String,
+
     pub wen_restart_path: String,
+    pub wen_restart_repair_threshold_percent: String,
+    pub wen_restart_heaviest_fork_threshold_delta_percent: String,
 
     pub thread_args: DefaultThreadArgs,
 }
@@ -2088,7 +2617,9 @@ impl DefaultArgs {
 
         DefaultArgs {
             bind_address: "0.0.0.0".to_string(),
+            entrypoint_refresh_interval: DEFAULT_ENTRYPOINT_REFRESH_INTERVAL_SECS.to_string(),
             ledger_path: "ledger".to_string(),
+            log_format: "text".to_string(),
             dynamic_port_range: format!("{}-{}", VALIDATOR_PORT_RANGE.0, VALIDATOR_PORT_RANGE.1),
             maximum_local_snapshot_age: "2500".to_string(),
             genesis_archive_unpacked_size: MAX_GENESIS_ARCHIVE_UNPACKED_SIZE.to_string(),
@@ -2140,6 +2671,7 @@ impl DefaultArgs {
             maximum_incremental_snapshot_archives_to_retain:
                 DEFAULT_MAX_INCREMENTAL_SNAPSHOT_ARCHIVES_TO_RETAIN.to_string(),
             snapshot_packager_niceness_adjustment: "0".to_string(),
+            snapshot_hash_gossip_quorum: "0".to_string(),
             full_snapshot_archive_interval_slots: DEFAULT_FULL_SNAPSHOT_ARCHIVE_INTERVAL_SLOTS
                 .to_string(),
             incremental_snapshot_archive_interval_slots:
@@ -2159,6 +2691,9 @@ impl DefaultArgs {
             tpu_connection_pool_size: DEFAULT_TPU_CONNECTION_POOL_SIZE.to_string(),
             tpu_max_connections_per_ipaddr_per_minute:
                 DEFAULT_MAX_CONNECTIONS_PER_IPADDR_PER_MINUTE.to_string(),
+            tpu_max_connections_per_ipaddr: DEFAULT_MAX_CONNECTIONS_PER_IPADDR.to_string(),
+            tpu_max_connections_per_second: DEFAULT_MAX_CONNECTIONS_PER_SECOND.to_string(),
+            tpu_staked_eviction_policy: StakedEvictionPolicy::default().to_string(),
             vote_use_quic: DEFAULT_VOTE_USE_QUIC.to_string(),
             tpu_max_connections_per_peer: DEFAULT_MAX_QUIC_CONNECTIONS_PER_PEER.to_string(),
             tpu_max_staked_connections: DEFAULT_MAX_STAKED_CONNECTIONS.to_string(),
@@ -2170,12 +2705,29 @@ impl DefaultArgs {
             tpu_max_streams_per_ms: DEFAULT_MAX_STREAMS_PER_MS.to_string(),
             num_quic_endpoints: DEFAULT_QUIC_ENDPOINTS.to_string(),
             rpc_max_request_body_size: MAX_REQUEST_BODY_SIZE.to_string(),
+            rpc_webhook_max_per_client: WebhookConfig::default()
+                .max_webhooks_per_client
+                .to_string(),
+            rpc_webhook_max_notifications_per_minute: WebhookConfig::default()
+                .max_notifications_per_client_per_minute
+                .to_string(),
+            transaction_ingestion_log_capacity: DEFAULT_TRANSACTION_INGESTION_LOG_CAPACITY
+                .to_string(),
+            epoch_stakes_archive_max_epochs: DEFAULT_EPOCH_STAKES_ARCHIVE_MAX_EPOCHS.to_string(),
+            supply_audit_max_entries: DEFAULT_SUPPLY_AUDIT_MAX_ENTRIES.to_string(),
+            identity_top_up_threshold: "1".to_string(),
+            identity_top_up_target: "5".to_string(),
+            identity_top_up_max_per_day: "10".to_string(),
             exit_min_idle_time: "10".to_string(),
             exit_max_delinquent_stake: "5".to_string(),
             wait_for_restart_window_min_idle_time: "10".to_string(),
             wait_for_restart_window_max_delinquent_stake: "5".to_string(),
             banking_trace_dir_byte_limit: BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT.to_string(),
+            conflict_graph_max_transactions: DEFAULT_MAX_SAMPLED_TRANSACTIONS.to_string(),
             wen_restart_path: "wen_restart_progress.proto".to_string(),
+            wen_restart_repair_threshold_percent: DEFAULT_REPAIR_THRESHOLD_PERCENT.to_string(),
+            wen_restart_heaviest_fork_threshold_delta_percent:
+                DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT.to_string(),
             thread_args: DefaultThreadArgs::default(),
         }
     }
@@ -2299,7 +2851,12 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .long("account-index")
                 .takes_value(true)
                 .multiple(true)
-                .possible_values(&["program-id", "spl-token-owner", "spl-token-mint"])
+                .possible_values(&[
+                    "program-id",
+                    "spl-token-owner",
+                    "spl-token-mint",
+                    "stake-voter",
+                ])
                 .value_name("INDEX")
                 .help("Enable an accounts index, indexed by the selected account field"),
         )
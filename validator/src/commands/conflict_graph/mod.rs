@@ -0,0 +1,108 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::{is_parsable, is_slot},
+    solana_sdk::clock::Slot,
+    std::{fs, path::Path, process::exit, thread::sleep, time::Duration},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub fn command(default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("conflict-graph")
+        .about(
+            "Sample the account conflict graph of the transactions scheduled during the next \
+             leader slot, write it to DOT and JSON files and display how parallelizable the \
+             slot was",
+        )
+        .arg(
+            Arg::with_name("slot")
+                .long("slot")
+                .value_name("SLOT")
+                .takes_value(true)
+                .validator(is_slot)
+                .help("Sample the first leader slot at or after this slot [default: next slot]"),
+        )
+        .arg(
+            Arg::with_name("max_transactions")
+                .long("max-transactions")
+                .value_name("COUNT")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .default_value(&default_args.conflict_graph_max_transactions)
+                .help("Maximum number of transactions sampled"),
+        )
+        .arg(
+            Arg::with_name("output_dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .takes_value(true)
+                .help("Directory to write the graph into [default: the ledger directory]"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    let slot = value_t!(matches, "slot", Slot).ok();
+    let max_transactions = value_t_or_exit!(matches, "max_transactions", usize);
+    let output_mode = matches.value_of("output");
+    // The validator may not run from the same directory
+    let output_dir = fs::canonicalize(
+        matches
+            .value_of("output_dir")
+            .map_or(ledger_path, Path::new),
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("Invalid output directory: {err}");
+        exit(1);
+    });
+    let output_dir = output_dir.display().to_string();
+
+    let runtime = admin_rpc_service::runtime();
+    let min_slot = runtime
+        .block_on(async {
+            admin_rpc_service::connect(ledger_path)
+                .await?
+                .sample_conflict_graph(slot, Some(max_transactions))
+                .await
+        })
+        .unwrap_or_else(|err| {
+            eprintln!("sampleConflictGraph request failed: {err}");
+            exit(1);
+        });
+    eprintln!("Waiting for the first leader slot at or after slot {min_slot}...");
+
+    let conflict_graph = loop {
+        sleep(POLL_INTERVAL);
+        let conflict_graph = runtime
+            .block_on(async {
+                admin_rpc_service::connect(ledger_path)
+                    .await?
+                    .conflict_graph(output_dir.clone())
+                    .await
+            })
+            .unwrap_or_else(|err| {
+                eprintln!("conflictGraph request failed: {err}");
+                exit(1);
+            });
+        if let Some(conflict_graph) = conflict_graph {
+            break conflict_graph;
+        }
+    };
+    if let Some(mode) = output_mode {
+        match mode {
+            "json" => println!("{}", serde_json::to_string_pretty(&conflict_graph).unwrap()),
+            "json-compact" => print!("{}", serde_json::to_string(&conflict_graph).unwrap()),
+            _ => unreachable!(),
+        }
+    } else {
+        print!("{conflict_graph}");
+    }
+}
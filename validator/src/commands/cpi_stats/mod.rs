@@ -0,0 +1,48 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{App, Arg, ArgMatches, SubCommand},
+    std::{path::Path, process::exit},
+};
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("cpi-stats")
+        .about(
+            "Display the CPI depth and count distributions of the instructions executed by the \
+             validator, per program. Requires the validator to run with --enable-cpi-stats",
+        )
+        .arg(
+            Arg::with_name("reset")
+                .long("reset")
+                .takes_value(false)
+                .help("Reset the stats after displaying them"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    let reset = matches.is_present("reset");
+    let output_mode = matches.value_of("output");
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let cpi_stats = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.cpi_stats(Some(reset)).await })
+        .unwrap_or_else(|err| {
+            eprintln!("CPI stats query failed: {err}");
+            exit(1);
+        });
+    if let Some(mode) = output_mode {
+        match mode {
+            "json" => println!("{}", serde_json::to_string_pretty(&cpi_stats).unwrap()),
+            "json-compact" => print!("{}", serde_json::to_string(&cpi_stats).unwrap()),
+            _ => unreachable!(),
+        }
+    } else {
+        print!("{cpi_stats}");
+    }
+}
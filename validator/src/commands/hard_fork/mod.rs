@@ -0,0 +1,106 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{value_t, value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::{is_hash, is_parsable, is_slot},
+    solana_sdk::clock::Slot,
+    std::{path::Path, process::exit},
+};
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("hard-fork")
+        .about("Coordinate the hard fork of a cluster restart with other operators")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("propose")
+                .about("Advertise the hard fork this validator intends to restart with in gossip")
+                .arg(
+                    Arg::with_name("slot")
+                        .index(1)
+                        .value_name("SLOT")
+                        .required(true)
+                        .takes_value(true)
+                        .validator(is_slot)
+                        .help("Slot of the hard fork, as passed to --hard-fork"),
+                )
+                .arg(
+                    Arg::with_name("bank_hash")
+                        .long("bank-hash")
+                        .value_name("HASH")
+                        .takes_value(true)
+                        .validator(is_hash)
+                        .help(
+                            "Expected bank hash of the hard fork slot [default: the bank hash \
+                             of the slot if it has been replayed by this validator]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("expected_shred_version")
+                        .long("expected-shred-version")
+                        .value_name("VERSION")
+                        .takes_value(true)
+                        .validator(is_parsable::<u16>)
+                        .help("Shred version of the cluster after the hard fork"),
+                )
+                .after_help(
+                    "Note: the proposal is only advertised by the currently running validator \
+                     instance",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("list")
+                .about("Display the hard fork proposals advertised in gossip and their stake")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode"),
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    match matches.subcommand() {
+        ("propose", Some(subcommand_matches)) => {
+            let slot = value_t_or_exit!(subcommand_matches, "slot", Slot);
+            let bank_hash = subcommand_matches.value_of("bank_hash").map(String::from);
+            let expected_shred_version =
+                value_t!(subcommand_matches, "expected_shred_version", u16).ok();
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            admin_rpc_service::runtime()
+                .block_on(async move {
+                    admin_client
+                        .await?
+                        .propose_hard_fork(slot, bank_hash, expected_shred_version)
+                        .await
+                })
+                .unwrap_or_else(|err| {
+                    eprintln!("proposeHardFork request failed: {err}");
+                    exit(1);
+                });
+            println!("Proposed hard fork at slot {slot}");
+        }
+        ("list", Some(subcommand_matches)) => {
+            let output_mode = subcommand_matches.value_of("output");
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let proposals = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.hard_fork_proposals().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("Hard fork proposals query failed: {err}");
+                    exit(1);
+                });
+            if let Some(mode) = output_mode {
+                match mode {
+                    "json" => println!("{}", serde_json::to_string_pretty(&proposals).unwrap()),
+                    "json-compact" => print!("{}", serde_json::to_string(&proposals).unwrap()),
+                    _ => unreachable!(),
+                }
+            } else {
+                print!("{proposals}");
+            }
+        }
+        _ => unreachable!(),
+    }
+}
@@ -1,12 +1,23 @@
 pub mod authorized_voter;
+pub mod conflict_graph;
+pub mod connections;
 pub mod contact_info;
+pub mod cpi_stats;
 pub mod exit;
+pub mod hard_fork;
 pub mod monitor;
+pub mod packet_stats;
+pub mod peer_filter;
 pub mod plugin;
+pub mod quic_servers;
+pub mod read_only_accounts_cache;
 pub mod repair_shred_from_peer;
 pub mod repair_whitelist;
+pub mod rpc_requests;
 pub mod set_identity;
 pub mod set_log_filter;
 pub mod set_public_address;
 pub mod staked_nodes_overrides;
+pub mod top;
 pub mod wait_for_restart_window;
+pub mod wen_restart;
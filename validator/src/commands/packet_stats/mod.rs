@@ -0,0 +1,51 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{App, Arg, ArgMatches, SubCommand},
+    std::{path::Path, process::exit},
+};
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("packet-stats")
+        .about(
+            "Display the number of packets dropped by the validator before reaching a block, \
+             per reason, since it started or the stats were last reset",
+        )
+        .arg(
+            Arg::with_name("reset")
+                .long("reset")
+                .takes_value(false)
+                .help("Reset the stats after displaying them"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    let reset = matches.is_present("reset");
+    let output_mode = matches.value_of("output");
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let packet_drop_stats = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.packet_drop_stats(Some(reset)).await })
+        .unwrap_or_else(|err| {
+            eprintln!("Packet stats query failed: {err}");
+            exit(1);
+        });
+    if let Some(mode) = output_mode {
+        match mode {
+            "json" => println!(
+                "{}",
+                serde_json::to_string_pretty(&packet_drop_stats).unwrap()
+            ),
+            "json-compact" => print!("{}", serde_json::to_string(&packet_drop_stats).unwrap()),
+            _ => unreachable!(),
+        }
+    } else {
+        print!("{packet_drop_stats}");
+    }
+}
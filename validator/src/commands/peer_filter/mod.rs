@@ -0,0 +1,97 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{values_t, App, AppSettings, Arg, ArgMatches, SubCommand},
+    solana_gossip::peer_filter::is_peer_filter_entry,
+    std::{path::Path, process::exit},
+};
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("peer-filter")
+        .about("Manage the validator's peer allow and deny lists")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("get")
+                .about("Display the validator's peer allow and deny lists")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set")
+                .about("Replace the validator's peer allow and deny lists")
+                .arg(
+                    Arg::with_name("allow")
+                        .long("allow")
+                        .validator(is_peer_filter_entry)
+                        .value_name("PUBKEY OR IP RANGE")
+                        .multiple(true)
+                        .takes_value(true)
+                        .help("Peer identities and IP ranges to exclusively accept"),
+                )
+                .arg(
+                    Arg::with_name("deny")
+                        .long("deny")
+                        .validator(is_peer_filter_entry)
+                        .value_name("PUBKEY OR IP RANGE")
+                        .multiple(true)
+                        .takes_value(true)
+                        .help("Peer identities and IP ranges to reject"),
+                )
+                .after_help(
+                    "Note: peer filter changes only apply to the currently running validator instance",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("remove-all")
+                .about("Clear the validator's peer allow and deny lists")
+                .after_help(
+                    "Note: peer filter changes only apply to the currently running validator instance",
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    match matches.subcommand() {
+        ("get", Some(subcommand_matches)) => {
+            let output_mode = subcommand_matches.value_of("output");
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let peer_filter = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.peer_filter().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("Peer filter query failed: {err}");
+                    exit(1);
+                });
+            match output_mode {
+                Some("json") => println!("{}", serde_json::to_string_pretty(&peer_filter).unwrap()),
+                Some("json-compact") => print!("{}", serde_json::to_string(&peer_filter).unwrap()),
+                Some(_) => unreachable!(),
+                None => print!("{peer_filter}"),
+            }
+        }
+        ("set", Some(subcommand_matches)) => {
+            let allowed = values_t!(subcommand_matches, "allow", String).unwrap_or_default();
+            let denied = values_t!(subcommand_matches, "deny", String).unwrap_or_default();
+            set_peer_filter(ledger_path, allowed, denied);
+        }
+        ("remove-all", _) => {
+            set_peer_filter(ledger_path, Vec::default(), Vec::default());
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn set_peer_filter(ledger_path: &Path, allowed: Vec<String>, denied: Vec<String>) {
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.set_peer_filter(allowed, denied).await })
+        .unwrap_or_else(|err| {
+            eprintln!("setPeerFilter request failed: {err}");
+            exit(1);
+        });
+}
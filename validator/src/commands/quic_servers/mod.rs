@@ -0,0 +1,41 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{App, Arg, ArgMatches, SubCommand},
+    std::{path::Path, process::exit},
+};
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("quic-servers")
+        .about(
+            "Display the connection tables of the validator's inbound QUIC servers, the IP \
+             addresses with the most connections, and the connections evicted or refused",
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    let output_mode = matches.value_of("output");
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let quic_servers = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.quic_servers().await })
+        .unwrap_or_else(|err| {
+            eprintln!("QUIC servers query failed: {err}");
+            exit(1);
+        });
+    if let Some(mode) = output_mode {
+        match mode {
+            "json" => println!("{}", serde_json::to_string_pretty(&quic_servers).unwrap()),
+            "json-compact" => print!("{}", serde_json::to_string(&quic_servers).unwrap()),
+            _ => unreachable!(),
+        }
+    } else {
+        print!("{quic_servers}");
+    }
+}
@@ -0,0 +1,104 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{values_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::is_parsable,
+    std::{path::Path, process::exit},
+};
+
+const MB: usize = 1024 * 1024;
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("read-only-accounts-cache")
+        .about("Manage the validator's read-only accounts cache")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("stats")
+                .about("Display the size of the cache and its hits, misses and evictions per fork")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-limit")
+                .about("Set the size of the cache")
+                .arg(
+                    Arg::with_name("limit_mb")
+                        .index(1)
+                        .value_name("MEGABYTES")
+                        .validator(is_parsable::<usize>)
+                        .takes_value(true)
+                        .min_values(1)
+                        .max_values(2)
+                        .required(true)
+                        .help(
+                            "How large the cache can get, in megabytes. If given a single \
+                             value, it is used as both the low and high watermarks. If given two \
+                             values, they are the low and high watermarks: the cache is shrunk \
+                             to the low watermark once it exceeds the high watermark",
+                        ),
+                )
+                .after_help(
+                    "Note: the limit only applies to the currently running validator instance",
+                ),
+        )
+        .subcommand(SubCommand::with_name("flush").about("Remove every account from the cache"))
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    match matches.subcommand() {
+        ("stats", Some(subcommand_matches)) => {
+            let output_mode = subcommand_matches.value_of("output");
+            let cache = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.read_only_accounts_cache().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("Read-only accounts cache query failed: {err}");
+                    exit(1);
+                });
+            if let Some(mode) = output_mode {
+                match mode {
+                    "json" => println!("{}", serde_json::to_string_pretty(&cache).unwrap()),
+                    "json-compact" => print!("{}", serde_json::to_string(&cache).unwrap()),
+                    _ => unreachable!(),
+                }
+            } else {
+                print!("{cache}");
+            }
+        }
+        ("set-limit", Some(subcommand_matches)) => {
+            let limits = values_t_or_exit!(subcommand_matches, "limit_mb", usize);
+            let (max_data_size_lo, max_data_size_hi) = match limits[..] {
+                [limit] => (limit * MB, limit * MB),
+                [lo, hi] => (lo * MB, hi * MB),
+                _ => unreachable!("clap enforces one or two values"),
+            };
+            admin_rpc_service::runtime()
+                .block_on(async move {
+                    admin_client
+                        .await?
+                        .set_read_only_accounts_cache_limit(max_data_size_lo, max_data_size_hi)
+                        .await
+                })
+                .unwrap_or_else(|err| {
+                    eprintln!("setReadOnlyAccountsCacheLimit request failed: {err}");
+                    exit(1);
+                });
+        }
+        ("flush", _) => {
+            let num_flushed = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.flush_read_only_accounts_cache().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("flushReadOnlyAccountsCache request failed: {err}");
+                    exit(1);
+                });
+            println!("Flushed {num_flushed} accounts");
+        }
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,87 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    console::Term,
+    solana_clap_utils::input_validators::is_parsable,
+    std::{path::Path, process::exit},
+};
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("top")
+        .about(
+            "Display the validator's CPU usage and disk IO per thread, and its memory \
+             allocation, refreshing until interrupted",
+        )
+        .arg(
+            Arg::with_name("interval")
+                .long("interval")
+                .takes_value(true)
+                .value_name("MILLISECONDS")
+                .default_value("1000")
+                .validator(is_parsable::<u64>)
+                .help("Sample interval, at most 10 seconds"),
+        )
+        .arg(
+            Arg::with_name("limit")
+                .long("limit")
+                .takes_value(true)
+                .value_name("COUNT")
+                .default_value("25")
+                .validator(is_parsable::<usize>)
+                .help("Number of thread groups to display, busiest first"),
+        )
+        .arg(
+            Arg::with_name("once")
+                .long("once")
+                .takes_value(false)
+                .help("Display a single sample then exit"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .requires("once")
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    let interval_ms = value_t_or_exit!(matches, "interval", u64);
+    let limit = value_t_or_exit!(matches, "limit", usize);
+    let once = matches.is_present("once");
+    let output_mode = matches.value_of("output");
+
+    let runtime = admin_rpc_service::runtime();
+    let admin_client = runtime
+        .block_on(admin_rpc_service::connect(ledger_path))
+        .unwrap_or_else(|err| {
+            eprintln!("Unable to connect to the validator: {err}");
+            exit(1);
+        });
+    let term = Term::stdout();
+    loop {
+        let mut profile = runtime
+            .block_on(admin_client.self_profile(Some(interval_ms)))
+            .unwrap_or_else(|err| {
+                eprintln!("Self profile query failed: {err}");
+                exit(1);
+            });
+        profile.threads.truncate(limit);
+        match output_mode {
+            Some("json") => println!("{}", serde_json::to_string_pretty(&profile).unwrap()),
+            Some("json-compact") => print!("{}", serde_json::to_string(&profile).unwrap()),
+            Some(_) => unreachable!(),
+            None => {
+                if !once {
+                    let _ = term.clear_screen();
+                }
+                print!("{profile}");
+            }
+        }
+        if once {
+            break;
+        }
+    }
+}
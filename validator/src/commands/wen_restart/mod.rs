@@ -0,0 +1,54 @@
+use {
+    crate::cli::DefaultArgs,
+    clap::{value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
+    solana_wen_restart::wen_restart_status::read_wen_restart_status,
+    std::{
+        path::{Path, PathBuf},
+        process::exit,
+    },
+};
+
+pub fn command(default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("wen-restart")
+        .about("Inspect a coordinated cluster restart")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("status")
+                .about("Display the aggregation progress of the wen_restart")
+                .arg(
+                    Arg::with_name("wen_restart_progress")
+                        .long("wen-restart-progress")
+                        .value_name("FILE")
+                        .takes_value(true)
+                        .default_value(&default_args.wen_restart_path)
+                        .help(
+                            "Progress file given to --wen-restart. \
+                            Relative paths are resolved against the ledger directory",
+                        ),
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    match matches.subcommand() {
+        ("status", Some(subcommand_matches)) => {
+            let wen_restart_path =
+                value_t_or_exit!(subcommand_matches, "wen_restart_progress", PathBuf);
+            let wen_restart_path = if wen_restart_path.is_absolute() {
+                wen_restart_path
+            } else {
+                ledger_path.join(wen_restart_path)
+            };
+            let status = read_wen_restart_status(&wen_restart_path).unwrap_or_else(|err| {
+                eprintln!(
+                    "Unable to read wen_restart progress from {}: {err}",
+                    wen_restart_path.display()
+                );
+                exit(1);
+            });
+            print!("{status}");
+        }
+        _ => unreachable!(),
+    }
+}
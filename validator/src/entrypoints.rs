@@ -0,0 +1,264 @@
+//! Gossip entrypoints with failover.
+//!
+//! Each entrypoint is given as `HOST:PORT[@PRIORITY]`. Entrypoints are resolved and health
+//! checked periodically, and only the reachable entrypoints of the best (lowest) priority are
+//! used, so that the validator fails over to the next priority when they all become
+//! unreachable and back once they recover.
+
+use {
+    log::*,
+    rand::{seq::SliceRandom, thread_rng},
+    solana_gossip::{cluster_info::ClusterInfo, contact_info::ContactInfo},
+    solana_metrics::datapoint_info,
+    solana_streamer::socket::SocketAddrSpace,
+    std::{
+        collections::HashSet,
+        net::{SocketAddr, TcpStream},
+        str::FromStr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{sleep, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+/// How long to wait for an entrypoint's ip echo server to accept a connection
+pub const ENTRYPOINT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+pub const DEFAULT_ENTRYPOINT_REFRESH_INTERVAL_SECS: u64 = 60;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entrypoint {
+    pub host_port: String,
+    pub priority: u8,
+}
+
+impl FromStr for Entrypoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (host_port, priority) = match s.rsplit_once('@') {
+            Some((host_port, priority)) => (
+                host_port,
+                priority
+                    .parse()
+                    .map_err(|err| format!("Invalid entrypoint priority {priority}: {err}"))?,
+            ),
+            None => (s, 0),
+        };
+        let (host, port) = host_port
+            .rsplit_once(':')
+            .ok_or_else(|| format!("Missing port in entrypoint: {host_port}"))?;
+        if host.is_empty() {
+            return Err(format!("Missing host in entrypoint: {host_port}"));
+        }
+        port.parse::<u16>()
+            .map_err(|err| format!("Invalid entrypoint port {port}: {err}"))?;
+        Ok(Self {
+            host_port: host_port.to_string(),
+            priority,
+        })
+    }
+}
+
+/// Validates the syntax of an entrypoint without resolving it, so that an unresolvable
+/// entrypoint does not prevent the validator from starting with the others.
+pub fn is_entrypoint(string: String) -> Result<(), String> {
+    Entrypoint::from_str(&string).map(|_| ())
+}
+
+pub struct Entrypoints {
+    entrypoints: Vec<Entrypoint>,
+    socket_addr_space: SocketAddrSpace,
+}
+
+impl Entrypoints {
+    pub fn new(entrypoints: Vec<Entrypoint>, socket_addr_space: SocketAddrSpace) -> Self {
+        Self {
+            entrypoints,
+            socket_addr_space,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entrypoints.is_empty()
+    }
+
+    /// Resolves the entrypoints, grouped by priority from best to worst.
+    /// Entrypoints which fail to resolve are skipped.
+    fn resolve(&self) -> Vec<(u8, SocketAddr)> {
+        let mut addrs: Vec<_> = self
+            .entrypoints
+            .iter()
+            .filter_map(|entrypoint| {
+                match solana_net_utils::parse_host_port(&entrypoint.host_port) {
+                    Ok(addr) if self.socket_addr_space.check(&addr) => {
+                        Some((entrypoint.priority, addr))
+                    }
+                    Ok(addr) => {
+                        warn!("invalid entrypoint address: {addr}");
+                        None
+                    }
+                    Err(err) => {
+                        warn!("failed to resolve entrypoint: {err}");
+                        None
+                    }
+                }
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        // Spread the load across entrypoints of the same priority
+        addrs.shuffle(&mut thread_rng());
+        addrs.sort_by_key(|(priority, _)| *priority);
+        addrs
+    }
+
+    /// Returns the reachable entrypoints with the best priority. If no entrypoint is reachable,
+    /// all resolved entrypoints are returned so that gossip keeps trying them.
+    pub fn active_addrs(&self) -> Vec<SocketAddr> {
+        let addrs = self.resolve();
+        let mut healthy = addrs
+            .iter()
+            .filter(|(_, addr)| is_reachable(addr, ENTRYPOINT_HEALTH_CHECK_TIMEOUT))
+            .peekable();
+        match healthy.peek() {
+            Some((best_priority, _)) => {
+                let best_priority = *best_priority;
+                healthy
+                    .take_while(|(priority, _)| *priority == best_priority)
+                    .map(|(_, addr)| *addr)
+                    .collect()
+            }
+            None => addrs.into_iter().map(|(_, addr)| addr).collect(),
+        }
+    }
+
+    /// Periodically replaces the `cluster_entrypoints` given to `cluster_info` with the active
+    /// entrypoints until `exit` is set.
+    pub fn start_refresh(
+        self: &Arc<Self>,
+        cluster_info: Arc<ClusterInfo>,
+        cluster_entrypoints: &[ContactInfo],
+        refresh_interval: Duration,
+        exit: Arc<AtomicBool>,
+    ) -> JoinHandle<()> {
+        let entrypoints = self.clone();
+        let mut current_addrs: HashSet<_> = cluster_entrypoints
+            .iter()
+            .filter_map(ContactInfo::gossip)
+            .collect();
+        Builder::new()
+            .name("solEntrypoints".to_string())
+            .spawn(move || {
+                let mut last_refresh = Instant::now();
+                while !exit.load(Ordering::Relaxed) {
+                    if last_refresh.elapsed() < refresh_interval {
+                        sleep(Duration::from_millis(100));
+                        continue;
+                    }
+                    last_refresh = Instant::now();
+                    entrypoints.refresh(&cluster_info, &mut current_addrs);
+                }
+            })
+            .unwrap()
+    }
+
+    fn refresh(&self, cluster_info: &ClusterInfo, current_addrs: &mut HashSet<SocketAddr>) {
+        let active_addrs = self.active_addrs();
+        if active_addrs.is_empty() {
+            warn!("Unable to resolve any entrypoint, keeping the current ones");
+            return;
+        }
+        let active_addrs_set: HashSet<_> = active_addrs.iter().copied().collect();
+        if *current_addrs == active_addrs_set {
+            return;
+        }
+        info!("Switching entrypoints from {current_addrs:?} to {active_addrs:?}");
+        datapoint_info!(
+            "entrypoint-failover",
+            ("num_entrypoints", active_addrs.len(), i64),
+            ("entrypoints", format!("{active_addrs:?}"), String),
+        );
+        cluster_info.set_entrypoints(
+            active_addrs
+                .iter()
+                .map(ContactInfo::new_gossip_entry_point)
+                .collect(),
+        );
+        *current_addrs = active_addrs_set;
+    }
+}
+
+/// Entrypoints run an ip echo server on their gossip port
+fn is_reachable(addr: &SocketAddr, timeout: Duration) -> bool {
+    match TcpStream::connect_timeout(addr, timeout) {
+        Ok(_) => true,
+        Err(err) => {
+            warn!("entrypoint {addr} is unreachable: {err}");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::net::TcpListener};
+
+    #[test]
+    fn test_parse_entrypoint() {
+        assert_eq!(
+            "entrypoint.x1.xyz:8001".parse::<Entrypoint>(),
+            Ok(Entrypoint {
+                host_port: "entrypoint.x1.xyz:8001".to_string(),
+                priority: 0,
+            })
+        );
+        assert_eq!(
+            "127.0.0.1:8001@2".parse::<Entrypoint>(),
+            Ok(Entrypoint {
+                host_port: "127.0.0.1:8001".to_string(),
+                priority: 2,
+            })
+        );
+        assert!(is_entrypoint("127.0.0.1".to_string()).is_err());
+        assert!(is_entrypoint(":8001".to_string()).is_err());
+        assert!(is_entrypoint("127.0.0.1:port".to_string()).is_err());
+        assert!(is_entrypoint("127.0.0.1:8001@-1".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_active_addrs_failover() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let healthy = listener.local_addr().unwrap();
+        let unreachable = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap()
+        };
+        let entrypoint = |addr: SocketAddr, priority| Entrypoint {
+            host_port: addr.to_string(),
+            priority,
+        };
+
+        // The best priority is unreachable, fail over to the next one
+        let entrypoints = Entrypoints::new(
+            vec![entrypoint(unreachable, 0), entrypoint(healthy, 1)],
+            SocketAddrSpace::Unspecified,
+        );
+        assert_eq!(entrypoints.active_addrs(), vec![healthy]);
+
+        // Nothing is reachable, keep trying everything by priority
+        drop(listener);
+        assert_eq!(entrypoints.active_addrs(), vec![unreachable, healthy]);
+
+        let entrypoints = Entrypoints::new(
+            vec![Entrypoint {
+                host_port: "unresolvable.invalid:8001".to_string(),
+                priority: 0,
+            }],
+            SocketAddrSpace::Unspecified,
+        );
+        assert!(entrypoints.active_addrs().is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(clippy::arithmetic_side_effects)]
 pub use solana_test_validator as test_validator;
 use {
+    crate::logger::LogFormat,
     console::style,
     fd_lock::{RwLock, RwLockWriteGuard},
     indicatif::{ProgressDrawTarget, ProgressStyle},
@@ -21,6 +22,9 @@ pub mod bootstrap;
 pub mod cli;
 pub mod commands;
 pub mod dashboard;
+pub mod entrypoints;
+pub mod logger;
+pub mod self_profile;
 
 #[cfg(unix)]
 fn redirect_stderr(filename: &str) {
@@ -36,7 +40,10 @@ fn redirect_stderr(filename: &str) {
 // Redirect stderr to a file with support for logrotate by sending a SIGUSR1 to the process.
 //
 // Upon success, future `log` macros and `eprintln!()` can be found in the specified log file.
-pub fn redirect_stderr_to_file(logfile: Option<String>) -> Option<JoinHandle<()>> {
+pub fn redirect_stderr_to_file(
+    logfile: Option<String>,
+    log_format: LogFormat,
+) -> Option<JoinHandle<()>> {
     // Default to RUST_BACKTRACE=1 for more informative validator logs
     if env::var_os("RUST_BACKTRACE").is_none() {
         env::set_var("RUST_BACKTRACE", "1")
@@ -44,7 +51,7 @@ pub fn redirect_stderr_to_file(logfile: Option<String>) -> Option<JoinHandle<()>
 
     match logfile {
         None => {
-            solana_logger::setup_with_default_filter();
+            logger::setup(log_format);
             None
         }
         Some(logfile) => {
@@ -58,7 +65,7 @@ pub fn redirect_stderr_to_file(logfile: Option<String>) -> Option<JoinHandle<()>
                             exit(1);
                         });
 
-                solana_logger::setup_with_default_filter();
+                logger::setup(log_format);
                 redirect_stderr(&logfile);
                 Some(
                     std::thread::Builder::new()
@@ -79,6 +86,9 @@ pub fn redirect_stderr_to_file(logfile: Option<String>) -> Option<JoinHandle<()>
             {
                 println!("logrotate is not supported on this platform");
                 solana_logger::setup_file_with_default(&logfile, solana_logger::DEFAULT_FILTER);
+                if log_format != LogFormat::Text {
+                    log::warn!("{log_format:?} logs are not supported on this platform");
+                }
                 None
             }
         }
@@ -0,0 +1,139 @@
+//! Logging of the validator, in plain text or as one JSON object per line.
+//!
+//! The filter of the logger can be replaced at runtime with [`set_filter`], which is what the
+//! `setLogFilter` admin RPC does, and the format chosen at startup is kept across filter changes.
+
+use {
+    env_logger::{fmt::Formatter, Builder, Env, Logger},
+    lazy_static::lazy_static,
+    log::{Log, Metadata, Record},
+    serde_json::json,
+    std::{io::Write, str::FromStr, sync::RwLock, thread},
+};
+
+pub const LOG_FORMATS: &[&str] = &["text", "json"];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line with the timestamp, level, module and fields of the record
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown log format: {s}")),
+        }
+    }
+}
+
+lazy_static! {
+    static ref LOG_FORMAT: RwLock<LogFormat> = RwLock::default();
+    static ref LOGGER: RwLock<Option<Logger>> = RwLock::default();
+}
+
+/// Forwards to the current logger, as the logger installed in `log` cannot be replaced.
+struct LoggerShim;
+
+impl Log for LoggerShim {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        LOGGER
+            .read()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|logger| logger.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record) {
+        if let Some(logger) = LOGGER.read().unwrap().as_ref() {
+            logger.log(record);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn format_json(buf: &mut Formatter, record: &Record) -> std::io::Result<()> {
+    let entry = json!({
+        "timestamp": buf.timestamp_nanos().to_string(),
+        "level": record.level().as_str(),
+        "module": record.module_path().unwrap_or_else(|| record.target()),
+        "message": record.args().to_string(),
+        "fields": {
+            "target": record.target(),
+            "thread": thread::current().name(),
+            "file": record.file(),
+            "line": record.line(),
+        },
+    });
+    writeln!(buf, "{entry}")
+}
+
+fn builder(format: LogFormat) -> Builder {
+    let mut builder = Builder::new();
+    match format {
+        LogFormat::Text => builder.format_timestamp_nanos(),
+        LogFormat::Json => builder.format(format_json),
+    };
+    builder
+}
+
+fn replace_logger(logger: Logger) {
+    log::set_max_level(logger.filter());
+    *LOGGER.write().unwrap() = Some(logger);
+    let _ = log::set_boxed_logger(Box::new(LoggerShim));
+}
+
+/// Logs to standard error in `format`, filtered by `RUST_LOG` or the default filter.
+pub fn setup(format: LogFormat) {
+    *LOG_FORMAT.write().unwrap() = format;
+    let mut builder = builder(format);
+    builder.parse_env(Env::default().default_filter_or(solana_logger::DEFAULT_FILTER));
+    replace_logger(builder.build());
+}
+
+/// Replaces the filter of the logger, given in the format of `RUST_LOG`.
+pub fn set_filter(filter: &str) {
+    if LOGGER.read().unwrap().is_none() {
+        // Logging was set up elsewhere
+        solana_logger::setup_with(filter);
+        return;
+    }
+    let mut builder = builder(*LOG_FORMAT.read().unwrap());
+    builder.parse_filters(filter);
+    replace_logger(builder.build());
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, log::Level};
+
+    #[test]
+    fn test_log_format_from_str() {
+        for format in LOG_FORMATS {
+            assert!(format.parse::<LogFormat>().is_ok());
+        }
+        assert_eq!("json".parse(), Ok(LogFormat::Json));
+        assert!("yaml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut builder = builder(LogFormat::Json);
+        builder.parse_filters("warn,solana_core::replay_stage=debug");
+        let logger = builder.build();
+        let enabled = |target, level| {
+            logger.enabled(&Metadata::builder().target(target).level(level).build())
+        };
+        assert!(enabled("solana_core::replay_stage", Level::Debug));
+        assert!(!enabled("solana_core::replay_stage", Level::Trace));
+        assert!(!enabled("solana_core::banking_stage", Level::Info));
+        assert!(enabled("solana_core::banking_stage", Level::Warn));
+    }
+}
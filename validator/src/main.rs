@@ -18,10 +18,13 @@ use {
             create_and_canonicalize_directory,
         },
     },
-    solana_clap_utils::input_parsers::{keypair_of, keypairs_of, pubkey_of, value_of, values_of},
+    solana_clap_utils::input_parsers::{
+        keypair_of, keypairs_of, lamports_of_sol, pubkey_of, value_of, values_of,
+    },
     solana_core::{
         banking_trace::DISABLED_BAKING_TRACE_DIR,
         consensus::tower_storage,
+        identity_top_up_service::{IdentityTopUpConfig, DEFAULT_IDENTITY_TOP_UP_CHECK_INTERVAL},
         system_monitor_service::SystemMonitorService,
         tpu::DEFAULT_TPU_COALESCE,
         validator::{
@@ -30,9 +33,11 @@ use {
             ValidatorStartProgress, ValidatorTpuConfig,
         },
     },
+    solana_download_utils::snapshot_url::SnapshotUrl,
     solana_gossip::{
         cluster_info::{Node, NodeConfig},
         contact_info::ContactInfo,
+        peer_filter::{PeerFilter, PeerFilterEntry},
     },
     solana_ledger::{
         blockstore_cleanup_service::{DEFAULT_MAX_LEDGER_SHREDS, DEFAULT_MIN_MAX_LEDGER_SHREDS},
@@ -45,10 +50,18 @@ use {
     solana_perf::recycler::enable_recycler_warming,
     solana_poh::poh_service,
     solana_rpc::{
+        block_meta_service::ArchivalAccountsConfig,
+        epoch_stakes_archive::EpochStakesArchive,
         rpc::{JsonRpcConfig, RpcBigtableConfig},
         rpc_pubsub_service::PubSubConfig,
+        rpc_webhooks::WebhookConfig,
     },
     solana_runtime::{
+        bank::{
+            freeze_hooks::{BankFreezeHook, CapitalizationHook, DEFAULT_FREEZE_HOOK_TIME_BUDGET},
+            supply_audit::SupplyAudit,
+        },
+        program_cache_warmup::read_program_list,
         runtime_config::RuntimeConfig,
         snapshot_bank_utils::DISABLED_SNAPSHOT_ARCHIVE_INTERVAL,
         snapshot_config::{SnapshotConfig, SnapshotUsage},
@@ -60,8 +73,13 @@ use {
         pubkey::Pubkey,
         signature::{Keypair, Signer},
     },
-    solana_send_transaction_service::send_transaction_service,
-    solana_streamer::{quic::QuicServerParams, socket::SocketAddrSpace},
+    solana_send_transaction_service::{
+        send_transaction_service, transaction_ingestion_log::TransactionIngestionLog,
+    },
+    solana_streamer::{
+        quic::{QuicServerParams, StakedEvictionPolicy},
+        socket::SocketAddrSpace,
+    },
     solana_tpu_client::tpu_client::DEFAULT_TPU_ENABLE_UDP,
     std::{
         collections::HashSet,
@@ -72,7 +90,10 @@ use {
         path::{Path, PathBuf},
         process::exit,
         str::FromStr,
-        sync::{Arc, RwLock},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
         time::Duration,
     },
     tachyon_validator::{
@@ -80,7 +101,11 @@ use {
         admin_rpc_service::{load_staked_nodes_overrides, StakedNodesOverrides},
         bootstrap,
         cli::{self, app, warn_for_deprecated_arguments, DefaultArgs},
-        commands, ledger_lockfile, lock_ledger, redirect_stderr_to_file,
+        commands,
+        entrypoints::{Entrypoint, Entrypoints},
+        ledger_lockfile, lock_ledger,
+        logger::LogFormat,
+        redirect_stderr_to_file,
     },
 };
 
@@ -161,6 +186,7 @@ fn configure_banking_trace_dir_byte_limit(
         // explicit user-supplied override value
         value_t_or_exit!(matches, "banking_trace_dir_byte_limit", u64)
     };
+    validator_config.banking_trace_capture = matches.is_present("capture_banking_trace");
 }
 
 pub fn main() {
@@ -183,10 +209,30 @@ pub fn main() {
             commands::plugin::execute(plugin_subcommand_matches, &ledger_path);
             return;
         }
+        ("connections", Some(subcommand_matches)) => {
+            commands::connections::execute(subcommand_matches, &ledger_path);
+            return;
+        }
         ("contact-info", Some(subcommand_matches)) => {
             commands::contact_info::execute(subcommand_matches, &ledger_path);
             return;
         }
+        ("conflict-graph", Some(subcommand_matches)) => {
+            commands::conflict_graph::execute(subcommand_matches, &ledger_path);
+            return;
+        }
+        ("cpi-stats", Some(subcommand_matches)) => {
+            commands::cpi_stats::execute(subcommand_matches, &ledger_path);
+            return;
+        }
+        ("read-only-accounts-cache", Some(subcommand_matches)) => {
+            commands::read_only_accounts_cache::execute(subcommand_matches, &ledger_path);
+            return;
+        }
+        ("hard-fork", Some(subcommand_matches)) => {
+            commands::hard_fork::execute(subcommand_matches, &ledger_path);
+            return;
+        }
         ("init", _) => Operation::Initialize,
         ("exit", Some(subcommand_matches)) => {
             commands::exit::execute(subcommand_matches, &ledger_path);
@@ -196,10 +242,26 @@ pub fn main() {
             commands::monitor::execute(&matches, &ledger_path);
             return;
         }
+        ("packet-stats", Some(subcommand_matches)) => {
+            commands::packet_stats::execute(subcommand_matches, &ledger_path);
+            return;
+        }
+        ("quic-servers", Some(subcommand_matches)) => {
+            commands::quic_servers::execute(subcommand_matches, &ledger_path);
+            return;
+        }
+        ("rpc-requests", Some(subcommand_matches)) => {
+            commands::rpc_requests::execute(subcommand_matches, &ledger_path);
+            return;
+        }
         ("staked-nodes-overrides", Some(subcommand_matches)) => {
             commands::staked_nodes_overrides::execute(subcommand_matches, &ledger_path);
             return;
         }
+        ("top", Some(subcommand_matches)) => {
+            commands::top::execute(subcommand_matches, &ledger_path);
+            return;
+        }
         ("set-identity", Some(subcommand_matches)) => {
             commands::set_identity::execute(subcommand_matches, &ledger_path);
             return;
@@ -216,6 +278,10 @@ pub fn main() {
             commands::repair_shred_from_peer::execute(subcommand_matches, &ledger_path);
             return;
         }
+        ("peer-filter", Some(subcommand_matches)) => {
+            commands::peer_filter::execute(subcommand_matches, &ledger_path);
+            return;
+        }
         ("repair-whitelist", Some(repair_whitelist_subcommand_matches)) => {
             commands::repair_whitelist::execute(repair_whitelist_subcommand_matches, &ledger_path);
             return;
@@ -224,6 +290,10 @@ pub fn main() {
             commands::set_public_address::execute(subcommand_matches, &ledger_path);
             return;
         }
+        ("wen-restart", Some(subcommand_matches)) => {
+            commands::wen_restart::execute(subcommand_matches, &ledger_path);
+            return;
+        }
         _ => unreachable!(),
     };
 
@@ -265,7 +335,8 @@ pub fn main() {
         }
     };
     let use_progress_bar = logfile.is_none();
-    let _logger_thread = redirect_stderr_to_file(logfile);
+    let log_format = value_t_or_exit!(matches, "log_format", LogFormat);
+    let _logger_thread = redirect_stderr_to_file(logfile, log_format);
 
     info!("{} {}", crate_name!(), solana_version);
     info!("Starting validator with: {:#?}", std::env::args_os());
@@ -320,6 +391,12 @@ pub fn main() {
             u64
         ),
         incremental_snapshot_fetch: !matches.is_present("no_incremental_snapshots"),
+        snapshot_urls: if matches.is_present("snapshot_url") {
+            values_t_or_exit!(matches, "snapshot_url", SnapshotUrl)
+        } else {
+            vec![]
+        },
+        max_snapshot_download_speed: value_t!(matches, "maximum_snapshot_download_speed", u64).ok(),
     };
 
     let private_rpc = matches.is_present("private_rpc");
@@ -485,24 +562,20 @@ pub fn main() {
     } else {
         AccountShrinkThreshold::IndividualStore { shrink_ratio }
     };
-    let entrypoint_addrs = values_t!(matches, "entrypoint", String)
-        .unwrap_or_default()
-        .into_iter()
-        .map(|entrypoint| {
-            solana_net_utils::parse_host_port(&entrypoint).unwrap_or_else(|e| {
-                eprintln!("failed to parse entrypoint address: {e}");
-                exit(1);
-            })
-        })
-        .collect::<HashSet<_>>()
-        .into_iter()
-        .collect::<Vec<_>>();
-    for addr in &entrypoint_addrs {
-        if !socket_addr_space.check(addr) {
-            eprintln!("invalid entrypoint address: {addr}");
-            exit(1);
-        }
+    let entrypoints = Arc::new(Entrypoints::new(
+        values_t!(matches, "entrypoint", Entrypoint).unwrap_or_default(),
+        socket_addr_space,
+    ));
+    let entrypoint_addrs = entrypoints.active_addrs();
+    if !entrypoints.is_empty() && entrypoint_addrs.is_empty() {
+        eprintln!("Unable to resolve any entrypoint address");
+        exit(1);
     }
+    let entrypoint_refresh_interval =
+        match value_t_or_exit!(matches, "entrypoint_refresh_interval", u64) {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        };
     // TODO: Once entrypoints are updated to return shred-version, this should
     // abort if it fails to obtain a shred-version, so that nodes always join
     // gossip with a valid shred-version. The code to adopt entrypoint shred
@@ -563,6 +636,8 @@ pub fn main() {
 
     accounts_index_config.index_limit_mb = if matches.is_present("disable_accounts_disk_index") {
         IndexLimitMb::InMemOnly
+    } else if let Ok(mb) = value_t!(matches, "accounts_index_memory_budget_mb", usize) {
+        IndexLimitMb::Limit(mb)
     } else {
         IndexLimitMb::Unlimited
     };
@@ -693,6 +768,12 @@ pub fn main() {
             .is_present("no_accounts_db_experimental_accumulator_hash"),
         verify_experimental_accumulator_hash: matches
             .is_present("accounts_db_verify_experimental_accumulator_hash"),
+        experimental_accumulator_hash_dual_mode_epochs: value_t!(
+            matches,
+            "accounts_db_experimental_accumulator_hash_dual_mode_epochs",
+            u64
+        )
+        .unwrap_or_default(),
         snapshots_use_experimental_accumulator_hash: matches
             .is_present("accounts_db_snapshots_use_experimental_accumulator_hash"),
         num_clean_threads: Some(accounts_db_clean_threads),
@@ -782,6 +863,88 @@ pub fn main() {
         };
 
     let full_api = matches.is_present("full_rpc_api");
+    let webhook_config = matches
+        .is_present("enable_rpc_webhooks")
+        .then(|| WebhookConfig {
+            max_webhooks_per_client: value_t_or_exit!(matches, "rpc_webhook_max_per_client", usize),
+            max_notifications_per_client_per_minute: value_t_or_exit!(
+                matches,
+                "rpc_webhook_max_notifications_per_minute",
+                u64
+            ),
+            allowed_hosts: values_t!(matches, "rpc_webhook_allowed_hosts", String)
+                .ok()
+                .map(|hosts| hosts.into_iter().collect()),
+            ..WebhookConfig::default()
+        });
+    let transaction_ingestion_log =
+        matches
+            .is_present("enable_transaction_ingestion_log")
+            .then(|| {
+                Arc::new(TransactionIngestionLog::new(value_t_or_exit!(
+                    matches,
+                    "transaction_ingestion_log_capacity",
+                    usize
+                )))
+            });
+
+    let epoch_stakes_archive = matches.is_present("enable_epoch_stakes_archive").then(|| {
+        EpochStakesArchive::new(
+            ledger_path.join("epoch_stakes"),
+            value_t_or_exit!(matches, "epoch_stakes_archive_max_epochs", u64),
+        )
+        .map(Arc::new)
+        .unwrap_or_else(|err| {
+            eprintln!("Failed to create the epoch stakes archive: {err}");
+            exit(1);
+        })
+    });
+
+    let mut bank_freeze_hooks: Vec<(Arc<dyn BankFreezeHook>, Duration)> = vec![];
+    if matches.is_present("check_bank_capitalization") {
+        bank_freeze_hooks.push((
+            Arc::new(CapitalizationHook),
+            DEFAULT_FREEZE_HOOK_TIME_BUDGET,
+        ));
+    }
+    let supply_audit = matches.value_of("supply_audit").map(|action| {
+        Arc::new(SupplyAudit::new(
+            action.parse().unwrap(),
+            value_t_or_exit!(matches, "supply_audit_max_entries", usize),
+        ))
+    });
+    if let Some(supply_audit) = &supply_audit {
+        bank_freeze_hooks.push((supply_audit.clone(), DEFAULT_FREEZE_HOOK_TIME_BUDGET));
+    }
+
+    let identity_top_up = keypair_of(&matches, "identity_top_up_treasury").map(|treasury| {
+        let threshold = lamports_of_sol(&matches, "identity_top_up_threshold").unwrap();
+        let target = lamports_of_sol(&matches, "identity_top_up_target").unwrap();
+        if target < threshold {
+            eprintln!(
+                "--identity-top-up-target must not be lower than --identity-top-up-threshold"
+            );
+            exit(1);
+        }
+        IdentityTopUpConfig {
+            treasury: Arc::new(treasury),
+            nonce_account: pubkey_of(&matches, "identity_top_up_nonce_account"),
+            threshold,
+            target,
+            max_per_day: lamports_of_sol(&matches, "identity_top_up_max_per_day").unwrap(),
+            check_interval: DEFAULT_IDENTITY_TOP_UP_CHECK_INTERVAL,
+        }
+    });
+
+    let program_cache_warmup_programs = matches
+        .value_of("program_cache_warmup_file")
+        .map(|path| {
+            read_program_list(Path::new(path)).unwrap_or_else(|err| {
+                eprintln!("Failed to read the program cache warm-up file: {err}");
+                exit(1);
+            })
+        })
+        .unwrap_or_default();
 
     let mut validator_config = ValidatorConfig {
         require_tower: matches.is_present("require_tower"),
@@ -826,6 +989,19 @@ pub fn main() {
                 usize
             )),
             skip_preflight_health_check: matches.is_present("skip_preflight_health_check"),
+            webhook_config,
+            transaction_ingestion_log: transaction_ingestion_log.clone(),
+            supply_audit,
+            epoch_stakes_archive,
+            enable_program_fee_market: matches.is_present("enable_rpc_program_fee_market"),
+            archival_accounts: matches.is_present("enable_archival_accounts").then(|| {
+                ArchivalAccountsConfig {
+                    owners: values_t!(matches, "archival_accounts_owner", Pubkey)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .collect(),
+                }
+            }),
         },
         on_start_geyser_plugin_config_files,
         geyser_plugin_always_enabled: matches.is_present("geyser_plugin_always_enabled"),
@@ -866,7 +1042,12 @@ pub fn main() {
         known_validators,
         repair_validators,
         repair_whitelist,
+        repair_over_quic: matches.is_present("repair_over_quic"),
         gossip_validators,
+        peer_filter: PeerFilter::new(
+            values_t!(matches, "peer_allow", PeerFilterEntry).unwrap_or_default(),
+            values_t!(matches, "peer_deny", PeerFilterEntry).unwrap_or_default(),
+        ),
         max_ledger_shreds,
         blockstore_options,
         run_verification: !(matches.is_present("skip_poh_verify")
@@ -895,12 +1076,24 @@ pub fn main() {
                 usize
             ),
             tpu_peers: rpc_send_transaction_tpu_peers,
+            ingestion_log: transaction_ingestion_log,
         },
+        rpc_send_transaction_staked_identity: keypair_of(
+            &matches,
+            "rpc_send_transaction_staked_identity",
+        )
+        .map(Arc::new),
         no_poh_speed_test: matches.is_present("no_poh_speed_test"),
         no_os_memory_stats_reporting: matches.is_present("no_os_memory_stats_reporting"),
         no_os_network_stats_reporting: matches.is_present("no_os_network_stats_reporting"),
         no_os_cpu_stats_reporting: matches.is_present("no_os_cpu_stats_reporting"),
         no_os_disk_stats_reporting: matches.is_present("no_os_disk_stats_reporting"),
+        prometheus_bind_address: matches.value_of("prometheus_bind_address").map(|addr| {
+            solana_net_utils::parse_host_port(addr).unwrap_or_else(|e| {
+                eprintln!("failed to parse prometheus bind address: {e}");
+                exit(1);
+            })
+        }),
         poh_pinned_cpu_core: value_of(&matches, "poh_pinned_cpu_core")
             .unwrap_or(poh_service::DEFAULT_PINNED_CPU_CORE),
         poh_hashes_per_batch: value_of(&matches, "poh_hashes_per_batch")
@@ -929,8 +1122,35 @@ pub fn main() {
         tvu_shred_sigverify_threads: tvu_sigverify_threads,
         delay_leader_block_for_pending_fork: matches
             .is_present("delay_leader_block_for_pending_fork"),
+        program_cache_warmup_programs,
+        bank_freeze_hooks,
+        identity_top_up,
+        enable_cpi_stats: matches.is_present("enable_cpi_stats"),
+        program_cache_warmup_persist_count: value_t!(
+            matches,
+            "program_cache_warmup_persist_count",
+            usize
+        )
+        .unwrap_or_default(),
         wen_restart_proto_path: value_t!(matches, "wen_restart", PathBuf).ok(),
         wen_restart_coordinator: value_t!(matches, "wen_restart_coordinator", Pubkey).ok(),
+        wen_restart_repair_threshold_percent: value_t_or_exit!(
+            matches,
+            "wen_restart_repair_threshold_percent",
+            u64
+        ),
+        wen_restart_heaviest_fork_threshold_delta_percent: value_t_or_exit!(
+            matches,
+            "wen_restart_heaviest_fork_threshold_delta_percent",
+            u64
+        ),
+        wen_restart_last_voted_fork_slots_timeout: value_t!(
+            matches,
+            "wen_restart_last_voted_fork_slots_timeout",
+            u64
+        )
+        .ok()
+        .map(Duration::from_secs),
         ..ValidatorConfig::default()
     };
 
@@ -1133,6 +1353,7 @@ pub fn main() {
         maximum_incremental_snapshot_archives_to_retain,
         accounts_hash_debug_verify: validator_config.accounts_db_test_hash_calculation,
         packager_thread_niceness_adj: snapshot_packager_niceness_adj,
+        gossip_quorum: value_t_or_exit!(matches, "snapshot_hash_gossip_quorum", usize),
     };
 
     // The accounts hash interval shall match the snapshot interval
@@ -1331,6 +1552,12 @@ pub fn main() {
 
     let tpu_max_connections_per_ipaddr_per_minute: u64 =
         value_t_or_exit!(matches, "tpu_max_connections_per_ipaddr_per_minute", u64);
+    let tpu_max_connections_per_ipaddr =
+        value_t_or_exit!(matches, "tpu_max_connections_per_ipaddr", u64);
+    let tpu_max_connections_per_second =
+        value_t_or_exit!(matches, "tpu_max_connections_per_second", u64);
+    let tpu_staked_eviction_policy =
+        value_t_or_exit!(matches, "tpu_staked_eviction_policy", StakedEvictionPolicy);
     let max_streams_per_ms = value_t_or_exit!(matches, "tpu_max_streams_per_ms", u64);
 
     let node_config = NodeConfig {
@@ -1411,6 +1638,8 @@ pub fn main() {
             &vote_account,
             authorized_voter_keypairs.clone(),
             &cluster_entrypoints,
+            &entrypoints,
+            entrypoint_refresh_interval,
             &mut validator_config,
             rpc_bootstrap_config,
             do_port_check,
@@ -1443,6 +1672,9 @@ pub fn main() {
         max_unstaked_connections: tpu_max_unstaked_connections.try_into().unwrap(),
         max_streams_per_ms,
         max_connections_per_ipaddr_per_min: tpu_max_connections_per_ipaddr_per_minute,
+        max_connections_per_ipaddr: tpu_max_connections_per_ipaddr.try_into().unwrap(),
+        max_connections_per_second: tpu_max_connections_per_second,
+        staked_eviction_policy: tpu_staked_eviction_policy,
         coalesce: tpu_coalesce,
         ..Default::default()
     };
@@ -1453,6 +1685,9 @@ pub fn main() {
         max_unstaked_connections: tpu_max_fwd_unstaked_connections.try_into().unwrap(),
         max_streams_per_ms,
         max_connections_per_ipaddr_per_min: tpu_max_connections_per_ipaddr_per_minute,
+        max_connections_per_ipaddr: tpu_max_connections_per_ipaddr.try_into().unwrap(),
+        max_connections_per_second: tpu_max_connections_per_second,
+        staked_eviction_policy: tpu_staked_eviction_policy,
         coalesce: tpu_coalesce,
         ..Default::default()
     };
@@ -1469,7 +1704,7 @@ pub fn main() {
         &ledger_path,
         &vote_account,
         authorized_voter_keypairs,
-        cluster_entrypoints,
+        cluster_entrypoints.clone(),
         &validator_config,
         should_check_duplicate_instance,
         rpc_to_plugin_manager_receiver,
@@ -1506,7 +1741,22 @@ pub fn main() {
         });
     }
     info!("Validator initialized");
+    let entrypoint_refresh_exit = Arc::new(AtomicBool::new(false));
+    let entrypoint_refresh = entrypoint_refresh_interval
+        .filter(|_| !cluster_entrypoints.is_empty())
+        .map(|refresh_interval| {
+            entrypoints.start_refresh(
+                validator.cluster_info.clone(),
+                &cluster_entrypoints,
+                refresh_interval,
+                entrypoint_refresh_exit.clone(),
+            )
+        });
     validator.join();
+    entrypoint_refresh_exit.store(true, Ordering::Relaxed);
+    if let Some(entrypoint_refresh) = entrypoint_refresh {
+        entrypoint_refresh.join().unwrap();
+    }
     info!("Validator exiting..");
 }
 
@@ -1518,6 +1768,7 @@ fn process_account_indexes(matches: &ArgMatches) -> AccountSecondaryIndexes {
             "program-id" => AccountIndex::ProgramId,
             "spl-token-mint" => AccountIndex::SplTokenMint,
             "spl-token-owner" => AccountIndex::SplTokenOwner,
+            "stake-voter" => AccountIndex::StakeVoter,
             _ => unreachable!(),
         })
         .collect();
@@ -0,0 +1,315 @@
+//! Samples the validator's own per-thread CPU time and disk IO, and the allocator's statistics,
+//! so operators can see which stage is hot without attaching an external profiler.
+//!
+//! Threads are read from `/proc/self/task`, so only Linux reports them. Threads differing only
+//! by a numeric suffix, such as the replay transaction threads, are reported as one group.
+
+use {
+    crate::admin_rpc_service::{
+        AdminRpcAllocationStats, AdminRpcComponentIo, AdminRpcSelfProfile, AdminRpcThreadProfile,
+    },
+    std::{
+        collections::{BTreeMap, HashMap},
+        fs, io, thread,
+        time::{Duration, Instant},
+    },
+};
+
+pub const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// The sample blocks an admin RPC thread for its whole interval
+pub const MAX_SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct ThreadSample {
+    name: String,
+    cpu_ticks: u64,
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+/// Parses the thread name and its user plus system CPU time, in clock ticks, from the contents
+/// of `/proc/<pid>/task/<tid>/stat`
+fn parse_stat(stat: &str) -> Option<(String, u64)> {
+    // The name may itself contain spaces and parentheses
+    let name_start = stat.find('(')? + 1;
+    let name_end = stat.rfind(')')?;
+    let name = stat.get(name_start..name_end)?.to_string();
+    // The fields following the name start at the 3rd, utime and stime are the 14th and 15th
+    let mut fields = stat.get(name_end + 1..)?.split_whitespace().skip(11);
+    let utime: u64 = fields.next()?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    Some((name, utime + stime))
+}
+
+/// Parses the bytes read from and written to storage from the contents of
+/// `/proc/<pid>/task/<tid>/io`
+fn parse_io(io: &str) -> (u64, u64) {
+    let field = |name: &str| {
+        io.lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| *key == name)
+            .and_then(|(_, value)| value.trim().parse().ok())
+            .unwrap_or_default()
+    };
+    (field("read_bytes"), field("write_bytes"))
+}
+
+fn sample_threads() -> io::Result<HashMap<u64, ThreadSample>> {
+    let mut samples = HashMap::new();
+    for entry in fs::read_dir("/proc/self/task")? {
+        let entry = entry?;
+        let Some(tid) = entry.file_name().to_str().and_then(|tid| tid.parse().ok()) else {
+            continue;
+        };
+        // The thread may have exited since the directory was listed
+        let Some((name, cpu_ticks)) = fs::read_to_string(entry.path().join("stat"))
+            .ok()
+            .as_deref()
+            .and_then(parse_stat)
+        else {
+            continue;
+        };
+        // Reading the IO accounting requires the same permissions as ptrace, which may be denied
+        let (read_bytes, write_bytes) = fs::read_to_string(entry.path().join("io"))
+            .map(|io| parse_io(&io))
+            .unwrap_or_default();
+        samples.insert(
+            tid,
+            ThreadSample {
+                name,
+                cpu_ticks,
+                read_bytes,
+                write_bytes,
+            },
+        );
+    }
+    Ok(samples)
+}
+
+#[cfg(unix)]
+fn clock_ticks_per_second() -> u64 {
+    match unsafe { libc::sysconf(libc::_SC_CLK_TCK) } {
+        ticks if ticks > 0 => ticks as u64,
+        _ => 100,
+    }
+}
+
+#[cfg(not(unix))]
+fn clock_ticks_per_second() -> u64 {
+    100
+}
+
+/// Groups threads which differ only by a numeric suffix, e.g. `solReplayTx03`
+fn thread_group(name: &str) -> &str {
+    match name.trim_end_matches(|c: char| c.is_ascii_digit()) {
+        "" => name,
+        group => group,
+    }
+}
+
+/// The subsystem a thread does IO for, by its name. IO done by other threads on behalf of the
+/// blockstore or accounts-db, such as replay writing shreds, is reported as `other`.
+fn io_component(name: &str) -> &'static str {
+    const BLOCKSTORE_PREFIXES: &[&str] = &["rocksdb:", "solBstore", "solRocks", "solLedgerCln"];
+    const ACCOUNTS_DB_PREFIXES: &[&str] = &[
+        "solAcct",
+        "solAccounts",
+        "solBgAccounts",
+        "solIdxFlusher",
+        "solDbStore",
+        "solFlush",
+    ];
+    if BLOCKSTORE_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+    {
+        "blockstore"
+    } else if ACCOUNTS_DB_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+    {
+        "accounts-db"
+    } else {
+        "other"
+    }
+}
+
+fn summarize(
+    before: &HashMap<u64, ThreadSample>,
+    after: &HashMap<u64, ThreadSample>,
+    elapsed: Duration,
+    clock_ticks_per_second: u64,
+) -> AdminRpcSelfProfile {
+    let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+    let per_second = |delta: u64| (delta as f64 / seconds) as u64;
+
+    let mut groups = BTreeMap::<&str, (usize, u64, u64, u64)>::new();
+    let mut components = BTreeMap::<&str, (u64, u64)>::new();
+    for (tid, sample) in after {
+        // Threads started during the interval are charged for all of their usage
+        let previous = before.get(tid).cloned().unwrap_or_default();
+        let cpu_ticks = sample.cpu_ticks.saturating_sub(previous.cpu_ticks);
+        let read_bytes = sample.read_bytes.saturating_sub(previous.read_bytes);
+        let write_bytes = sample.write_bytes.saturating_sub(previous.write_bytes);
+
+        let group = groups.entry(thread_group(&sample.name)).or_default();
+        group.0 += 1;
+        group.1 += cpu_ticks;
+        group.2 += read_bytes;
+        group.3 += write_bytes;
+
+        let component = components.entry(io_component(&sample.name)).or_default();
+        component.0 += read_bytes;
+        component.1 += write_bytes;
+    }
+
+    let cpu_percent =
+        |cpu_ticks: u64| 100.0 * cpu_ticks as f64 / clock_ticks_per_second as f64 / seconds;
+    let mut threads: Vec<_> = groups
+        .into_iter()
+        .map(
+            |(name, (threads, cpu_ticks, read_bytes, write_bytes))| AdminRpcThreadProfile {
+                name: name.to_string(),
+                threads,
+                cpu_percent: cpu_percent(cpu_ticks),
+                read_bytes_per_second: per_second(read_bytes),
+                write_bytes_per_second: per_second(write_bytes),
+            },
+        )
+        .collect();
+    threads.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+
+    AdminRpcSelfProfile {
+        interval_ms: elapsed.as_millis() as u64,
+        total_cpu_percent: threads.iter().map(|thread| thread.cpu_percent).sum(),
+        threads,
+        io: components
+            .into_iter()
+            .map(
+                |(component, (read_bytes, write_bytes))| AdminRpcComponentIo {
+                    component: component.to_string(),
+                    read_bytes_per_second: per_second(read_bytes),
+                    write_bytes_per_second: per_second(write_bytes),
+                },
+            )
+            .collect(),
+        allocation: None,
+    }
+}
+
+/// Returns the bytes allocated by the application and the bytes resident in physical memory
+#[cfg(all(
+    feature = "jemalloc-profiling",
+    not(any(target_env = "msvc", target_os = "freebsd"))
+))]
+fn allocator_stats() -> Option<(u64, u64)> {
+    use tikv_jemalloc_ctl::{epoch, stats};
+    // The statistics are cached until the epoch is advanced
+    epoch::advance().ok()?;
+    Some((
+        stats::allocated::read().ok()? as u64,
+        stats::resident::read().ok()? as u64,
+    ))
+}
+
+#[cfg(not(all(
+    feature = "jemalloc-profiling",
+    not(any(target_env = "msvc", target_os = "freebsd"))
+)))]
+fn allocator_stats() -> Option<(u64, u64)> {
+    None
+}
+
+/// Samples the process twice, `interval` apart, and reports the usage in between
+pub fn profile(interval: Duration) -> io::Result<AdminRpcSelfProfile> {
+    let interval = interval.min(MAX_SAMPLE_INTERVAL);
+    let allocator_before = allocator_stats();
+    let before = sample_threads()?;
+    let start = Instant::now();
+    thread::sleep(interval);
+    let after = sample_threads()?;
+    let elapsed = start.elapsed();
+    let allocator_after = allocator_stats();
+
+    let mut profile = summarize(&before, &after, elapsed, clock_ticks_per_second());
+    if let (Some((allocated_before, _)), Some((allocated_bytes, resident_bytes))) =
+        (allocator_before, allocator_after)
+    {
+        let growth = allocated_bytes as i64 - allocated_before as i64;
+        profile.allocation = Some(AdminRpcAllocationStats {
+            allocated_bytes,
+            resident_bytes,
+            net_allocated_bytes_per_second: (growth as f64 / elapsed.as_secs_f64()) as i64,
+        });
+    }
+    Ok(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stat() {
+        let stat = "1234 (solReplay (1)) S 1 1234 1234 0 -1 4194368 100 0 0 0 250 50 0 0 20 0 \
+                    1 0 100 0 0";
+        assert_eq!(parse_stat(stat), Some(("solReplay (1)".to_string(), 300)));
+        assert_eq!(parse_stat("1234 (truncated) S 1"), None);
+        assert_eq!(parse_stat(""), None);
+    }
+
+    #[test]
+    fn test_parse_io() {
+        let io = "rchar: 100\nwchar: 200\nsyscr: 1\nsyscw: 2\nread_bytes: 4096\nwrite_bytes: \
+                  8192\ncancelled_write_bytes: 0\n";
+        assert_eq!(parse_io(io), (4096, 8192));
+        assert_eq!(parse_io(""), (0, 0));
+    }
+
+    #[test]
+    fn test_thread_group() {
+        assert_eq!(thread_group("solReplayTx03"), "solReplayTx");
+        assert_eq!(thread_group("solPohTickProd"), "solPohTickProd");
+        assert_eq!(thread_group("1234"), "1234");
+        assert_eq!(io_component("rocksdb:low"), "blockstore");
+        assert_eq!(io_component("solBgAccounts"), "accounts-db");
+        assert_eq!(io_component("solReplayTx03"), "other");
+    }
+
+    #[test]
+    fn test_summarize() {
+        let sample = |name: &str, cpu_ticks, write_bytes| ThreadSample {
+            name: name.to_string(),
+            cpu_ticks,
+            read_bytes: 0,
+            write_bytes,
+        };
+        let before = HashMap::from([
+            (1, sample("solReplayTx00", 100, 0)),
+            (2, sample("solReplayTx01", 100, 0)),
+            (3, sample("rocksdb:low", 0, 1_000)),
+        ]);
+        let after = HashMap::from([
+            (1, sample("solReplayTx00", 150, 0)),
+            (2, sample("solReplayTx01", 200, 0)),
+            (3, sample("rocksdb:low", 10, 3_000)),
+            (4, sample("solNew", 5, 0)),
+        ]);
+        let profile = summarize(&before, &after, Duration::from_secs(2), 100);
+        assert_eq!(profile.interval_ms, 2_000);
+
+        let replay = &profile.threads[0];
+        assert_eq!(replay.name, "solReplayTx");
+        assert_eq!(replay.threads, 2);
+        assert_eq!(replay.cpu_percent, 75.0);
+        assert_eq!(profile.threads[1].name, "rocksdb:low");
+        assert_eq!(profile.threads[1].write_bytes_per_second, 1_000);
+        assert_eq!(profile.threads[2].name, "solNew");
+        assert_eq!(profile.total_cpu_percent, 82.5);
+
+        assert_eq!(profile.io[0].component, "blockstore");
+        assert_eq!(profile.io[0].write_bytes_per_second, 1_000);
+        assert_eq!(profile.io[1].component, "other");
+        assert_eq!(profile.io[1].write_bytes_per_second, 0);
+    }
+}
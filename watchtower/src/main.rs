@@ -16,9 +16,15 @@ use {
     solana_notifier::{NotificationType, Notifier},
     solana_pubkey::Pubkey,
     solana_rpc_client::rpc_client::RpcClient,
-    solana_rpc_client_api::{client_error, response::RpcVoteAccountStatus},
+    solana_rpc_client_api::{
+        client_error,
+        config::RpcSlashingEvidenceConfig,
+        response::{
+            RpcBlockProduction, RpcSlashingEvidence, RpcSlashingEvidenceKind, RpcVoteAccountStatus,
+        },
+    },
     std::{
-        collections::HashMap,
+        collections::{HashMap, HashSet},
         error,
         thread::sleep,
         time::{Duration, Instant},
@@ -34,6 +40,10 @@ struct Config {
     minimum_validator_identity_balance: u64,
     monitor_active_stake: bool,
     active_stake_alert_threshold: u8,
+    monitor_slashing_evidence: bool,
+    superminority_alert_threshold: Option<usize>,
+    delinquent_stake_alert_threshold: Option<u8>,
+    skipped_slots_alert_threshold: Option<u8>,
     unhealthy_threshold: usize,
     validator_identity_pubkeys: Vec<Pubkey>,
     name_suffix: String,
@@ -149,6 +159,39 @@ fn get_config() -> Config {
                 .default_value("80")
                 .help("Alert when the current stake for the cluster drops below this value"),
         )
+        .arg(
+            Arg::with_name("monitor_slashing_evidence")
+                .long("monitor-slashing-evidence")
+                .takes_value(false)
+                .help("Alert when the RPC node records new duplicate block or duplicate vote \
+                    evidence. Evidence recorded before startup is only logged")
+        )
+        .arg(
+            Arg::with_name("superminority_alert_threshold")
+                .long("superminority-alert-threshold")
+                .value_name("COUNT")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .help("Alert when the superminority, the smallest set of validators holding \
+                    more than a third of the stake, shrinks to this many validators or fewer")
+        )
+        .arg(
+            Arg::with_name("delinquent_stake_alert_threshold")
+                .long("delinquent-stake-alert-threshold")
+                .value_name("PERCENTAGE")
+                .takes_value(true)
+                .validator(is_valid_percentage)
+                .help("Alert when the delinquent stake exceeds this percentage of the stake")
+        )
+        .arg(
+            Arg::with_name("skipped_slots_alert_threshold")
+                .long("skipped-slots-alert-threshold")
+                .value_name("PERCENTAGE")
+                .takes_value(true)
+                .validator(is_valid_percentage)
+                .help("Alert when more than this percentage of the leader slots since the \
+                    previous check were skipped")
+        )
         .arg(
             Arg::with_name("ignore_http_bad_gateway")
                 .long("ignore-http-bad-gateway")
@@ -193,6 +236,12 @@ fn get_config() -> Config {
     let monitor_active_stake = matches.is_present("monitor_active_stake");
     let active_stake_alert_threshold =
         value_t_or_exit!(matches, "active_stake_alert_threshold", u8);
+    let monitor_slashing_evidence = matches.is_present("monitor_slashing_evidence");
+    let superminority_alert_threshold =
+        value_t!(matches, "superminority_alert_threshold", usize).ok();
+    let delinquent_stake_alert_threshold =
+        value_t!(matches, "delinquent_stake_alert_threshold", u8).ok();
+    let skipped_slots_alert_threshold = value_t!(matches, "skipped_slots_alert_threshold", u8).ok();
     let ignore_http_bad_gateway = matches.is_present("ignore_http_bad_gateway");
 
     let name_suffix = value_t_or_exit!(matches, "name_suffix", String);
@@ -206,6 +255,10 @@ fn get_config() -> Config {
         minimum_validator_identity_balance,
         monitor_active_stake,
         active_stake_alert_threshold,
+        monitor_slashing_evidence,
+        superminority_alert_threshold,
+        delinquent_stake_alert_threshold,
+        skipped_slots_alert_threshold,
         unhealthy_threshold,
         validator_identity_pubkeys,
         name_suffix,
@@ -219,13 +272,23 @@ fn get_config() -> Config {
     config
 }
 
-fn get_cluster_info(
-    config: &Config,
-    rpc_client: &RpcClient,
-) -> client_error::Result<(u64, Hash, RpcVoteAccountStatus, HashMap<Pubkey, u64>)> {
+type ClusterInfo = (
+    u64,
+    Hash,
+    RpcVoteAccountStatus,
+    HashMap<Pubkey, u64>,
+    Option<RpcBlockProduction>,
+);
+
+fn get_cluster_info(config: &Config, rpc_client: &RpcClient) -> client_error::Result<ClusterInfo> {
     let transaction_count = rpc_client.get_transaction_count()?;
     let recent_blockhash = rpc_client.get_latest_blockhash()?;
     let vote_accounts = rpc_client.get_vote_accounts()?;
+    let block_production = if config.skipped_slots_alert_threshold.is_some() {
+        Some(rpc_client.get_block_production()?.value)
+    } else {
+        None
+    };
 
     let mut validator_balances = HashMap::new();
     for validator_identity in &config.validator_identity_pubkeys {
@@ -240,9 +303,107 @@ fn get_cluster_info(
         recent_blockhash,
         vote_accounts,
         validator_balances,
+        block_production,
     ))
 }
 
+/// Returns the number of validators in the superminority, the smallest set of validators holding
+/// more than a third of the stake.
+fn superminority_size(vote_accounts: &RpcVoteAccountStatus) -> usize {
+    let mut stakes: Vec<u64> = vote_accounts
+        .current
+        .iter()
+        .chain(&vote_accounts.delinquent)
+        .map(|vote_account| vote_account.activated_stake)
+        .collect();
+    let total_stake: u128 = stakes.iter().map(|stake| u128::from(*stake)).sum();
+    stakes.sort_unstable_by(|a, b| b.cmp(a));
+    let mut stake = 0;
+    stakes
+        .into_iter()
+        .take_while(|activated_stake| {
+            let below = stake * 3 <= total_stake;
+            stake += u128::from(*activated_stake);
+            below
+        })
+        .count()
+}
+
+/// Tracks the leader slots and produced blocks of the epoch between checks, to compute the rate
+/// of skipped slots since the previous check.
+#[derive(Default)]
+struct SkippedSlotsMonitor {
+    last: Option<(u64, usize, usize)>,
+}
+
+impl SkippedSlotsMonitor {
+    /// Returns the number of leader slots since the previous call and the percentage of them
+    /// that were skipped, None on the first call and at the start of an epoch.
+    fn update(&mut self, block_production: &RpcBlockProduction) -> Option<(usize, f64)> {
+        let (leader_slots, blocks_produced) = block_production.by_identity.values().fold(
+            (0, 0),
+            |(slots, blocks), (leader_slots, blocks_produced)| {
+                (slots + leader_slots, blocks + blocks_produced)
+            },
+        );
+        let first_slot = block_production.range.first_slot;
+        let previous = self
+            .last
+            .replace((first_slot, leader_slots, blocks_produced));
+        let (last_first_slot, last_leader_slots, last_blocks_produced) = previous?;
+        if last_first_slot != first_slot || leader_slots <= last_leader_slots {
+            return None;
+        }
+        let leader_slots = leader_slots - last_leader_slots;
+        // Another RPC node behind a load balancer may report fewer blocks for the same range
+        let blocks_produced = blocks_produced.saturating_sub(last_blocks_produced);
+        let skipped_slots = leader_slots.saturating_sub(blocks_produced);
+        Some((
+            leader_slots,
+            skipped_slots as f64 * 100. / leader_slots as f64,
+        ))
+    }
+}
+
+/// Tracks the slashing evidence already reported so that only new evidence is
+/// alerted on.
+#[derive(Default)]
+struct SlashingEvidenceMonitor {
+    start_slot: u64,
+    seen: HashSet<(u64, RpcSlashingEvidenceKind, Option<String>)>,
+    initialized: bool,
+}
+
+impl SlashingEvidenceMonitor {
+    /// Returns the evidence recorded since the previous call. All evidence is
+    /// considered already seen on the first call.
+    fn poll(&mut self, rpc_client: &RpcClient) -> client_error::Result<Vec<RpcSlashingEvidence>> {
+        let evidence = rpc_client.get_slashing_evidence(RpcSlashingEvidenceConfig {
+            start_slot: Some(self.start_slot),
+            limit: None,
+        })?;
+        let new_evidence: Vec<_> = evidence
+            .into_iter()
+            .filter(|evidence| {
+                self.seen
+                    .insert((evidence.slot, evidence.kind, evidence.offender.clone()))
+            })
+            .collect();
+        if let Some(last) = new_evidence.last() {
+            self.start_slot = last.slot;
+            self.seen.retain(|(slot, ..)| *slot >= self.start_slot);
+        }
+        if !self.initialized {
+            self.initialized = true;
+            for evidence in &new_evidence {
+                info!("Existing slashing evidence: {evidence:?}");
+            }
+            return Ok(vec![]);
+        }
+        Ok(new_evidence)
+    }
+}
+
 fn main() -> Result<(), Box<dyn error::Error>> {
     solana_logger::setup_with_default_filter();
     solana_metrics::set_panic_hook("watchtower", /*version:*/ None);
@@ -257,10 +418,48 @@ fn main() -> Result<(), Box<dyn error::Error>> {
     let mut num_consecutive_failures = 0;
     let mut last_success = Instant::now();
     let mut incident = Hash::new_unique();
+    let mut slashing_evidence_monitor = SlashingEvidenceMonitor::default();
+    let mut skipped_slots_monitor = SkippedSlotsMonitor::default();
 
     loop {
+        if config.monitor_slashing_evidence {
+            match slashing_evidence_monitor.poll(&rpc_client) {
+                Ok(new_evidence) => {
+                    for evidence in new_evidence {
+                        let offender = evidence.offender.as_deref().unwrap_or("unknown");
+                        let notification_msg = format!(
+                            "agave-watchtower{}: Slashing evidence: {:?} in slot {} by {}",
+                            config.name_suffix,
+                            evidence.kind,
+                            evidence.slot,
+                            format_labeled_address(offender, &config.address_labels),
+                        );
+                        warn!("{notification_msg}");
+                        notifier.send(
+                            &notification_msg,
+                            &NotificationType::Trigger {
+                                incident: Hash::new_unique(),
+                            },
+                        );
+                        datapoint_error!(
+                            "watchtower-slashing-evidence",
+                            ("slot", evidence.slot, i64),
+                            ("offender", offender, String)
+                        );
+                    }
+                }
+                Err(err) => warn!("Failed to fetch slashing evidence: {err}"),
+            }
+        }
+
         let failure = match get_cluster_info(&config, &rpc_client) {
-            Ok((transaction_count, recent_blockhash, vote_accounts, validator_balances)) => {
+            Ok((
+                transaction_count,
+                recent_blockhash,
+                vote_accounts,
+                validator_balances,
+                block_production,
+            )) => {
                 info!("Current transaction count: {}", transaction_count);
                 info!("Recent blockhash: {}", recent_blockhash);
                 info!("Current validator count: {}", vote_accounts.current.len());
@@ -321,6 +520,66 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                     ));
                 }
 
+                let superminority_size = superminority_size(&vote_accounts);
+                let delinquent_stake_percent =
+                    total_delinquent_stake as f64 * 100. / total_stake as f64;
+                info!(
+                    "Superminority: {superminority_size} validators | Delinquent stake: \
+                     {delinquent_stake_percent:.2}%"
+                );
+                datapoint_info!(
+                    "watchtower-stake-concentration",
+                    ("superminority_size", superminority_size, i64),
+                    ("delinquent_stake_percent", delinquent_stake_percent, f64),
+                );
+
+                if let Some(threshold) = config.superminority_alert_threshold {
+                    if superminority_size <= threshold {
+                        failures.push((
+                            "superminority",
+                            format!(
+                                "{superminority_size} validators hold more than a third of the \
+                                 stake"
+                            ),
+                        ));
+                    }
+                }
+
+                if let Some(threshold) = config.delinquent_stake_alert_threshold {
+                    if delinquent_stake_percent > threshold as f64 {
+                        failures.push((
+                            "delinquent-stake",
+                            format!("Delinquent stake is {delinquent_stake_percent:.2}%"),
+                        ));
+                    }
+                }
+
+                if let (Some(threshold), Some(block_production)) =
+                    (config.skipped_slots_alert_threshold, &block_production)
+                {
+                    if let Some((leader_slots, skipped_percent)) =
+                        skipped_slots_monitor.update(block_production)
+                    {
+                        info!(
+                            "Skipped slots: {skipped_percent:.2}% of {leader_slots} leader slots"
+                        );
+                        datapoint_info!(
+                            "watchtower-skipped-slots",
+                            ("leader_slots", leader_slots, i64),
+                            ("skipped_percent", skipped_percent, f64),
+                        );
+                        if skipped_percent > threshold as f64 {
+                            failures.push((
+                                "skipped-slots",
+                                format!(
+                                    "{skipped_percent:.2}% of the last {leader_slots} leader \
+                                     slots were skipped"
+                                ),
+                            ));
+                        }
+                    }
+                }
+
                 let mut validator_errors = vec![];
                 for validator_identity in config.validator_identity_pubkeys.iter() {
                     let formatted_validator_identity = format_labeled_address(
@@ -425,3 +684,88 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         sleep(config.interval);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_rpc_client_api::response::{RpcBlockProductionRange, RpcVoteAccountInfo},
+    };
+
+    fn vote_account_info(activated_stake: u64) -> RpcVoteAccountInfo {
+        RpcVoteAccountInfo {
+            vote_pubkey: String::default(),
+            node_pubkey: String::default(),
+            activated_stake,
+            commission: 0,
+            epoch_vote_account: true,
+            epoch_credits: vec![],
+            last_vote: 0,
+            root_slot: 0,
+        }
+    }
+
+    fn block_production(first_slot: u64, by_identity: &[(usize, usize)]) -> RpcBlockProduction {
+        RpcBlockProduction {
+            by_identity: by_identity
+                .iter()
+                .enumerate()
+                .map(|(identity, counts)| (identity.to_string(), *counts))
+                .collect(),
+            range: RpcBlockProductionRange {
+                first_slot,
+                last_slot: first_slot + 1000,
+            },
+        }
+    }
+
+    #[test]
+    fn test_superminority_size() {
+        let vote_accounts = |current: &[u64], delinquent: &[u64]| RpcVoteAccountStatus {
+            current: current.iter().copied().map(vote_account_info).collect(),
+            delinquent: delinquent.iter().copied().map(vote_account_info).collect(),
+        };
+        assert_eq!(superminority_size(&vote_accounts(&[], &[])), 0);
+        assert_eq!(superminority_size(&vote_accounts(&[100], &[])), 1);
+        // 34 of 100 is more than a third
+        assert_eq!(superminority_size(&vote_accounts(&[34, 33, 33], &[])), 1);
+        // exactly a third is not more than a third
+        assert_eq!(superminority_size(&vote_accounts(&[10, 10, 10], &[])), 2);
+        assert_eq!(
+            superminority_size(&vote_accounts(&[1, 1, 1, 1, 1, 1, 1, 1, 1, 1], &[])),
+            4
+        );
+        // delinquent stake counts towards the superminority
+        assert_eq!(superminority_size(&vote_accounts(&[10, 10], &[40])), 1);
+    }
+
+    #[test]
+    fn test_skipped_slots_monitor() {
+        let mut monitor = SkippedSlotsMonitor::default();
+        assert_eq!(
+            monitor.update(&block_production(0, &[(4, 4), (4, 4)])),
+            None
+        );
+        // 8 new leader slots, 2 of which were skipped
+        assert_eq!(
+            monitor.update(&block_production(0, &[(8, 7), (8, 7)])),
+            Some((8, 25.))
+        );
+        // no new leader slots
+        assert_eq!(
+            monitor.update(&block_production(0, &[(8, 7), (8, 7)])),
+            None
+        );
+        // a new epoch restarts the count
+        assert_eq!(monitor.update(&block_production(1000, &[(4, 0)])), None);
+        assert_eq!(
+            monitor.update(&block_production(1000, &[(8, 4)])),
+            Some((4, 0.))
+        );
+        // another node reporting fewer blocks for the same range doesn't underflow
+        assert_eq!(
+            monitor.update(&block_production(1000, &[(12, 2)])),
+            Some((4, 100.))
+        );
+    }
+}
@@ -67,13 +67,13 @@ use {
 };
 
 // If >42% of the validators have this block, repair this block locally.
-const REPAIR_THRESHOLD: f64 = 0.42;
+pub const DEFAULT_REPAIR_THRESHOLD_PERCENT: u64 = 42;
 // When counting Heaviest Fork, only count those with no less than
 // 67% - 5% - (100% - active_stake) = active_stake - 38% stake.
 // 67% is the supermajority threshold (2/3), 5% is the assumption we
 // made regarding how much non-conforming/offline validators the
 // algorithm can tolerate.
-const HEAVIEST_FORK_THRESHOLD_DELTA: f64 = 0.38;
+pub const DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT: u64 = 38;
 // The coordinator print new stats every 10 seconds.
 const COORDINATOR_STAT_PRINT_INTERVAL_SECONDS: u64 = 10;
 
@@ -93,6 +93,7 @@ pub enum WenRestartError {
     MalformedProgress(RestartState, String),
     MissingLastVotedForkSlots,
     MissingSnapshotInProtobuf,
+    LastVotedForkSlotsTimeout(u64, u64),
     NotEnoughStakeAgreeingWithUs(Slot, Hash, HashMap<(Slot, Hash), u64>),
     UnexpectedState(wen_restart_proto::State),
 }
@@ -169,6 +170,13 @@ impl std::fmt::Display for WenRestartError {
             WenRestartError::MissingSnapshotInProtobuf => {
                 write!(f, "Missing snapshot in protobuf")
             }
+            WenRestartError::LastVotedForkSlotsTimeout(active_percent, threshold_percent) => {
+                write!(
+                    f,
+                    "Timed out waiting for last voted fork slots: {active_percent}% of the stake \
+                     is active, {threshold_percent}% is required",
+                )
+            }
             WenRestartError::NotEnoughStakeAgreeingWithUs(slot, hash, block_stake_map) => {
                 write!(
                     f,
@@ -231,9 +239,12 @@ pub(crate) fn send_restart_last_voted_fork_slots(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn aggregate_restart_last_voted_fork_slots(
     wen_restart_path: &PathBuf,
     wait_for_supermajority_threshold_percent: u64,
+    repair_threshold_percent: u64,
+    timeout: Option<Duration>,
     cluster_info: Arc<ClusterInfo>,
     last_voted_fork_slots: &Vec<Slot>,
     bank_forks: Arc<RwLock<BankForks>>,
@@ -246,7 +257,7 @@ pub(crate) fn aggregate_restart_last_voted_fork_slots(
     let root_slot = root_bank.slot();
     let mut last_voted_fork_slots_aggregate = LastVotedForkSlotsAggregate::new(
         root_bank.clone(),
-        REPAIR_THRESHOLD,
+        repair_threshold_percent as f64 / 100.0,
         last_voted_fork_slots,
         &cluster_info.id(),
     );
@@ -267,6 +278,7 @@ pub(crate) fn aggregate_restart_last_voted_fork_slots(
     let mut cursor = solana_gossip::crds::Cursor::default();
     let mut is_full_slots = HashSet::new();
     let mut old_progress = WenRestartProgress::default();
+    let aggregate_start = Instant::now();
     loop {
         if exit.load(Ordering::Relaxed) {
             return Err(WenRestartError::Exiting.into());
@@ -337,6 +349,13 @@ pub(crate) fn aggregate_restart_last_voted_fork_slots(
             *wen_restart_repair_slots.write().unwrap() = vec![];
             break;
         }
+        if timeout.is_some_and(|timeout| aggregate_start.elapsed() >= timeout) {
+            return Err(WenRestartError::LastVotedForkSlotsTimeout(
+                active_percent as u64,
+                wait_for_supermajority_threshold_percent,
+            )
+            .into());
+        }
         {
             *wen_restart_repair_slots.write().unwrap() = filtered_slots;
         }
@@ -353,15 +372,16 @@ fn is_over_stake_threshold(
     epoch_info_vec: &[LastVotedForkSlotsEpochInfo],
     epoch: Epoch,
     stake: &u64,
+    heaviest_fork_threshold_delta_percent: u64,
 ) -> bool {
     epoch_info_vec
         .iter()
         .find(|info| info.epoch == epoch)
         .is_some_and(|info| {
-            let threshold = info
-                .actively_voting_stake
-                .checked_sub((info.total_stake as f64 * HEAVIEST_FORK_THRESHOLD_DELTA) as u64)
-                .unwrap();
+            let threshold = info.actively_voting_stake.saturating_sub(
+                (info.total_stake as f64 * heaviest_fork_threshold_delta_percent as f64 / 100.0)
+                    as u64,
+            );
             stake >= &threshold
         })
 }
@@ -374,6 +394,7 @@ pub(crate) fn find_heaviest_fork(
     bank_forks: Arc<RwLock<BankForks>>,
     blockstore: Arc<Blockstore>,
     exit: Arc<AtomicBool>,
+    heaviest_fork_threshold_delta_percent: u64,
 ) -> Result<(Slot, Hash)> {
     let root_bank = bank_forks.read().unwrap().root_bank();
     let root_slot = root_bank.slot();
@@ -386,6 +407,7 @@ pub(crate) fn find_heaviest_fork(
                     &aggregate_final_result.epoch_info_vec,
                     root_bank.epoch_schedule().get_epoch(**slot),
                     stake,
+                    heaviest_fork_threshold_delta_percent,
                 )
         })
         .map(|(slot, _)| *slot)
@@ -971,6 +993,14 @@ pub struct WenRestartConfig {
     pub bank_forks: Arc<RwLock<BankForks>>,
     pub wen_restart_repair_slots: Option<Arc<RwLock<Vec<Slot>>>>,
     pub wait_for_supermajority_threshold_percent: u64,
+    /// Percentage of the active stake which must have a block for it to be repaired locally
+    pub repair_threshold_percent: u64,
+    /// Blocks are only considered for the heaviest fork if voted for by at least the active
+    /// stake minus this percentage of the total stake
+    pub heaviest_fork_threshold_delta_percent: u64,
+    /// Fail the restart if the active stake does not reach
+    /// `wait_for_supermajority_threshold_percent` within this time
+    pub last_voted_fork_slots_timeout: Option<Duration>,
     pub snapshot_config: SnapshotConfig,
     pub accounts_background_request_sender: AbsRequestSender,
     pub genesis_config_hash: Hash,
@@ -1008,6 +1038,8 @@ pub fn wait_for_wen_restart(config: WenRestartConfig) -> Result<()> {
                     None => aggregate_restart_last_voted_fork_slots(
                         &config.wen_restart_path,
                         config.wait_for_supermajority_threshold_percent,
+                        config.repair_threshold_percent,
+                        config.last_voted_fork_slots_timeout,
                         config.cluster_info.clone(),
                         &last_voted_fork_slots,
                         config.bank_forks.clone(),
@@ -1034,6 +1066,7 @@ pub fn wait_for_wen_restart(config: WenRestartConfig) -> Result<()> {
                             config.bank_forks.clone(),
                             config.blockstore.clone(),
                             config.exit.clone(),
+                            config.heaviest_fork_threshold_delta_percent,
                         )?;
                         info!(
                             "Heaviest fork found: slot: {}, bankhash: {}",
@@ -1378,7 +1411,7 @@ pub(crate) fn initialize(
     }
 }
 
-fn read_wen_restart_records(records_path: &PathBuf) -> Result<WenRestartProgress> {
+pub(crate) fn read_wen_restart_records(records_path: &PathBuf) -> Result<WenRestartProgress> {
     let buffer = read(records_path)?;
     let progress = WenRestartProgress::decode(&mut Cursor::new(buffer))?;
     info!("read record {:?}", progress);
@@ -1402,6 +1435,7 @@ pub(crate) fn write_wen_restart_records(
 mod tests {
     use {
         crate::wen_restart::{tests::wen_restart_proto::LastVotedForkSlotsAggregateFinal, *},
+        assert_matches::assert_matches,
         solana_accounts_db::hardened_unpack::MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
         solana_entry::entry::create_ticks,
         solana_gossip::{
@@ -1684,6 +1718,9 @@ mod tests {
             bank_forks: test_state.bank_forks.clone(),
             wen_restart_repair_slots: Some(Arc::new(RwLock::new(Vec::new()))),
             wait_for_supermajority_threshold_percent: 80,
+            repair_threshold_percent: DEFAULT_REPAIR_THRESHOLD_PERCENT,
+            heaviest_fork_threshold_delta_percent: DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
+            last_voted_fork_slots_timeout: None,
             snapshot_config: SnapshotConfig::default(),
             accounts_background_request_sender: AbsRequestSender::default(),
             genesis_config_hash: test_state.genesis_config_hash,
@@ -1755,6 +1792,9 @@ mod tests {
             bank_forks: test_state.bank_forks.clone(),
             wen_restart_repair_slots: wen_restart_repair_slots.clone(),
             wait_for_supermajority_threshold_percent: 80,
+            repair_threshold_percent: DEFAULT_REPAIR_THRESHOLD_PERCENT,
+            heaviest_fork_threshold_delta_percent: DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
+            last_voted_fork_slots_timeout: None,
             snapshot_config,
             accounts_background_request_sender: AbsRequestSender::default(),
             genesis_config_hash: test_state.genesis_config_hash,
@@ -2109,6 +2149,10 @@ mod tests {
                 bank_forks: test_state.bank_forks,
                 wen_restart_repair_slots: Some(Arc::new(RwLock::new(Vec::new()))),
                 wait_for_supermajority_threshold_percent: 80,
+                repair_threshold_percent: DEFAULT_REPAIR_THRESHOLD_PERCENT,
+                heaviest_fork_threshold_delta_percent:
+                    DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
+                last_voted_fork_slots_timeout: None,
                 snapshot_config: SnapshotConfig::default(),
                 accounts_background_request_sender: AbsRequestSender::default(),
                 genesis_config_hash: test_state.genesis_config_hash,
@@ -2537,6 +2581,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_wen_restart_aggregate_last_voted_fork_timeout() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let test_state = wen_restart_test_init(&ledger_path);
+        let mut progress = WenRestartProgress {
+            state: RestartState::LastVotedForkSlots.into(),
+            my_last_voted_fork_slots: Some(LastVotedForkSlotsRecord {
+                last_voted_fork_slots: test_state.last_voted_fork_slots.clone(),
+                last_vote_bankhash: Hash::new_unique().to_string(),
+                shred_version: SHRED_VERSION as u32,
+                wallclock: timestamp(),
+            }),
+            ..Default::default()
+        };
+        // Nobody else reports their last voted fork, so the supermajority is never reached.
+        assert_matches!(
+            aggregate_restart_last_voted_fork_slots(
+                &test_state.wen_restart_proto_path,
+                WAIT_FOR_SUPERMAJORITY_THRESHOLD_PERCENT,
+                DEFAULT_REPAIR_THRESHOLD_PERCENT,
+                Some(Duration::ZERO),
+                test_state.cluster_info.clone(),
+                &test_state.last_voted_fork_slots,
+                test_state.bank_forks.clone(),
+                test_state.blockstore.clone(),
+                Arc::new(RwLock::new(Vec::new())),
+                Arc::new(AtomicBool::new(false)),
+                &mut progress,
+            )
+            .unwrap_err()
+            .downcast::<WenRestartError>()
+            .unwrap(),
+            WenRestartError::LastVotedForkSlotsTimeout(_, WAIT_FOR_SUPERMAJORITY_THRESHOLD_PERCENT)
+        );
+    }
+
     #[test]
     fn test_wen_restart_aggregate_last_voted_fork_stop_and_restart() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
@@ -2602,6 +2682,8 @@ mod tests {
                     assert!(aggregate_restart_last_voted_fork_slots(
                         &wen_restart_proto_path_clone,
                         WAIT_FOR_SUPERMAJORITY_THRESHOLD_PERCENT,
+                        DEFAULT_REPAIR_THRESHOLD_PERCENT,
+                        None,
                         cluster_info_clone,
                         &last_voted_fork_slots,
                         bank_forks_clone,
@@ -2948,6 +3030,7 @@ mod tests {
                 test_state.bank_forks.clone(),
                 test_state.blockstore.clone(),
                 exit.clone(),
+                DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
             )
             .unwrap_err()
             .downcast::<WenRestartError>()
@@ -2969,6 +3052,7 @@ mod tests {
                 test_state.bank_forks.clone(),
                 test_state.blockstore.clone(),
                 exit.clone(),
+                DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
             )
             .unwrap_err()
             .downcast::<WenRestartError>()
@@ -2991,6 +3075,7 @@ mod tests {
                 test_state.bank_forks.clone(),
                 test_state.blockstore.clone(),
                 exit.clone(),
+                DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
             )
             .unwrap_err()
             .downcast::<WenRestartError>()
@@ -3044,6 +3129,7 @@ mod tests {
                 test_state.bank_forks.clone(),
                 test_state.blockstore.clone(),
                 exit.clone(),
+                DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
             )
             .unwrap_err()
             .downcast::<WenRestartError>()
@@ -3097,6 +3183,7 @@ mod tests {
                 test_state.bank_forks.clone(),
                 test_state.blockstore.clone(),
                 exit.clone(),
+                DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
             )
             .unwrap_err()
             .downcast::<WenRestartError>()
@@ -3395,6 +3482,9 @@ mod tests {
             bank_forks: test_state.bank_forks.clone(),
             wen_restart_repair_slots: Some(Arc::new(RwLock::new(Vec::new()))),
             wait_for_supermajority_threshold_percent: 80,
+            repair_threshold_percent: DEFAULT_REPAIR_THRESHOLD_PERCENT,
+            heaviest_fork_threshold_delta_percent: DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
+            last_voted_fork_slots_timeout: None,
             snapshot_config: SnapshotConfig::default(),
             accounts_background_request_sender: AbsRequestSender::default(),
             genesis_config_hash: test_state.genesis_config_hash,
@@ -3641,6 +3731,9 @@ mod tests {
             bank_forks: test_state.bank_forks.clone(),
             wen_restart_repair_slots: Some(Arc::new(RwLock::new(Vec::new()))),
             wait_for_supermajority_threshold_percent: 80,
+            repair_threshold_percent: DEFAULT_REPAIR_THRESHOLD_PERCENT,
+            heaviest_fork_threshold_delta_percent: DEFAULT_HEAVIEST_FORK_THRESHOLD_DELTA_PERCENT,
+            last_voted_fork_slots_timeout: None,
             snapshot_config: SnapshotConfig::default(),
             accounts_background_request_sender: AbsRequestSender::default(),
             genesis_config_hash: test_state.genesis_config_hash,
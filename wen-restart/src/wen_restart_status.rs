@@ -0,0 +1,274 @@
+//! The `wen_restart_status` module summarizes the progress of a wen_restart from the
+//! progress file it writes, so operators can follow the aggregation without a running node.
+
+use {
+    crate::{
+        solana::wen_restart_proto::{
+            HeaviestForkRecord, State as RestartState, WenRestartProgress,
+        },
+        wen_restart::read_wen_restart_records,
+    },
+    anyhow::Result,
+    solana_program::clock::{Epoch, Slot},
+    std::{fmt, path::PathBuf},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WenRestartForkStatus {
+    pub slot: Slot,
+    pub bankhash: String,
+    pub from: String,
+}
+
+impl WenRestartForkStatus {
+    fn new(record: &HeaviestForkRecord) -> Self {
+        Self {
+            slot: record.slot,
+            bankhash: record.bankhash.clone(),
+            from: record.from.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WenRestartEpochStakeStatus {
+    pub epoch: Epoch,
+    pub total_stake: u64,
+    pub actively_voting_stake: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WenRestartStatus {
+    pub state: String,
+    pub my_last_voted_slot: Option<Slot>,
+    /// Number of validators whose last voted fork has been received
+    pub last_voted_fork_slots_received: usize,
+    /// Stake of the validators which reported their last voted fork, once aggregated
+    pub epoch_stakes: Vec<WenRestartEpochStakeStatus>,
+    pub my_heaviest_fork: Option<WenRestartForkStatus>,
+    pub coordinator_heaviest_fork: Option<WenRestartForkStatus>,
+    /// Number of validators whose heaviest fork has been received
+    pub heaviest_fork_received: usize,
+    pub heaviest_fork_total_active_stake: u64,
+    pub snapshot_path: Option<String>,
+    pub conflicts: usize,
+}
+
+impl WenRestartStatus {
+    fn new(progress: &WenRestartProgress) -> Self {
+        let state = RestartState::from_i32(progress.state)
+            .map(|state| state.as_str_name().to_string())
+            .unwrap_or_else(|| format!("UNKNOWN({})", progress.state));
+        let (last_voted_fork_slots_received, epoch_stakes) = progress
+            .last_voted_fork_slots_aggregate
+            .as_ref()
+            .map(|aggregate| {
+                let epoch_stakes = aggregate
+                    .final_result
+                    .iter()
+                    .flat_map(|final_result| &final_result.epoch_infos)
+                    .map(|epoch_info| WenRestartEpochStakeStatus {
+                        epoch: epoch_info.epoch,
+                        total_stake: epoch_info.total_stake,
+                        actively_voting_stake: epoch_info.actively_voting_stake,
+                    })
+                    .collect();
+                (aggregate.received.len(), epoch_stakes)
+            })
+            .unwrap_or_default();
+        let (heaviest_fork_received, heaviest_fork_total_active_stake) = progress
+            .heaviest_fork_aggregate
+            .as_ref()
+            .map(|aggregate| (aggregate.received.len(), aggregate.total_active_stake))
+            .unwrap_or_default();
+        Self {
+            state,
+            my_last_voted_slot: progress
+                .my_last_voted_fork_slots
+                .as_ref()
+                .and_then(|record| record.last_voted_fork_slots.iter().max().copied()),
+            last_voted_fork_slots_received,
+            epoch_stakes,
+            my_heaviest_fork: progress
+                .my_heaviest_fork
+                .as_ref()
+                .map(WenRestartForkStatus::new),
+            coordinator_heaviest_fork: progress
+                .coordinator_heaviest_fork
+                .as_ref()
+                .map(WenRestartForkStatus::new),
+            heaviest_fork_received,
+            heaviest_fork_total_active_stake,
+            snapshot_path: progress
+                .my_snapshot
+                .as_ref()
+                .map(|snapshot| snapshot.path.clone()),
+            conflicts: progress.conflict_message.len(),
+        }
+    }
+}
+
+fn percent(stake: u64, total_stake: u64) -> f64 {
+    if total_stake == 0 {
+        0.0
+    } else {
+        stake as f64 * 100.0 / total_stake as f64
+    }
+}
+
+impl fmt::Display for WenRestartStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "State: {}", self.state)?;
+        if let Some(slot) = self.my_last_voted_slot {
+            writeln!(f, "My last voted slot: {slot}")?;
+        }
+        writeln!(
+            f,
+            "Last voted forks received: {}",
+            self.last_voted_fork_slots_received
+        )?;
+        for epoch_stake in &self.epoch_stakes {
+            writeln!(
+                f,
+                "  Epoch {}: {:.2}% of the stake active",
+                epoch_stake.epoch,
+                percent(epoch_stake.actively_voting_stake, epoch_stake.total_stake),
+            )?;
+        }
+        for (label, fork) in [
+            ("My heaviest fork", &self.my_heaviest_fork),
+            ("Coordinator heaviest fork", &self.coordinator_heaviest_fork),
+        ] {
+            if let Some(fork) = fork {
+                writeln!(
+                    f,
+                    "{label}: slot {} bankhash {} from {}",
+                    fork.slot, fork.bankhash, fork.from
+                )?;
+            }
+        }
+        writeln!(
+            f,
+            "Heaviest forks received: {}",
+            self.heaviest_fork_received
+        )?;
+        if let Some(total_stake) = self.epoch_stakes.last().map(|stake| stake.total_stake) {
+            writeln!(
+                f,
+                "  {:.2}% of the stake agrees with my heaviest fork",
+                percent(self.heaviest_fork_total_active_stake, total_stake),
+            )?;
+        }
+        if let Some(snapshot_path) = &self.snapshot_path {
+            writeln!(f, "Snapshot: {snapshot_path}")?;
+        }
+        if self.conflicts > 0 {
+            writeln!(f, "Conflicting messages: {}", self.conflicts)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the status of a wen_restart from its progress file
+pub fn read_wen_restart_status(wen_restart_path: &PathBuf) -> Result<WenRestartStatus> {
+    let progress = read_wen_restart_records(wen_restart_path)?;
+    Ok(WenRestartStatus::new(&progress))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            solana::wen_restart_proto::{
+                HeaviestForkAggregateRecord, LastVotedForkSlotsAggregateFinal,
+                LastVotedForkSlotsAggregateRecord, LastVotedForkSlotsEpochInfoRecord,
+                LastVotedForkSlotsRecord,
+            },
+            wen_restart::write_wen_restart_records,
+        },
+        std::collections::HashMap,
+    };
+
+    #[test]
+    fn test_read_wen_restart_status() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let wen_restart_path = temp_dir.path().join("wen_restart_progress.proto");
+        assert!(read_wen_restart_status(&wen_restart_path).is_err());
+
+        let heaviest_fork = HeaviestForkRecord {
+            slot: 12,
+            bankhash: "hash".to_string(),
+            total_active_stake: 800,
+            shred_version: 2,
+            wallclock: 0,
+            from: "me".to_string(),
+        };
+        let progress = WenRestartProgress {
+            state: RestartState::HeaviestFork.into(),
+            my_last_voted_fork_slots: Some(LastVotedForkSlotsRecord {
+                last_voted_fork_slots: vec![10, 9, 8],
+                last_vote_bankhash: "hash".to_string(),
+                shred_version: 2,
+                wallclock: 0,
+            }),
+            last_voted_fork_slots_aggregate: Some(LastVotedForkSlotsAggregateRecord {
+                received: HashMap::from([
+                    ("a".to_string(), LastVotedForkSlotsRecord::default()),
+                    ("b".to_string(), LastVotedForkSlotsRecord::default()),
+                ]),
+                final_result: Some(LastVotedForkSlotsAggregateFinal {
+                    slots_stake_map: HashMap::new(),
+                    epoch_infos: vec![LastVotedForkSlotsEpochInfoRecord {
+                        epoch: 1,
+                        total_stake: 1000,
+                        actively_voting_stake: 900,
+                        actively_voting_for_this_epoch_stake: 900,
+                    }],
+                }),
+            }),
+            my_heaviest_fork: Some(heaviest_fork.clone()),
+            heaviest_fork_aggregate: Some(HeaviestForkAggregateRecord {
+                received: vec![heaviest_fork],
+                total_active_stake: 850,
+            }),
+            ..WenRestartProgress::default()
+        };
+        write_wen_restart_records(&wen_restart_path, &progress).unwrap();
+
+        let status = read_wen_restart_status(&wen_restart_path).unwrap();
+        assert_eq!(
+            status,
+            WenRestartStatus {
+                state: "HEAVIEST_FORK".to_string(),
+                my_last_voted_slot: Some(10),
+                last_voted_fork_slots_received: 2,
+                epoch_stakes: vec![WenRestartEpochStakeStatus {
+                    epoch: 1,
+                    total_stake: 1000,
+                    actively_voting_stake: 900,
+                }],
+                my_heaviest_fork: Some(WenRestartForkStatus {
+                    slot: 12,
+                    bankhash: "hash".to_string(),
+                    from: "me".to_string(),
+                }),
+                coordinator_heaviest_fork: None,
+                heaviest_fork_received: 1,
+                heaviest_fork_total_active_stake: 850,
+                snapshot_path: None,
+                conflicts: 0,
+            }
+        );
+        assert_eq!(
+            status.to_string(),
+            "State: HEAVIEST_FORK\n\
+             My last voted slot: 10\n\
+             Last voted forks received: 2\n  \
+             Epoch 1: 90.00% of the stake active\n\
+             My heaviest fork: slot 12 bankhash hash from me\n\
+             Heaviest forks received: 1\n  \
+             85.00% of the stake agrees with my heaviest fork\n"
+        );
+    }
+}